@@ -0,0 +1,77 @@
+//! Criterion benchmark comparing the line diff algorithms selectable with the
+//! `diff_algorithm` config key.
+//!
+//! Inputs are the before/after pairs under `tests/fixtures/diff_algorithm`,
+//! each also repeated `LARGE_REPEAT` times so the cost on a file-sized input
+//! shows up next to the small case. The attribution each algorithm produces
+//! for the same fixtures is pinned by the unit tests in
+//! `authorship::imara_diff_utils`; this suite only measures what histogram,
+//! the default, costs relative to Myers.
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use git_ai::authorship::imara_diff_utils::compute_line_changes_with;
+use git_ai::config::DiffAlgorithm;
+use std::hint::black_box;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const FIXTURES: &[(&str, &str)] = &[("inserted_function", "rs"), ("swapped_functions", "c")];
+
+/// How many times a fixture is concatenated for the large variant.
+const LARGE_REPEAT: usize = 500;
+
+const ALGORITHMS: &[DiffAlgorithm] = &[
+    DiffAlgorithm::Histogram,
+    DiffAlgorithm::Myers,
+    DiffAlgorithm::MyersMinimal,
+];
+
+fn read_fixture(case: &str, ext: &str) -> (String, String) {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/diff_algorithm")
+        .join(case);
+    let read = |name: &str| {
+        std::fs::read_to_string(dir.join(format!("{}.{}", name, ext)))
+            .expect("bench: read diff_algorithm fixture")
+    };
+    (read("before"), read("after"))
+}
+
+fn bench_diff_algorithms(c: &mut Criterion) {
+    for (case, ext) in FIXTURES {
+        let (before, after) = read_fixture(case, ext);
+        let inputs = [
+            ("small", (before.clone(), after.clone())),
+            (
+                "large",
+                (before.repeat(LARGE_REPEAT), after.repeat(LARGE_REPEAT)),
+            ),
+        ];
+
+        let mut group = c.benchmark_group(format!("diff_algorithm/{}", case));
+        for (size, (old, new)) in &inputs {
+            for algorithm in ALGORITHMS {
+                group.bench_with_input(
+                    BenchmarkId::new(algorithm.as_str(), size),
+                    &(old, new),
+                    |b, (old, new)| {
+                        b.iter(|| {
+                            black_box(compute_line_changes_with(*algorithm, old, new));
+                        });
+                    },
+                );
+            }
+        }
+        group.finish();
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+        .measurement_time(Duration::from_secs(3))
+        .warm_up_time(Duration::from_secs(1));
+    targets = bench_diff_algorithms,
+}
+
+criterion_main!(benches);
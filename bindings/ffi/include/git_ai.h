@@ -0,0 +1,37 @@
+/*
+ * C ABI for git-ai commit stats and blame (libgit_ai_ffi).
+ *
+ * Every git_ai_* function that returns char* hands back a NUL-terminated JSON
+ * envelope, {"ok":true,"data":...} or {"ok":false,"error":"..."}, which must be
+ * released with git_ai_string_free. Strings are UTF-8. Arguments marked
+ * optional may be NULL.
+ */
+#ifndef GIT_AI_H
+#define GIT_AI_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Version of the envelope and payload shapes. */
+uint32_t git_ai_abi_version(void);
+
+/* Stats for `commit` (optional, defaults to HEAD), as `git-ai stats --json`. */
+char *git_ai_commit_stats(const char *repo_path, const char *commit);
+
+/*
+ * Attribution ranges for `file_path`, relative to the repository root.
+ * `options_json` (optional): {"line_ranges":[[1,40]],"newest_commit":"main"}
+ */
+char *git_ai_blame_file(const char *repo_path, const char *file_path,
+                        const char *options_json);
+
+void git_ai_string_free(char *ptr);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* GIT_AI_H */
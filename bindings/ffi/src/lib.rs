@@ -0,0 +1,115 @@
+//! C ABI over `git_ai::ffi`, loaded by the Python and Node bindings.
+//!
+//! Every entry point returns a heap-allocated, NUL-terminated JSON envelope,
+//! `{"ok":true,"data":...}` or `{"ok":false,"error":"..."}`, which the caller
+//! must release with `git_ai_string_free`. String arguments are UTF-8; the ones
+//! documented as optional may be NULL. See `include/git_ai.h`.
+
+use git_ai::error::GitAiError;
+use git_ai::ffi::{
+    BlameFileOptions, FFI_ABI_VERSION, blame_file, get_commit_stats, json_envelope,
+    parse_blame_file_options,
+};
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+/// # Safety
+/// `ptr` must be NULL or point to a NUL-terminated string valid for this call.
+unsafe fn optional_arg(ptr: *const c_char, name: &str) -> Result<Option<String>, GitAiError> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+    let value = unsafe { CStr::from_ptr(ptr) };
+    value
+        .to_str()
+        .map(|value| Some(value.to_string()))
+        .map_err(|_| GitAiError::Generic(format!("{} is not valid UTF-8", name)))
+}
+
+/// # Safety
+/// Same as [`optional_arg`].
+unsafe fn required_arg(ptr: *const c_char, name: &str) -> Result<String, GitAiError> {
+    unsafe { optional_arg(ptr, name) }?
+        .ok_or_else(|| GitAiError::Generic(format!("{} must not be NULL", name)))
+}
+
+/// Run `call` and hand its envelope to C. Panics must not unwind across the
+/// ABI boundary, so they are reported as errors instead.
+fn respond(call: impl FnOnce() -> String) -> *mut c_char {
+    let json = catch_unwind(AssertUnwindSafe(call)).unwrap_or_else(|_| {
+        json_envelope::<()>(Err(GitAiError::Generic("git-ai panicked".to_string())))
+    });
+    // serde_json escapes control characters, so the JSON has no interior NUL.
+    CString::new(json).unwrap_or_default().into_raw()
+}
+
+/// Version of the envelope and payload shapes; bindings check it on load.
+#[unsafe(no_mangle)]
+pub extern "C" fn git_ai_abi_version() -> u32 {
+    FFI_ABI_VERSION
+}
+
+/// Commit stats for `commit` (optional, defaults to HEAD) in the repository
+/// containing `repo_path`.
+///
+/// # Safety
+/// Both arguments must be NULL or NUL-terminated strings valid for this call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn git_ai_commit_stats(
+    repo_path: *const c_char,
+    commit: *const c_char,
+) -> *mut c_char {
+    respond(|| json_envelope(unsafe { commit_stats_args(repo_path, commit) }))
+}
+
+/// # Safety
+/// Same as [`git_ai_commit_stats`].
+unsafe fn commit_stats_args(
+    repo_path: *const c_char,
+    commit: *const c_char,
+) -> Result<git_ai::authorship::stats::CommitStats, GitAiError> {
+    let repo_path = unsafe { required_arg(repo_path, "repo_path") }?;
+    let commit = unsafe { optional_arg(commit, "commit") }?;
+    get_commit_stats(&repo_path, commit.as_deref())
+}
+
+/// Attribution ranges for `file_path` (relative to the repository root).
+/// `options_json` is optional, e.g. `{"line_ranges":[[1,40]],"newest_commit":"main"}`.
+///
+/// # Safety
+/// All arguments must be NULL or NUL-terminated strings valid for this call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn git_ai_blame_file(
+    repo_path: *const c_char,
+    file_path: *const c_char,
+    options_json: *const c_char,
+) -> *mut c_char {
+    respond(|| json_envelope(unsafe { blame_file_args(repo_path, file_path, options_json) }))
+}
+
+/// # Safety
+/// Same as [`git_ai_blame_file`].
+unsafe fn blame_file_args(
+    repo_path: *const c_char,
+    file_path: *const c_char,
+    options_json: *const c_char,
+) -> Result<git_ai::ffi::FileBlame, GitAiError> {
+    let repo_path = unsafe { required_arg(repo_path, "repo_path") }?;
+    let file_path = unsafe { required_arg(file_path, "file_path") }?;
+    let options = match unsafe { optional_arg(options_json, "options_json") }? {
+        Some(json) => parse_blame_file_options(&json)?,
+        None => BlameFileOptions::default(),
+    };
+    blame_file(&repo_path, &file_path, &options)
+}
+
+/// Release a string returned by any `git_ai_*` function. NULL is ignored.
+///
+/// # Safety
+/// `ptr` must come from this library and must not be used or freed again.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn git_ai_string_free(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        drop(unsafe { CString::from_raw(ptr) });
+    }
+}
@@ -0,0 +1,47 @@
+export class GitAiError extends Error {}
+
+export interface ToolModelStats {
+  ai_additions: number;
+  ai_accepted: number;
+}
+
+export interface CommitStats {
+  human_additions: number;
+  unknown_additions: number;
+  ai_additions: number;
+  ai_accepted: number;
+  git_diff_deleted_lines: number;
+  git_diff_added_lines: number;
+  tool_model_breakdown: Record<string, ToolModelStats>;
+}
+
+export interface BlameRange {
+  start_line: number;
+  end_line: number;
+  author_kind: "ai" | "known_human" | "untracked" | "unknown";
+  tool?: string;
+  model?: string;
+  prompt_id?: string;
+  tool_use_id?: string;
+  commit: string;
+}
+
+export interface FileBlame {
+  file_path: string;
+  ranges: BlameRange[];
+}
+
+export interface BlameFileOptions {
+  /** 1-indexed inclusive [start, end] ranges; empty blames the whole file. */
+  lineRanges?: Array<[number, number]>;
+  /** Blame as of this revision instead of HEAD. */
+  newestCommit?: string;
+}
+
+export function commitStats(repoPath: string, commit?: string): CommitStats;
+
+export function blameFile(
+  repoPath: string,
+  filePath: string,
+  options?: BlameFileOptions
+): FileBlame;
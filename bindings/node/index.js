@@ -0,0 +1,66 @@
+// Read git-ai commit stats and blame attribution without parsing CLI output.
+//
+// Thin koffi wrapper over the git_ai_ffi shared library, looked up in
+// GIT_AI_FFI_LIB, then next to this file, then on the system library path.
+
+"use strict";
+
+const fs = require("fs");
+const path = require("path");
+const koffi = require("koffi");
+
+const ABI_VERSION = 1;
+
+class GitAiError extends Error {}
+
+function libraryName() {
+  if (process.platform === "win32") return "git_ai_ffi.dll";
+  if (process.platform === "darwin") return "libgit_ai_ffi.dylib";
+  return "libgit_ai_ffi.so";
+}
+
+let api = null;
+
+function load() {
+  if (api) return api;
+  const bundled = path.join(__dirname, libraryName());
+  const libPath =
+    process.env.GIT_AI_FFI_LIB || (fs.existsSync(bundled) ? bundled : libraryName());
+  const lib = koffi.load(libPath);
+
+  const version = lib.func("uint32_t git_ai_abi_version()")();
+  if (version !== ABI_VERSION) {
+    throw new GitAiError(`${libPath} has ABI version ${version}, expected ${ABI_VERSION}`);
+  }
+
+  // Strings returned by the library are freed by it once koffi has copied them.
+  const free = lib.func("void git_ai_string_free(void *ptr)");
+  koffi.disposable("GitAiJson", "str", free);
+  api = {
+    commitStats: lib.func("GitAiJson git_ai_commit_stats(const char *repo, const char *commit)"),
+    blameFile: lib.func(
+      "GitAiJson git_ai_blame_file(const char *repo, const char *file, const char *options)"
+    ),
+  };
+  return api;
+}
+
+function unwrap(json) {
+  const envelope = JSON.parse(json);
+  if (!envelope.ok) throw new GitAiError(envelope.error);
+  return envelope.data;
+}
+
+/** Stats for `commit` (HEAD by default), as `git-ai stats --json`. */
+function commitStats(repoPath, commit) {
+  return unwrap(load().commitStats(String(repoPath), commit ?? null));
+}
+
+/** Attribution ranges for `filePath`, relative to the repository root. */
+function blameFile(repoPath, filePath, { lineRanges = [], newestCommit } = {}) {
+  const options = { line_ranges: lineRanges };
+  if (newestCommit !== undefined) options.newest_commit = newestCommit;
+  return unwrap(load().blameFile(String(repoPath), filePath, JSON.stringify(options)));
+}
+
+module.exports = { GitAiError, commitStats, blameFile };
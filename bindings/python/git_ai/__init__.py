@@ -0,0 +1,93 @@
+"""Read git-ai commit stats and blame attribution without parsing CLI output.
+
+Thin ctypes wrapper over the ``git_ai_ffi`` shared library. The library is
+looked up in ``GIT_AI_FFI_LIB``, then next to this package, then on the system
+library path.
+"""
+
+import ctypes
+import json
+import os
+import sys
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Tuple
+
+__all__ = ["GitAiError", "commit_stats", "blame_file"]
+
+ABI_VERSION = 1
+
+
+class GitAiError(Exception):
+    """Raised when git-ai reports an error for a call."""
+
+
+def _library_name() -> str:
+    if sys.platform == "win32":
+        return "git_ai_ffi.dll"
+    if sys.platform == "darwin":
+        return "libgit_ai_ffi.dylib"
+    return "libgit_ai_ffi.so"
+
+
+def _load() -> ctypes.CDLL:
+    override = os.environ.get("GIT_AI_FFI_LIB")
+    bundled = Path(__file__).with_name(_library_name())
+    path = override or (str(bundled) if bundled.exists() else _library_name())
+    lib = ctypes.CDLL(path)
+
+    lib.git_ai_abi_version.restype = ctypes.c_uint32
+    lib.git_ai_commit_stats.argtypes = [ctypes.c_char_p, ctypes.c_char_p]
+    lib.git_ai_commit_stats.restype = ctypes.c_void_p
+    lib.git_ai_blame_file.argtypes = [ctypes.c_char_p, ctypes.c_char_p, ctypes.c_char_p]
+    lib.git_ai_blame_file.restype = ctypes.c_void_p
+    lib.git_ai_string_free.argtypes = [ctypes.c_void_p]
+    lib.git_ai_string_free.restype = None
+
+    version = lib.git_ai_abi_version()
+    if version != ABI_VERSION:
+        raise GitAiError(
+            f"{path} has ABI version {version}, expected {ABI_VERSION}"
+        )
+    return lib
+
+
+_lib: Optional[ctypes.CDLL] = None
+
+
+def _call(name: str, *args: Optional[str]) -> Any:
+    global _lib
+    if _lib is None:
+        _lib = _load()
+    encoded = [None if arg is None else arg.encode("utf-8") for arg in args]
+    ptr = getattr(_lib, name)(*encoded)
+    try:
+        envelope = json.loads(ctypes.string_at(ptr).decode("utf-8"))
+    finally:
+        _lib.git_ai_string_free(ptr)
+    if not envelope["ok"]:
+        raise GitAiError(envelope["error"])
+    return envelope["data"]
+
+
+def commit_stats(repo_path: str, commit: Optional[str] = None) -> Dict[str, Any]:
+    """Stats for ``commit`` (HEAD by default), as ``git-ai stats --json``."""
+    return _call("git_ai_commit_stats", str(repo_path), commit)
+
+
+def blame_file(
+    repo_path: str,
+    file_path: str,
+    line_ranges: Optional[List[Tuple[int, int]]] = None,
+    newest_commit: Optional[str] = None,
+) -> Dict[str, Any]:
+    """Attribution ranges for ``file_path``, relative to the repository root.
+
+    Returns ``{"file_path": ..., "ranges": [...]}`` where each range has
+    ``start_line``, ``end_line``, ``author_kind`` (``ai``, ``known_human``,
+    ``untracked`` or ``unknown``), ``commit`` and, for AI lines, ``tool``,
+    ``model`` and ``prompt_id``.
+    """
+    options = {"line_ranges": [list(r) for r in line_ranges or []]}
+    if newest_commit is not None:
+        options["newest_commit"] = newest_commit
+    return _call("git_ai_blame_file", str(repo_path), file_path, json.dumps(options))
@@ -1,6 +1,85 @@
+use crate::api::cas::cas_hash;
 use crate::api::client::ApiClient;
-use crate::api::types::{ApiErrorResponse, CreateBundleRequest, CreateBundleResponse};
+use crate::api::types::{
+    ApiErrorResponse, CasObject, CasUploadRequest, CreateBundleRequest, CreateBundleResponse,
+    ReleaseBundleCommit, ReleaseBundleManifest, ReleaseBundleManifestEntry,
+};
 use crate::error::GitAiError;
+use std::collections::HashMap;
+
+/// Version of the [`ReleaseBundleManifest`] layout.
+pub const RELEASE_BUNDLE_VERSION: u32 = 1;
+
+/// Most CAS objects sent in one upload request.
+const CAS_UPLOAD_CHUNK: usize = 50;
+
+/// A release bundle ready for upload: one CAS object per commit followed by
+/// the manifest, whose content hash is the bundle's manifest id.
+#[derive(Debug, Clone)]
+pub struct ReleaseBundle {
+    pub manifest_id: String,
+    pub manifest: ReleaseBundleManifest,
+    pub objects: Vec<CasObject>,
+}
+
+impl ReleaseBundle {
+    /// Package `commits` (oldest first) into content-addressed objects. The
+    /// manifest lists each commit's object hash, so the manifest id pins the
+    /// exact content of the whole snapshot.
+    pub fn build(
+        range: &str,
+        from_sha: &str,
+        to_sha: &str,
+        repo_url: Option<String>,
+        created_at: i64,
+        commits: &[ReleaseBundleCommit],
+    ) -> Result<Self, GitAiError> {
+        let mut objects = Vec::with_capacity(commits.len() + 1);
+        let mut entries = Vec::with_capacity(commits.len());
+        for commit in commits {
+            let content = serde_json::to_value(commit)?;
+            let hash = cas_hash(&content)?;
+            entries.push(ReleaseBundleManifestEntry {
+                sha: commit.sha.clone(),
+                hash: hash.clone(),
+            });
+            objects.push(CasObject {
+                content,
+                hash,
+                metadata: HashMap::from([
+                    ("kind".to_string(), "release_bundle_commit".to_string()),
+                    ("commit_sha".to_string(), commit.sha.clone()),
+                ]),
+            });
+        }
+
+        let manifest = ReleaseBundleManifest {
+            version: RELEASE_BUNDLE_VERSION,
+            range: range.to_string(),
+            from_sha: from_sha.to_string(),
+            to_sha: to_sha.to_string(),
+            repo_url,
+            created_at,
+            commits: entries,
+        };
+        let content = serde_json::to_value(&manifest)?;
+        let manifest_id = cas_hash(&content)?;
+        objects.push(CasObject {
+            content,
+            hash: manifest_id.clone(),
+            metadata: HashMap::from([
+                ("kind".to_string(), "release_bundle_manifest".to_string()),
+                ("range".to_string(), range.to_string()),
+            ]),
+        });
+
+        Ok(ReleaseBundle {
+            manifest_id,
+            manifest,
+            objects,
+        })
+    }
+}
 
 /// Bundle API endpoints
 impl ApiClient {
@@ -63,4 +142,60 @@ impl ApiClient {
             ))),
         }
     }
+
+    /// Upload every object of `bundle` to CAS. The manifest is sent last, so a
+    /// manifest id is only ever resolvable once all its commits are stored.
+    ///
+    /// # Errors
+    /// * Returns `GitAiError::Generic` for HTTP errors or when the server
+    ///   rejects any object
+    pub fn push_release_bundle(&self, bundle: &ReleaseBundle) -> Result<(), GitAiError> {
+        for chunk in bundle.objects.chunks(CAS_UPLOAD_CHUNK) {
+            let response = self.upload_cas(CasUploadRequest {
+                objects: chunk.to_vec(),
+            })?;
+            if let Some(failed) = response.results.iter().find(|r| r.status != "ok") {
+                return Err(GitAiError::Generic(format!(
+                    "CAS rejected object {}: {}",
+                    failed.hash,
+                    failed.error.as_deref().unwrap_or("unknown error")
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(sha: &str) -> ReleaseBundleCommit {
+        ReleaseBundleCommit {
+            sha: sha.to_string(),
+            author: "Test User <test@example.com>".to_string(),
+            authored_at: "2026-01-01T00:00:00+00:00".to_string(),
+            committed_at: "2026-01-01T00:00:00+00:00".to_string(),
+            subject: "change".to_string(),
+            prompt_hashes: vec!["s_aaaaaaaaaaaaaa".to_string()],
+            authorship_log: None,
+        }
+    }
+
+    #[test]
+    fn release_bundle_manifest_id_pins_content() {
+        let commits = vec![commit("a1"), commit("b2")];
+        let bundle = ReleaseBundle::build("v1..v2", "a0", "b2", None, 100, &commits).unwrap();
+        assert_eq!(bundle.objects.len(), 3);
+        assert_eq!(bundle.objects.last().unwrap().hash, bundle.manifest_id);
+        assert_eq!(bundle.manifest.commits[1].hash, bundle.objects[1].hash);
+
+        let again = ReleaseBundle::build("v1..v2", "a0", "b2", None, 100, &commits).unwrap();
+        assert_eq!(again.manifest_id, bundle.manifest_id);
+
+        let mut changed = commits.clone();
+        changed[0].authorship_log = Some("note".to_string());
+        let changed = ReleaseBundle::build("v1..v2", "a0", "b2", None, 100, &changed).unwrap();
+        assert_ne!(changed.manifest_id, bundle.manifest_id);
+    }
 }
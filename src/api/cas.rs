@@ -4,6 +4,15 @@ use crate::api::types::{
 };
 use crate::error::GitAiError;
 
+/// Content hash of a CAS object: the SHA-256 of its RFC 8785 canonical JSON.
+pub fn cas_hash(content: &serde_json::Value) -> Result<String, GitAiError> {
+    use sha2::{Digest, Sha256};
+
+    let canonical = serde_json_canonicalizer::to_string(content)
+        .map_err(|e| GitAiError::Generic(format!("Failed to canonicalize JSON: {}", e)))?;
+    Ok(format!("{:x}", Sha256::digest(canonical.as_bytes())))
+}
+
 /// CAS API endpoints
 impl ApiClient {
     /// Upload CAS objects to the server
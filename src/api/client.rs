@@ -4,7 +4,10 @@ use crate::error::GitAiError;
 use crate::git::repository::{current_git_committer_identity_resolution, parse_git_var_identity};
 use crate::http;
 use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use url::Url;
 
 /// Global mutex to prevent multiple threads from refreshing simultaneously.
@@ -12,6 +15,188 @@ use url::Url;
 /// Note: Cross-process races are acceptable - both processes get valid tokens.
 static REFRESH_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
+/// Retries after the first attempt for transient failures, unless overridden
+/// with [`ApiContext::with_max_retries`].
+pub const DEFAULT_MAX_RETRIES: u32 = 2;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(4);
+/// Longest `Retry-After` waited out inline; longer waits open the circuit instead.
+const MAX_INLINE_RETRY_AFTER: Duration = Duration::from_secs(10);
+/// Consecutive failed calls to one backend before its circuit opens.
+const CIRCUIT_FAILURE_THRESHOLD: u32 = 3;
+const CIRCUIT_OPEN_SECS: u64 = 60;
+
+/// Per-backend circuit state in this process. Open circuits are also persisted
+/// (see [`circuit_file_path`]) so short-lived git hook processes skip a backend
+/// another process already found degraded.
+static CIRCUITS: Lazy<Mutex<HashMap<String, CircuitState>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct CircuitState {
+    consecutive_failures: u32,
+    /// Unix seconds until which calls fail fast without touching the network.
+    open_until: u64,
+}
+
+impl CircuitState {
+    fn is_open(&self, now: u64) -> bool {
+        self.open_until > now
+    }
+
+    /// Count a failed call. The circuit opens once the failure threshold is
+    /// reached, or right away for the server's own `Retry-After`. After it
+    /// closes again one more failure reopens it (half-open).
+    fn record_failure(&mut self, now: u64, retry_after: Option<Duration>) {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        if let Some(retry_after) = retry_after {
+            self.open_until = self.open_until.max(now + retry_after.as_secs().max(1));
+        } else if self.consecutive_failures >= CIRCUIT_FAILURE_THRESHOLD {
+            self.open_until = now + CIRCUIT_OPEN_SECS;
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Circuits are kept per backend host, so the notes backend and the main API
+/// trip independently.
+fn circuit_key(base_url: &str) -> String {
+    Url::parse(base_url)
+        .ok()
+        .and_then(|url| {
+            let host = url.host_str()?.to_string();
+            Some(match url.port() {
+                Some(port) => format!("{}:{}", host, port),
+                None => host,
+            })
+        })
+        .unwrap_or_else(|| base_url.to_string())
+}
+
+fn circuit_file_path() -> Option<PathBuf> {
+    #[cfg(any(test, feature = "test-support"))]
+    if let Ok(path) = std::env::var("GIT_AI_TEST_API_CIRCUIT_PATH") {
+        return Some(PathBuf::from(path));
+    }
+    if cfg!(test) {
+        return None;
+    }
+    Some(
+        dirs::home_dir()?
+            .join(".git-ai")
+            .join("internal")
+            .join("api-circuits.json"),
+    )
+}
+
+fn read_persisted_circuits() -> HashMap<String, u64> {
+    circuit_file_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Best effort: a lost update only means one extra slow call elsewhere.
+fn persist_circuit(key: &str, open_until: u64) {
+    let Some(path) = circuit_file_path() else {
+        return;
+    };
+    let now = unix_now();
+    let mut circuits = read_persisted_circuits();
+    circuits.retain(|_, until| *until > now);
+    let changed = if open_until > now {
+        circuits.insert(key.to_string(), open_until) != Some(open_until)
+    } else {
+        circuits.remove(key).is_some()
+    };
+    if !changed {
+        return;
+    }
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Ok(content) = serde_json::to_string(&circuits) {
+        let _ = std::fs::write(path, content);
+    }
+}
+
+/// Unix seconds until which the backend's circuit is open, if it is.
+fn circuit_open_until(key: &str, now: u64) -> Option<u64> {
+    let in_process = CIRCUITS
+        .lock()
+        .ok()
+        .and_then(|circuits| circuits.get(key).copied())
+        .filter(|state| state.is_open(now))
+        .map(|state| state.open_until);
+    in_process.or_else(|| {
+        read_persisted_circuits()
+            .get(key)
+            .copied()
+            .filter(|until| *until > now)
+    })
+}
+
+fn record_circuit_success(key: &str) {
+    let was_tracked = CIRCUITS
+        .lock()
+        .ok()
+        .and_then(|mut circuits| circuits.remove(key))
+        .is_some();
+    if was_tracked {
+        persist_circuit(key, 0);
+    }
+}
+
+fn record_circuit_failure(key: &str, retry_after: Option<Duration>) {
+    let now = unix_now();
+    let Ok(mut circuits) = CIRCUITS.lock() else {
+        return;
+    };
+    let state = circuits.entry(key.to_string()).or_default();
+    state.record_failure(now, retry_after);
+    if state.is_open(now) {
+        let open_until = state.open_until;
+        drop(circuits);
+        tracing::warn!(
+            backend = %key,
+            open_secs = open_until - now,
+            "API backend degraded; pausing requests"
+        );
+        persist_circuit(key, open_until);
+    }
+}
+
+/// Statuses worth retrying: rate limiting and server-side failures.
+fn is_transient_status(status_code: u16) -> bool {
+    matches!(status_code, 429 | 500 | 502 | 503 | 504)
+}
+
+/// Parse a `Retry-After` value (delay seconds or an HTTP date).
+fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let at = UNIX_EPOCH + Duration::from_secs(u64::try_from(at.timestamp()).ok()?);
+    Some(at.duration_since(now).unwrap_or_default())
+}
+
+/// Exponential backoff with jitter: half the capped delay is fixed, the other
+/// half scaled by `jitter` (0.0..1.0) so concurrent clients spread out.
+fn backoff_delay(attempt: u32, jitter: f64) -> Duration {
+    let exp = RETRY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(RETRY_MAX_DELAY);
+    exp / 2 + (exp / 2).mul_f64(jitter.clamp(0.0, 1.0))
+}
+
 /// Attempt to load stored credentials and refresh if needed.
 /// Returns None on any failure (not logged in, expired, refresh failed).
 /// Uses in-process Mutex for thread safety during token refresh.
@@ -161,6 +346,8 @@ pub struct ApiContext {
     pub author_identity: Option<String>,
     /// Request timeout in seconds
     pub timeout_secs: Option<u64>,
+    /// Retries for transient failures (transport errors, 429, 5xx)
+    pub max_retries: u32,
 }
 
 impl std::fmt::Debug for ApiContext {
@@ -174,6 +361,7 @@ impl std::fmt::Debug for ApiContext {
             .field("api_key", &self.api_key.as_ref().map(|_| "[REDACTED]"))
             .field("author_identity", &self.author_identity)
             .field("timeout_secs", &self.timeout_secs)
+            .field("max_retries", &self.max_retries)
             .finish()
     }
 }
@@ -232,6 +420,7 @@ impl ApiContext {
             api_key,
             author_identity,
             timeout_secs: Some(30),
+            max_retries: DEFAULT_MAX_RETRIES,
         }
     }
 
@@ -253,6 +442,7 @@ impl ApiContext {
             api_key,
             author_identity,
             timeout_secs: Some(30),
+            max_retries: DEFAULT_MAX_RETRIES,
         }
     }
 
@@ -274,6 +464,7 @@ impl ApiContext {
             api_key,
             author_identity,
             timeout_secs: Some(30),
+            max_retries: DEFAULT_MAX_RETRIES,
         }
     }
 
@@ -283,6 +474,89 @@ impl ApiContext {
         self
     }
 
+    /// Set how many times transient failures are retried. Use 0 on paths that
+    /// run inside git commands, where waiting on a slow backend is never worth it.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Attach the API key, author identity and bearer token headers.
+    fn authorize(&self, mut request: ureq::Request) -> ureq::Request {
+        if let Some(api_key) = &self.api_key {
+            request = request.set("X-API-Key", api_key);
+            if let Some(identity) = &self.author_identity {
+                request = request.set("X-Author-Identity", identity);
+            }
+        }
+        if let Some(token) = &self.auth_token {
+            request = request.set("Authorization", &format!("Bearer {}", token));
+        }
+        request
+    }
+
+    /// Run `send` with retries and the backend's circuit breaker.
+    ///
+    /// Transport errors, 429 and 5xx responses are retried up to `max_retries`
+    /// times, waiting out the server's `Retry-After` when it is short and
+    /// otherwise backing off with jitter. A call that still fails counts against
+    /// the circuit; while it is open, calls fail immediately so callers fall back
+    /// to their offline queues. The last response is returned as-is, so callers
+    /// keep mapping status codes themselves.
+    fn send_resilient(
+        &self,
+        mut send: impl FnMut() -> Result<http::Response, String>,
+    ) -> Result<http::Response, GitAiError> {
+        let key = circuit_key(&self.base_url);
+        let now = unix_now();
+        if let Some(open_until) = circuit_open_until(&key, now) {
+            return Err(GitAiError::Generic(format!(
+                "API backend {} is unavailable, skipping request for {}s",
+                key,
+                open_until - now
+            )));
+        }
+
+        let mut attempt = 0;
+        loop {
+            let result = send();
+            let (transient, retry_after) = match &result {
+                Ok(response) => (
+                    is_transient_status(response.status_code),
+                    response
+                        .retry_after()
+                        .and_then(|value| parse_retry_after(value, SystemTime::now())),
+                ),
+                Err(_) => (true, None),
+            };
+            if !transient {
+                record_circuit_success(&key);
+                return result
+                    .map_err(|e| GitAiError::Generic(format!("HTTP request failed: {}", e)));
+            }
+
+            let wait_too_long = retry_after.is_some_and(|wait| wait > MAX_INLINE_RETRY_AFTER);
+            if attempt >= self.max_retries || wait_too_long {
+                record_circuit_failure(&key, retry_after);
+                return result
+                    .map_err(|e| GitAiError::Generic(format!("HTTP request failed: {}", e)));
+            }
+
+            let delay = retry_after.unwrap_or_else(|| {
+                use rand::RngExt;
+                backoff_delay(attempt, rand::rng().random::<f64>())
+            });
+            tracing::debug!(
+                backend = %key,
+                attempt = attempt + 1,
+                delay_ms = delay.as_millis() as u64,
+                "retrying API request"
+            );
+            std::thread::sleep(delay);
+            attempt += 1;
+        }
+    }
+
     /// Build the full URL for an endpoint.
     ///
     /// The endpoint is appended to the base URL preserving any path prefix on
@@ -312,40 +586,21 @@ impl ApiContext {
         let url = self.build_url(endpoint)?;
         let body_json = serde_json::to_string(body).map_err(GitAiError::JsonError)?;
 
-        let (_agent, mut request) = Self::http_post(&url, self.timeout_secs);
-        request = request.set("Content-Type", "application/json");
-
-        if let Some(api_key) = &self.api_key {
-            request = request.set("X-API-Key", api_key);
-            if let Some(identity) = &self.author_identity {
-                request = request.set("X-Author-Identity", identity);
-            }
-        }
-        if let Some(token) = &self.auth_token {
-            request = request.set("Authorization", &format!("Bearer {}", token));
-        }
-
-        http::send_with_body(request, &body_json)
-            .map_err(|e| GitAiError::Generic(format!("HTTP request failed: {}", e)))
+        self.send_resilient(|| {
+            let (_agent, request) = Self::http_post(&url, self.timeout_secs);
+            let request = self.authorize(request.set("Content-Type", "application/json"));
+            http::send_with_body(request, &body_json)
+        })
     }
 
     /// Make a GET request
     pub fn get(&self, endpoint: &str) -> Result<http::Response, GitAiError> {
         let url = self.build_url(endpoint)?;
 
-        let (_agent, mut request) = Self::http_get(&url, self.timeout_secs);
-
-        if let Some(api_key) = &self.api_key {
-            request = request.set("X-API-Key", api_key);
-            if let Some(identity) = &self.author_identity {
-                request = request.set("X-Author-Identity", identity);
-            }
-        }
-        if let Some(token) = &self.auth_token {
-            request = request.set("Authorization", &format!("Bearer {}", token));
-        }
-
-        http::send(request).map_err(|e| GitAiError::Generic(format!("HTTP request failed: {}", e)))
+        self.send_resilient(|| {
+            let (_agent, request) = Self::http_get(&url, self.timeout_secs);
+            http::send(self.authorize(request))
+        })
     }
 }
 
@@ -529,6 +784,96 @@ mod tests {
         assert_eq!(final_count, 5);
     }
 
+    // ============= Retry / Circuit Breaker Tests =============
+
+    #[test]
+    fn test_parse_retry_after_seconds_and_http_date() {
+        let now = UNIX_EPOCH + Duration::from_secs(784_111_777); // Sun, 06 Nov 1994 08:49:37 GMT
+        assert_eq!(
+            parse_retry_after(" 120 ", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Sun, 06 Nov 1994 08:50:07 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Sun, 06 Nov 1994 08:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn test_backoff_delay_grows_with_jitter_and_caps() {
+        assert_eq!(backoff_delay(0, 0.0), Duration::from_millis(125));
+        assert_eq!(backoff_delay(0, 1.0), Duration::from_millis(250));
+        assert_eq!(backoff_delay(2, 0.5), Duration::from_millis(750));
+        assert_eq!(backoff_delay(30, 1.0), RETRY_MAX_DELAY);
+        assert_eq!(backoff_delay(30, 0.0), RETRY_MAX_DELAY / 2);
+    }
+
+    #[test]
+    fn test_circuit_opens_after_threshold_and_on_retry_after() {
+        let mut state = CircuitState::default();
+        for _ in 1..CIRCUIT_FAILURE_THRESHOLD {
+            state.record_failure(1_000, None);
+            assert!(!state.is_open(1_000));
+        }
+        state.record_failure(1_000, None);
+        assert!(state.is_open(1_000));
+        assert!(!state.is_open(1_000 + CIRCUIT_OPEN_SECS));
+
+        // Half-open: the next failure after the cooldown reopens it.
+        state.record_failure(2_000, None);
+        assert!(state.is_open(2_000));
+
+        let mut rate_limited = CircuitState::default();
+        rate_limited.record_failure(1_000, Some(Duration::from_secs(300)));
+        assert!(rate_limited.is_open(1_299));
+        assert!(!rate_limited.is_open(1_300));
+    }
+
+    #[test]
+    fn test_open_circuit_fails_fast_without_sending() {
+        let ctx = ApiContext::without_auth(Some("https://circuit-open.example.com".to_string()))
+            .with_max_retries(0);
+        let key = circuit_key(&ctx.base_url);
+        assert_eq!(key, "circuit-open.example.com");
+        record_circuit_failure(&key, Some(Duration::from_secs(60)));
+
+        let mut sent = 0;
+        let result = ctx.send_resilient(|| {
+            sent += 1;
+            Err("unreachable".to_string())
+        });
+        assert!(result.is_err());
+        assert_eq!(sent, 0);
+
+        record_circuit_success(&key);
+        let result = ctx.send_resilient(|| {
+            sent += 1;
+            Err("connection refused".to_string())
+        });
+        assert!(result.is_err());
+        assert_eq!(sent, 1);
+        record_circuit_success(&key);
+    }
+
+    #[test]
+    fn test_transient_failures_are_retried_up_to_max() {
+        let ctx = ApiContext::without_auth(Some("https://retry.example.com".to_string()))
+            .with_max_retries(2);
+        let mut sent = 0;
+        let result = ctx.send_resilient(|| {
+            sent += 1;
+            Err("connection reset".to_string())
+        });
+        assert!(result.is_err());
+        assert_eq!(sent, 3);
+        record_circuit_success(&circuit_key(&ctx.base_url));
+    }
+
     // ============= encode_for_header Tests =============
 
     #[test]
@@ -191,7 +191,7 @@ impl ApiClient {
                     error_response.error
                 )))
             }
-            401 => Err(GitAiError::Generic("Unauthorized".to_string())),
+            401 => Err(GitAiError::Auth("Unauthorized".to_string())),
             500 => {
                 let error_response: ApiErrorResponse =
                     serde_json::from_str(body).unwrap_or_else(|_| ApiErrorResponse {
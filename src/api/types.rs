@@ -72,6 +72,42 @@ pub struct CreateBundleResponse {
     pub url: String,
 }
 
+/// One commit of a release bundle, stored as its own CAS object.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReleaseBundleCommit {
+    pub sha: String,
+    pub author: String,
+    pub authored_at: String,
+    pub committed_at: String,
+    pub subject: String,
+    /// Prompt and session hashes the commit's authorship log references.
+    #[serde(default)]
+    pub prompt_hashes: Vec<String>,
+    /// The raw authorship note, when the commit has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorship_log: Option<String>,
+}
+
+/// Manifest of a release bundle. Its CAS hash is the bundle's manifest id.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReleaseBundleManifest {
+    pub version: u32,
+    pub range: String,
+    pub from_sha: String,
+    pub to_sha: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo_url: Option<String>,
+    pub created_at: i64,
+    /// Commits oldest first, each with the CAS hash of its object.
+    pub commits: Vec<ReleaseBundleManifestEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReleaseBundleManifestEntry {
+    pub sha: String,
+    pub hash: String,
+}
+
 /// Error response from API
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ApiErrorResponse {
@@ -1,4 +1,14 @@
+use super::CredentialStore;
 use serde::Deserialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a resolved org identity is reused before the credential store is read again.
+/// Keeps long-running processes (the daemon) in step with login/logout without hitting
+/// the keyring on every metric event.
+const ORG_IDENTITY_CACHE_TTL: Duration = Duration::from_secs(300);
+
+static ORG_IDENTITY_CACHE: Mutex<Option<(Instant, Option<OrgIdentity>)>> = Mutex::new(None);
 
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct TokenIdentity {
@@ -55,6 +65,60 @@ pub fn extract_identity_from_access_token(access_token: &str) -> TokenIdentity {
     }
 }
 
+/// Org-verified identity of the logged-in user, taken from the OAuth access token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrgIdentity {
+    /// Stable subject (`sub` claim) assigned by the org's identity provider.
+    pub subject: String,
+    pub email: Option<String>,
+}
+
+impl OrgIdentity {
+    /// Returns `None` when the token carries no subject, since an email alone is not
+    /// verified as belonging to a single engineer.
+    pub fn from_token_identity(identity: TokenIdentity) -> Option<Self> {
+        let subject = identity.user_id.filter(|sub| !sub.is_empty())?;
+        Some(Self {
+            subject,
+            email: identity.email.filter(|email| !email.is_empty()),
+        })
+    }
+}
+
+/// Org identity for the current credentials, or `None` when logged out or the
+/// refresh token has expired.
+pub fn current_org_identity() -> Option<OrgIdentity> {
+    if let Ok(cache) = ORG_IDENTITY_CACHE.lock()
+        && let Some((resolved_at, identity)) = cache.as_ref()
+        && resolved_at.elapsed() < ORG_IDENTITY_CACHE_TTL
+    {
+        return identity.clone();
+    }
+
+    let identity = load_org_identity();
+    if let Ok(mut cache) = ORG_IDENTITY_CACHE.lock() {
+        *cache = Some((Instant::now(), identity.clone()));
+    }
+    identity
+}
+
+fn load_org_identity() -> Option<OrgIdentity> {
+    let creds = CredentialStore::new().load().ok().flatten()?;
+    if creds.is_refresh_token_expired() {
+        return None;
+    }
+    OrgIdentity::from_token_identity(extract_identity_from_access_token(&creds.access_token))
+}
+
+/// Extract the email from a git identity string (`Name <email>`), used as the
+/// fallback identity when no org credentials are available.
+pub fn email_from_git_identity(identity: &str) -> Option<&str> {
+    let start = identity.rfind('<')?;
+    let end = identity[start..].find('>')? + start;
+    let email = identity[start + 1..end].trim();
+    (!email.is_empty()).then_some(email)
+}
+
 fn decode_base64_url(input: &str) -> Result<Vec<u8>, String> {
     let mut bytes = Vec::new();
     let mut buffer = 0u32;
@@ -102,6 +166,38 @@ mod tests {
         assert_eq!(identity.orgs[0].org_slug.as_deref(), Some("org-one"));
     }
 
+    #[test]
+    fn test_org_identity_requires_subject() {
+        let identity = TokenIdentity {
+            email: Some("user@example.com".to_string()),
+            ..Default::default()
+        };
+        assert!(OrgIdentity::from_token_identity(identity).is_none());
+
+        let identity = TokenIdentity {
+            user_id: Some("u123".to_string()),
+            email: Some("user@example.com".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            OrgIdentity::from_token_identity(identity),
+            Some(OrgIdentity {
+                subject: "u123".to_string(),
+                email: Some("user@example.com".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_email_from_git_identity() {
+        assert_eq!(
+            email_from_git_identity("Jane Doe <jane@corp.example>"),
+            Some("jane@corp.example")
+        );
+        assert_eq!(email_from_git_identity("Jane Doe"), None);
+        assert_eq!(email_from_git_identity("Jane Doe <>"), None);
+    }
+
     #[test]
     fn test_extract_identity_handles_non_jwt_token() {
         let identity = extract_identity_from_access_token("opaque-token");
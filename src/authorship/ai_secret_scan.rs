@@ -0,0 +1,127 @@
+//! Pre-commit secret scan over AI-authored lines.
+//!
+//! With `policy.ai_secret_scan` set in `.git-ai.toml` (or the user's
+//! `workspace` config), `git commit` first runs the entropy-based detector from
+//! [`crate::authorship::secrets`] over the staged added lines the pending
+//! working log attributes to AI. Human lines are not scanned, so the check
+//! stays cheap and only flags credentials an agent pasted. In `warn` mode the
+//! findings are printed and the commit goes ahead; in `block` mode the commit
+//! is refused. `--no-verify` (or `-n`, also inside a flag cluster such as
+//! `-anm`) skips the scan like any other pre-commit check; a later `--verify`
+//! restores it.
+
+use crate::authorship::secrets::{extract_tokens, is_random, redact_secret};
+use crate::error::GitAiError;
+use crate::git::cli_parser::summarize_commit_args;
+use crate::git::repository::Repository;
+use crate::workspace_config::{SecretScanMode, effective_workspace_config};
+
+/// A likely secret on an AI-authored staged line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AiSecretFinding {
+    pub file_path: String,
+    /// Line number in the staged (index) version of the file.
+    pub line: u32,
+    /// The secret with only its first and last few characters visible.
+    pub redacted: String,
+}
+
+/// Scan the AI-authored staged added lines of `repo` for likely secrets.
+pub fn scan_staged_ai_lines(repo: &Repository) -> Result<Vec<AiSecretFinding>, GitAiError> {
+    let ai_lines = crate::commands::commit::staged_ai_lines(repo)?;
+    if ai_lines.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut file_paths: Vec<String> = ai_lines.keys().cloned().collect();
+    file_paths.sort();
+    let contents = repo.get_all_staged_files_content(&file_paths)?;
+
+    let mut findings = Vec::new();
+    for file_path in &file_paths {
+        let Some(content) = contents.get(file_path) else {
+            continue;
+        };
+        findings.extend(secrets_on_lines(file_path, content, &ai_lines[file_path]));
+    }
+    Ok(findings)
+}
+
+/// Likely secrets on the 1-based `lines` of `content`.
+fn secrets_on_lines(file_path: &str, content: &str, lines: &[u32]) -> Vec<AiSecretFinding> {
+    let mut findings = Vec::new();
+    for (index, text) in content.lines().enumerate() {
+        let line = index as u32 + 1;
+        if lines.binary_search(&line).is_err() {
+            continue;
+        }
+        for (start, end) in extract_tokens(text) {
+            if is_random(&text.as_bytes()[start..end]) {
+                findings.push(AiSecretFinding {
+                    file_path: file_path.to_string(),
+                    line,
+                    redacted: redact_secret(&text[start..end]),
+                });
+            }
+        }
+    }
+    findings
+}
+
+/// Run the configured scan before `git commit`. Returns `false` when the
+/// commit has to be blocked. Scan failures never block a commit.
+pub fn check_before_commit(repo: &Repository, commit_args: &[String]) -> bool {
+    let mode = effective_workspace_config(repo).policy.secret_scan_mode();
+    if mode == SecretScanMode::Off || summarize_commit_args(commit_args).no_verify {
+        return true;
+    }
+
+    let findings = match scan_staged_ai_lines(repo) {
+        Ok(findings) => findings,
+        Err(e) => {
+            tracing::debug!("AI secret scan failed: {}", e);
+            return true;
+        }
+    };
+    if findings.is_empty() {
+        return true;
+    }
+
+    eprintln!("[git-ai] Possible secrets in AI-authored lines staged for commit:");
+    for finding in &findings {
+        eprintln!(
+            "  {}:{}  {}",
+            finding.file_path, finding.line, finding.redacted
+        );
+    }
+    if mode == SecretScanMode::Block {
+        eprintln!(
+            "[git-ai] Commit blocked by policy.ai_secret_scan = \"block\". Remove the secrets, or commit with --no-verify if they are not real."
+        );
+        return false;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secrets_on_lines_only_reports_requested_lines() {
+        let content = "let a = \"sk_test_4eC39HqLyjWDarjtT1zdp7dc\";\n\
+                       let name = \"my_variable_name\";\n\
+                       let b = \"pk_test_TYooMQauvdEDq54NiTphI7jx\";\n";
+
+        let findings = secrets_on_lines("src/keys.rs", content, &[2, 3]);
+        assert_eq!(
+            findings,
+            vec![AiSecretFinding {
+                file_path: "src/keys.rs".to_string(),
+                line: 3,
+                redacted: "pk_t********I7jx".to_string(),
+            }]
+        );
+        assert!(secrets_on_lines("src/keys.rs", content, &[2]).is_empty());
+    }
+}
@@ -0,0 +1,343 @@
+//! Anonymous mode: identities hashed with a workspace key.
+//!
+//! For repositories matching `anonymize_repositories` (local config or managed
+//! policy), author identities and prompt/session ids are replaced by
+//! `anon_<hmac>` before a note is written or a metric event is recorded. The
+//! HMAC-SHA256 is keyed with the workspace `anonymization_key`, so the same
+//! person or session always maps to the same value and aggregate reporting
+//! keeps working, while the raw identity never reaches a shared ref and cannot
+//! be recovered by hashing candidate emails without the key.
+//!
+//! Without a configured key a random per-machine key is generated under
+//! `~/.git-ai/internal`; identities then only aggregate on that machine.
+
+use crate::authorship::authorship_log_serialization::{
+    AuthorshipLog, generate_human_short_hash, generate_session_id, generate_short_hash,
+};
+use crate::config::Config;
+use crate::git::repository::Repository;
+use hmac::{Hmac, Mac};
+use rand::RngExt;
+use sha2::Sha256;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Prefix of anonymized values; values carrying it are never hashed again.
+pub const ANONYMIZED_PREFIX: &str = "anon_";
+
+pub(crate) fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
+
+/// `anon_` followed by the first 16 hex chars of HMAC-SHA256(key, value).
+/// Already anonymized values are returned unchanged.
+pub fn anonymize_value(key: &[u8], value: &str) -> String {
+    if value.starts_with(ANONYMIZED_PREFIX) {
+        return value.to_string();
+    }
+    let hex: String = hmac_sha256(key, value.as_bytes())
+        .iter()
+        .take(8)
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("{}{}", ANONYMIZED_PREFIX, hex)
+}
+
+fn local_key_path() -> Option<PathBuf> {
+    crate::config::internal_dir_path().map(|dir| dir.join("anonymization_key"))
+}
+
+fn read_local_key(path: &Path) -> Option<Vec<u8>> {
+    let key = fs::read_to_string(path).ok()?;
+    let key = key.trim();
+    (!key.is_empty()).then(|| key.as_bytes().to_vec())
+}
+
+/// Generate this machine's key and publish it unless another process got there
+/// first, in which case that key wins. The key is written to a private file and
+/// hard-linked into place, which fails instead of replacing an existing key, so
+/// two processes never end up hashing with different keys and no reader sees a
+/// half-written one.
+fn create_local_key(path: &Path) -> Option<Vec<u8>> {
+    let bytes: [u8; 32] = rand::rng().random();
+    let key: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    let parent = path.parent()?;
+    fs::create_dir_all(parent).ok()?;
+    let suffix: u64 = rand::rng().random();
+    let tmp_path = parent.join(format!(
+        "anonymization_key.{}.{:x}.tmp",
+        std::process::id(),
+        suffix
+    ));
+    fs::write(&tmp_path, &key).ok()?;
+    let published = fs::hard_link(&tmp_path, path);
+    let _ = fs::remove_file(&tmp_path);
+    match published {
+        Ok(()) => Some(key.into_bytes()),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => read_local_key(path),
+        Err(e) => {
+            tracing::debug!("failed to write anonymization key: {}", e);
+            None
+        }
+    }
+}
+
+/// The workspace key: the configured `anonymization_key`, otherwise this
+/// machine's generated key.
+pub fn workspace_key() -> Option<Vec<u8>> {
+    if let Some(key) = Config::get().anonymization_key() {
+        return Some(key.as_bytes().to_vec());
+    }
+    let path = local_key_path()?;
+    read_local_key(&path).or_else(|| create_local_key(&path))
+}
+
+/// The key to anonymize `repo`'s identities with, when anonymous mode applies.
+/// Resolved once per repository handle, so writing many notes reads the remotes
+/// and the key file once.
+pub fn key_for_repo(repo: &Repository) -> Option<Vec<u8>> {
+    repo.cached_anonymization_key
+        .get_or_init(|| {
+            if !Config::get().should_anonymize(repo) {
+                return None;
+            }
+            workspace_key()
+        })
+        .clone()
+}
+
+/// Replace the identities in `log` by their anonymized form.
+///
+/// Prompt, session and human keys derived from a raw identity are re-derived
+/// from the anonymized one, and attestations follow the renamed keys, so the
+/// raw value cannot be recovered from an unkeyed key hash either.
+pub fn anonymize_authorship_log(log: &mut AuthorshipLog, key: &[u8]) {
+    let mut renamed: HashMap<String, String> = HashMap::new();
+    let mut agents: HashSet<String> = HashSet::new();
+
+    for (hash, mut record) in std::mem::take(&mut log.metadata.prompts) {
+        agents.insert(format!(
+            "{}/{}",
+            record.agent_id.tool, record.agent_id.model
+        ));
+        let derived = generate_short_hash(&record.agent_id.id, &record.agent_id.tool) == hash;
+        record.agent_id.id = anonymize_value(key, &record.agent_id.id);
+        record.human_author = record
+            .human_author
+            .map(|author| anonymize_value(key, &author));
+        let new_hash = if derived {
+            generate_short_hash(&record.agent_id.id, &record.agent_id.tool)
+        } else {
+            hash.clone()
+        };
+        renamed.insert(hash, new_hash.clone());
+        log.metadata.prompts.insert(new_hash, record);
+    }
+
+    for (hash, mut record) in std::mem::take(&mut log.metadata.sessions) {
+        agents.insert(format!(
+            "{}/{}",
+            record.agent_id.tool, record.agent_id.model
+        ));
+        let derived = generate_session_id(&record.agent_id.id, &record.agent_id.tool) == hash;
+        record.agent_id.id = anonymize_value(key, &record.agent_id.id);
+        record.human_author = record
+            .human_author
+            .map(|author| anonymize_value(key, &author));
+        let new_hash = if derived {
+            generate_session_id(&record.agent_id.id, &record.agent_id.tool)
+        } else {
+            hash.clone()
+        };
+        renamed.insert(hash, new_hash.clone());
+        log.metadata.sessions.insert(new_hash, record);
+    }
+
+    for (hash, mut record) in std::mem::take(&mut log.metadata.humans) {
+        let derived = generate_human_short_hash(&record.author) == hash;
+        record.author = anonymize_value(key, &record.author);
+        let new_hash = if derived {
+            generate_human_short_hash(&record.author)
+        } else {
+            hash.clone()
+        };
+        renamed.insert(hash, new_hash.clone());
+        log.metadata.humans.insert(new_hash, record);
+    }
+
+    // Human checkpoints are labelled with their author; AI ones with tool/model.
+    // Deletions name their author the same way.
+    for label in &mut log.metadata.labels {
+        if !agents.contains(&label.author) {
+            label.author = anonymize_value(key, &label.author);
+        }
+    }
+    for deletion in &mut log.metadata.deletions {
+        if !agents.contains(&deletion.author) {
+            deletion.author = anonymize_value(key, &deletion.author);
+        }
+    }
+
+    for resolution in &mut log.metadata.resolutions {
+        resolution.resolved_by = anonymize_value(key, &resolution.resolved_by);
+    }
+
+    for attestation in &mut log.attestations {
+        for entry in &mut attestation.entries {
+            let (base, suffix) = match entry.hash.split_once("::") {
+                Some((base, trace)) => (base, Some(trace)),
+                None => (entry.hash.as_str(), None),
+            };
+            let Some(new_base) = renamed.get(base) else {
+                continue;
+            };
+            entry.hash = match suffix {
+                Some(trace) => format!("{}::{}", new_base, trace),
+                None => new_base.clone(),
+            };
+        }
+    }
+}
+
+/// Anonymize the raw notes in `entries` when `repo` is in anonymous mode.
+/// Notes that do not parse as authorship logs are written as given.
+pub fn anonymize_note_entries<'a>(
+    repo: &Repository,
+    entries: &'a [(String, String)],
+) -> Cow<'a, [(String, String)]> {
+    let Some(key) = key_for_repo(repo) else {
+        return Cow::Borrowed(entries);
+    };
+    Cow::Owned(
+        entries
+            .iter()
+            .map(|(commit_sha, note)| (commit_sha.clone(), anonymize_note(&key, note)))
+            .collect(),
+    )
+}
+
+/// [`anonymize_note_entries`] for a single note.
+pub fn anonymize_note_content<'a>(repo: &Repository, note: &'a str) -> Cow<'a, str> {
+    match key_for_repo(repo) {
+        Some(key) => Cow::Owned(anonymize_note(&key, note)),
+        None => Cow::Borrowed(note),
+    }
+}
+
+fn anonymize_note(key: &[u8], note: &str) -> String {
+    let Ok(mut log) = AuthorshipLog::deserialize_from_string(note) else {
+        return note.to_string();
+    };
+    anonymize_authorship_log(&mut log, key);
+    log.serialize_to_string()
+        .unwrap_or_else(|_| note.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::authorship_log::{HumanRecord, PromptRecord};
+    use crate::authorship::authorship_log_serialization::{AttestationEntry, FileAttestation};
+    use crate::authorship::working_log::AgentId;
+
+    #[test]
+    fn hmac_matches_rfc4231_test_case_2() {
+        let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        let hex: String = mac.iter().map(|byte| format!("{:02x}", byte)).collect();
+        assert_eq!(
+            hex,
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn create_local_key_keeps_the_key_already_published() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("anonymization_key");
+        let first = create_local_key(&path).unwrap();
+        assert_eq!(create_local_key(&path), Some(first.clone()));
+        assert_eq!(read_local_key(&path), Some(first));
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn anonymize_value_is_keyed_stable_and_idempotent() {
+        let alice = anonymize_value(b"key", "Alice <alice@example.com>");
+        assert!(alice.starts_with(ANONYMIZED_PREFIX));
+        assert_eq!(alice.len(), ANONYMIZED_PREFIX.len() + 16);
+        assert_eq!(alice, anonymize_value(b"key", "Alice <alice@example.com>"));
+        assert_ne!(
+            alice,
+            anonymize_value(b"other", "Alice <alice@example.com>")
+        );
+        assert_eq!(anonymize_value(b"key", &alice), alice);
+    }
+
+    #[test]
+    fn anonymize_authorship_log_rekeys_records_and_attestations() {
+        let agent_id = AgentId {
+            tool: "cursor".to_string(),
+            id: "session-123".to_string(),
+            model: "gpt".to_string(),
+        };
+        let prompt_hash = generate_short_hash(&agent_id.id, &agent_id.tool);
+        let human = "Alice <alice@example.com>".to_string();
+        let human_hash = generate_human_short_hash(&human);
+
+        let mut log = AuthorshipLog::new();
+        log.metadata.prompts.insert(
+            prompt_hash.clone(),
+            PromptRecord {
+                agent_id,
+                human_author: Some(human.clone()),
+                messages_url: None,
+                total_additions: 1,
+                total_deletions: 0,
+                accepted_lines: 1,
+                overriden_lines: 0,
+                custom_attributes: None,
+            },
+        );
+        log.metadata.humans.insert(
+            human_hash.clone(),
+            HumanRecord {
+                author: human.clone(),
+            },
+        );
+        let mut file = FileAttestation::new("a.rs".to_string());
+        file.add_entry(AttestationEntry::new(prompt_hash.clone(), vec![]));
+        file.add_entry(AttestationEntry::new(human_hash.clone(), vec![]));
+        log.attestations.push(file);
+
+        anonymize_authorship_log(&mut log, b"key");
+        let serialized = log.serialize_to_string().unwrap();
+        assert!(!serialized.contains("alice@example.com"));
+        assert!(!serialized.contains("session-123"));
+
+        let (new_prompt_hash, prompt) = log.metadata.prompts.iter().next().unwrap();
+        assert_ne!(new_prompt_hash, &prompt_hash);
+        assert_eq!(
+            prompt.human_author.as_deref(),
+            Some(anonymize_value(b"key", &human).as_str())
+        );
+        let (new_human_hash, _) = log.metadata.humans.iter().next().unwrap();
+        assert_ne!(new_human_hash, &human_hash);
+        let hashes: Vec<&str> = log.attestations[0]
+            .entries
+            .iter()
+            .map(|entry| entry.hash.as_str())
+            .collect();
+        assert_eq!(
+            hashes,
+            vec![new_prompt_hash.as_str(), new_human_hash.as_str()]
+        );
+
+        let once = log.clone();
+        anonymize_authorship_log(&mut log, b"key");
+        assert_eq!(log.metadata, once.metadata);
+    }
+}
@@ -0,0 +1,306 @@
+//! Inline attribution comments: `git-ai: human` and `git-ai: ai`.
+//!
+//! A comment carrying one of these markers forces the hunk it was added in to
+//! the named attribution at checkpoint time, whichever checkpoint records the
+//! change. This covers code the heuristics get wrong, such as an example an
+//! agent wrote out that was transcribed by hand from the docs (`human`), or a
+//! suggestion a developer typed in from a chat window (`ai`).
+//!
+//! A hunk is a run of lines the checkpoint inserted or replaced with no
+//! unchanged line in between. `git-ai: ai` in a human checkpoint credits the
+//! most recent AI author of the file; with no AI history it is ignored. Entries
+//! whose attributions were forced are recorded with the `override` flag.
+
+use crate::authorship::attribution_tracker::{
+    Attribution, attributions_to_line_attributions_for_checkpoint,
+};
+use crate::authorship::imara_diff_utils::{LineChangeTag, compute_line_changes};
+use crate::authorship::working_log::{CheckpointKind, WorkingLogEntry};
+
+const MARKER: &str = "git-ai:";
+
+/// Comment leaders a marker may follow.
+const COMMENT_LEADERS: &[&str] = &["//", "#", "--", ";", "/*", "<!--", "%"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributionDirective {
+    Human,
+    Ai,
+}
+
+/// The directive of a line containing a `git-ai:` marker comment.
+pub fn parse_directive(line: &str) -> Option<AttributionDirective> {
+    let marker_at = line.find(MARKER)?;
+    let before = line[..marker_at].trim_end();
+    if !COMMENT_LEADERS
+        .iter()
+        .any(|leader| before.ends_with(leader))
+    {
+        return None;
+    }
+    let value = line[marker_at + MARKER.len()..]
+        .trim()
+        .trim_end_matches("*/")
+        .trim_end_matches("-->")
+        .trim();
+    if value.eq_ignore_ascii_case("human") {
+        Some(AttributionDirective::Human)
+    } else if value.eq_ignore_ascii_case("ai") {
+        Some(AttributionDirective::Ai)
+    } else {
+        None
+    }
+}
+
+/// Hunks of `current` (1-indexed, inclusive line ranges) that contain a
+/// directive, with the last directive of each hunk.
+pub fn directive_hunks(previous: &str, current: &str) -> Vec<(u32, u32, AttributionDirective)> {
+    // Nearly every checkpoint has no marker at all; skip the diff for those.
+    if !current.contains(MARKER) {
+        return Vec::new();
+    }
+    let mut hunks = Vec::new();
+    let mut open: Option<(u32, u32, Option<AttributionDirective>)> = None;
+    let mut new_line = 0u32;
+    for change in compute_line_changes(previous, current) {
+        match change.tag() {
+            LineChangeTag::Equal => {
+                new_line += 1;
+                if let Some((start, end, Some(directive))) = open.take() {
+                    hunks.push((start, end, directive));
+                }
+            }
+            LineChangeTag::Insert => {
+                new_line += 1;
+                let directive = parse_directive(change.value());
+                match &mut open {
+                    Some((_, end, found)) => {
+                        *end = new_line;
+                        *found = directive.or(*found);
+                    }
+                    None => open = Some((new_line, new_line, directive)),
+                }
+            }
+            LineChangeTag::Delete => {}
+        }
+    }
+    if let Some((start, end, Some(directive))) = open {
+        hunks.push((start, end, directive));
+    }
+    hunks
+}
+
+/// Byte range of 1-indexed lines `start..=end` of `content`.
+fn line_byte_range(content: &str, start: u32, end: u32) -> Option<std::ops::Range<usize>> {
+    let mut offset = 0usize;
+    let mut range_start = None;
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        let line_num = index as u32 + 1;
+        if line_num == start {
+            range_start = Some(offset);
+        }
+        offset += line.len();
+        if line_num == end {
+            return range_start.map(|range_start| range_start..offset);
+        }
+    }
+    None
+}
+
+/// Force the hunks of `entry` that carry a directive comment, recomputing its
+/// line attributions. Returns whether anything was forced.
+///
+/// `author_id` is the checkpoint's author and `previous_attributions` the file's
+/// attributions before the checkpoint, used to find the AI author a human
+/// checkpoint's `git-ai: ai` hunk is credited to.
+pub fn apply_attribution_comments(
+    entry: &mut WorkingLogEntry,
+    previous_content: &str,
+    current_content: &str,
+    previous_attributions: &[Attribution],
+    author_id: &str,
+    is_ai_checkpoint: bool,
+    ts: u128,
+) -> bool {
+    let hunks = directive_hunks(previous_content, current_content);
+    if hunks.is_empty() {
+        return false;
+    }
+
+    let human_author = if is_ai_checkpoint {
+        CheckpointKind::Human.to_str()
+    } else {
+        author_id.to_string()
+    };
+    let ai_author = if is_ai_checkpoint {
+        Some(author_id.to_string())
+    } else {
+        previous_attributions
+            .iter()
+            .filter(|attr| crate::daemon::checkpoint::is_ai_author_id(&attr.author_id))
+            .max_by_key(|attr| attr.ts)
+            .map(|attr| attr.author_id.clone())
+    };
+
+    let mut forced = false;
+    for (start, end, directive) in hunks {
+        let forced_author = match directive {
+            AttributionDirective::Human => Some(human_author.clone()),
+            AttributionDirective::Ai => ai_author.clone(),
+        };
+        let (Some(forced_author), Some(range)) =
+            (forced_author, line_byte_range(current_content, start, end))
+        else {
+            continue;
+        };
+        let mut attributions = Vec::with_capacity(entry.attributions.len() + 1);
+        for attr in entry.attributions.drain(..) {
+            if attr.end <= range.start || attr.start >= range.end {
+                attributions.push(attr);
+                continue;
+            }
+            if attr.start < range.start {
+                attributions.push(Attribution::new(
+                    attr.start,
+                    range.start,
+                    attr.author_id.clone(),
+                    attr.ts,
+                ));
+            }
+            if attr.end > range.end {
+                attributions.push(Attribution::new(
+                    range.end,
+                    attr.end,
+                    attr.author_id.clone(),
+                    attr.ts,
+                ));
+            }
+        }
+        attributions.push(Attribution::new(range.start, range.end, forced_author, ts));
+        attributions.sort_by_key(|attr| (attr.start, attr.end));
+        entry.attributions = attributions;
+        forced = true;
+    }
+
+    if forced {
+        entry.line_attributions = attributions_to_line_attributions_for_checkpoint(
+            &entry.attributions,
+            current_content,
+            is_ai_checkpoint,
+        );
+        entry.attribution_override = true;
+    }
+    forced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directives_need_a_comment_leader() {
+        assert_eq!(
+            parse_directive("    // git-ai: human\n"),
+            Some(AttributionDirective::Human)
+        );
+        assert_eq!(
+            parse_directive("x = 1  # git-ai: AI"),
+            Some(AttributionDirective::Ai)
+        );
+        assert_eq!(
+            parse_directive("/* git-ai: human */"),
+            Some(AttributionDirective::Human)
+        );
+        assert_eq!(
+            parse_directive("<!-- git-ai: ai -->"),
+            Some(AttributionDirective::Ai)
+        );
+        assert_eq!(parse_directive("let s = \"git-ai: human\";"), None);
+        assert_eq!(parse_directive("// git-ai: robot"), None);
+    }
+
+    #[test]
+    fn ai_hunk_with_human_directive_is_forced_human() {
+        let previous = "fn base() {}\n";
+        let current = "fn base() {}\n// git-ai: human\nfn from_docs() {}\n\nfn ai() {}\n";
+        assert_eq!(
+            directive_hunks(previous, current),
+            vec![(2, 5, AttributionDirective::Human)]
+        );
+
+        let ai_author = "s_0123456789abcd";
+        let mut entry = WorkingLogEntry::new(
+            "lib.rs".to_string(),
+            "blob".to_string(),
+            vec![
+                Attribution::new(0, 13, "human".to_string(), 1),
+                Attribution::new(13, current.len(), ai_author.to_string(), 2),
+            ],
+            Vec::new(),
+        );
+        assert!(apply_attribution_comments(
+            &mut entry,
+            previous,
+            current,
+            &[],
+            ai_author,
+            true,
+            3
+        ));
+        assert!(entry.attribution_override);
+        assert!(
+            entry.line_attributions.is_empty(),
+            "{:?}",
+            entry.line_attributions
+        );
+    }
+
+    #[test]
+    fn human_hunk_with_ai_directive_credits_last_ai_author() {
+        let previous = "fn base() {}\n";
+        let current = "fn base() {}\nfn typed() {} // git-ai: ai\n";
+        let previous_attributions = vec![
+            Attribution::new(0, 5, "s_aaaaaaaaaaaaaa".to_string(), 1),
+            Attribution::new(5, 13, "s_bbbbbbbbbbbbbb".to_string(), 2),
+        ];
+        let mut entry = WorkingLogEntry::new(
+            "lib.rs".to_string(),
+            "blob".to_string(),
+            vec![
+                Attribution::new(0, 13, "human".to_string(), 1),
+                Attribution::new(13, current.len(), "human".to_string(), 3),
+            ],
+            Vec::new(),
+        );
+        assert!(apply_attribution_comments(
+            &mut entry,
+            previous,
+            current,
+            &previous_attributions,
+            "human",
+            false,
+            3
+        ));
+        assert_eq!(entry.line_attributions.len(), 1);
+        assert_eq!(entry.line_attributions[0].start_line, 2);
+        assert_eq!(entry.line_attributions[0].author_id, "s_bbbbbbbbbbbbbb");
+
+        // Without AI history the directive has nothing to credit.
+        let mut entry = WorkingLogEntry::new(
+            "lib.rs".to_string(),
+            "blob".to_string(),
+            Vec::new(),
+            Vec::new(),
+        );
+        assert!(!apply_attribution_comments(
+            &mut entry,
+            previous,
+            current,
+            &[],
+            "human",
+            false,
+            3
+        ));
+        assert!(!entry.attribution_override);
+    }
+}
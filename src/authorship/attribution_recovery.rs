@@ -29,6 +29,7 @@ const COPILOT_TOOLS: &[&str] = &[
     "github-copilot",
     "github-copilot-cli",
     "github-copilot-agent",
+    "copilot-agent",
     "copilot",
 ];
 const DEVIN_TOOLS: &[&str] = &["devin"];
@@ -556,6 +557,9 @@ fn recover_commit_metadata(
             agent_id: selection.agent_id.clone(),
             human_author: Some(human_author.to_string()),
             custom_attributes: None,
+            tool_calls: BTreeMap::new(),
+            subagents: BTreeMap::new(),
+            tool_versions: BTreeMap::new(),
         });
 
     let detected_agents = detections
@@ -1475,6 +1479,9 @@ fn insert_session_record(
             agent_id: candidate.agent_id.clone(),
             human_author: Some(human_author.to_string()),
             custom_attributes: None,
+            tool_calls: BTreeMap::new(),
+            subagents: BTreeMap::new(),
+            tool_versions: BTreeMap::new(),
         });
 }
 
@@ -1495,6 +1502,9 @@ fn insert_session_event_record(
             },
             human_author: Some(human_author.to_string()),
             custom_attributes: None,
+            tool_calls: BTreeMap::new(),
+            subagents: BTreeMap::new(),
+            tool_versions: BTreeMap::new(),
         });
 }
 
@@ -1741,7 +1751,7 @@ fn record_recovery_metric(input: RecoveryMetricInput<'_>) {
     {
         attrs = attrs.branch(short_branch);
     }
-    attrs = attrs.author(input.author_id);
+    attrs = attrs.author(input.author_id).with_current_identity();
 
     let event = MetricEvent::from_values_with_timestamp(values, attrs.to_sparse(), input.event_ts);
     crate::observability::log_metrics(vec![event]);
@@ -2,6 +2,10 @@
 //!
 //! This library maintains attribution ranges as files are edited, preserving
 //! authorship information even through moves, edits, and whitespace changes.
+//!
+//! CRLF line endings and a leading UTF-8 byte-order mark are normalized away
+//! before diffing, so converting a file's line endings or encoding marker never
+//! changes who wrote its lines.
 
 use crate::authorship::imara_diff_utils::{ByteDiff, ByteDiffOp, DiffOp, capture_diff_slices};
 use crate::authorship::move_detection::{DeletedLine, InsertedLine, detect_moves};
@@ -34,6 +38,14 @@ pub struct Attribution {
     pub ts: u128,
 }
 
+/// A known edit in byte offsets: `old` in the previous content was replaced by
+/// `new` in the current content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ByteEdit {
+    pub old: std::ops::Range<usize>,
+    pub new: std::ops::Range<usize>,
+}
+
 /// Represents attribution for a range of lines.
 /// Both start_line and end_line are inclusive (1-indexed).
 #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
@@ -171,6 +183,65 @@ pub(crate) struct MoveMapping {
     pub(crate) target_range: (usize, usize),
 }
 
+const UTF8_BOM: &str = "\u{feff}";
+
+/// Content with CRLF line endings folded to LF and any leading byte-order mark
+/// removed, keeping enough to map offsets between the two forms.
+struct EolNormalized {
+    text: String,
+    /// Raw offsets of the removed bytes, ascending.
+    removed: Vec<usize>,
+    /// Normalized offset each removed byte sat in front of, ascending.
+    removed_before: Vec<usize>,
+}
+
+impl EolNormalized {
+    fn new(content: &str) -> Self {
+        let mut removed = Vec::new();
+        let mut copied = 0usize;
+        if content.starts_with(UTF8_BOM) {
+            removed.extend(0..UTF8_BOM.len());
+            copied = UTF8_BOM.len();
+        }
+
+        let mut text = String::with_capacity(content.len());
+        for (idx, _) in content.match_indices("\r\n") {
+            text.push_str(&content[copied..idx]);
+            removed.push(idx);
+            copied = idx + 1;
+        }
+        text.push_str(&content[copied..]);
+
+        let removed_before = removed
+            .iter()
+            .enumerate()
+            .map(|(count, raw)| raw - count)
+            .collect();
+        EolNormalized {
+            text,
+            removed,
+            removed_before,
+        }
+    }
+
+    fn is_unchanged(&self) -> bool {
+        self.removed.is_empty()
+    }
+
+    fn to_normalized(&self, raw: usize) -> usize {
+        raw - self.removed.partition_point(|&removed| removed < raw)
+    }
+
+    /// Removed bytes go with the character after them, so a `\r` follows its
+    /// `\n` and a byte-order mark follows the first character.
+    fn to_raw(&self, normalized: usize) -> usize {
+        normalized
+            + self
+                .removed_before
+                .partition_point(|&before| before < normalized)
+    }
+}
+
 #[derive(Debug, Clone)]
 struct LineMetadata {
     number: usize,
@@ -261,16 +332,41 @@ struct DiffComputation {
 /// Configuration for the attribution tracker
 pub struct AttributionConfig {
     move_lines_threshold: usize,
+    normalize_line_endings: bool,
 }
 
 impl Default for AttributionConfig {
     fn default() -> Self {
         AttributionConfig {
             move_lines_threshold: 3,
+            normalize_line_endings: true,
         }
     }
 }
 
+impl AttributionConfig {
+    /// Diff line endings and byte-order marks like any other content, for files
+    /// `.gitattributes` keeps git from normalizing (`-text`, `binary`).
+    pub fn verbatim_line_endings() -> Self {
+        AttributionConfig {
+            normalize_line_endings: false,
+            ..AttributionConfig::default()
+        }
+    }
+}
+
+/// Whether `new` uses a different byte-order mark or line-ending convention
+/// than `old`. Edits reported against such a pair cover line-ending churn, so
+/// callers diff the contents instead of trusting them.
+pub fn line_ending_style_changed(old: &str, new: &str) -> bool {
+    fn style(content: &str) -> (bool, bool, bool) {
+        let crlf = content.matches("\r\n").count();
+        let lf = content.matches('\n').count();
+        (content.starts_with(UTF8_BOM), crlf > 0, lf > crlf)
+    }
+    style(old) != style(new)
+}
+
 /// Main attribution tracker
 pub struct AttributionTracker {
     config: AttributionConfig,
@@ -285,7 +381,16 @@ impl AttributionTracker {
     }
 
     /// Create a new attribution tracker with custom configuration
-    #[allow(dead_code)]
+    /// A tracker for a file whose line endings git normalizes, or keeps
+    /// verbatim when `.gitattributes` marks it `-text` or `binary`.
+    pub fn for_line_endings(normalize_line_endings: bool) -> Self {
+        if normalize_line_endings {
+            AttributionTracker::new()
+        } else {
+            AttributionTracker::with_config(AttributionConfig::verbatim_line_endings())
+        }
+    }
+
     pub fn with_config(config: AttributionConfig) -> Self {
         AttributionTracker { config }
     }
@@ -570,6 +675,62 @@ impl AttributionTracker {
             .then(|| sort_attributions_for_transform(old_attributions));
         let old_attributions = sorted_old_storage.as_deref().unwrap_or(old_attributions);
 
+        if self.config.normalize_line_endings {
+            let old_normalized = EolNormalized::new(old_content);
+            let new_normalized = EolNormalized::new(new_content);
+            if !old_normalized.is_unchanged() || !new_normalized.is_unchanged() {
+                let normalized_old_attributions: Vec<Attribution> = old_attributions
+                    .iter()
+                    .map(|attr| {
+                        Attribution::new(
+                            old_normalized.to_normalized(attr.start),
+                            old_normalized.to_normalized(attr.end),
+                            attr.author_id.clone(),
+                            attr.ts,
+                        )
+                    })
+                    .collect();
+                let updated = self.update_normalized_attributions(
+                    &old_normalized.text,
+                    &new_normalized.text,
+                    &normalized_old_attributions,
+                    current_author,
+                    ts,
+                    is_ai_checkpoint,
+                )?;
+                return Ok(updated
+                    .into_iter()
+                    .map(|attr| {
+                        Attribution::new(
+                            new_normalized.to_raw(attr.start),
+                            new_normalized.to_raw(attr.end),
+                            attr.author_id,
+                            attr.ts,
+                        )
+                    })
+                    .collect());
+            }
+        }
+
+        self.update_normalized_attributions(
+            old_content,
+            new_content,
+            old_attributions,
+            current_author,
+            ts,
+            is_ai_checkpoint,
+        )
+    }
+
+    fn update_normalized_attributions(
+        &self,
+        old_content: &str,
+        new_content: &str,
+        old_attributions: &[Attribution],
+        current_author: &str,
+        ts: u128,
+        is_ai_checkpoint: bool,
+    ) -> Result<Vec<Attribution>, GitAiError> {
         // Phase 1: Compute diff
         let diff_result = self.compute_diffs(old_content, new_content, is_ai_checkpoint)?;
 
@@ -604,6 +765,56 @@ impl AttributionTracker {
         Ok(self.merge_attributions(new_attributions))
     }
 
+    /// Update attributions through edits the caller already knows precisely,
+    /// skipping diff computation and move detection.
+    ///
+    /// `edits` must be sorted by `old.start` and must not overlap. Text outside
+    /// the edited ranges keeps its attribution at its shifted position; the
+    /// replacement text is attributed to `current_author`.
+    pub fn update_attributions_for_edits(
+        &self,
+        old_attributions: &[Attribution],
+        edits: &[ByteEdit],
+        current_author: &str,
+        ts: u128,
+    ) -> Vec<Attribution> {
+        let mut new_attributions = Vec::new();
+
+        // Unedited gaps of the old content and the offset each one moves by.
+        let mut gaps: Vec<(usize, usize, isize)> = Vec::with_capacity(edits.len() + 1);
+        let mut gap_start = 0usize;
+        let mut offset = 0isize;
+        for edit in edits {
+            gaps.push((gap_start, edit.old.start, offset));
+            offset += edit.new.len() as isize - edit.old.len() as isize;
+            gap_start = edit.old.end;
+            if !edit.new.is_empty() {
+                new_attributions.push(Attribution::new(
+                    edit.new.start,
+                    edit.new.end,
+                    current_author.to_string(),
+                    ts,
+                ));
+            }
+        }
+        gaps.push((gap_start, usize::MAX, offset));
+
+        for attr in old_attributions {
+            for &(start, end, offset) in &gaps {
+                if let Some((overlap_start, overlap_end)) = attr.intersection(start, end) {
+                    new_attributions.push(Attribution::new(
+                        overlap_start.saturating_add_signed(offset),
+                        overlap_end.saturating_add_signed(offset),
+                        attr.author_id.clone(),
+                        attr.ts,
+                    ));
+                }
+            }
+        }
+
+        self.merge_attributions(new_attributions)
+    }
+
     fn should_skip_move_detection(
         &self,
         old_content: &str,
@@ -2506,6 +2717,24 @@ mod tests {
         assert!(helper_owner.is_some(), "helper text should have an owner");
     }
 
+    #[test]
+    fn known_edits_shift_surrounding_attributions() {
+        let tracker = AttributionTracker::new();
+        let old = "keep\nold\ntail\n";
+        let new = "keep\nnew one\nnew two\ntail\n";
+        let old_attributions = vec![Attribution::new(0, old.len(), "human".to_string(), 1)];
+        let edits = [ByteEdit {
+            old: 5..9,
+            new: 5..21,
+        }];
+
+        let updated = tracker.update_attributions_for_edits(&old_attributions, &edits, "ai", 2);
+
+        assert_range_owned_by(&updated, 0, 5, "human");
+        assert_range_owned_by(&updated, 5, 21, "ai");
+        assert_range_owned_by(&updated, 21, new.len(), "human");
+    }
+
     #[test]
     fn deletions_remove_attribution() {
         let tracker = AttributionTracker::new();
@@ -2694,6 +2923,79 @@ mod tests {
         assert_range_owned_by(&updated, line3_start, line3_end, "Alice");
     }
 
+    #[test]
+    fn ai_checkpoint_crlf_to_lf_keeps_attributions() {
+        let tracker = AttributionTracker::new();
+        let old = "hello\r\nworld\r\n";
+        let new = "hello\nworld\nai line\n";
+        let old_attrs = vec![Attribution::new(0, old.len(), "Alice".into(), TEST_TS)];
+
+        let updated = tracker
+            .update_attributions_for_checkpoint(old, new, &old_attrs, "ai", TEST_TS + 1, true)
+            .unwrap();
+
+        assert_range_owned_by(&updated, 0, "hello\nworld".len(), "Alice");
+        assert_range_owned_by(&updated, "hello\nworld\n".len(), new.len() - 1, "ai");
+        let lines = attributions_to_line_attributions_for_checkpoint(&updated, new, true);
+        assert!(
+            lines
+                .iter()
+                .filter(|line| line.start_line <= 2)
+                .all(|line| line.author_id == "Alice"),
+            "EOL-only changes must not move lines to the AI: {:?}",
+            lines
+        );
+    }
+
+    #[test]
+    fn adding_byte_order_mark_keeps_attributions() {
+        let tracker = AttributionTracker::new();
+        let old = "hello\nworld\n";
+        let new = "\u{feff}hello\r\nworld\r\n";
+        let old_attrs = vec![Attribution::new(0, old.len(), "Alice".into(), TEST_TS)];
+
+        let updated = tracker
+            .update_attributions_for_checkpoint(old, new, &old_attrs, "ai", TEST_TS + 1, true)
+            .unwrap();
+
+        assert_range_owned_by(&updated, 0, new.len(), "Alice");
+    }
+
+    #[test]
+    fn verbatim_line_endings_treat_eol_changes_as_edits() {
+        let tracker = AttributionTracker::with_config(AttributionConfig::verbatim_line_endings());
+        let old = "hello\r\nworld\r\n";
+        let new = "hello\nworld\n";
+        let old_attrs = vec![Attribution::new(0, old.len(), "Alice".into(), TEST_TS)];
+
+        let updated = tracker
+            .update_attributions_for_checkpoint(old, new, &old_attrs, "ai", TEST_TS + 1, true)
+            .unwrap();
+
+        assert!(updated.iter().any(|attr| attr.author_id == "ai"));
+    }
+
+    #[test]
+    fn line_ending_style_changed_ignores_content_edits() {
+        assert!(!line_ending_style_changed("a\r\nb\r\n", "a\r\nB\r\nc\r\n"));
+        assert!(!line_ending_style_changed("a\nb\n", "a\nb\nc\n"));
+        assert!(line_ending_style_changed("a\nb\n", "a\r\nb\r\n"));
+        assert!(line_ending_style_changed("a\r\nb\r\n", "a\r\nb\r\nc\n"));
+        assert!(line_ending_style_changed("a\n", "\u{feff}a\n"));
+    }
+
+    #[test]
+    fn eol_normalized_offsets_round_trip() {
+        let normalized = EolNormalized::new("\u{feff}a\r\nb\r\n");
+        assert_eq!(normalized.text, "a\nb\n");
+        assert_eq!(normalized.to_normalized(3), 0);
+        assert_eq!(normalized.to_normalized(5), 1);
+        assert_eq!(normalized.to_raw(0), 0);
+        assert_eq!(normalized.to_raw(1), 4);
+        assert_eq!(normalized.to_raw(2), 6);
+        assert_eq!(normalized.to_raw(4), 9);
+    }
+
     #[test]
     fn collect_line_metadata_strips_cr_from_text() {
         // Verify that collect_line_metadata strips \r from the text field
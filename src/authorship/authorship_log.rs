@@ -1,6 +1,6 @@
-use crate::authorship::working_log::AgentId;
+use crate::authorship::working_log::{AgentId, SubagentInfo};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -219,9 +219,42 @@ pub struct SessionRecord {
     pub human_author: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub custom_attributes: Option<HashMap<String, String>>,
+    /// Agent tool invocations behind this session's lines: trace id -> tool_use_id.
+    /// Resolves an attestation hash `s_<session>::<trace id>` to the tool call
+    /// (and therefore the transcript tool input) that wrote those lines.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tool_calls: BTreeMap<String, String>,
+    /// Subagents (e.g. Claude Code Task tool agents) behind this session's
+    /// lines: trace id -> subagent. Lines written by the parent agent itself
+    /// have no entry.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub subagents: BTreeMap<String, SubagentInfo>,
+    /// Version of the agent tool (e.g. Claude Code 2.0.14) behind this
+    /// session's lines: trace id -> version. A session can span a tool upgrade.
+    /// Notes keep entries only for traces their attestations refer to.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tool_versions: BTreeMap<String, String>,
 }
 
 impl SessionRecord {
+    /// Tool invocation behind a full `s_<session>::<trace id>` attestation hash, if recorded.
+    pub fn tool_use_id_for_attestation(&self, attestation_hash: &str) -> Option<&str> {
+        let (_, trace_id) = attestation_hash.split_once("::")?;
+        self.tool_calls.get(trace_id).map(String::as_str)
+    }
+
+    /// Subagent behind a full `s_<session>::<trace id>` attestation hash, if recorded.
+    pub fn subagent_for_attestation(&self, attestation_hash: &str) -> Option<&SubagentInfo> {
+        let (_, trace_id) = attestation_hash.split_once("::")?;
+        self.subagents.get(trace_id)
+    }
+
+    /// Tool version behind a full `s_<session>::<trace id>` attestation hash, if recorded.
+    pub fn tool_version_for_attestation(&self, attestation_hash: &str) -> Option<&str> {
+        let (_, trace_id) = attestation_hash.split_once("::")?;
+        self.tool_versions.get(trace_id).map(String::as_str)
+    }
+
     /// Convert to a PromptRecord (with zeroed stats) for backwards-compatible lookup
     pub fn to_prompt_record(&self) -> PromptRecord {
         PromptRecord {
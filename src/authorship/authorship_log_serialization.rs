@@ -5,7 +5,7 @@ use crate::git::repository::Repository;
 use rand::RngExt;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt;
 
 /// Authorship log format version identifier
@@ -31,6 +31,69 @@ pub struct AuthorshipMetadata {
     pub humans: BTreeMap<String, HumanRecord>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub sessions: BTreeMap<String, SessionRecord>,
+    /// Set on revert commits: what the lines the revert removed were attributed to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert: Option<RevertRecord>,
+    /// Labelled checkpoints (`git-ai checkpoint --label`) that went into this
+    /// commit, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<CheckpointLabelRecord>,
+    /// Conflicts between this note and another version of it settled with
+    /// `git-ai resolve`, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resolutions: Vec<ResolutionRecord>,
+    /// Files git-ai was tracking that this commit deleted, and who deleted them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deletions: Vec<FileDeletionRecord>,
+}
+
+/// A checkpoint tagged with `git-ai checkpoint --label`, so attribution can be
+/// related to the stage of work it happened in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointLabelRecord {
+    pub label: String,
+    /// Unix seconds of the labelled checkpoint.
+    pub timestamp: u64,
+    /// `tool/model` for AI checkpoints, the author for human ones.
+    pub author: String,
+    pub additions: u32,
+    pub deletions: u32,
+}
+
+/// Audit marker left by `git-ai resolve` on the note it rewrote.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolutionRecord {
+    /// The other version: a notes ref, or `file:<path>` for a reconstructed note.
+    pub source: String,
+    pub resolved_by: String,
+    /// Unix seconds of the resolution.
+    pub timestamp: u64,
+    /// Conflicting lines that kept this note's attribution.
+    pub ours_lines: u32,
+    /// Conflicting lines that took the other version's attribution.
+    pub theirs_lines: u32,
+}
+
+/// A deleted file has no lines left to attest, so the deletion itself is
+/// recorded against whoever made it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileDeletionRecord {
+    pub file_path: String,
+    /// `tool/model` when an AI checkpoint recorded the deletion, otherwise the
+    /// human author, as for checkpoint labels.
+    pub author: String,
+    /// Lines the file had in the parent commit.
+    pub lines: u32,
+}
+
+/// Lines a revert commit removed, split by the attribution they had in the
+/// reverted commit's note, so AI churn reporting can count reverted AI work.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevertRecord {
+    pub reverted_commits: Vec<String>,
+    pub ai_lines_removed: u32,
+    pub human_lines_removed: u32,
+    pub unknown_lines_removed: u32,
 }
 
 impl AuthorshipMetadata {
@@ -42,6 +105,10 @@ impl AuthorshipMetadata {
             prompts: BTreeMap::new(),
             humans: BTreeMap::new(),
             sessions: BTreeMap::new(),
+            revert: None,
+            labels: Vec::new(),
+            resolutions: Vec::new(),
+            deletions: Vec::new(),
         }
     }
 }
@@ -160,6 +227,22 @@ impl AuthorshipLog {
             .unwrap()
     }
 
+    /// Drop session `tool_versions` entries for traces no attestation refers
+    /// to, so a note records versions only for the lines it attributes.
+    pub fn retain_attested_tool_versions(&mut self) {
+        let attested: HashSet<&str> = self
+            .attestations
+            .iter()
+            .flat_map(|file| file.entries.iter())
+            .map(|entry| entry.hash.as_str())
+            .collect();
+        for (session_id, record) in self.metadata.sessions.iter_mut() {
+            record.tool_versions.retain(|trace_id, _| {
+                attested.contains(format!("{}::{}", session_id, trace_id).as_str())
+            });
+        }
+    }
+
     /// Serialize to the new text format
     pub fn serialize_to_string(&self) -> Result<String, fmt::Error> {
         let mut output = String::new();
@@ -527,6 +610,46 @@ mod tests {
         assert_debug_snapshot!(format_line_ranges(&ranges));
     }
 
+    #[test]
+    fn test_retain_attested_tool_versions_drops_unattested_traces() {
+        let mut log = AuthorshipLog::new();
+        log.metadata.sessions.insert(
+            "s_abc".to_string(),
+            crate::authorship::authorship_log::SessionRecord {
+                agent_id: crate::authorship::working_log::AgentId {
+                    tool: "claude".to_string(),
+                    id: "session".to_string(),
+                    model: "sonnet".to_string(),
+                },
+                human_author: None,
+                custom_attributes: None,
+                tool_calls: BTreeMap::new(),
+                subagents: BTreeMap::new(),
+                tool_versions: [
+                    ("t_kept".to_string(), "2.0.14".to_string()),
+                    ("t_overwritten".to_string(), "2.0.14".to_string()),
+                ]
+                .into_iter()
+                .collect(),
+            },
+        );
+        log.get_or_create_file("src/lib.rs")
+            .add_entry(AttestationEntry::new(
+                "s_abc::t_kept".to_string(),
+                vec![LineRange::Single(1)],
+            ));
+
+        log.retain_attested_tool_versions();
+
+        assert_eq!(
+            log.metadata.sessions["s_abc"]
+                .tool_versions
+                .keys()
+                .collect::<Vec<_>>(),
+            vec!["t_kept"]
+        );
+    }
+
     #[test]
     fn test_parse_line_ranges() {
         let ranges = parse_line_ranges("1,2,19-222").unwrap();
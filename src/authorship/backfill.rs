@@ -0,0 +1,279 @@
+//! Coarse notes for history that predates git-ai.
+//!
+//! `git-ai backfill --from-github` walks merged pull requests and, for the ones
+//! that were evidently AI-authored (an AI label, an agent `Co-authored-by`
+//! trailer, or a PR opened by an agent account), writes a note crediting every
+//! line the merged commits added to that agent. The notes are deliberately
+//! coarse: there is no checkpoint data to say which lines a human touched up,
+//! so they only exist to keep stats trends from starting at zero on adoption
+//! day. Each synthesized prompt carries `backfill` custom attributes so reports
+//! can tell them apart from recorded attribution.
+
+use crate::authorship::agent_detection::{match_email_to_agent, match_username_to_platform};
+use crate::authorship::authorship_log::{LineRange, PromptRecord};
+use crate::authorship::authorship_log_serialization::{
+    AttestationEntry, AuthorshipLog, FileAttestation, generate_short_hash,
+};
+use crate::authorship::working_log::AgentId;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Labels treated as marking an AI-authored PR when none are given.
+pub const DEFAULT_AI_LABELS: &[&str] = &["ai", "ai-generated", "ai-assisted", "ai-authored"];
+
+/// Tool recorded when only a generic AI label identifies the PR.
+pub const UNKNOWN_BACKFILL_TOOL: &str = "unknown-agent";
+
+/// Label words that name the tool behind a PR, e.g. `copilot` or the `claude`
+/// in `claude-code`.
+const LABEL_TOOL_HINTS: &[(&str, &str)] = &[
+    ("copilot", "github-copilot"),
+    ("claude", "claude"),
+    ("cursor", "cursor"),
+    ("codex", "codex"),
+    ("devin", "devin"),
+    ("gemini", "gemini"),
+    ("windsurf", "windsurf"),
+];
+
+/// Why a pull request was considered AI-authored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BackfillDetection {
+    pub tool: String,
+    /// `label`, `co_authored_by` or `pr_author`.
+    pub source: &'static str,
+    /// The label, trailer value or login that matched.
+    pub evidence: String,
+}
+
+/// The agent behind a PR, judged from its labels, author login and the
+/// `Co-authored-by` trailers in its description and commit messages.
+///
+/// Trailers and agent accounts name the tool precisely, so they win over
+/// labels. `ai_labels` are compared case-insensitively; a label that names a
+/// known tool counts even when it is not listed.
+pub fn detect_pull_request_agent<'a>(
+    labels: impl IntoIterator<Item = &'a str>,
+    author_login: Option<&str>,
+    messages: impl IntoIterator<Item = &'a str>,
+    ai_labels: &[String],
+) -> Option<BackfillDetection> {
+    for message in messages {
+        for line in message.lines() {
+            let Some((name, value)) = line.trim().split_once(':') else {
+                continue;
+            };
+            if !name.trim().eq_ignore_ascii_case("co-authored-by") {
+                continue;
+            }
+            if let Some(tool) = trailer_email(value).and_then(match_email_to_agent) {
+                return Some(BackfillDetection {
+                    tool: tool.to_string(),
+                    source: "co_authored_by",
+                    evidence: value.trim().to_string(),
+                });
+            }
+        }
+    }
+
+    if let Some(login) = author_login
+        && let Some(tool) = match_username_to_platform(login)
+    {
+        return Some(BackfillDetection {
+            tool: tool.to_string(),
+            source: "pr_author",
+            evidence: login.to_string(),
+        });
+    }
+
+    let mut generic: Option<&str> = None;
+    for label in labels {
+        let lower = label.to_ascii_lowercase();
+        // Whole words only, so `precursor` does not read as `cursor`.
+        if let Some((_, tool)) = lower
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .find_map(|word| LABEL_TOOL_HINTS.iter().find(|(hint, _)| *hint == word))
+        {
+            return Some(BackfillDetection {
+                tool: tool.to_string(),
+                source: "label",
+                evidence: label.to_string(),
+            });
+        }
+        if generic.is_none() && ai_labels.iter().any(|ai| ai.eq_ignore_ascii_case(label)) {
+            generic = Some(label);
+        }
+    }
+    generic.map(|label| BackfillDetection {
+        tool: UNKNOWN_BACKFILL_TOOL.to_string(),
+        source: "label",
+        evidence: label.to_string(),
+    })
+}
+
+/// The address inside `Name <email>`, or the whole value when it has no brackets.
+fn trailer_email(value: &str) -> Option<&str> {
+    let value = value.trim();
+    let email = match (value.rfind('<'), value.rfind('>')) {
+        (Some(start), Some(end)) if start < end => &value[start + 1..end],
+        _ => value,
+    };
+    (!email.is_empty()).then_some(email)
+}
+
+/// A note crediting every line in `added_lines` (file -> added line numbers in
+/// `commit_sha`) to `detection`'s tool, or `None` when the commit adds nothing.
+///
+/// The prompt id is derived from the PR, so all commits of one PR share it.
+pub fn synthesize_backfill_note(
+    commit_sha: &str,
+    pr_number: u64,
+    detection: &BackfillDetection,
+    added_lines: &HashMap<String, Vec<u32>>,
+) -> Option<AuthorshipLog> {
+    let files: BTreeMap<&String, Vec<u32>> = added_lines
+        .iter()
+        .filter(|(_, lines)| !lines.is_empty())
+        .map(|(path, lines)| {
+            let mut lines = lines.clone();
+            lines.sort_unstable();
+            lines.dedup();
+            (path, lines)
+        })
+        .collect();
+    if files.is_empty() {
+        return None;
+    }
+
+    let agent_id = AgentId {
+        tool: detection.tool.clone(),
+        id: format!("github-pr-{}", pr_number),
+        model: "unknown".to_string(),
+    };
+    let prompt_hash = generate_short_hash(&agent_id.id, &agent_id.tool);
+    let total_lines: u32 = files.values().map(|lines| lines.len() as u32).sum();
+
+    let mut log = AuthorshipLog::new();
+    log.metadata.base_commit_sha = commit_sha.to_string();
+    for (path, lines) in files {
+        let mut file = FileAttestation::new(path.clone());
+        file.add_entry(AttestationEntry::new(
+            prompt_hash.clone(),
+            LineRange::compress_lines(&lines),
+        ));
+        log.attestations.push(file);
+    }
+    log.metadata.prompts.insert(
+        prompt_hash,
+        PromptRecord {
+            agent_id,
+            human_author: None,
+            total_additions: total_lines,
+            total_deletions: 0,
+            accepted_lines: total_lines,
+            overriden_lines: 0,
+            custom_attributes: Some(HashMap::from([
+                ("backfill".to_string(), "github".to_string()),
+                ("pull_request".to_string(), pr_number.to_string()),
+                ("detected_by".to_string(), detection.source.to_string()),
+            ])),
+            messages_url: None,
+        },
+    );
+    Some(log)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_labels() -> Vec<String> {
+        DEFAULT_AI_LABELS
+            .iter()
+            .map(|label| label.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_detect_prefers_trailers_then_agent_accounts_then_labels() {
+        let detection = detect_pull_request_agent(
+            ["ai-generated"],
+            Some("alice"),
+            ["Fix parser\n\nCo-authored-by: Claude <noreply@anthropic.com>"],
+            &default_labels(),
+        )
+        .unwrap();
+        assert_eq!(detection.tool, "claude-web");
+        assert_eq!(detection.source, "co_authored_by");
+
+        let detection = detect_pull_request_agent(
+            [],
+            Some("copilot-swe-agent[bot]"),
+            ["Initial plan"],
+            &default_labels(),
+        )
+        .unwrap();
+        assert_eq!(
+            (detection.tool.as_str(), detection.source),
+            ("github-copilot-agent", "pr_author")
+        );
+
+        let detection =
+            detect_pull_request_agent(["bug", "AI-Generated"], None, [], &default_labels())
+                .unwrap();
+        assert_eq!(detection.tool, UNKNOWN_BACKFILL_TOOL);
+        assert_eq!(detection.evidence, "AI-Generated");
+
+        let detection =
+            detect_pull_request_agent(["made-with-cursor"], None, [], &default_labels()).unwrap();
+        assert_eq!(detection.tool, "cursor");
+        assert_eq!(
+            detect_pull_request_agent(["precursor", "codexample"], None, [], &default_labels()),
+            None
+        );
+
+        assert_eq!(
+            detect_pull_request_agent(
+                ["bug"],
+                Some("alice"),
+                ["Co-authored-by: Bob <bob@example.com>"],
+                &default_labels()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_synthesized_note_credits_all_added_lines_to_the_pr_prompt() {
+        let detection = BackfillDetection {
+            tool: "cursor".to_string(),
+            source: "label",
+            evidence: "cursor".to_string(),
+        };
+        let added = HashMap::from([
+            ("src/lib.rs".to_string(), vec![4, 1, 2, 3]),
+            ("empty.rs".to_string(), Vec::new()),
+        ]);
+        let log = synthesize_backfill_note("abc123", 42, &detection, &added).unwrap();
+
+        assert_eq!(log.attestations.len(), 1);
+        assert_eq!(log.attestations[0].file_path, "src/lib.rs");
+        assert_eq!(
+            log.attestations[0].entries[0].line_ranges,
+            vec![LineRange::Range(1, 4)]
+        );
+        let prompt = log.metadata.prompts.values().next().unwrap();
+        assert_eq!(prompt.agent_id.id, "github-pr-42");
+        assert_eq!(prompt.accepted_lines, 4);
+        assert_eq!(
+            prompt
+                .custom_attributes
+                .as_ref()
+                .and_then(|attributes| attributes.get("backfill"))
+                .map(String::as_str),
+            Some("github")
+        );
+
+        assert!(synthesize_backfill_note("abc123", 42, &detection, &HashMap::new()).is_none());
+    }
+}
@@ -3,7 +3,7 @@ use crate::authorship::authorship_log_serialization::{
     AttestationEntry, AuthorshipLog, generate_session_id, generate_trace_id,
 };
 use crate::authorship::working_log::AgentId;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 const DEVIN_ID_PATH: &str = "/opt/.devin/devin_id";
 const DEVIN_DIR_PATH: &str = "/opt/.devin";
@@ -163,6 +163,9 @@ pub fn fill_unattributed_lines(
             agent_id,
             human_author: Some(human_author.to_string()),
             custom_attributes: None,
+            tool_calls: BTreeMap::new(),
+            subagents: BTreeMap::new(),
+            tool_versions: BTreeMap::new(),
         },
     );
 
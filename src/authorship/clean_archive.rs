@@ -0,0 +1,446 @@
+//! Archive pending attribution before `git clean` removes untracked files.
+//!
+//! New files an agent created are untracked until they are committed, so
+//! `git clean -fd` deletes them together with the only record of who wrote
+//! them. Before the wrapper forwards a destructive `git clean`, it asks git
+//! which paths would be removed, and copies every one that has pending
+//! attribution in the working log into `.git/ai/clean_archives/<id>/`:
+//!
+//! - `manifest.json` — the base commit and the archived paths
+//! - `files/<path>` — the file contents as they were before the clean
+//! - `working_log/` — a copy of the working log for the base commit
+//!
+//! `git-ai restore-attribution` writes the files back and re-appends their
+//! latest checkpoint (or INITIAL attribution) to the live working log. Git has
+//! no hook that runs before `clean`, so only the wrapper can take the archive.
+
+use crate::authorship::working_log::Checkpoint;
+use crate::error::GitAiError;
+use crate::git::repo_storage::{InitialAttributions, PersistedWorkingLog, copy_dir_contents};
+use crate::git::repository::{Repository, exec_git};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Archives kept per repository; older ones are deleted when a new one is taken.
+const MAX_CLEAN_ARCHIVES: usize = 20;
+const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CleanArchiveManifest {
+    pub id: String,
+    pub base_commit: String,
+    pub created_at: u64,
+    /// Repository-relative paths whose contents and attribution were archived.
+    pub files: Vec<String>,
+}
+
+/// Outcome of [`restore_clean_archive`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RestoreReport {
+    pub restored: Vec<String>,
+    /// Paths that already exist in the worktree and were left untouched.
+    pub skipped_existing: Vec<String>,
+}
+
+/// Archive the attributed files a `git clean` invocation is about to remove.
+///
+/// Returns `None` when the invocation removes nothing with pending attribution
+/// (including dry runs and cleans that git would refuse without `-f`).
+pub fn archive_before_clean(
+    repo: &Repository,
+    command_args: &[String],
+) -> Result<Option<CleanArchiveManifest>, GitAiError> {
+    if !clean_removes_files(repo, command_args) {
+        return Ok(None);
+    }
+    let Ok(base_commit) = repo.head().and_then(|head| head.target()) else {
+        return Ok(None);
+    };
+    if !repo.storage.has_working_log(&base_commit) {
+        return Ok(None);
+    }
+    let working_log = repo.storage.working_log_for_base_commit(&base_commit)?;
+    let mut attributed = working_log.all_ai_touched_files()?;
+    attributed.extend(working_log.read_initial_attributions().files.into_keys());
+    if attributed.is_empty() {
+        return Ok(None);
+    }
+
+    let removed = parse_clean_dry_run(
+        &clean_dry_run_output(repo, command_args)?,
+        &show_prefix(repo)?,
+    );
+    let files = attributed_paths_removed(&removed, &attributed);
+    if files.is_empty() {
+        return Ok(None);
+    }
+
+    let workdir = repo.workdir()?;
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let archives_dir = clean_archives_dir(repo);
+    let mut id = created_at.to_string();
+    let mut suffix = 1;
+    while archives_dir.join(&id).exists() {
+        id = format!("{}-{}", created_at, suffix);
+        suffix += 1;
+    }
+    let archive_dir = archives_dir.join(&id);
+
+    let mut archived = Vec::new();
+    for file in files {
+        let source = workdir.join(&file);
+        if !source.is_file() {
+            continue;
+        }
+        let target = archive_dir.join("files").join(&file);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(&source, &target)?;
+        archived.push(file);
+    }
+    if archived.is_empty() {
+        return Ok(None);
+    }
+    copy_dir_contents(&working_log.dir, &archive_dir.join("working_log"))?;
+
+    let manifest = CleanArchiveManifest {
+        id,
+        base_commit,
+        created_at,
+        files: archived,
+    };
+    fs::write(
+        archive_dir.join(MANIFEST_FILE),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+    prune_clean_archives(&archives_dir);
+    Ok(Some(manifest))
+}
+
+/// All archives for this repository, newest first.
+pub fn list_clean_archives(repo: &Repository) -> Vec<CleanArchiveManifest> {
+    let Ok(entries) = fs::read_dir(clean_archives_dir(repo)) else {
+        return Vec::new();
+    };
+    let mut manifests: Vec<CleanArchiveManifest> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let content = fs::read_to_string(entry.path().join(MANIFEST_FILE)).ok()?;
+            serde_json::from_str(&content).ok()
+        })
+        .collect();
+    manifests.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+    manifests
+}
+
+/// Write archived files back and re-attach their attribution to the working log.
+///
+/// Files that already exist are skipped unless `force` is set, since their
+/// current contents no longer match the archived attribution.
+pub fn restore_clean_archive(
+    repo: &Repository,
+    manifest: &CleanArchiveManifest,
+    force: bool,
+) -> Result<RestoreReport, GitAiError> {
+    let head = repo.head().and_then(|head| head.target()).ok();
+    if head.as_deref() != Some(manifest.base_commit.as_str()) {
+        return Err(GitAiError::Generic(format!(
+            "archive {} was taken on commit {}; check out that commit to restore it",
+            manifest.id, manifest.base_commit
+        )));
+    }
+
+    let workdir = repo.workdir()?;
+    let archive_dir = clean_archives_dir(repo).join(&manifest.id);
+    let archived_log = PersistedWorkingLog::new(
+        archive_dir.join("working_log"),
+        &manifest.base_commit,
+        workdir.clone(),
+        workdir.clone(),
+        None,
+    );
+    let working_log = repo
+        .storage
+        .working_log_for_base_commit(&manifest.base_commit)?;
+    let archived_checkpoints = archived_log.read_all_checkpoints()?;
+    let archived_initial = archived_log.read_initial_attributions();
+    let mut initial = working_log.read_initial_attributions();
+    let mut initial_changed = false;
+
+    let mut report = RestoreReport::default();
+    for file in &manifest.files {
+        let target = workdir.join(file);
+        if target.exists() && !force {
+            report.skipped_existing.push(file.clone());
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(archive_dir.join("files").join(file), &target)?;
+
+        if let Some(checkpoint) = latest_checkpoint_for_file(&archived_checkpoints, file) {
+            let entry = &checkpoint.entries[0];
+            let current = latest_checkpoint_for_file(&working_log.read_all_checkpoints()?, file);
+            if current.is_none_or(|current| current.entries[0].blob_sha != entry.blob_sha) {
+                working_log
+                    .persist_file_version(&archived_log.get_file_version(&entry.blob_sha)?)?;
+                working_log.append_checkpoint(&checkpoint)?;
+            }
+        } else if !initial.files.contains_key(file)
+            && merge_initial_file(
+                &mut initial,
+                &archived_initial,
+                &archived_log,
+                &working_log,
+                file,
+            )?
+        {
+            initial_changed = true;
+        }
+        report.restored.push(file.clone());
+    }
+    if initial_changed {
+        working_log.write_initial(initial)?;
+    }
+    Ok(report)
+}
+
+/// The newest checkpoint touching `file`, narrowed to that file's entry.
+///
+/// Line stats are cleared so the re-appended copy is not counted twice.
+fn latest_checkpoint_for_file(checkpoints: &[Checkpoint], file: &str) -> Option<Checkpoint> {
+    checkpoints.iter().rev().find_map(|checkpoint| {
+        let entry = checkpoint.entries.iter().find(|entry| entry.file == file)?;
+        let mut narrowed = checkpoint.clone();
+        narrowed.entries = vec![entry.clone()];
+        narrowed.line_stats = Default::default();
+        Some(narrowed)
+    })
+}
+
+/// Copy one file's INITIAL attribution, and the records it references, from the archive.
+fn merge_initial_file(
+    initial: &mut InitialAttributions,
+    archived: &InitialAttributions,
+    archived_log: &PersistedWorkingLog,
+    working_log: &PersistedWorkingLog,
+    file: &str,
+) -> Result<bool, GitAiError> {
+    let Some(line_attrs) = archived.files.get(file) else {
+        return Ok(false);
+    };
+    if let Some(content) = archived_log.stored_initial_file_content_from(archived, file) {
+        let blob_sha = working_log.persist_file_version(&content)?;
+        initial.file_blobs.insert(file.to_string(), blob_sha);
+    }
+    initial.files.insert(file.to_string(), line_attrs.clone());
+    for (id, prompt) in &archived.prompts {
+        initial
+            .prompts
+            .entry(id.clone())
+            .or_insert_with(|| prompt.clone());
+    }
+    for (id, human) in &archived.humans {
+        initial
+            .humans
+            .entry(id.clone())
+            .or_insert_with(|| human.clone());
+    }
+    for (id, session) in &archived.sessions {
+        initial
+            .sessions
+            .entry(id.clone())
+            .or_insert_with(|| session.clone());
+    }
+    Ok(true)
+}
+
+fn clean_archives_dir(repo: &Repository) -> PathBuf {
+    repo.storage.ai_dir.join("clean_archives")
+}
+
+fn prune_clean_archives(archives_dir: &Path) {
+    let Ok(entries) = fs::read_dir(archives_dir) else {
+        return;
+    };
+    let mut dirs: Vec<(u64, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let content = fs::read_to_string(entry.path().join(MANIFEST_FILE)).ok()?;
+            let manifest: CleanArchiveManifest = serde_json::from_str(&content).ok()?;
+            Some((manifest.created_at, entry.path()))
+        })
+        .collect();
+    if dirs.len() <= MAX_CLEAN_ARCHIVES {
+        return;
+    }
+    dirs.sort();
+    for (_, dir) in &dirs[..dirs.len() - MAX_CLEAN_ARCHIVES] {
+        let _ = fs::remove_dir_all(dir);
+    }
+}
+
+/// Whether git would actually delete files for this invocation.
+fn clean_removes_files(repo: &Repository, command_args: &[String]) -> bool {
+    let mut forced = false;
+    for arg in command_args {
+        match arg.as_str() {
+            "--" => break,
+            "-n" | "--dry-run" => return false,
+            "-f" | "--force" | "-i" | "--interactive" => forced = true,
+            short if short.starts_with('-') && !short.starts_with("--") => {
+                let flags = short[1..].split('e').next().unwrap_or_default();
+                if flags.contains('n') {
+                    return false;
+                }
+                forced |= flags.contains('f') || flags.contains('i');
+            }
+            _ => {}
+        }
+    }
+    forced
+        || repo
+            .config_get_str("clean.requireForce")
+            .ok()
+            .flatten()
+            .is_some_and(|value| {
+                matches!(
+                    value.trim().to_ascii_lowercase().as_str(),
+                    "false" | "no" | "off" | "0"
+                )
+            })
+}
+
+/// Run the same clean as a dry run, without the flags that change what it prints.
+fn clean_dry_run_output(repo: &Repository, command_args: &[String]) -> Result<String, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "-c".to_string(),
+        "core.quotePath=false".to_string(),
+        "clean".to_string(),
+        "--dry-run".to_string(),
+    ]);
+    let mut after_double_dash = false;
+    for arg in command_args {
+        if after_double_dash {
+            args.push(arg.clone());
+            continue;
+        }
+        match arg.as_str() {
+            "--" => {
+                after_double_dash = true;
+                args.push(arg.clone());
+            }
+            "-i" | "--interactive" | "-q" | "--quiet" => {}
+            short if short.starts_with('-') && !short.starts_with("--") => {
+                // Everything after `e` in a cluster is the exclude pattern.
+                let (flags, pattern) = match short[1..].find('e') {
+                    Some(pos) => short[1..].split_at(pos),
+                    None => (&short[1..], ""),
+                };
+                let flags: String = flags.chars().filter(|c| !matches!(c, 'i' | 'q')).collect();
+                if !flags.is_empty() || !pattern.is_empty() {
+                    args.push(format!("-{}{}", flags, pattern));
+                }
+            }
+            _ => args.push(arg.clone()),
+        }
+    }
+    let output = exec_git(&args)?;
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// The current directory relative to the worktree root, as git prints paths relative to it.
+fn show_prefix(repo: &Repository) -> Result<String, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend(["rev-parse".to_string(), "--show-prefix".to_string()]);
+    let output = exec_git(&args)?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Repository-relative paths from `git clean --dry-run` output. Directories
+/// keep their trailing `/`.
+pub fn parse_clean_dry_run(output: &str, prefix: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.strip_prefix("Would remove "))
+        .filter_map(|path| {
+            let is_dir = path.ends_with('/');
+            let mut parts: Vec<&str> = prefix.split('/').filter(|p| !p.is_empty()).collect();
+            for part in path.split('/').filter(|p| !p.is_empty() && *p != ".") {
+                if part == ".." {
+                    parts.pop()?;
+                } else {
+                    parts.push(part);
+                }
+            }
+            let joined = parts.join("/");
+            if joined.is_empty() {
+                return None;
+            }
+            Some(if is_dir {
+                format!("{}/", joined)
+            } else {
+                joined
+            })
+        })
+        .collect()
+}
+
+/// Attributed paths that are removed directly or sit under a removed directory.
+pub fn attributed_paths_removed(removed: &[String], attributed: &HashSet<String>) -> Vec<String> {
+    let mut files = BTreeSet::new();
+    for path in removed {
+        if path.ends_with('/') {
+            files.extend(
+                attributed
+                    .iter()
+                    .filter(|file| file.starts_with(path.as_str()))
+                    .cloned(),
+            );
+        } else if attributed.contains(path) {
+            files.insert(path.clone());
+        }
+    }
+    files.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_removed_paths_are_resolved_against_prefix() {
+        let output = "Would remove new.rs\nWould remove ../top.rs\nWould remove gen/\n";
+        assert_eq!(
+            parse_clean_dry_run(output, "src/"),
+            vec!["src/new.rs", "top.rs", "src/gen/"]
+        );
+    }
+
+    #[test]
+    fn test_only_attributed_paths_are_archived() {
+        let removed = vec![
+            "scratch.txt".to_string(),
+            "agent.rs".to_string(),
+            "gen/".to_string(),
+        ];
+        let attributed = HashSet::from([
+            "agent.rs".to_string(),
+            "gen/a.rs".to_string(),
+            "generated.rs".to_string(),
+        ]);
+        assert_eq!(
+            attributed_paths_removed(&removed, &attributed),
+            vec!["agent.rs", "gen/a.rs"]
+        );
+    }
+}
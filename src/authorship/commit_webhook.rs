@@ -0,0 +1,215 @@
+//! Outbound webhook fired after post-commit attribution.
+//!
+//! With `commit_webhook_url` configured, every commit POSTs a JSON payload with
+//! its stats, or `"stats": null` when post-commit skipped them (merge commits,
+//! commits too large to diff in the hook). When `commit_webhook_secret` is set
+//! the body is signed with HMAC-SHA256 and the hex digest sent as
+//! `X-Git-Ai-Signature: sha256=<hex>`, so receivers can check it came from
+//! git-ai. Delivery is best-effort: a failed POST is logged, never retried, and
+//! never fails the commit.
+//!
+//! In the daemon the POST runs on a blocking worker so a slow receiver does not
+//! hold up the next commit. The wrapper's post-commit hook exits right after,
+//! which would kill a detached thread, so there it posts inline.
+
+use crate::authorship::anonymize::hmac_sha256;
+use crate::authorship::stats::CommitStats;
+use crate::config::Config;
+use crate::git::repository::Repository;
+use serde::Serialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Value of the `X-Git-Ai-Event` header and the payload's `event` field.
+pub const COMMITTED_EVENT: &str = "committed";
+
+/// Header carrying `sha256=<hex HMAC of the body>` when a secret is configured.
+pub const SIGNATURE_HEADER: &str = "X-Git-Ai-Signature";
+
+/// Kept short: outside the daemon the POST runs inside the post-commit hook.
+const WEBHOOK_TIMEOUT_SECS: u64 = 5;
+
+#[derive(Debug, Serialize)]
+pub struct CommittedPayload<'a> {
+    pub event: &'static str,
+    /// Seconds since the epoch when the payload was built; signed along with
+    /// the rest of the body so receivers can reject replays.
+    pub timestamp: u64,
+    pub repo_url: Option<String>,
+    pub branch: Option<String>,
+    pub commit_sha: &'a str,
+    pub base_commit_sha: &'a str,
+    pub author: String,
+    pub stats: Option<&'a CommitStats>,
+}
+
+/// `sha256=` followed by the hex HMAC-SHA256 of `body` keyed with `secret`.
+pub fn sign_payload(secret: &str, body: &str) -> String {
+    let hex: String = hmac_sha256(secret.as_bytes(), body.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("sha256={}", hex)
+}
+
+fn configured_webhook() -> Option<(String, Option<String>)> {
+    let file_config = crate::config::effective_file_config_cached();
+    let url = file_config
+        .commit_webhook_url
+        .clone()
+        .filter(|url| !url.trim().is_empty())?;
+    let secret = file_config
+        .commit_webhook_secret
+        .clone()
+        .filter(|secret| !secret.is_empty());
+    Some((url, secret))
+}
+
+/// POST the commit's stats to the configured webhook, if any. `stats` is `None`
+/// when post-commit did not compute them.
+pub fn emit_committed(
+    repo: &Repository,
+    commit_sha: &str,
+    parent_sha: &str,
+    human_author: &str,
+    stats: Option<&CommitStats>,
+) {
+    let Some((url, secret)) = configured_webhook() else {
+        return;
+    };
+
+    let repo_url = crate::repo_url::resolve_repo_url_from_repo(repo);
+    let author = match crate::authorship::anonymize::workspace_key() {
+        Some(key)
+            if Config::get().should_anonymize_remote_urls(repo_url.as_deref().into_iter()) =>
+        {
+            crate::authorship::anonymize::anonymize_value(&key, human_author)
+        }
+        _ => human_author.to_string(),
+    };
+    let payload = CommittedPayload {
+        event: COMMITTED_EVENT,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        repo_url,
+        branch: repo.head().ok().and_then(|head| head.shorthand().ok()),
+        commit_sha,
+        base_commit_sha: parent_sha,
+        author,
+        stats,
+    };
+
+    let body = match serde_json::to_string(&payload) {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::debug!("commit webhook payload for {} failed: {}", commit_sha, e);
+            return;
+        }
+    };
+
+    let commit_sha = commit_sha.to_string();
+    let deliver = move || {
+        if let Err(e) = post_body(&url, secret.as_deref(), &body) {
+            tracing::debug!("commit webhook for {} failed: {}", commit_sha, e);
+        }
+    };
+    match tokio::runtime::Handle::try_current() {
+        Ok(runtime) => drop(runtime.spawn_blocking(deliver)),
+        Err(_) => deliver(),
+    }
+}
+
+fn post_body(url: &str, secret: Option<&str>, body: &str) -> Result<(), String> {
+    let agent = crate::http::build_agent(Some(WEBHOOK_TIMEOUT_SECS));
+    let mut request = agent
+        .post(url)
+        .set("Content-Type", "application/json")
+        .set(
+            "User-Agent",
+            &format!("git-ai/{}", env!("CARGO_PKG_VERSION")),
+        )
+        .set("X-Git-Ai-Event", COMMITTED_EVENT);
+    if let Some(secret) = secret {
+        request = request.set(SIGNATURE_HEADER, &sign_payload(secret, body));
+    }
+    let response = crate::http::send_with_body(request, body)?;
+    if (200..300).contains(&response.status_code) {
+        Ok(())
+    } else {
+        Err(format!("HTTP {}", response.status_code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_payload_is_prefixed_hex_hmac() {
+        let signature = sign_payload("Jefe", "what do ya want for nothing?");
+        assert_eq!(
+            signature,
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn committed_payload_serializes_stats() {
+        let stats = CommitStats {
+            ai_additions: 3,
+            human_additions: 2,
+            git_diff_added_lines: 5,
+            ..Default::default()
+        };
+        let payload = CommittedPayload {
+            event: COMMITTED_EVENT,
+            timestamp: 1_700_000_000,
+            repo_url: Some("https://github.com/acme/app".to_string()),
+            branch: None,
+            commit_sha: "abc123",
+            base_commit_sha: "def456",
+            author: "Alice <alice@example.com>".to_string(),
+            stats: Some(&stats),
+        };
+        let value: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string(&payload).unwrap()).unwrap();
+        assert_eq!(value["event"], "committed");
+        assert_eq!(value["commit_sha"], "abc123");
+        assert_eq!(value["branch"], serde_json::Value::Null);
+        assert_eq!(value["stats"]["ai_additions"], 3);
+        assert_eq!(value["stats"]["git_diff_added_lines"], 5);
+    }
+
+    #[test]
+    fn post_body_sends_signed_event() {
+        let body = r#"{"event":"committed"}"#;
+        let mut server = mockito::Server::new();
+        let hook = server
+            .mock("POST", "/hooks/commits")
+            .match_header("Content-Type", "application/json")
+            .match_header("X-Git-Ai-Event", COMMITTED_EVENT)
+            .match_header(SIGNATURE_HEADER, sign_payload("s3cret", body).as_str())
+            .match_body(body)
+            .with_status(204)
+            .create();
+
+        let url = format!("{}/hooks/commits", server.url());
+        assert_eq!(post_body(&url, Some("s3cret"), body), Ok(()));
+        hook.assert();
+    }
+
+    #[test]
+    fn post_body_reports_non_2xx_and_omits_unset_signature() {
+        let mut server = mockito::Server::new();
+        let hook = server
+            .mock("POST", "/hooks/commits")
+            .match_header(SIGNATURE_HEADER, mockito::Matcher::Missing)
+            .with_status(500)
+            .create();
+
+        let url = format!("{}/hooks/commits", server.url());
+        assert_eq!(post_body(&url, None, "{}"), Err("HTTP 500".to_string()));
+        hook.assert();
+    }
+}
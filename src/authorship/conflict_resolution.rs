@@ -1,7 +1,16 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::authorship::authorship_log::LineRange;
-use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::authorship_log::{LineRange, PromptRecord};
+use crate::authorship::authorship_log_serialization::{
+    AttestationEntry, AuthorshipLog, CheckpointLabelRecord, generate_short_hash,
+};
+use crate::authorship::working_log::AgentId;
+use crate::error::GitAiError;
+use crate::git::repo_storage::ConflictResolutionRecord;
+use crate::git::repository::Repository;
+
+/// Label recorded in the note for a merge tool's conflict resolution.
+pub const CONFLICT_RESOLUTION_LABEL: &str = "conflict-resolution";
 
 fn normalize_line_ranges(ranges: &[LineRange]) -> Vec<LineRange> {
     let mut lines: Vec<u32> = ranges.iter().flat_map(LineRange::expand).collect();
@@ -156,3 +165,210 @@ pub fn merge_conflict_resolution_authorship(
     merged.metadata.base_commit_sha = commit_sha.to_string();
     merged
 }
+
+/// Lines of `commit_sha` in `files` that no parent has, i.e. the lines the
+/// conflict resolution wrote rather than took from one side.
+fn lines_new_to_every_parent(
+    repo: &Repository,
+    parents: &[String],
+    commit_sha: &str,
+    files: &HashSet<String>,
+) -> Result<HashMap<String, Vec<u32>>, GitAiError> {
+    let mut new_lines: Option<HashMap<String, Vec<u32>>> = None;
+    for parent in parents {
+        let added = repo.diff_added_lines(parent, commit_sha, Some(files))?;
+        new_lines = Some(match new_lines {
+            None => added,
+            Some(previous) => previous
+                .into_iter()
+                .filter_map(|(file, lines)| {
+                    let added_here: HashSet<u32> = added.get(&file)?.iter().copied().collect();
+                    let lines: Vec<u32> = lines
+                        .into_iter()
+                        .filter(|line| added_here.contains(line))
+                        .collect();
+                    (!lines.is_empty()).then_some((file, lines))
+                })
+                .collect(),
+        });
+    }
+    Ok(new_lines.unwrap_or_default())
+}
+
+/// A note crediting `new_lines` to the merge tools of `records`, with a
+/// label per resolution. A file recorded more than once belongs to the
+/// latest record.
+fn resolution_log_from_records(
+    records: &[ConflictResolutionRecord],
+    new_lines: &HashMap<String, Vec<u32>>,
+) -> (AuthorshipLog, Vec<CheckpointLabelRecord>) {
+    let mut owner: HashMap<&str, usize> = HashMap::new();
+    for (index, record) in records.iter().enumerate() {
+        for file in &record.files {
+            owner.insert(file.as_str(), index);
+        }
+    }
+
+    let mut log = AuthorshipLog::new();
+    let mut labels = Vec::new();
+    for (index, record) in records.iter().enumerate() {
+        let mut files: Vec<(&str, &Vec<u32>)> = owner
+            .iter()
+            .filter(|(_, owner)| **owner == index)
+            .filter_map(|(file, _)| Some((*file, new_lines.get(*file)?)))
+            .filter(|(_, lines)| !lines.is_empty())
+            .collect();
+        if files.is_empty() {
+            continue;
+        }
+        files.sort();
+
+        let agent_id = AgentId {
+            tool: record.tool.clone(),
+            id: format!(
+                "{}-{}",
+                CONFLICT_RESOLUTION_LABEL,
+                &record.head[..record.head.len().min(12)]
+            ),
+            model: record.model.clone(),
+        };
+        let hash = generate_short_hash(&agent_id.id, &agent_id.tool);
+        let mut additions = 0u32;
+        for (file, lines) in files {
+            additions += lines.len() as u32;
+            log.get_or_create_file(file)
+                .add_entry(AttestationEntry::new(
+                    hash.clone(),
+                    LineRange::compress_lines(lines),
+                ));
+        }
+
+        let prompt = log
+            .metadata
+            .prompts
+            .entry(hash)
+            .or_insert_with(|| PromptRecord {
+                agent_id,
+                human_author: None,
+                messages_url: None,
+                total_additions: 0,
+                total_deletions: 0,
+                accepted_lines: 0,
+                overriden_lines: 0,
+                custom_attributes: None,
+            });
+        prompt.total_additions += additions;
+        prompt.accepted_lines += additions;
+
+        labels.push(CheckpointLabelRecord {
+            label: CONFLICT_RESOLUTION_LABEL.to_string(),
+            timestamp: record.timestamp,
+            author: format!("{}/{}", record.tool, record.model),
+            additions,
+            deletions: 0,
+        });
+    }
+    (log, labels)
+}
+
+/// Credit the lines of merge commit `commit_sha` written by AI merge tools
+/// (see `git-ai checkpoint --conflict-resolution`) to those tools. Lines the
+/// note already attributes keep their attribution. The applied records are
+/// dropped.
+pub fn fold_merge_tool_resolutions(
+    repo: &Repository,
+    commit_sha: &str,
+    log: &mut AuthorshipLog,
+) -> Result<(), GitAiError> {
+    let records = repo.storage.read_conflict_resolutions();
+    if records.is_empty() {
+        return Ok(());
+    }
+    let parents: Vec<String> = repo
+        .find_commit(commit_sha.to_string())?
+        .parents()
+        .map(|parent| parent.id().to_string())
+        .collect();
+    let records: Vec<ConflictResolutionRecord> = records
+        .into_iter()
+        .filter(|record| record.applies_to(&parents))
+        .collect();
+    if records.is_empty() {
+        return Ok(());
+    }
+
+    let files: HashSet<String> = records
+        .iter()
+        .flat_map(|record| record.files.iter().cloned())
+        .collect();
+    let new_lines = lines_new_to_every_parent(repo, &parents, commit_sha, &files)?;
+    let (resolution_log, labels) = resolution_log_from_records(&records, &new_lines);
+
+    *log =
+        merge_conflict_resolution_authorship(Some(std::mem::take(log)), resolution_log, commit_sha);
+    log.metadata.labels.extend(labels);
+    log.metadata.labels.sort_by_key(|label| label.timestamp);
+
+    repo.storage.remove_conflict_resolutions(&parents[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(tool: &str, files: &[&str], timestamp: u64) -> ConflictResolutionRecord {
+        ConflictResolutionRecord {
+            tool: tool.to_string(),
+            model: "unknown".to_string(),
+            files: files.iter().map(|file| file.to_string()).collect(),
+            head: "a".repeat(40),
+            merge_heads: vec!["b".repeat(40)],
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_resolution_log_credits_each_file_to_its_latest_tool() {
+        let records = vec![
+            record("cursor", &["src/a.rs", "src/b.rs"], 10),
+            record("claude", &["src/b.rs"], 20),
+        ];
+        let new_lines = HashMap::from([
+            ("src/a.rs".to_string(), vec![3, 4, 5]),
+            ("src/b.rs".to_string(), vec![7]),
+            ("src/c.rs".to_string(), vec![1]),
+        ]);
+
+        let (log, labels) = resolution_log_from_records(&records, &new_lines);
+
+        let tool_of = |file: &str| {
+            let attestation = log
+                .attestations
+                .iter()
+                .find(|attestation| attestation.file_path == file)?;
+            let hash = &attestation.entries[0].hash;
+            Some(log.metadata.prompts[hash].agent_id.tool.clone())
+        };
+        assert_eq!(tool_of("src/a.rs").as_deref(), Some("cursor"));
+        assert_eq!(tool_of("src/b.rs").as_deref(), Some("claude"));
+        assert_eq!(tool_of("src/c.rs"), None);
+
+        let summary: Vec<(&str, u32)> = labels
+            .iter()
+            .map(|label| (label.author.as_str(), label.additions))
+            .collect();
+        assert_eq!(summary, vec![("cursor/unknown", 3), ("claude/unknown", 1)]);
+    }
+
+    #[test]
+    fn test_record_applies_only_to_its_merge() {
+        let resolution = record("cursor", &["a.rs"], 1);
+        let head = "a".repeat(40);
+        let merged = "b".repeat(40);
+
+        assert!(resolution.applies_to(&[head.clone(), merged.clone()]));
+        assert!(!resolution.applies_to(&[merged.clone(), head.clone()]));
+        assert!(!resolution.applies_to(&[head.clone(), "c".repeat(40)]));
+        assert!(!resolution.applies_to(std::slice::from_ref(&head)));
+    }
+}
@@ -0,0 +1,174 @@
+//! Attribution coverage: how much of what was committed git-ai has evidence for.
+//!
+//! A commit's added line is covered when its authorship note attests it, to an
+//! AI session or to a known human from a checkpoint. Lines without any
+//! attestation are untracked. Coverage is reported per commit and summed over a
+//! range by `git-ai stats --coverage`; commits with added lines but no note at
+//! all usually point at a missing hook or agent integration.
+
+use crate::authorship::stats::CommitStats;
+use crate::authorship::stats_rollup::rollup_commit_stats;
+use crate::error::GitAiError;
+use crate::git::notes_api::commits_with_notes;
+use crate::git::repository::Repository;
+use crate::workspace_config::WhitespaceMode;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CommitCoverage {
+    pub commit_sha: String,
+    pub added_lines: u32,
+    /// Added lines attested to AI or to a known human.
+    pub attributed_lines: u32,
+    pub has_note: bool,
+    pub coverage_pct: f64,
+}
+
+impl CommitCoverage {
+    pub fn from_stats(commit_sha: &str, stats: &CommitStats, has_note: bool) -> Self {
+        let added_lines = stats.git_diff_added_lines;
+        let attributed_lines = stats
+            .ai_additions
+            .saturating_add(stats.human_additions)
+            .min(added_lines);
+        CommitCoverage {
+            commit_sha: commit_sha.to_string(),
+            added_lines,
+            attributed_lines,
+            has_note,
+            coverage_pct: coverage_pct(attributed_lines, added_lines),
+        }
+    }
+
+    pub fn untracked_lines(&self) -> u32 {
+        self.added_lines - self.attributed_lines
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CoverageReport {
+    pub added_lines: u32,
+    pub attributed_lines: u32,
+    pub coverage_pct: f64,
+    pub commits_with_notes: usize,
+    pub commits: Vec<CommitCoverage>,
+}
+
+impl CoverageReport {
+    pub fn from_commits(commits: Vec<CommitCoverage>) -> Self {
+        let added_lines = commits.iter().map(|c| c.added_lines).sum();
+        let attributed_lines = commits.iter().map(|c| c.attributed_lines).sum();
+        CoverageReport {
+            added_lines,
+            attributed_lines,
+            coverage_pct: coverage_pct(attributed_lines, added_lines),
+            commits_with_notes: commits.iter().filter(|c| c.has_note).count(),
+            commits,
+        }
+    }
+
+    /// Commits that added lines but carry no authorship note.
+    pub fn commits_missing_notes(&self) -> impl Iterator<Item = &CommitCoverage> {
+        self.commits
+            .iter()
+            .filter(|c| !c.has_note && c.added_lines > 0)
+    }
+}
+
+/// A commit that adds nothing has nothing left to attribute.
+fn coverage_pct(attributed_lines: u32, added_lines: u32) -> f64 {
+    if added_lines == 0 {
+        return 100.0;
+    }
+    f64::from(attributed_lines) * 100.0 / f64::from(added_lines)
+}
+
+/// Coverage of each of `commit_shas`, in the order given, and in total.
+pub fn coverage_for_commits(
+    repo: &Repository,
+    commit_shas: &[String],
+    ignore_patterns: &[String],
+    whitespace: WhitespaceMode,
+) -> Result<CoverageReport, GitAiError> {
+    let noted = commits_with_notes(repo, commit_shas)?;
+    let stats = rollup_commit_stats(repo, commit_shas, ignore_patterns, whitespace)?;
+    Ok(CoverageReport::from_commits(
+        commit_shas
+            .iter()
+            .zip(stats)
+            .map(|(sha, (stats, _))| CommitCoverage::from_stats(sha, &stats, noted.contains(sha)))
+            .collect(),
+    ))
+}
+
+pub fn print_coverage_report(report: &CoverageReport) {
+    println!(
+        "Attribution coverage: {:.1}% of {} added lines ({} of {} commits with notes)",
+        report.coverage_pct,
+        report.added_lines,
+        report.commits_with_notes,
+        report.commits.len()
+    );
+    if report.commits.len() > 1 {
+        for commit in &report.commits {
+            println!(
+                "  {}  {:>5.1}%  {}/{} lines{}",
+                &commit.commit_sha[..commit.commit_sha.len().min(8)],
+                commit.coverage_pct,
+                commit.attributed_lines,
+                commit.added_lines,
+                if commit.has_note { "" } else { "  (no note)" }
+            );
+        }
+    } else if let Some(commit) = report.commits.first()
+        && commit.untracked_lines() > 0
+    {
+        println!("  {} untracked lines", commit.untracked_lines());
+    }
+
+    let missing = report.commits_missing_notes().count();
+    if missing > 0 {
+        eprintln!(
+            "Warning: {} commit(s) added lines without an authorship note; check that git-ai runs for every committer and agent.",
+            missing
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(added: u32, ai: u32, human: u32) -> CommitStats {
+        CommitStats {
+            git_diff_added_lines: added,
+            ai_additions: ai,
+            human_additions: human,
+            unknown_additions: added.saturating_sub(ai + human),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn coverage_counts_ai_and_known_human_lines() {
+        let covered = CommitCoverage::from_stats("a", &stats(10, 6, 2), true);
+        assert_eq!(covered.attributed_lines, 8);
+        assert_eq!(covered.untracked_lines(), 2);
+        assert_eq!(covered.coverage_pct, 80.0);
+
+        let empty = CommitCoverage::from_stats("b", &stats(0, 0, 0), false);
+        assert_eq!(empty.coverage_pct, 100.0);
+
+        let untracked = CommitCoverage::from_stats("c", &stats(30, 0, 0), false);
+        let report = CoverageReport::from_commits(vec![covered, empty, untracked]);
+        assert_eq!(report.added_lines, 40);
+        assert_eq!(report.attributed_lines, 8);
+        assert_eq!(report.coverage_pct, 20.0);
+        assert_eq!(report.commits_with_notes, 1);
+        let missing: Vec<&str> = report
+            .commits_missing_notes()
+            .map(|c| c.commit_sha.as_str())
+            .collect();
+        assert_eq!(missing, vec!["c"]);
+    }
+}
@@ -0,0 +1,255 @@
+//! Carrying attribution through formatter churn.
+//!
+//! Formatters and save hooks can rewrite a file right after an agent writes it,
+//! before the next checkpoint. That checkpoint is human, so without help the
+//! reformatted lines read as human edits. When the `formatter_detection`
+//! feature flag is on and a non-AI checkpoint's change to a file last written
+//! by an AI checkpoint is formatting-only, the previous attributions are mapped
+//! onto the reformatted text instead.
+//!
+//! A change is formatting-only when both versions agree once whitespace is
+//! dropped, quote styles are unified, and trailing commas and line-ending
+//! semicolons are ignored.
+//!
+//! Counting is a separate, line-level concern: with `stats --ignore-whitespace`
+//! (or `ignore_whitespace` in `.git-ai.toml`), [`drop_whitespace_only_changes`]
+//! removes formatter churn from diff hunks before lines are attributed.
+
+use crate::authorship::attribution_tracker::Attribution;
+use crate::commands::diff::DiffHunk;
+use crate::workspace_config::WhitespaceMode;
+use std::collections::{HashMap, VecDeque};
+
+/// Characters that survive formatting, with their byte offsets.
+fn significant_chars(content: &str) -> Vec<(usize, char)> {
+    let mut chars = Vec::new();
+    for (offset, ch) in content.char_indices() {
+        if ch.is_whitespace() {
+            continue;
+        }
+        let rest = &content[offset + ch.len_utf8()..];
+        match ch {
+            ',' if rest
+                .trim_start()
+                .chars()
+                .next()
+                .is_none_or(|next| matches!(next, ')' | ']' | '}')) =>
+            {
+                continue;
+            }
+            ';' if rest
+                .split('\n')
+                .next()
+                .is_some_and(|line| line.trim().is_empty()) =>
+            {
+                continue;
+            }
+            '\'' | '`' => chars.push((offset, '"')),
+            _ => chars.push((offset, ch)),
+        }
+    }
+    chars
+}
+
+/// Significant characters of both versions, when they agree.
+fn aligned_significant_chars(
+    old_content: &str,
+    new_content: &str,
+) -> Option<(Vec<(usize, char)>, Vec<(usize, char)>)> {
+    let old_chars = significant_chars(old_content);
+    let new_chars = significant_chars(new_content);
+    let matches = old_chars.len() == new_chars.len()
+        && old_chars
+            .iter()
+            .zip(&new_chars)
+            .all(|((_, old), (_, new))| old == new);
+    matches.then_some((old_chars, new_chars))
+}
+
+/// Whether `new_content` is `old_content` reformatted, with no other change.
+pub fn is_formatting_only_change(old_content: &str, new_content: &str) -> bool {
+    aligned_significant_chars(old_content, new_content).is_some()
+}
+
+/// Map `old_attributions` onto `new_content` character by character when the
+/// change is formatting-only. Returns `None` for any other change.
+pub fn carry_attributions_through_reformat(
+    old_content: &str,
+    new_content: &str,
+    old_attributions: &[Attribution],
+) -> Option<Vec<Attribution>> {
+    let (old_chars, new_chars) = aligned_significant_chars(old_content, new_content)?;
+
+    let mut sorted = old_attributions.to_vec();
+    sorted.sort_by_key(|attr| attr.start);
+
+    let mut carried: Vec<Attribution> = Vec::new();
+    let mut cursor = 0usize;
+    for ((old_offset, _), (new_offset, new_char)) in old_chars.iter().zip(&new_chars) {
+        while cursor < sorted.len() && sorted[cursor].end <= *old_offset {
+            cursor += 1;
+        }
+        let Some(attr) = sorted[cursor..]
+            .iter()
+            .take_while(|attr| attr.start <= *old_offset)
+            .find(|attr| attr.end > *old_offset)
+        else {
+            continue;
+        };
+
+        let end = new_offset + new_char.len_utf8();
+        match carried.last_mut() {
+            Some(last) if last.author_id == attr.author_id && last.ts == attr.ts => {
+                last.end = end;
+            }
+            _ => carried.push(Attribution::new(
+                *new_offset,
+                end,
+                attr.author_id.clone(),
+                attr.ts,
+            )),
+        }
+    }
+    Some(carried)
+}
+
+/// `line` with the whitespace `mode` ignores removed, or `None` for a blank line.
+fn whitespace_key(line: &str, mode: WhitespaceMode) -> Option<String> {
+    let body = line.trim_start();
+    if body.is_empty() {
+        return None;
+    }
+    let tokens: String = body.split_whitespace().collect();
+    Some(match mode {
+        WhitespaceMode::Reindent => tokens,
+        _ => format!("{}{}", &line[..line.len() - body.len()], tokens),
+    })
+}
+
+/// Drop blank lines and added/deleted line pairs that differ only in
+/// whitespace from each hunk, so formatter churn is neither added nor deleted
+/// lines. Pairs are matched within a hunk, in order.
+pub fn drop_whitespace_only_changes(hunks: &mut [DiffHunk], mode: WhitespaceMode) {
+    if mode == WhitespaceMode::Exact {
+        return;
+    }
+    for hunk in hunks.iter_mut() {
+        if hunk.added_contents.len() != hunk.added_lines.len()
+            || hunk.deleted_contents.len() != hunk.deleted_lines.len()
+        {
+            continue;
+        }
+
+        let mut keep_deleted = vec![false; hunk.deleted_lines.len()];
+        let mut deleted_by_key: HashMap<String, VecDeque<usize>> = HashMap::new();
+        for (idx, content) in hunk.deleted_contents.iter().enumerate() {
+            if let Some(key) = whitespace_key(content, mode) {
+                keep_deleted[idx] = true;
+                deleted_by_key.entry(key).or_default().push_back(idx);
+            }
+        }
+        let keep_added: Vec<bool> = hunk
+            .added_contents
+            .iter()
+            .map(|content| {
+                let Some(key) = whitespace_key(content, mode) else {
+                    return false;
+                };
+                match deleted_by_key.get_mut(&key).and_then(VecDeque::pop_front) {
+                    Some(idx) => {
+                        keep_deleted[idx] = false;
+                        false
+                    }
+                    None => true,
+                }
+            })
+            .collect();
+
+        retain_by_mask(&mut hunk.added_lines, &keep_added);
+        retain_by_mask(&mut hunk.added_contents, &keep_added);
+        retain_by_mask(&mut hunk.deleted_lines, &keep_deleted);
+        retain_by_mask(&mut hunk.deleted_contents, &keep_deleted);
+    }
+}
+
+fn retain_by_mask<T>(values: &mut Vec<T>, keep: &[bool]) {
+    let mut keep = keep.iter();
+    values.retain(|_| keep.next().copied().unwrap_or(true));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hunk(deleted: &[&str], added: &[&str]) -> DiffHunk {
+        DiffHunk {
+            file_path: "src/lib.rs".to_string(),
+            old_file_path: None,
+            old_start: 1,
+            old_count: deleted.len() as u32,
+            new_start: 1,
+            new_count: added.len() as u32,
+            deleted_lines: (1..=deleted.len() as u32).collect(),
+            added_lines: (1..=added.len() as u32).collect(),
+            deleted_contents: deleted.iter().map(|s| s.to_string()).collect(),
+            added_contents: added.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn whitespace_mode_drops_spacing_changes_but_keeps_reindents() {
+        let mut hunks = vec![hunk(
+            &["let a=1;  ", "  call(x)", "old()"],
+            &["let a = 1;", "    call(x)", "", "new()"],
+        )];
+        drop_whitespace_only_changes(&mut hunks, WhitespaceMode::Whitespace);
+        assert_eq!(hunks[0].added_contents, vec!["    call(x)", "new()"]);
+        assert_eq!(hunks[0].added_lines, vec![2, 4]);
+        assert_eq!(hunks[0].deleted_contents, vec!["  call(x)", "old()"]);
+        assert_eq!(hunks[0].deleted_lines, vec![2, 3]);
+    }
+
+    #[test]
+    fn reindent_mode_also_drops_indentation_changes() {
+        let mut hunks = vec![hunk(&["  call(x)", "  call(x)"], &["    call(x)"])];
+        drop_whitespace_only_changes(&mut hunks, WhitespaceMode::Reindent);
+        assert!(hunks[0].added_lines.is_empty());
+        assert_eq!(hunks[0].deleted_lines, vec![2]);
+
+        let mut exact = vec![hunk(&["a"], &["a "])];
+        drop_whitespace_only_changes(&mut exact, WhitespaceMode::Exact);
+        assert_eq!(exact[0].added_lines, vec![1]);
+    }
+
+    #[test]
+    fn formatter_style_changes_are_formatting_only() {
+        assert!(is_formatting_only_change(
+            "const a = 'x'\nconst b = [1, 2]\n",
+            "const a = \"x\";\nconst b = [\n  1,\n  2,\n];\n",
+        ));
+        assert!(is_formatting_only_change("f(a,b)", "f(\n  a,\n  b,\n)"));
+        assert!(!is_formatting_only_change("f(a, b)", "f(a)"));
+        assert!(!is_formatting_only_change("f(a, b)", "f(ab)"));
+        assert!(!is_formatting_only_change("a; b", "a b"));
+    }
+
+    #[test]
+    fn carries_authors_onto_reformatted_text() {
+        let old = "ai();\nhuman();\n";
+        let new = "ai()\n\nhuman()\n";
+        let attrs = vec![
+            Attribution::new(0, 6, "ai".to_string(), 1),
+            Attribution::new(6, 15, "human".to_string(), 2),
+        ];
+
+        let carried = carry_attributions_through_reformat(old, new, &attrs).unwrap();
+        assert_eq!(
+            carried,
+            vec![
+                Attribution::new(0, 4, "ai".to_string(), 1),
+                Attribution::new(6, 13, "human".to_string(), 2),
+            ]
+        );
+        assert!(carry_attributions_through_reformat(old, "ai();\n", &attrs).is_none());
+    }
+}
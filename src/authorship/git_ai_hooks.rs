@@ -37,6 +37,14 @@ pub fn post_notes_updated(repo: &Repository, notes: &[(String, String)]) {
         return;
     }
 
+    let _span = tracing::info_span!(
+        target: crate::observability::trace::targets::HOOKS,
+        "post_notes_updated",
+        notes = notes.len(),
+        commands = hook_commands.len()
+    )
+    .entered();
+
     let context = build_repo_hook_context(repo);
     let repo_url = context.repo_url;
     let repo_name = context.repo_name;
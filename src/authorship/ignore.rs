@@ -1,4 +1,5 @@
 use crate::git::repository::Repository;
+use crate::workspace_config::effective_workspace_config;
 use glob::Pattern;
 use std::collections::HashSet;
 use std::fs;
@@ -152,6 +153,56 @@ fn parse_linguist_generated_patterns(contents: &str) -> Vec<String> {
     dedupe_patterns(patterns)
 }
 
+/// Patterns `.gitattributes` excludes from git's end-of-line normalization
+/// (`-text` or `binary`). Line-ending changes in matching files are real edits.
+pub fn load_verbatim_eol_patterns_from_root_gitattributes(repo: &Repository) -> Vec<String> {
+    let Some(contents) = load_root_gitattributes_contents(repo) else {
+        return Vec::new();
+    };
+    parse_verbatim_eol_patterns(&contents)
+}
+
+/// Matcher for the files whose line endings git keeps verbatim; see
+/// [`load_verbatim_eol_patterns_from_root_gitattributes`].
+pub fn build_verbatim_eol_matcher(repo: &Repository) -> IgnoreMatcher {
+    build_ignore_matcher(&load_verbatim_eol_patterns_from_root_gitattributes(repo))
+}
+
+fn parse_verbatim_eol_patterns(contents: &str) -> Vec<String> {
+    let mut patterns = Vec::new();
+
+    for raw_line in contents.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let tokens = split_gitattributes_tokens(line);
+        if tokens.len() < 2 || tokens[0].starts_with("[attr]") {
+            continue;
+        }
+
+        // Later attributes on a line override earlier ones, as in git.
+        let mut verbatim: Option<bool> = None;
+        for attr in &tokens[1..] {
+            match attr.as_str() {
+                "-text" | "binary" => verbatim = Some(true),
+                "text" | "!text" => verbatim = Some(false),
+                _ if attr.starts_with("text=") || attr.starts_with("eol=") => {
+                    verbatim = Some(false)
+                }
+                _ => {}
+            }
+        }
+
+        if verbatim == Some(true) {
+            patterns.push(tokens[0].to_string());
+        }
+    }
+
+    dedupe_patterns(patterns)
+}
+
 fn load_root_gitattributes_contents(repo: &Repository) -> Option<String> {
     if repo.is_bare_repository().unwrap_or(false) {
         return repo
@@ -237,6 +288,7 @@ pub fn effective_ignore_patterns(
         repo,
     ));
     patterns.extend(load_git_ai_ignore_patterns(repo));
+    patterns.extend(effective_workspace_config(repo).exclude_paths);
     patterns.extend(extra_patterns.iter().cloned());
     patterns.extend(user_patterns.iter().cloned());
     dedupe_patterns(patterns)
@@ -471,4 +523,19 @@ mod tests {
         assert!(!should_ignore_file_with_matcher("widget.dart", &matcher));
         assert!(!should_ignore_file_with_matcher("Objective.m", &matcher));
     }
+
+    #[test]
+    fn verbatim_eol_patterns_follow_text_and_binary_attributes() {
+        let contents = "\
+*.bat -text
+*.png binary
+*.sh text eol=lf
+vendor/** -text text=auto
+# *.md -text
+";
+        assert_eq!(
+            parse_verbatim_eol_patterns(contents),
+            vec!["*.bat".to_string(), "*.png".to_string()]
+        );
+    }
 }
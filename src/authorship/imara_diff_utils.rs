@@ -3,9 +3,26 @@
 //! imara-diff matches git's diff output more closely than `similar`, which is important
 //! for accurate line attribution tracking.
 
+use crate::config::DiffAlgorithm;
 use imara_diff::{Algorithm, Diff, InternedInput, TokenSource};
 use std::hash::Hash;
 
+// ============================================================================
+// Algorithm selection
+// ============================================================================
+
+fn imara_algorithm(algorithm: DiffAlgorithm) -> Algorithm {
+    match algorithm {
+        DiffAlgorithm::Histogram => Algorithm::Histogram,
+        DiffAlgorithm::Myers => Algorithm::Myers,
+        DiffAlgorithm::MyersMinimal => Algorithm::MyersMinimal,
+    }
+}
+
+fn configured_algorithm() -> DiffAlgorithm {
+    crate::config::Config::get().diff_algorithm()
+}
+
 // ============================================================================
 // Byte-level diff types (replacing diff_match_patch_rs)
 // ============================================================================
@@ -130,8 +147,17 @@ impl<'a, T: Clone + Hash + Eq> TokenSource for SliceTokenSource<'a, T> {
 /// # Returns
 /// A vector of `DiffOp` representing the changes between old and new.
 pub fn capture_diff_slices<T: Hash + Eq + Clone>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    capture_diff_slices_with(configured_algorithm(), old, new)
+}
+
+/// [`capture_diff_slices`] with an explicit algorithm instead of the configured one.
+pub fn capture_diff_slices_with<T: Hash + Eq + Clone>(
+    algorithm: DiffAlgorithm,
+    old: &[T],
+    new: &[T],
+) -> Vec<DiffOp> {
     let input = InternedInput::new(SliceTokenSource::new(old), SliceTokenSource::new(new));
-    let diff = Diff::compute(Algorithm::Myers, &input);
+    let diff = Diff::compute(imara_algorithm(algorithm), &input);
     hunks_to_diff_ops(&diff, old.len(), new.len())
 }
 
@@ -167,7 +193,7 @@ impl<'a> LineChange<'a> {
 
 /// Computes line changes between two strings, similar to `TextDiff::iter_all_changes`.
 ///
-/// Uses imara-diff with Myers algorithm and git-like post-processing.
+/// Uses imara-diff with the configured [`DiffAlgorithm`] and git-like post-processing.
 ///
 /// # Arguments
 /// * `old` - The original string
@@ -176,6 +202,15 @@ impl<'a> LineChange<'a> {
 /// # Returns
 /// A vector of `LineChange` representing each line's change status.
 pub fn compute_line_changes<'a>(old: &'a str, new: &'a str) -> Vec<LineChange<'a>> {
+    compute_line_changes_with(configured_algorithm(), old, new)
+}
+
+/// [`compute_line_changes`] with an explicit algorithm instead of the configured one.
+pub fn compute_line_changes_with<'a>(
+    algorithm: DiffAlgorithm,
+    old: &'a str,
+    new: &'a str,
+) -> Vec<LineChange<'a>> {
     let old_lines: Vec<&str> = split_lines_with_terminators(old);
     let new_lines: Vec<&str> = split_lines_with_terminators(new);
 
@@ -186,7 +221,7 @@ pub fn compute_line_changes<'a>(old: &'a str, new: &'a str) -> Vec<LineChange<'a
     let new_norm = normalize_line_endings(new);
 
     let input = InternedInput::new(old_norm.as_ref(), new_norm.as_ref());
-    let mut diff = Diff::compute(Algorithm::Myers, &input);
+    let mut diff = Diff::compute(imara_algorithm(algorithm), &input);
     diff.postprocess_lines(&input);
 
     let mut changes = Vec::new();
@@ -692,4 +727,73 @@ mod tests {
             "CRLF and LF content should produce the same number of lines"
         );
     }
+
+    #[test]
+    fn test_histogram_keeps_inserted_function_whole() {
+        // Histogram anchors on the unique lines of `a`, so the new function
+        // lands as one insert instead of sharing `a`'s brace and blank line.
+        let old = vec!["fn a() {", "    one();", "}", ""];
+        let new = vec![
+            "fn b() {",
+            "    two();",
+            "}",
+            "",
+            "fn a() {",
+            "    one();",
+            "}",
+            "",
+        ];
+
+        let ops = capture_diff_slices_with(DiffAlgorithm::Histogram, &old, &new);
+        let inserts: Vec<(usize, usize)> = ops
+            .iter()
+            .filter_map(|op| match op {
+                DiffOp::Insert {
+                    new_index, new_len, ..
+                } => Some((*new_index, *new_len)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(inserts, vec![(0, 4)]);
+    }
+
+    fn inserted_line_numbers(algorithm: DiffAlgorithm, case: &str, ext: &str) -> Vec<usize> {
+        let dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/diff_algorithm")
+            .join(case);
+        let old = std::fs::read_to_string(dir.join(format!("before.{}", ext))).unwrap();
+        let new = std::fs::read_to_string(dir.join(format!("after.{}", ext))).unwrap();
+
+        let mut line = 0;
+        let mut inserted = Vec::new();
+        for change in compute_line_changes_with(algorithm, &old, &new) {
+            match change.tag() {
+                LineChangeTag::Insert => {
+                    line += 1;
+                    inserted.push(line);
+                }
+                LineChangeTag::Equal => line += 1,
+                LineChangeTag::Delete => {}
+            }
+        }
+        inserted
+    }
+
+    #[test]
+    fn test_histogram_attributes_fixture_blocks_whole() {
+        assert_eq!(
+            inserted_line_numbers(DiffAlgorithm::Histogram, "inserted_function", "rs"),
+            (8..=15).collect::<Vec<_>>()
+        );
+        // Swapping two functions moves one of them; histogram reports that
+        // function as the insert, while Myers interleaves lines of both.
+        assert_eq!(
+            inserted_line_numbers(DiffAlgorithm::Histogram, "swapped_functions", "c"),
+            (1..=7).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            inserted_line_numbers(DiffAlgorithm::Myers, "swapped_functions", "c"),
+            vec![1, 3, 5, 8, 10, 11, 13]
+        );
+    }
 }
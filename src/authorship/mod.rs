@@ -1,29 +1,47 @@
 pub mod agent_detection;
+pub mod ai_secret_scan;
+pub mod anonymize;
+pub mod attribution_comments;
 pub mod attribution_recovery;
 pub mod attribution_tracker;
 pub mod authorship_log;
 pub mod authorship_log_serialization;
+pub mod backfill;
 pub mod background_agent;
+pub mod clean_archive;
+pub mod commit_webhook;
 pub mod conflict_resolution;
+pub mod coverage;
 pub mod diff_ai_accepted;
 pub(crate) mod diff_base;
+pub mod format_detection;
 pub mod git_ai_hooks;
 pub mod hunk_shift;
 pub mod ignore;
 pub mod imara_diff_utils;
 pub mod internal_db;
 pub mod move_detection;
+pub mod note_resolution;
+pub mod notes_merge;
+pub mod paste_detection;
 pub mod post_commit;
 
 pub mod prompt_utils;
+pub mod quarantine;
 pub mod range_authorship;
+pub mod reconstruct;
 pub mod rewrite;
 pub mod rewrite_cherry_pick;
+pub mod rewrite_exec;
+pub mod rewrite_merges;
 pub mod rewrite_reset;
 pub mod rewrite_revert;
 pub mod rewrite_stash;
+pub mod rewrite_subtree;
 pub mod secrets;
 pub mod stats;
+pub mod stats_rollup;
+pub mod test_paths;
 pub mod transcript;
 pub mod virtual_attribution;
 pub mod working_log;
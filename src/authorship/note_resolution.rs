@@ -0,0 +1,247 @@
+//! Line-level conflicts between two versions of one commit's authorship note.
+//!
+//! The local note can disagree with the one fetched from a remote
+//! (`refs/notes/ai-remote/<remote>`) or with a reconstructed note. Lines only
+//! one version attests are not conflicts: [`merge_authorship_logs`] takes them
+//! from whichever side has them. Lines both versions attest to different
+//! authors are, and `git-ai resolve` settles them range by range.
+
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::authorship_log_serialization::{
+    AttestationEntry, AuthorshipLog, ResolutionRecord,
+};
+use crate::authorship::notes_merge::merge_authorship_logs;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// A run of lines in one file that the two versions attribute differently.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AttributionConflict {
+    pub file_path: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    /// Attestation hash in the local note.
+    pub ours: String,
+    /// Attestation hash in the other version.
+    pub theirs: String,
+}
+
+impl AttributionConflict {
+    pub fn line_count(&self) -> u32 {
+        self.end_line - self.start_line + 1
+    }
+
+    fn range(&self) -> LineRange {
+        if self.start_line == self.end_line {
+            LineRange::Single(self.start_line)
+        } else {
+            LineRange::Range(self.start_line, self.end_line)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolutionSide {
+    Ours,
+    Theirs,
+}
+
+/// Line -> attestation hash for `file_path`; later entries win, as in blame.
+fn line_authors<'a>(log: &'a AuthorshipLog, file_path: &str) -> BTreeMap<u32, &'a str> {
+    let mut authors = BTreeMap::new();
+    for attestation in log
+        .attestations
+        .iter()
+        .filter(|attestation| attestation.file_path == file_path)
+    {
+        for entry in &attestation.entries {
+            for range in &entry.line_ranges {
+                for line in range.expand() {
+                    authors.insert(line, entry.hash.as_str());
+                }
+            }
+        }
+    }
+    authors
+}
+
+/// Ranges both versions attest to different authors, by file then line.
+pub fn find_conflicts(ours: &AuthorshipLog, theirs: &AuthorshipLog) -> Vec<AttributionConflict> {
+    let mut files: Vec<&str> = ours
+        .attestations
+        .iter()
+        .map(|attestation| attestation.file_path.as_str())
+        .filter(|path| {
+            theirs
+                .attestations
+                .iter()
+                .any(|attestation| attestation.file_path == *path)
+        })
+        .collect();
+    files.sort_unstable();
+    files.dedup();
+
+    let mut conflicts: Vec<AttributionConflict> = Vec::new();
+    for file_path in files {
+        let their_authors = line_authors(theirs, file_path);
+        for (line, our_hash) in line_authors(ours, file_path) {
+            let Some(their_hash) = their_authors.get(&line).filter(|hash| **hash != our_hash)
+            else {
+                continue;
+            };
+            if let Some(last) = conflicts.last_mut()
+                && last.file_path == file_path
+                && last.end_line + 1 == line
+                && last.ours == our_hash
+                && last.theirs == *their_hash
+            {
+                last.end_line = line;
+                continue;
+            }
+            conflicts.push(AttributionConflict {
+                file_path: file_path.to_string(),
+                start_line: line,
+                end_line: line,
+                ours: our_hash.to_string(),
+                theirs: their_hash.to_string(),
+            });
+        }
+    }
+    conflicts
+}
+
+/// Human-readable author of an attestation hash: `tool/model` for AI, the
+/// author for known humans, the hash itself when the note has no record.
+pub fn describe_author(log: &AuthorshipLog, hash: &str) -> String {
+    let key = hash.split("::").next().unwrap_or(hash);
+    if let Some(prompt) = log.metadata.prompts.get(key) {
+        return format!("{}/{}", prompt.agent_id.tool, prompt.agent_id.model);
+    }
+    if let Some(session) = log.metadata.sessions.get(key) {
+        return format!("{}/{}", session.agent_id.tool, session.agent_id.model);
+    }
+    if let Some(human) = log.metadata.humans.get(key) {
+        return human.author.clone();
+    }
+    hash.to_string()
+}
+
+/// The union of both versions with each conflict settled by `picks` (one per
+/// conflict, missing picks keep ours), and a [`ResolutionRecord`] appended.
+pub fn resolve_conflicts(
+    ours: &AuthorshipLog,
+    theirs: &AuthorshipLog,
+    conflicts: &[AttributionConflict],
+    picks: &[ResolutionSide],
+    mut record: ResolutionRecord,
+) -> AuthorshipLog {
+    let mut resolved = ours.clone();
+    merge_authorship_logs(&mut resolved, theirs);
+
+    record.ours_lines = 0;
+    record.theirs_lines = 0;
+    for (index, conflict) in conflicts.iter().enumerate() {
+        if picks.get(index) != Some(&ResolutionSide::Theirs) {
+            record.ours_lines += conflict.line_count();
+            continue;
+        }
+        record.theirs_lines += conflict.line_count();
+
+        let range = conflict.range();
+        let file = resolved.get_or_create_file(&conflict.file_path);
+        for entry in &mut file.entries {
+            entry.line_ranges = entry
+                .line_ranges
+                .iter()
+                .flat_map(|existing| existing.remove(&range))
+                .collect();
+        }
+        match file
+            .entries
+            .iter_mut()
+            .find(|entry| entry.hash == conflict.theirs)
+        {
+            Some(entry) => {
+                entry.line_ranges.push(range);
+                entry.line_ranges.sort();
+            }
+            None => file
+                .entries
+                .push(AttestationEntry::new(conflict.theirs.clone(), vec![range])),
+        }
+        file.entries.retain(|entry| !entry.line_ranges.is_empty());
+    }
+
+    resolved.metadata.resolutions.push(record);
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(attestations: &str, prompt_keys: &[&str]) -> AuthorshipLog {
+        let prompts: Vec<String> = prompt_keys
+            .iter()
+            .map(|key| {
+                format!(
+                    r#""{}": {{"agent_id": {{"tool": "cursor", "id": "{}", "model": "gpt-5"}}, "human_author": null, "total_additions": 0, "total_deletions": 0, "accepted_lines": 0, "overriden_lines": 0}}"#,
+                    key, key
+                )
+            })
+            .collect();
+        AuthorshipLog::deserialize_from_string(&format!(
+            "{}---\n{{\"schema_version\": \"authorship/3.0.0\", \"base_commit_sha\": \"abc\", \"prompts\": {{{}}}}}",
+            attestations,
+            prompts.join(", ")
+        ))
+        .expect("note parses")
+    }
+
+    #[test]
+    fn test_conflicting_ranges_are_found_and_resolved_per_pick() {
+        let ours = note("src/lib.rs\n  aaaa1111 1-6\n", &["aaaa1111"]);
+        let theirs = note(
+            "src/lib.rs\n  aaaa1111 1-2\n  bbbb2222 3-4\n  cccc3333 5-8\n",
+            &["bbbb2222", "cccc3333"],
+        );
+
+        let conflicts = find_conflicts(&ours, &theirs);
+        let spans: Vec<(u32, u32, &str)> = conflicts
+            .iter()
+            .map(|conflict| {
+                (
+                    conflict.start_line,
+                    conflict.end_line,
+                    conflict.theirs.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(spans, vec![(3, 4, "bbbb2222"), (5, 6, "cccc3333")]);
+        assert_eq!(describe_author(&theirs, "bbbb2222"), "cursor/gpt-5");
+
+        let record = ResolutionRecord {
+            source: "refs/notes/ai-remote/origin".to_string(),
+            resolved_by: "Alice <alice@example.com>".to_string(),
+            timestamp: 1,
+            ours_lines: 0,
+            theirs_lines: 0,
+        };
+        let resolved = resolve_conflicts(
+            &ours,
+            &theirs,
+            &conflicts,
+            &[ResolutionSide::Theirs, ResolutionSide::Ours],
+            record,
+        );
+
+        let authors = line_authors(&resolved, "src/lib.rs");
+        assert_eq!(authors.get(&3), Some(&"bbbb2222"));
+        assert_eq!(authors.get(&5), Some(&"aaaa1111"));
+        // Lines only theirs attested come along from the union.
+        assert_eq!(authors.get(&8), Some(&"cccc3333"));
+        assert!(resolved.metadata.prompts.contains_key("bbbb2222"));
+        let record = resolved.metadata.resolutions.last().unwrap();
+        assert_eq!((record.ours_lines, record.theirs_lines), (2, 2));
+    }
+}
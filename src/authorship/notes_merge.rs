@@ -0,0 +1,181 @@
+//! Semantic merge of two authorship notes written for the same commit.
+//!
+//! Concurrent writers (a local rewrite and a teammate's push, or two CI jobs)
+//! can leave different notes on one commit. Instead of keeping one blob
+//! wholesale, the notes are unioned: every line attested by ours stays as is,
+//! theirs fills in the lines ours does not cover, and the prompt, session and
+//! human records of both sides are kept.
+
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+
+/// Merge `theirs` into `ours`. A side that does not parse is dropped; when
+/// neither parses, `ours` is returned unchanged.
+pub fn merge_note_contents(ours: &str, theirs: &str) -> String {
+    if ours == theirs {
+        return ours.to_string();
+    }
+    let ours_log = AuthorshipLog::deserialize_from_string(ours);
+    let theirs_log = AuthorshipLog::deserialize_from_string(theirs);
+    match (ours_log, theirs_log) {
+        (Ok(mut ours_log), Ok(theirs_log)) => {
+            merge_authorship_logs(&mut ours_log, &theirs_log);
+            ours_log
+                .serialize_to_string()
+                .unwrap_or_else(|_| ours.to_string())
+        }
+        (Err(_), Ok(_)) => theirs.to_string(),
+        _ => ours.to_string(),
+    }
+}
+
+/// Union `theirs` into `ours`, with ours winning on lines both sides attest.
+pub fn merge_authorship_logs(ours: &mut AuthorshipLog, theirs: &AuthorshipLog) {
+    for their_file in &theirs.attestations {
+        let claimed: Vec<LineRange> = ours
+            .attestations
+            .iter()
+            .filter(|file| file.file_path == their_file.file_path)
+            .flat_map(|file| file.entries.iter())
+            .flat_map(|entry| entry.line_ranges.iter().cloned())
+            .collect();
+
+        let mut additions = Vec::new();
+        for their_entry in &their_file.entries {
+            let unclaimed = subtract_ranges(&their_entry.line_ranges, &claimed);
+            if !unclaimed.is_empty() {
+                additions.push((their_entry.hash.clone(), unclaimed));
+            }
+        }
+        if additions.is_empty() {
+            continue;
+        }
+
+        let our_file = ours.get_or_create_file(&their_file.file_path);
+        for (hash, ranges) in additions {
+            match our_file.entries.iter_mut().find(|entry| entry.hash == hash) {
+                Some(entry) => {
+                    entry.line_ranges.extend(ranges);
+                    entry.line_ranges.sort();
+                }
+                None => {
+                    let mut entry = their_file
+                        .entries
+                        .iter()
+                        .find(|entry| entry.hash == hash)
+                        .cloned()
+                        .expect("addition comes from an existing entry");
+                    entry.line_ranges = ranges;
+                    our_file.entries.push(entry);
+                }
+            }
+        }
+    }
+
+    for (key, record) in &theirs.metadata.prompts {
+        ours.metadata
+            .prompts
+            .entry(key.clone())
+            .or_insert_with(|| record.clone());
+    }
+    for (key, record) in &theirs.metadata.sessions {
+        ours.metadata
+            .sessions
+            .entry(key.clone())
+            .or_insert_with(|| record.clone());
+    }
+    for (key, record) in &theirs.metadata.humans {
+        ours.metadata
+            .humans
+            .entry(key.clone())
+            .or_insert_with(|| record.clone());
+    }
+    for record in &theirs.metadata.resolutions {
+        if !ours.metadata.resolutions.contains(record) {
+            ours.metadata.resolutions.push(record.clone());
+        }
+    }
+    for record in &theirs.metadata.deletions {
+        if !ours
+            .metadata
+            .deletions
+            .iter()
+            .any(|existing| existing.file_path == record.file_path)
+        {
+            ours.metadata.deletions.push(record.clone());
+        }
+    }
+}
+
+fn subtract_ranges(ranges: &[LineRange], claimed: &[LineRange]) -> Vec<LineRange> {
+    let mut remaining = ranges.to_vec();
+    for claim in claimed {
+        remaining = remaining
+            .iter()
+            .flat_map(|range| range.remove(claim))
+            .collect();
+        if remaining.is_empty() {
+            break;
+        }
+    }
+    remaining
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(attestations: &str, prompt_keys: &[&str]) -> String {
+        let prompts: Vec<String> = prompt_keys
+            .iter()
+            .map(|key| {
+                format!(
+                    r#""{}": {{"agent_id": {{"tool": "cursor", "id": "{}", "model": "gpt-5"}}, "human_author": null, "total_additions": 0, "total_deletions": 0, "accepted_lines": 0, "overriden_lines": 0}}"#,
+                    key, key
+                )
+            })
+            .collect();
+        format!(
+            "{}---\n{{\"schema_version\": \"authorship/3.0.0\", \"base_commit_sha\": \"abc\", \"prompts\": {{{}}}}}",
+            attestations,
+            prompts.join(", ")
+        )
+    }
+
+    #[test]
+    fn test_merge_unions_disjoint_lines_and_metadata() {
+        let ours = note("src/lib.rs\n  aaaa1111 1-5\n", &["aaaa1111"]);
+        let theirs = note(
+            "src/lib.rs\n  bbbb2222 4-8\nsrc/new.rs\n  bbbb2222 1\n",
+            &["bbbb2222"],
+        );
+
+        let merged = AuthorshipLog::deserialize_from_string(&merge_note_contents(&ours, &theirs))
+            .expect("merged note parses");
+        let lib = merged
+            .attestations
+            .iter()
+            .find(|file| file.file_path == "src/lib.rs")
+            .unwrap();
+        assert_eq!(lib.entries[0].hash, "aaaa1111");
+        assert_eq!(lib.entries[0].line_ranges, vec![LineRange::Range(1, 5)]);
+        assert_eq!(lib.entries[1].hash, "bbbb2222");
+        assert_eq!(lib.entries[1].line_ranges, vec![LineRange::Range(6, 8)]);
+        assert!(
+            merged
+                .attestations
+                .iter()
+                .any(|file| file.file_path == "src/new.rs")
+        );
+        assert!(merged.metadata.prompts.contains_key("aaaa1111"));
+        assert!(merged.metadata.prompts.contains_key("bbbb2222"));
+    }
+
+    #[test]
+    fn test_merge_keeps_parseable_side() {
+        let ours = note("src/lib.rs\n  aaaa1111 1\n", &["aaaa1111"]);
+        assert_eq!(merge_note_contents(&ours, "garbage"), ours);
+        assert_eq!(merge_note_contents("garbage", &ours), ours);
+        assert_eq!(merge_note_contents("garbage", "junk"), "garbage");
+    }
+}
@@ -0,0 +1,84 @@
+//! Opt-in heuristic for AI content pasted from outside any tracked tool.
+//!
+//! When the `ai_paste_detection` feature flag is on, a human (untracked)
+//! checkpoint whose change to a file is one large contiguous insertion, with no
+//! surrounding smaller edits that would suggest typing, is reported in the
+//! checkpoint metric with kind `ai_pasted` and a confidence value. This only
+//! feeds usage estimates: the working log and authorship notes still record
+//! those lines as human, since a heuristic is not evidence of AI authorship.
+
+/// Checkpoint metric kind for insertions classified as probable AI pastes.
+pub const AI_PASTED_KIND: &str = "ai_pasted";
+
+/// A single insertion must be longer than this to count as a paste.
+pub const PASTE_LINE_THRESHOLD: u32 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasteConfidence {
+    /// The burst dominates the change, but other small edits came with it.
+    Medium,
+    /// The change is exactly one pure insertion of at least twice the threshold.
+    High,
+}
+
+impl PasteConfidence {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PasteConfidence::Medium => "medium",
+            PasteConfidence::High => "high",
+        }
+    }
+}
+
+/// Classify one file's change in a human checkpoint.
+///
+/// `largest_insertion` is the longest run of contiguous inserted lines, while
+/// `additions` and `deletions` cover the whole change. Returns `None` unless the
+/// insertion exceeds [`PASTE_LINE_THRESHOLD`], accounts for at least 90% of the
+/// added lines, and deletions stay under 10% of them (a rewrite, not a paste).
+pub fn classify_paste(
+    largest_insertion: u32,
+    additions: u32,
+    deletions: u32,
+) -> Option<PasteConfidence> {
+    if largest_insertion <= PASTE_LINE_THRESHOLD {
+        return None;
+    }
+    let additions = u64::from(additions.max(largest_insertion));
+    let burst = u64::from(largest_insertion);
+    if burst * 10 < additions * 9 || u64::from(deletions) * 10 > additions {
+        return None;
+    }
+
+    if deletions == 0
+        && burst == additions
+        && largest_insertion >= PASTE_LINE_THRESHOLD.saturating_mul(2)
+    {
+        Some(PasteConfidence::High)
+    } else {
+        Some(PasteConfidence::Medium)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_or_typed_changes_are_not_pastes() {
+        assert_eq!(classify_paste(50, 50, 0), None);
+        assert_eq!(classify_paste(10, 80, 0), None);
+        // One 60-line block among 80 added lines looks like editing, not a paste.
+        assert_eq!(classify_paste(60, 80, 0), None);
+        // Replacing most of a file is a rewrite.
+        assert_eq!(classify_paste(60, 60, 40), None);
+    }
+
+    #[test]
+    fn test_single_burst_insertions_are_classified() {
+        assert_eq!(classify_paste(51, 51, 0), Some(PasteConfidence::Medium));
+        assert_eq!(classify_paste(95, 100, 3), Some(PasteConfidence::Medium));
+        assert_eq!(classify_paste(100, 100, 0), Some(PasteConfidence::High));
+        assert_eq!(PasteConfidence::High.as_str(), "high");
+    }
+}
@@ -1,8 +1,11 @@
 use crate::authorship::attribution_recovery::{
     AttributionRecoveryContext, FileTimestampsByPath, UnknownLinesByFile,
 };
-use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::authorship_log_serialization::{
+    AuthorshipLog, CheckpointLabelRecord, FileDeletionRecord,
+};
 use crate::authorship::diff_base::single_commit_diff_base;
+use crate::authorship::format_detection::drop_whitespace_only_changes;
 use crate::authorship::ignore::{
     build_ignore_matcher, effective_ignore_patterns, should_ignore_file_with_matcher,
 };
@@ -12,8 +15,10 @@ use crate::authorship::virtual_attribution::{AuthorshipLogDiffContext, VirtualAt
 use crate::authorship::working_log::{Checkpoint, CheckpointKind, WorkingLogEntry};
 use crate::config::Config;
 use crate::error::GitAiError;
-use crate::git::notes_api::write_note;
+use crate::git::notes_api::write_note_journaled;
+use crate::git::repo_storage::PersistedWorkingLog;
 use crate::git::repository::{Repository, batch_read_paths_at_treeishes, exec_git};
+use crate::workspace_config::{WhitespaceMode, effective_workspace_config};
 use std::collections::{HashMap, HashSet};
 use std::io::IsTerminal;
 
@@ -93,7 +98,7 @@ pub(crate) fn post_commit_from_working_log_with_recovery_timestamps(
     base_commit: Option<String>,
     commit_sha: String,
     human_author: String,
-    supress_output: bool,
+    options: PostCommitOptions,
     recovery_file_timestamps: Option<&FileTimestampsByPath>,
     before_external_recovery: Option<&dyn Fn(&UnknownLinesByFile)>,
 ) -> Result<(String, AuthorshipLog), GitAiError> {
@@ -102,11 +107,7 @@ pub(crate) fn post_commit_from_working_log_with_recovery_timestamps(
         base_commit,
         commit_sha,
         human_author,
-        PostCommitOptions {
-            supress_output,
-            compute_stats: true,
-            recover_attribution: true,
-        },
+        options,
         PostCommitContext {
             precomputed_parent_diff: None,
             recovery_file_timestamps,
@@ -382,6 +383,39 @@ where
         }
     }
 
+    authorship_log.metadata.labels = checkpoint_label_records(&parent_working_log);
+
+    let mut working_log_files: HashSet<String> = parent_working_log
+        .iter()
+        .flat_map(|checkpoint| checkpoint.entries.iter().map(|entry| entry.file.clone()))
+        .collect();
+    working_log_files.extend(initial_attributions_for_pathspecs.files.keys().cloned());
+    authorship_log.metadata.deletions = file_deletion_records(
+        repo,
+        &parent_sha,
+        &commit_sha,
+        &working_log_files,
+        &parent_working_log,
+        &working_log,
+        &human_author,
+    )
+    .unwrap_or_else(|e| {
+        tracing::debug!("Failed to record file deletions for {}: {}", commit_sha, e);
+        Vec::new()
+    });
+
+    if let Err(e) = crate::authorship::conflict_resolution::fold_merge_tool_resolutions(
+        repo,
+        &commit_sha,
+        &mut authorship_log,
+    ) {
+        tracing::debug!(
+            "Failed to fold merge tool resolutions into {}: {}",
+            commit_sha,
+            e
+        );
+    }
+
     authorship_log = transform(authorship_log)?;
     authorship_log.metadata.base_commit_sha = commit_sha.clone();
 
@@ -427,7 +461,7 @@ where
         .serialize_to_string()
         .map_err(|_| GitAiError::Generic("Failed to serialize authorship log".to_string()))?;
 
-    write_note(repo, &commit_sha, &authorship_note_str)?;
+    write_note_journaled(repo, &commit_sha, &authorship_note_str)?;
 
     // Compute stats once (needed for both metrics and terminal output), unless preflight
     // estimate predicts this would be too expensive for the commit hook path.
@@ -462,13 +496,28 @@ where
                 &commit_sha,
             )?;
 
-            let computed = stats_for_commit_stats_from_hunks(
-                repo,
-                &commit_sha,
-                &ignore_patterns,
-                &diff_hunks,
-                Some(&authorship_log),
-            )?;
+            // The diff artifacts keep every hunk; only the counts leave out
+            // the whitespace-only changes the workspace ignores.
+            let whitespace = effective_workspace_config(repo).whitespace_mode();
+            let computed = if whitespace == WhitespaceMode::Exact {
+                stats_for_commit_stats_from_hunks(
+                    repo,
+                    &commit_sha,
+                    &ignore_patterns,
+                    &diff_hunks,
+                    Some(&authorship_log),
+                )?
+            } else {
+                let mut stats_hunks = diff_hunks.clone();
+                drop_whitespace_only_changes(&mut stats_hunks, whitespace);
+                stats_for_commit_stats_from_hunks(
+                    repo,
+                    &commit_sha,
+                    &ignore_patterns,
+                    &stats_hunks,
+                    Some(&authorship_log),
+                )?
+            };
 
             let hunks_json = crate::commands::diff::build_diff_artifacts_from_hunks(
                 repo,
@@ -492,10 +541,25 @@ where
                 &parent_working_log,
                 hunks_json.as_deref(),
             );
+            crate::authorship::stats_rollup::record_commit_rollup(
+                repo,
+                &commit_sha,
+                &ignore_patterns,
+                whitespace,
+                &computed,
+            );
             stats = Some(computed);
         }
     }
 
+    crate::authorship::commit_webhook::emit_committed(
+        repo,
+        &commit_sha,
+        &parent_sha,
+        &human_author,
+        stats.as_ref(),
+    );
+
     if options.compute_stats && skip_reason.is_some() {
         match skip_reason.as_ref() {
             Some(StatsSkipReason::MergeCommit) => {
@@ -565,6 +629,123 @@ where
     })
 }
 
+/// Labels of the `--label`ed checkpoints among `checkpoints`, oldest first.
+pub(crate) fn checkpoint_label_records(checkpoints: &[Checkpoint]) -> Vec<CheckpointLabelRecord> {
+    let mut records: Vec<CheckpointLabelRecord> = checkpoints
+        .iter()
+        .filter_map(|checkpoint| {
+            Some(CheckpointLabelRecord {
+                label: checkpoint.label.clone()?,
+                timestamp: checkpoint.timestamp,
+                author: checkpoint
+                    .agent_id
+                    .as_ref()
+                    .map(|agent| format!("{}/{}", agent.tool, agent.model))
+                    .unwrap_or_else(|| checkpoint.author.clone()),
+                additions: checkpoint.line_stats.additions,
+                deletions: checkpoint.line_stats.deletions,
+            })
+        })
+        .collect();
+    records.sort_by_key(|record| record.timestamp);
+    records
+}
+
+/// Working-log files that `commit_sha` deleted, each credited to the
+/// checkpoint that saw it gone, or to `human_author` when no checkpoint did
+/// (e.g. `rm` followed by `git commit -a`).
+pub(crate) fn file_deletion_records(
+    repo: &Repository,
+    parent_sha: &str,
+    commit_sha: &str,
+    file_paths: &HashSet<String>,
+    checkpoints: &[Checkpoint],
+    working_log: &PersistedWorkingLog,
+    human_author: &str,
+) -> Result<Vec<FileDeletionRecord>, GitAiError> {
+    if parent_sha == "initial" || file_paths.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut deleted = deleted_files_in_commit(
+        repo,
+        &single_commit_diff_base(parent_sha, commit_sha),
+        commit_sha,
+    )?;
+    deleted.retain(|(file_path, _)| file_paths.contains(file_path));
+    deleted.sort();
+
+    Ok(deleted
+        .into_iter()
+        .map(|(file_path, lines)| {
+            let deleted_by = checkpoints
+                .iter()
+                .rev()
+                .find_map(|checkpoint| {
+                    let entry = checkpoint
+                        .entries
+                        .iter()
+                        .find(|entry| entry.file == file_path)?;
+                    Some((checkpoint, entry))
+                })
+                .filter(|(_, entry)| {
+                    entry.blob_sha.is_empty()
+                        || working_log
+                            .get_file_version(&entry.blob_sha)
+                            .is_ok_and(|content| content.is_empty())
+                })
+                .map(|(checkpoint, _)| {
+                    checkpoint
+                        .agent_id
+                        .as_ref()
+                        .map(|agent| format!("{}/{}", agent.tool, agent.model))
+                        .unwrap_or_else(|| checkpoint.author.clone())
+                });
+            FileDeletionRecord {
+                file_path,
+                author: deleted_by.unwrap_or_else(|| human_author.to_string()),
+                lines,
+            }
+        })
+        .collect())
+}
+
+/// Files deleted between `diff_base` and `commit_sha` with their deleted line
+/// counts, from one `git diff --numstat`. Rename detection pairs a moved file's
+/// old path with its new one, so only files that are really gone are listed.
+fn deleted_files_in_commit(
+    repo: &Repository,
+    diff_base: &str,
+    commit_sha: &str,
+) -> Result<Vec<(String, u32)>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend(
+        [
+            "diff",
+            "--numstat",
+            "-z",
+            "-M",
+            "--diff-filter=D",
+            "--no-relative",
+            diff_base,
+            commit_sha,
+        ]
+        .map(String::from),
+    );
+    let output = exec_git(&args)?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter_map(|record| {
+            let mut fields = record.splitn(3, '\t');
+            let _added = fields.next()?;
+            // Binary files report `-` instead of a line count.
+            let lines = fields.next()?.parse().unwrap_or(0);
+            let file_path = fields.next().filter(|path| !path.is_empty())?;
+            Some((file_path.to_string(), lines))
+        })
+        .collect())
+}
+
 fn commit_tree_snapshot_for_files(
     repo: &Repository,
     commit_sha: &str,
@@ -617,6 +798,39 @@ fn recovery_committed_hunks(
         .collect())
 }
 
+/// Run the attribution recovery that a degraded post-commit skipped (see
+/// [`crate::daemon::hook_budget`]) against the note already written for
+/// `commit_sha`, and rewrite the note.
+pub(crate) fn recover_attribution_for_commit(
+    repo: &Repository,
+    commit_sha: &str,
+) -> Result<(), GitAiError> {
+    let commit = repo.find_commit(commit_sha.to_string())?;
+    let parent_sha = if commit.parent_count()? > 0 {
+        commit.parent(0)?.id()
+    } else {
+        "initial".to_string()
+    };
+    let mut authorship_log = crate::git::notes_api::read_authorship_v3(repo, commit_sha)?;
+    let human_author = repo.effective_author_identity().formatted_or_unknown();
+    let recovery_hunks = recovery_committed_hunks(repo, &parent_sha, commit_sha, None)?;
+    crate::authorship::attribution_recovery::recover_attribution(
+        repo,
+        &parent_sha,
+        commit_sha,
+        &human_author,
+        &mut authorship_log,
+        &recovery_hunks,
+        AttributionRecoveryContext::default(),
+    )?;
+    authorship_log.metadata.base_commit_sha = commit_sha.to_string();
+
+    let authorship_note_str = authorship_log
+        .serialize_to_string()
+        .map_err(|_| GitAiError::Generic("Failed to serialize authorship log".to_string()))?;
+    write_note_journaled(repo, commit_sha, &authorship_note_str)
+}
+
 /// Amend-specific post-commit that merges blame-sourced attributions from the
 /// original commit with persisted working-log checkpoint data.
 pub fn post_commit_amend(
@@ -676,10 +890,12 @@ pub(crate) fn post_commit_amend_with_recovery_timestamps_detailed(
     let changed_files = repo.list_commit_files(amended_commit, None)?;
     let mut pathspecs: HashSet<String> = changed_files.into_iter().collect();
     let touched_files = working_log.all_touched_files()?;
+    let mut working_log_files = touched_files.clone();
     pathspecs.extend(touched_files);
     let initial_attributions_for_pathspecs = working_log.read_initial_attributions();
     for file_path in initial_attributions_for_pathspecs.files.keys() {
         pathspecs.insert(file_path.clone());
+        working_log_files.insert(file_path.clone());
     }
     let pathspecs_vec: Vec<String> = pathspecs.iter().cloned().collect();
     let observed_snapshot = working_log.observed_file_snapshot()?;
@@ -784,6 +1000,8 @@ pub(crate) fn post_commit_amend_with_recovery_timestamps_detailed(
     )?;
     authorship_log.metadata.base_commit_sha = amended_commit.to_string();
 
+    let mut original_deletions: Vec<FileDeletionRecord> = Vec::new();
+
     // Preserve human/session metadata from the original commit's note. Read
     // through notes_api so the HTTP notes backend is honored — with refs-only
     // reads the amended note keeps its s_/h_ attestation hashes but silently
@@ -816,6 +1034,46 @@ pub(crate) fn post_commit_amend_with_recovery_timestamps_detailed(
                 authorship_log.metadata.sessions.entry(id).or_insert(record);
             }
         }
+        authorship_log.metadata.labels = original_log.metadata.labels;
+        original_deletions = original_log.metadata.deletions;
+    }
+    let checkpoints = working_log.read_all_checkpoints()?;
+    authorship_log
+        .metadata
+        .labels
+        .extend(checkpoint_label_records(&checkpoints));
+
+    // Deletions the original commit recorded keep their author if the amended
+    // commit still deletes the file.
+    working_log_files.extend(
+        original_deletions
+            .iter()
+            .map(|record| record.file_path.clone()),
+    );
+    authorship_log.metadata.deletions = file_deletion_records(
+        repo,
+        &parent_sha,
+        amended_commit,
+        &working_log_files,
+        &checkpoints,
+        &working_log,
+        &human_author,
+    )
+    .unwrap_or_else(|e| {
+        tracing::debug!(
+            "Failed to record file deletions for {}: {}",
+            amended_commit,
+            e
+        );
+        Vec::new()
+    });
+    for record in &mut authorship_log.metadata.deletions {
+        if let Some(original) = original_deletions
+            .iter()
+            .find(|original| original.file_path == record.file_path)
+        {
+            record.author = original.author.clone();
+        }
     }
 
     // Inject custom attributes
@@ -832,7 +1090,7 @@ pub(crate) fn post_commit_amend_with_recovery_timestamps_detailed(
     let authorship_note_str = authorship_log
         .serialize_to_string()
         .map_err(|_| GitAiError::Generic("Failed to serialize authorship log".to_string()))?;
-    write_note(repo, amended_commit, &authorship_note_str)?;
+    write_note_journaled(repo, amended_commit, &authorship_note_str)?;
 
     // Write INITIAL file for uncommitted attributions
     if !initial_attributions.files.is_empty() {
@@ -1094,12 +1352,8 @@ pub(crate) fn commit_metric_attrs(
         .commit_sha(commit_sha)
         .base_commit_sha(parent_sha);
 
-    if let Ok(Some(remote_name)) = repo.get_default_remote()
-        && let Ok(remotes) = repo.remotes_with_urls()
-        && let Some((_, url)) = remotes.into_iter().find(|(n, _)| n == &remote_name)
-        && let Ok(normalized) = crate::repo_url::normalize_repo_url(&url)
-    {
-        attrs = attrs.repo_url(normalized);
+    if let Some(repo_url) = crate::repo_url::resolve_repo_url_from_repo(repo) {
+        attrs = attrs.repo_url(repo_url);
     }
 
     if let Ok(head_ref) = repo.head()
@@ -1185,6 +1439,48 @@ fn record_commit_metrics(
 mod tests {
     use super::*;
 
+    #[test]
+    fn checkpoint_label_records_keep_labelled_checkpoints_oldest_first() {
+        let checkpoint = |kind, timestamp, label: Option<&str>| {
+            let mut checkpoint =
+                Checkpoint::new(kind, String::new(), "Alice".to_string(), Vec::new());
+            checkpoint.timestamp = timestamp;
+            checkpoint.label = label.map(str::to_string);
+            checkpoint.line_stats.additions = 4;
+            checkpoint
+        };
+        let mut ai = checkpoint(CheckpointKind::AiAgent, 20, Some("after refactor"));
+        ai.agent_id = Some(crate::authorship::working_log::AgentId {
+            tool: "claude".to_string(),
+            id: "session".to_string(),
+            model: "sonnet".to_string(),
+        });
+        let records = checkpoint_label_records(&[
+            ai,
+            checkpoint(CheckpointKind::Human, 30, None),
+            checkpoint(CheckpointKind::Human, 10, Some("spike")),
+        ]);
+
+        let summary: Vec<(&str, u64, &str)> = records
+            .iter()
+            .map(|record| {
+                (
+                    record.label.as_str(),
+                    record.timestamp,
+                    record.author.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("spike", 10, "Alice"),
+                ("after refactor", 20, "claude/sonnet")
+            ]
+        );
+        assert_eq!(records[0].additions, 4);
+    }
+
     #[test]
     fn parse_commit_metric_metadata_output_reads_subject_body_and_timestamps() {
         let metadata = parse_commit_metric_metadata_output(concat!(
@@ -0,0 +1,737 @@
+//! Quarantine for attribution orphaned by upstream force-pushes.
+//!
+//! After a fetch or pull, the remote-tracking reflogs are scanned for git's own
+//! `forced-update` records. Commits that dropped out of a force-pushed ref and
+//! are no longer reachable from any local branch have their authorship notes,
+//! stable patch-ids and added-line fingerprints copied into
+//! `.git/ai/quarantine/<id>/`, together with any working logs based on them.
+//!
+//! `git-ai reconcile` maps quarantined commits onto the ref's new history: first
+//! by identical stable patch-id, then by the overlap of added lines for commits
+//! whose patch changed during the rewrite. Matched commits get their notes
+//! shifted onto the new commits and their working logs moved over.
+
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::rewrite::{find_merge_base, list_commits_in_range, shift_authorship_notes};
+use crate::authorship::rewrite_cherry_pick::stable_patch_ids_for_commits;
+use crate::error::GitAiError;
+use crate::git::notes_api;
+use crate::git::repo_storage::copy_dir_contents;
+use crate::git::repository::{Repository, exec_git, exec_git_allow_nonzero, exec_git_stdin};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const QUARANTINE_DIR: &str = "quarantine";
+const MANIFEST_FILE: &str = "manifest.json";
+const PROCESSED_FILE: &str = "processed";
+
+/// Forced updates older than this are history, not the fetch that just ran.
+const FORCED_UPDATE_LOOKBACK_SECS: i64 = 60 * 60;
+/// Upper bound on commits quarantined for one forced update.
+const MAX_QUARANTINED_COMMITS: usize = 1000;
+/// Candidates considered on the new side when the old tip can no longer be
+/// merge-based against it.
+const MAX_RECONCILE_CANDIDATES: usize = 500;
+/// Added lines fingerprinted per commit.
+const MAX_FINGERPRINT_LINES: usize = 4000;
+/// Minimum added-line overlap (Jaccard) for a similarity match.
+pub const RECONCILE_SIMILARITY_THRESHOLD: f64 = 0.5;
+
+/// One forced update of a remote-tracking ref, with what it orphaned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantineEntry {
+    pub id: String,
+    pub reference: String,
+    pub old_tip: String,
+    pub new_tip: String,
+    pub created_at: u64,
+    pub commits: Vec<QuarantinedCommit>,
+    /// Base commits whose working logs were snapshotted into the entry.
+    #[serde(default)]
+    pub working_logs: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantinedCommit {
+    pub sha: String,
+    pub patch_id: Option<String>,
+    /// Sorted hashes of the commit's added lines, for similarity matching.
+    #[serde(default)]
+    pub added_lines: Vec<u64>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconcileMatch {
+    PatchId,
+    Similarity,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReconciledCommit {
+    pub old_sha: String,
+    pub new_sha: String,
+    pub matched_by: ReconcileMatch,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ReconcileReport {
+    pub entries: usize,
+    pub reconciled: Vec<ReconciledCommit>,
+    /// Quarantined commits with no counterpart in the new history.
+    pub unmatched: Vec<String>,
+    pub working_logs_restored: usize,
+}
+
+fn quarantine_dir(repo: &Repository) -> PathBuf {
+    repo.storage.ai_dir.join(QUARANTINE_DIR)
+}
+
+/// Quarantine attribution for every recent forced update of a remote-tracking
+/// ref. Returns the entries created; forced updates already handled are skipped.
+pub fn quarantine_forced_remote_updates(
+    repo: &Repository,
+) -> Result<Vec<QuarantineEntry>, GitAiError> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let updates = forced_remote_updates(repo.common_dir(), now as i64);
+    if updates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let dir = quarantine_dir(repo);
+    let mut processed = read_processed(&dir);
+    let mut created = Vec::new();
+    for update in updates {
+        let id = entry_id(&update.reference, &update.old, &update.new);
+        if processed.contains(&id) {
+            continue;
+        }
+        match quarantine_update(repo, &dir, id.clone(), update, now)? {
+            QuarantineOutcome::Quarantined(entry) => {
+                tracing::info!(
+                    reference = %entry.reference,
+                    commits = entry.commits.len(),
+                    working_logs = entry.working_logs.len(),
+                    "quarantined attribution from force-pushed ref"
+                );
+                created.push(entry);
+                processed.insert(id);
+            }
+            QuarantineOutcome::NothingToQuarantine => {
+                processed.insert(id);
+            }
+            // Revisited on a later fetch, once the local branch is gone.
+            QuarantineOutcome::HeldByLocalBranch => {}
+        }
+    }
+    write_processed(&dir, &processed)?;
+    Ok(created)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ForcedUpdate {
+    reference: String,
+    old: String,
+    new: String,
+}
+
+/// `forced-update` records from the remote-tracking reflogs written within the
+/// lookback window.
+fn forced_remote_updates(common_dir: &Path, now: i64) -> Vec<ForcedUpdate> {
+    let logs_root = common_dir.join("logs");
+    let mut reflogs = Vec::new();
+    collect_files(&logs_root.join("refs").join("remotes"), &mut reflogs);
+
+    let mut updates = Vec::new();
+    for path in reflogs {
+        let Ok(reference) = path.strip_prefix(&logs_root) else {
+            continue;
+        };
+        let reference = reference.to_string_lossy().replace('\\', "/");
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        for line in content.lines() {
+            let Some((old, new, timestamp, message)) = parse_reflog_line(line) else {
+                continue;
+            };
+            if message.ends_with(": forced-update")
+                && now.saturating_sub(timestamp) <= FORCED_UPDATE_LOOKBACK_SECS
+            {
+                updates.push(ForcedUpdate {
+                    reference: reference.clone(),
+                    old: old.to_string(),
+                    new: new.to_string(),
+                });
+            }
+        }
+    }
+    updates
+}
+
+fn collect_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, out);
+        } else {
+            out.push(path);
+        }
+    }
+}
+
+/// Split `<old> <new> <ident> <timestamp> <tz>\t<message>`.
+fn parse_reflog_line(line: &str) -> Option<(&str, &str, i64, &str)> {
+    let (head, message) = line.split_once('\t')?;
+    let mut fields = head.splitn(3, ' ');
+    let old = fields.next()?;
+    let new = fields.next()?;
+    let mut ident_tail = fields.next()?.rsplitn(3, ' ');
+    let _tz = ident_tail.next()?;
+    let timestamp = ident_tail.next()?.parse().ok()?;
+    Some((old, new, timestamp, message))
+}
+
+fn entry_id(reference: &str, old: &str, new: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{}\0{}\0{}", reference, old, new).as_bytes());
+    format!("{:x}", hasher.finalize())[..16].to_string()
+}
+
+fn read_processed(dir: &Path) -> HashSet<String> {
+    fs::read_to_string(dir.join(PROCESSED_FILE))
+        .map(|content| content.lines().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+fn write_processed(dir: &Path, processed: &HashSet<String>) -> Result<(), GitAiError> {
+    fs::create_dir_all(dir)?;
+    let sorted: BTreeSet<&String> = processed.iter().collect();
+    let mut content = String::new();
+    for id in sorted {
+        content.push_str(id);
+        content.push('\n');
+    }
+    fs::write(dir.join(PROCESSED_FILE), content)?;
+    Ok(())
+}
+
+/// What handling one forced update amounted to.
+enum QuarantineOutcome {
+    Quarantined(QuarantineEntry),
+    /// The update orphaned no attributed commits; it never needs revisiting.
+    NothingToQuarantine,
+    /// The replaced commits are still on a local branch, so their attribution
+    /// is not lost yet.
+    HeldByLocalBranch,
+}
+
+fn quarantine_update(
+    repo: &Repository,
+    dir: &Path,
+    id: String,
+    update: ForcedUpdate,
+    now: u64,
+) -> Result<QuarantineOutcome, GitAiError> {
+    if !commit_exists(repo, &update.old) {
+        return Ok(QuarantineOutcome::NothingToQuarantine);
+    }
+    let orphaned = orphaned_commits(repo, &update.old, &update.new, true)?;
+    if orphaned.is_empty() {
+        if orphaned_commits(repo, &update.old, &update.new, false)?.is_empty() {
+            return Ok(QuarantineOutcome::NothingToQuarantine);
+        }
+        return Ok(QuarantineOutcome::HeldByLocalBranch);
+    }
+
+    let notes = notes_api::read_notes_batch(repo, &orphaned)?;
+    let working_logs: Vec<String> = orphaned
+        .iter()
+        .filter(|sha| repo.storage.has_working_log(sha))
+        .cloned()
+        .collect();
+    let noted: Vec<String> = orphaned
+        .iter()
+        .filter(|sha| notes.contains_key(*sha) || working_logs.contains(*sha))
+        .cloned()
+        .collect();
+    if noted.is_empty() {
+        return Ok(QuarantineOutcome::NothingToQuarantine);
+    }
+
+    let patch_ids = stable_patch_ids_for_commits(repo, &noted)?;
+    let mut fingerprints = added_line_fingerprints(repo, &noted)?;
+    let commits = noted
+        .iter()
+        .map(|sha| QuarantinedCommit {
+            sha: sha.clone(),
+            patch_id: patch_ids.get(sha).cloned(),
+            added_lines: fingerprints.remove(sha).unwrap_or_default(),
+            note: notes.get(sha).cloned(),
+        })
+        .collect();
+
+    let entry_dir = dir.join(&id);
+    for sha in &working_logs {
+        copy_dir_contents(
+            &repo.storage.working_logs.join(sha),
+            &entry_dir.join("working_logs").join(sha),
+        )?;
+    }
+    let entry = QuarantineEntry {
+        id,
+        reference: update.reference,
+        old_tip: update.old,
+        new_tip: update.new,
+        created_at: now,
+        commits,
+        working_logs,
+    };
+    write_entry(&entry_dir, &entry)?;
+    Ok(QuarantineOutcome::Quarantined(entry))
+}
+
+/// Commits in `old_tip` that are not in `new_tip` (nor, with
+/// `exclude_local_branches`, in any local branch), oldest first.
+fn orphaned_commits(
+    repo: &Repository,
+    old_tip: &str,
+    new_tip: &str,
+    exclude_local_branches: bool,
+) -> Result<Vec<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "rev-list".to_string(),
+        "--reverse".to_string(),
+        format!("--max-count={}", MAX_QUARANTINED_COMMITS),
+        old_tip.to_string(),
+        "--not".to_string(),
+        new_tip.to_string(),
+    ]);
+    if exclude_local_branches {
+        args.push("--branches".to_string());
+    }
+    let output = exec_git(&args)?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+fn commit_exists(repo: &Repository, sha: &str) -> bool {
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "cat-file".to_string(),
+        "-e".to_string(),
+        format!("{}^{{commit}}", sha),
+    ]);
+    exec_git_allow_nonzero(&args)
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Sorted, de-duplicated hashes of each commit's non-blank added lines.
+fn added_line_fingerprints(
+    repo: &Repository,
+    commits: &[String],
+) -> Result<HashMap<String, Vec<u64>>, GitAiError> {
+    if commits.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "log".to_string(),
+        "--stdin".to_string(),
+        "--no-walk".to_string(),
+        "--no-ext-diff".to_string(),
+        "--no-textconv".to_string(),
+        "--no-color".to_string(),
+        "--format=%x00%H".to_string(),
+        "-p".to_string(),
+    ]);
+    let stdin_data = commits.join("\n") + "\n";
+    let output = exec_git_stdin(&args, stdin_data.as_bytes())?;
+    Ok(parse_added_line_fingerprints(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+fn parse_added_line_fingerprints(log_output: &str) -> HashMap<String, Vec<u64>> {
+    let mut fingerprints: HashMap<String, Vec<u64>> = HashMap::new();
+    let mut current: Option<String> = None;
+    for line in log_output.lines() {
+        if let Some(sha) = line.strip_prefix('\0') {
+            let sha = sha.trim().to_string();
+            fingerprints.entry(sha.clone()).or_default();
+            current = Some(sha);
+            continue;
+        }
+        let Some(lines) = current.as_ref().and_then(|sha| fingerprints.get_mut(sha)) else {
+            continue;
+        };
+        if let Some(added) = line.strip_prefix('+')
+            && !line.starts_with("+++ ")
+            && !added.trim().is_empty()
+            && lines.len() < MAX_FINGERPRINT_LINES
+        {
+            lines.push(fnv1a(added.trim().as_bytes()));
+        }
+    }
+    for lines in fingerprints.values_mut() {
+        lines.sort_unstable();
+        lines.dedup();
+    }
+    fingerprints
+}
+
+/// Stable across builds, unlike `DefaultHasher`, since hashes are stored on disk.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Jaccard overlap of two sorted fingerprint sets.
+fn similarity(a: &[u64], b: &[u64]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let (mut i, mut j, mut shared) = (0, 0, 0usize);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                shared += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    shared as f64 / (a.len() + b.len() - shared) as f64
+}
+
+fn write_entry(entry_dir: &Path, entry: &QuarantineEntry) -> Result<(), GitAiError> {
+    fs::create_dir_all(entry_dir)?;
+    fs::write(
+        entry_dir.join(MANIFEST_FILE),
+        serde_json::to_string_pretty(entry)?,
+    )?;
+    Ok(())
+}
+
+/// Quarantine entries on disk, oldest first.
+pub fn list_quarantine_entries(repo: &Repository) -> Vec<QuarantineEntry> {
+    let Ok(dirs) = fs::read_dir(quarantine_dir(repo)) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<QuarantineEntry> = dirs
+        .flatten()
+        .filter_map(|dir| fs::read_to_string(dir.path().join(MANIFEST_FILE)).ok())
+        .filter_map(|content| serde_json::from_str(&content).ok())
+        .collect();
+    entries.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+    entries
+}
+
+/// Pair quarantined commits with `candidates`: identical patch-ids first, then
+/// the most similar remaining candidate at or above the similarity threshold.
+fn match_quarantined_commits(
+    commits: &[QuarantinedCommit],
+    candidates: &[String],
+    candidate_patch_ids: &HashMap<String, String>,
+    candidate_fingerprints: &HashMap<String, Vec<u64>>,
+) -> Vec<(usize, String, ReconcileMatch)> {
+    let mut used: HashSet<&str> = HashSet::new();
+    let mut matched: Vec<Option<(String, ReconcileMatch)>> = vec![None; commits.len()];
+
+    for (idx, commit) in commits.iter().enumerate() {
+        let Some(patch_id) = &commit.patch_id else {
+            continue;
+        };
+        if let Some(candidate) = candidates.iter().find(|sha| {
+            !used.contains(sha.as_str()) && candidate_patch_ids.get(*sha) == Some(patch_id)
+        }) {
+            used.insert(candidate.as_str());
+            matched[idx] = Some((candidate.clone(), ReconcileMatch::PatchId));
+        }
+    }
+
+    for (idx, commit) in commits.iter().enumerate() {
+        if matched[idx].is_some() {
+            continue;
+        }
+        let best = candidates
+            .iter()
+            .filter(|sha| !used.contains(sha.as_str()))
+            .filter_map(|sha| {
+                let score = similarity(&commit.added_lines, candidate_fingerprints.get(sha)?);
+                (score >= RECONCILE_SIMILARITY_THRESHOLD).then_some((sha, score))
+            })
+            .fold(
+                None,
+                |best: Option<(&String, f64)>, (sha, score)| match best {
+                    Some((_, best_score)) if best_score >= score => best,
+                    _ => Some((sha, score)),
+                },
+            );
+        if let Some((sha, _)) = best {
+            used.insert(sha.as_str());
+            matched[idx] = Some((sha.clone(), ReconcileMatch::Similarity));
+        }
+    }
+
+    matched
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, m)| m.map(|(sha, kind)| (idx, sha, kind)))
+        .collect()
+}
+
+/// Commits on the current side of `entry.reference` that may hold the rewrites.
+fn reconcile_candidates(repo: &Repository, entry: &QuarantineEntry) -> Vec<String> {
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "rev-parse".to_string(),
+        "--verify".to_string(),
+        "-q".to_string(),
+        format!("{}^{{commit}}", entry.reference),
+    ]);
+    let tip = exec_git_allow_nonzero(&args)
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|sha| !sha.is_empty())
+        .unwrap_or_else(|| entry.new_tip.clone());
+
+    if commit_exists(repo, &entry.old_tip)
+        && let Some(base) = find_merge_base(repo, &entry.old_tip, &tip)
+    {
+        return list_commits_in_range(repo, &base, &tip);
+    }
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "rev-list".to_string(),
+        "--reverse".to_string(),
+        format!("--max-count={}", MAX_RECONCILE_CANDIDATES),
+        tip,
+    ]);
+    exec_git_allow_nonzero(&args)
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Map every quarantine entry onto its ref's current history and restore the
+/// matched attribution. With `dry_run`, only reports what would be restored.
+///
+/// Notes are shifted through the old/new tree diff while the old commit object
+/// still exists. Once it has been garbage-collected, only identical patches
+/// are restored, with the quarantined note copied over as-is.
+pub fn reconcile_quarantine(
+    repo: &Repository,
+    dry_run: bool,
+) -> Result<ReconcileReport, GitAiError> {
+    let mut report = ReconcileReport::default();
+    for mut entry in list_quarantine_entries(repo) {
+        report.entries += 1;
+        let candidates = reconcile_candidates(repo, &entry);
+        let candidate_patch_ids = stable_patch_ids_for_commits(repo, &candidates)?;
+        let candidate_fingerprints = added_line_fingerprints(repo, &candidates)?;
+        let matches = match_quarantined_commits(
+            &entry.commits,
+            &candidates,
+            &candidate_patch_ids,
+            &candidate_fingerprints,
+        );
+
+        let mut shift_mappings = Vec::new();
+        let mut note_writes = Vec::new();
+        let mut resolved: HashSet<usize> = HashSet::new();
+        let mut note_shas: Vec<String> = matches.iter().map(|(_, sha, _)| sha.clone()).collect();
+        note_shas.extend(entry.commits.iter().map(|commit| commit.sha.clone()));
+        let existing_notes = notes_api::read_notes_batch(repo, &note_shas)?;
+        for (idx, new_sha, matched_by) in matches {
+            let commit = &entry.commits[idx];
+            if let Some(note) = &commit.note
+                && !existing_notes.contains_key(&new_sha)
+            {
+                if commit_exists(repo, &commit.sha) {
+                    // Put the note back if notes gc dropped it, then shift it.
+                    if !existing_notes.contains_key(&commit.sha) {
+                        note_writes.push((commit.sha.clone(), note.clone()));
+                    }
+                    shift_mappings.push((commit.sha.clone(), new_sha.clone()));
+                } else if matched_by == ReconcileMatch::PatchId {
+                    let Ok(mut log) = AuthorshipLog::deserialize_from_string(note) else {
+                        continue;
+                    };
+                    log.metadata.base_commit_sha = new_sha.clone();
+                    let serialized = log.serialize_to_string().map_err(|e| {
+                        GitAiError::Generic(format!("failed to serialize authorship log: {}", e))
+                    })?;
+                    note_writes.push((new_sha.clone(), serialized));
+                } else {
+                    continue;
+                }
+            }
+            if entry.working_logs.contains(&commit.sha) {
+                if !dry_run {
+                    restore_working_log(repo, &entry, &commit.sha, &new_sha)?;
+                }
+                report.working_logs_restored += 1;
+            }
+            resolved.insert(idx);
+            report.reconciled.push(ReconciledCommit {
+                old_sha: commit.sha.clone(),
+                new_sha,
+                matched_by,
+            });
+        }
+        if !dry_run {
+            notes_api::write_notes_batch(repo, &note_writes)?;
+            shift_authorship_notes(repo, &shift_mappings)?;
+        }
+
+        let remaining: Vec<QuarantinedCommit> = entry
+            .commits
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| !resolved.contains(idx))
+            .map(|(_, commit)| commit.clone())
+            .collect();
+        report
+            .unmatched
+            .extend(remaining.iter().map(|commit| commit.sha.clone()));
+        if dry_run {
+            continue;
+        }
+        let entry_dir = quarantine_dir(repo).join(&entry.id);
+        if remaining.is_empty() {
+            fs::remove_dir_all(&entry_dir)?;
+        } else {
+            entry.commits = remaining;
+            write_entry(&entry_dir, &entry)?;
+        }
+    }
+    Ok(report)
+}
+
+/// Move the working log for `old_sha` to `new_sha`, falling back to the
+/// quarantined copy when the live one is already gone.
+fn restore_working_log(
+    repo: &Repository,
+    entry: &QuarantineEntry,
+    old_sha: &str,
+    new_sha: &str,
+) -> Result<(), GitAiError> {
+    if repo.storage.has_working_log(old_sha) {
+        return repo.storage.rename_working_log(old_sha, new_sha);
+    }
+    if repo.storage.has_working_log(new_sha) {
+        return Ok(());
+    }
+    let snapshot = quarantine_dir(repo)
+        .join(&entry.id)
+        .join("working_logs")
+        .join(old_sha);
+    copy_dir_contents(&snapshot, &repo.storage.working_logs.join(new_sha))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forced_updates_are_read_from_recent_remote_reflog_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let reflog = dir.path().join("logs/refs/remotes/origin/feature");
+        fs::create_dir_all(reflog.parent().unwrap()).unwrap();
+        let (a, b, c) = ("a".repeat(40), "b".repeat(40), "c".repeat(40));
+        fs::write(
+            &reflog,
+            format!(
+                "{a} {b} Dev <dev@example.com> 1000 +0000\tfetch origin: forced-update\n\
+                 {b} {c} Dev <dev@example.com> 9000 +0000\tfetch: fast-forward\n\
+                 {c} {a} Dev <dev@example.com> 9500 +0000\tpull --rebase: forced-update\n"
+            ),
+        )
+        .unwrap();
+
+        let updates = forced_remote_updates(dir.path(), 10_000);
+        assert_eq!(
+            updates,
+            vec![ForcedUpdate {
+                reference: "refs/remotes/origin/feature".to_string(),
+                old: c,
+                new: a,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_match_prefers_patch_id_then_added_line_similarity() {
+        let commit = |sha: &str, patch_id: Option<&str>, added_lines: Vec<u64>| QuarantinedCommit {
+            sha: sha.to_string(),
+            patch_id: patch_id.map(str::to_string),
+            added_lines,
+            note: None,
+        };
+        let commits = vec![
+            commit("old1", Some("p1"), vec![1, 2, 3]),
+            commit("old2", Some("p2"), vec![10, 11, 12, 13]),
+            commit("old3", Some("p3"), vec![20, 21]),
+        ];
+        let candidates = vec!["new1".to_string(), "new2".to_string(), "new3".to_string()];
+        let patch_ids: HashMap<String, String> = [("new2", "p1"), ("new1", "px"), ("new3", "py")]
+            .into_iter()
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect();
+        let fingerprints: HashMap<String, Vec<u64>> = [
+            ("new1", vec![10, 11, 12, 99]),
+            ("new3", vec![20, 50, 51, 52]),
+        ]
+        .into_iter()
+        .map(|(a, b)| (a.to_string(), b))
+        .collect();
+
+        let matches = match_quarantined_commits(&commits, &candidates, &patch_ids, &fingerprints);
+        assert_eq!(
+            matches,
+            vec![
+                (0, "new2".to_string(), ReconcileMatch::PatchId),
+                (1, "new1".to_string(), ReconcileMatch::Similarity),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_added_line_fingerprints_skip_headers_and_blank_lines() {
+        let output = "\0abc\n\ndiff --git a/f b/f\n--- a/f\n+++ b/f\n@@ -0,0 +1,3 @@\n+one\n+\n+ two \n\0def\n+one\n";
+        let fingerprints = parse_added_line_fingerprints(output);
+        assert_eq!(fingerprints["abc"].len(), 2);
+        assert_eq!(fingerprints["def"], vec![fnv1a(b"one")]);
+        assert_eq!(similarity(&fingerprints["abc"], &fingerprints["def"]), 0.5);
+    }
+}
@@ -5,11 +5,17 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::authorship::diff_ai_accepted::diff_ai_accepted_stats;
+use crate::authorship::format_detection::drop_whitespace_only_changes;
 use crate::authorship::ignore::{build_ignore_matcher, should_ignore_file_with_matcher};
-use crate::authorship::stats::{CommitStats, stats_for_commit_stats, stats_from_authorship_log};
+use crate::authorship::stats::{
+    CommitStats, stats_for_commit_stats, stats_for_commit_stats_from_hunks_with_merge_flag,
+    stats_from_authorship_log,
+};
+use crate::commands::diff::get_diff_with_line_numbers;
 use crate::error::GitAiError;
 use crate::git::notes_api::{CommitAuthorship, filter_commits_with_notes};
 use crate::git::repository::{CommitRange, InternalGitProfile, Repository, exec_git_with_profile};
+use crate::workspace_config::WhitespaceMode;
 use std::io::IsTerminal;
 
 /// The git empty tree hash - represents an empty repository state
@@ -43,6 +49,7 @@ pub fn range_authorship(
     commit_range: CommitRange,
     pre_fetch_contents: bool,
     ignore_patterns: &[String],
+    whitespace: WhitespaceMode,
     commit_shas: Option<Vec<String>>,
 ) -> Result<RangeAuthorshipStats, GitAiError> {
     commit_range.is_valid()?;
@@ -122,6 +129,7 @@ pub fn range_authorship(
         commit_range_clone,
         &commit_shas,
         ignore_patterns,
+        whitespace,
     )?;
 
     Ok(RangeAuthorshipStats {
@@ -399,12 +407,33 @@ fn calculate_range_stats_direct(
     commit_range: CommitRange,
     commit_shas: &[String],
     ignore_patterns: &[String],
+    whitespace: WhitespaceMode,
 ) -> Result<CommitStats, GitAiError> {
     let start_sha = commit_range.start_oid.clone();
     let end_sha = commit_range.end_oid.clone();
     // Special case: single commit range (start == end)
     if start_sha == end_sha {
-        return stats_for_commit_stats(repo, &end_sha, ignore_patterns);
+        return stats_for_commit_stats(repo, &end_sha, ignore_patterns, whitespace);
+    }
+
+    // Leaving out whitespace-only changes needs the line contents, so count
+    // from the range's hunks instead of numstat.
+    if whitespace != WhitespaceMode::Exact {
+        let mut hunks = get_diff_with_line_numbers(repo, &start_sha, &end_sha)?;
+        drop_whitespace_only_changes(&mut hunks, whitespace);
+        let authorship_log = create_authorship_log_for_range(
+            repo,
+            &start_sha,
+            &end_sha,
+            commit_shas,
+            ignore_patterns,
+        )?;
+        return Ok(stats_for_commit_stats_from_hunks_with_merge_flag(
+            ignore_patterns,
+            &hunks,
+            Some(&authorship_log),
+            false,
+        ));
     }
 
     // Step 1: Get git diff stats between start and end
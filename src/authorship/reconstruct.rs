@@ -0,0 +1,192 @@
+//! Attribution reconstruction for history rewritten while git-ai was not running.
+//!
+//! A rebase or cherry-pick done with hooks off leaves its new commits without
+//! notes and no rewrite event to replay. `git-ai reconstruct <range>` takes the
+//! commits in the range that have no note and matches each to a noted commit
+//! on another branch (or any `--source` revision) with the same stable
+//! patch-id. The matched note is shifted onto it, exactly as a replayed
+//! rewrite would have done.
+
+use crate::authorship::rewrite::shift_authorship_notes;
+use crate::authorship::rewrite_cherry_pick::stable_patch_ids_for_commits;
+use crate::error::GitAiError;
+use crate::git::notes_api;
+use crate::git::repository::{Repository, exec_git};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Commits considered as note sources, newest first.
+pub const MAX_RECONSTRUCT_SOURCES: usize = 5000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReconstructedCommit {
+    /// Noted commit the attribution was taken from.
+    pub source_sha: String,
+    /// Commit in the range that received it.
+    pub target_sha: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ReconstructReport {
+    /// Non-merge commits in the range.
+    pub commits: usize,
+    /// Commits in the range that already had a note.
+    pub already_noted: usize,
+    pub reconstructed: Vec<ReconstructedCommit>,
+    /// Commits without a note and no noted commit with the same patch.
+    pub unmatched: Vec<String>,
+}
+
+/// Rebuild notes for the commits in `range` that lack one. Sources are the
+/// noted commits reachable from `source_revs`, or from every branch, tag and
+/// remote-tracking ref when empty. With `dry_run`, only reports the matches.
+pub fn reconstruct_range(
+    repo: &Repository,
+    range: &str,
+    source_revs: &[String],
+    dry_run: bool,
+) -> Result<ReconstructReport, GitAiError> {
+    let targets = rev_list(repo, &[range.to_string()], None)?;
+    let noted = notes_api::commits_with_notes(repo, &targets)?;
+    let missing: Vec<String> = targets
+        .iter()
+        .filter(|sha| !noted.contains(*sha))
+        .cloned()
+        .collect();
+    let mut report = ReconstructReport {
+        commits: targets.len(),
+        already_noted: targets.len() - missing.len(),
+        ..Default::default()
+    };
+    if missing.is_empty() {
+        return Ok(report);
+    }
+
+    let source_revs = if source_revs.is_empty() {
+        vec![
+            "--branches".to_string(),
+            "--tags".to_string(),
+            "--remotes".to_string(),
+        ]
+    } else {
+        source_revs.to_vec()
+    };
+    let missing_set: HashSet<&String> = missing.iter().collect();
+    let candidates: Vec<String> = rev_list(repo, &source_revs, Some(MAX_RECONSTRUCT_SOURCES))?
+        .into_iter()
+        .filter(|sha| !missing_set.contains(sha))
+        .collect();
+    let noted_sources = notes_api::commits_with_notes(repo, &candidates)?;
+    let sources: Vec<String> = candidates
+        .into_iter()
+        .filter(|sha| noted_sources.contains(sha))
+        .collect();
+
+    let mut patch_id_inputs = missing.clone();
+    patch_id_inputs.extend(sources.iter().cloned());
+    let patch_ids = stable_patch_ids_for_commits(repo, &patch_id_inputs)?;
+
+    let mut mappings = Vec::new();
+    for (target, source) in match_by_patch_id(&missing, &sources, &patch_ids) {
+        match source {
+            Some(source) => {
+                mappings.push((source.clone(), target.clone()));
+                report.reconstructed.push(ReconstructedCommit {
+                    source_sha: source,
+                    target_sha: target,
+                });
+            }
+            None => report.unmatched.push(target),
+        }
+    }
+    if !dry_run {
+        shift_authorship_notes(repo, &mappings)?;
+    }
+    Ok(report)
+}
+
+/// Pair every target with the first source sharing its patch-id. Sources can
+/// be reused: one patch may have been picked onto several branches.
+fn match_by_patch_id(
+    targets: &[String],
+    sources: &[String],
+    patch_ids: &HashMap<String, String>,
+) -> Vec<(String, Option<String>)> {
+    let mut source_by_patch_id: HashMap<&str, &String> = HashMap::new();
+    for source in sources {
+        if let Some(patch_id) = patch_ids.get(source) {
+            source_by_patch_id
+                .entry(patch_id.as_str())
+                .or_insert(source);
+        }
+    }
+    targets
+        .iter()
+        .map(|target| {
+            let source = patch_ids
+                .get(target)
+                .and_then(|patch_id| source_by_patch_id.get(patch_id.as_str()))
+                .map(|source| (*source).clone());
+            (target.clone(), source)
+        })
+        .collect()
+}
+
+/// Non-merge commits selected by `revs`, oldest first when unbounded and
+/// newest first when `max_count` is set.
+fn rev_list(
+    repo: &Repository,
+    revs: &[String],
+    max_count: Option<usize>,
+) -> Result<Vec<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend(["rev-list".to_string(), "--no-merges".to_string()]);
+    match max_count {
+        Some(max_count) => args.push(format!("--max-count={}", max_count)),
+        None => args.push("--reverse".to_string()),
+    }
+    args.extend(revs.iter().cloned());
+    let output = exec_git(&args)?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_by_patch_id_pairs_identical_patches() {
+        let strings = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        let patch_ids: HashMap<String, String> = [
+            ("picked", "p1"),
+            ("picked-again", "p1"),
+            ("edited", "p2"),
+            ("src-newer", "p1"),
+            ("src-older", "p1"),
+            ("src-other", "p3"),
+        ]
+        .into_iter()
+        .map(|(sha, patch_id)| (sha.to_string(), patch_id.to_string()))
+        .collect();
+
+        let matches = match_by_patch_id(
+            &strings(&["picked", "picked-again", "edited", "no-patch"]),
+            &strings(&["src-newer", "src-older", "src-other"]),
+            &patch_ids,
+        );
+        assert_eq!(
+            matches,
+            vec![
+                ("picked".to_string(), Some("src-newer".to_string())),
+                ("picked-again".to_string(), Some("src-newer".to_string())),
+                ("edited".to_string(), None),
+                ("no-patch".to_string(), None),
+            ]
+        );
+    }
+}
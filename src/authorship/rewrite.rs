@@ -2,10 +2,11 @@ use std::collections::{HashMap, HashSet};
 
 use crate::authorship::authorship_log_serialization::AuthorshipLog;
 use crate::authorship::hunk_shift::{DiffHunk, parse_hunk_header};
+use crate::authorship::rewrite_merges;
 use crate::config::Config;
 use crate::error::GitAiError;
 use crate::git::notes_api;
-use crate::git::repo_state::is_valid_git_oid;
+use crate::git::repo_state::{history_is_grafted, is_valid_git_oid};
 use crate::git::repository::{
     Repository, exec_git, exec_git_allow_nonzero, exec_git_stdin_streaming,
 };
@@ -64,6 +65,69 @@ impl RewriteMetricOperation {
     }
 }
 
+/// Identity of a single history rewrite transition.
+///
+/// The same `(old_sha, new_sha, operation)` can be observed more than once, e.g.
+/// when an amend is dispatched both through the proxy and through a hook. Handling
+/// it twice would shift notes that were already shifted, so callers record the key
+/// before applying a rewrite and skip keys they have already applied.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct RewriteIdempotencyKey {
+    pub old_sha: String,
+    pub new_sha: String,
+    pub operation: RewriteMetricOperation,
+}
+
+impl RewriteIdempotencyKey {
+    pub(crate) fn new(
+        old_sha: impl Into<String>,
+        new_sha: impl Into<String>,
+        operation: RewriteMetricOperation,
+    ) -> Self {
+        Self {
+            old_sha: old_sha.into(),
+            new_sha: new_sha.into(),
+            operation,
+        }
+    }
+}
+
+/// Bounded record of recently applied rewrite keys.
+#[derive(Debug, Default)]
+pub(crate) struct AppliedRewriteKeys {
+    order: std::collections::VecDeque<RewriteIdempotencyKey>,
+    keys: HashSet<RewriteIdempotencyKey>,
+}
+
+impl AppliedRewriteKeys {
+    const MAX_KEYS: usize = 256;
+
+    /// Records `key` and returns true if it had not been applied yet.
+    pub(crate) fn insert(&mut self, key: RewriteIdempotencyKey) -> bool {
+        if !self.keys.insert(key.clone()) {
+            return false;
+        }
+        self.order.push_back(key);
+        while self.order.len() > Self::MAX_KEYS {
+            if let Some(evicted) = self.order.pop_front() {
+                self.keys.remove(&evicted);
+            }
+        }
+        true
+    }
+
+    /// Forgets `key` so the rewrite can be applied again, e.g. after it failed.
+    pub(crate) fn remove(&mut self, key: &RewriteIdempotencyKey) {
+        if self.keys.remove(key) {
+            self.order.retain(|applied| applied != key);
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct RewriteMetricCommit {
     pub new_sha: String,
@@ -135,6 +199,8 @@ pub(crate) fn branch_name_from_ref(reference: &str) -> Option<String> {
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub(crate) struct RewriteOutcome {
     pub(crate) metric_commits: Vec<RewriteMetricCommit>,
+    /// Original -> rewritten commit pairs, when they were derived.
+    pub(crate) mappings: Vec<(String, String)>,
 }
 
 impl RewriteOutcome {
@@ -143,7 +209,15 @@ impl RewriteOutcome {
     }
 
     fn from_metric_commits(metric_commits: Vec<RewriteMetricCommit>) -> Self {
-        Self { metric_commits }
+        Self {
+            metric_commits,
+            mappings: Vec::new(),
+        }
+    }
+
+    fn with_mappings(mut self, mappings: Vec<(String, String)>) -> Self {
+        self.mappings = mappings;
+        self
     }
 }
 
@@ -385,6 +459,18 @@ pub(crate) fn handle_rewrite_event_with_metrics(
     repo: &Repository,
     event: RewriteEvent,
 ) -> Result<RewriteOutcome, GitAiError> {
+    let kind = match &event {
+        RewriteEvent::NonFastForward { .. } => "non_fast_forward",
+        RewriteEvent::CherryPickComplete { .. } => "cherry_pick",
+        RewriteEvent::SquashMerge { .. } => "squash_merge",
+    };
+    let _span = tracing::info_span!(
+        target: crate::observability::trace::targets::REWRITE,
+        "rewrite_event",
+        kind
+    )
+    .entered();
+
     match event {
         RewriteEvent::SquashMerge {
             ref source_head,
@@ -449,7 +535,24 @@ pub(crate) fn handle_non_fast_forward_rewrite_with_operation(
     onto: Option<&str>,
     operation: RewriteMetricOperation,
 ) -> Result<RewriteOutcome, GitAiError> {
-    let mappings = derive_mappings_from_range_diff(repo, old_tip, new_tip, onto)?;
+    // Shallow, grafted or replaced history can hide the merge base or the
+    // commits range-diff needs; reconstruct from the tip diff instead of erroring.
+    let grafted = history_is_grafted(repo.common_dir());
+    if grafted && find_merge_base(repo, old_tip, new_tip).is_none() {
+        return reconstruct_rewrite_from_tip_diff(repo, old_tip, new_tip, operation);
+    }
+    let mappings = match derive_mappings_from_range_diff(repo, old_tip, new_tip, onto) {
+        Ok(mappings) => mappings,
+        Err(e) if grafted => {
+            tracing::warn!(
+                target: crate::observability::trace::targets::REWRITE,
+                %e,
+                "range-diff failed on grafted history; reconstructing from the tip diff"
+            );
+            return reconstruct_rewrite_from_tip_diff(repo, old_tip, new_tip, operation);
+        }
+        Err(e) => return Err(e),
+    };
     if mappings.is_empty() {
         return Ok(RewriteOutcome::empty());
     }
@@ -457,12 +560,13 @@ pub(crate) fn handle_non_fast_forward_rewrite_with_operation(
     crate::git::sync_authorship::fetch_missing_notes_for_commits(repo, &source_shas)?;
     let shifted_notes = shift_authorship_notes_merging_existing_with_notes(repo, &mappings)?;
     if !rewrite_metrics_enabled() {
-        return Ok(RewriteOutcome::empty());
+        return Ok(RewriteOutcome::empty().with_mappings(mappings));
     }
     let metric_commits = metric_commits_from_mappings(&mappings, operation);
-    Ok(RewriteOutcome::from_metric_commits(
-        attach_authorship_notes(metric_commits, shifted_notes),
-    ))
+    Ok(
+        RewriteOutcome::from_metric_commits(attach_authorship_notes(metric_commits, shifted_notes))
+            .with_mappings(mappings),
+    )
 }
 
 fn handle_squash_merge(
@@ -471,8 +575,6 @@ fn handle_squash_merge(
     squash_commit: &str,
     onto: &str,
 ) -> Result<RewriteOutcome, GitAiError> {
-    use crate::authorship::hunk_shift::apply_hunk_shifts_to_file_attestation;
-
     let target_notes = notes_api::read_notes_batch(repo, &[squash_commit.to_string()])?;
     let existing_target_log = target_notes
         .get(squash_commit)
@@ -489,37 +591,7 @@ fn handle_squash_merge(
 
     crate::git::sync_authorship::fetch_missing_notes_for_commits(repo, &sources)?;
 
-    // Batch-read all source notes in O(1) git calls
-    let source_notes_map = notes_api::read_notes_batch(repo, &sources)?;
-
-    // Collect which source commits have parseable notes and need intermediate diffs
-    struct SourceNote {
-        log: AuthorshipLog,
-        diff_idx: Option<usize>,
-    }
-
-    let mut source_notes: Vec<SourceNote> = Vec::new();
-    let mut diff_pairs: Vec<(String, String)> = Vec::new();
-
-    for src_sha in &sources {
-        let Some(raw) = source_notes_map.get(src_sha) else {
-            continue;
-        };
-        let Ok(log) = AuthorshipLog::deserialize_from_string(raw) else {
-            continue;
-        };
-
-        let diff_idx = if src_sha.as_str() != source_head {
-            let idx = diff_pairs.len();
-            diff_pairs.push((src_sha.clone(), source_head.to_string()));
-            Some(idx)
-        } else {
-            None
-        };
-
-        source_notes.push(SourceNote { log, diff_idx });
-    }
-
+    let source_notes = read_source_notes(repo, &sources)?;
     if source_notes.is_empty() {
         if let Some(existing_log) = existing_target_log.as_ref()
             && !repo.storage.has_working_log(onto)
@@ -532,93 +604,185 @@ fn handle_squash_merge(
         return Ok(squash_metric_outcome(squash_commit, &sources, onto, note));
     }
 
-    // Add the final source_head→squash_commit pair
-    let final_diff_idx = diff_pairs.len();
-    diff_pairs.push((source_head.to_string(), squash_commit.to_string()));
+    let Some(mut final_log) =
+        reconstruct_log_from_sources(repo, source_notes, source_head, squash_commit)?
+    else {
+        return Ok(RewriteOutcome::empty());
+    };
 
-    // Single batched diff-tree call for ALL intermediate shifts + final shift
-    let diff_results = compute_diff_trees_batch(repo, &diff_pairs)?;
+    final_log.metadata.base_commit_sha = squash_commit.to_string();
 
-    // Phase 1: Shift intermediate notes to source_head's coordinate space and merge
-    let mut merged_log: Option<AuthorshipLog> = None;
+    let shifted_log = match existing_target_log {
+        Some(existing) => {
+            crate::authorship::conflict_resolution::merge_conflict_resolution_authorship(
+                Some(final_log),
+                existing,
+                squash_commit,
+            )
+        }
+        None => final_log,
+    };
 
-    for note in source_notes {
-        let mut log = note.log;
+    if repo.storage.has_working_log(onto) {
+        let note =
+            post_squash_resolution_working_log(repo, onto, squash_commit, Some(shifted_log))?;
+        Ok(squash_metric_outcome(squash_commit, &sources, onto, note))
+    } else {
+        let note = write_authorship_log_for_metrics(repo, squash_commit, &shifted_log)?;
+        Ok(squash_metric_outcome(squash_commit, &sources, onto, note))
+    }
+}
 
-        if let Some(idx) = note.diff_idx {
-            let diff_to_tip = &diff_results[idx];
-            for (old_path, new_path) in &diff_to_tip.renames {
-                for attestation in &mut log.attestations {
-                    if attestation.file_path == *old_path {
-                        attestation.file_path = new_path.clone();
-                    }
-                }
+/// Parseable notes of `sources`, in order, batch-read in O(1) git calls.
+pub(crate) fn read_source_notes(
+    repo: &Repository,
+    sources: &[String],
+) -> Result<Vec<(String, AuthorshipLog)>, GitAiError> {
+    let source_notes_map = notes_api::read_notes_batch(repo, sources)?;
+    Ok(sources
+        .iter()
+        .filter_map(|src_sha| {
+            let raw = source_notes_map.get(src_sha)?;
+            let log = AuthorshipLog::deserialize_from_string(raw).ok()?;
+            Some((src_sha.clone(), log))
+        })
+        .collect())
+}
+
+/// Fallback for rewrites whose old and new commits cannot be paired because
+/// the ancestry is shallow, grafted or replaced. Every old-side note within the
+/// available history is carried onto `new_tip` through the direct tree diff,
+/// the same reconstruction squash merges use, unless `new_tip` already has one.
+fn reconstruct_rewrite_from_tip_diff(
+    repo: &Repository,
+    old_tip: &str,
+    new_tip: &str,
+    operation: RewriteMetricOperation,
+) -> Result<RewriteOutcome, GitAiError> {
+    if !notes_api::read_notes_batch(repo, &[new_tip.to_string()])?.is_empty() {
+        return Ok(RewriteOutcome::empty());
+    }
+
+    let mut sources = list_commits_in_range(repo, new_tip, old_tip);
+    if sources.is_empty() {
+        sources.push(old_tip.to_string());
+    }
+    crate::git::sync_authorship::fetch_missing_notes_for_commits(repo, &sources)?;
+    let source_notes = read_source_notes(repo, &sources)?;
+    if source_notes.is_empty() {
+        return Ok(RewriteOutcome::empty());
+    }
+
+    let Some(mut log) = reconstruct_log_from_sources(repo, source_notes, old_tip, new_tip)? else {
+        return Ok(RewriteOutcome::empty());
+    };
+    log.metadata.base_commit_sha = new_tip.to_string();
+    let note = write_authorship_log_for_metrics(repo, new_tip, &log)?;
+    if !rewrite_metrics_enabled() {
+        return Ok(RewriteOutcome::empty());
+    }
+    let metric_commit = RewriteMetricCommit::new(new_tip.to_string(), sources, operation);
+    Ok(RewriteOutcome::from_metric_commits(vec![
+        attach_authorship_note(metric_commit, note),
+    ]))
+}
+
+/// Merge the notes of several source commits in `source_head`'s coordinates,
+/// then shift the result onto `target`. `source_notes` must not be empty.
+///
+/// Fails soft when history is incomplete (shallow clones, grafted or replaced
+/// imports): if the intermediate diffs cannot be computed, only `source_head`'s
+/// own note is carried across the direct `source_head`..`target` diff, and if
+/// even that fails nothing is reconstructed (`None`) rather than erroring.
+pub(crate) fn reconstruct_log_from_sources(
+    repo: &Repository,
+    source_notes: Vec<(String, AuthorshipLog)>,
+    source_head: &str,
+    target: &str,
+) -> Result<Option<AuthorshipLog>, GitAiError> {
+    let mut diff_pairs: Vec<(String, String)> = Vec::new();
+    let mut notes: Vec<(AuthorshipLog, Option<usize>)> = Vec::new();
+    for (src_sha, log) in source_notes {
+        let diff_idx = (src_sha != source_head).then(|| {
+            diff_pairs.push((src_sha, source_head.to_string()));
+            diff_pairs.len() - 1
+        });
+        notes.push((log, diff_idx));
+    }
+
+    // Add the final source_head→target pair
+    let mut final_diff_idx = diff_pairs.len();
+    diff_pairs.push((source_head.to_string(), target.to_string()));
+
+    // Single batched diff-tree call for ALL intermediate shifts + final shift
+    let diff_results = match compute_diff_trees_batch(repo, &diff_pairs) {
+        Ok(results) => results,
+        Err(e) => {
+            tracing::warn!(
+                target: crate::observability::trace::targets::REWRITE,
+                %e,
+                "intermediate diffs unavailable; reconstructing from the tip diff only"
+            );
+            notes.retain(|(_, diff_idx)| diff_idx.is_none());
+            if notes.is_empty() {
+                return Ok(None);
             }
-            if !diff_to_tip.hunks_by_file.is_empty() {
-                log.attestations = log
-                    .attestations
-                    .iter()
-                    .filter_map(|fa| match diff_to_tip.hunks_by_file.get(&fa.file_path) {
-                        Some(hunks) => apply_hunk_shifts_to_file_attestation(fa, hunks),
-                        None => Some(fa.clone()),
-                    })
-                    .collect();
+            final_diff_idx = 0;
+            let tip_pair = [(source_head.to_string(), target.to_string())];
+            match compute_diff_trees_batch(repo, &tip_pair) {
+                Ok(results) => results,
+                Err(e) => {
+                    tracing::warn!(
+                        target: crate::observability::trace::targets::REWRITE,
+                        %e,
+                        "tip diff unavailable; skipping authorship reconstruction"
+                    );
+                    return Ok(None);
+                }
             }
         }
+    };
 
+    // Phase 1: Shift intermediate notes to source_head's coordinate space and merge
+    let mut merged_log: Option<AuthorshipLog> = None;
+    for (mut log, diff_idx) in notes {
+        if let Some(idx) = diff_idx {
+            shift_log_through_diff(&mut log, &diff_results[idx]);
+        }
         match merged_log.as_mut() {
             Some(existing) => merge_authorship_logs(existing, &log),
             None => merged_log = Some(log),
         }
     }
 
-    let Some(mut final_log) = merged_log else {
-        return Ok(RewriteOutcome::empty());
-    };
+    // Phase 2: Shift merged log from source_head to target
+    Ok(merged_log.map(|mut log| {
+        shift_log_through_diff(&mut log, &diff_results[final_diff_idx]);
+        log
+    }))
+}
 
-    // Phase 2: Shift merged log from source_head to squash_commit
-    let diff_result = &diff_results[final_diff_idx];
+/// Move a note's attestations from the diff's old tree onto its new tree.
+pub(crate) fn shift_log_through_diff(log: &mut AuthorshipLog, result: &DiffTreeResult) {
+    use crate::authorship::hunk_shift::apply_hunk_shifts_to_file_attestation;
 
-    for (old_path, new_path) in &diff_result.renames {
-        for attestation in &mut final_log.attestations {
+    for (old_path, new_path) in &result.renames {
+        for attestation in &mut log.attestations {
             if attestation.file_path == *old_path {
                 attestation.file_path = new_path.clone();
             }
         }
     }
-
-    if !diff_result.hunks_by_file.is_empty() {
-        final_log.attestations = final_log
+    if !result.hunks_by_file.is_empty() {
+        log.attestations = log
             .attestations
             .iter()
-            .filter_map(|fa| match diff_result.hunks_by_file.get(&fa.file_path) {
+            .filter_map(|fa| match result.hunks_by_file.get(&fa.file_path) {
                 Some(hunks) => apply_hunk_shifts_to_file_attestation(fa, hunks),
                 None => Some(fa.clone()),
             })
             .collect();
     }
-
-    final_log.metadata.base_commit_sha = squash_commit.to_string();
-
-    let shifted_log = match existing_target_log {
-        Some(existing) => {
-            crate::authorship::conflict_resolution::merge_conflict_resolution_authorship(
-                Some(final_log),
-                existing,
-                squash_commit,
-            )
-        }
-        None => final_log,
-    };
-
-    if repo.storage.has_working_log(onto) {
-        let note =
-            post_squash_resolution_working_log(repo, onto, squash_commit, Some(shifted_log))?;
-        Ok(squash_metric_outcome(squash_commit, &sources, onto, note))
-    } else {
-        let note = write_authorship_log_for_metrics(repo, squash_commit, &shifted_log)?;
-        Ok(squash_metric_outcome(squash_commit, &sources, onto, note))
-    }
 }
 
 fn squash_metric_outcome(
@@ -933,6 +1097,16 @@ fn derive_mappings_from_range_diff(
     let range_diff_output = run_range_diff(repo, &base, old_tip, onto, new_tip)?;
     let mut mappings = parse_range_diff_output(&range_diff_output);
 
+    // `--rebase-merges` / `pull --rebase=merges` replayed the merges too; pair
+    // along the topology rather than range-diff's linearized order.
+    if rewrite_merges::range_has_merges(repo, onto, new_tip)
+        && rewrite_merges::range_has_merges(repo, &base, old_tip)
+    {
+        return rewrite_merges::derive_topology_mappings(
+            repo, &base, old_tip, onto, new_tip, &mappings,
+        );
+    }
+
     let merge_mappings = derive_merge_commit_mappings(repo, &base, old_tip, new_tip, &mappings)?;
     mappings.extend(merge_mappings);
 
@@ -952,7 +1126,7 @@ fn is_ancestor(repo: &Repository, ancestor: &str, descendant: &str) -> bool {
         .unwrap_or(false)
 }
 
-fn find_merge_base(repo: &Repository, a: &str, b: &str) -> Option<String> {
+pub(crate) fn find_merge_base(repo: &Repository, a: &str, b: &str) -> Option<String> {
     let mut args = repo.global_args_for_exec();
     args.extend(["merge-base".to_string(), a.to_string(), b.to_string()]);
 
@@ -1194,7 +1368,10 @@ fn list_merge_commits(repo: &Repository, base: &str, tip: &str) -> Result<Vec<St
         .collect())
 }
 
-fn get_commit_parents_batch(repo: &Repository, shas: &[String]) -> HashMap<String, Vec<String>> {
+pub(crate) fn get_commit_parents_batch(
+    repo: &Repository,
+    shas: &[String],
+) -> HashMap<String, Vec<String>> {
     if shas.is_empty() {
         return HashMap::new();
     }
@@ -1433,6 +1610,45 @@ mod tests {
         assert_eq!(branch_name_from_ref("refs/tags/v1"), None);
     }
 
+    #[test]
+    fn applied_rewrite_keys_reject_duplicate_transitions() {
+        let mut applied = AppliedRewriteKeys::default();
+        let amend = RewriteIdempotencyKey::new("old", "new", RewriteMetricOperation::Amend);
+        assert!(applied.insert(amend.clone()));
+        assert!(!applied.insert(amend));
+        // Same shas under a different operation are a distinct rewrite.
+        assert!(applied.insert(RewriteIdempotencyKey::new(
+            "old",
+            "new",
+            RewriteMetricOperation::NonFastForward,
+        )));
+    }
+
+    #[test]
+    fn applied_rewrite_keys_accept_released_transitions_again() {
+        let mut applied = AppliedRewriteKeys::default();
+        let rebase = RewriteIdempotencyKey::new("old", "new", RewriteMetricOperation::Rebase);
+        assert!(applied.insert(rebase.clone()));
+        applied.remove(&rebase);
+        assert!(applied.is_empty());
+        assert!(applied.insert(rebase));
+    }
+
+    #[test]
+    fn applied_rewrite_keys_evict_oldest_beyond_capacity() {
+        let mut applied = AppliedRewriteKeys::default();
+        let first = RewriteIdempotencyKey::new("old-0", "new-0", RewriteMetricOperation::Amend);
+        assert!(applied.insert(first.clone()));
+        for i in 1..=AppliedRewriteKeys::MAX_KEYS {
+            assert!(applied.insert(RewriteIdempotencyKey::new(
+                format!("old-{i}"),
+                format!("new-{i}"),
+                RewriteMetricOperation::Amend,
+            )));
+        }
+        assert!(applied.insert(first));
+    }
+
     #[test]
     fn rewrite_metric_operation_strings_are_stable() {
         assert_eq!(RewriteMetricOperation::Rebase.as_str(), "rebase");
@@ -0,0 +1,299 @@
+//! Attribution for lines that `git rebase --exec` steps change.
+//!
+//! An exec step (a formatter, an AI fixer, a script that amends the commit)
+//! rewrites a commit after it is picked. Those lines come from neither the
+//! original commit nor the commits it was rebased onto, so the shifted note of
+//! the original must not claim them. A line of a rewritten commit is an exec
+//! step change when it is added relative to its parent *and* relative to every
+//! original commit mapped onto it. Claims the originals' notes carried onto
+//! such lines are dropped, and the lines are credited to the tool named by
+//! `rebase_exec_author` in `.git-ai.toml`, or left to the human otherwise.
+//! Attribution that the exec step recorded itself (an agent with checkpoints
+//! amending the commit) is kept.
+
+use crate::authorship::authorship_log::{LineRange, SessionRecord};
+use crate::authorship::authorship_log_serialization::{
+    AttestationEntry, AuthorshipLog, generate_session_id,
+};
+use crate::authorship::rewrite::{compute_diff_trees_batch, get_commit_parents_batch};
+use crate::authorship::working_log::AgentId;
+use crate::error::GitAiError;
+use crate::git::notes_api;
+use crate::git::repository::Repository;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Agent id of the session that exec step changes are credited to.
+pub const REBASE_EXEC_AGENT_ID: &str = "rebase-exec";
+
+/// Re-attribute exec step changes in the rewritten side of `mappings`
+/// (original -> rewritten commit pairs). Returns the number of lines found.
+pub fn attribute_rebase_exec_changes(
+    repo: &Repository,
+    mappings: &[(String, String)],
+    tool: Option<&str>,
+) -> Result<usize, GitAiError> {
+    let mut originals_by_rewritten: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (original, rewritten) in mappings {
+        originals_by_rewritten
+            .entry(rewritten.as_str())
+            .or_default()
+            .push(original.as_str());
+    }
+    if originals_by_rewritten.is_empty() {
+        return Ok(0);
+    }
+
+    let rewritten: Vec<String> = originals_by_rewritten
+        .keys()
+        .map(|sha| sha.to_string())
+        .collect();
+    let parents = get_commit_parents_batch(repo, &rewritten);
+
+    // Per rewritten commit: its parent diff first, then one diff per original.
+    let mut diff_pairs: Vec<(String, String)> = Vec::new();
+    let mut pair_groups: Vec<(&str, std::ops::Range<usize>)> = Vec::new();
+    for (rewritten_sha, originals) in &originals_by_rewritten {
+        let Some(parent) = parents
+            .get(*rewritten_sha)
+            .and_then(|parents| parents.first())
+        else {
+            continue;
+        };
+        let start = diff_pairs.len();
+        diff_pairs.push((parent.clone(), rewritten_sha.to_string()));
+        for original in originals {
+            diff_pairs.push((original.to_string(), rewritten_sha.to_string()));
+        }
+        pair_groups.push((rewritten_sha, start..diff_pairs.len()));
+    }
+    if diff_pairs.is_empty() {
+        return Ok(0);
+    }
+    let diffs = compute_diff_trees_batch(repo, &diff_pairs)?;
+
+    let mut exec_lines_by_commit: Vec<(String, BTreeMap<String, Vec<u32>>)> = Vec::new();
+    for (rewritten_sha, range) in pair_groups {
+        let vs_parent = &diffs[range.start].added_lines_by_file;
+        let vs_originals: Vec<&HashMap<String, Vec<u32>>> = diffs[range.start + 1..range.end]
+            .iter()
+            .map(|diff| &diff.added_lines_by_file)
+            .collect();
+        let lines = exec_step_lines(vs_parent, &vs_originals);
+        if !lines.is_empty() {
+            exec_lines_by_commit.push((rewritten_sha.to_string(), lines));
+        }
+    }
+    if exec_lines_by_commit.is_empty() {
+        return Ok(0);
+    }
+
+    let mut shas: Vec<String> = exec_lines_by_commit
+        .iter()
+        .map(|(sha, _)| sha.clone())
+        .collect();
+    for (sha, _) in &exec_lines_by_commit {
+        if let Some(originals) = originals_by_rewritten.get(sha.as_str()) {
+            shas.extend(originals.iter().map(|original| original.to_string()));
+        }
+    }
+    let notes = notes_api::read_notes_batch(repo, &shas)?;
+
+    let mut total = 0usize;
+    let mut writes: Vec<(String, String)> = Vec::new();
+    for (sha, lines) in exec_lines_by_commit {
+        total += lines.values().map(Vec::len).sum::<usize>();
+        let existing = notes.get(&sha);
+        let mut log = match existing.map(|raw| AuthorshipLog::deserialize_from_string(raw)) {
+            Some(Ok(log)) => log,
+            Some(Err(_)) => continue,
+            // Without a note there is no prior claim to take back, and human
+            // lines need no record.
+            None if tool.is_none() => continue,
+            None => {
+                let mut log = AuthorshipLog::new();
+                log.metadata.base_commit_sha = sha.clone();
+                log
+            }
+        };
+
+        let prior_hashes: HashSet<String> = originals_by_rewritten
+            .get(sha.as_str())
+            .into_iter()
+            .flatten()
+            .filter_map(|original| notes.get(*original))
+            .filter_map(|raw| AuthorshipLog::deserialize_from_string(raw).ok())
+            .flat_map(|original_log| original_log.attestations)
+            .flat_map(|attestation| attestation.entries)
+            .map(|entry| entry.hash)
+            .collect();
+
+        credit_exec_step_lines(&mut log, &lines, &prior_hashes, tool);
+        let serialized = log.serialize_to_string().map_err(|e| {
+            GitAiError::Generic(format!("failed to serialize exec step authorship: {}", e))
+        })?;
+        if existing != Some(&serialized) {
+            writes.push((sha, serialized));
+        }
+    }
+    notes_api::write_notes_batch(repo, &writes)?;
+
+    tracing::debug!(
+        "rebase --exec: {} changed lines across {} commits",
+        total,
+        writes.len()
+    );
+    Ok(total)
+}
+
+/// Lines added relative to the parent that are also added relative to every
+/// original commit, by file.
+fn exec_step_lines(
+    vs_parent: &HashMap<String, Vec<u32>>,
+    vs_originals: &[&HashMap<String, Vec<u32>>],
+) -> BTreeMap<String, Vec<u32>> {
+    let mut result = BTreeMap::new();
+    if vs_originals.is_empty() {
+        return result;
+    }
+    for (file, parent_added) in vs_parent {
+        let mut lines: BTreeSet<u32> = parent_added.iter().copied().collect();
+        for original_added in vs_originals {
+            let original_added: BTreeSet<u32> = original_added
+                .get(file)
+                .map(|lines| lines.iter().copied().collect())
+                .unwrap_or_default();
+            lines.retain(|line| original_added.contains(line));
+        }
+        if !lines.is_empty() {
+            result.insert(file.clone(), lines.into_iter().collect());
+        }
+    }
+    result
+}
+
+/// Drop the claims of `prior_hashes` on `lines` and, when `tool` is set,
+/// attest them to that tool's exec session.
+fn credit_exec_step_lines(
+    log: &mut AuthorshipLog,
+    lines: &BTreeMap<String, Vec<u32>>,
+    prior_hashes: &HashSet<String>,
+    tool: Option<&str>,
+) {
+    for (file, file_lines) in lines {
+        let exec_ranges = LineRange::compress_lines(file_lines);
+        let mut still_claimed: Vec<LineRange> = Vec::new();
+        if let Some(attestation) = log
+            .attestations
+            .iter_mut()
+            .find(|attestation| attestation.file_path == *file)
+        {
+            for entry in &mut attestation.entries {
+                if prior_hashes.contains(&entry.hash) {
+                    entry.line_ranges = subtract_ranges(&entry.line_ranges, &exec_ranges);
+                } else {
+                    still_claimed.extend(entry.line_ranges.iter().cloned());
+                }
+            }
+            attestation
+                .entries
+                .retain(|entry| !entry.line_ranges.is_empty());
+        }
+
+        let unclaimed = subtract_ranges(&exec_ranges, &still_claimed);
+        if let Some(tool) = tool
+            && !unclaimed.is_empty()
+        {
+            let session_id = generate_session_id(REBASE_EXEC_AGENT_ID, tool);
+            log.get_or_create_file(file)
+                .entries
+                .push(AttestationEntry::new(session_id.clone(), unclaimed));
+            log.metadata
+                .sessions
+                .entry(session_id)
+                .or_insert_with(|| SessionRecord {
+                    agent_id: AgentId {
+                        tool: tool.to_string(),
+                        id: REBASE_EXEC_AGENT_ID.to_string(),
+                        model: "unknown".to_string(),
+                    },
+                    human_author: None,
+                    custom_attributes: None,
+                    tool_calls: BTreeMap::new(),
+                    subagents: BTreeMap::new(),
+                    tool_versions: BTreeMap::new(),
+                });
+        }
+    }
+    log.attestations
+        .retain(|attestation| !attestation.entries.is_empty());
+}
+
+fn subtract_ranges(ranges: &[LineRange], removed: &[LineRange]) -> Vec<LineRange> {
+    removed.iter().fold(ranges.to_vec(), |remaining, claim| {
+        remaining
+            .iter()
+            .flat_map(|range| range.remove(claim))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exec_lines_are_new_to_parent_and_originals() {
+        let vs_parent = HashMap::from([
+            ("src/a.rs".to_string(), vec![1, 2, 3, 7]),
+            ("src/b.rs".to_string(), vec![4]),
+        ]);
+        // Lines 1-3 of a.rs came from the original commit; 7 did not.
+        let vs_original = HashMap::from([
+            ("src/a.rs".to_string(), vec![7, 9]),
+            ("src/b.rs".to_string(), vec![4]),
+        ]);
+
+        let lines = exec_step_lines(&vs_parent, &[&vs_original]);
+        assert_eq!(
+            lines,
+            BTreeMap::from([
+                ("src/a.rs".to_string(), vec![7]),
+                ("src/b.rs".to_string(), vec![4]),
+            ])
+        );
+        assert!(exec_step_lines(&vs_parent, &[&vs_original, &HashMap::new()]).is_empty());
+    }
+
+    #[test]
+    fn exec_lines_replace_prior_claims() {
+        let mut log = AuthorshipLog::new();
+        let file = log.get_or_create_file("src/a.rs");
+        file.entries.push(AttestationEntry::new(
+            "s_aaaaaaaaaaaaaa".to_string(),
+            vec![LineRange::Range(1, 5)],
+        ));
+        // Recorded by the exec step itself, not carried from the original.
+        file.entries.push(AttestationEntry::new(
+            "s_bbbbbbbbbbbbbb".to_string(),
+            vec![LineRange::Single(3)],
+        ));
+        let lines = BTreeMap::from([("src/a.rs".to_string(), vec![2, 3])]);
+        let prior = HashSet::from(["s_aaaaaaaaaaaaaa".to_string()]);
+
+        let mut human = log.clone();
+        credit_exec_step_lines(&mut human, &lines, &prior, None);
+        let entries = &human.attestations[0].entries;
+        assert_eq!(
+            entries[0].line_ranges,
+            vec![LineRange::Single(1), LineRange::Range(4, 5)]
+        );
+        assert_eq!(entries[1].line_ranges, vec![LineRange::Single(3)]);
+
+        credit_exec_step_lines(&mut log, &lines, &prior, Some("prettier"));
+        let session_id = generate_session_id(REBASE_EXEC_AGENT_ID, "prettier");
+        let exec_entry = &log.attestations[0].entries[2];
+        assert_eq!(exec_entry.hash, session_id);
+        assert_eq!(exec_entry.line_ranges, vec![LineRange::Single(2)]);
+        assert_eq!(log.metadata.sessions[&session_id].agent_id.tool, "prettier");
+    }
+}
@@ -0,0 +1,327 @@
+//! Original -> rewritten commit pairs for rebases that keep merges
+//! (`git rebase --rebase-merges`, `git pull --rebase=merges`).
+//!
+//! range-diff linearizes both sides and ignores merge commits, so when side
+//! branches are replayed the pairs it finds can cross branches, and merges are
+//! left to a parent-set match. Here both sides are walked in topological order
+//! instead: a rewritten commit is paired with an original of the same stable
+//! patch-id, preferring the one whose parent corresponds to its own parent, and
+//! a rewritten merge is paired with the original merge whose parents all
+//! correspond to its parents. Commits whose patch changed during the rebase
+//! (conflict resolutions, `fixup`s) keep the range-diff pair.
+
+use crate::authorship::rewrite_cherry_pick::stable_patch_ids_for_commits;
+use crate::error::GitAiError;
+use crate::git::repository::{Repository, exec_git_allow_nonzero};
+use std::collections::{HashMap, HashSet};
+
+/// A commit of a rewritten range with its parents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TopoCommit {
+    pub sha: String,
+    pub parents: Vec<String>,
+}
+
+impl TopoCommit {
+    fn is_merge(&self) -> bool {
+        self.parents.len() > 1
+    }
+}
+
+/// Pairs for a rebase of `old_base..old_tip` onto `new_base..new_tip` that kept
+/// merges. `range_diff_pairs` are used for commits no patch-id pairs.
+pub(crate) fn derive_topology_mappings(
+    repo: &Repository,
+    old_base: &str,
+    old_tip: &str,
+    new_base: &str,
+    new_tip: &str,
+    range_diff_pairs: &[(String, String)],
+) -> Result<Vec<(String, String)>, GitAiError> {
+    let old_commits = list_topo_commits(repo, old_base, old_tip)?;
+    let new_commits = list_topo_commits(repo, new_base, new_tip)?;
+    let patch_shas: Vec<String> = old_commits
+        .iter()
+        .chain(new_commits.iter())
+        .filter(|commit| !commit.is_merge())
+        .map(|commit| commit.sha.clone())
+        .collect();
+    let patch_ids = stable_patch_ids_for_commits(repo, &patch_shas)?;
+    Ok(match_topology(
+        &old_commits,
+        &new_commits,
+        &patch_ids,
+        range_diff_pairs,
+    ))
+}
+
+/// Whether `base..tip` contains a merge commit.
+pub(crate) fn range_has_merges(repo: &Repository, base: &str, tip: &str) -> bool {
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "rev-list".to_string(),
+        "--merges".to_string(),
+        "--max-count=1".to_string(),
+        format!("{}..{}", base, tip),
+    ]);
+    exec_git_allow_nonzero(&args)
+        .ok()
+        .filter(|output| output.status.success())
+        .is_some_and(|output| !output.stdout.trim_ascii().is_empty())
+}
+
+fn list_topo_commits(
+    repo: &Repository,
+    base: &str,
+    tip: &str,
+) -> Result<Vec<TopoCommit>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "rev-list".to_string(),
+        "--topo-order".to_string(),
+        "--reverse".to_string(),
+        "--parents".to_string(),
+        format!("{}..{}", base, tip),
+    ]);
+    let output = exec_git_allow_nonzero(&args)?;
+    if !output.status.success() {
+        return Ok(Vec::new());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut shas = line.split_whitespace().map(str::to_string);
+            Some(TopoCommit {
+                sha: shas.next()?,
+                parents: shas.collect(),
+            })
+        })
+        .collect())
+}
+
+/// Pair `new_commits` with `old_commits` (both parents first). A parent outside
+/// its side's range corresponds to a parent outside the other side's range, so
+/// the old base lines up with the commit the rebase replayed onto.
+pub(crate) fn match_topology(
+    old_commits: &[TopoCommit],
+    new_commits: &[TopoCommit],
+    patch_ids: &HashMap<String, String>,
+    range_diff_pairs: &[(String, String)],
+) -> Vec<(String, String)> {
+    let old_in_range: HashSet<&str> = old_commits.iter().map(|c| c.sha.as_str()).collect();
+    let new_in_range: HashSet<&str> = new_commits.iter().map(|c| c.sha.as_str()).collect();
+    let parents_correspond = |old_parent: &str, new_parent: &str, mapped: &HashMap<&str, &str>| {
+        old_parent == new_parent
+            || mapped.get(old_parent) == Some(&new_parent)
+            || (!old_in_range.contains(old_parent) && !new_in_range.contains(new_parent))
+    };
+
+    let mut old_by_patch_id: HashMap<&str, Vec<&TopoCommit>> = HashMap::new();
+    for commit in old_commits.iter().filter(|commit| !commit.is_merge()) {
+        if let Some(patch_id) = patch_ids.get(&commit.sha) {
+            old_by_patch_id
+                .entry(patch_id.as_str())
+                .or_default()
+                .push(commit);
+        }
+    }
+    let old_merges: Vec<&TopoCommit> = old_commits.iter().filter(|c| c.is_merge()).collect();
+    let old_by_sha: HashMap<&str, &TopoCommit> =
+        old_commits.iter().map(|c| (c.sha.as_str(), c)).collect();
+    let new_by_sha: HashMap<&str, &TopoCommit> =
+        new_commits.iter().map(|c| (c.sha.as_str(), c)).collect();
+
+    let mut range_diff_by_new: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (old, new) in range_diff_pairs {
+        range_diff_by_new
+            .entry(new.as_str())
+            .or_default()
+            .push(old.as_str());
+    }
+
+    let mut mapped: HashMap<&str, &str> = HashMap::new();
+    let mut used_new: HashSet<&str> = HashSet::new();
+    let mut pairs: Vec<(String, String)> = Vec::new();
+
+    // Walk back from the tips in lockstep: merges pair parent by parent, and a
+    // walk stops at the first commit whose patch changed. This settles commits
+    // whose patch-id alone is ambiguous (the same change on two branches).
+    let mut stack: Vec<(&str, &str)> = match (old_commits.last(), new_commits.last()) {
+        (Some(old_tip), Some(new_tip)) => vec![(old_tip.sha.as_str(), new_tip.sha.as_str())],
+        _ => Vec::new(),
+    };
+    while let Some((old_sha, new_sha)) = stack.pop() {
+        if mapped.contains_key(old_sha) || used_new.contains(new_sha) {
+            continue;
+        }
+        let (Some(old), Some(new)) = (old_by_sha.get(old_sha), new_by_sha.get(new_sha)) else {
+            continue;
+        };
+        let same_shape = if old.is_merge() || new.is_merge() {
+            old.parents.len() == new.parents.len()
+        } else {
+            patch_ids.get(old_sha).is_some() && patch_ids.get(old_sha) == patch_ids.get(new_sha)
+        };
+        if !same_shape {
+            continue;
+        }
+        mapped.insert(old_sha, new_sha);
+        used_new.insert(new_sha);
+        pairs.push((old.sha.clone(), new.sha.clone()));
+        stack.extend(
+            old.parents
+                .iter()
+                .zip(&new.parents)
+                .map(|(op, np)| (op.as_str(), np.as_str())),
+        );
+    }
+
+    // Then forward in new-side topological order, so a commit's parents are
+    // always settled before it. A commit whose patch changed takes its
+    // range-diff partner here, so merges built on it can still pair.
+    for new_commit in new_commits {
+        if used_new.contains(new_commit.sha.as_str()) {
+            continue;
+        }
+        let candidate = if new_commit.is_merge() {
+            old_merges.iter().copied().find(|old_merge| {
+                !mapped.contains_key(old_merge.sha.as_str())
+                    && old_merge.parents.len() == new_commit.parents.len()
+                    && old_merge
+                        .parents
+                        .iter()
+                        .zip(&new_commit.parents)
+                        .all(|(op, np)| parents_correspond(op, np, &mapped))
+            })
+        } else {
+            let candidates: Vec<&TopoCommit> = patch_ids
+                .get(&new_commit.sha)
+                .and_then(|patch_id| old_by_patch_id.get(patch_id.as_str()))
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|old| !mapped.contains_key(old.sha.as_str()))
+                .collect();
+            let same_parent = candidates.iter().copied().find(|old| {
+                match (old.parents.first(), new_commit.parents.first()) {
+                    (Some(op), Some(np)) => parents_correspond(op, np, &mapped),
+                    _ => false,
+                }
+            });
+            same_parent
+                .or_else(|| candidates.first().copied())
+                .or_else(|| {
+                    range_diff_by_new
+                        .get(new_commit.sha.as_str())
+                        .into_iter()
+                        .flatten()
+                        .filter(|old| !mapped.contains_key(*old))
+                        .find_map(|old| old_by_sha.get(*old).copied())
+                })
+        };
+
+        if let Some(old) = candidate {
+            mapped.insert(old.sha.as_str(), new_commit.sha.as_str());
+            used_new.insert(new_commit.sha.as_str());
+            pairs.push((old.sha.clone(), new_commit.sha.clone()));
+        }
+    }
+
+    // Originals still unpaired were squashed or reworded into a commit that is
+    // already paired; range-diff knows which.
+    let new_merges: HashSet<&str> = new_commits
+        .iter()
+        .filter(|commit| commit.is_merge())
+        .map(|commit| commit.sha.as_str())
+        .collect();
+    for (old, new) in range_diff_pairs {
+        if mapped.contains_key(old.as_str()) || new_merges.contains(new.as_str()) {
+            continue;
+        }
+        if !new_in_range.contains(new.as_str()) {
+            continue;
+        }
+        mapped.insert(old.as_str(), new.as_str());
+        pairs.push((old.clone(), new.clone()));
+    }
+
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(sha: &str, parents: &[&str]) -> TopoCommit {
+        TopoCommit {
+            sha: sha.to_string(),
+            parents: parents.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn identical_patches_on_sibling_branches_follow_topology() {
+        // Original: m merges a1 and b1, both branched off base with the same
+        // patch, so patch-id alone cannot tell them apart; the merge's parent
+        // order can.
+        let old = vec![
+            commit("b1", &["base"]),
+            commit("a1", &["base"]),
+            commit("m", &["a1", "b1"]),
+        ];
+        let new = vec![
+            commit("a1'", &["onto"]),
+            commit("b1'", &["onto"]),
+            commit("m'", &["a1'", "b1'"]),
+        ];
+        let patch_ids = HashMap::from([
+            ("a1".to_string(), "p".to_string()),
+            ("b1".to_string(), "p".to_string()),
+            ("a1'".to_string(), "p".to_string()),
+            ("b1'".to_string(), "p".to_string()),
+        ]);
+
+        let pairs = match_topology(&old, &new, &patch_ids, &[]);
+        assert_eq!(pairs.len(), 3);
+        let merge = pairs.iter().find(|(_, n)| n == "m'").unwrap();
+        assert_eq!(merge.0, "m");
+        let a_new = &pairs.iter().find(|(o, _)| o == "a1").unwrap().1;
+        assert_eq!(a_new, "a1'");
+    }
+
+    #[test]
+    fn rewritten_patches_fall_back_to_range_diff() {
+        let old = vec![
+            commit("a1", &["base"]),
+            commit("b1", &["base"]),
+            commit("m", &["a1", "b1"]),
+        ];
+        let new = vec![
+            commit("a1'", &["onto"]),
+            commit("b1'", &["onto"]),
+            commit("m'", &["a1'", "b1'"]),
+        ];
+        // b1 was changed while resolving a conflict.
+        let patch_ids = HashMap::from([
+            ("a1".to_string(), "pa".to_string()),
+            ("b1".to_string(), "pb".to_string()),
+            ("a1'".to_string(), "pa".to_string()),
+            ("b1'".to_string(), "pb2".to_string()),
+        ]);
+        let range_diff = vec![
+            ("a1".to_string(), "b1'".to_string()),
+            ("b1".to_string(), "b1'".to_string()),
+        ];
+
+        let mut pairs = match_topology(&old, &new, &patch_ids, &range_diff);
+        pairs.sort();
+        assert_eq!(
+            pairs,
+            vec![
+                ("a1".to_string(), "a1'".to_string()),
+                ("b1".to_string(), "b1'".to_string()),
+                ("m".to_string(), "m'".to_string()),
+            ]
+        );
+    }
+}
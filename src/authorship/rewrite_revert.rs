@@ -2,11 +2,10 @@ use std::collections::{HashMap, HashSet};
 
 use crate::authorship::authorship_log::LineRange;
 use crate::authorship::authorship_log_serialization::{
-    AttestationEntry, AuthorshipLog, FileAttestation,
+    AttestationEntry, AuthorshipLog, FileAttestation, RevertRecord,
 };
-use crate::authorship::hunk_shift::apply_hunk_shifts_to_file_attestation;
-use crate::authorship::rewrite::compute_diff_trees_batch;
 use crate::authorship::rewrite::{RewriteMetricCommit, RewriteMetricOperation};
+use crate::authorship::rewrite::{compute_diff_trees_batch, shift_log_through_diff};
 use crate::error::GitAiError;
 use crate::git::notes_api;
 use crate::git::repository::{Repository, exec_git, exec_git_stdin};
@@ -35,6 +34,12 @@ pub(crate) fn handle_revert_commits_with_metrics(
     if specs.is_empty() {
         return Ok(Vec::new());
     }
+    let _span = tracing::info_span!(
+        target: crate::observability::trace::targets::REWRITE,
+        "revert",
+        commits = specs.len()
+    )
+    .entered();
     let collect_metrics = crate::authorship::rewrite::rewrite_metrics_enabled();
 
     // Resolve every spec's parent_sha (only those missing need a lookup) and the
@@ -131,22 +136,31 @@ pub(crate) fn handle_revert_commits_with_metrics(
         return Ok(Vec::new());
     }
 
-    // Batch-read all source notes in one call.
-    let source_base_shas: Vec<String> = {
-        let mut v: Vec<String> = resolved.iter().map(|r| r.source_base_sha.clone()).collect();
+    // Batch-read all source notes, plus the reverted commits' own notes (which
+    // attribute the lines each revert removes), in one call.
+    let note_shas: Vec<String> = {
+        let mut v: Vec<String> = resolved
+            .iter()
+            .flat_map(|r| std::iter::once(&r.source_base_sha).chain(r.original_shas.first()))
+            .cloned()
+            .collect();
         v.sort();
         v.dedup();
         v
     };
-    let notes = notes_api::read_notes_batch(repo, &source_base_shas)?;
+    let notes = notes_api::read_notes_batch(repo, &note_shas)?;
 
     // Build one batched diff-tree request covering, for each reverted commit:
     //  - (source_base, revert_commit): hunks to shift the source note forward,
-    //  - (parent, revert_commit): added lines re-introduced by the revert.
+    //  - (parent, revert_commit): added lines re-introduced by the revert, and
+    //    removed lines taken out by it,
+    //  - (reverted, parent): hunks to shift the reverted commit's note onto the
+    //    parent, where the removed lines are numbered.
     // Track each pair's index so we can read its result back.
     let mut diff_pairs: Vec<(String, String)> = Vec::new();
     let mut shift_idx: Vec<Option<usize>> = Vec::new();
     let mut added_idx: Vec<usize> = Vec::new();
+    let mut removed_shift_idx: Vec<Option<usize>> = Vec::new();
     for r in &resolved {
         // Only need the shift pair if the source note exists.
         let shift = if notes.contains_key(&r.source_base_sha) {
@@ -160,6 +174,15 @@ pub(crate) fn handle_revert_commits_with_metrics(
         let aidx = diff_pairs.len();
         diff_pairs.push((r.parent_sha.clone(), r.revert_commit.clone()));
         added_idx.push(aidx);
+        let removed_shift = match r.original_shas.first() {
+            Some(reverted) if *reverted != r.parent_sha && notes.contains_key(reverted) => {
+                let idx = diff_pairs.len();
+                diff_pairs.push((reverted.clone(), r.parent_sha.clone()));
+                Some(idx)
+            }
+            _ => None,
+        };
+        removed_shift_idx.push(removed_shift);
     }
     let diff_results = compute_diff_trees_batch(repo, &diff_pairs)?;
 
@@ -167,57 +190,59 @@ pub(crate) fn handle_revert_commits_with_metrics(
     let mut writes: Vec<(String, String)> = Vec::new();
     let mut metric_commits: Vec<RewriteMetricCommit> = Vec::new();
     for (i, r) in resolved.iter().enumerate() {
-        let Some(shift) = shift_idx[i] else {
-            continue;
-        };
-        let Some(source_note) = notes.get(&r.source_base_sha) else {
-            continue;
-        };
-        let Ok(mut log) = AuthorshipLog::deserialize_from_string(source_note) else {
-            continue;
-        };
-
-        // Added lines re-introduced by the revert (new-side hunk ranges of the
-        // parent->revert diff), keyed by file.
-        let added_lines = added_lines_from_diff_result(&diff_results[added_idx[i]]);
-        if added_lines.is_empty() {
-            continue;
-        }
+        let parent_diff = &diff_results[added_idx[i]];
+        let revert_record = r
+            .original_shas
+            .first()
+            .and_then(|reverted| notes.get(reverted))
+            .and_then(|note| AuthorshipLog::deserialize_from_string(note).ok())
+            .map(|mut reverted_log| {
+                if let Some(idx) = removed_shift_idx[i] {
+                    shift_log_through_diff(&mut reverted_log, &diff_results[idx]);
+                }
+                removed_lines_record(&reverted_log, parent_diff, &r.original_shas)
+            })
+            .filter(|record| {
+                record.ai_lines_removed + record.human_lines_removed + record.unknown_lines_removed
+                    > 0
+            });
 
-        let shift_result = &diff_results[shift];
-        for (old_path, new_path) in &shift_result.renames {
-            for attestation in &mut log.attestations {
-                if attestation.file_path == *old_path {
-                    attestation.file_path = new_path.clone();
+        let restored = shift_idx[i]
+            .and_then(|shift| {
+                let source_note = notes.get(&r.source_base_sha)?;
+                let log = AuthorshipLog::deserialize_from_string(source_note).ok()?;
+                Some((shift, log))
+            })
+            .and_then(|(shift, mut log)| {
+                // Added lines re-introduced by the revert (new-side hunk ranges of
+                // the parent->revert diff), keyed by file.
+                let added_lines = added_lines_from_diff_result(parent_diff);
+                if added_lines.is_empty() {
+                    return None;
                 }
-            }
-        }
-        if !shift_result.hunks_by_file.is_empty() {
-            log.attestations = log
-                .attestations
-                .iter()
-                .filter_map(|fa| match shift_result.hunks_by_file.get(&fa.file_path) {
-                    Some(hunks) => apply_hunk_shifts_to_file_attestation(fa, hunks),
-                    None => Some(fa.clone()),
-                })
-                .collect();
-        }
+                shift_log_through_diff(&mut log, &diff_results[shift]);
+                log.attestations = log
+                    .attestations
+                    .iter()
+                    .filter_map(|file| clip_file_attestation_to_lines(file, &added_lines))
+                    .collect();
+                (!log.attestations.is_empty()).then_some(log)
+            });
 
+        let has_restored_lines = restored.is_some();
+        let mut log = match (restored, &revert_record) {
+            (Some(log), _) => log,
+            (None, Some(_)) => AuthorshipLog::new(),
+            (None, None) => continue,
+        };
         log.metadata.base_commit_sha = r.revert_commit.clone();
-        log.attestations = log
-            .attestations
-            .iter()
-            .filter_map(|file| clip_file_attestation_to_lines(file, &added_lines))
-            .collect();
-        if log.attestations.is_empty() {
-            continue;
-        }
+        log.metadata.revert = revert_record;
 
         let Ok(note_str) = log.serialize_to_string() else {
             continue;
         };
         writes.push((r.revert_commit.clone(), note_str.clone()));
-        if collect_metrics {
+        if collect_metrics && has_restored_lines {
             metric_commits.push(
                 RewriteMetricCommit::new(
                     r.revert_commit.clone(),
@@ -245,6 +270,50 @@ fn legacy_revert_metric_original_sha(parent_sha: &str) -> Option<String> {
     }
 }
 
+/// Count the lines removed by the parent->revert diff by their attribution in
+/// `reverted_log`, which must already be numbered against the parent tree.
+fn removed_lines_record(
+    reverted_log: &AuthorshipLog,
+    parent_diff: &crate::authorship::rewrite::DiffTreeResult,
+    reverted_commits: &[String],
+) -> RevertRecord {
+    let mut record = RevertRecord {
+        reverted_commits: reverted_commits.to_vec(),
+        ..Default::default()
+    };
+    for (file, hunks) in &parent_diff.hunks_by_file {
+        // Hunks are keyed by the new path; attestations use the parent's path.
+        let parent_path = parent_diff
+            .renames
+            .iter()
+            .find(|(_, new_path)| new_path == file)
+            .map_or(file, |(old_path, _)| old_path);
+        let mut human_by_line: HashMap<u32, bool> = HashMap::new();
+        if let Some(attestation) = reverted_log
+            .attestations
+            .iter()
+            .find(|fa| fa.file_path == *parent_path)
+        {
+            for entry in &attestation.entries {
+                let is_human = entry.hash.starts_with("h_");
+                for line in entry.line_ranges.iter().flat_map(LineRange::expand) {
+                    human_by_line.insert(line, is_human);
+                }
+            }
+        }
+        for hunk in hunks {
+            for line in hunk.old_start..hunk.old_start + hunk.old_count {
+                match human_by_line.get(&line) {
+                    Some(true) => record.human_lines_removed += 1,
+                    Some(false) => record.ai_lines_removed += 1,
+                    None => record.unknown_lines_removed += 1,
+                }
+            }
+        }
+    }
+    record
+}
+
 /// Extract added line numbers per file from a diff-tree result, equivalent to
 /// the new-side coverage `diff_added_lines` would report for the same pair.
 fn added_lines_from_diff_result(
@@ -0,0 +1,587 @@
+//! Attribution for `git subtree` add, merge, pull and split.
+//!
+//! Subtree commands move history between a project and a directory of another
+//! project, so the same lines live at `lib.rs` on one side and `vendor/lib/lib.rs`
+//! on the other and plain note shifting loses them. Subtree commits are
+//! recognised by the `git-subtree-dir:`/`git-subtree-split:` trailers git writes
+//! into their messages (falling back to the merged parent and the command's
+//! `--prefix`), and notes are carried across with every file path remapped by
+//! the prefix:
+//!
+//! - add/merge/pull: the notes of the incoming subtree commits are shifted onto
+//!   the merged `<prefix>/` directory and recorded on the merge commit.
+//! - split: each synthesized split commit is paired with the mainline commit it
+//!   was copied from and receives that commit's note with the prefix stripped.
+
+use std::collections::HashMap;
+
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::rewrite::{
+    find_merge_base, list_commits_in_range, read_source_notes, reconstruct_log_from_sources,
+};
+use crate::error::GitAiError;
+use crate::git::log_records::log_records;
+use crate::git::notes_api;
+use crate::git::repo_storage::SubtreeSplitRecord;
+use crate::git::repository::{Repository, exec_git, exec_git_allow_nonzero};
+
+const SUBTREE_DIR_TRAILER: &str = "git-subtree-dir:";
+const SUBTREE_SPLIT_TRAILER: &str = "git-subtree-split:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubtreeOperation {
+    Add,
+    Merge,
+    Pull,
+    Split,
+}
+
+/// The parts of a `git subtree` command line attribution transfer needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubtreeInvocation {
+    pub operation: SubtreeOperation,
+    /// `--prefix`, without a trailing slash.
+    pub prefix: String,
+    /// `split -b/--branch`: the branch the split history was written to.
+    pub branch: Option<String>,
+    /// `split --rejoin`: the split history was merged back into `HEAD`.
+    pub rejoin: bool,
+    /// `split [<commit>]`: the mainline commit that was split.
+    pub commit: Option<String>,
+}
+
+impl SubtreeInvocation {
+    /// Parse `git subtree <subcommand> ...`. `None` for other commands, for
+    /// subcommands that do not create commits, or without a prefix.
+    pub fn parse(command: Option<&str>, args: &[String]) -> Option<Self> {
+        if command != Some("subtree") {
+            return None;
+        }
+        let mut operation = None;
+        let mut prefix = None;
+        let mut branch = None;
+        let mut rejoin = false;
+        let mut positionals = Vec::new();
+
+        let mut i = 0;
+        while i < args.len() {
+            let arg = args[i].as_str();
+            if let Some(value) = arg.strip_prefix("--prefix=") {
+                prefix = Some(value.to_string());
+            } else if let Some(value) = arg.strip_prefix("--branch=") {
+                branch = Some(value.to_string());
+            } else if matches!(arg, "-P" | "--prefix" | "-b" | "--branch") {
+                let value = args.get(i + 1).cloned();
+                if matches!(arg, "-P" | "--prefix") {
+                    prefix = value;
+                } else {
+                    branch = value;
+                }
+                i += 1;
+            } else if matches!(arg, "-m" | "--message" | "--onto" | "--annotate") {
+                i += 1;
+            } else if arg == "--rejoin" {
+                rejoin = true;
+            } else if !arg.starts_with('-') {
+                if operation.is_none() {
+                    operation = Some(match arg {
+                        "add" => SubtreeOperation::Add,
+                        "merge" => SubtreeOperation::Merge,
+                        "pull" => SubtreeOperation::Pull,
+                        "split" => SubtreeOperation::Split,
+                        _ => return None,
+                    });
+                } else {
+                    positionals.push(arg.to_string());
+                }
+            }
+            i += 1;
+        }
+
+        let prefix = normalize_prefix(&prefix?);
+        if prefix.is_empty() {
+            return None;
+        }
+        let operation = operation?;
+        Some(Self {
+            operation,
+            prefix,
+            branch,
+            rejoin,
+            commit: (operation == SubtreeOperation::Split)
+                .then(|| positionals.into_iter().next())
+                .flatten(),
+        })
+    }
+}
+
+fn normalize_prefix(prefix: &str) -> String {
+    prefix.trim_matches('/').to_string()
+}
+
+/// Transfer attribution for a completed subtree command that left `HEAD` at
+/// `head_sha`.
+pub fn handle_subtree_command(
+    repo: &Repository,
+    invocation: &SubtreeInvocation,
+    head_sha: &str,
+) -> Result<(), GitAiError> {
+    match invocation.operation {
+        SubtreeOperation::Add | SubtreeOperation::Merge | SubtreeOperation::Pull => {
+            transfer_into_subtree(repo, &invocation.prefix, head_sha)
+        }
+        SubtreeOperation::Split => {
+            let mainline = invocation.commit.as_deref().unwrap_or(head_sha);
+            let Some(mainline) = resolve_commit(repo, mainline) else {
+                return Ok(());
+            };
+            transfer_out_of_subtree(repo, invocation, &mainline, head_sha)
+        }
+    }
+}
+
+/// Value of `trailer` in `message`, from its last occurrence.
+fn trailer_value(message: &str, trailer: &str) -> Option<String> {
+    message
+        .lines()
+        .rev()
+        .find_map(|line| line.trim().strip_prefix(trailer))
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn commit_message(repo: &Repository, rev: &str) -> Option<String> {
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "log".to_string(),
+        "-1".to_string(),
+        "--format=%B".to_string(),
+        rev.to_string(),
+        "--".to_string(),
+    ]);
+    exec_git(&args)
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+}
+
+/// Full sha of `rev`, or `None` if it does not name an object in `repo`.
+fn resolve_commit(repo: &Repository, rev: &str) -> Option<String> {
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "rev-parse".to_string(),
+        "--verify".to_string(),
+        "--quiet".to_string(),
+        format!("{}^{{commit}}", rev),
+    ]);
+    let output = exec_git_allow_nonzero(&args).ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let sha = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!sha.is_empty()).then_some(sha)
+}
+
+fn commit_parents(repo: &Repository, commit_sha: &str) -> Vec<String> {
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "rev-list".to_string(),
+        "--parents".to_string(),
+        "-n1".to_string(),
+        commit_sha.to_string(),
+    ]);
+    exec_git(&args)
+        .ok()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .split_whitespace()
+                .skip(1)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The subtree history merged by `merge_sha` and the commit to start reading
+/// notes after, if only part of it is new.
+///
+/// Squashed merges point at a squash commit whose own trailer names the real
+/// split; that one is used when it is available locally.
+fn subtree_merge_source(repo: &Repository, merge_sha: &str) -> Option<(String, Option<String>)> {
+    let message = commit_message(repo, merge_sha)?;
+    let parents = commit_parents(repo, merge_sha);
+    let mainline = parents.first().cloned();
+
+    let merged = match trailer_value(&message, SUBTREE_SPLIT_TRAILER) {
+        Some(split) => resolve_commit(repo, &split)?,
+        None => parents.get(1).cloned()?,
+    };
+
+    if let Some(squash_message) = commit_message(repo, &merged)
+        && squash_message.starts_with("Squashed '")
+        && let Some(original) = trailer_value(&squash_message, SUBTREE_SPLIT_TRAILER)
+            .and_then(|split| resolve_commit(repo, &split))
+    {
+        // "Squashed 'dir/' changes from <old>..<new>" names the previous split.
+        let previous = squash_message
+            .lines()
+            .next()
+            .and_then(|subject| subject.rsplit_once(" changes from "))
+            .and_then(|(_, range)| range.split_once(".."))
+            .and_then(|(old, _)| resolve_commit(repo, old.trim()));
+        return Some((original, previous));
+    }
+
+    let base = mainline.and_then(|mainline| find_merge_base(repo, &mainline, &merged));
+    Some((merged, base))
+}
+
+/// Carry the notes of the subtree commits merged by `merge_sha` onto its
+/// `prefix/` directory.
+fn transfer_into_subtree(
+    repo: &Repository,
+    prefix: &str,
+    merge_sha: &str,
+) -> Result<(), GitAiError> {
+    let prefix = commit_message(repo, merge_sha)
+        .and_then(|message| trailer_value(&message, SUBTREE_DIR_TRAILER))
+        .map(|dir| normalize_prefix(&dir))
+        .unwrap_or_else(|| prefix.to_string());
+    let target = format!("{}:{}", merge_sha, prefix);
+    if resolve_tree(repo, &target).is_none() {
+        return Ok(());
+    }
+    let Some((source_head, base)) = subtree_merge_source(repo, merge_sha) else {
+        return Ok(());
+    };
+
+    let sources = match base {
+        Some(base) => list_commits_in_range(repo, &base, &source_head),
+        None => list_all_commits(repo, &source_head),
+    };
+    if sources.is_empty() {
+        return Ok(());
+    }
+    crate::git::sync_authorship::fetch_missing_notes_for_commits(repo, &sources)?;
+    let source_notes = read_source_notes(repo, &sources)?;
+    if source_notes.is_empty() {
+        return Ok(());
+    }
+
+    let Some(mut log) = reconstruct_log_from_sources(repo, source_notes, &source_head, &target)?
+    else {
+        return Ok(());
+    };
+    prefix_paths(&mut log, prefix.as_str());
+    log.metadata.base_commit_sha = merge_sha.to_string();
+
+    let existing = notes_api::read_notes_batch(repo, &[merge_sha.to_string()])?
+        .get(merge_sha)
+        .and_then(|raw| AuthorshipLog::deserialize_from_string(raw).ok())
+        .filter(|existing| !existing.attestations.is_empty());
+    let log = match existing {
+        Some(existing) => {
+            crate::authorship::conflict_resolution::merge_conflict_resolution_authorship(
+                Some(log),
+                existing,
+                merge_sha,
+            )
+        }
+        None => log,
+    };
+    write_note(repo, merge_sha, &log)
+}
+
+/// The split history `invocation` produced: the `-b` branch, the split merged
+/// back by `--rejoin`, the split recorded the last time this prefix was split
+/// at `mainline`, or else a rerun of the (deterministic) split, recorded for
+/// next time.
+fn resolve_split_head(
+    repo: &Repository,
+    invocation: &SubtreeInvocation,
+    mainline: &str,
+    head_sha: &str,
+) -> Result<Option<String>, GitAiError> {
+    if let Some(branch) = invocation.branch.as_deref() {
+        return Ok(resolve_commit(repo, branch));
+    }
+    if invocation.rejoin
+        && let Some(split) = commit_message(repo, head_sha)
+            .and_then(|message| trailer_value(&message, SUBTREE_SPLIT_TRAILER))
+    {
+        return Ok(resolve_commit(repo, &split));
+    }
+    if let Some(split) = repo
+        .storage
+        .read_subtree_split(&invocation.prefix, mainline)
+        .and_then(|split| resolve_commit(repo, &split))
+    {
+        return Ok(Some(split));
+    }
+
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "subtree".to_string(),
+        "split".to_string(),
+        format!("--prefix={}", invocation.prefix),
+        "--quiet".to_string(),
+        mainline.to_string(),
+    ]);
+    let output = exec_git_allow_nonzero(&args)?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    let split = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if split.is_empty() {
+        return Ok(None);
+    }
+    if let Err(e) = repo.storage.record_subtree_split(&SubtreeSplitRecord {
+        prefix: invocation.prefix.clone(),
+        mainline: mainline.to_string(),
+        split: split.clone(),
+    }) {
+        tracing::debug!("failed to record subtree split: {}", e);
+    }
+    Ok(Some(split))
+}
+
+/// Give each split commit without a note the note of the mainline commit it
+/// was copied from, restricted to `prefix/` and with the prefix stripped.
+///
+/// `git subtree split` copies author, committer and message verbatim, so a
+/// split commit is paired with the mainline commit touching `prefix/` that has
+/// the same identities, dates and message. The split history is only resolved
+/// once some of those mainline commits are known to carry a note.
+fn transfer_out_of_subtree(
+    repo: &Repository,
+    invocation: &SubtreeInvocation,
+    mainline: &str,
+    head_sha: &str,
+) -> Result<(), GitAiError> {
+    let prefix = invocation.prefix.as_str();
+    let mainline_commits = commit_idents(repo, mainline, Some(prefix))?;
+    let mainline_shas: Vec<String> = mainline_commits
+        .iter()
+        .map(|(sha, _)| sha.clone())
+        .collect();
+    let mainline_notes = notes_api::read_notes_batch(repo, &mainline_shas)?;
+    if mainline_notes.is_empty() {
+        return Ok(());
+    }
+    let mainline_by_ident: HashMap<String, String> = mainline_commits
+        .into_iter()
+        .filter(|(sha, _)| mainline_notes.contains_key(sha))
+        .map(|(sha, ident)| (ident, sha))
+        .collect();
+
+    let Some(split_head) = resolve_split_head(repo, invocation, mainline, head_sha)? else {
+        return Ok(());
+    };
+    let split_commits = commit_idents(repo, &split_head, None)?;
+    let split_shas: Vec<String> = split_commits.iter().map(|(sha, _)| sha.clone()).collect();
+    let existing = notes_api::read_notes_batch(repo, &split_shas)?;
+    let pairs: Vec<(String, String)> = split_commits
+        .into_iter()
+        .filter(|(sha, _)| !existing.contains_key(sha))
+        .filter_map(|(split_sha, ident)| {
+            mainline_by_ident
+                .get(&ident)
+                .map(|mainline_sha| (mainline_sha.clone(), split_sha))
+        })
+        .collect();
+    if pairs.is_empty() {
+        return Ok(());
+    }
+
+    let mut entries = Vec::new();
+    for (mainline_sha, split_sha) in pairs {
+        let Some(mut log) = mainline_notes
+            .get(&mainline_sha)
+            .and_then(|raw| AuthorshipLog::deserialize_from_string(raw).ok())
+        else {
+            continue;
+        };
+        strip_prefix_paths(&mut log, prefix);
+        if log.attestations.is_empty() {
+            continue;
+        }
+        log.metadata.base_commit_sha = split_sha.clone();
+        let serialized = log.serialize_to_string().map_err(|e| {
+            GitAiError::Generic(format!("failed to serialize subtree authorship log: {}", e))
+        })?;
+        entries.push((split_sha, serialized));
+    }
+    notes_api::write_notes_batch(repo, &entries)
+}
+
+/// `(sha, identity)` of the commits reachable from `tip`, limited to those
+/// touching `path` if given. The identity covers author, committer, both dates
+/// and the message.
+fn commit_idents(
+    repo: &Repository,
+    tip: &str,
+    path: Option<&str>,
+) -> Result<Vec<(String, String)>, GitAiError> {
+    let mut log_args = vec![tip.to_string(), "--".to_string()];
+    log_args.extend(path.map(str::to_string));
+    let records = log_records(
+        repo,
+        &["%H", "%an", "%ae", "%at", "%cn", "%ce", "%ct", "%B"],
+        &log_args,
+    )?;
+    Ok(records
+        .into_iter()
+        .map(|fields| {
+            (
+                fields[0].clone(),
+                fields[1..].join("\u{1f}").trim_end().to_string(),
+            )
+        })
+        .collect())
+}
+
+fn list_all_commits(repo: &Repository, tip: &str) -> Vec<String> {
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "rev-list".to_string(),
+        "--reverse".to_string(),
+        tip.to_string(),
+    ]);
+    exec_git(&args)
+        .ok()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(str::to_string)
+                .filter(|line| !line.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn resolve_tree(repo: &Repository, rev: &str) -> Option<String> {
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "rev-parse".to_string(),
+        "--verify".to_string(),
+        "--quiet".to_string(),
+        format!("{}^{{tree}}", rev),
+    ]);
+    let output = exec_git_allow_nonzero(&args).ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn write_note(repo: &Repository, commit_sha: &str, log: &AuthorshipLog) -> Result<(), GitAiError> {
+    let serialized = log.serialize_to_string().map_err(|e| {
+        GitAiError::Generic(format!("failed to serialize subtree authorship log: {}", e))
+    })?;
+    notes_api::write_notes_batch(repo, &[(commit_sha.to_string(), serialized)])
+}
+
+/// Move every attested file under `prefix/`.
+pub(crate) fn prefix_paths(log: &mut AuthorshipLog, prefix: &str) {
+    for attestation in &mut log.attestations {
+        attestation.file_path = format!("{}/{}", prefix, attestation.file_path);
+    }
+}
+
+/// Keep only the files under `prefix/`, with the prefix removed.
+pub(crate) fn strip_prefix_paths(log: &mut AuthorshipLog, prefix: &str) {
+    let dir = format!("{}/", prefix);
+    log.attestations.retain_mut(
+        |attestation| match attestation.file_path.strip_prefix(&dir) {
+            Some(path) => {
+                attestation.file_path = path.to_string();
+                true
+            }
+            None => false,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::authorship_log_serialization::FileAttestation;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_reads_operation_prefix_and_split_options() {
+        let add = SubtreeInvocation::parse(
+            Some("subtree"),
+            &args(&["add", "--prefix=vendor/lib/", "../lib", "main", "--squash"]),
+        )
+        .unwrap();
+        assert_eq!(add.operation, SubtreeOperation::Add);
+        assert_eq!(add.prefix, "vendor/lib");
+        assert_eq!(add.commit, None);
+
+        let split = SubtreeInvocation::parse(
+            Some("subtree"),
+            &args(&[
+                "split",
+                "-P",
+                "vendor/lib",
+                "-b",
+                "lib-only",
+                "--rejoin",
+                "v1",
+            ]),
+        )
+        .unwrap();
+        assert_eq!(split.operation, SubtreeOperation::Split);
+        assert_eq!(split.prefix, "vendor/lib");
+        assert_eq!(split.branch.as_deref(), Some("lib-only"));
+        assert!(split.rejoin);
+        assert_eq!(split.commit.as_deref(), Some("v1"));
+
+        assert!(SubtreeInvocation::parse(Some("subtree"), &args(&["push", "-P", "x"])).is_none());
+        assert!(SubtreeInvocation::parse(Some("subtree"), &args(&["add", "../lib"])).is_none());
+        assert!(SubtreeInvocation::parse(Some("merge"), &args(&["add", "-P", "x"])).is_none());
+    }
+
+    #[test]
+    fn trailer_value_reads_last_subtree_trailer() {
+        let message = "Squashed 'lib/' content from commit 1234567\n\n\
+                       git-subtree-dir: lib\n\
+                       git-subtree-split: 1234567890abcdef\n";
+        assert_eq!(
+            trailer_value(message, SUBTREE_DIR_TRAILER).as_deref(),
+            Some("lib")
+        );
+        assert_eq!(
+            trailer_value(message, SUBTREE_SPLIT_TRAILER).as_deref(),
+            Some("1234567890abcdef")
+        );
+        assert_eq!(
+            trailer_value("Merge commit 'abc'", SUBTREE_DIR_TRAILER),
+            None
+        );
+    }
+
+    #[test]
+    fn prefix_and_strip_round_trip_and_drop_outside_files() {
+        let mut log = AuthorshipLog::new();
+        log.attestations
+            .push(FileAttestation::new("src/lib.rs".to_string()));
+        prefix_paths(&mut log, "vendor/lib");
+        assert_eq!(log.attestations[0].file_path, "vendor/lib/src/lib.rs");
+
+        log.attestations
+            .push(FileAttestation::new("README.md".to_string()));
+        log.attestations
+            .push(FileAttestation::new("vendor/library/x.rs".to_string()));
+        strip_prefix_paths(&mut log, "vendor/lib");
+        let paths: Vec<&str> = log
+            .attestations
+            .iter()
+            .map(|attestation| attestation.file_path.as_str())
+            .collect();
+        assert_eq!(paths, vec!["src/lib.rs"]);
+    }
+}
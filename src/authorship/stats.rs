@@ -1,12 +1,17 @@
-use crate::authorship::authorship_log::LineRange;
+use crate::authorship::authorship_log::{LineRange, SessionRecord};
+use crate::authorship::authorship_log_serialization::{AuthorshipLog, CheckpointLabelRecord};
+use crate::authorship::format_detection::drop_whitespace_only_changes;
 use crate::authorship::ignore::{build_ignore_matcher, should_ignore_file_with_matcher};
+use crate::authorship::test_paths::{TestPathMatcher, test_path_matcher_for_repo};
+use crate::authorship::working_log::SubagentInfo;
 use crate::error::GitAiError;
 use crate::git::notes_api::read_authorship;
 use crate::git::repository::{Repository, exec_git};
 use crate::mdm::spinner::Spinner;
 use crate::utils::is_interactive_terminal;
+use crate::workspace_config::{NormalizationConfig, WhitespaceMode, effective_workspace_config};
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 const RECENT_COMMIT_WINDOW: Duration = Duration::from_secs(60);
@@ -38,57 +43,427 @@ pub struct CommitStats {
     pub git_diff_added_lines: u32,
     #[serde(default)]
     pub tool_model_breakdown: BTreeMap<String, ToolModelHeadlineStats>,
+    /// The share of the additions above that landed in test files.
+    #[serde(default, skip_serializing_if = "TestCodeStats::is_empty")]
+    pub test_code: TestCodeStats,
+}
+
+/// Added lines in files matching the repository's test globs. Everything else
+/// in the commit is production code.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct TestCodeStats {
+    #[serde(default)]
+    pub ai_additions: u32,
+    #[serde(default)]
+    pub human_additions: u32,
+    #[serde(default)]
+    pub unknown_additions: u32,
+}
+
+impl TestCodeStats {
+    pub fn is_empty(&self) -> bool {
+        self.total_additions() == 0
+    }
+
+    pub fn total_additions(&self) -> u32 {
+        self.ai_additions + self.human_additions + self.unknown_additions
+    }
+}
+
+/// Per-file authorship churn for a single commit, reported by `stats --by-file`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct FileStats {
+    #[serde(default)]
+    pub ai_additions: u32,
+    #[serde(default)]
+    pub human_additions: u32,
+    #[serde(default)]
+    pub unknown_additions: u32,
+    #[serde(default)]
+    pub ai_deletions: u32, // Deleted lines that were last written by AI
+    #[serde(default)]
+    pub human_deletions: u32, // Deleted lines that were not attributed to AI
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_test: bool,
+}
+
+/// JSON shape for `stats --by-file`: the usual commit stats plus a per-file breakdown.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitStatsByFile {
+    #[serde(flatten)]
+    pub stats: CommitStats,
+    #[serde(default)]
+    pub files: BTreeMap<String, FileStats>,
+}
+
+/// Output format of `git-ai stats`: `--format text|json|markdown`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsFormat {
+    Text,
+    Json,
+    Markdown,
+}
+
+impl StatsFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "text" => Some(StatsFormat::Text),
+            "json" => Some(StatsFormat::Json),
+            "markdown" | "md" => Some(StatsFormat::Markdown),
+            _ => None,
+        }
+    }
 }
 
 pub fn stats_command(
     repo: &Repository,
     commit_sha: Option<&str>,
-    json: bool,
+    format: StatsFormat,
+    by_file: bool,
+    detail: bool,
     ignore_patterns: &[String],
+    whitespace: WhitespaceMode,
 ) -> Result<(), GitAiError> {
-    let (target, refname) = if let Some(sha) = commit_sha {
+    let (target, refname) = resolve_stats_target(repo, commit_sha)?;
+
+    tracing::debug!(
+        "Stats command found commit: {} refname: {}",
+        target,
+        refname
+    );
+
+    let authorship_log = wait_for_recent_authorship(repo, &target)?;
+    let stats = stats_for_commit_stats_with_authorship(
+        repo,
+        &target,
+        ignore_patterns,
+        whitespace,
+        authorship_log.as_ref(),
+    )?;
+
+    // The markdown report always carries the per-file table, collapsed.
+    let files = if by_file || format == StatsFormat::Markdown {
+        Some(file_stats_for_commit(
+            repo,
+            &target,
+            ignore_patterns,
+            whitespace,
+            authorship_log.as_ref(),
+        )?)
+    } else {
+        None
+    };
+
+    let labels = detail.then(|| {
+        authorship_log
+            .as_ref()
+            .map(|log| log.metadata.labels.clone())
+            .unwrap_or_default()
+    });
+
+    let subagents = detail.then(|| {
+        authorship_log
+            .as_ref()
+            .map(subagent_line_counts)
+            .unwrap_or_default()
+    });
+
+    let tool_versions = detail.then(|| {
+        authorship_log
+            .as_ref()
+            .map(tool_version_line_counts)
+            .unwrap_or_default()
+    });
+
+    if format == StatsFormat::Markdown {
+        let title = format!("`{}`", &target[..target.len().min(8)]);
+        print!(
+            "{}",
+            write_stats_report_markdown(
+                &title,
+                &stats,
+                files.as_ref(),
+                labels.as_deref(),
+                subagents.as_ref(),
+                tool_versions.as_ref()
+            )
+        );
+        return Ok(());
+    }
+
+    let json = format == StatsFormat::Json;
+    if json && let Some(labels) = labels.as_ref() {
+        let mut value = match files {
+            Some(files) => serde_json::to_value(CommitStatsByFile { stats, files })?,
+            None => serde_json::to_value(&stats)?,
+        };
+        if let Some(object) = value.as_object_mut() {
+            object.insert("labels".to_string(), serde_json::to_value(labels)?);
+            object.insert("subagents".to_string(), serde_json::to_value(&subagents)?);
+            object.insert(
+                "tool_versions".to_string(),
+                serde_json::to_value(&tool_versions)?,
+            );
+        }
+        println!("{}", serde_json::to_string(&value)?);
+        return Ok(());
+    }
+
+    match (json, files) {
+        (true, Some(files)) => {
+            let json_str = serde_json::to_string(&CommitStatsByFile { stats, files })?;
+            println!("{}", json_str);
+        }
+        (true, None) => {
+            let json_str = serde_json::to_string(&stats)?;
+            println!("{}", json_str);
+        }
+        (false, files) => {
+            write_stats_to_terminal(&stats, true);
+            write_test_code_stats_to_terminal(&stats, true);
+            if let Some(files) = files {
+                write_file_stats_to_terminal(&files, true);
+            }
+            if let Some(labels) = labels {
+                println!();
+                if labels.is_empty() {
+                    println!("No labelled checkpoints in this commit.");
+                } else {
+                    println!("Labelled checkpoints:");
+                    for record in &labels {
+                        println!("  {}", format_checkpoint_label(record));
+                    }
+                }
+            }
+            if let Some(subagents) = subagents.filter(|subagents| !subagents.is_empty()) {
+                println!();
+                println!("Subagents:");
+                for (id, subagent) in &subagents {
+                    println!("  {}  {} lines", subagent.label(id), subagent.lines);
+                }
+            }
+            if let Some(tool_versions) =
+                tool_versions.filter(|tool_versions| !tool_versions.is_empty())
+            {
+                println!();
+                println!("Tool versions:");
+                for (tool_version, lines) in &tool_versions {
+                    println!("  {}  {} lines", tool_version, lines);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Attested lines of one subagent, see [`subagent_line_counts`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SubagentLines {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_type: Option<String>,
+    pub lines: u32,
+}
+
+impl SubagentLines {
+    /// Display name of subagent `id`, e.g. `explorer (a1b2)`.
+    pub fn label(&self, id: &str) -> String {
+        SubagentInfo {
+            id: id.to_string(),
+            agent_type: self.agent_type.clone(),
+        }
+        .to_string()
+    }
+}
+
+/// Attested lines in `log` per subagent (e.g. Claude Code Task tool agents),
+/// keyed by the subagent's id. Lines the parent agents wrote themselves are not
+/// counted.
+pub fn subagent_line_counts(log: &AuthorshipLog) -> BTreeMap<String, SubagentLines> {
+    let agent_types: HashMap<&str, &str> = log
+        .metadata
+        .sessions
+        .values()
+        .flat_map(|session| session.subagents.values())
+        .filter_map(|subagent| {
+            subagent
+                .agent_type
+                .as_deref()
+                .map(|agent_type| (subagent.id.as_str(), agent_type))
+        })
+        .collect();
+    attested_line_counts(log, |session, hash| {
+        session
+            .subagent_for_attestation(hash)
+            .map(|subagent| subagent.id.clone())
+    })
+    .into_iter()
+    .map(|(id, lines)| {
+        let agent_type = agent_types
+            .get(id.as_str())
+            .map(|agent_type| agent_type.to_string());
+        (id, SubagentLines { agent_type, lines })
+    })
+    .collect()
+}
+
+/// Attested lines in `log` per agent tool release, keyed like `claude 2.0.14`.
+/// Lines from checkpoints that recorded no tool version are not counted.
+pub fn tool_version_line_counts(log: &AuthorshipLog) -> BTreeMap<String, u32> {
+    attested_line_counts(log, |session, hash| {
+        session
+            .tool_version_for_attestation(hash)
+            .map(|version| format!("{} {}", session.agent_id.tool, version))
+    })
+}
+
+/// Attested lines in `log` grouped by `key`, which maps an entry's session and
+/// full attestation hash to its group. Entries without a group are skipped.
+fn attested_line_counts(
+    log: &AuthorshipLog,
+    key: impl Fn(&SessionRecord, &str) -> Option<String>,
+) -> BTreeMap<String, u32> {
+    let mut counts: BTreeMap<String, u32> = BTreeMap::new();
+    for attestation in &log.attestations {
+        for entry in &attestation.entries {
+            let session_id = entry.hash.split("::").next().unwrap_or(&entry.hash);
+            let Some(group) = log
+                .metadata
+                .sessions
+                .get(session_id)
+                .and_then(|session| key(session, &entry.hash))
+            else {
+                continue;
+            };
+            let lines: u32 = entry
+                .line_ranges
+                .iter()
+                .map(|range| match range {
+                    LineRange::Single(_) => 1,
+                    LineRange::Range(start, end) => end.saturating_sub(*start) + 1,
+                })
+                .sum();
+            *counts.entry(group).or_insert(0) += lines;
+        }
+    }
+    counts
+}
+
+/// One line describing a labelled checkpoint, e.g.
+/// `2026-10-16 14:02  after refactor  claude/sonnet  +42 -3`.
+pub fn format_checkpoint_label(record: &CheckpointLabelRecord) -> String {
+    let when = chrono::DateTime::from_timestamp(record.timestamp as i64, 0)
+        .map(|time| {
+            time.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| record.timestamp.to_string());
+    format!(
+        "{}  {}  {}  +{} -{}",
+        when, record.label, record.author, record.additions, record.deletions
+    )
+}
+
+/// Resolve the commit stats are computed for, returning its sha and a display refname.
+fn resolve_stats_target(
+    repo: &Repository,
+    commit_sha: Option<&str>,
+) -> Result<(String, String), GitAiError> {
+    if let Some(sha) = commit_sha {
         // Validate that the commit exists using revparse_single
         match repo.revparse_single(sha) {
             Ok(commit_obj) => {
                 // For a specific commit, we don't have a refname, so use the commit SHA
                 let full_sha = commit_obj.id();
-                (full_sha, sha.to_string())
+                Ok((full_sha, sha.to_string()))
             }
             Err(GitAiError::GitCliError { .. }) => {
-                return Err(GitAiError::Generic(format!("No commit found: {}", sha)));
+                Err(GitAiError::Generic(format!("No commit found: {}", sha)))
             }
-            Err(e) => return Err(e),
+            Err(e) => Err(e),
         }
     } else {
         // Default behavior: use current HEAD
         let head = repo.head()?;
         let target = head.target()?;
         let name = head.name().unwrap_or("HEAD").to_string();
-        (target, name)
-    };
-
-    tracing::debug!(
-        "Stats command found commit: {} refname: {}",
-        target,
-        refname
-    );
+        Ok((target, name))
+    }
+}
 
+/// Print the `AI-Attribution` trailer line for a commit (HEAD when `commit_sha` is `None`).
+pub fn stats_trailer_command(
+    repo: &Repository,
+    commit_sha: Option<&str>,
+    ignore_patterns: &[String],
+    whitespace: WhitespaceMode,
+) -> Result<(), GitAiError> {
+    let (target, _) = resolve_stats_target(repo, commit_sha)?;
     let authorship_log = wait_for_recent_authorship(repo, &target)?;
     let stats = stats_for_commit_stats_with_authorship(
         repo,
         &target,
         ignore_patterns,
+        whitespace,
         authorship_log.as_ref(),
     )?;
+    println!("{}", attribution_trailer_for_stats(&stats));
+    Ok(())
+}
 
-    if json {
-        let json_str = serde_json::to_string(&stats)?;
-        println!("{}", json_str);
+/// Key of the one-line trailer printed by `git-ai stats --trailer`.
+pub const ATTRIBUTION_TRAILER_KEY: &str = "AI-Attribution";
+
+/// Format an `AI-Attribution: ai=<n> human=<n> mixed=<n> tools=<a,b>` trailer.
+///
+/// `tools` is written as `none` when no AI tool contributed lines, so the value
+/// always has the same four fields for hooks and bots that parse it.
+pub fn format_attribution_trailer(
+    ai: u32,
+    human: u32,
+    mixed: u32,
+    tools: &BTreeSet<String>,
+) -> String {
+    let tools = if tools.is_empty() {
+        "none".to_string()
     } else {
-        write_stats_to_terminal(&stats, true);
-    }
+        tools.iter().cloned().collect::<Vec<_>>().join(",")
+    };
+    format!(
+        "{}: ai={} human={} mixed={} tools={}",
+        ATTRIBUTION_TRAILER_KEY, ai, human, mixed, tools
+    )
+}
 
-    Ok(())
+/// Split a commit's added lines into `(ai, human, mixed)`. Every added line that
+/// is not AI-attributed counts as human, including lines without any attestation.
+pub fn attribution_line_counts(stats: &CommitStats) -> (u32, u32, u32) {
+    (
+        stats.ai_accepted,
+        stats
+            .git_diff_added_lines
+            .saturating_sub(stats.ai_additions),
+        stats.ai_additions.saturating_sub(stats.ai_accepted),
+    )
+}
+
+/// Trailer for committed stats, using the split from [`attribution_line_counts`].
+pub fn attribution_trailer_for_stats(stats: &CommitStats) -> String {
+    let tools: BTreeSet<String> = stats
+        .tool_model_breakdown
+        .iter()
+        .filter(|(_, tool_stats)| tool_stats.ai_additions > 0)
+        .map(|(tool_model, _)| trailer_tool_name(tool_model.split("::").next().unwrap_or_default()))
+        .collect();
+    let (ai, human, mixed) = attribution_line_counts(stats);
+    format_attribution_trailer(ai, human, mixed, &tools)
+}
+
+/// Tool names are joined with commas and spaces separate trailer fields, so
+/// neither may appear inside a name.
+pub(crate) fn trailer_tool_name(tool: &str) -> String {
+    tool.trim().replace([',', ' '], "-")
 }
 
 fn wait_for_recent_authorship(
@@ -305,20 +680,264 @@ pub fn write_stats_to_terminal(stats: &CommitStats, is_interactive: bool) -> Str
     output
 }
 
-#[allow(dead_code)]
-pub fn write_stats_to_markdown(stats: &CommitStats) -> String {
+/// Test vs production split of a commit's additions; prints nothing when the
+/// commit touches no test files.
+pub fn write_test_code_stats_to_terminal(stats: &CommitStats, is_interactive: bool) -> String {
+    let test_code = &stats.test_code;
+    if test_code.is_empty() {
+        return String::new();
+    }
+    let production_ai = stats.ai_additions.saturating_sub(test_code.ai_additions);
+    let production_total = (stats.ai_additions + stats.human_additions + stats.unknown_additions)
+        .saturating_sub(test_code.total_additions());
+    let lines = [
+        format!(
+            "     tests       {} of {} added lines by ai",
+            test_code.ai_additions,
+            test_code.total_additions()
+        ),
+        format!(
+            "     production  {} of {} added lines by ai",
+            production_ai, production_total
+        ),
+    ];
+
     let mut output = String::new();
+    for line in lines {
+        output.push_str(&line);
+        output.push('\n');
+        if is_interactive {
+            println!("{}", line);
+        }
+    }
+    output
+}
 
-    // Set maximum bar width to 20 characters
-    let bar_width: usize = 20;
+pub fn write_file_stats_to_terminal(
+    files: &BTreeMap<String, FileStats>,
+    is_interactive: bool,
+) -> String {
+    let mut output = String::new();
+    if files.is_empty() {
+        return output;
+    }
 
+    let display_path = |path: &str, file_stats: &FileStats| {
+        if file_stats.is_test {
+            format!("{} (test)", path)
+        } else {
+            path.to_string()
+        }
+    };
+    let path_width = files
+        .iter()
+        .map(|(path, file_stats)| display_path(path, file_stats).len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let mut lines = vec![format!(
+        "\n{:<path_width$}  {:>8} {:>8} {:>8}  {:>8} {:>8}",
+        "file", "+ai", "+human", "+unknown", "-ai", "-human"
+    )];
+    for (path, file_stats) in files {
+        lines.push(format!(
+            "{:<path_width$}  {:>8} {:>8} {:>8}  {:>8} {:>8}",
+            display_path(path, file_stats),
+            file_stats.ai_additions,
+            file_stats.human_additions,
+            file_stats.unknown_additions,
+            file_stats.ai_deletions,
+            file_stats.human_deletions
+        ));
+    }
+
+    for line in lines {
+        output.push_str(&line);
+        output.push('\n');
+        if is_interactive {
+            println!("{}", line);
+        }
+    }
+
+    output
+}
+
+fn markdown_share(lines: u32, total: u32) -> String {
+    if total == 0 {
+        return "-".to_string();
+    }
+    format!(
+        "{}%",
+        ((lines as f64 / total as f64) * 100.0).round() as u32
+    )
+}
+
+/// Table cell text: pipes would end the cell and newlines the row.
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+/// GitHub-flavored markdown for `stats --format markdown`, ready to paste into
+/// a PR description or issue comment: the [`write_stats_to_markdown`] bar
+/// chart, an authorship summary and a tool/model table up front, with per-file, labelled-checkpoint, subagent and tool
+/// version breakdowns in collapsed `<details>` sections when given.
+pub fn write_stats_report_markdown(
+    title: &str,
+    stats: &CommitStats,
+    files: Option<&BTreeMap<String, FileStats>>,
+    labels: Option<&[CheckpointLabelRecord]>,
+    subagents: Option<&BTreeMap<String, SubagentLines>>,
+    tool_versions: Option<&BTreeMap<String, u32>>,
+) -> String {
+    let mut out = String::new();
+    let total = stats.ai_additions + stats.human_additions + stats.unknown_additions;
+
+    out.push_str(&format!("### AI attribution for {}\n\n", title));
+    if let Some(bars) = markdown_share_bars(stats) {
+        out.push_str(&bars);
+        out.push_str("\n\n");
+    }
+    out.push_str("| Author | Added lines | Share |\n");
+    out.push_str("| --- | ---: | ---: |\n");
+    for (author, lines) in [
+        ("AI", stats.ai_additions),
+        ("Human", stats.human_additions),
+        ("Unknown", stats.unknown_additions),
+    ] {
+        out.push_str(&format!(
+            "| {} | {} | {} |\n",
+            author,
+            lines,
+            markdown_share(lines, total)
+        ));
+    }
+    out.push_str(&format!(
+        "| **Total** | **{}** | |\n\n_{} lines removed._\n",
+        total, stats.git_diff_deleted_lines
+    ));
+
+    if !stats.tool_model_breakdown.is_empty() {
+        out.push_str("\n| Tool / model | AI lines | Accepted unchanged | Share of AI |\n");
+        out.push_str("| --- | ---: | ---: | ---: |\n");
+        for (tool_model, tool_stats) in &stats.tool_model_breakdown {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                markdown_cell(tool_model),
+                tool_stats.ai_additions,
+                tool_stats.ai_accepted,
+                markdown_share(tool_stats.ai_additions, stats.ai_additions)
+            ));
+        }
+    }
+
+    if let Some(files) = files.filter(|files| !files.is_empty()) {
+        out.push_str(&format!(
+            "\n<details>\n<summary>Per-file breakdown ({} file{})</summary>\n\n",
+            files.len(),
+            if files.len() == 1 { "" } else { "s" }
+        ));
+        out.push_str("| File | +AI | +Human | +Unknown | -AI | -Human |\n");
+        out.push_str("| --- | ---: | ---: | ---: | ---: | ---: |\n");
+        for (path, file_stats) in files {
+            out.push_str(&format!(
+                "| `{}`{} | {} | {} | {} | {} | {} |\n",
+                markdown_cell(path),
+                if file_stats.is_test { " (test)" } else { "" },
+                file_stats.ai_additions,
+                file_stats.human_additions,
+                file_stats.unknown_additions,
+                file_stats.ai_deletions,
+                file_stats.human_deletions
+            ));
+        }
+        out.push_str("\n</details>\n");
+    }
+
+    if let Some(labels) = labels.filter(|labels| !labels.is_empty()) {
+        out.push_str("\n<details>\n<summary>Labelled checkpoints</summary>\n\n");
+        for record in labels {
+            out.push_str(&format!("- {}\n", format_checkpoint_label(record)));
+        }
+        out.push_str("\n</details>\n");
+    }
+
+    if let Some(subagents) = subagents.filter(|subagents| !subagents.is_empty()) {
+        out.push_str("\n<details>\n<summary>Subagents</summary>\n\n");
+        out.push_str("| Subagent | Lines |\n| --- | ---: |\n");
+        for (id, subagent) in subagents {
+            out.push_str(&format!(
+                "| {} | {} |\n",
+                markdown_cell(&subagent.label(id)),
+                subagent.lines
+            ));
+        }
+        out.push_str("\n</details>\n");
+    }
+
+    if let Some(tool_versions) = tool_versions.filter(|tool_versions| !tool_versions.is_empty()) {
+        out.push_str("\n<details>\n<summary>Tool versions</summary>\n\n");
+        out.push_str("| Tool version | Lines |\n| --- | ---: |\n");
+        for (tool_version, lines) in tool_versions {
+            out.push_str(&format!(
+                "| {} | {} |\n",
+                markdown_cell(tool_version),
+                lines
+            ));
+        }
+        out.push_str("\n</details>\n");
+    }
+
+    out.push_str(&format!("\n<sub>Stats powered by {}</sub>\n", GIT_AI_LINK));
+    out
+}
+
+const GIT_AI_LINK: &str = "[Git AI](https://github.com/git-ai-project/git-ai)";
+
+/// Short markdown summary for a PR comment: the share bar chart and the top model.
+pub fn write_stats_to_markdown(stats: &CommitStats) -> String {
     // Handle deletion-only commits (no additions)
+    let Some(bars) = markdown_share_bars(stats) else {
+        return "(no additions)\n".to_string();
+    };
+
+    let mut output = String::new();
+    output.push_str(&format!("Stats powered by {}\n\n", GIT_AI_LINK));
+    output.push_str(&bars);
+
+    // Add details section
+    output.push_str("\n\n<details>\n");
+    output.push_str("<summary>More stats</summary>\n\n");
+
+    // Find top model by accepted lines
+    if !stats.tool_model_breakdown.is_empty()
+        && let Some((model_name, model_stats)) = stats
+            .tool_model_breakdown
+            .iter()
+            .max_by_key(|(_, stats)| stats.ai_accepted)
+    {
+        output.push_str(&format!(
+            "- Top model: {} ({} accepted lines)\n",
+            model_name, model_stats.ai_accepted
+        ));
+    }
+
+    output.push_str("\n</details>");
+
+    output
+}
+
+/// Fenced `you`/`ai` bar chart of added lines, or `None` when `stats` only
+/// deletes.
+fn markdown_share_bars(stats: &CommitStats) -> Option<String> {
     if stats.git_diff_added_lines == 0 && stats.git_diff_deleted_lines > 0 {
-        output.push_str("(no additions)");
-        output.push('\n');
-        return output;
+        return None;
     }
 
+    let mut output = String::new();
+
+    // Set maximum bar width to 20 characters
+    let bar_width: usize = 20;
+
     // Calculate total additions for the progress bar
     let total_additions = stats.git_diff_added_lines;
 
@@ -366,7 +985,6 @@ pub fn write_stats_to_markdown(stats: &CommitStats) -> String {
         0
     };
 
-    output.push_str("Stats powered by [Git AI](https://github.com/git-ai-project/git-ai)\n\n");
     // Build the fenced code block
     output.push_str("```text\n");
 
@@ -384,26 +1002,7 @@ pub fn write_stats_to_markdown(stats: &CommitStats) -> String {
 
     output.push_str("```");
 
-    // Add details section
-    output.push_str("\n\n<details>\n");
-    output.push_str("<summary>More stats</summary>\n\n");
-
-    // Find top model by accepted lines
-    if !stats.tool_model_breakdown.is_empty()
-        && let Some((model_name, model_stats)) = stats
-            .tool_model_breakdown
-            .iter()
-            .max_by_key(|(_, stats)| stats.ai_accepted)
-    {
-        output.push_str(&format!(
-            "- Top model: {} ({} accepted lines)\n",
-            model_name, model_stats.ai_accepted
-        ));
-    }
-
-    output.push_str("\n</details>");
-
-    output
+    Some(output)
 }
 
 /// Calculate commit stats from an authorship log
@@ -424,6 +1023,7 @@ pub fn stats_from_authorship_log(
         tool_model_breakdown: BTreeMap::new(),
         git_diff_deleted_lines,
         git_diff_added_lines,
+        test_code: TestCodeStats::default(),
     };
 
     // Update tool-level accepted counts using diff-based attribution.
@@ -458,12 +1058,14 @@ pub fn stats_for_commit_stats(
     repo: &Repository,
     commit_sha: &str,
     ignore_patterns: &[String],
+    whitespace: WhitespaceMode,
 ) -> Result<CommitStats, GitAiError> {
     let authorship_log = read_authorship(repo, commit_sha);
     stats_for_commit_stats_with_authorship(
         repo,
         commit_sha,
         ignore_patterns,
+        whitespace,
         authorship_log.as_ref(),
     )
 }
@@ -472,34 +1074,46 @@ pub fn stats_for_commit_stats_with_authorship(
     repo: &Repository,
     commit_sha: &str,
     ignore_patterns: &[String],
+    whitespace: WhitespaceMode,
     authorship_log: Option<&crate::authorship::authorship_log_serialization::AuthorshipLog>,
 ) -> Result<CommitStats, GitAiError> {
     let commit_obj = repo.revparse_single(commit_sha)?.peel_to_commit()?;
     let parent_count = commit_obj.parent_count()?;
 
-    if parent_count > 1 {
-        return stats_for_commit_stats_from_hunks(
+    let mut stats = if parent_count > 1 {
+        stats_for_commit_stats_from_hunks(repo, commit_sha, ignore_patterns, &[], authorship_log)?
+    } else {
+        let parent_sha = if parent_count == 0 {
+            None
+        } else {
+            Some(commit_obj.parent(0)?.id())
+        };
+        stats_for_commit_stats_with_parent_and_authorship(
             repo,
             commit_sha,
+            parent_sha.as_deref(),
             ignore_patterns,
-            &[],
+            whitespace,
             authorship_log,
-        );
-    }
-
-    let parent_sha = if parent_count == 0 {
-        None
-    } else {
-        Some(commit_obj.parent(0)?.id())
+        )?
     };
+    normalize_tool_model_breakdown(&mut stats, &effective_workspace_config(repo).normalize);
+    Ok(stats)
+}
 
-    stats_for_commit_stats_with_parent_and_authorship(
-        repo,
-        commit_sha,
-        parent_sha.as_deref(),
-        ignore_patterns,
-        authorship_log,
-    )
+/// Rename `tool::model` breakdown keys through the workspace normalization maps,
+/// merging entries that end up under the same name.
+pub fn normalize_tool_model_breakdown(stats: &mut CommitStats, normalize: &NormalizationConfig) {
+    if normalize.is_empty() {
+        return;
+    }
+    for (tool_model, tool_stats) in std::mem::take(&mut stats.tool_model_breakdown) {
+        let (tool, model) = tool_model.split_once("::").unwrap_or((&tool_model, ""));
+        let key = format!("{}::{}", normalize.tool(tool), normalize.model(model));
+        let merged = stats.tool_model_breakdown.entry(key).or_default();
+        merged.ai_additions += tool_stats.ai_additions;
+        merged.ai_accepted += tool_stats.ai_accepted;
+    }
 }
 
 pub fn stats_for_commit_stats_with_parent_and_authorship(
@@ -507,15 +1121,225 @@ pub fn stats_for_commit_stats_with_parent_and_authorship(
     commit_sha: &str,
     parent_sha: Option<&str>,
     ignore_patterns: &[String],
+    whitespace: WhitespaceMode,
     authorship_log: Option<&crate::authorship::authorship_log_serialization::AuthorshipLog>,
 ) -> Result<CommitStats, GitAiError> {
     use crate::commands::diff::get_diff_with_line_numbers;
 
     let from_ref = parent_sha.unwrap_or("4b825dc642cb6eb9a060e54bf8d69288fbee4904");
-    let hunks = get_diff_with_line_numbers(repo, from_ref, commit_sha)?;
+    let mut hunks = get_diff_with_line_numbers(repo, from_ref, commit_sha)?;
+    drop_whitespace_only_changes(&mut hunks, whitespace);
     stats_for_commit_stats_from_hunks(repo, commit_sha, ignore_patterns, &hunks, authorship_log)
 }
 
+/// Break a commit's changes down by file, attributing deleted lines to whoever last
+/// wrote them in the parent commit. Merge commits report additions only.
+pub fn file_stats_for_commit(
+    repo: &Repository,
+    commit_sha: &str,
+    ignore_patterns: &[String],
+    whitespace: WhitespaceMode,
+    authorship_log: Option<&crate::authorship::authorship_log_serialization::AuthorshipLog>,
+) -> Result<BTreeMap<String, FileStats>, GitAiError> {
+    use crate::commands::diff::get_diff_with_line_numbers;
+
+    let commit_obj = repo.revparse_single(commit_sha)?.peel_to_commit()?;
+    let parent_count = commit_obj.parent_count()?;
+    let is_merge_commit = parent_count > 1;
+    let parent_sha = if parent_count == 0 {
+        None
+    } else {
+        Some(commit_obj.parent(0)?.id())
+    };
+
+    let from_ref = parent_sha
+        .as_deref()
+        .unwrap_or("4b825dc642cb6eb9a060e54bf8d69288fbee4904");
+    let mut hunks = get_diff_with_line_numbers(repo, from_ref, commit_sha)?;
+    drop_whitespace_only_changes(&mut hunks, whitespace);
+    let ignore_matcher = build_ignore_matcher(ignore_patterns);
+    let test_matcher = test_path_matcher_for_repo(repo);
+
+    let mut files: BTreeMap<String, FileStats> = BTreeMap::new();
+    let mut added_lines_by_file: HashMap<String, Vec<u32>> = HashMap::new();
+    // Keyed by the reported path; the value carries the path at the parent commit.
+    let mut deleted_lines_by_file: BTreeMap<String, (String, Vec<u32>)> = BTreeMap::new();
+
+    for hunk in &hunks {
+        if should_ignore_file_with_matcher(&hunk.file_path, &ignore_matcher) {
+            continue;
+        }
+        if hunk.added_lines.is_empty() && hunk.deleted_lines.is_empty() {
+            continue;
+        }
+        files.entry(hunk.file_path.clone()).or_default();
+        if !hunk.added_lines.is_empty() {
+            added_lines_by_file
+                .entry(hunk.file_path.clone())
+                .or_default()
+                .extend(hunk.added_lines.iter().copied());
+        }
+        if !is_merge_commit && !hunk.deleted_lines.is_empty() {
+            let old_path = hunk.old_file_path.as_ref().unwrap_or(&hunk.file_path);
+            deleted_lines_by_file
+                .entry(hunk.file_path.clone())
+                .or_insert_with(|| (old_path.clone(), Vec::new()))
+                .1
+                .extend(hunk.deleted_lines.iter().copied());
+        }
+    }
+
+    for (file_path, mut added_lines) in added_lines_by_file {
+        added_lines.sort_unstable();
+        added_lines.dedup();
+        let added_count = added_lines.len() as u32;
+        let single_file = HashMap::from([(file_path.clone(), added_lines)]);
+        let (ai_accepted, known_human_accepted, _) =
+            accepted_lines_from_attestations(authorship_log, &single_file, is_merge_commit);
+
+        let file_stats = files.entry(file_path).or_default();
+        file_stats.ai_additions = ai_accepted;
+        file_stats.human_additions = known_human_accepted;
+        file_stats.unknown_additions = added_count
+            .saturating_sub(ai_accepted)
+            .saturating_sub(known_human_accepted);
+    }
+
+    if let Some(parent_sha) = parent_sha.as_deref() {
+        for (file_path, (old_path, mut deleted_lines)) in deleted_lines_by_file {
+            deleted_lines.sort_unstable();
+            deleted_lines.dedup();
+            let ai_deleted = ai_authored_line_count(repo, parent_sha, &old_path, &deleted_lines);
+            let file_stats = files.entry(file_path).or_default();
+            file_stats.ai_deletions = ai_deleted;
+            file_stats.human_deletions = (deleted_lines.len() as u32).saturating_sub(ai_deleted);
+        }
+    }
+
+    for (file_path, file_stats) in files.iter_mut() {
+        file_stats.is_test = test_matcher.is_test(file_path);
+    }
+
+    Ok(files)
+}
+
+/// Collapsible sections of `stats --format markdown` for a commit range, summed
+/// over its commits: the per-file breakdown always, and with `detail` the
+/// labelled checkpoints, subagents and tool versions from each commit's note.
+#[derive(Debug, Default)]
+pub struct RangeReportSections {
+    pub files: BTreeMap<String, FileStats>,
+    pub labels: Option<Vec<CheckpointLabelRecord>>,
+    pub subagents: Option<BTreeMap<String, SubagentLines>>,
+    pub tool_versions: Option<BTreeMap<String, u32>>,
+}
+
+/// Build the [`RangeReportSections`] for `commit_shas`. Merge commits are
+/// skipped, since the commits they bring in are already part of the range.
+pub fn range_report_sections(
+    repo: &Repository,
+    commit_shas: &[String],
+    ignore_patterns: &[String],
+    whitespace: WhitespaceMode,
+    detail: bool,
+) -> Result<RangeReportSections, GitAiError> {
+    let mut sections = RangeReportSections {
+        labels: detail.then(Vec::new),
+        subagents: detail.then(BTreeMap::new),
+        tool_versions: detail.then(BTreeMap::new),
+        ..Default::default()
+    };
+
+    for commit_sha in commit_shas {
+        if repo
+            .revparse_single(commit_sha)?
+            .peel_to_commit()?
+            .parent_count()?
+            > 1
+        {
+            continue;
+        }
+        let authorship_log = read_authorship(repo, commit_sha);
+
+        let files = file_stats_for_commit(
+            repo,
+            commit_sha,
+            ignore_patterns,
+            whitespace,
+            authorship_log.as_ref(),
+        )?;
+        for (path, file_stats) in files {
+            let total = sections.files.entry(path).or_default();
+            total.ai_additions += file_stats.ai_additions;
+            total.human_additions += file_stats.human_additions;
+            total.unknown_additions += file_stats.unknown_additions;
+            total.ai_deletions += file_stats.ai_deletions;
+            total.human_deletions += file_stats.human_deletions;
+            total.is_test |= file_stats.is_test;
+        }
+
+        let Some(log) = authorship_log.as_ref() else {
+            continue;
+        };
+        if let Some(labels) = sections.labels.as_mut() {
+            labels.extend(log.metadata.labels.iter().cloned());
+        }
+        if let Some(subagents) = sections.subagents.as_mut() {
+            for (id, counted) in subagent_line_counts(log) {
+                let total = subagents.entry(id).or_insert(SubagentLines {
+                    agent_type: None,
+                    lines: 0,
+                });
+                total.lines += counted.lines;
+                if total.agent_type.is_none() {
+                    total.agent_type = counted.agent_type;
+                }
+            }
+        }
+        if let Some(tool_versions) = sections.tool_versions.as_mut() {
+            for (tool_version, lines) in tool_version_line_counts(log) {
+                *tool_versions.entry(tool_version).or_default() += lines;
+            }
+        }
+    }
+
+    Ok(sections)
+}
+
+/// Count how many of `lines` (sorted, 1-indexed) in `file_path` at `commit_sha` were
+/// AI-authored, using a single blame over the affected ranges. Lines that cannot be
+/// blamed count as not AI.
+fn ai_authored_line_count(
+    repo: &Repository,
+    commit_sha: &str,
+    file_path: &str,
+    lines: &[u32],
+) -> u32 {
+    use crate::commands::blame::GitAiBlameOptions;
+    use crate::commands::diff::lines_to_ranges;
+
+    let options = GitAiBlameOptions {
+        line_ranges: lines_to_ranges(lines),
+        newest_commit: Some(commit_sha.to_string()),
+        no_output: true,
+        use_prompt_hashes_as_names: true,
+        ..GitAiBlameOptions::default()
+    };
+    let Ok(analysis) = repo.blame_analysis(file_path, &options) else {
+        return 0;
+    };
+
+    lines
+        .iter()
+        .filter(|line| {
+            analysis
+                .line_authors
+                .get(line)
+                .is_some_and(|author| analysis.prompt_records.contains_key(author))
+        })
+        .count() as u32
+}
+
 #[doc(hidden)]
 pub fn accepted_lines_from_attestations(
     authorship_log: Option<&crate::authorship::authorship_log_serialization::AuthorshipLog>,
@@ -614,12 +1438,61 @@ pub fn stats_for_commit_stats_from_hunks(
     let parent_count = commit_obj.parent_count()?;
     let is_merge_commit = parent_count > 1;
 
-    Ok(stats_for_commit_stats_from_hunks_with_merge_flag(
+    let mut stats = stats_for_commit_stats_from_hunks_with_merge_flag(
         ignore_patterns,
         hunks,
         authorship_log,
         is_merge_commit,
-    ))
+    );
+    stats.test_code = test_code_stats_from_hunks(
+        &test_path_matcher_for_repo(repo),
+        ignore_patterns,
+        hunks,
+        authorship_log,
+        is_merge_commit,
+    );
+    Ok(stats)
+}
+
+/// Attribute the added lines that land in test files, using the same rules as
+/// the commit-wide totals.
+pub(crate) fn test_code_stats_from_hunks(
+    test_matcher: &TestPathMatcher,
+    ignore_patterns: &[String],
+    hunks: &[crate::commands::diff::DiffHunk],
+    authorship_log: Option<&crate::authorship::authorship_log_serialization::AuthorshipLog>,
+    is_merge_commit: bool,
+) -> TestCodeStats {
+    let ignore_matcher = build_ignore_matcher(ignore_patterns);
+    let mut added_lines_by_file: HashMap<String, Vec<u32>> = HashMap::new();
+    let mut added_lines = 0u32;
+    for hunk in hunks {
+        if hunk.added_lines.is_empty()
+            || should_ignore_file_with_matcher(&hunk.file_path, &ignore_matcher)
+            || !test_matcher.is_test(&hunk.file_path)
+        {
+            continue;
+        }
+        added_lines += hunk.added_lines.len() as u32;
+        added_lines_by_file
+            .entry(hunk.file_path.clone())
+            .or_default()
+            .extend(hunk.added_lines.iter().copied());
+    }
+    for lines in added_lines_by_file.values_mut() {
+        lines.sort_unstable();
+        lines.dedup();
+    }
+
+    let (ai_accepted, known_human_accepted, _) =
+        accepted_lines_from_attestations(authorship_log, &added_lines_by_file, is_merge_commit);
+    TestCodeStats {
+        ai_additions: ai_accepted,
+        human_additions: known_human_accepted,
+        unknown_additions: added_lines
+            .saturating_sub(ai_accepted)
+            .saturating_sub(known_human_accepted),
+    }
 }
 
 pub(crate) fn stats_for_commit_stats_from_hunks_with_merge_flag(
@@ -714,6 +1587,111 @@ mod tests {
     use super::*;
     use insta::assert_debug_snapshot;
 
+    #[test]
+    fn test_file_stats_terminal_display() {
+        let files = BTreeMap::from([(
+            "src/generated.rs".to_string(),
+            FileStats {
+                ai_deletions: 12,
+                human_deletions: 3,
+                ..Default::default()
+            },
+        )]);
+
+        let output = write_file_stats_to_terminal(&files, false);
+        let row = output.lines().last().unwrap();
+        assert!(output.contains("-ai"));
+        assert!(row.starts_with("src/generated.rs"));
+        assert!(row.trim_end().ends_with("12        3"));
+
+        assert!(write_file_stats_to_terminal(&BTreeMap::new(), false).is_empty());
+    }
+
+    #[test]
+    fn test_normalize_tool_model_breakdown_merges_renamed_entries() {
+        let headline = |lines| ToolModelHeadlineStats {
+            ai_additions: lines,
+            ai_accepted: lines,
+        };
+        let mut stats = CommitStats {
+            tool_model_breakdown: BTreeMap::from([
+                (
+                    "claude-code::claude-sonnet-4-20250514".to_string(),
+                    headline(5),
+                ),
+                ("claude::claude-sonnet-4".to_string(), headline(3)),
+                ("cursor::gpt-5".to_string(), headline(2)),
+            ]),
+            ..Default::default()
+        };
+        let normalize = NormalizationConfig {
+            tools: BTreeMap::from([("claude-code".to_string(), "claude".to_string())]),
+            models: BTreeMap::from([(
+                "claude-sonnet-4-20250514".to_string(),
+                "claude-sonnet-4".to_string(),
+            )]),
+        };
+
+        normalize_tool_model_breakdown(&mut stats, &normalize);
+        assert_eq!(
+            stats.tool_model_breakdown.keys().collect::<Vec<_>>(),
+            vec!["claude::claude-sonnet-4", "cursor::gpt-5"]
+        );
+        assert_eq!(
+            stats.tool_model_breakdown["claude::claude-sonnet-4"].ai_additions,
+            8
+        );
+    }
+
+    #[test]
+    fn test_attribution_trailer_for_stats() {
+        let stats = CommitStats {
+            human_additions: 20,
+            unknown_additions: 10,
+            ai_additions: 124,
+            ai_accepted: 120,
+            git_diff_deleted_lines: 0,
+            git_diff_added_lines: 154,
+            tool_model_breakdown: BTreeMap::from([
+                (
+                    "claude-code::claude-sonnet-4".to_string(),
+                    ToolModelHeadlineStats {
+                        ai_additions: 100,
+                        ai_accepted: 100,
+                    },
+                ),
+                (
+                    "claude-code::claude-opus-4".to_string(),
+                    ToolModelHeadlineStats {
+                        ai_additions: 4,
+                        ai_accepted: 0,
+                    },
+                ),
+                (
+                    "cursor::gpt-5".to_string(),
+                    ToolModelHeadlineStats {
+                        ai_additions: 20,
+                        ai_accepted: 20,
+                    },
+                ),
+                (
+                    "codex::gpt-5".to_string(),
+                    ToolModelHeadlineStats::default(),
+                ),
+            ]),
+            test_code: Default::default(),
+        };
+
+        assert_eq!(
+            attribution_trailer_for_stats(&stats),
+            "AI-Attribution: ai=120 human=30 mixed=4 tools=claude-code,cursor"
+        );
+        assert_eq!(
+            attribution_trailer_for_stats(&CommitStats::default()),
+            "AI-Attribution: ai=0 human=0 mixed=0 tools=none"
+        );
+    }
+
     #[test]
     fn test_terminal_stats_display() {
         // Test with mixed human/AI stats
@@ -725,6 +1703,7 @@ mod tests {
             git_diff_deleted_lines: 15,
             git_diff_added_lines: 80,
             tool_model_breakdown: BTreeMap::new(),
+            test_code: Default::default(),
         };
 
         let mixed_output = write_stats_to_terminal(&stats, false);
@@ -739,6 +1718,7 @@ mod tests {
             git_diff_deleted_lines: 0,
             git_diff_added_lines: 100,
             tool_model_breakdown: BTreeMap::new(),
+            test_code: Default::default(),
         };
 
         let ai_only_output = write_stats_to_terminal(&ai_stats, false);
@@ -753,6 +1733,7 @@ mod tests {
             git_diff_deleted_lines: 10,
             git_diff_added_lines: 75,
             tool_model_breakdown: BTreeMap::new(),
+            test_code: Default::default(),
         };
 
         let human_only_output = write_stats_to_terminal(&human_stats, false);
@@ -767,6 +1748,7 @@ mod tests {
             git_diff_deleted_lines: 0,
             git_diff_added_lines: 102,
             tool_model_breakdown: BTreeMap::new(),
+            test_code: Default::default(),
         };
 
         let minimal_human_output = write_stats_to_terminal(&minimal_human_stats, false);
@@ -781,6 +1763,7 @@ mod tests {
             git_diff_deleted_lines: 25,
             git_diff_added_lines: 0,
             tool_model_breakdown: BTreeMap::new(),
+            test_code: Default::default(),
         };
 
         let deletion_only_output = write_stats_to_terminal(&deletion_only_stats, false);
@@ -797,6 +1780,7 @@ mod tests {
             git_diff_deleted_lines: 0,
             git_diff_added_lines: 1000,
             tool_model_breakdown: BTreeMap::new(),
+            test_code: Default::default(),
         };
         let with_untracked_output = write_stats_to_terminal(&untracked_stats, false);
         assert_debug_snapshot!(with_untracked_output);
@@ -810,6 +1794,7 @@ mod tests {
             git_diff_deleted_lines: 0,
             git_diff_added_lines: 100,
             tool_model_breakdown: BTreeMap::new(),
+            test_code: Default::default(),
         };
         let untracked_at_threshold_output = write_stats_to_terminal(&threshold_stats, false);
         assert_debug_snapshot!(untracked_at_threshold_output);
@@ -823,6 +1808,7 @@ mod tests {
             git_diff_deleted_lines: 0,
             git_diff_added_lines: 99,
             tool_model_breakdown: BTreeMap::new(),
+            test_code: Default::default(),
         };
         let untracked_just_above_output = write_stats_to_terminal(&above_threshold_stats, false);
         assert_debug_snapshot!(untracked_just_above_output);
@@ -836,6 +1822,7 @@ mod tests {
             git_diff_deleted_lines: 0,
             git_diff_added_lines: 100,
             tool_model_breakdown: BTreeMap::new(),
+            test_code: Default::default(),
         };
         let all_untracked_output = write_stats_to_terminal(&all_untracked_stats, false);
         assert_debug_snapshot!(all_untracked_output);
@@ -865,6 +1852,7 @@ mod tests {
             git_diff_deleted_lines: 15,
             git_diff_added_lines: 80,
             tool_model_breakdown: BTreeMap::new(),
+            test_code: Default::default(),
         };
 
         let mixed_output = write_stats_to_markdown(&stats);
@@ -879,6 +1867,7 @@ mod tests {
             git_diff_deleted_lines: 0,
             git_diff_added_lines: 100,
             tool_model_breakdown: BTreeMap::new(),
+            test_code: Default::default(),
         };
 
         let ai_only_output = write_stats_to_markdown(&ai_stats);
@@ -893,6 +1882,7 @@ mod tests {
             git_diff_deleted_lines: 10,
             git_diff_added_lines: 75,
             tool_model_breakdown: BTreeMap::new(),
+            test_code: Default::default(),
         };
 
         let human_only_output = write_stats_to_markdown(&human_stats);
@@ -907,6 +1897,7 @@ mod tests {
             git_diff_deleted_lines: 0,
             git_diff_added_lines: 102,
             tool_model_breakdown: BTreeMap::new(),
+            test_code: Default::default(),
         };
 
         let minimal_human_output = write_stats_to_markdown(&minimal_human_stats);
@@ -921,6 +1912,7 @@ mod tests {
             git_diff_deleted_lines: 25,
             git_diff_added_lines: 0,
             tool_model_breakdown: BTreeMap::new(),
+            test_code: Default::default(),
         };
 
         let deletion_only_output = write_stats_to_markdown(&deletion_only_stats);
@@ -0,0 +1,314 @@
+//! Materialized per-commit stats rollups stored in the metrics database.
+//!
+//! Each row caches one commit's `CommitStats` together with the blob oid of the
+//! authorship note it was computed from. Range queries sum the stored rows and
+//! only recompute commits whose note oid no longer matches (rewritten, fetched
+//! or merged notes), so repeated `stats --range` calls skip the note reads and
+//! diffs. Rollups are written on post-commit and by `stats --backfill`.
+//!
+//! Only the git-notes backend has note blob oids to validate against; with the
+//! HTTP backend every commit is computed directly and nothing is stored.
+
+use crate::authorship::stats::{CommitStats, attribution_line_counts, stats_for_commit_stats};
+use crate::config::{Config, NotesBackendKind};
+use crate::error::GitAiError;
+use crate::git::notes_api::{commits_with_notes, read_note_blob_oids};
+use crate::git::repository::{CommitRange, Repository, exec_git};
+use crate::metrics::db::{MetricsDatabase, StatsRollupRecord};
+use crate::workspace_config::WhitespaceMode;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Summed stats for a set of commits, with how many came from stored rollups.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RollupRangeStats {
+    pub commits: usize,
+    pub cached: usize,
+    pub computed: usize,
+    #[serde(flatten)]
+    pub stats: CommitStats,
+}
+
+/// Rollups are stored per ignore-pattern set and whitespace mode, since both
+/// change the numbers. `Exact` adds nothing to the key, so rollups stored before
+/// whitespace modes existed stay valid.
+pub fn rollup_ignore_key(ignore_patterns: &[String], whitespace: WhitespaceMode) -> String {
+    let mut patterns: Vec<&str> = ignore_patterns.iter().map(String::as_str).collect();
+    patterns.sort_unstable();
+    patterns.dedup();
+    let mut hasher = Sha256::new();
+    hasher.update(patterns.join("\n").as_bytes());
+    match whitespace {
+        WhitespaceMode::Exact => {}
+        WhitespaceMode::Whitespace => hasher.update(b"\0whitespace"),
+        WhitespaceMode::Reindent => hasher.update(b"\0reindent"),
+    }
+    let hex = format!("{:x}", hasher.finalize());
+    hex[..16].to_string()
+}
+
+/// Add one commit's stats into a running total.
+pub fn add_commit_stats(total: &mut CommitStats, stats: &CommitStats) {
+    total.human_additions = total.human_additions.saturating_add(stats.human_additions);
+    total.unknown_additions = total
+        .unknown_additions
+        .saturating_add(stats.unknown_additions);
+    total.ai_additions = total.ai_additions.saturating_add(stats.ai_additions);
+    total.ai_accepted = total.ai_accepted.saturating_add(stats.ai_accepted);
+    total.git_diff_added_lines = total
+        .git_diff_added_lines
+        .saturating_add(stats.git_diff_added_lines);
+    total.git_diff_deleted_lines = total
+        .git_diff_deleted_lines
+        .saturating_add(stats.git_diff_deleted_lines);
+    for (tool_model, tool_stats) in &stats.tool_model_breakdown {
+        let entry = total
+            .tool_model_breakdown
+            .entry(tool_model.clone())
+            .or_default();
+        entry.ai_additions = entry.ai_additions.saturating_add(tool_stats.ai_additions);
+        entry.ai_accepted = entry.ai_accepted.saturating_add(tool_stats.ai_accepted);
+    }
+    let test_code = &mut total.test_code;
+    test_code.ai_additions = test_code
+        .ai_additions
+        .saturating_add(stats.test_code.ai_additions);
+    test_code.human_additions = test_code
+        .human_additions
+        .saturating_add(stats.test_code.human_additions);
+    test_code.unknown_additions = test_code
+        .unknown_additions
+        .saturating_add(stats.test_code.unknown_additions);
+}
+
+/// Sum stats for `commit_shas`, reading current rollups and recomputing (and
+/// storing) the ones that are missing or were computed from a different note.
+pub fn rollup_stats_for_commits(
+    repo: &Repository,
+    commit_shas: &[String],
+    ignore_patterns: &[String],
+    whitespace: WhitespaceMode,
+) -> Result<RollupRangeStats, GitAiError> {
+    let mut summary = RollupRangeStats {
+        commits: commit_shas.len(),
+        ..Default::default()
+    };
+    for (stats, cached) in rollup_commit_stats(repo, commit_shas, ignore_patterns, whitespace)? {
+        if cached {
+            summary.cached += 1;
+        } else {
+            summary.computed += 1;
+        }
+        add_commit_stats(&mut summary.stats, &stats);
+    }
+    Ok(summary)
+}
+
+/// Stats of each of `commit_shas`, in order, paired with whether they came from
+/// a stored rollup. Missing or stale rollups are recomputed and stored.
+pub fn rollup_commit_stats(
+    repo: &Repository,
+    commit_shas: &[String],
+    ignore_patterns: &[String],
+    whitespace: WhitespaceMode,
+) -> Result<Vec<(CommitStats, bool)>, GitAiError> {
+    if !rollups_enabled() {
+        return commit_shas
+            .iter()
+            .map(|commit_sha| {
+                stats_for_commit_stats(repo, commit_sha, ignore_patterns, whitespace)
+                    .map(|s| (s, false))
+            })
+            .collect();
+    }
+
+    let ignore_key = rollup_ignore_key(ignore_patterns, whitespace);
+    let note_oids = read_note_blob_oids(repo, commit_shas)?;
+    let stored = match with_metrics_db(|db| db.get_stats_rollups(&ignore_key, commit_shas)) {
+        Ok(stored) => stored,
+        Err(e) => {
+            tracing::debug!("Failed to read stats rollups: {}", e);
+            Default::default()
+        }
+    };
+
+    let mut fresh = Vec::new();
+    let mut all_stats = Vec::with_capacity(commit_shas.len());
+    for commit_sha in commit_shas {
+        let note_oid = note_oids.get(commit_sha).cloned();
+        let cached = stored
+            .get(commit_sha)
+            .filter(|record| record.note_oid == note_oid)
+            .and_then(|record| serde_json::from_str::<CommitStats>(&record.stats_json).ok());
+        match cached {
+            Some(stats) => all_stats.push((stats, true)),
+            None => {
+                let stats = stats_for_commit_stats(repo, commit_sha, ignore_patterns, whitespace)?;
+                fresh.push(rollup_record(commit_sha, &ignore_key, note_oid, &stats)?);
+                all_stats.push((stats, false));
+            }
+        }
+    }
+
+    if let Err(e) = with_metrics_db(|db| db.upsert_stats_rollups(&fresh)) {
+        tracing::debug!("Failed to store stats rollups: {}", e);
+    }
+    Ok(all_stats)
+}
+
+/// Commits `stats --backfill` fills: every commit in `range`, or without a range
+/// the commits reachable from HEAD that carry an authorship note.
+pub fn commits_to_backfill(
+    repo: &Repository,
+    range: Option<CommitRange>,
+) -> Result<Vec<String>, GitAiError> {
+    if let Some(range) = range {
+        return Ok(range.into_iter().map(|c| c.id().to_string()).collect());
+    }
+
+    let mut args = repo.global_args_for_exec();
+    args.push("rev-list".to_string());
+    args.push("HEAD".to_string());
+    let output = exec_git(&args)?;
+    let reachable: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+    let noted = commits_with_notes(repo, &reachable)?;
+    Ok(reachable
+        .into_iter()
+        .filter(|sha| noted.contains(sha))
+        .collect())
+}
+
+/// Store the rollup for a commit whose stats were just computed (post-commit).
+/// Best effort: a missing rollup is recomputed on the next range query.
+pub fn record_commit_rollup(
+    repo: &Repository,
+    commit_sha: &str,
+    ignore_patterns: &[String],
+    whitespace: WhitespaceMode,
+    stats: &CommitStats,
+) {
+    if !rollups_enabled() {
+        return;
+    }
+
+    let result = read_note_blob_oids(repo, &[commit_sha.to_string()]).and_then(|note_oids| {
+        let record = rollup_record(
+            commit_sha,
+            &rollup_ignore_key(ignore_patterns, whitespace),
+            note_oids.get(commit_sha).cloned(),
+            stats,
+        )?;
+        with_metrics_db(|db| db.upsert_stats_rollups(&[record]))
+    });
+    if let Err(e) = result {
+        tracing::debug!("Failed to record stats rollup for {}: {}", commit_sha, e);
+    }
+}
+
+fn rollups_enabled() -> bool {
+    Config::get().notes_backend_kind() == NotesBackendKind::GitNotes
+}
+
+fn rollup_record(
+    commit_sha: &str,
+    ignore_key: &str,
+    note_oid: Option<String>,
+    stats: &CommitStats,
+) -> Result<StatsRollupRecord, GitAiError> {
+    let (ai_lines, human_lines, mixed_lines) = attribution_line_counts(stats);
+    Ok(StatsRollupRecord {
+        commit_sha: commit_sha.to_string(),
+        ignore_key: ignore_key.to_string(),
+        note_oid,
+        ai_lines,
+        human_lines,
+        mixed_lines,
+        stats_json: serde_json::to_string(stats)?,
+        computed_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+    })
+}
+
+fn with_metrics_db<T>(
+    f: impl FnOnce(&mut MetricsDatabase) -> Result<T, GitAiError>,
+) -> Result<T, GitAiError> {
+    let mut db = MetricsDatabase::global()?
+        .lock()
+        .map_err(|_| GitAiError::Generic("metrics database lock poisoned".to_string()))?;
+    f(&mut db)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::stats::ToolModelHeadlineStats;
+
+    #[test]
+    fn test_rollup_ignore_key_ignores_pattern_order_and_duplicates() {
+        let a = rollup_ignore_key(
+            &["*.lock".to_string(), "dist/**".to_string()],
+            WhitespaceMode::Exact,
+        );
+        let b = rollup_ignore_key(
+            &[
+                "dist/**".to_string(),
+                "*.lock".to_string(),
+                "*.lock".to_string(),
+            ],
+            WhitespaceMode::Exact,
+        );
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 16);
+        assert_ne!(a, rollup_ignore_key(&[], WhitespaceMode::Exact));
+    }
+
+    #[test]
+    fn test_rollup_ignore_key_separates_whitespace_modes() {
+        let exact = rollup_ignore_key(&[], WhitespaceMode::Exact);
+        let whitespace = rollup_ignore_key(&[], WhitespaceMode::Whitespace);
+        let reindent = rollup_ignore_key(&[], WhitespaceMode::Reindent);
+        assert_ne!(exact, whitespace);
+        assert_ne!(exact, reindent);
+        assert_ne!(whitespace, reindent);
+    }
+
+    #[test]
+    fn test_add_commit_stats_sums_totals_and_tool_vector() {
+        let commit = |ai: u32, added: u32| CommitStats {
+            ai_additions: ai,
+            ai_accepted: ai,
+            git_diff_added_lines: added,
+            git_diff_deleted_lines: 1,
+            unknown_additions: added - ai,
+            tool_model_breakdown: [(
+                "cursor::gpt-5".to_string(),
+                ToolModelHeadlineStats {
+                    ai_additions: ai,
+                    ai_accepted: ai,
+                },
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+
+        let mut total = CommitStats::default();
+        add_commit_stats(&mut total, &commit(3, 5));
+        add_commit_stats(&mut total, &commit(4, 10));
+
+        assert_eq!(total.ai_accepted, 7);
+        assert_eq!(total.git_diff_added_lines, 15);
+        assert_eq!(total.git_diff_deleted_lines, 2);
+        assert_eq!(total.unknown_additions, 8);
+        assert_eq!(total.tool_model_breakdown["cursor::gpt-5"].ai_accepted, 7);
+        assert_eq!(attribution_line_counts(&total), (7, 8, 0));
+    }
+}
@@ -0,0 +1,107 @@
+//! Classification of changed files as test or production code.
+//!
+//! Checkpoint entries and commit stats flag test files so reports can separate
+//! AI-written tests from AI-written features. `test_paths` in `.git-ai.toml`
+//! replaces the built-in globs below when set.
+
+use crate::authorship::ignore::IgnoreMatcher;
+use crate::git::repository::Repository;
+use crate::workspace_config::effective_workspace_config;
+
+const DEFAULT_TEST_PATTERNS: &[&str] = &[
+    "test/*",
+    "tests/*",
+    "spec/*",
+    "__tests__/*",
+    "*/test/*",
+    "*/tests/*",
+    "*/spec/*",
+    "*/__tests__/*",
+    "*.test.*",
+    "*.spec.*",
+    "*_test.go",
+    "*_test.py",
+    "test_*.py",
+    "*_spec.rb",
+    "*Test.java",
+    "*Tests.java",
+    "*Test.kt",
+    "*Tests.cs",
+];
+
+/// Matches repository-relative paths against test globs, by full path or file name.
+#[derive(Clone, Debug, Default)]
+pub struct TestPathMatcher {
+    matcher: IgnoreMatcher,
+}
+
+impl TestPathMatcher {
+    pub fn new(patterns: &[String]) -> Self {
+        Self {
+            matcher: IgnoreMatcher::new(patterns),
+        }
+    }
+
+    pub fn is_test(&self, path: &str) -> bool {
+        self.matcher.is_ignored(path)
+    }
+}
+
+pub fn default_test_patterns() -> Vec<String> {
+    DEFAULT_TEST_PATTERNS
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .collect()
+}
+
+/// The workspace `test_paths`, or the built-in globs when none are configured.
+pub fn effective_test_patterns(repo: &Repository) -> Vec<String> {
+    let configured = effective_workspace_config(repo).test_paths;
+    if configured.is_empty() {
+        default_test_patterns()
+    } else {
+        configured
+    }
+}
+
+pub fn test_path_matcher_for_repo(repo: &Repository) -> TestPathMatcher {
+    TestPathMatcher::new(&effective_test_patterns(repo))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_patterns_classify_common_layouts() {
+        let matcher = TestPathMatcher::new(&default_test_patterns());
+        for path in [
+            "tests/integration/main.rs",
+            "crates/core/tests/parse.rs",
+            "src/__tests__/app.tsx",
+            "src/app.test.ts",
+            "web/button.spec.js",
+            "pkg/server/handler_test.go",
+            "tools/test_cli.py",
+            "spec/models/user_spec.rb",
+            "src/main/java/FooTest.java",
+        ] {
+            assert!(matcher.is_test(path), "{} should be a test", path);
+        }
+        for path in [
+            "src/main.rs",
+            "src/latest/mod.rs",
+            "docs/testing.md",
+            "src/contest.py",
+        ] {
+            assert!(!matcher.is_test(path), "{} should be production", path);
+        }
+    }
+
+    #[test]
+    fn configured_patterns_replace_defaults() {
+        let matcher = TestPathMatcher::new(&["qa/**".to_string()]);
+        assert!(matcher.is_test("qa/smoke.rs"));
+        assert!(!matcher.is_test("tests/unit.rs"));
+    }
+}
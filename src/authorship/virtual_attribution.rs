@@ -7,7 +7,7 @@ use crate::authorship::hunk_shift::{DiffHunk, apply_hunk_shifts_to_line_attribut
 use crate::authorship::imara_diff_utils::{
     content_eq_ignoring_line_endings, normalize_line_endings,
 };
-use crate::authorship::working_log::CheckpointKind;
+use crate::authorship::working_log::{AgentId, Checkpoint, CheckpointKind};
 use crate::commands::blame::{GitAiBlameOptions, OLDEST_AI_BLAME_DATE};
 use crate::error::GitAiError;
 use crate::git::repository::{Repository, batch_read_paths_at_treeishes};
@@ -464,13 +464,13 @@ impl VirtualAttributions {
                             &agent_id.tool,
                         );
 
-                    let session_record = SessionRecord {
-                        agent_id: agent_id.clone(),
-                        human_author: human_author.clone(),
-                        custom_attributes: None,
-                    };
-
-                    sessions.insert(session_id.clone(), session_record);
+                    record_checkpoint_session(
+                        &mut sessions,
+                        &session_id,
+                        agent_id,
+                        &human_author,
+                        checkpoint,
+                    );
 
                     // Track additions/deletions keyed by session_id
                     *session_additions.entry(session_id.clone()).or_insert(0) +=
@@ -591,6 +591,91 @@ impl VirtualAttributions {
         })
     }
 
+    /// Create VirtualAttributions for unsaved editor buffers.
+    ///
+    /// Starts from [`Self::from_just_working_log`] and, for each `overlays` entry
+    /// (repo-relative path -> in-memory buffer contents), carries the file's
+    /// attributions from its on-disk content through to the buffer as a human
+    /// edit would, without writing a checkpoint. The result predicts what the
+    /// next checkpoint records once the buffer is saved, for live editor gutters.
+    pub fn from_buffer_overlay(
+        repo: Repository,
+        base_commit: String,
+        human_author: Option<String>,
+        overlays: &HashMap<String, String>,
+    ) -> Result<Self, GitAiError> {
+        let mut virtual_attrs = Self::from_just_working_log(repo, base_commit, human_author)?;
+        let verbatim_eol =
+            crate::authorship::ignore::build_verbatim_eol_matcher(&virtual_attrs.repo);
+        let human = CheckpointKind::Human.to_str();
+        let ts = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+
+        for (file_path, buffer) in overlays {
+            let disk_content = match virtual_attrs.file_contents.get(file_path) {
+                Some(content) => content.clone(),
+                None => virtual_attrs
+                    .repo
+                    .workdir()
+                    .ok()
+                    .and_then(|workdir| std::fs::read_to_string(workdir.join(file_path)).ok())
+                    .unwrap_or_default(),
+            };
+            virtual_attrs
+                .file_contents
+                .insert(file_path.clone(), buffer.clone());
+
+            let Some((disk_attrs, _)) = virtual_attrs.attributions.get(file_path) else {
+                continue;
+            };
+            let normalize_line_endings =
+                !crate::authorship::ignore::should_ignore_file_with_matcher(
+                    file_path,
+                    &verbatim_eol,
+                );
+            let unchanged = if normalize_line_endings {
+                content_eq_ignoring_line_endings(&disk_content, buffer)
+            } else {
+                disk_content == *buffer
+            };
+            if unchanged {
+                continue;
+            }
+            let tracker =
+                crate::authorship::attribution_tracker::AttributionTracker::for_line_endings(
+                    normalize_line_endings,
+                );
+            let filled =
+                tracker.attribute_unattributed_ranges(&disk_content, disk_attrs, &human, 0);
+            let buffer_attrs = tracker.update_attributions_for_checkpoint(
+                &disk_content,
+                buffer,
+                &filled,
+                &human,
+                ts,
+                false,
+            )?;
+            let line_attrs =
+                crate::authorship::attribution_tracker::attributions_to_line_attributions_for_checkpoint(
+                    &buffer_attrs,
+                    buffer,
+                    false,
+                );
+            if line_attrs.is_empty() {
+                virtual_attrs.attributions.remove(file_path);
+                continue;
+            }
+            let char_attrs = line_attributions_to_attributions(&line_attrs, buffer, 0);
+            virtual_attrs
+                .attributions
+                .insert(file_path.clone(), (char_attrs, line_attrs));
+        }
+
+        Ok(virtual_attrs)
+    }
+
     /// Create VirtualAttributions from working-log state using an exact captured snapshot
     /// instead of the live worktree.
     pub fn from_working_log_snapshot(
@@ -661,13 +746,13 @@ impl VirtualAttributions {
                             &agent_id.tool,
                         );
 
-                    let session_record = SessionRecord {
-                        agent_id: agent_id.clone(),
-                        human_author: human_author.clone(),
-                        custom_attributes: None,
-                    };
-
-                    sessions.insert(session_id.clone(), session_record);
+                    record_checkpoint_session(
+                        &mut sessions,
+                        &session_id,
+                        agent_id,
+                        &human_author,
+                        checkpoint,
+                    );
 
                     // Track additions/deletions keyed by session_id
                     *session_additions.entry(session_id.clone()).or_insert(0) +=
@@ -847,13 +932,13 @@ impl VirtualAttributions {
                             &agent_id.tool,
                         );
 
-                    let session_record = SessionRecord {
-                        agent_id: agent_id.clone(),
-                        human_author: human_author.clone(),
-                        custom_attributes: None,
-                    };
-
-                    sessions.insert(session_id.clone(), session_record);
+                    record_checkpoint_session(
+                        &mut sessions,
+                        &session_id,
+                        agent_id,
+                        &human_author,
+                        checkpoint,
+                    );
 
                     // Track additions/deletions keyed by session_id
                     *session_additions.entry(session_id.clone()).or_insert(0) +=
@@ -1091,6 +1176,7 @@ impl VirtualAttributions {
         authorship_log.metadata.sessions = self.sessions.clone();
 
         authorship_log.attestations = build_attestations_from_attributions(&self.attributions);
+        authorship_log.retain_attested_tool_versions();
 
         Ok(authorship_log)
     }
@@ -1246,7 +1332,7 @@ fn collect_committed_hunks(
 }
 
 /// Detect file renames between parent and commit. Returns a map of old_path → new_path.
-fn detect_renames_in_commit(
+pub(crate) fn detect_renames_in_commit(
     repo: &Repository,
     parent_sha: &str,
     commit_sha: &str,
@@ -1984,6 +2070,39 @@ fn batch_file_contents(
     Ok(map)
 }
 
+/// Of `file_paths`, those absent from both `commit_sha` and the worktree.
+/// Renamed paths are not deletions. Bare repositories have no worktree, so
+/// nothing counts as deleted there.
+fn files_deleted_in_commit_and_worktree<'a>(
+    repo: &Repository,
+    commit_sha: &str,
+    file_paths: impl Iterator<Item = &'a String>,
+    rename_map: &HashMap<String, String>,
+) -> Result<HashSet<String>, GitAiError> {
+    let Ok(workdir) = repo.workdir() else {
+        return Ok(HashSet::new());
+    };
+    let candidates: Vec<String> = file_paths
+        .filter(|file_path| !rename_map.contains_key(*file_path))
+        .filter(|file_path| !workdir.join(file_path).exists())
+        .cloned()
+        .collect();
+    if candidates.is_empty() {
+        return Ok(HashSet::new());
+    }
+
+    let requests: Vec<(String, String)> = candidates
+        .iter()
+        .map(|file_path| (commit_sha.to_string(), file_path.clone()))
+        .collect();
+    let committed = batch_read_paths_at_treeishes(repo, &requests)?;
+    Ok(requests
+        .into_iter()
+        .filter(|request| !committed.contains_key(request))
+        .map(|(_, file_path)| file_path)
+        .collect())
+}
+
 pub fn checkout_merge_final_state_snapshot(
     repo: &Repository,
     old_head: &str,
@@ -2267,9 +2386,22 @@ impl VirtualAttributions {
         // Remove files with no unstaged hunks
         unstaged_hunks.retain(|_, ranges| !ranges.is_empty());
 
+        // Files the commit deleted that are gone from the worktree too (e.g.
+        // `rm` + `git commit -a`) have nothing to attest and nothing to carry
+        // over; without this their observed content would land in INITIAL.
+        let deleted_files = files_deleted_in_commit_and_worktree(
+            repo,
+            commit_sha,
+            self.attributions
+                .iter()
+                .filter(|(_, (_, line_attrs))| !line_attrs.is_empty())
+                .map(|(file_path, _)| file_path),
+            &rename_map,
+        )?;
+
         // Process each file
         for (file_path, (_, line_attrs)) in &self.attributions {
-            if line_attrs.is_empty() {
+            if line_attrs.is_empty() || deleted_files.contains(file_path) {
                 continue;
             }
 
@@ -2693,6 +2825,7 @@ impl VirtualAttributions {
             sessions: initial_sessions,
         };
 
+        authorship_log.retain_attested_tool_versions();
         Ok((authorship_log, initial_attributions, initial_file_contents))
     }
 
@@ -2888,6 +3021,7 @@ impl VirtualAttributions {
                     || committed_prompt_ids.contains(prompt_id)
             });
         }
+        authorship_log.retain_attested_tool_versions();
 
         Ok(authorship_log)
     }
@@ -3086,9 +3220,9 @@ pub fn merge_attributions_favoring_first(
 ) -> Result<VirtualAttributions, GitAiError> {
     use crate::authorship::attribution_tracker::AttributionTracker;
 
-    let tracker = AttributionTracker::new();
     let ts = primary.ts;
     let repo = primary.repo.clone();
+    let verbatim_eol = crate::authorship::ignore::build_verbatim_eol_matcher(&repo);
     let base_commit = primary.base_commit.clone();
 
     // Merge prompts from both VAs (primary wins on conflict)
@@ -3131,6 +3265,12 @@ pub fn merge_attributions_favoring_first(
             None => continue, // Skip files not in final state
         };
 
+        // Line-ending churn is only an edit where `.gitattributes` keeps git
+        // from normalizing it.
+        let tracker = AttributionTracker::for_line_endings(
+            !crate::authorship::ignore::should_ignore_file_with_matcher(&file_path, &verbatim_eol),
+        );
+
         // Get attributions from both sources
         let primary_attrs = primary.get_char_attributions(&file_path);
         let secondary_attrs = secondary.get_char_attributions(&file_path);
@@ -3586,6 +3726,54 @@ pub fn restore_virtual_attribution_carryover(
     Ok(())
 }
 
+/// Fold `checkpoint` into the record of its agent session `session_id`, keeping
+/// what earlier checkpoints of the session recorded per trace: tool calls,
+/// subagents and tool versions.
+fn record_checkpoint_session(
+    sessions: &mut BTreeMap<String, SessionRecord>,
+    session_id: &str,
+    agent_id: &AgentId,
+    human_author: &Option<String>,
+    checkpoint: &Checkpoint,
+) {
+    let (tool_calls, subagents, tool_versions) = sessions
+        .remove(session_id)
+        .map(|existing| {
+            (
+                existing.tool_calls,
+                existing.subagents,
+                existing.tool_versions,
+            )
+        })
+        .unwrap_or_default();
+    let mut session_record = SessionRecord {
+        agent_id: agent_id.clone(),
+        human_author: human_author.clone(),
+        custom_attributes: None,
+        tool_calls,
+        subagents,
+        tool_versions,
+    };
+    if let Some(trace_id) = &checkpoint.trace_id {
+        if let Some(tool_use_id) = &checkpoint.tool_use_id {
+            session_record
+                .tool_calls
+                .insert(trace_id.clone(), tool_use_id.clone());
+        }
+        if let Some(subagent) = &checkpoint.subagent {
+            session_record
+                .subagents
+                .insert(trace_id.clone(), subagent.clone());
+        }
+        if let Some(tool_version) = &checkpoint.tool_version {
+            session_record
+                .tool_versions
+                .insert(trace_id.clone(), tool_version.clone());
+        }
+    }
+    sessions.insert(session_id.to_string(), session_record);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -19,6 +19,17 @@ pub struct WorkingLogEntry {
     pub attributions: Vec<Attribution>,
     #[serde(default)]
     pub line_attributions: Vec<LineAttribution>,
+    /// Whether the file matches the repository's test globs (see `test_paths`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_test: bool,
+    /// Whether an inline `git-ai:` comment forced some of the attributions (see
+    /// `attribution_comments`).
+    #[serde(
+        rename = "override",
+        default,
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub attribution_override: bool,
 }
 
 impl WorkingLogEntry {
@@ -34,6 +45,8 @@ impl WorkingLogEntry {
             blob_sha,
             attributions,
             line_attributions,
+            is_test: false,
+            attribution_override: false,
         }
     }
 }
@@ -45,6 +58,50 @@ pub struct AgentId {
     pub model: String,
 }
 
+/// A subagent spawned by the session's agent (e.g. a Claude Code Task tool
+/// agent) that made a checkpoint's edits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubagentInfo {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_type: Option<String>,
+}
+
+impl SubagentInfo {
+    /// The subagent recorded in checkpoint request metadata, if any.
+    pub fn from_metadata(metadata: &HashMap<String, String>) -> Option<Self> {
+        let id = metadata
+            .get(SUBAGENT_ID_METADATA_KEY)
+            .filter(|id| !id.is_empty())?;
+        Some(Self {
+            id: id.clone(),
+            agent_type: metadata
+                .get(SUBAGENT_TYPE_METADATA_KEY)
+                .filter(|agent_type| !agent_type.is_empty())
+                .cloned(),
+        })
+    }
+
+    /// External session id of the subagent's own transcript
+    /// (`subagents/agent-<id>.jsonl`), which its transcript metrics are keyed by.
+    pub fn external_session_id(&self) -> String {
+        if self.id.starts_with("agent-") {
+            self.id.clone()
+        } else {
+            format!("agent-{}", self.id)
+        }
+    }
+}
+
+impl fmt::Display for SubagentInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.agent_type {
+            Some(agent_type) => write!(f, "{} ({})", agent_type, self.id),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CheckpointKind {
     Human,
@@ -93,6 +150,21 @@ impl CheckpointKind {
     }
 }
 
+/// Checkpoint request metadata key carrying `git-ai checkpoint --label`.
+pub const CHECKPOINT_LABEL_METADATA_KEY: &str = "checkpoint_label";
+
+/// Checkpoint request metadata keys carrying the subagent behind an agent edit.
+pub const SUBAGENT_ID_METADATA_KEY: &str = "subagent_id";
+pub const SUBAGENT_TYPE_METADATA_KEY: &str = "subagent_type";
+
+/// Checkpoint request metadata key carrying the agent's own version, e.g. the
+/// Claude Code or Cursor release that made the edit.
+pub const TOOL_VERSION_METADATA_KEY: &str = "tool_version";
+
+/// A checkpoint repeating the previous one within this many seconds is a
+/// duplicate hook event, not a new edit.
+pub const CHECKPOINT_DEDUPE_WINDOW_SECS: u64 = 10;
+
 /// Metadata stored for KnownHuman checkpoints, identifying the IDE that fired the save event
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct KnownHumanMetadata {
@@ -136,6 +208,20 @@ pub struct Checkpoint {
     pub known_human_metadata: Option<KnownHumanMetadata>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub trace_id: Option<String>,
+    /// The agent tool invocation (e.g. Claude Code's Edit/Write/MultiEdit
+    /// `tool_use_id`) that produced this checkpoint's changes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_use_id: Option<String>,
+    /// Set by `git-ai checkpoint --label`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// The subagent that made this checkpoint's changes, when not the session's
+    /// own agent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subagent: Option<SubagentInfo>,
+    /// Version of the agent tool that made this checkpoint's changes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_version: Option<String>,
 }
 
 impl Checkpoint {
@@ -163,14 +249,57 @@ impl Checkpoint {
             git_ai_version: Some(GIT_AI_VERSION.to_string()),
             known_human_metadata: None,
             trace_id: None,
+            tool_use_id: None,
+            label: None,
+            subagent: None,
+            tool_version: None,
         }
     }
+
+    /// Whether this checkpoint repeats `previous`: the same file contents
+    /// (`diff` hashes every checkpointed file), kind and author, recorded within
+    /// [`CHECKPOINT_DEDUPE_WINDOW_SECS`]. Agents sometimes fire the same
+    /// post-edit hook twice.
+    pub fn is_duplicate_of(&self, previous: &Checkpoint) -> bool {
+        !self.diff.is_empty()
+            && self.diff == previous.diff
+            && self.kind == previous.kind
+            && self.author == previous.author
+            && self.agent_id == previous.agent_id
+            && self.timestamp.abs_diff(previous.timestamp) <= CHECKPOINT_DEDUPE_WINDOW_SECS
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_is_duplicate_of_requires_same_content_author_and_window() {
+        let mut first = Checkpoint::new(
+            CheckpointKind::AiAgent,
+            "hash-a".to_string(),
+            "agent".to_string(),
+            Vec::new(),
+        );
+        first.timestamp = 1_000;
+        let mut repeat = first.clone();
+        repeat.timestamp = 1_000 + CHECKPOINT_DEDUPE_WINDOW_SECS;
+        assert!(repeat.is_duplicate_of(&first));
+
+        let mut later = repeat.clone();
+        later.timestamp = 1_001 + CHECKPOINT_DEDUPE_WINDOW_SECS;
+        assert!(!later.is_duplicate_of(&first));
+
+        let mut other_content = repeat.clone();
+        other_content.diff = "hash-b".to_string();
+        assert!(!other_content.is_duplicate_of(&first));
+
+        let mut other_author = repeat.clone();
+        other_author.author = "human".to_string();
+        assert!(!other_author.is_duplicate_of(&first));
+    }
+
     #[test]
     fn test_checkpoint_serialization() {
         let entry = WorkingLogEntry::new(
@@ -0,0 +1,13 @@
+//! Hook entry point built with `--features hooks-only`.
+//!
+//! Agent hooks only ever run `checkpoint`, so this binary skips clap parsing, the
+//! superuser guard and the rest of the CLI dispatch. It links the same library as
+//! `git-ai`, so it is not smaller; it only narrows what a hook can invoke.
+//! `git-ai install-hooks` points hooks at this binary when it is installed next to
+//! `git-ai`.
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    git_ai::commands::git_ai_handlers::handle_git_ai_hook(&args);
+    std::process::exit(0);
+}
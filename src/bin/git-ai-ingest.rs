@@ -0,0 +1,96 @@
+//! Self-hosted metrics ingestion server built with `--features ingest-server`.
+//!
+//! Serves `POST /worker/metrics/upload` for clients whose `api_base_url` points
+//! here and stores the events in SQLite or Postgres. See `git_ai::ingest`.
+
+use git_ai::ingest::{IngestState, IngestStore, router};
+
+const DEFAULT_LISTEN: &str = "127.0.0.1:8787";
+const DEFAULT_DATABASE: &str = "git-ai-metrics.db";
+
+fn print_help() {
+    eprintln!("git-ai-ingest - self-hosted git-ai metrics ingestion server");
+    eprintln!();
+    eprintln!("Usage: git-ai-ingest [--listen <addr>] [--database <url>] [--api-key <key>]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!(
+        "  --listen <addr>     Address to bind (default: {}, env GIT_AI_INGEST_LISTEN)",
+        DEFAULT_LISTEN
+    );
+    eprintln!("  --database <url>    SQLite path or postgres:// URL");
+    eprintln!(
+        "                      (default: {}, env GIT_AI_INGEST_DATABASE)",
+        DEFAULT_DATABASE
+    );
+    eprintln!("  --api-key <key>     Require this key in X-API-Key or a bearer token");
+    eprintln!("                      (env GIT_AI_INGEST_API_KEY)");
+    eprintln!("  -h, --help          Show this help message");
+}
+
+#[tokio::main]
+async fn main() {
+    let mut listen = std::env::var("GIT_AI_INGEST_LISTEN").unwrap_or(DEFAULT_LISTEN.to_string());
+    let mut database =
+        std::env::var("GIT_AI_INGEST_DATABASE").unwrap_or(DEFAULT_DATABASE.to_string());
+    let mut api_key = std::env::var("GIT_AI_INGEST_API_KEY")
+        .ok()
+        .filter(|key| !key.is_empty());
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--listen" if i + 1 < args.len() => {
+                listen = args[i + 1].clone();
+                i += 2;
+            }
+            "--database" if i + 1 < args.len() => {
+                database = args[i + 1].clone();
+                i += 2;
+            }
+            "--api-key" if i + 1 < args.len() => {
+                api_key = Some(args[i + 1].clone());
+                i += 2;
+            }
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other => {
+                eprintln!("Error: unknown option '{}'", other);
+                eprintln!("Run 'git-ai-ingest --help' for usage");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .init();
+
+    let store = match IngestStore::connect(&database).await {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("Error: failed to open {}: {}", database, e);
+            std::process::exit(1);
+        }
+    };
+    let listener = match tokio::net::TcpListener::bind(&listen).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Error: failed to bind {}: {}", listen, e);
+            std::process::exit(1);
+        }
+    };
+
+    tracing::info!(%listen, "git-ai-ingest listening");
+    let app = router(IngestState { store, api_key });
+    if let Err(e) = axum::serve(listener, app).await {
+        eprintln!("Error: server failed: {}", e);
+        std::process::exit(1);
+    }
+}
@@ -5,6 +5,7 @@ use crate::git::repository::find_repository_in_path;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 const GITHUB_CI_TEMPLATE_YAML: &str = include_str!("workflow_templates/github.yaml");
 
@@ -234,6 +235,216 @@ fn authenticate_clone_url(clone_url: &str, token: &str) -> String {
     )
 }
 
+/// Merged PRs fetched per page of `GET /repos/{owner}/{repo}/pulls`.
+const PULLS_PER_PAGE: usize = 100;
+
+/// A merged pull request as listed by the REST API.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct GithubPullRequest {
+    pub number: u64,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<GithubLabel>,
+    #[serde(default)]
+    pub user: Option<GithubUser>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub merged_at: Option<String>,
+    #[serde(default)]
+    pub merge_commit_sha: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct GithubLabel {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct GithubUser {
+    pub login: String,
+}
+
+/// A commit of a pull request, with its full message for trailer detection.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct GithubPullRequestCommit {
+    pub sha: String,
+    pub commit: GithubCommitDetails,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct GithubCommitDetails {
+    #[serde(default)]
+    pub message: String,
+}
+
+/// REST API root: `GITHUB_API_URL` (set on GitHub Enterprise runners) or
+/// api.github.com.
+pub fn github_api_url() -> String {
+    std::env::var("GITHUB_API_URL")
+        .ok()
+        .filter(|url| !url.trim().is_empty())
+        .map(|url| url.trim_end_matches('/').to_string())
+        .unwrap_or_else(|| "https://api.github.com".to_string())
+}
+
+/// `owner/name` of a GitHub remote URL in any form git accepts.
+pub fn github_repo_slug(remote_url: &str) -> Option<String> {
+    let normalized = crate::repo_url::normalize_repo_url(remote_url).ok()?;
+    let path = normalized.strip_prefix("https://")?.split_once('/')?.1;
+    let mut parts = path.split('/');
+    let (owner, name) = (parts.next()?, parts.next()?);
+    if owner.is_empty() || name.is_empty() || parts.next().is_some() {
+        return None;
+    }
+    Some(format!("{}/{}", owner, name))
+}
+
+/// Longest wait for an exhausted GitHub rate limit before giving up instead.
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+/// Retries of one request after waiting out a rate limit.
+const RATE_LIMIT_RETRIES: u32 = 3;
+
+fn github_api_get(endpoint: &str, token: &str) -> Result<crate::http::Response, GitAiError> {
+    let agent = crate::http::build_agent(Some(30));
+    let mut attempt = 0;
+    loop {
+        let request = agent
+            .get(endpoint)
+            .set("Authorization", &format!("Bearer {}", token))
+            .set("Accept", "application/vnd.github+json")
+            .set(
+                "User-Agent",
+                &format!("git-ai/{}", env!("CARGO_PKG_VERSION")),
+            );
+        let response = crate::http::send(request)
+            .map_err(|e| GitAiError::Generic(format!("GitHub API request failed: {}", e)))?;
+        if let Some(wait) = rate_limit_wait(&response, SystemTime::now()) {
+            if attempt >= RATE_LIMIT_RETRIES || wait > MAX_RATE_LIMIT_WAIT {
+                return Err(GitAiError::Generic(format!(
+                    "GitHub API rate limit exhausted; it resets in {}s, re-run then",
+                    wait.as_secs()
+                )));
+            }
+            tracing::debug!(
+                wait_secs = wait.as_secs(),
+                "waiting for the GitHub API rate limit"
+            );
+            std::thread::sleep(wait);
+            attempt += 1;
+            continue;
+        }
+        if matches!(response.status_code, 401 | 403) {
+            return Err(GitAiError::Auth(format!(
+                "GitHub API returned {} for {}; check the token and its repository access",
+                response.status_code, endpoint
+            )));
+        }
+        if response.status_code != 200 {
+            return Err(GitAiError::Generic(format!(
+                "GitHub API returned {} for {}",
+                response.status_code, endpoint
+            )));
+        }
+        return Ok(response);
+    }
+}
+
+/// How long to wait before retrying a rate-limited response, or `None` when
+/// `response` was not rate limited. GitHub answers 403 or 429 with either
+/// `Retry-After` (secondary limits) or an exhausted `X-RateLimit-Remaining`
+/// and the reset time (primary limit).
+fn rate_limit_wait(response: &crate::http::Response, now: SystemTime) -> Option<Duration> {
+    if !matches!(response.status_code, 403 | 429) {
+        return None;
+    }
+    if let Some(secs) = response
+        .retry_after()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+    {
+        return Some(Duration::from_secs(secs));
+    }
+    if response.rate_limit_remaining().map(str::trim) != Some("0") {
+        return None;
+    }
+    let reset = response
+        .rate_limit_reset()
+        .and_then(|value| value.trim().parse::<u64>().ok())?;
+    let reset = UNIX_EPOCH + Duration::from_secs(reset);
+    // A second of slack so the retry does not land just before the reset.
+    Some(reset.duration_since(now).unwrap_or_default() + Duration::from_secs(1))
+}
+
+/// Up to `limit` merged pull requests of `repo_slug`, most recently merged
+/// first. Closed-but-unmerged PRs are skipped and do not count towards `limit`.
+///
+/// The API cannot sort by merge time, so pages are read by update time until
+/// no unread PR can have been merged later than the `limit`-th one found: a
+/// PR is never updated before it is merged.
+pub fn fetch_merged_pull_requests(
+    api_url: &str,
+    repo_slug: &str,
+    token: &str,
+    limit: usize,
+) -> Result<Vec<GithubPullRequest>, GitAiError> {
+    let mut merged: Vec<GithubPullRequest> = Vec::new();
+    let mut page = 1;
+    loop {
+        let endpoint = format!(
+            "{}/repos/{}/pulls?state=closed&sort=updated&direction=desc&per_page={}&page={}",
+            api_url, repo_slug, PULLS_PER_PAGE, page
+        );
+        let response = github_api_get(&endpoint, token)?;
+        let pulls: Vec<GithubPullRequest> = serde_json::from_slice(response.as_bytes())?;
+        let exhausted = pulls.len() < PULLS_PER_PAGE;
+        let oldest_updated = pulls.last().and_then(|pr| pr.updated_at.clone());
+        merged.extend(pulls.into_iter().filter(|pr| pr.merged_at.is_some()));
+        // ISO 8601 UTC timestamps sort chronologically as strings.
+        merged.sort_by(|a, b| b.merged_at.cmp(&a.merged_at));
+        if exhausted {
+            break;
+        }
+        if merged.len() >= limit
+            && let Some(oldest_updated) = oldest_updated
+            && merged[limit - 1].merged_at.as_ref() >= Some(&oldest_updated)
+        {
+            break;
+        }
+        page += 1;
+    }
+    merged.truncate(limit);
+    Ok(merged)
+}
+
+/// The commits of pull request `number` (the API caps this list at 250).
+pub fn fetch_pull_request_commits(
+    api_url: &str,
+    repo_slug: &str,
+    token: &str,
+    number: u64,
+) -> Result<Vec<GithubPullRequestCommit>, GitAiError> {
+    let mut commits = Vec::new();
+    let mut page = 1;
+    loop {
+        let endpoint = format!(
+            "{}/repos/{}/pulls/{}/commits?per_page={}&page={}",
+            api_url, repo_slug, number, PULLS_PER_PAGE, page
+        );
+        let response = github_api_get(&endpoint, token)?;
+        let batch: Vec<GithubPullRequestCommit> = serde_json::from_slice(response.as_bytes())?;
+        let exhausted = batch.len() < PULLS_PER_PAGE;
+        commits.extend(batch);
+        if exhausted {
+            return Ok(commits);
+        }
+        page += 1;
+    }
+}
+
 /// Install or update the GitHub Actions workflow in the current repository
 /// Writes the embedded template to .github/workflows/git-ai.yaml at the repo root
 pub fn install_github_ci_workflow() -> Result<PathBuf, GitAiError> {
@@ -274,6 +485,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_github_repo_slug_from_remote_urls() {
+        assert_eq!(
+            github_repo_slug("git@github.com:acme/repo.git").as_deref(),
+            Some("acme/repo")
+        );
+        assert_eq!(
+            github_repo_slug("https://github.example.com/acme/repo").as_deref(),
+            Some("acme/repo")
+        );
+        assert_eq!(github_repo_slug("https://gitlab.com/group/sub/repo"), None);
+    }
+
     #[test]
     fn test_github_synchronize_payload_deserializes_before_after() {
         let json = r#"{
@@ -314,4 +538,75 @@ mod tests {
         assert_eq!(pull_request.base.ref_name, "main");
         assert_eq!(pull_request.head.ref_name, "feature");
     }
+
+    #[test]
+    fn test_github_api_get_gives_up_on_a_long_rate_limit_reset() {
+        let mut server = mockito::Server::new();
+        let reset = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + 3600;
+        let mock = server
+            .mock("GET", "/repos/acme/repo/pulls/1/commits")
+            .with_status(403)
+            .with_header("X-RateLimit-Remaining", "0")
+            .with_header("X-RateLimit-Reset", &reset.to_string())
+            .expect(1)
+            .create();
+
+        let err = github_api_get(
+            &format!("{}/repos/acme/repo/pulls/1/commits", server.url()),
+            "token",
+        )
+        .unwrap_err();
+
+        mock.assert();
+        assert!(err.to_string().contains("rate limit exhausted"), "{}", err);
+    }
+
+    #[test]
+    fn test_github_api_get_retries_after_a_secondary_rate_limit() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", "/repos/acme/repo/pulls/1/commits")
+            .with_status(429)
+            .with_header("Retry-After", "0")
+            .expect(RATE_LIMIT_RETRIES as usize + 1)
+            .create();
+
+        let err = github_api_get(
+            &format!("{}/repos/acme/repo/pulls/1/commits", server.url()),
+            "token",
+        )
+        .unwrap_err();
+
+        mock.assert();
+        assert!(err.to_string().contains("rate limit exhausted"), "{}", err);
+    }
+
+    #[test]
+    fn test_fetch_merged_pull_requests_orders_by_merge_time() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock(
+                "GET",
+                "/repos/acme/repo/pulls?state=closed&sort=updated&direction=desc&per_page=100&page=1",
+            )
+            .with_status(200)
+            .with_body(
+                r#"[
+                {"number": 1, "updated_at": "2024-03-01T00:00:00Z", "merged_at": "2024-01-01T00:00:00Z"},
+                {"number": 2, "updated_at": "2024-02-15T00:00:00Z", "merged_at": null},
+                {"number": 3, "updated_at": "2024-02-10T00:00:00Z", "merged_at": "2024-02-10T00:00:00Z"}
+            ]"#,
+            )
+            .create();
+
+        let pulls = fetch_merged_pull_requests(&server.url(), "acme/repo", "token", 10).unwrap();
+
+        mock.assert();
+        let numbers: Vec<u64> = pulls.iter().map(|pr| pr.number).collect();
+        assert_eq!(numbers, vec![3, 1]);
+    }
 }
@@ -0,0 +1,600 @@
+//! `git-ai backfill --from-github` — baseline notes for history before git-ai.
+//!
+//! Walks the repository's merged pull requests through the GitHub API, picks
+//! out the AI-authored ones (see [`detect_pull_request_agent`]) and writes a
+//! coarse note on each of their commits that has none yet. Existing notes are
+//! never touched, so the command can be re-run as more history is covered.
+
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::backfill::{
+    BackfillDetection, DEFAULT_AI_LABELS, UNKNOWN_BACKFILL_TOOL, detect_pull_request_agent,
+    synthesize_backfill_note,
+};
+use crate::authorship::diff_base::single_commit_diff_base;
+use crate::ci::github::{
+    GithubPullRequest, GithubPullRequestCommit, fetch_merged_pull_requests,
+    fetch_pull_request_commits, github_api_url, github_repo_slug,
+};
+use crate::commands::output::OutputOptions;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::log_records::log_records;
+use crate::git::notes_api;
+use crate::git::repository::Repository;
+use serde::Serialize;
+use std::io::Read;
+
+/// Merged PRs examined when `--limit` is not given.
+const DEFAULT_PR_LIMIT: usize = 200;
+
+#[derive(Debug, Serialize)]
+struct BackfillOutput {
+    repository: String,
+    pull_requests_scanned: usize,
+    written: bool,
+    pull_requests: Vec<BackfilledPullRequest>,
+}
+
+#[derive(Debug, Serialize)]
+struct BackfilledPullRequest {
+    number: u64,
+    title: String,
+    #[serde(flatten)]
+    detection: BackfillDetection,
+    /// Commits that received a synthesized note.
+    commits: Vec<String>,
+    /// Commits left alone because they already had a note.
+    skipped_existing: Vec<String>,
+    /// Merged commits not present in the local clone.
+    missing: Vec<String>,
+    ai_lines: u32,
+}
+
+pub fn handle_backfill(args: &[String]) {
+    let mut output = OutputOptions::current();
+    let mut from_github = false;
+    let mut repo_slug: Option<String> = None;
+    let mut token_from_stdin = false;
+    let mut limit = DEFAULT_PR_LIMIT;
+    let mut labels: Vec<String> = Vec::new();
+    let mut dry_run = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--from-github" => {
+                from_github = true;
+                i += 1;
+            }
+            "--repo" if i + 1 < args.len() => {
+                repo_slug = Some(args[i + 1].clone());
+                i += 2;
+            }
+            "--token-stdin" => {
+                token_from_stdin = true;
+                i += 1;
+            }
+            "--limit" if i + 1 < args.len() => {
+                limit = match args[i + 1].parse::<usize>() {
+                    Ok(value) if value > 0 => value,
+                    _ => {
+                        eprintln!("Error: --limit expects a positive number");
+                        std::process::exit(1);
+                    }
+                };
+                i += 2;
+            }
+            "--label" if i + 1 < args.len() => {
+                labels.push(args[i + 1].clone());
+                i += 2;
+            }
+            "--dry-run" | "-n" => {
+                dry_run = true;
+                i += 1;
+            }
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other if output.parse_flag(other) => {
+                i += 1;
+            }
+            other => {
+                eprintln!("Error: unknown option '{}'", other);
+                eprintln!("Run 'git ai backfill --help' for usage");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if !from_github {
+        eprintln!("Error: give the history source with --from-github");
+        eprintln!("Run 'git ai backfill --help' for usage");
+        std::process::exit(1);
+    }
+    if labels.is_empty() {
+        labels = DEFAULT_AI_LABELS
+            .iter()
+            .map(|label| label.to_string())
+            .collect();
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Error: not a git repository ({})", e);
+            std::process::exit(1);
+        }
+    };
+
+    let Some(repo_slug) = repo_slug.or_else(|| default_repo_slug(&repo)) else {
+        eprintln!(
+            "Error: could not tell the GitHub repository from the remotes; pass --repo <owner>/<name>"
+        );
+        std::process::exit(1);
+    };
+    // The token is never taken from argv, where other users can read it in `ps`.
+    let token = if token_from_stdin {
+        let mut token = String::new();
+        if let Err(e) = std::io::stdin().read_to_string(&mut token) {
+            output.exit_with_error(&GitAiError::IoError(e));
+        }
+        Some(token)
+    } else {
+        std::env::var("GITHUB_TOKEN")
+            .ok()
+            .or_else(|| std::env::var("GH_TOKEN").ok())
+    };
+    let Some(token) = token
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+    else {
+        output.exit_with_error(&GitAiError::Auth(
+            "a GitHub token is required; set GITHUB_TOKEN or pipe it to --token-stdin".to_string(),
+        ));
+    };
+
+    let request = GithubBackfill {
+        api_url: github_api_url(),
+        repo_slug,
+        token,
+        limit,
+        labels,
+        dry_run,
+    };
+    match backfill_from_github(&repo, &request, &output) {
+        Ok(result) if output.is_json() => {
+            if let Err(e) = output.print_json(&result) {
+                output.exit_with_error(&e);
+            }
+        }
+        Ok(result) => {
+            if !output.is_quiet() {
+                print_summary(&result);
+            }
+        }
+        Err(e) => {
+            output.exit_with_error(&e);
+        }
+    }
+}
+
+/// `owner/name` of the default remote, when it points at GitHub or GHE.
+fn default_repo_slug(repo: &Repository) -> Option<String> {
+    let remote = repo.get_default_remote().ok()??;
+    let (_, url) = repo
+        .remotes_with_urls()
+        .ok()?
+        .into_iter()
+        .find(|(name, _)| *name == remote)?;
+    github_repo_slug(&url)
+}
+
+/// Where and what `backfill --from-github` reads.
+struct GithubBackfill {
+    api_url: String,
+    repo_slug: String,
+    token: String,
+    limit: usize,
+    labels: Vec<String>,
+    dry_run: bool,
+}
+
+fn backfill_from_github(
+    repo: &Repository,
+    request: &GithubBackfill,
+    output: &OutputOptions,
+) -> Result<BackfillOutput, GitAiError> {
+    let (api_url, repo_slug, token) = (
+        request.api_url.as_str(),
+        request.repo_slug.as_str(),
+        request.token.as_str(),
+    );
+    let labels = request.labels.as_slice();
+    let pull_requests = fetch_merged_pull_requests(api_url, repo_slug, token, request.limit)?;
+
+    let mut backfilled = Vec::new();
+    let mut notes: Vec<(String, String)> = Vec::new();
+    for pr in &pull_requests {
+        // Labels, the author, the description and the merged commits already in
+        // the clone cost no request; the PR's commits are only fetched when
+        // those name no specific agent.
+        let local_messages = merged_commit_messages(repo, pr);
+        let mut pr_commits: Option<Vec<GithubPullRequestCommit>> = None;
+        let mut detection = detect(pr, local_messages.iter().map(String::as_str), labels);
+        if detection
+            .as_ref()
+            .is_none_or(|detection| detection.tool == UNKNOWN_BACKFILL_TOOL)
+        {
+            let commits = fetch_pull_request_commits(api_url, repo_slug, token, pr.number)?;
+            let messages = local_messages
+                .iter()
+                .map(String::as_str)
+                .chain(commits.iter().map(|commit| commit.commit.message.as_str()));
+            detection = detect(pr, messages, labels);
+            pr_commits = Some(commits);
+        }
+        let Some(detection) = detection else {
+            continue;
+        };
+
+        let mut entry = BackfilledPullRequest {
+            number: pr.number,
+            title: pr.title.clone(),
+            detection,
+            commits: Vec::new(),
+            skipped_existing: Vec::new(),
+            missing: Vec::new(),
+            ai_lines: 0,
+        };
+        let merged = merged_commits(repo, pr, &mut entry.missing, || match pr_commits {
+            Some(commits) => Ok(commits),
+            None => fetch_pull_request_commits(api_url, repo_slug, token, pr.number),
+        })?;
+        for commit_sha in merged {
+            if notes_api::read_note(repo, &commit_sha).is_some() {
+                entry.skipped_existing.push(commit_sha);
+                continue;
+            }
+            let Some(log) =
+                backfill_note_for_commit(repo, &commit_sha, pr.number, &entry.detection)?
+            else {
+                continue;
+            };
+            entry.ai_lines += log
+                .metadata
+                .prompts
+                .values()
+                .map(|prompt| prompt.accepted_lines)
+                .sum::<u32>();
+            notes.push((
+                commit_sha.clone(),
+                log.serialize_to_string()
+                    .map_err(|e| GitAiError::Generic(format!("Failed to serialize note: {}", e)))?,
+            ));
+            entry.commits.push(commit_sha);
+        }
+
+        if output.is_verbose() {
+            eprintln!(
+                "#{} {} ({}: {}) -> {} commit(s)",
+                pr.number,
+                entry.detection.tool,
+                entry.detection.source,
+                entry.detection.evidence,
+                entry.commits.len()
+            );
+        }
+        backfilled.push(entry);
+    }
+
+    if !request.dry_run {
+        notes_api::write_notes_batch(repo, &notes)?;
+    }
+
+    Ok(BackfillOutput {
+        repository: repo_slug.to_string(),
+        pull_requests_scanned: pull_requests.len(),
+        written: !request.dry_run && !notes.is_empty(),
+        pull_requests: backfilled,
+    })
+}
+
+/// [`detect_pull_request_agent`] over `pr`'s labels, author and description
+/// plus `messages`.
+fn detect<'a>(
+    pr: &'a GithubPullRequest,
+    messages: impl IntoIterator<Item = &'a str>,
+    labels: &[String],
+) -> Option<BackfillDetection> {
+    detect_pull_request_agent(
+        pr.labels.iter().map(|label| label.name.as_str()),
+        pr.user.as_ref().map(|user| user.login.as_str()),
+        pr.body.iter().map(String::as_str).chain(messages),
+        labels,
+    )
+}
+
+/// Messages of the commits `pr` merged that are in the clone: the merge,
+/// squash or rebase tip commit, and the branch commits of a merge commit. A
+/// squash commit carries the `Co-authored-by` trailers of the commits it
+/// replaced.
+fn merged_commit_messages(repo: &Repository, pr: &GithubPullRequest) -> Vec<String> {
+    let Some(merge_sha) = pr.merge_commit_sha.as_deref() else {
+        return Vec::new();
+    };
+    let Ok(merge_commit) = repo.find_commit(merge_sha.to_string()) else {
+        return Vec::new();
+    };
+    let range = if merge_commit.parents().count() > 1 {
+        format!("{}^1..{}", merge_sha, merge_sha)
+    } else {
+        format!("{}^!", merge_sha)
+    };
+    log_records(repo, &["%B"], &[range])
+        .map(|records| records.into_iter().flatten().collect())
+        .unwrap_or_default()
+}
+
+/// The commits a merged PR left on the base branch, newest first: the PR's own
+/// commits when it was merged with a merge commit or rebased, or the squash
+/// commit. A merge commit missing from the local clone is recorded in
+/// `missing`.
+///
+/// A merge commit's second parent leads to the PR's commits. A rebase merge puts the PR's commits on the base branch one after the
+/// other, ending at the merge commit GitHub reports, with their messages
+/// intact; when the first-parent history there does not repeat the PR's
+/// messages, the PR was squashed. `pr_commits` is only called for these.
+fn merged_commits(
+    repo: &Repository,
+    pr: &GithubPullRequest,
+    missing: &mut Vec<String>,
+    pr_commits: impl FnOnce() -> Result<Vec<GithubPullRequestCommit>, GitAiError>,
+) -> Result<Vec<String>, GitAiError> {
+    let Some(merge_sha) = pr.merge_commit_sha.clone() else {
+        return Ok(Vec::new());
+    };
+    let merge_commit = match repo.find_commit(merge_sha.clone()) {
+        Ok(commit) => commit,
+        Err(_) => {
+            missing.push(merge_sha);
+            return Ok(Vec::new());
+        }
+    };
+
+    if merge_commit.parents().count() > 1 {
+        let branch = log_records(
+            repo,
+            &["%H"],
+            &[format!("{}^1..{}^2", merge_sha, merge_sha)],
+        )?;
+        return Ok(branch.into_iter().flatten().collect());
+    }
+
+    let pr_commits = pr_commits()?;
+    if pr_commits.len() > 1 {
+        let tips = log_records(
+            repo,
+            &["%H", "%B"],
+            &[
+                "--first-parent".to_string(),
+                format!("--max-count={}", pr_commits.len()),
+                merge_sha.clone(),
+            ],
+        )?;
+        let rebased = tips.len() == pr_commits.len()
+            && tips
+                .iter()
+                .zip(pr_commits.iter().rev())
+                .all(|(tip, commit)| tip[1].trim() == commit.commit.message.trim());
+        if rebased {
+            return Ok(tips.into_iter().map(|mut tip| tip.swap_remove(0)).collect());
+        }
+    }
+    Ok(vec![merge_sha])
+}
+
+fn backfill_note_for_commit(
+    repo: &Repository,
+    commit_sha: &str,
+    pr_number: u64,
+    detection: &BackfillDetection,
+) -> Result<Option<AuthorshipLog>, GitAiError> {
+    let commit = repo.find_commit(commit_sha.to_string())?;
+    // Merge commits inside a PR (e.g. syncing with the base branch) add nothing of their own.
+    let parent_sha = match commit
+        .parents()
+        .map(|parent| parent.id())
+        .collect::<Vec<_>>()
+    {
+        parents if parents.len() > 1 => return Ok(None),
+        parents => parents
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| "initial".to_string()),
+    };
+    let base = single_commit_diff_base(&parent_sha, commit_sha);
+    let added_lines = repo.diff_added_lines(&base, commit_sha, None)?;
+    Ok(synthesize_backfill_note(
+        commit_sha,
+        pr_number,
+        detection,
+        &added_lines,
+    ))
+}
+
+fn print_summary(result: &BackfillOutput) {
+    for pr in &result.pull_requests {
+        let commits: Vec<&str> = pr
+            .commits
+            .iter()
+            .map(|sha| &sha[..sha.len().min(8)])
+            .collect();
+        println!(
+            "#{} {} [{} via {}] {} line(s) on {}",
+            pr.number,
+            pr.title,
+            pr.detection.tool,
+            pr.detection.source,
+            pr.ai_lines,
+            if commits.is_empty() {
+                "no new commits".to_string()
+            } else {
+                commits.join(", ")
+            }
+        );
+        if !pr.missing.is_empty() {
+            println!(
+                "    {} commit(s) not in this clone; fetch them and re-run",
+                pr.missing.len()
+            );
+        }
+    }
+
+    let noted: usize = result.pull_requests.iter().map(|pr| pr.commits.len()).sum();
+    let verb = if result.written {
+        "Wrote"
+    } else {
+        "Would write"
+    };
+    println!(
+        "{} {} backfilled note(s) for {} AI pull request(s) out of {} merged in {}",
+        verb,
+        noted,
+        result.pull_requests.len(),
+        result.pull_requests_scanned,
+        result.repository
+    );
+}
+
+fn print_help() {
+    eprintln!("git-ai backfill - Synthesize notes for AI pull requests merged before git-ai");
+    eprintln!();
+    eprintln!("Usage: git-ai backfill --from-github [--repo <owner>/<name>] [--token-stdin]");
+    eprintln!("                       [--limit <n>] [--label <name>]... [--dry-run] [--json]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --from-github      Read merged pull requests from the GitHub API");
+    eprintln!("  --repo <slug>      GitHub repository (default: the default remote's)");
+    eprintln!(
+        "  --token-stdin      Read the API token from stdin (default: GITHUB_TOKEN, then GH_TOKEN)"
+    );
+    eprintln!(
+        "  --limit <n>        Merged pull requests to examine, latest merged first (default: {})",
+        DEFAULT_PR_LIMIT
+    );
+    eprintln!("  --label <name>     Label marking AI-authored PRs; repeat for several");
+    eprintln!(
+        "                     (default: {})",
+        DEFAULT_AI_LABELS.join(", ")
+    );
+    eprintln!("  -n, --dry-run      Report what would be written without writing");
+    eprintln!("  --json             Print the backfilled pull requests as JSON");
+    eprintln!("  --verbose          Explain each detection while scanning");
+    eprintln!("  --help             Show this help");
+    eprintln!();
+    eprintln!("A PR counts as AI-authored when an agent is a Co-authored-by trailer in its");
+    eprintln!("description or commits, an agent account opened it, or it carries an AI label.");
+    eprintln!("Every line its merged commits added is credited to that agent, with the");
+    eprintln!("prompt marked `backfill: github`. Commits that already have a note are skipped.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_utils::TmpRepo;
+
+    fn pull_request_json(number: u64, label: &str, merged_at: &str, merge_sha: &str) -> String {
+        format!(
+            r#"{{"number":{},"title":"PR {}","labels":[{{"name":"{}"}}],"updated_at":"{}","merged_at":"{}","merge_commit_sha":"{}"}}"#,
+            number, number, label, merged_at, merged_at, merge_sha
+        )
+    }
+
+    fn commits_json(messages: &[&str]) -> String {
+        let commits: Vec<String> = messages
+            .iter()
+            .enumerate()
+            .map(|(i, message)| {
+                format!(
+                    r#"{{"sha":"{:040}","commit":{{"message":"{}"}}}}"#,
+                    i + 1,
+                    message
+                )
+            })
+            .collect();
+        format!("[{}]", commits.join(","))
+    }
+
+    #[test]
+    fn test_backfill_covers_every_rebased_commit_and_only_the_squash_tip() {
+        let tmp = TmpRepo::new().expect("tmp repo");
+        tmp.write_file("base.txt", "base\n", false).unwrap();
+        tmp.commit_all("base").unwrap();
+        // PR 1 was rebase-merged: both of its commits landed on main.
+        tmp.write_file("one.txt", "one\n", false).unwrap();
+        let rebased_first = tmp.commit_all("feat: one").unwrap();
+        tmp.write_file("two.txt", "two\n", false).unwrap();
+        let rebased_tip = tmp.commit_all("feat: two").unwrap();
+        // PR 2 was squashed into a single commit.
+        tmp.write_file("three.txt", "three\nfour\n", false).unwrap();
+        let squash = tmp.commit_all("Add three and four (#2)").unwrap();
+
+        let mut server = mockito::Server::new();
+        // Listed by update time; PR 2 was merged last and must come first.
+        let pulls = server
+            .mock(
+                "GET",
+                "/repos/acme/repo/pulls?state=closed&sort=updated&direction=desc&per_page=100&page=1",
+            )
+            .match_header("Authorization", "Bearer test-token")
+            .with_status(200)
+            .with_body(format!(
+                "[{},{}]",
+                pull_request_json(1, "claude-code", "2024-01-01T00:00:00Z", &rebased_tip),
+                pull_request_json(2, "cursor", "2024-02-01T00:00:00Z", &squash),
+            ))
+            .create();
+        let rebased_commits = server
+            .mock(
+                "GET",
+                "/repos/acme/repo/pulls/1/commits?per_page=100&page=1",
+            )
+            .with_status(200)
+            .with_body(commits_json(&["feat: one", "feat: two"]))
+            .create();
+        let squashed_commits = server
+            .mock(
+                "GET",
+                "/repos/acme/repo/pulls/2/commits?per_page=100&page=1",
+            )
+            .with_status(200)
+            .with_body(commits_json(&["add three", "add four"]))
+            .create();
+
+        let request = GithubBackfill {
+            api_url: server.url(),
+            repo_slug: "acme/repo".to_string(),
+            token: "test-token".to_string(),
+            limit: 10,
+            labels: Vec::new(),
+            dry_run: true,
+        };
+        let result = backfill_from_github(tmp.gitai_repo(), &request, &OutputOptions::current())
+            .expect("backfill");
+
+        pulls.assert();
+        rebased_commits.assert();
+        squashed_commits.assert();
+        assert!(!result.written);
+        let numbers: Vec<u64> = result.pull_requests.iter().map(|pr| pr.number).collect();
+        assert_eq!(numbers, vec![2, 1]);
+        assert_eq!(result.pull_requests[0].commits, vec![squash]);
+        assert_eq!(result.pull_requests[0].ai_lines, 2);
+        assert_eq!(
+            result.pull_requests[1].commits,
+            vec![rebased_tip, rebased_first]
+        );
+        assert_eq!(result.pull_requests[1].detection.tool, "claude");
+    }
+}
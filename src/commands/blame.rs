@@ -1,7 +1,7 @@
 use crate::auth::CredentialStore;
 use crate::authorship::authorship_log::{HumanRecord, PromptRecord, SessionRecord};
 use crate::authorship::authorship_log_serialization::AuthorshipLog;
-use crate::authorship::working_log::CheckpointKind;
+use crate::authorship::working_log::{CheckpointKind, SubagentInfo};
 use crate::error::GitAiError;
 use crate::git::notes_api::read_authorship_v3;
 use crate::git::repository::Repository;
@@ -77,10 +77,13 @@ struct PreparedBlameRequest {
     options: GitAiBlameOptions,
 }
 
+/// Line range end meaning "through the last line of the file" (`-L <start>,`).
+pub const BLAME_RANGE_TO_EOF: u32 = u32::MAX;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct GitAiBlameOptions {
-    // Line range options
+    // Line range options. An end of `BLAME_RANGE_TO_EOF` runs to the end of the file.
     pub line_ranges: Vec<(u32, u32)>,
 
     pub newest_commit: Option<String>,
@@ -170,6 +173,27 @@ pub struct GitAiBlameOptions {
     // When true, a single git blame hunk may be split into multiple hunks
     // if different lines were authored by different humans working with AI
     pub split_hunks_by_ai_author: bool,
+
+    // Collapse output into one row per group (--group-by)
+    pub group_by: Option<BlameGroupBy>,
+}
+
+/// What `--group-by` collapses blame lines into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlameGroupBy {
+    /// One row per AI conversation.
+    Session,
+}
+
+/// The lines of a blamed file written in one AI conversation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BlameSessionGroup {
+    pub session_id: String,
+    pub tool: String,
+    pub model: String,
+    pub lines: u32,
+    pub first_line: u32,
+    pub last_line: u32,
 }
 
 impl Default for GitAiBlameOptions {
@@ -216,6 +240,7 @@ impl Default for GitAiBlameOptions {
             mark_unknown: false,
             show_prompt: false,
             split_hunks_by_ai_author: true,
+            group_by: None,
         }
     }
 }
@@ -294,7 +319,7 @@ impl Repository {
             }
             opts.use_prompt_hashes_as_names = true;
             opts
-        } else if options.show_prompt {
+        } else if options.show_prompt || options.group_by.is_some() {
             let mut opts = options.clone();
             opts.use_prompt_hashes_as_names = true;
             opts
@@ -371,7 +396,17 @@ impl Repository {
         let line_ranges = if options.line_ranges.is_empty() {
             vec![(1, total_lines)]
         } else {
-            options.line_ranges.clone()
+            options
+                .line_ranges
+                .iter()
+                .map(|&(start, end)| {
+                    if end == BLAME_RANGE_TO_EOF {
+                        (start, total_lines)
+                    } else {
+                        (start, end)
+                    }
+                })
+                .collect()
         };
 
         // Validate line ranges.
@@ -555,8 +590,8 @@ impl Repository {
         let BlameAnalysisResult {
             line_authors,
             prompt_records,
-            session_records: _,
-            blame_hunks: _,
+            session_records,
+            blame_hunks,
             humans: _,
         } = analysis;
 
@@ -565,11 +600,21 @@ impl Repository {
         }
 
         // Output based on format
-        if options.json {
+        if request.options.group_by == Some(BlameGroupBy::Session) {
+            output_session_groups_format(
+                &group_lines_by_session(&line_authors, &prompt_records),
+                &request.relative_file_path,
+                line_authors.len(),
+                options.json,
+            )?;
+        } else if options.json {
             output_json_format(
                 self,
                 &line_authors,
                 &prompt_records,
+                &session_records,
+                &blame_hunks,
+                &commits_with_notes,
                 &authorship_logs,
                 &prompt_commits,
                 &request.relative_file_path,
@@ -624,6 +669,34 @@ impl Repository {
         Ok(analysis)
     }
 
+    /// Attribution ranges for `file_path`, as reported in the `ranges` of
+    /// `blame --json` (committed content at HEAD unless `newest_commit` is set).
+    pub fn blame_ranges(
+        &self,
+        file_path: &str,
+        options: &GitAiBlameOptions,
+    ) -> Result<Vec<JsonBlameRange>, GitAiError> {
+        let options = GitAiBlameOptions {
+            json: true,
+            no_output: true,
+            ..options.clone()
+        };
+        let request = self.prepare_blame_request(file_path, &options)?;
+        let (analysis, _authorship_logs, _prompt_commits, commits_with_notes) = self
+            .run_blame_analysis_pipeline(
+                &request.relative_file_path,
+                &request.line_ranges,
+                &request.options,
+            )?;
+        Ok(build_json_blame_ranges(
+            &analysis.blame_hunks,
+            &analysis.line_authors,
+            &analysis.prompt_records,
+            &analysis.session_records,
+            &commits_with_notes,
+        ))
+    }
+
     pub fn blame_hunks(
         &self,
         file_path: &str,
@@ -1286,10 +1359,112 @@ struct BlameMetadata {
 #[derive(Debug, Serialize)]
 struct JsonBlameOutput {
     lines: std::collections::BTreeMap<String, String>,
+    /// Contiguous line ranges covering every blamed line, grouped by attribution
+    ranges: Vec<JsonBlameRange>,
     prompts: HashMap<String, PromptRecordWithOtherFiles>,
     metadata: BlameMetadata,
 }
 
+/// Kind of author attributed to a blame range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonBlameAuthorKind {
+    /// Written by an AI agent (has a prompt record)
+    Ai,
+    /// Explicitly attested as human-written (KnownHuman checkpoint)
+    KnownHuman,
+    /// Commit has an authorship note but the lines carry no attestation
+    Untracked,
+    /// Commit has no authorship note at all
+    Unknown,
+}
+
+/// A contiguous run of lines sharing the same attribution and commit.
+///
+/// Emitting ranges instead of per-line records keeps the payload proportional to
+/// the number of authorship transitions, which matters for IDEs on large files.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JsonBlameRange {
+    pub start_line: u32,
+    pub end_line: u32,
+    pub author_kind: JsonBlameAuthorKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_id: Option<String>,
+    /// Agent tool invocation that wrote these lines, when the session recorded it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_use_id: Option<String>,
+    /// Subagent that wrote these lines, when the session recorded one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subagent: Option<SubagentInfo>,
+    pub commit: String,
+}
+
+/// Group blamed lines into contiguous ranges keyed by (author, commit).
+///
+/// `line_authors` must have been produced with `use_prompt_hashes_as_names`, so AI
+/// lines carry their prompt hash and known-human lines carry their `h_` hash.
+fn build_json_blame_ranges(
+    blame_hunks: &[BlameHunk],
+    line_authors: &HashMap<u32, String>,
+    prompt_records: &HashMap<String, PromptRecord>,
+    session_records: &HashMap<String, SessionRecord>,
+    commits_with_notes: &std::collections::HashSet<String>,
+) -> Vec<JsonBlameRange> {
+    let mut lines: Vec<(u32, &str)> = blame_hunks
+        .iter()
+        .flat_map(|hunk| (hunk.range.0..=hunk.range.1).map(|line| (line, hunk.commit_sha.as_str())))
+        .collect();
+    lines.sort_by_key(|(line, _)| *line);
+
+    let mut ranges: Vec<JsonBlameRange> = Vec::new();
+    for (line, commit) in lines {
+        let author = line_authors.get(&line).map(String::as_str).unwrap_or("");
+        let prompt = prompt_records.get(author);
+        let author_kind = if prompt.is_some() {
+            JsonBlameAuthorKind::Ai
+        } else if author.starts_with("h_") {
+            JsonBlameAuthorKind::KnownHuman
+        } else if commits_with_notes.contains(commit) {
+            JsonBlameAuthorKind::Untracked
+        } else {
+            JsonBlameAuthorKind::Unknown
+        };
+        let prompt_id = prompt.map(|_| author.to_string());
+
+        if let Some(last) = ranges.last_mut()
+            && last.end_line + 1 == line
+            && last.commit == commit
+            && last.author_kind == author_kind
+            && last.prompt_id == prompt_id
+        {
+            last.end_line = line;
+            continue;
+        }
+
+        let session = session_records.get(author.split("::").next().unwrap_or(author));
+        ranges.push(JsonBlameRange {
+            start_line: line,
+            end_line: line,
+            author_kind,
+            tool: prompt.map(|p| p.agent_id.tool.clone()),
+            model: prompt.map(|p| p.agent_id.model.clone()),
+            tool_use_id: session
+                .and_then(|session| session.tool_use_id_for_attestation(author))
+                .map(str::to_string),
+            subagent: session
+                .and_then(|session| session.subagent_for_attestation(author))
+                .cloned(),
+            prompt_id,
+            commit: commit.to_string(),
+        });
+    }
+    ranges
+}
+
 /// Read model that patches PromptRecord with other_files and commits fields
 #[derive(Debug, Serialize)]
 struct PromptRecordWithOtherFiles {
@@ -1335,6 +1510,9 @@ fn output_json_format(
     repo: &Repository,
     line_authors: &HashMap<u32, String>,
     prompt_records: &HashMap<String, PromptRecord>,
+    session_records: &HashMap<String, SessionRecord>,
+    blame_hunks: &[BlameHunk],
+    commits_with_notes: &std::collections::HashSet<String>,
     authorship_logs: &[AuthorshipLog],
     prompt_commits: &HashMap<String, Vec<String>>,
     current_file: &str,
@@ -1417,8 +1595,17 @@ fn output_json_format(
 
     let current_user = repo.effective_author_identity().formatted();
 
+    let ranges = build_json_blame_ranges(
+        blame_hunks,
+        line_authors,
+        prompt_records,
+        session_records,
+        commits_with_notes,
+    );
+
     let output = JsonBlameOutput {
         lines: lines_map,
+        ranges,
         prompts: filtered_prompts,
         metadata: BlameMetadata {
             is_logged_in,
@@ -1433,6 +1620,100 @@ fn output_json_format(
     Ok(())
 }
 
+/// Collapse AI lines into one group per conversation, ordered by first line.
+/// Session attestations (`s_<id>::t_<id>`) group by their session; older
+/// prompt-hash attestations are a conversation each.
+pub fn group_lines_by_session(
+    line_authors: &HashMap<u32, String>,
+    prompt_records: &HashMap<String, PromptRecord>,
+) -> Vec<BlameSessionGroup> {
+    let mut ai_lines: Vec<(u32, &String)> = line_authors
+        .iter()
+        .filter(|(_, author)| prompt_records.contains_key(*author))
+        .map(|(line, author)| (*line, author))
+        .collect();
+    ai_lines.sort_by_key(|(line, _)| *line);
+
+    let mut groups: Vec<BlameSessionGroup> = Vec::new();
+    let mut index_by_session: HashMap<&str, usize> = HashMap::new();
+    for (line, author) in ai_lines {
+        let session_id = author.split("::").next().unwrap_or(author);
+        if let Some(&index) = index_by_session.get(session_id) {
+            let group = &mut groups[index];
+            group.lines += 1;
+            group.last_line = line;
+            continue;
+        }
+        let agent_id = &prompt_records[author].agent_id;
+        index_by_session.insert(session_id, groups.len());
+        groups.push(BlameSessionGroup {
+            session_id: session_id.to_string(),
+            tool: agent_id.tool.clone(),
+            model: agent_id.model.clone(),
+            lines: 1,
+            first_line: line,
+            last_line: line,
+        });
+    }
+    groups
+}
+
+#[derive(Serialize)]
+struct JsonSessionGroupsOutput<'a> {
+    file: &'a str,
+    total_lines: usize,
+    sessions: &'a [BlameSessionGroup],
+}
+
+fn output_session_groups_format(
+    groups: &[BlameSessionGroup],
+    file_path: &str,
+    total_lines: usize,
+    json: bool,
+) -> Result<(), GitAiError> {
+    if json {
+        let output = JsonSessionGroupsOutput {
+            file: file_path,
+            total_lines,
+            sessions: groups,
+        };
+        let json_str = serde_json::to_string_pretty(&output)
+            .map_err(|e| GitAiError::Generic(format!("Failed to serialize JSON output: {}", e)))?;
+        println!("{}", json_str);
+        return Ok(());
+    }
+
+    if groups.is_empty() {
+        println!("No AI-authored lines in {}", file_path);
+        return Ok(());
+    }
+
+    println!(
+        "{:<20} {:<12} {:<28} {:>5} {:>6} {:>6}",
+        "SESSION", "TOOL", "MODEL", "LINES", "FIRST", "LAST"
+    );
+    for group in groups {
+        println!(
+            "{:<20} {:<12} {:<28} {:>5} {:>6} {:>6}",
+            group.session_id,
+            group.tool,
+            group.model,
+            group.lines,
+            group.first_line,
+            group.last_line
+        );
+    }
+    let ai_lines: u32 = groups.iter().map(|group| group.lines).sum();
+    println!(
+        "{} of {} lines from {} AI conversation{}",
+        ai_lines,
+        total_lines,
+        groups.len(),
+        if groups.len() == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
+
 fn output_porcelain_format(
     repo: &Repository,
     _line_authors: &HashMap<u32, String>,
@@ -1934,6 +2215,16 @@ fn format_blame_date(author_time: i64, author_tz: &str, options: &GitAiBlameOpti
     }
 }
 
+fn parse_group_by_arg(value: &str) -> Result<BlameGroupBy, GitAiError> {
+    match value {
+        "session" => Ok(BlameGroupBy::Session),
+        other => Err(GitAiError::Generic(format!(
+            "Unsupported --group-by value '{}' (expected 'session')",
+            other
+        ))),
+    }
+}
+
 pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions), GitAiError> {
     let mut options = GitAiBlameOptions::default();
     let mut file_path = None;
@@ -1946,17 +2237,15 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
                 if i + 1 >= args.len() {
                     return Err(GitAiError::Generic("Missing argument for -L".to_string()));
                 }
-                let range_str = &args[i + 1];
-                if let Some((start, end)) = parse_line_range(range_str) {
-                    options.line_ranges.push((start, end));
-                } else {
-                    return Err(GitAiError::Generic(format!(
-                        "Invalid line range: {}",
-                        range_str
-                    )));
-                }
+                options
+                    .line_ranges
+                    .push(parse_line_range_arg(&args[i + 1])?);
                 i += 2;
             }
+            arg if arg.starts_with("-L") && arg.len() > 2 => {
+                options.line_ranges.push(parse_line_range_arg(&arg[2..])?);
+                i += 1;
+            }
 
             // Output format options
             "--porcelain" => {
@@ -2173,12 +2462,13 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
                         "Missing argument for --since".to_string(),
                     ));
                 }
-                options.oldest_date =
-                    Some(DateTime::parse_from_rfc3339(&args[i + 1]).map_err(|e| {
-                        GitAiError::Generic(format!("Invalid date format for --since: {}", e))
-                    })?);
+                set_since(&mut options, &args[i + 1])?;
                 i += 2;
             }
+            arg if arg.starts_with("--since=") => {
+                set_since(&mut options, &arg["--since=".len()..])?;
+                i += 1;
+            }
             // JSON output format
             "--json" => {
                 options.json = true;
@@ -2197,6 +2487,21 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
                 i += 1;
             }
 
+            // Collapse output into groups
+            "--group-by" => {
+                if i + 1 >= args.len() {
+                    return Err(GitAiError::Generic(
+                        "Missing argument for --group-by".to_string(),
+                    ));
+                }
+                options.group_by = Some(parse_group_by_arg(&args[i + 1])?);
+                i += 2;
+            }
+            arg if arg.starts_with("--group-by=") => {
+                options.group_by = Some(parse_group_by_arg(&arg["--group-by=".len()..])?);
+                i += 1;
+            }
+
             // File path (non-option argument)
             arg if !arg.starts_with('-') => {
                 if file_path.is_none() {
@@ -2222,17 +2527,50 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
     Ok((file_path, options))
 }
 
+/// Parse an `-L` value the way git blame does: `<start>,<end>`, `<start>,+<count>`,
+/// `<start>,-<count>`, `,<end>` (from line 1) and `<start>` or `<start>,` (to the
+/// end of the file, recorded as [`BLAME_RANGE_TO_EOF`]).
+fn parse_line_range_arg(range_str: &str) -> Result<(u32, u32), GitAiError> {
+    parse_line_range(range_str)
+        .ok_or_else(|| GitAiError::Generic(format!("Invalid line range: {}", range_str)))
+}
+
 fn parse_line_range(range_str: &str) -> Option<(u32, u32)> {
-    if let Some(dash_pos) = range_str.find(',') {
-        let start_str = &range_str[..dash_pos];
-        let end_str = &range_str[dash_pos + 1..];
+    let (start_str, end_str) = range_str.split_once(',').unwrap_or((range_str, ""));
+    let start = if start_str.is_empty() {
+        1
+    } else {
+        start_str.parse::<u32>().ok()?
+    };
+    if start_str.is_empty() && end_str.is_empty() {
+        return None;
+    }
 
-        if let (Ok(start), Ok(end)) = (start_str.parse::<u32>(), end_str.parse::<u32>()) {
-            return Some((start, end));
-        }
-    } else if let Ok(line) = range_str.parse::<u32>() {
-        return Some((line, line));
+    if end_str.is_empty() {
+        return Some((start, BLAME_RANGE_TO_EOF));
     }
+    if let Some(count) = end_str.strip_prefix('+') {
+        let count = count.parse::<u32>().ok().filter(|count| *count > 0)?;
+        return Some((start, start.saturating_add(count - 1)));
+    }
+    if let Some(count) = end_str.strip_prefix('-') {
+        let count = count.parse::<u32>().ok().filter(|count| *count > 0)?;
+        return Some((start.saturating_sub(count - 1).max(1), start));
+    }
+    Some((start, end_str.parse::<u32>().ok()?))
+}
 
-    None
+/// `--since` takes an RFC 3339 timestamp or any date git understands
+/// (e.g. "2 weeks ago"), which is passed through to git blame unchanged.
+fn set_since(options: &mut GitAiBlameOptions, value: &str) -> Result<(), GitAiError> {
+    if value.is_empty() {
+        return Err(GitAiError::Generic(
+            "Missing argument for --since".to_string(),
+        ));
+    }
+    match DateTime::parse_from_rfc3339(value) {
+        Ok(date) => options.oldest_date = Some(date),
+        Err(_) => options.oldest_date_spec = Some(value.to_string()),
+    }
+    Ok(())
 }
@@ -0,0 +1,242 @@
+//! `git-ai bundle push --range <from>..<to>` — upload an attribution snapshot
+//! of a release.
+//!
+//! Every commit in the range is packaged with its authorship note, the prompt
+//! and session hashes the note references, and its commit metadata. Each commit
+//! becomes a CAS object and a manifest lists them; the manifest's content hash
+//! is the bundle's id. Since CAS objects are addressed by content, the same id
+//! always names the same snapshot.
+
+use crate::api::bundle::ReleaseBundle;
+use crate::api::types::ReleaseBundleCommit;
+use crate::api::{ApiClient, ApiContext};
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::commands::output::OutputOptions;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::log_records::log_records;
+use crate::git::notes_api;
+use crate::git::repository::Repository;
+
+pub fn handle_bundle(args: &[String]) {
+    let subcommand = args.first().map(|s| s.as_str()).unwrap_or("--help");
+    match subcommand {
+        "push" => handle_bundle_push(&args[1..]),
+        "--help" | "-h" | "help" => print_help(),
+        other => {
+            eprintln!("Unknown git-ai bundle subcommand: {}", other);
+            eprintln!("Run 'git ai bundle --help' for usage.");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn handle_bundle_push(args: &[String]) {
+    let mut range: Option<String> = None;
+    let mut dry_run = false;
+    let mut json = OutputOptions::current().is_json();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--range" if i + 1 < args.len() => {
+                range = Some(args[i + 1].clone());
+                i += 2;
+            }
+            "--dry-run" => {
+                dry_run = true;
+                i += 1;
+            }
+            "--json" => {
+                json = true;
+                i += 1;
+            }
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other => {
+                eprintln!("Error: unknown option '{}'", other);
+                eprintln!("Run 'git ai bundle --help' for usage");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let Some(range) = range else {
+        eprintln!("Error: --range <from>..<to> is required");
+        std::process::exit(1);
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Error: not a git repository ({})", e);
+            std::process::exit(1);
+        }
+    };
+
+    let bundle = match build_release_bundle(&repo, &range) {
+        Ok(bundle) => bundle,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if !dry_run && let Err(e) = upload_release_bundle(&bundle) {
+        eprintln!("Error: failed to upload bundle: {}", e);
+        std::process::exit(1);
+    }
+
+    if json {
+        let output = serde_json::json!({
+            "manifest_id": bundle.manifest_id,
+            "uploaded": !dry_run,
+            "manifest": bundle.manifest,
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output).unwrap_or_default()
+        );
+        return;
+    }
+
+    let with_notes = bundle
+        .objects
+        .iter()
+        .filter(|object| object.content.get("authorship_log").is_some())
+        .count();
+    if dry_run {
+        eprintln!(
+            "Dry run: {} commits ({} with authorship notes) not uploaded",
+            bundle.manifest.commits.len(),
+            with_notes
+        );
+    } else {
+        eprintln!(
+            "Uploaded {} commits ({} with authorship notes) for {}",
+            bundle.manifest.commits.len(),
+            with_notes,
+            range
+        );
+    }
+    println!("{}", bundle.manifest_id);
+}
+
+/// Package the commits of `range` (`<from>..<to>`) into a release bundle.
+pub fn build_release_bundle(repo: &Repository, range: &str) -> Result<ReleaseBundle, GitAiError> {
+    let Some((from, to)) = range.split_once("..") else {
+        return Err(GitAiError::Generic(format!(
+            "Invalid range '{}': expected <from>..<to>",
+            range
+        )));
+    };
+    if from.is_empty() || to.is_empty() || to.starts_with('.') {
+        return Err(GitAiError::Generic(format!(
+            "Invalid range '{}': expected <from>..<to>",
+            range
+        )));
+    }
+    let from_sha = repo.revparse_single(from)?.peel_to_commit()?.id();
+    let to_sha = repo.revparse_single(to)?.peel_to_commit()?.id();
+
+    let commits = collect_range_commits(repo, &from_sha, &to_sha)?;
+    let shas: Vec<String> = commits.iter().map(|commit| commit.sha.clone()).collect();
+    let notes = notes_api::read_notes_batch(repo, &shas)?;
+    let commits: Vec<ReleaseBundleCommit> = commits
+        .into_iter()
+        .map(|mut commit| {
+            if let Some(raw) = notes.get(&commit.sha) {
+                commit.prompt_hashes = prompt_hashes(raw);
+                commit.authorship_log = Some(raw.clone());
+            }
+            commit
+        })
+        .collect();
+
+    ReleaseBundle::build(
+        range,
+        &from_sha,
+        &to_sha,
+        crate::repo_url::resolve_repo_url_from_repo(repo),
+        chrono::Utc::now().timestamp(),
+        &commits,
+    )
+}
+
+/// Commits reachable from `to_sha` but not `from_sha`, oldest first, without
+/// their notes.
+fn collect_range_commits(
+    repo: &Repository,
+    from_sha: &str,
+    to_sha: &str,
+) -> Result<Vec<ReleaseBundleCommit>, GitAiError> {
+    let records = log_records(
+        repo,
+        &["%H", "%an <%ae>", "%aI", "%cI", "%s"],
+        &["--reverse".to_string(), format!("{}..{}", from_sha, to_sha)],
+    )?;
+    Ok(records
+        .into_iter()
+        .map(|fields| {
+            let [sha, author, authored_at, committed_at, subject] =
+                <[String; 5]>::try_from(fields).unwrap_or_default();
+            ReleaseBundleCommit {
+                sha,
+                author,
+                authored_at,
+                committed_at,
+                subject,
+                prompt_hashes: Vec::new(),
+                authorship_log: None,
+            }
+        })
+        .collect())
+}
+
+/// Prompt and session hashes referenced by a raw authorship note, sorted.
+fn prompt_hashes(raw_note: &str) -> Vec<String> {
+    let Ok(log) = AuthorshipLog::deserialize_from_string(raw_note) else {
+        return Vec::new();
+    };
+    let mut hashes: Vec<String> = log
+        .metadata
+        .prompts
+        .into_keys()
+        .chain(log.metadata.sessions.into_keys())
+        .collect();
+    hashes.sort();
+    hashes.dedup();
+    hashes
+}
+
+fn upload_release_bundle(bundle: &ReleaseBundle) -> Result<(), GitAiError> {
+    let context = ApiContext::new(None);
+    let using_default_api = context.base_url == crate::config::DEFAULT_API_BASE_URL;
+    let client = ApiClient::new(context);
+    if using_default_api && !client.is_logged_in() && !client.has_api_key() {
+        return Err(GitAiError::Generic(
+            "not logged in; run 'git ai login' first".to_string(),
+        ));
+    }
+    client.push_release_bundle(bundle)
+}
+
+fn print_help() {
+    eprintln!("git-ai bundle - Upload attribution snapshots");
+    eprintln!();
+    eprintln!("Usage: git-ai bundle push --range <from>..<to> [--dry-run] [--json]");
+    eprintln!();
+    eprintln!("Subcommands:");
+    eprintln!("  push              Upload the authorship of every commit in a range");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --range <a>..<b>  Commits reachable from <b> but not <a>, e.g. v1.0..v1.1");
+    eprintln!("  --dry-run         Build the bundle and print its id without uploading");
+    eprintln!("  --json            Print the manifest id and manifest as JSON");
+    eprintln!("  --help            Show this help");
+    eprintln!();
+    eprintln!("Each commit's authorship note, prompt hashes, and metadata are stored as");
+    eprintln!("content-addressed objects listed by a manifest. The printed manifest id");
+    eprintln!("is the manifest's content hash, so it always names the same snapshot.");
+}
@@ -12,6 +12,7 @@ use crate::daemon::control_api::{BashSnapshotQueryResponse, ControlRequest};
 use crate::daemon::{DaemonConfig, send_control_request, send_control_request_with_timeout};
 use crate::error::GitAiError;
 use crate::utils::normalize_to_posix;
+use crate::workspace_config::effective_workspace_config_from_path;
 use ignore::WalkBuilder;
 use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use serde::{Deserialize, Serialize};
@@ -328,6 +329,16 @@ pub fn classify_tool(agent: Agent, tool_name: &str) -> ToolClass {
             "terminal" | "local_shell_call" => ToolClass::Bash,
             _ => ToolClass::Skip,
         },
+        Agent::OpenHands => match tool_name {
+            "str_replace_editor" | "file_editor" | "edit_file" => ToolClass::FileEdit,
+            "execute_bash" | "terminal" | "bash" => ToolClass::Bash,
+            _ => ToolClass::Skip,
+        },
+        Agent::AmazonQ => match tool_name {
+            "fs_write" | "write" => ToolClass::FileEdit,
+            "execute_bash" | "execute_cmd" | "shell" => ToolClass::Bash,
+            _ => ToolClass::Skip,
+        },
         Agent::Droid => match tool_name {
             "ApplyPatch" | "Edit" | "Write" | "Create" => ToolClass::FileEdit,
             "Bash" => ToolClass::Bash,
@@ -394,6 +405,8 @@ pub enum Agent {
     Claude,
     Gemini,
     ContinueCli,
+    OpenHands,
+    AmazonQ,
     Droid,
     Amp,
     OpenCode,
@@ -488,6 +501,7 @@ fn is_wm_covered(
 /// Only covers the git-ai-specific patterns:
 /// - Default ignore patterns (lock files, node_modules, etc.)
 /// - Patterns from `.git-ai-ignore` at the repo root
+/// - `exclude_paths` from `.git-ai.toml` and the user's `workspace` config
 /// - Linguist-generated patterns from `.gitattributes` at the repo root
 ///
 /// Standard `.gitignore` handling — including nested `.gitignore` files throughout
@@ -501,6 +515,7 @@ pub fn build_gitignore(repo_root: &Path) -> Result<Gitignore, GitAiError> {
     let shared_patterns: Vec<String> = default_ignore_patterns()
         .into_iter()
         .chain(load_git_ai_ignore_patterns_from_path(repo_root))
+        .chain(effective_workspace_config_from_path(repo_root).exclude_paths)
         .chain(load_linguist_generated_patterns_from_path(repo_root))
         .collect();
     for pattern in &shared_patterns {
@@ -1502,6 +1517,27 @@ mod tests {
             ToolClass::Bash
         );
 
+        // OpenHands
+        assert_eq!(
+            classify_tool(Agent::OpenHands, "str_replace_editor"),
+            ToolClass::FileEdit
+        );
+        assert_eq!(
+            classify_tool(Agent::OpenHands, "execute_bash"),
+            ToolClass::Bash
+        );
+
+        // Amazon Q Developer
+        assert_eq!(
+            classify_tool(Agent::AmazonQ, "fs_write"),
+            ToolClass::FileEdit
+        );
+        assert_eq!(
+            classify_tool(Agent::AmazonQ, "execute_bash"),
+            ToolClass::Bash
+        );
+        assert_eq!(classify_tool(Agent::AmazonQ, "fs_read"), ToolClass::Skip);
+
         // Droid
         assert_eq!(
             classify_tool(Agent::Droid, "ApplyPatch"),
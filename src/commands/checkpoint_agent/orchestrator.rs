@@ -3,7 +3,7 @@ use crate::authorship::working_log::{AgentId, CheckpointKind};
 use crate::checkpoint_content_budget::CheckpointContentBudget;
 use crate::commands::checkpoint_agent::presets::{
     KnownHumanEdit, ParsedHookEvent, PostBashCall, PostFileEdit, PreBashCall, PreFileEdit,
-    StreamSource, UntrackedEdit,
+    StreamSource, TabCompletion, UntrackedEdit,
 };
 use crate::config;
 use crate::daemon::checkpoint::PreparedPathRole;
@@ -28,6 +28,32 @@ pub struct CheckpointFile {
     pub content: Option<String>,
     pub repo_work_dir: PathBuf,
     pub base_commit: BaseCommit,
+    /// Exact edits supplied via `--hunks-json`. When they reproduce `content`
+    /// from the previously checkpointed version, the daemon uses them instead
+    /// of diffing the two versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hunks: Option<Vec<CheckpointHunk>>,
+}
+
+/// One edit to a file: `old_lines` lines starting at 1-indexed `old_start` in
+/// the previous version were replaced by `new_text`.
+///
+/// `new_text` is the exact replacement including line terminators. A pure
+/// insertion uses `old_lines: 0` and is placed before line `old_start`
+/// (`old_start` may be one past the last line to append).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointHunk {
+    pub old_start: u32,
+    pub old_lines: u32,
+    #[serde(default)]
+    pub new_text: String,
+}
+
+/// A file entry in the `--hunks-json` payload.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CheckpointFileHunks {
+    pub path: PathBuf,
+    pub hunks: Vec<CheckpointHunk>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -177,6 +203,7 @@ fn build_checkpoint_files(file_paths: &[PathBuf]) -> Result<Vec<CheckpointFile>,
             content,
             repo_work_dir: ctx.repo_work_dir.clone(),
             base_commit: ctx.base_commit.clone(),
+            hunks: None,
         });
     }
 
@@ -229,6 +256,47 @@ pub fn execute_preset_checkpoint(
     Ok(requests)
 }
 
+/// Parses a `--hunks-json` payload: an array of `{"path", "hunks"}` objects.
+/// Relative paths are resolved against `cwd`.
+pub fn parse_hunks_json(json: &str, cwd: &Path) -> Result<Vec<CheckpointFileHunks>, GitAiError> {
+    let mut files: Vec<CheckpointFileHunks> = serde_json::from_str(json)
+        .map_err(|e| GitAiError::PresetError(format!("invalid --hunks-json payload: {}", e)))?;
+    for file in &mut files {
+        if file.path.is_relative() {
+            file.path = cwd.join(&file.path);
+        }
+        if file.hunks.iter().any(|hunk| hunk.old_start == 0) {
+            return Err(GitAiError::PresetError(format!(
+                "hunk old_start is 1-indexed: {}",
+                file.path.display()
+            )));
+        }
+    }
+    Ok(files)
+}
+
+/// Attaches supplied hunks to the edited files of `requests`, matched by path.
+/// Hunks for files that are not part of any request are ignored.
+pub fn attach_checkpoint_hunks(requests: &mut [CheckpointRequest], hunks: &[CheckpointFileHunks]) {
+    if hunks.is_empty() {
+        return;
+    }
+    let by_path: HashMap<&Path, &[CheckpointHunk]> = hunks
+        .iter()
+        .map(|file| (file.path.as_path(), file.hunks.as_slice()))
+        .collect();
+    for request in requests
+        .iter_mut()
+        .filter(|request| request.path_role == PreparedPathRole::Edited)
+    {
+        for file in &mut request.files {
+            if let Some(file_hunks) = by_path.get(file.path.as_path()) {
+                file.hunks = Some(file_hunks.to_vec());
+            }
+        }
+    }
+}
+
 fn write_checkpoint_debug_log(
     preset_name: &str,
     hook_input: &str,
@@ -309,6 +377,7 @@ fn execute_event(
         ParsedHookEvent::PostBashCall(e) => execute_post_bash_call(e),
         ParsedHookEvent::KnownHumanEdit(e) => execute_known_human_edit(e),
         ParsedHookEvent::UntrackedEdit(e) => execute_untracked_edit(e),
+        ParsedHookEvent::TabCompletion(e) => execute_tab_completion(e),
     }
 }
 
@@ -423,6 +492,46 @@ fn execute_untracked_edit(e: UntrackedEdit) -> Result<Vec<CheckpointRequest>, Gi
     ))
 }
 
+/// Records an accepted or rejected inline completion. Produces no checkpoint.
+fn execute_tab_completion(e: TabCompletion) -> Result<Vec<CheckpointRequest>, GitAiError> {
+    let repo = discover_repository_in_path_no_git_exec(e.context.cwd.as_path()).ok();
+    let outcome = if e.accepted {
+        crate::metrics::TabCompletionValues::ACCEPTED
+    } else {
+        crate::metrics::TabCompletionValues::REJECTED
+    };
+    let mut values = crate::metrics::TabCompletionValues::new()
+        .outcome(outcome)
+        .suggested_lines(e.suggested_lines)
+        .accepted_lines(e.accepted_lines)
+        .completion_id(
+            e.context
+                .agent_id
+                .id
+                .strip_prefix("ai_tab-")
+                .unwrap_or(&e.context.agent_id.id),
+        );
+    if let Some(path) = &e.file_path {
+        let relative = repo
+            .as_ref()
+            .and_then(|repo| repo.workdir().ok())
+            .and_then(|workdir| path.strip_prefix(workdir).ok().map(Path::to_path_buf))
+            .unwrap_or_else(|| path.clone());
+        values = values.file_path(relative.to_string_lossy().replace('\\', "/"));
+    }
+
+    let attrs = crate::daemon::checkpoint::build_agent_usage_attrs(
+        repo.as_ref(),
+        &e.context.agent_id,
+        e.context
+            .metadata
+            .get(crate::authorship::working_log::TOOL_VERSION_METADATA_KEY)
+            .map(String::as_str),
+    );
+    crate::metrics::record(values, attrs);
+    Ok(Vec::new())
+}
+
 fn execute_pre_bash_call(e: PreBashCall) -> Result<Vec<CheckpointRequest>, GitAiError> {
     use crate::commands::checkpoint_agent::bash_tool::{
         self, BashHookAttemptPhase, BashHookAttemptSignal,
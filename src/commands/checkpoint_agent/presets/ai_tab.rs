@@ -1,5 +1,7 @@
 use super::parse;
-use super::{AgentPreset, ParsedHookEvent, PostFileEdit, PreFileEdit, PresetContext};
+use super::{
+    AgentPreset, ParsedHookEvent, PostFileEdit, PreFileEdit, PresetContext, TabCompletion,
+};
 use crate::authorship::working_log::AgentId;
 use crate::error::GitAiError;
 use std::collections::HashMap;
@@ -15,9 +17,12 @@ impl AgentPreset for AiTabPreset {
 
         let hook_event = parse::required_str(&data, "hook_event_name")?;
 
-        if hook_event != "before_edit" && hook_event != "after_edit" {
+        if !matches!(
+            hook_event,
+            "before_edit" | "after_edit" | "completion_accepted" | "completion_rejected"
+        ) {
             return Err(GitAiError::PresetError(format!(
-                "Unsupported hook_event_name '{}' for ai_tab preset (expected 'before_edit' or 'after_edit')",
+                "Unsupported hook_event_name '{}' for ai_tab preset (expected 'before_edit', 'after_edit', 'completion_accepted' or 'completion_rejected')",
                 hook_event
             )));
         }
@@ -63,6 +68,26 @@ impl AgentPreset for AiTabPreset {
             metadata: HashMap::from([("tool".to_string(), tool)]),
         };
 
+        if hook_event == "completion_accepted" || hook_event == "completion_rejected" {
+            let accepted = hook_event == "completion_accepted";
+            let suggested_lines = line_count(&data, "suggested_lines")?.unwrap_or(0);
+            let accepted_lines = if accepted {
+                line_count(&data, "accepted_lines")?.unwrap_or(suggested_lines)
+            } else {
+                0
+            };
+            let file_path = parse::optional_str(&data, "filepath")
+                .filter(|s| !s.trim().is_empty())
+                .map(|path| parse::resolve_absolute(path, cwd));
+            return Ok(vec![ParsedHookEvent::TabCompletion(TabCompletion {
+                context,
+                accepted,
+                suggested_lines,
+                accepted_lines,
+                file_path,
+            })]);
+        }
+
         let dirty_files = parse::dirty_files_from_value(&data, cwd);
 
         let event = if hook_event == "before_edit" {
@@ -88,6 +113,22 @@ impl AgentPreset for AiTabPreset {
     }
 }
 
+fn line_count(data: &serde_json::Value, key: &str) -> Result<Option<u32>, GitAiError> {
+    match data.get(key) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .and_then(|n| u32::try_from(n).ok())
+            .map(Some)
+            .ok_or_else(|| {
+                GitAiError::PresetError(format!(
+                    "{} must be a non-negative integer for ai_tab preset",
+                    key
+                ))
+            }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,6 +198,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ai_tab_completion_outcomes() {
+        let input = json!({
+            "hook_event_name": "completion_accepted",
+            "tool": "copilot",
+            "model": "gpt-4",
+            "repo_working_dir": "/home/user/project",
+            "completion_id": "comp-1",
+            "filepath": "src/lib.rs",
+            "suggested_lines": 5
+        })
+        .to_string();
+        let events = AiTabPreset.parse(&input, "t_test").unwrap();
+        match &events[0] {
+            ParsedHookEvent::TabCompletion(e) => {
+                assert!(e.accepted);
+                assert_eq!(e.suggested_lines, 5);
+                assert_eq!(e.accepted_lines, 5);
+                assert_eq!(
+                    e.file_path,
+                    Some(PathBuf::from("/home/user/project/src/lib.rs"))
+                );
+                assert_eq!(e.context.external_session_id, "ai_tab-comp-1");
+            }
+            _ => panic!("Expected TabCompletion"),
+        }
+
+        let input = json!({
+            "hook_event_name": "completion_rejected",
+            "tool": "copilot",
+            "model": "gpt-4",
+            "suggested_lines": 3,
+            "accepted_lines": 3
+        })
+        .to_string();
+        match &AiTabPreset.parse(&input, "t_test").unwrap()[0] {
+            ParsedHookEvent::TabCompletion(e) => {
+                assert!(!e.accepted);
+                assert_eq!(e.suggested_lines, 3);
+                assert_eq!(e.accepted_lines, 0);
+                assert!(e.file_path.is_none());
+            }
+            _ => panic!("Expected TabCompletion"),
+        }
+
+        let input = json!({
+            "hook_event_name": "completion_accepted",
+            "tool": "copilot",
+            "model": "gpt-4",
+            "suggested_lines": -1
+        })
+        .to_string();
+        assert!(AiTabPreset.parse(&input, "t_test").is_err());
+    }
+
     #[test]
     fn test_ai_tab_rejects_invalid_event() {
         let input = json!({
@@ -0,0 +1,151 @@
+use super::parse;
+use super::{
+    AgentPreset, ParsedHookEvent, PostBashCall, PostFileEdit, PreBashCall, PreFileEdit,
+    PresetContext,
+};
+use crate::authorship::working_log::AgentId;
+use crate::commands::checkpoint_agent::bash_tool::{self, Agent, ToolClass};
+use crate::error::GitAiError;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Preset for the Amazon Q Developer CLI.
+///
+/// Agent hooks send `preToolUse` / `postToolUse` with the tool call in
+/// `tool_name` / `tool_input`.
+pub struct AmazonQPreset;
+
+impl AgentPreset for AmazonQPreset {
+    fn parse(&self, hook_input: &str, trace_id: &str) -> Result<Vec<ParsedHookEvent>, GitAiError> {
+        let data: serde_json::Value = serde_json::from_str(hook_input)
+            .map_err(|e| GitAiError::PresetError(format!("Invalid JSON in hook_input: {}", e)))?;
+
+        let cwd = parse::required_str(&data, "cwd")?;
+        let hook_event = parse::optional_str_multi(&data, &["hook_event_name", "hookEventName"]);
+        let tool_name = parse::optional_str(&data, "tool_name");
+        let tool_use_id =
+            parse::str_or_default_multi(&data, &["tool_use_id", "tool_call_id"], "bash");
+
+        let tool_class = tool_name
+            .map(|n| bash_tool::classify_tool(Agent::AmazonQ, n))
+            .unwrap_or(ToolClass::Skip);
+        if tool_class == ToolClass::Skip {
+            return Ok(vec![]);
+        }
+
+        // The CLI does not pass a conversation id to hooks, so its edits in one
+        // working directory share a session.
+        let session_id = parse::optional_str(&data, "session_id")
+            .unwrap_or(cwd)
+            .to_string();
+
+        let context = PresetContext {
+            agent_id: AgentId {
+                tool: "amazon-q".to_string(),
+                id: session_id.clone(),
+                model: parse::optional_str(&data, "model")
+                    .unwrap_or("unknown")
+                    .to_string(),
+            },
+            external_session_id: session_id,
+            trace_id: trace_id.to_string(),
+            cwd: PathBuf::from(cwd),
+            metadata: HashMap::new(),
+        };
+
+        let is_pre = matches!(hook_event, Some("preToolUse") | Some("PreToolUse"));
+        let is_bash = tool_class == ToolClass::Bash;
+        let file_paths = parse::file_paths_from_tool_input(&data, cwd);
+
+        let bash_command = parse::bash_command_from_hook_input(&data);
+        let event = match (is_pre, is_bash) {
+            (true, true) => ParsedHookEvent::PreBashCall(PreBashCall {
+                context,
+                tool_use_id: tool_use_id.to_string(),
+                command: bash_command,
+            }),
+            (true, false) => ParsedHookEvent::PreFileEdit(PreFileEdit {
+                context,
+                file_paths,
+                dirty_files: parse::dirty_files_from_value(&data, cwd),
+                tool_use_id: Some(tool_use_id.to_string()),
+            }),
+            (false, true) => ParsedHookEvent::PostBashCall(PostBashCall {
+                context,
+                tool_use_id: tool_use_id.to_string(),
+                command: bash_command,
+                stream_source: None,
+            }),
+            (false, false) => ParsedHookEvent::PostFileEdit(PostFileEdit {
+                context,
+                file_paths,
+                dirty_files: parse::dirty_files_from_value(&data, cwd),
+                stream_source: None,
+                tool_use_id: Some(tool_use_id.to_string()),
+            }),
+        };
+
+        Ok(vec![event])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_amazon_q_cli_fs_write_is_file_edit() {
+        let input = json!({
+            "hook_event_name": "postToolUse",
+            "cwd": "/home/user/project",
+            "tool_name": "fs_write",
+            "tool_input": {"command": "str_replace", "path": "src/lib.rs"}
+        })
+        .to_string();
+        let events = AmazonQPreset.parse(&input, "t_test123456789a").unwrap();
+        match &events[0] {
+            ParsedHookEvent::PostFileEdit(e) => {
+                assert_eq!(e.context.agent_id.tool, "amazon-q");
+                assert_eq!(e.context.agent_id.id, "/home/user/project");
+                assert_eq!(e.context.agent_id.model, "unknown");
+                assert_eq!(
+                    e.file_paths,
+                    vec![PathBuf::from("/home/user/project/src/lib.rs")]
+                );
+            }
+            _ => panic!("Expected PostFileEdit"),
+        }
+    }
+
+    #[test]
+    fn test_amazon_q_cli_execute_bash_and_reads() {
+        let bash = json!({
+            "hook_event_name": "preToolUse",
+            "cwd": "/home/user/project",
+            "tool_name": "execute_bash",
+            "tool_input": {"command": "cargo fmt"}
+        })
+        .to_string();
+        match &AmazonQPreset.parse(&bash, "t_test123456789a").unwrap()[0] {
+            ParsedHookEvent::PreBashCall(e) => {
+                assert_eq!(e.command.as_deref(), Some("cargo fmt"));
+            }
+            _ => panic!("Expected PreBashCall"),
+        }
+
+        let read = json!({
+            "hook_event_name": "preToolUse",
+            "cwd": "/home/user/project",
+            "tool_name": "fs_read",
+            "tool_input": {"path": "src/lib.rs"}
+        })
+        .to_string();
+        assert!(
+            AmazonQPreset
+                .parse(&read, "t_test123456789a")
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
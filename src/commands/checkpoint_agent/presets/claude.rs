@@ -4,7 +4,9 @@ use super::{
     PresetContext, StreamFormat, StreamSource,
 };
 use crate::authorship::authorship_log_serialization::generate_session_id;
-use crate::authorship::working_log::AgentId;
+use crate::authorship::working_log::{
+    AgentId, SUBAGENT_ID_METADATA_KEY, SUBAGENT_TYPE_METADATA_KEY, TOOL_VERSION_METADATA_KEY,
+};
 use crate::commands::checkpoint_agent::bash_tool::{self, Agent, ToolClass};
 use crate::error::GitAiError;
 use std::collections::HashMap;
@@ -61,11 +63,51 @@ impl AgentPreset for ClaudePreset {
         let tool_name = parse::optional_str_multi(&data, &["tool_name", "toolName"]);
         let hook_event = parse::optional_str_multi(&data, &["hook_event_name", "hookEventName"]);
         let tool_use_id = parse::str_or_default_multi(&data, &["tool_use_id", "toolUseId"], "bash");
+        // File edits only link to a tool call when Claude Code actually sent one.
+        let edit_tool_use_id =
+            parse::optional_str_multi(&data, &["tool_use_id", "toolUseId"]).map(str::to_string);
 
         let is_bash = tool_name
             .map(|n| bash_tool::classify_tool(Agent::Claude, n) == ToolClass::Bash)
             .unwrap_or(false);
 
+        let transcript_path_buf = PathBuf::from(transcript_path);
+        let external_parent_session_id =
+            crate::streams::agents::claude::ClaudeAgent::detect_subagent_parent(
+                &transcript_path_buf,
+            );
+
+        let mut metadata =
+            HashMap::from([("transcript_path".to_string(), transcript_path.to_string())]);
+        // Hooks fired inside a Task tool subagent carry its `agent_id`; older
+        // payloads only point the transcript at `subagents/agent-<id>.jsonl`.
+        let subagent_id = parse::optional_str_multi(&data, &["agent_id", "agentId"])
+            .map(str::to_string)
+            .or_else(|| {
+                external_parent_session_id
+                    .is_some()
+                    .then(|| parse::required_file_stem(&data, "transcript_path").ok())
+                    .flatten()
+            });
+        if let Some(subagent_id) = subagent_id {
+            metadata.insert(SUBAGENT_ID_METADATA_KEY.to_string(), subagent_id);
+            if let Some(agent_type) =
+                parse::optional_str_multi(&data, &["agent_type", "agentType", "subagent_type"])
+            {
+                metadata.insert(
+                    SUBAGENT_TYPE_METADATA_KEY.to_string(),
+                    agent_type.to_string(),
+                );
+            }
+        }
+
+        // Hook payloads don't carry the Claude Code version; every transcript entry does.
+        if let Some(version) = crate::streams::model_extraction::extract_claude_code_version(
+            Path::new(transcript_path),
+        ) {
+            metadata.insert(TOOL_VERSION_METADATA_KEY.to_string(), version);
+        }
+
         let context = PresetContext {
             agent_id: AgentId {
                 tool: "claude".to_string(),
@@ -82,14 +124,9 @@ impl AgentPreset for ClaudePreset {
             external_session_id: session_id.clone(),
             trace_id: trace_id.to_string(),
             cwd: PathBuf::from(cwd),
-            metadata: HashMap::from([("transcript_path".to_string(), transcript_path.to_string())]),
+            metadata,
         };
 
-        let transcript_path_buf = PathBuf::from(transcript_path);
-        let external_parent_session_id =
-            crate::streams::agents::claude::ClaudeAgent::detect_subagent_parent(
-                &transcript_path_buf,
-            );
         let stream_source = Some(StreamSource {
             path: transcript_path_buf,
             format: StreamFormat::ClaudeJsonl,
@@ -109,7 +146,7 @@ impl AgentPreset for ClaudePreset {
                 context,
                 file_paths: parse::file_paths_from_tool_input(&data, cwd),
                 dirty_files: None,
-                tool_use_id: Some(tool_use_id.to_string()),
+                tool_use_id: edit_tool_use_id,
             }),
             (_, true) => ParsedHookEvent::PostBashCall(PostBashCall {
                 context,
@@ -122,7 +159,7 @@ impl AgentPreset for ClaudePreset {
                 file_paths: parse::file_paths_from_tool_input(&data, cwd),
                 dirty_files: None,
                 stream_source,
-                tool_use_id: Some(tool_use_id.to_string()),
+                tool_use_id: edit_tool_use_id,
             }),
         };
 
@@ -181,6 +218,7 @@ mod tests {
                     e.file_paths,
                     vec![PathBuf::from("/home/user/project/src/main.rs")]
                 );
+                assert_eq!(e.tool_use_id.as_deref(), Some("tu-1"));
                 assert!(e.stream_source.is_some());
                 if let Some(ts) = &e.stream_source {
                     assert_eq!(ts.format, StreamFormat::ClaudeJsonl);
@@ -220,6 +258,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_claude_file_edit_without_tool_use_id_is_unlinked() {
+        let input = json!({
+            "transcript_path": "/home/user/.claude/projects/abc123.jsonl",
+            "cwd": "/home/user/project",
+            "hook_event_name": "PostToolUse",
+            "tool_name": "Edit",
+            "session_id": "sess-1",
+            "tool_input": {"file_path": "src/main.rs"}
+        })
+        .to_string();
+        let events = ClaudePreset.parse(&input, "t_test123456789a").unwrap();
+        match &events[0] {
+            ParsedHookEvent::PostFileEdit(e) => assert_eq!(e.tool_use_id, None),
+            _ => panic!("Expected PostFileEdit"),
+        }
+    }
+
+    #[test]
+    fn test_claude_subagent_edit_records_subagent_metadata() {
+        let input = json!({
+            "transcript_path": "/home/user/.claude/projects/p/sess-1/subagents/agent-a1b2.jsonl",
+            "cwd": "/home/user/project",
+            "hook_event_name": "PostToolUse",
+            "tool_name": "Edit",
+            "session_id": "sess-1",
+            "agent_type": "code-reviewer",
+            "tool_input": {"file_path": "src/main.rs"}
+        })
+        .to_string();
+        let events = ClaudePreset.parse(&input, "t_test123456789a").unwrap();
+        match &events[0] {
+            ParsedHookEvent::PostFileEdit(e) => {
+                assert_eq!(
+                    e.context
+                        .metadata
+                        .get(SUBAGENT_ID_METADATA_KEY)
+                        .map(String::as_str),
+                    Some("agent-a1b2")
+                );
+                assert_eq!(
+                    e.context
+                        .metadata
+                        .get(SUBAGENT_TYPE_METADATA_KEY)
+                        .map(String::as_str),
+                    Some("code-reviewer")
+                );
+            }
+            _ => panic!("Expected PostFileEdit"),
+        }
+
+        let parent = make_claude_hook_input("PostToolUse", "Edit");
+        let events = ClaudePreset.parse(&parent, "t_test123456789a").unwrap();
+        match &events[0] {
+            ParsedHookEvent::PostFileEdit(e) => {
+                assert!(!e.context.metadata.contains_key(SUBAGENT_ID_METADATA_KEY));
+            }
+            _ => panic!("Expected PostFileEdit"),
+        }
+    }
+
     #[test]
     fn test_claude_session_id_from_filename() {
         let input = json!({
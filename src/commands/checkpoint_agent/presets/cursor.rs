@@ -4,7 +4,7 @@ use super::{
     PresetContext, StreamFormat, StreamSource,
 };
 use crate::authorship::authorship_log_serialization::generate_session_id;
-use crate::authorship::working_log::AgentId;
+use crate::authorship::working_log::{AgentId, TOOL_VERSION_METADATA_KEY};
 use crate::commands::checkpoint_agent::bash_tool::{self, Agent, ToolClass};
 use crate::error::GitAiError;
 use std::collections::HashMap;
@@ -115,6 +115,9 @@ impl AgentPreset for CursorPreset {
         if let Some(ref tp) = transcript_path {
             metadata.insert("transcript_path".to_string(), tp.clone());
         }
+        if let Some(version) = parse::optional_str(&data, "cursor_version") {
+            metadata.insert(TOOL_VERSION_METADATA_KEY.to_string(), version.to_string());
+        }
 
         let context = PresetContext {
             agent_id: AgentId {
@@ -501,6 +504,13 @@ mod tests {
                 );
                 assert_eq!(e.tool_use_id, "tu-shell-1");
                 assert_eq!(e.command.as_deref(), Some("date > current_time.txt"));
+                assert_eq!(
+                    e.context
+                        .metadata
+                        .get(TOOL_VERSION_METADATA_KEY)
+                        .map(String::as_str),
+                    Some("3.1.17")
+                );
             }
             _ => panic!("Expected PreBashCall, got {:?}", events[0]),
         }
@@ -145,6 +145,7 @@ pub(super) fn parse_vscode_native_hooks(
     data: &serde_json::Value,
     hook_event_name: &str,
     trace_id: &str,
+    tool: &str,
 ) -> Result<Vec<ParsedHookEvent>, GitAiError> {
     let cwd = parse::optional_str_multi(data, &["cwd", "workspace_folder", "workspaceFolder"])
         .ok_or_else(|| GitAiError::PresetError("cwd not found in hook_input".to_string()))?;
@@ -205,6 +206,14 @@ pub(super) fn parse_vscode_native_hooks(
         metadata.insert("chat_session_path".to_string(), path.clone());
     }
 
+    // The trace stays per checkpoint; the turn is carried alongside so
+    // turn-level metrics can group the checkpoints one turn produced.
+    if let Some(turn_id) =
+        parse::optional_str_multi(data, &["turn_id", "turnId", "request_id", "requestId"])
+    {
+        metadata.insert("turn_id".to_string(), turn_id.to_string());
+    }
+
     // Determine transcript format: newer native uses EventStreamJsonl
     let transcript_format = if transcript_path
         .as_deref()
@@ -218,7 +227,7 @@ pub(super) fn parse_vscode_native_hooks(
 
     let context = PresetContext {
         agent_id: AgentId {
-            tool: "github-copilot".to_string(),
+            tool: tool.to_string(),
             id: session_id.clone(),
             model: transcript_path
                 .as_ref()
@@ -249,7 +258,7 @@ pub(super) fn parse_vscode_native_hooks(
     let stream_source = transcript_path.map(|tp| StreamSource {
         path: PathBuf::from(tp),
         format: transcript_format,
-        session_id: generate_session_id(&context.external_session_id, "github-copilot"),
+        session_id: generate_session_id(&context.external_session_id, tool),
         external_session_id: context.external_session_id.clone(),
         external_parent_session_id: None,
     });
@@ -430,7 +439,7 @@ fn classify_copilot_tool(tool_name: &str) -> ToolClass {
 #[cfg(test)]
 mod tests {
     use super::super::super::AgentPreset;
-    use super::super::GithubCopilotPreset;
+    use super::super::{CopilotAgentPreset, GithubCopilotPreset};
     use super::*;
     use serde_json::json;
 
@@ -605,6 +614,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_copilot_agent_post_file_edit_uses_agent_tool_and_records_turn() {
+        let input = json!({
+            "hook_event_name": "PostToolUse",
+            "cwd": "/home/user/project",
+            "tool_name": "create_file",
+            "session_id": "sess-456",
+            "turn_id": "turn-7",
+            "tool_use_id": "tu-2",
+            "tool_input": {"file_path": "/home/user/project/src/new.rs"},
+            "transcript_path": "/home/user/.vscode/data/github.copilot-chat/transcripts/sess-456.json"
+        })
+        .to_string();
+        let events = CopilotAgentPreset
+            .parse(&input, "t_test123456789a")
+            .unwrap();
+        match &events[0] {
+            ParsedHookEvent::PostFileEdit(e) => {
+                assert_eq!(e.context.agent_id.tool, "copilot-agent");
+                assert_eq!(e.context.external_session_id, "sess-456");
+                assert_eq!(e.context.trace_id, "t_test123456789a");
+                assert_eq!(
+                    e.context.metadata.get("turn_id").map(String::as_str),
+                    Some("turn-7")
+                );
+                assert_eq!(
+                    e.stream_source.as_ref().map(|s| s.session_id.clone()),
+                    Some(generate_session_id("sess-456", "copilot-agent"))
+                );
+            }
+            _ => panic!("Expected PostFileEdit"),
+        }
+    }
+
+    #[test]
+    fn test_copilot_agent_skips_cli_payloads() {
+        let input = json!({
+            "hook_event_name": "PostToolUse",
+            "cwd": "/home/user/project",
+            "tool_name": "edit",
+            "session_id": "sess-456",
+            "tool_input": {"file_path": "/home/user/project/src/main.rs"}
+        })
+        .to_string();
+        assert!(
+            CopilotAgentPreset
+                .parse(&input, "t_test123456789a")
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_copilot_native_model_prefers_otel_selected_model_over_models_json_default() {
         let dir = tempfile::tempdir().unwrap();
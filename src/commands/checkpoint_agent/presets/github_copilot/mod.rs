@@ -7,6 +7,9 @@ use std::path::{Path, PathBuf};
 mod cli;
 mod ide;
 
+const GITHUB_COPILOT_TOOL: &str = "github-copilot";
+const COPILOT_AGENT_TOOL: &str = "copilot-agent";
+
 pub struct GithubCopilotPreset;
 
 impl AgentPreset for GithubCopilotPreset {
@@ -37,7 +40,19 @@ impl AgentPreset for GithubCopilotPreset {
             if !has_transcript_path {
                 return cli::parse_cli_hooks(&data, hook_event_name, trace_id);
             }
-            return ide::parse_vscode_native_hooks(&data, hook_event_name, trace_id);
+            // Both hook files live in ~/.copilot/hooks, so VS Code runs both; let the
+            // agent-mode hooks own VS Code tool calls once they are installed.
+            if crate::mdm::agents::copilot_agent_hooks_installed() {
+                return Err(GitAiError::PresetError(
+                    "Skipping VS Code hook: handled by the copilot-agent hooks.".to_string(),
+                ));
+            }
+            return ide::parse_vscode_native_hooks(
+                &data,
+                hook_event_name,
+                trace_id,
+                GITHUB_COPILOT_TOOL,
+            );
         }
 
         Err(GitAiError::PresetError(format!(
@@ -47,6 +62,49 @@ impl AgentPreset for GithubCopilotPreset {
     }
 }
 
+/// VS Code Copilot agent mode, installed with `git-ai install-hooks --copilot-agent`.
+///
+/// Parses the same native PreToolUse/PostToolUse payloads as the VS Code path of
+/// [`GithubCopilotPreset`], but attributes edits to `copilot-agent` so multi-file
+/// agent sessions are reported separately from inline completions.
+pub struct CopilotAgentPreset;
+
+impl AgentPreset for CopilotAgentPreset {
+    fn parse(&self, hook_input: &str, trace_id: &str) -> Result<Vec<ParsedHookEvent>, GitAiError> {
+        let data: serde_json::Value = serde_json::from_str(hook_input)
+            .map_err(|e| GitAiError::PresetError(format!("Invalid JSON in hook_input: {}", e)))?;
+
+        let hook_event_name =
+            parse::optional_str_multi(&data, &["hook_event_name", "hookEventName"]).unwrap_or("");
+        if hook_event_name != "PreToolUse" && hook_event_name != "PostToolUse" {
+            return Err(GitAiError::PresetError(format!(
+                "Invalid hook_event_name: {}. Expected 'PreToolUse' or 'PostToolUse'",
+                hook_event_name
+            )));
+        }
+
+        // The Copilot CLI reads the same hooks directory; its payloads carry no
+        // transcript path and are checkpointed by the github-copilot hooks instead.
+        let has_transcript_path = parse::optional_str_multi(
+            &data,
+            &[
+                "transcript_path",
+                "transcriptPath",
+                "chat_session_path",
+                "chatSessionPath",
+            ],
+        )
+        .is_some();
+        if !has_transcript_path {
+            return Err(GitAiError::PresetError(
+                "Skipping copilot-agent hook: not a VS Code agent session.".to_string(),
+            ));
+        }
+
+        ide::parse_vscode_native_hooks(&data, hook_event_name, trace_id, COPILOT_AGENT_TOOL)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Shared helpers (used by both ide.rs and cli.rs)
 // ---------------------------------------------------------------------------
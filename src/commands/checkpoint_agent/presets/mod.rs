@@ -2,6 +2,7 @@ pub mod parse;
 
 mod agent_v1;
 mod ai_tab;
+mod amazon_q;
 mod amp;
 mod claude;
 mod cline;
@@ -17,6 +18,7 @@ mod known_human;
 mod mock_ai;
 mod mock_known_human;
 mod opencode;
+mod openhands;
 mod pi;
 mod windsurf;
 
@@ -43,6 +45,7 @@ pub enum ParsedHookEvent {
     PostBashCall(PostBashCall),
     KnownHumanEdit(KnownHumanEdit),
     UntrackedEdit(UntrackedEdit),
+    TabCompletion(TabCompletion),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,6 +83,17 @@ pub struct UntrackedEdit {
     pub file_paths: Vec<PathBuf>,
 }
 
+/// An inline completion the user accepted or dismissed. Only counted for
+/// metrics; the accepted text itself is checkpointed via `after_edit`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TabCompletion {
+    pub context: PresetContext,
+    pub accepted: bool,
+    pub suggested_lines: u32,
+    pub accepted_lines: u32,
+    pub file_path: Option<PathBuf>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PreBashCall {
     pub context: PresetContext,
@@ -161,12 +175,15 @@ pub fn resolve_preset(name: &str) -> Result<Box<dyn AgentPreset>, GitAiError> {
         "cursor" => Ok(Box::new(cursor::CursorPreset)),
         "cursor-background" => Ok(Box::new(cursor::CursorBackgroundPreset)),
         "github-copilot" => Ok(Box::new(github_copilot::GithubCopilotPreset)),
+        "copilot-agent" => Ok(Box::new(github_copilot::CopilotAgentPreset)),
         "amp" => Ok(Box::new(amp::AmpPreset)),
         "ai_tab" => Ok(Box::new(ai_tab::AiTabPreset)),
         "firebender" => Ok(Box::new(firebender::FirebenderPreset)),
         "agent-v1" => Ok(Box::new(agent_v1::AgentV1Preset)),
         "droid" => Ok(Box::new(droid::DroidPreset)),
         "opencode" => Ok(Box::new(opencode::OpenCodePreset)),
+        "openhands" => Ok(Box::new(openhands::OpenHandsPreset)),
+        "amazon-q" => Ok(Box::new(amazon_q::AmazonQPreset)),
         "pi" => Ok(Box::new(pi::PiPreset)),
         "human" => Ok(Box::new(human::HumanPreset)),
         "mock_ai" => Ok(Box::new(mock_ai::MockAiPreset)),
@@ -0,0 +1,192 @@
+use super::parse;
+use super::{
+    AgentPreset, ParsedHookEvent, PostBashCall, PostFileEdit, PreBashCall, PreFileEdit,
+    PresetContext,
+};
+use crate::authorship::working_log::AgentId;
+use crate::commands::checkpoint_agent::bash_tool::{self, Agent, ToolClass};
+use crate::error::GitAiError;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Preset for OpenHands runtime event hooks.
+///
+/// OpenHands sends `PreToolUse` / `PostToolUse` events with an `event_type` field and
+/// the sandbox working directory in `working_dir`.
+pub struct OpenHandsPreset;
+
+impl OpenHandsPreset {
+    /// `str_replace_editor` / `file_editor` also serve read-only `view` commands.
+    fn is_read_only_editor_command(data: &serde_json::Value) -> bool {
+        data.get("tool_input")
+            .and_then(|input| input.get("command"))
+            .and_then(|c| c.as_str())
+            .map(|c| c == "view")
+            .unwrap_or(false)
+    }
+}
+
+impl AgentPreset for OpenHandsPreset {
+    fn parse(&self, hook_input: &str, trace_id: &str) -> Result<Vec<ParsedHookEvent>, GitAiError> {
+        let data: serde_json::Value = serde_json::from_str(hook_input)
+            .map_err(|e| GitAiError::PresetError(format!("Invalid JSON in hook_input: {}", e)))?;
+
+        let cwd = parse::optional_str_multi(&data, &["working_dir", "cwd"]).ok_or_else(|| {
+            GitAiError::PresetError("working_dir not found in hook_input".to_string())
+        })?;
+        let session_id = parse::optional_str_multi(&data, &["session_id", "conversation_id"])
+            .ok_or_else(|| {
+                GitAiError::PresetError("session_id not found in hook_input".to_string())
+            })?
+            .to_string();
+        let hook_event = parse::optional_str_multi(&data, &["event_type", "hook_event_name"]);
+        let tool_name = parse::optional_str(&data, "tool_name");
+        let tool_use_id =
+            parse::str_or_default_multi(&data, &["tool_call_id", "tool_use_id"], "bash");
+
+        let tool_class = tool_name
+            .map(|n| bash_tool::classify_tool(Agent::OpenHands, n))
+            .unwrap_or(ToolClass::Skip);
+        if tool_class == ToolClass::Skip
+            || (tool_class == ToolClass::FileEdit && Self::is_read_only_editor_command(&data))
+        {
+            return Ok(vec![]);
+        }
+
+        let hook_metadata = data.get("metadata");
+        let model = parse::optional_str(&data, "model")
+            .or_else(|| hook_metadata.and_then(|m| parse::optional_str(m, "model")))
+            .unwrap_or("unknown");
+
+        let mut metadata = HashMap::new();
+        if let Some(agent) = hook_metadata.and_then(|m| parse::optional_str(m, "agent")) {
+            metadata.insert("openhands_agent".to_string(), agent.to_string());
+        }
+        if let Some(runtime) = hook_metadata.and_then(|m| parse::optional_str(m, "runtime")) {
+            metadata.insert("openhands_runtime".to_string(), runtime.to_string());
+        }
+
+        let context = PresetContext {
+            agent_id: AgentId {
+                tool: "openhands".to_string(),
+                id: session_id.clone(),
+                model: model.to_string(),
+            },
+            external_session_id: session_id,
+            trace_id: trace_id.to_string(),
+            cwd: PathBuf::from(cwd),
+            metadata,
+        };
+
+        let is_pre = hook_event == Some("PreToolUse");
+        let is_bash = tool_class == ToolClass::Bash;
+
+        let bash_command = parse::bash_command_from_hook_input(&data);
+        let event = match (is_pre, is_bash) {
+            (true, true) => ParsedHookEvent::PreBashCall(PreBashCall {
+                context,
+                tool_use_id: tool_use_id.to_string(),
+                command: bash_command,
+            }),
+            (true, false) => ParsedHookEvent::PreFileEdit(PreFileEdit {
+                context,
+                file_paths: parse::file_paths_from_tool_input(&data, cwd),
+                dirty_files: None,
+                tool_use_id: Some(tool_use_id.to_string()),
+            }),
+            (false, true) => ParsedHookEvent::PostBashCall(PostBashCall {
+                context,
+                tool_use_id: tool_use_id.to_string(),
+                command: bash_command,
+                stream_source: None,
+            }),
+            (false, false) => ParsedHookEvent::PostFileEdit(PostFileEdit {
+                context,
+                file_paths: parse::file_paths_from_tool_input(&data, cwd),
+                dirty_files: None,
+                stream_source: None,
+                tool_use_id: Some(tool_use_id.to_string()),
+            }),
+        };
+
+        Ok(vec![event])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn make_hook_input(event: &str, tool: &str, command: &str) -> String {
+        json!({
+            "event_type": event,
+            "tool_name": tool,
+            "tool_call_id": "call-1",
+            "session_id": "oh-conv-1",
+            "working_dir": "/workspace/project",
+            "tool_input": {"command": command, "path": "/workspace/project/app.py"},
+            "metadata": {"model": "gpt-5", "agent": "CodeActAgent", "runtime": "docker"}
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_openhands_pre_file_edit() {
+        let input = make_hook_input("PreToolUse", "str_replace_editor", "str_replace");
+        let events = OpenHandsPreset.parse(&input, "t_test123456789a").unwrap();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            ParsedHookEvent::PreFileEdit(e) => {
+                assert_eq!(e.context.agent_id.tool, "openhands");
+                assert_eq!(e.context.agent_id.id, "oh-conv-1");
+                assert_eq!(e.context.agent_id.model, "gpt-5");
+                assert_eq!(
+                    e.file_paths,
+                    vec![PathBuf::from("/workspace/project/app.py")]
+                );
+                assert_eq!(e.tool_use_id.as_deref(), Some("call-1"));
+            }
+            _ => panic!("Expected PreFileEdit"),
+        }
+    }
+
+    #[test]
+    fn test_openhands_post_file_edit_carries_session_metadata() {
+        let input = make_hook_input("PostToolUse", "file_editor", "create");
+        let events = OpenHandsPreset.parse(&input, "t_test123456789a").unwrap();
+        match &events[0] {
+            ParsedHookEvent::PostFileEdit(e) => {
+                assert_eq!(
+                    e.context.metadata.get("openhands_agent").unwrap(),
+                    "CodeActAgent"
+                );
+                assert_eq!(
+                    e.context.metadata.get("openhands_runtime").unwrap(),
+                    "docker"
+                );
+            }
+            _ => panic!("Expected PostFileEdit"),
+        }
+    }
+
+    #[test]
+    fn test_openhands_execute_bash_is_bash_call() {
+        let input = make_hook_input("PostToolUse", "execute_bash", "cargo fmt");
+        let events = OpenHandsPreset.parse(&input, "t_test123456789a").unwrap();
+        match &events[0] {
+            ParsedHookEvent::PostBashCall(e) => {
+                assert_eq!(e.tool_use_id, "call-1");
+                assert_eq!(e.command.as_deref(), Some("cargo fmt"));
+            }
+            _ => panic!("Expected PostBashCall"),
+        }
+    }
+
+    #[test]
+    fn test_openhands_view_command_is_skipped() {
+        let input = make_hook_input("PostToolUse", "str_replace_editor", "view");
+        let events = OpenHandsPreset.parse(&input, "t_test123456789a").unwrap();
+        assert!(events.is_empty());
+    }
+}
@@ -0,0 +1,535 @@
+//! `git-ai commit` — commit staged changes with an attribution footer.
+//!
+//! The footer summarizes, from the pending working log, how many of the staged
+//! added lines were written by AI and which tool/model wrote them:
+//!
+//! ```text
+//! Git-AI-Lines: 42 of 60 added lines by AI
+//! Git-AI-Tool: claude claude-sonnet-4: 30 lines
+//! Git-AI-Tool: cursor gpt-5: 12 lines
+//! ```
+//!
+//! The commit itself is made through the regular git proxy, so the authorship
+//! note is written exactly as for a plain `git commit`.
+
+use crate::authorship::attribution_tracker::{
+    AttributionTracker, attributions_to_line_attributions, line_attributions_to_attributions,
+};
+use crate::authorship::diff_base::EMPTY_TREE_SHA;
+use crate::authorship::format_detection::drop_whitespace_only_changes;
+use crate::authorship::stats::{
+    ATTRIBUTION_TRAILER_KEY, format_attribution_trailer, trailer_tool_name,
+};
+use crate::authorship::virtual_attribution::VirtualAttributions;
+use crate::authorship::working_log::AgentId;
+use crate::commands::diff::get_staged_diff_with_line_numbers;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::repo_storage::InitialAttributions;
+use crate::git::repository::Repository;
+use crate::workspace_config::{NormalizationConfig, WhitespaceMode, effective_workspace_config};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Attribution of the staged added lines, as reported in the commit footer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StagedAttributionSummary {
+    pub added_lines: u32,
+    pub ai_lines: u32,
+    /// AI lines keyed by `"<tool> <model>"`.
+    pub by_tool_model: BTreeMap<String, u32>,
+}
+
+impl StagedAttributionSummary {
+    /// The `AI-Attribution` trailer for the pending commit. Staged lines carry no
+    /// separate mixed attribution yet, so `mixed` is always zero.
+    pub fn trailer(&self) -> String {
+        let tools: BTreeSet<String> = self
+            .by_tool_model
+            .iter()
+            .filter(|(_, lines)| **lines > 0)
+            .map(|(tool_model, _)| {
+                trailer_tool_name(tool_model.split(' ').next().unwrap_or_default())
+            })
+            .collect();
+        format_attribution_trailer(
+            self.ai_lines,
+            self.added_lines.saturating_sub(self.ai_lines),
+            0,
+            &tools,
+        )
+    }
+}
+
+pub fn handle_commit(args: &[String]) {
+    let mut messages: Vec<String> = Vec::new();
+    let mut dry_run = false;
+    let mut passthrough: Vec<String> = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "-m" | "--message" if i + 1 < args.len() => {
+                messages.push(args[i + 1].clone());
+                i += 1;
+            }
+            "--dry-run" => dry_run = true,
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            "-a" | "--all" | "-F" | "--file" | "--amend" => {
+                eprintln!(
+                    "Error: git-ai commit does not support {}; stage changes and use -m instead",
+                    args[i]
+                );
+                std::process::exit(1);
+            }
+            other => {
+                if let Some(message) = other.strip_prefix("--message=") {
+                    messages.push(message.to_string());
+                } else if let Some(message) = other.strip_prefix("-m").filter(|m| !m.is_empty()) {
+                    messages.push(message.to_string());
+                } else {
+                    passthrough.push(other.to_string());
+                }
+            }
+        }
+        i += 1;
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let staged_files = match repo.get_staged_filenames() {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    if staged_files.is_empty() {
+        eprintln!("Error: nothing staged to commit");
+        std::process::exit(1);
+    }
+
+    let summary = match staged_attribution_summary(&repo) {
+        Ok(summary) => summary,
+        Err(e) => {
+            eprintln!("Error computing attribution summary: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let workspace = effective_workspace_config(&repo);
+    if let Some(share) = workspace
+        .policy
+        .ai_share_violation(summary.ai_lines, summary.added_lines)
+    {
+        eprintln!(
+            "Warning: {:.1}% of staged added lines are AI-authored, above this repository's policy maximum of {}%",
+            share,
+            workspace.policy.max_ai_share_pct.unwrap_or_default()
+        );
+    }
+
+    let mut staged_files: Vec<String> = staged_files.into_iter().collect();
+    staged_files.sort();
+    let user_message = (!messages.is_empty()).then(|| messages.join("\n\n"));
+    let message = build_commit_message(user_message.as_deref(), &staged_files, &summary);
+    let message = match with_required_trailers(
+        message,
+        &workspace.required_trailers,
+        &passthrough_trailer_keys(&passthrough),
+        &summary,
+    ) {
+        Ok(message) => message,
+        Err(missing) => {
+            eprintln!(
+                "Error: this repository requires commit trailers: {}",
+                missing.join(", ")
+            );
+            eprintln!("Add them to the message or pass --trailer \"<key>: <value>\"");
+            std::process::exit(1);
+        }
+    };
+
+    if dry_run {
+        println!("{}", message);
+        return;
+    }
+
+    let mut git_args = vec!["commit".to_string(), "-m".to_string(), message];
+    git_args.extend(passthrough);
+    crate::commands::git_handlers::handle_git(&git_args);
+}
+
+/// Summarizes AI attribution of the staged added lines from the pending working log.
+/// Whitespace-only changes are left out when the workspace sets `ignore_whitespace`.
+pub fn staged_attribution_summary(
+    repo: &Repository,
+) -> Result<StagedAttributionSummary, GitAiError> {
+    let workspace = effective_workspace_config(repo);
+    let staged_added = staged_added_lines(repo, workspace.whitespace_mode())?;
+    let mut summary = StagedAttributionSummary {
+        added_lines: staged_added.values().map(|lines| lines.len() as u32).sum(),
+        ..Default::default()
+    };
+    let Some(initial) = staged_initial_attributions(repo, &staged_added)? else {
+        return Ok(summary);
+    };
+
+    summary.by_tool_model = ai_lines_by_tool_model(&initial, &staged_added, &workspace.normalize);
+    summary.ai_lines = summary
+        .by_tool_model
+        .values()
+        .sum::<u32>()
+        .min(summary.added_lines);
+    Ok(summary)
+}
+
+/// Staged added lines as index line numbers keyed by file, without the
+/// whitespace-only changes `mode` ignores.
+fn staged_added_lines(
+    repo: &Repository,
+    mode: WhitespaceMode,
+) -> Result<HashMap<String, Vec<u32>>, GitAiError> {
+    if mode == WhitespaceMode::Exact {
+        return repo.diff_staged_added_lines();
+    }
+    let mut hunks = get_staged_diff_with_line_numbers(repo)?;
+    drop_whitespace_only_changes(&mut hunks, mode);
+    let mut added: HashMap<String, Vec<u32>> = HashMap::new();
+    for hunk in hunks {
+        if !hunk.added_lines.is_empty() {
+            added
+                .entry(hunk.file_path)
+                .or_default()
+                .extend(hunk.added_lines);
+        }
+    }
+    Ok(added)
+}
+
+/// Staged added lines attributed to AI, as index line numbers keyed by file.
+pub fn staged_ai_lines(repo: &Repository) -> Result<HashMap<String, Vec<u32>>, GitAiError> {
+    let staged_added = repo.diff_staged_added_lines()?;
+    let Some(initial) = staged_initial_attributions(repo, &staged_added)? else {
+        return Ok(HashMap::new());
+    };
+
+    let mut ai_lines: HashMap<String, Vec<u32>> = HashMap::new();
+    for (file_path, line_attrs) in &initial.files {
+        let Some(added) = staged_added.get(file_path) else {
+            continue;
+        };
+        for line_attr in line_attrs {
+            if line_agent_id(&initial, &line_attr.author_id).is_none() {
+                continue;
+            }
+            ai_lines.entry(file_path.clone()).or_default().extend(
+                added
+                    .iter()
+                    .filter(|line| (line_attr.start_line..=line_attr.end_line).contains(line)),
+            );
+        }
+    }
+    for lines in ai_lines.values_mut() {
+        lines.sort_unstable();
+        lines.dedup();
+    }
+    ai_lines.retain(|_, lines| !lines.is_empty());
+    Ok(ai_lines)
+}
+
+/// Attribution of the staged files from the pending working log, with line
+/// ranges mapped from working-tree to index coordinates, or `None` when there
+/// is nothing to attribute from.
+fn staged_initial_attributions(
+    repo: &Repository,
+    staged_added: &HashMap<String, Vec<u32>>,
+) -> Result<Option<InitialAttributions>, GitAiError> {
+    // An unborn branch keeps its checkpoints under the `initial` working log,
+    // and its working tree is compared against the empty tree.
+    let head_sha = repo
+        .head_commit_sha()?
+        .unwrap_or_else(|| "initial".to_string());
+    let head_tree_ish = if head_sha == "initial" {
+        EMPTY_TREE_SHA
+    } else {
+        head_sha.as_str()
+    };
+    let working_log = repo.storage.working_log_for_base_commit(&head_sha)?;
+    if working_log.read_all_checkpoints()?.is_empty()
+        && working_log.read_initial_attributions().files.is_empty()
+    {
+        return Ok(None);
+    }
+
+    let human_author = repo.effective_author_identity().formatted_or_unknown();
+    let working_va = VirtualAttributions::from_just_working_log(
+        repo.clone(),
+        head_sha.clone(),
+        Some(human_author),
+    )?;
+    let pathspecs: HashSet<String> = staged_added.keys().cloned().collect();
+    let (_, mut initial, worktree_contents) = working_va
+        .to_authorship_log_and_initial_working_log(
+            repo,
+            &head_sha,
+            head_tree_ish,
+            Some(&pathspecs),
+            None,
+        )?;
+
+    // The working log attributes working-tree lines, while staged additions are
+    // index line numbers; unstaged hunks in between shift the two apart.
+    let file_paths: Vec<String> = initial.files.keys().cloned().collect();
+    let index_contents = repo.get_all_staged_files_content(&file_paths)?;
+    let tracker = AttributionTracker::new();
+    let mut index_files = HashMap::new();
+    for (file_path, line_attrs) in initial.files {
+        let (Some(worktree), Some(index)) = (
+            worktree_contents.get(&file_path),
+            index_contents.get(&file_path),
+        ) else {
+            continue;
+        };
+        let line_attrs = if worktree == index {
+            line_attrs
+        } else {
+            let attrs = line_attributions_to_attributions(&line_attrs, worktree, 0);
+            let mapped = tracker.update_attributions(worktree, index, &attrs, "unknown", 0)?;
+            attributions_to_line_attributions(&mapped, index)
+        };
+        index_files.insert(file_path, line_attrs);
+    }
+    initial.files = index_files;
+    Ok(Some(initial))
+}
+
+/// The agent behind an attribution author id, `None` for human authors.
+fn line_agent_id<'a>(initial: &'a InitialAttributions, author_id: &str) -> Option<&'a AgentId> {
+    if author_id.starts_with("s_") {
+        let session_key = author_id.split("::").next().unwrap_or(author_id);
+        initial.sessions.get(session_key).map(|s| &s.agent_id)
+    } else {
+        initial.prompts.get(author_id).map(|p| &p.agent_id)
+    }
+}
+
+/// Counts AI-attributed lines that are also staged additions, keyed by tool and
+/// model after workspace normalization.
+fn ai_lines_by_tool_model(
+    initial: &InitialAttributions,
+    staged_added: &HashMap<String, Vec<u32>>,
+    normalize: &NormalizationConfig,
+) -> BTreeMap<String, u32> {
+    let mut by_tool_model: BTreeMap<String, u32> = BTreeMap::new();
+    for (file_path, line_attrs) in &initial.files {
+        let Some(added) = staged_added.get(file_path) else {
+            continue;
+        };
+        for line_attr in line_attrs {
+            let Some(agent_id) = line_agent_id(initial, &line_attr.author_id) else {
+                continue;
+            };
+            let lines = added
+                .iter()
+                .filter(|line| (line_attr.start_line..=line_attr.end_line).contains(line))
+                .count() as u32;
+            if lines > 0 {
+                *by_tool_model
+                    .entry(format!(
+                        "{} {}",
+                        normalize.tool(&agent_id.tool),
+                        normalize.model(&agent_id.model)
+                    ))
+                    .or_default() += lines;
+            }
+        }
+    }
+    by_tool_model
+}
+
+/// Builds the commit message: the user's message (or a generated subject) plus
+/// the attribution footer as git trailers.
+pub fn build_commit_message(
+    user_message: Option<&str>,
+    staged_files: &[String],
+    summary: &StagedAttributionSummary,
+) -> String {
+    let body = match user_message.map(str::trim_end) {
+        Some(message) if !message.trim().is_empty() => message.to_string(),
+        _ => generated_subject(staged_files),
+    };
+
+    let mut footer = vec![format!(
+        "Git-AI-Lines: {} of {} added lines by AI",
+        summary.ai_lines, summary.added_lines
+    )];
+    for (tool_model, lines) in &summary.by_tool_model {
+        footer.push(format!("Git-AI-Tool: {}: {} lines", tool_model, lines));
+    }
+
+    format!("{}\n\n{}", body, footer.join("\n"))
+}
+
+/// Keys of the `--trailer` options passed through to git commit.
+fn passthrough_trailer_keys(passthrough: &[String]) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut args = passthrough.iter();
+    while let Some(arg) = args.next() {
+        let value = match arg.strip_prefix("--trailer=") {
+            Some(value) => Some(value),
+            None if arg == "--trailer" => args.next().map(String::as_str),
+            None => None,
+        };
+        if let Some(key) = value.and_then(|v| v.split([':', '=']).next()) {
+            keys.push(key.trim().to_string());
+        }
+    }
+    keys
+}
+
+/// Make sure every `required` trailer key is present. A missing `AI-Attribution`
+/// trailer is filled in from the summary; other missing keys are returned.
+pub fn with_required_trailers(
+    message: String,
+    required: &[String],
+    passthrough_keys: &[String],
+    summary: &StagedAttributionSummary,
+) -> Result<String, Vec<String>> {
+    let has_trailer = |key: &str| {
+        passthrough_keys.iter().any(|k| k.eq_ignore_ascii_case(key))
+            || message.lines().any(|line| {
+                line.split_once(':')
+                    .is_some_and(|(k, _)| k.trim().eq_ignore_ascii_case(key))
+            })
+    };
+
+    let mut missing = Vec::new();
+    let mut add_attribution = false;
+    for key in required {
+        if has_trailer(key) {
+            continue;
+        }
+        if key.eq_ignore_ascii_case(ATTRIBUTION_TRAILER_KEY) {
+            add_attribution = true;
+        } else {
+            missing.push(key.clone());
+        }
+    }
+    if !missing.is_empty() {
+        return Err(missing);
+    }
+    Ok(if add_attribution {
+        format!("{}\n{}", message, summary.trailer())
+    } else {
+        message
+    })
+}
+
+fn generated_subject(staged_files: &[String]) -> String {
+    match staged_files {
+        [only] => format!("Update {}", only),
+        files => format!("Update {} files", files.len()),
+    }
+}
+
+fn print_help() {
+    eprintln!("git-ai commit - Commit staged changes with an AI attribution footer");
+    eprintln!();
+    eprintln!("Usage: git-ai commit [-m <message>] [--dry-run] [<git commit options>]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  -m, --message <msg>    Commit message; the footer is appended to it.");
+    eprintln!("                         Without -m a subject is generated from the staged files.");
+    eprintln!("  --dry-run              Print the commit message without committing");
+    eprintln!("  -h, --help             Show this help");
+    eprintln!();
+    eprintln!("Other options (e.g. --no-verify, -s) are passed to git commit.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_footer_to_user_message() {
+        let summary = StagedAttributionSummary {
+            added_lines: 10,
+            ai_lines: 7,
+            by_tool_model: BTreeMap::from([
+                ("claude sonnet".to_string(), 5),
+                ("cursor gpt-5".to_string(), 2),
+            ]),
+        };
+        let message = build_commit_message(
+            Some("Fix parser\n"),
+            &["src/parser.rs".to_string()],
+            &summary,
+        );
+        assert_eq!(
+            message,
+            "Fix parser\n\n\
+             Git-AI-Lines: 7 of 10 added lines by AI\n\
+             Git-AI-Tool: claude sonnet: 5 lines\n\
+             Git-AI-Tool: cursor gpt-5: 2 lines"
+        );
+    }
+
+    #[test]
+    fn fills_attribution_trailer_and_reports_other_missing_trailers() {
+        let summary = StagedAttributionSummary {
+            added_lines: 4,
+            ai_lines: 3,
+            by_tool_model: BTreeMap::from([("claude sonnet".to_string(), 3)]),
+        };
+        let message = build_commit_message(Some("Fix parser"), &[], &summary);
+        let required = vec!["AI-Attribution".to_string(), "Reviewed-by".to_string()];
+
+        assert_eq!(
+            with_required_trailers(message.clone(), &required, &[], &summary),
+            Err(vec!["Reviewed-by".to_string()])
+        );
+
+        let with_review = format!("{}\nReviewed-by: Sam", message);
+        let filled = with_required_trailers(with_review, &required, &[], &summary).unwrap();
+        assert!(
+            filled.ends_with("Reviewed-by: Sam\nAI-Attribution: ai=3 human=1 mixed=0 tools=claude")
+        );
+
+        let passthrough = passthrough_trailer_keys(&[
+            "--trailer".to_string(),
+            "Reviewed-by: Sam".to_string(),
+            "--trailer=AI-Attribution=manual".to_string(),
+        ]);
+        assert_eq!(passthrough, vec!["Reviewed-by", "AI-Attribution"]);
+        assert_eq!(
+            with_required_trailers(message.clone(), &required, &passthrough, &summary),
+            Ok(message)
+        );
+    }
+
+    #[test]
+    fn generates_subject_without_user_message() {
+        let summary = StagedAttributionSummary {
+            added_lines: 3,
+            ..Default::default()
+        };
+        let one = build_commit_message(None, &["README.md".to_string()], &summary);
+        assert!(one.starts_with("Update README.md\n\n"));
+        let many = build_commit_message(
+            Some("   "),
+            &["a.rs".to_string(), "b.rs".to_string()],
+            &summary,
+        );
+        assert!(many.starts_with("Update 2 files\n\n"));
+        assert!(many.ends_with("Git-AI-Lines: 0 of 3 added lines by AI"));
+    }
+}
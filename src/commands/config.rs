@@ -2,7 +2,9 @@ use dirs;
 use serde_json::Value;
 use std::collections::HashMap;
 
-use crate::config::{AuthorConfig, CodexHooksFormat, NotesBackendKind};
+use crate::config::{
+    AuthorConfig, CodexHooksFormat, DiffAlgorithm, NotesBackendKind, TelemetryChannel,
+};
 use crate::git::repository::find_repository_in_path;
 
 /// Determines the type of pattern value provided
@@ -103,6 +105,9 @@ fn print_config_help() {
     println!("  exclude_repositories         Excluded repos (array)");
     println!("  telemetry_oss                OSS telemetry setting (on/off)");
     println!("  telemetry_enterprise_dsn     Enterprise telemetry DSN");
+    println!("  telemetry_metrics            Usage metrics uploads (on/off)");
+    println!("  telemetry_prompts            Prompt and transcript uploads (on/off)");
+    println!("  telemetry_crash_reports      Crash and error reports (on/off)");
     println!("  disable_version_checks       Disable version checks (bool)");
     println!("  disable_auto_updates         Disable auto updates (bool)");
     println!("  update_channel               Update channel (latest/next)");
@@ -119,9 +124,40 @@ fn print_config_help() {
     println!(
         "  transcript_streaming_lookback_days  Days to look back when sweeping transcripts (0 = unlimited)"
     );
+    println!(
+        "  diff_algorithm                      Line diff for attribution: histogram (default), myers, myers-minimal"
+    );
     println!("  max_checkpoint_file_size_bytes      Per-file checkpoint content limit in bytes");
     println!("  max_checkpoint_total_size_bytes     Per-checkpoint content limit in bytes");
     println!("  max_checkpoint_total_lines          Per-checkpoint content limit in lines");
+    println!(
+        "  max_note_size_bytes                 Largest note kept inline; bigger ones overflow (0 = off)"
+    );
+    println!("  trace_file                   JSON lines file for GIT_AI_TRACE output");
+    println!("  slack_webhook_url            Slack incoming webhook for `git-ai report --post`");
+    println!(
+        "  commit_webhook_url           URL each commit's stats are POSTed to after attribution"
+    );
+    println!(
+        "  commit_webhook_secret        Key the commit webhook payload is HMAC-SHA256 signed with"
+    );
+    println!("  workspace                    Layered over each repo's .git-ai.toml (object)");
+    println!("  hook_timeouts                Hook name -> timeout in ms, 0 disables (object)");
+    println!("  max_hook_latency_ms          Post-commit latency budget in ms, 0 disables");
+    println!(
+        "  ai_storage_warn_mb           .git/ai size in MB that status warns past, 0 disables"
+    );
+    println!("  working_logs_dir             Absolute base dir for new repos' working logs");
+    println!(
+        "  anonymize_repositories       Repos whose identities are hashed in notes/metrics (array)"
+    );
+    println!("  anonymization_key            Workspace key identities are HMAC-hashed with");
+    println!(
+        "  workspace_roots              Dirs whose checkouts `bg status --workspace` covers (array)"
+    );
+    println!(
+        "  repo_sync_min_interval_ms    Min ms between notes fetches after git fetch, 0 disables"
+    );
     println!("  custom_attributes            Custom telemetry attributes, string->string (object)");
     println!("  git_ai_hooks                 Hook name -> shell commands map (object)");
     println!("  codex_hooks_format           Codex hook install format (config_toml/hooks_json)");
@@ -137,11 +173,18 @@ fn print_config_help() {
     println!("                               \"<base>/worker/notes/?commits=...\".");
     println!();
     println!("Repository Patterns:");
-    println!("  For exclude/allow/exclude_prompts_in_repositories, you can provide:");
+    println!(
+        "  For exclude/allow/exclude_prompts_in_repositories and anonymize_repositories, you can provide:"
+    );
     println!("    - A glob pattern: \"*\", \"https://github.com/org/*\"");
     println!("    - A URL/git protocol: \"git@github.com:org/repo.git\"");
     println!("    - A file path: \".\" or \"/path/to/repo\" (resolves to repo's remotes)");
     println!();
+    println!("Managed Settings:");
+    println!("  Keys set by your organization's device management profile (macOS configuration");
+    println!("  profile for com.git-ai.git-ai, or Windows policy under HKLM/HKCU");
+    println!("  SOFTWARE\\Policies\\git-ai) override your config and can't be set or unset.");
+    println!();
     println!("Examples:");
     println!("  git-ai config exclude_repositories");
     println!("  git-ai config set disable_auto_updates true");
@@ -158,6 +201,8 @@ fn print_config_help() {
     println!("  git-ai config set transcript_streaming_lookback_days 1");
     println!("  git-ai config set custom_attributes '{{\"team\":\"platform\"}}'");
     println!("  git-ai config --add custom_attributes.team platform");
+    println!("  git-ai config set workspace '{{\"exclude_paths\":[\"scratch/**\"]}}'");
+    println!("  git-ai config set hook_timeouts '{{\"fetch_notes\":10000}}'");
     println!("  git-ai config unset exclude_repositories");
     println!();
     std::process::exit(0);
@@ -248,7 +293,7 @@ pub fn handle_config(args: &[String]) {
 }
 
 fn show_all_config() -> Result<(), String> {
-    let file_config = crate::config::load_file_config_public()?;
+    let file_config = crate::config::load_effective_file_config()?;
 
     // Build a complete effective config representation
     let mut effective_config = serde_json::Map::new();
@@ -298,6 +343,12 @@ fn show_all_config() -> Result<(), String> {
         "telemetry_oss_disabled".to_string(),
         Value::Bool(runtime_config.is_telemetry_oss_disabled()),
     );
+    for channel in TelemetryChannel::ALL {
+        effective_config.insert(
+            channel.config_key().to_string(),
+            telemetry_channel_value(runtime_config.telemetry().channel(channel).enabled),
+        );
+    }
     effective_config.insert(
         "disable_version_checks".to_string(),
         Value::Bool(runtime_config.version_checks_disabled()),
@@ -365,6 +416,11 @@ fn show_all_config() -> Result<(), String> {
         Value::Bool(runtime_config.allow_superuser()),
     );
 
+    effective_config.insert(
+        "diff_algorithm".to_string(),
+        Value::String(runtime_config.diff_algorithm().as_str().to_string()),
+    );
+
     // transcript_streaming_lookback_days: runtime normalizes 0 -> None (unlimited).
     // Surface unlimited as 0 so it round-trips through `config set`.
     effective_config.insert(
@@ -389,6 +445,82 @@ fn show_all_config() -> Result<(), String> {
         "max_checkpoint_total_lines".to_string(),
         Value::Number(runtime_config.max_checkpoint_total_lines().into()),
     );
+    effective_config.insert(
+        "max_note_size_bytes".to_string(),
+        Value::Number(runtime_config.max_note_size_bytes().into()),
+    );
+    if let Some(ref trace_file) = file_config.trace_file {
+        effective_config.insert("trace_file".to_string(), Value::String(trace_file.clone()));
+    }
+    // Webhook URLs embed their own credential, so mask them like the API key.
+    if let Some(ref url) = file_config.slack_webhook_url {
+        effective_config.insert(
+            "slack_webhook_url".to_string(),
+            Value::String(mask_api_key(url)),
+        );
+    }
+    if let Some(ref url) = file_config.commit_webhook_url {
+        effective_config.insert(
+            "commit_webhook_url".to_string(),
+            Value::String(mask_api_key(url)),
+        );
+    }
+    if file_config.commit_webhook_secret.is_some() {
+        effective_config.insert(
+            "commit_webhook_secret".to_string(),
+            Value::String("****".to_string()),
+        );
+    }
+    if let Some(ref workspace) = file_config.workspace {
+        effective_config.insert(
+            "workspace".to_string(),
+            serde_json::to_value(workspace)
+                .unwrap_or_else(|_| Value::Object(serde_json::Map::new())),
+        );
+    }
+    if let Some(ref hook_timeouts) = file_config.hook_timeouts {
+        effective_config.insert(
+            "hook_timeouts".to_string(),
+            serde_json::to_value(hook_timeouts)
+                .unwrap_or_else(|_| Value::Object(serde_json::Map::new())),
+        );
+    }
+    if let Some(ms) = file_config.max_hook_latency_ms {
+        effective_config.insert("max_hook_latency_ms".to_string(), Value::Number(ms.into()));
+    }
+    if let Some(mb) = file_config.ai_storage_warn_mb {
+        effective_config.insert("ai_storage_warn_mb".to_string(), Value::Number(mb.into()));
+    }
+    if let Some(ref dir) = file_config.working_logs_dir {
+        effective_config.insert("working_logs_dir".to_string(), Value::String(dir.clone()));
+    }
+    effective_config.insert(
+        "anonymize_repositories".to_string(),
+        serde_json::to_value(
+            file_config
+                .anonymize_repositories
+                .clone()
+                .unwrap_or_default(),
+        )
+        .unwrap_or_else(|_| Value::Array(vec![])),
+    );
+    if let Some(ref key) = file_config.anonymization_key {
+        effective_config.insert(
+            "anonymization_key".to_string(),
+            Value::String(mask_api_key(key)),
+        );
+    }
+    effective_config.insert(
+        "workspace_roots".to_string(),
+        serde_json::to_value(file_config.workspace_roots.clone().unwrap_or_default())
+            .unwrap_or_else(|_| Value::Array(vec![])),
+    );
+    if let Some(ms) = file_config.repo_sync_min_interval_ms {
+        effective_config.insert(
+            "repo_sync_min_interval_ms".to_string(),
+            Value::Number(ms.into()),
+        );
+    }
 
     effective_config.insert(
         "custom_attributes".to_string(),
@@ -427,6 +559,14 @@ fn show_all_config() -> Result<(), String> {
         effective_config.insert("notes_backend".to_string(), Value::Object(nb_map));
     }
 
+    let managed_keys = managed_config_keys();
+    if !managed_keys.is_empty() {
+        effective_config.insert(
+            "managed_by_organization".to_string(),
+            serde_json::to_value(managed_keys).unwrap(),
+        );
+    }
+
     let json = serde_json::to_string_pretty(&effective_config)
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
 
@@ -435,7 +575,16 @@ fn show_all_config() -> Result<(), String> {
 }
 
 fn get_config_value(key: &str) -> Result<(), String> {
-    let file_config = crate::config::load_file_config_public()?;
+    print_config_value(key)?;
+    let root = parse_key_path(key).swap_remove(0);
+    if managed_config_keys().contains(&root) {
+        eprintln!("Note: {} is managed by your organization", root);
+    }
+    Ok(())
+}
+
+fn print_config_value(key: &str) -> Result<(), String> {
+    let file_config = crate::config::load_effective_file_config()?;
     let runtime_config = crate::config::Config::get();
 
     let key_path = parse_key_path(key);
@@ -473,6 +622,10 @@ fn get_config_value(key: &str) -> Result<(), String> {
                     Value::Null
                 }
             }
+            key @ ("telemetry_metrics" | "telemetry_prompts" | "telemetry_crash_reports") => {
+                let channel = telemetry_channel_for_key(key).unwrap();
+                telemetry_channel_value(runtime_config.telemetry().channel(channel).enabled)
+            }
             "disable_version_checks" => Value::Bool(runtime_config.version_checks_disabled()),
             "disable_auto_updates" => Value::Bool(runtime_config.auto_updates_disabled()),
             "update_channel" => Value::String(runtime_config.update_channel().as_str().to_string()),
@@ -519,6 +672,7 @@ fn get_config_value(key: &str) -> Result<(), String> {
                     .unwrap_or(0)
                     .into(),
             ),
+            "diff_algorithm" => Value::String(runtime_config.diff_algorithm().as_str().to_string()),
             "max_checkpoint_file_size_bytes" => {
                 Value::Number(runtime_config.max_checkpoint_file_size_bytes().into())
             }
@@ -528,6 +682,70 @@ fn get_config_value(key: &str) -> Result<(), String> {
             "max_checkpoint_total_lines" => {
                 Value::Number(runtime_config.max_checkpoint_total_lines().into())
             }
+            "max_note_size_bytes" => Value::Number(runtime_config.max_note_size_bytes().into()),
+            "trace_file" => file_config
+                .trace_file
+                .clone()
+                .map(Value::String)
+                .unwrap_or(Value::Null),
+            "slack_webhook_url" => file_config
+                .slack_webhook_url
+                .as_deref()
+                .map(|url| Value::String(mask_api_key(url)))
+                .unwrap_or(Value::Null),
+            "commit_webhook_url" => file_config
+                .commit_webhook_url
+                .as_deref()
+                .map(|url| Value::String(mask_api_key(url)))
+                .unwrap_or(Value::Null),
+            "commit_webhook_secret" => file_config
+                .commit_webhook_secret
+                .as_ref()
+                .map(|_| Value::String("****".to_string()))
+                .unwrap_or(Value::Null),
+            "workspace" => file_config
+                .workspace
+                .as_ref()
+                .and_then(|workspace| serde_json::to_value(workspace).ok())
+                .unwrap_or(Value::Null),
+            "hook_timeouts" => file_config
+                .hook_timeouts
+                .as_ref()
+                .and_then(|timeouts| serde_json::to_value(timeouts).ok())
+                .unwrap_or(Value::Null),
+            "max_hook_latency_ms" => file_config
+                .max_hook_latency_ms
+                .map(|ms| Value::Number(ms.into()))
+                .unwrap_or(Value::Null),
+            "ai_storage_warn_mb" => file_config
+                .ai_storage_warn_mb
+                .map(|mb| Value::Number(mb.into()))
+                .unwrap_or(Value::Null),
+            "working_logs_dir" => file_config
+                .working_logs_dir
+                .clone()
+                .map(Value::String)
+                .unwrap_or(Value::Null),
+            "anonymize_repositories" => {
+                if let Some(ref repos) = file_config.anonymize_repositories {
+                    serde_json::to_value(repos).unwrap()
+                } else {
+                    Value::Array(vec![])
+                }
+            }
+            "anonymization_key" => file_config
+                .anonymization_key
+                .as_deref()
+                .map(|key| Value::String(mask_api_key(key)))
+                .unwrap_or(Value::Null),
+            "workspace_roots" => {
+                serde_json::to_value(file_config.workspace_roots.clone().unwrap_or_default())
+                    .unwrap_or_else(|_| Value::Array(vec![]))
+            }
+            "repo_sync_min_interval_ms" => file_config
+                .repo_sync_min_interval_ms
+                .map(|ms| Value::Number(ms.into()))
+                .unwrap_or(Value::Null),
             "custom_attributes" => serde_json::to_value(runtime_config.custom_attributes())
                 .unwrap_or_else(|_| Value::Object(serde_json::Map::new())),
             "notes_backend" => {
@@ -647,6 +865,7 @@ fn get_config_value(key: &str) -> Result<(), String> {
 }
 
 fn set_config_value(key: &str, value: &str, add_mode: bool) -> Result<(), String> {
+    ensure_not_device_managed(key)?;
     let mut file_config = crate::config::load_file_config_public()?;
     let key_path = parse_key_path(key);
 
@@ -695,6 +914,14 @@ fn set_config_value(key: &str, value: &str, add_mode: bool) -> Result<(), String
                 crate::config::save_file_config(&file_config)?;
                 println!("[telemetry_enterprise_dsn]: {}", value);
             }
+            key @ ("telemetry_metrics" | "telemetry_prompts" | "telemetry_crash_reports") => {
+                let enabled = parse_bool(value)?;
+                let value = if enabled { "on" } else { "off" };
+                *telemetry_channel_field(&mut file_config, key) = Some(value.to_string());
+                crate::config::save_file_config(&file_config)?;
+                println!("[{}]: {}", key, value);
+                warn_if_telemetry_channel_managed(key);
+            }
             "disable_version_checks" => {
                 let bool_value = parse_bool(value)?;
                 file_config.disable_version_checks = Some(bool_value);
@@ -832,6 +1059,17 @@ fn set_config_value(key: &str, value: &str, add_mode: bool) -> Result<(), String
                 crate::config::save_file_config(&file_config)?;
                 println!("[transcript_streaming_lookback_days]: {}", days);
             }
+            "diff_algorithm" => {
+                let algorithm = DiffAlgorithm::parse(value).ok_or_else(|| {
+                    format!(
+                        "Invalid diff_algorithm value '{}'. Expected histogram, myers or myers-minimal",
+                        value
+                    )
+                })?;
+                file_config.diff_algorithm = Some(algorithm.as_str().to_string());
+                crate::config::save_file_config(&file_config)?;
+                println!("[diff_algorithm]: {}", algorithm.as_str());
+            }
             "max_checkpoint_file_size_bytes" => {
                 let bytes = value.trim().parse::<usize>().map_err(|_| {
                     format!(
@@ -865,6 +1103,137 @@ fn set_config_value(key: &str, value: &str, add_mode: bool) -> Result<(), String
                 crate::config::save_file_config(&file_config)?;
                 println!("[max_checkpoint_total_lines]: {}", lines);
             }
+            "max_note_size_bytes" => {
+                let bytes = value.trim().parse::<usize>().map_err(|_| {
+                    format!(
+                        "Invalid max_note_size_bytes value '{}'. Expected a non-negative integer in bytes",
+                        value
+                    )
+                })?;
+                file_config.max_note_size_bytes = Some(bytes);
+                crate::config::save_file_config(&file_config)?;
+                println!("[max_note_size_bytes]: {}", bytes);
+            }
+            "trace_file" => {
+                file_config.trace_file = Some(value.to_string());
+                crate::config::save_file_config(&file_config)?;
+                println!("[trace_file]: {}", value);
+            }
+            "slack_webhook_url" => {
+                if !value.starts_with("https://") {
+                    return Err(format!(
+                        "Invalid slack_webhook_url '{}'. Expected an https:// URL",
+                        mask_api_key(value)
+                    ));
+                }
+                file_config.slack_webhook_url = Some(value.to_string());
+                crate::config::save_file_config(&file_config)?;
+                println!("[slack_webhook_url]: {}", mask_api_key(value));
+            }
+            "commit_webhook_url" => {
+                if !value.starts_with("https://") && !value.starts_with("http://") {
+                    return Err(format!(
+                        "Invalid commit_webhook_url '{}'. Expected an http:// or https:// URL",
+                        mask_api_key(value)
+                    ));
+                }
+                file_config.commit_webhook_url = Some(value.to_string());
+                crate::config::save_file_config(&file_config)?;
+                println!("[commit_webhook_url]: {}", mask_api_key(value));
+            }
+            "commit_webhook_secret" => {
+                file_config.commit_webhook_secret = Some(value.to_string());
+                crate::config::save_file_config(&file_config)?;
+                println!("[commit_webhook_secret]: ****");
+            }
+            "workspace" => {
+                if add_mode {
+                    return Err("Cannot use --add with workspace. Set the whole object".to_string());
+                }
+                let workspace: crate::workspace_config::WorkspaceConfig =
+                    serde_json::from_str(value)
+                        .map_err(|e| format!("Invalid workspace value: {}", e))?;
+                workspace.validate()?;
+                file_config.workspace = Some(workspace);
+                crate::config::save_file_config(&file_config)?;
+                println!("[workspace]: {}", value);
+            }
+            "hook_timeouts" => {
+                if add_mode {
+                    return Err(
+                        "Cannot use --add with hook_timeouts. Set the whole object".to_string()
+                    );
+                }
+                let timeouts: HashMap<String, u64> = serde_json::from_str(value)
+                    .map_err(|e| format!("Invalid hook_timeouts value: {}", e))?;
+                file_config.hook_timeouts = (!timeouts.is_empty()).then_some(timeouts);
+                crate::config::save_file_config(&file_config)?;
+                println!("[hook_timeouts]: {}", value);
+            }
+            "max_hook_latency_ms" => {
+                let ms = value.trim().parse::<u64>().map_err(|_| {
+                    format!(
+                        "Invalid max_hook_latency_ms value '{}'. Expected a non-negative integer in milliseconds",
+                        value
+                    )
+                })?;
+                file_config.max_hook_latency_ms = Some(ms);
+                crate::config::save_file_config(&file_config)?;
+                println!("[max_hook_latency_ms]: {}", ms);
+            }
+            "ai_storage_warn_mb" => {
+                let mb = value.trim().parse::<u64>().map_err(|_| {
+                    format!(
+                        "Invalid ai_storage_warn_mb value '{}'. Expected a non-negative integer in megabytes",
+                        value
+                    )
+                })?;
+                file_config.ai_storage_warn_mb = Some(mb);
+                crate::config::save_file_config(&file_config)?;
+                println!("[ai_storage_warn_mb]: {}", mb);
+            }
+            "working_logs_dir" => {
+                let dir = value.trim();
+                if !std::path::Path::new(dir).is_absolute() {
+                    return Err(format!(
+                        "Invalid working_logs_dir value '{}'. Expected an absolute path",
+                        value
+                    ));
+                }
+                file_config.working_logs_dir = Some(dir.to_string());
+                crate::config::save_file_config(&file_config)?;
+                println!("[working_logs_dir]: {}", dir);
+            }
+            "anonymize_repositories" => {
+                let added = set_repository_array_field(
+                    &mut file_config.anonymize_repositories,
+                    value,
+                    add_mode,
+                )?;
+                crate::config::save_file_config(&file_config)?;
+                log_array_changes(&added, add_mode);
+            }
+            "anonymization_key" => {
+                file_config.anonymization_key = Some(value.to_string());
+                crate::config::save_file_config(&file_config)?;
+                println!("[anonymization_key]: {}", mask_api_key(value));
+            }
+            "workspace_roots" => {
+                let added = set_workspace_roots(&mut file_config.workspace_roots, value, add_mode)?;
+                crate::config::save_file_config(&file_config)?;
+                log_array_changes(&added, add_mode);
+            }
+            "repo_sync_min_interval_ms" => {
+                let ms = value.trim().parse::<u64>().map_err(|_| {
+                    format!(
+                        "Invalid repo_sync_min_interval_ms value '{}'. Expected a non-negative integer in milliseconds",
+                        value
+                    )
+                })?;
+                file_config.repo_sync_min_interval_ms = Some(ms);
+                crate::config::save_file_config(&file_config)?;
+                println!("[repo_sync_min_interval_ms]: {}", ms);
+            }
             "custom_attributes" => {
                 if add_mode {
                     return Err("Cannot use --add with custom_attributes at top level. Use dot notation: custom_attributes.key".to_string());
@@ -1047,6 +1416,7 @@ fn set_config_value(key: &str, value: &str, add_mode: bool) -> Result<(), String
 }
 
 fn unset_config_value(key: &str) -> Result<(), String> {
+    ensure_not_device_managed(key)?;
     let mut file_config = crate::config::load_file_config_public()?;
     let key_path = parse_key_path(key);
 
@@ -1095,6 +1465,13 @@ fn unset_config_value(key: &str) -> Result<(), String> {
                     println!("- [telemetry_enterprise_dsn]: {}", v);
                 }
             }
+            key @ ("telemetry_metrics" | "telemetry_prompts" | "telemetry_crash_reports") => {
+                let old_value = telemetry_channel_field(&mut file_config, key).take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    println!("- [{}]: {}", key, v);
+                }
+            }
             "disable_version_checks" => {
                 let old_value = file_config.disable_version_checks.take();
                 crate::config::save_file_config(&file_config)?;
@@ -1204,6 +1581,13 @@ fn unset_config_value(key: &str) -> Result<(), String> {
                     println!("- [transcript_streaming_lookback_days]: {}", v);
                 }
             }
+            "diff_algorithm" => {
+                let old_value = file_config.diff_algorithm.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    println!("- [diff_algorithm]: {}", v);
+                }
+            }
             "max_checkpoint_file_size_bytes" => {
                 let old_value = file_config.max_checkpoint_file_size_bytes.take();
                 crate::config::save_file_config(&file_config)?;
@@ -1225,6 +1609,110 @@ fn unset_config_value(key: &str) -> Result<(), String> {
                     println!("- [max_checkpoint_total_lines]: {}", v);
                 }
             }
+            "max_note_size_bytes" => {
+                let old_value = file_config.max_note_size_bytes.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    println!("- [max_note_size_bytes]: {}", v);
+                }
+            }
+            "trace_file" => {
+                let old_value = file_config.trace_file.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    println!("- [trace_file]: {}", v);
+                }
+            }
+            "slack_webhook_url" => {
+                let old_value = file_config.slack_webhook_url.take();
+                crate::config::save_file_config(&file_config)?;
+                if old_value.is_some() {
+                    println!("- [slack_webhook_url]: ****");
+                }
+            }
+            "commit_webhook_url" => {
+                let old_value = file_config.commit_webhook_url.take();
+                crate::config::save_file_config(&file_config)?;
+                if old_value.is_some() {
+                    println!("- [commit_webhook_url]: ****");
+                }
+            }
+            "commit_webhook_secret" => {
+                let old_value = file_config.commit_webhook_secret.take();
+                crate::config::save_file_config(&file_config)?;
+                if old_value.is_some() {
+                    println!("- [commit_webhook_secret]: ****");
+                }
+            }
+            "workspace" => {
+                let old_value = file_config.workspace.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    println!(
+                        "- [workspace]: {}",
+                        serde_json::to_string(&v).unwrap_or_default()
+                    );
+                }
+            }
+            "hook_timeouts" => {
+                let old_value = file_config.hook_timeouts.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    println!(
+                        "- [hook_timeouts]: {}",
+                        serde_json::to_string(&v).unwrap_or_default()
+                    );
+                }
+            }
+            "max_hook_latency_ms" => {
+                let old_value = file_config.max_hook_latency_ms.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    println!("- [max_hook_latency_ms]: {}", v);
+                }
+            }
+            "ai_storage_warn_mb" => {
+                let old_value = file_config.ai_storage_warn_mb.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    println!("- [ai_storage_warn_mb]: {}", v);
+                }
+            }
+            "working_logs_dir" => {
+                let old_value = file_config.working_logs_dir.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    println!("- [working_logs_dir]: {}", v);
+                }
+            }
+            "anonymize_repositories" => {
+                let old_values = file_config.anonymize_repositories.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(items) = old_values {
+                    log_array_removals(&items);
+                }
+            }
+            "anonymization_key" => {
+                let old_value = file_config.anonymization_key.take();
+                crate::config::save_file_config(&file_config)?;
+                if old_value.is_some() {
+                    println!("- [anonymization_key]: ****");
+                }
+            }
+            "workspace_roots" => {
+                let old_values = file_config.workspace_roots.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(items) = old_values {
+                    log_array_removals(&items);
+                }
+            }
+            "repo_sync_min_interval_ms" => {
+                let old_value = file_config.repo_sync_min_interval_ms.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    println!("- [repo_sync_min_interval_ms]: {}", v);
+                }
+            }
             "custom_attributes" => {
                 let old_value = file_config.custom_attributes.take();
                 crate::config::save_file_config(&file_config)?;
@@ -1483,6 +1971,44 @@ fn resolve_repository_value(value: &str) -> Result<Vec<String>, String> {
     }
 }
 
+/// Set or extend `workspace_roots` from a path or a JSON array of paths.
+/// Roots must be absolute (or start with `~/`) since the daemon resolves them,
+/// not the shell that ran `git-ai config`.
+fn set_workspace_roots(
+    field: &mut Option<Vec<String>>,
+    value: &str,
+    add_mode: bool,
+) -> Result<Vec<String>, String> {
+    let roots: Vec<String> = if value.starts_with('[') {
+        serde_json::from_str(value).map_err(|e| format!("Invalid JSON array: {}", e))?
+    } else {
+        vec![value.trim().to_string()]
+    };
+    if let Some(root) = roots
+        .iter()
+        .find(|root| !root.starts_with("~/") && !std::path::Path::new(root).is_absolute())
+    {
+        return Err(format!(
+            "Invalid workspace_roots value '{}'. Expected an absolute path",
+            root
+        ));
+    }
+
+    if add_mode {
+        let mut existing = field.take().unwrap_or_default();
+        let added: Vec<String> = roots
+            .into_iter()
+            .filter(|root| !existing.contains(root))
+            .collect();
+        existing.extend(added.iter().cloned());
+        *field = Some(existing);
+        Ok(added)
+    } else {
+        *field = (!roots.is_empty()).then(|| roots.clone());
+        Ok(roots)
+    }
+}
+
 /// Log array changes with + prefix for add mode, or just list items for set mode
 fn log_array_changes(items: &[String], add_mode: bool) {
     #[allow(clippy::if_same_then_else)]
@@ -1614,6 +2140,69 @@ fn parse_bool(value: &str) -> Result<bool, String> {
     }
 }
 
+fn telemetry_channel_for_key(key: &str) -> Option<TelemetryChannel> {
+    TelemetryChannel::ALL
+        .into_iter()
+        .find(|channel| channel.config_key() == key)
+}
+
+fn telemetry_channel_field<'a>(
+    file_config: &'a mut crate::config::FileConfig,
+    key: &str,
+) -> &'a mut Option<String> {
+    match telemetry_channel_for_key(key) {
+        Some(TelemetryChannel::Metrics) => &mut file_config.telemetry_metrics,
+        Some(TelemetryChannel::Prompts) => &mut file_config.telemetry_prompts,
+        Some(TelemetryChannel::CrashReports) | None => &mut file_config.telemetry_crash_reports,
+    }
+}
+
+fn telemetry_channel_value(enabled: bool) -> Value {
+    Value::String(if enabled { "on" } else { "off" }.to_string())
+}
+
+/// Local telemetry settings are ignored while the managed policy enforces the channel.
+fn warn_if_telemetry_channel_managed(key: &str) {
+    let Some(channel) = telemetry_channel_for_key(key) else {
+        return;
+    };
+    let state = crate::config::Config::fresh().telemetry().channel(channel);
+    if state.managed {
+        eprintln!(
+            "Note: {} is managed by your organization and stays {}",
+            channel.label(),
+            if state.enabled { "on" } else { "off" }
+        );
+    }
+}
+
+/// Top-level keys the organization enforces, through the device's management
+/// profile or the managed telemetry policy.
+fn managed_config_keys() -> Vec<String> {
+    let mut keys = crate::mdm::managed_preferences::load_managed_preferences().enforced_keys();
+    let telemetry = crate::config::Config::get().telemetry();
+    for channel in TelemetryChannel::ALL {
+        let key = channel.config_key().to_string();
+        if telemetry.channel(channel).managed && !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    keys.sort();
+    keys
+}
+
+/// Keys pinned by the device's management profile would be saved but never take effect.
+fn ensure_not_device_managed(key: &str) -> Result<(), String> {
+    let root = parse_key_path(key).swap_remove(0);
+    if crate::mdm::managed_preferences::load_managed_preferences().enforces(&root) {
+        return Err(format!(
+            "{} is managed by your organization and can't be changed locally",
+            root
+        ));
+    }
+    Ok(())
+}
+
 fn parse_value(value: &str) -> Result<Value, String> {
     // Try to parse as JSON first
     if let Ok(json_value) = serde_json::from_str::<Value>(value) {
@@ -0,0 +1,182 @@
+//! `git-ai checkpoint --conflict-resolution --tool <name> [--model <name>] <files...>`
+//!
+//! Integration point for AI-powered merge tools. A `git mergetool` wrapper
+//! calls it for the files the tool resolved; the resolution is recorded in
+//! `.git/ai` against the in-progress merge, and when the merge commit is
+//! written the lines in those files that neither side had are credited to the
+//! tool (see [`fold_merge_tool_resolutions`]).
+//!
+//! [`fold_merge_tool_resolutions`]: crate::authorship::conflict_resolution::fold_merge_tool_resolutions
+
+use crate::git::find_repository;
+use crate::git::repo_storage::ConflictResolutionRecord;
+use crate::git::repository::Repository;
+use crate::utils::normalize_to_posix;
+use std::path::{Path, PathBuf};
+
+/// `args` are the checkpoint arguments, including `--conflict-resolution`.
+/// Like other checkpoints, failures are reported but exit 0 so a wrapper
+/// never aborts the merge tool.
+pub fn handle_conflict_resolution_checkpoint(args: &[String]) {
+    let mut tool: Option<String> = None;
+    let mut model: Option<String> = None;
+    let mut files: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--conflict-resolution" => i += 1,
+            "--tool" if i + 1 < args.len() => {
+                tool = Some(args[i + 1].clone());
+                i += 2;
+            }
+            "--model" if i + 1 < args.len() => {
+                model = Some(args[i + 1].clone());
+                i += 2;
+            }
+            "--" => {
+                files.extend(args[i + 1..].iter().cloned());
+                break;
+            }
+            other if other.starts_with("--") => {
+                eprintln!(
+                    "Error: unknown option '{}' for --conflict-resolution",
+                    other
+                );
+                std::process::exit(0);
+            }
+            _ => {
+                files.push(args[i].clone());
+                i += 1;
+            }
+        }
+    }
+
+    let Some(tool) = tool.filter(|tool| !tool.trim().is_empty()) else {
+        eprintln!("Error: --conflict-resolution requires --tool <name>");
+        std::process::exit(0);
+    };
+    if files.is_empty() {
+        eprintln!("Error: --conflict-resolution requires the resolved files");
+        std::process::exit(0);
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(0);
+        }
+    };
+    match record_conflict_resolution(
+        &repo,
+        tool,
+        model.unwrap_or_else(|| "unknown".to_string()),
+        &files,
+    ) {
+        Ok(record) => println!(
+            "Recorded conflict resolution by {} for {} file(s)",
+            record.tool,
+            record.files.len()
+        ),
+        Err(e) => eprintln!("Error: {}", e),
+    }
+}
+
+fn record_conflict_resolution(
+    repo: &Repository,
+    tool: String,
+    model: String,
+    files: &[String],
+) -> Result<ConflictResolutionRecord, String> {
+    let merge_heads = read_merge_heads(repo.path())
+        .ok_or_else(|| "no merge in progress (MERGE_HEAD not found)".to_string())?;
+    let head = repo
+        .head()
+        .and_then(|head| head.target())
+        .map_err(|e| e.to_string())?;
+    let workdir = repo.workdir().map_err(|e| e.to_string())?;
+    let cwd = std::env::current_dir().map_err(|e| e.to_string())?;
+    let files = files
+        .iter()
+        .map(|file| repo_relative_path(&workdir, &cwd, file))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Resolutions left behind by an aborted merge must not leak into a later one.
+    for stale in repo.storage.read_conflict_resolutions() {
+        if stale.head != head || stale.merge_heads != merge_heads {
+            repo.storage
+                .remove_conflict_resolutions(&stale.head)
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    let record = ConflictResolutionRecord {
+        tool,
+        model,
+        files,
+        head,
+        merge_heads,
+        timestamp: chrono::Utc::now().timestamp().max(0) as u64,
+    };
+    repo.storage
+        .append_conflict_resolution(&record)
+        .map_err(|e| e.to_string())?;
+    Ok(record)
+}
+
+/// The commits listed in `MERGE_HEAD`, or `None` when no merge is in progress.
+fn read_merge_heads(git_dir: &Path) -> Option<Vec<String>> {
+    let content = std::fs::read_to_string(git_dir.join("MERGE_HEAD")).ok()?;
+    let heads: Vec<String> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+    (!heads.is_empty()).then_some(heads)
+}
+
+/// `file` (absolute, or relative to `cwd`) relative to the worktree root.
+fn repo_relative_path(workdir: &Path, cwd: &Path, file: &str) -> Result<String, String> {
+    let path = Path::new(file);
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        cwd.join(path)
+    };
+    let workdir = canonical(workdir);
+    let absolute = match (absolute.parent(), absolute.file_name()) {
+        (Some(parent), Some(name)) => canonical(parent).join(name),
+        _ => absolute,
+    };
+    let relative = absolute
+        .strip_prefix(&workdir)
+        .map_err(|_| format!("'{}' is outside the repository", file))?;
+    Ok(normalize_to_posix(&relative.to_string_lossy()))
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_relative_path_resolves_against_cwd() {
+        let temp = tempfile::tempdir().unwrap();
+        let workdir = temp.path();
+        std::fs::create_dir_all(workdir.join("src")).unwrap();
+
+        assert_eq!(
+            repo_relative_path(workdir, workdir, "src/lib.rs").unwrap(),
+            "src/lib.rs"
+        );
+        assert_eq!(
+            repo_relative_path(workdir, &workdir.join("src"), "lib.rs").unwrap(),
+            "src/lib.rs"
+        );
+        assert!(repo_relative_path(&workdir.join("src"), workdir, "other.rs").is_err());
+    }
+}
@@ -36,6 +36,12 @@ pub fn handle_daemon(args: &[String]) {
                 std::process::exit(1);
             }
         }
+        "status" if has_flag(&args[1..], "--workspace") => {
+            if let Err(e) = handle_workspace_status() {
+                eprintln!("Failed to get status: {}", e);
+                std::process::exit(1);
+            }
+        }
         "status" => {
             let repo = parse_repo_arg(&args[1..]).unwrap_or_else(default_repo_path);
             if let Err(e) = handle_status(repo) {
@@ -481,6 +487,24 @@ fn handle_status(repo_working_dir: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Status of every checkout under `workspace_roots`, from the one daemon.
+fn handle_workspace_status() -> Result<(), String> {
+    let config = daemon_config_from_env_or_default_paths()?;
+    if !daemon_is_up(&config) {
+        return Err("background service is not running".to_string());
+    }
+    let response = send_control_request(
+        &config.control_socket_path,
+        &ControlRequest::StatusWorkspace,
+    )
+    .map_err(|e| e.to_string())?;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&response).map_err(|e| e.to_string())?
+    );
+    Ok(())
+}
+
 fn handle_tail(args: &[String]) -> Result<(), String> {
     let config = daemon_config_from_env_or_default_paths()?;
     if !daemon_is_up(&config) {
@@ -787,7 +811,7 @@ fn print_help() {
     eprintln!("Usage:");
     eprintln!("  git-ai bg start");
     eprintln!("  git-ai bg run");
-    eprintln!("  git-ai bg status [--repo <path>]");
+    eprintln!("  git-ai bg status [--repo <path> | --workspace]");
     eprintln!("  git-ai bg shutdown [--hard]");
     eprintln!("  git-ai bg restart [--hard]");
     eprintln!("  git-ai bg tail [-n <lines>] [--full] [-f | --follow]");
@@ -1,7 +1,7 @@
 use crate::auth::{AuthState, collect_auth_status, format_unix_timestamp};
 use crate::config;
 use crate::diagnostics::{DiagnosticCheckResult, GitDiagnosticTarget};
-use crate::git::find_repository_in_path;
+use crate::git::find_repository;
 use crate::git::repository::{
     GitAuthorIdentity, GitConfigIdentityResolution, GitIdentityResolution,
     global_git_config_identity_resolution,
@@ -106,6 +106,7 @@ fn build_debug_report(options: DebugOptions) -> String {
     let git_config = collect_git_config_dump(&git_cmd);
     debug_progress("collecting git-ai config and login state");
     let git_ai_config = collect_git_ai_config_dump();
+    let hook_binary = collect_hook_binary_info(&git_cmd);
     let platform_info = collect_platform_info();
     let hardware_info = collect_hardware_info();
     let repository_info = collect_repository_info();
@@ -309,6 +310,9 @@ fn build_debug_report(options: DebugOptions) -> String {
     }
     let _ = writeln!(out);
 
+    append_hook_binary_info(&mut out, &hook_binary);
+    let _ = writeln!(out);
+
     let _ = writeln!(out, "== Git AI Login ==");
     let _ = writeln!(out, "Credential backend: {}", auth_info.backend);
     match &auth_info.state {
@@ -416,6 +420,84 @@ enum RepositoryCommitterIdentity {
     NotInRepository(String),
 }
 
+struct HookBinaryInfo {
+    current_link: Option<std::path::PathBuf>,
+    current_link_target: Option<std::path::PathBuf>,
+    embedded: Option<String>,
+}
+
+fn collect_hook_binary_info(git_cmd: &str) -> HookBinaryInfo {
+    let current_link = crate::mdm::hook_binary::current_link_path();
+    let current_link_target = current_link
+        .as_deref()
+        .and_then(|link| fs::read_link(link).ok());
+    let driver_key = format!(
+        "{}.driver",
+        crate::commands::install_hooks::NOTES_MERGE_DRIVER_SECTION
+    );
+    let embedded = run_git_command_capture(git_cmd, &["config", "--global", "--get", &driver_key])
+        .ok()
+        .and_then(|driver| {
+            let rest = driver.trim().strip_prefix('"')?;
+            rest.split_once('"').map(|(path, _)| path.to_string())
+        });
+    HookBinaryInfo {
+        current_link,
+        current_link_target,
+        embedded,
+    }
+}
+
+fn append_hook_binary_info(out: &mut String, info: &HookBinaryInfo) {
+    let _ = writeln!(out, "== Hook Binary ==");
+    match (&info.current_link, &info.current_link_target) {
+        (Some(link), Some(target)) => {
+            let _ = writeln!(
+                out,
+                "Current link: {} -> {}",
+                link.display(),
+                target.display()
+            );
+        }
+        (Some(link), None) => {
+            let _ = writeln!(out, "Current link: {} <missing>", link.display());
+        }
+        (None, _) => {
+            let _ = writeln!(out, "Current link: <unavailable>");
+        }
+    }
+    let Some(embedded) = &info.embedded else {
+        let _ = writeln!(
+            out,
+            "Embedded path: <not configured; run `git-ai install-hooks`>"
+        );
+        return;
+    };
+    let _ = writeln!(out, "Embedded path: {}", embedded);
+    match crate::mdm::hook_binary::resolve_hook_binary(Path::new(embedded)) {
+        Some((resolved, source)) => {
+            let _ = writeln!(
+                out,
+                "Resolves to: {} (via {})",
+                resolved.display(),
+                source.as_str()
+            );
+            if source != crate::mdm::hook_binary::HookBinarySource::Embedded {
+                let _ = writeln!(
+                    out,
+                    "  <warning: embedded binary is missing; run `git-ai install-hooks` to re-embed>"
+                );
+            }
+        }
+        None => {
+            let _ = writeln!(
+                out,
+                "Resolves to: <error: no git-ai binary found; run `git-ai install-hooks`>"
+            );
+        }
+    }
+}
+
 fn collect_git_committer_identity_info(
     repository_info: &RepositoryInfo,
 ) -> GitCommitterIdentityInfo {
@@ -1202,11 +1284,7 @@ struct RepositoryInfo {
 }
 
 fn collect_repository_info() -> RepositoryInfo {
-    let cwd = env::current_dir()
-        .map(|p| p.display().to_string())
-        .unwrap_or_else(|_| ".".to_string());
-
-    let repo = match find_repository_in_path(&cwd) {
+    let repo = match find_repository(&Vec::<String>::new()) {
         Ok(repo) => repo,
         Err(e) => {
             return RepositoryInfo {
@@ -4,6 +4,7 @@ use crate::authorship::ignore::{
     build_ignore_matcher, effective_ignore_patterns, should_ignore_file_with_matcher,
 };
 use crate::commands::blame::GitAiBlameOptions;
+use crate::commands::output::OutputOptions;
 use crate::error::GitAiError;
 use crate::git::notes_api::{read_authorship, read_note};
 use crate::git::repository::{InternalGitProfile, Repository, exec_git_with_profile};
@@ -208,7 +209,13 @@ pub fn handle_diff(repo: &Repository, args: &[String]) -> Result<(), GitAiError>
         std::process::exit(1);
     }
 
-    let parsed = parse_diff_args(args)?;
+    // Output flags may also come before the command, so re-add `--json` for
+    // the diff parser once they have been consumed.
+    let mut args = args.to_vec();
+    if OutputOptions::current().is_json() {
+        args.push("--json".to_string());
+    }
+    let parsed = parse_diff_args(&args)?;
     let output = execute_diff(repo, parsed)?;
     print!("{}", output);
 
@@ -436,7 +443,7 @@ fn resolve_commit(repo: &Repository, rev: &str) -> Result<String, GitAiError> {
     Ok(sha)
 }
 
-fn resolve_parent(repo: &Repository, commit: &str) -> Result<String, GitAiError> {
+pub(crate) fn resolve_parent(repo: &Repository, commit: &str) -> Result<String, GitAiError> {
     let parent_rev = format!("{}^", commit);
 
     // Try to resolve parent
@@ -476,10 +483,25 @@ pub fn get_diff_with_line_numbers(
     from: &str,
     to: &str,
 ) -> Result<Vec<DiffHunk>, GitAiError> {
+    let _span = tracing::debug_span!("diff").entered();
     let diff_text = get_diff_text(repo, from, to, true)?;
     parse_diff_hunks(&diff_text)
 }
 
+/// Hunks of the staged changes (`git diff --cached`), without rename detection.
+pub fn get_staged_diff_with_line_numbers(repo: &Repository) -> Result<Vec<DiffHunk>, GitAiError> {
+    let _span = tracing::debug_span!("diff").entered();
+    let mut args = repo.global_args_for_exec();
+    args.push("diff".to_string());
+    args.push("--cached".to_string());
+    args.push("-U0".to_string());
+    args.push("--no-color".to_string());
+    args.push("--no-renames".to_string());
+
+    let output = exec_git_with_profile(&args, InternalGitProfile::PatchParse)?;
+    parse_diff_hunks(&String::from_utf8_lossy(&output.stdout))
+}
+
 fn get_diff_text(
     repo: &Repository,
     from: &str,
@@ -1181,6 +1203,9 @@ fn apply_blame_for_side(
                         agent_id: prompt_record.agent_id.clone(),
                         human_author: prompt_record.human_author.clone(),
                         custom_attributes: prompt_record.custom_attributes.clone(),
+                        tool_calls: BTreeMap::new(),
+                        subagents: BTreeMap::new(),
+                        tool_versions: BTreeMap::new(),
                     });
             }
         } else {
@@ -1280,7 +1305,7 @@ fn apply_blame_for_side(
 
 /// Convert a sorted list of line numbers to contiguous ranges
 /// e.g., [1, 2, 3, 5, 6, 10] -> [(1, 3), (5, 6), (10, 10)]
-fn lines_to_ranges(lines: &[u32]) -> Vec<(u32, u32)> {
+pub(crate) fn lines_to_ranges(lines: &[u32]) -> Vec<(u32, u32)> {
     if lines.is_empty() {
         return Vec::new();
     }
@@ -2110,6 +2135,224 @@ fn format_attribution(attribution: &Attribution, humans: &BTreeMap<String, Human
     }
 }
 
+// ============================================================================
+// Attribution Gutter Output (git-ai show --patch)
+// ============================================================================
+
+const GUTTER_LABEL_MAX_WIDTH: usize = 16;
+const AI_GUTTER_COLOR: &str = "\x1b[35m"; // Magenta
+const HUMAN_GUTTER_COLOR: &str = "\x1b[34m"; // Blue
+const MIXED_HUNK_COLOR: &str = "\x1b[33m"; // Yellow
+
+/// Render the diff with an attribution gutter (tool or author label and a
+/// colored bar) in front of every changed line, and a summary of the hunk's
+/// added lines (AI, human, or mixed, with per-tool counts) on each hunk header.
+pub fn format_attributed_patch(
+    repo: &Repository,
+    from_commit: &str,
+    to_commit: &str,
+    attributions: &HashMap<DiffLineKey, Attribution>,
+    humans: &BTreeMap<String, HumanRecord>,
+    included_files: &HashSet<String>,
+    use_color: bool,
+) -> Result<String, GitAiError> {
+    let sections = get_diff_sections_by_file(repo, from_commit, to_commit)?;
+    let label_width = attributions
+        .values()
+        .map(|attribution| gutter_label(attribution, humans).chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(GUTTER_LABEL_MAX_WIDTH);
+    let mut result = String::new();
+
+    for (file_path, section_text) in sections {
+        if !included_files.contains(&file_path) {
+            continue;
+        }
+
+        let mut hunk: Option<(&str, Vec<(&str, Option<&Attribution>)>)> = None;
+        let mut old_line_num = 0u32;
+        let mut new_line_num = 0u32;
+
+        for line in section_text.lines() {
+            if line.starts_with("@@ ") {
+                if let Some((header, lines)) = hunk.take() {
+                    push_attributed_hunk(
+                        &mut result,
+                        header,
+                        &lines,
+                        humans,
+                        label_width,
+                        use_color,
+                    );
+                }
+                if let Some((old_start, new_start)) = parse_hunk_header_for_line_nums(line) {
+                    old_line_num = old_start;
+                    new_line_num = new_start;
+                }
+                hunk = Some((line, Vec::new()));
+                continue;
+            }
+            let Some((_, lines)) = hunk.as_mut() else {
+                let line = if use_color && is_diff_header_line(line, false) {
+                    format!("\x1b[1m{}\x1b[0m", line)
+                } else {
+                    line.to_string()
+                };
+                result.push_str(&line);
+                result.push('\n');
+                continue;
+            };
+            let attribution = if line.starts_with('+') {
+                new_line_num += 1;
+                attributions.get(&DiffLineKey {
+                    file: file_path.clone(),
+                    line: new_line_num - 1,
+                    side: LineSide::New,
+                })
+            } else if line.starts_with('-') {
+                old_line_num += 1;
+                attributions.get(&DiffLineKey {
+                    file: file_path.clone(),
+                    line: old_line_num - 1,
+                    side: LineSide::Old,
+                })
+            } else {
+                if line.starts_with(' ') {
+                    old_line_num += 1;
+                    new_line_num += 1;
+                }
+                None
+            };
+            lines.push((line, attribution));
+        }
+        if let Some((header, lines)) = hunk.take() {
+            push_attributed_hunk(&mut result, header, &lines, humans, label_width, use_color);
+        }
+    }
+
+    Ok(result)
+}
+
+fn push_attributed_hunk(
+    result: &mut String,
+    header: &str,
+    lines: &[(&str, Option<&Attribution>)],
+    humans: &BTreeMap<String, HumanRecord>,
+    label_width: usize,
+    use_color: bool,
+) {
+    let added: Vec<&Attribution> = lines
+        .iter()
+        .filter(|(line, _)| line.starts_with('+'))
+        .filter_map(|(_, attribution)| *attribution)
+        .collect();
+    let summary = hunk_attribution_summary(&added);
+
+    result.push_str(&" ".repeat(label_width + 3));
+    match (&summary, use_color) {
+        (Some((kind, text)), true) => {
+            let color = match *kind {
+                "AI" => AI_GUTTER_COLOR,
+                "human" => HUMAN_GUTTER_COLOR,
+                _ => MIXED_HUNK_COLOR,
+            };
+            result.push_str(&format!(
+                "\x1b[36m{}\x1b[0m  {}{}\x1b[0m\n",
+                header, color, text
+            ));
+        }
+        (Some((_, text)), false) => result.push_str(&format!("{}  {}\n", header, text)),
+        (None, true) => result.push_str(&format!("\x1b[36m{}\x1b[0m\n", header)),
+        (None, false) => result.push_str(&format!("{}\n", header)),
+    }
+
+    for (line, attribution) in lines {
+        let label = attribution
+            .map(|attribution| gutter_label(attribution, humans))
+            .unwrap_or_default();
+        let label: String = label.chars().take(label_width).collect();
+        if !use_color {
+            let bar = if attribution.is_some() { '|' } else { ' ' };
+            result.push_str(&format!(
+                "{:>width$} {} {}\n",
+                label,
+                bar,
+                line,
+                width = label_width
+            ));
+            continue;
+        }
+        let gutter_color = match attribution {
+            Some(Attribution::Ai(_)) => AI_GUTTER_COLOR,
+            Some(Attribution::Human(_)) => HUMAN_GUTTER_COLOR,
+            _ => "\x1b[2m",
+        };
+        let bar = if attribution.is_some() { '▌' } else { ' ' };
+        let line_color = if line.starts_with('+') {
+            "\x1b[32m"
+        } else if line.starts_with('-') {
+            "\x1b[31m"
+        } else {
+            ""
+        };
+        result.push_str(&format!(
+            "{}{:>width$} {}\x1b[0m {}{}\x1b[0m\n",
+            gutter_color,
+            label,
+            bar,
+            line_color,
+            line,
+            width = label_width
+        ));
+    }
+}
+
+fn gutter_label(attribution: &Attribution, humans: &BTreeMap<String, HumanRecord>) -> String {
+    match attribution {
+        Attribution::Ai(tool) => tool.clone(),
+        Attribution::Human(human_id) => humans
+            .get(human_id)
+            .map(|human| human.author.clone())
+            .unwrap_or_else(|| "human".to_string()),
+        Attribution::NoData => String::new(),
+    }
+}
+
+/// Classify a hunk by the attribution of its added lines: `AI`, `human`, or
+/// `mixed`, with a label such as `[mixed: claude 3, human 2]`. `None` when no
+/// added line has attribution.
+fn hunk_attribution_summary(added: &[&Attribution]) -> Option<(&'static str, String)> {
+    let mut ai_by_tool: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut human_lines = 0usize;
+    for attribution in added {
+        match attribution {
+            Attribution::Ai(tool) => *ai_by_tool.entry(tool.as_str()).or_default() += 1,
+            Attribution::Human(_) => human_lines += 1,
+            Attribution::NoData => {}
+        }
+    }
+
+    let kind = match (ai_by_tool.is_empty(), human_lines > 0) {
+        (true, false) => return None,
+        (true, true) => "human",
+        (false, false) => "AI",
+        (false, true) => "mixed",
+    };
+    let mut parts: Vec<String> = ai_by_tool
+        .iter()
+        .map(|(tool, count)| format!("{} {}", tool, count))
+        .collect();
+    if human_lines > 0 {
+        parts.push(if kind == "human" {
+            human_lines.to_string()
+        } else {
+            format!("human {}", human_lines)
+        });
+    }
+    Some((kind, format!("[{}: {}]", kind, parts.join(", "))))
+}
+
 /// Custom serializer for annotations that converts LineRange to JSON tuples
 fn serialize_annotations<S>(
     annotations: &BTreeMap<String, Vec<LineRange>>,
@@ -2244,6 +2487,29 @@ mod tests {
     use crate::authorship::working_log::AgentId;
     use std::collections::{BTreeMap, HashMap, HashSet};
 
+    #[test]
+    fn test_hunk_attribution_summary_classifies_added_lines() {
+        let claude = Attribution::Ai("claude".to_string());
+        let cursor = Attribution::Ai("cursor".to_string());
+        let human = Attribution::Human("h_1".to_string());
+        let no_data = Attribution::NoData;
+
+        assert_eq!(
+            hunk_attribution_summary(&[&claude, &cursor, &claude]),
+            Some(("AI", "[AI: claude 2, cursor 1]".to_string()))
+        );
+        assert_eq!(
+            hunk_attribution_summary(&[&human, &no_data, &human]),
+            Some(("human", "[human: 2]".to_string()))
+        );
+        assert_eq!(
+            hunk_attribution_summary(&[&claude, &human]),
+            Some(("mixed", "[mixed: claude 1, human 1]".to_string()))
+        );
+        assert_eq!(hunk_attribution_summary(&[&no_data]), None);
+        assert_eq!(hunk_attribution_summary(&[]), None);
+    }
+
     #[test]
     fn test_parse_diff_args_single_commit() {
         let args = vec!["abc123".to_string()];
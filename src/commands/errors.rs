@@ -0,0 +1,131 @@
+//! `git-ai errors` — inspect the local crash and error ring buffer.
+
+use crate::commands::output::OutputOptions;
+use crate::observability::errors::{
+    ErrorRecord, clear_records, errors_log_path, read_records, upload_unreported_records,
+};
+
+/// Records printed by `errors show` unless `--limit` is given.
+const DEFAULT_SHOW_LIMIT: usize = 20;
+
+pub fn handle_errors(args: &[String]) {
+    match args.first().map(String::as_str) {
+        Some("show") => handle_show(&args[1..]),
+        Some("clear") => {
+            if let Err(e) = clear_records() {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+            eprintln!("Cleared the local error log");
+        }
+        Some("upload") => match upload_unreported_records() {
+            Ok(0) => eprintln!("No unreported errors to upload"),
+            Ok(count) => eprintln!("Submitted {} error record(s) for upload", count),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        },
+        Some("--help" | "-h" | "help") | None => print_help(),
+        Some(other) => {
+            eprintln!("Error: unknown errors subcommand '{}'", other);
+            print_help();
+            std::process::exit(1);
+        }
+    }
+}
+
+fn handle_show(args: &[String]) {
+    let mut json = OutputOptions::current().is_json();
+    let mut limit = DEFAULT_SHOW_LIMIT;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--json" => json = true,
+            "--all" => limit = usize::MAX,
+            "--limit" if i + 1 < args.len() => {
+                limit = match args[i + 1].parse() {
+                    Ok(limit) => limit,
+                    Err(_) => {
+                        eprintln!("Error: --limit expects a number, got '{}'", args[i + 1]);
+                        std::process::exit(1);
+                    }
+                };
+                i += 1;
+            }
+            other => {
+                eprintln!("Error: unknown argument '{}'", other);
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let records = read_records();
+    let shown: Vec<&ErrorRecord> = records.iter().rev().take(limit).collect();
+
+    if json {
+        match serde_json::to_string(&shown) {
+            Ok(output) => println!("{}", output),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if shown.is_empty() {
+        eprintln!("No errors recorded");
+        return;
+    }
+    for record in &shown {
+        println!("{}", format_record(record));
+    }
+    if shown.len() < records.len() {
+        eprintln!(
+            "Showing {} of {} records (use --all to show every record)",
+            shown.len(),
+            records.len()
+        );
+    }
+    if let Some(path) = errors_log_path() {
+        eprintln!("Log file: {}", path.display());
+    }
+}
+
+fn format_record(record: &ErrorRecord) -> String {
+    let mut out = format!(
+        "{}  {}  v{}{}\n  {}",
+        record.timestamp,
+        record.kind.as_str(),
+        record.version,
+        if record.reported { "  (reported)" } else { "" },
+        record.message
+    );
+    if let Some(location) = &record.location {
+        out.push_str(&format!("\n  at {}", location));
+    }
+    for cause in &record.chain {
+        out.push_str(&format!("\n  caused by: {}", cause));
+    }
+    if let Some(context) = &record.context {
+        out.push_str(&format!("\n  context: {}", context));
+    }
+    out
+}
+
+fn print_help() {
+    eprintln!("git-ai errors - Inspect locally recorded crashes and errors");
+    eprintln!();
+    eprintln!("Usage: git-ai errors <subcommand>");
+    eprintln!();
+    eprintln!("Subcommands:");
+    eprintln!("  show       Show recorded errors, newest first");
+    eprintln!("    --limit <n>      Number of records to show (default: 20)");
+    eprintln!("    --all            Show every record");
+    eprintln!("    --json           Output in JSON format");
+    eprintln!("  upload     Submit records not yet sent to crash reporting");
+    eprintln!("  clear      Delete the local error log");
+}
@@ -0,0 +1,534 @@
+//! `git-ai export --format parquet` — write attribution tables for warehouses.
+//!
+//! Two files are written to the output directory:
+//!
+//! - `commits.parquet`: one row per commit with its line attribution totals.
+//! - `checkpoints.parquet`: one row per attestation in a commit's authorship
+//!   note, i.e. per file and checkpoint (session trace, prompt, or known human)
+//!   that wrote lines in that commit.
+//!
+//! Column names, types and order are part of the export's contract: columns are
+//! only ever appended, so warehouse tables loaded from earlier exports keep
+//! working. [`COMMITS_SCHEMA`] and [`CHECKPOINTS_SCHEMA`] are the definitions.
+
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::stats_rollup::rollup_commit_stats;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::log_records::log_records;
+use crate::git::notes_api;
+use crate::git::repository::Repository;
+use crate::workspace_config::effective_workspace_config;
+use parquet::basic::Compression;
+use parquet::data_type::{BoolType, ByteArray, ByteArrayType, Int32Type, Int64Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+pub const COMMITS_FILE: &str = "commits.parquet";
+pub const CHECKPOINTS_FILE: &str = "checkpoints.parquet";
+
+pub const COMMITS_SCHEMA: &str = "
+message git_ai_commits {
+    REQUIRED BYTE_ARRAY commit_sha (UTF8);
+    OPTIONAL BYTE_ARRAY repo_url (UTF8);
+    REQUIRED BYTE_ARRAY author (UTF8);
+    REQUIRED INT64 authored_at (TIMESTAMP(MILLIS,true));
+    REQUIRED INT64 committed_at (TIMESTAMP(MILLIS,true));
+    REQUIRED BOOLEAN has_authorship_note;
+    REQUIRED INT32 ai_additions;
+    REQUIRED INT32 ai_accepted;
+    REQUIRED INT32 human_additions;
+    REQUIRED INT32 unknown_additions;
+    REQUIRED INT32 git_diff_added_lines;
+    REQUIRED INT32 git_diff_deleted_lines;
+}
+";
+
+pub const CHECKPOINTS_SCHEMA: &str = "
+message git_ai_checkpoints {
+    REQUIRED BYTE_ARRAY commit_sha (UTF8);
+    REQUIRED BYTE_ARRAY file_path (UTF8);
+    REQUIRED BYTE_ARRAY attestation_hash (UTF8);
+    REQUIRED BYTE_ARRAY author_kind (UTF8);
+    OPTIONAL BYTE_ARRAY session_id (UTF8);
+    OPTIONAL BYTE_ARRAY trace_id (UTF8);
+    OPTIONAL BYTE_ARRAY tool_use_id (UTF8);
+    OPTIONAL BYTE_ARRAY tool (UTF8);
+    OPTIONAL BYTE_ARRAY model (UTF8);
+    OPTIONAL BYTE_ARRAY human_author (UTF8);
+    REQUIRED INT32 lines;
+}
+";
+
+/// Row of [`COMMITS_SCHEMA`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitRow {
+    pub commit_sha: String,
+    pub repo_url: Option<String>,
+    pub author: String,
+    pub authored_at_ms: i64,
+    pub committed_at_ms: i64,
+    pub has_authorship_note: bool,
+    pub ai_additions: u32,
+    pub ai_accepted: u32,
+    pub human_additions: u32,
+    pub unknown_additions: u32,
+    pub git_diff_added_lines: u32,
+    pub git_diff_deleted_lines: u32,
+}
+
+/// Row of [`CHECKPOINTS_SCHEMA`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CheckpointRow {
+    pub commit_sha: String,
+    pub file_path: String,
+    pub attestation_hash: String,
+    /// `"ai"` or `"human"`.
+    pub author_kind: String,
+    pub session_id: Option<String>,
+    pub trace_id: Option<String>,
+    pub tool_use_id: Option<String>,
+    pub tool: Option<String>,
+    pub model: Option<String>,
+    pub human_author: Option<String>,
+    pub lines: u32,
+}
+
+/// Rows written by [`export_parquet`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExportSummary {
+    pub commits: usize,
+    pub checkpoints: usize,
+}
+
+pub fn handle_export(args: &[String]) {
+    let mut format: Option<String> = None;
+    let mut range: Option<String> = None;
+    let mut output_dir = PathBuf::from(".");
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--format" if i + 1 < args.len() => {
+                format = Some(args[i + 1].clone());
+                i += 2;
+            }
+            "--range" if i + 1 < args.len() => {
+                range = Some(args[i + 1].clone());
+                i += 2;
+            }
+            "--output-dir" | "-o" if i + 1 < args.len() => {
+                output_dir = PathBuf::from(&args[i + 1]);
+                i += 2;
+            }
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other => {
+                eprintln!("Error: unknown option '{}'", other);
+                eprintln!("Run 'git ai export --help' for usage");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    match format.as_deref() {
+        Some("parquet") => {}
+        Some(other) => {
+            eprintln!("Error: unsupported export format '{}'", other);
+            std::process::exit(1);
+        }
+        None => {
+            eprintln!("Error: --format parquet is required");
+            std::process::exit(1);
+        }
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Error: not a git repository ({})", e);
+            std::process::exit(1);
+        }
+    };
+
+    match export_parquet(&repo, range.as_deref(), &output_dir) {
+        Ok(summary) => {
+            eprintln!(
+                "Exported {} commits and {} checkpoint rows to {}",
+                summary.commits,
+                summary.checkpoints,
+                output_dir.display()
+            );
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Write `commits.parquet` and `checkpoints.parquet` for the commits of `range`
+/// (any `git log` revision range, default `HEAD`) into `output_dir`.
+pub fn export_parquet(
+    repo: &Repository,
+    range: Option<&str>,
+    output_dir: &Path,
+) -> Result<ExportSummary, GitAiError> {
+    let (commits, checkpoints) = collect_rows(repo, range.unwrap_or("HEAD"))?;
+    std::fs::create_dir_all(output_dir)?;
+    write_commits(&output_dir.join(COMMITS_FILE), &commits)?;
+    write_checkpoints(&output_dir.join(CHECKPOINTS_FILE), &checkpoints)?;
+    Ok(ExportSummary {
+        commits: commits.len(),
+        checkpoints: checkpoints.len(),
+    })
+}
+
+fn collect_rows(
+    repo: &Repository,
+    range: &str,
+) -> Result<(Vec<CommitRow>, Vec<CheckpointRow>), GitAiError> {
+    let mut commits = list_commits(repo, range)?;
+    let shas: Vec<String> = commits.iter().map(|row| row.commit_sha.clone()).collect();
+    let notes = notes_api::read_notes_batch(repo, &shas)?;
+    let repo_url = crate::repo_url::resolve_repo_url_from_repo(repo);
+
+    // Stats come from the stored per-commit rollups, so repeated exports only
+    // diff commits that are new or whose note changed since the last run.
+    let whitespace = effective_workspace_config(repo).whitespace_mode();
+    let stats = rollup_commit_stats(repo, &shas, &[], whitespace)?;
+
+    let mut checkpoints = Vec::new();
+    for (row, (stats, _cached)) in commits.iter_mut().zip(stats) {
+        let log = notes
+            .get(&row.commit_sha)
+            .and_then(|raw| AuthorshipLog::deserialize_from_string(raw).ok());
+        row.repo_url = repo_url.clone();
+        row.has_authorship_note = log.is_some();
+        row.ai_additions = stats.ai_additions;
+        row.ai_accepted = stats.ai_accepted;
+        row.human_additions = stats.human_additions;
+        row.unknown_additions = stats.unknown_additions;
+        row.git_diff_added_lines = stats.git_diff_added_lines;
+        row.git_diff_deleted_lines = stats.git_diff_deleted_lines;
+        if let Some(log) = &log {
+            checkpoints.extend(checkpoint_rows(&row.commit_sha, log));
+        }
+    }
+    Ok((commits, checkpoints))
+}
+
+/// Commits of `range`, oldest first, with only the fields `git log` provides.
+fn list_commits(repo: &Repository, range: &str) -> Result<Vec<CommitRow>, GitAiError> {
+    let records = log_records(
+        repo,
+        &["%H", "%an <%ae>", "%at", "%ct"],
+        &["--reverse".to_string(), range.to_string(), "--".to_string()],
+    )?;
+    Ok(records
+        .into_iter()
+        .map(|fields| {
+            let [sha, author, authored_at, committed_at] =
+                <[String; 4]>::try_from(fields).unwrap_or_default();
+            CommitRow {
+                commit_sha: sha,
+                author,
+                authored_at_ms: authored_at.parse::<i64>().unwrap_or(0) * 1000,
+                committed_at_ms: committed_at.parse::<i64>().unwrap_or(0) * 1000,
+                ..Default::default()
+            }
+        })
+        .collect())
+}
+
+/// One row per file and attestation of `log`.
+fn checkpoint_rows(commit_sha: &str, log: &AuthorshipLog) -> Vec<CheckpointRow> {
+    let mut rows = Vec::new();
+    for file in &log.attestations {
+        for entry in &file.entries {
+            let mut row = CheckpointRow {
+                commit_sha: commit_sha.to_string(),
+                file_path: file.file_path.clone(),
+                attestation_hash: entry.hash.clone(),
+                author_kind: "ai".to_string(),
+                lines: entry.line_ranges.iter().map(line_range_len).sum(),
+                ..Default::default()
+            };
+            if entry.hash.starts_with("h_") {
+                row.author_kind = "human".to_string();
+                row.human_author = log
+                    .metadata
+                    .humans
+                    .get(&entry.hash)
+                    .map(|human| human.author.clone());
+            } else if entry.hash.starts_with("s_") {
+                let (session_key, trace_id) = match entry.hash.split_once("::") {
+                    Some((session_key, trace_id)) => (session_key, Some(trace_id)),
+                    None => (entry.hash.as_str(), None),
+                };
+                row.session_id = Some(session_key.to_string());
+                row.trace_id = trace_id.map(str::to_string);
+                if let Some(session) = log.metadata.sessions.get(session_key) {
+                    row.tool_use_id = session
+                        .tool_use_id_for_attestation(&entry.hash)
+                        .map(str::to_string);
+                    row.tool = Some(session.agent_id.tool.clone());
+                    row.model = Some(session.agent_id.model.clone());
+                    row.human_author = session.human_author.clone();
+                }
+            } else {
+                row.session_id = Some(entry.hash.clone());
+                if let Some(prompt) = log.metadata.prompts.get(&entry.hash) {
+                    row.tool = Some(prompt.agent_id.tool.clone());
+                    row.model = Some(prompt.agent_id.model.clone());
+                    row.human_author = prompt.human_author.clone();
+                }
+            }
+            rows.push(row);
+        }
+    }
+    rows
+}
+
+fn line_range_len(range: &LineRange) -> u32 {
+    match range {
+        LineRange::Single(_) => 1,
+        LineRange::Range(start, end) => end.saturating_sub(*start) + 1,
+    }
+}
+
+/// Values of one column, in schema order.
+enum Column {
+    Text(Vec<String>),
+    OptionalText(Vec<Option<String>>),
+    Int32(Vec<u32>),
+    Int64(Vec<i64>),
+    Boolean(Vec<bool>),
+}
+
+fn write_commits(path: &Path, rows: &[CommitRow]) -> Result<(), GitAiError> {
+    let column = |f: fn(&CommitRow) -> u32| Column::Int32(rows.iter().map(f).collect());
+    write_table(
+        path,
+        COMMITS_SCHEMA,
+        vec![
+            Column::Text(rows.iter().map(|r| r.commit_sha.clone()).collect()),
+            Column::OptionalText(rows.iter().map(|r| r.repo_url.clone()).collect()),
+            Column::Text(rows.iter().map(|r| r.author.clone()).collect()),
+            Column::Int64(rows.iter().map(|r| r.authored_at_ms).collect()),
+            Column::Int64(rows.iter().map(|r| r.committed_at_ms).collect()),
+            Column::Boolean(rows.iter().map(|r| r.has_authorship_note).collect()),
+            column(|r| r.ai_additions),
+            column(|r| r.ai_accepted),
+            column(|r| r.human_additions),
+            column(|r| r.unknown_additions),
+            column(|r| r.git_diff_added_lines),
+            column(|r| r.git_diff_deleted_lines),
+        ],
+    )
+}
+
+fn write_checkpoints(path: &Path, rows: &[CheckpointRow]) -> Result<(), GitAiError> {
+    let text =
+        |f: fn(&CheckpointRow) -> &String| Column::Text(rows.iter().map(f).cloned().collect());
+    let optional = |f: fn(&CheckpointRow) -> &Option<String>| {
+        Column::OptionalText(rows.iter().map(f).cloned().collect())
+    };
+    write_table(
+        path,
+        CHECKPOINTS_SCHEMA,
+        vec![
+            text(|r| &r.commit_sha),
+            text(|r| &r.file_path),
+            text(|r| &r.attestation_hash),
+            text(|r| &r.author_kind),
+            optional(|r| &r.session_id),
+            optional(|r| &r.trace_id),
+            optional(|r| &r.tool_use_id),
+            optional(|r| &r.tool),
+            optional(|r| &r.model),
+            optional(|r| &r.human_author),
+            Column::Int32(rows.iter().map(|r| r.lines).collect()),
+        ],
+    )
+}
+
+/// Write `columns` as a single row group of a Parquet file with `schema`.
+fn write_table(path: &Path, schema: &str, columns: Vec<Column>) -> Result<(), GitAiError> {
+    let parquet_error = |e: parquet::errors::ParquetError| {
+        GitAiError::Generic(format!("failed to write {}: {}", path.display(), e))
+    };
+    let schema = Arc::new(parse_message_type(schema).map_err(parquet_error)?);
+    let properties = Arc::new(
+        WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .set_created_by(format!("git-ai {}", env!("CARGO_PKG_VERSION")))
+            .build(),
+    );
+    let mut writer = SerializedFileWriter::new(File::create(path)?, schema, properties)
+        .map_err(parquet_error)?;
+    let mut row_group = writer.next_row_group().map_err(parquet_error)?;
+    for column in columns {
+        let Some(mut column_writer) = row_group.next_column().map_err(parquet_error)? else {
+            return Err(GitAiError::Generic(format!(
+                "failed to write {}: more columns than the schema defines",
+                path.display()
+            )));
+        };
+        let written = match column {
+            Column::Text(values) => {
+                let values: Vec<ByteArray> =
+                    values.into_iter().map(|v| v.into_bytes().into()).collect();
+                column_writer
+                    .typed::<ByteArrayType>()
+                    .write_batch(&values, None, None)
+            }
+            Column::OptionalText(values) => {
+                let def_levels: Vec<i16> = values.iter().map(|v| i16::from(v.is_some())).collect();
+                let values: Vec<ByteArray> = values
+                    .into_iter()
+                    .flatten()
+                    .map(|v| v.into_bytes().into())
+                    .collect();
+                column_writer
+                    .typed::<ByteArrayType>()
+                    .write_batch(&values, Some(&def_levels), None)
+            }
+            Column::Int32(values) => {
+                let values: Vec<i32> = values
+                    .into_iter()
+                    .map(|v| i32::try_from(v).unwrap_or(i32::MAX))
+                    .collect();
+                column_writer
+                    .typed::<Int32Type>()
+                    .write_batch(&values, None, None)
+            }
+            Column::Int64(values) => column_writer
+                .typed::<Int64Type>()
+                .write_batch(&values, None, None),
+            Column::Boolean(values) => column_writer
+                .typed::<BoolType>()
+                .write_batch(&values, None, None),
+        };
+        written.map_err(parquet_error)?;
+        column_writer.close().map_err(parquet_error)?;
+    }
+    row_group.close().map_err(parquet_error)?;
+    writer.close().map_err(parquet_error)?;
+    Ok(())
+}
+
+fn print_help() {
+    eprintln!("git-ai export - Export attribution tables for data warehouses");
+    eprintln!();
+    eprintln!("Usage: git-ai export --format parquet [--range <range>] [--output-dir <dir>]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --format parquet    Output format (only parquet is supported)");
+    eprintln!("  --range <range>     Commits to export, e.g. v1.0..v1.1 (default: HEAD)");
+    eprintln!("  --output-dir <dir>  Directory for the exported files (default: .)");
+    eprintln!("  --help              Show this help");
+    eprintln!();
+    eprintln!("Writes {} (one row per commit) and", COMMITS_FILE);
+    eprintln!(
+        "{} (one row per file and checkpoint in each",
+        CHECKPOINTS_FILE
+    );
+    eprintln!("commit's authorship note). Columns are only ever appended.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::authorship_log::{HumanRecord, PromptRecord};
+    use crate::authorship::authorship_log_serialization::{AttestationEntry, FileAttestation};
+    use crate::authorship::working_log::AgentId;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    fn sample_log() -> AuthorshipLog {
+        let mut log = AuthorshipLog::new();
+        log.metadata.prompts.insert(
+            "abcd1234abcd1234".to_string(),
+            PromptRecord {
+                agent_id: AgentId {
+                    tool: "cursor".to_string(),
+                    id: "session".to_string(),
+                    model: "gpt-5".to_string(),
+                },
+                human_author: Some("Dev <dev@example.com>".to_string()),
+                messages_url: None,
+                total_additions: 3,
+                total_deletions: 0,
+                accepted_lines: 3,
+                overriden_lines: 0,
+                custom_attributes: None,
+            },
+        );
+        log.metadata.humans.insert(
+            "h_0123456789abcd".to_string(),
+            HumanRecord {
+                author: "Dev <dev@example.com>".to_string(),
+            },
+        );
+        let mut file = FileAttestation::new("src/lib.rs".to_string());
+        file.add_entry(AttestationEntry::new(
+            "abcd1234abcd1234".to_string(),
+            vec![LineRange::Range(1, 2), LineRange::Single(5)],
+        ));
+        file.add_entry(AttestationEntry::new(
+            "h_0123456789abcd".to_string(),
+            vec![LineRange::Single(3)],
+        ));
+        log.attestations.push(file);
+        log
+    }
+
+    #[test]
+    fn checkpoint_rows_resolve_prompts_and_humans() {
+        let rows = checkpoint_rows("c0ffee", &sample_log());
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].author_kind, "ai");
+        assert_eq!(rows[0].tool.as_deref(), Some("cursor"));
+        assert_eq!(rows[0].model.as_deref(), Some("gpt-5"));
+        assert_eq!(rows[0].lines, 3);
+        assert_eq!(rows[1].author_kind, "human");
+        assert_eq!(
+            rows[1].human_author.as_deref(),
+            Some("Dev <dev@example.com>")
+        );
+        assert_eq!(rows[1].session_id, None);
+    }
+
+    #[test]
+    fn parquet_tables_match_their_schemas() {
+        let dir = tempfile::tempdir().unwrap();
+        let commits = vec![CommitRow {
+            commit_sha: "c0ffee".to_string(),
+            author: "Dev <dev@example.com>".to_string(),
+            has_authorship_note: true,
+            ai_additions: 3,
+            ..Default::default()
+        }];
+        let checkpoints = checkpoint_rows("c0ffee", &sample_log());
+        write_commits(&dir.path().join(COMMITS_FILE), &commits).unwrap();
+        write_checkpoints(&dir.path().join(CHECKPOINTS_FILE), &checkpoints).unwrap();
+
+        for (file, rows) in [(COMMITS_FILE, 1), (CHECKPOINTS_FILE, 2)] {
+            let reader =
+                SerializedFileReader::new(File::open(dir.path().join(file)).unwrap()).unwrap();
+            assert_eq!(
+                reader.metadata().file_metadata().num_rows(),
+                rows,
+                "{}",
+                file
+            );
+        }
+    }
+}
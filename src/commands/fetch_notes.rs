@@ -1,3 +1,4 @@
+use crate::commands::output::OutputOptions;
 use crate::config::NotesBackendKind;
 use crate::error::GitAiError;
 use crate::git::find_repository;
@@ -15,7 +16,7 @@ struct FetchNotesJsonOutput {
 
 pub fn handle_fetch_notes(args: &[String]) {
     let mut remote: Option<String> = None;
-    let mut json_output = false;
+    let mut json_output = OutputOptions::current().is_json();
 
     let mut i = 0;
     while i < args.len() {
@@ -11,6 +11,11 @@ const MAX_BATCH_SIZE: usize = 1000;
 
 /// Handle the flush-metrics-db command
 pub fn handle_flush_metrics_db(_args: &[String]) {
+    if !crate::config::Config::fresh().telemetry().metrics.enabled {
+        eprintln!("flush-metrics-db: skipping (usage metrics telemetry is off)");
+        return;
+    }
+
     let context = ApiContext::new(None);
     let api_base_url = context.base_url.clone();
     let client = ApiClient::new(context);
@@ -20,6 +25,20 @@ pub fn handle_flush_metrics_db(_args: &[String]) {
         return;
     }
 
+    // Serialize with the daemon and other manual flushes so a batch is never
+    // uploaded twice.
+    let _flush_lock = match MetricsDatabase::try_acquire_flush_lock() {
+        Ok(Some(lock)) => lock,
+        Ok(None) => {
+            eprintln!("flush-metrics-db: skipping (another flush is in progress)");
+            return;
+        }
+        Err(e) => {
+            eprintln!("flush-metrics-db: failed to acquire flush lock: {}", e);
+            return;
+        }
+    };
+
     // Get database connection
     let db = match MetricsDatabase::global() {
         Ok(db) => db,
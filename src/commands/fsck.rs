@@ -0,0 +1,673 @@
+//! `git-ai fsck` — validate locally stored attribution and prune corrupt entries.
+//!
+//! Every note on `refs/notes/ai` must parse, attest only files that exist at its
+//! commit, keep its line ranges inside those files and reference prompts or
+//! sessions recorded in its own metadata. Known-human (`h_`) entries are not
+//! reference-checked: cherry-picks legitimately carry them without a record.
+//! Every working log under `.git/ai/working_logs` must parse, and its checkpoint
+//! entries and INITIAL snapshot must point at blobs in the log's `blobs/`.
+//!
+//! With `--prune`, unparseable notes are removed and other notes are rewritten
+//! without their corrupt entries. Working logs drop unparseable checkpoints and
+//! entries whose blobs are gone; an unparseable INITIAL file is deleted.
+
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::authorship_log_serialization::{AuthorshipLog, AuthorshipMetadata};
+use crate::authorship::working_log::Checkpoint;
+use crate::commands::notes_gc::commit_times;
+use crate::commands::notes_migrate::list_notes;
+use crate::commands::output::OutputOptions;
+use crate::config::{Config, NotesBackendKind};
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::notes_api;
+use crate::git::repo_storage::InitialAttributions;
+use crate::git::repository::{Repository, batch_read_paths_at_treeishes, exec_git_stdin};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Notes whose attested files are read from git in a single batch.
+const NOTES_PER_BATCH: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FsckIssueKind {
+    /// The note does not deserialize as an authorship log.
+    UnparseableNote,
+    /// The note attests a file that does not exist at its commit.
+    MissingFile,
+    /// A line range is empty, starts at 0 or runs past the end of the file.
+    InvalidRange,
+    /// An entry's hash is not among the note's prompts or sessions.
+    UnresolvedPrompt,
+    /// A checkpoint line or the INITIAL file does not deserialize.
+    UnparseableWorkingLog,
+    /// A checkpoint entry or INITIAL snapshot points at a missing blob.
+    MissingBlob,
+}
+
+impl FsckIssueKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::UnparseableNote => "unparseable-note",
+            Self::MissingFile => "missing-file",
+            Self::InvalidRange => "invalid-range",
+            Self::UnresolvedPrompt => "unresolved-prompt",
+            Self::UnparseableWorkingLog => "unparseable-working-log",
+            Self::MissingBlob => "missing-blob",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FsckIssue {
+    pub kind: FsckIssueKind,
+    /// The annotated commit for note issues, the base commit for working logs.
+    pub commit: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+    pub detail: String,
+}
+
+impl FsckIssue {
+    fn new(kind: FsckIssueKind, commit: &str, file_path: Option<&str>, detail: String) -> Self {
+        Self {
+            kind,
+            commit: commit.to_string(),
+            file_path: file_path.map(str::to_string),
+            detail,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct FsckReport {
+    pub notes_checked: usize,
+    /// Notes on commits missing locally, checked without file and range checks.
+    pub notes_without_commit: usize,
+    pub working_logs_checked: usize,
+    pub issues: Vec<FsckIssue>,
+    pub notes_removed: usize,
+    pub notes_rewritten: usize,
+    pub working_logs_rewritten: usize,
+}
+
+pub fn handle_fsck(args: &[String]) {
+    let mut prune = false;
+    let mut json = OutputOptions::current().is_json();
+
+    for arg in args {
+        match arg.as_str() {
+            "--prune" => prune = true,
+            "--json" => json = true,
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other => {
+                eprintln!("error: unknown option '{}'", other);
+                eprintln!("Run 'git-ai fsck --help' for usage");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("error: not a git repository ({})", e);
+            std::process::exit(1);
+        }
+    };
+
+    let report = match run_fsck(&repo, prune) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    if json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(s) => println!("{}", s),
+            Err(e) => {
+                eprintln!("error serializing JSON: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        print_report(&report, prune);
+    }
+
+    if !prune && !report.issues.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+pub fn run_fsck(repo: &Repository, prune: bool) -> Result<FsckReport, GitAiError> {
+    let mut report = FsckReport::default();
+    if Config::get().notes_backend_kind() == NotesBackendKind::GitNotes {
+        check_notes(repo, prune, &mut report)?;
+    } else {
+        eprintln!("Skipping note checks: notes are stored on the HTTP backend.");
+    }
+    check_working_logs(repo, prune, &mut report)?;
+    Ok(report)
+}
+
+fn check_notes(repo: &Repository, prune: bool, report: &mut FsckReport) -> Result<(), GitAiError> {
+    let commits: Vec<String> = list_notes(repo)?
+        .into_iter()
+        .map(|(_, commit)| commit)
+        .collect();
+    let present = commit_times(repo, &commits)?;
+
+    let mut removed: Vec<String> = Vec::new();
+    let mut rewritten: Vec<(String, String)> = Vec::new();
+    for chunk in commits.chunks(NOTES_PER_BATCH) {
+        let contents = notes_api::read_notes_batch(repo, chunk)?;
+        let mut parsed: Vec<(&String, AuthorshipLog)> = Vec::new();
+        for commit in chunk {
+            let Some(content) = contents.get(commit) else {
+                continue;
+            };
+            report.notes_checked += 1;
+            match AuthorshipLog::deserialize_from_string(content) {
+                Ok(log) => parsed.push((commit, log)),
+                Err(e) => {
+                    report.issues.push(FsckIssue::new(
+                        FsckIssueKind::UnparseableNote,
+                        commit,
+                        None,
+                        e.to_string(),
+                    ));
+                    removed.push(commit.clone());
+                }
+            }
+        }
+
+        let requests: Vec<(String, String)> = parsed
+            .iter()
+            .filter(|(commit, _)| present.contains_key(*commit))
+            .flat_map(|(commit, log)| {
+                log.attestations
+                    .iter()
+                    .map(|file| ((*commit).clone(), file.file_path.clone()))
+            })
+            .collect();
+        let files = batch_read_paths_at_treeishes(repo, &requests)?;
+
+        for (commit, log) in parsed {
+            let line_counts: Option<HashMap<String, Option<u32>>> = if present.contains_key(commit)
+            {
+                Some(
+                    log.attestations
+                        .iter()
+                        .map(|file| {
+                            let content = files.get(&(commit.clone(), file.file_path.clone()));
+                            (
+                                file.file_path.clone(),
+                                content.map(|c| c.lines().count() as u32),
+                            )
+                        })
+                        .collect(),
+                )
+            } else {
+                report.notes_without_commit += 1;
+                None
+            };
+            let (issues, repaired) = check_note(commit, log, line_counts.as_ref());
+            if issues.is_empty() {
+                continue;
+            }
+            report.issues.extend(issues);
+            let content = repaired.serialize_to_string().map_err(|_| {
+                GitAiError::Generic(format!("failed to serialize note for {}", commit))
+            })?;
+            rewritten.push((commit.clone(), content));
+        }
+    }
+
+    if prune {
+        remove_notes(repo, &removed)?;
+        notes_api::write_notes_batch(repo, &rewritten)?;
+        report.notes_removed = removed.len();
+        report.notes_rewritten = rewritten.len();
+    }
+    Ok(())
+}
+
+/// Check a parsed note against the line counts of its attested files at its
+/// commit (`None` for a file that is absent). Without `line_counts`, because
+/// the commit is not available locally, only references and range shapes are
+/// checked. Returns the issues and the note with the offending parts dropped.
+pub fn check_note(
+    commit: &str,
+    mut log: AuthorshipLog,
+    line_counts: Option<&HashMap<String, Option<u32>>>,
+) -> (Vec<FsckIssue>, AuthorshipLog) {
+    let mut issues = Vec::new();
+    let metadata = &log.metadata;
+    log.attestations.retain_mut(|file| {
+        let path = file.file_path.as_str();
+        let line_count = match line_counts {
+            Some(counts) => match counts.get(path).copied().flatten() {
+                Some(count) => Some(count),
+                None => {
+                    issues.push(FsckIssue::new(
+                        FsckIssueKind::MissingFile,
+                        commit,
+                        Some(path),
+                        "file does not exist at this commit".to_string(),
+                    ));
+                    return false;
+                }
+            },
+            None => None,
+        };
+
+        file.entries.retain_mut(|entry| {
+            if !prompt_reference_resolves(metadata, &entry.hash) {
+                issues.push(FsckIssue::new(
+                    FsckIssueKind::UnresolvedPrompt,
+                    commit,
+                    Some(path),
+                    format!("{} is not recorded in the note's metadata", entry.hash),
+                ));
+                return false;
+            }
+            entry.line_ranges.retain(|range| {
+                let (start, end) = match range {
+                    LineRange::Single(line) => (*line, *line),
+                    LineRange::Range(start, end) => (*start, *end),
+                };
+                let fits = line_count.is_none_or(|count| end <= count);
+                if start >= 1 && start <= end && fits {
+                    return true;
+                }
+                let detail = match line_count {
+                    Some(count) if start >= 1 && start <= end => {
+                        format!("lines {}-{} exceed the file's {} lines", start, end, count)
+                    }
+                    _ => format!("invalid line range {}-{}", start, end),
+                };
+                issues.push(FsckIssue::new(
+                    FsckIssueKind::InvalidRange,
+                    commit,
+                    Some(path),
+                    detail,
+                ));
+                false
+            });
+            !entry.line_ranges.is_empty()
+        });
+        !file.entries.is_empty()
+    });
+    (issues, log)
+}
+
+fn prompt_reference_resolves(metadata: &AuthorshipMetadata, hash: &str) -> bool {
+    if hash.starts_with("h_") {
+        true
+    } else if hash.starts_with("s_") {
+        let session_key = hash.split("::").next().unwrap_or(hash);
+        metadata.sessions.contains_key(session_key)
+    } else {
+        metadata.prompts.contains_key(hash)
+    }
+}
+
+fn remove_notes(repo: &Repository, commits: &[String]) -> Result<(), GitAiError> {
+    if commits.is_empty() {
+        return Ok(());
+    }
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "notes".to_string(),
+        "--ref=ai".to_string(),
+        "remove".to_string(),
+        "--ignore-missing".to_string(),
+        "--stdin".to_string(),
+    ]);
+    let stdin = commits.join("\n") + "\n";
+    exec_git_stdin(&args, stdin.as_bytes())?;
+    Ok(())
+}
+
+fn check_working_logs(
+    repo: &Repository,
+    prune: bool,
+    report: &mut FsckReport,
+) -> Result<(), GitAiError> {
+    let Ok(entries) = fs::read_dir(&repo.storage.working_logs) else {
+        return Ok(());
+    };
+    let mut dirs: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    for dir in dirs {
+        let Some(base_commit) = dir.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        // `old-<sha>` directories are retention copies of committed logs.
+        if base_commit.starts_with("old-") {
+            continue;
+        }
+        report.working_logs_checked += 1;
+        let issues = check_working_log(&dir, base_commit, prune)?;
+        if prune && !issues.is_empty() {
+            report.working_logs_rewritten += 1;
+        }
+        report.issues.extend(issues);
+    }
+    Ok(())
+}
+
+/// Check one working log directory, rewriting it without the corrupt parts
+/// when `prune` is set.
+pub fn check_working_log(
+    dir: &Path,
+    base_commit: &str,
+    prune: bool,
+) -> Result<Vec<FsckIssue>, GitAiError> {
+    let mut issues = Vec::new();
+    let blob_exists = |sha: &str| dir.join("blobs").join(sha).is_file();
+
+    let checkpoints_file = dir.join("checkpoints.jsonl");
+    let mut kept_lines: Vec<String> = Vec::new();
+    let mut checkpoints_changed = false;
+    for (index, line) in read_optional(&checkpoints_file)?
+        .unwrap_or_default()
+        .lines()
+        .enumerate()
+    {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut checkpoint: Checkpoint = match serde_json::from_str(line) {
+            Ok(checkpoint) => checkpoint,
+            Err(e) => {
+                issues.push(FsckIssue::new(
+                    FsckIssueKind::UnparseableWorkingLog,
+                    base_commit,
+                    None,
+                    format!("checkpoints.jsonl line {}: {}", index + 1, e),
+                ));
+                checkpoints_changed = true;
+                continue;
+            }
+        };
+        let entries_before = checkpoint.entries.len();
+        checkpoint.entries.retain(|entry| {
+            if entry.blob_sha.is_empty() || blob_exists(&entry.blob_sha) {
+                return true;
+            }
+            issues.push(FsckIssue::new(
+                FsckIssueKind::MissingBlob,
+                base_commit,
+                Some(entry.file.as_str()),
+                format!("checkpoint blob {} is missing", entry.blob_sha),
+            ));
+            false
+        });
+        if checkpoint.entries.len() == entries_before {
+            kept_lines.push(line.to_string());
+        } else {
+            checkpoints_changed = true;
+            kept_lines.push(serde_json::to_string(&checkpoint)?);
+        }
+    }
+
+    let initial_file = dir.join("INITIAL");
+    // `Some(None)` deletes INITIAL, `Some(Some(json))` rewrites it.
+    let mut initial_repair: Option<Option<String>> = None;
+    if let Some(contents) = read_optional(&initial_file)? {
+        match serde_json::from_str::<InitialAttributions>(&contents) {
+            Ok(mut initial) => {
+                let mut missing: Vec<(String, String)> = initial
+                    .file_blobs
+                    .iter()
+                    .filter(|(_, sha)| !blob_exists(sha))
+                    .map(|(file, sha)| (file.clone(), sha.clone()))
+                    .collect();
+                missing.sort();
+                for (file, sha) in &missing {
+                    initial.file_blobs.remove(file);
+                    issues.push(FsckIssue::new(
+                        FsckIssueKind::MissingBlob,
+                        base_commit,
+                        Some(file.as_str()),
+                        format!("INITIAL blob {} is missing", sha),
+                    ));
+                }
+                if !missing.is_empty() {
+                    initial_repair = Some(Some(serde_json::to_string_pretty(&initial)?));
+                }
+            }
+            Err(e) => {
+                issues.push(FsckIssue::new(
+                    FsckIssueKind::UnparseableWorkingLog,
+                    base_commit,
+                    None,
+                    format!("INITIAL: {}", e),
+                ));
+                initial_repair = Some(None);
+            }
+        }
+    }
+
+    if prune {
+        if checkpoints_changed {
+            let mut contents = kept_lines.join("\n");
+            if !contents.is_empty() {
+                contents.push('\n');
+            }
+            fs::write(&checkpoints_file, contents)?;
+        }
+        match initial_repair {
+            Some(Some(json)) => fs::write(&initial_file, json)?,
+            Some(None) => fs::remove_file(&initial_file)?,
+            None => {}
+        }
+    }
+    Ok(issues)
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>, GitAiError> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(Some(String::from_utf8_lossy(&bytes).into_owned())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn short(sha: &str) -> &str {
+    &sha[..sha.len().min(8)]
+}
+
+fn print_report(report: &FsckReport, prune: bool) {
+    for issue in &report.issues {
+        let file = issue
+            .file_path
+            .as_deref()
+            .map(|path| format!("{}: ", path))
+            .unwrap_or_default();
+        println!(
+            "{}  {}  {}{}",
+            issue.kind.as_str(),
+            short(&issue.commit),
+            file,
+            issue.detail
+        );
+    }
+    eprintln!(
+        "Checked {} note(s) and {} working log(s): {} issue(s).",
+        report.notes_checked,
+        report.working_logs_checked,
+        report.issues.len()
+    );
+    if report.notes_without_commit > 0 {
+        eprintln!(
+            "  {} note(s) on commits missing locally were checked without their files.",
+            report.notes_without_commit
+        );
+    }
+    if prune {
+        eprintln!(
+            "Removed {} note(s); rewrote {} note(s) and {} working log(s).",
+            report.notes_removed, report.notes_rewritten, report.working_logs_rewritten
+        );
+    } else if !report.issues.is_empty() {
+        eprintln!("Run 'git-ai fsck --prune' to drop the corrupt entries.");
+    }
+}
+
+fn print_help() {
+    eprintln!("git-ai fsck - Validate stored AI attribution");
+    eprintln!();
+    eprintln!("Usage: git-ai fsck [--prune] [--json]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --prune      Remove unparseable notes and drop corrupt entries");
+    eprintln!("  --json       Output the report as JSON");
+    eprintln!("  -h, --help   Show this help");
+    eprintln!();
+    eprintln!("Checks that authorship notes parse, attest files that exist at their commit,");
+    eprintln!("keep line ranges inside those files and reference recorded prompts, and that");
+    eprintln!("working logs parse and point at existing blobs. Exits 1 when issues are found");
+    eprintln!("and --prune was not given.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::authorship_log_serialization::{AttestationEntry, FileAttestation};
+
+    fn note() -> AuthorshipLog {
+        let mut log = AuthorshipLog::new();
+        let mut file = FileAttestation::new("src/lib.rs".to_string());
+        file.add_entry(AttestationEntry::new(
+            "aaaaaaaaaaaaaaaa".to_string(),
+            vec![LineRange::Range(1, 3), LineRange::Range(8, 12)],
+        ));
+        file.add_entry(AttestationEntry::new(
+            "bbbbbbbbbbbbbbbb".to_string(),
+            vec![LineRange::Single(4)],
+        ));
+        log.attestations.push(file);
+        log.attestations
+            .push(FileAttestation::new("src/gone.rs".to_string()));
+        log.attestations[1].add_entry(AttestationEntry::new(
+            "aaaaaaaaaaaaaaaa".to_string(),
+            vec![LineRange::Single(1)],
+        ));
+        log.metadata.prompts.insert(
+            "aaaaaaaaaaaaaaaa".to_string(),
+            crate::authorship::authorship_log::PromptRecord {
+                agent_id: crate::authorship::working_log::AgentId {
+                    tool: "claude".to_string(),
+                    id: "session".to_string(),
+                    model: "sonnet".to_string(),
+                },
+                human_author: None,
+                total_additions: 0,
+                total_deletions: 0,
+                accepted_lines: 0,
+                overriden_lines: 0,
+                custom_attributes: None,
+                messages_url: None,
+            },
+        );
+        log
+    }
+
+    #[test]
+    fn test_check_note_reports_and_drops_corrupt_entries() {
+        let line_counts = HashMap::from([
+            ("src/lib.rs".to_string(), Some(10)),
+            ("src/gone.rs".to_string(), None),
+        ]);
+        let (issues, repaired) = check_note("c0ffee", note(), Some(&line_counts));
+
+        let kinds: Vec<FsckIssueKind> = issues.iter().map(|issue| issue.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                FsckIssueKind::InvalidRange,
+                FsckIssueKind::UnresolvedPrompt,
+                FsckIssueKind::MissingFile,
+            ]
+        );
+        assert_eq!(issues[0].detail, "lines 8-12 exceed the file's 10 lines");
+        assert_eq!(repaired.attestations.len(), 1);
+        assert_eq!(repaired.attestations[0].entries.len(), 1);
+        assert_eq!(
+            repaired.attestations[0].entries[0].line_ranges,
+            vec![LineRange::Range(1, 3)]
+        );
+
+        // Without the commit only references and range shapes can be checked.
+        let (issues, repaired) = check_note("c0ffee", note(), None);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, FsckIssueKind::UnresolvedPrompt);
+        assert_eq!(repaired.attestations.len(), 2);
+    }
+
+    #[test]
+    fn test_check_working_log_prunes_missing_blobs_and_bad_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("blobs")).unwrap();
+        fs::write(dir.path().join("blobs").join("present"), "content").unwrap();
+        let checkpoint = |blob: &str| {
+            serde_json::json!({
+                "kind": "AiAgent",
+                "diff": "",
+                "author": "dev",
+                "entries": [
+                    {"file": "a.rs", "blob_sha": "present"},
+                    {"file": "b.rs", "blob_sha": blob}
+                ],
+                "timestamp": 1,
+                "agent_id": null
+            })
+            .to_string()
+        };
+        fs::write(
+            dir.path().join("checkpoints.jsonl"),
+            format!(
+                "{}\n{{not json\n{}\n",
+                checkpoint("present"),
+                checkpoint("missing")
+            ),
+        )
+        .unwrap();
+        fs::write(dir.path().join("INITIAL"), "{").unwrap();
+
+        let issues = check_working_log(dir.path(), "base", false).unwrap();
+        let kinds: Vec<FsckIssueKind> = issues.iter().map(|issue| issue.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                FsckIssueKind::UnparseableWorkingLog,
+                FsckIssueKind::MissingBlob,
+                FsckIssueKind::UnparseableWorkingLog,
+            ]
+        );
+        assert_eq!(issues[1].file_path.as_deref(), Some("b.rs"));
+
+        check_working_log(dir.path(), "base", true).unwrap();
+        assert!(!dir.path().join("INITIAL").exists());
+        let contents = fs::read_to_string(dir.path().join("checkpoints.jsonl")).unwrap();
+        assert_eq!(contents.lines().count(), 2);
+        assert!(
+            check_working_log(dir.path(), "base", false)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
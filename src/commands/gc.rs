@@ -0,0 +1,325 @@
+//! `git-ai gc` — reclaim space in `.git/ai`.
+//!
+//! Removes working logs that can no longer contribute to a note:
+//! - archived `old-<sha>` logs, which are otherwise kept for a week after
+//!   their commit;
+//! - logs whose base commit is not the HEAD of any worktree and that have not
+//!   been written for `--max-age-days`, left behind by resets and branch
+//!   switches outside git-ai's view;
+//! - attribution saved for stash entries that are no longer in `git stash list`.
+//!
+//! Authorship notes are untouched; `git-ai notes-gc` prunes those.
+
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::repo_storage::RepoStorage;
+use crate::git::repository::{Repository, exec_git};
+use crate::git::storage_usage::{format_size, path_size};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Working logs untouched for this long, and not at any worktree HEAD, are stale.
+const DEFAULT_MAX_AGE_DAYS: u64 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GcReason {
+    ArchivedWorkingLog,
+    StaleWorkingLog,
+    DroppedStash,
+}
+
+impl GcReason {
+    fn label(self) -> &'static str {
+        match self {
+            GcReason::ArchivedWorkingLog => "archived working log",
+            GcReason::StaleWorkingLog => "stale working log",
+            GcReason::DroppedStash => "dropped stash",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GcCandidate {
+    pub path: PathBuf,
+    pub reason: GcReason,
+    pub bytes: u64,
+}
+
+pub fn handle_gc(args: &[String]) {
+    let mut dry_run = false;
+    let mut max_age_days = DEFAULT_MAX_AGE_DAYS;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--dry-run" | "-n" => dry_run = true,
+            "--max-age-days" if i + 1 < args.len() => {
+                max_age_days = match args[i + 1].parse() {
+                    Ok(days) => days,
+                    Err(_) => {
+                        eprintln!(
+                            "error: --max-age-days expects a number, got '{}'",
+                            args[i + 1]
+                        );
+                        std::process::exit(1);
+                    }
+                };
+                i += 1;
+            }
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other => {
+                eprintln!("error: unknown option '{}'", other);
+                eprintln!("Run 'git-ai gc --help' for usage");
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("error: not a git repository ({})", e);
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(e) = run_gc(&repo, max_age_days, dry_run) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run_gc(repo: &Repository, max_age_days: u64, dry_run: bool) -> Result<(), GitAiError> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let cutoff = now.saturating_sub(max_age_days.saturating_mul(86_400));
+    let candidates = plan_storage_gc(
+        &repo.storage,
+        &worktree_heads(repo)?,
+        &live_stashes(repo)?,
+        cutoff,
+    );
+
+    if candidates.is_empty() {
+        eprintln!("Nothing to prune in {}.", repo.storage.ai_dir.display());
+        return Ok(());
+    }
+
+    let mut freed = 0u64;
+    for candidate in &candidates {
+        let name = candidate
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        eprintln!(
+            "{} {} {} ({})",
+            if dry_run { "would prune" } else { "pruned" },
+            candidate.reason.label(),
+            name,
+            format_size(candidate.bytes)
+        );
+        if dry_run {
+            freed += candidate.bytes;
+            continue;
+        }
+        let removed = if candidate.path.is_dir() {
+            fs::remove_dir_all(&candidate.path)
+        } else {
+            fs::remove_file(&candidate.path)
+        };
+        match removed {
+            Ok(()) => freed += candidate.bytes,
+            Err(e) => eprintln!("warning: could not remove {}: {}", name, e),
+        }
+    }
+    eprintln!(
+        "{} {}.",
+        if dry_run { "Would free" } else { "Freed" },
+        format_size(freed)
+    );
+    Ok(())
+}
+
+/// Everything `git-ai gc` would remove from `storage`. `cutoff` is the Unix
+/// time before which an unreferenced working log counts as stale.
+pub fn plan_storage_gc(
+    storage: &RepoStorage,
+    worktree_heads: &HashSet<String>,
+    live_stashes: &HashSet<String>,
+    cutoff: u64,
+) -> Vec<GcCandidate> {
+    let mut candidates = Vec::new();
+
+    for path in sorted_entries(&storage.working_logs) {
+        if !path.is_dir() {
+            continue;
+        }
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let reason = if name.starts_with("old-") {
+            GcReason::ArchivedWorkingLog
+        } else if !worktree_heads.contains(&name) && last_modified(&path) < cutoff {
+            GcReason::StaleWorkingLog
+        } else {
+            continue;
+        };
+        candidates.push(GcCandidate {
+            bytes: path_size(&path),
+            path,
+            reason,
+        });
+    }
+
+    // Pre-v2 stash attribution is cleaned up on the next stash operation anyway.
+    let legacy_stashes = storage.ai_dir.join("stashes");
+    if legacy_stashes.exists() {
+        candidates.push(GcCandidate {
+            bytes: path_size(&legacy_stashes),
+            path: legacy_stashes,
+            reason: GcReason::DroppedStash,
+        });
+    }
+    for path in sorted_entries(&storage.ai_dir.join("stashes_v2")) {
+        let is_live = path
+            .file_name()
+            .is_some_and(|name| live_stashes.contains(name.to_string_lossy().as_ref()));
+        if !is_live {
+            candidates.push(GcCandidate {
+                bytes: path_size(&path),
+                path,
+                reason: GcReason::DroppedStash,
+            });
+        }
+    }
+
+    candidates
+}
+
+fn sorted_entries(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+        .unwrap_or_default();
+    paths.sort();
+    paths
+}
+
+/// Latest modification time of `dir` or any direct child, in Unix seconds.
+/// Appending a checkpoint only touches `checkpoints.jsonl`, not the directory.
+fn last_modified(dir: &Path) -> u64 {
+    let mtime = |path: &Path| {
+        fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    };
+    sorted_entries(dir)
+        .iter()
+        .map(|path| mtime(path))
+        .fold(mtime(dir), u64::max)
+}
+
+/// HEAD commit of every worktree of `repo`.
+fn worktree_heads(repo: &Repository) -> Result<HashSet<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend(["worktree", "list", "--porcelain"].map(String::from));
+    let output = exec_git(&args)?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.strip_prefix("HEAD "))
+        .map(|sha| sha.trim().to_string())
+        .collect())
+}
+
+/// Commits of every entry in `git stash list`.
+fn live_stashes(repo: &Repository) -> Result<HashSet<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend(["stash", "list", "--format=%H"].map(String::from));
+    let output = exec_git(&args)?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|sha| sha.trim().to_string())
+        .filter(|sha| !sha.is_empty())
+        .collect())
+}
+
+fn print_help() {
+    eprintln!("git-ai gc - Reclaim space in .git/ai");
+    eprintln!();
+    eprintln!("Usage: git-ai gc [--dry-run] [--max-age-days <N>]");
+    eprintln!();
+    eprintln!("Removes archived working logs, working logs that are not at any");
+    eprintln!("worktree's HEAD and have not been written for N days, and attribution");
+    eprintln!("saved for stash entries that no longer exist.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  -n, --dry-run          List what would be removed without removing it");
+    eprintln!(
+        "  --max-age-days <N>     Age after which unreferenced working logs are stale (default: {})",
+        DEFAULT_MAX_AGE_DAYS
+    );
+    eprintln!("  -h, --help             Show this help message");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_plan_storage_gc_keeps_live_logs_and_stashes() {
+        let tmp = TempDir::new().unwrap();
+        let storage = RepoStorage::for_repo_path(&tmp.path().join("repo"), tmp.path()).unwrap();
+        for name in ["head1", "orphan", "old-done"] {
+            let dir = storage.working_logs.join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("checkpoints.jsonl"), "{}\n").unwrap();
+        }
+        for sha in ["kept", "dropped"] {
+            let dir = storage.ai_dir.join("stashes_v2").join(sha);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("metadata.json"), "{}").unwrap();
+        }
+        let heads = HashSet::from(["head1".to_string()]);
+        let stashes = HashSet::from(["kept".to_string()]);
+
+        // Everything was just written, so nothing is stale yet.
+        let plan = plan_storage_gc(&storage, &heads, &stashes, 0);
+        let pruned: Vec<(String, GcReason)> = plan
+            .iter()
+            .map(|c| {
+                (
+                    c.path.file_name().unwrap().to_string_lossy().into_owned(),
+                    c.reason,
+                )
+            })
+            .collect();
+        assert_eq!(
+            pruned,
+            vec![
+                ("old-done".to_string(), GcReason::ArchivedWorkingLog),
+                ("dropped".to_string(), GcReason::DroppedStash),
+            ]
+        );
+
+        let plan = plan_storage_gc(&storage, &heads, &stashes, u64::MAX);
+        assert!(
+            plan.iter()
+                .any(|c| c.path.ends_with("orphan") && c.reason == GcReason::StaleWorkingLog)
+        );
+        assert!(!plan.iter().any(|c| c.path.ends_with("head1")));
+        assert!(plan.iter().all(|c| c.bytes > 0));
+    }
+}
@@ -1,18 +1,26 @@
+use crate::authorship::coverage;
 use crate::authorship::ignore::effective_ignore_patterns;
 use crate::authorship::internal_db::InternalDatabase;
 use crate::authorship::range_authorship;
-use crate::authorship::stats::stats_command;
+use crate::authorship::stats::{
+    CommitStats, StatsFormat, range_report_sections, stats_command, stats_trailer_command,
+    write_stats_report_markdown, write_stats_to_terminal,
+};
+use crate::authorship::stats_rollup;
 use crate::commands;
+use crate::commands::output::OutputOptions;
 use crate::config;
 use crate::daemon::ControlRequest;
+use crate::error::GitAiError;
 use crate::git::find_repository;
-use crate::git::find_repository_in_path;
-use crate::git::repository::{CommitRange, Repository};
+use crate::git::repository::{CommitRange, Repository, split_leading_repository_args};
 use crate::git::sync_authorship::{NotesExistence, fetch_authorship_notes, push_authorship_notes};
 use crate::observability::log_message;
-use crate::utils::is_interactive_terminal;
+use crate::utils::{format_local_timestamp, is_interactive_terminal};
+use crate::workspace_config::{WhitespaceMode, effective_workspace_config};
 use serde::{Deserialize, Serialize};
 use std::env;
+use std::fmt::Write as _;
 use std::io::IsTerminal;
 use std::io::Read;
 
@@ -24,6 +32,15 @@ pub fn handle_git_ai(args: &[String]) {
             None
         };
 
+    let (repository_args, command_args) = split_leading_repository_args(args);
+    if let Err(e) = apply_repository_args(&repository_args) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+    // `git ai --json <command>` etc. apply to whichever command follows.
+    let command_args = OutputOptions::take_leading_flags(&command_args);
+    let args = command_args.as_slice();
+
     if args.is_empty() {
         print_help();
         return;
@@ -54,24 +71,12 @@ pub fn handle_git_ai(args: &[String]) {
             | "install"
             | "uninstall-hooks"
             | "usage"
-    );
+            | "top"
+            | "report"
+    ) && !(args[0] == "errors"
+        && args.get(1).map(String::as_str) != Some("upload"));
     if needs_daemon {
-        use crate::daemon::telemetry_handle::{
-            DaemonTelemetryInitResult, init_daemon_telemetry_handle,
-        };
-        match init_daemon_telemetry_handle() {
-            DaemonTelemetryInitResult::Connected | DaemonTelemetryInitResult::Skipped => {}
-            DaemonTelemetryInitResult::Failed(err) => {
-                eprintln!(
-                    "error: failed to connect to git-ai background service: {}",
-                    err
-                );
-                if args[0].as_str() == "checkpoint" {
-                    std::process::exit(0);
-                }
-                std::process::exit(1);
-            }
-        }
+        init_telemetry_or_exit(args[0].as_str());
     }
 
     // Start DB warmup early for commands that need database access
@@ -80,17 +85,18 @@ pub fn handle_git_ai(args: &[String]) {
     }
 
     match args[0].as_str() {
-        "help" | "--help" | "-h" => {
-            print_help();
-        }
-        "version" | "--version" | "-v" => {
-            if cfg!(debug_assertions) {
-                println!("{} (debug)", env!("CARGO_PKG_VERSION"));
-            } else {
-                println!(env!("CARGO_PKG_VERSION"));
-            }
-            std::process::exit(0);
-        }
+        "help" | "--help" | "-h" => match args.get(1) {
+            Some(command) => match command_help(command) {
+                Some(help) => eprint!("{}", help),
+                None => {
+                    eprintln!("No help for unknown git-ai command: {}", command);
+                    eprintln!("Run 'git ai help' to list commands.");
+                    std::process::exit(1);
+                }
+            },
+            None => print_help(),
+        },
+        "version" | "--version" | "-v" => print_version(),
         "config" => {
             commands::config::handle_config(&args[1..]);
             if is_interactive_terminal() {
@@ -112,6 +118,15 @@ pub fn handle_git_ai(args: &[String]) {
         "usage" => {
             commands::usage::handle_usage(&args[1..]);
         }
+        "top" => {
+            commands::top::handle_top(&args[1..]);
+        }
+        "timeline" => {
+            commands::timeline::handle_timeline(&args[1..]);
+        }
+        "report" => {
+            commands::report::handle_report(&args[1..]);
+        }
         "analyze" => {
             commands::analyze::handle_analyze(&args[1..]);
             if is_interactive_terminal() {
@@ -121,6 +136,9 @@ pub fn handle_git_ai(args: &[String]) {
         "status" => {
             commands::status::handle_status(&args[1..]);
         }
+        "commit" => {
+            commands::commit::handle_commit(&args[1..]);
+        }
         "show" => {
             commands::show::handle_show(&args[1..]);
         }
@@ -131,6 +149,12 @@ pub fn handle_git_ai(args: &[String]) {
                     t.elapsed().as_secs_f64() * 1000.0
                 );
             }
+            let _span = tracing::info_span!(
+                target: crate::observability::trace::targets::HOOKS,
+                "checkpoint",
+                preset = args.get(1).map(String::as_str).unwrap_or("")
+            )
+            .entered();
             handle_checkpoint(&args[1..]);
         }
         "log" => {
@@ -165,7 +189,7 @@ pub fn handle_git_ai(args: &[String]) {
             }
             Err(e) => {
                 eprintln!("Install hooks failed: {}", e);
-                std::process::exit(1);
+                std::process::exit(e.exit_code());
             }
         },
         "uninstall-hooks" => match commands::install_hooks::run_uninstall(&args[1..]) {
@@ -176,7 +200,7 @@ pub fn handle_git_ai(args: &[String]) {
             }
             Err(e) => {
                 eprintln!("Uninstall hooks failed: {}", e);
-                std::process::exit(1);
+                std::process::exit(e.exit_code());
             }
         },
         "git-hooks" => {
@@ -203,12 +227,18 @@ pub fn handle_git_ai(args: &[String]) {
         "whoami" => {
             commands::whoami::handle_whoami(&args[1..]);
         }
+        "errors" => {
+            commands::errors::handle_errors(&args[1..]);
+        }
         "exchange-nonce" => {
             commands::exchange_nonce::handle_exchange_nonce(&args[1..]);
         }
         "dash" | "dashboard" => {
             commands::personal_dashboard::handle_personal_dashboard(&args[1..]);
         }
+        "profile" => {
+            commands::profile::handle_profile(&args[1..]);
+        }
         "show-prompt" => {
             commands::show_prompt::handle_show_prompt(&args[1..]);
         }
@@ -230,8 +260,63 @@ pub fn handle_git_ai(args: &[String]) {
         "notes" => {
             handle_notes_subcommand(&args[1..]);
         }
+        "notes-gc" => {
+            commands::notes_gc::handle_notes_gc(&args[1..]);
+        }
+        "gc" => {
+            commands::gc::handle_gc(&args[1..]);
+        }
+        "restore-attribution" => {
+            commands::restore_attribution::handle_restore_attribution(&args[1..]);
+        }
+        "backfill" => {
+            commands::backfill::handle_backfill(&args[1..]);
+        }
+        "reconcile" => {
+            commands::reconcile::handle_reconcile(&args[1..]);
+        }
+        "reconstruct" => {
+            commands::reconstruct::handle_reconstruct(&args[1..]);
+        }
+        "resolve" => {
+            commands::resolve::handle_resolve(&args[1..]);
+        }
+        "repair" => {
+            commands::repair::handle_repair(&args[1..]);
+        }
+        "fsck" => {
+            commands::fsck::handle_fsck(&args[1..]);
+        }
+        "session" => {
+            commands::session::handle_session(&args[1..]);
+        }
+        "server-hooks" => {
+            commands::server_hooks::handle_server_hooks(&args[1..]);
+        }
+        "migrate-storage" => {
+            commands::migrate_storage::handle_migrate_storage(&args[1..]);
+        }
+        "state" => {
+            commands::state::handle_state(&args[1..]);
+        }
+        "bundle" => {
+            commands::bundle::handle_bundle(&args[1..]);
+        }
+        #[cfg(feature = "parquet-export")]
+        "export" => {
+            commands::export::handle_export(&args[1..]);
+        }
+        #[cfg(not(feature = "parquet-export"))]
+        "export" => {
+            eprintln!("Error: this git-ai was built without the parquet-export feature");
+            std::process::exit(1);
+        }
+        "prompts" => {
+            commands::prompts::handle_prompts(&args[1..]);
+        }
         _ => {
-            println!("Unknown git-ai command: {}", args[0]);
+            eprintln!("Unknown git-ai command: {}", args[0]);
+            eprintln!("Run 'git ai help' to list commands.");
             std::process::exit(1);
         }
     }
@@ -244,6 +329,12 @@ pub(crate) fn handle_notes_subcommand(args: &[String]) {
         "migrate" => {
             commands::notes_migrate::handle_notes_migrate(&args[1..]);
         }
+        "gc" => {
+            commands::notes_gc::handle_notes_gc(&args[1..]);
+        }
+        "upgrade" => {
+            commands::notes_upgrade::handle_notes_upgrade(&args[1..]);
+        }
         // Hidden: in-memory reference implementation of the notes backend HTTP
         // contract. Intentionally not advertised in `--help`; it is for
         // developers, tests, and benchmarks, not end users.
@@ -257,6 +348,10 @@ pub(crate) fn handle_notes_subcommand(args: &[String]) {
             eprintln!();
             eprintln!("Subcommands:");
             eprintln!("  migrate    Bulk-upload existing git notes to the HTTP backend");
+            eprintln!("  gc         Prune notes on unreachable commits (same as notes-gc)");
+            eprintln!(
+                "  upgrade    Copy notes into the newest schema namespace (refs/notes/ai-v2)"
+            );
             eprintln!();
             eprintln!("Run 'git ai notes <subcommand> --help' for details.");
         }
@@ -313,87 +408,674 @@ fn handle_notes_serve(args: &[String]) {
 }
 
 fn print_help() {
-    eprintln!("git-ai - git proxy with AI authorship tracking");
-    eprintln!();
-    eprintln!("Usage: git-ai <command> [args...]");
-    eprintln!();
-    eprintln!("Commands:");
-    eprintln!("  checkpoint         Checkpoint working changes and attribute author");
-    eprintln!(
-        "    Presets: claude, cline, codex, continue-cli, cursor, gemini, github-copilot, amp, windsurf, opencode, pi, ai_tab, firebender, human, mock_ai, mock_known_human, known_human"
-    );
-    eprintln!(
+    eprint!("{}", help_text());
+    std::process::exit(0);
+}
+
+/// `git ai help <command>`: the usage line plus the command's block of the
+/// full help, or `None` when no entry documents `command`.
+fn command_help(command: &str) -> Option<String> {
+    let text = help_text();
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines.iter().position(|line| {
+        line.strip_prefix("  ")
+            .filter(|entry| !entry.starts_with(' '))
+            .and_then(|entry| entry.split([' ', ',']).next())
+            == Some(command)
+    })?;
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| !line.starts_with("    "))
+        .map_or(lines.len(), |offset| start + 1 + offset);
+    Some(format!(
+        "Usage: git ai {} [args...]\n\n{}\n",
+        command,
+        lines[start..end].join("\n")
+    ))
+}
+
+fn help_text() -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "git-ai - git proxy with AI authorship tracking");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Usage: git-ai <command> [args...]");
+    let _ = writeln!(out, "   or: git ai <command> [args...]");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Commands:");
+    let _ = writeln!(
+        out,
+        "  checkpoint         Checkpoint working changes and attribute author"
+    );
+    let _ = writeln!(
+        out,
+        "    Presets: claude, cline, codex, continue-cli, cursor, gemini, github-copilot, amp, windsurf, opencode, openhands, amazon-q, pi, ai_tab, firebender, human, mock_ai, mock_known_human, known_human"
+    );
+    let _ = writeln!(
+        out,
         "    --hook-input <json|stdin>   JSON payload required by presets, or 'stdin' to read from stdin"
     );
-    eprintln!("    human [pathspecs...]             Untracked/legacy human checkpoint");
-    eprintln!("    mock_ai [pathspecs...]           Test preset accepting optional file pathspecs");
-    eprintln!("    mock_known_human [pathspecs...]  Test preset for KnownHuman checkpoints");
-    eprintln!("  log [args...]      Show commit log with AI authorship stats");
-    eprintln!("                        Use --raw or --notes to include raw authorship note data");
-    eprintln!("  blame <file>       Git blame with AI authorship overlay");
-    eprintln!("  diff <commit|range>  Show diff with AI authorship annotations");
-    eprintln!("    <commit>              Diff from commit's parent to commit");
-    eprintln!("    <commit1>..<commit2>  Diff between two commits");
-    eprintln!("    --json                 Output in JSON format");
-    eprintln!(
+    let _ = writeln!(
+        out,
+        "    --hunks-json <json|stdin>   Exact edits per file ([{{path, hunks: [{{old_start, old_lines, new_text}}]}}]), skipping the internal diff"
+    );
+    let _ = writeln!(
+        out,
+        "    --label <text>              Tag the checkpoint, e.g. \"after refactor\" (see stats --detail, timeline)"
+    );
+    let _ = writeln!(
+        out,
+        "    --conflict-resolution --tool <name> [--model <name>] <files...>"
+    );
+    let _ = writeln!(
+        out,
+        "                                Credit an AI merge tool's resolution of <files> in the merge commit"
+    );
+    let _ = writeln!(
+        out,
+        "    human [pathspecs...]             Untracked/legacy human checkpoint"
+    );
+    let _ = writeln!(
+        out,
+        "    mock_ai [pathspecs...]           Test preset accepting optional file pathspecs"
+    );
+    let _ = writeln!(
+        out,
+        "    mock_known_human [pathspecs...]  Test preset for KnownHuman checkpoints"
+    );
+    let _ = writeln!(
+        out,
+        "  log [args...]      Show commit log with AI authorship stats"
+    );
+    let _ = writeln!(
+        out,
+        "                        Use --raw or --notes to include raw authorship note data"
+    );
+    let _ = writeln!(
+        out,
+        "  blame <file>       Git blame with AI authorship overlay"
+    );
+    let _ = writeln!(
+        out,
+        "    --group-by session   One row per AI conversation (session, model, line count, first/last line)"
+    );
+    let _ = writeln!(
+        out,
+        "  diff <commit|range>  Show diff with AI authorship annotations"
+    );
+    let _ = writeln!(
+        out,
+        "    <commit>              Diff from commit's parent to commit"
+    );
+    let _ = writeln!(out, "    <commit1>..<commit2>  Diff between two commits");
+    let _ = writeln!(out, "    --json                 Output in JSON format");
+    let _ = writeln!(
+        out,
         "    --include-stats        Include commit_stats in JSON output (single commit only)"
     );
-    eprintln!(
+    let _ = writeln!(
+        out,
         "    --all-prompts          Include all prompts from commit note in JSON output (single commit only)"
     );
-    eprintln!("  stats [commit]     Show AI authorship statistics for a commit");
-    eprintln!("    --json                 Output in JSON format");
-    eprintln!("  usage              Show local AI usage statistics");
-    eprintln!("    --period <1d|3d|7d|30d>  Time window (default: 30d)");
-    eprintln!("    --json                 Output in JSON format");
-    eprintln!("  analyze [beta]      Analyze agent sessions and effectiveness");
-    eprintln!("  status             Show uncommitted AI authorship status (debug)");
-    eprintln!("    --json                 Output in JSON format");
-    eprintln!(
+    let _ = writeln!(
+        out,
+        "  stats [commit]     Show AI authorship statistics for a commit"
+    );
+    let _ = writeln!(out, "    --json                 Output in JSON format");
+    let _ = writeln!(
+        out,
+        "    --format <fmt>         text, json, or markdown (GitHub tables for PRs and issues)"
+    );
+    let _ = writeln!(
+        out,
+        "    --by-file              Break down additions and AI/human deletions per file"
+    );
+    let _ = writeln!(
+        out,
+        "    --detail               List labelled checkpoints, subagents and tool versions (markdown for ranges)"
+    );
+    let _ = writeln!(
+        out,
+        "    --trailer              Print a one-line AI-Attribution trailer (staged changes if no commit)"
+    );
+    let _ = writeln!(
+        out,
+        "    --ignore-whitespace    Leave out blank lines and whitespace-only changes"
+    );
+    let _ = writeln!(
+        out,
+        "    --ignore-reindent      Like --ignore-whitespace, also leaving out reindented lines"
+    );
+    let _ = writeln!(
+        out,
+        "    --range <a>..<b>       Sum cached per-commit stats rollups over a commit range"
+    );
+    let _ = writeln!(
+        out,
+        "    --backfill [<a>..<b>]  Fill stats rollups (default: noted commits reachable from HEAD)"
+    );
+    let _ = writeln!(
+        out,
+        "    --save-baseline <name> Save [commit] (default: HEAD) as a named baseline"
+    );
+    let _ = writeln!(
+        out,
+        "    --baseline <name>      Sum rollups for commits since a saved baseline"
+    );
+    let _ = writeln!(out, "    --list-baselines       List saved baselines");
+    let _ = writeln!(
+        out,
+        "    --coverage             Share of added lines with attribution evidence, per commit in a range"
+    );
+    let _ = writeln!(out, "  usage              Show local AI usage statistics");
+    let _ = writeln!(
+        out,
+        "    --period <1d|3d|7d|30d>  Time window (default: 30d)"
+    );
+    let _ = writeln!(out, "    --json                 Output in JSON format");
+    let _ = writeln!(
+        out,
+        "  timeline [<a>..<b>]  Labelled checkpoints across commits, oldest first"
+    );
+    let _ = writeln!(
+        out,
+        "    -n <count>             Commits to scan when no range is given (default: 20)"
+    );
+    let _ = writeln!(out, "    --json                 Output in JSON format");
+    let _ = writeln!(
+        out,
+        "  top                Live view of agent sessions, checkpoints, and pending attribution"
+    );
+    let _ = writeln!(
+        out,
+        "    --once                 Print one snapshot and exit"
+    );
+    let _ = writeln!(
+        out,
+        "    --json                 Print one snapshot as JSON and exit"
+    );
+    let _ = writeln!(
+        out,
+        "  report             Summarize local AI activity for sharing"
+    );
+    let _ = writeln!(
+        out,
+        "    --digest <daily|weekly|monthly>  Period to summarize"
+    );
+    let _ = writeln!(
+        out,
+        "    --format <markdown|slack-json>   Output format (default: markdown)"
+    );
+    let _ = writeln!(
+        out,
+        "    --post                 Post the digest to the configured slack_webhook_url"
+    );
+    let _ = writeln!(
+        out,
+        "  analyze [beta]      Analyze agent sessions and effectiveness"
+    );
+    let _ = writeln!(
+        out,
+        "  profile <git args> Run a git command through git-ai and print a per-phase timing breakdown"
+    );
+    let _ = writeln!(out, "    --json                 Output in JSON format");
+    let _ = writeln!(
+        out,
+        "  status             Show uncommitted AI authorship status (debug)"
+    );
+    let _ = writeln!(out, "    --json                 Output in JSON format");
+    let _ = writeln!(
+        out,
         "    --diff-only            Report only current-diff stats, omitting the per-checkpoint breakdown"
     );
-    eprintln!("  show <rev|range>   Display authorship logs for a revision or range");
-    eprintln!("  show-prompt <id>   Display a prompt record by its ID");
-    eprintln!("    --commit <rev>        Look in a specific commit only");
-    eprintln!(
+    let _ = writeln!(
+        out,
+        "  commit             Commit staged changes with an AI attribution footer"
+    );
+    let _ = writeln!(
+        out,
+        "    -m <msg>               Message to append the footer to (generated if omitted)"
+    );
+    let _ = writeln!(
+        out,
+        "    --dry-run              Print the commit message without committing"
+    );
+    let _ = writeln!(
+        out,
+        "  show <rev|range>   Display authorship logs for a revision or range"
+    );
+    let _ = writeln!(
+        out,
+        "    -p, --patch           Show the diff with an AI/human attribution gutter"
+    );
+    let _ = writeln!(
+        out,
+        "  show-prompt <id>   Display a prompt record by its ID"
+    );
+    let _ = writeln!(
+        out,
+        "    --commit <rev>        Look in a specific commit only"
+    );
+    let _ = writeln!(
+        out,
         "    --offset <n>          Skip n occurrences (0 = most recent, mutually exclusive with --commit)"
     );
-    eprintln!("  config             View and manage git-ai configuration");
-    eprintln!("                        Show all config as formatted JSON");
-    eprintln!("    <key>                 Show specific config value (supports dot notation)");
-    eprintln!("    set <key> <value>     Set a config value (arrays: single value = [value])");
-    eprintln!("    --add <key> <value>   Add to array or upsert into object");
-    eprintln!("    unset <key>           Remove config value (reverts to default)");
-    eprintln!("  debug              Print support/debug diagnostics");
-    eprintln!("  bg                 Run and control git-ai background service");
-    eprintln!("  install-hooks      Install git hooks for AI authorship tracking");
-    eprintln!("    --skills               Also install agent skill files");
-    eprintln!("    --visual-studio-extension");
-    eprintln!("                           Also install the Visual Studio extension on Windows");
-    eprintln!("  uninstall-hooks    Remove git-ai hooks from all detected tools");
-    eprintln!("  ci                 Continuous integration utilities");
-    eprintln!("    github                 GitHub CI helpers");
-    eprintln!("  git-path           Print the path to the underlying git executable");
-    eprintln!("  await [beta]       Wait for the background service to finish all work");
-    eprintln!("    --timeout <seconds>    Maximum time to wait (default: 30)");
-    eprintln!("  upgrade            Check for updates and install if available");
-    eprintln!("    --force               Reinstall latest version even if already up to date");
-    eprintln!("  fetch-notes [remote] Synchronously fetch AI authorship notes");
-    eprintln!("    --remote <name>       Explicit remote name (default: upstream or origin)");
-    eprintln!("    --json                Output result as JSON");
-    eprintln!("  login              Authenticate with Git AI");
-    eprintln!("  logout             Clear stored credentials");
-    eprintln!("  whoami             Show auth state and login identity");
-    eprintln!("  version, -v, --version     Print the git-ai version");
-    eprintln!("  help, -h, --help           Show this help message");
-    eprintln!();
+    let _ = writeln!(
+        out,
+        "  prompts            Browse stored prompt records (notes and working log)"
+    );
+    let _ = writeln!(out, "    list | search <text> | show <id>");
+    let _ = writeln!(
+        out,
+        "    --tool, --model, --file, --since, --until  Filter records"
+    );
+    let _ = writeln!(out, "    --json                Print records as JSON");
+    let _ = writeln!(
+        out,
+        "  config             View and manage git-ai configuration"
+    );
+    let _ = writeln!(
+        out,
+        "                        Show all config as formatted JSON"
+    );
+    let _ = writeln!(
+        out,
+        "    <key>                 Show specific config value (supports dot notation)"
+    );
+    let _ = writeln!(
+        out,
+        "    set <key> <value>     Set a config value (arrays: single value = [value])"
+    );
+    let _ = writeln!(
+        out,
+        "    --add <key> <value>   Add to array or upsert into object"
+    );
+    let _ = writeln!(
+        out,
+        "    unset <key>           Remove config value (reverts to default)"
+    );
+    let _ = writeln!(out, "  debug              Print support/debug diagnostics");
+    let _ = writeln!(
+        out,
+        "  errors             Inspect locally recorded crashes and errors"
+    );
+    let _ = writeln!(
+        out,
+        "    show [--json]         Show recorded errors, newest first"
+    );
+    let _ = writeln!(
+        out,
+        "    upload                Submit records not yet sent to crash reporting"
+    );
+    let _ = writeln!(out, "    clear                 Delete the local error log");
+    let _ = writeln!(
+        out,
+        "  bg                 Run and control git-ai background service"
+    );
+    let _ = writeln!(
+        out,
+        "  install-hooks      Install git hooks for AI authorship tracking"
+    );
+    let _ = writeln!(
+        out,
+        "    --dry-run              Show the config and agent settings changes without applying"
+    );
+    let _ = writeln!(
+        out,
+        "    --plan-json            Print the dry-run plan as JSON"
+    );
+    let _ = writeln!(
+        out,
+        "    --skills               Also install agent skill files"
+    );
+    let _ = writeln!(out, "    --visual-studio-extension");
+    let _ = writeln!(
+        out,
+        "                           Also install the Visual Studio extension on Windows"
+    );
+    let _ = writeln!(
+        out,
+        "    --copilot-agent        Also record VS Code Copilot agent mode as copilot-agent"
+    );
+    let _ = writeln!(
+        out,
+        "  uninstall-hooks    Remove git-ai hooks from all detected tools"
+    );
+    let _ = writeln!(out, "  ci                 Continuous integration utilities");
+    let _ = writeln!(out, "    github                 GitHub CI helpers");
+    let _ = writeln!(
+        out,
+        "  git-path           Print the path to the underlying git executable"
+    );
+    let _ = writeln!(
+        out,
+        "  await [beta]       Wait for the background service to finish all work"
+    );
+    let _ = writeln!(
+        out,
+        "    --timeout <seconds>    Maximum time to wait (default: 30)"
+    );
+    let _ = writeln!(
+        out,
+        "  upgrade            Check for updates and install if available"
+    );
+    let _ = writeln!(
+        out,
+        "    --force               Reinstall latest version even if already up to date"
+    );
+    let _ = writeln!(
+        out,
+        "  migrate-storage    Upgrade .git/ai storage to the current format"
+    );
+    let _ = writeln!(
+        out,
+        "    --dry-run             Report what would be migrated without writing"
+    );
+    let _ = writeln!(
+        out,
+        "  state export|import  Snapshot or restore .git/ai state and notes as a bundle"
+    );
+    let _ = writeln!(out, "    --output <path>       Bundle path for export");
+    let _ = writeln!(
+        out,
+        "  bundle push --range <a>..<b>  Upload a release's attribution snapshot to CAS"
+    );
+    let _ = writeln!(
+        out,
+        "    --dry-run             Build the bundle and print its manifest id only"
+    );
+    let _ = writeln!(out, "    --json                Print the manifest as JSON");
+    let _ = writeln!(
+        out,
+        "  export --format parquet  Write commit and checkpoint attribution tables"
+    );
+    let _ = writeln!(
+        out,
+        "                          (needs a build with the parquet-export feature)"
+    );
+    let _ = writeln!(
+        out,
+        "    --range <range>       Commits to export (default: HEAD)"
+    );
+    let _ = writeln!(
+        out,
+        "    --output-dir <dir>    Directory for the Parquet files"
+    );
+    let _ = writeln!(
+        out,
+        "  notes-gc           Prune AI notes attached to unreachable commits"
+    );
+    let _ = writeln!(
+        out,
+        "    --dry-run             Report what would be pruned without writing"
+    );
+    let _ = writeln!(
+        out,
+        "    --retention-days <n>  Keep notes on unreachable commits newer than n days"
+    );
+    let _ = writeln!(
+        out,
+        "  gc                 Prune stale working logs and dropped stash attribution in .git/ai"
+    );
+    let _ = writeln!(
+        out,
+        "    --dry-run             Report what would be pruned without removing it"
+    );
+    let _ = writeln!(
+        out,
+        "    --max-age-days <n>    Keep working logs written in the last n days"
+    );
+    let _ = writeln!(
+        out,
+        "  backfill --from-github  Synthesize coarse notes for AI pull requests merged before git-ai"
+    );
+    let _ = writeln!(
+        out,
+        "    --repo <owner>/<name>  GitHub repository (default: the default remote's)"
+    );
+    let _ = writeln!(
+        out,
+        "    --label <name>        Label marking AI-authored PRs (repeatable)"
+    );
+    let _ = writeln!(
+        out,
+        "    --limit <n>           Merged PRs to examine, newest first"
+    );
+    let _ = writeln!(
+        out,
+        "    --dry-run             Report what would be written without writing"
+    );
+    let _ = writeln!(
+        out,
+        "  reconcile          Map attribution quarantined by upstream force-pushes onto new history"
+    );
+    let _ = writeln!(
+        out,
+        "    --list                List quarantined force-push rewrites"
+    );
+    let _ = writeln!(
+        out,
+        "    --dry-run             Report matches without writing"
+    );
+    let _ = writeln!(
+        out,
+        "  reconstruct <range>  Rebuild notes missing in <range> from commits with the same patch-id"
+    );
+    let _ = writeln!(
+        out,
+        "    --source <rev>        Take notes only from commits reachable from <rev>"
+    );
+    let _ = writeln!(
+        out,
+        "    --dry-run             Report matches without writing"
+    );
+    let _ = writeln!(
+        out,
+        "  resolve [<commit>]  Settle attribution conflicts with another version of a note"
+    );
+    let _ = writeln!(
+        out,
+        "    --remote <name> | --ref <notes-ref> | --file <path>  The other version"
+    );
+    let _ = writeln!(
+        out,
+        "    --ours, --theirs      Pick one side for every conflict instead of asking"
+    );
+    let _ = writeln!(
+        out,
+        "  repair             Finish notes syncs deferred after a hook timeout"
+    );
+    let _ = writeln!(
+        out,
+        "    --list                List deferred work without running it"
+    );
+    let _ = writeln!(
+        out,
+        "  fsck               Validate notes and working logs for corrupt attribution"
+    );
+    let _ = writeln!(
+        out,
+        "    --prune               Remove corrupt notes and entries"
+    );
+    let _ = writeln!(
+        out,
+        "  restore-attribution [id]  Recover files and AI attribution removed by git clean"
+    );
+    let _ = writeln!(
+        out,
+        "    --list                List archives taken before git clean"
+    );
+    let _ = writeln!(
+        out,
+        "    --force               Overwrite files that already exist"
+    );
+    let _ = writeln!(
+        out,
+        "  session start|end|status  Attribute human checkpoints to an AI tool"
+    );
+    let _ = writeln!(out, "    --tool <name>         Tool to attribute edits to");
+    let _ = writeln!(out, "    --model <name>        Model to attribute edits to");
+    let _ = writeln!(
+        out,
+        "    --ttl <duration>      End the session automatically (default: 8h)"
+    );
+    let _ = writeln!(
+        out,
+        "  server-hooks install  Install receive hooks that validate pushed AI notes"
+    );
+    let _ = writeln!(
+        out,
+        "    --max-note-bytes <n>  Reject notes larger than n bytes"
+    );
+    let _ = writeln!(
+        out,
+        "    --require-signed-push Require a valid push certificate for notes pushes"
+    );
+    let _ = writeln!(
+        out,
+        "  fetch-notes [remote] Synchronously fetch AI authorship notes"
+    );
+    let _ = writeln!(
+        out,
+        "    --remote <name>       Explicit remote name (default: upstream or origin)"
+    );
+    let _ = writeln!(out, "    --json                Output result as JSON");
+    let _ = writeln!(out, "  login              Authenticate with Git AI");
+    let _ = writeln!(out, "  logout             Clear stored credentials");
+    let _ = writeln!(
+        out,
+        "  whoami             Show auth state and login identity"
+    );
+    let _ = writeln!(out, "  version, -v, --version     Print the git-ai version");
+    let _ = writeln!(out, "  help, -h, --help           Show this help message");
+    let _ = writeln!(out, "    <command>             Show help for one command");
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "Output options (before the command or among its arguments):"
+    );
+    let _ = writeln!(
+        out,
+        "  --json                 Print machine-readable JSON on stdout"
+    );
+    let _ = writeln!(
+        out,
+        "  --quiet, -q            Print only results and errors"
+    );
+    let _ = writeln!(
+        out,
+        "  --verbose              Print additional detail where available"
+    );
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "Exit codes (with --json, failures also print {{\"error\": {{category, exit_code, message}}}}):"
+    );
+    let _ = writeln!(
+        out,
+        "  1 internal  3 git  4 storage  5 notes  6 hook  7 sync  8 auth  9 policy"
+    );
+    let _ = writeln!(out);
+    out
+}
+
+/// Connect the global telemetry handle to the background service, exiting if it is
+/// unreachable. Checkpoints exit 0 so a broken service never fails an agent's hook.
+fn init_telemetry_or_exit(command: &str) {
+    use crate::daemon::telemetry_handle::{
+        DaemonTelemetryInitResult, init_daemon_telemetry_handle,
+    };
+    match init_daemon_telemetry_handle() {
+        DaemonTelemetryInitResult::Connected | DaemonTelemetryInitResult::Skipped => {}
+        DaemonTelemetryInitResult::Failed(err) => {
+            eprintln!(
+                "error: failed to connect to git-ai background service: {}",
+                err
+            );
+            if command == "checkpoint" {
+                std::process::exit(0);
+            }
+            std::process::exit(1);
+        }
+    }
+}
+
+fn print_version() -> ! {
+    if cfg!(debug_assertions) {
+        println!("{} (debug)", env!("CARGO_PKG_VERSION"));
+    } else {
+        println!(env!("CARGO_PKG_VERSION"));
+    }
     std::process::exit(0);
 }
 
+/// Entry point for the `git-ai-hook` binary (`hooks-only` feature).
+///
+/// Only `checkpoint` is dispatched, plus `--version`, which agent plugins (e.g.
+/// Amp's) run to probe the installed binary. Anything else is rejected rather than
+/// forwarded to the full CLI.
+#[cfg(feature = "hooks-only")]
+pub fn handle_git_ai_hook(args: &[String]) {
+    match args.first().map(String::as_str) {
+        Some("checkpoint") => {
+            init_telemetry_or_exit("checkpoint");
+            handle_checkpoint(&args[1..]);
+        }
+        Some("version" | "--version" | "-v") => print_version(),
+        Some(other) => {
+            eprintln!(
+                "git-ai-hook only handles 'checkpoint'; run 'git-ai {}' instead",
+                other
+            );
+            std::process::exit(1);
+        }
+        None => {
+            eprintln!("Usage: git-ai-hook checkpoint <preset> [--hook-input <json|stdin>]");
+            std::process::exit(1);
+        }
+    }
+}
+
 fn handle_checkpoint(args: &[String]) {
     let perf = std::env::var("GIT_AI_DEBUG_PERFORMANCE").is_ok_and(|v| !v.is_empty() && v != "0");
     let t0 = std::time::Instant::now();
 
+    if args.iter().any(|arg| arg == "--conflict-resolution") {
+        crate::commands::conflict_resolution::handle_conflict_resolution_checkpoint(args);
+        return;
+    }
+
+    // `--hunks-json` and `--label` are consumed here so presets never see them
+    // as file paths.
+    let mut hunks_json = None;
+    let mut label = None;
+    let mut remaining_args = Vec::with_capacity(args.len());
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--hunks-json" {
+            let Some(value) = args.get(i + 1) else {
+                eprintln!("Error: --hunks-json requires a value or 'stdin' to read from stdin");
+                std::process::exit(0);
+            };
+            hunks_json = Some(value.clone());
+            i += 2;
+        } else if args[i] == "--label" {
+            let Some(value) = args.get(i + 1).map(|value| value.trim()) else {
+                eprintln!("Error: --label requires a value");
+                std::process::exit(0);
+            };
+            if value.is_empty() {
+                eprintln!("Error: --label requires a value");
+                std::process::exit(0);
+            }
+            label = Some(value.to_string());
+            i += 2;
+        } else {
+            remaining_args.push(args[i].clone());
+            i += 1;
+        }
+    }
+    let args = remaining_args.as_slice();
+
     let mut hook_input = None;
     let mut i = 0;
     while i < args.len() {
@@ -465,18 +1147,40 @@ fn handle_checkpoint(args: &[String]) {
         );
     }
 
-    let t_orchestrator = std::time::Instant::now();
-    let requests = match crate::commands::checkpoint_agent::orchestrator::execute_preset_checkpoint(
-        preset_name,
-        &effective_hook_input,
-    ) {
-        Ok(r) => r,
-        Err(e) => {
-            eprintln!("{} preset error: {}", preset_name, e);
+    let file_hunks = match hunks_json.as_deref().map(read_hunks_json) {
+        Some(Ok(file_hunks)) => file_hunks,
+        Some(Err(e)) => {
+            eprintln!("Error: {}", e);
             std::process::exit(0);
         }
+        None => Vec::new(),
     };
 
+    let t_orchestrator = std::time::Instant::now();
+    let mut requests =
+        match crate::commands::checkpoint_agent::orchestrator::execute_preset_checkpoint(
+            preset_name,
+            &effective_hook_input,
+        ) {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("{} preset error: {}", preset_name, e);
+                std::process::exit(0);
+            }
+        };
+    crate::commands::checkpoint_agent::orchestrator::attach_checkpoint_hunks(
+        &mut requests,
+        &file_hunks,
+    );
+    if let Some(label) = label.as_ref() {
+        for request in &mut requests {
+            request.metadata.insert(
+                crate::authorship::working_log::CHECKPOINT_LABEL_METADATA_KEY.to_string(),
+                label.clone(),
+            );
+        }
+    }
+
     if perf {
         eprintln!(
             "[perf] checkpoint: orchestrator={:.1}ms (requests={}, files={})",
@@ -585,6 +1289,58 @@ fn handle_checkpoint(args: &[String]) {
     }
 }
 
+/// Reads a `--hunks-json` value, either inline JSON or `stdin`.
+fn read_hunks_json(
+    value: &str,
+) -> Result<Vec<crate::commands::checkpoint_agent::orchestrator::CheckpointFileHunks>, String> {
+    let json = if value == "stdin" {
+        let mut buffer = String::new();
+        std::io::stdin()
+            .read_to_string(&mut buffer)
+            .map_err(|e| format!("failed to read stdin for --hunks-json: {}", e))?;
+        buffer
+    } else {
+        value.to_string()
+    };
+    let cwd = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+    crate::commands::checkpoint_agent::orchestrator::parse_hunks_json(
+        strip_utf8_bom(json).trim(),
+        &cwd,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Applies leading `-C`, `--git-dir` and `--work-tree` options the way git does
+/// for external commands: `-C` changes the working directory, and the others are
+/// exported as `GIT_DIR`/`GIT_WORK_TREE` so every subcommand's repository
+/// discovery sees them.
+fn apply_repository_args(repository_args: &[String]) -> Result<(), String> {
+    let mut idx = 0usize;
+    while idx < repository_args.len() {
+        let arg = repository_args[idx].as_str();
+        let (flag, value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                idx += 1;
+                (flag, value)
+            }
+            _ => {
+                idx += 2;
+                (arg, repository_args[idx - 1].as_str())
+            }
+        };
+        match flag {
+            "-C" => std::env::set_current_dir(value)
+                .map_err(|e| format!("cannot change to '{}': {}", value, e))?,
+            // SAFETY: this runs at the start of `handle_git_ai`, before telemetry,
+            // the daemon client, or any other thread has been started.
+            "--git-dir" => unsafe { std::env::set_var("GIT_DIR", value) },
+            "--work-tree" => unsafe { std::env::set_var("GIT_WORK_TREE", value) },
+            other => return Err(format!("unexpected repository option '{}'", other)),
+        }
+    }
+    Ok(())
+}
+
 fn strip_utf8_bom(input: String) -> String {
     if let Some(stripped) = input.strip_prefix('\u{feff}') {
         stripped.to_string()
@@ -836,12 +1592,11 @@ fn handle_ai_blame(args: &[String]) {
         std::process::exit(1);
     }
 
-    // Find the git repository from current directory
     let current_dir = env::current_dir()
         .unwrap_or_else(|_| std::path::PathBuf::from("."))
         .to_string_lossy()
         .to_string();
-    let repo = match find_repository_in_path(&current_dir) {
+    let repo = match find_repository(&Vec::<String>::new()) {
         Ok(repo) => repo,
         Err(e) => {
             eprintln!("Failed to find repository: {}", e);
@@ -857,6 +1612,7 @@ fn handle_ai_blame(args: &[String]) {
             std::process::exit(1);
         }
     };
+    options.json |= OutputOptions::current().is_json();
 
     // Auto-detect ignore-revs-file if not explicitly provided, not disabled via --no-ignore-revs-file,
     // and git version supports --ignore-revs-file (git >= 2.23)
@@ -914,16 +1670,12 @@ fn handle_ai_blame(args: &[String]) {
 
     if let Err(e) = repo.blame(&file_path, &options) {
         eprintln!("Blame failed: {}", e);
-        std::process::exit(1);
+        std::process::exit(e.exit_code());
     }
 }
 
 fn handle_ai_diff(args: &[String]) {
-    let current_dir = env::current_dir()
-        .unwrap_or_else(|_| std::path::PathBuf::from("."))
-        .to_string_lossy()
-        .to_string();
-    let repo = match find_repository_in_path(&current_dir) {
+    let repo = match find_repository(&Vec::<String>::new()) {
         Ok(repo) => repo,
         Err(e) => {
             eprintln!("Failed to find repository: {}", e);
@@ -932,7 +1684,7 @@ fn handle_ai_diff(args: &[String]) {
     };
     if let Err(e) = commands::diff::handle_diff(&repo, args) {
         eprintln!("Diff failed: {}", e);
-        std::process::exit(1);
+        std::process::exit(e.exit_code());
     }
 }
 
@@ -946,16 +1698,99 @@ fn handle_stats(args: &[String]) {
         }
     };
     // Parse stats-specific arguments
-    let mut json_output = false;
+    let mut output = OutputOptions::current();
+    let mut format: Option<StatsFormat> = None;
+    let mut by_file = false;
+    let mut detail = false;
+    let mut trailer = false;
+    let mut use_rollups = false;
+    let mut backfill = false;
     let mut commit_sha = None;
     let mut commit_range: Option<CommitRange> = None;
     let mut ignore_patterns: Vec<String> = Vec::new();
+    let mut save_baseline: Option<String> = None;
+    let mut baseline: Option<String> = None;
+    let mut list_baselines = false;
+    let mut coverage = false;
+    let mut whitespace: Option<WhitespaceMode> = None;
 
     let mut i = 0;
     while i < args.len() {
         match args[i].as_str() {
-            "--json" => {
-                json_output = true;
+            "--by-file" => {
+                by_file = true;
+                i += 1;
+            }
+            "--ignore-whitespace" => {
+                whitespace.get_or_insert(WhitespaceMode::Whitespace);
+                i += 1;
+            }
+            "--ignore-reindent" => {
+                whitespace = Some(WhitespaceMode::Reindent);
+                i += 1;
+            }
+            "--coverage" => {
+                coverage = true;
+                i += 1;
+            }
+            "--format" => {
+                let Some(value) = args.get(i + 1) else {
+                    eprintln!("--format requires one of: text, json, markdown");
+                    std::process::exit(1);
+                };
+                let Some(parsed) = StatsFormat::parse(value) else {
+                    eprintln!(
+                        "Unknown stats format '{}'. Expected text, json or markdown",
+                        value
+                    );
+                    std::process::exit(1);
+                };
+                format = Some(parsed);
+                i += 2;
+            }
+            "--detail" => {
+                detail = true;
+                i += 1;
+            }
+            "--trailer" => {
+                trailer = true;
+                i += 1;
+            }
+            "--range" => {
+                let Some(arg) = args.get(i + 1) else {
+                    eprintln!("--range requires a <commit>..<commit> argument");
+                    std::process::exit(1);
+                };
+                if commit_range.is_some() {
+                    eprintln!("Only one commit range may be given");
+                    std::process::exit(1);
+                }
+                if !arg.contains("..") {
+                    eprintln!("Invalid commit range format. Expected: <commit>..<commit>");
+                    std::process::exit(1);
+                }
+                commit_range = Some(parse_stats_commit_range(&repo, arg));
+                use_rollups = true;
+                i += 2;
+            }
+            "--backfill" => {
+                backfill = true;
+                i += 1;
+            }
+            "--save-baseline" | "--baseline" => {
+                let Some(name) = args.get(i + 1).filter(|name| !name.starts_with("--")) else {
+                    eprintln!("{} requires a baseline name", args[i]);
+                    std::process::exit(1);
+                };
+                if args[i] == "--save-baseline" {
+                    save_baseline = Some(name.clone());
+                } else {
+                    baseline = Some(name.clone());
+                }
+                i += 2;
+            }
+            "--list-baselines" => {
+                list_baselines = true;
                 i += 1;
             }
             "--ignore" => {
@@ -985,33 +1820,20 @@ fn handle_stats(args: &[String]) {
                     std::process::exit(1);
                 }
             }
+            flag if output.parse_flag(flag) => {
+                i += 1;
+            }
             _ => {
                 // First non-flag argument is treated as commit SHA or range
                 if commit_sha.is_none() {
                     let arg = &args[i];
                     // Check if this is a commit range (contains "..")
                     if arg.contains("..") {
-                        let parts: Vec<&str> = arg.split("..").collect();
-                        if parts.len() == 2 {
-                            match CommitRange::new_infer_refname(
-                                &repo,
-                                normalize_head_rev(parts[0]),
-                                normalize_head_rev(parts[1]),
-                                // @todo this is probably fine, but we might want to give users an option to override from this command.
-                                None,
-                            ) {
-                                Ok(range) => {
-                                    commit_range = Some(range);
-                                }
-                                Err(e) => {
-                                    eprintln!("Failed to create commit range: {}", e);
-                                    std::process::exit(1);
-                                }
-                            }
-                        } else {
-                            eprintln!("Invalid commit range format. Expected: <commit>..<commit>");
+                        if commit_range.is_some() {
+                            eprintln!("Only one commit range may be given");
                             std::process::exit(1);
                         }
+                        commit_range = Some(parse_stats_commit_range(&repo, arg));
                     } else {
                         commit_sha = Some(normalize_head_rev(arg));
                     }
@@ -1024,23 +1846,239 @@ fn handle_stats(args: &[String]) {
         }
     }
 
+    let format = match format {
+        Some(format) if output.is_json() && format != StatsFormat::Json => {
+            eprintln!("--json cannot be combined with --format text or markdown");
+            std::process::exit(1);
+        }
+        Some(format) => format,
+        None if output.is_json() => StatsFormat::Json,
+        None => StatsFormat::Text,
+    };
+    if format == StatsFormat::Json {
+        output.format = crate::commands::output::OutputFormat::Json;
+    }
+    let json_output = format == StatsFormat::Json;
+    let markdown = format == StatsFormat::Markdown;
+    if markdown && (coverage || trailer || backfill || list_baselines || save_baseline.is_some()) {
+        eprintln!(
+            "--format markdown cannot be combined with --coverage, --trailer, --backfill or baseline management"
+        );
+        std::process::exit(1);
+    }
+
     let effective_patterns = effective_ignore_patterns(&repo, &ignore_patterns, &[]);
 
+    let whitespace =
+        whitespace.unwrap_or_else(|| effective_workspace_config(&repo).whitespace_mode());
+
+    if list_baselines {
+        print_stats_baselines(&repo, output);
+        return;
+    }
+
+    if let Some(name) = save_baseline {
+        if commit_range.is_some() || baseline.is_some() || trailer || backfill {
+            eprintln!("--save-baseline only accepts an optional <commit>");
+            std::process::exit(1);
+        }
+        save_stats_baseline(
+            &repo,
+            &name,
+            commit_sha.as_deref().unwrap_or("HEAD"),
+            output,
+        );
+        return;
+    }
+
+    // `--baseline <name>` is `--range <baseline commit>..<commit>` over rollups.
+    if let Some(name) = baseline.as_deref() {
+        if commit_range.is_some() || trailer || backfill || by_file || detail {
+            eprintln!("--baseline only accepts an optional end <commit> and --json");
+            std::process::exit(1);
+        }
+        let Some(saved) = repo.storage.read_stats_baselines().remove(name) else {
+            eprintln!("No stats baseline named '{}'", name);
+            eprintln!("Run 'git ai stats --list-baselines' to see saved baselines.");
+            std::process::exit(1);
+        };
+        let end = commit_sha.take().unwrap_or_else(|| "HEAD".to_string());
+        commit_range = Some(parse_stats_commit_range(
+            &repo,
+            &format!("{}..{}", saved.commit_sha, end),
+        ));
+        use_rollups = true;
+        if !output.is_quiet() && !markdown {
+            println!(
+                "Since baseline '{}' ({}, saved {})",
+                name,
+                &saved.commit_sha[..saved.commit_sha.len().min(8)],
+                format_local_timestamp(saved.created_at)
+            );
+        }
+    }
+
+    if coverage {
+        if trailer || by_file || detail || backfill {
+            eprintln!(
+                "--coverage cannot be combined with --trailer, --by-file, --detail or --backfill"
+            );
+            std::process::exit(1);
+        }
+        let commit_shas: Vec<String> = match commit_range {
+            // rev-list order is newest first; report oldest first.
+            Some(range) => {
+                let mut shas: Vec<String> = range.into_iter().map(|c| c.id().to_string()).collect();
+                shas.reverse();
+                shas
+            }
+            None => {
+                let rev = commit_sha.as_deref().unwrap_or("HEAD");
+                match repo.revparse_single(&format!("{}^{{commit}}", rev)) {
+                    Ok(object) => vec![object.id()],
+                    Err(e) => {
+                        eprintln!("Failed to resolve {}: {}", rev, e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+        };
+        match coverage::coverage_for_commits(&repo, &commit_shas, &effective_patterns, whitespace) {
+            Ok(report) => {
+                if json_output {
+                    let _ = output.print_json(&report);
+                } else {
+                    coverage::print_coverage_report(&report);
+                }
+            }
+            Err(e) => output.exit_with_error(&e),
+        }
+        return;
+    }
+
+    if trailer {
+        if json_output || by_file || backfill || commit_range.is_some() {
+            eprintln!(
+                "--trailer cannot be combined with --json, --by-file, --backfill or a commit range"
+            );
+            std::process::exit(1);
+        }
+        // Without a rev, describe the commit that is about to be made from the index.
+        let result = match commit_sha.as_deref() {
+            Some(sha) => stats_trailer_command(&repo, Some(sha), &effective_patterns, whitespace),
+            None => crate::commands::commit::staged_attribution_summary(&repo)
+                .map(|summary| println!("{}", summary.trailer())),
+        };
+        if let Err(e) = result {
+            output.exit_with_error(&e);
+        }
+        return;
+    }
+
+    if backfill {
+        if json_output || by_file || use_rollups || commit_sha.is_some() {
+            eprintln!("--backfill only accepts an optional <commit>..<commit> range");
+            std::process::exit(1);
+        }
+        let result = stats_rollup::commits_to_backfill(&repo, commit_range).and_then(|shas| {
+            stats_rollup::rollup_stats_for_commits(&repo, &shas, &effective_patterns, whitespace)
+        });
+        match result {
+            Ok(summary) => {
+                if !output.is_quiet() {
+                    eprintln!(
+                        "Backfilled stats rollups for {} commit(s) ({} already current).",
+                        summary.computed, summary.cached
+                    );
+                }
+            }
+            Err(e) => output.exit_with_error(&e),
+        }
+        return;
+    }
+
+    // `--range` sums per-commit rollups instead of diffing the range as one squash.
+    if use_rollups && let Some(range) = commit_range {
+        exit_if_range_breakdown_without_markdown(by_file, detail, markdown);
+        let title = range_markdown_title(&range);
+        let commit_shas: Vec<String> = range.into_iter().map(|c| c.id().to_string()).collect();
+        match stats_rollup::rollup_stats_for_commits(
+            &repo,
+            &commit_shas,
+            &effective_patterns,
+            whitespace,
+        ) {
+            Ok(summary) => {
+                if markdown {
+                    let title = format!("{} ({} commits)", title, summary.commits);
+                    match range_markdown(
+                        &repo,
+                        &title,
+                        &summary.stats,
+                        &commit_shas,
+                        &effective_patterns,
+                        whitespace,
+                        detail,
+                    ) {
+                        Ok(markdown) => print!("{}", markdown),
+                        Err(e) => output.exit_with_error(&e),
+                    }
+                } else if json_output {
+                    let _ = output.print_json(&summary);
+                } else {
+                    write_stats_to_terminal(&summary.stats, true);
+                    println!(
+                        "  {} commits ({} from rollups, {} computed)",
+                        summary.commits, summary.cached, summary.computed
+                    );
+                }
+            }
+            Err(e) => output.exit_with_error(&e),
+        }
+        return;
+    }
+
     // Handle commit range if detected
     if let Some(range) = commit_range {
-        match range_authorship::range_authorship(range, false, &effective_patterns, None) {
+        exit_if_range_breakdown_without_markdown(by_file, detail, markdown);
+        let title = range_markdown_title(&range);
+        let commit_shas: Vec<String> = if markdown {
+            range
+                .clone()
+                .into_iter()
+                .map(|c| c.id().to_string())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        match range_authorship::range_authorship(
+            range,
+            false,
+            &effective_patterns,
+            whitespace,
+            None,
+        ) {
             Ok(stats) => {
-                if json_output {
-                    let json_str = serde_json::to_string(&stats).unwrap();
-                    println!("{}", json_str);
+                if markdown {
+                    match range_markdown(
+                        &repo,
+                        &title,
+                        &stats.range_stats,
+                        &commit_shas,
+                        &effective_patterns,
+                        whitespace,
+                        detail,
+                    ) {
+                        Ok(markdown) => print!("{}", markdown),
+                        Err(e) => output.exit_with_error(&e),
+                    }
+                } else if json_output {
+                    let _ = output.print_json(&stats);
                 } else {
                     range_authorship::print_range_authorship_stats(&stats);
                 }
             }
-            Err(e) => {
-                eprintln!("Range authorship failed: {}", e);
-                std::process::exit(1);
-            }
+            Err(e) => output.exit_with_error(&e),
         }
         return;
     }
@@ -1048,21 +2086,133 @@ fn handle_stats(args: &[String]) {
     if let Err(e) = stats_command(
         &repo,
         commit_sha.as_deref(),
-        json_output,
+        format,
+        by_file,
+        detail,
         &effective_patterns,
+        whitespace,
     ) {
-        match e {
-            crate::error::GitAiError::Generic(msg) if msg.starts_with("No commit found:") => {
+        match &e {
+            GitAiError::Generic(msg) if msg.starts_with("No commit found:") => {
                 eprintln!("{}", msg);
+                std::process::exit(1);
             }
-            _ => {
-                eprintln!("Stats failed: {}", e);
-            }
+            _ => output.exit_with_error(&e),
         }
+    }
+}
+
+fn save_stats_baseline(repo: &Repository, name: &str, rev: &str, output: OutputOptions) {
+    let commit_sha = match repo.revparse_single(&format!("{}^{{commit}}", rev)) {
+        Ok(object) => object.id(),
+        Err(e) => {
+            eprintln!("Failed to resolve {}: {}", rev, e);
+            std::process::exit(1);
+        }
+    };
+    let baseline = crate::git::repo_storage::StatsBaseline {
+        commit_sha,
+        created_at: chrono::Utc::now().timestamp(),
+    };
+    if let Err(e) = repo.storage.write_stats_baseline(name, &baseline) {
+        output.exit_with_error(&e);
+    }
+    if output.is_json() {
+        let saved = serde_json::json!({
+            "name": name,
+            "commit_sha": baseline.commit_sha,
+            "created_at": baseline.created_at,
+        });
+        let _ = output.print_json(&saved);
+    } else if !output.is_quiet() {
+        println!(
+            "Saved stats baseline '{}' at {}",
+            name,
+            &baseline.commit_sha[..8]
+        );
+    }
+}
+
+fn print_stats_baselines(repo: &Repository, output: OutputOptions) {
+    let baselines = repo.storage.read_stats_baselines();
+    if output.is_json() {
+        let _ = output.print_json(&baselines);
+        return;
+    }
+    if baselines.is_empty() {
+        println!("No stats baselines saved");
+        return;
+    }
+    for (name, baseline) in &baselines {
+        println!(
+            "{}  {}  saved {}",
+            name,
+            &baseline.commit_sha[..baseline.commit_sha.len().min(8)],
+            format_local_timestamp(baseline.created_at)
+        );
+    }
+}
+
+/// `--by-file` and `--detail` break a range down only in the markdown report.
+fn exit_if_range_breakdown_without_markdown(by_file: bool, detail: bool, markdown: bool) {
+    if (by_file || detail) && !markdown {
+        eprintln!(
+            "{} is only supported for a single commit or with --format markdown",
+            if by_file { "--by-file" } else { "--detail" }
+        );
         std::process::exit(1);
     }
 }
 
+/// `` `abcd1234..ef567890` `` heading for a range in `stats --format markdown`.
+fn range_markdown_title(range: &CommitRange) -> String {
+    let short = |sha: &str| sha[..sha.len().min(8)].to_string();
+    format!("`{}..{}`", short(&range.start_oid), short(&range.end_oid))
+}
+
+/// The markdown report for a range, with its sections summed over `commit_shas`.
+fn range_markdown(
+    repo: &Repository,
+    title: &str,
+    stats: &CommitStats,
+    commit_shas: &[String],
+    ignore_patterns: &[String],
+    whitespace: WhitespaceMode,
+    detail: bool,
+) -> Result<String, GitAiError> {
+    let sections = range_report_sections(repo, commit_shas, ignore_patterns, whitespace, detail)?;
+    Ok(write_stats_report_markdown(
+        title,
+        stats,
+        Some(&sections.files),
+        sections.labels.as_deref(),
+        sections.subagents.as_ref(),
+        sections.tool_versions.as_ref(),
+    ))
+}
+
+/// Parse a `<commit>..<commit>` argument for `stats`, exiting on invalid input.
+fn parse_stats_commit_range<'a>(repo: &'a Repository, arg: &str) -> CommitRange<'a> {
+    let parts: Vec<&str> = arg.split("..").collect();
+    if parts.len() != 2 {
+        eprintln!("Invalid commit range format. Expected: <commit>..<commit>");
+        std::process::exit(1);
+    }
+    match CommitRange::new_infer_refname(
+        repo,
+        normalize_head_rev(parts[0]),
+        normalize_head_rev(parts[1]),
+        // @todo this is probably fine, but we might want to give users an option to override from this command.
+        None,
+    ) {
+        Ok(range) => range,
+        Err(e) => {
+            eprintln!("Failed to create commit range: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
 /// Normalise a revision token that the user may have typed with a lowercase
 /// "head" prefix.  On case-insensitive file systems (macOS) git accepts both
 /// "head" and "HEAD", but in a linked worktree "head" can resolve to the
@@ -1116,6 +2266,10 @@ fn handle_git_hooks(args: &[String]) {
         }
         _ => {
             eprintln!("The git core hooks feature has been sunset.");
+            eprintln!(
+                "Attribution is captured through the git wrapper and trace2, so repositories"
+            );
+            eprintln!("with a local core.hooksPath (e.g. Husky) need no hook shims.");
             eprintln!("Usage: git-ai git-hooks remove");
             std::process::exit(1);
         }
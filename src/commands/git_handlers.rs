@@ -1,6 +1,6 @@
 use crate::commands::git_hook_handlers::ENV_SKIP_MANAGED_HOOKS;
 use crate::config;
-use crate::git::cli_parser::{ParsedGitInvocation, parse_git_cli_args};
+use crate::git::cli_parser::{ParsedGitInvocation, git_ai_subcommand_args, parse_git_cli_args};
 use crate::git::find_repository;
 use crate::git::repository::Repository;
 #[cfg(windows)]
@@ -61,8 +61,30 @@ pub fn handle_git(args: &[String]) {
         return;
     }
 
-    let parsed = parse_git_cli_args(args);
+    let parsed = {
+        let _span =
+            tracing::debug_span!(target: crate::observability::trace::targets::HOOKS, "parse")
+                .entered();
+        parse_git_cli_args(args)
+    };
+
+    // `git ai ...` runs in-process: it works without `git-ai` on PATH, and
+    // `git ai --help` / `git help ai` are not turned into man page lookups.
+    if let Some(git_ai_args) = git_ai_subcommand_args(&parsed) {
+        crate::commands::git_ai_handlers::handle_git_ai(&git_ai_args);
+        std::process::exit(0);
+    }
 
+    exit_with_status(run_git_command(args, &parsed));
+}
+
+/// Run a wrapped (non-`git ai`) git command: git itself plus the wrapper's
+/// pre- and post-command work. Returns git's exit status instead of exiting,
+/// so `git-ai profile` can report on the run.
+pub(crate) fn run_git_command(
+    args: &[String],
+    parsed: &ParsedGitInvocation,
+) -> std::process::ExitStatus {
     let is_read_only = parsed.command.as_deref().is_some_and(|cmd| {
         crate::git::command_classification::is_definitely_read_only_git_invocation(
             cmd,
@@ -71,12 +93,54 @@ pub fn handle_git(args: &[String]) {
     });
 
     if is_read_only {
-        let exit_status = proxy_to_git(args, false);
-        exit_with_status(exit_status);
+        let _span = tracing::info_span!(
+            target: crate::observability::trace::targets::HOOKS,
+            "git",
+            command = parsed.command.as_deref().unwrap_or("")
+        )
+        .entered();
+        return proxy_to_git(args, false);
     }
 
-    let repository = find_repository(&parsed.global_args).ok();
-    let exit_status = proxy_to_git(args, false);
+    let repository = {
+        let _span =
+            tracing::debug_span!(target: crate::observability::trace::targets::HOOKS, "repository")
+                .entered();
+        find_repository(&parsed.global_args).ok()
+    };
+    {
+        let _span = tracing::debug_span!(
+            target: crate::observability::trace::targets::HOOKS,
+            "pre_command"
+        )
+        .entered();
+        if parsed.command.as_deref() == Some("clean")
+            && let Some(repo) = repository.as_ref()
+        {
+            archive_attribution_before_clean(parsed, repo);
+        }
+        if parsed.command.as_deref() == Some("commit")
+            && let Some(repo) = repository.as_ref()
+            && !crate::authorship::ai_secret_scan::check_before_commit(repo, &parsed.command_args)
+        {
+            std::process::exit(crate::error::ErrorCategory::Policy.exit_code());
+        }
+    }
+    let exit_status = {
+        let _span = tracing::info_span!(
+            target: crate::observability::trace::targets::HOOKS,
+            "git",
+            command = parsed.command.as_deref().unwrap_or("")
+        )
+        .entered();
+        let exit_status = proxy_to_git(args, false);
+        tracing::debug!(
+            target: crate::observability::trace::targets::HOOKS,
+            code = exit_status.code().unwrap_or(-1),
+            "git exited"
+        );
+        exit_status
+    };
 
     // After a successful commit, wait briefly for the daemon to produce an
     // authorship note so we can show stats inline (same UX as plain wrapper mode).
@@ -84,10 +148,15 @@ pub fn handle_git(args: &[String]) {
         && parsed.command.as_deref() == Some("commit")
         && let Some(repo) = repository.as_ref()
     {
-        maybe_show_async_post_commit_stats(&parsed, repo);
+        let _span = tracing::debug_span!(
+            target: crate::observability::trace::targets::HOOKS,
+            "post_command"
+        )
+        .entered();
+        maybe_show_async_post_commit_stats(parsed, repo);
     }
 
-    exit_with_status(exit_status);
+    exit_status
 }
 
 #[cfg(feature = "test-support")]
@@ -195,18 +264,32 @@ fn parse_alias_tokens(value: &str) -> Option<Vec<String>> {
     Some(tokens)
 }
 
+/// Archive pending attribution for attributed untracked files `git clean` is
+/// about to delete. Failures are logged and never block the clean itself.
+fn archive_attribution_before_clean(parsed: &ParsedGitInvocation, repo: &Repository) {
+    match crate::authorship::clean_archive::archive_before_clean(repo, &parsed.command_args) {
+        Ok(Some(manifest)) => eprintln!(
+            "[git-ai] Archived AI attribution for {} file(s) removed by git clean. Run `git ai restore-attribution` to recover them.",
+            manifest.files.len()
+        ),
+        Ok(None) => {}
+        Err(e) => tracing::debug!("Failed to archive attribution before git clean: {}", e),
+    }
+}
+
 /// In async (wrapper-to-daemon) mode, after a successful `git commit`, poll for
 /// the daemon-produced authorship note and display stats inline when available.
 /// Mirrors the same skip/display rules as plain wrapper mode in post_commit.rs.
 fn maybe_show_async_post_commit_stats(parsed: &ParsedGitInvocation, repo: &Repository) {
     use crate::authorship::ignore::effective_ignore_patterns;
     use crate::authorship::stats::{stats_for_commit_stats, write_stats_to_terminal};
-    use crate::git::cli_parser::is_dry_run;
+    use crate::git::cli_parser::commit_creates_commit;
     use crate::git::notes_api::read_note;
+    use crate::workspace_config::effective_workspace_config;
     use std::io::IsTerminal;
 
     // Respect the same suppression flags as the synchronous wrapper path.
-    if is_dry_run(&parsed.command_args) {
+    if !commit_creates_commit(&parsed.command_args) {
         return;
     }
     let suppress_output = parsed.has_command_flag("--porcelain")
@@ -292,7 +375,8 @@ fn maybe_show_async_post_commit_stats(parsed: &ParsedGitInvocation, repo: &Repos
     }
 
     // Compute and display the full stats.
-    if let Ok(stats) = stats_for_commit_stats(repo, &commit_sha, &ignore_patterns) {
+    let whitespace = effective_workspace_config(repo).whitespace_mode();
+    if let Ok(stats) = stats_for_commit_stats(repo, &commit_sha, &ignore_patterns, whitespace) {
         write_stats_to_terminal(&stats, true);
     }
 }
@@ -433,7 +517,7 @@ fn proxy_to_git(args: &[String], exit_on_completion: bool) -> std::process::Exit
 }
 
 // Exit mirroring the child's termination: same signal if signaled, else exit code
-fn exit_with_status(status: std::process::ExitStatus) -> ! {
+pub(crate) fn exit_with_status(status: std::process::ExitStatus) -> ! {
     #[cfg(unix)]
     {
         if let Some(sig) = status.signal() {
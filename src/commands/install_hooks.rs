@@ -1,12 +1,15 @@
+use crate::commands::output::OutputOptions;
 use crate::config;
 use crate::daemon::DaemonConfig;
 use crate::error::GitAiError;
 use crate::mdm::agents::get_all_installers;
+use crate::mdm::hook_binary;
 use crate::mdm::hook_installer::HookInstallerParams;
 use crate::mdm::skills_installer;
 use crate::mdm::spinner::{Spinner, print_diff};
 use crate::mdm::utils::get_current_binary_path;
-use std::collections::{HashMap, HashSet};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
@@ -14,16 +17,29 @@ use std::process::{Command, Stdio};
 const TRACE2_EVENT_TARGET_KEY: &str = "trace2.eventTarget";
 const TRACE2_EVENT_NESTING_KEY: &str = "trace2.eventNesting";
 const TRACE2_EVENT_NESTING_VALUE: &str = "0";
+/// Merge driver section written by earlier installs; uninstall still removes it.
+const LEGACY_NOTES_MERGE_DRIVER_SECTION: &str = "merge.git-ai-notes";
 const VISUAL_STUDIO_INSTALLER_ID: &str = "visual-studio";
+const COPILOT_AGENT_INSTALLER_ID: &str = "copilot-agent";
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 struct InstallOptions {
     dry_run: bool,
+    plan_json: bool,
     verbose: bool,
     install_skills: bool,
     include_visual_studio_extension: bool,
+    include_copilot_agent: bool,
     api_base: Option<String>,
     api_key: Option<String>,
+    output: OutputOptions,
+}
+
+impl InstallOptions {
+    /// Whether config diffs are printed alongside each agent's status.
+    fn shows_diffs(&self) -> bool {
+        (self.verbose || self.dry_run) && !self.output.is_quiet()
+    }
 }
 
 /// Installation status for a tool
@@ -119,7 +135,7 @@ pub fn to_hashmap(statuses: HashMap<String, InstallStatus>) -> HashMap<String, S
 }
 
 fn print_amp_plugins_note(installer_id: &str) {
-    if installer_id == "amp" {
+    if installer_id == "amp" && !OutputOptions::current().is_quiet() {
         println!("  Note: Amp plugins are experimental. Run amp with `PLUGINS=all amp`.");
     }
 }
@@ -211,7 +227,7 @@ fn set_global_git_config_value(git_cmd: &str, key: &str, value: &str) -> Result<
     if status.success() {
         Ok(())
     } else {
-        Err(GitAiError::Generic(format!(
+        Err(GitAiError::Hook(format!(
             "failed to set global git config key '{}'",
             key
         )))
@@ -246,7 +262,7 @@ fn remove_global_git_config_section(git_cmd: &str, section: &str) -> Result<(),
     if status.success() || status.code() == Some(128) {
         Ok(())
     } else {
-        Err(GitAiError::Generic(format!(
+        Err(GitAiError::Hook(format!(
             "failed to remove global git config section '{}'",
             section
         )))
@@ -325,6 +341,22 @@ pub fn run(args: &[String]) -> Result<HashMap<String, String>, GitAiError> {
         }),
     };
 
+    if options.plan_json {
+        let plan = build_install_plan(&options, &install_config)?;
+        println!("{}", serde_json::to_string_pretty(&plan)?);
+        return Ok(plan
+            .agents
+            .into_iter()
+            .map(|agent| (agent.id, agent.status))
+            .collect());
+    }
+    if options.dry_run && !options.output.is_quiet() {
+        match plan_settings(&install_config) {
+            Ok(settings) => print_settings_plan(&settings),
+            Err(e) => eprintln!("Warning: could not plan global settings: {e}"),
+        }
+    }
+
     // Daemon trace2 config must be in place before any install work starts.
     // Non-fatal: the global git config may be read-only (e.g. Nix store symlink).
     if let Err(e) = configure_daemon_trace2(options.dry_run) {
@@ -341,7 +373,20 @@ pub fn run(args: &[String]) -> Result<HashMap<String, String>, GitAiError> {
     // Get absolute path to the current binary
     let binary_path = get_current_binary_path()?;
     persist_install_config_with_values(&binary_path, options.dry_run, &install_config)?;
-    let params = HookInstallerParams { binary_path };
+    // Like the daemon, the shared link is left alone inside test harnesses.
+    let in_test_harness = std::env::var_os("GIT_AI_TEST_DB_PATH").is_some()
+        || std::env::var_os("GITAI_TEST_DB_PATH").is_some();
+    if !options.dry_run
+        && !in_test_harness
+        && let Some(install_dir) = binary_path.parent()
+        && let Err(e) = hook_binary::update_current_link(install_dir)
+    {
+        eprintln!("Warning: could not update ~/.git-ai/bin/current (non-fatal): {e}");
+    }
+    let hook_target = hook_binary::stable_binary_path(&binary_path);
+    let params = HookInstallerParams {
+        binary_path: resolve_hook_binary_path(&hook_target),
+    };
 
     // Run async operations and convert result.
     let statuses = crate::tokio_runtime::block_on(async_run_install(&params, &options))?;
@@ -352,19 +397,32 @@ pub fn run(args: &[String]) -> Result<HashMap<String, String>, GitAiError> {
         cleanup_legacy_envelope_logs();
     }
 
-    Ok(to_hashmap(statuses))
+    let statuses = to_hashmap(statuses);
+    if options.output.is_json() {
+        options
+            .output
+            .print_json(&statuses.iter().collect::<BTreeMap<_, _>>())?;
+    }
+    Ok(statuses)
 }
 
 fn parse_install_options(args: &[String]) -> Result<InstallOptions, GitAiError> {
+    let mut output = OutputOptions::current();
     let mut options = InstallOptions::default();
 
     let mut args = args.iter();
     while let Some(arg) = args.next() {
         match arg.as_str() {
+            flag if output.parse_flag(flag) => {}
             "--dry-run" | "--dry-run=true" => options.dry_run = true,
-            "--verbose" | "-v" => options.verbose = true,
+            "--plan-json" => {
+                options.dry_run = true;
+                options.plan_json = true;
+            }
+            "-v" => options.verbose = true,
             "--skills" => options.install_skills = true,
             "--visual-studio-extension" => options.include_visual_studio_extension = true,
+            "--copilot-agent" => options.include_copilot_agent = true,
             value if value.starts_with("--api-base=") => {
                 options.api_base = non_empty_value(&value[11..]);
             }
@@ -386,6 +444,8 @@ fn parse_install_options(args: &[String]) -> Result<InstallOptions, GitAiError>
             _ => {}
         }
     }
+    options.verbose |= output.is_verbose();
+    options.output = output;
 
     Ok(options)
 }
@@ -396,7 +456,11 @@ fn non_empty_value(value: &str) -> Option<String> {
 }
 
 fn should_include_installer(id: &str, options: &InstallOptions) -> bool {
-    options.include_visual_studio_extension || id != VISUAL_STUDIO_INSTALLER_ID
+    match id {
+        VISUAL_STUDIO_INSTALLER_ID => options.include_visual_studio_extension,
+        COPILOT_AGENT_INSTALLER_ID => options.include_copilot_agent,
+        _ => true,
+    }
 }
 
 #[derive(Default)]
@@ -427,48 +491,70 @@ fn persist_install_config_with_values(
         return Ok(false);
     }
 
-    let api_base = &install_config.api_base;
-    let api_key = &install_config.api_key;
-
-    if api_base.is_none() && api_key.is_none() {
+    if install_config.api_base.is_none() && install_config.api_key.is_none() {
         return Ok(false);
     }
 
     let mut file_config = crate::config::load_file_config_public().map_err(GitAiError::Generic)?;
-    let mut changed = false;
+    if apply_install_config_values(&mut file_config, binary_path, install_config).is_empty() {
+        return Ok(false);
+    }
+
+    crate::config::save_file_config(&file_config).map_err(GitAiError::Generic)?;
+    Ok(true)
+}
+
+/// Apply `install_config` to `file_config`, returning each setting it changed.
+/// API keys are masked in the returned values.
+fn apply_install_config_values(
+    file_config: &mut crate::config::FileConfig,
+    binary_path: &Path,
+    install_config: &InstallConfig,
+) -> Vec<PlannedSetting> {
+    let mut changes = Vec::new();
 
-    if let Some(api_base) = api_base
+    if let Some(api_base) = &install_config.api_base
         && file_config.api_base_url.as_deref() != Some(api_base.as_str())
     {
+        changes.push(PlannedSetting {
+            key: "api_base_url".to_string(),
+            current: file_config.api_base_url.clone(),
+            planned: Some(api_base.clone()),
+        });
         file_config.api_base_url = Some(api_base.clone());
-        changed = true;
     }
 
-    if let Some(api_key) = api_key
+    if let Some(api_key) = &install_config.api_key
         && file_config.api_key.as_deref() != Some(api_key.as_str())
     {
+        changes.push(PlannedSetting {
+            key: "api_key".to_string(),
+            current: file_config
+                .api_key
+                .as_ref()
+                .map(|_| MASKED_VALUE.to_string()),
+            planned: Some(MASKED_VALUE.to_string()),
+        });
         file_config.api_key = Some(api_key.clone());
-        changed = true;
     }
 
-    if api_base.is_some() {
+    if install_config.api_base.is_some() {
         let git_path_missing = file_config
             .git_path
             .as_ref()
             .map(|value| value.trim().is_empty())
             .unwrap_or(true);
         if git_path_missing && let Some(git_path) = detect_install_git_path(binary_path) {
+            changes.push(PlannedSetting {
+                key: "git_path".to_string(),
+                current: file_config.git_path.clone(),
+                planned: Some(git_path.clone()),
+            });
             file_config.git_path = Some(git_path);
-            changed = true;
         }
     }
 
-    if !changed {
-        return Ok(false);
-    }
-
-    crate::config::save_file_config(&file_config).map_err(GitAiError::Generic)?;
-    Ok(true)
+    changes
 }
 
 fn detect_install_git_path(binary_path: &Path) -> Option<String> {
@@ -491,6 +577,284 @@ fn detect_install_git_path(binary_path: &Path) -> Option<String> {
     }
 }
 
+/// Agent hooks run through the checkpoint-only `git-ai-hook` binary when it is installed next to
+/// `git-ai` (built with the `hooks-only` feature); otherwise they use `git-ai` itself.
+fn resolve_hook_binary_path(binary_path: &Path) -> PathBuf {
+    let hook_binary =
+        binary_path.with_file_name(format!("git-ai-hook{}", std::env::consts::EXE_SUFFIX));
+    if hook_binary.is_file() {
+        hook_binary
+    } else {
+        binary_path.to_path_buf()
+    }
+}
+
+// ============================================================================
+// Install Plan (--dry-run / --plan-json)
+// ============================================================================
+
+/// Shown instead of API keys in plans.
+const MASKED_VALUE: &str = "********";
+
+/// Global git config that install-hooks reads but never changes.
+const PRESERVED_GIT_CONFIG_KEYS: &[&str] = &["core.hooksPath"];
+
+/// A setting install-hooks would change. `planned: None` means it is removed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct PlannedSetting {
+    key: String,
+    current: Option<String>,
+    planned: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct PreservedSetting {
+    key: String,
+    value: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+struct SettingsPlan {
+    /// Global git config (`git config --global`).
+    git_config: Vec<PlannedSetting>,
+    /// Global git config left as it is, such as an existing `core.hooksPath`.
+    preserved_git_config: Vec<PreservedSetting>,
+    /// The git-ai config file.
+    config_file: Vec<PlannedSetting>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct PlannedAgentChange {
+    id: String,
+    name: String,
+    status: String,
+    /// Unified diffs of the settings files that would be edited.
+    diffs: Vec<String>,
+    messages: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct InstallPlan {
+    binary_path: String,
+    hook_binary_path: String,
+    #[serde(flatten)]
+    settings: SettingsPlan,
+    agents: Vec<PlannedAgentChange>,
+    skills_changed: bool,
+    restarts_background_service: bool,
+}
+
+fn global_git_config_entries(git_cmd: &str) -> Vec<(String, String)> {
+    let mut command = Command::new(git_cmd);
+    command
+        .args(["config", "--global", "--list"])
+        .stderr(Stdio::null());
+    crate::git::repository::apply_internal_git_env(&mut command);
+    let Ok(output) = command.output() else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Global git config and git-ai config changes install-hooks would make.
+fn plan_settings(install_config: &InstallConfig) -> Result<SettingsPlan, GitAiError> {
+    let runtime_config = config::Config::fresh();
+    let existing = global_git_config_entries(runtime_config.git_cmd());
+    // `git config --list` lowercases section and variable names.
+    let current = |key: &str| {
+        existing
+            .iter()
+            .rev()
+            .find(|(existing_key, _)| existing_key.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value.clone())
+    };
+
+    let event_target = DaemonConfig::from_env_or_default_paths()?.trace2_event_target();
+    let desired = [
+        (TRACE2_EVENT_TARGET_KEY.to_string(), event_target),
+        (
+            TRACE2_EVENT_NESTING_KEY.to_string(),
+            TRACE2_EVENT_NESTING_VALUE.to_string(),
+        ),
+    ];
+
+    let mut plan = SettingsPlan::default();
+    // The whole trace2 section is reset, so other trace2 keys are removed.
+    let mut removed: Vec<&str> = existing
+        .iter()
+        .map(|(key, _)| key.as_str())
+        .filter(|key| key.starts_with("trace2."))
+        .filter(|key| !desired.iter().any(|(d, _)| d.eq_ignore_ascii_case(key)))
+        .collect();
+    removed.sort_unstable();
+    removed.dedup();
+    for key in removed {
+        plan.git_config.push(PlannedSetting {
+            key: key.to_string(),
+            current: current(key),
+            planned: None,
+        });
+    }
+    for (key, value) in desired {
+        let current = current(&key);
+        if current.as_deref() != Some(value.as_str()) {
+            plan.git_config.push(PlannedSetting {
+                key,
+                current,
+                planned: Some(value),
+            });
+        }
+    }
+    for key in PRESERVED_GIT_CONFIG_KEYS {
+        if let Some(value) = current(key) {
+            plan.preserved_git_config.push(PreservedSetting {
+                key: key.to_string(),
+                value,
+            });
+        }
+    }
+
+    if install_config.api_base.is_some() || install_config.api_key.is_some() {
+        let mut file_config =
+            crate::config::load_file_config_public().map_err(GitAiError::Generic)?;
+        plan.config_file =
+            apply_install_config_values(&mut file_config, binary_path, install_config);
+    }
+    Ok(plan)
+}
+
+/// What each detected agent's installer would change, without printing or
+/// writing anything.
+fn plan_agent_changes(
+    params: &HookInstallerParams,
+    options: &InstallOptions,
+) -> (Vec<PlannedAgentChange>, HashSet<String>) {
+    let mut agents = Vec::new();
+    let mut installed_tools = HashSet::new();
+    for installer in get_all_installers() {
+        let id = installer.id();
+        if !should_include_installer(id, options) {
+            continue;
+        }
+        let mut change = PlannedAgentChange {
+            id: id.to_string(),
+            name: installer.name().to_string(),
+            status: InstallStatus::AlreadyInstalled.as_str().to_string(),
+            diffs: Vec::new(),
+            messages: Vec::new(),
+        };
+        match installer.check_hooks(params) {
+            Ok(check) if !check.tool_installed => {
+                change.status = InstallStatus::NotFound.as_str().to_string();
+                agents.push(change);
+                continue;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                change.status = InstallStatus::Failed.as_str().to_string();
+                change.messages.push(e.to_string());
+                agents.push(change);
+                continue;
+            }
+        }
+        installed_tools.insert(id.to_string());
+
+        let mut changed = false;
+        if installer.uses_config_hooks() {
+            match installer.install_hooks(params, true) {
+                Ok(Some(diff)) => {
+                    changed = true;
+                    change.diffs.push(diff);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    change.status = InstallStatus::Failed.as_str().to_string();
+                    change.messages.push(e.to_string());
+                    agents.push(change);
+                    continue;
+                }
+            }
+        }
+        match installer.install_extras(params, true) {
+            Ok(results) => {
+                for result in results {
+                    changed |= result.changed;
+                    change.messages.push(result.message);
+                    change.diffs.extend(result.diff);
+                }
+            }
+            Err(e) => change.messages.push(format!("Extras install error: {}", e)),
+        }
+        if changed {
+            change.status = InstallStatus::Installed.as_str().to_string();
+        }
+        agents.push(change);
+    }
+    (agents, installed_tools)
+}
+
+fn build_install_plan(
+    options: &InstallOptions,
+    install_config: &InstallConfig,
+) -> Result<InstallPlan, GitAiError> {
+    let binary_path = get_current_binary_path()?;
+    let hook_target = hook_binary::stable_binary_path(&binary_path);
+    let params = HookInstallerParams {
+        binary_path: resolve_hook_binary_path(&hook_target),
+    };
+    let settings = plan_settings(install_config)?;
+    let (agents, installed_tools) = plan_agent_changes(&params, options);
+    let skills = if options.install_skills {
+        skills_installer::install_skills(true, false, &installed_tools)
+    } else {
+        skills_installer::uninstall_skills(true, false)
+    };
+    let in_test_harness = std::env::var_os("GIT_AI_TEST_DB_PATH").is_some()
+        || std::env::var_os("GITAI_TEST_DB_PATH").is_some();
+
+    Ok(InstallPlan {
+        binary_path: binary_path.display().to_string(),
+        hook_binary_path: params.binary_path.display().to_string(),
+        settings,
+        agents,
+        skills_changed: skills.is_ok_and(|result| result.changed),
+        restarts_background_service: !in_test_harness,
+    })
+}
+
+fn print_settings_plan(plan: &SettingsPlan) {
+    let describe = |setting: &PlannedSetting| match (&setting.current, &setting.planned) {
+        (_, None) => format!("  - {} (removed)", setting.key),
+        (None, Some(planned)) => format!("  + {} = {}", setting.key, planned),
+        (Some(current), Some(planned)) => {
+            format!("  ~ {} = {} (was {})", setting.key, planned, current)
+        }
+    };
+
+    println!("\n\x1b[1mGlobal git config\x1b[0m");
+    if plan.git_config.is_empty() {
+        println!("  (no changes)");
+    }
+    for setting in &plan.git_config {
+        println!("{}", describe(setting));
+    }
+    for preserved in &plan.preserved_git_config {
+        println!("  = {} = {} (preserved)", preserved.key, preserved.value);
+    }
+    if !plan.config_file.is_empty() {
+        println!("\n\x1b[1mgit-ai config\x1b[0m");
+        for setting in &plan.config_file {
+            println!("{}", describe(setting));
+        }
+    }
+}
+
 #[cfg(windows)]
 fn parse_git_og_cmd_path(contents: &str) -> Option<String> {
     contents.lines().find_map(|line| {
@@ -504,24 +868,42 @@ fn parse_git_og_cmd_path(contents: &str) -> Option<String> {
 /// Main entry point for uninstall-hooks command
 pub fn run_uninstall(args: &[String]) -> Result<HashMap<String, String>, GitAiError> {
     // Parse flags
+    let mut output = OutputOptions::current();
     let mut dry_run = false;
     let mut verbose = false;
     for arg in args {
+        if output.parse_flag(arg) {
+            continue;
+        }
         if arg == "--dry-run" || arg == "--dry-run=true" {
             dry_run = true;
         }
-        if arg == "--verbose" || arg == "-v" {
+        if arg == "-v" {
             verbose = true;
         }
     }
+    let verbose = (verbose || output.is_verbose()) && !output.is_quiet();
 
     // Get absolute path to the current binary
     let binary_path = get_current_binary_path()?;
     let params = HookInstallerParams { binary_path };
 
+    if !dry_run
+        && let Err(e) = remove_global_git_config_section(
+            config::Config::fresh().git_cmd(),
+            LEGACY_NOTES_MERGE_DRIVER_SECTION,
+        )
+    {
+        eprintln!("Warning: could not remove notes merge driver (non-fatal): {e}");
+    }
+
     // Run async operations and convert result.
     let statuses = crate::tokio_runtime::block_on(async_run_uninstall(&params, dry_run, verbose))?;
-    Ok(to_hashmap(statuses))
+    let statuses = to_hashmap(statuses);
+    if output.is_json() {
+        output.print_json(&statuses.iter().collect::<BTreeMap<_, _>>())?;
+    }
+    Ok(statuses)
 }
 
 async fn async_run_install(
@@ -535,7 +917,9 @@ async fn async_run_install(
     let mut detailed_results: Vec<(String, InstallResult)> = Vec::new();
 
     // === Coding Agents ===
-    println!("\n\x1b[1mCoding Agents\x1b[0m");
+    if !options.output.is_quiet() {
+        println!("\n\x1b[1mCoding Agents\x1b[0m");
+    }
 
     let installers = get_all_installers();
     let mut installed_tools: HashSet<String> = HashSet::new();
@@ -575,7 +959,7 @@ async fn async_run_install(
                                 spinner.success(&format!("{}: Hooks updated", name));
                                 print_amp_plugins_note(id);
                             }
-                            if options.verbose {
+                            if options.shows_diffs() {
                                 println!();
                                 print_diff(&diff);
                             }
@@ -641,7 +1025,7 @@ async fn async_run_install(
                                 extra_spinner.start();
                                 extra_spinner.pending(&result.message);
                             }
-                            if options.verbose
+                            if options.shows_diffs()
                                 && let Some(diff) = result.diff
                             {
                                 println!();
@@ -713,7 +1097,9 @@ async fn async_run_install(
         has_changes = true;
     }
 
-    if !any_checked {
+    if options.output.is_quiet() {
+        // Per-agent results are reported through the returned statuses.
+    } else if !any_checked {
         println!("No compatible IDEs or agent configurations detected. Nothing to install.");
     } else if has_changes && options.dry_run {
         println!("\n\x1b[33m⚠ Dry-run mode (default). No changes were made.\x1b[0m");
@@ -722,7 +1108,7 @@ async fn async_run_install(
     }
 
     // Check for running agents that had hooks updated and warn about restart
-    if !options.dry_run && !updated_agents.is_empty() {
+    if !options.dry_run && !options.output.is_quiet() && !updated_agents.is_empty() {
         let mut any_running = false;
 
         for (agent_name, pnames) in &updated_agents {
@@ -878,7 +1264,10 @@ async fn async_run_uninstall(
     }
 
     // === Coding Agents ===
-    println!("\n\x1b[1mCoding Agents\x1b[0m");
+    let quiet = OutputOptions::current().is_quiet();
+    if !quiet {
+        println!("\n\x1b[1mCoding Agents\x1b[0m");
+    }
 
     let installers = get_all_installers();
 
@@ -964,7 +1353,9 @@ async fn async_run_uninstall(
         }
     }
 
-    if !any_checked {
+    if quiet {
+        // Per-agent results are reported through the returned statuses.
+    } else if !any_checked {
         println!("No git-ai hooks found to uninstall.");
     } else if has_changes && dry_run {
         println!("\n\x1b[33m⚠ Dry-run mode (default). No changes were made.\x1b[0m");
@@ -1083,6 +1474,25 @@ mod tests {
             &options
         ));
         assert!(should_include_installer("vscode", &options));
+        assert!(!should_include_installer(
+            COPILOT_AGENT_INSTALLER_ID,
+            &options
+        ));
+    }
+
+    #[test]
+    fn parse_install_options_enables_copilot_agent_flag() {
+        let options = parse_install_options(&["--copilot-agent".to_string()]).unwrap();
+
+        assert!(options.include_copilot_agent);
+        assert!(should_include_installer(
+            COPILOT_AGENT_INSTALLER_ID,
+            &options
+        ));
+        assert!(!should_include_installer(
+            VISUAL_STUDIO_INSTALLER_ID,
+            &options
+        ));
     }
 
     #[test]
@@ -1131,6 +1541,42 @@ mod tests {
         assert!(err.to_string().contains("missing value for --api-base"));
     }
 
+    #[test]
+    fn parse_install_options_plan_json_implies_dry_run() {
+        let options = parse_install_options(&["--plan-json".to_string()]).unwrap();
+
+        assert!(options.plan_json);
+        assert!(options.dry_run);
+    }
+
+    #[test]
+    fn apply_install_config_values_masks_api_key_and_skips_unchanged() {
+        let temp = tempdir().unwrap();
+        let binary_path = test_binary_path(temp.path());
+        let mut file_config = crate::config::FileConfig {
+            api_base_url: Some("https://enterprise.example".to_string()),
+            api_key: Some("sk-old".to_string()),
+            git_path: Some("/usr/bin/git".to_string()),
+            ..Default::default()
+        };
+        let install_config = InstallConfig {
+            api_base: Some("https://enterprise.example".to_string()),
+            api_key: Some("sk-new".to_string()),
+        };
+
+        let changes = apply_install_config_values(&mut file_config, &binary_path, &install_config);
+
+        assert_eq!(
+            changes,
+            vec![PlannedSetting {
+                key: "api_key".to_string(),
+                current: Some(MASKED_VALUE.to_string()),
+                planned: Some(MASKED_VALUE.to_string()),
+            }]
+        );
+        assert_eq!(file_config.api_key.as_deref(), Some("sk-new"));
+    }
+
     #[test]
     #[cfg(not(windows))]
     #[serial]
@@ -1244,6 +1690,20 @@ mod tests {
         assert_eq!(config.git_path.as_deref(), Some(existing_git_path));
     }
 
+    #[test]
+    fn resolve_hook_binary_path_prefers_sibling_hook_binary() {
+        let temp = tempdir().unwrap();
+        let binary_path = test_binary_path(temp.path());
+        fs::write(&binary_path, "").unwrap();
+        assert_eq!(resolve_hook_binary_path(&binary_path), binary_path);
+
+        let hook_binary = temp
+            .path()
+            .join(format!("git-ai-hook{}", std::env::consts::EXE_SUFFIX));
+        fs::write(&hook_binary, "").unwrap();
+        assert_eq!(resolve_hook_binary_path(&binary_path), hook_binary);
+    }
+
     #[test]
     #[serial]
     fn persist_install_config_skips_without_env_or_in_dry_run() {
@@ -6,6 +6,7 @@ use crate::authorship::stats::{
 use crate::config::{Config, NotesBackendKind};
 use crate::error::GitAiError;
 use crate::git::repository::Repository;
+use crate::workspace_config::{WhitespaceMode, effective_workspace_config};
 use crossterm::{
     cursor::{Hide, MoveTo, Show},
     event::{self, Event, KeyCode},
@@ -373,18 +374,21 @@ struct LogRenderer {
     options: ParsedLogArgs,
     stream: CommitStream,
     ignore_patterns: Vec<String>,
+    whitespace: WhitespaceMode,
     eof: bool,
 }
 
 impl LogRenderer {
     fn new(repo: Repository, options: ParsedLogArgs) -> Result<Self, LogError> {
         let ignore_patterns = effective_ignore_patterns(&repo, &[], &[]);
+        let whitespace = effective_workspace_config(&repo).whitespace_mode();
         let stream = CommitStream::spawn(&repo, &options.git_log_args, options.show_decorations)?;
         Ok(Self {
             repo,
             options,
             stream,
             ignore_patterns,
+            whitespace,
             eof: false,
         })
     }
@@ -422,6 +426,7 @@ impl LogRenderer {
                     note,
                     &self.options,
                     &self.ignore_patterns,
+                    self.whitespace,
                 )
             })
             .collect())
@@ -561,6 +566,7 @@ fn render_commit(
     raw_note: Option<&str>,
     options: &ParsedLogArgs,
     ignore_patterns: &[String],
+    whitespace: WhitespaceMode,
 ) -> String {
     let authorship_log =
         raw_note.and_then(|note| AuthorshipLog::deserialize_from_string(note).ok());
@@ -631,6 +637,7 @@ fn render_commit(
         &commit.parents,
         authorship_log.as_ref(),
         ignore_patterns,
+        whitespace,
     ) {
         Ok(stats) => append_indented_block(&mut out, &stats, 6),
         Err(message) => append_indented_line(&mut out, &message, 6),
@@ -655,6 +662,7 @@ fn render_stats(
     parents: &[String],
     authorship_log: Option<&AuthorshipLog>,
     ignore_patterns: &[String],
+    whitespace: WhitespaceMode,
 ) -> Result<String, String> {
     if parents.len() > 1 {
         return Err("stats skipped for merge commit".to_string());
@@ -683,6 +691,7 @@ fn render_stats(
         commit_sha,
         parents.first().map(String::as_str),
         ignore_patterns,
+        whitespace,
         authorship_log,
     )
     .map_err(|e| format!("stats unavailable: {}", e))?;
@@ -0,0 +1,119 @@
+//! `git-ai migrate-storage` — upgrade the repository's `.git/ai` storage format in place.
+//!
+//! The current working logs are backed up under `.git/ai/backups/` before any file is
+//! rewritten. Storage written by a newer git-ai is refused when the repository is opened.
+//!
+//! `--working-logs-dir <dir>` moves the working logs out of `.git` instead, for
+//! backup and sync tools that struggle with rapidly changing files there; the
+//! new location is recorded in `.git/ai/location`.
+
+use crate::git::find_repository;
+use crate::git::repo_storage::STORAGE_FORMAT_VERSION;
+use std::path::PathBuf;
+
+pub fn handle_migrate_storage(args: &[String]) {
+    let mut dry_run = false;
+    let mut working_logs_dir: Option<String> = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dry-run" | "-n" => dry_run = true,
+            "--working-logs-dir" => match args.next() {
+                Some(dir) => working_logs_dir = Some(dir.clone()),
+                None => {
+                    eprintln!("Error: --working-logs-dir requires a directory or 'default'");
+                    std::process::exit(1);
+                }
+            },
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other => {
+                eprintln!("Error: unknown option '{}'", other);
+                eprintln!("Run 'git ai migrate-storage --help' for usage");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let mut repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if let Some(dir) = working_logs_dir {
+        let target = (dir != "default").then(|| PathBuf::from(&dir));
+        if dry_run {
+            println!(
+                "Would move working logs from {} to {}.",
+                repo.storage.working_logs.display(),
+                target
+                    .as_deref()
+                    .unwrap_or(&repo.storage.ai_dir.join("working_logs"))
+                    .display()
+            );
+            return;
+        }
+        if let Err(e) = repo.storage.relocate_working_logs(target.as_deref()) {
+            eprintln!("Moving working logs failed: {}", e);
+            std::process::exit(1);
+        }
+        println!(
+            "Working logs are now in {}.",
+            repo.storage.working_logs.display()
+        );
+        return;
+    }
+
+    let report = match repo.storage.migrate_storage(dry_run) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Storage migration failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if report.from_version == report.to_version {
+        println!(
+            "git-ai storage is already at version {}; nothing to migrate.",
+            STORAGE_FORMAT_VERSION
+        );
+        return;
+    }
+
+    if dry_run {
+        println!(
+            "Would migrate git-ai storage from version {} to {} ({} working log(s)).",
+            report.from_version, report.to_version, report.working_logs_migrated
+        );
+        return;
+    }
+
+    println!(
+        "Migrated git-ai storage from version {} to {} ({} working log(s)).",
+        report.from_version, report.to_version, report.working_logs_migrated
+    );
+    if let Some(backup_dir) = report.backup_dir {
+        println!("Backup written to {}", backup_dir.display());
+    }
+}
+
+fn print_help() {
+    eprintln!("git-ai migrate-storage - upgrade .git/ai storage to the current format");
+    eprintln!();
+    eprintln!("Usage: git-ai migrate-storage [--dry-run] [--working-logs-dir <dir|default>]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  -n, --dry-run                 Report what would change without writing anything");
+    eprintln!(
+        "  --working-logs-dir <dir>      Move working logs to <dir> (absolute path), or back"
+    );
+    eprintln!("                                into .git/ai with 'default'");
+    eprintln!("  -h, --help                    Show this help message");
+    eprintln!();
+    eprintln!("New repositories use the global `working_logs_dir` config as their base.");
+}
@@ -1,15 +1,24 @@
 pub mod analyze;
 pub mod r#await;
+pub mod backfill;
 pub mod blame;
+pub mod bundle;
 pub mod checkpoint_agent;
 pub mod ci_handlers;
+pub mod commit;
 pub mod config;
+pub mod conflict_resolution;
 pub mod daemon;
 pub mod debug;
 pub mod diff;
+pub mod errors;
 pub mod exchange_nonce;
+#[cfg(feature = "parquet-export")]
+pub mod export;
 pub mod fetch_notes;
 pub mod flush_metrics_db;
+pub mod fsck;
+pub mod gc;
 pub mod git_ai_handlers;
 pub mod git_handlers;
 pub mod git_hook_handlers;
@@ -17,11 +26,28 @@ pub mod install_hooks;
 pub mod log;
 pub mod login;
 pub mod logout;
+pub mod migrate_storage;
+pub mod notes_gc;
 pub mod notes_migrate;
+pub mod notes_upgrade;
+pub mod output;
 pub mod personal_dashboard;
+pub mod profile;
+pub mod prompts;
+pub mod reconcile;
+pub mod reconstruct;
+pub mod repair;
+pub mod report;
+pub mod resolve;
+pub mod restore_attribution;
+pub mod server_hooks;
+pub mod session;
 pub mod show;
 pub mod show_prompt;
+pub mod state;
 pub mod status;
+pub mod timeline;
+pub mod top;
 pub mod upgrade;
 pub mod usage;
 pub mod whoami;
@@ -0,0 +1,338 @@
+//! `git-ai notes-gc` — prune authorship notes attached to unreachable commits.
+//!
+//! A note is pruned when its target commit exists locally, is not reachable from
+//! any ref or reflog entry, and was committed before the retention window. Notes
+//! on commits that are missing locally are kept: they usually belong to commits
+//! another developer pushed that have not been fetched here.
+//!
+//! The surviving notes are rewritten with a single `git fast-import` commit on
+//! top of `refs/notes/ai`, which also normalizes the tree fanout. History is
+//! kept so the notes ref still fast-forwards when pushed.
+
+use crate::config::{Config, NotesBackendKind};
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::refs::{AI_AUTHORSHIP_REFNAME, notes_path_for_object};
+use crate::git::repository::{Repository, exec_git, exec_git_allow_nonzero, exec_git_stdin};
+use std::collections::{HashMap, HashSet};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Unreachable commits younger than this are kept, so notes survive until
+/// `git gc` would also have pruned the commits.
+const DEFAULT_RETENTION_DAYS: u64 = 30;
+
+/// A note selected for pruning.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrunableNote {
+    pub commit_sha: String,
+    pub commit_time: u64,
+}
+
+/// Outcome of classifying every note on `refs/notes/ai`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct NotesGcPlan {
+    pub total_notes: usize,
+    /// Notes whose target commit is not in the local object database.
+    pub missing_targets: usize,
+    /// Unreachable but still inside the retention window.
+    pub retained_unreachable: usize,
+    pub prune: Vec<PrunableNote>,
+}
+
+pub fn handle_notes_gc(args: &[String]) {
+    let mut dry_run = false;
+    let mut retention_days = DEFAULT_RETENTION_DAYS;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--dry-run" | "-n" => dry_run = true,
+            "--retention-days" if i + 1 < args.len() => {
+                retention_days = match args[i + 1].parse() {
+                    Ok(days) => days,
+                    Err(_) => {
+                        eprintln!(
+                            "error: --retention-days expects a number, got '{}'",
+                            args[i + 1]
+                        );
+                        std::process::exit(1);
+                    }
+                };
+                i += 1;
+            }
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other => {
+                eprintln!("error: unknown option '{}'", other);
+                eprintln!("Run 'git-ai notes-gc --help' for usage");
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    if Config::fresh().notes_backend_kind() != NotesBackendKind::GitNotes {
+        eprintln!("error: `git-ai notes-gc` only applies to the git_notes backend");
+        std::process::exit(1);
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("error: not a git repository ({})", e);
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(e) = run_notes_gc(&repo, retention_days, dry_run) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run_notes_gc(repo: &Repository, retention_days: u64, dry_run: bool) -> Result<(), GitAiError> {
+    let Some(notes_tip) = notes_ref_tip(repo)? else {
+        eprintln!("No notes found in refs/notes/ai. Nothing to prune.");
+        return Ok(());
+    };
+    let notes = crate::commands::notes_migrate::list_notes(repo)?;
+    if notes.is_empty() {
+        eprintln!("No notes found in refs/notes/ai. Nothing to prune.");
+        return Ok(());
+    }
+
+    let targets: Vec<String> = notes.iter().map(|(_, commit)| commit.clone()).collect();
+    let commit_times = commit_times(repo, &targets)?;
+    let reachable = reachable_commits(repo)?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let cutoff = now.saturating_sub(retention_days.saturating_mul(86_400));
+    let plan = plan_notes_gc(&targets, &commit_times, &reachable, cutoff);
+
+    print_report(&plan, retention_days, now, dry_run);
+    if dry_run || plan.prune.is_empty() {
+        return Ok(());
+    }
+
+    let pruned: HashSet<&str> = plan
+        .prune
+        .iter()
+        .map(|note| note.commit_sha.as_str())
+        .collect();
+    let kept: Vec<(String, String)> = notes
+        .into_iter()
+        .filter(|(_, commit)| !pruned.contains(commit.as_str()))
+        .collect();
+    rewrite_notes_ref(repo, &notes_tip, &kept, plan.prune.len(), now)?;
+    eprintln!(
+        "Pruned {} note(s); {} remain.",
+        plan.prune.len(),
+        kept.len()
+    );
+    Ok(())
+}
+
+/// Classify notes by the reachability and age of their target commits.
+pub fn plan_notes_gc(
+    targets: &[String],
+    commit_times: &HashMap<String, u64>,
+    reachable: &HashSet<String>,
+    cutoff: u64,
+) -> NotesGcPlan {
+    let mut plan = NotesGcPlan {
+        total_notes: targets.len(),
+        ..Default::default()
+    };
+    for commit_sha in targets {
+        let Some(&commit_time) = commit_times.get(commit_sha) else {
+            plan.missing_targets += 1;
+            continue;
+        };
+        if reachable.contains(commit_sha) {
+            continue;
+        }
+        if commit_time >= cutoff {
+            plan.retained_unreachable += 1;
+            continue;
+        }
+        plan.prune.push(PrunableNote {
+            commit_sha: commit_sha.clone(),
+            commit_time,
+        });
+    }
+    plan
+}
+
+fn notes_ref_tip(repo: &Repository) -> Result<Option<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "rev-parse".to_string(),
+        "--verify".to_string(),
+        "--quiet".to_string(),
+        format!("refs/notes/{}", AI_AUTHORSHIP_REFNAME),
+    ]);
+    let output = exec_git_allow_nonzero(&args)?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    Ok(Some(
+        String::from_utf8_lossy(&output.stdout).trim().to_string(),
+    ))
+}
+
+/// Committer timestamps of the given commits that exist locally.
+pub(crate) fn commit_times(
+    repo: &Repository,
+    commits: &[String],
+) -> Result<HashMap<String, u64>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "log".to_string(),
+        "--no-walk=unsorted".to_string(),
+        "--ignore-missing".to_string(),
+        "--format=%H %ct".to_string(),
+        "--stdin".to_string(),
+    ]);
+    let stdin = commits.join("\n") + "\n";
+    let output = exec_git_stdin(&args, stdin.as_bytes())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout
+        .lines()
+        .filter_map(|line| {
+            let (sha, time) = line.split_once(' ')?;
+            Some((sha.to_string(), time.trim().parse().ok()?))
+        })
+        .collect())
+}
+
+/// Every commit reachable from a ref (other than notes refs) or a reflog entry.
+fn reachable_commits(repo: &Repository) -> Result<HashSet<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "rev-list".to_string(),
+        "--exclude=refs/notes/*".to_string(),
+        "--all".to_string(),
+        "--reflog".to_string(),
+    ]);
+    let output = exec_git(&args)?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .collect())
+}
+
+/// Commit the kept notes as a fresh tree on top of the current notes ref.
+fn rewrite_notes_ref(
+    repo: &Repository,
+    notes_tip: &str,
+    kept: &[(String, String)],
+    pruned: usize,
+    now: u64,
+) -> Result<(), GitAiError> {
+    let notes_ref = format!("refs/notes/{}", AI_AUTHORSHIP_REFNAME);
+    let message = format!("Prune {} unreachable note(s) (git-ai notes-gc)\n", pruned);
+
+    let mut stream = String::new();
+    stream.push_str(&format!("commit {}\n", notes_ref));
+    stream.push_str(&format!(
+        "committer git-ai <git-ai@noreply> {} +0000\n",
+        now
+    ));
+    stream.push_str(&format!("data {}\n{}", message.len(), message));
+    // Parenting on the tip we read makes fast-import refuse the (non fast-forward)
+    // update if a note was written in the meantime, instead of dropping it.
+    stream.push_str(&format!("from {}\n", notes_tip));
+    stream.push_str("deleteall\n");
+    for (blob, object) in kept {
+        stream.push_str(&format!(
+            "M 100644 {} {}\n",
+            blob,
+            notes_path_for_object(object)
+        ));
+    }
+    stream.push_str("done\n");
+
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "fast-import".to_string(),
+        "--quiet".to_string(),
+        "--done".to_string(),
+    ]);
+    exec_git_stdin(&args, stream.as_bytes())?;
+    Ok(())
+}
+
+fn print_report(plan: &NotesGcPlan, retention_days: u64, now: u64, dry_run: bool) {
+    eprintln!("Notes in refs/notes/ai: {}", plan.total_notes);
+    eprintln!(
+        "  unreachable, within {} day retention: {}",
+        retention_days, plan.retained_unreachable
+    );
+    eprintln!(
+        "  target commit not present locally (kept): {}",
+        plan.missing_targets
+    );
+    eprintln!(
+        "  {}: {}",
+        if dry_run { "would prune" } else { "pruning" },
+        plan.prune.len()
+    );
+    if dry_run {
+        for note in &plan.prune {
+            let age_days = now.saturating_sub(note.commit_time) / 86_400;
+            println!("{}  {} days old", note.commit_sha, age_days);
+        }
+    }
+}
+
+fn print_help() {
+    eprintln!("git-ai notes-gc - Prune AI authorship notes on unreachable commits");
+    eprintln!();
+    eprintln!("Usage: git-ai notes-gc [--dry-run] [--retention-days <n>]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  -n, --dry-run            Report what would be pruned without writing");
+    eprintln!(
+        "  --retention-days <n>     Keep notes on unreachable commits newer than n days (default: {})",
+        DEFAULT_RETENTION_DAYS
+    );
+    eprintln!("  -h, --help               Show this help");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_prunes_only_old_unreachable_local_commits() {
+        let targets = vec![
+            "reachable".to_string(),
+            "old_unreachable".to_string(),
+            "young_unreachable".to_string(),
+            "missing".to_string(),
+        ];
+        let commit_times = HashMap::from([
+            ("reachable".to_string(), 10),
+            ("old_unreachable".to_string(), 10),
+            ("young_unreachable".to_string(), 500),
+        ]);
+        let reachable = HashSet::from(["reachable".to_string()]);
+
+        let plan = plan_notes_gc(&targets, &commit_times, &reachable, 100);
+
+        assert_eq!(plan.total_notes, 4);
+        assert_eq!(plan.missing_targets, 1);
+        assert_eq!(plan.retained_unreachable, 1);
+        assert_eq!(
+            plan.prune,
+            vec![PrunableNote {
+                commit_sha: "old_unreachable".to_string(),
+                commit_time: 10,
+            }]
+        );
+    }
+}
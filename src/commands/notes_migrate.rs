@@ -235,7 +235,7 @@ pub fn handle_notes_migrate(args: &[String]) {
 }
 
 /// Run `git notes --ref=ai list` and return `(blob_sha, commit_sha)` pairs.
-fn list_notes(
+pub(crate) fn list_notes(
     repo: &crate::git::repository::Repository,
 ) -> Result<Vec<(String, String)>, GitAiError> {
     use crate::git::repository::exec_git;
@@ -280,7 +280,7 @@ fn list_notes(
 ///
 /// Feeds the blob SHAs on stdin and parses the binary protocol output.
 /// Returns a map of `blob_sha → content`.
-fn cat_file_batch(
+pub(crate) fn cat_file_batch(
     repo: &crate::git::repository::Repository,
     blob_shas: &[String],
 ) -> Result<HashMap<String, String>, GitAiError> {
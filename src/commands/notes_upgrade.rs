@@ -0,0 +1,137 @@
+//! `git-ai notes upgrade` — copy authorship notes into the newest notes namespace.
+//!
+//! Every note in `refs/notes/ai` is rewritten in the newest schema and attached
+//! to the same commit under that schema's ref (see [`crate::git::notes_schema`]).
+//! `refs/notes/ai` is left untouched so clients that only read it keep working.
+//! Commits that already have a note in the target namespace are skipped unless
+//! `--force` is given.
+
+use crate::commands::notes_migrate::{cat_file_batch, list_notes};
+use crate::config::{Config, NotesBackendKind};
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::notes_schema::{self, NotesNamespace};
+use crate::git::repository::Repository;
+
+/// Outcome of [`upgrade_notes`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NotesUpgradeReport {
+    pub upgraded: usize,
+    pub already_present: usize,
+    pub unreadable: Vec<String>,
+}
+
+pub fn handle_notes_upgrade(args: &[String]) {
+    let mut dry_run = false;
+    let mut force = false;
+    for arg in args {
+        match arg.as_str() {
+            "--dry-run" => dry_run = true,
+            "--force" => force = true,
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other => {
+                eprintln!("error: unknown option '{}'", other);
+                eprintln!("Run 'git ai notes upgrade --help' for usage");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if Config::fresh().notes_backend_kind() == NotesBackendKind::Http {
+        eprintln!("error: `git-ai notes upgrade` rewrites git notes; the HTTP backend has none.");
+        std::process::exit(1);
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("error: not a git repository ({})", e);
+            std::process::exit(1);
+        }
+    };
+
+    let target = NotesNamespace::V2;
+    let report = match upgrade_notes(&repo, target, dry_run, force) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    for commit_sha in &report.unreadable {
+        eprintln!("warning: skipped unreadable note on {}", commit_sha);
+    }
+    println!(
+        "{} {} note(s) into {} ({} already present)",
+        if dry_run { "Would upgrade" } else { "Upgraded" },
+        report.upgraded,
+        target.full_ref(),
+        report.already_present
+    );
+}
+
+/// Rewrite the notes of `refs/notes/ai` into `target`.
+pub fn upgrade_notes(
+    repo: &Repository,
+    target: NotesNamespace,
+    dry_run: bool,
+    force: bool,
+) -> Result<NotesUpgradeReport, GitAiError> {
+    let note_pairs = list_notes(repo)?;
+    let blob_shas: Vec<String> = note_pairs.iter().map(|(blob, _)| blob.clone()).collect();
+    let blobs = cat_file_batch(repo, &blob_shas)?;
+
+    let commit_shas: Vec<String> = note_pairs
+        .iter()
+        .map(|(_, commit_sha)| commit_sha.clone())
+        .collect();
+    let existing = if force {
+        Default::default()
+    } else {
+        notes_schema::read_namespace_notes(repo, target, &commit_shas)?
+    };
+
+    let mut report = NotesUpgradeReport::default();
+    let mut entries: Vec<(String, String)> = Vec::new();
+    for (blob_sha, commit_sha) in &note_pairs {
+        if existing.contains_key(commit_sha) {
+            report.already_present += 1;
+            continue;
+        }
+        let upgraded = blobs
+            .get(blob_sha)
+            .ok_or_else(|| GitAiError::Generic(format!("missing note blob {}", blob_sha)))
+            .and_then(|content| notes_schema::upgrade_note(content, commit_sha));
+        match upgraded {
+            Ok(note) => entries.push((commit_sha.clone(), note)),
+            Err(_) => report.unreadable.push(commit_sha.clone()),
+        }
+    }
+
+    report.upgraded = entries.len();
+    if !dry_run {
+        notes_schema::write_namespace_notes(repo, target, &entries)?;
+    }
+    Ok(report)
+}
+
+fn print_help() {
+    eprintln!("git ai notes upgrade - Copy AI notes into the newest notes schema");
+    eprintln!();
+    eprintln!("Usage: git ai notes upgrade [--dry-run] [--force]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --dry-run   Report how many notes would be upgraded without writing");
+    eprintln!("  --force     Rewrite notes that already exist in the target namespace");
+    eprintln!();
+    eprintln!(
+        "Notes in refs/notes/ai are copied under {}, pinned to the commit they annotate.",
+        NotesNamespace::V2.full_ref()
+    );
+    eprintln!("refs/notes/ai is not modified, so older clients keep reading it.");
+    eprintln!("Once upgraded, notes sync fetches and pushes both refs.");
+}
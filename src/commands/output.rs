@@ -0,0 +1,154 @@
+//! Output conventions shared by `git-ai` subcommands.
+//!
+//! `--json` switches a command to a single machine-readable document on
+//! stdout, while `--quiet`/`-q` and `--verbose` control how much
+//! human-readable text is printed. The flags may be given before the
+//! subcommand (`git ai --json stats`), where they apply to whichever command
+//! runs, or among the options of a command that parses them with
+//! [`OutputOptions::parse_flag`]. Failures exit with the codes documented in
+//! [`crate::error`].
+
+use crate::error::GitAiError;
+use serde::Serialize;
+use std::sync::RwLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputOptions {
+    pub format: OutputFormat,
+    pub verbosity: Verbosity,
+}
+
+static CURRENT: RwLock<OutputOptions> = RwLock::new(OutputOptions::DEFAULT);
+
+impl Default for OutputOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl OutputOptions {
+    const DEFAULT: Self = Self {
+        format: OutputFormat::Text,
+        verbosity: Verbosity::Normal,
+    };
+
+    /// The options in effect for this process: the global flags given before
+    /// the subcommand, updated by [`OutputOptions::parse_flag`].
+    pub fn current() -> Self {
+        CURRENT.read().map(|options| *options).unwrap_or_default()
+    }
+
+    /// Applies `arg` if it is an output flag and records the result as current.
+    /// Commands call this from their own option loop, where option values and
+    /// arguments after `--` are already accounted for, so `--label -q` keeps
+    /// its value.
+    pub fn parse_flag(&mut self, arg: &str) -> bool {
+        if !self.apply_flag(arg) {
+            return false;
+        }
+        if let Ok(mut current) = CURRENT.write() {
+            *current = *self;
+        }
+        true
+    }
+
+    /// Consumes the output flags that precede the subcommand name, leaving
+    /// the subcommand and its arguments.
+    pub fn take_leading_flags(args: &[String]) -> Vec<String> {
+        let mut options = Self::current();
+        let split = args
+            .iter()
+            .position(|arg| !options.apply_flag(arg))
+            .unwrap_or(args.len());
+        if let Ok(mut current) = CURRENT.write() {
+            *current = options;
+        }
+        args[split..].to_vec()
+    }
+
+    fn apply_flag(&mut self, arg: &str) -> bool {
+        match arg {
+            "--json" => self.format = OutputFormat::Json,
+            "--quiet" | "-q" => self.verbosity = Verbosity::Quiet,
+            "--verbose" => self.verbosity = Verbosity::Verbose,
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn is_json(&self) -> bool {
+        self.format == OutputFormat::Json
+    }
+
+    /// Whether progress and informational text should be suppressed. JSON
+    /// output implies this so stdout stays a single parseable document.
+    pub fn is_quiet(&self) -> bool {
+        self.is_json() || self.verbosity == Verbosity::Quiet
+    }
+
+    pub fn is_verbose(&self) -> bool {
+        self.verbosity == Verbosity::Verbose
+    }
+
+    /// Prints `value` as one line of JSON on stdout.
+    pub fn print_json<T: Serialize + ?Sized>(&self, value: &T) -> Result<(), GitAiError> {
+        println!("{}", serde_json::to_string(value)?);
+        Ok(())
+    }
+
+    /// Reports `err` and exits with its category's exit code. With `--json`
+    /// the error is also printed on stdout as an `{"error": {...}}` document.
+    pub fn exit_with_error(&self, err: &GitAiError) -> ! {
+        if self.is_json()
+            && let Ok(payload) = serde_json::to_string(&err.payload())
+        {
+            println!("{}", payload);
+        }
+        eprintln!("Error: {}", err);
+        std::process::exit(err.exit_code());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn apply_flag_recognizes_output_flags_only() {
+        let mut options = OutputOptions::default();
+        assert!(options.apply_flag("--json"));
+        assert!(options.is_json());
+        assert!(options.is_quiet());
+        assert!(options.apply_flag("--verbose"));
+        assert!(options.is_verbose());
+        assert!(options.apply_flag("-q"));
+        assert_eq!(options.verbosity, Verbosity::Quiet);
+        assert!(!options.apply_flag("-v"));
+        assert!(!options.apply_flag("--by-file"));
+    }
+
+    #[test]
+    fn take_leading_flags_stops_at_the_subcommand() {
+        let rest = OutputOptions::take_leading_flags(&args(&["--json", "stats", "--verbose"]));
+        assert_eq!(rest, args(&["stats", "--verbose"]));
+        assert!(OutputOptions::current().is_json());
+        assert!(!OutputOptions::current().is_verbose());
+    }
+}
@@ -0,0 +1,214 @@
+//! `git-ai profile [--json] [--] <git args...>`
+//!
+//! Runs one git command through the wrapper, exactly as `git <args>` would,
+//! and prints where the time went: argument parsing, repository discovery,
+//! hooks, git itself, checkpoints, diffs, notes IO and post-command work.
+//! When the background service is running, the report also waits for it to
+//! finish the command's side effects. What the service spent in checkpoints,
+//! diffs, notes IO and rewrites meanwhile is reported under those phases, and
+//! the rest of the wait as `background`.
+//!
+//! The report goes to stderr so the command's own output stays untouched; the
+//! exit status is git's.
+
+use crate::commands::git_handlers::run_git_command;
+use crate::daemon::{ControlRequest, DaemonConfig, send_control_request};
+use crate::git::cli_parser::{ParsedGitInvocation, git_ai_subcommand_args, parse_git_cli_args};
+use crate::git::find_repository;
+use crate::observability::profile::{
+    PhaseRow, PhaseTiming, ProfileLayer, add_daemon_timings, phase_rows,
+};
+use crate::observability::trace::targets;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tracing_subscriber::layer::SubscriberExt;
+
+pub fn handle_profile(args: &[String]) {
+    let mut json = false;
+    let mut git_args: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--json" => {
+                json = true;
+                i += 1;
+            }
+            "--" => {
+                git_args.extend(args[i + 1..].iter().cloned());
+                break;
+            }
+            "-h" | "--help" => {
+                print_help();
+                std::process::exit(0);
+            }
+            _ => {
+                git_args.extend(args[i..].iter().cloned());
+                break;
+            }
+        }
+    }
+
+    if git_args.is_empty() {
+        eprintln!("Error: profile requires a git command, e.g. `git-ai profile status`");
+        print_help();
+        std::process::exit(1);
+    }
+
+    let layer = ProfileLayer::new();
+    let timings = layer.timings();
+    let subscriber = tracing_subscriber::registry().with(layer);
+
+    let daemon = running_daemon();
+    let daemon_before = daemon.as_ref().and_then(daemon_snapshot);
+    let started = Instant::now();
+    let exit_status = tracing::subscriber::with_default(subscriber, || {
+        let parsed = {
+            let _span = tracing::debug_span!(target: targets::HOOKS, "parse").entered();
+            parse_git_cli_args(&git_args)
+        };
+        if git_ai_subcommand_args(&parsed).is_some() {
+            eprintln!("Error: profile runs git commands; `git ai` subcommands are not supported");
+            std::process::exit(1);
+        }
+        let exit_status = run_git_command(&git_args, &parsed);
+        if let Some(config) = &daemon {
+            let _span = tracing::debug_span!(target: targets::SYNC, "background").entered();
+            wait_for_background_work(config, &parsed);
+        }
+        exit_status
+    });
+    let wall = started.elapsed();
+    let daemon_after = daemon.as_ref().and_then(daemon_snapshot);
+
+    let rows = match timings.lock() {
+        Ok(mut timings) => {
+            if let (Some(before), Some(after)) = (&daemon_before, &daemon_after) {
+                add_daemon_timings(&mut timings, before, after);
+            }
+            phase_rows(&timings, wall)
+        }
+        Err(_) => Vec::new(),
+    };
+    if json {
+        let report = serde_json::json!({
+            "command": git_args,
+            "exit_code": exit_status.code(),
+            "wall_ms": wall.as_secs_f64() * 1000.0,
+            "phases": rows,
+        });
+        eprintln!("{}", report);
+    } else {
+        eprint!("{}", render_report(&git_args, wall, &rows));
+    }
+
+    crate::commands::git_handlers::exit_with_status(exit_status);
+}
+
+/// The background service's config, when it is running.
+fn running_daemon() -> Option<DaemonConfig> {
+    let config = DaemonConfig::from_env_or_default_paths().ok()?;
+    crate::commands::daemon::daemon_is_up(&config).then_some(config)
+}
+
+/// The background service's phase timings so far.
+fn daemon_snapshot(config: &DaemonConfig) -> Option<HashMap<String, PhaseTiming>> {
+    match send_control_request(
+        &config.control_socket_path,
+        &ControlRequest::ProfileSnapshot,
+    ) {
+        Ok(response) => serde_json::from_value(response.data?).ok(),
+        Err(e) => {
+            tracing::debug!("profile: reading daemon timings failed: {}", e);
+            None
+        }
+    }
+}
+
+/// Waits until the background service has applied this command's side
+/// effects.
+fn wait_for_background_work(config: &DaemonConfig, parsed: &ParsedGitInvocation) {
+    let Some(repo_working_dir) = find_repository(&parsed.global_args)
+        .ok()
+        .and_then(|repo| repo.workdir().ok())
+    else {
+        return;
+    };
+    let request = ControlRequest::SyncFamily {
+        repo_working_dir: repo_working_dir.to_string_lossy().to_string(),
+    };
+    if let Err(e) = send_control_request(&config.control_socket_path, &request) {
+        tracing::debug!("profile: waiting for background work failed: {}", e);
+    }
+}
+
+fn render_report(git_args: &[String], wall: Duration, rows: &[PhaseRow]) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "\ngit {}  ({:.1} ms)\n",
+        git_args.join(" "),
+        wall.as_secs_f64() * 1000.0
+    ));
+    out.push_str(&format!(
+        "  {:<14} {:>6} {:>11} {:>7}\n",
+        "phase", "calls", "ms", "share"
+    ));
+    for row in rows {
+        let calls = if row.calls == 0 {
+            "-".to_string()
+        } else {
+            row.calls.to_string()
+        };
+        out.push_str(&format!(
+            "  {:<14} {:>6} {:>11.1} {:>6.1}%\n",
+            row.phase, calls, row.ms, row.share
+        ));
+    }
+    out
+}
+
+fn print_help() {
+    eprintln!("Usage: git-ai profile [--json] [--] <git args...>");
+    eprintln!();
+    eprintln!("Runs a git command through git-ai and prints a per-phase timing breakdown");
+    eprintln!("(parse, repository, hooks, exec, checkpoint, diff, notes io, rewrite,");
+    eprintln!("post-command, background) to stderr. Exits with git's status.");
+    eprintln!();
+    eprintln!("  --json    Print the report as JSON");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_report_lists_rows_with_shares() {
+        let rows = vec![
+            PhaseRow {
+                phase: "exec".to_string(),
+                calls: 1,
+                ms: 42.0,
+                share: 84.0,
+            },
+            PhaseRow {
+                phase: "other".to_string(),
+                calls: 0,
+                ms: 8.0,
+                share: 16.0,
+            },
+        ];
+        let report = render_report(
+            &["status".to_string(), "-s".to_string()],
+            Duration::from_millis(50),
+            &rows,
+        );
+
+        assert!(report.contains("git status -s  (50.0 ms)"));
+        assert!(report.contains("exec"));
+        assert!(report.contains("84.0%"));
+        assert!(
+            report
+                .lines()
+                .any(|line| line.trim_start().starts_with("other") && line.contains(" - "))
+        );
+    }
+}
@@ -0,0 +1,621 @@
+//! `git-ai prompts list|show|search` — browse the prompt records git-ai keeps.
+//!
+//! Prompt records come from two places: the prompts and sessions of authorship
+//! notes on committed history, and the AI checkpoints in the working log of
+//! `HEAD` for changes not yet committed. Every field git-ai stores for a prompt
+//! is shown, so users can audit exactly what is retained.
+
+use crate::authorship::authorship_log_serialization::{AuthorshipLog, generate_session_id};
+use crate::commands::output::OutputOptions;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::log_records::log_records;
+use crate::git::notes_api;
+use crate::git::repository::Repository;
+use crate::utils::format_local_timestamp;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptSource {
+    /// A prompt or session of a commit's authorship note.
+    Note,
+    /// AI checkpoints of uncommitted changes.
+    WorkingLog,
+}
+
+/// One stored prompt record, as it appears in one commit or in the working log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PromptEntry {
+    pub id: String,
+    pub source: PromptSource,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    pub tool: String,
+    pub model: String,
+    /// The agent's own conversation or session id.
+    pub agent_id: String,
+    pub human_author: Option<String>,
+    /// Commit time for notes, latest checkpoint time for the working log (Unix
+    /// seconds).
+    pub timestamp: i64,
+    pub files: Vec<String>,
+    pub accepted_lines: u32,
+    pub overridden_lines: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages_url: Option<String>,
+    /// Custom attributes of a note record, or the agent metadata of working log
+    /// checkpoints (e.g. transcript paths).
+    pub attributes: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PromptFilter {
+    pub tool: Option<String>,
+    pub model: Option<String>,
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+    pub file: Option<String>,
+    pub text: Option<String>,
+}
+
+impl PromptFilter {
+    pub fn matches(&self, entry: &PromptEntry) -> bool {
+        if self
+            .tool
+            .as_ref()
+            .is_some_and(|tool| !entry.tool.eq_ignore_ascii_case(tool))
+        {
+            return false;
+        }
+        if self
+            .model
+            .as_ref()
+            .is_some_and(|model| !entry.model.contains(model.as_str()))
+        {
+            return false;
+        }
+        if self.since.is_some_and(|since| entry.timestamp < since)
+            || self.until.is_some_and(|until| entry.timestamp >= until)
+        {
+            return false;
+        }
+        if let Some(file) = &self.file {
+            let prefix = format!("{}/", file.trim_end_matches('/'));
+            if !entry
+                .files
+                .iter()
+                .any(|path| path == file || path.starts_with(&prefix))
+            {
+                return false;
+            }
+        }
+        match &self.text {
+            Some(text) => entry_matches_text(entry, &text.to_lowercase()),
+            None => true,
+        }
+    }
+}
+
+/// Case-insensitive substring match over every stored field.
+fn entry_matches_text(entry: &PromptEntry, needle: &str) -> bool {
+    let mut fields = vec![
+        entry.id.as_str(),
+        entry.tool.as_str(),
+        entry.model.as_str(),
+        entry.agent_id.as_str(),
+    ];
+    fields.extend(entry.human_author.as_deref());
+    fields.extend(entry.messages_url.as_deref());
+    fields.extend(entry.files.iter().map(String::as_str));
+    fields.extend(
+        entry
+            .attributes
+            .iter()
+            .flat_map(|(key, value)| [key.as_str(), value.as_str()]),
+    );
+    fields
+        .iter()
+        .any(|field| field.to_lowercase().contains(needle))
+}
+
+pub fn handle_prompts(args: &[String]) {
+    let subcommand = args.first().map(String::as_str).unwrap_or("list");
+    let result = match subcommand {
+        "list" => parse_list_args(args.get(1..).unwrap_or_default(), None).and_then(run_list),
+        "search" => match args.get(1) {
+            Some(text) if !text.starts_with('-') => {
+                parse_list_args(&args[2..], Some(text.clone())).and_then(run_list)
+            }
+            _ => Err("search requires a text to look for".to_string()),
+        },
+        "show" => run_show(&args[1..]),
+        "--help" | "-h" | "help" => {
+            print_help();
+            return;
+        }
+        other => Err(format!("unknown git-ai prompts subcommand '{}'", other)),
+    };
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        eprintln!("Run 'git ai prompts --help' for usage.");
+        std::process::exit(1);
+    }
+}
+
+struct ListArgs {
+    filter: PromptFilter,
+    range: Option<String>,
+    json: bool,
+}
+
+fn parse_list_args(args: &[String], text: Option<String>) -> Result<ListArgs, String> {
+    let mut parsed = ListArgs {
+        filter: PromptFilter {
+            text,
+            ..Default::default()
+        },
+        range: None,
+        json: OutputOptions::current().is_json(),
+    };
+    let mut i = 0;
+    while i < args.len() {
+        let flag = args[i].as_str();
+        if flag == "--json" {
+            parsed.json = true;
+            i += 1;
+            continue;
+        }
+        let value = args
+            .get(i + 1)
+            .cloned()
+            .ok_or_else(|| format!("{} requires a value", flag));
+        match flag {
+            "--tool" => parsed.filter.tool = Some(value?),
+            "--model" => parsed.filter.model = Some(value?),
+            "--file" => parsed.filter.file = Some(value?),
+            "--since" => parsed.filter.since = Some(parse_date(&value?)?),
+            // Inclusive: up to the end of that day.
+            "--until" => parsed.filter.until = Some(parse_date(&value?)? + 24 * 60 * 60),
+            "--range" => parsed.range = Some(value?),
+            other => return Err(format!("unknown option '{}'", other)),
+        }
+        i += 2;
+    }
+    Ok(parsed)
+}
+
+/// Start of `YYYY-MM-DD` in UTC, as Unix seconds.
+fn parse_date(value: &str) -> Result<i64, String> {
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| datetime.and_utc().timestamp())
+        .ok_or_else(|| format!("invalid date '{}' (expected YYYY-MM-DD)", value))
+}
+
+fn run_list(args: ListArgs) -> Result<(), String> {
+    let repo = find_repository(&Vec::<String>::new())
+        .map_err(|e| format!("not a git repository ({})", e))?;
+    let entries: Vec<PromptEntry> = collect_prompts(&repo, args.range.as_deref())
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|entry| args.filter.matches(entry))
+        .collect();
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&entries).unwrap_or_else(|_| "[]".to_string())
+        );
+        return Ok(());
+    }
+    if entries.is_empty() {
+        eprintln!("No prompts found.");
+        return Ok(());
+    }
+    for entry in &entries {
+        let location = match &entry.commit {
+            Some(commit) => commit.chars().take(8).collect(),
+            None => "uncommitted".to_string(),
+        };
+        println!(
+            "{}  {}  {:<11}  {}/{}  {} file(s)",
+            entry.id,
+            format_local_timestamp(entry.timestamp),
+            location,
+            entry.tool,
+            entry.model,
+            entry.files.len()
+        );
+    }
+    Ok(())
+}
+
+fn run_show(args: &[String]) -> Result<(), String> {
+    let mut id: Option<&str> = None;
+    let mut range: Option<String> = None;
+    let mut json = OutputOptions::current().is_json();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--json" => json = true,
+            "--range" => {
+                i += 1;
+                range = Some(
+                    args.get(i)
+                        .cloned()
+                        .ok_or_else(|| "--range requires a value".to_string())?,
+                );
+            }
+            other if other.starts_with('-') => {
+                return Err(format!("unknown option '{}'", other));
+            }
+            other if id.is_none() => id = Some(other),
+            _ => return Err("only one prompt ID can be specified".to_string()),
+        }
+        i += 1;
+    }
+    let id = id.ok_or_else(|| "show requires a prompt ID".to_string())?;
+    // Attestation hashes carry a `::<trace id>` suffix; the record is keyed by
+    // the session.
+    let id = id.split("::").next().unwrap_or(id);
+
+    let repo = find_repository(&Vec::<String>::new())
+        .map_err(|e| format!("not a git repository ({})", e))?;
+    let entries: Vec<PromptEntry> = collect_prompts(&repo, range.as_deref())
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|entry| entry.id == id)
+        .collect();
+    if entries.is_empty() {
+        return Err(format!("prompt '{}' not found", id));
+    }
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&entries).unwrap_or_else(|_| "[]".to_string())
+        );
+        return Ok(());
+    }
+    for (index, entry) in entries.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        print_entry(entry);
+    }
+    Ok(())
+}
+
+fn print_entry(entry: &PromptEntry) {
+    println!("prompt {}", entry.id);
+    match &entry.commit {
+        Some(commit) => println!("Commit:       {}", commit),
+        None => println!("Commit:       (uncommitted, from the working log)"),
+    }
+    println!("Date:         {}", format_local_timestamp(entry.timestamp));
+    println!("Tool:         {}", entry.tool);
+    println!("Model:        {}", entry.model);
+    println!("Agent ID:     {}", entry.agent_id);
+    if let Some(human_author) = &entry.human_author {
+        println!("Human author: {}", human_author);
+    }
+    if entry.source == PromptSource::Note {
+        println!(
+            "Lines:        {} accepted, {} overridden",
+            entry.accepted_lines, entry.overridden_lines
+        );
+    }
+    if let Some(messages_url) = &entry.messages_url {
+        println!("Messages:     {}", messages_url);
+    }
+    if !entry.files.is_empty() {
+        println!("Files:");
+        for file in &entry.files {
+            println!("  {}", file);
+        }
+    }
+    if !entry.attributes.is_empty() {
+        println!("Attributes:");
+        for (key, value) in &entry.attributes {
+            println!("  {}: {}", key, value);
+        }
+    }
+}
+
+/// Prompt records of the working log of `HEAD` followed by those of the notes
+/// of `range` (any `git log` revision range, default `HEAD`), newest first.
+pub fn collect_prompts(
+    repo: &Repository,
+    range: Option<&str>,
+) -> Result<Vec<PromptEntry>, GitAiError> {
+    let mut entries = working_log_prompts(repo)?;
+
+    let commits = match repo.head().and_then(|head| head.target()) {
+        Ok(_) => list_commits(repo, range.unwrap_or("HEAD"))?,
+        // No commits yet.
+        Err(_) if range.is_none() => Vec::new(),
+        Err(e) => return Err(e),
+    };
+    let shas: Vec<String> = commits.iter().map(|(sha, _)| sha.clone()).collect();
+    let notes = notes_api::read_notes_batch(repo, &shas)?;
+    for (sha, committed_at) in &commits {
+        if let Some(log) = notes
+            .get(sha)
+            .and_then(|raw| AuthorshipLog::deserialize_from_string(raw).ok())
+        {
+            entries.extend(note_prompts(sha, *committed_at, &log));
+        }
+    }
+    Ok(entries)
+}
+
+/// Commits of `range` with their commit times, newest first.
+fn list_commits(repo: &Repository, range: &str) -> Result<Vec<(String, i64)>, GitAiError> {
+    let records = log_records(repo, &["%H", "%ct"], &[range.to_string(), "--".to_string()])?;
+    Ok(records
+        .into_iter()
+        .map(|fields| {
+            let committed_at = fields[1].parse().unwrap_or(0);
+            (fields[0].clone(), committed_at)
+        })
+        .collect())
+}
+
+fn note_prompts(commit_sha: &str, committed_at: i64, log: &AuthorshipLog) -> Vec<PromptEntry> {
+    let files_for = |id: &str| -> Vec<String> {
+        let session_prefix = format!("{}::", id);
+        log.attestations
+            .iter()
+            .filter(|file| {
+                file.entries
+                    .iter()
+                    .any(|entry| entry.hash == id || entry.hash.starts_with(&session_prefix))
+            })
+            .map(|file| file.file_path.clone())
+            .collect()
+    };
+
+    let mut entries = Vec::new();
+    for (id, prompt) in &log.metadata.prompts {
+        entries.push(PromptEntry {
+            id: id.clone(),
+            source: PromptSource::Note,
+            commit: Some(commit_sha.to_string()),
+            tool: prompt.agent_id.tool.clone(),
+            model: prompt.agent_id.model.clone(),
+            agent_id: prompt.agent_id.id.clone(),
+            human_author: prompt.human_author.clone(),
+            timestamp: committed_at,
+            files: files_for(id),
+            accepted_lines: prompt.accepted_lines,
+            overridden_lines: prompt.overriden_lines,
+            messages_url: prompt.messages_url.clone(),
+            attributes: prompt
+                .custom_attributes
+                .clone()
+                .unwrap_or_default()
+                .into_iter()
+                .collect(),
+        });
+    }
+    for (id, session) in &log.metadata.sessions {
+        entries.push(PromptEntry {
+            id: id.clone(),
+            source: PromptSource::Note,
+            commit: Some(commit_sha.to_string()),
+            tool: session.agent_id.tool.clone(),
+            model: session.agent_id.model.clone(),
+            agent_id: session.agent_id.id.clone(),
+            human_author: session.human_author.clone(),
+            timestamp: committed_at,
+            files: files_for(id),
+            accepted_lines: 0,
+            overridden_lines: 0,
+            messages_url: None,
+            attributes: session
+                .custom_attributes
+                .clone()
+                .unwrap_or_default()
+                .into_iter()
+                .collect(),
+        });
+    }
+    entries
+}
+
+/// One entry per AI session in the working log of `HEAD`.
+fn working_log_prompts(repo: &Repository) -> Result<Vec<PromptEntry>, GitAiError> {
+    let Ok(head_sha) = repo.head().and_then(|head| head.target()) else {
+        return Ok(Vec::new());
+    };
+    if !repo.storage.has_working_log(&head_sha) {
+        return Ok(Vec::new());
+    }
+    let checkpoints = repo
+        .storage
+        .working_log_for_base_commit(&head_sha)?
+        .read_all_checkpoints()?;
+
+    let mut by_id: BTreeMap<String, (PromptEntry, BTreeSet<String>)> = BTreeMap::new();
+    for checkpoint in checkpoints.iter().filter(|c| c.kind.is_ai()) {
+        let Some(agent_id) = &checkpoint.agent_id else {
+            continue;
+        };
+        let id = generate_session_id(&agent_id.id, &agent_id.tool);
+        let (entry, files) = by_id.entry(id.clone()).or_insert_with(|| {
+            (
+                PromptEntry {
+                    id,
+                    source: PromptSource::WorkingLog,
+                    commit: None,
+                    tool: agent_id.tool.clone(),
+                    model: agent_id.model.clone(),
+                    agent_id: agent_id.id.clone(),
+                    human_author: None,
+                    timestamp: 0,
+                    files: Vec::new(),
+                    accepted_lines: 0,
+                    overridden_lines: 0,
+                    messages_url: None,
+                    attributes: BTreeMap::new(),
+                },
+                BTreeSet::new(),
+            )
+        });
+        entry.timestamp = entry.timestamp.max(checkpoint.timestamp as i64);
+        // Later checkpoints win, so the model reflects the latest one.
+        entry.model = agent_id.model.clone();
+        if let Some(metadata) = &checkpoint.agent_metadata {
+            entry.attributes.extend(metadata.clone());
+        }
+        files.extend(checkpoint.entries.iter().map(|e| e.file.clone()));
+    }
+
+    let mut entries: Vec<PromptEntry> = by_id
+        .into_values()
+        .map(|(mut entry, files)| {
+            entry.files = files.into_iter().collect();
+            entry
+        })
+        .collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.timestamp));
+    Ok(entries)
+}
+
+fn print_help() {
+    eprintln!("git ai prompts - Browse the prompt records git-ai stores");
+    eprintln!();
+    eprintln!("Usage:");
+    eprintln!("  git ai prompts list [filters] [--json]");
+    eprintln!("  git ai prompts search <text> [filters] [--json]");
+    eprintln!("  git ai prompts show <id> [--range <range>] [--json]");
+    eprintln!();
+    eprintln!("Records come from authorship notes on committed history and from");
+    eprintln!("the working log of uncommitted changes.");
+    eprintln!();
+    eprintln!("Filters:");
+    eprintln!("  --tool <tool>       Only prompts from this tool (e.g. claude, cursor)");
+    eprintln!("  --model <model>     Only prompts whose model contains this text");
+    eprintln!("  --file <path>       Only prompts that touched this file or directory");
+    eprintln!("  --since <date>      Only prompts on or after YYYY-MM-DD (UTC)");
+    eprintln!("  --until <date>      Only prompts on or before YYYY-MM-DD (UTC)");
+    eprintln!("  --range <range>     Commits to read notes from (default: HEAD)");
+    eprintln!();
+    eprintln!("`search` matches text in any stored field, case-insensitively.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::authorship_log::{LineRange, PromptRecord, SessionRecord};
+    use crate::authorship::authorship_log_serialization::{AttestationEntry, FileAttestation};
+    use crate::authorship::working_log::AgentId;
+    use std::collections::HashMap;
+
+    fn agent(tool: &str, model: &str) -> AgentId {
+        AgentId {
+            tool: tool.to_string(),
+            id: format!("{}-thread", tool),
+            model: model.to_string(),
+        }
+    }
+
+    fn sample_log() -> AuthorshipLog {
+        let mut log = AuthorshipLog::new();
+        log.metadata.prompts.insert(
+            "abcdef0123456789".to_string(),
+            PromptRecord {
+                agent_id: agent("cursor", "gpt-5"),
+                human_author: Some("Dev <dev@example.com>".to_string()),
+                messages_url: None,
+                total_additions: 3,
+                total_deletions: 0,
+                accepted_lines: 3,
+                overriden_lines: 0,
+                custom_attributes: Some(HashMap::from([(
+                    "team".to_string(),
+                    "platform".to_string(),
+                )])),
+            },
+        );
+        log.metadata.sessions.insert(
+            "s_00112233445566".to_string(),
+            SessionRecord {
+                agent_id: agent("claude", "claude-sonnet"),
+                human_author: None,
+                custom_attributes: None,
+                tool_calls: Default::default(),
+                subagents: Default::default(),
+                tool_versions: Default::default(),
+            },
+        );
+        let mut file = FileAttestation::new("src/lib.rs".to_string());
+        file.add_entry(AttestationEntry::new(
+            "s_00112233445566::t_aaaaaaaaaaaaaa".to_string(),
+            vec![LineRange::Range(1, 2)],
+        ));
+        log.attestations.push(file);
+        let mut file = FileAttestation::new("docs/guide.md".to_string());
+        file.add_entry(AttestationEntry::new(
+            "abcdef0123456789".to_string(),
+            vec![LineRange::Single(4)],
+        ));
+        log.attestations.push(file);
+        log
+    }
+
+    #[test]
+    fn note_prompts_cover_prompts_and_sessions() {
+        let entries = note_prompts("c0ffee", 1_700_000_000, &sample_log());
+        assert_eq!(entries.len(), 2);
+        let prompt = entries.iter().find(|e| e.tool == "cursor").unwrap();
+        assert_eq!(prompt.files, vec!["docs/guide.md".to_string()]);
+        assert_eq!(
+            prompt.attributes.get("team").map(String::as_str),
+            Some("platform")
+        );
+        let session = entries.iter().find(|e| e.tool == "claude").unwrap();
+        assert_eq!(session.id, "s_00112233445566");
+        assert_eq!(session.files, vec!["src/lib.rs".to_string()]);
+    }
+
+    #[test]
+    fn filters_match_tool_file_date_and_text() {
+        let entries = note_prompts("c0ffee", parse_date("2026-03-02").unwrap(), &sample_log());
+        let count = |filter: PromptFilter| entries.iter().filter(|e| filter.matches(e)).count();
+
+        assert_eq!(count(PromptFilter::default()), 2);
+        assert_eq!(
+            count(PromptFilter {
+                tool: Some("Claude".to_string()),
+                ..Default::default()
+            }),
+            1
+        );
+        assert_eq!(
+            count(PromptFilter {
+                file: Some("docs".to_string()),
+                ..Default::default()
+            }),
+            1
+        );
+        assert_eq!(
+            count(PromptFilter {
+                since: Some(parse_date("2026-03-03").unwrap()),
+                ..Default::default()
+            }),
+            0
+        );
+        assert_eq!(
+            count(PromptFilter {
+                text: Some("PLATFORM".to_string()),
+                ..Default::default()
+            }),
+            1
+        );
+        assert!(parse_date("03/02/2026").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! `git-ai reconcile` — restore attribution quarantined after upstream force-pushes.
+//!
+//! See [`crate::authorship::quarantine`] for how commits are quarantined on
+//! fetch and matched onto the rewritten history.
+
+use crate::authorship::quarantine::{
+    ReconcileMatch, ReconcileReport, list_quarantine_entries, reconcile_quarantine,
+};
+use crate::commands::output::OutputOptions;
+use crate::git::find_repository;
+
+pub fn handle_reconcile(args: &[String]) {
+    let mut dry_run = false;
+    let mut json = OutputOptions::current().is_json();
+    let mut list = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "--dry-run" | "-n" => dry_run = true,
+            "--json" => json = true,
+            "--list" => list = true,
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other => {
+                eprintln!("error: unknown option '{}'", other);
+                eprintln!("Run 'git-ai reconcile --help' for usage");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("error: not a git repository ({})", e);
+            std::process::exit(1);
+        }
+    };
+
+    if list {
+        let entries = list_quarantine_entries(&repo);
+        if json {
+            match serde_json::to_string_pretty(&entries) {
+                Ok(s) => println!("{}", s),
+                Err(e) => {
+                    eprintln!("error serializing JSON: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        if entries.is_empty() {
+            eprintln!("No quarantined attribution.");
+        }
+        for entry in entries {
+            println!(
+                "{}  {}  {}..{}  {} commit(s), {} working log(s)",
+                entry.id,
+                entry.reference,
+                short(&entry.old_tip),
+                short(&entry.new_tip),
+                entry.commits.len(),
+                entry.working_logs.len()
+            );
+        }
+        return;
+    }
+
+    let report = match reconcile_quarantine(&repo, dry_run) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    if json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(s) => println!("{}", s),
+            Err(e) => {
+                eprintln!("error serializing JSON: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        print_report(&report, dry_run);
+    }
+}
+
+fn short(sha: &str) -> &str {
+    &sha[..sha.len().min(8)]
+}
+
+fn print_report(report: &ReconcileReport, dry_run: bool) {
+    if report.entries == 0 {
+        eprintln!("No quarantined attribution. Nothing to reconcile.");
+        return;
+    }
+    for commit in &report.reconciled {
+        let matched_by = match commit.matched_by {
+            ReconcileMatch::PatchId => "patch-id",
+            ReconcileMatch::Similarity => "similar patch",
+        };
+        println!(
+            "{} -> {}  ({})",
+            short(&commit.old_sha),
+            short(&commit.new_sha),
+            matched_by
+        );
+    }
+    for sha in &report.unmatched {
+        println!("{} -> (no match, kept in quarantine)", short(sha));
+    }
+    eprintln!(
+        "{} {} commit(s) and {} working log(s); {} left in quarantine.",
+        if dry_run { "Would restore" } else { "Restored" },
+        report.reconciled.len(),
+        report.working_logs_restored,
+        report.unmatched.len()
+    );
+}
+
+fn print_help() {
+    eprintln!("git-ai reconcile - Restore attribution orphaned by upstream force-pushes");
+    eprintln!();
+    eprintln!("Usage: git-ai reconcile [options]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --list            List quarantined force-push rewrites");
+    eprintln!("  --dry-run, -n     Report matches without writing notes or working logs");
+    eprintln!("  --json            Output as JSON");
+    eprintln!("  --help            Show this help");
+    eprintln!();
+    eprintln!("When a fetch force-updates a remote-tracking branch, notes and working logs");
+    eprintln!("of commits that disappeared are quarantined. This maps them onto the new");
+    eprintln!("history by patch-id, or by added-line similarity when the patch changed.");
+}
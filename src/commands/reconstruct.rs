@@ -0,0 +1,125 @@
+//! `git-ai reconstruct` — recover attribution for commits rewritten without git-ai.
+//!
+//! See [`crate::authorship::reconstruct`] for how commits are matched.
+
+use crate::authorship::reconstruct::{ReconstructReport, reconstruct_range};
+use crate::commands::output::OutputOptions;
+use crate::git::find_repository;
+
+pub fn handle_reconstruct(args: &[String]) {
+    let mut dry_run = false;
+    let mut json = OutputOptions::current().is_json();
+    let mut range: Option<String> = None;
+    let mut sources: Vec<String> = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--dry-run" | "-n" => dry_run = true,
+            "--json" => json = true,
+            "--source" if i + 1 < args.len() => {
+                sources.push(args[i + 1].clone());
+                i += 1;
+            }
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other if other.starts_with('-') => {
+                eprintln!("error: unknown option '{}'", other);
+                eprintln!("Run 'git-ai reconstruct --help' for usage");
+                std::process::exit(1);
+            }
+            other if range.is_none() => range = Some(other.to_string()),
+            other => {
+                eprintln!("error: unexpected argument '{}'", other);
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let Some(range) = range else {
+        eprintln!("error: reconstruct requires a commit range, e.g. main~20..main");
+        std::process::exit(1);
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("error: not a git repository ({})", e);
+            std::process::exit(1);
+        }
+    };
+
+    let report = match reconstruct_range(&repo, &range, &sources, dry_run) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    if json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(s) => println!("{}", s),
+            Err(e) => {
+                eprintln!("error serializing JSON: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        print_report(&report, dry_run);
+    }
+}
+
+fn short(sha: &str) -> &str {
+    &sha[..sha.len().min(8)]
+}
+
+fn print_report(report: &ReconstructReport, dry_run: bool) {
+    if report.commits == report.already_noted {
+        eprintln!(
+            "All {} commit(s) in the range have notes. Nothing to reconstruct.",
+            report.commits
+        );
+        return;
+    }
+    for commit in &report.reconstructed {
+        println!(
+            "{} -> {}  (patch-id)",
+            short(&commit.source_sha),
+            short(&commit.target_sha)
+        );
+    }
+    for sha in &report.unmatched {
+        println!("{}  (no noted commit with the same patch)", short(sha));
+    }
+    eprintln!(
+        "{} {} of {} commit(s) missing notes; {} already had notes.",
+        if dry_run {
+            "Would reconstruct"
+        } else {
+            "Reconstructed"
+        },
+        report.reconstructed.len(),
+        report.reconstructed.len() + report.unmatched.len(),
+        report.already_noted
+    );
+}
+
+fn print_help() {
+    eprintln!("git-ai reconstruct - Recover attribution for commits rewritten without git-ai");
+    eprintln!();
+    eprintln!("Usage: git-ai reconstruct <range> [options]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --source <rev>    Take notes only from commits reachable from <rev> (repeatable;");
+    eprintln!("                    default: every branch, tag and remote-tracking branch)");
+    eprintln!("  --dry-run, -n     Report matches without writing notes");
+    eprintln!("  --json            Output as JSON");
+    eprintln!("  --help            Show this help");
+    eprintln!();
+    eprintln!("Rebases and cherry-picks made while git-ai's hooks were off leave commits");
+    eprintln!("without notes. Each commit in <range> that has none is matched to a noted");
+    eprintln!("commit with the same patch-id, and that commit's attribution is carried over.");
+}
@@ -0,0 +1,180 @@
+//! `git-ai repair` — finish background work that was deferred.
+//!
+//! Notes syncs that outlive their `hook_timeouts` entry in the background
+//! service are journaled instead of blocking later commands (see
+//! [`crate::daemon::watchdog`]), as is the attribution recovery skipped by
+//! repositories over their hook latency budget ([`crate::daemon::hook_budget`]).
+//! This replays them without a timeout, along with any post-commit notes whose
+//! write never landed, and takes a repaired repository out of degraded mode.
+
+use crate::commands::output::OutputOptions;
+use crate::git::find_repository;
+use crate::git::notes_api::replay_pending_notes;
+use crate::git::repo_storage::{PendingRepairRecord, RepairKind};
+use crate::git::repository::Repository;
+use crate::git::sync_authorship::{fetch_authorship_notes, push_authorship_notes};
+
+pub fn handle_repair(args: &[String]) {
+    let mut output = OutputOptions::current();
+    let mut list = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "--list" => list = true,
+            other if output.parse_flag(other) => {}
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other => {
+                eprintln!("error: unknown option '{}'", other);
+                eprintln!("Run 'git-ai repair --help' for usage");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("error: not a git repository ({})", e);
+            std::process::exit(1);
+        }
+    };
+
+    let pending = repo.storage.read_pending_repairs();
+    if list {
+        if output.is_json() {
+            let records: Vec<_> = pending
+                .iter()
+                .map(|record| {
+                    serde_json::json!({
+                        "kind": record.kind.hook_name(),
+                        "remote": record.remote,
+                        "commit": record.commit_sha,
+                        "deferred_at": record.deferred_at,
+                    })
+                })
+                .collect();
+            if let Err(e) = output.print_json(&records) {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        if pending.is_empty() && !output.is_quiet() {
+            eprintln!("No deferred work.");
+        }
+        for record in &pending {
+            println!(
+                "{}  {}  deferred at {}",
+                record.kind.hook_name(),
+                record.target(),
+                chrono::DateTime::from_timestamp(record.deferred_at, 0)
+                    .map(|time| time.to_rfc3339())
+                    .unwrap_or_else(|| record.deferred_at.to_string())
+            );
+        }
+        return;
+    }
+
+    let mut failed = 0usize;
+    let mut repaired = Vec::new();
+    for record in &pending {
+        match run_repair(&repo, record) {
+            Ok(()) => {
+                if let Err(e) = repo.storage.remove_pending_repair(record) {
+                    eprintln!("error: {}", e);
+                    std::process::exit(1);
+                }
+                if !output.is_quiet() {
+                    println!("{} {}: done", record.kind.hook_name(), record.target());
+                }
+                repaired.push(serde_json::json!({
+                    "kind": record.kind.hook_name(),
+                    "remote": record.remote,
+                    "commit": record.commit_sha,
+                }));
+            }
+            Err(e) => {
+                failed += 1;
+                eprintln!("{} {}: {}", record.kind.hook_name(), record.target(), e);
+            }
+        }
+    }
+
+    let replayed = match replay_pending_notes(&repo) {
+        Ok(count) => count,
+        Err(e) => {
+            failed += 1;
+            eprintln!("pending notes: {}", e);
+            0
+        }
+    };
+    if failed == 0
+        && let Err(e) = crate::daemon::hook_budget::clear_degradation(&repo)
+    {
+        failed += 1;
+        eprintln!("hook budget: {}", e);
+    }
+    if output.is_json() {
+        let summary = serde_json::json!({
+            "repaired": repaired,
+            "failed": failed,
+            "notes_written": replayed,
+        });
+        if let Err(e) = output.print_json(&summary) {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    } else if !output.is_quiet() {
+        if replayed > 0 {
+            println!("Wrote {} pending authorship note(s).", replayed);
+        }
+        if pending.is_empty() && replayed == 0 && failed == 0 {
+            eprintln!("Nothing to repair.");
+        }
+    }
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
+
+fn run_repair(
+    repo: &Repository,
+    record: &PendingRepairRecord,
+) -> Result<(), crate::error::GitAiError> {
+    let remote = record.remote.as_str();
+    match record.kind {
+        RepairKind::FetchNotes => {
+            if crate::config::Config::get().notes_backend_kind()
+                == crate::config::NotesBackendKind::Http
+            {
+                return crate::git::notes_api::warm_cache_for_remote(repo, remote);
+            }
+            fetch_authorship_notes(repo, remote).map(|_| ())
+        }
+        RepairKind::PushNotes => push_authorship_notes(repo, remote),
+        RepairKind::RecoverAttribution => {
+            crate::authorship::post_commit::recover_attribution_for_commit(repo, record.target())
+        }
+    }
+}
+
+fn print_help() {
+    eprintln!("git-ai repair - Finish background work deferred after a timeout");
+    eprintln!();
+    eprintln!("Usage: git-ai repair [options]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --list            List deferred work without running it");
+    eprintln!("  --json            Print the listing or a summary of the run as JSON");
+    eprintln!("  --quiet, -q       Only report failures");
+    eprintln!("  --help            Show this help");
+    eprintln!();
+    eprintln!("Notes fetches and pushes that exceed their hook_timeouts entry are");
+    eprintln!("deferred so they never hold up git, as is attribution recovery in");
+    eprintln!("repositories degraded by max_hook_latency_ms. This runs them to");
+    eprintln!("completion, writes any journaled authorship notes that were not written,");
+    eprintln!("and ends degraded mode.");
+}
@@ -0,0 +1,409 @@
+//! `git-ai report --digest` — periodic summary built from local metrics.
+//!
+//! The digest is computed entirely from the local metrics database. With
+//! `--post` it is sent to the Slack incoming webhook configured as
+//! `slack_webhook_url`; that webhook is the only network call made.
+
+use crate::metrics::local_stats::{ActivityDigest, compute_digest};
+use chrono::{Local, TimeZone};
+use serde_json::{Value, json};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DigestPeriod {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl DigestPeriod {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "daily" => Some(DigestPeriod::Daily),
+            "weekly" => Some(DigestPeriod::Weekly),
+            "monthly" => Some(DigestPeriod::Monthly),
+            _ => None,
+        }
+    }
+
+    fn days(self) -> u32 {
+        match self {
+            DigestPeriod::Daily => 1,
+            DigestPeriod::Weekly => 7,
+            DigestPeriod::Monthly => 30,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            DigestPeriod::Daily => "daily",
+            DigestPeriod::Weekly => "weekly",
+            DigestPeriod::Monthly => "monthly",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DigestFormat {
+    Markdown,
+    SlackJson,
+}
+
+pub fn handle_report(args: &[String]) {
+    let mut period = None;
+    let mut format = DigestFormat::Markdown;
+    let mut post = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--digest" if i + 1 < args.len() => {
+                period = match DigestPeriod::parse(&args[i + 1]) {
+                    Some(period) => Some(period),
+                    None => exit_with(&format!(
+                        "Invalid --digest value '{}'. Expected daily, weekly or monthly",
+                        args[i + 1]
+                    )),
+                };
+                i += 1;
+            }
+            "--format" if i + 1 < args.len() => {
+                format = match args[i + 1].as_str() {
+                    "markdown" | "md" => DigestFormat::Markdown,
+                    "slack-json" => DigestFormat::SlackJson,
+                    other => exit_with(&format!(
+                        "Invalid --format value '{}'. Expected markdown or slack-json",
+                        other
+                    )),
+                };
+                i += 1;
+            }
+            "--post" => post = true,
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other => {
+                eprintln!("Unknown argument: {}", other);
+                eprintln!("Run 'git-ai report --help' for usage.");
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let Some(period) = period else {
+        exit_with("Missing --digest <daily|weekly|monthly>");
+    };
+
+    let until_ts = now_ts();
+    let since_ts = until_ts.saturating_sub(period.days() * 24 * 3600);
+    let label = match period.days() {
+        1 => "last 1 day".to_string(),
+        days => format!("last {} days", days),
+    };
+    let digest = match compute_digest(since_ts, until_ts, label) {
+        Ok(digest) => digest,
+        Err(e) => exit_with(&format!("error: {}", e)),
+    };
+
+    if post {
+        let webhook_url = crate::config::load_effective_file_config()
+            .ok()
+            .and_then(|config| config.slack_webhook_url)
+            .filter(|url| !url.trim().is_empty());
+        let Some(webhook_url) = webhook_url else {
+            exit_with(
+                "No Slack webhook configured. Set one with: git-ai config set slack_webhook_url <url>",
+            );
+        };
+        if let Err(e) = post_to_slack(&webhook_url, &slack_payload(&digest, period)) {
+            exit_with(&format!("Failed to post digest to Slack: {}", e));
+        }
+        eprintln!("Posted {} digest to Slack.", period.as_str());
+        return;
+    }
+
+    match format {
+        DigestFormat::Markdown => print!("{}", render_markdown(&digest, period)),
+        DigestFormat::SlackJson => {
+            match serde_json::to_string_pretty(&slack_payload(&digest, period)) {
+                Ok(s) => println!("{}", s),
+                Err(e) => exit_with(&format!("error serializing JSON: {}", e)),
+            }
+        }
+    }
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1);
+}
+
+fn now_ts() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        .min(u32::MAX as u64) as u32
+}
+
+fn print_help() {
+    eprintln!("git-ai report - Summarize local AI activity for sharing");
+    eprintln!();
+    eprintln!("Usage: git-ai report --digest <daily|weekly|monthly> [options]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --digest <period>                 Period to summarize, ending now");
+    eprintln!("  --format <markdown|slack-json>    Output format (default: markdown)");
+    eprintln!("  --post                            Post to the configured slack_webhook_url");
+    eprintln!("  --help                            Show this help");
+    eprintln!();
+    eprintln!("The digest is computed from locally recorded metric events only.");
+}
+
+fn date_range(digest: &ActivityDigest) -> String {
+    let day = |ts: u32| {
+        Local
+            .timestamp_opt(i64::from(ts), 0)
+            .single()
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_default()
+    };
+    format!("{} – {}", day(digest.since_ts), day(digest.until_ts))
+}
+
+fn ai_share(digest: &ActivityDigest) -> String {
+    digest
+        .ai_share_pct
+        .map(|pct| format!("{:.1}%", pct))
+        .unwrap_or_else(|| "n/a".to_string())
+}
+
+/// e.g. "62.5% (5 of 8 suggestions, 14 lines)".
+fn tab_acceptance(digest: &ActivityDigest) -> String {
+    let tab = &digest.tab_completions;
+    match tab.acceptance_rate_pct {
+        Some(pct) => format!(
+            "{:.1}% ({} of {} suggestions, {} lines)",
+            pct,
+            tab.accepted,
+            tab.accepted + tab.rejected,
+            tab.accepted_lines
+        ),
+        None => "n/a".to_string(),
+    }
+}
+
+fn file_label(file: &crate::metrics::local_stats::DigestFile) -> String {
+    match &file.repo_url {
+        Some(repo_url) => format!("`{}` ({})", file.file_path, repo_url),
+        None => format!("`{}`", file.file_path),
+    }
+}
+
+fn render_markdown(digest: &ActivityDigest, period: DigestPeriod) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("# git-ai {} digest\n\n", period.as_str()));
+    out.push_str(&format!(
+        "_{} ({})_\n\n",
+        capitalize(&digest.period_label),
+        date_range(digest)
+    ));
+
+    out.push_str("| Metric | Value |\n|---|---|\n");
+    for (metric, value) in [
+        ("AI share of added lines", ai_share(digest)),
+        ("AI lines committed", digest.ai_lines.to_string()),
+        ("Human lines committed", digest.human_lines.to_string()),
+        ("AI-assisted commits", digest.commits.to_string()),
+        ("Agent sessions", digest.sessions.to_string()),
+        ("Tab completion acceptance", tab_acceptance(digest)),
+        (
+            "Estimated spend",
+            format!("${:.2}", digest.estimated_cost_usd),
+        ),
+    ] {
+        out.push_str(&format!("| {} | {} |\n", metric, value));
+    }
+
+    out.push_str("\n## Top tools\n\n");
+    if digest.top_tools.is_empty() {
+        out.push_str("_No AI lines committed._\n");
+    }
+    for (rank, (tool, lines)) in digest.top_tools.iter().enumerate() {
+        out.push_str(&format!("{}. {} — {} lines\n", rank + 1, tool, lines));
+    }
+
+    out.push_str("\n## Biggest AI-authored files\n\n");
+    if digest.top_files.is_empty() {
+        out.push_str("_No AI lines committed._\n");
+    }
+    for (rank, file) in digest.top_files.iter().enumerate() {
+        out.push_str(&format!(
+            "{}. {} — {} lines\n",
+            rank + 1,
+            file_label(file),
+            file.ai_lines
+        ));
+    }
+    out
+}
+
+/// Slack Block Kit message; `text` is the notification fallback.
+fn slack_payload(digest: &ActivityDigest, period: DigestPeriod) -> Value {
+    let list = |items: Vec<String>| {
+        if items.is_empty() {
+            "_No AI lines committed._".to_string()
+        } else {
+            items.join("\n")
+        }
+    };
+    let tools = list(
+        digest
+            .top_tools
+            .iter()
+            .map(|(tool, lines)| format!("• {} — {} lines", tool, lines))
+            .collect(),
+    );
+    let files = list(
+        digest
+            .top_files
+            .iter()
+            .map(|file| format!("• {} — {} lines", file_label(file), file.ai_lines))
+            .collect(),
+    );
+    let mrkdwn = |text: String| json!({"type": "mrkdwn", "text": text});
+
+    json!({
+        "text": format!(
+            "git-ai {} digest: {} AI share, {} AI lines across {} commits",
+            period.as_str(),
+            ai_share(digest),
+            digest.ai_lines,
+            digest.commits
+        ),
+        "blocks": [
+            {
+                "type": "header",
+                "text": {"type": "plain_text", "text": format!("git-ai {} digest", period.as_str())}
+            },
+            {
+                "type": "section",
+                "fields": [
+                    mrkdwn(format!("*AI share*\n{}", ai_share(digest))),
+                    mrkdwn(format!("*AI lines*\n{}", digest.ai_lines)),
+                    mrkdwn(format!("*AI-assisted commits*\n{}", digest.commits)),
+                    mrkdwn(format!("*Agent sessions*\n{}", digest.sessions)),
+                    mrkdwn(format!("*Tab completion acceptance*\n{}", tab_acceptance(digest))),
+                ]
+            },
+            {"type": "section", "text": mrkdwn(format!("*Top tools*\n{}", tools))},
+            {
+                "type": "section",
+                "text": mrkdwn(format!("*Biggest AI-authored files*\n{}", files))
+            },
+            {
+                "type": "context",
+                "elements": [mrkdwn(format!(
+                    "{} ({}) · computed locally by git-ai {}",
+                    capitalize(&digest.period_label),
+                    date_range(digest),
+                    env!("CARGO_PKG_VERSION")
+                ))]
+            }
+        ]
+    })
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn post_to_slack(webhook_url: &str, payload: &Value) -> Result<(), String> {
+    let agent = crate::http::build_agent(Some(30));
+    let request = agent
+        .post(webhook_url)
+        .set("Content-Type", "application/json");
+    let response = crate::http::send_with_body(request, &payload.to_string())?;
+    if response.status_code == 200 {
+        Ok(())
+    } else {
+        Err(format!(
+            "HTTP {}: {}",
+            response.status_code,
+            response.as_str().unwrap_or("").trim()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::local_stats::{DigestFile, TabCompletionSummary};
+
+    fn digest() -> ActivityDigest {
+        ActivityDigest {
+            period_label: "last 7 days".to_string(),
+            since_ts: 1_700_000_000,
+            until_ts: 1_700_604_800,
+            commits: 4,
+            ai_lines: 30,
+            human_lines: 10,
+            diff_added_lines: 40,
+            ai_share_pct: Some(75.0),
+            sessions: 2,
+            estimated_cost_usd: 1.5,
+            top_tools: vec![("claude · sonnet-4-6".to_string(), 30)],
+            top_files: vec![DigestFile {
+                repo_url: Some("github.com/acme/project".to_string()),
+                file_path: "src/lib.rs".to_string(),
+                ai_lines: 12,
+            }],
+            tab_completions: TabCompletionSummary {
+                accepted: 5,
+                rejected: 3,
+                accepted_lines: 14,
+                acceptance_rate_pct: Some(62.5),
+            },
+        }
+    }
+
+    #[test]
+    fn test_markdown_and_slack_digest_render_the_same_summary() {
+        let markdown = render_markdown(&digest(), DigestPeriod::Weekly);
+        assert!(markdown.starts_with("# git-ai weekly digest\n"));
+        assert!(markdown.contains("| AI share of added lines | 75.0% |"));
+        assert!(
+            markdown
+                .contains("| Tab completion acceptance | 62.5% (5 of 8 suggestions, 14 lines) |")
+        );
+        assert!(markdown.contains("1. claude · sonnet-4-6 — 30 lines"));
+        assert!(markdown.contains("1. `src/lib.rs` (github.com/acme/project) — 12 lines"));
+
+        let payload = slack_payload(&digest(), DigestPeriod::Weekly);
+        assert_eq!(
+            payload["text"],
+            "git-ai weekly digest: 75.0% AI share, 30 AI lines across 4 commits"
+        );
+        assert_eq!(payload["blocks"][0]["type"], "header");
+        assert_eq!(
+            payload["blocks"][1]["fields"][0]["text"],
+            "*AI share*\n75.0%"
+        );
+        assert_eq!(
+            payload["blocks"][1]["fields"][4]["text"],
+            "*Tab completion acceptance*\n62.5% (5 of 8 suggestions, 14 lines)"
+        );
+        assert!(
+            payload["blocks"][3]["text"]["text"]
+                .as_str()
+                .unwrap()
+                .contains("`src/lib.rs`")
+        );
+    }
+}
@@ -0,0 +1,399 @@
+//! `git-ai resolve` — settle conflicting attribution between two versions of a note.
+//!
+//! Compares the local note of a commit with another version of it (the note a
+//! remote has, any notes ref, or a reconstructed note on disk), shows each
+//! range the two attribute differently together with the code, and asks which
+//! side to keep. The union of both versions with those picks applied is written
+//! back to the local note with a `resolutions` audit marker.
+
+use crate::authorship::authorship_log_serialization::{AuthorshipLog, ResolutionRecord};
+use crate::authorship::note_resolution::{
+    AttributionConflict, ResolutionSide, describe_author, find_conflicts, resolve_conflicts,
+};
+use crate::commands::output::OutputOptions;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::notes_api;
+use crate::git::refs::tracking_ref_for_remote;
+use crate::git::repository::{Repository, exec_git};
+use serde::Serialize;
+use std::io::{BufRead, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Code lines shown per side of a conflict before eliding the rest.
+const MAX_PREVIEW_LINES: usize = 6;
+
+enum OtherVersion {
+    NotesRef(String),
+    File(String),
+}
+
+impl OtherVersion {
+    fn source(&self) -> String {
+        match self {
+            OtherVersion::NotesRef(notes_ref) => notes_ref.clone(),
+            OtherVersion::File(path) => format!("file:{}", path),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ResolveOutput<'a> {
+    commit: &'a str,
+    source: String,
+    conflicts: Vec<ConflictOutput<'a>>,
+    written: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ConflictOutput<'a> {
+    #[serde(flatten)]
+    conflict: &'a AttributionConflict,
+    ours_author: String,
+    theirs_author: String,
+    picked: &'static str,
+}
+
+pub fn handle_resolve(args: &[String]) {
+    let mut output = OutputOptions::current();
+    let mut commit: Option<String> = None;
+    let mut other: Option<OtherVersion> = None;
+    let mut pick_all: Option<ResolutionSide> = None;
+    let mut dry_run = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--remote" | "--ref" | "--file" if i + 1 < args.len() => {
+                if other.is_some() {
+                    eprintln!("Error: only one of --remote, --ref and --file may be given");
+                    std::process::exit(1);
+                }
+                let value = args[i + 1].clone();
+                other = Some(match args[i].as_str() {
+                    "--remote" => OtherVersion::NotesRef(tracking_ref_for_remote(&value)),
+                    "--ref" if value.starts_with("refs/") => OtherVersion::NotesRef(value),
+                    "--ref" => OtherVersion::NotesRef(format!("refs/notes/{}", value)),
+                    _ => OtherVersion::File(value),
+                });
+                i += 2;
+            }
+            "--ours" => {
+                pick_all = Some(ResolutionSide::Ours);
+                i += 1;
+            }
+            "--theirs" => {
+                pick_all = Some(ResolutionSide::Theirs);
+                i += 1;
+            }
+            "--dry-run" | "-n" => {
+                dry_run = true;
+                i += 1;
+            }
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            flag if output.parse_flag(flag) => {
+                i += 1;
+            }
+            other if !other.starts_with('-') && commit.is_none() => {
+                commit = Some(other.to_string());
+                i += 1;
+            }
+            other => {
+                eprintln!("Error: unknown option '{}'", other);
+                eprintln!("Run 'git ai resolve --help' for usage");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let Some(other) = other else {
+        eprintln!("Error: give the other version with --remote, --ref or --file");
+        eprintln!("Run 'git ai resolve --help' for usage");
+        std::process::exit(1);
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Error: not a git repository ({})", e);
+            std::process::exit(1);
+        }
+    };
+
+    let commit_sha = match repo.revparse_single(commit.as_deref().unwrap_or("HEAD")) {
+        Ok(object) => object.id(),
+        Err(e) => {
+            output.exit_with_error(&e);
+        }
+    };
+
+    let (ours, theirs) = match load_versions(&repo, &commit_sha, &other) {
+        Ok(versions) => versions,
+        Err(e) => {
+            output.exit_with_error(&e);
+        }
+    };
+
+    let conflicts = find_conflicts(&ours, &theirs);
+    if conflicts.is_empty() {
+        if output.is_json() {
+            print_json_or_exit(
+                &output,
+                &ResolveOutput {
+                    commit: &commit_sha,
+                    source: other.source(),
+                    conflicts: Vec::new(),
+                    written: false,
+                },
+            );
+        } else if !output.is_quiet() {
+            eprintln!(
+                "No conflicting attribution between the local note and {}.",
+                other.source()
+            );
+        }
+        return;
+    }
+
+    // JSON output cannot prompt, so without a side it only reports.
+    let dry_run = dry_run || (output.is_json() && pick_all.is_none());
+    let picks: Vec<ResolutionSide> = match pick_all {
+        Some(side) => vec![side; conflicts.len()],
+        None if dry_run => vec![ResolutionSide::Ours; conflicts.len()],
+        None => match prompt_for_picks(&repo, &commit_sha, &ours, &theirs, &conflicts) {
+            Some(picks) => picks,
+            None => {
+                eprintln!("Aborted; the note was not changed.");
+                std::process::exit(1);
+            }
+        },
+    };
+
+    let record = ResolutionRecord {
+        source: other.source(),
+        resolved_by: repo.effective_author_identity().formatted_or_unknown(),
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default(),
+        ours_lines: 0,
+        theirs_lines: 0,
+    };
+    let resolved = resolve_conflicts(&ours, &theirs, &conflicts, &picks, record);
+
+    if dry_run && !output.is_json() && !output.is_quiet() {
+        for conflict in &conflicts {
+            println!(
+                "{}:{}-{}  ours: {}  theirs: {}",
+                conflict.file_path,
+                conflict.start_line,
+                conflict.end_line,
+                describe_author(&ours, &conflict.ours),
+                describe_author(&theirs, &conflict.theirs)
+            );
+        }
+    }
+
+    let written = !dry_run;
+    if written {
+        let result = resolved
+            .serialize_to_string()
+            .map_err(|e| GitAiError::Generic(format!("Failed to serialize note: {}", e)))
+            .and_then(|note| notes_api::write_note(&repo, &commit_sha, &note));
+        if let Err(e) = result {
+            eprintln!("Error: failed to write the resolved note: {}", e);
+            std::process::exit(e.exit_code());
+        }
+    }
+
+    if output.is_json() {
+        let conflicts = conflicts
+            .iter()
+            .zip(&picks)
+            .map(|(conflict, pick)| ConflictOutput {
+                conflict,
+                ours_author: describe_author(&ours, &conflict.ours),
+                theirs_author: describe_author(&theirs, &conflict.theirs),
+                picked: match pick {
+                    ResolutionSide::Ours => "ours",
+                    ResolutionSide::Theirs => "theirs",
+                },
+            })
+            .collect();
+        print_json_or_exit(
+            &output,
+            &ResolveOutput {
+                commit: &commit_sha,
+                source: other.source(),
+                conflicts,
+                written,
+            },
+        );
+        return;
+    }
+
+    if !output.is_quiet()
+        && let Some(record) = resolved.metadata.resolutions.last()
+    {
+        let verb = if written { "Resolved" } else { "Would resolve" };
+        println!(
+            "{} {} conflicting range(s) on {}: {} line(s) kept ours, {} took {}",
+            verb,
+            conflicts.len(),
+            &commit_sha[..commit_sha.len().min(8)],
+            record.ours_lines,
+            record.theirs_lines,
+            record.source
+        );
+    }
+}
+
+/// The local note and the other version of it, both parsed.
+fn load_versions(
+    repo: &Repository,
+    commit_sha: &str,
+    other: &OtherVersion,
+) -> Result<(AuthorshipLog, AuthorshipLog), GitAiError> {
+    let ours = notes_api::read_note(repo, commit_sha)
+        .ok_or_else(|| GitAiError::Notes(format!("no local authorship note on {}", commit_sha)))?;
+    let theirs = match other {
+        OtherVersion::NotesRef(notes_ref) => read_note_from_ref(repo, notes_ref, commit_sha)?,
+        OtherVersion::File(path) => std::fs::read_to_string(path)?,
+    };
+
+    let parse = |content: &str, what: &str| {
+        AuthorshipLog::deserialize_from_string(content)
+            .map_err(|e| GitAiError::Notes(format!("{} does not parse: {}", what, e)))
+    };
+    Ok((
+        parse(&ours, "the local note")?,
+        parse(&theirs, &other.source())?,
+    ))
+}
+
+fn read_note_from_ref(
+    repo: &Repository,
+    notes_ref: &str,
+    commit_sha: &str,
+) -> Result<String, GitAiError> {
+    // `--ref` prepends `refs/notes/` itself.
+    let ref_arg = notes_ref.strip_prefix("refs/notes/").unwrap_or(notes_ref);
+    let mut args = repo.global_args_for_exec();
+    args.push("notes".to_string());
+    args.push(format!("--ref={}", ref_arg));
+    args.push("show".to_string());
+    args.push(commit_sha.to_string());
+    let output = exec_git(&args)
+        .map_err(|_| GitAiError::Notes(format!("no note on {} in {}", commit_sha, notes_ref)))?;
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// Ask for a side per conflict on stdin. `q` or the end of input aborts,
+/// returning `None`, so a closed stdin never picks a side by default.
+fn prompt_for_picks(
+    repo: &Repository,
+    commit_sha: &str,
+    ours: &AuthorshipLog,
+    theirs: &AuthorshipLog,
+    conflicts: &[AttributionConflict],
+) -> Option<Vec<ResolutionSide>> {
+    let stdin = std::io::stdin();
+    let mut input = stdin.lock().lines();
+    let mut picks = Vec::with_capacity(conflicts.len());
+
+    for (index, conflict) in conflicts.iter().enumerate() {
+        println!();
+        println!(
+            "[{}/{}] {}:{}-{}",
+            index + 1,
+            conflicts.len(),
+            conflict.file_path,
+            conflict.start_line,
+            conflict.end_line
+        );
+        println!(
+            "  ours:   {} ({})",
+            describe_author(ours, &conflict.ours),
+            conflict.ours
+        );
+        println!(
+            "  theirs: {} ({})",
+            describe_author(theirs, &conflict.theirs),
+            conflict.theirs
+        );
+        for line in preview_lines(repo, commit_sha, conflict) {
+            println!("  | {}", line);
+        }
+
+        let pick = loop {
+            print!("Keep [o]urs or take [t]heirs? (q to abort) [o] ");
+            let _ = std::io::stdout().flush();
+            let Some(Ok(answer)) = input.next() else {
+                println!();
+                return None;
+            };
+            match answer.trim().to_ascii_lowercase().as_str() {
+                "" | "o" | "ours" => break ResolutionSide::Ours,
+                "t" | "theirs" => break ResolutionSide::Theirs,
+                "q" | "quit" => return None,
+                other => println!("Unrecognized answer '{}'.", other),
+            }
+        };
+        picks.push(pick);
+    }
+    Some(picks)
+}
+
+/// The conflicting lines as committed, elided after [`MAX_PREVIEW_LINES`].
+fn preview_lines(
+    repo: &Repository,
+    commit_sha: &str,
+    conflict: &AttributionConflict,
+) -> Vec<String> {
+    let Ok(content) = repo.get_file_content(&conflict.file_path, commit_sha) else {
+        return Vec::new();
+    };
+    let content = String::from_utf8_lossy(&content);
+    let mut lines: Vec<String> = content
+        .lines()
+        .skip(conflict.start_line.saturating_sub(1) as usize)
+        .take(conflict.line_count() as usize)
+        .map(str::to_string)
+        .collect();
+    if lines.len() > MAX_PREVIEW_LINES {
+        let elided = lines.len() - MAX_PREVIEW_LINES;
+        lines.truncate(MAX_PREVIEW_LINES);
+        lines.push(format!("... {} more line(s)", elided));
+    }
+    lines
+}
+
+fn print_json_or_exit<T: Serialize>(output: &OutputOptions, value: &T) {
+    if let Err(e) = output.print_json(value) {
+        output.exit_with_error(&e);
+    }
+}
+
+fn print_help() {
+    eprintln!("git-ai resolve - Settle conflicting attribution between two versions of a note");
+    eprintln!();
+    eprintln!(
+        "Usage: git-ai resolve [<commit>] (--remote <name> | --ref <notes-ref> | --file <path>)"
+    );
+    eprintln!("                      [--ours | --theirs] [--dry-run] [--json]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --remote <name>    Compare with the note fetched from <name>");
+    eprintln!("  --ref <notes-ref>  Compare with the note on another notes ref");
+    eprintln!("  --file <path>      Compare with a reconstructed note stored in <path>");
+    eprintln!("  --ours, --theirs   Pick the same side for every conflict instead of asking");
+    eprintln!("  -n, --dry-run      Show the conflicts without writing the note");
+    eprintln!("  --json             Print the conflicts and picks as JSON");
+    eprintln!("  --help             Show this help");
+    eprintln!();
+    eprintln!("Lines only one version attests are taken from it either way. The resolved");
+    eprintln!("note records who resolved it against which version under `resolutions`.");
+}
@@ -0,0 +1,107 @@
+//! `git-ai restore-attribution` — recover files and attribution removed by `git clean`.
+
+use crate::authorship::clean_archive::{list_clean_archives, restore_clean_archive};
+use crate::git::find_repository;
+
+pub fn handle_restore_attribution(args: &[String]) {
+    let mut list = false;
+    let mut force = false;
+    let mut archive_id: Option<String> = None;
+
+    for arg in args {
+        match arg.as_str() {
+            "--list" => list = true,
+            "--force" | "-f" => force = true,
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other if other.starts_with('-') => {
+                eprintln!("error: unknown option '{}'", other);
+                eprintln!("Run 'git-ai restore-attribution --help' for usage");
+                std::process::exit(1);
+            }
+            other if archive_id.is_none() => archive_id = Some(other.to_string()),
+            other => {
+                eprintln!("error: unexpected argument '{}'", other);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("error: not a git repository ({})", e);
+            std::process::exit(1);
+        }
+    };
+
+    let archives = list_clean_archives(&repo);
+    if list {
+        if archives.is_empty() {
+            eprintln!("No git clean archives found.");
+        }
+        for archive in &archives {
+            println!(
+                "{}  base {}  {} file(s)",
+                archive.id,
+                &archive.base_commit[..archive.base_commit.len().min(8)],
+                archive.files.len()
+            );
+            for file in &archive.files {
+                println!("    {}", file);
+            }
+        }
+        return;
+    }
+
+    let archive = match archive_id.as_deref() {
+        Some(id) => archives.iter().find(|archive| archive.id == id),
+        None => archives.first(),
+    };
+    let Some(archive) = archive else {
+        match archive_id {
+            Some(id) => eprintln!("error: no git clean archive with id '{}'", id),
+            None => eprintln!("No git clean archives found."),
+        }
+        std::process::exit(1);
+    };
+
+    match restore_clean_archive(&repo, archive, force) {
+        Ok(report) => {
+            for file in &report.restored {
+                println!("restored {}", file);
+            }
+            for file in &report.skipped_existing {
+                eprintln!(
+                    "skipped {} (already exists; use --force to overwrite)",
+                    file
+                );
+            }
+            eprintln!(
+                "Restored {} file(s) from archive {}.",
+                report.restored.len(),
+                archive.id
+            );
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn print_help() {
+    eprintln!("git-ai restore-attribution - Recover files and AI attribution removed by git clean");
+    eprintln!();
+    eprintln!("Usage: git-ai restore-attribution [<archive-id>] [--force]");
+    eprintln!("       git-ai restore-attribution --list");
+    eprintln!();
+    eprintln!("Restores the most recent archive unless an id is given.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --list       List archives taken before git clean, newest first");
+    eprintln!("  -f, --force  Overwrite files that already exist in the worktree");
+    eprintln!("  -h, --help   Show this help");
+}
@@ -0,0 +1,648 @@
+//! `git-ai server-hooks` — receive hooks for self-hosted git servers.
+//!
+//! `install` writes `pre-receive` and `post-receive` scripts into a (usually
+//! bare) repository. Both scripts exec back into this binary:
+//!
+//! - `pre-receive` checks every note a push adds or changes on `refs/notes/ai`:
+//!   the notes tree may only hold note blobs named after object ids, each blob
+//!   must stay under the size limit and parse as a current authorship log, and
+//!   with `--require-signed-push` the push itself must carry a valid push
+//!   certificate (`git push --signed`). Any failure rejects the whole push.
+//! - `post-receive` updates the server's stats rollups for the commits whose
+//!   notes changed, so range queries run on the server stay cheap.
+//!
+//! Updates to other refs are never inspected.
+
+use crate::authorship::authorship_log_serialization::{AUTHORSHIP_LOG_VERSION, AuthorshipLog};
+use crate::authorship::ignore::effective_ignore_patterns;
+use crate::authorship::stats_rollup::rollup_stats_for_commits;
+use crate::error::GitAiError;
+use crate::git::authorship_traversal::batch_read_blobs_with_oids;
+use crate::git::find_repository;
+use crate::git::refs::AI_AUTHORSHIP_FULL_REF;
+use crate::git::repository::{Repository, exec_git, exec_git_stdin};
+use crate::mdm::utils::get_current_binary_path;
+use crate::workspace_config::effective_workspace_config;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// First line after the shebang of every script we write; scripts without it
+/// are never overwritten or removed without `--force`.
+const MANAGED_MARKER: &str = "# Managed by git-ai server-hooks. Do not edit.";
+
+/// Largest accepted note blob unless `--max-note-bytes` overrides it.
+pub const DEFAULT_MAX_NOTE_BYTES: u64 = 1024 * 1024;
+
+/// Rejections printed before the remaining ones are summarized as a count.
+const MAX_REPORTED_REJECTIONS: usize = 20;
+
+const HOOK_NAMES: [&str; 2] = ["pre-receive", "post-receive"];
+
+/// Checks `pre-receive` applies to `refs/notes/ai` updates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotesPolicy {
+    pub max_note_bytes: u64,
+    pub require_signed_push: bool,
+}
+
+impl Default for NotesPolicy {
+    fn default() -> Self {
+        Self {
+            max_note_bytes: DEFAULT_MAX_NOTE_BYTES,
+            require_signed_push: false,
+        }
+    }
+}
+
+/// One `<old> <new> <ref>` line from a receive hook's stdin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefUpdate {
+    pub old_oid: String,
+    pub new_oid: String,
+    pub refname: String,
+}
+
+impl RefUpdate {
+    fn is_notes_update(&self) -> bool {
+        self.refname == AI_AUTHORSHIP_FULL_REF && !is_zero_oid(&self.new_oid)
+    }
+}
+
+/// A note blob added or modified by a notes ref update.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ChangedNote {
+    path: String,
+    mode: String,
+    blob_oid: String,
+}
+
+pub fn handle_server_hooks(args: &[String]) {
+    let subcommand = args.first().map(String::as_str).unwrap_or("--help");
+    let result = match subcommand {
+        "install" => handle_install(&args[1..]),
+        "uninstall" => handle_uninstall(&args[1..]),
+        "pre-receive" => handle_pre_receive(&args[1..]),
+        "post-receive" => handle_post_receive(),
+        "--help" | "-h" | "help" => {
+            print_help();
+            return;
+        }
+        other => {
+            eprintln!("error: unknown server-hooks subcommand '{}'", other);
+            eprintln!("Run 'git-ai server-hooks --help' for usage");
+            std::process::exit(1);
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        std::process::exit(e.exit_code());
+    }
+}
+
+fn handle_install(args: &[String]) -> Result<(), GitAiError> {
+    let mut git_dir: Option<PathBuf> = None;
+    let mut policy = NotesPolicy::default();
+    let mut force = false;
+    let mut dry_run = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--git-dir" if i + 1 < args.len() => {
+                git_dir = Some(PathBuf::from(&args[i + 1]));
+                i += 1;
+            }
+            "--max-note-bytes" if i + 1 < args.len() => {
+                policy.max_note_bytes = parse_max_note_bytes(&args[i + 1])?;
+                i += 1;
+            }
+            "--require-signed-push" => policy.require_signed_push = true,
+            "--force" | "-f" => force = true,
+            "--dry-run" | "-n" => dry_run = true,
+            "--help" | "-h" => {
+                print_help();
+                return Ok(());
+            }
+            other => {
+                return Err(GitAiError::Generic(format!(
+                    "unknown install option '{}'",
+                    other
+                )));
+            }
+        }
+        i += 1;
+    }
+
+    let hooks_dir = hooks_dir(git_dir)?;
+    let binary_path = get_current_binary_path()?;
+    for hook_name in HOOK_NAMES {
+        let path = hooks_dir.join(hook_name);
+        if !force && let Some(existing) = read_unmanaged_hook(&path)? {
+            return Err(GitAiError::Hook(format!(
+                "{} already exists and was not written by git-ai ({} bytes); use --force to replace it",
+                path.display(),
+                existing.len()
+            )));
+        }
+        let script = hook_script(&binary_path, hook_name, &policy);
+        if dry_run {
+            println!("would write {}:\n{}", path.display(), script);
+            continue;
+        }
+        write_hook_script(&path, &script)?;
+        eprintln!("installed {}", path.display());
+    }
+    if policy.require_signed_push && !dry_run {
+        eprintln!(
+            "note: signed pushes also need `git config receive.certNonceSeed <secret>` on this repository"
+        );
+    }
+    Ok(())
+}
+
+fn handle_uninstall(args: &[String]) -> Result<(), GitAiError> {
+    let mut git_dir: Option<PathBuf> = None;
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--git-dir" if i + 1 < args.len() => {
+                git_dir = Some(PathBuf::from(&args[i + 1]));
+                i += 1;
+            }
+            other => {
+                return Err(GitAiError::Generic(format!(
+                    "unknown uninstall option '{}'",
+                    other
+                )));
+            }
+        }
+        i += 1;
+    }
+
+    let hooks_dir = hooks_dir(git_dir)?;
+    for hook_name in HOOK_NAMES {
+        let path = hooks_dir.join(hook_name);
+        let Ok(existing) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if !existing.contains(MANAGED_MARKER) {
+            eprintln!("skipped {} (not written by git-ai)", path.display());
+            continue;
+        }
+        fs::remove_file(&path)?;
+        eprintln!("removed {}", path.display());
+    }
+    Ok(())
+}
+
+fn handle_pre_receive(args: &[String]) -> Result<(), GitAiError> {
+    let mut policy = NotesPolicy::default();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--max-note-bytes" if i + 1 < args.len() => {
+                policy.max_note_bytes = parse_max_note_bytes(&args[i + 1])?;
+                i += 1;
+            }
+            "--require-signed-push" => policy.require_signed_push = true,
+            other => {
+                return Err(GitAiError::Generic(format!(
+                    "unknown pre-receive option '{}'",
+                    other
+                )));
+            }
+        }
+        i += 1;
+    }
+
+    let updates = read_ref_updates_from_stdin()?;
+    if !updates.iter().any(RefUpdate::is_notes_update) {
+        return Ok(());
+    }
+    let repo = find_repository(&Vec::<String>::new())?;
+
+    let mut rejections = Vec::new();
+    if policy.require_signed_push {
+        let status = std::env::var("GIT_PUSH_CERT_STATUS").unwrap_or_default();
+        if let Some(reason) = push_certificate_rejection(&status) {
+            rejections.push(reason);
+        }
+    }
+    for update in updates.iter().filter(|update| update.is_notes_update()) {
+        rejections.extend(validate_notes_update(&repo, update, &policy)?);
+    }
+    if rejections.is_empty() {
+        return Ok(());
+    }
+
+    eprintln!(
+        "git-ai: rejecting push, {} problem(s) in {}:",
+        rejections.len(),
+        AI_AUTHORSHIP_FULL_REF
+    );
+    for rejection in rejections.iter().take(MAX_REPORTED_REJECTIONS) {
+        eprintln!("git-ai:   {}", rejection);
+    }
+    if rejections.len() > MAX_REPORTED_REJECTIONS {
+        eprintln!(
+            "git-ai:   ... and {} more",
+            rejections.len() - MAX_REPORTED_REJECTIONS
+        );
+    }
+    std::process::exit(crate::error::ErrorCategory::Policy.exit_code());
+}
+
+fn handle_post_receive() -> Result<(), GitAiError> {
+    let updates = read_ref_updates_from_stdin()?;
+    if !updates.iter().any(RefUpdate::is_notes_update) {
+        return Ok(());
+    }
+    let repo = find_repository(&Vec::<String>::new())?;
+
+    let mut commit_shas = Vec::new();
+    for update in updates.iter().filter(|update| update.is_notes_update()) {
+        commit_shas.extend(
+            changed_notes(&repo, update)?
+                .iter()
+                .filter_map(|note| note_target_from_path(&note.path)),
+        );
+    }
+    commit_shas.sort();
+    commit_shas.dedup();
+    let commit_shas = existing_commits(&repo, &commit_shas)?;
+    if commit_shas.is_empty() {
+        return Ok(());
+    }
+
+    // Rollup failures must not fail the push, which has already been accepted.
+    let ignore_patterns = effective_ignore_patterns(&repo, &[], &[]);
+    let whitespace = effective_workspace_config(&repo).whitespace_mode();
+    match rollup_stats_for_commits(&repo, &commit_shas, &ignore_patterns, whitespace) {
+        Ok(summary) => eprintln!(
+            "git-ai: updated stats rollups for {} commit(s)",
+            summary.commits
+        ),
+        Err(e) => eprintln!("git-ai: failed to update stats rollups: {}", e),
+    }
+    Ok(())
+}
+
+/// Problems with one `refs/notes/ai` update; empty when the update is accepted.
+fn validate_notes_update(
+    repo: &Repository,
+    update: &RefUpdate,
+    policy: &NotesPolicy,
+) -> Result<Vec<String>, GitAiError> {
+    let mut rejections = Vec::new();
+    let mut candidates = Vec::new();
+    for note in changed_notes(repo, update)? {
+        if note_target_from_path(&note.path).is_none() {
+            rejections.push(format!("{}: not a note path", note.path));
+        } else if !note.mode.starts_with("100") {
+            rejections.push(format!("{}: not a blob (mode {})", note.path, note.mode));
+        } else {
+            candidates.push(note);
+        }
+    }
+
+    let blob_oids: Vec<String> = candidates.iter().map(|n| n.blob_oid.clone()).collect();
+    let sizes = blob_sizes(repo, &blob_oids)?;
+    let mut readable = Vec::new();
+    for note in candidates {
+        match sizes.get(&note.blob_oid) {
+            Some(&size) if size > policy.max_note_bytes => rejections.push(format!(
+                "{}: note is {} bytes (limit {})",
+                note.path, size, policy.max_note_bytes
+            )),
+            Some(_) => readable.push(note),
+            None => rejections.push(format!("{}: note blob is missing", note.path)),
+        }
+    }
+
+    let oids: Vec<String> = readable.iter().map(|n| n.blob_oid.clone()).collect();
+    let mut contents = batch_read_blobs_with_oids(&repo.global_args_for_exec(), &oids)?;
+    // An overflow stub is checked against the full note it names, which the
+    // same push carries in refs/notes/ai-overflow.
+    crate::git::note_overflow::resolve_stubs(&repo.global_args_for_exec(), &mut contents);
+    for note in readable {
+        let content = contents.get(&note.blob_oid).map(String::as_str);
+        if let Err(reason) = validate_note_content(content.unwrap_or_default()) {
+            rejections.push(format!("{}: {}", note.path, reason));
+        }
+    }
+    Ok(rejections)
+}
+
+/// Parse an authorship note and require the current schema version.
+pub fn validate_note_content(content: &str) -> Result<(), String> {
+    let log = AuthorshipLog::deserialize_from_string(content)
+        .map_err(|e| format!("malformed authorship note: {}", e))?;
+    if log.metadata.schema_version != AUTHORSHIP_LOG_VERSION {
+        return Err(format!(
+            "unsupported schema version '{}' (expected '{}')",
+            log.metadata.schema_version, AUTHORSHIP_LOG_VERSION
+        ));
+    }
+    Ok(())
+}
+
+/// Why a push must be rejected given `GIT_PUSH_CERT_STATUS`, if it must.
+pub fn push_certificate_rejection(status: &str) -> Option<String> {
+    match status {
+        "G" => None,
+        "" => Some("push is not signed (use `git push --signed`)".to_string()),
+        other => Some(format!(
+            "push certificate signature is not valid (status {})",
+            other
+        )),
+    }
+}
+
+pub fn parse_ref_updates(input: &str) -> Vec<RefUpdate> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            Some(RefUpdate {
+                old_oid: parts.next()?.to_string(),
+                new_oid: parts.next()?.to_string(),
+                refname: parts.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The annotated object id for a path in a notes tree, with or without fanout.
+pub fn note_target_from_path(path: &str) -> Option<String> {
+    let target: String = path.split('/').collect();
+    let is_oid =
+        (target.len() == 40 || target.len() == 64) && target.bytes().all(|b| b.is_ascii_hexdigit());
+    is_oid.then(|| target.to_ascii_lowercase())
+}
+
+fn is_zero_oid(oid: &str) -> bool {
+    oid.bytes().all(|b| b == b'0')
+}
+
+fn read_ref_updates_from_stdin() -> Result<Vec<RefUpdate>, GitAiError> {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+    Ok(parse_ref_updates(&input))
+}
+
+/// Note blobs added or modified between the old and new notes commits.
+fn changed_notes(repo: &Repository, update: &RefUpdate) -> Result<Vec<ChangedNote>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    if is_zero_oid(&update.old_oid) {
+        args.extend([
+            "ls-tree".to_string(),
+            "-r".to_string(),
+            update.new_oid.clone(),
+        ]);
+        let output = exec_git(&args)?;
+        // <mode> <type> <oid>\t<path>
+        return Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let (meta, path) = line.split_once('\t')?;
+                let mut meta = meta.split_whitespace();
+                let mode = meta.next()?.to_string();
+                let blob_oid = meta.nth(1)?.to_string();
+                Some(ChangedNote {
+                    path: path.to_string(),
+                    mode,
+                    blob_oid,
+                })
+            })
+            .collect());
+    }
+
+    args.extend([
+        "diff-tree".to_string(),
+        "-r".to_string(),
+        "--raw".to_string(),
+        "--no-renames".to_string(),
+        update.old_oid.clone(),
+        update.new_oid.clone(),
+    ]);
+    let output = exec_git(&args)?;
+    // :<old mode> <new mode> <old oid> <new oid> <status>\t<path>
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (meta, path) = line.split_once('\t')?;
+            let meta: Vec<&str> = meta.trim_start_matches(':').split_whitespace().collect();
+            if meta.len() < 5 || meta[4] == "D" {
+                return None;
+            }
+            Some(ChangedNote {
+                path: path.to_string(),
+                mode: meta[1].to_string(),
+                blob_oid: meta[3].to_string(),
+            })
+        })
+        .collect())
+}
+
+fn blob_sizes(repo: &Repository, blob_oids: &[String]) -> Result<HashMap<String, u64>, GitAiError> {
+    if blob_oids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "cat-file".to_string(),
+        "--batch-check=%(objectname) %(objecttype) %(objectsize)".to_string(),
+    ]);
+    let stdin = blob_oids.join("\n") + "\n";
+    let output = exec_git_stdin(&args, stdin.as_bytes())?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let oid = parts.next()?;
+            if parts.next()? != "blob" {
+                return None;
+            }
+            Some((oid.to_string(), parts.next()?.parse().ok()?))
+        })
+        .collect())
+}
+
+/// The subset of `commit_shas` present in the repository; notes may arrive for
+/// commits that are pushed separately or never.
+fn existing_commits(repo: &Repository, commit_shas: &[String]) -> Result<Vec<String>, GitAiError> {
+    if commit_shas.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "cat-file".to_string(),
+        "--batch-check=%(objectname) %(objecttype)".to_string(),
+    ]);
+    let stdin = commit_shas.join("\n") + "\n";
+    let output = exec_git_stdin(&args, stdin.as_bytes())?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (oid, kind) = line.split_once(' ')?;
+            (kind == "commit").then(|| oid.to_string())
+        })
+        .collect())
+}
+
+fn parse_max_note_bytes(value: &str) -> Result<u64, GitAiError> {
+    value
+        .parse::<u64>()
+        .ok()
+        .filter(|bytes| *bytes > 0)
+        .ok_or_else(|| {
+            GitAiError::Generic(format!(
+                "--max-note-bytes expects a positive number, got '{}'",
+                value
+            ))
+        })
+}
+
+fn hooks_dir(git_dir: Option<PathBuf>) -> Result<PathBuf, GitAiError> {
+    let git_dir = match git_dir {
+        Some(git_dir) => git_dir,
+        None => find_repository(&Vec::<String>::new())?
+            .common_dir()
+            .to_path_buf(),
+    };
+    if !git_dir.is_dir() {
+        return Err(GitAiError::Hook(format!(
+            "{} is not a git directory",
+            git_dir.display()
+        )));
+    }
+    Ok(git_dir.join("hooks"))
+}
+
+/// Contents of an existing hook that git-ai did not write.
+fn read_unmanaged_hook(path: &Path) -> Result<Option<String>, GitAiError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let existing = fs::read_to_string(path)?;
+    Ok((!existing.contains(MANAGED_MARKER)).then_some(existing))
+}
+
+pub fn hook_script(binary_path: &Path, hook_name: &str, policy: &NotesPolicy) -> String {
+    let mut command = format!(
+        "exec '{}' server-hooks {}",
+        binary_path.display().to_string().replace('\'', r"'\''"),
+        hook_name
+    );
+    if hook_name == "pre-receive" {
+        command.push_str(&format!(" --max-note-bytes {}", policy.max_note_bytes));
+        if policy.require_signed_push {
+            command.push_str(" --require-signed-push");
+        }
+    }
+    format!("#!/bin/sh\n{}\n{}\n", MANAGED_MARKER, command)
+}
+
+fn write_hook_script(path: &Path, script: &str) -> Result<(), GitAiError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, script)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(())
+}
+
+fn print_help() {
+    eprintln!("git-ai server-hooks - Enforce AI authorship note integrity on a git server");
+    eprintln!();
+    eprintln!("Usage: git-ai server-hooks install [options]");
+    eprintln!("       git-ai server-hooks uninstall [--git-dir <path>]");
+    eprintln!();
+    eprintln!("Installs pre-receive and post-receive hooks that reject malformed");
+    eprintln!("refs/notes/ai pushes and keep server-side stats rollups current.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --git-dir <path>          Repository to install into (default: current)");
+    eprintln!(
+        "  --max-note-bytes <n>      Reject notes larger than n bytes (default: {})",
+        DEFAULT_MAX_NOTE_BYTES
+    );
+    eprintln!("  --require-signed-push     Reject notes pushes without a valid push certificate");
+    eprintln!("  -f, --force               Replace existing hooks not written by git-ai");
+    eprintln!("  -n, --dry-run             Print the hook scripts without writing them");
+    eprintln!("  -h, --help                Show this help");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ref_updates_and_note_paths() {
+        let updates = parse_ref_updates(
+            "0000000000000000000000000000000000000000 1111111111111111111111111111111111111111 refs/notes/ai\n\
+             2222222222222222222222222222222222222222 0000000000000000000000000000000000000000 refs/heads/old\n",
+        );
+        assert_eq!(updates.len(), 2);
+        assert!(updates[0].is_notes_update());
+        assert!(!updates[1].is_notes_update());
+
+        let sha = "abcdef0123456789abcdef0123456789abcdef01";
+        assert_eq!(note_target_from_path(sha).as_deref(), Some(sha));
+        assert_eq!(
+            note_target_from_path("ab/cdef0123456789abcdef0123456789abcdef01").as_deref(),
+            Some(sha)
+        );
+        assert_eq!(note_target_from_path("README.md"), None);
+        assert_eq!(note_target_from_path("abcdef"), None);
+    }
+
+    #[test]
+    fn test_validate_note_content_requires_current_schema() {
+        let mut log = AuthorshipLog::new();
+        log.metadata.base_commit_sha = "abc".to_string();
+        let note = log.serialize_to_string().unwrap();
+        assert!(validate_note_content(&note).is_ok());
+
+        assert!(validate_note_content("not a note").is_err());
+        let old = note.replace(AUTHORSHIP_LOG_VERSION, "authorship/2.0.0");
+        assert!(
+            validate_note_content(&old)
+                .unwrap_err()
+                .contains("unsupported schema version")
+        );
+    }
+
+    #[test]
+    fn test_push_certificate_rejection_accepts_only_good_signatures() {
+        assert_eq!(push_certificate_rejection("G"), None);
+        assert!(push_certificate_rejection("").is_some());
+        assert!(push_certificate_rejection("B").is_some());
+        assert!(push_certificate_rejection("N").is_some());
+    }
+
+    #[test]
+    fn test_hook_script_embeds_policy_for_pre_receive_only() {
+        let policy = NotesPolicy {
+            max_note_bytes: 4096,
+            require_signed_push: true,
+        };
+        let binary = Path::new("/opt/git-ai/bin/git-ai");
+
+        let pre = hook_script(binary, "pre-receive", &policy);
+        assert!(pre.starts_with("#!/bin/sh\n"));
+        assert!(pre.contains(MANAGED_MARKER));
+        assert!(pre.contains(
+            "exec '/opt/git-ai/bin/git-ai' server-hooks pre-receive --max-note-bytes 4096 --require-signed-push"
+        ));
+
+        let post = hook_script(binary, "post-receive", &policy);
+        assert!(post.ends_with("exec '/opt/git-ai/bin/git-ai' server-hooks post-receive\n"));
+    }
+}
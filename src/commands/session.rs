@@ -0,0 +1,195 @@
+//! `git-ai session start|end|status` — attribute human checkpoints to an AI tool.
+//!
+//! Terminal agents without hooks never fire AI checkpoints, so their edits land
+//! as human. Starting a session records a [`SessionOverrideRecord`] in `.git/ai`;
+//! while it is active the daemon credits untracked human checkpoints in this
+//! repository to the given tool and model. The override lapses after a TTL even
+//! if `session end` is never run.
+
+use crate::git::find_repository;
+use crate::git::repo_storage::SessionOverrideRecord;
+use crate::git::repository::Repository;
+use crate::utils::format_local_timestamp;
+
+/// Default lifetime of a session override.
+pub const DEFAULT_SESSION_TTL_SECS: i64 = 8 * 60 * 60;
+
+pub fn handle_session(args: &[String]) {
+    let subcommand = args.first().map(|s| s.as_str()).unwrap_or("--help");
+    match subcommand {
+        "start" => handle_session_start(&args[1..]),
+        "end" => handle_session_end(),
+        "status" => handle_session_status(),
+        "--help" | "-h" | "help" => print_help(),
+        other => {
+            eprintln!("Unknown git-ai session subcommand: {}", other);
+            eprintln!("Run 'git ai session --help' for usage.");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn handle_session_start(args: &[String]) {
+    let mut tool: Option<String> = None;
+    let mut model: Option<String> = None;
+    let mut ttl_secs = DEFAULT_SESSION_TTL_SECS;
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--tool" if i + 1 < args.len() => {
+                tool = Some(args[i + 1].clone());
+                i += 2;
+            }
+            "--model" if i + 1 < args.len() => {
+                model = Some(args[i + 1].clone());
+                i += 2;
+            }
+            "--ttl" if i + 1 < args.len() => {
+                ttl_secs = match parse_ttl(&args[i + 1]) {
+                    Some(secs) => secs,
+                    None => {
+                        eprintln!(
+                            "Error: invalid --ttl '{}' (expected e.g. 90m, 8h, 1d)",
+                            args[i + 1]
+                        );
+                        std::process::exit(1);
+                    }
+                };
+                i += 2;
+            }
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other => {
+                eprintln!("Error: unknown option '{}'", other);
+                eprintln!("Run 'git ai session --help' for usage");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let Some(tool) = tool.filter(|t| !t.trim().is_empty()) else {
+        eprintln!("Error: git-ai session start requires --tool <name>");
+        std::process::exit(1);
+    };
+    let model = model.unwrap_or_else(|| "unknown".to_string());
+
+    let repo = find_repository_or_exit();
+    let now = chrono::Utc::now().timestamp();
+    let record = SessionOverrideRecord {
+        tool,
+        model,
+        session_id: crate::uuid::generate_v4(),
+        started_at: now,
+        expires_at: now + ttl_secs,
+    };
+    if let Err(e) = repo.storage.write_session_override(&record) {
+        eprintln!("Failed to start session: {}", e);
+        std::process::exit(1);
+    }
+    println!(
+        "Attributing human checkpoints to {} ({}) until 'git ai session end' or {}",
+        record.tool,
+        record.model,
+        format_local_timestamp(record.expires_at)
+    );
+}
+
+fn handle_session_end() {
+    let repo = find_repository_or_exit();
+    let active = repo
+        .storage
+        .active_session_override(chrono::Utc::now().timestamp());
+    if let Err(e) = repo.storage.clear_session_override() {
+        eprintln!("Failed to end session: {}", e);
+        std::process::exit(1);
+    }
+    match active {
+        Some(record) => println!("Ended {} ({}) session", record.tool, record.model),
+        None => println!("No active session"),
+    }
+}
+
+fn handle_session_status() {
+    let repo = find_repository_or_exit();
+    match repo
+        .storage
+        .active_session_override(chrono::Utc::now().timestamp())
+    {
+        Some(record) => println!(
+            "Active session: {} ({}) since {}, expires {}",
+            record.tool,
+            record.model,
+            format_local_timestamp(record.started_at),
+            format_local_timestamp(record.expires_at)
+        ),
+        None => println!("No active session"),
+    }
+}
+
+/// `90m`, `8h`, `1d`, or plain seconds.
+pub fn parse_ttl(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let (digits, unit) = match value.char_indices().last()? {
+        (idx, c) if c.is_ascii_alphabetic() => (&value[..idx], Some(c)),
+        _ => (value, None),
+    };
+    let amount: i64 = digits.parse().ok().filter(|n| *n > 0)?;
+    let multiplier = match unit {
+        None | Some('s') => 1,
+        Some('m') => 60,
+        Some('h') => 60 * 60,
+        Some('d') => 24 * 60 * 60,
+        Some(_) => return None,
+    };
+    amount.checked_mul(multiplier)
+}
+
+fn find_repository_or_exit() -> Repository {
+    match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn print_help() {
+    eprintln!("git-ai session - attribute human checkpoints to an AI tool");
+    eprintln!();
+    eprintln!("Usage:");
+    eprintln!("  git-ai session start --tool <name> [--model <name>] [--ttl <duration>]");
+    eprintln!("  git-ai session end");
+    eprintln!("  git-ai session status");
+    eprintln!();
+    eprintln!("For terminal agents without hooks: while a session is active, human");
+    eprintln!("checkpoints in this repository are recorded as edits by the given tool.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --tool <name>          Tool to attribute edits to (required)");
+    eprintln!("  --model <name>         Model to attribute edits to (default: unknown)");
+    eprintln!(
+        "  --ttl <duration>       End the session automatically, e.g. 90m, 8h, 1d (default: 8h)"
+    );
+    eprintln!("  -h, --help             Show this help message");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ttl() {
+        assert_eq!(parse_ttl("90"), Some(90));
+        assert_eq!(parse_ttl("45s"), Some(45));
+        assert_eq!(parse_ttl("90m"), Some(5400));
+        assert_eq!(parse_ttl("8h"), Some(DEFAULT_SESSION_TTL_SECS));
+        assert_eq!(parse_ttl("2d"), Some(172_800));
+        assert_eq!(parse_ttl("0h"), None);
+        assert_eq!(parse_ttl("h"), None);
+        assert_eq!(parse_ttl("3w"), None);
+        assert_eq!(parse_ttl(""), None);
+    }
+}
@@ -1,17 +1,34 @@
+use crate::commands::diff::{
+    DiffCommandOptions, build_diff_artifacts_with_note, format_attributed_patch, resolve_parent,
+};
 use crate::error::GitAiError;
 use crate::git::find_repository;
-use crate::git::notes_api::{CommitAuthorship, filter_commits_with_notes};
-use crate::git::repository::{CommitRange, Repository};
+use crate::git::notes_api::{CommitAuthorship, filter_commits_with_notes, read_authorship};
+use crate::git::repository::{CommitRange, Repository, exec_git};
+use std::io::IsTerminal;
 
 const NO_AUTHORSHIP_DATA_MESSAGE: &str = "No authorship data found for this revision";
 
 pub fn handle_show(args: &[String]) {
-    if args.is_empty() {
+    let mut patch = false;
+    let mut specs: Vec<&String> = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--patch" | "-p" => patch = true,
+            other if other.starts_with('-') => {
+                eprintln!("Error: unknown option '{}'", other);
+                std::process::exit(1);
+            }
+            _ => specs.push(arg),
+        }
+    }
+
+    if specs.is_empty() {
         eprintln!("Error: show requires a revision or range");
         std::process::exit(1);
     }
 
-    if args.len() > 1 {
+    if specs.len() > 1 {
         eprintln!("Error: show accepts exactly one revision or range");
         std::process::exit(1);
     }
@@ -24,12 +41,65 @@ pub fn handle_show(args: &[String]) {
         }
     };
 
-    if let Err(e) = show_authorship(&repo, &args[0]) {
+    let result = if patch {
+        show_patch(&repo, specs[0])
+    } else {
+        show_authorship(&repo, specs[0])
+    };
+    if let Err(e) = result {
         eprintln!("Failed to show authorship: {}", e);
         std::process::exit(1);
     }
 }
 
+/// Print each commit like `git show`, with the attribution gutter from its
+/// note on the diff.
+fn show_patch(repo: &Repository, spec: &str) -> Result<(), GitAiError> {
+    let use_color = std::io::stdout().is_terminal();
+    let commits = resolve_commits(repo, spec)?;
+    for (index, sha) in commits.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+
+        let mut args = repo.global_args_for_exec();
+        args.extend(
+            ["show", "-s", "--format=medium", "--no-color", sha.as_str()].map(String::from),
+        );
+        let header = String::from_utf8_lossy(&exec_git(&args)?.stdout).into_owned();
+        if use_color && let Some((first, rest)) = header.split_once('\n') {
+            println!("\x1b[33m{}\x1b[0m", first);
+            println!("{}", rest.trim_end());
+        } else {
+            println!("{}", header.trim_end());
+        }
+        println!();
+
+        let parent = resolve_parent(repo, sha)?;
+        let note = read_authorship(repo, sha);
+        let artifacts = build_diff_artifacts_with_note(
+            repo,
+            &parent,
+            sha,
+            &DiffCommandOptions::default(),
+            note.as_ref(),
+        )?;
+        print!(
+            "{}",
+            format_attributed_patch(
+                repo,
+                &parent,
+                sha,
+                &artifacts.attributions,
+                &artifacts.humans,
+                &artifacts.included_files,
+                use_color,
+            )?
+        );
+    }
+    Ok(())
+}
+
 fn show_authorship(repo: &Repository, spec: &str) -> Result<(), GitAiError> {
     let commits = resolve_commits(repo, spec)?;
     if commits.is_empty() {
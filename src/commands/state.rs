@@ -0,0 +1,389 @@
+//! `git-ai state export|import` — snapshot and restore a repository's git-ai state.
+//!
+//! A state bundle is a zip archive holding the repository's `.git/ai` directory
+//! (stash attributions, hook state, storage version), its working logs wherever
+//! they live, and every authorship note under `refs/notes/ai`. Bundles are meant to be attached to bug
+//! reports and to let tests materialize complex states without replaying them.
+//!
+//! Bundle layout:
+//!
+//! ```text
+//! manifest.json          StateBundleManifest
+//! ai/<relative path>     files from .git/ai
+//! ai/working_logs/<path> working log files, even when relocated outside .git/ai
+//! notes/<commit sha>     authorship note content
+//! ```
+
+use crate::commands::notes_migrate::{cat_file_batch, list_notes};
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::notes_api;
+use crate::git::repo_state::is_valid_git_oid;
+use crate::git::repo_storage::{STORAGE_FORMAT_VERSION, WORKING_LOGS_LOCATION_FILE};
+use crate::git::repository::Repository;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+const MANIFEST_ENTRY: &str = "manifest.json";
+const AI_DIR_PREFIX: &str = "ai/";
+const NOTES_PREFIX: &str = "notes/";
+const WORKING_LOGS_ENTRY: &str = "working_logs";
+
+/// Top-level `.git/ai` entries that are never bundled: migration backups are
+/// redundant, the managed hooks directory points at this machine's binary, and
+/// `location` holds an absolute path on this machine. Working logs are bundled
+/// from wherever they live rather than as part of the `.git/ai` walk.
+const EXCLUDED_AI_ENTRIES: &[&str] = &[
+    "backups",
+    "hooks",
+    WORKING_LOGS_LOCATION_FILE,
+    WORKING_LOGS_ENTRY,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StateBundleManifest {
+    pub git_ai_version: String,
+    pub storage_format_version: u32,
+    pub created_at: i64,
+    pub ai_files: usize,
+    pub notes: usize,
+}
+
+/// Outcome of [`export_state`] and [`import_state`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateBundleReport {
+    pub ai_files: usize,
+    pub notes: usize,
+}
+
+pub fn handle_state(args: &[String]) {
+    let subcommand = args.first().map(|s| s.as_str()).unwrap_or("--help");
+    match subcommand {
+        "export" => handle_state_export(&args[1..]),
+        "import" => handle_state_import(&args[1..]),
+        "--help" | "-h" | "help" => print_help(),
+        other => {
+            eprintln!("Unknown git-ai state subcommand: {}", other);
+            eprintln!("Run 'git ai state --help' for usage.");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn handle_state_export(args: &[String]) {
+    let mut output: Option<PathBuf> = None;
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--output" | "-o" if i + 1 < args.len() => {
+                output = Some(PathBuf::from(&args[i + 1]));
+                i += 2;
+            }
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other => {
+                eprintln!("Error: unknown option '{}'", other);
+                eprintln!("Run 'git ai state --help' for usage");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let repo = find_repository_or_exit();
+    let output = output.unwrap_or_else(|| {
+        PathBuf::from(format!(
+            "git-ai-state-{}.zip",
+            chrono::Utc::now().format("%Y%m%d%H%M%S")
+        ))
+    });
+
+    match export_state(&repo, &output) {
+        Ok(report) => println!(
+            "Exported {} git-ai file(s) and {} note(s) to {}",
+            report.ai_files,
+            report.notes,
+            output.display()
+        ),
+        Err(e) => {
+            eprintln!("State export failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn handle_state_import(args: &[String]) {
+    let mut input: Option<PathBuf> = None;
+    for arg in args {
+        match arg.as_str() {
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other if other.starts_with('-') => {
+                eprintln!("Error: unknown option '{}'", other);
+                eprintln!("Run 'git ai state --help' for usage");
+                std::process::exit(1);
+            }
+            path => input = Some(PathBuf::from(path)),
+        }
+    }
+
+    let Some(input) = input else {
+        eprintln!("Error: git-ai state import requires a bundle path");
+        eprintln!("Run 'git ai state --help' for usage");
+        std::process::exit(1);
+    };
+
+    let repo = find_repository_or_exit();
+    match import_state(&repo, &input) {
+        Ok(report) => println!(
+            "Imported {} git-ai file(s) and {} note(s) from {}",
+            report.ai_files,
+            report.notes,
+            input.display()
+        ),
+        Err(e) => {
+            eprintln!("State import failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn find_repository_or_exit() -> Repository {
+    match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Writes the repository's `.git/ai` state and authorship notes to a zip bundle.
+///
+/// Notes are read with one `git notes list` and one `git cat-file --batch` call.
+pub fn export_state(repo: &Repository, output: &Path) -> Result<StateBundleReport, GitAiError> {
+    let mut ai_files = Vec::new();
+    collect_ai_files(&repo.storage.ai_dir, &repo.storage.ai_dir, &mut ai_files)?;
+    let mut ai_files: Vec<(String, PathBuf)> = ai_files
+        .into_iter()
+        .map(|relative| {
+            let source = repo.storage.ai_dir.join(&relative);
+            (relative, source)
+        })
+        .collect();
+    if repo.storage.working_logs.is_dir() {
+        let mut working_log_files = Vec::new();
+        collect_ai_files(
+            &repo.storage.working_logs,
+            &repo.storage.working_logs,
+            &mut working_log_files,
+        )?;
+        ai_files.extend(working_log_files.into_iter().map(|relative| {
+            let source = repo.storage.working_logs.join(&relative);
+            (format!("{}/{}", WORKING_LOGS_ENTRY, relative), source)
+        }));
+    }
+    ai_files.sort();
+
+    let note_pairs = list_notes(repo)?;
+    let blob_shas: Vec<String> = note_pairs.iter().map(|(blob, _)| blob.clone()).collect();
+    let blobs = cat_file_batch(repo, &blob_shas)?;
+    let mut notes: Vec<(String, String)> = note_pairs
+        .into_iter()
+        .filter_map(|(blob, commit)| blobs.get(&blob).map(|content| (commit, content.clone())))
+        .collect();
+    notes.sort();
+
+    let manifest = StateBundleManifest {
+        git_ai_version: env!("CARGO_PKG_VERSION").to_string(),
+        storage_format_version: repo.storage.storage_format_version()?,
+        created_at: chrono::Utc::now().timestamp(),
+        ai_files: ai_files.len(),
+        notes: notes.len(),
+    };
+
+    if let Some(parent) = output.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+    let mut writer = zip::ZipWriter::new(fs::File::create(output)?);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    start_entry(&mut writer, MANIFEST_ENTRY, options)?;
+    writer.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+    for (relative, source) in &ai_files {
+        start_entry(
+            &mut writer,
+            &format!("{}{}", AI_DIR_PREFIX, relative),
+            options,
+        )?;
+        writer.write_all(&fs::read(source)?)?;
+    }
+    for (commit_sha, content) in &notes {
+        start_entry(
+            &mut writer,
+            &format!("{}{}", NOTES_PREFIX, commit_sha),
+            options,
+        )?;
+        writer.write_all(content.as_bytes())?;
+    }
+    writer
+        .finish()
+        .map_err(|e| GitAiError::Generic(format!("Failed to finish state bundle: {}", e)))?;
+
+    Ok(StateBundleReport {
+        ai_files: manifest.ai_files,
+        notes: manifest.notes,
+    })
+}
+
+/// Restores a bundle written by [`export_state`] into this repository.
+///
+/// Bundled `.git/ai` files overwrite existing files with the same path; other
+/// files are left in place. Working logs are restored into this repository's
+/// working log directory, wherever it lives, and this repository's `location`
+/// file is never replaced. Notes are written in a single batch through the
+/// configured notes backend. Bundles from a newer storage format, and bundles
+/// with a note entry not named by a full commit id, are refused before anything
+/// is written.
+pub fn import_state(repo: &Repository, input: &Path) -> Result<StateBundleReport, GitAiError> {
+    let mut archive = zip::ZipArchive::new(fs::File::open(input)?)
+        .map_err(|e| GitAiError::Generic(format!("Failed to read state bundle: {}", e)))?;
+
+    let manifest: StateBundleManifest =
+        serde_json::from_slice(&read_entry(&mut archive, MANIFEST_ENTRY)?)?;
+    if manifest.storage_format_version > STORAGE_FORMAT_VERSION {
+        return Err(GitAiError::Generic(format!(
+            "State bundle uses storage format version {}, but this git-ai only supports up to version {}. Upgrade git-ai with `git-ai upgrade`.",
+            manifest.storage_format_version, STORAGE_FORMAT_VERSION
+        )));
+    }
+
+    // Read and check every entry before writing anything, so a corrupted or
+    // hand-edited bundle is refused instead of leaving the repository half-imported.
+    let mut ai_entries: Vec<(PathBuf, Vec<u8>)> = Vec::new();
+    let mut notes: Vec<(String, String)> = Vec::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).map_err(|e| {
+            GitAiError::Generic(format!("Failed to read state bundle entry: {}", e))
+        })?;
+        if file.is_dir() {
+            continue;
+        }
+        // `enclosed_name` rejects absolute paths and `..` components.
+        let Some(entry_path) = file.enclosed_name() else {
+            continue;
+        };
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)?;
+
+        if let Ok(relative) = entry_path.strip_prefix(AI_DIR_PREFIX) {
+            // Older bundles may carry the exporting machine's `location` file.
+            if relative == Path::new(WORKING_LOGS_LOCATION_FILE) {
+                continue;
+            }
+            ai_entries.push((relative.to_path_buf(), contents));
+        } else if let Ok(commit) = entry_path.strip_prefix(NOTES_PREFIX) {
+            let commit = commit.to_string_lossy().to_string();
+            if !is_valid_git_oid(&commit) {
+                return Err(GitAiError::Generic(format!(
+                    "State bundle entry {}{} is not named by a commit id; nothing was imported",
+                    NOTES_PREFIX, commit
+                )));
+            }
+            let content = String::from_utf8(contents).map_err(|e| {
+                GitAiError::Generic(format!(
+                    "State bundle note for {} is not UTF-8 ({}); nothing was imported",
+                    commit, e
+                ))
+            })?;
+            notes.push((commit, content));
+        }
+    }
+
+    for (relative, contents) in &ai_entries {
+        let dest = match relative.strip_prefix(WORKING_LOGS_ENTRY) {
+            Ok(working_log) => repo.storage.working_logs.join(working_log),
+            Err(_) => repo.storage.ai_dir.join(relative),
+        };
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&dest, contents)?;
+    }
+    notes_api::write_notes_batch(repo, &notes)?;
+    Ok(StateBundleReport {
+        ai_files: ai_entries.len(),
+        notes: notes.len(),
+    })
+}
+
+fn start_entry(
+    writer: &mut zip::ZipWriter<fs::File>,
+    name: &str,
+    options: zip::write::SimpleFileOptions,
+) -> Result<(), GitAiError> {
+    writer.start_file(name, options).map_err(|e| {
+        GitAiError::Generic(format!(
+            "Failed to write state bundle entry {}: {}",
+            name, e
+        ))
+    })
+}
+
+fn read_entry(archive: &mut zip::ZipArchive<fs::File>, name: &str) -> Result<Vec<u8>, GitAiError> {
+    let mut file = archive
+        .by_name(name)
+        .map_err(|e| GitAiError::Generic(format!("State bundle is missing {}: {}", name, e)))?;
+    let mut contents = Vec::new();
+    file.read_to_end(&mut contents)?;
+    Ok(contents)
+}
+
+/// Collects regular files under `dir` as `/`-separated paths relative to `root`.
+/// Symlinks are skipped so a bundle never captures machine-specific links.
+fn collect_ai_files(root: &Path, dir: &Path, out: &mut Vec<String>) -> Result<(), GitAiError> {
+    for entry in fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if dir == root
+            && EXCLUDED_AI_ENTRIES.contains(&entry.file_name().to_string_lossy().as_ref())
+        {
+            continue;
+        }
+        if file_type.is_dir() {
+            collect_ai_files(root, &path, out)?;
+        } else if file_type.is_file()
+            && let Ok(relative) = path.strip_prefix(root)
+        {
+            out.push(crate::utils::normalize_to_posix(
+                &relative.to_string_lossy(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn print_help() {
+    eprintln!("git-ai state - snapshot and restore git-ai state for this repository");
+    eprintln!();
+    eprintln!("Usage:");
+    eprintln!("  git-ai state export [--output <path>]");
+    eprintln!("  git-ai state import <path>");
+    eprintln!();
+    eprintln!("The bundle contains .git/ai (stash attributions, hook state), the working");
+    eprintln!("logs wherever they live, and all authorship notes under refs/notes/ai.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!(
+        "  -o, --output <path>    Bundle path for export (default: git-ai-state-<timestamp>.zip)"
+    );
+    eprintln!("  -h, --help             Show this help message");
+}
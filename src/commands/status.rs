@@ -1,14 +1,18 @@
+use crate::authorship::diff_base::EMPTY_TREE_SHA;
 use crate::authorship::ignore::{
     IgnoreMatcher, build_ignore_matcher, effective_ignore_patterns, should_ignore_file_with_matcher,
 };
 use crate::authorship::stats::{CommitStats, stats_from_authorship_log, write_stats_to_terminal};
 use crate::authorship::virtual_attribution::VirtualAttributions;
 use crate::authorship::working_log::CheckpointKind;
+use crate::commands::output::OutputOptions;
+use crate::config::{Config, TelemetryChannel, TelemetrySettings};
 use crate::error::GitAiError;
 use crate::git::find_repository;
 use crate::git::repo_storage::InitialAttributions;
 use crate::git::repository::{InternalGitProfile, Repository, exec_git_with_profile};
 use crate::git::status::MAX_PATHSPEC_ARGS;
+use crate::git::storage_usage::{StorageUsage, format_size};
 use serde::Serialize;
 use std::collections::{BTreeMap, HashSet};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -30,58 +34,115 @@ struct StatusOutput {
     /// get just the diff-scoped `stats`.
     #[serde(skip_serializing_if = "Option::is_none")]
     checkpoints: Option<Vec<CheckpointInfo>>,
+    /// Effective telemetry channel state; omitted with `--diff-only` like `checkpoints`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    telemetry: Option<TelemetrySettings>,
+    /// Present while the repository is degraded by `max_hook_latency_ms`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    degraded: Option<DegradedInfo>,
+    /// `.git/ai` footprint; omitted with `--diff-only` like `checkpoints`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    storage: Option<StorageInfo>,
+}
+
+#[derive(Serialize)]
+struct DegradedInfo {
+    since: u64,
+    last_hook: String,
+    last_duration_ms: u64,
+}
+
+#[derive(Serialize)]
+struct StorageInfo {
+    #[serde(flatten)]
+    usage: StorageUsage,
+    total: u64,
+    /// `ai_storage_warn_mb` in bytes; absent when the warning is disabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    warn_threshold: Option<u64>,
+}
+
+impl StorageInfo {
+    fn over_threshold(&self) -> bool {
+        self.warn_threshold
+            .is_some_and(|threshold| self.total > threshold)
+    }
 }
 
 pub fn handle_status(args: &[String]) {
-    let mut json_output = false;
+    let mut output = OutputOptions::current();
     let mut diff_only = false;
-
-    let mut i = 0;
-    while i < args.len() {
-        match args[i].as_str() {
-            "--json" => json_output = true,
-            "--diff-only" => diff_only = true,
-            _ => {}
+    for arg in args {
+        if !output.parse_flag(arg) && arg == "--diff-only" {
+            diff_only = true;
         }
-        i += 1;
     }
 
-    if let Err(e) = run_status(json_output, diff_only) {
-        eprintln!("Error: {}", e);
-        std::process::exit(1);
+    if let Err(e) = run_status(output, diff_only) {
+        output.exit_with_error(&e);
     }
 }
 
-fn run_status(json: bool, diff_only: bool) -> Result<(), GitAiError> {
+fn run_status(output: OutputOptions, diff_only: bool) -> Result<(), GitAiError> {
     let repo = find_repository(&[])?;
     let ignore_patterns = effective_ignore_patterns(&repo, &[], &[]);
     let ignore_matcher = build_ignore_matcher(&ignore_patterns);
 
     let default_user_name = repo.effective_author_identity().formatted_or_unknown();
 
-    let head = repo.head()?;
-    let head_sha = head.target()?;
+    // On an unborn branch, checkpoints are kept under the `initial` working log
+    // and the working tree is compared against the empty tree.
+    let head_sha = repo
+        .head_commit_sha()?
+        .unwrap_or_else(|| "initial".to_string());
+    let head_tree_ish = if head_sha == "initial" {
+        EMPTY_TREE_SHA
+    } else {
+        head_sha.as_str()
+    };
 
     let working_log = repo.storage.working_log_for_base_commit(&head_sha)?;
     let checkpoints = working_log.read_all_checkpoints()?;
     let initial_attributions = working_log.read_initial_attributions();
 
+    let telemetry = *Config::get().telemetry();
+    let degraded = crate::daemon::hook_budget::degradation(&repo).map(|record| DegradedInfo {
+        since: record.degraded_since.unwrap_or_default(),
+        last_hook: record.last_hook,
+        last_duration_ms: record.last_duration_ms,
+    });
+    // Measuring walks every file under the ai dir, so only do it when the
+    // output reports storage: not for `--diff-only`, nor quiet terminal output.
+    let storage = (!diff_only && (output.is_json() || !output.is_quiet())).then(|| {
+        let usage = crate::git::storage_usage::measure(&repo.storage);
+        StorageInfo {
+            usage,
+            total: usage.total(),
+            warn_threshold: crate::git::storage_usage::warn_threshold_bytes(),
+        }
+    });
+
     let has_checkpoints = !checkpoints.is_empty();
     let has_initial = !initial_attributions.files.is_empty();
 
     if !has_checkpoints && !has_initial {
-        if json {
-            let output = StatusOutput {
+        if output.is_json() {
+            output.print_json(&StatusOutput {
                 stats: CommitStats::default(),
                 checkpoints: if diff_only { None } else { Some(vec![]) },
-            };
-            let json_str = serde_json::to_string(&output)?;
-            println!("{}", json_str);
-        } else {
-            eprintln!(
-                "No checkpoints recorded since last commit ({})",
-                &head_sha[..7]
-            );
+                telemetry: (!diff_only).then_some(telemetry),
+                degraded,
+                storage,
+            })?;
+        } else if !output.is_quiet() {
+            if head_sha == "initial" {
+                eprintln!("No checkpoints recorded on this branch yet (no commits)");
+            } else {
+                eprintln!(
+                    "No checkpoints recorded since last commit ({})",
+                    &head_sha[..7]
+                );
+            }
             eprintln!();
 
             eprintln!(
@@ -90,6 +151,18 @@ fn run_status(json: bool, diff_only: bool) -> Result<(), GitAiError> {
             eprintln!();
             eprintln!("  git-ai install-hooks");
             eprintln!();
+            if !diff_only {
+                eprintln!("{}", format_telemetry_status(&telemetry));
+            }
+            if let Some(degraded) = degraded.as_ref() {
+                eprintln!("{}", format_degraded_status(degraded));
+            }
+            if let Some(storage) = storage.as_ref() {
+                eprintln!("{}", format_storage_status(storage));
+                if storage.over_threshold() {
+                    eprintln!("{}", format_storage_warning(storage));
+                }
+            }
         }
         return Ok(());
     }
@@ -141,14 +214,14 @@ fn run_status(json: bool, diff_only: bool) -> Result<(), GitAiError> {
     let (authorship_log, initial, _) = working_va.to_authorship_log_and_initial_working_log(
         &repo,
         &head_sha,
-        &head_sha,
+        head_tree_ish,
         Some(&pathspecs),
         None,
     )?;
 
     // Get actual git diff stats between HEAD and working directory (like post_commit does)
     let (total_additions, total_deletions) =
-        get_working_dir_diff_stats(&repo, Some(&pathspecs), &ignore_matcher)?;
+        get_working_dir_diff_stats(&repo, head_tree_ish, Some(&pathspecs), &ignore_matcher)?;
 
     // For status (uncommitted changes), the AI attributions are in `initial` (uncommitted),
     // not in authorship_log.attestations (which is for committed changes).
@@ -164,23 +237,37 @@ fn run_status(json: bool, diff_only: bool) -> Result<(), GitAiError> {
         &BTreeMap::new(),
     );
 
-    if json {
-        let output = StatusOutput {
+    if output.is_json() {
+        output.print_json(&StatusOutput {
             stats,
             checkpoints: if diff_only {
                 None
             } else {
                 Some(checkpoint_infos)
             },
-        };
-        let json_str = serde_json::to_string(&output)?;
-        println!("{}", json_str);
+            telemetry: (!diff_only).then_some(telemetry),
+            degraded,
+            storage,
+        })?;
         return Ok(());
     }
 
     write_stats_to_terminal(&stats, true);
 
-    if diff_only {
+    if !output.is_quiet()
+        && let Some(degraded) = degraded.as_ref()
+    {
+        println!();
+        println!("{}", format_degraded_status(degraded));
+    }
+
+    if let Some(storage) = storage.as_ref().filter(|storage| storage.over_threshold()) {
+        println!();
+        println!("{}", format_storage_warning(storage));
+    }
+
+    // `--quiet` keeps just the stats, like `--diff-only`.
+    if diff_only || output.is_quiet() {
         return Ok(());
     }
 
@@ -209,9 +296,80 @@ fn run_status(json: bool, diff_only: bool) -> Result<(), GitAiError> {
         }
     }
 
+    println!();
+    println!("{}", format_telemetry_status(&telemetry));
+    if let Some(storage) = storage.as_ref() {
+        println!("{}", format_storage_status(storage));
+    }
+
     Ok(())
 }
 
+/// One-line summary of which telemetry channels upload, e.g.
+/// `Telemetry: usage metrics on, prompt uploads off (managed), crash reports on`.
+fn format_telemetry_status(telemetry: &TelemetrySettings) -> String {
+    let channels: Vec<String> = TelemetryChannel::ALL
+        .into_iter()
+        .map(|channel| {
+            let state = telemetry.channel(channel);
+            format!(
+                "{} {}{}",
+                channel.label(),
+                if state.enabled { "on" } else { "off" },
+                if state.managed { " (managed)" } else { "" }
+            )
+        })
+        .collect();
+    format!("\x1b[90mTelemetry: {}\x1b[0m", channels.join(", "))
+}
+
+/// Warning shown while post-commit runs degraded by `max_hook_latency_ms`.
+fn format_degraded_status(degraded: &DegradedInfo) -> String {
+    format!(
+        "\x1b[33mHook budget exceeded {} (last {} took {}ms): attribution recovery is deferred. Run `git-ai repair`.\x1b[0m",
+        format_time_ago(degraded.since),
+        degraded.last_hook,
+        degraded.last_duration_ms
+    )
+}
+
+/// One-line `.git/ai` footprint, e.g.
+/// `Storage: 12.0 MB in .git/ai (working logs 2.0 MB, blobs 9.5 MB, ...)`.
+fn format_storage_status(storage: &StorageInfo) -> String {
+    let usage = &storage.usage;
+    let parts: Vec<String> = [
+        ("working logs", usage.working_logs),
+        ("blobs", usage.blobs),
+        ("archived", usage.archived_working_logs),
+        ("stashes", usage.stashes),
+        ("state", usage.state),
+        ("other", usage.other),
+    ]
+    .into_iter()
+    .filter(|(_, bytes)| *bytes > 0)
+    .map(|(label, bytes)| format!("{} {}", label, format_size(bytes)))
+    .collect();
+    let breakdown = if parts.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", parts.join(", "))
+    };
+    format!(
+        "\x1b[90mStorage: {} in .git/ai{}\x1b[0m",
+        format_size(storage.total),
+        breakdown
+    )
+}
+
+/// Warning shown once `.git/ai` outgrows `ai_storage_warn_mb`.
+fn format_storage_warning(storage: &StorageInfo) -> String {
+    format!(
+        "\x1b[33m.git/ai is using {}, over the {} ai_storage_warn_mb threshold. Run `git-ai gc` to prune stale working logs.\x1b[0m",
+        format_size(storage.total),
+        format_size(storage.warn_threshold.unwrap_or_default())
+    )
+}
+
 fn format_time_ago(timestamp: u64) -> String {
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -230,17 +388,19 @@ fn format_time_ago(timestamp: u64) -> String {
     }
 }
 
-/// Get git diff statistics between HEAD and the working directory
+/// Get git diff statistics between HEAD (or the empty tree on an unborn branch)
+/// and the working directory.
 /// This mirrors the logic in stats.rs get_git_diff_stats but for uncommitted changes
 fn get_working_dir_diff_stats(
     repo: &Repository,
+    head_tree_ish: &str,
     pathspecs: Option<&HashSet<String>>,
     ignore_matcher: &IgnoreMatcher,
 ) -> Result<(u32, u32), GitAiError> {
     let mut args = repo.global_args_for_exec();
     args.push("diff".to_string());
     args.push("--numstat".to_string());
-    args.push("HEAD".to_string());
+    args.push(head_tree_ish.to_string());
 
     // Add pathspecs if provided to scope the diff to specific files
     // Only pass as CLI args when under threshold to avoid E2BIG
@@ -0,0 +1,174 @@
+//! `git-ai timeline` — labelled checkpoints across commits.
+//!
+//! Lists the `git-ai checkpoint --label` tags recorded in the authorship notes
+//! of recent commits, oldest first, followed by labels on checkpoints that are
+//! not committed yet, so attribution phases can be lined up with stages of
+//! work.
+
+use crate::authorship::authorship_log_serialization::{AuthorshipLog, CheckpointLabelRecord};
+use crate::authorship::post_commit::checkpoint_label_records;
+use crate::authorship::stats::format_checkpoint_label;
+use crate::commands::output::OutputOptions;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::notes_api;
+use crate::git::repository::{Repository, exec_git};
+use serde::Serialize;
+
+const DEFAULT_MAX_COUNT: usize = 20;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineEntry {
+    /// The commit the checkpoint went into, `None` while it is uncommitted.
+    pub commit: Option<String>,
+    #[serde(flatten)]
+    pub label: CheckpointLabelRecord,
+}
+
+pub fn handle_timeline(args: &[String]) {
+    let mut output = OutputOptions::current();
+    let mut range: Option<String> = None;
+    let mut max_count = DEFAULT_MAX_COUNT;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "-n" | "--max-count" if i + 1 < args.len() => {
+                max_count = match args[i + 1].parse::<usize>() {
+                    Ok(n) if n > 0 => n,
+                    _ => {
+                        eprintln!(
+                            "Error: {} expects a positive integer, got '{}'",
+                            args[i],
+                            args[i + 1]
+                        );
+                        std::process::exit(1);
+                    }
+                };
+                i += 2;
+            }
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other if output.parse_flag(other) => {
+                i += 1;
+            }
+            other if !other.starts_with('-') && range.is_none() => {
+                range = Some(other.to_string());
+                i += 1;
+            }
+            other => {
+                eprintln!("Error: unknown option '{}'", other);
+                eprintln!("Run 'git ai timeline --help' for usage");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Error: not a git repository ({})", e);
+            std::process::exit(1);
+        }
+    };
+
+    let entries = match collect_timeline(&repo, range.as_deref(), max_count) {
+        Ok(entries) => entries,
+        Err(e) => {
+            output.exit_with_error(&e);
+        }
+    };
+
+    if output.is_json() {
+        if let Err(e) = output.print_json(&entries) {
+            output.exit_with_error(&e);
+        }
+        return;
+    }
+
+    if entries.is_empty() {
+        if !output.is_quiet() {
+            eprintln!("No labelled checkpoints. Tag one with `git ai checkpoint --label <text>`.");
+        }
+        return;
+    }
+    for entry in &entries {
+        let commit = entry
+            .commit
+            .as_deref()
+            .map(|sha| sha[..sha.len().min(8)].to_string())
+            .unwrap_or_else(|| "uncommitted".to_string());
+        println!("{:<11}  {}", commit, format_checkpoint_label(&entry.label));
+    }
+}
+
+/// Labels in the notes of the last `max_count` commits of `range` (default:
+/// `HEAD`), oldest first. Without a range, labels of uncommitted checkpoints on
+/// top of `HEAD` follow.
+pub fn collect_timeline(
+    repo: &Repository,
+    range: Option<&str>,
+    max_count: usize,
+) -> Result<Vec<TimelineEntry>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("rev-list".to_string());
+    args.push("--reverse".to_string());
+    args.push(format!("--max-count={}", max_count));
+    args.push(range.unwrap_or("HEAD").to_string());
+    args.push("--".to_string());
+    let output = exec_git(&args)?;
+    let shas: Vec<String> = String::from_utf8(output.stdout)?
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    let notes = notes_api::read_notes_batch(repo, &shas)?;
+    let mut entries = Vec::new();
+    for sha in &shas {
+        let Some(log) = notes
+            .get(sha)
+            .and_then(|raw| AuthorshipLog::deserialize_from_string(raw).ok())
+        else {
+            continue;
+        };
+        entries.extend(log.metadata.labels.into_iter().map(|label| TimelineEntry {
+            commit: Some(sha.clone()),
+            label,
+        }));
+    }
+
+    if range.is_none() {
+        let head_sha = repo.head()?.target()?;
+        let checkpoints = repo
+            .storage
+            .working_log_for_base_commit(&head_sha)?
+            .read_all_checkpoints()?;
+        entries.extend(
+            checkpoint_label_records(&checkpoints)
+                .into_iter()
+                .map(|label| TimelineEntry {
+                    commit: None,
+                    label,
+                }),
+        );
+    }
+
+    Ok(entries)
+}
+
+fn print_help() {
+    eprintln!("git-ai timeline - Labelled checkpoints across commits");
+    eprintln!();
+    eprintln!("Usage: git-ai timeline [<commit>|<a>..<b>] [-n <count>] [--json]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  -n, --max-count <n>  Commits to scan, most recent (default: 20)");
+    eprintln!("  --json               Print the entries as JSON");
+    eprintln!("  --help               Show this help");
+    eprintln!();
+    eprintln!("Labels come from `git-ai checkpoint --label <text>`. Without a range,");
+    eprintln!("labels on checkpoints that are not committed yet are listed last.");
+}
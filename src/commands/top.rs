@@ -0,0 +1,316 @@
+//! `git-ai top` — live view of AI coding activity on this machine.
+//!
+//! Polls the local metrics database and shows active agent sessions,
+//! checkpoint rate, recently touched files, and AI lines that have been
+//! checkpointed but not yet committed in each repository.
+
+use crate::commands::output::OutputOptions;
+use crate::metrics::local_stats::{LiveActivity, compute_live_activity};
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyModifiers},
+    execute, queue,
+    style::{Attribute, Print, SetAttribute},
+    terminal::{
+        self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
+        enable_raw_mode,
+    },
+};
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const DEFAULT_INTERVAL_SECS: u64 = 2;
+const DEFAULT_WINDOW_MINS: u32 = 10;
+/// Rows shown per table in the live view so all three sections fit on screen.
+const LIVE_MAX_ROWS: usize = 10;
+
+pub fn handle_top(args: &[String]) {
+    let mut interval_secs = DEFAULT_INTERVAL_SECS;
+    let mut window_mins = DEFAULT_WINDOW_MINS;
+    let mut once = false;
+    let mut json = OutputOptions::current().is_json();
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--interval" | "-n" if i + 1 < args.len() => {
+                interval_secs = parse_positive(&args[i], &args[i + 1]) as u64;
+                i += 1;
+            }
+            "--window" if i + 1 < args.len() => {
+                window_mins = parse_positive(&args[i], &args[i + 1]);
+                i += 1;
+            }
+            "--once" => once = true,
+            "--json" => json = true,
+            "--help" | "-h" => {
+                print_help();
+                return;
+            }
+            other => {
+                eprintln!("Unknown argument: {}", other);
+                eprintln!("Run 'git-ai top --help' for usage.");
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let window_secs = window_mins.saturating_mul(60);
+    if json || once || !io::stdout().is_terminal() {
+        let activity = load_activity_or_exit(window_secs);
+        if json {
+            match serde_json::to_string_pretty(&activity) {
+                Ok(s) => println!("{}", s),
+                Err(e) => {
+                    eprintln!("error serializing JSON: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        } else {
+            for line in render_lines(&activity, now_ts(), usize::MAX) {
+                println!("{}", line);
+            }
+        }
+        return;
+    }
+
+    if let Err(e) = run_live(Duration::from_secs(interval_secs), window_secs) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn parse_positive(flag: &str, value: &str) -> u32 {
+    match value.parse::<u32>() {
+        Ok(n) if n > 0 => n,
+        _ => {
+            eprintln!(
+                "Error: {} expects a positive integer, got '{}'",
+                flag, value
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
+fn load_activity_or_exit(window_secs: u32) -> LiveActivity {
+    match compute_live_activity(now_ts(), window_secs) {
+        Ok(activity) => activity,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn now_ts() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        .min(u32::MAX as u64) as u32
+}
+
+fn run_live(interval: Duration, window_secs: u32) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    let _guard = TerminalGuard::enter(&mut stdout)?;
+    let mut last_refresh: Option<Instant> = None;
+    let mut lines: Vec<String> = Vec::new();
+    let mut needs_redraw = true;
+
+    loop {
+        if last_refresh.is_none_or(|at| at.elapsed() >= interval) {
+            let now = now_ts();
+            lines = match compute_live_activity(now, window_secs) {
+                Ok(activity) => render_lines(&activity, now, LIVE_MAX_ROWS),
+                Err(e) => vec![format!("Failed to read metrics: {}", e)],
+            };
+            last_refresh = Some(Instant::now());
+            needs_redraw = true;
+        }
+
+        if needs_redraw {
+            draw(&mut stdout, &lines)?;
+            needs_redraw = false;
+        }
+
+        if !event::poll(Duration::from_millis(250))? {
+            continue;
+        }
+        match event::read()? {
+            Event::Key(key) => match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+                KeyCode::Char('r') => last_refresh = None,
+                _ => {}
+            },
+            Event::Resize(_, _) => {
+                queue!(stdout, MoveTo(0, 0), Clear(ClearType::All))?;
+                needs_redraw = true;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn draw(stdout: &mut io::Stdout, lines: &[String]) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let body_height = usize::from(height.saturating_sub(1));
+    for row in 0..body_height {
+        let line = lines.get(row).map(String::as_str).unwrap_or("");
+        queue!(
+            stdout,
+            MoveTo(0, row as u16),
+            Clear(ClearType::CurrentLine),
+            Print(truncate(line, usize::from(width)))
+        )?;
+    }
+    queue!(
+        stdout,
+        MoveTo(0, height.saturating_sub(1)),
+        Clear(ClearType::CurrentLine),
+        SetAttribute(Attribute::Reverse),
+        Print(truncate(" q quit  r refresh ", usize::from(width))),
+        SetAttribute(Attribute::Reset)
+    )?;
+    stdout.flush()
+}
+
+fn truncate(line: &str, width: usize) -> String {
+    line.chars().take(width).collect()
+}
+
+/// Render the activity snapshot as plain lines, capping each table at `max_rows`.
+fn render_lines(activity: &LiveActivity, now: u32, max_rows: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    lines.push(format!(
+        "git-ai top — last {} min · {:.1} checkpoints/min · {} active session(s)",
+        activity.window_secs / 60,
+        activity.checkpoints_per_minute,
+        activity.sessions.len()
+    ));
+
+    lines.push(String::new());
+    lines.push("SESSIONS".to_string());
+    if activity.sessions.is_empty() {
+        lines.push("  (no agent checkpoints in this window)".to_string());
+    } else {
+        lines.push(format!(
+            "  {:<10} {:<24} {:<28} {:>6} {:>8} {:>6}  REPO",
+            "TOOL", "MODEL", "SESSION", "CKPTS", "+LINES", "SEEN"
+        ));
+        for session in activity.sessions.iter().take(max_rows) {
+            lines.push(format!(
+                "  {:<10} {:<24} {:<28} {:>6} {:>8} {:>6}  {}",
+                truncate(&session.tool, 10),
+                truncate(session.model.as_deref().unwrap_or("-"), 24),
+                truncate(&session.session_id, 28),
+                session.checkpoints,
+                plus(session.ai_lines_added),
+                format_ago(now, session.last_seen_ts),
+                display_repo(session.repo_url.as_deref())
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.push("FILES".to_string());
+    if activity.files.is_empty() {
+        lines.push("  (no files touched in this window)".to_string());
+    } else {
+        for file in activity.files.iter().take(max_rows) {
+            lines.push(format!(
+                "  {:>6} {:>6}  {}  {}",
+                file.checkpoints,
+                format_ago(now, file.last_seen_ts),
+                file.file_path,
+                display_repo(file.repo_url.as_deref())
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.push("PENDING ATTRIBUTION".to_string());
+    if activity.repos.is_empty() {
+        lines.push("  (all checkpointed AI lines are committed)".to_string());
+    } else {
+        for repo in activity.repos.iter().take(max_rows) {
+            let last_commit = repo
+                .last_commit_ts
+                .map(|ts| format!("last commit {} ago", format_ago(now, ts)))
+                .unwrap_or_else(|| "no recent commit".to_string());
+            lines.push(format!(
+                "  {:>8} lines in {:>4} checkpoint(s)  {}  ({})",
+                plus(repo.pending_ai_lines),
+                repo.pending_checkpoints,
+                display_repo(Some(&repo.repo_url)),
+                last_commit
+            ));
+        }
+    }
+
+    lines
+}
+
+fn display_repo(repo_url: Option<&str>) -> &str {
+    match repo_url {
+        Some(url) if !url.is_empty() => url
+            .trim_start_matches("https://")
+            .trim_start_matches("http://"),
+        _ => "unknown",
+    }
+}
+
+fn plus(lines: u32) -> String {
+    format!("+{}", lines)
+}
+
+fn format_ago(now: u32, ts: u32) -> String {
+    let secs = now.saturating_sub(ts);
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}h", secs / 3600)
+    }
+}
+
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter(stdout: &mut io::Stdout) -> io::Result<Self> {
+        enable_raw_mode()?;
+        if let Err(error) = execute!(stdout, EnterAlternateScreen, Clear(ClearType::All), Hide) {
+            let _ = disable_raw_mode();
+            return Err(error);
+        }
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+        let mut stdout = io::stdout();
+        let _ = execute!(stdout, Show, LeaveAlternateScreen);
+    }
+}
+
+fn print_help() {
+    eprintln!("git-ai top - Live view of AI coding activity on this machine");
+    eprintln!();
+    eprintln!("Usage: git-ai top [options]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  -n, --interval <secs>             Refresh interval (default: 2)");
+    eprintln!("  --window <mins>                   Activity window (default: 10)");
+    eprintln!("  --once                            Print one snapshot and exit");
+    eprintln!("  --json                            Print one snapshot as JSON and exit");
+    eprintln!("  --help                            Show this help");
+    eprintln!();
+    eprintln!("Reads locally recorded metric events. Press q to quit, r to refresh.");
+}
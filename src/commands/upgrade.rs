@@ -649,6 +649,14 @@ fn run_install_script(script_content: &str, tag: &str, silent: bool) -> Result<(
         // Clean up temp script
         let _ = fs::remove_file(&script_path);
 
+        // The script installs into ~/.git-ai/bin; hooks embedding the
+        // `current` link follow it there (see `mdm::hook_binary`).
+        if result.is_ok()
+            && let Some(install_dir) = crate::config::git_ai_dir_path().map(|p| p.join("bin"))
+        {
+            let _ = crate::mdm::hook_binary::update_current_link(&install_dir);
+        }
+
         result
     }
 }
@@ -1,5 +1,6 @@
 //! `git-ai usage` — local statistics from persisted metric events.
 
+use crate::commands::output::OutputOptions;
 use crate::metrics::local_stats::{
     BucketGranularity, LocalActivityStats, RepoActivitySummary, compute_all,
 };
@@ -16,7 +17,7 @@ struct UsageJsonOutput<'a> {
 }
 
 pub fn handle_usage(args: &[String]) {
-    let mut json = false;
+    let mut json = OutputOptions::current().is_json();
 
     let mut i = 0;
     while i < args.len() {
@@ -2,6 +2,7 @@ use crate::api::{ApiClient, ApiContext, metrics_upload_allowed};
 use crate::auth::state::AuthStatus;
 use crate::auth::{AuthState, collect_auth_status, format_unix_timestamp};
 use crate::config;
+use crate::config::{TelemetryChannelState, TelemetrySettings};
 use crate::metrics::db::{MetricsDatabase, MetricsStatus};
 use std::fmt::Write as _;
 
@@ -24,6 +25,7 @@ pub fn handle_whoami(args: &[String]) {
     let config = config::Config::fresh();
     let api_base_url = config.api_base_url().to_string();
     let telemetry_oss_disabled = config.is_telemetry_oss_disabled();
+    let telemetry = *config.telemetry();
     let auth = collect_auth_status();
     let api_ctx = ApiContext::new(None);
     let api_client = ApiClient::new(api_ctx.clone());
@@ -38,6 +40,7 @@ pub fn handle_whoami(args: &[String]) {
             &api_client,
             metrics_status.as_ref().map_err(String::as_str),
             telemetry_oss_disabled,
+            &telemetry,
         )
     );
 
@@ -61,6 +64,7 @@ fn render_whoami(
     api_client: &ApiClient,
     metrics_status: Result<&MetricsStatus, &str>,
     telemetry_oss_disabled: bool,
+    telemetry: &TelemetrySettings,
 ) -> String {
     let mut out = String::new();
 
@@ -145,7 +149,7 @@ fn render_whoami(
     writeln!(
         out,
         "  Metrics delivery: {}",
-        metrics_delivery_status(api_base_url, api_client)
+        metrics_delivery_status(api_base_url, api_client, telemetry.metrics)
     )
     .unwrap();
     writeln!(
@@ -217,7 +221,18 @@ fn login_status(auth: &AuthStatus, api_client: &ApiClient) -> String {
     }
 }
 
-fn metrics_delivery_status(api_base_url: &str, api_client: &ApiClient) -> String {
+fn metrics_delivery_status(
+    api_base_url: &str,
+    api_client: &ApiClient,
+    metrics: TelemetryChannelState,
+) -> String {
+    if !metrics.enabled {
+        return if metrics.managed {
+            "off (disabled by organization policy)".to_string()
+        } else {
+            "off (telemetry_metrics is off)".to_string()
+        };
+    }
     if !metrics_upload_allowed(api_base_url, api_client) {
         return "off (requires an API key or login)".to_string();
     }
@@ -306,6 +321,7 @@ mod tests {
             api_key: api_key.map(str::to_string),
             author_identity: author_identity.map(str::to_string),
             timeout_secs: Some(30),
+            max_retries: crate::api::client::DEFAULT_MAX_RETRIES,
         }
     }
 
@@ -335,7 +351,15 @@ mod tests {
         let client = ApiClient::new(ctx.clone());
         let metrics = metrics_status();
 
-        let output = render_whoami(&ctx.base_url, &auth, &ctx, &client, Ok(&metrics), false);
+        let output = render_whoami(
+            &ctx.base_url,
+            &auth,
+            &ctx,
+            &client,
+            Ok(&metrics),
+            false,
+            &TelemetrySettings::default(),
+        );
 
         assert!(output.contains("API access: connected via API key"));
         assert!(output.contains("API key: configured (gita...7890)"));
@@ -370,7 +394,15 @@ mod tests {
         let client = ApiClient::new(ctx.clone());
         let metrics = metrics_status();
 
-        let output = render_whoami(&ctx.base_url, &auth, &ctx, &client, Ok(&metrics), true);
+        let output = render_whoami(
+            &ctx.base_url,
+            &auth,
+            &ctx,
+            &client,
+            Ok(&metrics),
+            true,
+            &TelemetrySettings::default(),
+        );
 
         assert!(output.contains("API access: connected via login"));
         assert!(output.contains("API key: unset"));
@@ -395,12 +427,52 @@ mod tests {
         let client = ApiClient::new(ctx.clone());
         let metrics = metrics_status();
 
-        let output = render_whoami(&ctx.base_url, &auth, &ctx, &client, Ok(&metrics), false);
+        let output = render_whoami(
+            &ctx.base_url,
+            &auth,
+            &ctx,
+            &client,
+            Ok(&metrics),
+            false,
+            &TelemetrySettings::default(),
+        );
 
         assert!(output.contains("API key: unset"));
         assert!(output.contains("Login: not logged in"));
     }
 
+    #[test]
+    fn render_whoami_reports_managed_metrics_channel_off() {
+        let auth = auth_status(AuthState::LoggedOut);
+        let ctx = api_context(
+            crate::config::DEFAULT_API_BASE_URL,
+            Some("gitai_test_1234567890"),
+            None,
+            None,
+        );
+        let client = ApiClient::new(ctx.clone());
+        let metrics = metrics_status();
+        let telemetry = TelemetrySettings {
+            metrics: TelemetryChannelState {
+                enabled: false,
+                managed: true,
+            },
+            ..Default::default()
+        };
+
+        let output = render_whoami(
+            &ctx.base_url,
+            &auth,
+            &ctx,
+            &client,
+            Ok(&metrics),
+            false,
+            &telemetry,
+        );
+
+        assert!(output.contains("Metrics delivery: off (disabled by organization policy)"));
+    }
+
     #[test]
     fn should_exit_failure_preserves_stored_login_success() {
         let logged_out = auth_status(AuthState::LoggedOut);
@@ -425,7 +497,15 @@ mod tests {
         let client = ApiClient::new(ctx.clone());
         let metrics = metrics_status();
 
-        let output = render_whoami(&ctx.base_url, &auth, &ctx, &client, Ok(&metrics), false);
+        let output = render_whoami(
+            &ctx.base_url,
+            &auth,
+            &ctx,
+            &client,
+            Ok(&metrics),
+            false,
+            &TelemetrySettings::default(),
+        );
 
         assert!(output.contains(
             "API access: not connected (login credentials found, but no usable access token)"
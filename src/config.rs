@@ -3,7 +3,7 @@ use std::env;
 use std::fs;
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
-use std::sync::{Mutex, OnceLock};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
 use glob::Pattern;
@@ -21,6 +21,7 @@ pub const DEFAULT_API_BASE_URL: &str = "https://usegitai.com";
 pub const DEFAULT_MAX_CHECKPOINT_FILE_SIZE_BYTES: usize = 3 * 1024 * 1024;
 pub const DEFAULT_MAX_CHECKPOINT_TOTAL_SIZE_BYTES: usize = 32 * 1024 * 1024;
 pub const DEFAULT_MAX_CHECKPOINT_TOTAL_LINES: usize = 500_000;
+pub const DEFAULT_MAX_NOTE_SIZE_BYTES: usize = 1024 * 1024;
 
 /// Which backend to use for storing authorship notes.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -48,6 +49,42 @@ impl std::fmt::Display for NotesBackendKind {
     }
 }
 
+/// Line diff algorithm behind attribution tracking and squash reconstruction,
+/// set with the `diff_algorithm` config key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DiffAlgorithm {
+    /// Anchors on rare lines first, like git's `--histogram`, which keeps
+    /// moved blocks and repeated lines (braces, blank lines) aligned with
+    /// the code they belong to. See `tests/fixtures/diff_algorithm` and the
+    /// `diff_algorithm` benchmark.
+    #[default]
+    Histogram,
+    /// Classic Myers with imara-diff's heuristics for large inputs.
+    Myers,
+    /// Myers without the heuristics: the shortest edit script, but slower.
+    MyersMinimal,
+}
+
+impl DiffAlgorithm {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "histogram" => Some(DiffAlgorithm::Histogram),
+            "myers" => Some(DiffAlgorithm::Myers),
+            "myers-minimal" | "minimal" => Some(DiffAlgorithm::MyersMinimal),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiffAlgorithm::Histogram => "histogram",
+            DiffAlgorithm::Myers => "myers",
+            DiffAlgorithm::MyersMinimal => "myers-minimal",
+        }
+    }
+}
+
 /// Configuration for the notes backend.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct NotesBackendConfig {
@@ -154,6 +191,114 @@ impl std::fmt::Display for PromptStorageMode {
     }
 }
 
+/// Telemetry upload channels that can be toggled independently.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TelemetryChannel {
+    /// Usage metrics and product analytics events.
+    Metrics,
+    /// Prompt and transcript uploads to the CAS API.
+    Prompts,
+    /// Crash and error reports.
+    CrashReports,
+}
+
+impl TelemetryChannel {
+    pub const ALL: [TelemetryChannel; 3] = [
+        TelemetryChannel::Metrics,
+        TelemetryChannel::Prompts,
+        TelemetryChannel::CrashReports,
+    ];
+
+    /// The config file key controlling this channel.
+    pub fn config_key(&self) -> &'static str {
+        match self {
+            TelemetryChannel::Metrics => "telemetry_metrics",
+            TelemetryChannel::Prompts => "telemetry_prompts",
+            TelemetryChannel::CrashReports => "telemetry_crash_reports",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TelemetryChannel::Metrics => "usage metrics",
+            TelemetryChannel::Prompts => "prompt uploads",
+            TelemetryChannel::CrashReports => "crash reports",
+        }
+    }
+}
+
+/// Effective state of a single telemetry channel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct TelemetryChannelState {
+    pub enabled: bool,
+    /// True when the state is enforced by the organization's managed policy.
+    pub managed: bool,
+}
+
+impl Default for TelemetryChannelState {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            managed: false,
+        }
+    }
+}
+
+impl TelemetryChannelState {
+    /// Resolves a channel: the managed policy wins, then the local `"on"`/`"off"`
+    /// setting, and channels are enabled by default.
+    fn resolve(managed: Option<bool>, local: Option<&str>) -> Self {
+        match managed {
+            Some(enabled) => Self {
+                enabled,
+                managed: true,
+            },
+            None => Self {
+                enabled: local.is_none_or(|value| value.trim() != "off"),
+                managed: false,
+            },
+        }
+    }
+}
+
+/// Effective state of every telemetry channel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct TelemetrySettings {
+    pub metrics: TelemetryChannelState,
+    pub prompts: TelemetryChannelState,
+    pub crash_reports: TelemetryChannelState,
+}
+
+impl TelemetrySettings {
+    fn resolve(
+        managed: &crate::mdm::policy::ManagedTelemetryPolicy,
+        file_cfg: Option<&FileConfig>,
+    ) -> Self {
+        Self {
+            metrics: TelemetryChannelState::resolve(
+                managed.metrics,
+                file_cfg.and_then(|c| c.telemetry_metrics.as_deref()),
+            ),
+            prompts: TelemetryChannelState::resolve(
+                managed.prompts,
+                file_cfg.and_then(|c| c.telemetry_prompts.as_deref()),
+            ),
+            crash_reports: TelemetryChannelState::resolve(
+                managed.crash_reports,
+                file_cfg.and_then(|c| c.telemetry_crash_reports.as_deref()),
+            ),
+        }
+    }
+
+    pub fn channel(&self, channel: TelemetryChannel) -> TelemetryChannelState {
+        match channel {
+            TelemetryChannel::Metrics => self.metrics,
+            TelemetryChannel::Prompts => self.prompts,
+            TelemetryChannel::CrashReports => self.crash_reports,
+        }
+    }
+}
+
 #[derive(Serialize)]
 pub struct Config {
     git_path: String,
@@ -165,8 +310,15 @@ pub struct Config {
     allow_repositories: Vec<Pattern>,
     #[serde(serialize_with = "serialize_patterns")]
     exclude_repositories: Vec<Pattern>,
+    #[serde(serialize_with = "serialize_patterns")]
+    managed_required_repositories: Vec<Pattern>,
+    #[serde(serialize_with = "serialize_patterns")]
+    anonymize_repositories: Vec<Pattern>,
+    #[serde(serialize_with = "serialize_masked_api_key")]
+    anonymization_key: Option<String>,
     telemetry_oss_disabled: bool,
     telemetry_enterprise_dsn: Option<String>,
+    telemetry: TelemetrySettings,
     disable_version_checks: bool,
     disable_auto_updates: bool,
     update_channel: UpdateChannel,
@@ -184,9 +336,11 @@ pub struct Config {
     codex_hooks_format: CodexHooksFormat,
     notes_backend: NotesBackendConfig,
     transcript_streaming_lookback_days: Option<u32>,
+    diff_algorithm: DiffAlgorithm,
     max_checkpoint_file_size_bytes: usize,
     max_checkpoint_total_size_bytes: usize,
     max_checkpoint_total_lines: usize,
+    max_note_size_bytes: usize,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize)]
@@ -237,6 +391,12 @@ pub struct FileConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub telemetry_enterprise_dsn: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry_metrics: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry_prompts: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry_crash_reports: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub disable_version_checks: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub disable_auto_updates: Option<bool>,
@@ -269,11 +429,59 @@ pub struct FileConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub transcript_streaming_lookback_days: Option<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff_algorithm: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_checkpoint_file_size_bytes: Option<usize>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_checkpoint_total_size_bytes: Option<usize>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_checkpoint_total_lines: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_note_size_bytes: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slack_webhook_url: Option<String>,
+    /// URL each attributed commit's stats are POSTed to (see
+    /// [`crate::authorship::commit_webhook`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_webhook_url: Option<String>,
+    /// HMAC-SHA256 key the commit webhook's body is signed with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_webhook_secret: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<crate::workspace_config::WorkspaceConfig>,
+    /// Milliseconds a background hook (`fetch_notes`, `push_notes`) may run
+    /// before it is deferred to `git-ai repair`; `0` disables the timeout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hook_timeouts: Option<HashMap<String, u64>>,
+    /// Milliseconds the post-commit hook may take before it counts against
+    /// the repository's latency budget; unset or `0` disables the budget.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_hook_latency_ms: Option<u64>,
+    /// Size in MB of `.git/ai` past which `git-ai status` suggests `git-ai gc`;
+    /// `0` disables the warning.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai_storage_warn_mb: Option<u64>,
+    /// Absolute directory new repositories keep their working logs under,
+    /// instead of `.git/ai/working_logs`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_logs_dir: Option<String>,
+    /// Repo URL patterns whose author identities and prompt ids are HMAC-hashed
+    /// before they are written to notes or metrics (`"*"` for every repo).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anonymize_repositories: Option<Vec<String>>,
+    /// Workspace key for anonymized identities. Everyone reporting together
+    /// must share it; a managed policy key takes precedence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anonymization_key: Option<String>,
+    /// Directories whose git checkouts `git-ai bg status --workspace` reports on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace_roots: Option<Vec<String>>,
+    /// Minimum milliseconds between two notes fetches the daemon runs for the
+    /// same repository and remote; unset or `0` disables the throttle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo_sync_min_interval_ms: Option<u64>,
 }
 
 static CONFIG: OnceLock<Config> = OnceLock::new();
@@ -316,6 +524,12 @@ pub struct ConfigPatch {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub telemetry_oss_disabled: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry_metrics: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry_prompts: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry_crash_reports: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub disable_version_checks: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub disable_auto_updates: Option<bool>,
@@ -334,11 +548,17 @@ pub struct ConfigPatch {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub transcript_streaming_lookback_days: Option<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff_algorithm: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_checkpoint_file_size_bytes: Option<usize>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_checkpoint_total_size_bytes: Option<usize>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_checkpoint_total_lines: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_note_size_bytes: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_hook_latency_ms: Option<u64>,
 }
 
 impl Config {
@@ -406,7 +626,9 @@ impl Config {
     }
 
     pub fn has_repository_filters(&self) -> bool {
-        !self.allow_repositories.is_empty() || !self.exclude_repositories.is_empty()
+        !self.allow_repositories.is_empty()
+            || !self.exclude_repositories.is_empty()
+            || !self.managed_required_repositories.is_empty()
     }
 
     pub fn is_allowed_repository(&self, repository: &Option<Repository>) -> bool {
@@ -432,6 +654,17 @@ impl Config {
             return true;
         }
 
+        // Managed (MDM) policy: repos the organization requires git-ai in are always
+        // allowed, regardless of local allow/exclude settings
+        if !self.managed_required_repositories.is_empty()
+            && let Some(remotes) = remotes
+            && remotes.iter().any(|remote| {
+                remote_matches_patterns(&self.managed_required_repositories, &remote.1)
+            })
+        {
+            return true;
+        }
+
         // First check if repository is in exclusion list - exclusions take precedence
         if !self.exclude_repositories.is_empty()
             && let Some(remotes) = remotes
@@ -513,11 +746,47 @@ impl Config {
         }
     }
 
+    /// Returns true if identities written to notes and metrics for `repository`
+    /// must be anonymized. Like prompt exclusion, local repositories (no
+    /// remotes) only match the wildcard `"*"` pattern.
+    pub fn should_anonymize(&self, repository: &Repository) -> bool {
+        if self.anonymize_repositories.is_empty() {
+            return false;
+        }
+        let remotes = repository.remotes_with_urls().ok().unwrap_or_default();
+        self.should_anonymize_remote_urls(remotes.iter().map(|(_, url)| url.as_str()))
+    }
+
+    /// [`Config::should_anonymize`] for a repository known by its remote URLs.
+    pub fn should_anonymize_remote_urls<'a>(
+        &self,
+        mut remote_urls: impl Iterator<Item = &'a str>,
+    ) -> bool {
+        if self
+            .anonymize_repositories
+            .iter()
+            .any(|pattern| pattern.as_str() == "*")
+        {
+            return true;
+        }
+        remote_urls.any(|url| remote_matches_patterns(&self.anonymize_repositories, url))
+    }
+
+    /// The configured workspace anonymization key, if any.
+    pub fn anonymization_key(&self) -> Option<&str> {
+        self.anonymization_key.as_deref()
+    }
+
     /// Returns true if OSS telemetry is disabled.
     pub fn is_telemetry_oss_disabled(&self) -> bool {
         self.telemetry_oss_disabled
     }
 
+    /// Returns the effective state of each telemetry channel.
+    pub fn telemetry(&self) -> &TelemetrySettings {
+        &self.telemetry
+    }
+
     /// Returns the telemetry_enterprise_dsn if set.
     pub fn telemetry_enterprise_dsn(&self) -> Option<&str> {
         self.telemetry_enterprise_dsn.as_deref()
@@ -571,6 +840,15 @@ impl Config {
             return PromptStorageMode::Local;
         }
 
+        let mode = self.configured_prompt_storage(repository);
+        // Prompts that would be uploaded stay local while the prompts channel is off.
+        if mode == PromptStorageMode::Default && !self.telemetry.prompts.enabled {
+            return PromptStorageMode::Local;
+        }
+        mode
+    }
+
+    fn configured_prompt_storage(&self, repository: &Option<Repository>) -> PromptStorageMode {
         // Step 2: If no include list, use the global prompt_storage (legacy behavior)
         if self.include_prompts_in_repositories.is_empty() {
             return self
@@ -645,6 +923,11 @@ impl Config {
         self.transcript_streaming_lookback_days
     }
 
+    /// Returns the line diff algorithm used for attribution.
+    pub fn diff_algorithm(&self) -> DiffAlgorithm {
+        self.diff_algorithm
+    }
+
     /// Returns the per-file size limit for checkpoint content reads.
     pub fn max_checkpoint_file_size_bytes(&self) -> usize {
         self.max_checkpoint_file_size_bytes
@@ -660,6 +943,12 @@ impl Config {
         self.max_checkpoint_total_lines
     }
 
+    /// Returns the largest authorship note kept inline in the notes ref; larger
+    /// notes are stored as overflow objects. `0` disables the limit.
+    pub fn max_note_size_bytes(&self) -> usize {
+        self.max_note_size_bytes
+    }
+
     /// Returns true if quiet mode is enabled (suppresses chart output after commits)
     pub fn is_quiet(&self) -> bool {
         self.quiet
@@ -947,12 +1236,17 @@ fn author_config_file_fingerprint(path: &Path) -> Option<AuthorConfigFileFingerp
 }
 
 fn build_config() -> Config {
-    let file_cfg = load_file_config();
+    let managed_preferences = crate::mdm::managed_preferences::load_managed_preferences();
+    let file_cfg = load_managed_file_config();
+    let managed_policy = crate::mdm::policy::load_managed_policy()
+        .unwrap_or_default()
+        .with_device_policy(&managed_preferences.policy);
     let exclude_prompts_in_repositories = file_cfg
         .as_ref()
         .and_then(|c| c.exclude_prompts_in_repositories.clone())
         .unwrap_or_default()
         .into_iter()
+        .chain(managed_policy.exclude_prompts_in_repositories.clone())
         .filter_map(|pattern_str| {
             Pattern::new(&pattern_str)
                 .map_err(|e| {
@@ -1012,6 +1306,42 @@ fn build_config() -> Config {
                 .ok()
         })
         .collect();
+    let managed_required_repositories = managed_policy
+        .require_repositories
+        .into_iter()
+        .filter_map(|pattern_str| {
+            Pattern::new(&pattern_str)
+                .map_err(|e| {
+                    eprintln!(
+                        "Warning: Invalid glob pattern in managed require_repositories '{}': {}",
+                        pattern_str, e
+                    );
+                })
+                .ok()
+        })
+        .collect();
+    let anonymize_repositories = file_cfg
+        .as_ref()
+        .and_then(|c| c.anonymize_repositories.clone())
+        .unwrap_or_default()
+        .into_iter()
+        .chain(managed_policy.anonymize_repositories.clone())
+        .filter_map(|pattern_str| {
+            Pattern::new(&pattern_str)
+                .map_err(|e| {
+                    eprintln!(
+                        "Warning: Invalid glob pattern in anonymize_repositories '{}': {}",
+                        pattern_str, e
+                    );
+                })
+                .ok()
+        })
+        .collect();
+    let anonymization_key = managed_policy
+        .anonymization_key
+        .clone()
+        .or_else(|| file_cfg.as_ref().and_then(|c| c.anonymization_key.clone()))
+        .filter(|key| !key.is_empty());
     let telemetry_oss_disabled = file_cfg
         .as_ref()
         .and_then(|c| c.telemetry_oss.clone())
@@ -1021,6 +1351,7 @@ fn build_config() -> Config {
         .as_ref()
         .and_then(|c| c.telemetry_enterprise_dsn.clone())
         .filter(|s| !s.is_empty());
+    let telemetry = TelemetrySettings::resolve(&managed_policy.telemetry, file_cfg.as_ref());
 
     // Default to disabled (true) unless this is an OSS build
     // OSS builds set OSS_BUILD env var at compile time to "1", which enables auto-updates by default
@@ -1184,6 +1515,18 @@ fn build_config() -> Config {
         .or(Some(7))
         .and_then(|v| if v == 0 { None } else { Some(v) });
 
+    // Diff algorithm: env > file > default (histogram). Unknown values fall back.
+    let diff_algorithm = env::var("GIT_AI_DIFF_ALGORITHM")
+        .ok()
+        .and_then(|v| DiffAlgorithm::parse(&v))
+        .or_else(|| {
+            file_cfg
+                .as_ref()
+                .and_then(|c| c.diff_algorithm.as_deref())
+                .and_then(DiffAlgorithm::parse)
+        })
+        .unwrap_or_default();
+
     // Checkpoint content limits: env > file > defaults.
     let max_checkpoint_file_size_bytes = env::var("GIT_AI_MAX_CHECKPOINT_FILE_SIZE_BYTES")
         .ok()
@@ -1211,6 +1554,12 @@ fn build_config() -> Config {
         .or_else(|| file_cfg.as_ref().and_then(|c| c.max_checkpoint_total_lines))
         .unwrap_or(DEFAULT_MAX_CHECKPOINT_TOTAL_LINES);
 
+    let max_note_size_bytes = env::var("GIT_AI_MAX_NOTE_SIZE_BYTES")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .or_else(|| file_cfg.as_ref().and_then(|c| c.max_note_size_bytes))
+        .unwrap_or(DEFAULT_MAX_NOTE_SIZE_BYTES);
+
     #[cfg(any(test, feature = "test-support"))]
     {
         let mut config = Config {
@@ -1219,8 +1568,12 @@ fn build_config() -> Config {
             include_prompts_in_repositories,
             allow_repositories,
             exclude_repositories,
+            managed_required_repositories,
+            anonymize_repositories,
+            anonymization_key,
             telemetry_oss_disabled,
             telemetry_enterprise_dsn,
+            telemetry,
             disable_version_checks,
             disable_auto_updates,
             update_channel,
@@ -1237,9 +1590,11 @@ fn build_config() -> Config {
             codex_hooks_format,
             notes_backend,
             transcript_streaming_lookback_days,
+            diff_algorithm,
             max_checkpoint_file_size_bytes,
             max_checkpoint_total_size_bytes,
             max_checkpoint_total_lines,
+            max_note_size_bytes,
         };
         apply_test_config_patch(&mut config);
         config
@@ -1252,8 +1607,12 @@ fn build_config() -> Config {
         include_prompts_in_repositories,
         allow_repositories,
         exclude_repositories,
+        managed_required_repositories,
+        anonymize_repositories,
+        anonymization_key,
         telemetry_oss_disabled,
         telemetry_enterprise_dsn,
+        telemetry,
         disable_version_checks,
         disable_auto_updates,
         update_channel,
@@ -1270,9 +1629,11 @@ fn build_config() -> Config {
         codex_hooks_format,
         notes_backend,
         transcript_streaming_lookback_days,
+        diff_algorithm,
         max_checkpoint_file_size_bytes,
         max_checkpoint_total_size_bytes,
         max_checkpoint_total_lines,
+        max_note_size_bytes,
     }
 }
 
@@ -1329,7 +1690,10 @@ fn build_feature_flags(file_cfg: &Option<FileConfig>) -> FeatureFlags {
         serde_json::from_value(value).ok()
     });
 
-    FeatureFlags::from_env_and_file(file_flags)
+    let remote_flags = crate::feature_flags::remote::load_remote_feature_flags()
+        .map(|document| document.to_overrides());
+
+    FeatureFlags::from_sources(remote_flags, file_flags)
 }
 
 fn resolve_git_path(file_cfg: &Option<FileConfig>) -> String {
@@ -1446,6 +1810,17 @@ fn load_file_config() -> Option<FileConfig> {
     parse_file_config_bytes(&data).ok()
 }
 
+/// The user's file config with the settings enforced by the device's
+/// management profile written over it.
+fn load_managed_file_config() -> Option<FileConfig> {
+    let managed = crate::mdm::managed_preferences::load_managed_preferences();
+    match load_file_config() {
+        Some(config) => Some(managed.apply_to(config)),
+        None if managed.settings.is_empty() => None,
+        None => Some(managed.apply_to(FileConfig::default())),
+    }
+}
+
 fn parse_file_config_bytes(data: &[u8]) -> Result<FileConfig, serde_json::Error> {
     // Windows PowerShell 5.1 writes UTF-8 with BOM by default for `Out-File -Encoding UTF8`.
     // Tolerate BOM-prefixed config files so upgrades/installers don't brick config parsing.
@@ -1545,6 +1920,70 @@ pub fn load_file_config_public() -> Result<FileConfig, String> {
     parse_file_config_bytes(&data).map_err(|e| format!("Failed to parse config file: {}", e))
 }
 
+/// Load the file config with device-managed settings applied. For reading
+/// settings only: saving it would copy the managed values into the user's file.
+pub fn load_effective_file_config() -> Result<FileConfig, String> {
+    let config = load_file_config_public()?;
+    Ok(crate::mdm::managed_preferences::load_managed_preferences().apply_to(config))
+}
+
+/// Upper bound on how stale [`effective_file_config_cached`] can be when the
+/// config file is unchanged; device-managed settings are picked up at this pace.
+const FILE_CONFIG_CACHE_TTL: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileConfigCacheKey {
+    path: Option<PathBuf>,
+    len: Option<u64>,
+    modified: Option<std::time::SystemTime>,
+}
+
+struct CachedFileConfig {
+    key: FileConfigCacheKey,
+    loaded_at: Instant,
+    config: Arc<FileConfig>,
+}
+
+static FILE_CONFIG_CACHE: OnceLock<Mutex<Option<CachedFileConfig>>> = OnceLock::new();
+
+/// [`load_effective_file_config`] for hot paths of long-lived processes such as
+/// the daemon. The file is only re-parsed when its size or mtime changes, or
+/// after [`FILE_CONFIG_CACHE_TTL`]. An unreadable config yields the defaults.
+pub fn effective_file_config_cached() -> Arc<FileConfig> {
+    let path = config_file_path();
+    let metadata = path.as_ref().and_then(|path| fs::metadata(path).ok());
+    let key = FileConfigCacheKey {
+        path,
+        len: metadata.as_ref().map(|m| m.len()),
+        modified: metadata.and_then(|m| m.modified().ok()),
+    };
+    let load = || {
+        Arc::new(load_effective_file_config().unwrap_or_else(|e| {
+            tracing::debug!("failed to load config file: {}", e);
+            FileConfig::default()
+        }))
+    };
+
+    let cache = FILE_CONFIG_CACHE.get_or_init(|| Mutex::new(None));
+    let Ok(mut guard) = cache.lock() else {
+        return load();
+    };
+    let now = Instant::now();
+    if let Some(cached) = guard.as_ref()
+        && cached.key == key
+        && now.duration_since(cached.loaded_at) < FILE_CONFIG_CACHE_TTL
+    {
+        return Arc::clone(&cached.config);
+    }
+    let config = load();
+    *guard = Some(CachedFileConfig {
+        key,
+        loaded_at: now,
+        config: Arc::clone(&config),
+    });
+    config
+}
+
 /// Save the file config
 pub fn save_file_config(config: &FileConfig) -> Result<(), String> {
     let path =
@@ -1667,6 +2106,15 @@ fn apply_test_config_patch(config: &mut Config) {
         if let Some(telemetry_oss_disabled) = patch.telemetry_oss_disabled {
             config.telemetry_oss_disabled = telemetry_oss_disabled;
         }
+        if let Some(enabled) = patch.telemetry_metrics {
+            config.telemetry.metrics.enabled = enabled;
+        }
+        if let Some(enabled) = patch.telemetry_prompts {
+            config.telemetry.prompts.enabled = enabled;
+        }
+        if let Some(enabled) = patch.telemetry_crash_reports {
+            config.telemetry.crash_reports.enabled = enabled;
+        }
         if let Some(disable_version_checks) = patch.disable_version_checks {
             config.disable_version_checks = disable_version_checks;
         }
@@ -1719,6 +2167,13 @@ fn apply_test_config_patch(config: &mut Config) {
         if let Some(days) = patch.transcript_streaming_lookback_days {
             config.transcript_streaming_lookback_days = if days == 0 { None } else { Some(days) };
         }
+        if let Some(algorithm) = patch
+            .diff_algorithm
+            .as_deref()
+            .and_then(DiffAlgorithm::parse)
+        {
+            config.diff_algorithm = algorithm;
+        }
         if let Some(max_bytes) = patch.max_checkpoint_file_size_bytes {
             config.max_checkpoint_file_size_bytes = max_bytes;
         }
@@ -1728,6 +2183,9 @@ fn apply_test_config_patch(config: &mut Config) {
         if let Some(max_lines) = patch.max_checkpoint_total_lines {
             config.max_checkpoint_total_lines = max_lines;
         }
+        if let Some(max_bytes) = patch.max_note_size_bytes {
+            config.max_note_size_bytes = max_bytes;
+        }
     }
 }
 
@@ -1751,8 +2209,12 @@ mod tests {
                 .into_iter()
                 .filter_map(|s| Pattern::new(&s).ok())
                 .collect(),
+            managed_required_repositories: vec![],
+            anonymize_repositories: vec![],
+            anonymization_key: None,
             telemetry_oss_disabled: false,
             telemetry_enterprise_dsn: None,
+            telemetry: TelemetrySettings::default(),
             disable_version_checks: false,
             disable_auto_updates: false,
             update_channel: UpdateChannel::Latest,
@@ -1769,9 +2231,11 @@ mod tests {
             codex_hooks_format: CodexHooksFormat::ConfigToml,
             notes_backend: NotesBackendConfig::default(),
             transcript_streaming_lookback_days: Some(7),
+            diff_algorithm: DiffAlgorithm::default(),
             max_checkpoint_file_size_bytes: DEFAULT_MAX_CHECKPOINT_FILE_SIZE_BYTES,
             max_checkpoint_total_size_bytes: DEFAULT_MAX_CHECKPOINT_TOTAL_SIZE_BYTES,
             max_checkpoint_total_lines: DEFAULT_MAX_CHECKPOINT_TOTAL_LINES,
+            max_note_size_bytes: DEFAULT_MAX_NOTE_SIZE_BYTES,
         }
     }
 
@@ -1996,8 +2460,12 @@ mod tests {
             include_prompts_in_repositories: vec![],
             allow_repositories: vec![],
             exclude_repositories: vec![],
+            managed_required_repositories: vec![],
+            anonymize_repositories: vec![],
+            anonymization_key: None,
             telemetry_oss_disabled: false,
             telemetry_enterprise_dsn: None,
+            telemetry: TelemetrySettings::default(),
             disable_version_checks: false,
             disable_auto_updates: false,
             update_channel: UpdateChannel::Latest,
@@ -2014,9 +2482,11 @@ mod tests {
             codex_hooks_format: CodexHooksFormat::ConfigToml,
             notes_backend: NotesBackendConfig::default(),
             transcript_streaming_lookback_days: Some(7),
+            diff_algorithm: DiffAlgorithm::default(),
             max_checkpoint_file_size_bytes: DEFAULT_MAX_CHECKPOINT_FILE_SIZE_BYTES,
             max_checkpoint_total_size_bytes: DEFAULT_MAX_CHECKPOINT_TOTAL_SIZE_BYTES,
             max_checkpoint_total_lines: DEFAULT_MAX_CHECKPOINT_TOTAL_LINES,
+            max_note_size_bytes: DEFAULT_MAX_NOTE_SIZE_BYTES,
         }
     }
 
@@ -2144,8 +2614,12 @@ mod tests {
                 .collect(),
             allow_repositories: vec![],
             exclude_repositories: vec![],
+            managed_required_repositories: vec![],
+            anonymize_repositories: vec![],
+            anonymization_key: None,
             telemetry_oss_disabled: false,
             telemetry_enterprise_dsn: None,
+            telemetry: TelemetrySettings::default(),
             disable_version_checks: false,
             disable_auto_updates: false,
             update_channel: UpdateChannel::Latest,
@@ -2162,9 +2636,11 @@ mod tests {
             codex_hooks_format: CodexHooksFormat::ConfigToml,
             notes_backend: NotesBackendConfig::default(),
             transcript_streaming_lookback_days: Some(7),
+            diff_algorithm: DiffAlgorithm::default(),
             max_checkpoint_file_size_bytes: DEFAULT_MAX_CHECKPOINT_FILE_SIZE_BYTES,
             max_checkpoint_total_size_bytes: DEFAULT_MAX_CHECKPOINT_TOTAL_SIZE_BYTES,
             max_checkpoint_total_lines: DEFAULT_MAX_CHECKPOINT_TOTAL_LINES,
+            max_note_size_bytes: DEFAULT_MAX_NOTE_SIZE_BYTES,
         }
     }
 
@@ -2237,6 +2713,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_effective_prompt_storage_prompts_channel_off_keeps_uploads_local() {
+        let mut config = create_test_config_with_include_prompts(vec![], vec![], "default", None);
+        config.telemetry.prompts.enabled = false;
+        assert_eq!(
+            config.effective_prompt_storage(&None),
+            PromptStorageMode::Local
+        );
+
+        // Notes mode does not upload, so it is unaffected.
+        let mut config = create_test_config_with_include_prompts(vec![], vec![], "notes", None);
+        config.telemetry.prompts.enabled = false;
+        assert_eq!(
+            config.effective_prompt_storage(&None),
+            PromptStorageMode::Notes
+        );
+    }
+
+    #[test]
+    fn test_should_anonymize_remote_urls_matches_patterns_and_wildcard() {
+        let mut config = create_test_config(vec![], vec![]);
+        assert!(!config.should_anonymize_remote_urls(["https://github.com/acme/app"].into_iter()));
+
+        config.anonymize_repositories = vec![Pattern::new("https://github.com/acme/*").unwrap()];
+        assert!(config.should_anonymize_remote_urls(["https://github.com/acme/app"].into_iter()));
+        assert!(!config.should_anonymize_remote_urls(["https://github.com/other/app"].into_iter()));
+        assert!(!config.should_anonymize_remote_urls(std::iter::empty()));
+
+        config.anonymize_repositories = vec![Pattern::new("*").unwrap()];
+        assert!(config.should_anonymize_remote_urls(std::iter::empty()));
+    }
+
+    #[test]
+    fn test_telemetry_channels_resolve_managed_over_local() {
+        let file_cfg = FileConfig {
+            telemetry_metrics: Some("off".to_string()),
+            telemetry_prompts: Some("off".to_string()),
+            telemetry_crash_reports: Some("on".to_string()),
+            ..Default::default()
+        };
+        let managed = crate::mdm::policy::ManagedTelemetryPolicy {
+            prompts: Some(true),
+            crash_reports: Some(false),
+            ..Default::default()
+        };
+
+        let telemetry = TelemetrySettings::resolve(&managed, Some(&file_cfg));
+        assert_eq!(
+            telemetry.metrics,
+            TelemetryChannelState {
+                enabled: false,
+                managed: false
+            }
+        );
+        assert_eq!(
+            telemetry.prompts,
+            TelemetryChannelState {
+                enabled: true,
+                managed: true
+            }
+        );
+        assert_eq!(
+            telemetry.crash_reports,
+            TelemetryChannelState {
+                enabled: false,
+                managed: true
+            }
+        );
+    }
+
+    #[test]
+    fn test_telemetry_channels_default_on() {
+        let telemetry = TelemetrySettings::resolve(&Default::default(), None);
+        for channel in TelemetryChannel::ALL {
+            assert_eq!(telemetry.channel(channel), TelemetryChannelState::default());
+            assert!(telemetry.channel(channel).enabled);
+        }
+    }
+
     #[test]
     fn test_effective_prompt_storage_no_fallback_defaults_to_local() {
         // Non-wildcard include with None repo and no fallback = Local
@@ -2397,6 +2952,37 @@ mod tests {
         assert!(config.is_allowed_repository_with_remotes(Some(&remotes)));
     }
 
+    #[test]
+    fn test_managed_required_repo_overrides_local_exclusion() {
+        let mut config = create_test_config(vec![], vec!["https://github.com/acme/*".to_string()]);
+        config.managed_required_repositories =
+            vec![Pattern::new("https://github.com/acme/*").unwrap()];
+        let remotes = vec![(
+            "origin".to_string(),
+            "https://github.com/acme/service".to_string(),
+        )];
+        assert!(config.has_repository_filters());
+        assert!(config.is_allowed_repository_with_remotes(Some(&remotes)));
+    }
+
+    #[test]
+    fn test_managed_required_repo_overrides_local_allowlist() {
+        let mut config =
+            create_test_config(vec!["https://github.com/personal/*".to_string()], vec![]);
+        config.managed_required_repositories =
+            vec![Pattern::new("https://github.com/acme/*").unwrap()];
+        let required = vec![(
+            "origin".to_string(),
+            "https://github.com/acme/service".to_string(),
+        )];
+        let other = vec![(
+            "origin".to_string(),
+            "https://github.com/other/repo".to_string(),
+        )];
+        assert!(config.is_allowed_repository_with_remotes(Some(&required)));
+        assert!(!config.is_allowed_repository_with_remotes(Some(&other)));
+    }
+
     #[test]
     fn test_allowlist_with_remotes() {
         let config = create_test_config(vec!["https://github.com/myorg/*".to_string()], vec![]);
@@ -2633,6 +3219,25 @@ mod tests {
         assert_eq!(parse_kind(""), None);
     }
 
+    #[test]
+    fn test_diff_algorithm_parse_round_trips() {
+        for algorithm in [
+            DiffAlgorithm::Histogram,
+            DiffAlgorithm::Myers,
+            DiffAlgorithm::MyersMinimal,
+        ] {
+            assert_eq!(DiffAlgorithm::parse(algorithm.as_str()), Some(algorithm));
+        }
+        assert_eq!(
+            DiffAlgorithm::parse(" Minimal "),
+            Some(DiffAlgorithm::MyersMinimal)
+        );
+        // imara-diff has no patience implementation to select.
+        assert_eq!(DiffAlgorithm::parse("patience"), None);
+        assert_eq!(DiffAlgorithm::parse("diff3"), None);
+        assert_eq!(DiffAlgorithm::default(), DiffAlgorithm::Histogram);
+    }
+
     #[test]
     fn test_notes_backend_env_var_overrides_file_config_via_fresh() {
         // Verify that GIT_AI_NOTES_BACKEND_KIND=http is correctly resolved in
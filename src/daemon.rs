@@ -4,16 +4,19 @@ use crate::config;
 use crate::daemon::git_backend::GitBackend;
 use crate::error::GitAiError;
 use crate::git::cli_parser::{
-    ParsedGitInvocation, explicit_rebase_branch_arg, parse_git_cli_args, summarize_rebase_args,
+    ParsedGitInvocation, explicit_rebase_branch_arg, parse_git_cli_args, rebase_has_exec,
+    summarize_rebase_args,
 };
 use crate::git::find_repository_in_path;
 use crate::git::repo_state::{
+    cherry_pick_sequencer_todo_for_worktree, cherry_pick_stop_for_worktree,
     common_dir_for_worktree, git_dir_for_worktree, worktree_root_for_path,
 };
+use crate::git::repo_storage::RepairKind;
 use crate::git::repository::{
     Repository, discover_repository_in_path_no_git_exec, exec_git, exec_git_stdin,
 };
-use crate::git::sync_authorship::{fetch_authorship_notes, fetch_remote_from_args};
+use crate::git::sync_authorship::{fetch_authorship_notes_cancellable, fetch_remote_from_args};
 use crate::utils::LockFile;
 use crate::{
     authorship::working_log::CheckpointKind,
@@ -62,6 +65,7 @@ pub mod domain;
 pub mod family_actor;
 pub mod git_backend;
 pub mod global_actor;
+pub mod hook_budget;
 pub mod reducer;
 pub mod ref_cursor;
 pub mod rewrite_metrics;
@@ -73,10 +77,12 @@ pub mod telemetry_worker;
 pub mod test_sync;
 pub mod trace_normalizer;
 pub mod transcript_redaction;
+pub mod watchdog;
+pub mod workspace;
 
 pub use control_api::{
     BashSessionQueryResponse, BashSnapshotQueryResponse, ControlRequest, ControlResponse,
-    FamilyStatus, TelemetryEnvelope,
+    FamilyStatus, TelemetryEnvelope, WorkspaceRepoStatus, WorkspaceStatus,
 };
 
 const PID_META_FILE: &str = "daemon.pid.json";
@@ -870,7 +876,14 @@ fn apply_checkpoint_side_effect(mut request: CheckpointRequest) -> Result<(), Gi
                 && let Some(ref agent_id) = request.agent_id
                 && crate::daemon::checkpoint::should_emit_agent_usage(agent_id)
             {
-                let attrs = crate::daemon::checkpoint::build_agent_usage_attrs(None, agent_id);
+                let attrs = crate::daemon::checkpoint::build_agent_usage_attrs(
+                    None,
+                    agent_id,
+                    request
+                        .metadata
+                        .get(crate::authorship::working_log::TOOL_VERSION_METADATA_KEY)
+                        .map(String::as_str),
+                );
                 let values = crate::metrics::AgentUsageValues::new();
                 crate::metrics::record(values, attrs);
             }
@@ -878,12 +891,20 @@ fn apply_checkpoint_side_effect(mut request: CheckpointRequest) -> Result<(), Gi
         }
     };
     let author = repo.effective_author_identity().formatted_or_unknown();
+    apply_session_override(&repo, &mut request);
 
     if request.checkpoint_kind.is_ai()
         && let Some(ref agent_id) = request.agent_id
         && crate::daemon::checkpoint::should_emit_agent_usage(agent_id)
     {
-        let attrs = crate::daemon::checkpoint::build_agent_usage_attrs(Some(&repo), agent_id);
+        let attrs = crate::daemon::checkpoint::build_agent_usage_attrs(
+            Some(&repo),
+            agent_id,
+            request
+                .metadata
+                .get(crate::authorship::working_log::TOOL_VERSION_METADATA_KEY)
+                .map(String::as_str),
+        );
         let values = crate::metrics::AgentUsageValues::new();
         crate::metrics::record(values, attrs);
     }
@@ -902,6 +923,34 @@ fn apply_checkpoint_side_effect(mut request: CheckpointRequest) -> Result<(), Gi
     )
 }
 
+/// While a `git-ai session start` override is active, untracked human
+/// checkpoints (no agent, not KnownHuman) are recorded as edits by that tool.
+fn apply_session_override(
+    repo: &crate::git::repository::Repository,
+    request: &mut CheckpointRequest,
+) {
+    if request.checkpoint_kind != CheckpointKind::Human || request.agent_id.is_some() {
+        return;
+    }
+    let Some(session) = repo
+        .storage
+        .active_session_override(chrono::Utc::now().timestamp())
+    else {
+        return;
+    };
+    request.checkpoint_kind = CheckpointKind::AiAgent;
+    request.agent_id = Some(crate::authorship::working_log::AgentId {
+        tool: session.tool,
+        id: session.session_id,
+        model: session.model,
+    });
+    request.path_role = PreparedPathRole::Edited;
+    request
+        .metadata
+        .entry("edit_kind".to_string())
+        .or_insert_with(|| "session_override".to_string());
+}
+
 fn resolve_checkpoint_request(
     repo: &crate::git::repository::Repository,
     request: &mut CheckpointRequest,
@@ -927,6 +976,7 @@ fn resolve_checkpoint_request(
 
     let mut files = Vec::new();
     let mut dirty_files: HashMap<String, Arc<str>> = HashMap::new();
+    let mut hunks = HashMap::new();
     let mut seen = std::collections::HashSet::new();
     let config = config::Config::fresh();
     let mut content_budget = CheckpointContentBudget::from_config(&config);
@@ -974,6 +1024,9 @@ fn resolve_checkpoint_request(
                 continue;
             }
             dirty_files.insert(relative_path.clone(), Arc::from(content));
+            if let Some(file_hunks) = file.hunks.take() {
+                hunks.insert(relative_path.clone(), file_hunks);
+            }
             files.push(relative_path);
         }
     }
@@ -993,6 +1046,7 @@ fn resolve_checkpoint_request(
             ts,
             files,
             dirty_files,
+            hunks,
         },
     ))
 }
@@ -1025,6 +1079,42 @@ fn compute_watermarks_from_stat(
     watermarks
 }
 
+/// Line attributions predicted for `content` as the unsaved buffer of
+/// `file_path` (absolute, or relative to the repository root).
+fn buffer_attribution(
+    repo_working_dir: &str,
+    file_path: &str,
+    content: String,
+) -> Result<Value, GitAiError> {
+    let repo = find_repository_in_path(repo_working_dir)?;
+    let workdir = repo.workdir()?;
+    let relative_path = Path::new(file_path)
+        .strip_prefix(&workdir)
+        .unwrap_or(Path::new(file_path))
+        .to_string_lossy()
+        .replace('\\', "/");
+    let base_commit = repo.head()?.target()?;
+    let overlays = HashMap::from([(relative_path.clone(), content)]);
+    let virtual_attrs =
+        crate::authorship::virtual_attribution::VirtualAttributions::from_buffer_overlay(
+            repo,
+            base_commit.clone(),
+            None,
+            &overlays,
+        )?;
+    let line_attributions = virtual_attrs
+        .get_line_attributions(&relative_path)
+        .cloned()
+        .unwrap_or_default();
+    Ok(json!({
+        "file_path": relative_path,
+        "base_commit": base_commit,
+        "line_attributions": line_attributions,
+        "sessions": virtual_attrs.sessions(),
+        "prompts": virtual_attrs.prompts(),
+    }))
+}
+
 fn capture_commit_file_timestamps(
     worktree: &Path,
     commit_sha: &str,
@@ -1087,7 +1177,7 @@ fn apply_push_side_effect(
 ) -> Result<(), GitAiError> {
     use crate::config::NotesBackendKind;
     use crate::git::cli_parser::is_dry_run;
-    use crate::git::sync_authorship::{push_authorship_notes, push_remote_from_args};
+    use crate::git::sync_authorship::{push_authorship_notes_cancellable, push_remote_from_args};
 
     if crate::config::Config::get().notes_backend_kind() == NotesBackendKind::Http {
         tracing::debug!("apply_push_side_effect: skipping authorship push (Http backend)");
@@ -1112,7 +1202,14 @@ fn apply_push_side_effect(
     crate::commands::upgrade::maybe_schedule_background_update_check();
     tracing::debug!("started pushing authorship notes to remote: {}", remote);
 
-    push_authorship_notes(&repo, &remote)
+    let worker_repo = repo.clone();
+    let worker_remote = remote.clone();
+    crate::daemon::watchdog::run_deferrable(
+        &repo,
+        RepairKind::PushNotes,
+        &remote,
+        move |cancelled| push_authorship_notes_cancellable(&worker_repo, &worker_remote, cancelled),
+    )
 }
 
 fn transcript_sweep_triggers_for_events(
@@ -1147,8 +1244,6 @@ fn apply_pull_notes_sync_side_effect(
     command: Option<&str>,
     args: &[String],
 ) -> Result<(), GitAiError> {
-    use crate::config::NotesBackendKind;
-
     let repo = find_repository_in_path(worktree)?;
     let parsed = parsed_invocation_for_side_effect(command, args);
     let remote = fetch_remote_from_args(&repo, &parsed)?;
@@ -1162,17 +1257,75 @@ fn apply_pull_notes_sync_side_effect(
         "handling pull notes sync"
     );
 
-    if notes_backend == NotesBackendKind::Http {
-        return crate::git::notes_api::warm_cache_for_remote(&repo, &remote);
+    fetch_notes_under_watchdog(&repo, &remote, notes_backend)
+}
+
+/// Fetch notes after `git fetch`, unless the previous successful notes fetch
+/// for the repository and remote is within `repo_sync_min_interval_ms`.
+/// Editors fetching in the background would otherwise refetch notes for every
+/// checkout over and over. Best effort: the fetch itself already succeeded.
+fn apply_fetch_notes_sync_side_effect(
+    worktree: &str,
+    command: Option<&str>,
+    args: &[String],
+) -> Result<(), GitAiError> {
+    let repo = find_repository_in_path(worktree)?;
+    let parsed = parsed_invocation_for_side_effect(command, args);
+    let remote = fetch_remote_from_args(&repo, &parsed)?;
+    if !crate::daemon::workspace::notes_sync_due(&repo, &remote) {
+        tracing::debug!(remote = %remote, "notes fetch throttled by repo_sync_min_interval_ms");
+        return Ok(());
     }
+    let notes_backend = crate::config::Config::fresh().notes_backend_kind();
 
-    fetch_authorship_notes(&repo, &remote)?;
-    Ok(())
+    tracing::info!(
+        command = command.unwrap_or("fetch"),
+        remote = %remote,
+        backend = %notes_backend,
+        worktree = %worktree,
+        "handling fetch notes sync"
+    );
+
+    fetch_notes_under_watchdog(&repo, &remote, notes_backend)
 }
 
-fn apply_clone_notes_sync_side_effect(worktree: &str) -> Result<(), GitAiError> {
-    use crate::config::NotesBackendKind;
+/// Fetch `remote`'s notes (or warm the HTTP backend cache for it) under the
+/// `fetch_notes` hook timeout. A successful fetch, even one finishing after the
+/// timeout, restarts the `repo_sync_min_interval_ms` throttle.
+fn fetch_notes_under_watchdog(
+    repo: &Repository,
+    remote: &str,
+    notes_backend: config::NotesBackendKind,
+) -> Result<(), GitAiError> {
+    let worker_repo = repo.clone();
+    let worker_remote = remote.to_string();
+    crate::daemon::watchdog::run_deferrable(
+        repo,
+        RepairKind::FetchNotes,
+        remote,
+        move |cancelled| {
+            if notes_backend == config::NotesBackendKind::Http {
+                crate::git::notes_api::warm_cache_for_remote(&worker_repo, &worker_remote)?;
+            } else {
+                fetch_authorship_notes_cancellable(&worker_repo, &worker_remote, cancelled)?;
+            }
+            crate::daemon::workspace::record_notes_sync(&worker_repo, &worker_remote);
+            Ok(())
+        },
+    )
+}
 
+/// Snapshot attribution orphaned by force-pushed remote refs for `git-ai reconcile`.
+/// Best effort: a failure here must not fail the fetch side effects.
+fn apply_fetch_quarantine_side_effect(worktree: &str) {
+    let result = find_repository_in_path(worktree)
+        .and_then(|repo| crate::authorship::quarantine::quarantine_forced_remote_updates(&repo));
+    if let Err(e) = result {
+        tracing::warn!(worktree = %worktree, %e, "failed to quarantine force-pushed attribution");
+    }
+}
+
+fn apply_clone_notes_sync_side_effect(worktree: &str) -> Result<(), GitAiError> {
     let repo = find_repository_in_path(worktree)?;
     let remote = "origin";
     let notes_backend = crate::config::Config::fresh().notes_backend_kind();
@@ -1185,12 +1338,7 @@ fn apply_clone_notes_sync_side_effect(worktree: &str) -> Result<(), GitAiError>
         "handling clone notes sync"
     );
 
-    if notes_backend == NotesBackendKind::Http {
-        return crate::git::notes_api::warm_cache_for_remote(&repo, remote);
-    }
-
-    fetch_authorship_notes(&repo, remote)?;
-    Ok(())
+    fetch_notes_under_watchdog(&repo, remote, notes_backend)
 }
 
 fn apply_pull_fast_forward_working_log_side_effect(
@@ -1203,6 +1351,66 @@ fn apply_pull_fast_forward_working_log_side_effect(
     Ok(())
 }
 
+/// Moves the pre-merge working log onto the commit produced by `git merge`.
+///
+/// For a merge commit, any parent count including octopus merges, checkpoints
+/// recorded against the pre-merge HEAD (e.g. conflict resolutions finished with
+/// `git merge --continue`) are committed into the merge commit's note and the
+/// remaining uncommitted attributions carry over to the new HEAD. Resolutions
+/// recorded by `git-ai checkpoint --conflict-resolution` are folded in even
+/// without a working log. Fast-forward merges only rename the working log.
+fn apply_merge_complete_working_log_side_effect(
+    worktree: &str,
+    old_head: &str,
+    new_head: &str,
+) -> Result<(), GitAiError> {
+    if !is_valid_oid(old_head) || !is_valid_oid(new_head) || old_head == new_head {
+        return Ok(());
+    }
+    let repo = find_repository_in_path(worktree)?;
+    let has_working_log = repo.storage.has_working_log(old_head);
+    if !has_working_log
+        && !repo
+            .storage
+            .read_conflict_resolutions()
+            .iter()
+            .any(|record| record.head == old_head)
+    {
+        return Ok(());
+    }
+
+    let parents: Vec<String> = repo
+        .find_commit(new_head.to_string())?
+        .parents()
+        .map(|parent| parent.id().to_string())
+        .collect();
+    if parents.len() > 1 {
+        if !parents.iter().any(|parent| parent == old_head) {
+            tracing::debug!(
+                old_head = %old_head,
+                new_head = %new_head,
+                parents = parents.len(),
+                "merge commit does not descend directly from pre-merge HEAD; leaving working log"
+            );
+            return Ok(());
+        }
+        let author = repo.effective_author_identity().formatted_or_unknown();
+        crate::authorship::post_commit::post_commit_from_working_log(
+            &repo,
+            Some(old_head.to_string()),
+            new_head.to_string(),
+            author,
+            true,
+        )?;
+    } else if has_working_log
+        && (parents.iter().any(|parent| parent == old_head)
+            || repo_is_ancestor(&repo, old_head, new_head))
+    {
+        repo.storage.rename_working_log(old_head, new_head)?;
+    }
+    Ok(())
+}
+
 fn remove_working_log_attributions_for_pathspecs(
     repository: &Repository,
     head: &str,
@@ -1261,8 +1469,95 @@ fn apply_checkout_switch_working_log_side_effect(
             }
             return Ok(());
         }
+        if cmd.exit_code == 0
+            && parsed
+                .command_args
+                .iter()
+                .any(|arg| arg == "--orphan" || arg.starts_with("--orphan="))
+        {
+            return carry_working_log_to_orphan_branch(&repo, worktree);
+        }
+    }
+
+    move_working_log_for_checkout_switch(&repo, cmd, &parsed, &old_head, &new_head)?;
+    fork_working_log_for_branch_switch(&repo, worktree, &old_head, &new_head)?;
+    track_detached_head(&repo, worktree, &old_head, &new_head)
+}
+
+/// `checkout --orphan` keeps the index and working tree but leaves HEAD on an
+/// unborn branch without writing a HEAD reflog entry, so no ref change reports
+/// the move. The newest HEAD reflog entry still names the commit that was left;
+/// its working log moves to `initial`, which checkpoints on the unborn branch
+/// use and its root commit consumes.
+fn carry_working_log_to_orphan_branch(
+    repo: &Repository,
+    worktree: &Path,
+) -> Result<(), GitAiError> {
+    let Some(state) = crate::git::repo_state::read_head_state_for_worktree(worktree) else {
+        return Ok(());
+    };
+    // The orphan branch already has a commit; the commit handled the log.
+    if state.head.is_some() {
+        return Ok(());
+    }
+    let Ok(reflog) = std::fs::read_to_string(repo.path().join("logs").join("HEAD")) else {
+        return Ok(());
+    };
+    let Some(left_head) = reflog
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .and_then(|line| line.split_whitespace().nth(1))
+        .filter(|oid| is_valid_oid(oid) && !is_zero_oid(oid))
+    else {
+        return Ok(());
+    };
+    repo.storage.rename_working_log(left_head, "initial")
+}
+
+/// Copy-on-branch for working logs.
+///
+/// Working logs are keyed by commit, so after `checkout -b` / `switch -c` from a
+/// dirty HEAD (or switching to another branch at the same commit) two branches
+/// share one log, and a commit on either consumes it. The branch that was left
+/// gets a fork of the log instead; when it is checked out again at the same
+/// commit, the fork is restored if the shared log was consumed meanwhile.
+fn fork_working_log_for_branch_switch(
+    repo: &Repository,
+    worktree: &Path,
+    old_head: &str,
+    new_head: &str,
+) -> Result<(), GitAiError> {
+    let Some(state) = crate::git::repo_state::read_head_state_for_worktree(worktree) else {
+        return Ok(());
+    };
+    let (Some(head), Some(branch)) = (state.head, state.branch) else {
+        return Ok(());
+    };
+    // HEAD has moved again since this command; the later command handles it.
+    if state.detached || (!new_head.is_empty() && head != new_head) {
+        return Ok(());
     }
 
+    if old_head == head
+        && let Some(left) = branch_left_by_last_checkout(repo)
+        && left != branch
+    {
+        repo.storage.fork_working_log(&head, &left)?;
+    }
+    if repo.storage.restore_working_log_fork(&branch, &head)? {
+        tracing::debug!(branch = %branch, head = %head, "restored forked working log");
+    }
+    Ok(())
+}
+
+fn move_working_log_for_checkout_switch(
+    repo: &Repository,
+    cmd: &crate::daemon::domain::NormalizedCommand,
+    parsed: &ParsedGitInvocation,
+    old_head: &str,
+    new_head: &str,
+) -> Result<(), GitAiError> {
     if old_head.is_empty() || new_head.is_empty() || old_head == new_head {
         return Ok(());
     }
@@ -1279,35 +1574,175 @@ fn apply_checkout_switch_working_log_side_effect(
     };
 
     if is_force {
-        repo.storage.delete_working_log_for_base_commit(&old_head)?;
+        repo.storage.delete_working_log_for_base_commit(old_head)?;
         return Ok(());
     }
 
     if is_merge {
         let final_state =
             crate::authorship::virtual_attribution::checkout_merge_final_state_snapshot(
-                &repo, &old_head, &new_head,
+                repo, old_head, new_head,
             )?;
         if final_state.is_empty() {
-            repo.storage.delete_working_log_for_base_commit(&old_head)?;
+            repo.storage.delete_working_log_for_base_commit(old_head)?;
             return Ok(());
         }
         let author = repo.effective_author_identity().formatted_or_unknown();
         crate::authorship::virtual_attribution::restore_working_log_carryover(
-            &repo,
-            &old_head,
-            &new_head,
+            repo,
+            old_head,
+            new_head,
             final_state,
             Some(author),
         )?;
-        repo.storage.delete_working_log_for_base_commit(&old_head)?;
+        repo.storage.delete_working_log_for_base_commit(old_head)?;
         return Ok(());
     }
 
-    repo.storage.rename_working_log(&old_head, &new_head)?;
+    repo.storage.rename_working_log(old_head, new_head)?;
     Ok(())
 }
 
+/// Keep attribution continuous across a detached-HEAD excursion.
+///
+/// Detaching records the branch that was left and its head; further detached
+/// checkouts advance the recorded detached commit. When a checkout or switch
+/// lands on a branch again (returning, or `switch -c` from the detached commit),
+/// a working log still keyed to the last detached commit is moved onto the new
+/// HEAD instead of being left orphaned, and the record is dropped.
+fn track_detached_head(
+    repo: &Repository,
+    worktree: &Path,
+    old_head: &str,
+    new_head: &str,
+) -> Result<(), GitAiError> {
+    let Some(state) = crate::git::repo_state::read_head_state_for_worktree(worktree) else {
+        return Ok(());
+    };
+    let Some(head) = state.head else {
+        return Ok(());
+    };
+    // HEAD has moved again since this command; the later command tracks it.
+    if !new_head.is_empty() && head != new_head {
+        return Ok(());
+    }
+
+    let record = repo.storage.read_detached_head();
+    if state.detached {
+        let record = match record {
+            Some(mut record) => {
+                record.detached_head = head;
+                record
+            }
+            None => crate::git::repo_storage::DetachedHeadRecord {
+                branch: branch_left_by_last_checkout(repo),
+                branch_head: if old_head.is_empty() || is_zero_oid(old_head) {
+                    head.clone()
+                } else {
+                    old_head.to_string()
+                },
+                detached_head: head,
+            },
+        };
+        tracing::debug!(
+            branch = ?record.branch,
+            detached_head = %record.detached_head,
+            "tracking detached HEAD"
+        );
+        return repo.storage.write_detached_head(&record);
+    }
+
+    let Some(record) = record else {
+        return Ok(());
+    };
+    // A log at `old_head` was already moved (or deliberately discarded) above.
+    if record.detached_head != head
+        && record.detached_head != old_head
+        && repo.storage.has_working_log(&record.detached_head)
+    {
+        tracing::debug!(
+            from = %record.detached_head,
+            to = %head,
+            "re-associating detached HEAD working log"
+        );
+        repo.storage
+            .rename_working_log(&record.detached_head, &head)?;
+    }
+    repo.storage.clear_detached_head()
+}
+
+/// Working-log side effect of force-moving a branch that is not checked out
+/// (`git branch -f <name> <commit>`).
+///
+/// Working logs are keyed by commit, so the move leaves the old tip's log
+/// behind. While HEAD or another branch still sits on the old tip the log stays
+/// put. Otherwise it follows the branch on a fast-forward, as for `update-ref`,
+/// and is archived as `old-<sha>` in every other case, including a move onto
+/// HEAD's commit, whose live log it must not be merged into.
+fn apply_branch_force_working_log_side_effect(
+    repo: &Repository,
+    reference: &str,
+    old: &str,
+    new: &str,
+) -> Result<(), GitAiError> {
+    if !repo.storage.has_working_log(old) {
+        return Ok(());
+    }
+    let head = repo.head().ok().and_then(|head| head.target().ok());
+    if head.as_deref() == Some(old) || other_branch_points_at(repo, reference, old) {
+        return Ok(());
+    }
+
+    if head.as_deref() != Some(new) && repo_is_ancestor(repo, old, new) {
+        tracing::debug!(
+            reference,
+            old,
+            new,
+            "re-associating working log after branch -f"
+        );
+        repo.storage.rename_working_log(old, new)
+    } else {
+        tracing::debug!(reference, old, new, "archiving working log after branch -f");
+        repo.storage.delete_working_log_for_base_commit(old)
+    }
+}
+
+/// Whether a local branch other than `reference` points at `oid`. Assumes one
+/// does when git cannot tell, so the caller leaves the working log alone.
+fn other_branch_points_at(repo: &Repository, reference: &str, oid: &str) -> bool {
+    let mut args = repo.global_args_for_exec();
+    args.push("for-each-ref".to_string());
+    args.push("--points-at".to_string());
+    args.push(oid.to_string());
+    args.push("--format=%(refname)".to_string());
+    args.push("refs/heads/".to_string());
+    let Ok(output) = exec_git(&args) else {
+        return true;
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .any(|name| !name.is_empty() && name != reference)
+}
+
+/// Branch named by the newest HEAD reflog entry (`checkout: moving from <branch>
+/// to <target>`), when the previous HEAD was a branch rather than a commit.
+fn branch_left_by_last_checkout(repo: &Repository) -> Option<String> {
+    let mut args = repo.global_args_for_exec();
+    args.extend(
+        ["log", "-g", "-1", "--format=%gs", "HEAD"]
+            .iter()
+            .map(|arg| arg.to_string()),
+    );
+    let output = exec_git(&args).ok()?;
+    let subject = String::from_utf8_lossy(&output.stdout);
+    let (from, _) = subject
+        .trim()
+        .strip_prefix("checkout: moving from ")?
+        .split_once(" to ")?;
+    (!from.is_empty() && !from.chars().all(|c| c.is_ascii_hexdigit())).then(|| from.to_string())
+}
+
 fn recent_checkout_switch_prerequisite_from_command(
     cmd: &crate::daemon::domain::NormalizedCommand,
 ) -> Option<RecentReplayPrerequisite> {
@@ -1559,6 +1994,38 @@ fn first_head_transition_old(cmd: &crate::daemon::domain::NormalizedCommand) ->
         .map(|change| change.old.clone())
 }
 
+/// Whether `new_commits` were picked from `sources`, pairwise in order.
+/// Cherry-pick keeps each source's author and author date, so a pairing that
+/// disagrees on them belongs to some other sequence.
+fn cherry_pick_sources_match_commits(
+    repo: &Repository,
+    sources: &[String],
+    new_commits: &[String],
+) -> bool {
+    let pairs = sources.len().min(new_commits.len());
+    if pairs == 0 {
+        return true;
+    }
+    let mut log_args = vec!["--no-walk=unsorted".to_string()];
+    log_args.extend(sources[..pairs].iter().cloned());
+    log_args.extend(new_commits[..pairs].iter().cloned());
+    let Ok(records) =
+        crate::git::log_records::log_records(repo, &["%H", "%an", "%ae", "%at"], &log_args)
+    else {
+        return false;
+    };
+    let authors: HashMap<&str, &[String]> = records
+        .iter()
+        .map(|fields| (fields[0].as_str(), &fields[1..]))
+        .collect();
+    sources.iter().zip(new_commits).all(|(source, picked)| {
+        match (authors.get(source.as_str()), authors.get(picked.as_str())) {
+            (Some(source), Some(picked)) => source == picked,
+            _ => false,
+        }
+    })
+}
+
 fn cherry_pick_original_head(cmd: &crate::daemon::domain::NormalizedCommand) -> Option<String> {
     first_head_transition_old(cmd)
 }
@@ -1857,6 +2324,18 @@ fn resolve_explicit_revert_sources_for_side_effect(
     )
 }
 
+/// Full source OIDs of the picks still pending in a stopped cherry-pick sequence.
+fn cherry_pick_sequencer_sources_for_worktree(
+    worktree: &Path,
+) -> Result<Option<Vec<String>>, GitAiError> {
+    let Some(todo) = cherry_pick_sequencer_todo_for_worktree(worktree) else {
+        return Ok(None);
+    };
+    let repo = find_repository_in_path(&worktree.to_string_lossy())?;
+    let sources = resolve_cherry_pick_source_args_with_git_in_head_context(&repo, &todo, None)?;
+    Ok((sources.len() == todo.len()).then_some(sources))
+}
+
 fn cherry_pick_state_exists_for_worktree(worktree: &Path) -> bool {
     git_dir_for_worktree(worktree).is_some_and(|git_dir| {
         git_dir.join("CHERRY_PICK_HEAD").exists() || git_dir.join("sequencer").join("todo").exists()
@@ -1901,6 +2380,23 @@ fn apply_revert_complete_rewrite(
     Ok(())
 }
 
+fn apply_subtree_rewrite(
+    repo: &crate::git::repository::Repository,
+    cmd: &crate::daemon::domain::NormalizedCommand,
+    invocation: &crate::authorship::rewrite_subtree::SubtreeInvocation,
+) -> Result<(), GitAiError> {
+    let head_sha = match cmd
+        .ref_changes
+        .iter()
+        .rev()
+        .find(|change| change.reference == "HEAD" && is_valid_oid(&change.new))
+    {
+        Some(change) => change.new.clone(),
+        None => repo.head()?.target()?,
+    };
+    crate::authorship::rewrite_subtree::handle_subtree_command(repo, invocation, &head_sha)
+}
+
 fn apply_cherry_pick_complete_rewrite(
     repo: &crate::git::repository::Repository,
     original_head: &str,
@@ -2531,6 +3027,10 @@ pub struct ActorDaemonCoordinator {
     recent_replay_prerequisites_by_family:
         Mutex<HashMap<String, VecDeque<RecentReplayPrerequisite>>>,
     side_effect_errors_by_family: Mutex<HashMap<String, BTreeMap<u64, String>>>,
+    /// Recently applied rewrite transitions per worktree, so a rewrite observed twice
+    /// (e.g. double-dispatched amends) never shifts notes a second time.
+    applied_rewrites_by_worktree:
+        Mutex<HashMap<String, crate::authorship::rewrite::AppliedRewriteKeys>>,
     side_effect_exec_locks: Mutex<HashMap<String, Arc<AsyncMutex<()>>>>,
     bash_sessions: Mutex<crate::daemon::bash_sessions::BashSessionState>,
     test_completion_log_dir: Option<PathBuf>,
@@ -2615,6 +3115,7 @@ impl ActorDaemonCoordinator {
             commit_file_timestamp_snapshots_by_root: Mutex::new(HashMap::new()),
             recent_replay_prerequisites_by_family: Mutex::new(HashMap::new()),
             side_effect_errors_by_family: Mutex::new(HashMap::new()),
+            applied_rewrites_by_worktree: Mutex::new(HashMap::new()),
             side_effect_exec_locks: Mutex::new(HashMap::new()),
             bash_sessions: Mutex::new(crate::daemon::bash_sessions::BashSessionState::new()),
             test_completion_log_dir: std::env::var("GIT_AI_TEST_DB_PATH")
@@ -2921,6 +3422,9 @@ impl ActorDaemonCoordinator {
         if let Ok(mut map) = self.side_effect_errors_by_family.lock() {
             map.retain(|_, errors| !errors.is_empty());
         }
+        if let Ok(mut map) = self.applied_rewrites_by_worktree.lock() {
+            map.retain(|_, applied| !applied.is_empty());
+        }
         if let Ok(mut map) = self.family_sequencers_by_family.lock() {
             map.retain(|_, state| !state.entries.is_empty());
         }
@@ -3271,6 +3775,16 @@ impl ActorDaemonCoordinator {
         seq: u64,
         error: &GitAiError,
     ) -> Result<(), GitAiError> {
+        crate::observability::errors::record_error(
+            error,
+            Some(&serde_json::json!({
+                "component": "daemon",
+                "phase": "command_side_effect",
+                "family": family,
+                "seq": seq,
+            })),
+            false,
+        );
         let mut map = self
             .side_effect_errors_by_family
             .lock()
@@ -4531,16 +5045,34 @@ impl ActorDaemonCoordinator {
         &self,
         worktree: &Path,
     ) -> Result<Vec<String>, GitAiError> {
-        let map = self
+        let mut map = self
             .pending_cherry_pick_sources_by_worktree
             .lock()
             .map_err(|_| {
                 GitAiError::Generic("pending cherry-pick sources map lock poisoned".to_string())
             })?;
-        Ok(map
-            .get(&Self::worktree_state_key(worktree))
-            .cloned()
-            .unwrap_or_default())
+        let key = Self::worktree_state_key(worktree);
+        let Some(sources) = map.get(&key).cloned() else {
+            return Ok(Vec::new());
+        };
+        // The resume failed, so git's sequencer state is still on disk: the
+        // sources only apply while it stops inside them. State left behind by an
+        // aborted or replaced sequence is discarded rather than applied to the
+        // commits of a later one.
+        let stop = cherry_pick_stop_for_worktree(worktree);
+        if stop
+            .as_ref()
+            .is_some_and(|stop| stop.matches_sources(&sources))
+        {
+            return Ok(sources);
+        }
+        tracing::debug!(
+            cherry_pick_head = ?stop.map(|stop| stop.head),
+            pending_sources = sources.len(),
+            "discarding pending cherry-pick sources that do not match the sequencer state"
+        );
+        map.remove(&key);
+        Ok(Vec::new())
     }
 
     fn set_pending_cherry_pick_no_commit_for_worktree(
@@ -4584,6 +5116,46 @@ impl ActorDaemonCoordinator {
         Ok(())
     }
 
+    /// Records a rewrite transition for `worktree` and returns true the first time
+    /// it is seen. Callers skip rewrite handling when this returns false, and
+    /// call [`Self::release_rewrite_claim`] when handling fails so a replay of
+    /// the same event can retry it.
+    fn claim_rewrite_once(
+        &self,
+        worktree: &Path,
+        key: crate::authorship::rewrite::RewriteIdempotencyKey,
+    ) -> Result<bool, GitAiError> {
+        let mut map = self
+            .applied_rewrites_by_worktree
+            .lock()
+            .map_err(|_| GitAiError::Generic("applied rewrites map lock poisoned".to_string()))?;
+        let claimed = map
+            .entry(Self::worktree_state_key(worktree))
+            .or_default()
+            .insert(key.clone());
+        if !claimed {
+            tracing::debug!(
+                old_sha = %key.old_sha,
+                new_sha = %key.new_sha,
+                operation = key.operation.as_str(),
+                "skipping duplicate rewrite event"
+            );
+        }
+        Ok(claimed)
+    }
+
+    fn release_rewrite_claim(
+        &self,
+        worktree: &Path,
+        key: &crate::authorship::rewrite::RewriteIdempotencyKey,
+    ) {
+        if let Ok(mut map) = self.applied_rewrites_by_worktree.lock()
+            && let Some(keys) = map.get_mut(&Self::worktree_state_key(worktree))
+        {
+            keys.remove(key);
+        }
+    }
+
     fn take_pending_cherry_pick_no_commit_for_worktree(
         &self,
         worktree: &Path,
@@ -4842,31 +5414,40 @@ impl ActorDaemonCoordinator {
             } else {
                 onto_hint.clone()
             };
-            let outcome = if is_rebase_cmd {
-                crate::authorship::rewrite::handle_non_fast_forward_rewrite_with_operation(
-                    &repo,
-                    old_tip,
-                    new_tip,
-                    rewrite_onto.as_deref(),
-                    crate::authorship::rewrite::RewriteMetricOperation::Rebase,
-                )?
+            let operation = if is_rebase_cmd {
+                crate::authorship::rewrite::RewriteMetricOperation::Rebase
             } else if cmd.primary_command.as_deref() == Some("update-ref") {
-                crate::authorship::rewrite::handle_non_fast_forward_rewrite_with_operation(
-                    &repo,
-                    old_tip,
-                    new_tip,
-                    rewrite_onto.as_deref(),
-                    crate::authorship::rewrite::RewriteMetricOperation::UpdateRef,
-                )?
+                crate::authorship::rewrite::RewriteMetricOperation::UpdateRef
             } else {
+                crate::authorship::rewrite::RewriteMetricOperation::NonFastForward
+            };
+            let rewrite_key = crate::authorship::rewrite::RewriteIdempotencyKey::new(
+                *old_tip, *new_tip, operation,
+            );
+            if !self.claim_rewrite_once(worktree, rewrite_key.clone())? {
+                continue;
+            }
+            let outcome =
                 crate::authorship::rewrite::handle_non_fast_forward_rewrite_with_operation(
                     &repo,
                     old_tip,
                     new_tip,
                     rewrite_onto.as_deref(),
-                    crate::authorship::rewrite::RewriteMetricOperation::NonFastForward,
-                )?
-            };
+                    operation,
+                )
+                .inspect_err(|_| self.release_rewrite_claim(worktree, &rewrite_key))?;
+            if is_rebase_cmd
+                && rebase_has_exec(&parsed_invocation_for_normalized_command(cmd).command_args)
+            {
+                let workspace = crate::workspace_config::effective_workspace_config(&repo);
+                if let Err(e) = crate::authorship::rewrite_exec::attribute_rebase_exec_changes(
+                    &repo,
+                    &outcome.mappings,
+                    workspace.rebase_exec_tool(),
+                ) {
+                    tracing::warn!(%e, "failed to attribute rebase --exec changes");
+                }
+            }
             repo.storage.rename_working_log(old_tip, new_tip)?;
             let metric_context = if is_rebase_cmd {
                 let conflict_base = rewrite_onto.clone().or_else(|| onto_hint.clone());
@@ -5123,6 +5704,18 @@ impl ActorDaemonCoordinator {
         if !skip_non_ff && cmd.exit_code == 0 {
             self.detect_and_handle_non_ff_rewrites(cmd)?;
         }
+        if cmd.exit_code == 0
+            && let Some(worktree) = cmd.worktree.as_ref()
+        {
+            let parsed = parsed_invocation_for_normalized_command(cmd);
+            if let Some(invocation) = crate::authorship::rewrite_subtree::SubtreeInvocation::parse(
+                parsed.command.as_deref(),
+                &parsed.command_args,
+            ) {
+                let repo = find_repository_in_path(&worktree.to_string_lossy())?;
+                apply_subtree_rewrite(&repo, cmd, &invocation)?;
+            }
+        }
 
         if cmd.exit_code != 0 {
             let rebase_start = cmd
@@ -5189,6 +5782,11 @@ impl ActorDaemonCoordinator {
                 if cmd.invoked_args.iter().any(|arg| arg == "--abort") {
                     self.clear_pending_cherry_pick_sources_for_worktree(worktree)?;
                     self.clear_pending_cherry_pick_no_commit_for_worktree(worktree)?;
+                } else if cmd.exit_code >= 128 && cherry_pick_stop_for_worktree(worktree).is_none()
+                {
+                    // Git rejected the command (bad arguments or revisions) without
+                    // stopping anywhere, so a later resume has nothing to pick up.
+                    self.clear_pending_cherry_pick_sources_for_worktree(worktree)?;
                 } else if cmd.exit_code != 0 {
                     let new_commits = cherry_pick_destination_commits(cmd);
                     let is_continue = cherry_pick_command_has_flag(cmd, "--continue");
@@ -5228,7 +5826,12 @@ impl ActorDaemonCoordinator {
                             &new_commits,
                         )?;
                     }
-                    if !source_oids.is_empty() || is_continue || is_skip {
+                    // When git stopped inside a multi-commit sequence, its todo is
+                    // authoritative for what `--continue` will apply next, even if
+                    // the sources could not be aligned with the picks made so far.
+                    if let Some(remaining) = cherry_pick_sequencer_sources_for_worktree(worktree)? {
+                        self.set_pending_cherry_pick_sources_for_worktree(worktree, remaining)?;
+                    } else if !source_oids.is_empty() || is_continue || is_skip {
                         let applied_sources = new_commits
                             .len()
                             .min(source_oids.len().saturating_sub(skipped_sources));
@@ -5296,12 +5899,23 @@ impl ActorDaemonCoordinator {
                     crate::daemon::domain::SemanticEvent::CloneCompleted { .. } => {
                         apply_clone_notes_sync_side_effect(&worktree)?;
                     }
+                    crate::daemon::domain::SemanticEvent::FetchCompleted { .. } => {
+                        apply_fetch_quarantine_side_effect(&worktree);
+                        if let Err(e) = apply_fetch_notes_sync_side_effect(
+                            &worktree,
+                            cmd.invoked_command.as_deref(),
+                            &cmd.invoked_args,
+                        ) {
+                            tracing::warn!(worktree = %worktree, %e, "failed to fetch notes after fetch");
+                        }
+                    }
                     crate::daemon::domain::SemanticEvent::PullCompleted { .. } => {
                         apply_pull_notes_sync_side_effect(
                             &worktree,
                             cmd.invoked_command.as_deref(),
                             &cmd.invoked_args,
                         )?;
+                        apply_fetch_quarantine_side_effect(&worktree);
                     }
                     crate::daemon::domain::SemanticEvent::PushCompleted { .. } => {
                         apply_push_side_effect(
@@ -5319,9 +5933,43 @@ impl ActorDaemonCoordinator {
                         if !new_head.is_empty() {
                             let repo = find_repository_in_path(&worktree)?;
                             let mut sources = source_commits.clone();
+                            let is_continue = cherry_pick_command_has_flag(cmd, "--continue");
                             let is_skip = cherry_pick_command_has_flag(cmd, "--skip");
                             let explicit_source_args = cherry_pick_source_args_for_side_effect(cmd);
-                            if !sources.is_empty() {
+                            // Sources recorded when the sequence stopped come from
+                            // git's own todo, so they take precedence on resume.
+                            let resumed_sources = if is_continue || is_skip {
+                                self.take_pending_cherry_pick_sources_for_worktree(
+                                    worktree.as_ref(),
+                                )?
+                            } else {
+                                Vec::new()
+                            };
+                            let destinations = if new_commits.is_empty() {
+                                vec![new_head.clone()]
+                            } else {
+                                new_commits.clone()
+                            };
+                            if !resumed_sources.is_empty() {
+                                sources = resumed_sources;
+                                if is_skip {
+                                    sources.remove(0);
+                                }
+                                // The sequencer files are gone once the resume
+                                // completes; check the sources against the
+                                // commits git actually wrote instead.
+                                if !cherry_pick_sources_match_commits(
+                                    &repo,
+                                    &sources,
+                                    &destinations,
+                                ) {
+                                    tracing::debug!(
+                                        pending_sources = sources.len(),
+                                        "discarding pending cherry-pick sources that do not match the picked commits"
+                                    );
+                                    sources.clear();
+                                }
+                            } else if !sources.is_empty() {
                                 self.clear_pending_cherry_pick_sources_for_worktree(
                                     worktree.as_ref(),
                                 )?;
@@ -5340,15 +5988,7 @@ impl ActorDaemonCoordinator {
                                 sources = self.take_pending_cherry_pick_sources_for_worktree(
                                     worktree.as_ref(),
                                 )?;
-                                if is_skip && !sources.is_empty() {
-                                    sources.remove(0);
-                                }
                             }
-                            let destinations = if new_commits.is_empty() {
-                                vec![new_head.clone()]
-                            } else {
-                                new_commits.clone()
-                            };
                             if original_head != new_head {
                                 if original_head.is_empty() {
                                     return Err(GitAiError::Generic(format!(
@@ -5383,6 +6023,11 @@ impl ActorDaemonCoordinator {
                             )?;
                         }
                     }
+                    crate::daemon::domain::SemanticEvent::MergeComplete { old_head, new_head } => {
+                        apply_merge_complete_working_log_side_effect(
+                            &worktree, old_head, new_head,
+                        )?;
+                    }
                     crate::daemon::domain::SemanticEvent::MergeSquash { source_head, onto } => {
                         self.set_pending_squash_merge_for_worktree(
                             worktree.as_ref(),
@@ -5426,27 +6071,46 @@ impl ActorDaemonCoordinator {
                                     )?;
                                 }
                             }
-                            crate::daemon::domain::StashOpKind::Apply
-                            | crate::daemon::domain::StashOpKind::Branch => {
+                            crate::daemon::domain::StashOpKind::Apply => {
                                 if let Some(stash_sha) = resolve_stash_sha(cmd) {
-                                    let effective_head = if matches!(
-                                        kind,
-                                        crate::daemon::domain::StashOpKind::Branch
-                                    ) {
-                                        stash_base_head(&repo, stash_sha)
-                                    } else {
-                                        None
-                                    };
                                     let base_head = stash_base_head(&repo, stash_sha);
-                                    let target_head = effective_head
-                                        .as_deref()
-                                        .or(head.as_deref())
-                                        .or(base_head.as_deref());
+                                    let target_head = head.as_deref().or(base_head.as_deref());
                                     crate::authorship::rewrite_stash::handle_stash_pop_or_apply_with_head(
                                         &repo, stash_sha, false, target_head,
                                     )?;
                                 }
                             }
+                            crate::daemon::domain::StashOpKind::Branch => {
+                                // `stash branch` checks out a new branch at the stash's
+                                // base commit, applies the stash there and drops it if
+                                // it applied cleanly. Changes the checkout carried keep
+                                // their attribution, and the stash's attribution goes to
+                                // the new branch's working log, not the original HEAD's.
+                                if let Some(stash_sha) = resolve_stash_sha(cmd) {
+                                    let base_head = stash_base_head(&repo, stash_sha);
+                                    let head_change = cmd
+                                        .ref_changes
+                                        .iter()
+                                        .find(|change| change.reference == "HEAD");
+                                    let target_head = head_change
+                                        .map(|change| change.new.as_str())
+                                        .filter(|new| !new.is_empty())
+                                        .or(base_head.as_deref());
+                                    if let (Some(change), Some(target)) = (head_change, target_head)
+                                        && !change.old.is_empty()
+                                        && !is_zero_oid(&change.old)
+                                        && change.old != target
+                                    {
+                                        repo.storage.rename_working_log(&change.old, target)?;
+                                    }
+                                    crate::authorship::rewrite_stash::handle_stash_pop_or_apply_with_head(
+                                        &repo,
+                                        stash_sha,
+                                        cmd.exit_code == 0,
+                                        target_head,
+                                    )?;
+                                }
+                            }
                             crate::daemon::domain::StashOpKind::Drop => {
                                 if let Some(stash_sha) = resolve_stash_sha(cmd) {
                                     crate::authorship::rewrite_stash::handle_stash_drop(
@@ -5544,6 +6208,11 @@ impl ActorDaemonCoordinator {
                                 );
                             };
 
+                            // A repository over its hook latency budget skips recovery and
+                            // stats here and leaves the recovery to `git-ai repair`.
+                            let degraded = crate::daemon::hook_budget::degradation(&repo).is_some();
+                            let started = std::time::Instant::now();
+
                             // Post-commit note generation does synchronous git/filesystem work
                             // and may briefly wait for transcript recovery. Mark it as blocking
                             // so the transcript worker can process the recovery sweep promptly.
@@ -5553,12 +6222,26 @@ impl ActorDaemonCoordinator {
                                     base_opt.clone(),
                                     new_head.clone(),
                                     author,
-                                    true,
+                                    crate::authorship::post_commit::PostCommitOptions {
+                                        supress_output: true,
+                                        compute_stats: !degraded,
+                                        recover_attribution: !degraded,
+                                    },
                                     recovery_file_timestamps.as_ref(),
                                     Some(&recovery_preflight),
                                 )
                             })?;
 
+                            if degraded {
+                                crate::daemon::hook_budget::defer_recovery(&repo, new_head)?;
+                            } else {
+                                crate::daemon::hook_budget::record_hook_latency(
+                                    &repo,
+                                    "post_commit",
+                                    started.elapsed(),
+                                );
+                            }
+
                             if cmd.primary_command.as_deref() == Some("commit")
                                 && let Some(pending) = self
                                     .take_pending_cherry_pick_no_commit_for_worktree(
@@ -5583,6 +6266,11 @@ impl ActorDaemonCoordinator {
                         }
                     }
                     crate::daemon::domain::SemanticEvent::CommitAmended { old_head, new_head } => {
+                        let amend_key = crate::authorship::rewrite::RewriteIdempotencyKey::new(
+                            old_head.as_str(),
+                            new_head.as_str(),
+                            crate::authorship::rewrite::RewriteMetricOperation::Amend,
+                        );
                         if !old_head.is_empty()
                             && !new_head.is_empty()
                             && old_head != new_head
@@ -5590,8 +6278,13 @@ impl ActorDaemonCoordinator {
                             && !is_zero_oid(old_head)
                             && is_valid_oid(new_head)
                             && !is_zero_oid(new_head)
+                            && self.claim_rewrite_once(Path::new(&worktree), amend_key.clone())?
                         {
-                            let repo = find_repository_in_path(&worktree)?;
+                            let release_claim = |_: &GitAiError| {
+                                self.release_rewrite_claim(Path::new(&worktree), &amend_key)
+                            };
+                            let repo =
+                                find_repository_in_path(&worktree).inspect_err(release_claim)?;
                             let author = repo.effective_author_identity().formatted_or_unknown();
                             let recovery_file_timestamps = Self::take_commit_file_timestamps(
                                 commit_file_timestamp_snapshots,
@@ -5618,7 +6311,8 @@ impl ActorDaemonCoordinator {
                                     recovery_file_timestamps.as_ref(),
                                     Some(&recovery_preflight),
                                 )
-                            })?;
+                            })
+                            .inspect_err(release_claim)?;
                             if crate::authorship::rewrite::rewrite_metrics_enabled() {
                                 crate::daemon::rewrite_metrics::spawn_rewrite_commit_metrics(
                                     &repo,
@@ -5646,11 +6340,22 @@ impl ActorDaemonCoordinator {
                                 repo.storage.delete_working_log_for_base_commit(old_head)?;
                             }
                             _ => {
+                                let reset_key =
+                                    crate::authorship::rewrite::RewriteIdempotencyKey::new(
+                                        old_head.as_str(),
+                                        new_head.as_str(),
+                                        crate::authorship::rewrite::RewriteMetricOperation::NonFastForward,
+                                    );
                                 if is_ancestor_commit(&repo, new_head, old_head) {
                                     crate::authorship::rewrite_reset::reconstruct_working_log_after_backward_reset(
                                         &repo, old_head, new_head,
                                     )?;
-                                } else if !is_ancestor_commit(&repo, old_head, new_head) {
+                                } else if !is_ancestor_commit(&repo, old_head, new_head)
+                                    && self.claim_rewrite_once(
+                                        Path::new(&worktree),
+                                        reset_key.clone(),
+                                    )?
+                                {
                                     let outcome =
                                         crate::authorship::rewrite::handle_rewrite_event_with_metrics(
                                         &repo,
@@ -5659,7 +6364,10 @@ impl ActorDaemonCoordinator {
                                             new_tip: new_head.to_string(),
                                             onto: None,
                                         },
-                                    )?;
+                                    )
+                                    .inspect_err(|_| {
+                                        self.release_rewrite_claim(Path::new(&worktree), &reset_key)
+                                    })?;
                                     crate::daemon::rewrite_metrics::spawn_rewrite_commit_metrics(
                                         &repo,
                                         outcome.metric_commits,
@@ -5760,6 +6468,34 @@ impl ActorDaemonCoordinator {
             }
         }
 
+        // Handle `git branch -f`: the force-moved branch's old tip may still own
+        // a working log.
+        if primary == "branch"
+            && cmd.exit_code == 0
+            && let Some(worktree) = cmd.worktree.as_ref()
+        {
+            for event in events {
+                if let crate::daemon::domain::SemanticEvent::RefUpdated {
+                    reference,
+                    old,
+                    new,
+                } = event
+                {
+                    if !reference.starts_with("refs/heads/")
+                        || !is_valid_oid(old)
+                        || is_zero_oid(old)
+                        || !is_valid_oid(new)
+                        || is_zero_oid(new)
+                        || old == new
+                    {
+                        continue;
+                    }
+                    let repo = find_repository_in_path(&worktree.to_string_lossy())?;
+                    apply_branch_force_working_log_side_effect(&repo, reference, old, new)?;
+                }
+            }
+        }
+
         let parsed_invocation = parsed_invocation_for_normalized_command(cmd);
         for trigger in transcript_sweep_triggers_for_events(events) {
             if trigger == crate::daemon::stream_worker::SweepTrigger::PostPush
@@ -5964,6 +6700,36 @@ impl ActorDaemonCoordinator {
         })
     }
 
+    /// Status of every checkout under `workspace_roots`. A checkout whose
+    /// status cannot be read is reported with its error instead.
+    async fn status_for_workspace(&self) -> WorkspaceStatus {
+        let roots = crate::daemon::workspace::workspace_roots();
+        let checkouts = crate::daemon::workspace::discover_repositories(
+            &roots,
+            crate::daemon::workspace::MAX_DISCOVERY_DEPTH,
+        );
+        let mut repositories = Vec::with_capacity(checkouts.len());
+        for checkout in checkouts {
+            let repo_working_dir = checkout.to_string_lossy().into_owned();
+            let (status, error) = match self.status_for_family(repo_working_dir.clone()).await {
+                Ok(status) => (Some(status), None),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            repositories.push(WorkspaceRepoStatus {
+                repo_working_dir,
+                status,
+                error,
+            });
+        }
+        WorkspaceStatus {
+            roots: roots
+                .iter()
+                .map(|root| root.to_string_lossy().into_owned())
+                .collect(),
+            repositories,
+        }
+    }
+
     async fn sync_family(&self, repo_working_dir: String) -> Result<FamilyStatus, GitAiError> {
         let family = self.backend.resolve_family(Path::new(&repo_working_dir))?;
         self.wait_for_trace_ingest_processed_through().await;
@@ -6181,6 +6947,11 @@ impl ActorDaemonCoordinator {
                         .map(|v| ControlResponse::ok(None, Some(v)))
                         .map_err(GitAiError::from)
                 }),
+            ControlRequest::StatusWorkspace => {
+                serde_json::to_value(self.status_for_workspace().await)
+                    .map(|v| ControlResponse::ok(None, Some(v)))
+                    .map_err(GitAiError::from)
+            }
             ControlRequest::SnapshotWatermarks { repo_working_dir } => self
                 .watermarks_for_family(repo_working_dir.clone())
                 .await
@@ -6214,6 +6985,11 @@ impl ActorDaemonCoordinator {
                 });
                 Ok(ControlResponse::ok(None, None))
             }
+            ControlRequest::ProfileSnapshot => {
+                serde_json::to_value(crate::observability::profile::process_snapshot())
+                    .map(|v| ControlResponse::ok(None, Some(v)))
+                    .map_err(GitAiError::from)
+            }
             ControlRequest::Await { timeout_secs } => {
                 let result = self.await_completion(timeout_secs).await;
                 serde_json::to_value(result)
@@ -6454,6 +7230,17 @@ impl ActorDaemonCoordinator {
                 };
                 Ok(response)
             }
+            ControlRequest::BufferAttribution {
+                repo_working_dir,
+                file_path,
+                content,
+            } => tokio::task::spawn_blocking(move || {
+                buffer_attribution(&repo_working_dir, &file_path, content)
+            })
+            .await
+            .map_err(|e| GitAiError::Generic(format!("buffer attribution task failed: {}", e)))
+            .and_then(|result| result)
+            .map(|data| ControlResponse::ok(None, Some(data))),
             ControlRequest::Shutdown => Ok(ControlResponse::ok(None, None)),
         };
 
@@ -7270,7 +8057,8 @@ fn daemon_socket_health_check_loop(
     }
 }
 
-/// Background loop that periodically checks for available updates.
+/// Background loop that periodically checks for available updates and refreshes
+/// the managed (MDM) repository policy and remote feature flags.
 ///
 /// Sleeps in short increments so it can exit promptly when the coordinator
 /// signals shutdown.  When an update is detected, it requests a graceful
@@ -7300,6 +8088,24 @@ fn daemon_update_check_loop(coordinator: Arc<ActorDaemonCoordinator>, started_at
 
         coordinator.gc_stale_family_state();
 
+        match crate::mdm::policy::refresh_managed_policy_if_due() {
+            Ok(outcome) => {
+                tracing::debug!(?outcome, "managed policy refresh");
+            }
+            Err(err) => {
+                tracing::warn!(%err, "managed policy refresh failed");
+            }
+        }
+
+        match crate::feature_flags::remote::refresh_remote_feature_flags_if_due() {
+            Ok(outcome) => {
+                tracing::debug!(?outcome, "remote feature flags refresh");
+            }
+            Err(err) => {
+                tracing::warn!(%err, "remote feature flags refresh failed");
+            }
+        }
+
         match check_for_update_available() {
             Ok(DaemonUpdateCheckResult::UpdateReady) => {
                 tracing::info!("update check: newer version available, requesting shutdown");
@@ -7362,7 +8168,7 @@ pub(crate) async fn run_daemon(config: DaemonConfig) -> Result<DaemonExitAction,
     // Initialize tracing subscriber before log file redirect so the fmt layer
     // captures stderr (fd 2). After dup2, writes go to the daemon log file.
     {
-        use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
+        use tracing_subscriber::{EnvFilter, Layer, layer::SubscriberExt, util::SubscriberInitExt};
 
         let env_filter = if std::env::var("GIT_AI_DEBUG").as_deref() == Ok("1") {
             EnvFilter::new("debug")
@@ -7370,22 +8176,33 @@ pub(crate) async fn run_daemon(config: DaemonConfig) -> Result<DaemonExitAction,
             EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"))
         };
 
+        // The log filter applies to the logging layers only: the profile layer
+        // needs the debug-level phase spans for `git-ai profile`.
+        let logging = tracing_subscriber::fmt::layer()
+            .with_target(false)
+            .with_thread_ids(false)
+            .with_ansi(false)
+            .with_writer(std::io::stderr)
+            .and_then(crate::daemon::sentry_layer::SentryLayer)
+            .and_then(crate::daemon::daemon_log_layer::DaemonLogUploadLayer)
+            .and_then(crate::observability::trace::json_file_layer())
+            .with_filter(env_filter);
+
         tracing_subscriber::registry()
-            .with(env_filter)
-            .with(
-                tracing_subscriber::fmt::layer()
-                    .with_target(false)
-                    .with_thread_ids(false)
-                    .with_ansi(false)
-                    .with_writer(std::io::stderr),
-            )
-            .with(crate::daemon::sentry_layer::SentryLayer)
-            .with(crate::daemon::daemon_log_layer::DaemonLogUploadLayer)
+            .with(logging)
+            .with(crate::observability::profile::process_profile_layer())
             .init();
     }
 
     let _log_guard = maybe_setup_daemon_log_file(&config);
 
+    // The daemon restarts after every upgrade; keep hooks that embed the
+    // `current` link pointed at this binary. Test daemons leave it alone.
+    #[cfg(not(windows))]
+    if !daemon_is_test_mode() {
+        crate::mdm::hook_binary::refresh_current_link();
+    }
+
     tracing::info!(
         pid = std::process::id(),
         version = env!("CARGO_PKG_VERSION"),
@@ -7571,6 +8388,8 @@ fn checkpoint_control_response_timeout(
         }
         ControlRequest::SyncFamily { .. } => DAEMON_CHECKPOINT_RESPONSE_TIMEOUT,
         ControlRequest::SnapshotWatermarks { .. } => Duration::from_millis(500),
+        // One family status per checkout under the workspace roots.
+        ControlRequest::StatusWorkspace => Duration::from_secs(30),
         // Await blocks until the requested timeout is reached; give the daemon
         // a small grace period over the requested limit so the caller sees a
         // response rather than a client-side socket timeout.
@@ -7992,6 +8811,7 @@ mod tests {
                     content: None,
                     repo_work_dir: std::path::PathBuf::from("/tmp/repo"),
                     base_commit: BaseCommit::Initial,
+                    hunks: None,
                 }],
                 path_role: PreparedPathRole::WillEdit,
                 stream_source: None,
@@ -8782,6 +9602,7 @@ mod tests {
                 content: Some("checkpoint content\n".to_string()),
                 repo_work_dir: repo.clone(),
                 base_commit: BaseCommit::Initial,
+                hunks: None,
             }],
             path_role: PreparedPathRole::Edited,
             stream_source: None,
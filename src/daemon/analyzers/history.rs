@@ -3,7 +3,7 @@ use crate::daemon::domain::{
     AnalysisResult, CommandClass, Confidence, NormalizedCommand, ResetKind, SemanticEvent,
 };
 use crate::error::GitAiError;
-use crate::git::cli_parser::explicit_rebase_branch_arg;
+use crate::git::cli_parser::{explicit_rebase_branch_arg, summarize_commit_args};
 use crate::git::repo_state::is_valid_git_oid;
 
 #[derive(Default)]
@@ -21,7 +21,7 @@ impl CommandAnalyzer for HistoryAnalyzer {
         let mut events = Vec::new();
         match name {
             "commit" | "revert" => {
-                let amend = args.iter().any(|arg| arg == "--amend");
+                let amend = name == "commit" && summarize_commit_args(&args).amend;
                 if amend {
                     if let Some((old_head, new_head)) = amend_head_change(cmd) {
                         events.push(SemanticEvent::CommitAmended { old_head, new_head });
@@ -82,11 +82,7 @@ impl CommandAnalyzer for HistoryAnalyzer {
                         events.push(SemanticEvent::MergeSquash { source_head, onto });
                     }
                 } else if let Some((old_head, new_head)) = head_change(cmd, state.refs) {
-                    events.push(SemanticEvent::RefUpdated {
-                        reference: "HEAD".to_string(),
-                        old: old_head,
-                        new: new_head,
-                    });
+                    events.push(SemanticEvent::MergeComplete { old_head, new_head });
                 }
             }
             "update-ref" => {
@@ -545,6 +541,33 @@ mod tests {
         assert_only_opaque(&result);
     }
 
+    #[test]
+    fn octopus_merge_emits_merge_complete() {
+        let analyzer = HistoryAnalyzer;
+        let mut cmd = command("merge", &["git", "merge", "feature-a", "feature-b"]);
+        cmd.ref_changes = vec![RefChange {
+            reference: "HEAD".to_string(),
+            old: "1111111111111111111111111111111111111111".to_string(),
+            new: "2222222222222222222222222222222222222222".to_string(),
+        }];
+
+        let result = analyzer
+            .analyze(
+                &cmd,
+                AnalysisView {
+                    refs: &Default::default(),
+                },
+            )
+            .unwrap();
+
+        assert!(result.events.iter().any(|event| matches!(
+            event,
+            SemanticEvent::MergeComplete { old_head, new_head }
+                if old_head == "1111111111111111111111111111111111111111"
+                    && new_head == "2222222222222222222222222222222222222222"
+        )));
+    }
+
     #[test]
     fn commit_without_amend_emits_commit_created() {
         let analyzer = HistoryAnalyzer;
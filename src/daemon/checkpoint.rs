@@ -1,5 +1,6 @@
 use crate::authorship::attribution_tracker::{
-    Attribution, AttributionTracker, INITIAL_ATTRIBUTION_TS, LineAttribution,
+    Attribution, AttributionTracker, ByteEdit, INITIAL_ATTRIBUTION_TS, LineAttribution,
+    line_ending_style_changed,
 };
 use crate::authorship::authorship_log_serialization::generate_session_id;
 #[cfg(not(any(test, feature = "test-support")))]
@@ -7,9 +8,11 @@ use crate::authorship::authorship_log_serialization::generate_short_hash;
 use crate::authorship::imara_diff_utils::{
     LineChangeTag, compute_line_changes, content_eq_ignoring_line_endings,
 };
+use crate::authorship::paste_detection::{AI_PASTED_KIND, classify_paste};
 use crate::authorship::working_log::CheckpointKind;
 use crate::authorship::working_log::{Checkpoint, WorkingLogEntry};
-use crate::commands::checkpoint_agent::orchestrator::CheckpointRequest;
+use crate::commands::checkpoint_agent::orchestrator::{CheckpointHunk, CheckpointRequest};
+use crate::config::Config;
 use crate::error::GitAiError;
 use crate::git::repo_storage::PersistedWorkingLog;
 use crate::git::repository::Repository;
@@ -30,6 +33,8 @@ pub struct FileLineStats {
     pub deletions: u32,
     pub additions_sloc: u32,
     pub deletions_sloc: u32,
+    /// Longest run of contiguous inserted lines.
+    pub largest_insertion: u32,
 }
 
 /// Latest checkpoint state needed to process a file in the next checkpoint.
@@ -37,9 +42,10 @@ pub struct FileLineStats {
 struct PreviousFileState {
     blob_sha: String,
     attributions: Vec<Attribution>,
+    written_by_ai: bool,
 }
 
-use crate::authorship::working_log::AgentId;
+use crate::authorship::working_log::{AgentId, SubagentInfo};
 
 #[cfg_attr(any(test, feature = "test-support"), allow(dead_code))]
 const AGENT_USAGE_MIN_INTERVAL_SECS: u64 = 150;
@@ -85,14 +91,17 @@ pub struct ResolvedCheckpointExecution {
     pub ts: u128,
     pub files: Vec<String>,
     pub dirty_files: HashMap<String, Arc<str>>,
+    /// Integration-supplied hunks keyed by repo-relative path.
+    pub hunks: HashMap<String, Vec<CheckpointHunk>>,
 }
 
 /// Build EventAttributes for AgentUsage events.
 /// When repo is available, includes repo_url and branch. Always includes tool, model,
-/// session_id, and custom attributes.
+/// session_id, and custom attributes, plus the tool version when the preset reported one.
 pub fn build_agent_usage_attrs(
     repo: Option<&Repository>,
     agent_id: &AgentId,
+    tool_version: Option<&str>,
 ) -> crate::metrics::EventAttributes {
     let session_id = generate_session_id(&agent_id.id, &agent_id.tool);
 
@@ -101,6 +110,7 @@ pub fn build_agent_usage_attrs(
         .tool(&agent_id.tool)
         .model(&agent_id.model)
         .external_session_id(&agent_id.id)
+        .tool_version_opt(tool_version.map(str::to_string))
         .custom_attributes_map(crate::config::Config::fresh().custom_attributes());
 
     if let Some(repo) = repo {
@@ -124,6 +134,8 @@ fn build_checkpoint_attrs(
     repo: &Repository,
     base_commit: &str,
     agent_id: Option<&AgentId>,
+    subagent: Option<&SubagentInfo>,
+    tool_version: Option<&str>,
 ) -> crate::metrics::EventAttributes {
     // Extract session_id from agent_id if available
     let session_id = agent_id
@@ -140,7 +152,19 @@ fn build_checkpoint_attrs(
         attrs = attrs
             .tool(&agent_id.tool)
             .model(&agent_id.model)
-            .external_session_id(&agent_id.id);
+            .external_session_id(&agent_id.id)
+            .tool_version_opt(tool_version.map(str::to_string));
+
+        // Subagent edits are keyed like the subagent's transcript session, so
+        // they join with its transcript metrics and still roll up to the parent.
+        if let Some(subagent) = subagent {
+            let external_session_id = subagent.external_session_id();
+            attrs = attrs
+                .session_id(generate_session_id(&external_session_id, &agent_id.tool))
+                .external_session_id(external_session_id)
+                .parent_session_id(generate_session_id(&agent_id.id, &agent_id.tool))
+                .external_parent_session_id(&agent_id.id);
+        }
     }
 
     // Attach custom attributes using Config::fresh() to support runtime config updates
@@ -273,6 +297,7 @@ fn execute_resolved_checkpoint(
         &file_content_hashes,
         &checkpoints,
         &checkpoint_request,
+        &resolved.hunks,
         resolved.ts,
         Some(resolved.base_commit.as_str()),
         trace_id.clone(),
@@ -294,9 +319,20 @@ fn execute_resolved_checkpoint(
         checkpoint.timestamp = (resolved.ts / 1000) as u64;
         checkpoint.line_stats = compute_line_stats(&file_stats)?;
         checkpoint.trace_id = Some(trace_id.clone());
+        checkpoint.label = checkpoint_request
+            .metadata
+            .get(crate::authorship::working_log::CHECKPOINT_LABEL_METADATA_KEY)
+            .cloned();
 
         if kind.is_ai() {
             checkpoint.agent_id = checkpoint_request.agent_id.clone();
+            checkpoint.tool_use_id = checkpoint_request.metadata.get("tool_use_id").cloned();
+            checkpoint.subagent = SubagentInfo::from_metadata(&checkpoint_request.metadata);
+            checkpoint.tool_version = checkpoint_request
+                .metadata
+                .get(crate::authorship::working_log::TOOL_VERSION_METADATA_KEY)
+                .filter(|version| !version.is_empty())
+                .cloned();
             checkpoint.agent_metadata = if checkpoint_request.metadata.is_empty() {
                 None
             } else {
@@ -333,15 +369,29 @@ fn execute_resolved_checkpoint(
         );
 
         let append_start = Instant::now();
-        working_log.append_checkpoint(&checkpoint)?;
+        let appended = working_log.append_checkpoint(&checkpoint)?;
         tracing::debug!(
             "[BENCHMARK] Appending checkpoint to working log took {:?}",
             append_start.elapsed()
         );
+        if !appended {
+            record_suppressed_duplicate(
+                repo,
+                &resolved.base_commit,
+                &checkpoint,
+                working_log.read_all_checkpoints()?.last(),
+            );
+            return Ok((0, resolved.files.len(), 0));
+        }
         checkpoints.push(checkpoint.clone());
 
-        let mut attrs =
-            build_checkpoint_attrs(repo, &resolved.base_commit, checkpoint.agent_id.as_ref());
+        let mut attrs = build_checkpoint_attrs(
+            repo,
+            &resolved.base_commit,
+            checkpoint.agent_id.as_ref(),
+            checkpoint.subagent.as_ref(),
+            checkpoint.tool_version.as_deref(),
+        );
 
         // Add trace_id to attributes - links all checkpoint events together
         if let Some(ref tid) = checkpoint.trace_id {
@@ -361,10 +411,26 @@ fn execute_resolved_checkpoint(
             .get("edit_kind")
             .map(|s| s.as_str());
 
+        let detect_pastes =
+            kind == CheckpointKind::Human && Config::get().get_feature_flags().ai_paste_detection;
+
         for (entry, file_stat) in entries.iter().zip(file_stats.iter()) {
+            let paste_confidence = if detect_pastes {
+                classify_paste(
+                    file_stat.largest_insertion,
+                    file_stat.additions,
+                    file_stat.deletions,
+                )
+            } else {
+                None
+            };
+            let metric_kind = match paste_confidence {
+                Some(_) => AI_PASTED_KIND.to_string(),
+                None => checkpoint.kind.to_str(),
+            };
             let mut values = crate::metrics::CheckpointValues::new()
                 .checkpoint_ts(checkpoint.timestamp)
-                .kind(checkpoint.kind.to_str().to_string())
+                .kind(metric_kind)
                 .file_path(entry.file.clone())
                 .lines_added(file_stat.additions)
                 .lines_deleted(file_stat.deletions)
@@ -377,6 +443,9 @@ fn execute_resolved_checkpoint(
             if let Some(ek) = edit_kind {
                 values = values.edit_kind(ek);
             }
+            if let Some(confidence) = paste_confidence {
+                values = values.paste_confidence(confidence.as_str());
+            }
 
             let file_attrs = attrs.clone().author(&checkpoint.author);
             crate::metrics::record(values, file_attrs);
@@ -431,6 +500,37 @@ fn execute_resolved_checkpoint(
     Ok((entries.len(), resolved.files.len(), checkpoints.len()))
 }
 
+/// Counts a checkpoint dropped as a duplicate of `previous`.
+fn record_suppressed_duplicate(
+    repo: &Repository,
+    base_commit: &str,
+    checkpoint: &Checkpoint,
+    previous: Option<&Checkpoint>,
+) {
+    let age_secs = previous
+        .map(|previous| checkpoint.timestamp.saturating_sub(previous.timestamp))
+        .unwrap_or(0);
+    let mut values = crate::metrics::CheckpointDedupedValues::new()
+        .kind(checkpoint.kind.to_str())
+        .file_count(checkpoint.entries.len() as u32)
+        .age_secs(age_secs as u32);
+    if let Some(tool_use_id) = &checkpoint.tool_use_id {
+        values = values.external_tool_use_id(tool_use_id);
+    }
+    let mut attrs = build_checkpoint_attrs(
+        repo,
+        base_commit,
+        checkpoint.agent_id.as_ref(),
+        checkpoint.subagent.as_ref(),
+        checkpoint.tool_version.as_deref(),
+    )
+    .author(&checkpoint.author);
+    if let Some(trace_id) = &checkpoint.trace_id {
+        attrs = attrs.trace_id(trace_id);
+    }
+    crate::metrics::record(values, attrs);
+}
+
 fn save_current_file_states(
     working_log: &PersistedWorkingLog,
     files: &[String],
@@ -555,6 +655,7 @@ fn build_previous_file_state_maps(
                 PreviousFileState {
                     blob_sha: entry.blob_sha.clone(),
                     attributions: entry.attributions.clone(),
+                    written_by_ai: checkpoint.kind.is_ai(),
                 },
             );
 
@@ -581,6 +682,8 @@ fn get_checkpoint_entry_for_file(
     initial_attributions: Arc<HashMap<String, Vec<LineAttribution>>>,
     initial_snapshot_contents: Arc<HashMap<String, Arc<str>>>,
     parent_note_attributions: Arc<HashMap<String, Vec<LineAttribution>>>,
+    hunks: Option<Vec<CheckpointHunk>>,
+    normalize_line_endings: bool,
     ts: u128,
 ) -> Result<Option<(WorkingLogEntry, FileLineStats)>, GitAiError> {
     let file_start = Instant::now();
@@ -766,7 +869,36 @@ fn get_checkpoint_entry_for_file(
         return Ok(Some((entry, FileLineStats::default())));
     }
 
-    let (entry, stats) = make_entry_for_file(FileEntryInput {
+    // A formatter or save hook that rewrote the file right after an AI edit
+    // should not turn the reformatted AI lines into human ones.
+    if !kind.is_ai()
+        && previous_state
+            .as_ref()
+            .is_some_and(|state| state.written_by_ai)
+        && Config::get().get_feature_flags().formatter_detection
+        && let Some(carried_attributions) =
+            crate::authorship::format_detection::carry_attributions_through_reformat(
+                &previous_content,
+                &current_content,
+                &prev_attributions,
+            )
+    {
+        let line_attributions =
+            crate::authorship::attribution_tracker::attributions_to_line_attributions_for_checkpoint(
+                &carried_attributions,
+                &current_content,
+                false,
+            );
+        let entry = WorkingLogEntry::new(
+            file_path,
+            file_content_hash,
+            carried_attributions,
+            line_attributions,
+        );
+        return Ok(Some((entry, FileLineStats::default())));
+    }
+
+    let (mut entry, stats) = make_entry_for_file(FileEntryInput {
         file_path: &file_path,
         blob_sha: &file_content_hash,
         author_id: author_id.as_ref(),
@@ -774,8 +906,19 @@ fn get_checkpoint_entry_for_file(
         previous_content: &previous_content,
         previous_attributions: &prev_attributions,
         content: &current_content,
+        hunks: hunks.as_deref(),
+        normalize_line_endings,
         ts,
     })?;
+    crate::authorship::attribution_comments::apply_attribution_comments(
+        &mut entry,
+        &previous_content,
+        &current_content,
+        &prev_attributions,
+        author_id.as_ref(),
+        kind.is_ai(),
+        ts,
+    );
 
     tracing::debug!(
         "[BENCHMARK] Processing file {} took {:?}",
@@ -795,6 +938,7 @@ async fn get_checkpoint_entries(
     file_content_hashes: &HashMap<String, String>,
     previous_checkpoints: &[Checkpoint],
     checkpoint_request: &CheckpointRequest,
+    file_hunks: &HashMap<String, Vec<CheckpointHunk>>,
     ts: u128,
     head_commit_override: Option<&str>,
     trace_id: String,
@@ -879,6 +1023,7 @@ async fn get_checkpoint_entries(
     let initial_attributions = Arc::new(initial_attributions);
     let initial_snapshot_contents = Arc::new(initial_snapshot_contents);
     let parent_note_attributions = Arc::new(parent_note_attributions);
+    let verbatim_eol_matcher = crate::authorship::ignore::build_verbatim_eol_matcher(repo);
 
     // Spawn tasks for each file
     let spawn_start = Instant::now();
@@ -899,6 +1044,11 @@ async fn get_checkpoint_entries(
         let initial_attributions = Arc::clone(&initial_attributions);
         let initial_snapshot_contents = Arc::clone(&initial_snapshot_contents);
         let parent_note_attributions = Arc::clone(&parent_note_attributions);
+        let hunks = file_hunks.get(&file_path).cloned();
+        let normalize_line_endings = !crate::authorship::ignore::should_ignore_file_with_matcher(
+            &file_path,
+            &verbatim_eol_matcher,
+        );
         let semaphore = Arc::clone(&semaphore);
 
         let task = async move {
@@ -921,6 +1071,8 @@ async fn get_checkpoint_entries(
                     initial_attributions.clone(),
                     initial_snapshot_contents.clone(),
                     parent_note_attributions.clone(),
+                    hunks,
+                    normalize_line_endings,
                     ts,
                 )
             })
@@ -947,11 +1099,13 @@ async fn get_checkpoint_entries(
     // Process results
     let process_start = Instant::now();
     let results_count = results.len();
+    let test_matcher = crate::authorship::test_paths::test_path_matcher_for_repo(repo);
     let mut entries = Vec::new();
     let mut file_stats = Vec::new();
     for result in results {
         match result {
-            Ok(Some((entry, stats))) => {
+            Ok(Some((mut entry, stats))) => {
+                entry.is_test = test_matcher.is_test(&entry.file);
                 entries.push(entry);
                 file_stats.push(stats);
             }
@@ -980,6 +1134,9 @@ struct FileEntryInput<'a> {
     previous_content: &'a str,
     previous_attributions: &'a [Attribution],
     content: &'a str,
+    hunks: Option<&'a [CheckpointHunk]>,
+    /// False for files `.gitattributes` marks `-text` or `binary`.
+    normalize_line_endings: bool,
     ts: u128,
 }
 
@@ -994,10 +1151,12 @@ fn make_entry_for_file(
         previous_content,
         previous_attributions,
         content,
+        hunks,
+        normalize_line_endings,
         ts,
     } = input;
 
-    let tracker = AttributionTracker::new();
+    let tracker = AttributionTracker::for_line_endings(normalize_line_endings);
 
     let fill_start = Instant::now();
     let filled_in_prev_attributions = tracker.attribute_unattributed_ranges(
@@ -1012,15 +1171,45 @@ fn make_entry_for_file(
         fill_start.elapsed()
     );
 
+    // Hunks that convert line endings would attribute the whole converted
+    // range to the agent; diffing normalizes that churn away instead.
+    let eol_churn = normalize_line_endings
+        && hunks.is_some()
+        && line_ending_style_changed(previous_content, content);
+    if eol_churn {
+        tracing::debug!(
+            "Supplied hunks for {} change line endings; diffing instead",
+            file_path
+        );
+    }
+    let known_edits = hunks.filter(|_| !eol_churn).and_then(|hunks| {
+        let edits = hunks_to_byte_edits(previous_content, content, hunks);
+        if edits.is_none() {
+            tracing::debug!(
+                "Supplied hunks for {} do not match the previous checkpoint; diffing instead",
+                file_path
+            );
+        }
+        edits
+    });
+
     let update_start = Instant::now();
-    let new_attributions = tracker.update_attributions_for_checkpoint(
-        previous_content,
-        content,
-        &filled_in_prev_attributions,
-        author_id,
-        ts,
-        is_ai_checkpoint,
-    )?;
+    let new_attributions = match known_edits.as_deref() {
+        Some(edits) => tracker.update_attributions_for_edits(
+            &filled_in_prev_attributions,
+            edits,
+            author_id,
+            ts,
+        ),
+        None => tracker.update_attributions_for_checkpoint(
+            previous_content,
+            content,
+            &filled_in_prev_attributions,
+            author_id,
+            ts,
+            is_ai_checkpoint,
+        )?,
+    };
     tracing::debug!(
         "[BENCHMARK]   update_attributions for {} took {:?}",
         file_path,
@@ -1045,7 +1234,10 @@ fn make_entry_for_file(
 
     // Compute line stats while we already have both contents in memory
     let stats_start = Instant::now();
-    let line_stats = compute_file_line_stats(previous_content, content);
+    let line_stats = match known_edits.as_deref() {
+        Some(edits) => file_line_stats_from_edits(previous_content, content, edits),
+        None => compute_file_line_stats(previous_content, content),
+    };
     tracing::debug!(
         "[BENCHMARK]   compute_file_line_stats for {} took {:?}",
         file_path,
@@ -1069,7 +1261,11 @@ pub fn compute_file_line_stats(previous_content: &str, current_content: &str) ->
 
     // Use imara_diff to count line changes (matches git's diff algorithm)
     let changes = compute_line_changes(previous_content, current_content);
+    let mut insertion_run = 0u32;
     for change in changes {
+        if change.tag() != LineChangeTag::Insert {
+            insertion_run = 0;
+        }
         match change.tag() {
             LineChangeTag::Insert => {
                 let non_whitespace_lines = change
@@ -1077,8 +1273,11 @@ pub fn compute_file_line_stats(previous_content: &str, current_content: &str) ->
                     .lines()
                     .filter(|line| !line.trim().is_empty())
                     .count() as u32;
-                stats.additions += change.value().lines().count() as u32;
+                let inserted_lines = change.value().lines().count() as u32;
+                stats.additions += inserted_lines;
                 stats.additions_sloc += non_whitespace_lines;
+                insertion_run += inserted_lines;
+                stats.largest_insertion = stats.largest_insertion.max(insertion_run);
             }
             LineChangeTag::Delete => {
                 let non_whitespace_lines = change
@@ -1096,6 +1295,71 @@ pub fn compute_file_line_stats(previous_content: &str, current_content: &str) ->
     stats
 }
 
+/// Converts integration-supplied hunks into byte edits of `previous_content`.
+///
+/// Returns `None` when the hunks overlap, point past the end of the file, or do
+/// not turn `previous_content` into exactly `current_content` (for example when
+/// the file changed after the integration last saw it).
+fn hunks_to_byte_edits(
+    previous_content: &str,
+    current_content: &str,
+    hunks: &[CheckpointHunk],
+) -> Option<Vec<ByteEdit>> {
+    // Byte offset where each 1-indexed line starts, ending with the content length.
+    let mut line_starts = vec![0usize];
+    line_starts.extend(previous_content.match_indices('\n').map(|(idx, _)| idx + 1));
+    if line_starts.last() != Some(&previous_content.len()) {
+        line_starts.push(previous_content.len());
+    }
+
+    let mut sorted: Vec<&CheckpointHunk> = hunks.iter().collect();
+    sorted.sort_by_key(|hunk| hunk.old_start);
+
+    let mut edits = Vec::with_capacity(sorted.len());
+    let mut rebuilt = String::with_capacity(current_content.len());
+    let mut copied_to = 0usize;
+    for hunk in sorted {
+        let first_line = hunk.old_start.checked_sub(1)? as usize;
+        let old_start = *line_starts.get(first_line)?;
+        let old_end = *line_starts.get(first_line + hunk.old_lines as usize)?;
+        if old_start < copied_to {
+            return None;
+        }
+        rebuilt.push_str(&previous_content[copied_to..old_start]);
+        let new_start = rebuilt.len();
+        rebuilt.push_str(&hunk.new_text);
+        edits.push(ByteEdit {
+            old: old_start..old_end,
+            new: new_start..rebuilt.len(),
+        });
+        copied_to = old_end;
+    }
+    rebuilt.push_str(&previous_content[copied_to..]);
+
+    (rebuilt == current_content).then_some(edits)
+}
+
+/// Line statistics for known edits: removed lines count as deletions and
+/// replacement lines as additions.
+fn file_line_stats_from_edits(
+    previous_content: &str,
+    current_content: &str,
+    edits: &[ByteEdit],
+) -> FileLineStats {
+    let mut stats = FileLineStats::default();
+    for edit in edits {
+        let removed = &previous_content[edit.old.clone()];
+        let added = &current_content[edit.new.clone()];
+        stats.deletions += removed.lines().count() as u32;
+        stats.deletions_sloc += removed.lines().filter(|l| !l.trim().is_empty()).count() as u32;
+        let added_lines = added.lines().count() as u32;
+        stats.additions += added_lines;
+        stats.additions_sloc += added.lines().filter(|l| !l.trim().is_empty()).count() as u32;
+        stats.largest_insertion = stats.largest_insertion.max(added_lines);
+    }
+    stats
+}
+
 /// Aggregate line statistics from individual file stats
 /// This avoids redundant diff computation since stats are already computed during entry creation
 fn compute_line_stats(
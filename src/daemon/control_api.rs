@@ -17,6 +17,9 @@ pub enum ControlRequest {
     SyncFamily { repo_working_dir: String },
     #[serde(rename = "status.family")]
     StatusFamily { repo_working_dir: String },
+    /// Status of every checkout under the configured `workspace_roots`.
+    #[serde(rename = "status.workspace")]
+    StatusWorkspace,
     #[serde(rename = "telemetry.submit")]
     SubmitTelemetry { envelopes: Vec<TelemetryEnvelope> },
     #[serde(rename = "cas.submit")]
@@ -84,6 +87,18 @@ pub enum ControlRequest {
         ended_at_ns: u128,
         command: Option<String>,
     },
+    /// Predicted line attributions for an unsaved editor buffer of `file_path`
+    /// (see `VirtualAttributions::from_buffer_overlay`).
+    #[serde(rename = "attribution.buffer")]
+    BufferAttribution {
+        repo_working_dir: String,
+        file_path: String,
+        content: String,
+    },
+    /// Phase timings of the daemon's own spans since it started (see
+    /// `observability::profile`).
+    #[serde(rename = "profile.snapshot")]
+    ProfileSnapshot,
     /// Wait for the daemon to finish all in-flight work and flush telemetry.
     #[serde(rename = "await")]
     Await { timeout_secs: u64 },
@@ -144,6 +159,21 @@ pub struct FamilyStatus {
     pub last_error: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct WorkspaceStatus {
+    pub roots: Vec<String>,
+    pub repositories: Vec<WorkspaceRepoStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceRepoStatus {
+    pub repo_working_dir: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<FamilyStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 /// A telemetry envelope sent from client to daemon.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
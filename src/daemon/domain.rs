@@ -129,6 +129,10 @@ pub enum SemanticEvent {
         source_head: String,
         onto: String,
     },
+    MergeComplete {
+        old_head: String,
+        new_head: String,
+    },
     CherryPickComplete {
         original_head: String,
         new_head: String,
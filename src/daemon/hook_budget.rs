@@ -0,0 +1,183 @@
+//! Hook latency budget with automatic degradation.
+//!
+//! With `max_hook_latency_ms` configured, the daemon times the post-commit
+//! side effect of every commit. After [`DEGRADE_AFTER_OVER_BUDGET`] runs in a
+//! row over the budget the repository switches to degraded mode: post-commit
+//! still writes the authorship note from the working log, but skips attribution
+//! recovery and stats and journals the commit for `git-ai repair`, which runs
+//! the skipped recovery and leaves degraded mode. The streak and the mode are
+//! kept in the metrics database so they survive daemon restarts, and
+//! `git-ai status` reports degraded mode.
+
+use crate::error::GitAiError;
+use crate::git::repo_storage::{PendingRepairRecord, RepairKind};
+use crate::git::repository::Repository;
+use crate::metrics::db::{HookBudgetRecord, MetricsDatabase};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Consecutive over-budget runs that switch a repository to degraded mode.
+pub const DEGRADE_AFTER_OVER_BUDGET: u32 = 3;
+
+/// The configured budget, or `None` when unset or `0`.
+pub fn max_hook_latency() -> Option<Duration> {
+    crate::config::effective_file_config_cached()
+        .max_hook_latency_ms
+        .filter(|ms| *ms > 0)
+        .map(Duration::from_millis)
+}
+
+/// Key of `repo` in the `hook_budget` table, shared by its worktrees.
+fn repo_key(repo: &Repository) -> String {
+    std::fs::canonicalize(repo.common_dir())
+        .unwrap_or_else(|_| repo.common_dir().to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// The budget state of `repo` while it is in degraded mode.
+pub fn degradation(repo: &Repository) -> Option<HookBudgetRecord> {
+    let db = MetricsDatabase::global().ok()?.lock().ok()?;
+    db.get_hook_budget(&repo_key(repo))
+        .ok()
+        .flatten()
+        .filter(|record| record.degraded_since.is_some())
+}
+
+/// Record that `hook` took `elapsed` in `repo`, switching the repository to
+/// degraded mode once it has been over budget too many times in a row.
+pub fn record_hook_latency(repo: &Repository, hook: &str, elapsed: Duration) {
+    let Some(budget) = max_hook_latency() else {
+        return;
+    };
+    let Ok(db) = MetricsDatabase::global() else {
+        return;
+    };
+    let Ok(mut db) = db.lock() else {
+        return;
+    };
+    let key = repo_key(repo);
+    let previous = db.get_hook_budget(&key).ok().flatten();
+    let was_degraded = previous
+        .as_ref()
+        .is_some_and(|record| record.degraded_since.is_some());
+    let record = next_state(
+        previous,
+        key,
+        hook,
+        elapsed,
+        budget,
+        chrono::Utc::now().timestamp().max(0) as u64,
+    );
+    if let Err(e) = db.upsert_hook_budget(&record) {
+        tracing::debug!(%e, "failed to record hook latency");
+        return;
+    }
+
+    if !was_degraded && record.degraded_since.is_some() {
+        tracing::warn!(
+            hook,
+            repo = %record.repo_key,
+            budget_ms = budget.as_millis() as u64,
+            "hook over budget {} times in a row; switching to degraded mode",
+            record.over_budget_streak
+        );
+        crate::observability::log_performance(
+            "hook_budget_degraded",
+            elapsed,
+            Some(serde_json::json!({
+                "hook": hook,
+                "budget_ms": budget.as_millis() as u64,
+            })),
+            Some(HashMap::from([("hook".to_string(), hook.to_string())])),
+        );
+    }
+}
+
+fn next_state(
+    previous: Option<HookBudgetRecord>,
+    repo_key: String,
+    hook: &str,
+    elapsed: Duration,
+    budget: Duration,
+    now: u64,
+) -> HookBudgetRecord {
+    let previous_streak = previous
+        .as_ref()
+        .map_or(0, |record| record.over_budget_streak);
+    let over_budget_streak = if elapsed > budget {
+        previous_streak.saturating_add(1)
+    } else {
+        0
+    };
+    let degraded_since = previous
+        .and_then(|record| record.degraded_since)
+        .or_else(|| (over_budget_streak >= DEGRADE_AFTER_OVER_BUDGET).then_some(now));
+    HookBudgetRecord {
+        repo_key,
+        over_budget_streak,
+        last_hook: hook.to_string(),
+        last_duration_ms: elapsed.as_millis().min(u64::MAX as u128) as u64,
+        degraded_since,
+        updated_at: now,
+    }
+}
+
+/// Journal `commit_sha` for `git-ai repair` to run the attribution recovery a
+/// degraded post-commit skipped.
+pub fn defer_recovery(repo: &Repository, commit_sha: &str) -> Result<(), GitAiError> {
+    repo.storage.append_pending_repair(&PendingRepairRecord {
+        kind: RepairKind::RecoverAttribution,
+        remote: String::new(),
+        commit_sha: Some(commit_sha.to_string()),
+        deferred_at: chrono::Utc::now().timestamp(),
+    })
+}
+
+/// Leave degraded mode once its deferred work has been repaired.
+pub fn clear_degradation(repo: &Repository) -> Result<(), GitAiError> {
+    let db = MetricsDatabase::global()?;
+    let mut db = db
+        .lock()
+        .map_err(|_| GitAiError::Generic("metrics database lock poisoned".to_string()))?;
+    db.clear_hook_budget(&repo_key(repo))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(previous: Option<HookBudgetRecord>, elapsed_ms: u64, now: u64) -> HookBudgetRecord {
+        next_state(
+            previous,
+            "repo".to_string(),
+            "post_commit",
+            Duration::from_millis(elapsed_ms),
+            Duration::from_millis(500),
+            now,
+        )
+    }
+
+    #[test]
+    fn degrades_after_consecutive_overruns_and_stays_degraded() {
+        let first = run(None, 800, 1);
+        assert_eq!(first.over_budget_streak, 1);
+        let reset = run(Some(first), 100, 2);
+        assert_eq!(reset.over_budget_streak, 0);
+        assert_eq!(reset.degraded_since, None);
+
+        let mut state = reset;
+        for now in 3..3 + u64::from(DEGRADE_AFTER_OVER_BUDGET) {
+            assert_eq!(state.degraded_since, None);
+            state = run(Some(state), 800, now);
+        }
+        assert_eq!(state.over_budget_streak, DEGRADE_AFTER_OVER_BUDGET);
+        let degraded_since = state.degraded_since;
+        assert!(degraded_since.is_some());
+
+        let fast = run(Some(state), 10, 100);
+        assert_eq!(fast.over_budget_streak, 0);
+        assert_eq!(fast.degraded_since, degraded_since);
+        assert_eq!(fast.last_duration_ms, 10);
+    }
+}
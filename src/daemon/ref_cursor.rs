@@ -2,10 +2,13 @@ use crate::daemon::analyzers::{command_args, normalized_args};
 use crate::daemon::domain::{Confidence, FamilyKey, FamilyState, NormalizedCommand, RefChange};
 use crate::error::GitAiError;
 use crate::git::cli_parser::{
-    explicit_rebase_branch_arg, parse_git_cli_args, summarize_rebase_args,
+    explicit_rebase_branch_arg, parse_git_cli_args, summarize_commit_args, summarize_rebase_args,
 };
 use crate::git::find_repository_in_path;
-use crate::git::repo_state::{common_dir_for_worktree, git_dir_for_worktree, is_valid_git_oid};
+use crate::git::repo_state::{
+    cherry_pick_sequencer_todo_for_worktree, common_dir_for_worktree, git_dir_for_worktree,
+    is_valid_git_oid,
+};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::io::{Read, Seek, SeekFrom};
@@ -27,6 +30,10 @@ pub struct RefCursor {
     command_start_hints: HashMap<String, u64>,
     stash_stack: Vec<String>,
     pending_cherry_pick_source_oids: Vec<String>,
+    /// Picks left in `.git/sequencer/todo` when the last cherry-pick stopped.
+    /// Bounds the `--continue`/`--skip` HEAD span even when the stopped command's
+    /// sources (e.g. a range) could not be resolved from the ref snapshot.
+    pending_cherry_pick_steps: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -130,6 +137,7 @@ impl RefCursor {
             command_start_hints: HashMap::new(),
             stash_stack: Vec::new(),
             pending_cherry_pick_source_oids: Vec::new(),
+            pending_cherry_pick_steps: 0,
         }
     }
 
@@ -400,7 +408,7 @@ impl RefCursor {
         let args = command_args(cmd);
         match cmd.primary_command.as_deref()? {
             "commit" => {
-                let amend = args.iter().any(|arg| arg == "--amend");
+                let amend = summarize_commit_args(&args).amend;
                 let prefixes: Vec<String> = if amend {
                     vec!["commit (amend):".to_string()]
                 } else {
@@ -432,7 +440,10 @@ impl RefCursor {
                 {
                     usize::MAX
                 } else if source_args.is_empty() {
-                    self.pending_cherry_pick_source_oids.len().max(1)
+                    self.pending_cherry_pick_source_oids
+                        .len()
+                        .max(self.pending_cherry_pick_steps)
+                        .max(1)
                 } else {
                     source_args.len().max(1)
                 };
@@ -455,7 +466,7 @@ impl RefCursor {
         state: &FamilyState,
     ) -> Result<(), GitAiError> {
         let args = command_args(cmd);
-        let amend = args.iter().any(|arg| arg == "--amend");
+        let amend = summarize_commit_args(&args).amend;
         let prefixes = if amend {
             &["commit (amend):"] as &[&str]
         } else {
@@ -466,6 +477,9 @@ impl RefCursor {
             .without_old_oid_constraint()
             .with_reflog_messages(commit_reflog_messages(&args, amend));
         let Some(entry) = self.find_commit_head_entry(cmd, prefixes, expected)? else {
+            if let Some(change) = infer_commit_head_change_without_reflog(cmd, state, amend) {
+                cmd.ref_changes.push(change);
+            }
             return Ok(());
         };
 
@@ -511,6 +525,7 @@ impl RefCursor {
             .any(|arg| matches!(arg.as_str(), "--abort" | "--quit"))
         {
             self.pending_cherry_pick_source_oids.clear();
+            self.pending_cherry_pick_steps = 0;
             return Ok(());
         }
 
@@ -521,6 +536,9 @@ impl RefCursor {
         if is_skip && !self.pending_cherry_pick_source_oids.is_empty() {
             self.pending_cherry_pick_source_oids.remove(0);
         }
+        if is_skip {
+            self.pending_cherry_pick_steps = self.pending_cherry_pick_steps.saturating_sub(1);
+        }
 
         let source_args = if is_continue || is_skip {
             Vec::new()
@@ -542,6 +560,13 @@ impl RefCursor {
         };
 
         if cmd.exit_code != 0 && unresolved_explicit_sources {
+            if let Some(todo) = cmd
+                .worktree
+                .as_deref()
+                .and_then(cherry_pick_sequencer_todo_for_worktree)
+            {
+                self.pending_cherry_pick_steps = todo.len();
+            }
             return Ok(());
         }
 
@@ -551,6 +576,11 @@ impl RefCursor {
 
         let source_limit = if unresolved_explicit_sources {
             usize::MAX
+        } else if is_continue || is_skip {
+            cmd.cherry_pick_source_oids
+                .len()
+                .max(self.pending_cherry_pick_steps)
+                .max(1)
         } else {
             cmd.cherry_pick_source_oids.len().max(1)
         };
@@ -574,12 +604,18 @@ impl RefCursor {
                 .skip(applied_count.min(cmd.cherry_pick_source_oids.len()))
                 .cloned()
                 .collect();
+            self.pending_cherry_pick_steps = cmd
+                .worktree
+                .as_deref()
+                .and_then(cherry_pick_sequencer_todo_for_worktree)
+                .map_or(0, |todo| todo.len());
         } else if is_continue
             || is_skip
             || !cmd.cherry_pick_source_oids.is_empty()
             || applied_count > 0
         {
             self.pending_cherry_pick_source_oids.clear();
+            self.pending_cherry_pick_steps = 0;
         }
 
         Ok(())
@@ -930,6 +966,9 @@ impl RefCursor {
             }
         } else if matches!(kind, "pop" | "drop") {
             self.consume_destructive_stash_operation(stash_args.get(1), cmd)?;
+        } else if kind == "branch" {
+            // A successful `stash branch` drops the stash it applied.
+            self.consume_destructive_stash_operation(stash_args.get(2), cmd)?;
         }
 
         if matches!(kind, "apply" | "pop" | "branch")
@@ -3504,6 +3543,144 @@ fn working_log_base_oids(worktree: &Path) -> HashSet<String> {
     out
 }
 
+/// Whether git records HEAD updates for `worktree`: `core.logAllRefUpdates`
+/// is not `false` and the HEAD reflog exists.
+fn head_reflog_enabled(worktree: &Path, git_dir: &Path) -> bool {
+    let disabled = crate::git::repository::config_get_str_for_path_no_git_exec(
+        worktree,
+        "core.logallrefupdates",
+    )
+    .ok()
+    .flatten()
+    .is_some_and(|value| {
+        matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "false" | "no" | "off" | "0"
+        )
+    });
+    !disabled && git_dir.join("logs").join("HEAD").exists()
+}
+
+/// Committer time and parents of a commit, as read by [`commit_shapes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CommitShape {
+    committer_time: i64,
+    parents: Vec<String>,
+}
+
+/// Shapes of `shas` (missing commits are left out) in one `git log` call.
+fn commit_shapes(
+    repo: &crate::git::repository::Repository,
+    shas: &[String],
+) -> HashMap<String, CommitShape> {
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "log".to_string(),
+        "--no-walk=unsorted".to_string(),
+        "--ignore-missing".to_string(),
+        "--format=%H %ct %P".to_string(),
+    ]);
+    args.extend(shas.iter().cloned());
+    let Ok(output) = crate::git::repository::exec_git(&args) else {
+        return HashMap::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let sha = fields.next()?.to_string();
+            let committer_time = fields.next()?.parse().ok()?;
+            let parents = fields.map(str::to_string).collect();
+            Some((
+                sha,
+                CommitShape {
+                    committer_time,
+                    parents,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// The commit `new` amended: the first candidate that is a different commit
+/// with the same parents, committed no later than `new`.
+fn select_amended_commit<'a>(
+    new_sha: &str,
+    new: &CommitShape,
+    candidates: &'a [String],
+    shapes: &HashMap<String, CommitShape>,
+) -> Option<&'a String> {
+    candidates.iter().find(|candidate| {
+        candidate.as_str() != new_sha
+            && shapes.get(candidate.as_str()).is_some_and(|shape| {
+                shape.parents == new.parents && shape.committer_time <= new.committer_time
+            })
+    })
+}
+
+/// HEAD transition of a successful `git commit` in a repository without a
+/// HEAD reflog (`core.logAllRefUpdates=false`), where the reflog-based
+/// enrichment finds nothing.
+///
+/// The current HEAD is taken as the new commit only if it was committed inside
+/// the command's time window. A new commit replaced its first parent; an amend
+/// replaced the candidate with the same parents, tried in order: `ORIG_HEAD`,
+/// the HEAD the daemon last saw, working log bases, and branch tips.
+fn infer_commit_head_change_without_reflog(
+    cmd: &NormalizedCommand,
+    state: &FamilyState,
+    amend: bool,
+) -> Option<RefChange> {
+    if cmd.exit_code != 0 {
+        return None;
+    }
+    let worktree = cmd.worktree.as_deref()?;
+    let git_dir = git_dir_for_worktree(worktree)?;
+    if head_reflog_enabled(worktree, &git_dir) {
+        return None;
+    }
+    let repo = find_repository_in_path(&worktree.to_string_lossy()).ok()?;
+    let new_head = repo.head().ok()?.target().ok()?;
+
+    let mut candidates: Vec<String> = Vec::new();
+    if amend {
+        let orig_head = fs::read_to_string(git_dir.join("ORIG_HEAD"))
+            .ok()
+            .map(|value| value.trim().to_string());
+        let expected = ExpectedTransition::from_state_and_working_logs(cmd, state);
+        let mut others: Vec<String> = expected.old_oids.into_iter().collect();
+        others.sort();
+        for oid in orig_head
+            .into_iter()
+            .chain(state.refs.get("HEAD").cloned())
+            .chain(others)
+        {
+            if valid_non_zero_oid(&oid) && !candidates.contains(&oid) {
+                candidates.push(oid);
+            }
+        }
+    }
+
+    let mut shas = vec![new_head.clone()];
+    shas.extend(candidates.iter().cloned());
+    let shapes = commit_shapes(&repo, &shas);
+    let new = shapes.get(&new_head)?;
+    if !reflog_timestamp_window(cmd).contains(new.committer_time) {
+        return None;
+    }
+
+    let old = if amend {
+        select_amended_commit(&new_head, new, &candidates, &shapes)?.clone()
+    } else {
+        new.parents.first()?.clone()
+    };
+    Some(RefChange {
+        reference: "HEAD".to_string(),
+        old,
+        new: new_head,
+    })
+}
+
 fn checkout_is_path_checkout(cmd: &NormalizedCommand) -> bool {
     let args = command_args(cmd);
     args.iter().any(|arg| arg == "--")
@@ -3717,6 +3894,27 @@ mod tests {
     const F: &str = "6666666666666666666666666666666666666666";
     const G: &str = "7777777777777777777777777777777777777777";
 
+    #[test]
+    fn select_amended_commit_requires_same_parents_and_earlier_commit() {
+        let shape = |committer_time: i64, parents: &[&str]| CommitShape {
+            committer_time,
+            parents: parents.iter().map(|parent| parent.to_string()).collect(),
+        };
+        let new = shape(100, &[A]);
+        let shapes = HashMap::from([
+            (B.to_string(), shape(90, &[B])),
+            (C.to_string(), shape(200, &[A])),
+            (D.to_string(), shape(50, &[A])),
+        ]);
+        let candidates = vec![E.to_string(), B.to_string(), C.to_string(), D.to_string()];
+
+        assert_eq!(
+            select_amended_commit(E, &new, &candidates, &shapes),
+            Some(&D.to_string())
+        );
+        assert_eq!(select_amended_commit(D, &new, &candidates, &shapes), None);
+    }
+
     #[test]
     fn commit_subject_matches_git_reflog_trailing_whitespace_cleanup() {
         assert_eq!(commit_subject("subject \t"), Some("subject".to_string()));
@@ -5568,6 +5766,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cherry_pick_continue_after_stopped_range_consumes_every_sequencer_pick() {
+        let temp = tempfile::tempdir().unwrap();
+        let worktree = temp.path().join("repo");
+        let git_dir = worktree.join(".git");
+        fs::create_dir_all(git_dir.join("logs")).unwrap();
+        fs::write(git_dir.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        let family = FamilyKey::new(git_dir.to_string_lossy().to_string());
+        let mut state = family_state(&family);
+        state.refs.insert("HEAD".to_string(), B.to_string());
+        let mut cursor = RefCursor::new(family.clone());
+
+        // The range cannot be resolved from the ref snapshot, and git stops on
+        // the first of two picks.
+        fs::create_dir_all(git_dir.join("sequencer")).unwrap();
+        fs::write(
+            git_dir.join("sequencer").join("todo"),
+            "pick 1111111 Pick one\npick 5555555 Pick two\n",
+        )
+        .unwrap();
+        fs::write(git_dir.join("CHERRY_PICK_HEAD"), format!("{A}\n")).unwrap();
+        let mut stopped = command_with_worktree(
+            &family,
+            Some(worktree.clone()),
+            &["cherry-pick", "topic~2..topic"],
+        );
+        stopped.exit_code = 1;
+        cursor.enrich_command(&mut stopped, &state).unwrap();
+        assert_eq!(cursor.pending_cherry_pick_steps, 2);
+
+        fs::remove_dir_all(git_dir.join("sequencer")).unwrap();
+        fs::remove_file(git_dir.join("CHERRY_PICK_HEAD")).unwrap();
+        append_reflog(
+            &git_dir,
+            "HEAD",
+            &[
+                (B, C, "cherry-pick: Pick one"),
+                (C, D, "cherry-pick: Pick two"),
+            ],
+        );
+        let mut cmd =
+            command_with_worktree(&family, Some(worktree), &["cherry-pick", "--continue"]);
+        cursor.enrich_command(&mut cmd, &state).unwrap();
+
+        assert_eq!(
+            cmd.ref_changes
+                .iter()
+                .map(|change| change.new.as_str())
+                .collect::<Vec<_>>(),
+            vec![C, D]
+        );
+        assert_eq!(cursor.pending_cherry_pick_steps, 0);
+    }
+
     #[test]
     fn revert_span_starts_at_first_revert_when_expected_state_matches_second_revert() {
         let temp = tempfile::tempdir().unwrap();
@@ -377,7 +377,7 @@ fn rewrite_metric_attrs(
         batch_context.custom_attributes_json.as_deref(),
     );
 
-    attrs
+    attrs.with_current_identity()
 }
 
 fn apply_rewrite_metric_custom_attributes(
@@ -839,7 +839,8 @@ impl StreamWorker {
     }
 
     fn should_enqueue_shared_stream_immediately(tool: &str, stream: &StreamDescriptor) -> bool {
-        matches!(tool, "copilot" | "github-copilot") && stream.stream_kind == "otel_traces"
+        matches!(tool, "copilot" | "github-copilot" | "copilot-agent")
+            && stream.stream_kind == "otel_traces"
     }
 
     fn ensure_subagent_session(
@@ -532,6 +532,7 @@ async fn telemetry_flush_loop(
                 flush_telemetry_batch(snapshot, &daemon_id_for_flush)
             } else {
                 flush_pending_metrics();
+                flush_queued_cas();
                 Vec::new()
             };
             let await_status = collect_await_flush_status(flush_mode);
@@ -613,6 +614,7 @@ fn flush_telemetry_batch(batch: TelemetryBuffer, daemon_id: &str) -> Vec<DaemonL
     flush_notes();
 
     flush_pending_metrics();
+    flush_queued_cas();
 
     if batch.daemon_logs.is_empty() {
         Vec::new()
@@ -667,7 +669,8 @@ fn flush_metrics(events: &[MetricEvent]) {
     let api_base_url = context.base_url.clone();
     let client = ApiClient::new(context);
 
-    let should_upload = metrics_upload_allowed(&api_base_url, &client);
+    let should_upload = Config::fresh().telemetry().metrics.enabled
+        && metrics_upload_allowed(&api_base_url, &client);
     METRICS_UPLOAD_AVAILABLE.store(should_upload, Ordering::Relaxed);
 
     let mut upload_failed = false;
@@ -696,7 +699,8 @@ fn flush_pending_metrics() {
     let api_base_url = context.base_url.clone();
     let client = ApiClient::new(context);
 
-    let should_upload = metrics_upload_allowed(&api_base_url, &client);
+    let should_upload = Config::fresh().telemetry().metrics.enabled
+        && metrics_upload_allowed(&api_base_url, &client);
     METRICS_UPLOAD_AVAILABLE.store(should_upload, Ordering::Relaxed);
     if !should_upload {
         return;
@@ -740,6 +744,10 @@ fn flush_pending_metrics_from_db(
     client: &ApiClient,
     deadline: std::time::Instant,
 ) -> Result<PendingMetricsFlushResult, GitAiError> {
+    // Another process is already uploading; its rows will be delivered by it.
+    let Some(_flush_lock) = MetricsDatabase::try_acquire_flush_lock()? else {
+        return Ok(PendingMetricsFlushResult::default());
+    };
     flush_pending_metric_records_with(
         read_pending_metrics_batch,
         mark_metric_records_delivered,
@@ -916,7 +924,8 @@ fn current_unix_ts() -> u64 {
 }
 
 fn daemon_log_upload_enabled() -> bool {
-    Config::fresh().get_feature_flags().daemon_log_upload
+    let config = Config::fresh();
+    config.get_feature_flags().daemon_log_upload && config.telemetry().crash_reports.enabled
 }
 
 fn daemon_heartbeat_event(uptime: std::time::Duration) -> DaemonLogEvent {
@@ -1077,19 +1086,25 @@ fn flush_sentry_and_posthog(
     performances: &[PerformanceEvent],
     messages: &[MessageEvent],
 ) {
+    let telemetry = config.telemetry();
+
     // Check for Enterprise DSN
-    let enterprise_dsn = config
-        .telemetry_enterprise_dsn()
-        .map(|s| s.to_string())
-        .or_else(|| {
-            std::env::var("SENTRY_ENTERPRISE")
-                .ok()
-                .or_else(|| option_env!("SENTRY_ENTERPRISE").map(|s| s.to_string()))
-                .filter(|s| !s.is_empty())
-        });
+    let enterprise_dsn = if telemetry.crash_reports.enabled {
+        config
+            .telemetry_enterprise_dsn()
+            .map(|s| s.to_string())
+            .or_else(|| {
+                std::env::var("SENTRY_ENTERPRISE")
+                    .ok()
+                    .or_else(|| option_env!("SENTRY_ENTERPRISE").map(|s| s.to_string()))
+                    .filter(|s| !s.is_empty())
+            })
+    } else {
+        None
+    };
 
     // Check for OSS DSN
-    let oss_dsn = if config.is_telemetry_oss_disabled() {
+    let oss_dsn = if config.is_telemetry_oss_disabled() || !telemetry.crash_reports.enabled {
         None
     } else {
         std::env::var("SENTRY_OSS")
@@ -1099,7 +1114,7 @@ fn flush_sentry_and_posthog(
     };
 
     // Check for PostHog configuration
-    let posthog_api_key = if config.is_telemetry_oss_disabled() {
+    let posthog_api_key = if config.is_telemetry_oss_disabled() || !telemetry.metrics.enabled {
         None
     } else {
         std::env::var("POSTHOG_API_KEY")
@@ -1408,6 +1423,11 @@ fn count_pending_notes_for_await() -> usize {
 }
 
 fn flush_cas(records: Vec<CasSyncPayload>) {
+    if !Config::fresh().telemetry().prompts.enabled {
+        tracing::debug!("telemetry: skipping CAS flush, prompt uploads are off");
+        return;
+    }
+
     let context = ApiContext::new(None);
     let api_base_url = context.base_url.clone();
     let client = ApiClient::new(context);
@@ -1462,12 +1482,82 @@ fn flush_cas(records: Vec<CasSyncPayload>) {
                 tracing::debug!(count = chunk.len(), "telemetry: uploaded CAS objects");
             }
             Err(e) => {
-                tracing::warn!(%e, "telemetry: CAS upload error");
+                tracing::warn!(%e, "telemetry: CAS upload error, queueing for retry");
+                enqueue_failed_cas(chunk);
             }
         }
     }
 }
 
+/// Park CAS objects whose upload failed in the internal DB queue, so a degraded
+/// backend delays them instead of dropping them.
+fn enqueue_failed_cas(objects: &[CasObject]) {
+    let Ok(db) = crate::authorship::internal_db::InternalDatabase::global() else {
+        return;
+    };
+    let Ok(mut db_lock) = db.lock() else {
+        return;
+    };
+    for object in objects {
+        if let Err(e) = db_lock.enqueue_cas_object(&object.content, Some(&object.metadata)) {
+            tracing::warn!(%e, "telemetry: failed to queue CAS object");
+        }
+    }
+}
+
+/// Retry queued CAS uploads whose backoff has elapsed.
+fn flush_queued_cas() {
+    if !Config::fresh().telemetry().prompts.enabled {
+        return;
+    }
+    let Ok(db) = crate::authorship::internal_db::InternalDatabase::global() else {
+        return;
+    };
+    let records = match db.lock() {
+        Ok(mut db_lock) => db_lock.dequeue_cas_batch(50).unwrap_or_default(),
+        Err(_) => return,
+    };
+    if records.is_empty() {
+        return;
+    }
+
+    let context = ApiContext::new(None);
+    let using_default_api = context.base_url == crate::config::DEFAULT_API_BASE_URL;
+    let client = ApiClient::new(context);
+    if using_default_api && !client.is_logged_in() && !client.has_api_key() {
+        // Left locked; stale-lock recovery releases them again after 10 minutes.
+        return;
+    }
+    let objects = records
+        .iter()
+        .filter_map(|record| {
+            Some(CasObject {
+                content: serde_json::from_str(&record.data).ok()?,
+                hash: record.hash.clone(),
+                metadata: record.metadata.clone(),
+            })
+        })
+        .collect();
+    let result = client.upload_cas(CasUploadRequest { objects });
+
+    let Ok(mut db_lock) = db.lock() else {
+        return;
+    };
+    for record in &records {
+        let _ = match &result {
+            Ok(_) => db_lock.delete_cas_sync_record(record.id),
+            Err(e) => db_lock.update_cas_sync_failure(record.id, &e.to_string()),
+        };
+    }
+    match result {
+        Ok(_) => tracing::debug!(
+            count = records.len(),
+            "telemetry: uploaded queued CAS objects"
+        ),
+        Err(e) => tracing::debug!(%e, "telemetry: queued CAS upload failed"),
+    }
+}
+
 /// Minimal Sentry client (mirrors flush.rs SentryClient)
 struct SentryClient {
     endpoint: String,
@@ -29,6 +29,9 @@ pub fn tracks_primary_command_for_test_sync(
                 "add" | "remove" | "rm" | "rename" | "set-head" | "set-branches" | "set-url"
             )
         }),
+        "subtree" => invoked_args.first().is_some_and(|subcommand| {
+            matches!(subcommand.as_str(), "add" | "merge" | "pull" | "split")
+        }),
         "stash" => !invoked_args
             .first()
             .is_some_and(|subcommand| matches!(subcommand.as_str(), "list" | "show")),
@@ -0,0 +1,196 @@
+//! Per-hook timeouts for daemon side effects that talk to a remote.
+//!
+//! Notes sync after a fetch, pull, clone, or push runs in the family sequencer,
+//! so a hung remote would hold back every later command of the repository
+//! (and anything awaiting it). Each such side effect runs on its own thread
+//! under a timeout from the `hook_timeouts` config (milliseconds by hook name,
+//! `0` disables the timeout). When the timeout expires the work is cancelled:
+//! git children started through [`crate::git::repository::exec_git_cancellable`]
+//! are killed, a `hook_timeout` performance event is logged, and the work is
+//! recorded for `git-ai repair` instead of blocking the sequencer.
+
+use crate::error::GitAiError;
+use crate::git::repo_storage::{PendingRepairRecord, RepairKind};
+use crate::git::repository::Repository;
+use std::collections::HashMap;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Timeout for hooks without a `hook_timeouts` entry.
+pub const DEFAULT_HOOK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Result of [`run_with_timeout`].
+#[derive(Debug)]
+pub enum WatchdogOutcome<T> {
+    Completed(T),
+    /// The work was cancelled and may still be winding down on its thread.
+    TimedOut,
+    /// The work panicked, or its thread could not be started.
+    Failed(String),
+}
+
+/// The configured timeout for `hook`, or `None` when it is disabled.
+pub fn hook_timeout(hook: &str) -> Option<Duration> {
+    let config = crate::config::effective_file_config_cached();
+    timeout_from_config(
+        config.hook_timeouts.as_ref().unwrap_or(&HashMap::new()),
+        hook,
+    )
+}
+
+fn timeout_from_config(timeouts: &HashMap<String, u64>, hook: &str) -> Option<Duration> {
+    match timeouts.get(hook) {
+        Some(0) => None,
+        Some(ms) => Some(Duration::from_millis(*ms)),
+        None => Some(DEFAULT_HOOK_TIMEOUT),
+    }
+}
+
+/// Run `work` on a separate thread and wait at most `timeout` for it. On
+/// timeout the flag passed to `work` is set; the work is expected to check it
+/// before each step that changes state and stop there.
+pub fn run_with_timeout<T, F>(hook: &str, timeout: Option<Duration>, work: F) -> WatchdogOutcome<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&AtomicBool) -> T + Send + 'static,
+{
+    let Some(timeout) = timeout else {
+        return WatchdogOutcome::Completed(work(&AtomicBool::new(false)));
+    };
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let (tx, rx) = mpsc::channel();
+    let worker_cancelled = Arc::clone(&cancelled);
+    let spawned = std::thread::Builder::new()
+        .name(format!("git-ai-{}", hook))
+        .spawn(move || {
+            let result =
+                std::panic::catch_unwind(AssertUnwindSafe(|| work(worker_cancelled.as_ref())))
+                    .map_err(|payload| panic_message(payload.as_ref()));
+            let _ = tx.send(result);
+        });
+    if let Err(e) = spawned {
+        tracing::warn!(hook, %e, "failed to spawn hook thread");
+        return WatchdogOutcome::Failed(format!("failed to spawn hook thread: {}", e));
+    }
+
+    match rx.recv_timeout(timeout) {
+        Ok(Ok(result)) => WatchdogOutcome::Completed(result),
+        Ok(Err(message)) => WatchdogOutcome::Failed(format!("panicked: {}", message)),
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            cancelled.store(true, Ordering::Relaxed);
+            WatchdogOutcome::TimedOut
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            WatchdogOutcome::Failed("hook thread exited without a result".to_string())
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Run `kind` of work against `remote` under its hook timeout. A timed out run
+/// is logged and journaled for `git-ai repair` rather than returned as an error.
+pub fn run_deferrable<F>(
+    repo: &Repository,
+    kind: RepairKind,
+    remote: &str,
+    work: F,
+) -> Result<(), GitAiError>
+where
+    F: FnOnce(&AtomicBool) -> Result<(), GitAiError> + Send + 'static,
+{
+    let hook = kind.hook_name();
+    let timeout = hook_timeout(hook);
+    match run_with_timeout(hook, timeout, work) {
+        WatchdogOutcome::Completed(result) => result,
+        WatchdogOutcome::Failed(message) => Err(GitAiError::Generic(format!(
+            "{} for {} {}",
+            hook, remote, message
+        ))),
+        WatchdogOutcome::TimedOut => {
+            let waited = timeout.unwrap_or_default();
+            tracing::warn!(
+                hook,
+                remote = %remote,
+                timeout_ms = waited.as_millis() as u64,
+                "hook timed out; deferring to git-ai repair"
+            );
+            crate::observability::log_performance(
+                "hook_timeout",
+                waited,
+                Some(serde_json::json!({ "hook": hook })),
+                Some(HashMap::from([("hook".to_string(), hook.to_string())])),
+            );
+            repo.storage.append_pending_repair(&PendingRepairRecord {
+                kind,
+                remote: remote.to_string(),
+                commit_sha: None,
+                deferred_at: chrono::Utc::now().timestamp(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hook_timeouts_default_and_zero_disables() {
+        let timeouts = HashMap::from([
+            ("fetch_notes".to_string(), 1500),
+            ("push_notes".to_string(), 0),
+        ]);
+        assert_eq!(
+            timeout_from_config(&timeouts, "fetch_notes"),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(timeout_from_config(&timeouts, "push_notes"), None);
+        assert_eq!(
+            timeout_from_config(&timeouts, "other"),
+            Some(DEFAULT_HOOK_TIMEOUT)
+        );
+    }
+
+    #[test]
+    fn timed_out_work_is_cancelled() {
+        let (done_tx, done_rx) = mpsc::channel();
+        let outcome = run_with_timeout("test", Some(Duration::from_millis(20)), move |cancelled| {
+            while !cancelled.load(Ordering::Relaxed) {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            let _ = done_tx.send(());
+        });
+        assert!(matches!(outcome, WatchdogOutcome::TimedOut));
+        assert!(done_rx.recv_timeout(Duration::from_secs(5)).is_ok());
+
+        let outcome = run_with_timeout("test", Some(Duration::from_secs(5)), |_| 7);
+        assert!(matches!(outcome, WatchdogOutcome::Completed(7)));
+        assert!(matches!(
+            run_with_timeout("test", None, |_| 8),
+            WatchdogOutcome::Completed(8)
+        ));
+    }
+
+    #[test]
+    fn panicking_work_is_reported_as_failed() {
+        let outcome: WatchdogOutcome<()> =
+            run_with_timeout("test", Some(Duration::from_secs(5)), |_| panic!("boom"));
+        match outcome {
+            WatchdogOutcome::Failed(message) => assert!(message.contains("boom"), "{}", message),
+            other => panic!("expected Failed, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! Workspace roots: many checkouts served by the one daemon.
+//!
+//! The daemon is already a single process for every repository on the
+//! machine. `workspace_roots` lists directories whose git checkouts it should
+//! report on together: `git-ai bg status --workspace` discovers the checkouts
+//! under each root and returns every family's status in one response.
+//!
+//! With many checkouts, editors that fetch in the background make the daemon
+//! fetch notes after every `git fetch` of every repository.
+//! `repo_sync_min_interval_ms` throttles those per repository and remote: a
+//! notes fetch following `git fetch` within the interval of the previous
+//! successful one is skipped (`0` or unset disables the throttle). Notes
+//! fetches after `git pull` and `git clone` always run, since rebase
+//! attribution needs them, and count as the previous fetch.
+
+use crate::git::repository::Repository;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// How deep below a root checkouts are looked for.
+pub const MAX_DISCOVERY_DEPTH: usize = 4;
+
+/// Directories never descended into while discovering checkouts.
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target"];
+
+/// The configured workspace roots, with a leading `~/` expanded.
+pub fn workspace_roots() -> Vec<PathBuf> {
+    crate::config::effective_file_config_cached()
+        .workspace_roots
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|root| expand_home(root))
+        .collect()
+}
+
+fn expand_home(root: &str) -> PathBuf {
+    if let Some(rest) = root.strip_prefix("~/")
+        && let Some(home) = dirs::home_dir()
+    {
+        return home.join(rest);
+    }
+    PathBuf::from(root)
+}
+
+/// Checkouts under `roots`, sorted and without duplicates. A directory with a
+/// `.git` entry (a directory, or a file for linked worktrees) is a checkout
+/// and is not descended into further; hidden directories are skipped.
+pub fn discover_repositories(roots: &[PathBuf], max_depth: usize) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    for root in roots {
+        discover_into(root, max_depth, &mut found);
+    }
+    found.sort();
+    found.dedup();
+    found
+}
+
+fn discover_into(dir: &Path, depth_left: usize, found: &mut Vec<PathBuf>) {
+    if dir.join(".git").exists() {
+        found.push(dir.to_path_buf());
+        return;
+    }
+    if depth_left == 0 {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if !file_type.is_dir() {
+            continue;
+        }
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_ref()) {
+            continue;
+        }
+        discover_into(&entry.path(), depth_left - 1, found);
+    }
+}
+
+/// The configured minimum interval between notes fetches of one repository.
+pub fn repo_sync_min_interval() -> Option<Duration> {
+    crate::config::effective_file_config_cached()
+        .repo_sync_min_interval_ms
+        .filter(|ms| *ms > 0)
+        .map(Duration::from_millis)
+}
+
+/// Last-run times of throttled work, by key.
+#[derive(Debug, Default)]
+pub struct RepoThrottle {
+    last_run: Mutex<HashMap<String, Instant>>,
+}
+
+impl RepoThrottle {
+    /// Whether work for `key` may run at `now`: no interval is configured, or
+    /// the interval has passed since its last recorded run.
+    pub fn is_due(&self, key: &str, interval: Option<Duration>, now: Instant) -> bool {
+        let Some(interval) = interval else {
+            return true;
+        };
+        let Ok(last_run) = self.last_run.lock() else {
+            return true;
+        };
+        last_run
+            .get(key)
+            .is_none_or(|last| now.saturating_duration_since(*last) >= interval)
+    }
+
+    /// Record that work for `key` completed at `now`.
+    pub fn record(&self, key: &str, now: Instant) {
+        if let Ok(mut last_run) = self.last_run.lock() {
+            last_run.insert(key.to_string(), now);
+        }
+    }
+}
+
+fn notes_throttle() -> &'static RepoThrottle {
+    static THROTTLE: OnceLock<RepoThrottle> = OnceLock::new();
+    THROTTLE.get_or_init(RepoThrottle::default)
+}
+
+/// Worktrees of one repository share the budget.
+fn notes_sync_key(repo: &Repository, remote: &str) -> String {
+    let common_dir = std::fs::canonicalize(repo.common_dir())
+        .unwrap_or_else(|_| repo.common_dir().to_path_buf());
+    format!("{}\u{0}{}", common_dir.display(), remote)
+}
+
+/// Whether a background notes fetch of `remote` for `repo` is due under
+/// `repo_sync_min_interval_ms`.
+pub fn notes_sync_due(repo: &Repository, remote: &str) -> bool {
+    notes_throttle().is_due(
+        &notes_sync_key(repo, remote),
+        repo_sync_min_interval(),
+        Instant::now(),
+    )
+}
+
+/// Record a successful notes fetch of `remote` for `repo`.
+pub fn record_notes_sync(repo: &Repository, remote: &str) {
+    notes_throttle().record(&notes_sync_key(repo, remote), Instant::now());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discovers_checkouts_without_descending_into_them() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        for dir in [
+            "app/.git",
+            "app/vendor/lib/.git",
+            "group/service/.git",
+            "group/.hidden/repo/.git",
+            "group/node_modules/pkg/.git",
+            "deep/a/b/c/d/e/.git",
+        ] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        std::fs::create_dir_all(root.join("linked")).unwrap();
+        std::fs::write(
+            root.join("linked/.git"),
+            "gitdir: ../app/.git/worktrees/l\n",
+        )
+        .unwrap();
+
+        let found = discover_repositories(&[root.to_path_buf()], MAX_DISCOVERY_DEPTH);
+        assert_eq!(
+            found,
+            vec![
+                root.join("app"),
+                root.join("group/service"),
+                root.join("linked"),
+            ]
+        );
+    }
+
+    #[test]
+    fn throttle_skips_work_within_the_interval_of_the_last_run() {
+        let throttle = RepoThrottle::default();
+        let start = Instant::now();
+        let interval = Some(Duration::from_secs(60));
+
+        assert!(throttle.is_due("a", interval, start));
+        // Nothing recorded yet, e.g. the first fetch failed.
+        assert!(throttle.is_due("a", interval, start + Duration::from_secs(1)));
+
+        throttle.record("a", start);
+        assert!(!throttle.is_due("a", interval, start + Duration::from_secs(30)));
+        assert!(throttle.is_due("b", interval, start + Duration::from_secs(30)));
+        assert!(throttle.is_due("a", interval, start + Duration::from_secs(61)));
+        assert!(throttle.is_due("a", None, start + Duration::from_secs(30)));
+    }
+}
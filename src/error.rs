@@ -1,5 +1,88 @@
+//! The crate-wide error type and the CLI exit codes derived from it.
+//!
+//! Every [`GitAiError`] belongs to an [`ErrorCategory`], and commands that fail
+//! exit with the category's code so scripts and CI can branch on the kind of
+//! failure without parsing messages:
+//!
+//! | Code | Category  | Meaning                                                   |
+//! |------|-----------|-----------------------------------------------------------|
+//! | 1    | `internal`| Unclassified failure                                      |
+//! | 3    | `git`     | A git invocation failed                                   |
+//! | 4    | `storage` | Local state (working logs, databases, files) unreadable   |
+//! | 5    | `notes`   | An authorship note is missing, corrupt or unsupported     |
+//! | 6    | `hook`    | Installing or running git-ai hooks failed                 |
+//! | 7    | `sync`    | Pushing or fetching notes failed                          |
+//! | 8    | `auth`    | Not logged in, or the server rejected the credentials     |
+//! | 9    | `policy`  | Refused by a workspace or organization policy             |
+//!
+//! With `--json`, the failure is also printed on stdout as
+//! `{"error": {"category": ..., "exit_code": ..., "message": ...}}`.
+//!
+//! Commands report failures this way through
+//! [`OutputOptions::exit_with_error`](crate::commands::output::OutputOptions::exit_with_error)
+//! (`stats`, `status`, `timeline`, `resolve`, `backfill`) or exit with
+//! [`GitAiError::exit_code`] after their own message (`blame`, `diff`,
+//! `install-hooks`, `uninstall-hooks`). Other commands, and usage errors in
+//! any command, still exit with 1.
+
+use serde::Serialize;
 use std::fmt;
 
+/// Failure category of a [`GitAiError`], see the module docs for exit codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    Internal,
+    Git,
+    Storage,
+    Notes,
+    Hook,
+    Sync,
+    Auth,
+    Policy,
+}
+
+impl ErrorCategory {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorCategory::Internal => 1,
+            ErrorCategory::Git => 3,
+            ErrorCategory::Storage => 4,
+            ErrorCategory::Notes => 5,
+            ErrorCategory::Hook => 6,
+            ErrorCategory::Sync => 7,
+            ErrorCategory::Auth => 8,
+            ErrorCategory::Policy => 9,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCategory::Internal => "internal",
+            ErrorCategory::Git => "git",
+            ErrorCategory::Storage => "storage",
+            ErrorCategory::Notes => "notes",
+            ErrorCategory::Hook => "hook",
+            ErrorCategory::Sync => "sync",
+            ErrorCategory::Auth => "auth",
+            ErrorCategory::Policy => "policy",
+        }
+    }
+}
+
+/// The `--json` error document: `{"error": {...}}`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorPayload {
+    pub error: ErrorPayloadBody,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorPayloadBody {
+    pub category: ErrorCategory,
+    pub exit_code: i32,
+    pub message: String,
+}
+
 #[derive(Debug)]
 pub enum GitAiError {
     IoError(std::io::Error),
@@ -16,9 +99,58 @@ pub enum GitAiError {
     FromUtf8Error(std::string::FromUtf8Error),
     PresetError(String),
     SqliteError(rusqlite::Error),
+    /// Working logs, internal databases or other local state.
+    Storage(String),
+    /// Authorship notes: missing, unparsable or of an unsupported version.
+    Notes(String),
+    /// Installing, removing or running git-ai hooks.
+    Hook(String),
+    /// Pushing or fetching notes to or from a remote.
+    Sync(String),
+    /// Missing or rejected credentials.
+    Auth(String),
+    /// An operation refused by workspace or organization policy.
+    Policy(String),
     Generic(String),
 }
 
+impl GitAiError {
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            GitAiError::GitCliError { .. } | GitAiError::GixError(_) => ErrorCategory::Git,
+            GitAiError::IoError(_) | GitAiError::SqliteError(_) | GitAiError::Storage(_) => {
+                ErrorCategory::Storage
+            }
+            GitAiError::Notes(_) => ErrorCategory::Notes,
+            GitAiError::Hook(_) => ErrorCategory::Hook,
+            GitAiError::Sync(_) => ErrorCategory::Sync,
+            GitAiError::Auth(_) => ErrorCategory::Auth,
+            GitAiError::Policy(_) => ErrorCategory::Policy,
+            GitAiError::JsonError(_)
+            | GitAiError::Utf8Error(_)
+            | GitAiError::FromUtf8Error(_)
+            | GitAiError::PresetError(_)
+            | GitAiError::Generic(_) => ErrorCategory::Internal,
+        }
+    }
+
+    /// The documented CLI exit code for this error.
+    pub fn exit_code(&self) -> i32 {
+        self.category().exit_code()
+    }
+
+    pub fn payload(&self) -> ErrorPayload {
+        let category = self.category();
+        ErrorPayload {
+            error: ErrorPayloadBody {
+                category,
+                exit_code: category.exit_code(),
+                message: self.to_string(),
+            },
+        }
+    }
+}
+
 impl fmt::Display for GitAiError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -38,6 +170,12 @@ impl fmt::Display for GitAiError {
             GitAiError::FromUtf8Error(e) => write!(f, "From UTF-8 error: {}", e),
             GitAiError::PresetError(e) => write!(f, "{}", e),
             GitAiError::SqliteError(e) => write!(f, "SQLite error: {}", e),
+            GitAiError::Storage(e) => write!(f, "Storage error: {}", e),
+            GitAiError::Notes(e) => write!(f, "Notes error: {}", e),
+            GitAiError::Hook(e) => write!(f, "Hook error: {}", e),
+            GitAiError::Sync(e) => write!(f, "Sync error: {}", e),
+            GitAiError::Auth(e) => write!(f, "Authentication error: {}", e),
+            GitAiError::Policy(e) => write!(f, "Policy violation: {}", e),
             GitAiError::Generic(e) => write!(f, "Generic error: {}", e),
             GitAiError::GixError(e) => write!(f, "Gix error: {}", e),
         }
@@ -91,9 +229,16 @@ impl Clone for GitAiError {
             GitAiError::Utf8Error(e) => GitAiError::Utf8Error(*e),
             GitAiError::FromUtf8Error(e) => GitAiError::FromUtf8Error(e.clone()),
             GitAiError::PresetError(s) => GitAiError::PresetError(s.clone()),
-            GitAiError::SqliteError(e) => GitAiError::Generic(format!("SQLite error: {}", e)),
+            // rusqlite errors cannot be cloned; keep the storage category.
+            GitAiError::SqliteError(e) => GitAiError::Storage(format!("SQLite: {}", e)),
+            GitAiError::Storage(s) => GitAiError::Storage(s.clone()),
+            GitAiError::Notes(s) => GitAiError::Notes(s.clone()),
+            GitAiError::Hook(s) => GitAiError::Hook(s.clone()),
+            GitAiError::Sync(s) => GitAiError::Sync(s.clone()),
+            GitAiError::Auth(s) => GitAiError::Auth(s.clone()),
+            GitAiError::Policy(s) => GitAiError::Policy(s.clone()),
             GitAiError::Generic(s) => GitAiError::Generic(s.clone()),
-            GitAiError::GixError(e) => GitAiError::Generic(format!("Gix error: {}", e)),
+            GitAiError::GixError(e) => GitAiError::GixError(e.clone()),
         }
     }
 }
@@ -270,32 +415,70 @@ mod tests {
         let err = GitAiError::from(json_err);
         let cloned = err.clone();
         assert!(matches!(cloned, GitAiError::Generic(_)));
+        assert_eq!(cloned.category(), err.category());
         let display = format!("{}", cloned);
         assert!(display.contains("JSON error"));
     }
 
     #[test]
-    fn test_error_clone_sqlite_converts_to_generic() {
+    fn test_error_clone_sqlite_converts_to_storage() {
         let temp_dir = tempfile::tempdir().unwrap();
         let db_path = temp_dir.path().join("error.db");
         let conn = crate::sqlite::open_with_memory_limits(&db_path).unwrap();
         let sql_err = conn.execute("BAD SQL", []).unwrap_err();
         let err = GitAiError::from(sql_err);
         let cloned = err.clone();
-        assert!(matches!(cloned, GitAiError::Generic(_)));
+        assert!(matches!(cloned, GitAiError::Storage(_)));
+        assert_eq!(cloned.category(), err.category());
         let display = format!("{}", cloned);
-        assert!(display.contains("SQLite error"));
+        assert!(display.contains("SQLite"));
     }
 
     #[test]
-    fn test_error_clone_gix_converts_to_generic() {
+    fn test_error_clone_gix_error() {
         let err = GitAiError::GixError("gix error".to_string());
         let cloned = err.clone();
-        assert!(matches!(cloned, GitAiError::Generic(_)));
+        assert!(matches!(cloned, GitAiError::GixError(_)));
+        assert_eq!(cloned.category(), ErrorCategory::Git);
         let display = format!("{}", cloned);
         assert!(display.contains("Gix error"));
     }
 
+    #[test]
+    fn test_error_categories_map_to_documented_exit_codes() {
+        let io_err = GitAiError::from(std::io::Error::other("disk"));
+        assert_eq!(io_err.category(), ErrorCategory::Storage);
+        assert_eq!(io_err.exit_code(), 4);
+        assert_eq!(
+            GitAiError::GixError("bad object".to_string()).exit_code(),
+            3
+        );
+        assert_eq!(GitAiError::Notes("corrupt".to_string()).exit_code(), 5);
+        assert_eq!(GitAiError::Hook("exists".to_string()).exit_code(), 6);
+        assert_eq!(GitAiError::Sync("rejected".to_string()).exit_code(), 7);
+        assert_eq!(GitAiError::Auth("Unauthorized".to_string()).exit_code(), 8);
+        assert_eq!(GitAiError::Policy("blocked".to_string()).exit_code(), 9);
+        assert_eq!(GitAiError::Generic("other".to_string()).exit_code(), 1);
+    }
+
+    #[test]
+    fn test_error_payload_is_machine_readable() {
+        let err = GitAiError::Auth("Unauthorized".to_string());
+        let json = serde_json::to_value(err.payload()).unwrap();
+        assert_eq!(json["error"]["category"], "auth");
+        assert_eq!(json["error"]["exit_code"], 8);
+        assert_eq!(
+            json["error"]["message"],
+            "Authentication error: Unauthorized"
+        );
+    }
+
+    #[test]
+    fn test_error_clone_keeps_category() {
+        let err = GitAiError::Sync("push rejected".to_string());
+        assert!(matches!(err.clone(), GitAiError::Sync(msg) if msg == "push rejected"));
+    }
+
     #[test]
     fn test_error_is_std_error() {
         let err = GitAiError::Generic("test".to_string());
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+pub mod remote;
+
 /// Parse a boolean from an environment variable string.
 /// Accepts: "1", "true", "True", "TRUE" → Some(true)
 ///          "0", "false", "False", "FALSE" → Some(false)
@@ -85,6 +87,8 @@ define_feature_flags!(
     bash_checkpoints_v2: bash_checkpoints_v2, debug = false, release = false,
     daemon_log_upload: daemon_log_upload, debug = true, release = true,
     rewrite_metrics_events: rewrite_metrics_events, debug = true, release = false,
+    ai_paste_detection: ai_paste_detection, debug = false, release = false,
+    formatter_detection: formatter_detection, debug = true, release = true,
 );
 
 impl FeatureFlags {
@@ -105,13 +109,29 @@ impl FeatureFlags {
 
     /// Build FeatureFlags from both file and environment variables
     /// Precedence: Environment > File > Default
+    #[allow(dead_code)]
+    pub(crate) fn from_env_and_file(file_flags: Option<DeserializableFeatureFlags>) -> Self {
+        Self::from_sources(None, file_flags)
+    }
+
+    /// Build FeatureFlags from the remote document, file and environment variables
+    /// Precedence: Environment > File > Remote > Default
     /// - Starts with defaults
+    /// - Applies the cached remote (org rollout) document if present
     /// - Applies file config overrides if present
     /// - Applies environment variable overrides if present (highest priority)
-    pub(crate) fn from_env_and_file(file_flags: Option<DeserializableFeatureFlags>) -> Self {
+    pub(crate) fn from_sources(
+        remote_flags: Option<DeserializableFeatureFlags>,
+        file_flags: Option<DeserializableFeatureFlags>,
+    ) -> Self {
         // Start with defaults
         let mut result = FeatureFlags::default();
 
+        // Apply remote rollout overrides
+        if let Some(remote) = remote_flags {
+            result = Self::merge_with(result, remote);
+        }
+
         // Apply file config overrides
         if let Some(file) = file_flags {
             result = Self::merge_with(result, file);
@@ -141,6 +161,8 @@ mod tests {
             assert!(!flags.bash_checkpoints_v2);
             assert!(flags.daemon_log_upload);
             assert!(flags.rewrite_metrics_events);
+            assert!(!flags.ai_paste_detection);
+            assert!(flags.formatter_detection);
         }
         #[cfg(not(debug_assertions))]
         {
@@ -151,6 +173,8 @@ mod tests {
             assert!(!flags.bash_checkpoints_v2);
             assert!(flags.daemon_log_upload);
             assert!(!flags.rewrite_metrics_events);
+            assert!(!flags.ai_paste_detection);
+            assert!(flags.formatter_detection);
         }
     }
 
@@ -230,6 +254,49 @@ mod tests {
         }
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn test_file_overrides_remote_flags() {
+        unsafe {
+            std::env::remove_var("GIT_AI_AUTH_KEYRING");
+            std::env::remove_var("GIT_AI_REWRITE_METRICS_EVENTS");
+        }
+
+        let remote_flags = DeserializableFeatureFlags {
+            auth_keyring: Some(true),
+            rewrite_metrics_events: Some(true),
+            ..Default::default()
+        };
+        let file_flags = DeserializableFeatureFlags {
+            rewrite_metrics_events: Some(false),
+            ..Default::default()
+        };
+
+        let flags = FeatureFlags::from_sources(Some(remote_flags), Some(file_flags));
+        assert!(flags.auth_keyring);
+        assert!(!flags.rewrite_metrics_events);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_env_overrides_remote_flags() {
+        unsafe {
+            std::env::set_var("GIT_AI_AUTH_KEYRING", "false");
+        }
+
+        let remote_flags = DeserializableFeatureFlags {
+            auth_keyring: Some(true),
+            ..Default::default()
+        };
+
+        let flags = FeatureFlags::from_sources(Some(remote_flags), None);
+        assert!(!flags.auth_keyring);
+
+        unsafe {
+            std::env::remove_var("GIT_AI_AUTH_KEYRING");
+        }
+    }
+
     #[test]
     fn test_serialization() {
         let flags = FeatureFlags {
@@ -240,6 +307,8 @@ mod tests {
             bash_checkpoints_v2: true,
             daemon_log_upload: true,
             rewrite_metrics_events: true,
+            ai_paste_detection: true,
+            formatter_detection: true,
         };
 
         let serialized = serde_json::to_string(&flags).unwrap();
@@ -250,6 +319,8 @@ mod tests {
         assert!(serialized.contains("bash_checkpoints_v2"));
         assert!(serialized.contains("daemon_log_upload"));
         assert!(serialized.contains("rewrite_metrics_events"));
+        assert!(serialized.contains("ai_paste_detection"));
+        assert!(serialized.contains("formatter_detection"));
     }
 
     #[test]
@@ -262,6 +333,8 @@ mod tests {
             bash_checkpoints_v2: true,
             daemon_log_upload: true,
             rewrite_metrics_events: true,
+            ai_paste_detection: true,
+            formatter_detection: true,
         };
         let cloned = flags.clone();
         assert_eq!(cloned.auth_keyring, flags.auth_keyring);
@@ -271,6 +344,8 @@ mod tests {
         assert_eq!(cloned.bash_checkpoints_v2, flags.bash_checkpoints_v2);
         assert_eq!(cloned.daemon_log_upload, flags.daemon_log_upload);
         assert_eq!(cloned.rewrite_metrics_events, flags.rewrite_metrics_events);
+        assert_eq!(cloned.ai_paste_detection, flags.ai_paste_detection);
+        assert_eq!(cloned.formatter_detection, flags.formatter_detection);
     }
 
     #[test]
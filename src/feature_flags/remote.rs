@@ -0,0 +1,210 @@
+//! Feature flags rolled out by an organization through the git-ai API.
+//!
+//! The flag document is fetched periodically (by the daemon) and cached on disk
+//! at `~/.git-ai/internal/remote_feature_flags.json`. Config building only reads
+//! the cached copy, and remote values sit below the config file and `GIT_AI_*`
+//! environment overrides, so a local setting always wins.
+
+use super::DeserializableFeatureFlags;
+use crate::api::{ApiClient, ApiContext};
+use crate::error::GitAiError;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Endpoint serving the organization's feature flag document.
+const REMOTE_FEATURE_FLAGS_ENDPOINT: &str = "/worker/feature-flags";
+
+/// Default refresh cadence when the server does not specify one.
+const DEFAULT_FLAGS_REFRESH_INTERVAL_SECS: u64 = 3600;
+
+/// Lower bound on the server-provided refresh interval to avoid hammering the endpoint.
+const MIN_FLAGS_REFRESH_INTERVAL_SECS: u64 = 300;
+
+/// Flag document served by the feature flags endpoint.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RemoteFeatureFlags {
+    /// Flag values keyed by their config file name (e.g. `rewrite_metrics_events`).
+    /// Unknown names are ignored so the server can stage flags ahead of a release.
+    #[serde(default)]
+    pub flags: serde_json::Map<String, serde_json::Value>,
+    /// Server-suggested refresh interval in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_interval_secs: Option<u64>,
+}
+
+impl RemoteFeatureFlags {
+    fn refresh_interval(&self) -> Duration {
+        Duration::from_secs(
+            self.refresh_interval_secs
+                .unwrap_or(DEFAULT_FLAGS_REFRESH_INTERVAL_SECS)
+                .max(MIN_FLAGS_REFRESH_INTERVAL_SECS),
+        )
+    }
+
+    /// Known flags from the document; values that are not booleans are dropped
+    /// individually rather than discarding the whole document.
+    pub(crate) fn to_overrides(&self) -> DeserializableFeatureFlags {
+        let flags: serde_json::Map<String, serde_json::Value> = self
+            .flags
+            .iter()
+            .filter(|(_, value)| value.is_boolean())
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        serde_json::from_value(serde_json::Value::Object(flags)).unwrap_or_default()
+    }
+}
+
+/// On-disk cache entry for the remote flag document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedRemoteFeatureFlags {
+    fetched_at: u64,
+    document: RemoteFeatureFlags,
+}
+
+/// Result of a remote flag refresh attempt.
+#[derive(Debug, PartialEq, Eq)]
+pub enum FlagsRefreshOutcome {
+    /// The cached document is still fresh; no request was made.
+    NotDue,
+    /// No credentials are available, so there are no org flags to fetch.
+    NotAuthenticated,
+    /// The document was fetched and cached.
+    Updated,
+}
+
+/// Returns the path to the remote flag cache (~/.git-ai/internal/remote_feature_flags.json)
+pub fn remote_feature_flags_path() -> Option<PathBuf> {
+    crate::config::internal_dir_path().map(|dir| dir.join("remote_feature_flags.json"))
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_secs(0))
+        .as_secs()
+}
+
+fn read_cached_flags() -> Option<CachedRemoteFeatureFlags> {
+    let path = remote_feature_flags_path()?;
+    let bytes = fs::read(path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+fn write_cached_flags(cached: &CachedRemoteFeatureFlags) -> Result<(), GitAiError> {
+    let path = remote_feature_flags_path().ok_or_else(|| {
+        GitAiError::Generic("Could not determine remote feature flags path".to_string())
+    })?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_vec_pretty(cached)?;
+    // Write to a temp file and rename so concurrent readers never observe a torn file.
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, json)?;
+    fs::rename(&tmp_path, &path)?;
+    Ok(())
+}
+
+/// Load the cached remote flag document, if one has been fetched.
+pub fn load_remote_feature_flags() -> Option<RemoteFeatureFlags> {
+    read_cached_flags().map(|cached| cached.document)
+}
+
+fn is_refresh_due(cached: Option<&CachedRemoteFeatureFlags>, now: u64) -> bool {
+    match cached {
+        Some(cached) => {
+            now.saturating_sub(cached.fetched_at) >= cached.document.refresh_interval().as_secs()
+        }
+        None => true,
+    }
+}
+
+/// Fetch the remote flag document when the cached copy is stale.
+///
+/// Intended for background callers (the daemon's periodic loop); never call this
+/// from checkpoint or hook paths.
+pub fn refresh_remote_feature_flags_if_due() -> Result<FlagsRefreshOutcome, GitAiError> {
+    if !is_refresh_due(read_cached_flags().as_ref(), current_timestamp()) {
+        return Ok(FlagsRefreshOutcome::NotDue);
+    }
+    refresh_remote_feature_flags()
+}
+
+/// Fetch the remote flag document unconditionally and cache it.
+pub fn refresh_remote_feature_flags() -> Result<FlagsRefreshOutcome, GitAiError> {
+    let client = ApiClient::new(ApiContext::new(None));
+    if !client.is_logged_in() && !client.has_api_key() {
+        return Ok(FlagsRefreshOutcome::NotAuthenticated);
+    }
+
+    let document = client.fetch_remote_feature_flags()?;
+    write_cached_flags(&CachedRemoteFeatureFlags {
+        fetched_at: current_timestamp(),
+        document,
+    })?;
+    Ok(FlagsRefreshOutcome::Updated)
+}
+
+/// Remote feature flag API endpoints
+impl ApiClient {
+    /// Fetch the organization's feature flag document.
+    ///
+    /// A 404 means the organization has not staged any flags, which is treated
+    /// as an empty document so a rollback clears previously cached values.
+    pub fn fetch_remote_feature_flags(&self) -> Result<RemoteFeatureFlags, GitAiError> {
+        let response = self.context().get(REMOTE_FEATURE_FLAGS_ENDPOINT)?;
+        match response.status_code {
+            200 => {
+                let body = response.as_str().map_err(|e| {
+                    GitAiError::Generic(format!("Failed to read response body: {}", e))
+                })?;
+                serde_json::from_str(body).map_err(GitAiError::JsonError)
+            }
+            404 => Ok(RemoteFeatureFlags::default()),
+            401 => Err(GitAiError::Auth("Unauthorized".to_string())),
+            status => Err(GitAiError::Generic(format!(
+                "Feature flags request failed with status {}",
+                status
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_ignores_unknown_and_non_boolean_flags() {
+        let document: RemoteFeatureFlags = serde_json::from_str(
+            r#"{"flags":{"rewrite_metrics_events":true,"auth_keyring":"yes","not_a_flag":true}}"#,
+        )
+        .unwrap();
+        let overrides = document.to_overrides();
+        assert_eq!(overrides.rewrite_metrics_events, Some(true));
+        assert_eq!(overrides.auth_keyring, None);
+    }
+
+    #[test]
+    fn test_refresh_due_without_cache() {
+        assert!(is_refresh_due(None, 1_000));
+    }
+
+    #[test]
+    fn test_refresh_respects_server_interval_with_minimum() {
+        let cached = CachedRemoteFeatureFlags {
+            fetched_at: 1_000,
+            document: RemoteFeatureFlags {
+                refresh_interval_secs: Some(1),
+                ..Default::default()
+            },
+        };
+        assert!(!is_refresh_due(Some(&cached), 1_010));
+        assert!(is_refresh_due(
+            Some(&cached),
+            1_000 + MIN_FLAGS_REFRESH_INTERVAL_SECS
+        ));
+    }
+}
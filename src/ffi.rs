@@ -0,0 +1,112 @@
+//! Library entry points for the C ABI in `bindings/ffi`.
+//!
+//! Available with the `ffi` feature. Each call opens the repository at
+//! `repo_path` and returns plain serializable data; the C layer wraps results
+//! in [`json_envelope`] so the Python and Node packages only decode JSON.
+
+use crate::authorship::ignore::effective_ignore_patterns;
+use crate::authorship::stats::{CommitStats, stats_for_commit_stats};
+use crate::commands::blame::{GitAiBlameOptions, JsonBlameRange};
+use crate::error::GitAiError;
+use crate::git::repository::find_repository_in_path;
+use crate::workspace_config::effective_workspace_config;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Version of the envelope and payload shapes, bumped on breaking changes.
+pub const FFI_ABI_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BlameFileOptions {
+    /// 1-indexed inclusive `[start, end]` line ranges; empty blames the whole file.
+    pub line_ranges: Vec<(u32, u32)>,
+    /// Blame as of this revision instead of HEAD.
+    pub newest_commit: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileBlame {
+    pub file_path: String,
+    pub ranges: Vec<JsonBlameRange>,
+}
+
+/// Stats for `commit` (HEAD when `None`), as printed by `git-ai stats --json`.
+pub fn get_commit_stats(repo_path: &str, commit: Option<&str>) -> Result<CommitStats, GitAiError> {
+    let repo = find_repository_in_path(repo_path)?;
+    let commit_sha = repo
+        .revparse_single(commit.unwrap_or("HEAD"))?
+        .peel_to_commit()?
+        .id();
+    let ignore_patterns = effective_ignore_patterns(&repo, &[], &[]);
+    let whitespace = effective_workspace_config(&repo).whitespace_mode();
+    stats_for_commit_stats(&repo, &commit_sha, &ignore_patterns, whitespace)
+}
+
+/// Options as passed over the C ABI, e.g. `{"line_ranges":[[1,40]]}`.
+pub fn parse_blame_file_options(json: &str) -> Result<BlameFileOptions, GitAiError> {
+    serde_json::from_str(json)
+        .map_err(|e| GitAiError::Generic(format!("invalid blame options: {}", e)))
+}
+
+/// Attribution ranges for `file_path`, relative to the repository root.
+pub fn blame_file(
+    repo_path: &str,
+    file_path: &str,
+    options: &BlameFileOptions,
+) -> Result<FileBlame, GitAiError> {
+    let repo = find_repository_in_path(repo_path)?;
+    let blame_options = GitAiBlameOptions {
+        line_ranges: options.line_ranges.clone(),
+        newest_commit: options.newest_commit.clone(),
+        ..GitAiBlameOptions::default()
+    };
+    Ok(FileBlame {
+        file_path: file_path.to_string(),
+        ranges: repo.blame_ranges(file_path, &blame_options)?,
+    })
+}
+
+/// `{"ok":true,"data":...}` on success, `{"ok":false,"error":"..."}` otherwise.
+pub fn json_envelope<T: Serialize>(result: Result<T, GitAiError>) -> String {
+    let data = result.and_then(|data| serde_json::to_value(data).map_err(GitAiError::from));
+    let value = match data {
+        Ok(data) => json!({"ok": true, "data": data}),
+        Err(e) => json!({"ok": false, "error": e.to_string()}),
+    };
+    value.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_envelope_and_blame_options() {
+        let ok = json_envelope(Ok(CommitStats {
+            ai_additions: 3,
+            ..Default::default()
+        }));
+        let ok: serde_json::Value = serde_json::from_str(&ok).unwrap();
+        assert_eq!(ok["ok"], true);
+        assert_eq!(ok["data"]["ai_additions"], 3);
+
+        let err = json_envelope::<CommitStats>(Err(GitAiError::Generic("no repo".to_string())));
+        let err: serde_json::Value = serde_json::from_str(&err).unwrap();
+        assert_eq!(err["ok"], false);
+        assert!(err["error"].as_str().unwrap().contains("no repo"));
+
+        let options =
+            parse_blame_file_options(r#"{"line_ranges": [[1, 10]], "newest_commit": "main"}"#)
+                .unwrap();
+        assert_eq!(options.line_ranges, vec![(1, 10)]);
+        assert_eq!(options.newest_commit.as_deref(), Some("main"));
+        assert!(
+            parse_blame_file_options("{}")
+                .unwrap()
+                .line_ranges
+                .is_empty()
+        );
+        assert!(parse_blame_file_options(r#"{"line_ranges": "1-10"}"#).is_err());
+    }
+}
@@ -30,7 +30,9 @@ pub async fn load_ai_touched_files_for_commits(
         let mut blob_oids: Vec<String> = unique_blob_oids.into_iter().collect();
         blob_oids.sort();
 
-        let blob_contents = batch_read_blobs_with_oids(&repo.global_args_for_exec(), &blob_oids)?;
+        let mut blob_contents =
+            batch_read_blobs_with_oids(&repo.global_args_for_exec(), &blob_oids)?;
+        crate::git::note_overflow::resolve_stubs(&repo.global_args_for_exec(), &mut blob_contents);
 
         let mut all_files = HashSet::new();
         for blob_oid in note_blob_map.into_values() {
@@ -105,7 +107,7 @@ pub(crate) fn batch_read_blobs_with_oids(
     let stdin_data = blob_oids.join("\n") + "\n";
     let output = exec_git_stdin(&args, stdin_data.as_bytes())?;
 
-    let results = parse_cat_file_batch_output_with_oids(&output.stdout)?;
+    let mut results = parse_cat_file_batch_output_with_oids(&output.stdout)?;
     for oid in blob_oids {
         if !results.contains_key(oid) {
             return Err(GitAiError::Generic(format!(
@@ -236,6 +236,14 @@ pub fn rebase_has_control_mode(command_args: &[String]) -> bool {
     summarize_rebase_args(command_args).is_control_mode
 }
 
+/// True when the rebase runs `--exec` commands after its picks.
+pub fn rebase_has_exec(command_args: &[String]) -> bool {
+    command_args
+        .iter()
+        .take_while(|arg| *arg != "--")
+        .any(|arg| arg == "-x" || arg == "--exec" || arg.starts_with("--exec="))
+}
+
 pub fn explicit_rebase_branch_arg(command_args: &[String]) -> Option<String> {
     let summary = summarize_rebase_args(command_args);
     if summary.is_control_mode {
@@ -249,6 +257,128 @@ pub fn explicit_rebase_branch_arg(command_args: &[String]) -> Option<String> {
     }
 }
 
+/// What a `git commit` invocation will do, as far as its arguments tell.
+///
+/// Status output formats (`--short`, `--porcelain`, `--long`, `-z`) imply
+/// `--dry-run`, so those invocations never create or amend a commit.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitArgsSummary {
+    pub dry_run: bool,
+    pub amend: bool,
+    /// `--no-verify` / `-n`: the pre-commit and commit-msg hooks are skipped.
+    /// A later `--verify` turns them back on, as in git.
+    pub no_verify: bool,
+    pub all: bool,
+    pub include: bool,
+    pub only: bool,
+    pub pathspecs: Vec<String>,
+}
+
+impl CommitArgsSummary {
+    /// True when the invocation creates a new commit or amends HEAD.
+    pub fn creates_commit(&self) -> bool {
+        !self.dry_run
+    }
+}
+
+/// Long `git commit` options whose value may be given as the next argument.
+fn commit_long_option_takes_value(option: &str) -> bool {
+    matches!(
+        option,
+        "--message"
+            | "--file"
+            | "--reuse-message"
+            | "--reedit-message"
+            | "--fixup"
+            | "--squash"
+            | "--author"
+            | "--date"
+            | "--template"
+            | "--cleanup"
+            | "--trailer"
+            | "--pathspec-from-file"
+    )
+}
+
+/// Short `git commit` options that take a value, attached or as the next argument.
+fn commit_short_option_takes_value(option: char) -> bool {
+    matches!(option, 'm' | 'F' | 'C' | 'c' | 't')
+}
+
+pub fn summarize_commit_args(command_args: &[String]) -> CommitArgsSummary {
+    let mut summary = CommitArgsSummary::default();
+    let mut explicit_dry_run = false;
+    let mut status_format = false;
+    let mut i = 0usize;
+
+    while i < command_args.len() {
+        let arg = command_args[i].as_str();
+        i += 1;
+
+        if arg == "--" {
+            summary.pathspecs.extend(command_args[i..].iter().cloned());
+            break;
+        }
+
+        if let Some(long) = arg.strip_prefix("--") {
+            let name = long.split('=').next().unwrap_or_default();
+            match name {
+                "dry-run" => explicit_dry_run = true,
+                "no-dry-run" => explicit_dry_run = false,
+                "short" | "porcelain" | "long" | "null" => status_format = true,
+                "amend" => summary.amend = true,
+                "no-amend" => summary.amend = false,
+                "no-verify" => summary.no_verify = true,
+                "verify" => summary.no_verify = false,
+                "all" => summary.all = true,
+                "include" => summary.include = true,
+                "only" => summary.only = true,
+                _ => {
+                    if !long.contains('=') && commit_long_option_takes_value(&format!("--{name}")) {
+                        i += 1;
+                    }
+                }
+            }
+            continue;
+        }
+
+        if let Some(cluster) = arg.strip_prefix('-')
+            && !cluster.is_empty()
+        {
+            for (offset, option) in cluster.char_indices() {
+                match option {
+                    'a' => summary.all = true,
+                    'i' => summary.include = true,
+                    'n' => summary.no_verify = true,
+                    'o' => summary.only = true,
+                    'z' => status_format = true,
+                    // -S<keyid> and -u<mode> only take attached values.
+                    'S' | 'u' => break,
+                    _ if commit_short_option_takes_value(option) => {
+                        if offset + option.len_utf8() == cluster.len() {
+                            i += 1;
+                        }
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            continue;
+        }
+
+        summary.pathspecs.push(arg.to_string());
+    }
+
+    summary.dry_run = explicit_dry_run || status_format;
+    summary
+}
+
+/// True when `git commit <command_args>` creates or amends a commit, as
+/// opposed to only previewing one (`--dry-run` and the status formats).
+pub fn commit_creates_commit(command_args: &[String]) -> bool {
+    summarize_commit_args(command_args).creates_commit()
+}
+
 pub fn stash_subcommand(command_args: &[String]) -> Option<&str> {
     match command_args.first().map(String::as_str) {
         Some("push" | "save" | "apply" | "pop" | "drop" | "list" | "branch" | "show") => {
@@ -637,6 +767,44 @@ pub fn parse_git_cli_args(args: &[String]) -> ParsedGitInvocation {
     }
 }
 
+/// Arguments for `git-ai` when a git invocation is really a git-ai command:
+/// `git ai <args>`, or `git help ai [<command>]`. Repository selection (`-C`,
+/// `--git-dir`, `--work-tree`) carries over; other global git options do not
+/// apply to git-ai commands and are dropped.
+pub fn git_ai_subcommand_args(parsed: &ParsedGitInvocation) -> Option<Vec<String>> {
+    let command_args: Vec<String> = match parsed.command.as_deref() {
+        Some("ai") => parsed.command_args.clone(),
+        Some("help") if parsed.command_args.first().map(String::as_str) == Some("ai") => {
+            std::iter::once("help".to_string())
+                .chain(
+                    parsed.command_args[1..]
+                        .iter()
+                        .filter(|arg| !arg.starts_with('-'))
+                        .cloned(),
+                )
+                .collect()
+        }
+        _ => return None,
+    };
+
+    let mut args = Vec::new();
+    let mut global_args = parsed.global_args.iter();
+    while let Some(arg) = global_args.next() {
+        if matches!(arg.as_str(), "-C" | "--git-dir" | "--work-tree") {
+            args.push(arg.clone());
+            args.extend(global_args.next().cloned());
+        } else if let Some(path) = arg.strip_prefix("-C") {
+            args.extend(["-C".to_string(), path.to_string()]);
+        } else if arg.starts_with("--git-dir=") || arg.starts_with("--work-tree=") {
+            args.push(arg.clone());
+        } else if arg == "-c" {
+            global_args.next();
+        }
+    }
+    args.extend(command_args);
+    Some(args)
+}
+
 pub fn is_dry_run(args: &[String]) -> bool {
     args.iter().any(|arg| arg == "--dry-run")
 }
@@ -1,3 +1,5 @@
+use crate::git::cli_parser::commit_creates_commit;
+
 /// Returns true if the given git subcommand is guaranteed to never mutate
 /// repository state (refs, objects, config, worktree). Used to skip expensive
 /// trace2 ingestion work and suppress trace2 emission for read-only commands.
@@ -45,6 +47,8 @@ pub fn is_definitely_read_only_command(command: &str) -> bool {
 /// - `git worktree list` is read-only; `add`/`remove` are not
 /// - `git notes show` / `git notes list` / `git notes get-ref` are read-only;
 ///   `add`/`append`/`remove` are not
+/// - `git commit --dry-run` (and `--short`/`--porcelain`/`--long`, which imply
+///   it) only previews the commit
 ///
 /// IDEs like Zed issue thousands of `stash list` and `worktree list` calls
 /// per minute for their git panel UI. These must be identified as read-only
@@ -56,6 +60,7 @@ pub fn is_definitely_read_only_git_invocation(command: &str, command_args: &[Str
 
     match command {
         "branch" => branch_invocation_is_read_only(command_args),
+        "commit" => !commit_creates_commit(command_args),
         "notes" => matches!(
             command_args.first().map(String::as_str),
             Some("show" | "list" | "get-ref")
@@ -434,6 +439,26 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn commit_dry_run_is_read_only_invocation() {
+        for args in [
+            vec!["--dry-run"],
+            vec!["--short", "-a"],
+            vec!["--porcelain=v2"],
+            vec!["-z"],
+        ] {
+            let args = args.into_iter().map(str::to_string).collect::<Vec<_>>();
+            assert!(
+                is_definitely_read_only_git_invocation("commit", &args),
+                "git commit {args:?} should be read-only"
+            );
+        }
+        assert!(!is_definitely_read_only_git_invocation(
+            "commit",
+            &["-m".to_string(), "--dry-run".to_string()]
+        ));
+    }
+
     #[test]
     fn stash_mutating_subcommands_are_not_read_only() {
         for subcommand in ["pop", "apply", "drop", "branch", "push", "save"] {
@@ -157,7 +157,8 @@ impl<'a> FastObjectReader<'a> {
     }
 
     fn decompress_object(&self, oid: &str) -> Option<Vec<u8>> {
-        if self.has_alternates() {
+        // Replaced objects must come from git, which applies `refs/replace/*`.
+        if self.has_alternates() || crate::git::repo_state::replace_refs_active(self.common_dir) {
             return None;
         }
         let path = self.object_path(oid)?;
@@ -625,6 +626,23 @@ mod tests {
         assert_eq!(reader.try_read_blob(sha), None);
     }
 
+    #[test]
+    fn test_replace_refs_cause_fallback() {
+        let temp = setup_git_dir();
+        let sha = "abc123def456789012345678901234567890abcd";
+        write_loose_object(temp.path(), sha, "blob", b"content");
+        let reader = FastObjectReader::new(temp.path());
+        assert_eq!(reader.try_read_blob(sha), Some(b"content".to_vec()));
+
+        fs::create_dir_all(temp.path().join("refs/replace")).unwrap();
+        fs::write(
+            temp.path().join("refs/replace").join(sha),
+            "1111111111111111111111111111111111111111\n",
+        )
+        .unwrap();
+        assert_eq!(reader.try_read_blob(sha), None);
+    }
+
     #[test]
     fn test_invalid_oid_returns_none() {
         let temp = setup_git_dir();
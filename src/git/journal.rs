@@ -0,0 +1,216 @@
+//! Append-only JSON-lines journals under `.git/ai`.
+//!
+//! Post-commit notes, deferred repairs and merge tool resolutions are recorded
+//! one JSON object per line. Appends and rewrites take a sibling `.lock` file so
+//! a record appended while another process drops records is not lost. Lines
+//! that do not parse (a write torn by a crash, or a record from an incompatible
+//! version) are skipped on read and moved to a sibling `.rejected` file on the
+//! next rewrite, so they stay inspectable without being retried forever.
+//!
+//! Appends are not fsynced: the journals guard against a crashing process, and
+//! the page cache survives that. Paying a disk flush on every commit would not
+//! buy anything the notes ref itself has.
+
+use crate::error::GitAiError;
+use crate::utils::LockFile;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How long an append or rewrite waits for another process to release the lock.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+fn lock_path(path: &Path) -> PathBuf {
+    path.with_extension("jsonl.lock")
+}
+
+fn rejected_path(path: &Path) -> PathBuf {
+    path.with_extension("jsonl.rejected")
+}
+
+fn lock(path: &Path) -> Result<LockFile, GitAiError> {
+    let lock_path = lock_path(path);
+    let deadline = Instant::now() + LOCK_TIMEOUT;
+    loop {
+        if let Some(lock) = LockFile::try_acquire(&lock_path) {
+            return Ok(lock);
+        }
+        if Instant::now() >= deadline {
+            return Err(GitAiError::Storage(format!(
+                "timed out waiting for {}",
+                lock_path.display()
+            )));
+        }
+        std::thread::sleep(LOCK_RETRY_INTERVAL);
+    }
+}
+
+/// Append `record` as one line. A tail left without its newline by a crash is
+/// terminated first, so the torn line does not swallow this record.
+pub(crate) fn append<T: Serialize>(path: &Path, record: &T) -> Result<(), GitAiError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let _lock = lock(path)?;
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .read(true)
+        .append(true)
+        .open(path)?;
+    let mut line = String::new();
+    if file.metadata()?.len() > 0 {
+        let mut last = [0u8; 1];
+        file.seek(SeekFrom::End(-1))?;
+        file.read_exact(&mut last)?;
+        if last[0] != b'\n' {
+            line.push('\n');
+        }
+    }
+    line.push_str(&serde_json::to_string(record)?);
+    line.push('\n');
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Every record that parses, oldest first.
+pub(crate) fn read<T: DeserializeOwned>(path: &Path) -> Vec<T> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Keep only the records `keep` accepts, deleting the journal once empty.
+/// Lines that do not parse are moved to the `.rejected` file.
+pub(crate) fn retain<T: DeserializeOwned>(
+    path: &Path,
+    mut keep: impl FnMut(&T) -> bool,
+) -> Result<(), GitAiError> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let _lock = lock(path)?;
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut kept = String::new();
+    let mut rejected = String::new();
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        match serde_json::from_str::<T>(line) {
+            Ok(record) if keep(&record) => {
+                kept.push_str(line);
+                kept.push('\n');
+            }
+            Ok(_) => {}
+            Err(_) => {
+                rejected.push_str(line);
+                rejected.push('\n');
+            }
+        }
+    }
+    if !rejected.is_empty() {
+        tracing::warn!(journal = %path.display(), "moving unreadable journal records aside");
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(rejected_path(path))?
+            .write_all(rejected.as_bytes())?;
+    }
+
+    if kept.is_empty() {
+        return match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        };
+    }
+    let tmp_path = path.with_extension("jsonl.tmp");
+    fs::write(&tmp_path, kept)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Move the records `reject` selects to the `.rejected` file.
+pub(crate) fn reject<T: DeserializeOwned + Serialize>(
+    path: &Path,
+    mut reject: impl FnMut(&T) -> bool,
+) -> Result<(), GitAiError> {
+    let mut rejected: Vec<String> = Vec::new();
+    retain(path, |record: &T| {
+        if reject(record) {
+            if let Ok(line) = serde_json::to_string(record) {
+                rejected.push(line);
+            }
+            false
+        } else {
+            true
+        }
+    })?;
+    if rejected.is_empty() {
+        return Ok(());
+    }
+    let mut content = rejected.join("\n");
+    content.push('\n');
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(rejected_path(path))?
+        .write_all(content.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        id: u32,
+    }
+
+    #[test]
+    fn test_append_terminates_a_torn_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal.jsonl");
+        fs::write(&path, "{\"id\":1}\n{\"id\":").unwrap();
+
+        append(&path, &Record { id: 2 }).unwrap();
+
+        assert_eq!(
+            read::<Record>(&path),
+            vec![Record { id: 1 }, Record { id: 2 }]
+        );
+    }
+
+    #[test]
+    fn test_retain_moves_unreadable_lines_aside() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal.jsonl");
+        fs::write(&path, "{\"id\":1}\ngarbage\n{\"id\":2}\n").unwrap();
+
+        retain(&path, |record: &Record| record.id != 1).unwrap();
+
+        assert_eq!(read::<Record>(&path), vec![Record { id: 2 }]);
+        assert_eq!(
+            fs::read_to_string(rejected_path(&path)).unwrap(),
+            "garbage\n"
+        );
+
+        reject(&path, |record: &Record| record.id == 2).unwrap();
+        assert!(!path.exists());
+        assert_eq!(
+            fs::read_to_string(rejected_path(&path)).unwrap(),
+            "garbage\n{\"id\":2}\n"
+        );
+    }
+}
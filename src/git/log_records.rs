@@ -0,0 +1,65 @@
+//! `git log` output split into one record per commit and one field per format
+//! placeholder.
+//!
+//! Fields are separated by `%x1f` and records terminated by `%x1e`, so fields
+//! may hold anything git prints, including multi-line messages (`%B`).
+
+use crate::error::GitAiError;
+use crate::git::repository::{Repository, exec_git};
+
+const FIELD_SEPARATOR: char = '\u{1f}';
+const RECORD_SEPARATOR: char = '\u{1e}';
+
+/// Run `git log <log_args>` printing `placeholders` for each commit, and return
+/// each commit's fields in placeholder order.
+pub(crate) fn log_records(
+    repo: &Repository,
+    placeholders: &[&str],
+    log_args: &[String],
+) -> Result<Vec<Vec<String>>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("log".to_string());
+    args.push(format!("--format={}%x1e", placeholders.join("%x1f")));
+    args.extend(log_args.iter().cloned());
+    let output = exec_git(&args)?;
+    parse_log_records(&String::from_utf8(output.stdout)?, placeholders.len())
+}
+
+fn parse_log_records(stdout: &str, field_count: usize) -> Result<Vec<Vec<String>>, GitAiError> {
+    stdout
+        .split(RECORD_SEPARATOR)
+        .map(|record| record.trim_start_matches('\n'))
+        .filter(|record| !record.is_empty())
+        .map(|record| {
+            let fields: Vec<String> = record
+                .splitn(field_count, FIELD_SEPARATOR)
+                .map(str::to_string)
+                .collect();
+            if fields.len() != field_count {
+                return Err(GitAiError::Generic(format!(
+                    "Unexpected git log output: {}",
+                    record
+                )));
+            }
+            Ok(fields)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_records_keeps_multiline_fields() {
+        let stdout = "a\u{1f}subject\n\nbody\n\u{1e}\nb\u{1f}one line\u{1e}\n";
+        assert_eq!(
+            parse_log_records(stdout, 2).unwrap(),
+            vec![
+                vec!["a".to_string(), "subject\n\nbody\n".to_string()],
+                vec!["b".to_string(), "one line".to_string()],
+            ]
+        );
+        assert!(parse_log_records("a\u{1e}", 2).is_err());
+    }
+}
@@ -1,7 +1,11 @@
 pub mod cli_parser;
 pub mod command_classification;
 pub mod fast_reader;
+pub(crate) mod journal;
+pub(crate) mod log_records;
+pub mod note_overflow;
 pub mod notes_api;
+pub mod notes_schema;
 pub mod refs;
 pub mod repo_state;
 pub mod repository;
@@ -18,4 +22,5 @@ pub use repository::{
 };
 pub mod repo_storage;
 pub mod status;
+pub mod storage_usage;
 pub mod sync_authorship;
@@ -0,0 +1,281 @@
+//! Overflow storage for oversized authorship notes.
+//!
+//! A note larger than `max_note_size_bytes` is not written to `refs/notes/ai`.
+//! The full note goes to `refs/notes/ai-overflow` instead, which is pushed and
+//! fetched along with `refs/notes/ai`, and is also uploaded to CAS when an
+//! account or API key is configured. The note in `refs/notes/ai` is a compact
+//! stub that names both copies:
+//!
+//! ```text
+//! {"git_ai_note_overflow":1,"blob":"<git blob oid>","cas":"<cas hash>","bytes":<size>}
+//! ```
+//!
+//! The note readers resolve stubs transparently: first from the local blob,
+//! then from CAS. A stub that cannot be resolved reads as no
+//! note. Only the git notes backend spills; the HTTP backend has no notes push.
+
+use crate::api::cas::cas_hash;
+use crate::api::{ApiClient, ApiContext, CasObject, CasUploadRequest};
+use crate::config::Config;
+use crate::error::GitAiError;
+use crate::git::refs::{
+    notes_add_batch_to_ref, parse_cat_file_batch_output_with_oids, sanitize_remote_name,
+};
+use crate::git::repository::{Repository, exec_git_stdin};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Notes ref holding the full content of spilled notes.
+pub const OVERFLOW_NOTES_REF: &str = "refs/notes/ai-overflow";
+
+const STUB_VERSION: u32 = 1;
+
+/// Where a remote's [`OVERFLOW_NOTES_REF`] is fetched to before merging.
+pub fn overflow_tracking_ref_for_remote(remote_name: &str) -> String {
+    format!(
+        "refs/notes/ai-overflow-remote/{}",
+        sanitize_remote_name(remote_name)
+    )
+}
+
+/// The note left in `refs/notes/ai` in place of an oversized one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverflowStub {
+    pub git_ai_note_overflow: u32,
+    /// Blob of the full note, kept reachable by [`OVERFLOW_NOTES_REF`].
+    pub blob: String,
+    /// CAS hash of the full note (see [`cas_object_content`]).
+    pub cas: String,
+    pub bytes: usize,
+}
+
+impl OverflowStub {
+    /// The stub `content` holds, if it is one. Authorship notes start with
+    /// attestation lines or the `---` divider, never with `{`.
+    pub fn parse(content: &str) -> Option<Self> {
+        if !content.trim_start().starts_with('{') {
+            return None;
+        }
+        serde_json::from_str::<Self>(content.trim())
+            .ok()
+            .filter(|stub| stub.git_ai_note_overflow == STUB_VERSION)
+    }
+
+    pub fn to_note(&self) -> String {
+        // Serializing a struct of strings and integers cannot fail.
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// CAS object holding a full note.
+pub fn cas_object_content(note: &str) -> serde_json::Value {
+    serde_json::json!({ "authorship_note": note })
+}
+
+/// Whether a note of `len` bytes exceeds `limit` (`0` disables the limit).
+pub fn exceeds_limit(len: usize, limit: usize) -> bool {
+    limit > 0 && len > limit
+}
+
+/// `entries` (commit sha, note) with every note over `max_note_size_bytes`
+/// moved to overflow storage and replaced by its stub.
+pub fn spill_oversized(
+    repo: &Repository,
+    entries: &[(String, String)],
+) -> Result<Vec<(String, String)>, GitAiError> {
+    let limit = Config::get().max_note_size_bytes();
+    if !entries
+        .iter()
+        .any(|(_, note)| exceeds_limit(note.len(), limit))
+    {
+        return Ok(entries.to_vec());
+    }
+
+    let mut spilled = Vec::new();
+    let mut cas_objects = Vec::new();
+    let mut result = Vec::with_capacity(entries.len());
+    for (commit_sha, note) in entries {
+        if !exceeds_limit(note.len(), limit) {
+            result.push((commit_sha.clone(), note.clone()));
+            continue;
+        }
+        let content = cas_object_content(note);
+        let stub = OverflowStub {
+            git_ai_note_overflow: STUB_VERSION,
+            blob: hash_blob(repo, note)?,
+            cas: cas_hash(&content)?,
+            bytes: note.len(),
+        };
+        tracing::debug!(
+            "authorship note for {} is {} bytes, storing it as overflow object {}",
+            commit_sha,
+            note.len(),
+            stub.blob
+        );
+        cas_objects.push(CasObject {
+            content,
+            hash: stub.cas.clone(),
+            metadata: HashMap::from([("kind".to_string(), "authorship_note".to_string())]),
+        });
+        spilled.push((commit_sha.clone(), note.clone()));
+        result.push((commit_sha.clone(), stub.to_note()));
+    }
+
+    notes_add_batch_to_ref(repo, OVERFLOW_NOTES_REF, &spilled)?;
+    upload_to_cas(cas_objects);
+    Ok(result)
+}
+
+/// Replace every overflow stub among `notes` with the full note, dropping
+/// stubs that cannot be resolved.
+pub fn resolve_stubs<K>(global_args: &[String], notes: &mut HashMap<K, String>)
+where
+    K: Clone + Eq + std::hash::Hash + std::fmt::Display,
+{
+    let stubs: Vec<(K, OverflowStub)> = notes
+        .iter()
+        .filter_map(|(key, content)| Some((key.clone(), OverflowStub::parse(content)?)))
+        .collect();
+    if stubs.is_empty() {
+        return;
+    }
+
+    let blob_oids: Vec<String> = stubs.iter().map(|(_, stub)| stub.blob.clone()).collect();
+    let local = read_blobs(global_args, &blob_oids).unwrap_or_else(|e| {
+        tracing::debug!("reading overflow note blobs failed: {}", e);
+        HashMap::new()
+    });
+    for (key, stub) in stubs {
+        match local
+            .get(&stub.blob)
+            .cloned()
+            .or_else(|| read_from_cas(&stub.cas))
+        {
+            Some(note) => {
+                notes.insert(key, note);
+            }
+            None => {
+                tracing::debug!(
+                    "overflow note {} for {} is not available locally or in CAS",
+                    stub.blob,
+                    key
+                );
+                notes.remove(&key);
+            }
+        }
+    }
+}
+
+/// A single note, with an overflow stub resolved.
+pub fn resolve_note(global_args: &[String], content: String) -> Option<String> {
+    let mut notes = HashMap::from([("note".to_string(), content)]);
+    resolve_stubs(global_args, &mut notes);
+    notes.remove("note")
+}
+
+fn hash_blob(repo: &Repository, note: &str) -> Result<String, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("hash-object".to_string());
+    args.push("--stdin".to_string());
+    let output = exec_git_stdin(&args, note.as_bytes())?;
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+/// Blob contents by oid; blobs missing from the object database are left out.
+fn read_blobs(
+    global_args: &[String],
+    blob_oids: &[String],
+) -> Result<HashMap<String, String>, GitAiError> {
+    let mut args = global_args.to_vec();
+    args.push("cat-file".to_string());
+    args.push("--batch".to_string());
+    let stdin_data = blob_oids.join("\n") + "\n";
+    let output = exec_git_stdin(&args, stdin_data.as_bytes())?;
+    parse_cat_file_batch_output_with_oids(&output.stdout)
+}
+
+fn cas_client() -> Option<ApiClient> {
+    let context = ApiContext::new(None).with_max_retries(0);
+    let using_default_api = context.base_url == crate::config::DEFAULT_API_BASE_URL;
+    let client = ApiClient::new(context);
+    if using_default_api && !client.is_logged_in() && !client.has_api_key() {
+        return None;
+    }
+    Some(client)
+}
+
+/// Best-effort upload; failures are queued for the telemetry worker's CAS retry.
+fn upload_to_cas(objects: Vec<CasObject>) {
+    if objects.is_empty() {
+        return;
+    }
+    let Some(client) = cas_client() else {
+        tracing::debug!("not uploading overflow notes to CAS: not logged in");
+        return;
+    };
+    if let Err(e) = client.upload_cas(CasUploadRequest {
+        objects: objects.clone(),
+    }) {
+        tracing::debug!("overflow note CAS upload failed, queueing for retry: {}", e);
+        if let Ok(db) = crate::authorship::internal_db::InternalDatabase::global()
+            && let Ok(mut db_lock) = db.lock()
+        {
+            for object in &objects {
+                let _ = db_lock.enqueue_cas_object(&object.content, Some(&object.metadata));
+            }
+        }
+    }
+}
+
+/// A full note fetched from CAS, verified against `hash`. Fetched notes are
+/// kept for the life of the process.
+fn read_from_cas(hash: &str) -> Option<String> {
+    static FETCHED: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+    let fetched = FETCHED.get_or_init(Default::default);
+    if let Some(note) = fetched.lock().ok()?.get(hash) {
+        return Some(note.clone());
+    }
+
+    let response = cas_client()?.read_ca_prompt_store(&[hash]).ok()?;
+    let content = response
+        .results
+        .into_iter()
+        .find(|result| result.hash == hash && result.status == "ok")?
+        .content?;
+    if cas_hash(&content).ok()? != hash {
+        tracing::debug!("CAS returned content not matching overflow hash {}", hash);
+        return None;
+    }
+    let note = content.get("authorship_note")?.as_str()?.to_string();
+    fetched.lock().ok()?.insert(hash.to_string(), note.clone());
+    Some(note)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::authorship_log_serialization::AuthorshipLog;
+
+    #[test]
+    fn stub_round_trips_and_is_never_mistaken_for_a_note() {
+        let stub = OverflowStub {
+            git_ai_note_overflow: STUB_VERSION,
+            blob: "a".repeat(40),
+            cas: "b".repeat(64),
+            bytes: 5_000_000,
+        };
+        assert_eq!(OverflowStub::parse(&stub.to_note()), Some(stub));
+
+        let note = AuthorshipLog::new().serialize_to_string().unwrap();
+        assert_eq!(OverflowStub::parse(&note), None);
+        assert_eq!(OverflowStub::parse("{\"unrelated\":true}"), None);
+    }
+
+    #[test]
+    fn limit_zero_disables_spilling() {
+        assert!(exceeds_limit(11, 10));
+        assert!(!exceeds_limit(10, 10));
+        assert!(!exceeds_limit(usize::MAX, 0));
+    }
+}
@@ -10,6 +10,7 @@
 use crate::authorship::authorship_log_serialization::AuthorshipLog;
 use crate::config::{Config, NotesBackendKind};
 use crate::error::GitAiError;
+use crate::git::repo_storage::PendingNoteRecord;
 use crate::git::repository::Repository;
 use std::collections::{HashMap, HashSet};
 
@@ -19,10 +20,82 @@ pub use crate::git::refs::CommitAuthorship;
 // --- Writes ---
 
 pub fn write_note(repo: &Repository, commit_sha: &str, content: &str) -> Result<(), GitAiError> {
+    let content = crate::authorship::anonymize::anonymize_note_content(repo, content);
+    let content = content.as_ref();
     match Config::get().notes_backend_kind() {
         NotesBackendKind::Http => http_write_note(commit_sha, content),
-        NotesBackendKind::GitNotes => crate::git::refs::notes_add(repo, commit_sha, content),
+        NotesBackendKind::GitNotes => {
+            let entries = [(commit_sha.to_string(), content.to_string())];
+            match crate::git::note_overflow::spill_oversized(repo, &entries)?.pop() {
+                Some((_, note)) => crate::git::refs::notes_add(repo, commit_sha, &note),
+                None => Ok(()),
+            }
+        }
+    }
+}
+
+/// Write a note produced by post-commit through the pending-note journal.
+///
+/// The note is journaled in `.git/ai` first and the record dropped once the
+/// write succeeds, so a daemon crash or a failing backend leaves it for
+/// [`replay_pending_notes`], which runs here before each new write.
+pub fn write_note_journaled(
+    repo: &Repository,
+    commit_sha: &str,
+    content: &str,
+) -> Result<(), GitAiError> {
+    if let Err(e) = replay_pending_notes(repo) {
+        tracing::debug!("pending note replay failed: {}", e);
+    }
+    repo.storage.append_pending_note(&PendingNoteRecord {
+        commit_sha: commit_sha.to_string(),
+        note: content.to_string(),
+    })?;
+    write_note(repo, commit_sha, content)?;
+    repo.storage.remove_pending_notes(&[commit_sha.to_string()])
+}
+
+/// Write every journaled note that was never confirmed. Returns how many were
+/// written.
+///
+/// When the batch fails, records whose commit no longer exists are moved aside
+/// so one bad record does not block the rest; the others stay journaled.
+pub fn replay_pending_notes(repo: &Repository) -> Result<usize, GitAiError> {
+    let pending = repo.storage.read_pending_notes();
+    if pending.is_empty() {
+        return Ok(0);
     }
+    let entries: Vec<(String, String)> = pending
+        .into_iter()
+        .map(|record| (record.commit_sha, record.note))
+        .collect();
+    if let Err(e) = write_notes_batch(repo, &entries) {
+        let (missing, writable): (Vec<_>, Vec<_>) = entries
+            .into_iter()
+            .partition(|(sha, _)| repo.find_commit(sha.clone()).is_err());
+        if missing.is_empty() {
+            return Err(e);
+        }
+        let missing: Vec<String> = missing.into_iter().map(|(sha, _)| sha).collect();
+        tracing::warn!(
+            commits = missing.len(),
+            "rejecting journaled notes for commits that no longer exist"
+        );
+        repo.storage.reject_pending_notes(&missing)?;
+        if writable.is_empty() {
+            return Ok(0);
+        }
+        write_notes_batch(repo, &writable)?;
+        return finish_replay(repo, writable);
+    }
+    finish_replay(repo, entries)
+}
+
+fn finish_replay(repo: &Repository, written: Vec<(String, String)>) -> Result<usize, GitAiError> {
+    let commit_shas: Vec<String> = written.into_iter().map(|(sha, _)| sha).collect();
+    repo.storage.remove_pending_notes(&commit_shas)?;
+    tracing::debug!("replayed {} pending authorship notes", commit_shas.len());
+    Ok(commit_shas.len())
 }
 
 pub fn write_notes_batch(
@@ -32,15 +105,30 @@ pub fn write_notes_batch(
     if entries.is_empty() {
         return Ok(());
     }
+    let _span = tracing::debug_span!(
+        target: crate::observability::trace::targets::STORAGE,
+        "write_notes",
+        notes = entries.len()
+    )
+    .entered();
+    let entries = crate::authorship::anonymize::anonymize_note_entries(repo, entries);
     match Config::get().notes_backend_kind() {
-        NotesBackendKind::Http => http_write_batch(entries),
-        NotesBackendKind::GitNotes => crate::git::refs::notes_add_batch(repo, entries),
+        NotesBackendKind::Http => http_write_batch(&entries),
+        NotesBackendKind::GitNotes => {
+            let entries = crate::git::note_overflow::spill_oversized(repo, &entries)?;
+            crate::git::refs::notes_add_batch(repo, &entries)
+        }
     }
 }
 
 // --- Reads ---
 
 pub fn read_note(repo: &Repository, commit_sha: &str) -> Option<String> {
+    let _span = tracing::debug_span!(
+        target: crate::observability::trace::targets::STORAGE,
+        "read_note"
+    )
+    .entered();
     match Config::get().notes_backend_kind() {
         NotesBackendKind::Http => http_read_note(commit_sha)
             .or_else(|| crate::git::refs::show_authorship_note(repo, commit_sha)),
@@ -117,7 +205,7 @@ pub fn read_authorship_v3(
         NotesBackendKind::Http => {
             if let Some(content) = http_read_note(commit_sha) {
                 AuthorshipLog::deserialize_from_string(&content)
-                    .map_err(|e| GitAiError::Generic(format!("notes deserialization error: {}", e)))
+                    .map_err(|e| GitAiError::Notes(format!("notes deserialization error: {}", e)))
             } else {
                 crate::git::refs::get_reference_as_authorship_log_v3(repo, commit_sha)
             }
@@ -219,7 +307,7 @@ pub fn filter_commits_with_notes(
                         if let Some(content) = cached_map.get(sha)
                             && let Ok(authorship_log) =
                                 AuthorshipLog::deserialize_from_string(content)
-                                    .map_err(|e| GitAiError::Generic(e.to_string()))
+                                    .map_err(|e| GitAiError::Notes(e.to_string()))
                         {
                             return CommitAuthorship::Log {
                                 sha: sha.clone(),
@@ -262,7 +350,7 @@ fn http_search_notes(repo: &Repository, pattern: &str) -> Result<Vec<String>, Gi
         let db = crate::notes::db::NotesDatabase::global()?;
         let db_lock = db
             .lock()
-            .map_err(|e| GitAiError::Generic(format!("notes-db lock: {}", e)))?;
+            .map_err(|e| GitAiError::Storage(format!("notes-db lock: {}", e)))?;
         db_lock.search_notes_content(pattern)?.into_iter().collect()
     };
 
@@ -485,7 +573,7 @@ pub fn warm_cache_for_remote(repo: &Repository, remote: &str) -> Result<(), GitA
             return Ok(());
         }
     };
-    let ctx = ApiContext::new(Some(backend_url));
+    let ctx = ApiContext::new(Some(backend_url)).with_max_retries(0);
     let client = ApiClient::new(ctx);
 
     // Skip when not authenticated (matches daemon flush_notes pattern).
@@ -543,7 +631,7 @@ fn http_write_note(commit_sha: &str, content: &str) -> Result<(), GitAiError> {
     let db = crate::notes::db::NotesDatabase::global()?;
     let mut db_lock = db
         .lock()
-        .map_err(|e| GitAiError::Generic(format!("notes-db lock: {}", e)))?;
+        .map_err(|e| GitAiError::Storage(format!("notes-db lock: {}", e)))?;
     db_lock.upsert_note(commit_sha, content)?;
     drop(db_lock);
     crate::daemon::telemetry_handle::submit_notes();
@@ -554,7 +642,7 @@ fn http_write_batch(entries: &[(String, String)]) -> Result<(), GitAiError> {
     let db = crate::notes::db::NotesDatabase::global()?;
     let mut db_lock = db
         .lock()
-        .map_err(|e| GitAiError::Generic(format!("notes-db lock: {}", e)))?;
+        .map_err(|e| GitAiError::Storage(format!("notes-db lock: {}", e)))?;
     db_lock.upsert_notes_batch(entries)?;
     drop(db_lock);
     crate::daemon::telemetry_handle::submit_notes();
@@ -588,7 +676,7 @@ fn http_fetch_and_cache_notes(commit_shas: &[String]) -> HashMap<String, String>
         return HashMap::new();
     };
 
-    let ctx = crate::api::client::ApiContext::new(Some(backend_url));
+    let ctx = crate::api::client::ApiContext::new(Some(backend_url)).with_max_retries(0);
     let client = crate::api::client::ApiClient::new(ctx);
     if !client.is_logged_in() && !client.has_api_key() {
         return HashMap::new();
@@ -0,0 +1,149 @@
+//! Schema-versioned authorship notes namespaces.
+//!
+//! Each notes namespace gets its own ref so a change in how notes are written
+//! never rewrites notes in place. `refs/notes/ai-v2` notes use the same
+//! serialized schema ([`AUTHORSHIP_LOG_VERSION`]) as `refs/notes/ai`, and
+//! additionally pin `base_commit_sha` to the commit they are attached to, which
+//! v1 notes only approximate after history rewrites.
+//!
+//! Writes still go to `refs/notes/ai`. Reads look there first and fall back to
+//! newer namespaces present in the repository, translating what they find into
+//! the current form, so teammates on different versions can share notes.
+//! `git-ai notes upgrade` copies existing v1 notes into `refs/notes/ai-v2`;
+//! from then on notes sync fetches and pushes that namespace too.
+
+use crate::authorship::authorship_log_serialization::{AUTHORSHIP_LOG_VERSION, AuthorshipLog};
+use crate::error::GitAiError;
+use crate::git::fast_reader::FastRefReader;
+use crate::git::repository::Repository;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotesNamespace {
+    /// `refs/notes/ai`, the namespace every client writes.
+    V1,
+    /// `refs/notes/ai-v2`.
+    V2,
+}
+
+impl NotesNamespace {
+    /// Newest first: the order readers fall back through after V1.
+    pub const NEWER_THAN_V1: &'static [NotesNamespace] = &[NotesNamespace::V2];
+
+    /// Name for `git notes --ref=<name>`.
+    pub fn notes_ref(self) -> &'static str {
+        match self {
+            NotesNamespace::V1 => "ai",
+            NotesNamespace::V2 => "ai-v2",
+        }
+    }
+
+    pub fn full_ref(self) -> &'static str {
+        match self {
+            NotesNamespace::V1 => "refs/notes/ai",
+            NotesNamespace::V2 => "refs/notes/ai-v2",
+        }
+    }
+
+    /// Where notes sync fetches this namespace of `remote_name` to.
+    pub fn tracking_ref_for_remote(self, remote_name: &str) -> String {
+        format!(
+            "refs/notes/{}-remote/{}",
+            self.notes_ref(),
+            crate::git::refs::sanitize_remote_name(remote_name)
+        )
+    }
+
+    /// Whether the namespace ref exists. Loose and packed refs are read from
+    /// disk so the read fallback costs no git process in repositories that
+    /// never upgraded; other ref backends (reftable) ask git.
+    pub fn exists_in(self, repo: &Repository) -> bool {
+        let common_dir = repo.common_dir();
+        if FastRefReader::new(common_dir, common_dir)
+            .try_resolve_ref(self.full_ref())
+            .is_some()
+        {
+            return true;
+        }
+        common_dir.join("reftable").is_dir() && crate::git::refs::ref_exists(repo, self.full_ref())
+    }
+}
+
+/// Parse a note from any namespace attached to `commit_sha`, in the form
+/// current code works with: `base_commit_sha` set to the annotated commit.
+pub fn read_versioned_note(content: &str, commit_sha: &str) -> Result<AuthorshipLog, GitAiError> {
+    let mut log = AuthorshipLog::deserialize_from_string(content)
+        .map_err(|e| GitAiError::Generic(format!("malformed authorship note: {}", e)))?;
+    if log.metadata.schema_version != AUTHORSHIP_LOG_VERSION {
+        return Err(GitAiError::Generic(format!(
+            "unsupported authorship schema version '{}'",
+            log.metadata.schema_version
+        )));
+    }
+    log.metadata.schema_version = AUTHORSHIP_LOG_VERSION.to_string();
+    log.metadata.base_commit_sha = commit_sha.to_string();
+    Ok(log)
+}
+
+/// [`read_versioned_note`], serialized back to a note.
+pub fn to_current_note(content: &str, commit_sha: &str) -> Result<String, GitAiError> {
+    serialize(&read_versioned_note(content, commit_sha)?)
+}
+
+/// Rewrite a note as a v2 note for `commit_sha`.
+pub fn upgrade_note(content: &str, commit_sha: &str) -> Result<String, GitAiError> {
+    serialize(&read_versioned_note(content, commit_sha)?)
+}
+
+fn serialize(log: &AuthorshipLog) -> Result<String, GitAiError> {
+    log.serialize_to_string()
+        .map_err(|e| GitAiError::Generic(format!("failed to serialize authorship note: {}", e)))
+}
+
+/// Raw notes of `namespace` for `commit_shas`, as stored.
+pub fn read_namespace_notes(
+    repo: &Repository,
+    namespace: NotesNamespace,
+    commit_shas: &[String],
+) -> Result<std::collections::HashMap<String, String>, GitAiError> {
+    crate::git::refs::notes_for_commits_from_ref(repo, namespace.full_ref(), commit_shas)
+}
+
+/// Attach `entries` (commit sha, note content) in `namespace`.
+pub fn write_namespace_notes(
+    repo: &Repository,
+    namespace: NotesNamespace,
+    entries: &[(String, String)],
+) -> Result<(), GitAiError> {
+    crate::git::refs::notes_add_batch_to_ref(repo, namespace.full_ref(), entries).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v1_note() -> String {
+        let mut log = AuthorshipLog::new();
+        log.metadata.base_commit_sha = "stale".to_string();
+        log.serialize_to_string().unwrap()
+    }
+
+    #[test]
+    fn upgrade_round_trips_through_versioned_reader() {
+        let sha = "abcdef0123456789abcdef0123456789abcdef01";
+        let v2 = upgrade_note(&v1_note(), sha).unwrap();
+        assert!(v2.contains(AUTHORSHIP_LOG_VERSION), "{}", v2);
+        assert!(v2.contains(sha), "{}", v2);
+
+        let log = read_versioned_note(&v2, sha).unwrap();
+        assert_eq!(log.metadata.schema_version, AUTHORSHIP_LOG_VERSION);
+        assert_eq!(log.metadata.base_commit_sha, sha);
+        assert!(
+            to_current_note(&v2, sha)
+                .unwrap()
+                .contains(AUTHORSHIP_LOG_VERSION)
+        );
+
+        let unknown = v1_note().replace(AUTHORSHIP_LOG_VERSION, "authorship/9.0.0");
+        assert!(read_versioned_note(&unknown, sha).is_err());
+    }
+}
@@ -1,6 +1,8 @@
 use crate::authorship::authorship_log_serialization::{AUTHORSHIP_LOG_VERSION, AuthorshipLog};
 use crate::authorship::working_log::Checkpoint;
 use crate::error::GitAiError;
+use crate::git::note_overflow;
+use crate::git::notes_schema::{self, NotesNamespace};
 use crate::git::repository::{Repository, exec_git, exec_git_allow_nonzero, exec_git_stdin};
 use serde_json;
 use std::collections::{HashMap, HashSet};
@@ -68,7 +70,7 @@ pub fn parse_batch_check_blob_oid(line: &str) -> Option<String> {
     }
 }
 
-fn parse_cat_file_batch_output_with_oids(
+pub(in crate::git) fn parse_cat_file_batch_output_with_oids(
     data: &[u8],
 ) -> Result<HashMap<String, String>, GitAiError> {
     let mut results = HashMap::new();
@@ -136,15 +138,16 @@ fn batch_read_blob_contents(
 
     let stdin_data = blob_oids.join("\n") + "\n";
     let output = exec_git_stdin(&args, stdin_data.as_bytes())?;
-    let results = parse_cat_file_batch_output_with_oids(&output.stdout)?;
+    let mut results = parse_cat_file_batch_output_with_oids(&output.stdout)?;
     for oid in blob_oids {
         if !results.contains_key(oid) {
-            return Err(GitAiError::Generic(format!(
+            return Err(GitAiError::Notes(format!(
                 "missing git blob object referenced by authorship note: {}",
                 oid
             )));
         }
     }
+    note_overflow::resolve_stubs(&repo.global_args_for_exec(), &mut results);
     Ok(results)
 }
 
@@ -162,15 +165,53 @@ pub(in crate::git) fn note_blob_oids_for_commits(
 ///
 /// Returns a map of commit SHA -> raw note content for commits that currently
 /// have notes in `refs/notes/ai`.
+///
+/// Commits without one fall back to newer notes namespaces present in the
+/// repository, translated to the current schema (see `notes_schema`).
 pub(in crate::git) fn notes_for_commits(
     repo: &Repository,
     commit_shas: &[String],
+) -> Result<HashMap<String, String>, GitAiError> {
+    let mut notes = notes_for_commits_from_ref(repo, AI_AUTHORSHIP_FULL_REF, commit_shas)?;
+    for namespace in NotesNamespace::NEWER_THAN_V1 {
+        let missing: Vec<String> = commit_shas
+            .iter()
+            .filter(|sha| !notes.contains_key(*sha))
+            .cloned()
+            .collect();
+        if missing.is_empty() || !namespace.exists_in(repo) {
+            continue;
+        }
+        for (commit_sha, content) in
+            notes_for_commits_from_ref(repo, namespace.full_ref(), &missing)?
+        {
+            match notes_schema::to_current_note(&content, &commit_sha) {
+                Ok(note) => {
+                    notes.insert(commit_sha, note);
+                }
+                Err(e) => tracing::debug!(
+                    "skipping unreadable {} note for {}: {}",
+                    namespace.notes_ref(),
+                    commit_sha,
+                    e
+                ),
+            }
+        }
+    }
+    Ok(notes)
+}
+
+/// Raw note contents for a set of commits from a specific notes ref.
+pub(in crate::git) fn notes_for_commits_from_ref(
+    repo: &Repository,
+    notes_ref: &str,
+    commit_shas: &[String],
 ) -> Result<HashMap<String, String>, GitAiError> {
     if commit_shas.is_empty() {
         return Ok(HashMap::new());
     }
 
-    let note_blob_oids = note_blob_oids_for_commits(repo, commit_shas)?;
+    let note_blob_oids = note_blob_oids_for_commits_from_ref(repo, notes_ref, commit_shas)?;
     if note_blob_oids.is_empty() {
         return Ok(HashMap::new());
     }
@@ -227,7 +268,7 @@ pub fn note_blob_oids_for_commits_from_ref(
     for entry in root_entries {
         if let Some(commit_sha) = path_to_commit.get(&entry.path) {
             if entry.object_type != "blob" {
-                return Err(GitAiError::Generic(format!(
+                return Err(GitAiError::Notes(format!(
                     "authorship note path {} in {} is {}, expected blob",
                     entry.path, notes_ref, entry.object_type
                 )));
@@ -242,7 +283,7 @@ pub fn note_blob_oids_for_commits_from_ref(
         for entry in entries {
             if let Some(commit_sha) = path_to_commit.get(&entry.path) {
                 if entry.object_type != "blob" {
-                    return Err(GitAiError::Generic(format!(
+                    return Err(GitAiError::Notes(format!(
                         "authorship note path {} in {} is {}, expected blob",
                         entry.path, notes_ref, entry.object_type
                     )));
@@ -372,14 +413,26 @@ pub(in crate::git) fn notes_add_batch(
     repo: &Repository,
     entries: &[(String, String)],
 ) -> Result<(), GitAiError> {
+    let written = notes_add_batch_to_ref(repo, AI_AUTHORSHIP_FULL_REF, entries)?;
+    crate::authorship::git_ai_hooks::post_notes_updated(repo, &written);
+    Ok(())
+}
+
+/// Attach notes on `notes_ref` in one fast-import commit. Returns the entries
+/// written, deduplicated by commit (last entry wins).
+pub(in crate::git) fn notes_add_batch_to_ref(
+    repo: &Repository,
+    notes_ref: &str,
+    entries: &[(String, String)],
+) -> Result<Vec<(String, String)>, GitAiError> {
     if entries.is_empty() {
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     let mut args = repo.global_args_for_exec();
     args.push("rev-parse".to_string());
     args.push("--verify".to_string());
-    args.push("refs/notes/ai".to_string());
+    args.push(notes_ref.to_string());
     let existing_notes_tip = match exec_git(&args) {
         Ok(output) => Some(String::from_utf8(output.stdout)?.trim().to_string()),
         Err(GitAiError::GitCliError {
@@ -413,7 +466,7 @@ pub(in crate::git) fn notes_add_batch(
         script.extend_from_slice(b"\n");
     }
 
-    script.extend_from_slice(b"commit refs/notes/ai\n");
+    script.extend_from_slice(format!("commit {}\n", notes_ref).as_bytes());
     script.extend_from_slice(format!("committer git-ai <git-ai@local> {} +0000\n", now).as_bytes());
     script.extend_from_slice(b"data 0\n");
     if let Some(existing_tip) = existing_notes_tip {
@@ -435,9 +488,8 @@ pub(in crate::git) fn notes_add_batch(
     fast_import_args.push("fast-import".to_string());
     fast_import_args.push("--quiet".to_string());
     exec_git_stdin(&fast_import_args, &script)?;
-    crate::authorship::git_ai_hooks::post_notes_updated(repo, &deduped_entries);
 
-    Ok(())
+    Ok(deduped_entries)
 }
 
 /// Batch-attach existing note blobs to commits without rewriting blob contents.
@@ -642,21 +694,36 @@ pub(in crate::git) fn get_commits_with_notes_from_list(
 }
 
 // Show an authorship note and return its JSON content if found, or None if it doesn't exist.
+// Commits without one fall back to newer notes namespaces, as in `notes_for_commits`.
 pub(in crate::git) fn show_authorship_note(repo: &Repository, commit_sha: &str) -> Option<String> {
+    if let Some(note) = show_note_in_ref(repo, NotesNamespace::V1.notes_ref(), commit_sha) {
+        return Some(note);
+    }
+    NotesNamespace::NEWER_THAN_V1
+        .iter()
+        .filter(|namespace| namespace.exists_in(repo))
+        .find_map(|namespace| {
+            let content = show_note_in_ref(repo, namespace.notes_ref(), commit_sha)?;
+            notes_schema::to_current_note(&content, commit_sha).ok()
+        })
+}
+
+fn show_note_in_ref(repo: &Repository, notes_ref: &str, commit_sha: &str) -> Option<String> {
     let mut args = repo.global_args_for_exec();
     args.push("notes".to_string());
-    args.push("--ref=ai".to_string());
+    args.push(format!("--ref={}", notes_ref));
     args.push("show".to_string());
     args.push(commit_sha.to_string());
 
-    match exec_git(&args) {
+    let note = match exec_git(&args) {
         Ok(output) => String::from_utf8(output.stdout)
             .ok()
             .map(|s| s.trim().to_string())
             .filter(|s| !s.is_empty()),
         Err(GitAiError::GitCliError { code: Some(1), .. }) => None,
         Err(_) => None,
-    }
+    }?;
+    note_overflow::resolve_note(&repo.global_args_for_exec(), note)
 }
 
 /// Return the subset of `commit_shas` that currently has an authorship note.
@@ -687,7 +754,7 @@ pub fn get_reference_as_working_log(
     commit_sha: &str,
 ) -> Result<Vec<Checkpoint>, GitAiError> {
     let content = show_authorship_note(repo, commit_sha)
-        .ok_or_else(|| GitAiError::Generic("No authorship note found".to_string()))?;
+        .ok_or_else(|| GitAiError::Notes("No authorship note found".to_string()))?;
     let working_log = serde_json::from_str(&content)?;
     Ok(working_log)
 }
@@ -697,13 +764,13 @@ pub(in crate::git) fn get_reference_as_authorship_log_v3(
     commit_sha: &str,
 ) -> Result<AuthorshipLog, GitAiError> {
     let content = show_authorship_note(repo, commit_sha)
-        .ok_or_else(|| GitAiError::Generic("No authorship note found".to_string()))?;
+        .ok_or_else(|| GitAiError::Notes("No authorship note found".to_string()))?;
 
     // Try to deserialize as AuthorshipLog
     let mut authorship_log = match AuthorshipLog::deserialize_from_string(&content) {
         Ok(log) => log,
         Err(_) => {
-            return Err(GitAiError::Generic(
+            return Err(GitAiError::Notes(
                 "Failed to parse authorship log".to_string(),
             ));
         }
@@ -711,7 +778,7 @@ pub(in crate::git) fn get_reference_as_authorship_log_v3(
 
     // Check version compatibility
     if authorship_log.metadata.schema_version != AUTHORSHIP_LOG_VERSION {
-        return Err(GitAiError::Generic(format!(
+        return Err(GitAiError::Notes(format!(
             "Unsupported authorship log version: {} (expected: {})",
             authorship_log.metadata.schema_version, AUTHORSHIP_LOG_VERSION
         )));
@@ -766,16 +833,30 @@ pub fn ref_exists(repo: &Repository, ref_name: &str) -> bool {
 /// Merge notes from a source ref into refs/notes/ai
 /// Uses the 'ours' strategy to combine notes without data loss
 pub fn merge_notes_from_ref(repo: &Repository, source_ref: &str) -> Result<(), GitAiError> {
+    merge_notes_from_ref_into(repo, AI_AUTHORSHIP_REFNAME, source_ref)
+}
+
+/// Merge `source_ref` into `refs/notes/<notes_refname>`, keeping local notes on
+/// conflict.
+pub fn merge_notes_from_ref_into(
+    repo: &Repository,
+    notes_refname: &str,
+    source_ref: &str,
+) -> Result<(), GitAiError> {
     let mut args = repo.global_args_for_exec();
     args.push("notes".to_string());
-    args.push(format!("--ref={}", AI_AUTHORSHIP_REFNAME));
+    args.push(format!("--ref={}", notes_refname));
     args.push("merge".to_string());
     args.push("-s".to_string());
     args.push("ours".to_string());
     args.push("--quiet".to_string());
     args.push(source_ref.to_string());
 
-    tracing::debug!("Merging notes from {} into refs/notes/ai", source_ref);
+    tracing::debug!(
+        "Merging notes from {} into refs/notes/{}",
+        source_ref,
+        notes_refname
+    );
     exec_git(&args)?;
     Ok(())
 }
@@ -850,6 +931,134 @@ pub fn fallback_merge_notes_ours(repo: &Repository, source_ref: &str) -> Result<
     Ok(())
 }
 
+/// Annotated objects whose note was changed on both `refs/notes/ai` and
+/// `source_ref` since their merge base. Call before merging: these are the
+/// notes an "ours" merge resolves by dropping the source side. Without a merge
+/// base every object noted on both sides counts as diverged.
+pub fn diverged_note_objects(
+    repo: &Repository,
+    source_ref: &str,
+) -> Result<HashSet<String>, GitAiError> {
+    let local_ref = format!("refs/notes/{}", AI_AUTHORSHIP_REFNAME);
+    let mut args = repo.global_args_for_exec();
+    args.extend_from_slice(&[
+        "merge-base".to_string(),
+        local_ref.clone(),
+        source_ref.to_string(),
+    ]);
+    let output = exec_git_allow_nonzero(&args)?;
+    let base = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || base.is_empty() {
+        let local: HashSet<String> = list_all_notes(repo, &local_ref)?
+            .into_iter()
+            .map(|(_, object)| object)
+            .collect();
+        return Ok(list_all_notes(repo, source_ref)?
+            .into_iter()
+            .map(|(_, object)| object)
+            .filter(|object| local.contains(object))
+            .collect());
+    }
+
+    let ours = changed_note_objects(repo, &base, &local_ref)?;
+    if ours.is_empty() {
+        return Ok(ours);
+    }
+    let theirs = changed_note_objects(repo, &base, source_ref)?;
+    Ok(ours.intersection(&theirs).cloned().collect())
+}
+
+/// Annotated objects whose note differs between two notes commits.
+fn changed_note_objects(
+    repo: &Repository,
+    from: &str,
+    to: &str,
+) -> Result<HashSet<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend_from_slice(&[
+        "diff-tree".to_string(),
+        "-r".to_string(),
+        "-z".to_string(),
+        "--no-renames".to_string(),
+        "--name-only".to_string(),
+        from.to_string(),
+        to.to_string(),
+    ]);
+    let output = exec_git(&args)?;
+    Ok(output
+        .stdout
+        .split(|byte| *byte == 0)
+        .filter(|path| !path.is_empty())
+        // Fanout directories are part of the path: `ab/cdef...` annotates `abcdef...`.
+        .map(|path| String::from_utf8_lossy(path).replace('/', ""))
+        .collect())
+}
+
+/// After an "ours" merge, union the notes in `diverged` (see
+/// [`diverged_note_objects`]) so attestations only present in the source note
+/// are kept (see [`crate::authorship::notes_merge`]). Notes that changed on one
+/// side only were already merged by git and are left alone, so notes pruned
+/// locally are not brought back. Returns the number of notes rewritten.
+pub fn union_conflicting_notes_from_ref(
+    repo: &Repository,
+    source_ref: &str,
+    diverged: &HashSet<String>,
+) -> Result<usize, GitAiError> {
+    if diverged.is_empty() {
+        return Ok(0);
+    }
+    let local_ref = format!("refs/notes/{}", AI_AUTHORSHIP_REFNAME);
+    let local_notes: HashMap<String, String> = list_all_notes(repo, &local_ref)?
+        .into_iter()
+        .map(|(blob, object)| (object, blob))
+        .collect();
+
+    let conflicts: Vec<(String, String, String)> = list_all_notes(repo, source_ref)?
+        .into_iter()
+        .filter(|(_, object)| diverged.contains(object))
+        .filter_map(|(source_blob, object)| {
+            let local_blob = local_notes.get(&object)?;
+            (*local_blob != source_blob).then(|| (object, local_blob.clone(), source_blob))
+        })
+        .collect();
+    if conflicts.is_empty() {
+        return Ok(0);
+    }
+
+    let blob_oids: Vec<String> = conflicts
+        .iter()
+        .flat_map(|(_, local_blob, source_blob)| [local_blob.clone(), source_blob.clone()])
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let contents = batch_read_blob_contents(repo, &blob_oids)?;
+
+    let mut entries = Vec::new();
+    for (object, local_blob, source_blob) in &conflicts {
+        let (Some(ours), Some(theirs)) = (contents.get(local_blob), contents.get(source_blob))
+        else {
+            continue;
+        };
+        let merged = crate::authorship::notes_merge::merge_note_contents(ours, theirs);
+        if merged != *ours {
+            entries.push((object.clone(), merged));
+        }
+    }
+
+    let rewritten = entries.len();
+    // The union is built from resolved notes; spill it again where it is over
+    // the limit.
+    let entries = note_overflow::spill_oversized(repo, &entries)?;
+    notes_add_batch(repo, &entries)?;
+    tracing::debug!(
+        "unioned {} conflicting notes from {} into {}",
+        rewritten,
+        source_ref,
+        local_ref
+    );
+    Ok(rewritten)
+}
+
 /// List all notes on a given ref. Returns Vec<(note_blob_sha, annotated_object_sha)>.
 fn list_all_notes(repo: &Repository, notes_ref: &str) -> Result<Vec<(String, String)>, GitAiError> {
     // `git notes list` uses --ref to specify which notes ref.
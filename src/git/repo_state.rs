@@ -76,6 +76,55 @@ pub fn common_dir_for_repo_path(path: &Path) -> Option<PathBuf> {
     None
 }
 
+/// Whether `refs/replace/*` objects stand in for history in git commands.
+/// `GIT_NO_REPLACE_OBJECTS` turns replacement off, as it does for git itself.
+pub fn replace_refs_active(common_dir: &Path) -> bool {
+    if std::env::var_os("GIT_NO_REPLACE_OBJECTS").is_some() {
+        return false;
+    }
+    let has_loose = fs::read_dir(common_dir.join("refs").join("replace"))
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false);
+    has_loose || packed_refs_have_replacements(common_dir)
+}
+
+/// Cached per `packed-refs` mtime, since object reads check this repeatedly.
+fn packed_refs_have_replacements(common_dir: &Path) -> bool {
+    use once_cell::sync::Lazy;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use std::time::SystemTime;
+
+    static CACHE: Lazy<Mutex<HashMap<PathBuf, (SystemTime, bool)>>> =
+        Lazy::new(|| Mutex::new(HashMap::new()));
+
+    let path = common_dir.join("packed-refs");
+    let Ok(modified) = fs::metadata(&path).and_then(|meta| meta.modified()) else {
+        return false;
+    };
+    if let Ok(cache) = CACHE.lock()
+        && let Some((cached_at, has_replace)) = cache.get(&path)
+        && *cached_at == modified
+    {
+        return *has_replace;
+    }
+    let has_replace = fs::read_to_string(&path)
+        .map(|content| content.contains(" refs/replace/"))
+        .unwrap_or(false);
+    if let Ok(mut cache) = CACHE.lock() {
+        cache.insert(path, (modified, has_replace));
+    }
+    has_replace
+}
+
+/// Whether parent walks may stop or jump where the object graph does not: a
+/// shallow clone, legacy `info/grafts`, or active replace refs.
+pub fn history_is_grafted(common_dir: &Path) -> bool {
+    common_dir.join("shallow").is_file()
+        || common_dir.join("info").join("grafts").is_file()
+        || replace_refs_active(common_dir)
+}
+
 pub fn read_head_state_for_worktree(worktree: &Path) -> Option<HeadState> {
     use crate::git::fast_reader::{FastRefReader, HeadKind};
     let git_dir = git_dir_for_worktree(worktree)?;
@@ -100,6 +149,90 @@ pub fn read_head_state_for_worktree(worktree: &Path) -> Option<HeadState> {
     }
 }
 
+/// Pending picks of an interrupted multi-commit cherry-pick, read from
+/// `.git/sequencer/todo`.
+///
+/// The stopped pick is listed first, followed by the picks `--continue` will
+/// apply; object names may be abbreviated. Returns `None` unless a cherry-pick
+/// sequence is stopped at the commit recorded in `CHERRY_PICK_HEAD`.
+pub fn cherry_pick_sequencer_todo_for_worktree(worktree: &Path) -> Option<Vec<String>> {
+    let git_dir = git_dir_for_worktree(worktree)?;
+    let cherry_pick_head = fs::read_to_string(git_dir.join("CHERRY_PICK_HEAD")).ok()?;
+    let todo = fs::read_to_string(git_dir.join("sequencer").join("todo")).ok()?;
+    let picks = parse_sequencer_pick_todo(&todo)?;
+    picks
+        .first()
+        .is_some_and(|stopped| cherry_pick_head.trim().starts_with(stopped.as_str()))
+        .then_some(picks)
+}
+
+/// Where an interrupted cherry-pick is stopped: the commit recorded in
+/// `CHERRY_PICK_HEAD` and, for a multi-commit sequence, the raw content of
+/// `.git/sequencer/todo`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CherryPickStop {
+    pub head: String,
+    pub todo: Option<String>,
+}
+
+/// The stop of the cherry-pick in progress in `worktree`, if any.
+pub fn cherry_pick_stop_for_worktree(worktree: &Path) -> Option<CherryPickStop> {
+    let git_dir = git_dir_for_worktree(worktree)?;
+    let head = fs::read_to_string(git_dir.join("CHERRY_PICK_HEAD")).ok()?;
+    let head = head.trim();
+    if !is_valid_git_oid(head) {
+        return None;
+    }
+    let todo = fs::read_to_string(git_dir.join("sequencer").join("todo")).ok();
+    Some(CherryPickStop {
+        head: head.to_string(),
+        todo,
+    })
+}
+
+impl CherryPickStop {
+    /// Whether this stop lies within `sources` (full object names, in pick
+    /// order): `CHERRY_PICK_HEAD` must be one of them, and a sequencer todo must
+    /// list exactly the sources from there on.
+    pub fn matches_sources(&self, sources: &[String]) -> bool {
+        let Some(stopped_at) = sources.iter().position(|source| *source == self.head) else {
+            return false;
+        };
+        let Some(todo) = self.todo.as_deref() else {
+            return stopped_at + 1 == sources.len();
+        };
+        let remaining = &sources[stopped_at..];
+        parse_sequencer_pick_todo(todo).is_some_and(|picks| {
+            picks.len() == remaining.len()
+                && picks
+                    .iter()
+                    .zip(remaining)
+                    .all(|(pick, source)| source.starts_with(pick.as_str()))
+        })
+    }
+}
+
+/// Object names of the `pick` lines in a sequencer todo, or `None` if the todo
+/// contains any other command (e.g. a `revert` sequence).
+fn parse_sequencer_pick_todo(todo: &str) -> Option<Vec<String>> {
+    let mut picks = Vec::new();
+    for line in todo.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        if !matches!(parts.next(), Some("pick" | "p")) {
+            return None;
+        }
+        let oid = parts.next()?;
+        if !oid.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        picks.push(oid.to_string());
+    }
+    Some(picks)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -111,6 +244,34 @@ mod tests {
         fs::write(path, contents).unwrap();
     }
 
+    #[test]
+    fn history_is_grafted_detects_shallow_grafts_and_replace_refs() {
+        let temp = tempfile::tempdir().unwrap();
+        let common_dir = temp.path();
+        assert!(!history_is_grafted(common_dir));
+
+        write_file(
+            &common_dir.join("packed-refs"),
+            "# pack-refs with: peeled fully-peeled sorted\n\
+             1111111111111111111111111111111111111111 refs/heads/main\n",
+        );
+        assert!(!history_is_grafted(common_dir));
+
+        write_file(
+            &common_dir.join("shallow"),
+            "1111111111111111111111111111111111111111\n",
+        );
+        assert!(history_is_grafted(common_dir));
+        fs::remove_file(common_dir.join("shallow")).unwrap();
+
+        write_file(
+            &common_dir.join("refs/replace/2222222222222222222222222222222222222222"),
+            "3333333333333333333333333333333333333333\n",
+        );
+        assert!(replace_refs_active(common_dir));
+        assert!(history_is_grafted(common_dir));
+    }
+
     #[test]
     fn worktree_root_for_path_walks_parent_directories() {
         let temp = tempfile::tempdir().unwrap();
@@ -143,4 +304,72 @@ mod tests {
         assert_eq!(state.branch.as_deref(), Some("main"));
         assert!(!state.detached);
     }
+
+    #[test]
+    fn parse_sequencer_pick_todo_lists_pending_picks_in_order() {
+        let todo = "pick 1a2b3c4 Second change\n\n# comment\np 5d6e7f8 Third change\n";
+        assert_eq!(
+            parse_sequencer_pick_todo(todo),
+            Some(vec!["1a2b3c4".to_string(), "5d6e7f8".to_string()])
+        );
+        assert_eq!(parse_sequencer_pick_todo("revert 1a2b3c4 Undo\n"), None);
+    }
+
+    #[test]
+    fn cherry_pick_sequencer_todo_requires_matching_cherry_pick_head() {
+        let temp = tempfile::tempdir().unwrap();
+        let worktree = temp.path();
+        write_file(&worktree.join(".git/HEAD"), "ref: refs/heads/main\n");
+        write_file(
+            &worktree.join(".git/sequencer/todo"),
+            "pick 1a2b3c4 Second change\npick 5d6e7f8 Third change\n",
+        );
+        assert_eq!(cherry_pick_sequencer_todo_for_worktree(worktree), None);
+
+        write_file(
+            &worktree.join(".git/CHERRY_PICK_HEAD"),
+            "1a2b3c4aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n",
+        );
+        assert_eq!(
+            cherry_pick_sequencer_todo_for_worktree(worktree),
+            Some(vec!["1a2b3c4".to_string(), "5d6e7f8".to_string()])
+        );
+
+        write_file(
+            &worktree.join(".git/CHERRY_PICK_HEAD"),
+            "ffffffffaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n",
+        );
+        assert_eq!(cherry_pick_sequencer_todo_for_worktree(worktree), None);
+    }
+
+    #[test]
+    fn cherry_pick_stop_matches_sources_against_head_and_todo() {
+        let temp = tempfile::tempdir().unwrap();
+        let worktree = temp.path();
+        write_file(&worktree.join(".git/HEAD"), "ref: refs/heads/main\n");
+        assert_eq!(cherry_pick_stop_for_worktree(worktree), None);
+
+        let second = "1a2b3c4aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string();
+        let third = "5d6e7f8bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string();
+        write_file(
+            &worktree.join(".git/CHERRY_PICK_HEAD"),
+            &format!("{second}\n"),
+        );
+        let single = cherry_pick_stop_for_worktree(worktree).unwrap();
+        assert_eq!(single.todo, None);
+        assert!(single.matches_sources(std::slice::from_ref(&second)));
+        assert!(!single.matches_sources(std::slice::from_ref(&third)));
+
+        write_file(
+            &worktree.join(".git/sequencer/todo"),
+            "pick 1a2b3c4 Second change\npick 5d6e7f8 Third change\n",
+        );
+        let sequence = cherry_pick_stop_for_worktree(worktree).unwrap();
+        assert!(sequence.matches_sources(&[second.clone(), third.clone()]));
+        // A sequence that already applied earlier picks still stops inside it.
+        let first = "9f9f9f9ccccccccccccccccccccccccccccccccc".to_string();
+        assert!(sequence.matches_sources(&[first, second.clone(), third.clone()]));
+        assert!(!sequence.matches_sources(std::slice::from_ref(&second)));
+        assert!(!sequence.matches_sources(&[third, second]));
+    }
 }
@@ -3,10 +3,11 @@ use crate::authorship::authorship_log::{HumanRecord, PromptRecord, SessionRecord
 use crate::authorship::authorship_log_serialization::generate_short_hash;
 use crate::authorship::working_log::{CHECKPOINT_API_VERSION, Checkpoint, CheckpointKind};
 use crate::error::GitAiError;
+use crate::git::journal;
 use crate::utils::normalize_to_posix;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
@@ -15,6 +16,36 @@ use std::time::{Duration, SystemTime};
 
 pub const MAX_CHECKPOINTS_JSONL_BYTES: u64 = 1024 * 1024 * 1024;
 
+/// Version of the on-disk `.git/ai` layout written by this build.
+///
+/// - 1: legacy layout without a version marker (INITIAL, checkpoints.jsonl, blobs/).
+///   Checkpoints may still carry 7-char prompt hashes or stale checkpoint API versions.
+/// - 2: `STORAGE_VERSION` marker present; checkpoints are stored with 16-char prompt
+///   hashes and only the current checkpoint API version.
+pub const STORAGE_FORMAT_VERSION: u32 = 2;
+
+/// Storage version assumed for `.git/ai` directories that predate the version marker.
+pub const LEGACY_STORAGE_FORMAT_VERSION: u32 = 1;
+
+pub(crate) const STORAGE_VERSION_FILE: &str = "STORAGE_VERSION";
+
+pub(crate) const DETACHED_HEAD_FILE: &str = "detached_head.json";
+
+pub(crate) const SESSION_OVERRIDE_FILE: &str = "session_override.json";
+
+pub(crate) const STATS_BASELINES_FILE: &str = "stats_baselines.json";
+
+pub(crate) const PENDING_NOTES_FILE: &str = "pending_notes.jsonl";
+
+pub(crate) const PENDING_REPAIRS_FILE: &str = "pending_repairs.jsonl";
+
+pub(crate) const CONFLICT_RESOLUTIONS_FILE: &str = "conflict_resolutions.jsonl";
+
+pub(crate) const SUBTREE_SPLITS_FILE: &str = "subtree_splits.jsonl";
+
+/// Records the directory working logs were moved to, when not `ai/working_logs`.
+pub const WORKING_LOGS_LOCATION_FILE: &str = "location";
+
 #[cfg(feature = "test-support")]
 const TEST_CHECKPOINTS_JSONL_MAX_BYTES_ENV: &str = "GIT_AI_TEST_CHECKPOINTS_JSONL_MAX_BYTES";
 
@@ -36,9 +67,155 @@ pub struct InitialAttributions {
     pub sessions: std::collections::BTreeMap<String, SessionRecord>,
 }
 
+/// Where a worktree's HEAD was when it left a branch for a detached commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DetachedHeadRecord {
+    /// Branch that was checked out before detaching, when known.
+    pub branch: Option<String>,
+    /// Commit HEAD was at on that branch.
+    pub branch_head: String,
+    /// Latest detached commit checked out.
+    pub detached_head: String,
+}
+
+/// Set by `git-ai session start`: untracked human checkpoints in this repository
+/// are attributed to `tool`/`model` until the session ends or expires.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionOverrideRecord {
+    pub tool: String,
+    pub model: String,
+    pub session_id: String,
+    /// Unix seconds.
+    pub started_at: i64,
+    /// Unix seconds after which the override is ignored, so a forgotten
+    /// `session end` cannot misattribute later human work indefinitely.
+    pub expires_at: i64,
+}
+
+impl SessionOverrideRecord {
+    pub fn is_expired_at(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+}
+
+/// A named commit saved by `git-ai stats --save-baseline`; `stats --baseline`
+/// reports the AI stats of everything committed since.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatsBaseline {
+    pub commit_sha: String,
+    /// Unix seconds.
+    pub created_at: i64,
+}
+
+/// An authorship note journaled before it is written, so a write lost to a
+/// crash or a failing notes backend is replayed later.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingNoteRecord {
+    pub commit_sha: String,
+    pub note: String,
+}
+
+/// Files of an in-progress merge resolved by an AI merge tool, recorded by
+/// `git-ai checkpoint --conflict-resolution` and folded into the merge
+/// commit's note.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflictResolutionRecord {
+    pub tool: String,
+    pub model: String,
+    /// Repository-relative paths.
+    pub files: Vec<String>,
+    /// HEAD when the resolution was recorded: the merge commit's first parent.
+    pub head: String,
+    /// The commits being merged in (`MERGE_HEAD`).
+    pub merge_heads: Vec<String>,
+    /// Unix seconds.
+    pub timestamp: u64,
+}
+
+impl ConflictResolutionRecord {
+    /// Whether this resolution belongs to a merge commit with `parents`.
+    pub fn applies_to(&self, parents: &[String]) -> bool {
+        parents.len() > 1
+            && parents[0] == self.head
+            && self
+                .merge_heads
+                .iter()
+                .all(|merge_head| parents[1..].contains(merge_head))
+    }
+}
+
+/// The split history `git subtree split` produced for `prefix/` at `mainline`,
+/// so handling the same split again does not rerun it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubtreeSplitRecord {
+    pub prefix: String,
+    pub mainline: String,
+    pub split: String,
+}
+
+/// Background work that outlived its timeout and was deferred to `git-ai repair`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepairKind {
+    FetchNotes,
+    PushNotes,
+    /// Attribution recovery skipped by a post-commit in degraded mode.
+    RecoverAttribution,
+}
+
+impl RepairKind {
+    /// Key of this work in the `hook_timeouts` config and `repair` output.
+    pub fn hook_name(self) -> &'static str {
+        match self {
+            RepairKind::FetchNotes => "fetch_notes",
+            RepairKind::PushNotes => "push_notes",
+            RepairKind::RecoverAttribution => "recover_attribution",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingRepairRecord {
+    pub kind: RepairKind,
+    /// Remote of a deferred notes fetch or push.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub remote: String,
+    /// Commit of a deferred [`RepairKind::RecoverAttribution`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_sha: Option<String>,
+    /// Unix seconds.
+    pub deferred_at: i64,
+}
+
+impl PendingRepairRecord {
+    /// What the deferred work applies to: its commit, or else its remote.
+    pub fn target(&self) -> &str {
+        self.commit_sha.as_deref().unwrap_or(&self.remote)
+    }
+
+    fn is_same_work(&self, other: &PendingRepairRecord) -> bool {
+        self.kind == other.kind
+            && self.remote == other.remote
+            && self.commit_sha == other.commit_sha
+    }
+}
+
+/// Outcome of [`RepoStorage::migrate_storage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageMigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub working_logs_migrated: usize,
+    pub backup_dir: Option<PathBuf>,
+}
+
 #[derive(Debug, Clone)]
 pub struct RepoStorage {
     pub ai_dir: PathBuf,
+    /// The `.git/ai` directory of the main repository. Equals `ai_dir` except
+    /// for worktrees with isolated storage; state that is shared by every
+    /// worktree, like journaled notes, lives here.
+    pub common_ai_dir: PathBuf,
     pub repo_workdir: PathBuf,
     pub working_logs: PathBuf,
     pub logs: PathBuf,
@@ -46,31 +223,162 @@ pub struct RepoStorage {
 
 impl RepoStorage {
     pub fn for_repo_path(repo_path: &Path, repo_workdir: &Path) -> Result<RepoStorage, GitAiError> {
-        Self::for_ai_dir(&repo_path.join("ai"), repo_workdir)
+        let ai_dir = repo_path.join("ai");
+        Self::for_ai_dir(&ai_dir, &ai_dir, repo_workdir)
     }
 
     pub fn for_isolated_worktree_storage(
         ai_dir: &Path,
+        common_ai_dir: &Path,
         repo_workdir: &Path,
     ) -> Result<RepoStorage, GitAiError> {
-        Self::for_ai_dir(ai_dir, repo_workdir)
+        Self::for_ai_dir(ai_dir, common_ai_dir, repo_workdir)
     }
 
-    fn for_ai_dir(ai_dir: &Path, repo_workdir: &Path) -> Result<RepoStorage, GitAiError> {
-        let working_logs_dir = ai_dir.join("working_logs");
+    fn for_ai_dir(
+        ai_dir: &Path,
+        common_ai_dir: &Path,
+        repo_workdir: &Path,
+    ) -> Result<RepoStorage, GitAiError> {
+        let working_logs_dir = resolve_working_logs_dir(ai_dir, configured_working_logs_base)?;
         let logs_dir = ai_dir.join("logs");
 
         let config = RepoStorage {
             ai_dir: ai_dir.to_path_buf(),
+            common_ai_dir: common_ai_dir.to_path_buf(),
             repo_workdir: repo_workdir.to_path_buf(),
             working_logs: working_logs_dir,
             logs: logs_dir,
         };
 
         config.ensure_config_directory()?;
+        config.ensure_supported_storage_version()?;
         Ok(config)
     }
 
+    fn storage_version_file(&self) -> PathBuf {
+        self.ai_dir.join(STORAGE_VERSION_FILE)
+    }
+
+    /// Returns the storage format version of this `.git/ai` directory.
+    ///
+    /// Directories without a version marker are legacy (version 1).
+    pub fn storage_format_version(&self) -> Result<u32, GitAiError> {
+        match fs::read_to_string(self.storage_version_file()) {
+            Ok(raw) => raw.trim().parse::<u32>().map_err(|_| {
+                GitAiError::Storage(format!(
+                    "Unreadable git-ai storage version '{}' in {}",
+                    raw.trim(),
+                    self.storage_version_file().display()
+                ))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(LEGACY_STORAGE_FORMAT_VERSION),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn write_storage_format_version(&self, version: u32) -> Result<(), GitAiError> {
+        fs::write(self.storage_version_file(), format!("{}\n", version))?;
+        Ok(())
+    }
+
+    /// Refuses to operate on storage written by a newer git-ai, and stamps fresh
+    /// (empty) storage with the current version so it never needs migrating.
+    fn ensure_supported_storage_version(&self) -> Result<(), GitAiError> {
+        if self.storage_version_file().exists() {
+            let version = self.storage_format_version()?;
+            if version > STORAGE_FORMAT_VERSION {
+                return Err(GitAiError::Storage(format!(
+                    "git-ai storage in {} uses format version {}, but this git-ai only supports up to version {}. Upgrade git-ai with `git-ai upgrade`.",
+                    self.ai_dir.display(),
+                    version,
+                    STORAGE_FORMAT_VERSION
+                )));
+            }
+            return Ok(());
+        }
+
+        let has_working_logs = fs::read_dir(&self.working_logs)
+            .map(|mut entries| entries.next().is_some())
+            .unwrap_or(false);
+        if !has_working_logs {
+            self.write_storage_format_version(STORAGE_FORMAT_VERSION)?;
+        }
+        Ok(())
+    }
+
+    /// Upgrades this `.git/ai` directory in place to [`STORAGE_FORMAT_VERSION`].
+    ///
+    /// The existing working logs are copied to `backups/storage-v<from>-<timestamp>/`
+    /// before any file is rewritten. With `dry_run`, nothing is written.
+    pub fn migrate_storage(&self, dry_run: bool) -> Result<StorageMigrationReport, GitAiError> {
+        let from_version = self.storage_format_version()?;
+        let mut report = StorageMigrationReport {
+            from_version,
+            to_version: STORAGE_FORMAT_VERSION,
+            working_logs_migrated: 0,
+            backup_dir: None,
+        };
+        if from_version > STORAGE_FORMAT_VERSION {
+            return Err(GitAiError::Storage(format!(
+                "Cannot migrate git-ai storage from version {} (newer than supported version {})",
+                from_version, STORAGE_FORMAT_VERSION
+            )));
+        }
+        if from_version == STORAGE_FORMAT_VERSION {
+            return Ok(report);
+        }
+
+        let working_log_dirs: Vec<PathBuf> = fs::read_dir(&self.working_logs)?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        report.working_logs_migrated = working_log_dirs.len();
+        if dry_run {
+            return Ok(report);
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO)
+            .as_secs();
+        let backup_dir = self
+            .ai_dir
+            .join("backups")
+            .join(format!("storage-v{}-{}", from_version, timestamp));
+        copy_dir_contents(&self.working_logs, &backup_dir.join("working_logs"))?;
+        report.backup_dir = Some(backup_dir);
+
+        let canonical_workdir = self
+            .repo_workdir
+            .canonicalize()
+            .unwrap_or_else(|_| self.repo_workdir.clone());
+        for dir in working_log_dirs {
+            let base_commit = dir
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let working_log = PersistedWorkingLog::new(
+                dir,
+                &base_commit,
+                self.repo_workdir.clone(),
+                canonical_workdir.clone(),
+                None,
+            );
+            if !working_log.checkpoints_file().exists() {
+                continue;
+            }
+            // Reading applies the legacy upgrades (prompt hash widening, dropping
+            // unsupported API versions); writing persists them in the new format.
+            let checkpoints = working_log.read_all_checkpoints()?;
+            working_log.write_all_checkpoints(&checkpoints)?;
+        }
+
+        self.write_storage_format_version(STORAGE_FORMAT_VERSION)?;
+        Ok(report)
+    }
+
     #[doc(hidden)]
     pub fn ensure_config_directory(&self) -> Result<(), GitAiError> {
         fs::create_dir_all(&self.ai_dir)?;
@@ -84,6 +392,253 @@ impl RepoStorage {
         Ok(())
     }
 
+    /// Whether the working logs live outside `ai/working_logs`.
+    pub fn working_logs_relocated(&self) -> bool {
+        self.working_logs != self.ai_dir.join("working_logs")
+    }
+
+    /// Move the working logs to `target`, or back to `ai/working_logs` with
+    /// `None`, and record the new location in `ai/location`.
+    pub fn relocate_working_logs(&mut self, target: Option<&Path>) -> Result<(), GitAiError> {
+        let default_dir = self.ai_dir.join("working_logs");
+        let target = match target {
+            Some(target) if !target.is_absolute() => {
+                return Err(GitAiError::Storage(format!(
+                    "working log directory must be an absolute path, got {}",
+                    target.display()
+                )));
+            }
+            Some(target) => target.to_path_buf(),
+            None => default_dir.clone(),
+        };
+        if target == self.working_logs {
+            return Ok(());
+        }
+        if dir_has_entries(&target) {
+            return Err(GitAiError::Storage(format!(
+                "{} is not empty; choose an empty directory for the working logs",
+                target.display()
+            )));
+        }
+
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let _ = fs::remove_dir(&target);
+        if fs::rename(&self.working_logs, &target).is_err() {
+            // Across filesystems: copy, then drop the old directory.
+            copy_dir_contents(&self.working_logs, &target)?;
+            fs::remove_dir_all(&self.working_logs)?;
+        }
+        fs::create_dir_all(&target)?;
+
+        let location_file = self.ai_dir.join(WORKING_LOGS_LOCATION_FILE);
+        if target == default_dir {
+            match fs::remove_file(&location_file) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        } else {
+            write_working_logs_location(&self.ai_dir, &target)?;
+        }
+        self.working_logs = target;
+        Ok(())
+    }
+
+    /* Detached HEAD tracking */
+
+    pub fn read_detached_head(&self) -> Option<DetachedHeadRecord> {
+        let content = fs::read_to_string(self.ai_dir.join(DETACHED_HEAD_FILE)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn write_detached_head(&self, record: &DetachedHeadRecord) -> Result<(), GitAiError> {
+        fs::write(
+            self.ai_dir.join(DETACHED_HEAD_FILE),
+            serde_json::to_string(record)?,
+        )?;
+        Ok(())
+    }
+
+    pub fn clear_detached_head(&self) -> Result<(), GitAiError> {
+        match fs::remove_file(self.ai_dir.join(DETACHED_HEAD_FILE)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /* Session attribution override */
+
+    pub fn read_session_override(&self) -> Option<SessionOverrideRecord> {
+        let content = fs::read_to_string(self.ai_dir.join(SESSION_OVERRIDE_FILE)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn write_session_override(&self, record: &SessionOverrideRecord) -> Result<(), GitAiError> {
+        fs::create_dir_all(&self.ai_dir)?;
+        fs::write(
+            self.ai_dir.join(SESSION_OVERRIDE_FILE),
+            serde_json::to_string(record)?,
+        )?;
+        Ok(())
+    }
+
+    pub fn clear_session_override(&self) -> Result<(), GitAiError> {
+        match fs::remove_file(self.ai_dir.join(SESSION_OVERRIDE_FILE)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// The current override, clearing it once it has expired.
+    pub fn active_session_override(&self, now: i64) -> Option<SessionOverrideRecord> {
+        let record = self.read_session_override()?;
+        if record.is_expired_at(now) {
+            let _ = self.clear_session_override();
+            return None;
+        }
+        Some(record)
+    }
+
+    /* Stats baselines */
+
+    pub fn read_stats_baselines(&self) -> BTreeMap<String, StatsBaseline> {
+        fs::read_to_string(self.ai_dir.join(STATS_BASELINES_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save `baseline` under `name`, replacing any baseline of that name.
+    pub fn write_stats_baseline(
+        &self,
+        name: &str,
+        baseline: &StatsBaseline,
+    ) -> Result<(), GitAiError> {
+        let mut baselines = self.read_stats_baselines();
+        baselines.insert(name.to_string(), baseline.clone());
+        fs::create_dir_all(&self.ai_dir)?;
+        fs::write(
+            self.ai_dir.join(STATS_BASELINES_FILE),
+            serde_json::to_string_pretty(&baselines)?,
+        )?;
+        Ok(())
+    }
+
+    /* Pending note journal */
+
+    /// The journal is shared by every worktree, like the notes ref it feeds.
+    fn pending_notes_path(&self) -> PathBuf {
+        self.common_ai_dir.join(PENDING_NOTES_FILE)
+    }
+
+    pub fn append_pending_note(&self, record: &PendingNoteRecord) -> Result<(), GitAiError> {
+        journal::append(&self.pending_notes_path(), record)
+    }
+
+    /// Journaled notes not yet confirmed written, latest record per commit.
+    /// Lines torn by a crash mid-append are skipped.
+    pub fn read_pending_notes(&self) -> Vec<PendingNoteRecord> {
+        let mut records: Vec<PendingNoteRecord> = Vec::new();
+        for record in journal::read::<PendingNoteRecord>(&self.pending_notes_path()) {
+            records.retain(|existing| existing.commit_sha != record.commit_sha);
+            records.push(record);
+        }
+        records
+    }
+
+    /// Drop journal records for `commit_shas`, deleting the journal once empty.
+    pub fn remove_pending_notes(&self, commit_shas: &[String]) -> Result<(), GitAiError> {
+        journal::retain(&self.pending_notes_path(), |record: &PendingNoteRecord| {
+            !commit_shas.contains(&record.commit_sha)
+        })
+    }
+
+    /// Move the records for `commit_shas` aside: their notes can never be
+    /// written, and retrying them would hold up every later replay.
+    pub fn reject_pending_notes(&self, commit_shas: &[String]) -> Result<(), GitAiError> {
+        journal::reject(&self.pending_notes_path(), |record: &PendingNoteRecord| {
+            commit_shas.contains(&record.commit_sha)
+        })
+    }
+
+    /* Pending repair journal */
+
+    pub fn append_pending_repair(&self, record: &PendingRepairRecord) -> Result<(), GitAiError> {
+        journal::append(&self.ai_dir.join(PENDING_REPAIRS_FILE), record)
+    }
+
+    /// Deferred work not yet repaired, latest record per kind and target.
+    /// Lines torn by a crash mid-append are skipped.
+    pub fn read_pending_repairs(&self) -> Vec<PendingRepairRecord> {
+        let mut records: Vec<PendingRepairRecord> = Vec::new();
+        for record in journal::read::<PendingRepairRecord>(&self.ai_dir.join(PENDING_REPAIRS_FILE))
+        {
+            records.retain(|existing| !existing.is_same_work(&record));
+            records.push(record);
+        }
+        records
+    }
+
+    /// Drop the records for the same work as `repaired`, deleting the journal
+    /// once empty.
+    pub fn remove_pending_repair(&self, repaired: &PendingRepairRecord) -> Result<(), GitAiError> {
+        journal::retain(
+            &self.ai_dir.join(PENDING_REPAIRS_FILE),
+            |record: &PendingRepairRecord| !record.is_same_work(repaired),
+        )
+    }
+
+    /* Merge tool conflict resolutions */
+
+    pub fn append_conflict_resolution(
+        &self,
+        record: &ConflictResolutionRecord,
+    ) -> Result<(), GitAiError> {
+        journal::append(&self.ai_dir.join(CONFLICT_RESOLUTIONS_FILE), record)
+    }
+
+    /// Recorded resolutions, oldest first. Torn lines are skipped.
+    pub fn read_conflict_resolutions(&self) -> Vec<ConflictResolutionRecord> {
+        journal::read(&self.ai_dir.join(CONFLICT_RESOLUTIONS_FILE))
+    }
+
+    /// Drop the resolutions recorded against `head`, deleting the file once
+    /// empty. Called once the merge they belong to is committed.
+    pub fn remove_conflict_resolutions(&self, head: &str) -> Result<(), GitAiError> {
+        journal::retain(
+            &self.ai_dir.join(CONFLICT_RESOLUTIONS_FILE),
+            |record: &ConflictResolutionRecord| record.head != head,
+        )
+    }
+
+    /* Subtree split results */
+
+    /// The journal is shared by every worktree, like the objects it names.
+    fn subtree_splits_path(&self) -> PathBuf {
+        self.common_ai_dir.join(SUBTREE_SPLITS_FILE)
+    }
+
+    /// The split recorded for `prefix` at `mainline`, if any.
+    pub fn read_subtree_split(&self, prefix: &str, mainline: &str) -> Option<String> {
+        journal::read::<SubtreeSplitRecord>(&self.subtree_splits_path())
+            .into_iter()
+            .rev()
+            .find(|record| record.prefix == prefix && record.mainline == mainline)
+            .map(|record| record.split)
+    }
+
+    /// Record the latest split of `record.prefix`, replacing the previous one
+    /// so the journal holds one record per prefix.
+    pub fn record_subtree_split(&self, record: &SubtreeSplitRecord) -> Result<(), GitAiError> {
+        journal::retain(
+            &self.subtree_splits_path(),
+            |existing: &SubtreeSplitRecord| existing.prefix != record.prefix,
+        )?;
+        journal::append(&self.subtree_splits_path(), record)
+    }
+
     /* Working Log Persistance */
 
     pub fn has_working_log(&self, sha: &str) -> bool {
@@ -205,6 +760,109 @@ impl RepoStorage {
         Ok(())
     }
 
+    /* Working log forks */
+
+    fn working_log_fork_dir(&self, branch: &str) -> PathBuf {
+        let encoded = branch.replace('%', "%25").replace('/', "%2F");
+        self.ai_dir.join("working_log_forks").join(encoded)
+    }
+
+    /// Copy the working log for `sha` aside for `branch`, replacing any earlier
+    /// fork of that branch. Used when another branch starts sharing `sha` while
+    /// it has pending attribution: whichever branch commits first consumes the
+    /// shared log, and the fork keeps `branch`'s copy. Returns whether a log was
+    /// forked.
+    pub fn fork_working_log(&self, sha: &str, branch: &str) -> Result<bool, GitAiError> {
+        let source = self.working_logs.join(sha);
+        if !source.exists() {
+            return Ok(false);
+        }
+        let fork_dir = self.working_log_fork_dir(branch);
+        if fork_dir.exists() {
+            fs::remove_dir_all(&fork_dir)?;
+        }
+        copy_dir_contents(&source, &fork_dir)?;
+        fs::write(fork_dir.join(".fork_base"), sha)?;
+        tracing::debug!("Forked working log {} for branch {}", sha, branch);
+        Ok(true)
+    }
+
+    /// Bring back `branch`'s fork now that the branch is checked out at `sha`.
+    ///
+    /// A fork taken at another commit is stale and dropped. If the shared log
+    /// was consumed in the meantime the fork takes its place; if the log still
+    /// holds everything the fork does, the fork is redundant and dropped;
+    /// otherwise the fork is merged in ahead of the current entries. Returns
+    /// whether attribution was restored from the fork.
+    pub fn restore_working_log_fork(&self, branch: &str, sha: &str) -> Result<bool, GitAiError> {
+        let fork_dir = self.working_log_fork_dir(branch);
+        let Ok(fork_base) = fs::read_to_string(fork_dir.join(".fork_base")) else {
+            return Ok(false);
+        };
+        if fork_base.trim() != sha {
+            fs::remove_dir_all(&fork_dir)?;
+            return Ok(false);
+        }
+        let _ = fs::remove_file(fork_dir.join(".fork_base"));
+
+        let current_dir = self.working_logs.join(sha);
+        if !current_dir.exists() {
+            fs::rename(&fork_dir, &current_dir)?;
+            tracing::debug!("Restored working log {} from fork of {}", sha, branch);
+            return Ok(true);
+        }
+
+        let canonical = self
+            .repo_workdir
+            .canonicalize()
+            .unwrap_or_else(|_| self.repo_workdir.clone());
+        let fork_log = PersistedWorkingLog::new(
+            fork_dir.clone(),
+            sha,
+            self.repo_workdir.clone(),
+            canonical.clone(),
+            None,
+        );
+        let current_log = PersistedWorkingLog::new(
+            current_dir.clone(),
+            sha,
+            self.repo_workdir.clone(),
+            canonical,
+            None,
+        );
+        let checkpoint_key = |checkpoint: &Checkpoint| {
+            (
+                checkpoint.timestamp,
+                checkpoint.author.clone(),
+                checkpoint.trace_id.clone(),
+            )
+        };
+        let fork_keys: Vec<_> = fork_log
+            .read_all_checkpoints()?
+            .iter()
+            .map(checkpoint_key)
+            .collect();
+        let current_keys: Vec<_> = current_log
+            .read_all_checkpoints()?
+            .iter()
+            .map(checkpoint_key)
+            .collect();
+        let current_initial = current_log.read_initial_attributions();
+        let same_lineage = current_keys.starts_with(&fork_keys)
+            && fork_log
+                .read_initial_attributions()
+                .files
+                .keys()
+                .all(|file| current_initial.files.contains_key(file));
+        let restored = !same_lineage;
+        if restored {
+            self.merge_working_log_dirs(sha, sha, &fork_dir, &current_dir)?;
+            tracing::debug!("Merged fork of {} into working log {}", branch, sha);
+        }
+        fs::remove_dir_all(&fork_dir)?;
+        Ok(restored)
+    }
+
     fn merge_working_log_dirs(
         &self,
         old_sha: &str,
@@ -264,7 +922,71 @@ impl RepoStorage {
     }
 }
 
-fn copy_dir_contents(src: &Path, dst: &Path) -> Result<(), GitAiError> {
+/// The global `working_logs_dir` setting, the base directory new repositories
+/// keep their working logs under.
+fn configured_working_logs_base() -> Option<PathBuf> {
+    crate::config::effective_file_config_cached()
+        .working_logs_dir
+        .as_deref()
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+}
+
+/// Where the working logs of `ai_dir` live: the directory recorded in
+/// `ai/location`, otherwise `ai/working_logs`. Storage created from scratch is
+/// pointed at a per-repository directory under the configured global base
+/// instead; repositories git-ai already tracks only move when asked to, with
+/// [`RepoStorage::relocate_working_logs`] (`git-ai migrate-storage
+/// --working-logs-dir`).
+fn resolve_working_logs_dir(
+    ai_dir: &Path,
+    configured_base: impl FnOnce() -> Option<PathBuf>,
+) -> Result<PathBuf, GitAiError> {
+    if let Some(dir) = read_working_logs_location(ai_dir) {
+        return Ok(dir);
+    }
+    let default_dir = ai_dir.join("working_logs");
+    if ai_dir.exists() {
+        return Ok(default_dir);
+    }
+    let Some(base) = configured_base() else {
+        return Ok(default_dir);
+    };
+
+    let canonical_ai_dir = fs::create_dir_all(ai_dir)
+        .and_then(|_| ai_dir.canonicalize())
+        .unwrap_or_else(|_| ai_dir.to_path_buf());
+    let key = format!(
+        "{:x}",
+        Sha256::digest(canonical_ai_dir.to_string_lossy().as_bytes())
+    );
+    let dir = base.join(&key[..16]).join("working_logs");
+    write_working_logs_location(ai_dir, &dir)?;
+    Ok(dir)
+}
+
+fn read_working_logs_location(ai_dir: &Path) -> Option<PathBuf> {
+    let raw = fs::read_to_string(ai_dir.join(WORKING_LOGS_LOCATION_FILE)).ok()?;
+    let dir = PathBuf::from(raw.trim());
+    (!raw.trim().is_empty() && dir.is_absolute()).then_some(dir)
+}
+
+fn write_working_logs_location(ai_dir: &Path, dir: &Path) -> Result<(), GitAiError> {
+    fs::create_dir_all(ai_dir)?;
+    fs::write(
+        ai_dir.join(WORKING_LOGS_LOCATION_FILE),
+        format!("{}\n", dir.display()),
+    )?;
+    Ok(())
+}
+
+fn dir_has_entries(dir: &Path) -> bool {
+    fs::read_dir(dir)
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false)
+}
+
+pub(crate) fn copy_dir_contents(src: &Path, dst: &Path) -> Result<(), GitAiError> {
     if !src.exists() {
         return Ok(());
     }
@@ -445,17 +1167,32 @@ impl PersistedWorkingLog {
             return Ok(content.clone());
         }
 
-        Err(GitAiError::Generic(format!(
+        Err(GitAiError::Storage(format!(
             "read_current_file_content: file '{}' not found in dirty_files snapshot (filesystem fallback is not allowed in checkpoint flow)",
             file_path
         )))
     }
 
     /* append checkpoint */
-    pub fn append_checkpoint(&self, checkpoint: &Checkpoint) -> Result<(), GitAiError> {
+    /// Appends `checkpoint` to the working log. Returns `false` without writing
+    /// when it duplicates the last checkpoint (see [`Checkpoint::is_duplicate_of`]).
+    pub fn append_checkpoint(&self, checkpoint: &Checkpoint) -> Result<bool, GitAiError> {
         // Read existing checkpoints
         let mut checkpoints = self.read_all_checkpoints().unwrap_or_default();
 
+        if checkpoints
+            .last()
+            .is_some_and(|previous| checkpoint.is_duplicate_of(previous))
+        {
+            tracing::debug!(
+                "skipping duplicate {} checkpoint {} from {}",
+                checkpoint.kind.to_str(),
+                checkpoint.diff,
+                checkpoint.author
+            );
+            return Ok(false);
+        }
+
         // Create a copy, potentially without transcript to reduce storage size.
         //
         // Tools that DON'T support refetch (transcript must be kept):
@@ -468,7 +1205,8 @@ impl PersistedWorkingLog {
         self.prune_old_char_attributions(&mut checkpoints);
 
         // Write all checkpoints back
-        self.write_all_checkpoints(&checkpoints)
+        self.write_all_checkpoints(&checkpoints)?;
+        Ok(true)
     }
 
     pub fn read_all_checkpoints(&self) -> Result<Vec<Checkpoint>, GitAiError> {
@@ -611,7 +1349,7 @@ impl PersistedWorkingLog {
             "checkpoints.jsonl exceeded maximum size; deleting and recreating empty file"
         );
         crate::observability::log_error(
-            &GitAiError::Generic(message),
+            &GitAiError::Storage(message),
             Some(serde_json::json!({
                 "event": "checkpoints_jsonl_oversized_reset",
                 "base_commit": self.base_commit,
@@ -704,7 +1442,7 @@ impl PersistedWorkingLog {
             let content = self
                 .stored_initial_file_content_from(&initial, file_path)
                 .ok_or_else(|| {
-                    GitAiError::Generic(format!(
+                    GitAiError::Storage(format!(
                         "INITIAL missing persisted file snapshot for {}",
                         file_path
                     ))
@@ -714,7 +1452,13 @@ impl PersistedWorkingLog {
 
         for checkpoint in self.read_all_checkpoints()? {
             for entry in checkpoint.entries {
-                let content = self.get_file_version(&entry.blob_sha)?;
+                // Entries for files that were already gone when checkpointed
+                // carry no blob; their observed state is empty.
+                let content = if entry.blob_sha.is_empty() {
+                    String::new()
+                } else {
+                    self.get_file_version(&entry.blob_sha)?
+                };
                 snapshot.insert(entry.file, content);
             }
         }
@@ -722,7 +1466,6 @@ impl PersistedWorkingLog {
         Ok(snapshot)
     }
 
-    #[allow(dead_code)]
     pub fn all_ai_touched_files(&self) -> Result<HashSet<String>, GitAiError> {
         let checkpoints = self.read_all_checkpoints()?;
         let mut touched_files = HashSet::new();
@@ -760,7 +1503,7 @@ impl PersistedWorkingLog {
         let mut file_blobs = HashMap::new();
         for file_path in filtered.keys() {
             let content = file_contents.get(file_path).ok_or_else(|| {
-                GitAiError::Generic(format!(
+                GitAiError::Storage(format!(
                     "INITIAL missing file content snapshot for {}",
                     file_path
                 ))
@@ -819,7 +1562,7 @@ impl PersistedWorkingLog {
             return Ok(Some(content));
         }
         if initial.files.contains_key(file_path) {
-            return Err(GitAiError::Generic(format!(
+            return Err(GitAiError::Storage(format!(
                 "INITIAL missing persisted file snapshot for {}",
                 file_path
             )));
@@ -892,6 +1635,304 @@ mod tests {
         vec![LineAttribution::new(1, 1, author.to_string(), None)]
     }
 
+    #[test]
+    fn test_fresh_storage_is_stamped_with_current_version() {
+        let tmp = TempDir::new().unwrap();
+        let storage = RepoStorage::for_repo_path(&tmp.path().join("repo"), tmp.path()).unwrap();
+        assert_eq!(
+            storage.storage_format_version().unwrap(),
+            STORAGE_FORMAT_VERSION
+        );
+    }
+
+    #[test]
+    fn test_detached_head_record_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let storage = RepoStorage::for_repo_path(&tmp.path().join("repo"), tmp.path()).unwrap();
+        assert_eq!(storage.read_detached_head(), None);
+
+        let record = DetachedHeadRecord {
+            branch: Some("main".to_string()),
+            branch_head: "a".repeat(40),
+            detached_head: "b".repeat(40),
+        };
+        storage.write_detached_head(&record).unwrap();
+        assert_eq!(storage.read_detached_head(), Some(record));
+
+        storage.clear_detached_head().unwrap();
+        assert_eq!(storage.read_detached_head(), None);
+        storage.clear_detached_head().unwrap();
+    }
+
+    #[test]
+    fn test_session_override_expires() {
+        let tmp = TempDir::new().unwrap();
+        let storage = RepoStorage::for_repo_path(&tmp.path().join("repo"), tmp.path()).unwrap();
+        assert_eq!(storage.active_session_override(100), None);
+
+        let record = SessionOverrideRecord {
+            tool: "aider".to_string(),
+            model: "gpt-4o".to_string(),
+            session_id: "abc".to_string(),
+            started_at: 100,
+            expires_at: 200,
+        };
+        storage.write_session_override(&record).unwrap();
+        assert_eq!(storage.active_session_override(199), Some(record));
+
+        assert_eq!(storage.active_session_override(200), None);
+        assert_eq!(storage.read_session_override(), None);
+        storage.clear_session_override().unwrap();
+    }
+
+    #[test]
+    fn test_stats_baselines_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let storage = RepoStorage::for_repo_path(&tmp.path().join("repo"), tmp.path()).unwrap();
+        assert!(storage.read_stats_baselines().is_empty());
+
+        let first = StatsBaseline {
+            commit_sha: "a".repeat(40),
+            created_at: 100,
+        };
+        let second = StatsBaseline {
+            commit_sha: "b".repeat(40),
+            created_at: 200,
+        };
+        storage.write_stats_baseline("release-1.0", &first).unwrap();
+        storage.write_stats_baseline("release-1.1", &first).unwrap();
+        storage
+            .write_stats_baseline("release-1.1", &second)
+            .unwrap();
+
+        let baselines = storage.read_stats_baselines();
+        assert_eq!(baselines.len(), 2);
+        assert_eq!(baselines["release-1.0"], first);
+        assert_eq!(baselines["release-1.1"], second);
+    }
+
+    #[test]
+    fn test_pending_notes_journal_keeps_latest_and_skips_torn_lines() {
+        let tmp = TempDir::new().unwrap();
+        let storage = RepoStorage::for_repo_path(&tmp.path().join("repo"), tmp.path()).unwrap();
+        let record = |sha: &str, note: &str| PendingNoteRecord {
+            commit_sha: sha.to_string(),
+            note: note.to_string(),
+        };
+        storage.append_pending_note(&record("a", "old")).unwrap();
+        storage.append_pending_note(&record("b", "b")).unwrap();
+        storage.append_pending_note(&record("a", "new")).unwrap();
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .open(storage.ai_dir.join(PENDING_NOTES_FILE))
+            .unwrap();
+        write!(file, "{{\"commit_sha\": \"c").unwrap();
+        // The next append is not swallowed by the torn tail.
+        storage.append_pending_note(&record("d", "d")).unwrap();
+
+        assert_eq!(
+            storage.read_pending_notes(),
+            vec![record("b", "b"), record("a", "new"), record("d", "d")]
+        );
+
+        storage
+            .remove_pending_notes(&["a".to_string(), "d".to_string()])
+            .unwrap();
+        assert_eq!(storage.read_pending_notes(), vec![record("b", "b")]);
+        storage.reject_pending_notes(&["b".to_string()]).unwrap();
+        assert!(!storage.ai_dir.join(PENDING_NOTES_FILE).exists());
+    }
+
+    #[test]
+    fn test_pending_repairs_keep_latest_per_kind_and_remote() {
+        let tmp = TempDir::new().unwrap();
+        let storage = RepoStorage::for_repo_path(&tmp.path().join("repo"), tmp.path()).unwrap();
+        let record = |kind: RepairKind, remote: &str, deferred_at: i64| PendingRepairRecord {
+            kind,
+            remote: remote.to_string(),
+            commit_sha: None,
+            deferred_at,
+        };
+        storage
+            .append_pending_repair(&record(RepairKind::FetchNotes, "origin", 1))
+            .unwrap();
+        storage
+            .append_pending_repair(&record(RepairKind::PushNotes, "origin", 2))
+            .unwrap();
+        storage
+            .append_pending_repair(&record(RepairKind::FetchNotes, "origin", 3))
+            .unwrap();
+
+        assert_eq!(
+            storage.read_pending_repairs(),
+            vec![
+                record(RepairKind::PushNotes, "origin", 2),
+                record(RepairKind::FetchNotes, "origin", 3)
+            ]
+        );
+
+        let recovery = PendingRepairRecord {
+            kind: RepairKind::RecoverAttribution,
+            remote: String::new(),
+            commit_sha: Some("abc123".to_string()),
+            deferred_at: 4,
+        };
+        storage.append_pending_repair(&recovery).unwrap();
+        assert_eq!(storage.read_pending_repairs().len(), 3);
+        assert_eq!(storage.read_pending_repairs()[2].target(), "abc123");
+
+        storage
+            .remove_pending_repair(&record(RepairKind::PushNotes, "origin", 0))
+            .unwrap();
+        storage
+            .remove_pending_repair(&record(RepairKind::FetchNotes, "upstream", 0))
+            .unwrap();
+        storage.remove_pending_repair(&recovery).unwrap();
+        assert_eq!(
+            storage.read_pending_repairs(),
+            vec![record(RepairKind::FetchNotes, "origin", 3)]
+        );
+        storage
+            .remove_pending_repair(&record(RepairKind::FetchNotes, "origin", 0))
+            .unwrap();
+        assert!(!storage.ai_dir.join(PENDING_REPAIRS_FILE).exists());
+    }
+
+    #[test]
+    fn test_working_logs_resolve_through_location_file() {
+        let tmp = TempDir::new().unwrap();
+        let ai_dir = tmp.path().join("repo").join("ai");
+        let base = tmp.path().join("elsewhere");
+
+        assert_eq!(
+            resolve_working_logs_dir(&ai_dir, || None).unwrap(),
+            ai_dir.join("working_logs")
+        );
+        let relocated = resolve_working_logs_dir(&ai_dir, || Some(base.clone())).unwrap();
+        assert!(relocated.starts_with(&base), "{}", relocated.display());
+        assert_eq!(read_working_logs_location(&ai_dir), Some(relocated.clone()));
+        // Once recorded, the location wins regardless of the global setting.
+        assert_eq!(
+            resolve_working_logs_dir(&ai_dir, || None).unwrap(),
+            relocated
+        );
+
+        // Existing storage is never relocated without an explicit migration,
+        // with or without working logs in it.
+        let other_ai_dir = tmp.path().join("other").join("ai");
+        fs::create_dir_all(other_ai_dir.join("working_logs").join("abc")).unwrap();
+        assert_eq!(
+            resolve_working_logs_dir(&other_ai_dir, || Some(base.clone())).unwrap(),
+            other_ai_dir.join("working_logs")
+        );
+        let idle_ai_dir = tmp.path().join("idle").join("ai");
+        fs::create_dir_all(&idle_ai_dir).unwrap();
+        assert_eq!(
+            resolve_working_logs_dir(&idle_ai_dir, || Some(base.clone())).unwrap(),
+            idle_ai_dir.join("working_logs")
+        );
+        assert_eq!(read_working_logs_location(&idle_ai_dir), None);
+    }
+
+    #[test]
+    fn test_relocate_working_logs_moves_logs_and_back() {
+        let tmp = TempDir::new().unwrap();
+        let workdir = tmp.path().join("work");
+        fs::create_dir_all(&workdir).unwrap();
+        let mut storage = RepoStorage::for_repo_path(&tmp.path().join("repo"), &workdir).unwrap();
+        let sha = "1111111111111111111111111111111111111111";
+        storage
+            .working_log_for_base_commit(sha)
+            .unwrap()
+            .reset_working_log()
+            .unwrap();
+
+        let target = tmp.path().join("logs-outside-git");
+        assert!(
+            storage
+                .relocate_working_logs(Some(Path::new("relative")))
+                .is_err()
+        );
+        storage.relocate_working_logs(Some(&target)).unwrap();
+        assert!(storage.working_logs_relocated());
+        assert!(storage.has_working_log(sha));
+        assert!(!storage.ai_dir.join("working_logs").join(sha).exists());
+
+        let reopened = RepoStorage::for_repo_path(&tmp.path().join("repo"), &workdir).unwrap();
+        assert_eq!(reopened.working_logs, target);
+
+        storage.relocate_working_logs(None).unwrap();
+        assert!(!storage.working_logs_relocated());
+        assert!(storage.ai_dir.join("working_logs").join(sha).exists());
+        assert!(!storage.ai_dir.join(WORKING_LOGS_LOCATION_FILE).exists());
+    }
+
+    #[test]
+    fn test_storage_from_newer_version_is_refused() {
+        let tmp = TempDir::new().unwrap();
+        let ai_dir = tmp.path().join("repo").join("ai");
+        fs::create_dir_all(&ai_dir).unwrap();
+        fs::write(
+            ai_dir.join(STORAGE_VERSION_FILE),
+            format!("{}", STORAGE_FORMAT_VERSION + 1),
+        )
+        .unwrap();
+
+        let err = RepoStorage::for_repo_path(&tmp.path().join("repo"), tmp.path()).unwrap_err();
+        assert!(err.to_string().contains("git-ai upgrade"), "{}", err);
+    }
+
+    #[test]
+    fn test_migrate_legacy_storage_backs_up_and_stamps_version() {
+        let tmp = TempDir::new().unwrap();
+        let ai_dir = tmp.path().join("repo").join("ai");
+        let sha = "1111111111111111111111111111111111111111";
+        fs::create_dir_all(ai_dir.join("working_logs").join(sha)).unwrap();
+        fs::write(
+            ai_dir
+                .join("working_logs")
+                .join(sha)
+                .join("checkpoints.jsonl"),
+            "",
+        )
+        .unwrap();
+
+        let storage = RepoStorage::for_repo_path(&tmp.path().join("repo"), tmp.path()).unwrap();
+        assert_eq!(
+            storage.storage_format_version().unwrap(),
+            LEGACY_STORAGE_FORMAT_VERSION
+        );
+
+        let dry_run = storage.migrate_storage(true).unwrap();
+        assert_eq!(dry_run.working_logs_migrated, 1);
+        assert!(dry_run.backup_dir.is_none());
+        assert_eq!(
+            storage.storage_format_version().unwrap(),
+            LEGACY_STORAGE_FORMAT_VERSION
+        );
+
+        let report = storage.migrate_storage(false).unwrap();
+        assert_eq!(report.from_version, LEGACY_STORAGE_FORMAT_VERSION);
+        assert_eq!(report.to_version, STORAGE_FORMAT_VERSION);
+        let backup_dir = report.backup_dir.expect("backup dir");
+        assert!(
+            backup_dir
+                .join("working_logs")
+                .join(sha)
+                .join("checkpoints.jsonl")
+                .exists()
+        );
+        assert_eq!(
+            storage.storage_format_version().unwrap(),
+            STORAGE_FORMAT_VERSION
+        );
+
+        // Already current: migrating again is a no-op.
+        let again = storage.migrate_storage(false).unwrap();
+        assert_eq!(again.working_logs_migrated, 0);
+        assert!(again.backup_dir.is_none());
+    }
+
     /// Regression (#9): merge_working_log_dirs (via rename_working_log when the
     /// destination already exists) must preserve OLD-base INITIAL entries on a
     /// shared key, per the documented "preserve the old-base entries first".
@@ -960,4 +2001,68 @@ mod tests {
         assert!(merged.files.contains_key("old_only.txt"));
         assert!(merged.files.contains_key("new_only.txt"));
     }
+
+    #[test]
+    fn test_append_checkpoint_skips_duplicate_of_last_checkpoint() {
+        let tmp = TempDir::new().unwrap();
+        let workdir = tmp.path().join("workdir");
+        fs::create_dir_all(&workdir).unwrap();
+        let storage = RepoStorage::for_repo_path(&tmp.path().join("ai"), &workdir).unwrap();
+        let log = storage
+            .working_log_for_base_commit("1111111111111111111111111111111111111111")
+            .unwrap();
+
+        let checkpoint = Checkpoint::new(
+            CheckpointKind::AiAgent,
+            "content-hash".to_string(),
+            "agent".to_string(),
+            Vec::new(),
+        );
+        assert!(log.append_checkpoint(&checkpoint).unwrap());
+        assert!(!log.append_checkpoint(&checkpoint).unwrap());
+
+        let mut human = checkpoint.clone();
+        human.kind = CheckpointKind::Human;
+        human.author = "human".to_string();
+        assert!(log.append_checkpoint(&human).unwrap());
+        // Only the last checkpoint is compared: an A, B, A sequence is kept.
+        assert!(log.append_checkpoint(&checkpoint).unwrap());
+        assert_eq!(log.read_all_checkpoints().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_working_log_fork_restores_consumed_log_and_drops_redundant_one() {
+        let tmp = TempDir::new().unwrap();
+        let workdir = tmp.path().join("workdir");
+        fs::create_dir_all(&workdir).unwrap();
+        let storage = RepoStorage::for_repo_path(&tmp.path().join("ai"), &workdir).unwrap();
+        let sha = "1111111111111111111111111111111111111111";
+
+        let log = storage.working_log_for_base_commit(sha).unwrap();
+        log.append_checkpoint(&Checkpoint::new(
+            CheckpointKind::AiAgent,
+            String::new(),
+            "agent".to_string(),
+            Vec::new(),
+        ))
+        .unwrap();
+
+        // Nothing consumed the shared log: the fork is redundant.
+        assert!(storage.fork_working_log(sha, "feature/a").unwrap());
+        assert!(!storage.restore_working_log_fork("feature/a", sha).unwrap());
+        assert!(!storage.working_log_fork_dir("feature/a").exists());
+
+        // The other branch committed first and consumed the log.
+        assert!(storage.fork_working_log(sha, "feature/a").unwrap());
+        storage.delete_working_log_for_base_commit(sha).unwrap();
+        assert!(storage.restore_working_log_fork("feature/a", sha).unwrap());
+        let restored = storage.working_log_for_base_commit(sha).unwrap();
+        assert_eq!(restored.read_all_checkpoints().unwrap().len(), 1);
+
+        // A fork taken at another commit is stale.
+        assert!(storage.fork_working_log(sha, "main").unwrap());
+        let other = "2222222222222222222222222222222222222222";
+        assert!(!storage.restore_working_log_fork("main", other).unwrap());
+        assert!(!storage.working_log_fork_dir("main").exists());
+    }
 }
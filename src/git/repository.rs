@@ -1090,6 +1090,8 @@ pub struct Repository {
     canonical_workdir: PathBuf,
     /// Cached git author identity resolved via `git var GIT_COMMITTER_IDENT`.
     cached_author_identity: std::sync::OnceLock<GitAuthorIdentity>,
+    /// Cached anonymous-mode key, see `anonymize::key_for_repo`.
+    pub(crate) cached_anonymization_key: std::sync::OnceLock<Option<Vec<u8>>>,
 }
 
 impl Repository {
@@ -1176,6 +1178,31 @@ impl Repository {
         }
     }
 
+    /// Commit HEAD points at, or `None` on an unborn branch: a repository with
+    /// no commits yet, or after `checkout --orphan`.
+    pub fn head_commit_sha(&self) -> Result<Option<String>, GitAiError> {
+        use crate::git::fast_reader::FastRefReader;
+        let head = self.head()?;
+        if head.ref_name == "HEAD" {
+            return head.target().map(Some);
+        }
+        let reader = FastRefReader::new(&self.git_dir, &self.git_common_dir);
+        if let Some(sha) = reader.try_resolve_ref(&head.ref_name) {
+            return Ok(Some(sha));
+        }
+
+        let mut args = self.global_args_for_exec();
+        args.push("rev-parse".to_string());
+        args.push("--verify".to_string());
+        args.push("--quiet".to_string());
+        args.push(format!("{}^{{commit}}", head.ref_name));
+        let output = exec_git_allow_nonzero(&args)?;
+        if !output.status.success() {
+            return Ok(None);
+        }
+        Ok(Some(String::from_utf8(output.stdout)?.trim().to_string()))
+    }
+
     // Returns the path to the .git folder for normal repositories or the repository itself for bare repositories.
     // TODO Test on bare repositories.
     pub fn path(&self) -> &Path {
@@ -1276,11 +1303,33 @@ impl Repository {
             .collect())
     }
 
-    // List all remotes with their URLs as tuples (name, url)
+    /// List all remotes with their effective fetch URLs as `(name, url)`.
+    ///
+    /// A remote with several `url` entries pushes to all of them but fetches
+    /// from the first, so the first is its URL here (falling back to the first
+    /// `pushurl` when it has no `url`). `url.<base>.insteadOf` rewrites are
+    /// applied, so clones configured with different shorthands report the
+    /// same URL.
     pub fn remotes_with_urls(&self) -> Result<Vec<(String, String)>, GitAiError> {
         let config = self.get_git_config_file()?;
-        let mut remotes = Vec::new();
 
+        let mut rewrites = Vec::new();
+        for section in config.sections() {
+            if !section.header().name().eq_ignore_ascii_case(b"url") {
+                continue;
+            }
+            let Some(base) = section.header().subsection_name() else {
+                continue;
+            };
+            for instead_of in section.body().values("insteadOf") {
+                rewrites.push(crate::repo_url::UrlRewrite {
+                    base: base.to_string(),
+                    instead_of: instead_of.to_string(),
+                });
+            }
+        }
+
+        let mut remotes: Vec<(String, String)> = Vec::new();
         for section in config.sections() {
             if !section.header().name().eq_ignore_ascii_case(b"remote") {
                 continue;
@@ -1288,10 +1337,22 @@ impl Repository {
             let Some(name) = section.header().subsection_name() else {
                 continue;
             };
-            let Some(url) = section.body().value("url") else {
+            let name = name.to_string();
+            // A remote split across sections keeps the URL of its first one.
+            if remotes.iter().any(|(existing, _)| *existing == name) {
+                continue;
+            }
+            let body = section.body();
+            let Some(url) = body
+                .values("url")
+                .into_iter()
+                .next()
+                .or_else(|| body.values("pushurl").into_iter().next())
+            else {
                 continue;
             };
-            remotes.push((name.to_string(), url.to_string()));
+            let url = crate::repo_url::apply_url_rewrites(&url.to_string(), &rewrites);
+            remotes.push((name, url));
         }
 
         Ok(remotes)
@@ -1775,6 +1836,7 @@ impl Repository {
         to_ref: &str,
         pathspecs: Option<&HashSet<String>>,
     ) -> Result<HashMap<String, Vec<u32>>, GitAiError> {
+        let _span = tracing::debug_span!("diff").entered();
         let mut args = self.global_args_for_exec();
         args.push("diff".to_string());
         args.push("-U0".to_string()); // Zero context lines
@@ -1840,6 +1902,22 @@ impl Repository {
         parse_diff_added_lines(&diff_output)
     }
 
+    /// Get added line numbers from the staged changes (`git diff --cached`).
+    /// Returns a HashMap of file paths to vectors of added line numbers in the index version.
+    pub fn diff_staged_added_lines(&self) -> Result<HashMap<String, Vec<u32>>, GitAiError> {
+        let mut args = self.global_args_for_exec();
+        args.push("diff".to_string());
+        args.push("--cached".to_string());
+        args.push("-U0".to_string());
+        args.push("--no-color".to_string());
+        args.push("--no-renames".to_string());
+
+        let output = exec_git_with_profile(&args, InternalGitProfile::PatchParse)?;
+        let diff_output = String::from_utf8_lossy(&output.stdout);
+
+        parse_diff_added_lines(&diff_output).map(|(added, _deleted_count)| added)
+    }
+
     /// Get list of changed files between two refs using `git diff --name-only`
     /// Returns a Vec of file paths that differ between the two refs
     pub fn diff_changed_files(
@@ -2027,7 +2105,23 @@ pub fn find_repository(global_args: &[String]) -> Result<Repository, GitAiError>
         workdir.display().to_string()
     };
 
-    if normalized_global_args.is_empty() {
+    if has_repository_location_override(global_args) {
+        // The repository was located through `--git-dir`/`--work-tree` or
+        // `GIT_DIR`/`GIT_WORK_TREE`, which may be relative to the caller's cwd.
+        // Pin both to absolute paths so internal commands resolve the same
+        // repository from `command_root`.
+        normalized_global_args = vec![
+            "-C".to_string(),
+            command_root,
+            "--git-dir".to_string(),
+            git_dir.display().to_string(),
+        ];
+        if !is_bare {
+            normalized_global_args.push("--work-tree".to_string());
+            normalized_global_args.push(workdir.display().to_string());
+        }
+        normalized_global_args.extend(strip_repository_location_args(global_args));
+    } else if normalized_global_args.is_empty() {
         normalized_global_args = vec!["-C".to_string(), command_root];
     } else if normalized_global_args.len() == 2
         && normalized_global_args[0] == "-C"
@@ -2051,7 +2145,11 @@ pub fn find_repository(global_args: &[String]) -> Result<Repository, GitAiError>
     let storage = if worktree_ai_dir == git_dir.join("ai") {
         RepoStorage::for_repo_path(&git_dir, &workdir)?
     } else {
-        RepoStorage::for_isolated_worktree_storage(&worktree_ai_dir, &workdir)?
+        RepoStorage::for_isolated_worktree_storage(
+            &worktree_ai_dir,
+            &git_common_dir.join("ai"),
+            &workdir,
+        )?
     };
 
     Ok(Repository {
@@ -2068,10 +2166,66 @@ pub fn find_repository(global_args: &[String]) -> Result<Repository, GitAiError>
         workdir,
         canonical_workdir,
         cached_author_identity: std::sync::OnceLock::new(),
+        cached_anonymization_key: std::sync::OnceLock::new(),
     })
 }
 
 #[doc(hidden)]
+/// Returns true when the repository location comes from `--git-dir`/`--work-tree`
+/// global args or the `GIT_DIR`/`GIT_WORK_TREE` environment rather than from
+/// directory discovery.
+fn has_repository_location_override(global_args: &[String]) -> bool {
+    let env_override = ["GIT_DIR", "GIT_WORK_TREE"]
+        .iter()
+        .any(|var| std::env::var_os(var).is_some_and(|value| !value.is_empty()));
+    env_override
+        || global_args.iter().any(|arg| {
+            matches!(arg.as_str(), "--git-dir" | "--work-tree")
+                || arg.starts_with("--git-dir=")
+                || arg.starts_with("--work-tree=")
+        })
+}
+
+/// Drops `-C`, `--git-dir` and `--work-tree` (both `--opt value` and `--opt=value`
+/// forms) from `global_args`, keeping everything else in order.
+fn strip_repository_location_args(global_args: &[String]) -> Vec<String> {
+    let mut kept = Vec::new();
+    let mut idx = 0usize;
+    while idx < global_args.len() {
+        let arg = global_args[idx].as_str();
+        if matches!(arg, "-C" | "--git-dir" | "--work-tree") {
+            idx += 2;
+            continue;
+        }
+        if !(arg.starts_with("--git-dir=") || arg.starts_with("--work-tree=")) {
+            kept.push(global_args[idx].clone());
+        }
+        idx += 1;
+    }
+    kept
+}
+
+/// Splits the leading git global options that select a repository (`-C <path>`,
+/// `--git-dir`, `--work-tree`) off a `git-ai` command line, so that
+/// `git-ai -C <path> stats` behaves like `git -C <path> ai stats`.
+///
+/// Returns `(repository_args, remaining_args)`. Parsing stops at the first
+/// argument that is not one of those options.
+pub fn split_leading_repository_args(args: &[String]) -> (Vec<String>, Vec<String>) {
+    let mut idx = 0usize;
+    while idx < args.len() {
+        let arg = args[idx].as_str();
+        if matches!(arg, "-C" | "--git-dir" | "--work-tree") && idx + 1 < args.len() {
+            idx += 2;
+        } else if arg.starts_with("--git-dir=") || arg.starts_with("--work-tree=") {
+            idx += 1;
+        } else {
+            break;
+        }
+    }
+    (args[..idx].to_vec(), args[idx..].to_vec())
+}
+
 pub fn resolve_command_base_dir(global_args: &[String]) -> Result<PathBuf, GitAiError> {
     let mut base: Option<PathBuf> = None;
     let mut idx = 0usize;
@@ -2368,7 +2522,7 @@ pub fn from_bare_repository(git_dir: &Path) -> Result<Repository, GitAiError> {
     let storage = if worktree_ai_dir == git_dir.join("ai") {
         RepoStorage::for_repo_path(git_dir, &workdir)?
     } else {
-        RepoStorage::for_isolated_worktree_storage(&worktree_ai_dir, &workdir)?
+        RepoStorage::for_isolated_worktree_storage(&worktree_ai_dir, &git_dir.join("ai"), &workdir)?
     };
 
     Ok(Repository {
@@ -2385,6 +2539,7 @@ pub fn from_bare_repository(git_dir: &Path) -> Result<Repository, GitAiError> {
         workdir,
         canonical_workdir,
         cached_author_identity: std::sync::OnceLock::new(),
+        cached_anonymization_key: std::sync::OnceLock::new(),
     })
 }
 
@@ -2425,7 +2580,11 @@ fn repository_from_discovered_paths(
     let storage = if worktree_ai_dir == git_dir.join("ai") {
         RepoStorage::for_repo_path(git_dir, workdir)?
     } else {
-        RepoStorage::for_isolated_worktree_storage(&worktree_ai_dir, workdir)?
+        RepoStorage::for_isolated_worktree_storage(
+            &worktree_ai_dir,
+            &git_common_dir.join("ai"),
+            workdir,
+        )?
     };
 
     Ok(Repository {
@@ -2442,6 +2601,7 @@ fn repository_from_discovered_paths(
         workdir: workdir.to_path_buf(),
         canonical_workdir,
         cached_author_identity: std::sync::OnceLock::new(),
+        cached_anonymization_key: std::sync::OnceLock::new(),
     })
 }
 
@@ -2828,6 +2988,84 @@ pub fn exec_git_with_profile(
     Ok(output)
 }
 
+/// Like [`exec_git`], but kills the child as soon as `cancelled` is set, so a
+/// hung remote cannot outlive the watchdog that gave up on it.
+pub fn exec_git_cancellable(
+    args: &[String],
+    cancelled: &std::sync::atomic::AtomicBool,
+) -> Result<Output, GitAiError> {
+    use std::io::Read;
+    use std::sync::atomic::Ordering;
+
+    let effective_args = args_with_internal_git_profile(
+        &args_with_disabled_hooks_if_needed(args),
+        InternalGitProfile::General,
+    );
+    spawn_probe_log(&effective_args);
+    let mut cmd = Command::new(config::Config::get().git_cmd());
+    cmd.args(&effective_args)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+    apply_internal_git_env(&mut cmd);
+
+    #[cfg(windows)]
+    {
+        if !is_interactive_terminal() {
+            cmd.creation_flags(CREATE_NO_WINDOW);
+        }
+    }
+
+    let mut child = cmd.spawn().map_err(GitAiError::IoError)?;
+    let drain = |pipe: Option<Box<dyn Read + Send>>| {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            buf
+        })
+    };
+    let stdout = drain(
+        child
+            .stdout
+            .take()
+            .map(|p| Box::new(p) as Box<dyn Read + Send>),
+    );
+    let stderr = drain(
+        child
+            .stderr
+            .take()
+            .map(|p| Box::new(p) as Box<dyn Read + Send>),
+    );
+
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(GitAiError::IoError)? {
+            break status;
+        }
+        if cancelled.load(Ordering::Relaxed) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(GitAiError::Sync("git command cancelled".to_string()));
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    };
+
+    let output = Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    };
+    if !output.status.success() {
+        return Err(GitAiError::GitCliError {
+            code: output.status.code(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            args: effective_args,
+        });
+    }
+    Ok(output)
+}
+
 /// Helper to execute a git command with data provided on stdin
 pub fn exec_git_stdin(args: &[String], stdin_data: &[u8]) -> Result<Output, GitAiError> {
     exec_git_stdin_with_profile(args, stdin_data, InternalGitProfile::General)
@@ -3541,4 +3779,60 @@ mod tests {
         assert!(rewritten.iter().any(|arg| arg == "--no-color"));
         assert!(rewritten.iter().any(|arg| arg == "--no-relative"));
     }
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn split_leading_repository_args_stops_at_subcommand() {
+        let args = strings(&[
+            "-C",
+            "repo",
+            "--git-dir=/tmp/store.git",
+            "--work-tree",
+            "/tmp/tree",
+            "stats",
+            "-C",
+            "HEAD",
+        ]);
+        let (repository_args, rest) = split_leading_repository_args(&args);
+        assert_eq!(
+            repository_args,
+            strings(&[
+                "-C",
+                "repo",
+                "--git-dir=/tmp/store.git",
+                "--work-tree",
+                "/tmp/tree"
+            ])
+        );
+        assert_eq!(rest, strings(&["stats", "-C", "HEAD"]));
+    }
+
+    #[test]
+    fn split_leading_repository_args_leaves_plain_commands_alone() {
+        let args = strings(&["blame", "src/main.rs"]);
+        let (repository_args, rest) = split_leading_repository_args(&args);
+        assert!(repository_args.is_empty());
+        assert_eq!(rest, args);
+    }
+
+    #[test]
+    fn strip_repository_location_args_keeps_other_global_args() {
+        let args = strings(&[
+            "-C",
+            "repo",
+            "-c",
+            "core.quotepath=false",
+            "--git-dir",
+            ".git",
+            "--work-tree=.",
+            "--no-pager",
+        ]);
+        assert_eq!(
+            strip_repository_location_args(&args),
+            strings(&["-c", "core.quotepath=false", "--no-pager"])
+        );
+    }
 }
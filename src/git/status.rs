@@ -212,7 +212,15 @@ fn parse_porcelain_v2(data: &[u8]) -> Result<Vec<StatusEntry>, GitAiError> {
         .peekable();
 
     while let Some(raw) = parts.next() {
-        let record = str::from_utf8(raw)?;
+        // With -z paths are emitted verbatim, so they may not be UTF-8. Such an
+        // entry (and the original path of a rename) is skipped instead of
+        // failing the whole status.
+        let Ok(record) = str::from_utf8(raw) else {
+            if raw.first() == Some(&b'2') {
+                parts.next();
+            }
+            continue;
+        };
         let mut chars = record.chars();
         let tag = chars
             .next()
@@ -220,7 +228,10 @@ fn parse_porcelain_v2(data: &[u8]) -> Result<Vec<StatusEntry>, GitAiError> {
 
         match tag {
             '1' | 'u' => {
-                let mut fields = record.splitn(9, ' ');
+                // Ordinary entries have 8 fields before the path, unmerged
+                // entries 10 (three stage modes and hashes).
+                let path_field = if tag == 'u' { 10 } else { 8 };
+                let mut fields = record.splitn(path_field + 1, ' ');
                 let _ = fields.next(); // tag
                 let xy = fields
                     .next()
@@ -235,7 +246,7 @@ fn parse_porcelain_v2(data: &[u8]) -> Result<Vec<StatusEntry>, GitAiError> {
                 let unstaged = StatusCode::from(xy.chars().nth(1).unwrap());
 
                 // skip submodule/metadata fields to capture path
-                for _ in 0..6 {
+                for _ in 2..path_field {
                     fields.next();
                 }
 
@@ -290,7 +301,10 @@ fn parse_porcelain_v2(data: &[u8]) -> Result<Vec<StatusEntry>, GitAiError> {
                 let orig_path_bytes = parts.next().ok_or_else(|| {
                     GitAiError::Generic("Missing original path for rename/copy".into())
                 })?;
-                let orig_path = nfc_path(str::from_utf8(orig_path_bytes)?.to_string());
+                let Ok(orig_path) = str::from_utf8(orig_path_bytes) else {
+                    continue;
+                };
+                let orig_path = nfc_path(orig_path.to_string());
 
                 let kind = match staged {
                     StatusCode::Renamed => EntryKind::Rename,
@@ -361,7 +375,7 @@ mod tests {
         raw.extend_from_slice(b"? assets/logo (1).svg\0");
         raw.extend_from_slice(b"? dir with spaces/file name [draft].md\0");
         raw.extend_from_slice(b"! target/.keep\0");
-        raw.extend_from_slice(b"u UU N... 100644 100644 100644 eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee ffffffffffffffffffffffffffffffffffffffff 1 2 3 some unmerged/path.txt\0");
+        raw.extend_from_slice(b"u UU N... 100644 100644 100644 100644 eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee ffffffffffffffffffffffffffffffffffffffff 1111111111111111111111111111111111111111 some unmerged/path.txt\0");
 
         let entries: Vec<StatusEntry> = parse_porcelain_v2(&raw).expect("parse succeeds");
 
@@ -392,4 +406,28 @@ mod tests {
 
         assert_debug_snapshot!(entries);
     }
+
+    #[test]
+    fn parse_porcelain_v2_keeps_exotic_paths_and_skips_undecodable_ones() {
+        let mut raw = Vec::new();
+        raw.extend_from_slice(b"1 .M N... 100644 100644 100644 1111111111111111111111111111111111111111 1111111111111111111111111111111111111111 tab\there\nnewline.rs\0");
+        raw.extend_from_slice(b"1 .M N... 100644 100644 100644 2222222222222222222222222222222222222222 2222222222222222222222222222222222222222 bad\xff.rs\0");
+        raw.extend_from_slice(b"2 R. N... 100644 100644 100644 3333333333333333333333333333333333333333 3333333333333333333333333333333333333333 R100 renamed\xfe.rs\0old.rs\0");
+        raw.extend_from_slice(b"u UU N... 100644 100644 100644 100644 4444444444444444444444444444444444444444 5555555555555555555555555555555555555555 6666666666666666666666666666666666666666 conflict with spaces.rs\0");
+        raw.extend_from_slice(b"? caf\xc3\xa9 \"quoted\".txt\0");
+
+        let paths: Vec<String> = parse_porcelain_v2(&raw)
+            .expect("parse succeeds")
+            .into_iter()
+            .map(|entry| entry.path)
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "tab\there\nnewline.rs",
+                "conflict with spaces.rs",
+                "caf\u{e9} \"quoted\".txt",
+            ]
+        );
+    }
 }
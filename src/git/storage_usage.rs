@@ -0,0 +1,194 @@
+//! On-disk footprint of a repository's `.git/ai` directory.
+//!
+//! `git-ai status` reports the breakdown and warns once the total passes
+//! `ai_storage_warn_mb`, pointing at `git-ai gc`. Working logs relocated with
+//! `working_logs_dir` are counted even though they live outside `.git/ai`.
+
+use crate::git::repo_storage::{
+    CONFLICT_RESOLUTIONS_FILE, DETACHED_HEAD_FILE, PENDING_NOTES_FILE, PENDING_REPAIRS_FILE,
+    RepoStorage, SESSION_OVERRIDE_FILE, STATS_BASELINES_FILE, STORAGE_VERSION_FILE,
+    WORKING_LOGS_LOCATION_FILE,
+};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// Warning threshold used when `ai_storage_warn_mb` is unset.
+pub const DEFAULT_AI_STORAGE_WARN_MB: u64 = 1024;
+
+/// Files under `.git/ai` holding hook and command state between runs.
+const STATE_FILES: &[&str] = &[
+    STORAGE_VERSION_FILE,
+    WORKING_LOGS_LOCATION_FILE,
+    DETACHED_HEAD_FILE,
+    SESSION_OVERRIDE_FILE,
+    STATS_BASELINES_FILE,
+    PENDING_NOTES_FILE,
+    PENDING_REPAIRS_FILE,
+    CONFLICT_RESOLUTIONS_FILE,
+];
+
+/// Byte totals per kind of `.git/ai` data.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct StorageUsage {
+    /// Checkpoints and INITIAL files of live working logs and branch forks.
+    pub working_logs: u64,
+    /// File snapshots under each working log's `blobs/`.
+    pub blobs: u64,
+    /// Working logs archived as `old-<sha>` after their commit.
+    pub archived_working_logs: u64,
+    /// Attribution saved for stash entries.
+    pub stashes: u64,
+    /// Hook and command state files (pending notes and repairs, detached HEAD, ...).
+    pub state: u64,
+    /// Everything else: quarantined logs, clean archives, logs.
+    pub other: u64,
+}
+
+impl StorageUsage {
+    pub fn total(&self) -> u64 {
+        self.working_logs
+            + self.blobs
+            + self.archived_working_logs
+            + self.stashes
+            + self.state
+            + self.other
+    }
+}
+
+/// Walk `storage` and total its size by kind.
+pub fn measure(storage: &RepoStorage) -> StorageUsage {
+    let mut usage = StorageUsage::default();
+    measure_working_logs(&storage.working_logs, &mut usage);
+
+    let Ok(entries) = fs::read_dir(&storage.ai_dir) else {
+        return usage;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path == storage.working_logs {
+            continue;
+        }
+        let name = entry.file_name();
+        let size = path_size(&path);
+        match name.to_string_lossy().as_ref() {
+            "working_log_forks" => usage.working_logs += size,
+            "stashes" | "stashes_v2" => usage.stashes += size,
+            name if STATE_FILES.contains(&name) => usage.state += size,
+            _ => usage.other += size,
+        }
+    }
+    usage
+}
+
+fn measure_working_logs(dir: &Path, usage: &mut StorageUsage) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if entry.file_name().to_string_lossy().starts_with("old-") {
+            usage.archived_working_logs += path_size(&path);
+            continue;
+        }
+        let Ok(log_entries) = fs::read_dir(&path) else {
+            usage.working_logs += path_size(&path);
+            continue;
+        };
+        for log_entry in log_entries.flatten() {
+            let size = path_size(&log_entry.path());
+            if log_entry.file_name() == "blobs" {
+                usage.blobs += size;
+            } else {
+                usage.working_logs += size;
+            }
+        }
+    }
+}
+
+/// Apparent size of a file, or of every file under a directory. Symlinks are
+/// counted as links, not followed.
+pub(crate) fn path_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| path_size(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// The configured warning threshold in bytes, or `None` when set to `0`.
+pub fn warn_threshold_bytes() -> Option<u64> {
+    let mb = crate::config::load_effective_file_config()
+        .ok()
+        .and_then(|config| config.ai_storage_warn_mb)
+        .unwrap_or(DEFAULT_AI_STORAGE_WARN_MB);
+    (mb > 0).then(|| mb.saturating_mul(1024 * 1024))
+}
+
+/// Human-readable size, e.g. `512 B`, `3.4 MB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0usize;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_measure_splits_footprint_by_kind() {
+        let tmp = TempDir::new().unwrap();
+        let storage = RepoStorage::for_repo_path(&tmp.path().join("repo"), tmp.path()).unwrap();
+        let baseline = measure(&storage);
+
+        let log_dir = storage.working_logs.join("abc123");
+        fs::create_dir_all(log_dir.join("blobs")).unwrap();
+        fs::write(log_dir.join("checkpoints.jsonl"), vec![b'x'; 100]).unwrap();
+        fs::write(log_dir.join("blobs").join("deadbeef"), vec![b'x'; 40]).unwrap();
+        let archived = storage.working_logs.join("old-def456");
+        fs::create_dir_all(&archived).unwrap();
+        fs::write(archived.join("checkpoints.jsonl"), vec![b'x'; 7]).unwrap();
+        let stash = storage.ai_dir.join("stashes_v2").join("5a5h");
+        fs::create_dir_all(&stash).unwrap();
+        fs::write(stash.join("metadata.json"), vec![b'x'; 20]).unwrap();
+        fs::write(storage.ai_dir.join(PENDING_NOTES_FILE), vec![b'x'; 3]).unwrap();
+        fs::create_dir_all(storage.ai_dir.join("quarantine")).unwrap();
+        fs::write(storage.ai_dir.join("quarantine").join("x"), vec![b'x'; 5]).unwrap();
+
+        let usage = measure(&storage);
+        assert_eq!(usage.working_logs - baseline.working_logs, 100);
+        assert_eq!(usage.blobs, 40);
+        assert_eq!(usage.archived_working_logs, 7);
+        assert_eq!(usage.stashes, 20);
+        assert_eq!(usage.state - baseline.state, 3);
+        assert_eq!(usage.other - baseline.other, 5);
+        assert_eq!(usage.total() - baseline.total(), 175);
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+}
@@ -1,13 +1,18 @@
+use crate::git::note_overflow::{OVERFLOW_NOTES_REF, overflow_tracking_ref_for_remote};
+use crate::git::notes_schema::NotesNamespace;
 use crate::git::refs::{
-    AI_AUTHORSHIP_PUSH_REFSPEC, copy_ref, fallback_merge_notes_ours, merge_notes_from_ref,
-    ref_exists, tracking_ref_for_remote,
+    AI_AUTHORSHIP_PUSH_REFSPEC, copy_ref, diverged_note_objects, fallback_merge_notes_ours,
+    merge_notes_from_ref, merge_notes_from_ref_into, ref_exists, tracking_ref_for_remote,
+    union_conflicting_notes_from_ref,
 };
 use crate::{
     error::GitAiError,
-    git::{cli_parser::ParsedGitInvocation, repository::exec_git},
+    git::{cli_parser::ParsedGitInvocation, repository::exec_git_cancellable},
 };
 
 use super::repository::Repository;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 #[cfg(windows)]
 fn disabled_hooks_config() -> &'static str {
@@ -58,7 +63,7 @@ pub fn fetch_remote_from_args(
         .or_else(|| repository.get_default_remote().ok().flatten());
 
     remote.map(|r| r.to_string()).ok_or_else(|| {
-        GitAiError::Generic(
+        GitAiError::Sync(
             "Could not determine a remote for fetch/push operation. \
                  No remote was specified in args, no upstream is configured, \
                  and no default remote was found."
@@ -95,7 +100,7 @@ pub fn push_remote_from_args(
         .or_else(|| repository.get_default_remote().ok().flatten());
 
     remote.map(|r| r.to_string()).ok_or_else(|| {
-        GitAiError::Generic(
+        GitAiError::Sync(
             "Could not determine a remote for push operation. \
                  No remote was specified in args, no upstream is configured, \
                  and no default remote was found."
@@ -164,7 +169,7 @@ pub fn fetch_missing_notes_for_commits(
             .filter(|sha| !noted_after_fetch.contains(sha.as_str()))
             .collect();
         if !still_missing.is_empty() {
-            return Err(GitAiError::Generic(format!(
+            return Err(GitAiError::Sync(format!(
                 "failed to fetch authorship notes for source commits {:?}: {}",
                 still_missing, error
             )));
@@ -182,6 +187,23 @@ pub fn fetch_authorship_notes(
     repository: &Repository,
     remote_name: &str,
 ) -> Result<NotesExistence, GitAiError> {
+    fetch_authorship_notes_cancellable(repository, remote_name, &AtomicBool::new(false))
+}
+
+/// Like [`fetch_authorship_notes`], but once `cancelled` is set it stops before
+/// touching `refs/notes/ai` and leaves the fetched tracking ref for a later pass.
+pub fn fetch_authorship_notes_cancellable(
+    repository: &Repository,
+    remote_name: &str,
+    cancelled: &AtomicBool,
+) -> Result<NotesExistence, GitAiError> {
+    let _span = tracing::info_span!(
+        target: crate::observability::trace::targets::SYNC,
+        "fetch_notes",
+        remote = remote_name
+    )
+    .entered();
+
     // Generate tracking ref for this remote
     let tracking_ref = tracking_ref_for_remote(remote_name);
 
@@ -211,7 +233,7 @@ pub fn fetch_authorship_notes(
 
     tracing::debug!("fetch command: {:?}", fetch_authorship);
 
-    match exec_git(&fetch_authorship) {
+    match exec_git_cancellable(&fetch_authorship, cancelled) {
         Ok(output) => {
             tracing::debug!(
                 "fetch stdout: '{}'",
@@ -234,6 +256,7 @@ pub fn fetch_authorship_notes(
             return Err(e);
         }
     }
+    check_cancelled(cancelled, "authorship notes fetch")?;
 
     // After successful fetch, merge the tracking ref into refs/notes/ai
     let local_notes_ref = "refs/notes/ai";
@@ -246,6 +269,10 @@ pub fn fetch_authorship_notes(
                 tracking_ref,
                 local_notes_ref
             );
+            let diverged = diverged_note_objects(repository, &tracking_ref).unwrap_or_else(|e| {
+                tracing::debug!("notes divergence check failed: {}", e);
+                HashSet::new()
+            });
             if let Err(e) = merge_notes_from_ref(repository, &tracking_ref) {
                 tracing::debug!("notes merge failed: {}", e);
                 // Fallback: manually merge notes when git notes merge crashes
@@ -254,6 +281,9 @@ pub fn fetch_authorship_notes(
                     return Err(e2);
                 }
             }
+            if let Err(e) = union_conflicting_notes_from_ref(repository, &tracking_ref, &diverged) {
+                tracing::debug!("notes union failed: {}", e);
+            }
         } else {
             // Only tracking ref exists - copy it to local
             tracing::debug!(
@@ -270,9 +300,78 @@ pub fn fetch_authorship_notes(
         tracing::debug!("tracking ref {} was not created after fetch", tracking_ref);
     }
 
+    fetch_and_merge_upgraded_notes(repository, remote_name, cancelled);
+    fetch_and_merge_overflow_notes(repository, remote_name, cancelled);
+
     Ok(NotesExistence::Found)
 }
 
+/// Fetch the remote's `refs/notes/ai-overflow` and merge it into the local one,
+/// so the full notes behind overflow stubs in `refs/notes/ai` are readable.
+/// Best effort: most remotes have no oversized notes and so no such ref.
+fn fetch_and_merge_overflow_notes(
+    repository: &Repository,
+    remote_name: &str,
+    cancelled: &AtomicBool,
+) {
+    if cancelled.load(Ordering::Relaxed) {
+        return;
+    }
+    let tracking_ref = overflow_tracking_ref_for_remote(remote_name);
+    let fetch_args = build_authorship_fetch_args(
+        repository.global_args_for_exec(),
+        remote_name,
+        &format!("+{}:{}", OVERFLOW_NOTES_REF, tracking_ref),
+    );
+    if let Err(e) = exec_git_cancellable(&fetch_args, cancelled) {
+        tracing::debug!("{} fetch skipped: {}", OVERFLOW_NOTES_REF, e);
+        return;
+    }
+    if cancelled.load(Ordering::Relaxed) || !ref_exists(repository, &tracking_ref) {
+        return;
+    }
+    let result = if ref_exists(repository, OVERFLOW_NOTES_REF) {
+        let notes_refname = OVERFLOW_NOTES_REF.trim_start_matches("refs/notes/");
+        merge_notes_from_ref_into(repository, notes_refname, &tracking_ref)
+    } else {
+        copy_ref(repository, &tracking_ref, OVERFLOW_NOTES_REF)
+    };
+    if let Err(e) = result {
+        tracing::debug!("{} merge failed: {}", OVERFLOW_NOTES_REF, e);
+    }
+}
+
+/// Fetch the remote's `refs/notes/ai-v2` and merge it into the local one.
+/// Only repositories that ran `git-ai notes upgrade` have the local ref, so
+/// everyone else pays no extra round trip. Best effort: the remote may not have
+/// upgraded yet.
+fn fetch_and_merge_upgraded_notes(
+    repository: &Repository,
+    remote_name: &str,
+    cancelled: &AtomicBool,
+) {
+    let namespace = NotesNamespace::V2;
+    if !namespace.exists_in(repository) || cancelled.load(Ordering::Relaxed) {
+        return;
+    }
+    let tracking_ref = namespace.tracking_ref_for_remote(remote_name);
+    let fetch_args = build_authorship_fetch_args(
+        repository.global_args_for_exec(),
+        remote_name,
+        &format!("+{}:{}", namespace.full_ref(), tracking_ref),
+    );
+    if let Err(e) = exec_git_cancellable(&fetch_args, cancelled) {
+        tracing::debug!("{} fetch skipped: {}", namespace.full_ref(), e);
+        return;
+    }
+    if cancelled.load(Ordering::Relaxed) || !ref_exists(repository, &tracking_ref) {
+        return;
+    }
+    if let Err(e) = merge_notes_from_ref_into(repository, namespace.notes_ref(), &tracking_ref) {
+        tracing::debug!("{} merge failed: {}", namespace.full_ref(), e);
+    }
+}
+
 fn is_missing_remote_notes_ref_error(error: &GitAiError) -> bool {
     let GitAiError::GitCliError { stderr, .. } = error else {
         return false;
@@ -292,6 +391,23 @@ const PUSH_NOTES_MAX_ATTEMPTS: usize = 3;
 
 // for use with post-push hook
 pub fn push_authorship_notes(repository: &Repository, remote_name: &str) -> Result<(), GitAiError> {
+    push_authorship_notes_cancellable(repository, remote_name, &AtomicBool::new(false))
+}
+
+/// Like [`push_authorship_notes`], but gives up between attempts once
+/// `cancelled` is set.
+pub fn push_authorship_notes_cancellable(
+    repository: &Repository,
+    remote_name: &str,
+    cancelled: &AtomicBool,
+) -> Result<(), GitAiError> {
+    let _span = tracing::info_span!(
+        target: crate::observability::trace::targets::SYNC,
+        "push_notes",
+        remote = remote_name
+    )
+    .entered();
+
     // Belt-and-suspenders: when the HTTP backend is active, notes are not stored
     // in refs/notes/ai so there is nothing to push.
     if crate::config::Config::get().notes_backend_kind() == crate::config::NotesBackendKind::Http {
@@ -302,6 +418,7 @@ pub fn push_authorship_notes(repository: &Repository, remote_name: &str) -> Resu
     let mut last_error = None;
 
     for attempt in 0..PUSH_NOTES_MAX_ATTEMPTS {
+        check_cancelled(cancelled, "authorship notes push")?;
         if attempt > 0 {
             tracing::debug!(
                 "retrying notes push (attempt {}/{})",
@@ -310,14 +427,20 @@ pub fn push_authorship_notes(repository: &Repository, remote_name: &str) -> Resu
             );
         }
 
-        fetch_and_merge_tracking_notes(repository, remote_name);
+        fetch_and_merge_tracking_notes(repository, remote_name, cancelled);
 
-        // Push notes without force (requires fast-forward)
-        let push_args = build_authorship_push_args(repository.global_args_for_exec(), remote_name);
+        // Push notes without force (requires fast-forward). Upgraded and
+        // overflow notes go along in the same push once the repository has them.
+        let push_args = build_authorship_push_args(
+            repository.global_args_for_exec(),
+            remote_name,
+            NotesNamespace::V2.exists_in(repository),
+            ref_exists(repository, OVERFLOW_NOTES_REF),
+        );
 
         tracing::debug!("pushing authorship refs (no force): {:?}", &push_args);
 
-        match exec_git(&push_args) {
+        match exec_git_cancellable(&push_args, cancelled) {
             Ok(_) => return Ok(()),
             Err(e) => {
                 tracing::debug!("authorship push failed: {}", e);
@@ -332,12 +455,15 @@ pub fn push_authorship_notes(repository: &Repository, remote_name: &str) -> Resu
         }
     }
 
-    Err(last_error
-        .unwrap_or_else(|| GitAiError::Generic("notes push exhausted retries".to_string())))
+    Err(last_error.unwrap_or_else(|| GitAiError::Sync("notes push exhausted retries".to_string())))
 }
 
 /// Fetch remote notes into a tracking ref and merge into local refs/notes/ai.
-fn fetch_and_merge_tracking_notes(repository: &Repository, remote_name: &str) {
+fn fetch_and_merge_tracking_notes(
+    repository: &Repository,
+    remote_name: &str,
+    cancelled: &AtomicBool,
+) {
     let tracking_ref = tracking_ref_for_remote(remote_name);
     let fetch_refspec = format!("+refs/notes/ai:{}", tracking_ref);
 
@@ -350,7 +476,7 @@ fn fetch_and_merge_tracking_notes(repository: &Repository, remote_name: &str) {
     tracing::debug!("pre-push authorship fetch: {:?}", &fetch_args);
 
     // Fetch is best-effort; if it fails (e.g., no remote notes yet), continue
-    if exec_git(&fetch_args).is_err() {
+    if exec_git_cancellable(&fetch_args, cancelled).is_err() {
         return;
     }
 
@@ -360,6 +486,10 @@ fn fetch_and_merge_tracking_notes(repository: &Repository, remote_name: &str) {
         return;
     }
 
+    if ref_exists(repository, OVERFLOW_NOTES_REF) {
+        fetch_and_merge_overflow_notes(repository, remote_name, cancelled);
+    }
+
     if !ref_exists(repository, local_notes_ref) {
         // Only tracking ref exists - copy it to local
         tracing::debug!(
@@ -379,6 +509,12 @@ fn fetch_and_merge_tracking_notes(repository: &Repository, remote_name: &str) {
         tracking_ref,
         local_notes_ref
     );
+    fetch_and_merge_upgraded_notes(repository, remote_name, cancelled);
+
+    let diverged = diverged_note_objects(repository, &tracking_ref).unwrap_or_else(|e| {
+        tracing::debug!("pre-push notes divergence check failed: {}", e);
+        HashSet::new()
+    });
     if let Err(e) = merge_notes_from_ref(repository, &tracking_ref) {
         tracing::debug!("pre-push notes merge failed: {}", e);
         // Fallback: manually merge notes when git notes merge crashes
@@ -388,6 +524,16 @@ fn fetch_and_merge_tracking_notes(repository: &Repository, remote_name: &str) {
             tracing::debug!("pre-push fallback merge also failed: {}", e2);
         }
     }
+    if let Err(e) = union_conflicting_notes_from_ref(repository, &tracking_ref, &diverged) {
+        tracing::debug!("pre-push notes union failed: {}", e);
+    }
+}
+
+fn check_cancelled(cancelled: &AtomicBool, operation: &str) -> Result<(), GitAiError> {
+    if cancelled.load(Ordering::Relaxed) {
+        return Err(GitAiError::Sync(format!("{} cancelled", operation)));
+    }
+    Ok(())
 }
 
 fn is_non_fast_forward_error(error: &GitAiError) -> bool {
@@ -471,7 +617,12 @@ fn build_authorship_fetch_args(
     args
 }
 
-fn build_authorship_push_args(global_args: Vec<String>, remote_name: &str) -> Vec<String> {
+fn build_authorship_push_args(
+    global_args: Vec<String>,
+    remote_name: &str,
+    include_upgraded_notes: bool,
+    include_overflow_notes: bool,
+) -> Vec<String> {
     let mut args = with_disabled_hooks(global_args);
     args.push("push".to_string());
     args.push("--quiet".to_string());
@@ -480,6 +631,13 @@ fn build_authorship_push_args(global_args: Vec<String>, remote_name: &str) -> Ve
     args.push("--no-signed".to_string());
     args.push(remote_name.to_string());
     args.push(AI_AUTHORSHIP_PUSH_REFSPEC.to_string());
+    if include_upgraded_notes {
+        let v2_ref = NotesNamespace::V2.full_ref();
+        args.push(format!("{}:{}", v2_ref, v2_ref));
+    }
+    if include_overflow_notes {
+        args.push(format!("{}:{}", OVERFLOW_NOTES_REF, OVERFLOW_NOTES_REF));
+    }
     args
 }
 
@@ -506,14 +664,43 @@ mod tests {
     #[test]
     fn authorship_push_args_always_disable_hooks() {
         let disabled_hooks = disabled_hooks_config();
-        let args =
-            build_authorship_push_args(vec!["-C".to_string(), "/tmp/repo".to_string()], "origin");
+        let args = build_authorship_push_args(
+            vec!["-C".to_string(), "/tmp/repo".to_string()],
+            "origin",
+            false,
+            false,
+        );
 
         assert!(
             args.windows(2)
                 .any(|pair| pair[0] == "-c" && pair[1] == disabled_hooks)
         );
         assert!(args.contains(&"push".to_string()));
+        assert!(!args.iter().any(|arg| arg.contains("ai-v2")));
+    }
+
+    #[test]
+    fn authorship_push_args_include_upgraded_notes_when_present() {
+        let args = build_authorship_push_args(Vec::new(), "origin", true, false);
+        assert_eq!(
+            &args[args.len() - 2..],
+            [
+                AI_AUTHORSHIP_PUSH_REFSPEC.to_string(),
+                "refs/notes/ai-v2:refs/notes/ai-v2".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn authorship_push_args_include_overflow_notes_when_present() {
+        let args = build_authorship_push_args(Vec::new(), "origin", false, true);
+        assert_eq!(
+            &args[args.len() - 2..],
+            [
+                AI_AUTHORSHIP_PUSH_REFSPEC.to_string(),
+                "refs/notes/ai-overflow:refs/notes/ai-overflow".to_string()
+            ]
+        );
     }
 
     #[test]
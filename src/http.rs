@@ -24,10 +24,28 @@ pub fn build_agent(timeout_secs: Option<u64>) -> ureq::Agent {
 /// responses (matching minreq's previous behavior and what callers expect).
 pub struct Response {
     pub status_code: u16,
+    retry_after: Option<String>,
+    rate_limit_remaining: Option<String>,
+    rate_limit_reset: Option<String>,
     body: Vec<u8>,
 }
 
 impl Response {
+    /// Raw `Retry-After` header value, when the server sent one.
+    pub fn retry_after(&self) -> Option<&str> {
+        self.retry_after.as_deref()
+    }
+
+    /// Raw `X-RateLimit-Remaining` header value (GitHub's requests left in the window).
+    pub fn rate_limit_remaining(&self) -> Option<&str> {
+        self.rate_limit_remaining.as_deref()
+    }
+
+    /// Raw `X-RateLimit-Reset` header value (Unix seconds when the window resets).
+    pub fn rate_limit_reset(&self) -> Option<&str> {
+        self.rate_limit_reset.as_deref()
+    }
+
     pub fn as_str(&self) -> Result<&str, std::str::Utf8Error> {
         std::str::from_utf8(&self.body)
     }
@@ -43,12 +61,21 @@ impl Response {
 
 fn read_ureq_response(response: ureq::Response) -> Result<Response, String> {
     let status_code = response.status();
+    let retry_after = response.header("Retry-After").map(str::to_string);
+    let rate_limit_remaining = response.header("X-RateLimit-Remaining").map(str::to_string);
+    let rate_limit_reset = response.header("X-RateLimit-Reset").map(str::to_string);
     let mut body = Vec::new();
     response
         .into_reader()
         .read_to_end(&mut body)
         .map_err(|e| format!("Failed to read response body: {}", e))?;
-    Ok(Response { status_code, body })
+    Ok(Response {
+        status_code,
+        retry_after,
+        rate_limit_remaining,
+        rate_limit_reset,
+        body,
+    })
 }
 
 /// Execute a ureq request, normalizing errors so that HTTP error status codes
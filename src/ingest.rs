@@ -0,0 +1,357 @@
+//! Self-hosted ingestion endpoint for metrics uploads.
+//!
+//! Available with the `ingest-server` feature and served by the `git-ai-ingest`
+//! binary. It accepts the same `POST /worker/metrics/upload` requests the
+//! client sends to the hosted API, checks the batch against
+//! [`METRICS_API_VERSION`], and stores each event as a row in SQLite or
+//! Postgres. Point clients at it with `api_base_url`.
+
+use crate::api::ApiErrorResponse;
+use crate::api::metrics::{MetricsUploadError, MetricsUploadResponse};
+use crate::error::GitAiError;
+use crate::metrics::types::MetricEventId;
+use crate::metrics::{METRICS_API_VERSION, MetricEvent, MetricsBatch};
+use axum::Json;
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::{DefaultBodyLimit, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use rusqlite::Connection;
+use std::sync::{Arc, Mutex};
+
+/// Most events the client puts in one upload.
+pub const MAX_EVENTS_PER_BATCH: usize = 1000;
+
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+const KNOWN_EVENT_IDS: &[MetricEventId] = &[
+    MetricEventId::Committed,
+    MetricEventId::AgentUsage,
+    MetricEventId::InstallHooks,
+    MetricEventId::Checkpoint,
+    MetricEventId::SessionEvent,
+    MetricEventId::OtelTrace,
+    MetricEventId::RewriteCommitted,
+    MetricEventId::TabCompletion,
+    MetricEventId::CheckpointDeduped,
+];
+
+const SQLITE_SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS metric_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    received_at INTEGER NOT NULL,
+    api_version INTEGER NOT NULL,
+    event_id INTEGER NOT NULL,
+    event_ts INTEGER NOT NULL,
+    event_values TEXT NOT NULL,
+    event_attrs TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS metric_events_event_ts ON metric_events (event_id, event_ts);
+";
+
+const POSTGRES_SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS metric_events (
+    id BIGSERIAL PRIMARY KEY,
+    received_at BIGINT NOT NULL,
+    api_version INTEGER NOT NULL,
+    event_id INTEGER NOT NULL,
+    event_ts BIGINT NOT NULL,
+    event_values JSONB NOT NULL,
+    event_attrs JSONB NOT NULL
+);
+CREATE INDEX IF NOT EXISTS metric_events_event_ts ON metric_events (event_id, event_ts);
+";
+
+/// Where accepted events are written.
+pub enum IngestStore {
+    Sqlite(Arc<Mutex<Connection>>),
+    Postgres(tokio::sync::Mutex<tokio_postgres::Client>),
+}
+
+impl IngestStore {
+    /// Open `database`: a `postgres://` or `postgresql://` URL, or a SQLite
+    /// path (optionally prefixed with `sqlite://`). Creates the table if needed.
+    pub async fn connect(database: &str) -> Result<Self, GitAiError> {
+        if database.starts_with("postgres://") || database.starts_with("postgresql://") {
+            let (client, connection) = tokio_postgres::connect(database, tokio_postgres::NoTls)
+                .await
+                .map_err(|e| GitAiError::Generic(format!("postgres connect failed: {}", e)))?;
+            tokio::spawn(async move {
+                if let Err(e) = connection.await {
+                    tracing::error!(%e, "postgres connection closed");
+                }
+            });
+            client
+                .batch_execute(POSTGRES_SCHEMA)
+                .await
+                .map_err(|e| GitAiError::Generic(format!("postgres schema failed: {}", e)))?;
+            return Ok(IngestStore::Postgres(tokio::sync::Mutex::new(client)));
+        }
+
+        let path = database.strip_prefix("sqlite://").unwrap_or(database);
+        let conn = crate::sqlite::open_with_memory_limits(path)?;
+        conn.execute_batch(SQLITE_SCHEMA)?;
+        Ok(IngestStore::Sqlite(Arc::new(Mutex::new(conn))))
+    }
+
+    /// Write `events` in one transaction.
+    pub async fn insert(
+        &self,
+        version: u8,
+        events: Vec<MetricEvent>,
+        received_at: i64,
+    ) -> Result<(), GitAiError> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        match self {
+            IngestStore::Sqlite(conn) => {
+                let conn = Arc::clone(conn);
+                tokio::task::spawn_blocking(move || {
+                    let mut conn = conn
+                        .lock()
+                        .map_err(|_| GitAiError::Generic("sqlite store poisoned".to_string()))?;
+                    insert_events_sqlite(&mut conn, version, &events, received_at)
+                })
+                .await
+                .map_err(|e| GitAiError::Generic(format!("sqlite insert task failed: {}", e)))?
+            }
+            IngestStore::Postgres(client) => {
+                let mut client = client.lock().await;
+                insert_events_postgres(&mut client, version, &events, received_at)
+                    .await
+                    .map_err(|e| GitAiError::Generic(format!("postgres insert failed: {}", e)))
+            }
+        }
+    }
+}
+
+pub fn insert_events_sqlite(
+    conn: &mut Connection,
+    version: u8,
+    events: &[MetricEvent],
+    received_at: i64,
+) -> Result<(), GitAiError> {
+    let tx = conn.transaction()?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO metric_events
+                (received_at, api_version, event_id, event_ts, event_values, event_attrs)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        for event in events {
+            stmt.execute(rusqlite::params![
+                received_at,
+                version,
+                event.event_id,
+                event.timestamp,
+                serde_json::to_string(&event.values)?,
+                serde_json::to_string(&event.attrs)?,
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+async fn insert_events_postgres(
+    client: &mut tokio_postgres::Client,
+    version: u8,
+    events: &[MetricEvent],
+    received_at: i64,
+) -> Result<(), tokio_postgres::Error> {
+    let tx = client.transaction().await?;
+    let stmt = tx
+        .prepare(
+            "INSERT INTO metric_events
+                (received_at, api_version, event_id, event_ts, event_values, event_attrs)
+             VALUES ($1, $2, $3, $4, CAST($5::text AS jsonb), CAST($6::text AS jsonb))",
+        )
+        .await?;
+    for event in events {
+        let values = serde_json::to_string(&event.values).unwrap_or_else(|_| "{}".to_string());
+        let attrs = serde_json::to_string(&event.attrs).unwrap_or_else(|_| "{}".to_string());
+        tx.execute(
+            &stmt,
+            &[
+                &received_at,
+                &i32::from(version),
+                &i32::from(event.event_id),
+                &i64::from(event.timestamp),
+                &values,
+                &attrs,
+            ],
+        )
+        .await?;
+    }
+    tx.commit().await
+}
+
+/// Check a batch before storing it. A wrong API version or an oversized batch
+/// rejects the whole request; otherwise the per-event errors are returned,
+/// indexed like the client's batch, and every other event is accepted.
+pub fn validate_batch(batch: &MetricsBatch) -> Result<Vec<MetricsUploadError>, String> {
+    if batch.version != METRICS_API_VERSION {
+        return Err(format!(
+            "Unsupported metrics API version {} (expected {})",
+            batch.version, METRICS_API_VERSION
+        ));
+    }
+    if batch.events.len() > MAX_EVENTS_PER_BATCH {
+        return Err(format!(
+            "Batch has {} events (max {})",
+            batch.events.len(),
+            MAX_EVENTS_PER_BATCH
+        ));
+    }
+
+    let errors = batch
+        .events
+        .iter()
+        .enumerate()
+        .filter_map(|(index, event)| {
+            let error = if !KNOWN_EVENT_IDS
+                .iter()
+                .any(|id| *id as u16 == event.event_id)
+            {
+                format!("Unknown event id {}", event.event_id)
+            } else if event.timestamp == 0 {
+                "Missing event timestamp".to_string()
+            } else {
+                return None;
+            };
+            Some(MetricsUploadError { index, error })
+        })
+        .collect();
+    Ok(errors)
+}
+
+pub struct IngestState {
+    pub store: IngestStore,
+    /// Required in `X-API-Key` or as a bearer token when set.
+    pub api_key: Option<String>,
+}
+
+pub fn router(state: IngestState) -> Router {
+    Router::new()
+        .route("/worker/metrics/upload", post(upload_metrics))
+        .route("/health", get(|| async { "ok" }))
+        .layer(DefaultBodyLimit::max(MAX_BODY_BYTES))
+        .with_state(Arc::new(state))
+}
+
+fn error_response(status: StatusCode, error: impl Into<String>) -> Response {
+    let body = ApiErrorResponse {
+        error: error.into(),
+        details: None,
+    };
+    (status, Json(body)).into_response()
+}
+
+fn is_authorized(headers: &HeaderMap, api_key: &str) -> bool {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    header("x-api-key") == Some(api_key)
+        || header("authorization").and_then(|value| value.strip_prefix("Bearer ")) == Some(api_key)
+}
+
+async fn upload_metrics(
+    State(state): State<Arc<IngestState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    if let Some(api_key) = state.api_key.as_deref()
+        && !is_authorized(&headers, api_key)
+    {
+        return error_response(StatusCode::UNAUTHORIZED, "Unauthorized");
+    }
+
+    let batch: MetricsBatch = match serde_json::from_slice(&body) {
+        Ok(batch) => batch,
+        Err(e) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid request body: {}", e),
+            );
+        }
+    };
+    let errors = match validate_batch(&batch) {
+        Ok(errors) => errors,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+
+    let accepted: Vec<MetricEvent> = batch
+        .events
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| !errors.iter().any(|error| error.index == *index))
+        .map(|(_, event)| event)
+        .collect();
+    let received_at = chrono::Utc::now().timestamp();
+    if let Err(e) = state
+        .store
+        .insert(batch.version, accepted, received_at)
+        .await
+    {
+        tracing::error!(%e, "failed to store metrics batch");
+        return error_response(StatusCode::INTERNAL_SERVER_ERROR, "Failed to store events");
+    }
+
+    Json(MetricsUploadResponse { errors }).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn event(event_id: u16, timestamp: u32) -> MetricEvent {
+        MetricEvent {
+            timestamp,
+            event_id,
+            values: [("0".to_string(), Value::from(3))].into_iter().collect(),
+            attrs: Default::default(),
+        }
+    }
+
+    #[test]
+    fn validate_batch_rejects_version_and_flags_bad_events() {
+        let mut batch = MetricsBatch::new(vec![
+            event(MetricEventId::Committed as u16, 1_700_000_000),
+            event(999, 1_700_000_000),
+            event(MetricEventId::Checkpoint as u16, 0),
+        ]);
+        let errors = validate_batch(&batch).unwrap();
+        assert_eq!(
+            errors.iter().map(|e| e.index).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+
+        batch.version = METRICS_API_VERSION + 1;
+        assert!(validate_batch(&batch).is_err());
+    }
+
+    #[test]
+    fn sqlite_store_writes_one_row_per_event() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(SQLITE_SCHEMA).unwrap();
+        let events = vec![
+            event(MetricEventId::Committed as u16, 1_700_000_000),
+            event(MetricEventId::AgentUsage as u16, 1_700_000_001),
+        ];
+        insert_events_sqlite(&mut conn, METRICS_API_VERSION, &events, 42).unwrap();
+
+        let rows: Vec<(i64, String)> = conn
+            .prepare("SELECT event_id, event_values FROM metric_events ORDER BY id")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![(1, "{\"0\":3}".to_string()), (2, "{\"0\":3}".to_string())]
+        );
+    }
+}
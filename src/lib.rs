@@ -10,8 +10,12 @@ pub mod diagnostic_sentinels;
 pub mod diagnostics;
 pub mod error;
 pub mod feature_flags;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod git;
 pub mod http;
+#[cfg(feature = "ingest-server")]
+pub mod ingest;
 pub mod mdm;
 pub mod metrics;
 pub mod notes;
@@ -21,6 +25,9 @@ pub mod repo_url;
 pub(crate) mod sandbox;
 pub mod sqlite;
 pub mod streams;
+#[cfg(feature = "test-support")]
+pub mod test_support;
 pub mod tokio_runtime;
 pub mod utils;
 pub mod uuid;
+pub mod workspace_config;
@@ -34,7 +34,16 @@ fn is_superuser_exempt_command(args: &[String]) -> bool {
             .is_some_and(|s| s == "run" || s == "status" || s == "shutdown")
 }
 
+fn is_daemon_run_command(args: &[String]) -> bool {
+    matches!(
+        args.first().map(String::as_str),
+        Some("bg" | "d" | "daemon")
+    ) && args.get(1).is_some_and(|s| s == "run")
+}
+
 fn main() {
+    git_ai::observability::errors::install_panic_hook();
+
     // Get the binary name that was called
     let binary_name = std::env::args_os()
         .next()
@@ -59,6 +68,11 @@ fn main() {
 
     let cli = Cli::parse();
 
+    // The daemon installs its own subscriber (which also honors the trace file).
+    if !is_daemon_run_command(&cli.args) {
+        git_ai::observability::trace::init_cli_tracing();
+    }
+
     #[cfg(debug_assertions)]
     {
         if std::env::var("GIT_AI").as_deref() == Ok("git") {
@@ -0,0 +1,331 @@
+use crate::error::GitAiError;
+use crate::mdm::hook_installer::{HookCheckResult, HookInstaller, HookInstallerParams};
+use crate::mdm::utils::{binary_exists, generate_diff, home_dir, write_atomic};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const AMAZON_Q_CHECKPOINT_CMD: &str = "checkpoint amazon-q --hook-input stdin";
+const AMAZON_Q_CATCH_ALL_MATCHER: &str = "*";
+const AMAZON_Q_HOOK_EVENTS: &[&str] = &["preToolUse", "postToolUse"];
+
+/// Hooks Amazon Q Developer CLI edits into checkpoints.
+///
+/// The Q Developer CLI gets `preToolUse` / `postToolUse` command hooks in its
+/// default agent (`~/.aws/amazonq/cli-agents/default.json`). Without that file
+/// the CLI runs its built-in default agent; a file holding only our hooks would
+/// replace it and drop its tools, so hooks are only merged into an existing
+/// one. The Q IDE plugins expose no edit events, so they are not hooked.
+pub struct AmazonQInstaller;
+
+impl AmazonQInstaller {
+    fn config_dir() -> PathBuf {
+        home_dir().join(".aws").join("amazonq")
+    }
+
+    fn agent_path() -> PathBuf {
+        Self::config_dir().join("cli-agents").join("default.json")
+    }
+
+    fn cli_installed() -> bool {
+        binary_exists("q") || Self::config_dir().exists()
+    }
+
+    fn is_amazon_q_checkpoint_command(cmd: &str) -> bool {
+        cmd.contains("git-ai") && cmd.contains("checkpoint amazon-q")
+    }
+
+    fn desired_command(params: &HookInstallerParams) -> String {
+        format!(
+            "{} {}",
+            params.binary_path.display(),
+            AMAZON_Q_CHECKPOINT_CMD
+        )
+    }
+
+    fn event_commands<'a>(config: &'a Value, event: &str) -> Vec<&'a str> {
+        config
+            .get("hooks")
+            .and_then(|hooks| hooks.get(event))
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|hook| hook.get("command").and_then(|c| c.as_str()))
+            .collect()
+    }
+
+    fn install_agent_hooks_at(
+        agent_path: &Path,
+        params: &HookInstallerParams,
+        dry_run: bool,
+    ) -> Result<Option<String>, GitAiError> {
+        if !agent_path.exists() {
+            tracing::debug!(
+                "{} does not exist; not replacing the built-in Amazon Q default agent",
+                agent_path.display()
+            );
+            return Ok(None);
+        }
+        let existing_content = fs::read_to_string(agent_path)?;
+        let existing: Value = serde_json::from_str(&existing_content)?;
+
+        let desired_cmd = Self::desired_command(params);
+        let mut merged = existing.clone();
+        let mut hooks_obj = merged.get("hooks").cloned().unwrap_or_else(|| json!({}));
+
+        for event in AMAZON_Q_HOOK_EVENTS {
+            let mut entries = hooks_obj
+                .get(*event)
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default();
+
+            // Drop stale git-ai entries so an old binary path is replaced.
+            entries.retain(|entry| {
+                entry
+                    .get("command")
+                    .and_then(|c| c.as_str())
+                    .map(|cmd| !Self::is_amazon_q_checkpoint_command(cmd) || cmd == desired_cmd)
+                    .unwrap_or(true)
+            });
+            if !entries.iter().any(|entry| {
+                entry.get("command").and_then(|c| c.as_str()) == Some(desired_cmd.as_str())
+            }) {
+                entries.push(json!({
+                    "matcher": AMAZON_Q_CATCH_ALL_MATCHER,
+                    "command": desired_cmd,
+                }));
+            }
+
+            if let Some(obj) = hooks_obj.as_object_mut() {
+                obj.insert(event.to_string(), Value::Array(entries));
+            }
+        }
+
+        if let Some(root) = merged.as_object_mut() {
+            root.insert("hooks".to_string(), hooks_obj);
+        }
+
+        if existing == merged {
+            return Ok(None);
+        }
+
+        let new_content = serde_json::to_string_pretty(&merged)?;
+        let diff_output = generate_diff(agent_path, &existing_content, &new_content);
+
+        if !dry_run {
+            write_atomic(agent_path, new_content.as_bytes())?;
+        }
+
+        Ok(Some(diff_output))
+    }
+
+    fn uninstall_agent_hooks_at(
+        agent_path: &Path,
+        dry_run: bool,
+    ) -> Result<Option<String>, GitAiError> {
+        if !agent_path.exists() {
+            return Ok(None);
+        }
+
+        let existing_content = fs::read_to_string(agent_path)?;
+        let existing: Value = serde_json::from_str(&existing_content)?;
+
+        let mut merged = existing.clone();
+        let mut changed = false;
+        for event in AMAZON_Q_HOOK_EVENTS {
+            let Some(entries) = merged
+                .get_mut("hooks")
+                .and_then(|hooks| hooks.get_mut(*event))
+                .and_then(|v| v.as_array_mut())
+            else {
+                continue;
+            };
+            let original_len = entries.len();
+            entries.retain(|entry| {
+                entry
+                    .get("command")
+                    .and_then(|c| c.as_str())
+                    .map(|cmd| !Self::is_amazon_q_checkpoint_command(cmd))
+                    .unwrap_or(true)
+            });
+            changed |= entries.len() != original_len;
+        }
+
+        if !changed {
+            return Ok(None);
+        }
+
+        let new_content = serde_json::to_string_pretty(&merged)?;
+        let diff_output = generate_diff(agent_path, &existing_content, &new_content);
+
+        if !dry_run {
+            write_atomic(agent_path, new_content.as_bytes())?;
+        }
+
+        Ok(Some(diff_output))
+    }
+}
+
+impl HookInstaller for AmazonQInstaller {
+    fn name(&self) -> &str {
+        "Amazon Q Developer"
+    }
+
+    fn id(&self) -> &str {
+        "amazon-q"
+    }
+
+    fn process_names(&self) -> Vec<&str> {
+        vec!["q", "qchat"]
+    }
+
+    fn check_hooks(&self, params: &HookInstallerParams) -> Result<HookCheckResult, GitAiError> {
+        if !Self::cli_installed() {
+            return Ok(HookCheckResult {
+                tool_installed: false,
+                hooks_installed: false,
+                hooks_up_to_date: false,
+            });
+        }
+
+        let desired_cmd = Self::desired_command(params);
+        let agent: Value = fs::read_to_string(Self::agent_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_else(|| json!({}));
+        let configured: Vec<String> = AMAZON_Q_HOOK_EVENTS
+            .iter()
+            .map(|event| {
+                Self::event_commands(&agent, event)
+                    .into_iter()
+                    .find(|cmd| Self::is_amazon_q_checkpoint_command(cmd))
+                    .unwrap_or_default()
+                    .to_string()
+            })
+            .collect();
+
+        Ok(HookCheckResult {
+            tool_installed: true,
+            hooks_installed: configured
+                .iter()
+                .any(|cmd| Self::is_amazon_q_checkpoint_command(cmd)),
+            hooks_up_to_date: configured.iter().all(|cmd| *cmd == desired_cmd),
+        })
+    }
+
+    fn install_hooks(
+        &self,
+        params: &HookInstallerParams,
+        dry_run: bool,
+    ) -> Result<Option<String>, GitAiError> {
+        if !Self::cli_installed() {
+            return Ok(None);
+        }
+        Self::install_agent_hooks_at(&Self::agent_path(), params, dry_run)
+    }
+
+    fn uninstall_hooks(
+        &self,
+        _params: &HookInstallerParams,
+        dry_run: bool,
+    ) -> Result<Option<String>, GitAiError> {
+        Self::uninstall_agent_hooks_at(&Self::agent_path(), dry_run)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_params() -> HookInstallerParams {
+        HookInstallerParams {
+            binary_path: PathBuf::from("/usr/local/bin/git-ai"),
+        }
+    }
+
+    #[test]
+    fn test_install_agent_hooks_merges_into_existing_default_agent_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let agent_path = temp_dir.path().join("cli-agents").join("default.json");
+
+        // No default agent file: the CLI's built-in default must stay in charge.
+        assert!(
+            AmazonQInstaller::install_agent_hooks_at(&agent_path, &test_params(), false)
+                .unwrap()
+                .is_none()
+        );
+        assert!(!agent_path.exists());
+
+        fs::create_dir_all(agent_path.parent().unwrap()).unwrap();
+        fs::write(
+            &agent_path,
+            r#"{"name":"default","tools":["*"],"allowedTools":["fs_read"]}"#,
+        )
+        .unwrap();
+        let diff =
+            AmazonQInstaller::install_agent_hooks_at(&agent_path, &test_params(), false).unwrap();
+        assert!(diff.is_some());
+
+        let agent: Value = serde_json::from_str(&fs::read_to_string(&agent_path).unwrap()).unwrap();
+        assert_eq!(agent["name"], "default");
+        assert_eq!(agent["tools"], json!(["*"]));
+        assert_eq!(agent["allowedTools"], json!(["fs_read"]));
+        for event in AMAZON_Q_HOOK_EVENTS {
+            assert_eq!(agent["hooks"][*event][0]["matcher"], "*");
+            assert_eq!(
+                agent["hooks"][*event][0]["command"],
+                "/usr/local/bin/git-ai checkpoint amazon-q --hook-input stdin"
+            );
+        }
+
+        assert!(
+            AmazonQInstaller::install_agent_hooks_at(&agent_path, &test_params(), false)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_install_agent_hooks_replaces_stale_entry_and_keeps_user_hooks() {
+        let temp_dir = TempDir::new().unwrap();
+        let agent_path = temp_dir.path().join("default.json");
+        fs::write(
+            &agent_path,
+            r#"{"name":"default","tools":["*"],"hooks":{"postToolUse":[{"matcher":"fs_write","command":"cargo fmt"},{"matcher":"*","command":"/old/git-ai checkpoint amazon-q --hook-input stdin"}]}}"#,
+        )
+        .unwrap();
+
+        AmazonQInstaller::install_agent_hooks_at(&agent_path, &test_params(), false).unwrap();
+
+        let agent: Value = serde_json::from_str(&fs::read_to_string(&agent_path).unwrap()).unwrap();
+        assert_eq!(agent["tools"], json!(["*"]));
+        assert_eq!(
+            AmazonQInstaller::event_commands(&agent, "postToolUse"),
+            vec![
+                "cargo fmt",
+                "/usr/local/bin/git-ai checkpoint amazon-q --hook-input stdin"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_uninstall_agent_hooks_removes_only_git_ai_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let agent_path = temp_dir.path().join("default.json");
+        fs::write(&agent_path, r#"{"name":"default","tools":["*"]}"#).unwrap();
+        AmazonQInstaller::install_agent_hooks_at(&agent_path, &test_params(), false).unwrap();
+
+        let diff = AmazonQInstaller::uninstall_agent_hooks_at(&agent_path, false).unwrap();
+        assert!(diff.is_some());
+
+        let agent: Value = serde_json::from_str(&fs::read_to_string(&agent_path).unwrap()).unwrap();
+        assert!(AmazonQInstaller::event_commands(&agent, "preToolUse").is_empty());
+        assert!(
+            AmazonQInstaller::uninstall_agent_hooks_at(&agent_path, false)
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use crate::error::GitAiError;
+use crate::mdm::agents::GitHubCopilotInstaller;
+use crate::mdm::hook_installer::{HookCheckResult, HookInstaller, HookInstallerParams};
+use crate::mdm::utils::{
+    generate_diff, home_dir, resolve_editor_cli, settings_paths_for_products,
+    should_process_settings_target, write_atomic,
+};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::PathBuf;
+
+const COPILOT_AGENT_CHECKPOINT_CMD: &str = "checkpoint copilot-agent --hook-input stdin";
+
+/// Whether the agent-mode hooks are installed. While they are, VS Code tool calls
+/// are checkpointed as `copilot-agent` and the `github-copilot` hooks skip them.
+pub fn copilot_agent_hooks_installed() -> bool {
+    fs::read_to_string(CopilotAgentInstaller::hooks_path())
+        .map(|content| content.contains("checkpoint copilot-agent"))
+        .unwrap_or(false)
+}
+
+/// Opt-in target (`install-hooks --copilot-agent`) for VS Code Copilot agent mode.
+///
+/// Writes a dedicated hooks file next to the `github-copilot` one, so agent-mode
+/// sessions are recorded with the `copilot-agent` tool and their own session and
+/// turn ids instead of being folded into Copilot completions.
+pub struct CopilotAgentInstaller;
+
+impl CopilotAgentInstaller {
+    fn hooks_path() -> PathBuf {
+        home_dir()
+            .join(".copilot")
+            .join("hooks")
+            .join("git-ai-copilot-agent.json")
+    }
+
+    fn desired_hooks(params: &HookInstallerParams) -> Value {
+        let hook = GitHubCopilotInstaller::checkpoint_hook(
+            &params.binary_path,
+            COPILOT_AGENT_CHECKPOINT_CMD,
+        );
+        json!({
+            "hooks": {
+                "PreToolUse": [hook.clone()],
+                "PostToolUse": [hook]
+            }
+        })
+    }
+}
+
+impl HookInstaller for CopilotAgentInstaller {
+    fn name(&self) -> &str {
+        "GitHub Copilot agent mode"
+    }
+
+    fn id(&self) -> &str {
+        "copilot-agent"
+    }
+
+    fn process_names(&self) -> Vec<&str> {
+        vec!["Code", "code"]
+    }
+
+    fn check_hooks(&self, params: &HookInstallerParams) -> Result<HookCheckResult, GitAiError> {
+        let has_vscode = resolve_editor_cli("code").is_some()
+            || home_dir().join(".vscode").exists()
+            || settings_paths_for_products(&["Code", "Code - Insiders"])
+                .iter()
+                .any(|path| should_process_settings_target(path));
+        if !has_vscode {
+            return Ok(HookCheckResult {
+                tool_installed: false,
+                hooks_installed: false,
+                hooks_up_to_date: false,
+            });
+        }
+
+        let existing: Option<Value> = fs::read_to_string(Self::hooks_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok());
+        Ok(HookCheckResult {
+            tool_installed: true,
+            hooks_installed: copilot_agent_hooks_installed(),
+            hooks_up_to_date: existing.as_ref() == Some(&Self::desired_hooks(params)),
+        })
+    }
+
+    fn install_hooks(
+        &self,
+        params: &HookInstallerParams,
+        dry_run: bool,
+    ) -> Result<Option<String>, GitAiError> {
+        let hooks_path = Self::hooks_path();
+        let existing_content = fs::read_to_string(&hooks_path).unwrap_or_default();
+        let desired = Self::desired_hooks(params);
+        if serde_json::from_str::<Value>(&existing_content)
+            .ok()
+            .as_ref()
+            == Some(&desired)
+        {
+            return Ok(None);
+        }
+
+        // The file is owned by git-ai, so it is rewritten rather than merged.
+        let new_content = serde_json::to_string_pretty(&desired)?;
+        let diff_output = generate_diff(&hooks_path, &existing_content, &new_content);
+        if !dry_run {
+            if let Some(dir) = hooks_path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            write_atomic(&hooks_path, new_content.as_bytes())?;
+        }
+        Ok(Some(diff_output))
+    }
+
+    fn uninstall_hooks(
+        &self,
+        _params: &HookInstallerParams,
+        dry_run: bool,
+    ) -> Result<Option<String>, GitAiError> {
+        let hooks_path = Self::hooks_path();
+        let Ok(existing_content) = fs::read_to_string(&hooks_path) else {
+            return Ok(None);
+        };
+        let diff_output = generate_diff(&hooks_path, &existing_content, "");
+        if !dry_run {
+            fs::remove_file(&hooks_path)?;
+        }
+        Ok(Some(diff_output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use tempfile::tempdir;
+
+    #[test]
+    #[serial]
+    fn test_install_and_uninstall_copilot_agent_hooks() {
+        let temp = tempdir().unwrap();
+        let prev_home = std::env::var_os("HOME");
+        let prev_userprofile = std::env::var_os("USERPROFILE");
+        // SAFETY: tests are serialized via #[serial], so mutating process env is safe.
+        unsafe {
+            std::env::set_var("HOME", temp.path());
+            std::env::set_var("USERPROFILE", temp.path());
+        }
+
+        let installer = CopilotAgentInstaller;
+        let params = HookInstallerParams {
+            binary_path: PathBuf::from("/tmp/git-ai/bin/git-ai"),
+        };
+        assert!(!copilot_agent_hooks_installed());
+        assert!(installer.install_hooks(&params, false).unwrap().is_some());
+        assert!(copilot_agent_hooks_installed());
+        assert!(installer.install_hooks(&params, false).unwrap().is_none());
+
+        let content: Value =
+            serde_json::from_str(&fs::read_to_string(CopilotAgentInstaller::hooks_path()).unwrap())
+                .unwrap();
+        assert_eq!(
+            content["hooks"]["PostToolUse"][0]["command"].as_str(),
+            Some("/tmp/git-ai/bin/git-ai checkpoint copilot-agent --hook-input stdin")
+        );
+
+        assert!(installer.uninstall_hooks(&params, false).unwrap().is_some());
+        assert!(!copilot_agent_hooks_installed());
+
+        // SAFETY: tests are serialized via #[serial], so restoring process env is safe.
+        unsafe {
+            match prev_home {
+                Some(v) => std::env::set_var("HOME", v),
+                None => std::env::remove_var("HOME"),
+            }
+            match prev_userprofile {
+                Some(v) => std::env::set_var("USERPROFILE", v),
+                None => std::env::remove_var("USERPROFILE"),
+            }
+        }
+    }
+}
@@ -56,7 +56,7 @@ impl GitHubCopilotInstaller {
         }
     }
 
-    fn checkpoint_hook(binary_path: &Path, checkpoint_command: &str) -> Value {
+    pub(super) fn checkpoint_hook(binary_path: &Path, checkpoint_command: &str) -> Value {
         let binary_path = normalize_windows_path_for_shell(binary_path);
         let shell_path = Self::shell_quote_path(&binary_path);
         let powershell_path = format!("'{}'", binary_path.replace('\'', "''"));
@@ -1,7 +1,9 @@
+mod amazon_q;
 mod amp;
 mod claude_code;
 mod cline;
 mod codex;
+mod copilot_agent;
 mod cursor;
 mod droid;
 mod firebender;
@@ -9,16 +11,19 @@ mod gemini;
 mod github_copilot;
 mod jetbrains;
 mod opencode;
+mod openhands;
 mod pi;
 #[cfg(windows)]
 mod visual_studio;
 mod vscode;
 mod windsurf;
 
+pub use amazon_q::AmazonQInstaller;
 pub use amp::AmpInstaller;
 pub use claude_code::ClaudeCodeInstaller;
 pub use cline::ClineInstaller;
 pub use codex::CodexInstaller;
+pub use copilot_agent::{CopilotAgentInstaller, copilot_agent_hooks_installed};
 pub use cursor::CursorInstaller;
 pub use droid::DroidInstaller;
 pub use firebender::FirebenderInstaller;
@@ -26,6 +31,7 @@ pub use gemini::GeminiInstaller;
 pub use github_copilot::GitHubCopilotInstaller;
 pub use jetbrains::JetBrainsInstaller;
 pub use opencode::OpenCodeInstaller;
+pub use openhands::OpenHandsInstaller;
 pub use pi::PiInstaller;
 #[cfg(windows)]
 pub use visual_studio::VisualStudioInstaller;
@@ -43,6 +49,7 @@ pub fn get_all_installers() -> Vec<Box<dyn HookInstaller>> {
         Box::new(CursorInstaller),
         Box::new(VSCodeInstaller),
         Box::new(GitHubCopilotInstaller),
+        Box::new(CopilotAgentInstaller),
         Box::new(AmpInstaller),
         Box::new(OpenCodeInstaller),
         Box::new(PiInstaller),
@@ -50,6 +57,8 @@ pub fn get_all_installers() -> Vec<Box<dyn HookInstaller>> {
         Box::new(DroidInstaller),
         Box::new(FirebenderInstaller),
         Box::new(JetBrainsInstaller),
+        Box::new(OpenHandsInstaller),
+        Box::new(AmazonQInstaller),
     ];
 
     #[cfg(windows)]
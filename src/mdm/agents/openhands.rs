@@ -0,0 +1,335 @@
+use crate::error::GitAiError;
+use crate::mdm::hook_installer::{HookCheckResult, HookInstaller, HookInstallerParams};
+use crate::mdm::utils::{binary_exists, generate_diff, home_dir, write_atomic};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const OPENHANDS_CHECKPOINT_CMD: &str = "checkpoint openhands --hook-input stdin";
+const OPENHANDS_CATCH_ALL_MATCHER: &str = "*";
+const OPENHANDS_HOOK_EVENTS: &[&str] = &["PreToolUse", "PostToolUse"];
+
+/// Installs OpenHands runtime event hooks into `~/.openhands/hooks.json`.
+pub struct OpenHandsInstaller;
+
+impl OpenHandsInstaller {
+    fn config_dir() -> PathBuf {
+        home_dir().join(".openhands")
+    }
+
+    fn hooks_path() -> PathBuf {
+        Self::config_dir().join("hooks.json")
+    }
+
+    fn is_openhands_checkpoint_command(cmd: &str) -> bool {
+        cmd.contains("git-ai") && cmd.contains("checkpoint openhands")
+    }
+
+    fn block_commands(block: &Value) -> impl Iterator<Item = &str> {
+        block
+            .get("hooks")
+            .and_then(|h| h.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|hook| hook.get("command").and_then(|c| c.as_str()))
+    }
+
+    fn event_commands<'a>(config: &'a Value, event: &str) -> Vec<&'a str> {
+        config
+            .get(event)
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .flat_map(Self::block_commands)
+            .collect()
+    }
+
+    fn install_hooks_at(
+        hooks_path: &Path,
+        params: &HookInstallerParams,
+        dry_run: bool,
+    ) -> Result<Option<String>, GitAiError> {
+        let existing_content = if hooks_path.exists() {
+            fs::read_to_string(hooks_path)?
+        } else {
+            String::new()
+        };
+
+        let existing: Value = if existing_content.trim().is_empty() {
+            json!({})
+        } else {
+            serde_json::from_str(&existing_content)?
+        };
+
+        let desired_cmd = format!(
+            "{} {}",
+            params.binary_path.display(),
+            OPENHANDS_CHECKPOINT_CMD
+        );
+        let desired_hook = json!({ "type": "command", "command": desired_cmd });
+
+        let mut merged = existing.clone();
+        for event in OPENHANDS_HOOK_EVENTS {
+            let mut blocks = merged
+                .get(*event)
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default();
+
+            // Drop any existing git-ai entries, then re-add a single one to the
+            // catch-all block so stale binary paths are replaced.
+            for block in blocks.iter_mut() {
+                if let Some(hooks) = block.get_mut("hooks").and_then(|h| h.as_array_mut()) {
+                    hooks.retain(|hook| {
+                        hook.get("command")
+                            .and_then(|c| c.as_str())
+                            .map(|cmd| !Self::is_openhands_checkpoint_command(cmd))
+                            .unwrap_or(true)
+                    });
+                }
+            }
+            blocks.retain(|block| {
+                block
+                    .get("hooks")
+                    .and_then(|h| h.as_array())
+                    .map(|hooks| !hooks.is_empty())
+                    .unwrap_or(true)
+            });
+
+            match blocks.iter_mut().find(|block| {
+                block.get("matcher").and_then(|m| m.as_str()) == Some(OPENHANDS_CATCH_ALL_MATCHER)
+            }) {
+                Some(block) => {
+                    if let Some(hooks) = block.get_mut("hooks").and_then(|h| h.as_array_mut()) {
+                        hooks.push(desired_hook.clone());
+                    } else if let Some(obj) = block.as_object_mut() {
+                        obj.insert("hooks".to_string(), json!([desired_hook.clone()]));
+                    }
+                }
+                None => blocks.push(json!({
+                    "matcher": OPENHANDS_CATCH_ALL_MATCHER,
+                    "hooks": [desired_hook.clone()]
+                })),
+            }
+
+            if let Some(root) = merged.as_object_mut() {
+                root.insert(event.to_string(), Value::Array(blocks));
+            }
+        }
+
+        if existing == merged {
+            return Ok(None);
+        }
+
+        let new_content = serde_json::to_string_pretty(&merged)?;
+        let diff_output = generate_diff(hooks_path, &existing_content, &new_content);
+
+        if !dry_run {
+            if let Some(dir) = hooks_path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            write_atomic(hooks_path, new_content.as_bytes())?;
+        }
+
+        Ok(Some(diff_output))
+    }
+
+    fn uninstall_hooks_at(hooks_path: &Path, dry_run: bool) -> Result<Option<String>, GitAiError> {
+        if !hooks_path.exists() {
+            return Ok(None);
+        }
+
+        let existing_content = fs::read_to_string(hooks_path)?;
+        let existing: Value = serde_json::from_str(&existing_content)?;
+
+        let mut merged = existing.clone();
+        let mut changed = false;
+
+        for event in OPENHANDS_HOOK_EVENTS {
+            let Some(blocks) = merged.get_mut(*event).and_then(|v| v.as_array_mut()) else {
+                continue;
+            };
+            for block in blocks.iter_mut() {
+                if let Some(hooks) = block.get_mut("hooks").and_then(|h| h.as_array_mut()) {
+                    let original_len = hooks.len();
+                    hooks.retain(|hook| {
+                        hook.get("command")
+                            .and_then(|c| c.as_str())
+                            .map(|cmd| !Self::is_openhands_checkpoint_command(cmd))
+                            .unwrap_or(true)
+                    });
+                    if hooks.len() != original_len {
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        if !changed {
+            return Ok(None);
+        }
+
+        let new_content = serde_json::to_string_pretty(&merged)?;
+        let diff_output = generate_diff(hooks_path, &existing_content, &new_content);
+
+        if !dry_run {
+            write_atomic(hooks_path, new_content.as_bytes())?;
+        }
+
+        Ok(Some(diff_output))
+    }
+}
+
+impl HookInstaller for OpenHandsInstaller {
+    fn name(&self) -> &str {
+        "OpenHands"
+    }
+
+    fn id(&self) -> &str {
+        "openhands"
+    }
+
+    fn process_names(&self) -> Vec<&str> {
+        vec!["openhands"]
+    }
+
+    fn check_hooks(&self, params: &HookInstallerParams) -> Result<HookCheckResult, GitAiError> {
+        if !binary_exists("openhands") && !Self::config_dir().exists() {
+            return Ok(HookCheckResult {
+                tool_installed: false,
+                hooks_installed: false,
+                hooks_up_to_date: false,
+            });
+        }
+
+        let hooks_path = Self::hooks_path();
+        if !hooks_path.exists() {
+            return Ok(HookCheckResult {
+                tool_installed: true,
+                hooks_installed: false,
+                hooks_up_to_date: false,
+            });
+        }
+
+        let content = fs::read_to_string(&hooks_path)?;
+        let existing: Value = serde_json::from_str(&content).unwrap_or_else(|_| json!({}));
+        let desired_cmd = format!(
+            "{} {}",
+            params.binary_path.display(),
+            OPENHANDS_CHECKPOINT_CMD
+        );
+
+        Ok(HookCheckResult {
+            tool_installed: true,
+            hooks_installed: OPENHANDS_HOOK_EVENTS.iter().all(|event| {
+                Self::event_commands(&existing, event)
+                    .into_iter()
+                    .any(Self::is_openhands_checkpoint_command)
+            }),
+            hooks_up_to_date: OPENHANDS_HOOK_EVENTS.iter().all(|event| {
+                Self::event_commands(&existing, event)
+                    .into_iter()
+                    .any(|cmd| cmd == desired_cmd)
+            }),
+        })
+    }
+
+    fn install_hooks(
+        &self,
+        params: &HookInstallerParams,
+        dry_run: bool,
+    ) -> Result<Option<String>, GitAiError> {
+        Self::install_hooks_at(&Self::hooks_path(), params, dry_run)
+    }
+
+    fn uninstall_hooks(
+        &self,
+        _params: &HookInstallerParams,
+        dry_run: bool,
+    ) -> Result<Option<String>, GitAiError> {
+        Self::uninstall_hooks_at(&Self::hooks_path(), dry_run)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_params() -> HookInstallerParams {
+        HookInstallerParams {
+            binary_path: PathBuf::from("/usr/local/bin/git-ai"),
+        }
+    }
+
+    #[test]
+    fn test_install_hooks_creates_catch_all_blocks() {
+        let temp_dir = TempDir::new().unwrap();
+        let hooks_path = temp_dir.path().join(".openhands").join("hooks.json");
+
+        let diff =
+            OpenHandsInstaller::install_hooks_at(&hooks_path, &test_params(), false).unwrap();
+        assert!(diff.is_some());
+
+        let config: Value =
+            serde_json::from_str(&fs::read_to_string(&hooks_path).unwrap()).unwrap();
+        for event in OPENHANDS_HOOK_EVENTS {
+            assert_eq!(config[*event][0]["matcher"], "*");
+            assert_eq!(
+                config[*event][0]["hooks"][0]["command"],
+                "/usr/local/bin/git-ai checkpoint openhands --hook-input stdin"
+            );
+        }
+
+        assert!(
+            OpenHandsInstaller::install_hooks_at(&hooks_path, &test_params(), false)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_install_hooks_replaces_stale_entry_and_keeps_user_hooks() {
+        let temp_dir = TempDir::new().unwrap();
+        let hooks_path = temp_dir.path().join("hooks.json");
+        fs::write(
+            &hooks_path,
+            r#"{"PostToolUse":[{"matcher":"execute_bash","hooks":[{"type":"command","command":"/old/git-ai checkpoint openhands --hook-input stdin"}]},{"matcher":"*","hooks":[{"type":"command","command":"./lint.sh"}]}]}"#,
+        )
+        .unwrap();
+
+        OpenHandsInstaller::install_hooks_at(&hooks_path, &test_params(), false).unwrap();
+
+        let config: Value =
+            serde_json::from_str(&fs::read_to_string(&hooks_path).unwrap()).unwrap();
+        let blocks = config["PostToolUse"].as_array().unwrap();
+        assert_eq!(blocks.len(), 1);
+        let commands = OpenHandsInstaller::event_commands(&config, "PostToolUse");
+        assert_eq!(
+            commands,
+            vec![
+                "./lint.sh",
+                "/usr/local/bin/git-ai checkpoint openhands --hook-input stdin"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_uninstall_hooks_removes_only_git_ai_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let hooks_path = temp_dir.path().join("hooks.json");
+        OpenHandsInstaller::install_hooks_at(&hooks_path, &test_params(), false).unwrap();
+
+        let diff = OpenHandsInstaller::uninstall_hooks_at(&hooks_path, false).unwrap();
+        assert!(diff.is_some());
+
+        let config: Value =
+            serde_json::from_str(&fs::read_to_string(&hooks_path).unwrap()).unwrap();
+        assert!(OpenHandsInstaller::event_commands(&config, "PreToolUse").is_empty());
+        assert!(
+            OpenHandsInstaller::uninstall_hooks_at(&hooks_path, false)
+                .unwrap()
+                .is_none()
+        );
+    }
+}
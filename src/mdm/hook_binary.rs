@@ -0,0 +1,237 @@
+//! Where hooks find the git-ai binary.
+//!
+//! Agent hooks embed an absolute binary path. Package managers install into
+//! versioned directories (e.g. Homebrew's Cellar), so that path disappears on
+//! upgrade and every hook silently stops recording.
+//!
+//! `install-hooks` and `upgrade` keep `~/.git-ai/bin/current` pointing at the
+//! directory of the installed binary, and the daemon re-points an existing link
+//! at its own binary on startup, which follows every upgrade, including ones a
+//! package manager did on its own. Hooks embed the path through that link when
+//! it resolves to the installing binary. A hook binary is then resolved in
+//! order: the embedded path, the same file name on `PATH`, and the file name
+//! under the `current` link. `git-ai debug` reports which step resolves.
+
+use crate::error::GitAiError;
+use std::path::{Path, PathBuf};
+
+/// Name of the link under `~/.git-ai/bin` that points at the installed binary's
+/// directory.
+pub const CURRENT_LINK_NAME: &str = "current";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookBinarySource {
+    Embedded,
+    PathLookup,
+    CurrentLink,
+}
+
+impl HookBinarySource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HookBinarySource::Embedded => "embedded path",
+            HookBinarySource::PathLookup => "PATH lookup",
+            HookBinarySource::CurrentLink => "~/.git-ai/bin/current",
+        }
+    }
+}
+
+/// `~/.git-ai/bin/current`.
+pub fn current_link_path() -> Option<PathBuf> {
+    crate::config::git_ai_dir_path().map(|dir| dir.join("bin").join(CURRENT_LINK_NAME))
+}
+
+/// Point `~/.git-ai/bin/current` at `install_dir`. Returns whether the link
+/// changed. Not supported on Windows, where creating symlinks needs elevation.
+pub fn update_current_link(install_dir: &Path) -> Result<bool, GitAiError> {
+    let Some(link) = current_link_path() else {
+        return Ok(false);
+    };
+    update_link_at(&link, install_dir)
+}
+
+/// Re-point an existing `~/.git-ai/bin/current` at the running binary's
+/// directory. A missing link is left alone: only `install-hooks` creates it.
+pub fn refresh_current_link() {
+    let Some(link) = current_link_path() else {
+        return;
+    };
+    if std::fs::symlink_metadata(&link).is_err() {
+        return;
+    }
+    let Some(install_dir) = crate::mdm::utils::get_current_binary_path()
+        .ok()
+        .and_then(|binary| binary.parent().map(Path::to_path_buf))
+    else {
+        return;
+    };
+    match update_link_at(&link, &install_dir) {
+        Ok(true) => tracing::info!(target = %install_dir.display(), "updated current link"),
+        Ok(false) => {}
+        Err(e) => tracing::debug!("could not update current link: {}", e),
+    }
+}
+
+#[cfg(unix)]
+fn update_link_at(link: &Path, install_dir: &Path) -> Result<bool, GitAiError> {
+    // A directory reached through the link itself would make it point at itself.
+    if install_dir.starts_with(link) {
+        return Ok(false);
+    }
+    if std::fs::read_link(link).is_ok_and(|target| target == install_dir) {
+        return Ok(false);
+    }
+    let Some(parent) = link.parent() else {
+        return Ok(false);
+    };
+    std::fs::create_dir_all(parent)?;
+    // Swap the link in with a rename so hooks never see it missing.
+    let staging = parent.join(format!(".{}.{}", CURRENT_LINK_NAME, std::process::id()));
+    let _ = std::fs::remove_file(&staging);
+    std::os::unix::fs::symlink(install_dir, &staging)?;
+    std::fs::rename(&staging, link)?;
+    Ok(true)
+}
+
+#[cfg(not(unix))]
+fn update_link_at(_link: &Path, _install_dir: &Path) -> Result<bool, GitAiError> {
+    Ok(false)
+}
+
+/// The path hooks should embed for `binary_path`: through the `current` link
+/// when it points at the binary's directory, so upgrades that move the binary
+/// keep hooks working; otherwise `binary_path` itself.
+pub fn stable_binary_path(binary_path: &Path) -> PathBuf {
+    match current_link_path() {
+        Some(link) => stable_binary_path_via(&link, binary_path),
+        None => binary_path.to_path_buf(),
+    }
+}
+
+fn stable_binary_path_via(link: &Path, binary_path: &Path) -> PathBuf {
+    let (Some(install_dir), Some(file_name)) = (binary_path.parent(), binary_path.file_name())
+    else {
+        return binary_path.to_path_buf();
+    };
+    // Already installed at a stable location.
+    if link.parent() == Some(install_dir) {
+        return binary_path.to_path_buf();
+    }
+    let linked = link.canonicalize().ok();
+    let installed = install_dir.canonicalize().ok();
+    if linked.is_some() && linked == installed {
+        link.join(file_name)
+    } else {
+        binary_path.to_path_buf()
+    }
+}
+
+/// Resolve the binary a hook embedding `embedded` will run.
+pub fn resolve_hook_binary(embedded: &Path) -> Option<(PathBuf, HookBinarySource)> {
+    resolve_hook_binary_with(
+        embedded,
+        std::env::var_os("PATH").as_deref(),
+        current_link_path().as_deref(),
+    )
+}
+
+fn resolve_hook_binary_with(
+    embedded: &Path,
+    path_var: Option<&std::ffi::OsStr>,
+    current_link: Option<&Path>,
+) -> Option<(PathBuf, HookBinarySource)> {
+    if embedded.is_file() {
+        return Some((embedded.to_path_buf(), HookBinarySource::Embedded));
+    }
+    let file_name = embedded.file_name()?;
+    if let Some(found) = path_var
+        .into_iter()
+        .flat_map(std::env::split_paths)
+        .map(|dir| dir.join(file_name))
+        .find(|candidate| candidate.is_file())
+    {
+        return Some((found, HookBinarySource::PathLookup));
+    }
+    current_link
+        .map(|link| link.join(file_name))
+        .filter(|candidate| candidate.is_file())
+        .map(|candidate| (candidate, HookBinarySource::CurrentLink))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn resolution_falls_back_from_embedded_to_path_to_current_link() {
+        let temp = tempdir().unwrap();
+        let old_dir = temp.path().join("old");
+        let path_dir = temp.path().join("on-path");
+        let current_dir = temp.path().join("current");
+        for dir in [&old_dir, &path_dir, &current_dir] {
+            fs::create_dir_all(dir).unwrap();
+        }
+        let embedded = old_dir.join("git-ai");
+        fs::write(&embedded, "").unwrap();
+        let path_var = std::env::join_paths([&path_dir]).unwrap();
+
+        let resolved =
+            resolve_hook_binary_with(&embedded, Some(path_var.as_os_str()), Some(&current_dir));
+        assert_eq!(resolved.unwrap().1, HookBinarySource::Embedded);
+
+        // The binary moved away, e.g. a package upgrade removed its directory.
+        fs::remove_file(&embedded).unwrap();
+        fs::write(current_dir.join("git-ai"), "").unwrap();
+        let resolved =
+            resolve_hook_binary_with(&embedded, Some(path_var.as_os_str()), Some(&current_dir));
+        assert_eq!(
+            resolved,
+            Some((current_dir.join("git-ai"), HookBinarySource::CurrentLink))
+        );
+
+        fs::write(path_dir.join("git-ai"), "").unwrap();
+        let resolved =
+            resolve_hook_binary_with(&embedded, Some(path_var.as_os_str()), Some(&current_dir));
+        assert_eq!(resolved.unwrap().1, HookBinarySource::PathLookup);
+
+        fs::remove_file(path_dir.join("git-ai")).unwrap();
+        fs::remove_file(current_dir.join("git-ai")).unwrap();
+        assert_eq!(
+            resolve_hook_binary_with(&embedded, Some(path_var.as_os_str()), Some(&current_dir)),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn stable_path_goes_through_current_link_to_the_installing_binary() {
+        let temp = tempdir().unwrap();
+        let cellar = temp.path().join("Cellar").join("1.0").join("bin");
+        fs::create_dir_all(&cellar).unwrap();
+        let binary = cellar.join("git-ai");
+        fs::write(&binary, "").unwrap();
+        let link = temp
+            .path()
+            .join(".git-ai")
+            .join("bin")
+            .join(CURRENT_LINK_NAME);
+
+        // No link yet: embed the binary itself.
+        assert_eq!(stable_binary_path_via(&link, &binary), binary);
+
+        assert!(update_link_at(&link, &cellar).unwrap());
+        assert!(!update_link_at(&link, &cellar).unwrap());
+        assert_eq!(stable_binary_path_via(&link, &binary), link.join("git-ai"));
+
+        // A binary in the link's own directory is already stable.
+        let installed = link.parent().unwrap().join("git-ai");
+        assert_eq!(stable_binary_path_via(&link, &installed), installed);
+
+        // A binary run through the link never re-points the link at itself.
+        assert!(!update_link_at(&link, &link).unwrap());
+        assert!(!update_link_at(&link, &link.join("bin")).unwrap());
+        assert_eq!(fs::read_link(&link).unwrap(), cellar);
+    }
+}
@@ -0,0 +1,352 @@
+//! Settings enforced by the device's management profile.
+//!
+//! Administrators can push git-ai settings to managed machines without the
+//! server-side policy in [`crate::mdm::policy`]:
+//! - macOS: a configuration profile with a payload for the
+//!   `com.git-ai.git-ai` domain, which the system installs under
+//!   `/Library/Managed Preferences`;
+//! - Windows: Group Policy values under `SOFTWARE\Policies\git-ai` in
+//!   `HKEY_LOCAL_MACHINE` or `HKEY_CURRENT_USER`.
+//!
+//! Keys use the `~/.git-ai/config.json` names. Each managed key overrides the
+//! user's value and is refused by `git-ai config set`/`unset`. The policy keys
+//! the MDM endpoint serves (`require_repositories`,
+//! `exclude_prompts_in_repositories`, `anonymize_repositories`,
+//! `anonymization_key`, `telemetry`) and the `telemetry_*` channel keys are
+//! combined with the fetched organization policy instead. Machine-wide values
+//! win over per-user ones.
+
+use crate::config::{FileConfig, TelemetryChannel};
+use crate::mdm::policy::ManagedPolicy;
+use serde_json::{Map, Value};
+use std::sync::OnceLock;
+
+/// Preference domain of the macOS configuration profile payload.
+pub const MACOS_PREFERENCE_DOMAIN: &str = "com.git-ai.git-ai";
+
+/// Registry key, under HKLM and HKCU, holding Windows policy values.
+pub const WINDOWS_POLICY_KEY: &str = r"SOFTWARE\Policies\git-ai";
+
+/// Keys with the meaning they have in the organization policy.
+const POLICY_KEYS: &[&str] = &[
+    "require_repositories",
+    "exclude_prompts_in_repositories",
+    "anonymize_repositories",
+    "anonymization_key",
+    "telemetry",
+];
+
+static MANAGED_PREFERENCES: OnceLock<ManagedPreferences> = OnceLock::new();
+
+/// Everything the device's management profile enforces.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ManagedPreferences {
+    /// Config file keys and the values enforced for them.
+    pub settings: Map<String, Value>,
+    /// Repository and telemetry policy, merged with the organization's.
+    pub policy: ManagedPolicy,
+}
+
+impl ManagedPreferences {
+    /// Split a profile's key/value pairs into enforced settings and policy.
+    /// Unknown keys and values of the wrong type are dropped with a warning.
+    pub fn from_object(object: Map<String, Value>) -> Self {
+        let mut preferences = ManagedPreferences::default();
+        let mut policy = Map::new();
+        for (key, value) in object {
+            if POLICY_KEYS.contains(&key.as_str()) {
+                policy.insert(key, value);
+            } else if let Some(channel) = telemetry_channel_for_key(&key) {
+                match channel_enabled(&value) {
+                    Some(enabled) => {
+                        *preferences.telemetry_channel_mut(channel) = Some(enabled);
+                    }
+                    None => warn_ignored(&key),
+                }
+            } else if let Some(value) = validated_setting(&key, value) {
+                preferences.settings.insert(key, value);
+            } else {
+                warn_ignored(&key);
+            }
+        }
+
+        // Channels set by key take precedence over a `telemetry` object.
+        let telemetry = preferences.policy.telemetry.clone();
+        match serde_json::from_value::<ManagedPolicy>(Value::Object(policy)) {
+            Ok(parsed) => preferences.policy = parsed,
+            Err(e) => eprintln!("Warning: Ignoring managed repository policy: {}", e),
+        }
+        let merged = &mut preferences.policy.telemetry;
+        merged.metrics = telemetry.metrics.or(merged.metrics);
+        merged.prompts = telemetry.prompts.or(merged.prompts);
+        merged.crash_reports = telemetry.crash_reports.or(merged.crash_reports);
+        preferences
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.settings.is_empty() && self.policy.is_empty()
+    }
+
+    /// True when the profile pins `key` so the user's value is ignored.
+    pub fn enforces(&self, key: &str) -> bool {
+        if self.settings.contains_key(key) {
+            return true;
+        }
+        let telemetry = &self.policy.telemetry;
+        match telemetry_channel_for_key(key) {
+            Some(TelemetryChannel::Metrics) => telemetry.metrics.is_some(),
+            Some(TelemetryChannel::Prompts) => telemetry.prompts.is_some(),
+            Some(TelemetryChannel::CrashReports) => telemetry.crash_reports.is_some(),
+            None => false,
+        }
+    }
+
+    /// Every key [`Self::enforces`], sorted.
+    pub fn enforced_keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.settings.keys().cloned().collect();
+        keys.extend(
+            TelemetryChannel::ALL
+                .iter()
+                .map(|channel| channel.config_key())
+                .filter(|key| self.enforces(key))
+                .map(str::to_string),
+        );
+        keys.sort();
+        keys
+    }
+
+    /// `file_config` with the enforced settings written over it.
+    pub fn apply_to(&self, file_config: FileConfig) -> FileConfig {
+        if self.settings.is_empty() {
+            return file_config;
+        }
+        let Ok(Value::Object(mut merged)) = serde_json::to_value(&file_config) else {
+            return file_config;
+        };
+        merged.extend(self.settings.clone());
+        serde_json::from_value(Value::Object(merged)).unwrap_or(file_config)
+    }
+
+    fn telemetry_channel_mut(&mut self, channel: TelemetryChannel) -> &mut Option<bool> {
+        let telemetry = &mut self.policy.telemetry;
+        match channel {
+            TelemetryChannel::Metrics => &mut telemetry.metrics,
+            TelemetryChannel::Prompts => &mut telemetry.prompts,
+            TelemetryChannel::CrashReports => &mut telemetry.crash_reports,
+        }
+    }
+}
+
+/// The device's managed preferences, read once per process. Empty when no
+/// profile is installed or the platform has none.
+pub fn load_managed_preferences() -> &'static ManagedPreferences {
+    MANAGED_PREFERENCES.get_or_init(|| ManagedPreferences::from_object(read_platform_preferences()))
+}
+
+fn telemetry_channel_for_key(key: &str) -> Option<TelemetryChannel> {
+    TelemetryChannel::ALL
+        .into_iter()
+        .find(|channel| channel.config_key() == key)
+}
+
+/// Channel state from `"on"`/`"off"`, a boolean, or a registry DWORD.
+fn channel_enabled(value: &Value) -> Option<bool> {
+    match value {
+        Value::String(s) if s.trim() == "on" => Some(true),
+        Value::String(s) if s.trim() == "off" => Some(false),
+        other => as_bool(other),
+    }
+}
+
+fn as_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Bool(b) => Some(*b),
+        Value::Number(n) => match n.as_u64() {
+            Some(0) => Some(false),
+            Some(1) => Some(true),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `value` as `FileConfig` stores it under `key`, or `None` when `key` is not
+/// a config key or `value` does not fit it. Registry DWORDs stand in for booleans.
+fn validated_setting(key: &str, value: Value) -> Option<Value> {
+    let candidates = [Some(value.clone()), as_bool(&value).map(Value::Bool)];
+    candidates.into_iter().flatten().find_map(|candidate| {
+        let mut object = Map::new();
+        object.insert(key.to_string(), candidate);
+        let config: FileConfig = serde_json::from_value(Value::Object(object)).ok()?;
+        match serde_json::to_value(&config).ok()? {
+            Value::Object(mut round_trip) => round_trip.remove(key),
+            _ => None,
+        }
+    })
+}
+
+fn warn_ignored(key: &str) {
+    eprintln!(
+        "Warning: Ignoring managed setting '{}': unknown key or invalid value",
+        key
+    );
+}
+
+#[cfg(target_os = "macos")]
+fn read_platform_preferences() -> Map<String, Value> {
+    use std::path::Path;
+    use std::process::Command;
+
+    let managed_dir = Path::new("/Library/Managed Preferences");
+    let file_name = format!("{}.plist", MACOS_PREFERENCE_DOMAIN);
+    let mut paths = Vec::new();
+    if let Ok(user) = std::env::var("USER") {
+        paths.push(managed_dir.join(user).join(&file_name));
+    }
+    paths.push(managed_dir.join(&file_name));
+
+    // Later paths win, so the device-wide profile overrides a per-user one.
+    let mut preferences = Map::new();
+    for path in paths.iter().filter(|path| path.exists()) {
+        let output = Command::new("plutil")
+            .args(["-convert", "json", "-o", "-"])
+            .arg(path)
+            .output();
+        match output {
+            Ok(output) if output.status.success() => {
+                if let Ok(Value::Object(object)) = serde_json::from_slice(&output.stdout) {
+                    preferences.extend(object);
+                }
+            }
+            _ => eprintln!(
+                "Warning: Could not read managed preferences at {}",
+                path.display()
+            ),
+        }
+    }
+    preferences
+}
+
+#[cfg(windows)]
+fn read_platform_preferences() -> Map<String, Value> {
+    use winreg::RegKey;
+    use winreg::enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, KEY_READ};
+
+    // Machine policy wins over user policy, as with Group Policy.
+    let mut preferences = Map::new();
+    for hive in [HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE] {
+        if let Ok(key) = RegKey::predef(hive).open_subkey_with_flags(WINDOWS_POLICY_KEY, KEY_READ) {
+            preferences.extend(read_registry_key(&key));
+        }
+    }
+    preferences
+}
+
+/// Values of `key` as JSON; subkeys become nested objects. `REG_SZ` values
+/// holding a JSON array or object are parsed, so lists fit in one value.
+#[cfg(windows)]
+fn read_registry_key(key: &winreg::RegKey) -> Map<String, Value> {
+    use winreg::enums::{KEY_READ, RegType};
+
+    let mut object = Map::new();
+    for (name, raw) in key.enum_values().flatten() {
+        let value = match raw.vtype {
+            RegType::REG_SZ | RegType::REG_EXPAND_SZ => {
+                key.get_value::<String, _>(&name).ok().map(|s| {
+                    let trimmed = s.trim_start();
+                    if trimmed.starts_with('[') || trimmed.starts_with('{') {
+                        serde_json::from_str(&s).unwrap_or(Value::String(s))
+                    } else {
+                        Value::String(s)
+                    }
+                })
+            }
+            RegType::REG_DWORD => key.get_value::<u32, _>(&name).ok().map(Value::from),
+            RegType::REG_QWORD => key.get_value::<u64, _>(&name).ok().map(Value::from),
+            RegType::REG_MULTI_SZ => key.get_value::<Vec<String>, _>(&name).ok().map(Value::from),
+            _ => None,
+        };
+        if let Some(value) = value {
+            object.insert(name, value);
+        }
+    }
+    for name in key.enum_keys().flatten() {
+        if let Ok(subkey) = key.open_subkey_with_flags(&name, KEY_READ) {
+            object.insert(name, Value::Object(read_registry_key(&subkey)));
+        }
+    }
+    object
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn read_platform_preferences() -> Map<String, Value> {
+    Map::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn preferences(value: Value) -> ManagedPreferences {
+        match value {
+            Value::Object(object) => ManagedPreferences::from_object(object),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_splits_settings_from_policy() {
+        let prefs = preferences(json!({
+            "telemetry_enterprise_dsn": "https://sentry.example.com/1",
+            "disable_auto_updates": 1,
+            "telemetry_prompts": "off",
+            "require_repositories": ["github.com/acme/*"],
+            "not_a_setting": true,
+            "quiet": "loud",
+        }));
+
+        assert_eq!(
+            prefs.settings.get("telemetry_enterprise_dsn"),
+            Some(&json!("https://sentry.example.com/1"))
+        );
+        assert_eq!(
+            prefs.settings.get("disable_auto_updates"),
+            Some(&json!(true))
+        );
+        assert!(!prefs.settings.contains_key("not_a_setting"));
+        assert!(!prefs.settings.contains_key("quiet"));
+        assert_eq!(prefs.policy.telemetry.prompts, Some(false));
+        assert_eq!(prefs.policy.require_repositories, vec!["github.com/acme/*"]);
+        assert_eq!(
+            prefs.enforced_keys(),
+            vec![
+                "disable_auto_updates",
+                "telemetry_enterprise_dsn",
+                "telemetry_prompts"
+            ]
+        );
+        assert!(!prefs.enforces("require_repositories"));
+    }
+
+    #[test]
+    fn test_apply_overrides_user_values() {
+        let prefs = preferences(json!({
+            "api_base_url": "https://git-ai.acme.internal",
+            "prompt_storage": "local",
+        }));
+        let user: FileConfig = serde_json::from_value(json!({
+            "api_base_url": "https://example.com",
+            "quiet": true,
+        }))
+        .unwrap();
+
+        let effective = prefs.apply_to(user);
+        assert_eq!(
+            effective.api_base_url.as_deref(),
+            Some("https://git-ai.acme.internal")
+        );
+        assert_eq!(effective.prompt_storage.as_deref(), Some("local"));
+        assert_eq!(effective.quiet, Some(true));
+    }
+}
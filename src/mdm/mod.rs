@@ -1,6 +1,9 @@
 pub mod agents;
+pub mod hook_binary;
 pub mod hook_installer;
 pub mod jetbrains;
+pub mod managed_preferences;
+pub mod policy;
 pub mod skills_installer;
 pub mod spinner;
 pub mod utils;
@@ -0,0 +1,305 @@
+//! Managed repository policy distributed by an organization's MDM endpoint.
+//!
+//! The policy is fetched periodically (by the daemon) and cached on disk at
+//! `~/.git-ai/internal/managed_policy.json`. Config building only ever reads the
+//! cached copy so that enforcement in checkpoints and hooks never touches the
+//! network.
+
+use crate::api::{ApiClient, ApiContext};
+use crate::error::GitAiError;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Endpoint serving the organization's managed policy.
+const MANAGED_POLICY_ENDPOINT: &str = "/worker/mdm/policy";
+
+/// Default refresh cadence when the server does not specify one.
+const DEFAULT_POLICY_REFRESH_INTERVAL_SECS: u64 = 6 * 3600;
+
+/// Lower bound on the server-provided refresh interval to avoid hammering the endpoint.
+const MIN_POLICY_REFRESH_INTERVAL_SECS: u64 = 300;
+
+/// Repository policy pushed by an organization administrator.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManagedPolicy {
+    /// Repo URL patterns that must always have git-ai enabled. A match overrides
+    /// any local `exclude_repositories` / `allow_repositories` setting.
+    #[serde(default)]
+    pub require_repositories: Vec<String>,
+    /// Repo URL patterns where prompt capture must be disabled. Merged with the
+    /// local `exclude_prompts_in_repositories` list.
+    #[serde(default)]
+    pub exclude_prompts_in_repositories: Vec<String>,
+    /// Telemetry channels forced on or off for every user in the organization.
+    #[serde(default, skip_serializing_if = "ManagedTelemetryPolicy::is_empty")]
+    pub telemetry: ManagedTelemetryPolicy,
+    /// Repo URL patterns whose identities must be anonymized in notes and
+    /// metrics. Merged with the local `anonymize_repositories` list.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub anonymize_repositories: Vec<String>,
+    /// Workspace key anonymized identities are HMAC-hashed with, shared by the
+    /// organization so hashed identities aggregate across machines.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anonymization_key: Option<String>,
+    /// Server-suggested refresh interval in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_interval_secs: Option<u64>,
+}
+
+impl ManagedPolicy {
+    pub fn is_empty(&self) -> bool {
+        self.require_repositories.is_empty()
+            && self.exclude_prompts_in_repositories.is_empty()
+            && self.anonymize_repositories.is_empty()
+            && self.anonymization_key.is_none()
+            && self.telemetry.is_empty()
+    }
+
+    /// Combine with the policy from the device's management profile. Repository
+    /// lists are merged; the device's telemetry channels and anonymization key win.
+    pub fn with_device_policy(mut self, device: &ManagedPolicy) -> Self {
+        self.require_repositories
+            .extend(device.require_repositories.iter().cloned());
+        self.exclude_prompts_in_repositories
+            .extend(device.exclude_prompts_in_repositories.iter().cloned());
+        self.anonymize_repositories
+            .extend(device.anonymize_repositories.iter().cloned());
+        if device.anonymization_key.is_some() {
+            self.anonymization_key = device.anonymization_key.clone();
+        }
+        let telemetry = &mut self.telemetry;
+        telemetry.metrics = device.telemetry.metrics.or(telemetry.metrics);
+        telemetry.prompts = device.telemetry.prompts.or(telemetry.prompts);
+        telemetry.crash_reports = device.telemetry.crash_reports.or(telemetry.crash_reports);
+        self
+    }
+
+    fn refresh_interval(&self) -> Duration {
+        Duration::from_secs(
+            self.refresh_interval_secs
+                .unwrap_or(DEFAULT_POLICY_REFRESH_INTERVAL_SECS)
+                .max(MIN_POLICY_REFRESH_INTERVAL_SECS),
+        )
+    }
+}
+
+/// Per-channel telemetry enforcement. `None` leaves the channel to the local config.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManagedTelemetryPolicy {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompts: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crash_reports: Option<bool>,
+}
+
+impl ManagedTelemetryPolicy {
+    pub fn is_empty(&self) -> bool {
+        self.metrics.is_none() && self.prompts.is_none() && self.crash_reports.is_none()
+    }
+}
+
+/// On-disk cache entry for the managed policy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedManagedPolicy {
+    fetched_at: u64,
+    policy: ManagedPolicy,
+}
+
+/// Result of a policy refresh attempt.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PolicyRefreshOutcome {
+    /// The cached policy is still fresh; no request was made.
+    NotDue,
+    /// No credentials are available, so there is no org policy to fetch.
+    NotAuthenticated,
+    /// The policy was fetched and cached.
+    Updated,
+}
+
+/// Returns the path to the managed policy cache (~/.git-ai/internal/managed_policy.json)
+pub fn managed_policy_path() -> Option<PathBuf> {
+    crate::config::internal_dir_path().map(|dir| dir.join("managed_policy.json"))
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_secs(0))
+        .as_secs()
+}
+
+fn read_cached_policy() -> Option<CachedManagedPolicy> {
+    let path = managed_policy_path()?;
+    let bytes = fs::read(path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+fn write_cached_policy(cached: &CachedManagedPolicy) -> Result<(), GitAiError> {
+    let path = managed_policy_path()
+        .ok_or_else(|| GitAiError::Generic("Could not determine policy path".to_string()))?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_vec_pretty(cached)?;
+    // Write to a temp file and rename so concurrent readers never observe a torn file.
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, json)?;
+    fs::rename(&tmp_path, &path)?;
+    Ok(())
+}
+
+/// Load the cached managed policy, if one has been fetched.
+pub fn load_managed_policy() -> Option<ManagedPolicy> {
+    read_cached_policy().map(|cached| cached.policy)
+}
+
+fn is_refresh_due(cached: Option<&CachedManagedPolicy>, now: u64) -> bool {
+    match cached {
+        Some(cached) => {
+            now.saturating_sub(cached.fetched_at) >= cached.policy.refresh_interval().as_secs()
+        }
+        None => true,
+    }
+}
+
+/// Fetch the managed policy from the server when the cached copy is stale.
+///
+/// Intended for background callers (the daemon's periodic loop); never call this
+/// from checkpoint or hook paths.
+pub fn refresh_managed_policy_if_due() -> Result<PolicyRefreshOutcome, GitAiError> {
+    if !is_refresh_due(read_cached_policy().as_ref(), current_timestamp()) {
+        return Ok(PolicyRefreshOutcome::NotDue);
+    }
+    refresh_managed_policy()
+}
+
+/// Fetch the managed policy from the server unconditionally and cache it.
+pub fn refresh_managed_policy() -> Result<PolicyRefreshOutcome, GitAiError> {
+    let client = ApiClient::new(ApiContext::new(None));
+    if !client.is_logged_in() && !client.has_api_key() {
+        return Ok(PolicyRefreshOutcome::NotAuthenticated);
+    }
+
+    let policy = client.fetch_managed_policy()?;
+    write_cached_policy(&CachedManagedPolicy {
+        fetched_at: current_timestamp(),
+        policy,
+    })?;
+    Ok(PolicyRefreshOutcome::Updated)
+}
+
+/// Managed policy API endpoints
+impl ApiClient {
+    /// Fetch the organization's managed repository policy.
+    ///
+    /// A 404 means the organization has not configured a policy, which is
+    /// treated as an empty policy so stale restrictions are cleared.
+    pub fn fetch_managed_policy(&self) -> Result<ManagedPolicy, GitAiError> {
+        let response = self.context().get(MANAGED_POLICY_ENDPOINT)?;
+        match response.status_code {
+            200 => {
+                let body = response.as_str().map_err(|e| {
+                    GitAiError::Generic(format!("Failed to read response body: {}", e))
+                })?;
+                serde_json::from_str(body).map_err(GitAiError::JsonError)
+            }
+            404 => Ok(ManagedPolicy::default()),
+            401 => Err(GitAiError::Auth("Unauthorized".to_string())),
+            status => Err(GitAiError::Generic(format!(
+                "Managed policy request failed with status {}",
+                status
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_deserializes_with_missing_fields() {
+        let policy: ManagedPolicy =
+            serde_json::from_str(r#"{"require_repositories":["github.com/acme/*"]}"#).unwrap();
+        assert_eq!(policy.require_repositories, vec!["github.com/acme/*"]);
+        assert!(policy.exclude_prompts_in_repositories.is_empty());
+        assert!(!policy.is_empty());
+        assert!(policy.telemetry.is_empty());
+    }
+
+    #[test]
+    fn test_policy_telemetry_only_is_not_empty() {
+        let policy: ManagedPolicy =
+            serde_json::from_str(r#"{"telemetry":{"prompts":false}}"#).unwrap();
+        assert_eq!(policy.telemetry.prompts, Some(false));
+        assert_eq!(policy.telemetry.metrics, None);
+        assert!(!policy.is_empty());
+    }
+
+    #[test]
+    fn test_device_policy_merges_lists_and_wins_telemetry() {
+        let org = ManagedPolicy {
+            require_repositories: vec!["github.com/acme/*".to_string()],
+            telemetry: ManagedTelemetryPolicy {
+                metrics: Some(true),
+                prompts: Some(true),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let device = ManagedPolicy {
+            require_repositories: vec!["github.com/acme-labs/*".to_string()],
+            telemetry: ManagedTelemetryPolicy {
+                prompts: Some(false),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let merged = org.with_device_policy(&device);
+        assert_eq!(
+            merged.require_repositories,
+            vec!["github.com/acme/*", "github.com/acme-labs/*"]
+        );
+        assert_eq!(merged.telemetry.metrics, Some(true));
+        assert_eq!(merged.telemetry.prompts, Some(false));
+    }
+
+    #[test]
+    fn test_refresh_due_without_cache() {
+        assert!(is_refresh_due(None, 1_000));
+    }
+
+    #[test]
+    fn test_refresh_respects_default_interval() {
+        let cached = CachedManagedPolicy {
+            fetched_at: 1_000,
+            policy: ManagedPolicy::default(),
+        };
+        assert!(!is_refresh_due(Some(&cached), 1_000 + 60));
+        assert!(is_refresh_due(
+            Some(&cached),
+            1_000 + DEFAULT_POLICY_REFRESH_INTERVAL_SECS
+        ));
+    }
+
+    #[test]
+    fn test_refresh_interval_is_clamped_to_minimum() {
+        let cached = CachedManagedPolicy {
+            fetched_at: 1_000,
+            policy: ManagedPolicy {
+                refresh_interval_secs: Some(1),
+                ..Default::default()
+            },
+        };
+        assert!(!is_refresh_due(Some(&cached), 1_010));
+        assert!(is_refresh_due(
+            Some(&cached),
+            1_000 + MIN_POLICY_REFRESH_INTERVAL_SECS
+        ));
+    }
+}
@@ -1,12 +1,22 @@
+use crate::commands::output::OutputOptions;
 use indicatif::{ProgressBar, ProgressStyle};
 
-/// Spinner UI component for showing progress
+/// Spinner UI component for showing progress. Hidden, along with its final
+/// status line, when the command runs with `--quiet` or `--json`.
 pub struct Spinner {
     pb: ProgressBar,
+    quiet: bool,
 }
 
 impl Spinner {
     pub fn new(message: &str) -> Self {
+        let quiet = OutputOptions::current().is_quiet();
+        if quiet {
+            return Self {
+                pb: ProgressBar::hidden(),
+                quiet,
+            };
+        }
         let pb = ProgressBar::new_spinner();
         pb.set_style(
             ProgressStyle::default_spinner()
@@ -17,7 +27,7 @@ impl Spinner {
         pb.set_message(message.to_string());
         pb.enable_steady_tick(std::time::Duration::from_millis(100));
 
-        Self { pb }
+        Self { pb, quiet }
     }
 
     pub fn start(&self) {
@@ -41,31 +51,42 @@ impl Spinner {
     pub fn success(&self, message: &str) {
         // Clear spinner and show success with green checkmark and bold green text
         self.pb.finish_and_clear();
-        println!("\x1b[1;32m✓ {}\x1b[0m", message);
+        if !self.quiet {
+            println!("\x1b[1;32m✓ {}\x1b[0m", message);
+        }
     }
 
     pub fn pending(&self, message: &str) {
         // Clear spinner and show pending with yellow warning triangle and bold yellow text
         self.pb.finish_and_clear();
-        println!("\x1b[1;33m⚠ {}\x1b[0m", message);
+        if !self.quiet {
+            println!("\x1b[1;33m⚠ {}\x1b[0m", message);
+        }
     }
 
     pub fn error(&self, message: &str) {
         // Clear spinner and show error with red X and bold red text
         self.pb.finish_and_clear();
-        println!("\x1b[1;31m✗ {}\x1b[0m", message);
+        if !self.quiet {
+            println!("\x1b[1;31m✗ {}\x1b[0m", message);
+        }
     }
 
     #[allow(dead_code)]
     pub fn skipped(&self, message: &str) {
         // Clear spinner and show skipped with gray circle and gray text
         self.pb.finish_and_clear();
-        println!("\x1b[90m○ {}\x1b[0m", message);
+        if !self.quiet {
+            println!("\x1b[90m○ {}\x1b[0m", message);
+        }
     }
 }
 
 /// Print a formatted diff using colors
 pub fn print_diff(diff_text: &str) {
+    if OutputOptions::current().is_quiet() {
+        return;
+    }
     // Print a formatted diff using colors
     for line in diff_text.lines() {
         if line.starts_with("+++") || line.starts_with("---") {
@@ -11,6 +11,8 @@ pub mod attr_pos {
     pub const COMMIT_SHA: usize = 3;
     pub const BASE_COMMIT_SHA: usize = 4;
     pub const BRANCH: usize = 5;
+    pub const IDENTITY_SUBJECT: usize = 6;
+    pub const IDENTITY_EMAIL: usize = 7;
     pub const TOOL: usize = 20;
     pub const MODEL: usize = 21;
     // Position 22 (PROMPT_ID): TOMBSTONED - never reuse this index
@@ -20,6 +22,7 @@ pub mod attr_pos {
     pub const TRACE_ID: usize = 25;
     pub const PARENT_SESSION_ID: usize = 26;
     pub const EXTERNAL_PARENT_SESSION_ID: usize = 27;
+    pub const TOOL_VERSION: usize = 28;
     pub const CUSTOM_ATTRIBUTES: usize = 30;
 }
 
@@ -33,6 +36,8 @@ pub mod attr_pos {
 /// | 3 | commit_sha | String | No (nullable) |
 /// | 4 | base_commit_sha | String | No (nullable) |
 /// | 5 | branch | String | No (nullable) |
+/// | 6 | identity_subject | String | No (nullable) |
+/// | 7 | identity_email | String | No (nullable) |
 /// | 20 | tool | String | No (nullable) |
 /// | 21 | model | String | No (nullable) |
 /// | 22 | prompt_id (TOMBSTONED) | String | No (nullable) |
@@ -41,6 +46,7 @@ pub mod attr_pos {
 /// | 25 | trace_id | String | No (nullable) |
 /// | 26 | parent_session_id | String | No (nullable) |
 /// | 27 | external_parent_session_id | String | No (nullable) |
+/// | 28 | tool_version | String | No (nullable) |
 /// | 30 | custom_attributes | String (JSON) | No (nullable) |
 #[derive(Debug, Clone, Default)]
 pub struct EventAttributes {
@@ -50,6 +56,8 @@ pub struct EventAttributes {
     pub commit_sha: PosField<String>,
     pub base_commit_sha: PosField<String>,
     pub branch: PosField<String>,
+    pub identity_subject: PosField<String>,
+    pub identity_email: PosField<String>,
     pub tool: PosField<String>,
     pub model: PosField<String>,
     pub prompt_id: PosField<String>,
@@ -58,6 +66,7 @@ pub struct EventAttributes {
     pub parent_session_id: PosField<String>,
     pub external_session_id: PosField<String>,
     pub external_parent_session_id: PosField<String>,
+    pub tool_version: PosField<String>,
     pub custom_attributes: PosField<String>,
 }
 
@@ -148,6 +157,76 @@ impl EventAttributes {
         self
     }
 
+    // Builder methods for identity_subject
+    pub fn identity_subject(mut self, value: impl Into<String>) -> Self {
+        self.identity_subject = Some(Some(value.into()));
+        self
+    }
+
+    // Builder methods for identity_email
+    pub fn identity_email(mut self, value: impl Into<String>) -> Self {
+        self.identity_email = Some(Some(value.into()));
+        self
+    }
+
+    /// Tag the event with the org-verified identity when the user is logged in,
+    /// otherwise with the email from the git `author` attribute.
+    ///
+    /// Leaves attributes untouched if an identity has already been set.
+    pub fn resolved_identity(
+        mut self,
+        org_identity: Option<&crate::auth::identity::OrgIdentity>,
+    ) -> Self {
+        if self.identity_subject.is_some() || self.identity_email.is_some() {
+            return self;
+        }
+
+        if let Some(identity) = org_identity {
+            self = self.identity_subject(identity.subject.clone());
+            if let Some(email) = &identity.email {
+                self = self.identity_email(email.clone());
+            }
+            return self;
+        }
+
+        let git_email = self
+            .author
+            .as_ref()
+            .and_then(|author| author.as_deref())
+            .and_then(crate::auth::identity::email_from_git_identity)
+            .map(str::to_string);
+        match git_email {
+            Some(email) => self.identity_email(email),
+            None => self,
+        }
+    }
+
+    /// [`Self::resolved_identity`] using the current credentials.
+    pub fn with_current_identity(self) -> Self {
+        let org_identity = crate::auth::identity::current_org_identity();
+        self.resolved_identity(org_identity.as_ref())
+    }
+
+    /// Replace the identities and session ids with their anonymized form (see
+    /// [`crate::authorship::anonymize`]).
+    pub fn anonymized(mut self, key: &[u8]) -> Self {
+        for field in [
+            &mut self.author,
+            &mut self.identity_subject,
+            &mut self.identity_email,
+            &mut self.prompt_id,
+            &mut self.session_id,
+            &mut self.parent_session_id,
+            &mut self.external_session_id,
+            &mut self.external_parent_session_id,
+        ] {
+            if let Some(Some(value)) = field {
+                *value = crate::authorship::anonymize::anonymize_value(key, value);
+            }
+        }
+        self
+    }
+
     // Builder methods for tool
     pub fn tool(mut self, value: impl Into<String>) -> Self {
         self.tool = Some(Some(value.into()));
@@ -249,6 +328,25 @@ impl EventAttributes {
         }
     }
 
+    // Builder methods for tool_version
+    pub fn tool_version(mut self, value: impl Into<String>) -> Self {
+        self.tool_version = Some(Some(value.into()));
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn tool_version_null(mut self) -> Self {
+        self.tool_version = Some(None);
+        self
+    }
+
+    pub fn tool_version_opt(self, value: Option<String>) -> Self {
+        match value {
+            Some(v) => self.tool_version(v),
+            None => self,
+        }
+    }
+
     // Builder methods for custom_attributes
     pub fn custom_attributes(mut self, value: impl Into<String>) -> Self {
         self.custom_attributes = Some(Some(value.into()));
@@ -294,6 +392,16 @@ impl PosEncoded for EventAttributes {
             string_to_json(&self.base_commit_sha),
         );
         sparse_set(&mut map, attr_pos::BRANCH, string_to_json(&self.branch));
+        sparse_set(
+            &mut map,
+            attr_pos::IDENTITY_SUBJECT,
+            string_to_json(&self.identity_subject),
+        );
+        sparse_set(
+            &mut map,
+            attr_pos::IDENTITY_EMAIL,
+            string_to_json(&self.identity_email),
+        );
         sparse_set(&mut map, attr_pos::TOOL, string_to_json(&self.tool));
         sparse_set(&mut map, attr_pos::MODEL, string_to_json(&self.model));
         // Position 22 (PROMPT_ID) is TOMBSTONED - no longer written, only read for legacy data
@@ -318,6 +426,11 @@ impl PosEncoded for EventAttributes {
             attr_pos::EXTERNAL_PARENT_SESSION_ID,
             string_to_json(&self.external_parent_session_id),
         );
+        sparse_set(
+            &mut map,
+            attr_pos::TOOL_VERSION,
+            string_to_json(&self.tool_version),
+        );
         sparse_set(
             &mut map,
             attr_pos::CUSTOM_ATTRIBUTES,
@@ -334,6 +447,8 @@ impl PosEncoded for EventAttributes {
             commit_sha: sparse_get_string(arr, attr_pos::COMMIT_SHA),
             base_commit_sha: sparse_get_string(arr, attr_pos::BASE_COMMIT_SHA),
             branch: sparse_get_string(arr, attr_pos::BRANCH),
+            identity_subject: sparse_get_string(arr, attr_pos::IDENTITY_SUBJECT),
+            identity_email: sparse_get_string(arr, attr_pos::IDENTITY_EMAIL),
             tool: sparse_get_string(arr, attr_pos::TOOL),
             model: sparse_get_string(arr, attr_pos::MODEL),
             prompt_id: sparse_get_string(arr, attr_pos::PROMPT_ID),
@@ -345,6 +460,7 @@ impl PosEncoded for EventAttributes {
                 arr,
                 attr_pos::EXTERNAL_PARENT_SESSION_ID,
             ),
+            tool_version: sparse_get_string(arr, attr_pos::TOOL_VERSION),
             custom_attributes: sparse_get_string(arr, attr_pos::CUSTOM_ATTRIBUTES),
         }
     }
@@ -590,6 +706,8 @@ mod tests {
         assert_eq!(COMMIT_SHA, 3);
         assert_eq!(BASE_COMMIT_SHA, 4);
         assert_eq!(BRANCH, 5);
+        assert_eq!(IDENTITY_SUBJECT, 6);
+        assert_eq!(IDENTITY_EMAIL, 7);
         assert_eq!(TOOL, 20);
         assert_eq!(MODEL, 21);
         assert_eq!(PROMPT_ID, 22);
@@ -598,6 +716,44 @@ mod tests {
         assert_eq!(TRACE_ID, 25);
     }
 
+    #[test]
+    fn test_resolved_identity_prefers_org_identity() {
+        let org = crate::auth::identity::OrgIdentity {
+            subject: "u123".to_string(),
+            email: Some("jane@corp.example".to_string()),
+        };
+        let attrs = EventAttributes::with_version("1.0.0")
+            .author("Jane <jane.personal@example.com>")
+            .resolved_identity(Some(&org));
+        assert_eq!(attrs.identity_subject, Some(Some("u123".to_string())));
+        assert_eq!(
+            attrs.identity_email,
+            Some(Some("jane@corp.example".to_string()))
+        );
+
+        let sparse = attrs.to_sparse();
+        assert_eq!(sparse.get("6"), Some(&Value::String("u123".to_string())));
+        assert_eq!(
+            sparse.get("7"),
+            Some(&Value::String("jane@corp.example".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_resolved_identity_falls_back_to_git_author() {
+        let attrs = EventAttributes::with_version("1.0.0")
+            .author("Jane <jane.personal@example.com>")
+            .resolved_identity(None);
+        assert_eq!(attrs.identity_subject, None);
+        assert_eq!(
+            attrs.identity_email,
+            Some(Some("jane.personal@example.com".to_string()))
+        );
+
+        let attrs = EventAttributes::with_version("1.0.0").resolved_identity(None);
+        assert_eq!(attrs.identity_email, None);
+    }
+
     #[test]
     fn test_event_attributes_session_id_builder() {
         let attrs = EventAttributes::with_version("1.0.0")
@@ -726,6 +882,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_event_attributes_anonymized_hashes_identities_only() {
+        let attrs = EventAttributes::with_version("1.0.0")
+            .author("Dev <dev@example.com>")
+            .identity_email("dev@example.com")
+            .external_session_id("session-uuid")
+            .tool("cursor")
+            .anonymized(b"workspace-key");
+
+        let author = attrs.author.clone().flatten().unwrap();
+        assert!(author.starts_with(crate::authorship::anonymize::ANONYMIZED_PREFIX));
+        assert_ne!(
+            attrs.identity_email,
+            Some(Some("dev@example.com".to_string()))
+        );
+        assert_ne!(
+            attrs.external_session_id,
+            Some(Some("session-uuid".to_string()))
+        );
+        assert_eq!(attrs.tool, Some(Some("cursor".to_string())));
+        assert_eq!(attrs.identity_subject, None);
+    }
+
     #[test]
     fn test_event_attributes_external_session_id_opt() {
         let attrs = EventAttributes::with_version("1.0.0")
@@ -738,4 +917,20 @@ mod tests {
         );
         assert_eq!(attrs.external_parent_session_id, None);
     }
+
+    #[test]
+    fn test_event_attributes_tool_version_round_trip() {
+        let attrs = EventAttributes::with_version("1.0.0")
+            .tool("claude")
+            .tool_version("2.0.14");
+
+        let sparse = attrs.to_sparse();
+        assert_eq!(sparse.get("28"), Some(&Value::String("2.0.14".to_string())));
+
+        let restored = EventAttributes::from_sparse(&sparse);
+        assert_eq!(restored.tool_version, Some(Some("2.0.14".to_string())));
+
+        let unset = EventAttributes::with_version("1.0.0").tool_version_opt(None);
+        assert!(!unset.to_sparse().contains_key("28"));
+    }
 }
@@ -9,13 +9,15 @@ use crate::metrics::attrs::attr_pos;
 use crate::metrics::events::{checkpoint_pos, otel_trace_pos, session_event_pos};
 use crate::metrics::pos_encoded::sparse_get_string;
 use crate::metrics::types::{MetricEvent, MetricEventId};
+use crate::utils::LockFile;
 use rusqlite::{Connection, OptionalExtension, params, params_from_iter};
 use serde_json::{Map, Value};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Mutex, OnceLock};
 
 /// Current schema version (must match MIGRATIONS.len())
-const SCHEMA_VERSION: usize = 5;
+const SCHEMA_VERSION: usize = 7;
 
 // This value is part of the metrics retry index schema. Changing it requires a
 // migration that rebuilds `metrics_retryable` with the same literal used by
@@ -24,6 +26,8 @@ const SCHEMA_VERSION: usize = 5;
 const MAX_METRIC_UPLOAD_ATTEMPTS: u32 = 6;
 const METRIC_PROCESSING_LOCK_TIMEOUT_SECS: u64 = 10 * 60;
 pub(crate) const METADATA_BACKFILL_BATCH_SIZE: usize = 1000;
+// Stays well under SQLite's default bound-parameter limit.
+const STATS_ROLLUP_LOOKUP_BATCH_SIZE: usize = 500;
 const NS_PER_SECOND: u128 = 1_000_000_000;
 
 const RETRYABLE_METRIC_IDS_SQL: &str = "SELECT id FROM metrics \
@@ -89,6 +93,31 @@ const MIGRATIONS: &[&str] = &[
 
     DROP INDEX IF EXISTS metrics_pending_retry;
     "#,
+    // Migration 5 -> 6: Materialized per-commit stats rollups for range queries.
+    r#"
+    CREATE TABLE IF NOT EXISTS stats_rollups (
+        commit_sha TEXT NOT NULL,
+        ignore_key TEXT NOT NULL,
+        note_oid TEXT,
+        ai_lines INTEGER NOT NULL,
+        human_lines INTEGER NOT NULL,
+        mixed_lines INTEGER NOT NULL,
+        stats_json TEXT NOT NULL,
+        computed_at INTEGER NOT NULL,
+        PRIMARY KEY (commit_sha, ignore_key)
+    );
+    "#,
+    // Migration 6 -> 7: Per-repository hook latency budget state.
+    r#"
+    CREATE TABLE IF NOT EXISTS hook_budget (
+        repo_key TEXT PRIMARY KEY,
+        over_budget_streak INTEGER NOT NULL,
+        last_hook TEXT NOT NULL,
+        last_duration_ms INTEGER NOT NULL,
+        degraded_since INTEGER,
+        updated_at INTEGER NOT NULL
+    );
+    "#,
 ];
 
 /// Global database singleton
@@ -125,6 +154,36 @@ pub(crate) struct SessionEventRecoveryCandidate {
     pub repo_url: Option<String>,
 }
 
+/// Materialized stats for one commit under one set of ignore patterns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatsRollupRecord {
+    pub commit_sha: String,
+    pub ignore_key: String,
+    /// Blob oid of the authorship note the rollup was computed from, `None` when
+    /// the commit had no note. A different oid on read means the note changed.
+    pub note_oid: Option<String>,
+    pub ai_lines: u32,
+    pub human_lines: u32,
+    pub mixed_lines: u32,
+    /// Serialized `CommitStats`, which carries the per tool/model vector.
+    pub stats_json: String,
+    pub computed_at: u64,
+}
+
+/// Hook latency budget state for one repository, see
+/// [`crate::daemon::hook_budget`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookBudgetRecord {
+    pub repo_key: String,
+    /// Consecutive hook runs over `max_hook_latency_ms`.
+    pub over_budget_streak: u32,
+    pub last_hook: String,
+    pub last_duration_ms: u64,
+    /// Unix seconds when the repository switched to degraded mode.
+    pub degraded_since: Option<u64>,
+    pub updated_at: u64,
+}
+
 /// Point-in-time status summary for local metric delivery.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MetricsStatus {
@@ -283,6 +342,27 @@ impl MetricsDatabase {
         Ok(home.join(".git-ai").join("internal").join("metrics-db"))
     }
 
+    /// Try to take the per-user lock that serializes metric uploads across processes.
+    ///
+    /// Returns `Ok(None)` when another process (the daemon or a manual
+    /// `flush-metrics-db`) is already uploading. Row-level processing locks only
+    /// protect a dequeued batch for `METRIC_PROCESSING_LOCK_TIMEOUT_SECS`, so a
+    /// slow upload could otherwise be re-dequeued and sent twice.
+    pub fn try_acquire_flush_lock() -> Result<Option<LockFile>, GitAiError> {
+        let lock_path = Self::flush_lock_path(&Self::database_path()?);
+        if let Some(parent) = lock_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(LockFile::try_acquire(&lock_path))
+    }
+
+    /// Lock file path next to the database: `<db path>.flush.lock`
+    fn flush_lock_path(db_path: &std::path::Path) -> PathBuf {
+        let mut file_name = db_path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".flush.lock");
+        db_path.with_file_name(file_name)
+    }
+
     /// Initialize schema and handle migrations
     fn initialize_schema(&mut self) -> Result<(), GitAiError> {
         // FAST PATH: Check if database is already at current version
@@ -1299,6 +1379,158 @@ impl MetricsDatabase {
         Ok((summary, last_id))
     }
 
+    /// Insert or replace per-commit stats rollups.
+    pub fn upsert_stats_rollups(
+        &mut self,
+        records: &[StatsRollupRecord],
+    ) -> Result<(), GitAiError> {
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        let tx = self.conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                r#"
+                INSERT INTO stats_rollups (
+                    commit_sha, ignore_key, note_oid, ai_lines, human_lines,
+                    mixed_lines, stats_json, computed_at
+                )
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                ON CONFLICT(commit_sha, ignore_key) DO UPDATE SET
+                    note_oid = excluded.note_oid,
+                    ai_lines = excluded.ai_lines,
+                    human_lines = excluded.human_lines,
+                    mixed_lines = excluded.mixed_lines,
+                    stats_json = excluded.stats_json,
+                    computed_at = excluded.computed_at
+                "#,
+            )?;
+            for record in records {
+                stmt.execute(params![
+                    record.commit_sha,
+                    record.ignore_key,
+                    record.note_oid,
+                    record.ai_lines as i64,
+                    record.human_lines as i64,
+                    record.mixed_lines as i64,
+                    record.stats_json,
+                    record.computed_at as i64,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Look up stored rollups for `commit_shas` computed with `ignore_key`.
+    ///
+    /// Commits without a stored rollup are absent from the returned map; callers
+    /// decide whether a stored row is still current by comparing `note_oid`.
+    pub fn get_stats_rollups(
+        &self,
+        ignore_key: &str,
+        commit_shas: &[String],
+    ) -> Result<HashMap<String, StatsRollupRecord>, GitAiError> {
+        let mut rollups = HashMap::new();
+        for chunk in commit_shas.chunks(STATS_ROLLUP_LOOKUP_BATCH_SIZE) {
+            let placeholders = (0..chunk.len())
+                .map(|i| format!("?{}", i + 2))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let sql = format!(
+                "SELECT commit_sha, ignore_key, note_oid, ai_lines, human_lines, mixed_lines, \
+                 stats_json, computed_at FROM stats_rollups \
+                 WHERE ignore_key = ?1 AND commit_sha IN ({placeholders})"
+            );
+
+            let mut values = Vec::with_capacity(chunk.len() + 1);
+            values.push(rusqlite::types::Value::Text(ignore_key.to_string()));
+            values.extend(
+                chunk
+                    .iter()
+                    .map(|sha| rusqlite::types::Value::Text(sha.clone())),
+            );
+
+            let mut stmt = self.conn.prepare(&sql)?;
+            let rows = stmt.query_map(params_from_iter(values.iter()), |row| {
+                Ok(StatsRollupRecord {
+                    commit_sha: row.get(0)?,
+                    ignore_key: row.get(1)?,
+                    note_oid: row.get(2)?,
+                    ai_lines: row.get::<_, i64>(3)?.max(0) as u32,
+                    human_lines: row.get::<_, i64>(4)?.max(0) as u32,
+                    mixed_lines: row.get::<_, i64>(5)?.max(0) as u32,
+                    stats_json: row.get(6)?,
+                    computed_at: row.get::<_, i64>(7)?.max(0) as u64,
+                })
+            })?;
+            for row in rows {
+                let record = row?;
+                rollups.insert(record.commit_sha.clone(), record);
+            }
+        }
+        Ok(rollups)
+    }
+
+    /// Insert or replace the hook budget state of `record.repo_key`.
+    pub fn upsert_hook_budget(&mut self, record: &HookBudgetRecord) -> Result<(), GitAiError> {
+        self.conn.execute(
+            r#"
+            INSERT INTO hook_budget (
+                repo_key, over_budget_streak, last_hook, last_duration_ms,
+                degraded_since, updated_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT(repo_key) DO UPDATE SET
+                over_budget_streak = excluded.over_budget_streak,
+                last_hook = excluded.last_hook,
+                last_duration_ms = excluded.last_duration_ms,
+                degraded_since = excluded.degraded_since,
+                updated_at = excluded.updated_at
+            "#,
+            params![
+                record.repo_key,
+                record.over_budget_streak as i64,
+                record.last_hook,
+                record.last_duration_ms as i64,
+                record.degraded_since.map(|ts| ts as i64),
+                record.updated_at as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_hook_budget(&self, repo_key: &str) -> Result<Option<HookBudgetRecord>, GitAiError> {
+        let record = self
+            .conn
+            .query_row(
+                "SELECT repo_key, over_budget_streak, last_hook, last_duration_ms, \
+                 degraded_since, updated_at FROM hook_budget WHERE repo_key = ?1",
+                params![repo_key],
+                |row| {
+                    Ok(HookBudgetRecord {
+                        repo_key: row.get(0)?,
+                        over_budget_streak: row.get::<_, i64>(1)?.max(0) as u32,
+                        last_hook: row.get(2)?,
+                        last_duration_ms: row.get::<_, i64>(3)?.max(0) as u64,
+                        degraded_since: row.get::<_, Option<i64>>(4)?.map(|ts| ts.max(0) as u64),
+                        updated_at: row.get::<_, i64>(5)?.max(0) as u64,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(record)
+    }
+
+    pub fn clear_hook_budget(&mut self, repo_key: &str) -> Result<(), GitAiError> {
+        self.conn.execute(
+            "DELETE FROM hook_budget WHERE repo_key = ?1",
+            params![repo_key],
+        )?;
+        Ok(())
+    }
+
     /// Returns whether an `agent_usage` event should be emitted for this prompt_id.
     ///
     /// If emitted, this method also updates the prompt's last-sent timestamp.
@@ -1522,6 +1754,15 @@ mod tests {
         (db, temp_dir)
     }
 
+    #[test]
+    fn test_flush_lock_path_sits_next_to_database() {
+        let db_path = PathBuf::from("/home/user/.git-ai/internal/metrics-db");
+        assert_eq!(
+            MetricsDatabase::flush_lock_path(&db_path),
+            PathBuf::from("/home/user/.git-ai/internal/metrics-db.flush.lock")
+        );
+    }
+
     fn unix_now() -> u64 {
         std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -1672,7 +1913,7 @@ mod tests {
                 |row| row.get(0),
             )
             .unwrap();
-        assert_eq!(version, "5");
+        assert_eq!(version, "7");
 
         for column in [
             "delivered_ts",
@@ -1768,7 +2009,7 @@ mod tests {
                 |row| row.get(0),
             )
             .unwrap();
-        assert_eq!(version, "5");
+        assert_eq!(version, "7");
     }
 
     #[test]
@@ -1807,7 +2048,7 @@ mod tests {
                 |row| row.get(0),
             )
             .unwrap();
-        assert_eq!(version, "5");
+        assert_eq!(version, "7");
         assert_eq!(db.count().unwrap(), 1);
         assert_eq!(db.count_retryable().unwrap(), 1);
     }
@@ -1850,7 +2091,7 @@ mod tests {
                 |row| row.get(0),
             )
             .unwrap();
-        assert_eq!(version, "5");
+        assert_eq!(version, "7");
 
         for column in [
             "delivered_ts",
@@ -1919,7 +2160,7 @@ mod tests {
                 |row| row.get(0),
             )
             .unwrap();
-        assert_eq!(version, "5");
+        assert_eq!(version, "7");
         assert!(db.column_exists("metrics", "event_ts").unwrap());
         assert!(db.column_exists("metrics", "event_kind").unwrap());
         for index in [
@@ -1978,13 +2219,69 @@ mod tests {
                 |row| row.get(0),
             )
             .unwrap();
-        assert_eq!(version, "5");
+        assert_eq!(version, "7");
         assert_metric_index_exists(&db, "metrics_retryable");
         assert_metric_index_missing(&db, "metrics_pending_retry");
         assert_eq!(db.count().unwrap(), 1);
         assert_eq!(db.status().unwrap().stopped_after_errors, 1);
     }
 
+    fn stats_rollup(commit_sha: &str, note_oid: Option<&str>, ai_lines: u32) -> StatsRollupRecord {
+        StatsRollupRecord {
+            commit_sha: commit_sha.to_string(),
+            ignore_key: "default".to_string(),
+            note_oid: note_oid.map(str::to_string),
+            ai_lines,
+            human_lines: 3,
+            mixed_lines: 0,
+            stats_json: "{}".to_string(),
+            computed_at: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn test_stats_rollups_upsert_replaces_and_lookup_filters_by_ignore_key() {
+        let (mut db, _temp_dir) = create_test_db();
+        db.upsert_stats_rollups(&[
+            stats_rollup("aaa", Some("note-1"), 5),
+            stats_rollup("bbb", None, 0),
+        ])
+        .unwrap();
+        db.upsert_stats_rollups(&[stats_rollup("aaa", Some("note-2"), 7)])
+            .unwrap();
+
+        let shas = vec!["aaa".to_string(), "bbb".to_string(), "ccc".to_string()];
+        let rollups = db.get_stats_rollups("default", &shas).unwrap();
+        assert_eq!(rollups.len(), 2);
+        assert_eq!(rollups["aaa"], stats_rollup("aaa", Some("note-2"), 7));
+        assert_eq!(rollups["bbb"].note_oid, None);
+
+        assert!(db.get_stats_rollups("other", &shas).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_hook_budget_upsert_get_and_clear() {
+        let (mut db, _temp_dir) = create_test_db();
+        assert_eq!(db.get_hook_budget("/repo/.git").unwrap(), None);
+
+        let mut record = HookBudgetRecord {
+            repo_key: "/repo/.git".to_string(),
+            over_budget_streak: 1,
+            last_hook: "post_commit".to_string(),
+            last_duration_ms: 900,
+            degraded_since: None,
+            updated_at: 1_700_000_000,
+        };
+        db.upsert_hook_budget(&record).unwrap();
+        record.over_budget_streak = 3;
+        record.degraded_since = Some(1_700_000_100);
+        db.upsert_hook_budget(&record).unwrap();
+        assert_eq!(db.get_hook_budget("/repo/.git").unwrap(), Some(record));
+
+        db.clear_hook_budget("/repo/.git").unwrap();
+        assert_eq!(db.get_hook_budget("/repo/.git").unwrap(), None);
+    }
+
     #[test]
     fn test_insert_events() {
         let (mut db, _temp_dir) = create_test_db();
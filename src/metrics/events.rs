@@ -781,7 +781,7 @@ impl EventValues for InstallHooksValues {
 /// One event per file in the checkpoint.
 pub mod checkpoint_pos {
     pub const CHECKPOINT_TS: usize = 0; // u64 - checkpoint timestamp
-    pub const KIND: usize = 1; // String ("human", "ai_agent", "ai_tab")
+    pub const KIND: usize = 1; // String ("human", "ai_agent", "ai_tab", "ai_pasted")
     pub const FILE_PATH: usize = 2; // String - full relative file path
     pub const LINES_ADDED: usize = 3; // u32 - for this file
     pub const LINES_DELETED: usize = 4; // u32 - for this file
@@ -791,6 +791,7 @@ pub mod checkpoint_pos {
     pub const EDIT_KIND: usize = 8; // String - nullable ("file_edit" | "bash")
     pub const CHECKPOINT_TYPE: usize = 9; // String - nullable ("recovered_bash", etc.)
     pub const ATTRIBUTION_RECOVERY_METADATA: usize = 10; // String - nullable JSON
+    pub const PASTE_CONFIDENCE: usize = 11; // String - nullable ("medium" | "high")
 }
 
 /// Values for Event ID 4: checkpoint
@@ -812,6 +813,7 @@ pub mod checkpoint_pos {
 /// | 8 | edit_kind | String (nullable) |
 /// | 9 | checkpoint_type | String (nullable) |
 /// | 10 | attribution_recovery_metadata | String (nullable JSON) |
+/// | 11 | paste_confidence | String (nullable) |
 #[derive(Debug, Clone, Default)]
 pub struct CheckpointValues {
     pub checkpoint_ts: PosField<u64>,
@@ -825,6 +827,7 @@ pub struct CheckpointValues {
     pub edit_kind: PosField<String>,
     pub checkpoint_type: PosField<String>,
     pub attribution_recovery_metadata: PosField<String>,
+    pub paste_confidence: PosField<String>,
 }
 
 impl CheckpointValues {
@@ -952,6 +955,17 @@ impl CheckpointValues {
         self.attribution_recovery_metadata = Some(None);
         self
     }
+
+    pub fn paste_confidence(mut self, value: impl Into<String>) -> Self {
+        self.paste_confidence = Some(Some(value.into()));
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn paste_confidence_null(mut self) -> Self {
+        self.paste_confidence = Some(None);
+        self
+    }
 }
 
 impl PosEncoded for CheckpointValues {
@@ -1009,6 +1023,11 @@ impl PosEncoded for CheckpointValues {
             checkpoint_pos::ATTRIBUTION_RECOVERY_METADATA,
             string_to_json(&self.attribution_recovery_metadata),
         );
+        sparse_set(
+            &mut map,
+            checkpoint_pos::PASTE_CONFIDENCE,
+            string_to_json(&self.paste_confidence),
+        );
 
         map
     }
@@ -1029,6 +1048,7 @@ impl PosEncoded for CheckpointValues {
                 arr,
                 checkpoint_pos::ATTRIBUTION_RECOVERY_METADATA,
             ),
+            paste_confidence: sparse_get_string(arr, checkpoint_pos::PASTE_CONFIDENCE),
         }
     }
 }
@@ -1450,6 +1470,21 @@ mod tests {
         assert_eq!(sparse.get("4"), Some(&Value::Number(20.into())));
     }
 
+    #[test]
+    fn test_checkpoint_values_paste_confidence_round_trip() {
+        use super::PosEncoded;
+
+        let values = CheckpointValues::new()
+            .kind("ai_pasted")
+            .paste_confidence("high");
+        let sparse = PosEncoded::to_sparse(&values);
+        assert_eq!(sparse.get("11"), Some(&Value::String("high".to_string())));
+
+        let decoded = <CheckpointValues as PosEncoded>::from_sparse(&sparse);
+        assert_eq!(decoded.kind, Some(Some("ai_pasted".to_string())));
+        assert_eq!(decoded.paste_confidence, Some(Some("high".to_string())));
+    }
+
     #[test]
     fn test_checkpoint_values_from_sparse() {
         use super::PosEncoded;
@@ -2006,6 +2041,236 @@ impl EventValues for OtelTraceValues {
     }
 }
 
+/// Value positions for "tab_completion" event.
+pub mod tab_completion_pos {
+    pub const OUTCOME: usize = 0; // String - "accepted" | "rejected"
+    pub const SUGGESTED_LINES: usize = 1; // u32 - lines in the suggestion
+    pub const ACCEPTED_LINES: usize = 2; // u32 - lines kept (0 when rejected)
+    pub const FILE_PATH: usize = 3; // String - nullable
+    pub const COMPLETION_ID: usize = 4; // String - nullable
+}
+
+/// Values for Event ID 8: tab_completion
+///
+/// Recorded by the `ai_tab` preset when an inline completion is accepted or
+/// rejected. Uses EventAttributes for tool, model and repo metadata.
+///
+/// **Fields:**
+/// | Position | Name | Type |
+/// |----------|------|------|
+/// | 0 | outcome | String |
+/// | 1 | suggested_lines | u32 |
+/// | 2 | accepted_lines | u32 |
+/// | 3 | file_path | String (nullable) |
+/// | 4 | completion_id | String (nullable) |
+#[derive(Debug, Clone, Default)]
+pub struct TabCompletionValues {
+    pub outcome: PosField<String>,
+    pub suggested_lines: PosField<u32>,
+    pub accepted_lines: PosField<u32>,
+    pub file_path: PosField<String>,
+    pub completion_id: PosField<String>,
+}
+
+impl TabCompletionValues {
+    pub const ACCEPTED: &'static str = "accepted";
+    pub const REJECTED: &'static str = "rejected";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn outcome(mut self, value: impl Into<String>) -> Self {
+        self.outcome = Some(Some(value.into()));
+        self
+    }
+
+    pub fn suggested_lines(mut self, value: u32) -> Self {
+        self.suggested_lines = Some(Some(value));
+        self
+    }
+
+    pub fn accepted_lines(mut self, value: u32) -> Self {
+        self.accepted_lines = Some(Some(value));
+        self
+    }
+
+    pub fn file_path(mut self, value: impl Into<String>) -> Self {
+        self.file_path = Some(Some(value.into()));
+        self
+    }
+
+    pub fn completion_id(mut self, value: impl Into<String>) -> Self {
+        self.completion_id = Some(Some(value.into()));
+        self
+    }
+}
+
+impl PosEncoded for TabCompletionValues {
+    fn to_sparse(&self) -> SparseArray {
+        let mut map = SparseArray::new();
+
+        sparse_set(
+            &mut map,
+            tab_completion_pos::OUTCOME,
+            string_to_json(&self.outcome),
+        );
+        sparse_set(
+            &mut map,
+            tab_completion_pos::SUGGESTED_LINES,
+            u32_to_json(&self.suggested_lines),
+        );
+        sparse_set(
+            &mut map,
+            tab_completion_pos::ACCEPTED_LINES,
+            u32_to_json(&self.accepted_lines),
+        );
+        sparse_set(
+            &mut map,
+            tab_completion_pos::FILE_PATH,
+            string_to_json(&self.file_path),
+        );
+        sparse_set(
+            &mut map,
+            tab_completion_pos::COMPLETION_ID,
+            string_to_json(&self.completion_id),
+        );
+
+        map
+    }
+
+    fn from_sparse(arr: &SparseArray) -> Self {
+        Self {
+            outcome: sparse_get_string(arr, tab_completion_pos::OUTCOME),
+            suggested_lines: sparse_get_u32(arr, tab_completion_pos::SUGGESTED_LINES),
+            accepted_lines: sparse_get_u32(arr, tab_completion_pos::ACCEPTED_LINES),
+            file_path: sparse_get_string(arr, tab_completion_pos::FILE_PATH),
+            completion_id: sparse_get_string(arr, tab_completion_pos::COMPLETION_ID),
+        }
+    }
+}
+
+impl EventValues for TabCompletionValues {
+    fn event_id() -> MetricEventId {
+        MetricEventId::TabCompletion
+    }
+
+    fn to_sparse(&self) -> SparseArray {
+        PosEncoded::to_sparse(self)
+    }
+
+    fn from_sparse(arr: &SparseArray) -> Self {
+        PosEncoded::from_sparse(arr)
+    }
+}
+
+/// Value positions for "checkpoint_deduped" event.
+pub mod checkpoint_deduped_pos {
+    pub const KIND: usize = 0; // String ("human", "ai_agent", ...)
+    pub const FILE_COUNT: usize = 1; // u32 - files in the suppressed checkpoint
+    pub const AGE_SECS: usize = 2; // u32 - seconds since the checkpoint it repeats
+    pub const TOOL_USE_ID: usize = 3; // String - nullable
+}
+
+/// Values for Event ID 9: checkpoint_deduped
+///
+/// Recorded once per checkpoint dropped as a duplicate of the previous one
+/// (an agent firing the same post-edit hook twice). Uses EventAttributes for
+/// tool, model and repo metadata.
+///
+/// **Fields:**
+/// | Position | Name | Type |
+/// |----------|------|------|
+/// | 0 | kind | String |
+/// | 1 | file_count | u32 |
+/// | 2 | age_secs | u32 |
+/// | 3 | external_tool_use_id | String (nullable) |
+#[derive(Debug, Clone, Default)]
+pub struct CheckpointDedupedValues {
+    pub kind: PosField<String>,
+    pub file_count: PosField<u32>,
+    pub age_secs: PosField<u32>,
+    pub external_tool_use_id: PosField<String>,
+}
+
+impl CheckpointDedupedValues {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn kind(mut self, value: impl Into<String>) -> Self {
+        self.kind = Some(Some(value.into()));
+        self
+    }
+
+    pub fn file_count(mut self, value: u32) -> Self {
+        self.file_count = Some(Some(value));
+        self
+    }
+
+    pub fn age_secs(mut self, value: u32) -> Self {
+        self.age_secs = Some(Some(value));
+        self
+    }
+
+    pub fn external_tool_use_id(mut self, value: impl Into<String>) -> Self {
+        self.external_tool_use_id = Some(Some(value.into()));
+        self
+    }
+}
+
+impl PosEncoded for CheckpointDedupedValues {
+    fn to_sparse(&self) -> SparseArray {
+        let mut map = SparseArray::new();
+
+        sparse_set(
+            &mut map,
+            checkpoint_deduped_pos::KIND,
+            string_to_json(&self.kind),
+        );
+        sparse_set(
+            &mut map,
+            checkpoint_deduped_pos::FILE_COUNT,
+            u32_to_json(&self.file_count),
+        );
+        sparse_set(
+            &mut map,
+            checkpoint_deduped_pos::AGE_SECS,
+            u32_to_json(&self.age_secs),
+        );
+        sparse_set(
+            &mut map,
+            checkpoint_deduped_pos::TOOL_USE_ID,
+            string_to_json(&self.external_tool_use_id),
+        );
+
+        map
+    }
+
+    fn from_sparse(arr: &SparseArray) -> Self {
+        Self {
+            kind: sparse_get_string(arr, checkpoint_deduped_pos::KIND),
+            file_count: sparse_get_u32(arr, checkpoint_deduped_pos::FILE_COUNT),
+            age_secs: sparse_get_u32(arr, checkpoint_deduped_pos::AGE_SECS),
+            external_tool_use_id: sparse_get_string(arr, checkpoint_deduped_pos::TOOL_USE_ID),
+        }
+    }
+}
+
+impl EventValues for CheckpointDedupedValues {
+    fn event_id() -> MetricEventId {
+        MetricEventId::CheckpointDeduped
+    }
+
+    fn to_sparse(&self) -> SparseArray {
+        PosEncoded::to_sparse(self)
+    }
+
+    fn from_sparse(arr: &SparseArray) -> Self {
+        PosEncoded::from_sparse(arr)
+    }
+}
+
 #[cfg(test)]
 mod session_event_tests {
     use super::*;
@@ -2219,4 +2484,52 @@ mod session_event_tests {
         assert_eq!(OtelTraceValues::event_id(), MetricEventId::OtelTrace);
         assert_eq!(OtelTraceValues::event_id() as u16, 6);
     }
+
+    #[test]
+    fn test_tab_completion_values_sparse_roundtrip() {
+        let values = TabCompletionValues::new()
+            .outcome(TabCompletionValues::ACCEPTED)
+            .suggested_lines(4)
+            .accepted_lines(3)
+            .file_path("src/lib.rs")
+            .completion_id("comp-1");
+        assert_eq!(TabCompletionValues::event_id() as u16, 8);
+
+        let sparse = PosEncoded::to_sparse(&values);
+        assert_eq!(
+            sparse.get("0"),
+            Some(&serde_json::Value::String("accepted".to_string()))
+        );
+        assert_eq!(sparse.get("2"), Some(&serde_json::Value::Number(3.into())));
+
+        let decoded = <TabCompletionValues as PosEncoded>::from_sparse(&sparse);
+        assert_eq!(decoded.outcome, Some(Some("accepted".to_string())));
+        assert_eq!(decoded.suggested_lines, Some(Some(4)));
+        assert_eq!(decoded.accepted_lines, Some(Some(3)));
+        assert_eq!(decoded.file_path, Some(Some("src/lib.rs".to_string())));
+        assert_eq!(decoded.completion_id, Some(Some("comp-1".to_string())));
+
+        let rejected = PosEncoded::to_sparse(&TabCompletionValues::new().outcome("rejected"));
+        assert_eq!(rejected.get("1"), None);
+    }
+
+    #[test]
+    fn test_checkpoint_deduped_values_sparse_roundtrip() {
+        let values = CheckpointDedupedValues::new()
+            .kind("ai_agent")
+            .file_count(2)
+            .age_secs(1)
+            .external_tool_use_id("toolu_1");
+        assert_eq!(CheckpointDedupedValues::event_id() as u16, 9);
+
+        let sparse = PosEncoded::to_sparse(&values);
+        let decoded = <CheckpointDedupedValues as PosEncoded>::from_sparse(&sparse);
+        assert_eq!(decoded.kind, Some(Some("ai_agent".to_string())));
+        assert_eq!(decoded.file_count, Some(Some(2)));
+        assert_eq!(decoded.age_secs, Some(Some(1)));
+        assert_eq!(
+            decoded.external_tool_use_id,
+            Some(Some("toolu_1".to_string()))
+        );
+    }
 }
@@ -4,10 +4,14 @@
 /// to that session for yield and ai_lines_committed calculations.
 const YIELD_WINDOW_SECS: u32 = 4 * 3600;
 
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
 use crate::error::GitAiError;
 use crate::metrics::attrs::attr_pos;
 use crate::metrics::db::{MetricHistoryRecord, MetricsDatabase};
-use crate::metrics::events::{checkpoint_pos, committed_pos, session_event_pos};
+use crate::metrics::events::{
+    checkpoint_pos, committed_pos, session_event_pos, tab_completion_pos,
+};
 use crate::metrics::pos_encoded::{
     sparse_get_string, sparse_get_u32, sparse_get_vec_string, sparse_get_vec_u32,
 };
@@ -1287,11 +1291,393 @@ pub fn compute_repo_summaries(
     repo_summaries_from_records(&all_records, since_ts, granularity)
 }
 
+/// How far back `git-ai top` looks for the last commit in each repository when
+/// computing pending (checkpointed but uncommitted) AI lines.
+const LIVE_PENDING_LOOKBACK_SECS: u32 = 24 * 3600;
+
+/// Event types read by `git-ai top`.
+const LIVE_EVENT_IDS: &[u16] = &[
+    1, // Committed
+    4, // Checkpoint
+];
+
+/// Machine-wide snapshot of recent agent activity for `git-ai top`.
+#[derive(Debug, Default, Serialize)]
+pub struct LiveActivity {
+    /// Width of the activity window in seconds.
+    pub window_secs: u32,
+    /// Checkpoints per minute, averaged over the window.
+    pub checkpoints_per_minute: f64,
+    /// Sessions that checkpointed within the window, most recent first.
+    pub sessions: Vec<LiveSession>,
+    /// Files touched by checkpoints within the window, most recent first.
+    pub files: Vec<LiveFile>,
+    /// Repositories with AI lines checkpointed since their last commit.
+    pub repos: Vec<LivePendingRepo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LiveSession {
+    pub session_id: String,
+    pub tool: String,
+    pub model: Option<String>,
+    pub repo_url: Option<String>,
+    pub checkpoints: u32,
+    pub ai_lines_added: u32,
+    pub last_seen_ts: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LiveFile {
+    pub repo_url: Option<String>,
+    pub file_path: String,
+    pub checkpoints: u32,
+    pub last_seen_ts: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LivePendingRepo {
+    pub repo_url: String,
+    /// AI lines added by checkpoints newer than the repository's last commit.
+    pub pending_ai_lines: u32,
+    pub pending_checkpoints: u32,
+    pub last_commit_ts: Option<u32>,
+}
+
+/// Read recent metric events and summarize live activity for `git-ai top`.
+pub fn compute_live_activity(now_ts: u32, window_secs: u32) -> Result<LiveActivity, GitAiError> {
+    let since_ts = now_ts.saturating_sub(window_secs.max(LIVE_PENDING_LOOKBACK_SECS));
+    let db = MetricsDatabase::global()?;
+    let records = {
+        let db_lock = db
+            .lock()
+            .map_err(|_| GitAiError::Generic("metrics DB lock poisoned".to_string()))?;
+        db_lock.get_metric_history(since_ts, None, LIVE_EVENT_IDS)?
+    };
+    Ok(live_activity_from_records(&records, now_ts, window_secs))
+}
+
+/// Aggregate pre-fetched checkpoint and commit events into a [`LiveActivity`].
+///
+/// Checkpoint events are recorded once per file, so checkpoints are counted by
+/// distinct trace id when one is present.
+pub fn live_activity_from_records(
+    records: &[MetricHistoryRecord],
+    now_ts: u32,
+    window_secs: u32,
+) -> LiveActivity {
+    let window_start = now_ts.saturating_sub(window_secs);
+    let checkpoint_key = |record: &MetricHistoryRecord, index: usize| {
+        sparse_get_string(&record.event.attrs, attr_pos::TRACE_ID)
+            .flatten()
+            .unwrap_or_else(|| format!("#{}", index))
+    };
+
+    let mut last_commit_ts: HashMap<String, u32> = HashMap::new();
+    for record in records.iter().filter(|r| r.event_id == 1) {
+        if let Some(repo_url) = &record.repo_url {
+            let ts = last_commit_ts.entry(repo_url.clone()).or_insert(record.ts);
+            *ts = (*ts).max(record.ts);
+        }
+    }
+
+    let mut window_checkpoints: HashSet<String> = HashSet::new();
+    let mut sessions: HashMap<String, (LiveSession, HashSet<String>)> = HashMap::new();
+    let mut files: HashMap<(Option<String>, String), (LiveFile, HashSet<String>)> = HashMap::new();
+    let mut pending: HashMap<String, (u32, HashSet<String>)> = HashMap::new();
+
+    for (index, record) in records.iter().enumerate() {
+        if record.event_id != 4 {
+            continue;
+        }
+        let event = &record.event;
+        let key = checkpoint_key(record, index);
+        let kind = sparse_get_string(&event.values, checkpoint_pos::KIND)
+            .flatten()
+            .unwrap_or_default();
+        let is_ai = matches!(kind.as_str(), "ai_agent" | "ai_tab");
+        let lines_added = sparse_get_u32(&event.values, checkpoint_pos::LINES_ADDED)
+            .flatten()
+            .unwrap_or(0);
+
+        if is_ai
+            && let Some(repo_url) = &record.repo_url
+            && last_commit_ts
+                .get(repo_url)
+                .is_none_or(|commit_ts| record.ts > *commit_ts)
+        {
+            let (lines, checkpoints) = pending.entry(repo_url.clone()).or_default();
+            *lines += lines_added;
+            checkpoints.insert(key.clone());
+        }
+
+        if record.ts < window_start {
+            continue;
+        }
+        window_checkpoints.insert(key.clone());
+
+        let file_path = sparse_get_string(&event.values, checkpoint_pos::FILE_PATH)
+            .flatten()
+            .unwrap_or_default();
+        if !file_path.is_empty() {
+            let (file, checkpoints) = files
+                .entry((record.repo_url.clone(), file_path.clone()))
+                .or_insert_with(|| {
+                    (
+                        LiveFile {
+                            repo_url: record.repo_url.clone(),
+                            file_path,
+                            checkpoints: 0,
+                            last_seen_ts: record.ts,
+                        },
+                        HashSet::new(),
+                    )
+                });
+            checkpoints.insert(key.clone());
+            file.last_seen_ts = file.last_seen_ts.max(record.ts);
+        }
+
+        let Some(session_id) = sparse_get_string(&event.attrs, attr_pos::SESSION_ID)
+            .flatten()
+            .filter(|id| !id.is_empty())
+        else {
+            continue;
+        };
+        if !is_ai {
+            continue;
+        }
+        let (session, checkpoints) = sessions.entry(session_id.clone()).or_insert_with(|| {
+            (
+                LiveSession {
+                    session_id,
+                    tool: sparse_get_string(&event.attrs, attr_pos::TOOL)
+                        .flatten()
+                        .unwrap_or_else(|| "unknown".to_string()),
+                    model: sparse_get_string(&event.attrs, attr_pos::MODEL).flatten(),
+                    repo_url: record.repo_url.clone(),
+                    checkpoints: 0,
+                    ai_lines_added: 0,
+                    last_seen_ts: record.ts,
+                },
+                HashSet::new(),
+            )
+        });
+        checkpoints.insert(key);
+        session.ai_lines_added += lines_added;
+        session.last_seen_ts = session.last_seen_ts.max(record.ts);
+    }
+
+    let mut sessions: Vec<LiveSession> = sessions
+        .into_values()
+        .map(|(mut session, checkpoints)| {
+            session.checkpoints = checkpoints.len() as u32;
+            session
+        })
+        .collect();
+    sessions.sort_by_key(|s| (Reverse(s.last_seen_ts), s.session_id.clone()));
+
+    let mut files: Vec<LiveFile> = files
+        .into_values()
+        .map(|(mut file, checkpoints)| {
+            file.checkpoints = checkpoints.len() as u32;
+            file
+        })
+        .collect();
+    files.sort_by_key(|f| (Reverse(f.last_seen_ts), f.file_path.clone()));
+
+    let mut repos: Vec<LivePendingRepo> = pending
+        .into_iter()
+        .filter(|(_, (lines, _))| *lines > 0)
+        .map(|(repo_url, (lines, checkpoints))| LivePendingRepo {
+            last_commit_ts: last_commit_ts.get(&repo_url).copied(),
+            repo_url,
+            pending_ai_lines: lines,
+            pending_checkpoints: checkpoints.len() as u32,
+        })
+        .collect();
+    repos.sort_by_key(|r| (Reverse(r.pending_ai_lines), r.repo_url.clone()));
+
+    let window_minutes = (window_secs.max(60) as f64) / 60.0;
+    LiveActivity {
+        window_secs,
+        checkpoints_per_minute: window_checkpoints.len() as f64 / window_minutes,
+        sessions,
+        files,
+        repos,
+    }
+}
+
+// ─── Periodic digest ──────────────────────────────────────────────────────────
+
+/// Rows kept in each of the digest's top-N lists.
+const DIGEST_TOP_N: usize = 5;
+
+/// One reporting period summarized for `git-ai report --digest`.
+#[derive(Debug, Serialize)]
+pub struct ActivityDigest {
+    pub period_label: String,
+    pub since_ts: u32,
+    pub until_ts: u32,
+    /// Commits with AI-attributed lines.
+    pub commits: u32,
+    pub ai_lines: u32,
+    pub human_lines: u32,
+    pub diff_added_lines: u32,
+    /// Share of committed added lines attributed to AI, in percent.
+    /// `None` when nothing was committed in the period.
+    pub ai_share_pct: Option<f64>,
+    pub sessions: u32,
+    pub estimated_cost_usd: f64,
+    /// Committed AI lines per "tool · model", largest first.
+    pub top_tools: Vec<(String, u32)>,
+    /// Files with the most committed AI lines, largest first.
+    pub top_files: Vec<DigestFile>,
+    pub tab_completions: TabCompletionSummary,
+}
+
+/// Inline completions reported by the `ai_tab` preset.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TabCompletionSummary {
+    pub accepted: u32,
+    pub rejected: u32,
+    pub accepted_lines: u32,
+    /// Accepted share of all suggestions, in percent. `None` when none were shown.
+    pub acceptance_rate_pct: Option<f64>,
+}
+
+fn summarize_tab_completions(records: &[&MetricHistoryRecord]) -> TabCompletionSummary {
+    let mut summary = TabCompletionSummary::default();
+    for record in records.iter().filter(|r| r.event_id == 8) {
+        let values = &record.event.values;
+        match sparse_get_string(values, tab_completion_pos::OUTCOME)
+            .flatten()
+            .as_deref()
+        {
+            Some("accepted") => {
+                summary.accepted += 1;
+                summary.accepted_lines +=
+                    sparse_get_u32(values, tab_completion_pos::ACCEPTED_LINES)
+                        .flatten()
+                        .unwrap_or(0);
+            }
+            Some("rejected") => summary.rejected += 1,
+            _ => {}
+        }
+    }
+    let shown = summary.accepted + summary.rejected;
+    summary.acceptance_rate_pct =
+        (shown > 0).then(|| summary.accepted as f64 * 100.0 / shown as f64);
+    summary
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DigestFile {
+    pub repo_url: Option<String>,
+    pub file_path: String,
+    pub ai_lines: u32,
+}
+
+/// Read metric events between `since_ts` and `until_ts` and build a digest.
+pub fn compute_digest(
+    since_ts: u32,
+    until_ts: u32,
+    period_label: String,
+) -> Result<ActivityDigest, GitAiError> {
+    let records = fetch_metric_history(since_ts, None)?;
+    digest_from_records(&records, since_ts, until_ts, period_label)
+}
+
+/// Aggregate pre-fetched events into an [`ActivityDigest`].
+///
+/// Per-file AI lines come from the authorship note stored on each committed
+/// event, so they only count lines that were actually committed.
+pub fn digest_from_records(
+    records: &[MetricHistoryRecord],
+    since_ts: u32,
+    until_ts: u32,
+    period_label: String,
+) -> Result<ActivityDigest, GitAiError> {
+    let refs: Vec<&MetricHistoryRecord> = records
+        .iter()
+        .filter(|r| r.ts >= since_ts && r.ts <= until_ts)
+        .collect();
+    let stats = compute_activity_from_records(
+        &refs,
+        since_ts,
+        period_label.clone(),
+        BucketGranularity::Daily,
+    )?;
+
+    let mut file_lines: HashMap<(Option<String>, String), u32> = HashMap::new();
+    for record in refs.iter().filter(|r| r.event_id == 1) {
+        let Some(note) =
+            sparse_get_string(&record.event.values, committed_pos::AUTHORSHIP_NOTE).flatten()
+        else {
+            continue;
+        };
+        let Ok(log) = AuthorshipLog::deserialize_from_string(&note) else {
+            continue;
+        };
+        for attestation in &log.attestations {
+            let ai_lines: u32 = attestation
+                .entries
+                .iter()
+                .filter(|entry| !entry.hash.starts_with("h_"))
+                .flat_map(|entry| &entry.line_ranges)
+                .map(|range| match range {
+                    LineRange::Single(_) => 1,
+                    LineRange::Range(start, end) => end.saturating_sub(*start) + 1,
+                })
+                .sum();
+            if ai_lines > 0 {
+                *file_lines
+                    .entry((record.repo_url.clone(), attestation.file_path.clone()))
+                    .or_default() += ai_lines;
+            }
+        }
+    }
+    let mut top_files: Vec<DigestFile> = file_lines
+        .into_iter()
+        .map(|((repo_url, file_path), ai_lines)| DigestFile {
+            repo_url,
+            file_path,
+            ai_lines,
+        })
+        .collect();
+    top_files.sort_by(|a, b| {
+        b.ai_lines
+            .cmp(&a.ai_lines)
+            .then_with(|| a.file_path.cmp(&b.file_path))
+    });
+    top_files.truncate(DIGEST_TOP_N);
+
+    let commits = &stats.commits;
+    let ai_share_pct = (commits.diff_added_lines > 0)
+        .then(|| (commits.ai_lines as f64 * 100.0 / commits.diff_added_lines as f64).min(100.0));
+    Ok(ActivityDigest {
+        period_label,
+        since_ts,
+        until_ts,
+        commits: commits.total,
+        ai_lines: commits.ai_lines,
+        human_lines: commits.human_lines,
+        diff_added_lines: commits.diff_added_lines,
+        ai_share_pct,
+        sessions: stats.sessions.total,
+        estimated_cost_usd: stats.tokens.estimated_cost_usd,
+        top_tools: commits.by_tool.iter().take(DIGEST_TOP_N).cloned().collect(),
+        top_files,
+        tab_completions: summarize_tab_completions(&refs),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::metrics::attrs::EventAttributes;
-    use crate::metrics::events::{CheckpointValues, CommittedValues, SessionEventValues};
+    use crate::metrics::events::{
+        CheckpointValues, CommittedValues, SessionEventValues, TabCompletionValues,
+    };
     use crate::metrics::pos_encoded::{PosEncoded, sparse_get_string};
     use serde_json::json;
 
@@ -1429,6 +1815,128 @@ mod tests {
         assert!(stats.buckets.iter().any(|bucket| bucket.ai_lines == 10));
     }
 
+    #[test]
+    fn digest_sums_period_and_ranks_ai_files_from_committed_notes() {
+        use crate::authorship::authorship_log_serialization::{AttestationEntry, FileAttestation};
+
+        let now = now_ts();
+        let repo = "github.com/acme/project";
+        let mut log = AuthorshipLog::new();
+        for (path, hash, range) in [
+            ("src/big.rs", "aaaaaaaaaaaaaaaa", LineRange::Range(1, 8)),
+            ("src/small.rs", "aaaaaaaaaaaaaaaa", LineRange::Single(3)),
+            ("src/human.rs", "h_00000000000000", LineRange::Range(1, 20)),
+        ] {
+            let mut file = FileAttestation::new(path.to_string());
+            file.add_entry(AttestationEntry::new(hash.to_string(), vec![range]));
+            log.attestations.push(file);
+        }
+        let with_note = record(MetricEvent::with_timestamp(
+            now.saturating_sub(600),
+            &CommittedValues::new()
+                .human_additions(3)
+                .git_diff_added_lines(12)
+                .tool_model_pairs(vec![
+                    "all".to_string(),
+                    "claude::claude-sonnet-4-6".to_string(),
+                ])
+                .ai_additions(vec![9, 9])
+                .authorship_note(log.serialize_to_string().unwrap()),
+            attrs(Some(repo), "claude", None),
+        ));
+        let tab = |outcome: &str, accepted_lines: u32| {
+            record(MetricEvent::with_timestamp(
+                now.saturating_sub(300),
+                &TabCompletionValues::new()
+                    .outcome(outcome)
+                    .suggested_lines(2)
+                    .accepted_lines(accepted_lines),
+                attrs(Some(repo), "copilot", None),
+            ))
+        };
+        let records = [
+            // Outside the period.
+            committed(now.saturating_sub(30 * 24 * 3600), repo, 50, 0, 50),
+            with_note,
+            tab("accepted", 2),
+            tab("accepted", 1),
+            tab("rejected", 0),
+            tab("rejected", 0),
+        ];
+
+        let digest = digest_from_records(
+            &records,
+            now.saturating_sub(7 * 24 * 3600),
+            now,
+            "last 7 days".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(digest.commits, 1);
+        assert_eq!(digest.ai_lines, 9);
+        assert_eq!(digest.ai_share_pct, Some(75.0));
+        assert_eq!(
+            digest.tab_completions,
+            TabCompletionSummary {
+                accepted: 2,
+                rejected: 2,
+                accepted_lines: 3,
+                acceptance_rate_pct: Some(50.0),
+            }
+        );
+        assert_eq!(
+            digest.top_tools,
+            vec![("claude · sonnet-4-6".to_string(), 9)]
+        );
+        assert_eq!(
+            digest.top_files,
+            vec![
+                DigestFile {
+                    repo_url: Some(repo.to_string()),
+                    file_path: "src/big.rs".to_string(),
+                    ai_lines: 8,
+                },
+                DigestFile {
+                    repo_url: Some(repo.to_string()),
+                    file_path: "src/small.rs".to_string(),
+                    ai_lines: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn live_activity_reports_window_sessions_files_and_pending_lines() {
+        let now = now_ts();
+        let repo = "github.com/acme/project";
+        let records = [
+            checkpoint(now.saturating_sub(7200), repo, 4),
+            committed(now.saturating_sub(3600), repo, 4, 0, 4),
+            checkpoint(now.saturating_sub(1800), repo, 3),
+            checkpoint(now.saturating_sub(60), repo, 5),
+        ];
+
+        let live = live_activity_from_records(&records, now, 600);
+
+        assert_eq!(live.window_secs, 600);
+        assert!((live.checkpoints_per_minute - 0.1).abs() < f64::EPSILON);
+        assert_eq!(live.sessions.len(), 1);
+        assert_eq!(live.sessions[0].session_id, "session-1");
+        assert_eq!(live.sessions[0].tool, "claude");
+        assert_eq!(live.sessions[0].ai_lines_added, 5);
+        assert_eq!(live.files.len(), 1);
+        assert_eq!(live.files[0].file_path, "src/main.rs");
+        assert_eq!(
+            live.repos,
+            vec![LivePendingRepo {
+                repo_url: repo.to_string(),
+                pending_ai_lines: 8,
+                pending_checkpoints: 2,
+                last_commit_ts: Some(now.saturating_sub(3600)),
+            }]
+        );
+    }
+
     fn day(y: i32, m: u32, d: u32) -> NaiveDate {
         NaiveDate::from_ymd_opt(y, m, d).unwrap()
     }
@@ -15,8 +15,9 @@ pub mod types;
 // Re-export all public types for external crates
 pub use attrs::EventAttributes;
 pub use events::{
-    AgentUsageValues, CheckpointValues, CommittedValues, InstallHooksValues, OtelTraceValues,
-    RewriteCommittedValues, SessionEventValues,
+    AgentUsageValues, CheckpointDedupedValues, CheckpointValues, CommittedValues,
+    InstallHooksValues, OtelTraceValues, RewriteCommittedValues, SessionEventValues,
+    TabCompletionValues,
 };
 pub use pos_encoded::PosEncoded;
 pub use types::{EventValues, METRICS_API_VERSION, MetricEvent, MetricsBatch};
@@ -53,11 +54,24 @@ pub fn record<V: EventValues>(values: V, attrs: EventAttributes) {
     if should_ignore_debug_self_check_event(&attrs) {
         return;
     }
+    let attrs = anonymize_if_enabled(attrs.with_current_identity());
     let event = MetricEvent::new(&values, attrs.to_sparse());
     // Write directly to observability log
     crate::observability::log_metrics(vec![event]);
 }
 
+/// Anonymize identities when the event's repository is in anonymous mode.
+fn anonymize_if_enabled(attrs: EventAttributes) -> EventAttributes {
+    let repo_url = attrs.repo_url.clone().flatten();
+    if !crate::config::Config::get().should_anonymize_remote_urls(repo_url.as_deref().into_iter()) {
+        return attrs;
+    }
+    match crate::authorship::anonymize::workspace_key() {
+        Some(key) => attrs.anonymized(&key),
+        None => attrs,
+    }
+}
+
 fn should_ignore_debug_self_check_event(attrs: &EventAttributes) -> bool {
     if attrs.repo_url.as_ref().is_some_and(|repo_url| {
         repo_url
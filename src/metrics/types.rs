@@ -23,6 +23,8 @@ pub enum MetricEventId {
     SessionEvent = 5,
     OtelTrace = 6,
     RewriteCommitted = 7,
+    TabCompletion = 8,
+    CheckpointDeduped = 9,
 }
 
 /// Trait for event-specific values.
@@ -199,6 +201,8 @@ mod tests {
         assert_eq!(MetricEventId::InstallHooks as u16, 3);
         assert_eq!(MetricEventId::Checkpoint as u16, 4);
         assert_eq!(MetricEventId::RewriteCommitted as u16, 7);
+        assert_eq!(MetricEventId::TabCompletion as u16, 8);
+        assert_eq!(MetricEventId::CheckpointDeduped as u16, 9);
     }
 
     #[test]
@@ -0,0 +1,388 @@
+//! Local ring buffer of panics and reported errors.
+//!
+//! Every panic and every error passed to [`super::log_error`] is appended to
+//! `~/.git-ai/internal/errors.jsonl`, so field failures can be inspected with
+//! `git-ai errors show` even when no telemetry leaves the machine. Paths and
+//! remote URLs are redacted before anything is written. Once the file grows past
+//! [`COMPACT_THRESHOLD_BYTES`] it is compacted to the newest
+//! [`MAX_ERROR_RECORDS`] entries.
+
+use crate::error::GitAiError;
+use crate::utils::LockFile;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Number of records kept when the buffer is compacted.
+pub const MAX_ERROR_RECORDS: usize = 200;
+
+/// Size at which appends trigger a compaction.
+const COMPACT_THRESHOLD_BYTES: u64 = 256 * 1024;
+
+static URL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\b[a-zA-Z][a-zA-Z0-9+.-]*://[^\s'"<>]+"#).unwrap());
+static SCP_REMOTE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b[\w.-]+@[\w.-]+:[\w~./-]+").unwrap());
+static UNIX_PATH_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(^|[\s'"(=])((?:/[^\s/'":]+)+)"#).unwrap());
+static WINDOWS_PATH_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(^|[\s'"(=])([A-Za-z]:\\(?:[^\s\\'":]+\\)*[^\s\\'":]+)"#).unwrap());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorRecordKind {
+    Panic,
+    Error,
+}
+
+impl ErrorRecordKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorRecordKind::Panic => "panic",
+            ErrorRecordKind::Error => "error",
+        }
+    }
+}
+
+/// A single captured failure.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorRecord {
+    pub timestamp: String,
+    pub kind: ErrorRecordKind,
+    pub message: String,
+    /// Messages of the error's `source()` chain, outermost first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chain: Vec<String>,
+    /// Source location of a panic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<Value>,
+    pub version: String,
+    /// True once the record has been handed to crash reporting.
+    #[serde(default)]
+    pub reported: bool,
+}
+
+impl ErrorRecord {
+    fn new(kind: ErrorRecordKind, message: &str) -> Self {
+        Self {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            kind,
+            message: redact(message),
+            chain: Vec::new(),
+            location: None,
+            context: None,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            reported: false,
+        }
+    }
+}
+
+/// Returns the path to the error ring buffer (~/.git-ai/internal/errors.jsonl)
+pub fn errors_log_path() -> Option<PathBuf> {
+    crate::config::internal_dir_path().map(|dir| dir.join("errors.jsonl"))
+}
+
+fn errors_lock_path(log_path: &Path) -> PathBuf {
+    log_path.with_extension("jsonl.lock")
+}
+
+/// Install a panic hook that records panics before running the previous hook.
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = if let Some(s) = info.payload().downcast_ref::<&str>() {
+            (*s).to_string()
+        } else if let Some(s) = info.payload().downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "unknown panic".to_string()
+        };
+        let mut record = ErrorRecord::new(ErrorRecordKind::Panic, &message);
+        record.location = info
+            .location()
+            .map(|location| format!("{}:{}", location.file(), location.line()));
+        if let Some(path) = errors_log_path() {
+            let _ = append_record(&path, &record);
+        }
+        previous(info);
+    }));
+}
+
+/// Record an error and its `source()` chain. Failures to write are ignored.
+pub fn record_error(error: &dyn std::error::Error, context: Option<&Value>, reported: bool) {
+    if cfg!(test) {
+        return;
+    }
+    let Some(path) = errors_log_path() else {
+        return;
+    };
+    let _ = append_record(&path, &error_record(error, context, reported));
+}
+
+fn error_record(
+    error: &dyn std::error::Error,
+    context: Option<&Value>,
+    reported: bool,
+) -> ErrorRecord {
+    let mut record = ErrorRecord::new(ErrorRecordKind::Error, &error.to_string());
+    let mut source = error.source();
+    while let Some(cause) = source {
+        record.chain.push(redact(&cause.to_string()));
+        source = cause.source();
+    }
+    record.context = context.map(redact_value);
+    record.reported = reported;
+    record
+}
+
+/// Replace paths and remote URLs so records do not identify repositories.
+/// Absolute paths become a short hash, so repeated failures on the same file
+/// can still be correlated without revealing any of its name.
+pub fn redact(text: &str) -> String {
+    let text = URL_RE.replace_all(text, "<url>");
+    let text = SCP_REMOTE_RE.replace_all(&text, "<remote>");
+    let text = UNIX_PATH_RE.replace_all(&text, redact_path_capture);
+    WINDOWS_PATH_RE
+        .replace_all(&text, redact_path_capture)
+        .into_owned()
+}
+
+fn redact_path_capture(caps: &regex::Captures) -> String {
+    let digest = Sha256::digest(caps[2].as_bytes());
+    let hash: String = digest[..4].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}<path:{}>", &caps[1], hash)
+}
+
+fn redact_value(value: &Value) -> Value {
+    match value {
+        Value::String(s) => Value::String(redact(s)),
+        Value::Array(items) => Value::Array(items.iter().map(redact_value).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), redact_value(value)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn append_record(path: &Path, record: &ErrorRecord) -> Result<(), GitAiError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut line = serde_json::to_string(record)?;
+    line.push('\n');
+    // A single append-mode write keeps concurrent writers from interleaving lines.
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    file.write_all(line.as_bytes())?;
+
+    if file.metadata()?.len() > COMPACT_THRESHOLD_BYTES
+        && let Some(_lock) = LockFile::try_acquire(&errors_lock_path(path))
+    {
+        let mut records = read_records_from(path);
+        let excess = records.len().saturating_sub(MAX_ERROR_RECORDS);
+        records.drain(..excess);
+        write_records(path, &records)?;
+    }
+    Ok(())
+}
+
+fn read_records_from(path: &Path) -> Vec<ErrorRecord> {
+    let Ok(data) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    data.lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+fn write_records(path: &Path, records: &[ErrorRecord]) -> Result<(), GitAiError> {
+    let mut data = String::new();
+    for record in records {
+        data.push_str(&serde_json::to_string(record)?);
+        data.push('\n');
+    }
+    let tmp_path = path.with_extension("jsonl.tmp");
+    fs::write(&tmp_path, data)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// All buffered records, oldest first. Malformed lines are skipped.
+pub fn read_records() -> Vec<ErrorRecord> {
+    errors_log_path()
+        .map(|path| read_records_from(&path))
+        .unwrap_or_default()
+}
+
+/// Remove every buffered record.
+pub fn clear_records() -> Result<(), GitAiError> {
+    let Some(path) = errors_log_path() else {
+        return Ok(());
+    };
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Submit records that were never handed to crash reporting and mark them
+/// reported. Returns the number submitted.
+pub fn upload_unreported_records() -> Result<usize, GitAiError> {
+    if !crate::config::Config::get()
+        .telemetry()
+        .crash_reports
+        .enabled
+    {
+        return Err(GitAiError::Generic(
+            "crash reports are off (telemetry_crash_reports)".to_string(),
+        ));
+    }
+    let path = errors_log_path()
+        .ok_or_else(|| GitAiError::Generic("Could not determine errors log path".to_string()))?;
+    let _lock = LockFile::try_acquire(&errors_lock_path(&path)).ok_or_else(|| {
+        GitAiError::Generic("another process is updating the error log".to_string())
+    })?;
+
+    let mut records = read_records_from(&path);
+    let envelopes: Vec<_> = records
+        .iter_mut()
+        .filter(|record| !record.reported)
+        .map(|record| {
+            record.reported = true;
+            error_envelope(record)
+        })
+        .collect();
+    if envelopes.is_empty() {
+        return Ok(0);
+    }
+    let submitted = envelopes.len();
+    super::submit_telemetry_envelope(envelopes);
+    write_records(&path, &records)?;
+    Ok(submitted)
+}
+
+fn error_envelope(record: &ErrorRecord) -> crate::daemon::TelemetryEnvelope {
+    let mut context = serde_json::json!({
+        "source": "errors_ring_buffer",
+        "kind": record.kind.as_str(),
+        "version": record.version,
+    });
+    if let Some(location) = &record.location {
+        context["location"] = Value::String(location.clone());
+    }
+    if !record.chain.is_empty() {
+        context["chain"] = serde_json::json!(record.chain);
+    }
+    if let Some(extra) = &record.context {
+        context["context"] = extra.clone();
+    }
+    crate::daemon::TelemetryEnvelope::Error {
+        timestamp: record.timestamp.clone(),
+        message: record.message.clone(),
+        context: Some(context),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Wrapped(std::io::Error);
+
+    impl std::fmt::Display for Wrapped {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "failed to read /home/alice/work/secret-repo/.git/HEAD")
+        }
+    }
+
+    impl std::error::Error for Wrapped {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn test_redact_paths_and_remotes() {
+        let redacted = redact("open /Users/bob/acme/api/src/main.rs failed");
+        assert!(redacted.starts_with("open <path:"), "{}", redacted);
+        assert!(!redacted.contains("main.rs"), "{}", redacted);
+        assert!(!redacted.contains("bob"), "{}", redacted);
+        assert_eq!(
+            redacted,
+            redact("open /Users/bob/acme/api/src/main.rs failed"),
+            "the same path hashes the same way"
+        );
+        assert_ne!(
+            redacted,
+            redact("open /Users/bob/acme/api/src/lib.rs failed")
+        );
+        assert_eq!(
+            redact("push to https://token@github.com/acme/api.git rejected"),
+            "push to <url> rejected"
+        );
+        assert_eq!(
+            redact("remote git@github.com:acme/api.git"),
+            "remote <remote>"
+        );
+        let redacted = redact(r"path=C:\Users\bob\acme\file.txt");
+        assert!(redacted.starts_with("path=<path:"), "{}", redacted);
+        assert!(!redacted.contains("file.txt"), "{}", redacted);
+        // Relative source locations stay readable.
+        assert_eq!(
+            redact("panicked at src/daemon/ref_cursor.rs:10"),
+            "panicked at src/daemon/ref_cursor.rs:10"
+        );
+    }
+
+    #[test]
+    fn test_error_record_captures_redacted_chain_and_context() {
+        let error = Wrapped(std::io::Error::other("permission denied"));
+        let context = serde_json::json!({"worktree": "/home/alice/work/secret-repo", "seq": 3});
+        let record = error_record(&error, Some(&context), false);
+
+        assert_eq!(record.kind, ErrorRecordKind::Error);
+        assert!(record.message.starts_with("failed to read <path:"));
+        assert!(!record.message.contains("HEAD"));
+        assert_eq!(record.chain, vec!["permission denied".to_string()]);
+        let context = record.context.unwrap();
+        assert_eq!(context["seq"], 3);
+        let worktree = context["worktree"].as_str().unwrap();
+        assert!(worktree.starts_with("<path:"), "{}", worktree);
+        assert!(!worktree.contains("secret-repo"), "{}", worktree);
+    }
+
+    #[test]
+    fn test_append_compacts_to_newest_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("errors.jsonl");
+        let padding = "x".repeat(2048);
+        let total = MAX_ERROR_RECORDS + 50;
+        for i in 0..total {
+            let record = ErrorRecord::new(ErrorRecordKind::Error, &format!("{i} {padding}"));
+            append_record(&path, &record).unwrap();
+        }
+
+        let records = read_records_from(&path);
+        assert_eq!(records.len(), MAX_ERROR_RECORDS);
+        assert!(
+            records
+                .last()
+                .unwrap()
+                .message
+                .starts_with(&format!("{} ", total - 1))
+        );
+    }
+}
@@ -3,7 +3,10 @@ use std::time::Duration;
 
 use crate::metrics::MetricEvent;
 
+pub mod errors;
 pub mod performance_targets;
+pub mod profile;
+pub mod trace;
 
 /// Maximum events per metrics envelope
 pub const MAX_METRICS_PER_ENVELOPE: usize = 1000;
@@ -20,8 +23,9 @@ fn submit_telemetry_envelope(envelopes: Vec<crate::daemon::TelemetryEnvelope>) {
     }
 }
 
-/// Log an error to Sentry (via daemon telemetry worker)
+/// Log an error to Sentry (via daemon telemetry worker) and the local error ring buffer
 pub fn log_error(error: &dyn std::error::Error, context: Option<serde_json::Value>) {
+    errors::record_error(error, context.as_ref(), true);
     let envelope = crate::daemon::TelemetryEnvelope::Error {
         timestamp: chrono::Utc::now().to_rfc3339(),
         message: error.to_string(),
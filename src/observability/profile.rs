@@ -0,0 +1,400 @@
+//! Phase timings for `git-ai profile`.
+//!
+//! [`ProfileLayer`] maps the spans git-ai already emits (see [`super::trace`])
+//! to coarse phases and sums their self time: a span's duration minus the
+//! time spent in profiled spans nested inside it, so a notes read inside the
+//! post-command step is counted once, as notes IO. Spans without a phase are
+//! transparent and their time goes to the nearest profiled ancestor.
+//!
+//! Checkpoints, diffs, notes IO and rewrites of a wrapped command mostly run
+//! in the daemon. The daemon installs [`process_profile_layer`], which keeps
+//! totals for its own spans since it started, and serves them over the
+//! control socket (`profile.snapshot`). `git-ai profile` takes a snapshot
+//! before and after the command and moves the difference out of
+//! `background` into the phases it belongs to, so work other repositories
+//! caused in the daemon meanwhile is counted too.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::Subscriber;
+use tracing::span::{Attributes, Id};
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Phases in report order.
+pub const PHASES: &[&str] = &[
+    "parse",
+    "repository",
+    "hooks",
+    "exec",
+    "checkpoint",
+    "diff",
+    "notes io",
+    "rewrite",
+    "post-command",
+    "background",
+];
+
+/// The phase a span named `name` is reported under.
+pub fn phase_for_span(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "parse" => "parse",
+        "repository" => "repository",
+        "pre_command" | "post_notes_updated" => "hooks",
+        "git" => "exec",
+        "checkpoint" => "checkpoint",
+        "diff" => "diff",
+        "read_note" | "write_notes" | "fetch_notes" | "push_notes" => "notes io",
+        "rewrite_event" | "revert" => "rewrite",
+        "post_command" => "post-command",
+        "background" => "background",
+        _ => return None,
+    })
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhaseTiming {
+    pub calls: u32,
+    pub total: Duration,
+}
+
+/// Timings collected by a [`ProfileLayer`], by phase.
+pub type PhaseTimings = Arc<Mutex<HashMap<&'static str, PhaseTiming>>>;
+
+/// Collects the self time of profiled spans into shared [`PhaseTimings`].
+#[derive(Default)]
+pub struct ProfileLayer {
+    timings: PhaseTimings,
+}
+
+impl ProfileLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn timings(&self) -> PhaseTimings {
+        Arc::clone(&self.timings)
+    }
+}
+
+fn process_timings() -> &'static PhaseTimings {
+    static TIMINGS: OnceLock<PhaseTimings> = OnceLock::new();
+    TIMINGS.get_or_init(PhaseTimings::default)
+}
+
+/// A [`ProfileLayer`] collecting into this process's totals (see
+/// [`process_snapshot`]). It sees profiled spans at every level, whatever the
+/// log filter of the other layers is.
+pub fn process_profile_layer<S>() -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    ProfileLayer {
+        timings: Arc::clone(process_timings()),
+    }
+    .with_filter(filter_fn(|metadata| {
+        metadata.is_span() && phase_for_span(metadata.name()).is_some()
+    }))
+}
+
+/// This process's totals so far, by phase.
+pub fn process_snapshot() -> HashMap<String, PhaseTiming> {
+    match process_timings().lock() {
+        Ok(timings) => timings
+            .iter()
+            .map(|(phase, timing)| (phase.to_string(), *timing))
+            .collect(),
+        Err(_) => HashMap::new(),
+    }
+}
+
+/// Move the daemon's work between two of its snapshots out of `background`
+/// into the phases it ran under. `background` keeps only the waiting no
+/// daemon span accounts for.
+pub fn add_daemon_timings(
+    timings: &mut HashMap<&'static str, PhaseTiming>,
+    before: &HashMap<String, PhaseTiming>,
+    after: &HashMap<String, PhaseTiming>,
+) {
+    let mut moved = Duration::ZERO;
+    for phase in PHASES.iter().filter(|phase| **phase != "background") {
+        let Some(end) = after.get(*phase) else {
+            continue;
+        };
+        let start = before.get(*phase).copied().unwrap_or_default();
+        let calls = end.calls.saturating_sub(start.calls);
+        let total = end.total.saturating_sub(start.total);
+        if calls == 0 && total.is_zero() {
+            continue;
+        }
+        let timing = timings.entry(phase).or_default();
+        timing.calls += calls;
+        timing.total += total;
+        moved += total;
+    }
+    if let Some(background) = timings.get_mut("background") {
+        background.total = background.total.saturating_sub(moved);
+    }
+}
+
+/// Stored on each profiled span.
+struct SpanClock {
+    phase: &'static str,
+    started: Instant,
+    /// Time spent in profiled spans nested inside this one.
+    nested: Duration,
+}
+
+impl<S> Layer<S> for ProfileLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        if let Some(phase) = phase_for_span(span.name()) {
+            span.extensions_mut().insert(SpanClock {
+                phase,
+                started: Instant::now(),
+                nested: Duration::ZERO,
+            });
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some((phase, elapsed, nested)) = span
+            .extensions()
+            .get::<SpanClock>()
+            .map(|clock| (clock.phase, clock.started.elapsed(), clock.nested))
+        else {
+            return;
+        };
+
+        if let Ok(mut timings) = self.timings.lock() {
+            let timing = timings.entry(phase).or_default();
+            timing.calls += 1;
+            timing.total += elapsed.saturating_sub(nested);
+        }
+
+        let mut ancestor = span.parent();
+        while let Some(parent) = ancestor {
+            if let Some(clock) = parent.extensions_mut().get_mut::<SpanClock>() {
+                clock.nested += elapsed;
+                break;
+            }
+            ancestor = parent.parent();
+        }
+    }
+}
+
+/// One row of the profile report.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PhaseRow {
+    pub phase: String,
+    pub calls: u32,
+    pub ms: f64,
+    pub share: f64,
+}
+
+/// Report rows in [`PHASES`] order, followed by `other` for wall time no
+/// phase accounts for. Phases that never ran are left out.
+pub fn phase_rows(timings: &HashMap<&'static str, PhaseTiming>, wall: Duration) -> Vec<PhaseRow> {
+    let wall_ms = wall.as_secs_f64() * 1000.0;
+    let share = |ms: f64| {
+        if wall_ms > 0.0 {
+            ms / wall_ms * 100.0
+        } else {
+            0.0
+        }
+    };
+
+    let mut rows = Vec::new();
+    let mut accounted = 0.0;
+    for phase in PHASES {
+        let Some(timing) = timings.get(phase) else {
+            continue;
+        };
+        let ms = timing.total.as_secs_f64() * 1000.0;
+        accounted += ms;
+        rows.push(PhaseRow {
+            phase: phase.to_string(),
+            calls: timing.calls,
+            ms,
+            share: share(ms),
+        });
+    }
+    let other = (wall_ms - accounted).max(0.0);
+    rows.push(PhaseRow {
+        phase: "other".to_string(),
+        calls: 0,
+        ms: other,
+        share: share(other),
+    });
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_nested_profiled_spans_count_as_self_time() {
+        let layer = ProfileLayer::new();
+        let timings = layer.timings();
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _post = tracing::info_span!("post_command").entered();
+            std::thread::sleep(Duration::from_millis(5));
+            for _ in 0..2 {
+                let _unprofiled = tracing::info_span!("stats").entered();
+                let _read = tracing::debug_span!("read_note").entered();
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        });
+
+        let timings = timings.lock().unwrap();
+        let notes = timings["notes io"];
+        let post = timings["post-command"];
+        assert_eq!(notes.calls, 2);
+        assert_eq!(post.calls, 1);
+        assert!(notes.total >= Duration::from_millis(20));
+        assert!(post.total >= Duration::from_millis(5));
+        assert!(
+            post.total < Duration::from_millis(20),
+            "nested notes reads must not count as post-command time: {:?}",
+            post.total
+        );
+    }
+
+    #[test]
+    fn test_profiled_debug_spans_are_seen_under_an_info_log_filter() {
+        let before = process_snapshot();
+        let subscriber = tracing_subscriber::registry()
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_writer(std::io::sink)
+                    .with_filter(tracing_subscriber::EnvFilter::new("info")),
+            )
+            .with(process_profile_layer());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _checkpoint = tracing::debug_span!("checkpoint").entered();
+            let _unprofiled = tracing::debug_span!("stats").entered();
+        });
+
+        let after = process_snapshot();
+        let calls = |snapshot: &HashMap<String, PhaseTiming>| {
+            snapshot.get("checkpoint").map_or(0, |timing| timing.calls)
+        };
+        assert!(calls(&after) > calls(&before));
+    }
+
+    #[test]
+    fn test_daemon_timings_move_out_of_background() {
+        let ms = Duration::from_millis;
+        let mut timings = HashMap::from([
+            (
+                "exec",
+                PhaseTiming {
+                    calls: 1,
+                    total: ms(30),
+                },
+            ),
+            (
+                "background",
+                PhaseTiming {
+                    calls: 1,
+                    total: ms(50),
+                },
+            ),
+        ]);
+        let before = HashMap::from([(
+            "checkpoint".to_string(),
+            PhaseTiming {
+                calls: 4,
+                total: ms(100),
+            },
+        )]);
+        let after = HashMap::from([
+            (
+                "checkpoint".to_string(),
+                PhaseTiming {
+                    calls: 5,
+                    total: ms(120),
+                },
+            ),
+            (
+                "notes io".to_string(),
+                PhaseTiming {
+                    calls: 2,
+                    total: ms(15),
+                },
+            ),
+            (
+                "background".to_string(),
+                PhaseTiming {
+                    calls: 9,
+                    total: ms(900),
+                },
+            ),
+        ]);
+
+        add_daemon_timings(&mut timings, &before, &after);
+
+        assert_eq!(
+            timings["checkpoint"],
+            PhaseTiming {
+                calls: 1,
+                total: ms(20)
+            }
+        );
+        assert_eq!(timings["notes io"].total, ms(15));
+        assert_eq!(timings["exec"].total, ms(30));
+        assert_eq!(
+            timings["background"],
+            PhaseTiming {
+                calls: 1,
+                total: ms(15)
+            }
+        );
+    }
+
+    #[test]
+    fn test_phase_rows_follow_report_order_and_add_other() {
+        let timings = HashMap::from([
+            (
+                "exec",
+                PhaseTiming {
+                    calls: 1,
+                    total: Duration::from_millis(60),
+                },
+            ),
+            (
+                "parse",
+                PhaseTiming {
+                    calls: 1,
+                    total: Duration::from_millis(10),
+                },
+            ),
+        ]);
+
+        let rows = phase_rows(&timings, Duration::from_millis(100));
+        let summary: Vec<(&str, u32, i64)> = rows
+            .iter()
+            .map(|row| (row.phase.as_str(), row.calls, row.share.round() as i64))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("parse", 1, 10), ("exec", 1, 60), ("other", 0, 30)]
+        );
+    }
+}
@@ -0,0 +1,293 @@
+//! Opt-in structured tracing for bug reports.
+//!
+//! `GIT_AI_TRACE` turns on a tracing subscriber for CLI and git-proxy
+//! invocations. It takes a level (`error`..`trace`, or `1` for `debug`) or full
+//! filter directives using the subsystem targets in [`targets`], e.g.
+//! `GIT_AI_TRACE=rewrite=trace,sync=debug,warn`. Output goes to stderr, or as
+//! JSON lines to `GIT_AI_TRACE_FILE` (falling back to the `trace_file` config
+//! key), in which case the file alone also enables tracing at `debug`.
+//!
+//! Each JSON line is an `event`, `span_start` or `span_end` record; span ends
+//! carry `duration_ms`, so hook and rewrite timings can be read off directly.
+
+use serde_json::{Map, Value, json};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+pub const TRACE_ENV: &str = "GIT_AI_TRACE";
+pub const TRACE_FILE_ENV: &str = "GIT_AI_TRACE_FILE";
+
+/// Tracing targets for the main subsystems, usable in `GIT_AI_TRACE` directives.
+pub mod targets {
+    /// Git command wrapping, agent checkpoint hooks and `git_ai_hooks` commands.
+    pub const HOOKS: &str = "hooks";
+    /// Authorship rewrites after rebase, cherry-pick, revert, amend and friends.
+    pub const REWRITE: &str = "rewrite";
+    /// Authorship note reads and writes.
+    pub const STORAGE: &str = "storage";
+    /// Fetching and pushing authorship notes.
+    pub const SYNC: &str = "sync";
+}
+
+/// Filter directives for a `GIT_AI_TRACE` value, or `None` when tracing is off.
+pub fn trace_filter_directives(value: &str) -> Option<String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "" | "0" | "false" | "off" => None,
+        "1" | "true" | "on" => Some("debug".to_string()),
+        _ => Some(value.trim().to_string()),
+    }
+}
+
+/// Install the tracing subscriber for this process if tracing was requested.
+/// A no-op when another subscriber is already installed.
+pub fn init_cli_tracing() {
+    use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
+
+    let directives = std::env::var(TRACE_ENV)
+        .ok()
+        .and_then(|value| trace_filter_directives(&value));
+    let json_layer = json_file_layer();
+    let directives = match (directives, json_layer.is_some()) {
+        (Some(directives), _) => directives,
+        (None, true) => "debug".to_string(),
+        (None, false) => return,
+    };
+    let filter = EnvFilter::try_new(&directives).unwrap_or_else(|e| {
+        eprintln!(
+            "[git-ai] invalid {} value '{}': {}",
+            TRACE_ENV, directives, e
+        );
+        EnvFilter::new("debug")
+    });
+
+    let stderr_layer = json_layer.is_none().then(|| {
+        tracing_subscriber::fmt::layer()
+            .with_ansi(false)
+            .with_writer(std::io::stderr)
+    });
+    let _ = tracing_subscriber::registry()
+        .with(filter)
+        .with(stderr_layer)
+        .with(json_layer)
+        .try_init();
+}
+
+/// JSON lines sink for `GIT_AI_TRACE_FILE` / the `trace_file` config key.
+pub fn json_file_layer() -> Option<JsonTraceLayer> {
+    let path = trace_file_path()?;
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    match OpenOptions::new().create(true).append(true).open(&path) {
+        Ok(file) => Some(JsonTraceLayer {
+            file: Mutex::new(file),
+        }),
+        Err(e) => {
+            eprintln!("[git-ai] cannot open trace file {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+fn trace_file_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var(TRACE_FILE_ENV)
+        && !path.trim().is_empty()
+    {
+        return Some(PathBuf::from(path));
+    }
+    crate::config::load_effective_file_config()
+        .ok()?
+        .trace_file
+        .filter(|path| !path.trim().is_empty())
+        .map(PathBuf::from)
+}
+
+/// Writes every event and span boundary as one JSON object per line.
+pub struct JsonTraceLayer {
+    file: Mutex<File>,
+}
+
+/// Stored on each span so its end record can report fields and duration.
+struct SpanTiming {
+    started: Instant,
+    fields: Map<String, Value>,
+}
+
+impl JsonTraceLayer {
+    fn write_record(&self, record: Value) {
+        let Ok(mut line) = serde_json::to_vec(&record) else {
+            return;
+        };
+        line.push(b'\n');
+        if let Ok(mut file) = self.file.lock() {
+            // Unbuffered so records survive `std::process::exit`.
+            let _ = file.write_all(&line);
+        }
+    }
+}
+
+fn base_record(kind: &str, metadata: &tracing::Metadata<'_>) -> Map<String, Value> {
+    let mut record = Map::new();
+    record.insert("ts".to_string(), json!(chrono::Utc::now().to_rfc3339()));
+    record.insert("pid".to_string(), json!(std::process::id()));
+    record.insert("kind".to_string(), json!(kind));
+    record.insert("level".to_string(), json!(metadata.level().as_str()));
+    record.insert("target".to_string(), json!(metadata.target()));
+    if kind != "event" {
+        record.insert("name".to_string(), json!(metadata.name()));
+    }
+    record
+}
+
+impl<S> Layer<S> for JsonTraceLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = JsonVisitor::default();
+        attrs.record(&mut visitor);
+
+        let mut record = base_record("span_start", span.metadata());
+        record.insert("fields".to_string(), Value::Object(visitor.fields.clone()));
+        self.write_record(Value::Object(record));
+
+        span.extensions_mut().insert(SpanTiming {
+            started: Instant::now(),
+            fields: visitor.fields,
+        });
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut visitor = JsonVisitor::default();
+        event.record(&mut visitor);
+
+        let mut record = base_record("event", event.metadata());
+        record.insert("message".to_string(), json!(visitor.message));
+        record.insert("fields".to_string(), Value::Object(visitor.fields));
+        if let Some(scope) = ctx.event_scope(event) {
+            let spans: Vec<Value> = scope.from_root().map(|span| json!(span.name())).collect();
+            record.insert("spans".to_string(), Value::Array(spans));
+        }
+        self.write_record(Value::Object(record));
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let mut record = base_record("span_end", span.metadata());
+        if let Some(timing) = span.extensions().get::<SpanTiming>() {
+            record.insert(
+                "duration_ms".to_string(),
+                json!(timing.started.elapsed().as_secs_f64() * 1000.0),
+            );
+            record.insert("fields".to_string(), Value::Object(timing.fields.clone()));
+        }
+        self.write_record(Value::Object(record));
+    }
+}
+
+#[derive(Default)]
+struct JsonVisitor {
+    message: String,
+    fields: Map<String, Value>,
+}
+
+impl Visit for JsonVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        let value = format!("{:?}", value);
+        if field.name() == "message" {
+            self.message = value;
+        } else {
+            self.fields.insert(field.name().to_string(), json!(value));
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.fields.insert(field.name().to_string(), json!(value));
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.fields.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.fields.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.fields.insert(field.name().to_string(), json!(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_trace_filter_directives() {
+        assert_eq!(trace_filter_directives(""), None);
+        assert_eq!(trace_filter_directives("0"), None);
+        assert_eq!(trace_filter_directives("off"), None);
+        assert_eq!(trace_filter_directives("1").as_deref(), Some("debug"));
+        assert_eq!(trace_filter_directives("trace").as_deref(), Some("trace"));
+        assert_eq!(
+            trace_filter_directives(" rewrite=trace,info ").as_deref(),
+            Some("rewrite=trace,info")
+        );
+    }
+
+    #[test]
+    fn test_json_layer_writes_events_and_span_boundaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trace.jsonl");
+        let layer = JsonTraceLayer {
+            file: Mutex::new(File::create(&path).unwrap()),
+        };
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _span =
+                tracing::info_span!(target: targets::HOOKS, "checkpoint", preset = "mock_ai")
+                    .entered();
+            tracing::debug!(target: targets::STORAGE, notes = 2u64, "wrote notes");
+        });
+
+        let records: Vec<Value> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 3);
+
+        assert_eq!(records[0]["kind"], "span_start");
+        assert_eq!(records[0]["target"], "hooks");
+        assert_eq!(records[0]["fields"]["preset"], "mock_ai");
+
+        assert_eq!(records[1]["kind"], "event");
+        assert_eq!(records[1]["level"], "DEBUG");
+        assert_eq!(records[1]["message"], "wrote notes");
+        assert_eq!(records[1]["fields"]["notes"], 2);
+        assert_eq!(records[1]["spans"], json!(["checkpoint"]));
+
+        assert_eq!(records[2]["kind"], "span_end");
+        assert_eq!(records[2]["name"], "checkpoint");
+        assert!(records[2]["duration_ms"].as_f64().is_some());
+    }
+}
@@ -38,6 +38,25 @@ pub fn normalize_repo_url(url_str: &str) -> Result<String, String> {
     Ok(canonical)
 }
 
+/// A `url.<base>.insteadOf = <prefix>` rewrite from git config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlRewrite {
+    pub base: String,
+    pub instead_of: String,
+}
+
+/// Rewrite a remote URL the way git does before fetching: the longest
+/// `insteadOf` prefix that matches is replaced by its base. URLs no rewrite
+/// matches are returned unchanged.
+pub fn apply_url_rewrites(url: &str, rewrites: &[UrlRewrite]) -> String {
+    rewrites
+        .iter()
+        .filter(|rewrite| !rewrite.instead_of.is_empty() && url.starts_with(&rewrite.instead_of))
+        .max_by_key(|rewrite| rewrite.instead_of.len())
+        .map(|rewrite| format!("{}{}", rewrite.base, &url[rewrite.instead_of.len()..]))
+        .unwrap_or_else(|| url.to_string())
+}
+
 /// Validate that normalized URL is a proper HTTPS URL
 fn validate_normalized_url(url_str: &str) -> Result<(), String> {
     let url = Url::parse(url_str).map_err(|e| format!("Failed to parse normalized URL: {}", e))?;
@@ -80,8 +99,11 @@ fn normalize_ssh_url(host: &str, path: &str) -> Result<String, String> {
 
 /// Resolve a normalized repo URL from an already-opened Repository.
 ///
-/// Finds the default remote and normalizes its URL to canonical HTTPS format.
+/// Finds the default remote and normalizes its URL (after `insteadOf` rewrites;
+/// see [`Repository::remotes_with_urls`]) to canonical HTTPS format.
 /// Returns None if there is no remote or the URL cannot be normalized.
+///
+/// [`Repository::remotes_with_urls`]: crate::git::repository::Repository::remotes_with_urls
 pub fn resolve_repo_url_from_repo(repo: &crate::git::repository::Repository) -> Option<String> {
     let remote_name = repo.get_default_remote().ok()??;
     let remotes = repo.remotes_with_urls().ok()?;
@@ -102,7 +124,48 @@ pub fn resolve_repo_url_from_path(work_dir: &std::path::Path) -> Option<String>
 
 #[cfg(test)]
 mod tests {
-    use super::normalize_repo_url;
+    use super::{UrlRewrite, apply_url_rewrites, normalize_repo_url};
+
+    fn rewrite(base: &str, instead_of: &str) -> UrlRewrite {
+        UrlRewrite {
+            base: base.to_string(),
+            instead_of: instead_of.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_apply_url_rewrites_longest_prefix_wins() {
+        let rewrites = vec![
+            rewrite("git@github.com:", "gh:"),
+            rewrite("https://github.com/", "https://mirror.internal/"),
+            rewrite("git@github.com:acme/", "https://mirror.internal/acme/"),
+        ];
+        assert_eq!(
+            apply_url_rewrites("gh:user/repo.git", &rewrites),
+            "git@github.com:user/repo.git"
+        );
+        assert_eq!(
+            apply_url_rewrites("https://mirror.internal/acme/app", &rewrites),
+            "git@github.com:acme/app"
+        );
+        assert_eq!(
+            apply_url_rewrites("https://mirror.internal/other/app", &rewrites),
+            "https://github.com/other/app"
+        );
+        assert_eq!(
+            apply_url_rewrites("https://gitlab.com/user/repo", &rewrites),
+            "https://gitlab.com/user/repo"
+        );
+    }
+
+    #[test]
+    fn test_rewritten_urls_normalize_to_the_same_repo() {
+        let rewrites = vec![rewrite("https://github.com/", "gh:")];
+        assert_eq!(
+            normalize_repo_url(&apply_url_rewrites("gh:user/repo", &rewrites)).unwrap(),
+            normalize_repo_url("git@github.com:user/repo.git").unwrap()
+        );
+    }
 
     #[test]
     fn test_normalize_repo_url_https() {
@@ -199,7 +199,9 @@ pub fn get_agent(agent_type: &str) -> Option<Box<dyn Agent>> {
         "claude" => Some(Box::new(super::agents::ClaudeAgent::new())),
         "cursor" => Some(Box::new(super::agents::CursorAgent::new())),
         "droid" => Some(Box::new(super::agents::DroidAgent::new())),
-        "copilot" | "github-copilot" => Some(Box::new(super::agents::CopilotAgent::new())),
+        "copilot" | "github-copilot" | "copilot-agent" => {
+            Some(Box::new(super::agents::CopilotAgent::new()))
+        }
         "copilot-cli" | "github-copilot-cli" => {
             Some(Box::new(super::agents::CopilotCliAgent::new()))
         }
@@ -228,6 +228,12 @@ impl Agent for CopilotAgent {
     fn discover_sessions(&self) -> Result<Vec<DiscoveredSession>, StreamError> {
         let paths = Self::scan_transcript_files();
         let mut sessions = Vec::new();
+        // Match the tool the hooks attribute VS Code sessions to.
+        let tool = if crate::mdm::agents::copilot_agent_hooks_installed() {
+            "copilot-agent"
+        } else {
+            "github-copilot"
+        };
 
         for path in paths {
             // Copilot chat_session_id from the hook payload matches the file stem
@@ -238,11 +244,11 @@ impl Agent for CopilotAgent {
             else {
                 continue;
             };
-            let session_id = generate_session_id(&external_session_id, "github-copilot");
+            let session_id = generate_session_id(&external_session_id, tool);
 
             let session = DiscoveredSession {
                 session_id,
-                tool: "github-copilot".to_string(),
+                tool: tool.to_string(),
                 stream_path: path,
                 external_session_id,
                 external_parent_session_id: None,
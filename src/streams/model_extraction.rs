@@ -40,32 +40,39 @@ pub fn extract_model_from_droid_settings(
     Ok(json.get("model").and_then(|v| v.as_str()).map(String::from))
 }
 
-fn extract_model_from_jsonl_tail(path: &Path) -> Result<Option<String>, StreamError> {
-    let mut file = match File::open(path) {
-        Ok(f) => f,
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
-        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => return Ok(None),
-        Err(_) => return Ok(None),
-    };
-
-    let file_size = match file.metadata() {
-        Ok(m) => m.len(),
-        Err(_) => return Ok(None),
-    };
+/// Version of the agent that wrote a Claude Code transcript, taken from the
+/// `version` field of its most recent entry.
+pub fn extract_claude_code_version(path: &Path) -> Option<String> {
+    let (lines, _) = read_jsonl_tail(path)?;
+    lines.iter().rev().find_map(|line| {
+        let json: serde_json::Value = serde_json::from_str(line.trim()).ok()?;
+        json.get("version")
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+            .map(String::from)
+    })
+}
 
+/// Lines in the last 50 KiB of `path`, with the offset the tail starts at.
+fn read_jsonl_tail(path: &Path) -> Option<(Vec<String>, u64)> {
+    let mut file = File::open(path).ok()?;
+    let file_size = file.metadata().ok()?.len();
     if file_size == 0 {
-        return Ok(None);
+        return None;
     }
 
     let read_size = std::cmp::min(51200, file_size);
     let seek_pos = file_size - read_size;
-
-    if file.seek(SeekFrom::Start(seek_pos)).is_err() {
-        return Ok(None);
-    }
+    file.seek(SeekFrom::Start(seek_pos)).ok()?;
 
     let reader = BufReader::new(file);
-    let lines: Vec<String> = reader.lines().map_while(Result::ok).collect();
+    Some((reader.lines().map_while(Result::ok).collect(), seek_pos))
+}
+
+fn extract_model_from_jsonl_tail(path: &Path) -> Result<Option<String>, StreamError> {
+    let Some((lines, seek_pos)) = read_jsonl_tail(path) else {
+        return Ok(None);
+    };
 
     for line in lines.iter().rev() {
         if let Some(model) = extract_model_from_jsonl_line(line) {
@@ -462,6 +469,19 @@ mod tests {
         assert_eq!(result, Some("claude-sonnet-4-20250514".to_string()));
     }
 
+    #[test]
+    fn test_extract_claude_code_version() {
+        let path = fixture_path("example-claude-code.jsonl");
+        assert_eq!(
+            extract_claude_code_version(&path),
+            Some("1.0.111".to_string())
+        );
+        assert_eq!(
+            extract_claude_code_version(Path::new("/nonexistent/transcript.jsonl")),
+            None
+        );
+    }
+
     #[test]
     fn test_extract_model_droid_settings() {
         let path = fixture_path("droid-session.settings.json");
@@ -0,0 +1,422 @@
+//! Scratch-repository harness for downstream integration tests.
+//!
+//! Available with the `test-support` feature. A [`TestRepo`] owns a temporary
+//! repository, an isolated `HOME`, and a dedicated `git-ai` daemon, so agent
+//! integrations and IDE plugins can drive checkpoints and git commands through a
+//! real `git-ai` binary and inspect the authorship notes it writes:
+//!
+//! ```ignore
+//! use git_ai::test_support::TestRepo;
+//!
+//! let repo = TestRepo::new()?;
+//! repo.write_file("notes.txt", "one\n")?;
+//! repo.stage_all_and_commit("Initial commit")?;
+//!
+//! repo.write_file("notes.txt", "one\ntwo\n")?;
+//! repo.git_ai(&["checkpoint", "mock_ai", "notes.txt"])?;
+//! let commit = repo.stage_all_and_commit("Agent edit")?;
+//! assert!(repo.authorship_log(&commit)?.is_some());
+//! ```
+//!
+//! The binary is taken from `GIT_AI_TEST_BINARY_PATH` (falling back to `git-ai`
+//! on `PATH`) and must be built with the `test-support` feature so the daemon
+//! reports command completions the harness waits on.
+
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::daemon::test_sync::{
+    TEST_SYNC_SESSION_CONFIG_KEY, tracks_parsed_git_invocation_for_test_sync,
+};
+use crate::daemon::{
+    ControlRequest, DaemonConfig, local_socket_connects_with_timeout, send_control_request,
+};
+use crate::error::GitAiError;
+use crate::git::cli_parser::parse_git_cli_args;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+/// Environment variable naming the `git-ai` binary under test.
+pub const TEST_BINARY_PATH_ENV: &str = "GIT_AI_TEST_BINARY_PATH";
+
+const DAEMON_READY_TIMEOUT: Duration = Duration::from_secs(60);
+const DAEMON_PROBE_TIMEOUT: Duration = Duration::from_millis(100);
+const DAEMON_COMPLETION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A scratch git repository wired to its own `git-ai` daemon.
+///
+/// The repository, home directory and daemon are torn down on drop.
+pub struct TestRepo {
+    _tmp: TempDir,
+    path: PathBuf,
+    home: PathBuf,
+    binary: PathBuf,
+    daemon: Child,
+    sync_sessions: AtomicUsize,
+    /// Checkpoint requests handed to the daemon so far.
+    checkpoints_sent: AtomicUsize,
+}
+
+impl TestRepo {
+    /// Create a repo using the binary from `GIT_AI_TEST_BINARY_PATH`, or `git-ai`
+    /// from `PATH` when the variable is unset.
+    pub fn new() -> Result<Self, GitAiError> {
+        let binary = std::env::var_os(TEST_BINARY_PATH_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("git-ai"));
+        Self::with_binary(binary)
+    }
+
+    /// Create a repo driven by the given `git-ai` binary.
+    pub fn with_binary(binary: impl Into<PathBuf>) -> Result<Self, GitAiError> {
+        let tmp = tempfile::tempdir()?;
+        let home = tmp.path().join("home");
+        let path = tmp.path().join("repo");
+        fs::create_dir_all(&home)?;
+        fs::create_dir_all(&path)?;
+        let home = home.canonicalize()?;
+        let path = path.canonicalize()?;
+
+        let daemon_config = DaemonConfig::from_home(&home);
+        let daemon_log_dir = daemon_config.internal_dir.join("daemon");
+        fs::create_dir_all(&daemon_log_dir)?;
+        let daemon_stderr = fs::File::create(daemon_log_dir.join("daemon.test.stderr.log"))?;
+
+        let binary = binary.into();
+        let mut command = Command::new(&binary);
+        command
+            .args(["bg", "run"])
+            .current_dir(&home)
+            .stdout(Stdio::null())
+            .stderr(daemon_stderr);
+        configure_git_ai_env(&mut command, &home);
+        let daemon = command.spawn().map_err(|e| {
+            GitAiError::Generic(format!(
+                "failed to start git-ai daemon from {}: {}",
+                binary.display(),
+                e
+            ))
+        })?;
+
+        let repo = Self {
+            _tmp: tmp,
+            path,
+            home,
+            binary,
+            daemon,
+            sync_sessions: AtomicUsize::new(0),
+            checkpoints_sent: AtomicUsize::new(0),
+        };
+        repo.wait_for_daemon()?;
+
+        repo.git(&["init", "-b", "main"])?;
+        for (key, value) in [
+            ("user.name", "git-ai test"),
+            ("user.email", "test@git-ai.local"),
+            ("commit.gpgsign", "false"),
+        ] {
+            repo.git(&["config", key, value])?;
+        }
+        Ok(repo)
+    }
+
+    /// Working-tree path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Isolated `HOME` used by git, git-ai and the daemon.
+    pub fn home(&self) -> &Path {
+        &self.home
+    }
+
+    /// Write a file relative to the working tree and return its absolute path.
+    pub fn write_file(&self, name: &str, contents: &str) -> Result<PathBuf, GitAiError> {
+        let file_path = self.path.join(name);
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&file_path, contents)?;
+        Ok(file_path)
+    }
+
+    /// Read a file relative to the working tree.
+    pub fn read_file(&self, name: &str) -> Result<String, GitAiError> {
+        Ok(fs::read_to_string(self.path.join(name))?)
+    }
+
+    /// Run a git command in the repo and return its combined output.
+    ///
+    /// Commands the daemon tracks (commit, rebase, cherry-pick, ...) are
+    /// followed by a wait until the daemon has applied their side effects.
+    pub fn git(&self, args: &[&str]) -> Result<String, GitAiError> {
+        let argv: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        let session = tracks_parsed_git_invocation_for_test_sync(&parse_git_cli_args(&argv))
+            .then(|| self.next_sync_session());
+
+        let mut command = Command::new("git");
+        command.arg("-C").arg(&self.path);
+        if let Some(session) = &session {
+            command
+                .arg("-c")
+                .arg(format!("{}={}", TEST_SYNC_SESSION_CONFIG_KEY, session));
+        }
+        command.args(args);
+        configure_isolated_env(&mut command, &self.home);
+        let trace_socket = DaemonConfig::from_home(&self.home).trace_socket_path;
+        command
+            .env(
+                "GIT_TRACE2_EVENT",
+                DaemonConfig::trace2_event_target_for_path(&trace_socket),
+            )
+            .env("GIT_TRACE2_EVENT_NESTING", "0");
+
+        let result = command_result(command.output()?, "git", args);
+        if let Some(session) = &session {
+            self.wait_for_completion(session)?;
+        }
+        result
+    }
+
+    /// Run a `git-ai` command in the repo and return its combined output.
+    pub fn git_ai(&self, args: &[&str]) -> Result<String, GitAiError> {
+        self.git_ai_with_stdin(args, None)
+    }
+
+    /// Run a `git-ai` command with `stdin` (e.g. an agent hook payload for
+    /// `checkpoint <preset> --hook-input stdin`).
+    pub fn git_ai_with_stdin(
+        &self,
+        args: &[&str],
+        stdin: Option<&[u8]>,
+    ) -> Result<String, GitAiError> {
+        let mut command = Command::new(&self.binary);
+        command
+            .args(args)
+            .current_dir(&self.path)
+            .stdin(if stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        configure_git_ai_env(&mut command, &self.home);
+        command.env("GIT_AI_DAEMON_CHECKPOINT_DELEGATE", "true");
+
+        let mut child = command.spawn()?;
+        if let Some(data) = stdin
+            && let Some(mut child_stdin) = child.stdin.take()
+        {
+            child_stdin.write_all(data)?;
+        }
+        let output = child.wait_with_output()?;
+        // Checkpoints are delegated to the daemon; wait until it has applied them.
+        let sent = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find_map(|line| line.strip_prefix("checkpoint_requests="))
+            .and_then(|count| count.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+        if sent > 0 {
+            let expected = self.checkpoints_sent.fetch_add(sent, Ordering::SeqCst) + sent;
+            self.wait_for_completions(|entries| {
+                entries
+                    .iter()
+                    .filter(|entry| entry["kind"].as_str() == Some("checkpoint"))
+                    .count()
+                    >= expected
+            })?;
+        }
+        let result = command_result(output, "git-ai", args);
+        self.sync()?;
+        result
+    }
+
+    /// Stage every change and commit it, returning the new commit SHA.
+    pub fn stage_all_and_commit(&self, message: &str) -> Result<String, GitAiError> {
+        self.git(&["add", "-A"])?;
+        self.git(&["commit", "-m", message])?;
+        Ok(self.git(&["rev-parse", "HEAD"])?.trim().to_string())
+    }
+
+    /// The authorship note attached to `commit`, if git-ai wrote one.
+    pub fn authorship_log(&self, commit: &str) -> Result<Option<AuthorshipLog>, GitAiError> {
+        let Ok(note) = self.git(&["notes", "--ref=ai", "show", commit]) else {
+            return Ok(None);
+        };
+        if note.trim().is_empty() {
+            return Ok(None);
+        }
+        AuthorshipLog::deserialize_from_string(&note)
+            .map(Some)
+            .map_err(|e| GitAiError::Generic(format!("invalid authorship note: {}", e)))
+    }
+
+    /// Wait until the daemon has drained all queued work for this repo.
+    pub fn sync(&self) -> Result<(), GitAiError> {
+        let response = send_control_request(
+            &DaemonConfig::from_home(&self.home).control_socket_path,
+            &ControlRequest::SyncFamily {
+                repo_working_dir: self.path.to_string_lossy().to_string(),
+            },
+        )?;
+        if response.ok {
+            Ok(())
+        } else {
+            Err(GitAiError::Generic(format!(
+                "daemon sync failed: {}",
+                response
+                    .error
+                    .unwrap_or_else(|| "unknown error".to_string())
+            )))
+        }
+    }
+
+    fn next_sync_session(&self) -> String {
+        let id = self.sync_sessions.fetch_add(1, Ordering::SeqCst) + 1;
+        format!("test-support-{}-{}", std::process::id(), id)
+    }
+
+    fn wait_for_daemon(&self) -> Result<(), GitAiError> {
+        let control_socket = DaemonConfig::from_home(&self.home).control_socket_path;
+        let start = Instant::now();
+        while start.elapsed() < DAEMON_READY_TIMEOUT {
+            if local_socket_connects_with_timeout(&control_socket, DAEMON_PROBE_TIMEOUT).is_ok()
+                && send_control_request(&control_socket, &ControlRequest::Ping)
+                    .is_ok_and(|response| response.ok)
+            {
+                return Ok(());
+            }
+            std::thread::sleep(Duration::from_millis(25));
+        }
+        Err(GitAiError::Generic(format!(
+            "git-ai daemon did not become ready within {:?}",
+            DAEMON_READY_TIMEOUT
+        )))
+    }
+
+    /// Wait for the daemon's completion log to record the tracked command.
+    fn wait_for_completion(&self, session: &str) -> Result<(), GitAiError> {
+        self.wait_for_completions(|entries| {
+            entries
+                .iter()
+                .any(|entry| entry["test_sync_session"].as_str() == Some(session))
+        })?;
+        self.sync()
+    }
+
+    /// Poll the daemon's completion log until `done` holds, failing fast on an
+    /// entry reporting an error.
+    fn wait_for_completions(
+        &self,
+        done: impl Fn(&[serde_json::Value]) -> bool,
+    ) -> Result<(), GitAiError> {
+        let family_key = self
+            .path
+            .join(".git")
+            .canonicalize()?
+            .to_string_lossy()
+            .to_string();
+        let log_path =
+            DaemonConfig::from_home(&self.home).test_completion_log_path_for_family(&family_key);
+        let start = Instant::now();
+        while start.elapsed() < DAEMON_COMPLETION_TIMEOUT {
+            let entries: Vec<serde_json::Value> = fs::read_to_string(&log_path)
+                .unwrap_or_default()
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .filter(|entry: &serde_json::Value| entry["sync_tracked"].as_bool() == Some(true))
+                .collect();
+            if let Some(failed) = entries
+                .iter()
+                .find(|entry| entry["status"].as_str() == Some("error"))
+            {
+                return Err(GitAiError::Generic(format!(
+                    "daemon failed to process a command: {}",
+                    failed["error"].as_str().unwrap_or("unknown error")
+                )));
+            }
+            if done(&entries) {
+                return Ok(());
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        Err(GitAiError::Generic(format!(
+            "daemon did not finish processing within {:?}",
+            DAEMON_COMPLETION_TIMEOUT
+        )))
+    }
+}
+
+impl Drop for TestRepo {
+    fn drop(&mut self) {
+        let _ = send_control_request(
+            &DaemonConfig::from_home(&self.home).control_socket_path,
+            &ControlRequest::Shutdown,
+        );
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while Instant::now() < deadline {
+            if matches!(self.daemon.try_wait(), Ok(Some(_))) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let _ = self.daemon.kill();
+        let _ = self.daemon.wait();
+    }
+}
+
+/// Keep git and git-ai away from the developer's config and state.
+fn configure_isolated_env(command: &mut Command, home: &Path) {
+    command
+        .env("HOME", home)
+        .env("GIT_CONFIG_GLOBAL", home.join(".gitconfig"))
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("GIT_CONFIG_NOSYSTEM", "1")
+        .env(
+            "GIT_AI_TEST_NOTES_DB_PATH",
+            home.join(".git-ai").join("internal").join("notes-db"),
+        );
+    #[cfg(windows)]
+    command
+        .env("USERPROFILE", home)
+        .env("APPDATA", home.join("AppData").join("Roaming"))
+        .env("LOCALAPPDATA", home.join("AppData").join("Local"));
+}
+
+fn configure_git_ai_env(command: &mut Command, home: &Path) {
+    configure_isolated_env(command, home);
+    let daemon_config = DaemonConfig::from_home(home);
+    let test_db_path = daemon_config.internal_dir.join("test-db");
+    command
+        .env("GIT_AI_DAEMON_HOME", home)
+        .env(
+            "GIT_AI_DAEMON_CONTROL_SOCKET",
+            &daemon_config.control_socket_path,
+        )
+        .env(
+            "GIT_AI_DAEMON_TRACE_SOCKET",
+            &daemon_config.trace_socket_path,
+        )
+        .env("GIT_AI_TEST_DB_PATH", &test_db_path)
+        .env("GITAI_TEST_DB_PATH", &test_db_path);
+}
+
+fn command_result(output: Output, program: &str, args: &[&str]) -> Result<String, GitAiError> {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if output.status.success() {
+        Ok(format!("{}{}", stdout, stderr))
+    } else {
+        Err(GitAiError::Generic(format!(
+            "`{} {}` failed ({}): {}",
+            program,
+            args.join(" "),
+            output.status,
+            stderr.trim()
+        )))
+    }
+}
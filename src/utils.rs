@@ -65,6 +65,18 @@ fn resolve_git_ai_exe_from_invocation_path(path: PathBuf) -> PathBuf {
     canonical_path
 }
 
+/// Unix seconds as local `YYYY-MM-DD HH:MM` for human-readable listings, or
+/// the raw number when out of range.
+pub fn format_local_timestamp(secs: i64) -> String {
+    chrono::DateTime::from_timestamp(secs, 0)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| secs.to_string())
+}
+
 pub(crate) fn current_git_ai_exe() -> Result<PathBuf, GitAiError> {
     let path = std::env::current_exe()?;
     Ok(resolve_git_ai_exe_from_invocation_path(path))
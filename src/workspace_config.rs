@@ -0,0 +1,356 @@
+//! Project settings from a `.git-ai.toml` committed at the repository root.
+//!
+//! Teams version their attribution policy alongside the code:
+//!
+//! ```toml
+//! exclude_paths = ["generated/**", "*.pb.rs"]
+//! required_trailers = ["AI-Attribution", "Reviewed-by"]
+//! test_paths = ["tests/**", "*_test.go"]
+//! rebase_exec_author = "prettier"
+//! ignore_whitespace = "reindent"
+//!
+//! [policy]
+//! max_ai_share_pct = 80
+//! ai_secret_scan = "block"
+//!
+//! [normalize.tools]
+//! "claude-code" = "claude"
+//!
+//! [normalize.models]
+//! "claude-sonnet-4-20250514" = "claude-sonnet-4"
+//! ```
+//!
+//! The same settings can be set per user under the `workspace` key of the
+//! global config. The repository file sits underneath it: lists are combined,
+//! and the user's policy values and normalization entries win.
+
+use crate::error::GitAiError;
+use crate::git::repository::Repository;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+pub const WORKSPACE_CONFIG_FILE: &str = ".git-ai.toml";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceConfig {
+    /// Globs excluded from attribution stats, like `.git-ai-ignore` entries.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exclude_paths: Vec<String>,
+    /// Trailer keys every `git-ai commit` message must carry.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub required_trailers: Vec<String>,
+    /// Globs for test code, replacing the built-in test patterns when set.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub test_paths: Vec<String>,
+    /// Who is credited with lines `git rebase --exec` steps change: `human`
+    /// (the default) or a tool name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rebase_exec_author: Option<String>,
+    /// Leave whitespace-only changes out of stats, rollups, exports and the
+    /// `max_ai_share_pct` check (see [`WhitespaceMode`]).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_whitespace: Option<WhitespaceMode>,
+    #[serde(skip_serializing_if = "PolicyConfig::is_empty")]
+    pub policy: PolicyConfig,
+    #[serde(skip_serializing_if = "NormalizationConfig::is_empty")]
+    pub normalize: NormalizationConfig,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PolicyConfig {
+    /// Highest percentage of a commit's added lines that may be AI-authored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_ai_share_pct: Option<f64>,
+    /// Scan AI-authored staged lines for secrets before `git commit`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ai_secret_scan: Option<SecretScanMode>,
+}
+
+/// What the pre-commit secret scan does with AI-authored secrets it finds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretScanMode {
+    #[default]
+    Off,
+    /// Print the findings and commit anyway.
+    Warn,
+    /// Print the findings and refuse the commit.
+    Block,
+}
+
+/// Which changes attribution counts treat as formatter churn rather than edits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WhitespaceMode {
+    /// Count every changed line.
+    #[default]
+    Exact,
+    /// Skip blank lines and lines whose only change is whitespace after the
+    /// indentation (trailing spaces, spacing between tokens).
+    Whitespace,
+    /// Also skip lines whose only change is their indentation.
+    Reindent,
+}
+
+impl PolicyConfig {
+    pub fn is_empty(&self) -> bool {
+        self.max_ai_share_pct.is_none() && self.ai_secret_scan.is_none()
+    }
+
+    pub fn secret_scan_mode(&self) -> SecretScanMode {
+        self.ai_secret_scan.unwrap_or_default()
+    }
+
+    /// The AI share of `added_lines`, when it is above `max_ai_share_pct`.
+    pub fn ai_share_violation(&self, ai_lines: u32, added_lines: u32) -> Option<f64> {
+        let max = self.max_ai_share_pct?;
+        if added_lines == 0 {
+            return None;
+        }
+        let share = f64::from(ai_lines) * 100.0 / f64::from(added_lines);
+        (share > max).then_some(share)
+    }
+}
+
+/// Maps of raw tool and model names to the names a team reports under.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NormalizationConfig {
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub tools: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub models: BTreeMap<String, String>,
+}
+
+impl NormalizationConfig {
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty() && self.models.is_empty()
+    }
+
+    pub fn tool<'a>(&'a self, tool: &'a str) -> &'a str {
+        self.tools.get(tool).map(String::as_str).unwrap_or(tool)
+    }
+
+    pub fn model<'a>(&'a self, model: &'a str) -> &'a str {
+        self.models.get(model).map(String::as_str).unwrap_or(model)
+    }
+}
+
+impl WorkspaceConfig {
+    /// Layer `overrides` (the user's settings) on top of this repository config.
+    pub fn merged_with(mut self, overrides: WorkspaceConfig) -> WorkspaceConfig {
+        extend_unique(&mut self.exclude_paths, overrides.exclude_paths);
+        extend_unique(&mut self.required_trailers, overrides.required_trailers);
+        extend_unique(&mut self.test_paths, overrides.test_paths);
+        if overrides.rebase_exec_author.is_some() {
+            self.rebase_exec_author = overrides.rebase_exec_author;
+        }
+        if overrides.ignore_whitespace.is_some() {
+            self.ignore_whitespace = overrides.ignore_whitespace;
+        }
+        if overrides.policy.max_ai_share_pct.is_some() {
+            self.policy.max_ai_share_pct = overrides.policy.max_ai_share_pct;
+        }
+        if overrides.policy.ai_secret_scan.is_some() {
+            self.policy.ai_secret_scan = overrides.policy.ai_secret_scan;
+        }
+        self.normalize.tools.extend(overrides.normalize.tools);
+        self.normalize.models.extend(overrides.normalize.models);
+        self
+    }
+
+    /// The tool credited with `git rebase --exec` changes, or `None` for human.
+    pub fn rebase_exec_tool(&self) -> Option<&str> {
+        self.rebase_exec_author
+            .as_deref()
+            .map(str::trim)
+            .filter(|author| !author.is_empty() && !author.eq_ignore_ascii_case("human"))
+    }
+
+    pub fn whitespace_mode(&self) -> WhitespaceMode {
+        self.ignore_whitespace.unwrap_or_default()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(max) = self.policy.max_ai_share_pct
+            && !(0.0..=100.0).contains(&max)
+        {
+            return Err(format!(
+                "policy.max_ai_share_pct must be between 0 and 100, got {}",
+                max
+            ));
+        }
+        if let Some(key) = self
+            .required_trailers
+            .iter()
+            .find(|key| key.trim().is_empty() || key.contains([':', ' ']))
+        {
+            return Err(format!(
+                "invalid trailer key '{}' in required_trailers",
+                key
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn extend_unique(target: &mut Vec<String>, values: Vec<String>) {
+    for value in values {
+        if !target.contains(&value) {
+            target.push(value);
+        }
+    }
+}
+
+pub fn parse_workspace_config(contents: &str) -> Result<WorkspaceConfig, GitAiError> {
+    let config: WorkspaceConfig = toml::from_str(contents)
+        .map_err(|e| GitAiError::Generic(format!("{}: {}", WORKSPACE_CONFIG_FILE, e)))?;
+    config
+        .validate()
+        .map_err(|e| GitAiError::Generic(format!("{}: {}", WORKSPACE_CONFIG_FILE, e)))?;
+    Ok(config)
+}
+
+/// The repository's own `.git-ai.toml`, read from HEAD in bare repositories.
+/// A missing file is an empty config; an invalid one is reported and ignored.
+pub fn load_repo_workspace_config(repo: &Repository) -> WorkspaceConfig {
+    let contents = if repo.is_bare_repository().unwrap_or(false) {
+        repo.get_file_content(WORKSPACE_CONFIG_FILE, "HEAD")
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+    } else {
+        repo.workdir()
+            .ok()
+            .and_then(|workdir| fs::read_to_string(workdir.join(WORKSPACE_CONFIG_FILE)).ok())
+    };
+    contents.map(|c| parse_or_warn(&c)).unwrap_or_default()
+}
+
+/// Repository settings merged under the user's `workspace` config.
+pub fn effective_workspace_config(repo: &Repository) -> WorkspaceConfig {
+    load_repo_workspace_config(repo).merged_with(user_workspace_config())
+}
+
+/// Like [`effective_workspace_config`], for callers that only have the repo root.
+pub fn effective_workspace_config_from_path(repo_root: &Path) -> WorkspaceConfig {
+    fs::read_to_string(repo_root.join(WORKSPACE_CONFIG_FILE))
+        .map(|c| parse_or_warn(&c))
+        .unwrap_or_default()
+        .merged_with(user_workspace_config())
+}
+
+fn user_workspace_config() -> WorkspaceConfig {
+    crate::config::load_effective_file_config()
+        .ok()
+        .and_then(|config| config.workspace)
+        .unwrap_or_default()
+}
+
+fn parse_or_warn(contents: &str) -> WorkspaceConfig {
+    parse_workspace_config(contents).unwrap_or_else(|e| {
+        eprintln!("Warning: ignoring invalid {}", e);
+        WorkspaceConfig::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_workspace_config() {
+        let config = parse_workspace_config(
+            r#"
+exclude_paths = ["generated/**"]
+required_trailers = ["AI-Attribution"]
+test_paths = ["qa/**"]
+rebase_exec_author = "prettier"
+ignore_whitespace = "reindent"
+
+[policy]
+max_ai_share_pct = 80
+ai_secret_scan = "warn"
+
+[normalize.tools]
+"claude-code" = "claude"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.exclude_paths, vec!["generated/**"]);
+        assert_eq!(config.required_trailers, vec!["AI-Attribution"]);
+        assert_eq!(config.test_paths, vec!["qa/**"]);
+        assert_eq!(config.rebase_exec_tool(), Some("prettier"));
+        assert_eq!(config.whitespace_mode(), WhitespaceMode::Reindent);
+        assert_eq!(config.policy.max_ai_share_pct, Some(80.0));
+        assert_eq!(config.policy.secret_scan_mode(), SecretScanMode::Warn);
+        assert_eq!(config.normalize.tool("claude-code"), "claude");
+        assert_eq!(config.normalize.tool("cursor"), "cursor");
+        assert_eq!(
+            parse_workspace_config("").unwrap(),
+            WorkspaceConfig::default()
+        );
+        assert_eq!(
+            parse_workspace_config("rebase_exec_author = \"Human\"")
+                .unwrap()
+                .rebase_exec_tool(),
+            None
+        );
+
+        assert!(parse_workspace_config("[policy]\nmax_ai_share_pct = 120").is_err());
+        assert!(parse_workspace_config("[policy]\nai_secret_scan = \"loud\"").is_err());
+        assert!(parse_workspace_config("ignore_whitespace = \"tabs\"").is_err());
+        assert!(parse_workspace_config("required_trailers = [\"Bad Key\"]").is_err());
+        assert!(parse_workspace_config("exclude_paths = \"generated/**\"").is_err());
+    }
+
+    #[test]
+    fn test_user_config_merges_over_repo_config() {
+        let repo = WorkspaceConfig {
+            exclude_paths: vec!["generated/**".to_string()],
+            required_trailers: vec!["AI-Attribution".to_string()],
+            test_paths: Vec::new(),
+            rebase_exec_author: None,
+            ignore_whitespace: Some(WhitespaceMode::Whitespace),
+            policy: PolicyConfig {
+                max_ai_share_pct: Some(80.0),
+                ai_secret_scan: Some(SecretScanMode::Block),
+            },
+            normalize: NormalizationConfig {
+                tools: BTreeMap::from([
+                    ("claude-code".to_string(), "claude".to_string()),
+                    ("cursor-agent".to_string(), "cursor".to_string()),
+                ]),
+                models: BTreeMap::new(),
+            },
+        };
+        let user = WorkspaceConfig {
+            exclude_paths: vec!["generated/**".to_string(), "scratch/**".to_string()],
+            policy: PolicyConfig {
+                max_ai_share_pct: Some(90.0),
+                ..Default::default()
+            },
+            normalize: NormalizationConfig {
+                tools: BTreeMap::from([("claude-code".to_string(), "anthropic".to_string())]),
+                models: BTreeMap::new(),
+            },
+            ..Default::default()
+        };
+
+        let merged = repo.merged_with(user);
+        assert_eq!(merged.exclude_paths, vec!["generated/**", "scratch/**"]);
+        assert_eq!(merged.required_trailers, vec!["AI-Attribution"]);
+        assert_eq!(merged.whitespace_mode(), WhitespaceMode::Whitespace);
+        assert_eq!(merged.policy.max_ai_share_pct, Some(90.0));
+        assert_eq!(merged.policy.secret_scan_mode(), SecretScanMode::Block);
+        assert_eq!(merged.normalize.tool("claude-code"), "anthropic");
+        assert_eq!(merged.normalize.tool("cursor-agent"), "cursor");
+
+        assert_eq!(merged.policy.ai_share_violation(95, 100), Some(95.0));
+        assert_eq!(merged.policy.ai_share_violation(90, 100), None);
+        assert_eq!(merged.policy.ai_share_violation(0, 0), None);
+    }
+}
@@ -1619,6 +1619,7 @@ fn daemon_stalled_unidentified_trace_connection_does_not_block_checkpoint_contro
             content: Some("checkpoint content\n".to_string()),
             repo_work_dir: repo.path().to_path_buf(),
             base_commit: BaseCommit::Initial,
+            hunks: None,
         }],
         path_role: PreparedPathRole::Edited,
         stream_source: None,
@@ -1665,12 +1666,14 @@ fn daemon_checkpoint_resolution_applies_total_content_budget() {
                 content: Some("a".repeat(48)),
                 repo_work_dir: repo.path().to_path_buf(),
                 base_commit: BaseCommit::Initial,
+                hunks: None,
             },
             CheckpointFile {
                 path: PathBuf::from("z_skipped.txt"),
                 content: Some("z".repeat(64)),
                 repo_work_dir: repo.path().to_path_buf(),
                 base_commit: BaseCommit::Initial,
+                hunks: None,
             },
         ],
         path_role: PreparedPathRole::Edited,
@@ -1765,6 +1768,7 @@ fn daemon_partial_trace_line_does_not_block_checkpoint_control_request() {
             content: Some("checkpoint content\n".to_string()),
             repo_work_dir: repo.path().to_path_buf(),
             base_commit: BaseCommit::Initial,
+            hunks: None,
         }],
         path_role: PreparedPathRole::Edited,
         stream_source: None,
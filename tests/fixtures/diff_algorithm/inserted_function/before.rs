@@ -0,0 +1,14 @@
+use std::collections::HashMap;
+
+pub fn load(path: &str) -> String {
+    let content = std::fs::read_to_string(path).unwrap_or_default();
+    content.trim().to_string()
+}
+
+pub fn count_words(text: &str) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for word in text.split_whitespace() {
+        *counts.entry(word.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
@@ -217,9 +217,44 @@ fn test_ai_reflow_of_human_content_one_to_many_lines_attributed_to_ai() {
     ]);
 }
 
+/// A formatter runs on save right after the AI edit: quotes, semicolons and
+/// trailing commas change along with the layout. The next human checkpoint
+/// should keep the AI attribution of the reformatted lines.
+#[test]
+fn test_formatter_rewrite_after_ai_edit_retains_ai() {
+    let repo = TestRepo::new();
+    let file_path = repo.path().join("format.js");
+
+    fs::write(&file_path, "").unwrap();
+    repo.git_ai(&["checkpoint", "--", "format.js"]).unwrap();
+
+    fs::write(&file_path, "const a = 'x'\nconst b = [1, 2]\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "format.js"])
+        .unwrap();
+
+    fs::write(
+        &file_path,
+        "const a = \"x\";\nconst b = [\n  1,\n  2,\n];\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint", "--", "format.js"]).unwrap();
+    repo.stage_all_and_commit("AI edit reformatted on save")
+        .unwrap();
+
+    let mut file = repo.filename("format.js");
+    file.assert_lines_and_blame(crate::lines![
+        "const a = \"x\";".ai(),
+        "const b = [".ai(),
+        "  1,".ai(),
+        "  2,".ai(),
+        "];".ai(),
+    ]);
+}
+
 crate::reuse_tests_in_worktree!(
     test_ai_reflow_two_lines_to_one_attributed_to_ai,
     test_ai_reflow_of_human_content_one_to_many_lines_attributed_to_ai,
     test_human_reflow_on_ai_code_retains_ai_attribution,
     test_human_reflow_of_ai_set_contents_retains_ai,
+    test_formatter_rewrite_after_ai_edit_retains_ai,
 );
@@ -820,7 +820,49 @@ fn test_amend_delete_known_human_line_preserves_human_record_in_note() {
     }
 }
 
+/// With reflogs disabled there is no HEAD entry describing the commit or the
+/// amend; attribution must still follow both.
+#[test]
+fn test_amend_detected_with_reflog_disabled() {
+    let repo = TestRepo::new();
+    repo.git(&["config", "core.logAllRefUpdates", "false"])
+        .unwrap();
+    let git_dir = repo
+        .git(&["rev-parse", "--absolute-git-dir"])
+        .unwrap()
+        .trim()
+        .to_string();
+    let _ = std::fs::remove_dir_all(std::path::Path::new(&git_dir).join("logs"));
+
+    let mut file = repo.filename("test.txt");
+    file.set_contents(crate::lines!["line 1", "line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(2, crate::lines!["// AI line".ai()]);
+    repo.stage_all_and_commit("Add AI line").unwrap();
+
+    file.insert_at(0, crate::lines!["// AI header".ai()]);
+    repo.git(&["add", "-A"]).unwrap();
+    repo.git(&["commit", "--amend", "-m", "Add AI lines"])
+        .unwrap();
+
+    assert!(
+        !std::path::Path::new(&git_dir)
+            .join("logs")
+            .join("HEAD")
+            .exists(),
+        "the HEAD reflog should stay disabled"
+    );
+    file.assert_lines_and_blame(crate::lines![
+        "// AI header".ai(),
+        "line 1".human(),
+        "line 2".human(),
+        "// AI line".ai()
+    ]);
+}
+
 crate::reuse_tests_in_worktree!(
+    test_amend_detected_with_reflog_disabled,
     test_amend_add_lines_at_top,
     test_amend_add_lines_in_middle,
     test_amend_add_lines_at_bottom,
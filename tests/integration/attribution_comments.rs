@@ -0,0 +1,66 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+use std::fs;
+
+/// An AI checkpoint hunk marked `git-ai: human` is recorded as human, while the
+/// agent's other hunks stay AI.
+#[test]
+fn test_human_directive_forces_ai_hunk_to_human() {
+    let repo = TestRepo::new();
+    let file_path = repo.path().join("lib.rs");
+    fs::write(&file_path, "fn base() {}\n").unwrap();
+    repo.git_ai(&["checkpoint", "--", "lib.rs"]).unwrap();
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    fs::write(
+        &file_path,
+        "fn agent_one() {}\n\
+         fn base() {}\n\
+         // git-ai: human\n\
+         fn from_docs() {}\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "lib.rs"]).unwrap();
+    repo.stage_all_and_commit("Agent edits").unwrap();
+
+    let mut file = repo.filename("lib.rs");
+    file.assert_lines_and_blame(crate::lines![
+        "fn agent_one() {}".ai(),
+        "fn base() {}".human(),
+        "// git-ai: human".human(),
+        "fn from_docs() {}".human(),
+    ]);
+}
+
+/// A human hunk marked `git-ai: ai` is credited to the file's AI author.
+#[test]
+fn test_ai_directive_credits_human_hunk_to_ai() {
+    let repo = TestRepo::new();
+    let file_path = repo.path().join("lib.rs");
+    fs::write(&file_path, "fn base() {}\n").unwrap();
+    repo.git_ai(&["checkpoint", "--", "lib.rs"]).unwrap();
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    fs::write(&file_path, "fn base() {}\nfn agent() {}\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "lib.rs"]).unwrap();
+
+    fs::write(
+        &file_path,
+        "fn base() {}\nfn agent() {}\nfn typed_from_chat() {} // git-ai: ai\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint", "--", "lib.rs"]).unwrap();
+    repo.stage_all_and_commit("Mixed edits").unwrap();
+
+    let mut file = repo.filename("lib.rs");
+    file.assert_lines_and_blame(crate::lines![
+        "fn base() {}".human(),
+        "fn agent() {}".ai(),
+        "fn typed_from_chat() {} // git-ai: ai".ai(),
+    ]);
+}
+
+crate::reuse_tests_in_worktree!(
+    test_human_directive_forces_ai_hunk_to_human,
+    test_ai_directive_credits_human_hunk_to_ai,
+);
@@ -593,6 +593,51 @@ fn test_blame_format_json_line_ranges() {
     );
 }
 
+#[test]
+fn test_blame_format_json_attribution_ranges() {
+    // Output format: JSON ranges cover every line, grouped by contiguous attribution
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(crate::lines![
+        "Line 1".ai(),
+        "Line 2".ai(),
+        "Line 3".ai(),
+        "Line 4".human(),
+        "Line 5".ai()
+    ]);
+    let commit = repo.stage_all_and_commit("Test").unwrap();
+
+    let output = repo.git_ai(&["blame", "--json", "test.txt"]).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).expect("Should be valid JSON");
+
+    let ranges = json["ranges"]
+        .as_array()
+        .expect("ranges should be an array");
+    let spans: Vec<(u64, u64, &str)> = ranges
+        .iter()
+        .map(|r| {
+            (
+                r["start_line"].as_u64().unwrap(),
+                r["end_line"].as_u64().unwrap(),
+                r["author_kind"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(spans.len(), 3, "unexpected ranges: {}", output);
+    assert_eq!((spans[0].0, spans[0].1, spans[0].2), (1, 3, "ai"));
+    assert_eq!((spans[1].0, spans[1].1), (4, 4));
+    assert_ne!(spans[1].2, "ai");
+    assert_eq!((spans[2].0, spans[2].1, spans[2].2), (5, 5, "ai"));
+
+    let first = &ranges[0];
+    assert_eq!(first["tool"].as_str(), Some("mock_ai"));
+    assert_eq!(first["commit"].as_str(), Some(commit.commit_sha.as_str()));
+    let prompt_id = first["prompt_id"].as_str().expect("AI range has prompt_id");
+    assert!(json["prompts"].get(prompt_id).is_some());
+    assert!(ranges[1].get("prompt_id").is_none());
+}
+
 #[test]
 fn test_blame_format_default_with_flags() {
     // Output format: Default format with various flags
@@ -1201,6 +1246,28 @@ fn test_blame_stress_deeply_nested_path() {
     assert!(output.contains("Deep content"));
 }
 
+#[test]
+fn test_blame_with_replace_ref_grafted_history() {
+    // Partially imported history: the AI commit is grafted into a root commit,
+    // so blame must read through `refs/replace/*` instead of the loose objects.
+    let repo = TestRepo::new();
+    let mut file = repo.filename("imported.txt");
+
+    file.set_contents(crate::lines!["Imported line".human()]);
+    repo.stage_all_and_commit("Imported history").unwrap();
+
+    file.insert_at(1, crate::lines!["AI line 1".ai(), "AI line 2".ai()]);
+    repo.stage_all_and_commit("AI work").unwrap();
+
+    repo.git(&["replace", "--graft", "HEAD"])
+        .expect("grafting HEAD into a root commit should succeed");
+
+    let output = repo.git_ai(&["blame", "imported.txt"]).unwrap();
+    assert!(output.contains("AI line 1"));
+    assert!(output.contains("AI line 2"));
+    assert!(output.contains("mock_ai"));
+}
+
 crate::reuse_tests_in_worktree!(
     test_blame_success_basic_file,
     test_blame_success_only_human_lines,
@@ -1229,6 +1296,7 @@ crate::reuse_tests_in_worktree!(
     test_blame_format_incremental,
     test_blame_format_json_structure,
     test_blame_format_json_line_ranges,
+    test_blame_format_json_attribution_ranges,
     test_blame_format_default_with_flags,
     test_blame_ai_authorship_hunk_splitting,
     test_blame_ai_authorship_no_splitting,
@@ -1246,4 +1314,5 @@ crate::reuse_tests_in_worktree!(
     test_blame_date_format_short,
     test_blame_stress_many_small_hunks,
     test_blame_stress_deeply_nested_path,
+    test_blame_with_replace_ref_grafted_history,
 );
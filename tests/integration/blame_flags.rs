@@ -162,6 +162,66 @@ fn test_blame_line_range() {
     );
 }
 
+#[test]
+fn test_blame_git_style_line_range_specs() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(crate::lines![
+        "Line 1",
+        "Line 2",
+        "Line 3".ai(),
+        "Line 4",
+        "Line 5".ai(),
+        "Line 6"
+    ]);
+
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    for spec in [
+        vec!["-L", "2,+2"],
+        vec!["-L", "4,-3"],
+        vec!["-L", ",3"],
+        vec!["-L", "4"],
+        vec!["-L", "5,"],
+        vec!["-L3,4"],
+    ] {
+        let mut args = vec!["blame"];
+        args.extend(spec.iter().copied());
+        args.push("test.txt");
+
+        let git_norm = normalize_for_snapshot(&repo.git(&args).unwrap());
+        let git_ai_norm = normalize_for_snapshot(&repo.git_ai(&args).unwrap());
+        assert_eq!(
+            git_norm, git_ai_norm,
+            "Normalized blame outputs should match for {:?}",
+            spec
+        );
+    }
+}
+
+#[test]
+fn test_blame_since_accepts_git_date_specs() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(crate::lines!["Line 1", "Line 2".ai(), "Line 3"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    for args in [
+        vec!["blame", "--since", "10 years ago", "test.txt"],
+        vec!["blame", "--since=10.years.ago", "test.txt"],
+    ] {
+        let git_norm = normalize_for_snapshot(&repo.git(&args).unwrap());
+        let git_ai_norm = normalize_for_snapshot(&repo.git_ai(&args).unwrap());
+        assert_eq!(
+            git_norm, git_ai_norm,
+            "Normalized blame outputs should match for {:?}",
+            args
+        );
+    }
+}
+
 #[test]
 fn test_blame_multiple_line_ranges_default() {
     let repo = TestRepo::new();
@@ -1472,6 +1532,60 @@ fn test_blame_ai_human_author() {
     );
 }
 
+#[test]
+fn test_blame_group_by_session() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(crate::lines![
+        "Line 1",
+        "Line 2".ai(),
+        "Line 3".ai(),
+        "Line 4",
+        "Line 5".ai()
+    ]);
+
+    repo.stage_all_and_commit("Mixed authorship commit")
+        .unwrap();
+
+    let output = repo
+        .git_ai(&["blame", "--group-by", "session", "--json", "test.txt"])
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).expect("Should be valid JSON");
+    assert_eq!(json["file"], "test.txt");
+    assert_eq!(json["total_lines"], 5);
+    let sessions = json["sessions"].as_array().unwrap();
+    assert_eq!(
+        sessions.len(),
+        1,
+        "One checkpoint is one conversation: {}",
+        output
+    );
+    assert_eq!(sessions[0]["tool"], "mock_ai");
+    assert_eq!(sessions[0]["lines"], 3);
+    assert_eq!(sessions[0]["first_line"], 2);
+    assert_eq!(sessions[0]["last_line"], 5);
+
+    let text = repo
+        .git_ai(&["blame", "--group-by=session", "test.txt"])
+        .unwrap();
+    assert!(text.contains("SESSION"), "Missing header: {}", text);
+    assert!(
+        text.contains("3 of 5 lines from 1 AI conversation"),
+        "Missing summary: {}",
+        text
+    );
+
+    let err = repo
+        .git_ai(&["blame", "--group-by", "commit", "test.txt"])
+        .unwrap_err();
+    assert!(
+        err.contains("Unsupported --group-by value 'commit'"),
+        "{}",
+        err
+    );
+}
+
 crate::reuse_tests_in_worktree!(
     test_blame_basic_format,
     test_blame_line_range,
@@ -1501,4 +1615,5 @@ crate::reuse_tests_in_worktree!(
     test_blame_without_ignore_revs_file_works_normally,
     test_blame_ignore_revs_with_multiple_commits,
     test_blame_ai_human_author,
+    test_blame_group_by_session,
 );
@@ -0,0 +1,51 @@
+use crate::repos::test_repo::TestRepo;
+use git_ai::authorship::virtual_attribution::VirtualAttributions;
+use git_ai::git::repository as GitAiRepository;
+use std::collections::HashMap;
+use std::fs;
+
+/// AI lines keep their attribution in an unsaved buffer where the human
+/// shifted them, and the on-disk working log is left untouched.
+#[test]
+fn test_buffer_overlay_carries_ai_lines_through_unsaved_edits() {
+    let repo = TestRepo::new();
+    let file_path = repo.path().join("lib.rs");
+    fs::write(&file_path, "fn base() {}\n").unwrap();
+    repo.git_ai(&["checkpoint", "--", "lib.rs"]).unwrap();
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    fs::write(&file_path, "fn base() {}\nfn ai() {}\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "lib.rs"]).unwrap();
+    let checkpoints_before = repo.current_working_logs().read_all_checkpoints().unwrap();
+
+    let gitai_repo = GitAiRepository::find_repository_in_path(repo.path().to_str().unwrap())
+        .expect("should open repo");
+    let base_commit = gitai_repo.head().unwrap().target().unwrap();
+    let overlays = HashMap::from([(
+        "lib.rs".to_string(),
+        "// typed, not saved\nfn base() {}\nfn ai() {}\nfn more() {}\n".to_string(),
+    )]);
+    let virtual_attrs =
+        VirtualAttributions::from_buffer_overlay(gitai_repo, base_commit, None, &overlays)
+            .expect("overlay attribution should succeed");
+
+    let line_attrs = virtual_attrs
+        .get_line_attributions("lib.rs")
+        .expect("AI lines should remain");
+    assert_eq!(line_attrs.len(), 1, "{:?}", line_attrs);
+    assert_eq!((line_attrs[0].start_line, line_attrs[0].end_line), (3, 3));
+    assert_ne!(line_attrs[0].author_id, "human");
+    assert_eq!(
+        virtual_attrs.get_file_content("lib.rs").map(String::as_str),
+        overlays.get("lib.rs").map(String::as_str)
+    );
+
+    assert_eq!(
+        fs::read_to_string(&file_path).unwrap(),
+        "fn base() {}\nfn ai() {}\n"
+    );
+    let checkpoints_after = repo.current_working_logs().read_all_checkpoints().unwrap();
+    assert_eq!(checkpoints_after.len(), checkpoints_before.len());
+}
+
+crate::reuse_tests_in_worktree!(test_buffer_overlay_carries_ai_lines_through_unsaved_edits,);
@@ -0,0 +1,55 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+
+#[test]
+fn test_bundle_push_dry_run_packages_release_range() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(crate::lines!["fn base() {}".human()]);
+    let base = repo.stage_all_and_commit("Initial").unwrap();
+    repo.git(&["tag", "v1"]).unwrap();
+
+    file.set_contents(crate::lines!["fn base() {}".human(), "fn ai() {}".ai()]);
+    let ai_commit = repo.stage_all_and_commit("AI change").unwrap();
+    file.set_contents(crate::lines![
+        "fn base() {}".human(),
+        "fn ai() {}".ai(),
+        "fn human() {}".human()
+    ]);
+    let human_commit = repo.stage_all_and_commit("Human change").unwrap();
+    repo.git(&["tag", "v2"]).unwrap();
+
+    let output = repo
+        .git_ai(&["bundle", "push", "--range", "v1..v2", "--dry-run", "--json"])
+        .expect("bundle push --dry-run should succeed");
+    let json: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+
+    assert_eq!(json["uploaded"], false);
+    let manifest = &json["manifest"];
+    assert_eq!(manifest["range"], "v1..v2");
+    assert_eq!(manifest["from_sha"], base.commit_sha.as_str());
+    assert_eq!(manifest["to_sha"], human_commit.commit_sha.as_str());
+    let shas: Vec<&str> = manifest["commits"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["sha"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        shas,
+        vec![
+            ai_commit.commit_sha.as_str(),
+            human_commit.commit_sha.as_str()
+        ]
+    );
+
+    let manifest_id = json["manifest_id"].as_str().unwrap();
+    assert_eq!(manifest_id.len(), 64, "{}", output);
+
+    let err = repo
+        .git_ai(&["bundle", "push", "--range", "v2", "--dry-run"])
+        .expect_err("a range without .. is rejected");
+    assert!(err.contains("expected <from>..<to>"), "{}", err);
+}
+
+crate::reuse_tests_in_worktree!(test_bundle_push_dry_run_packages_release_range,);
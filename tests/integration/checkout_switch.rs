@@ -1,5 +1,7 @@
 use crate::repos::test_file::ExpectedLineExt;
 use crate::repos::test_repo::{TestRepo, default_branchname};
+use git_ai::git::repository::find_repository_in_path;
+use std::fs;
 
 /// Test that checkout to a different branch migrates the working log to the new HEAD.
 #[test]
@@ -480,6 +482,165 @@ fn test_checkout_merge_conflict_preserves_ai_attribution() {
     let _ = checkout_result;
 }
 
+/// Test that work started on a detached HEAD follows the user back to their branch.
+#[test]
+fn test_detached_head_return_to_branch_keeps_working_log() {
+    let repo = TestRepo::new();
+
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(vec!["# Test Repo".to_string()]);
+    repo.stage_all_and_commit("initial commit")
+        .expect("initial commit should succeed");
+
+    let mut file2 = repo.filename("file2.txt");
+    file2.set_contents(vec!["Some content".to_string()]);
+    repo.stage_all_and_commit("second commit")
+        .expect("second commit should succeed");
+
+    repo.git(&["switch", "--detach", "HEAD~1"])
+        .expect("detaching should succeed");
+
+    // Create AI changes (uncommitted) while detached
+    let mut ai_file = repo.filename("ai_work.txt");
+    ai_file.set_contents(vec!["AI generated line 1".ai(), "AI generated line 2".ai()]);
+
+    repo.git_ai(&["checkpoint", "mock_ai"])
+        .expect("checkpoint should succeed");
+
+    // Return to the branch, carrying the uncommitted work
+    repo.git(&["switch", default_branchname()])
+        .expect("switching back should succeed");
+
+    repo.stage_all_and_commit("commit after returning to branch")
+        .expect("commit should succeed");
+
+    ai_file.assert_lines_and_blame(vec!["AI generated line 1".ai(), "AI generated line 2".ai()]);
+}
+
+/// Test that creating a branch from a detached HEAD keeps the working log.
+#[test]
+fn test_switch_create_branch_from_detached_head_keeps_working_log() {
+    let repo = TestRepo::new();
+
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(vec!["# Test Repo".to_string()]);
+    repo.stage_all_and_commit("initial commit")
+        .expect("initial commit should succeed");
+
+    let mut file2 = repo.filename("file2.txt");
+    file2.set_contents(vec!["Some content".to_string()]);
+    repo.stage_all_and_commit("second commit")
+        .expect("second commit should succeed");
+
+    // Create AI changes (uncommitted), then detach at an older commit
+    let mut ai_file = repo.filename("ai_work.txt");
+    ai_file.set_contents(vec!["AI generated line".ai()]);
+
+    repo.git_ai(&["checkpoint", "mock_ai"])
+        .expect("checkpoint should succeed");
+
+    repo.git(&["checkout", "--detach", "HEAD~1"])
+        .expect("detaching should succeed");
+
+    // Turn the detached state into a branch and commit there
+    repo.git(&["switch", "-c", "fix-from-detached"])
+        .expect("branch creation should succeed");
+
+    repo.stage_all_and_commit("commit on new branch")
+        .expect("commit should succeed");
+
+    ai_file.assert_lines_and_blame(vec!["AI generated line".ai()]);
+}
+
+/// Branching off a dirty HEAD and committing there first must not take the
+/// pending attribution away from the branch that was left.
+#[test]
+fn test_switch_create_forks_working_log_for_original_branch() {
+    let repo = TestRepo::new();
+
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(vec!["# Test Repo".to_string()]);
+    repo.stage_all_and_commit("initial commit")
+        .expect("initial commit should succeed");
+
+    let mut ai_file = repo.filename("ai_work.txt");
+    ai_file.set_contents(vec!["AI generated line".ai()]);
+    repo.git_ai(&["checkpoint", "mock_ai"])
+        .expect("checkpoint should succeed");
+
+    // Branch off the same HEAD and commit the shared changes there first.
+    repo.git(&["switch", "-c", "side"])
+        .expect("branch creation should succeed");
+    repo.stage_all_and_commit("commit on side branch")
+        .expect("commit should succeed");
+    ai_file.assert_lines_and_blame(vec!["AI generated line".ai()]);
+
+    // Back on the original branch the agent's change lands there as well.
+    repo.git(&["switch", default_branchname()])
+        .expect("switch back should succeed");
+    fs::write(repo.path().join("ai_work.txt"), "AI generated line").unwrap();
+    repo.stage_all_and_commit("commit on original branch")
+        .expect("commit should succeed");
+
+    ai_file.assert_lines_and_blame(vec!["AI generated line".ai()]);
+}
+
+/// Force-moving a branch that is not checked out carries a working log left on
+/// its old tip along a fast-forward and archives it otherwise.
+#[test]
+fn test_branch_force_reassociates_or_archives_old_tip_working_log() {
+    let repo = TestRepo::new();
+
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(vec!["# Test Repo".to_string()]);
+    repo.stage_all_and_commit("initial commit")
+        .expect("initial commit should succeed");
+    let base = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+
+    repo.git(&["checkout", "-b", "topic"])
+        .expect("branch creation should succeed");
+    let mut file2 = repo.filename("file2.txt");
+    file2.set_contents(vec!["Some content".to_string()]);
+    repo.stage_all_and_commit("second commit")
+        .expect("second commit should succeed");
+    let second = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+    let mut file3 = repo.filename("file3.txt");
+    file3.set_contents(vec!["More content".to_string()]);
+    repo.stage_all_and_commit("third commit")
+        .expect("third commit should succeed");
+    let third = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+
+    repo.git(&["checkout", default_branchname()])
+        .expect("checkout should succeed");
+    repo.git(&["branch", "-f", "topic", &second])
+        .expect("branch -f should succeed");
+
+    // Leave a working log behind on topic's tip, as an abandoned session would.
+    let mut ai_file = repo.filename("ai_work.txt");
+    ai_file.set_contents(vec!["AI generated line".ai()]);
+    repo.git_ai(&["checkpoint", "mock_ai"])
+        .expect("checkpoint should succeed");
+    repo.sync_daemon_force();
+    let gitai_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
+    gitai_repo
+        .storage
+        .rename_working_log(&base, &second)
+        .unwrap();
+    fs::remove_file(repo.path().join("ai_work.txt")).unwrap();
+
+    repo.git(&["branch", "-f", "topic", &third])
+        .expect("fast-forward branch -f should succeed");
+    repo.sync_daemon_force();
+    assert!(!gitai_repo.storage.has_working_log(&second));
+    assert!(gitai_repo.storage.has_working_log(&third));
+
+    repo.git(&["branch", "-f", "topic", &base])
+        .expect("rewinding branch -f should succeed");
+    repo.sync_daemon_force();
+    assert!(!gitai_repo.storage.has_working_log(&third));
+    assert!(!gitai_repo.storage.has_working_log(&base));
+}
+
 crate::reuse_tests_in_worktree!(
     test_checkout_branch_migrates_working_log,
     test_checkout_force_deletes_working_log,
@@ -493,4 +654,8 @@ crate::reuse_tests_in_worktree!(
     test_checkout_with_mixed_attribution,
     test_checkout_pathspec_multiple_files,
     test_checkout_merge_conflict_preserves_ai_attribution,
+    test_detached_head_return_to_branch_keeps_working_log,
+    test_switch_create_branch_from_detached_head_keeps_working_log,
+    test_switch_create_forks_working_log_for_original_branch,
+    test_branch_force_reassociates_or_archives_old_tip_working_log,
 );
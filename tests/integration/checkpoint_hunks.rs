@@ -0,0 +1,150 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+use git_ai::daemon::checkpoint::is_ai_author_id;
+use std::fs;
+
+fn hunks_json(path: &str, old_start: u32, old_lines: u32, new_text: &str) -> String {
+    serde_json::json!([{
+        "path": path,
+        "hunks": [{
+            "old_start": old_start,
+            "old_lines": old_lines,
+            "new_text": new_text,
+        }],
+    }])
+    .to_string()
+}
+
+#[test]
+fn test_checkpoint_with_hunks_json_attributes_supplied_ranges() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("notes.txt");
+    file.set_contents(crate::lines!["one".human(), "two".human(), "three".human()]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::write(repo.path().join("notes.txt"), "one\nTWO a\nTWO b\nthree\n").unwrap();
+    let hunks = hunks_json("notes.txt", 2, 1, "TWO a\nTWO b\n");
+    repo.git_ai(&["checkpoint", "mock_ai", "notes.txt", "--hunks-json", &hunks])
+        .expect("checkpoint with --hunks-json should succeed");
+
+    let checkpoints = repo.current_working_logs().read_all_checkpoints().unwrap();
+    let latest = checkpoints.last().expect("checkpoint should be recorded");
+    assert_eq!(latest.line_stats.additions, 2);
+    assert_eq!(latest.line_stats.deletions, 1);
+
+    repo.stage_all_and_commit("Agent edit").unwrap();
+    file.assert_lines_and_blame(crate::lines![
+        "one".human(),
+        "TWO a".ai(),
+        "TWO b".ai(),
+        "three".human(),
+    ]);
+}
+
+#[test]
+fn test_checkpoint_with_stale_hunks_json_falls_back_to_diff() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("notes.txt");
+    file.set_contents(crate::lines!["one".human(), "two".human()]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // The hunk only describes the second line, so it no longer reproduces the
+    // file and the checkpoint must diff the full contents instead.
+    fs::write(repo.path().join("notes.txt"), "ONE\nTWO\n").unwrap();
+    let hunks = hunks_json("notes.txt", 2, 1, "TWO\n");
+    repo.git_ai(&["checkpoint", "mock_ai", "notes.txt", "--hunks-json", &hunks])
+        .expect("checkpoint with stale --hunks-json should succeed");
+    repo.stage_all_and_commit("Agent edit").unwrap();
+
+    file.assert_lines_and_blame(crate::lines!["ONE".ai(), "TWO".ai()]);
+}
+
+#[test]
+fn test_checkpoint_rejects_invalid_hunks_json() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("notes.txt");
+    file.set_contents(crate::lines!["one".human()]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::write(repo.path().join("notes.txt"), "one\ntwo\n").unwrap();
+    let output = repo
+        .git_ai(&[
+            "checkpoint",
+            "mock_ai",
+            "notes.txt",
+            "--hunks-json",
+            "{not json",
+        ])
+        .unwrap_or_else(|e| e);
+    assert!(
+        output.contains("invalid --hunks-json payload"),
+        "expected a parse error, got: {}",
+        output
+    );
+    assert!(
+        repo.current_working_logs()
+            .read_all_checkpoints()
+            .unwrap()
+            .iter()
+            .all(|checkpoint| !checkpoint.kind.is_ai()),
+        "no AI checkpoint should be recorded for an invalid payload"
+    );
+}
+
+/// Lines of `path` the latest checkpoint attributes to an AI author.
+fn latest_ai_lines(repo: &TestRepo, path: &str) -> Vec<u32> {
+    let checkpoints = repo.current_working_logs().read_all_checkpoints().unwrap();
+    let entry = checkpoints
+        .last()
+        .and_then(|checkpoint| checkpoint.entries.iter().find(|entry| entry.file == path))
+        .expect("latest checkpoint should record the file");
+    entry
+        .line_attributions
+        .iter()
+        .filter(|attr| is_ai_author_id(&attr.author_id))
+        .flat_map(|attr| attr.start_line..=attr.end_line)
+        .collect()
+}
+
+#[test]
+fn test_checkpoint_with_hunks_json_ignores_line_ending_conversion() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("notes.txt");
+    file.set_contents(crate::lines!["one".human(), "two".human()]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // The agent converts the file to CRLF and edits one line, reporting the
+    // whole file as a single hunk.
+    fs::write(repo.path().join("notes.txt"), "one\r\nTWO\r\n").unwrap();
+    let hunks = hunks_json("notes.txt", 1, 2, "one\r\nTWO\r\n");
+    repo.git_ai(&["checkpoint", "mock_ai", "notes.txt", "--hunks-json", &hunks])
+        .expect("checkpoint with --hunks-json should succeed");
+    assert_eq!(latest_ai_lines(&repo, "notes.txt"), vec![2]);
+
+    repo.stage_all_and_commit("Agent edit").unwrap();
+    let stats = repo.stats().unwrap();
+    assert_eq!(stats.git_diff_added_lines, 2);
+    assert_eq!(stats.ai_accepted, 1);
+}
+
+#[test]
+fn test_checkpoint_treats_line_ending_conversion_as_edit_for_text_unset_files() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join(".gitattributes"), "notes.txt -text\n").unwrap();
+    let mut file = repo.filename("notes.txt");
+    file.set_contents(crate::lines!["one".human(), "two".human()]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // git stores `-text` files byte for byte, so the conversion is part of the
+    // agent's edit, whether it reports hunks or not.
+    fs::write(repo.path().join("notes.txt"), "one\r\nTWO\r\n").unwrap();
+    let hunks = hunks_json("notes.txt", 1, 2, "one\r\nTWO\r\n");
+    repo.git_ai(&["checkpoint", "mock_ai", "notes.txt", "--hunks-json", &hunks])
+        .expect("checkpoint with --hunks-json should succeed");
+    assert_eq!(latest_ai_lines(&repo, "notes.txt"), vec![1, 2]);
+
+    repo.stage_all_and_commit("Agent edit").unwrap();
+    let stats = repo.stats().unwrap();
+    assert_eq!(stats.git_diff_added_lines, 2);
+    assert_eq!(stats.ai_accepted, 2);
+}
@@ -0,0 +1,47 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+use std::fs;
+
+#[test]
+fn test_checkpoint_label_flows_into_note_and_timeline() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("notes.txt");
+    file.set_contents(crate::lines!["one".human()]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::write(repo.path().join("notes.txt"), "one\ntwo\nthree\n").unwrap();
+    repo.git_ai(&[
+        "checkpoint",
+        "mock_ai",
+        "notes.txt",
+        "--label",
+        "after refactor",
+    ])
+    .expect("labelled checkpoint should succeed");
+
+    let checkpoints = repo.current_working_logs().read_all_checkpoints().unwrap();
+    let latest = checkpoints.last().expect("checkpoint should be recorded");
+    assert_eq!(latest.label.as_deref(), Some("after refactor"));
+
+    let commit = repo.stage_all_and_commit("Agent edit").unwrap();
+    let labels = &commit.authorship_log.metadata.labels;
+    assert_eq!(labels.len(), 1);
+    assert_eq!(labels[0].label, "after refactor");
+    assert_eq!(labels[0].additions, 2);
+
+    let stats = repo
+        .git_ai(&["stats", "--detail", "--json"])
+        .expect("stats --detail should succeed");
+    let stats: serde_json::Value = serde_json::from_str(stats.trim()).unwrap();
+    assert_eq!(stats["labels"][0]["label"], "after refactor");
+
+    let timeline = repo
+        .git_ai(&["timeline", "--json"])
+        .expect("timeline should succeed");
+    let timeline: serde_json::Value = serde_json::from_str(timeline.trim()).unwrap();
+    assert_eq!(timeline.as_array().map(Vec::len), Some(1));
+    assert_eq!(timeline[0]["commit"], commit.commit_sha.as_str());
+    assert_eq!(timeline[0]["label"], "after refactor");
+}
+
+crate::reuse_tests_in_worktree!(test_checkpoint_label_flows_into_note_and_timeline,);
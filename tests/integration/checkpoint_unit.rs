@@ -237,6 +237,7 @@ fn test_checkpoint_base_override_controls_head_context_for_entry_generation() {
             content: Some("line from commit B\n".to_string()),
             repo_work_dir: repo.path().to_path_buf(),
             base_commit: BaseCommit::Sha(base_commit.clone()),
+            hunks: None,
         }],
         path_role: PreparedPathRole::Edited,
         stream_source: None,
@@ -256,6 +257,7 @@ fn test_checkpoint_base_override_controls_head_context_for_entry_generation() {
             .as_millis(),
         files: vec![lines_file],
         dirty_files,
+        hunks: HashMap::new(),
     };
 
     execute_resolved_checkpoint_from_daemon(
@@ -290,6 +292,7 @@ fn test_ai_checkpoint_without_agent_id_is_rejected() {
             content: Some(content.to_string()),
             repo_work_dir: repo.path().to_path_buf(),
             base_commit: BaseCommit::Sha(base_commit.clone()),
+            hunks: None,
         }],
         path_role: PreparedPathRole::Edited,
         stream_source: None,
@@ -305,6 +308,7 @@ fn test_ai_checkpoint_without_agent_id_is_rejected() {
             .as_millis(),
         files: vec![lines_file.clone()],
         dirty_files: HashMap::from([(lines_file, Arc::from(content))]),
+        hunks: HashMap::new(),
     };
 
     let error = execute_resolved_checkpoint_from_daemon(
@@ -413,6 +417,7 @@ fn test_checkpoint_with_paths_outside_repo() {
             .as_millis(),
         files: vec![lines_file.clone()],
         dirty_files: HashMap::from([(lines_file.clone(), Arc::from(content.clone()))]),
+        hunks: HashMap::new(),
     };
 
     let checkpoint_request = CheckpointRequest {
@@ -428,6 +433,7 @@ fn test_checkpoint_with_paths_outside_repo() {
             content: Some(content.clone()),
             repo_work_dir: repo.path().to_path_buf(),
             base_commit: BaseCommit::Sha(base_commit),
+            hunks: None,
         }],
         path_role: PreparedPathRole::Edited,
         stream_source: None,
@@ -979,6 +985,17 @@ fn test_compute_line_stats_ignores_whitespace_only_lines() {
     );
 }
 
+#[test]
+fn test_compute_file_line_stats_tracks_largest_contiguous_insertion() {
+    let old = "a\nb\nc\n";
+    let new = "a\nx1\nx2\nx3\nb\ny1\nc\n";
+
+    let stats = compute_file_line_stats(old, new);
+
+    assert_eq!(stats.additions, 4);
+    assert_eq!(stats.largest_insertion, 3);
+}
+
 // ====================================================================
 // CRLF / LF normalization tests for compute_file_line_stats
 // ====================================================================
@@ -1333,6 +1350,7 @@ fn test_checkpoint_fails_with_initial_missing_blobs() {
             .as_millis(),
         files: vec!["file_b.txt".to_string()],
         dirty_files,
+        hunks: HashMap::new(),
     };
 
     let checkpoint_request = CheckpointRequest {
@@ -1348,6 +1366,7 @@ fn test_checkpoint_fails_with_initial_missing_blobs() {
             content: Some("hello\nai added\nnew line\n".to_string()),
             repo_work_dir: repo.path().to_path_buf(),
             base_commit: BaseCommit::Sha(head_sha),
+            hunks: None,
         }],
         path_role: PreparedPathRole::Edited,
         stream_source: None,
@@ -334,6 +334,62 @@ fn test_cherry_pick_with_conflict_and_continue() {
     ]);
 }
 
+/// Test a cherry-picked range that stops on a conflict midway: every commit in
+/// the sequence, including those applied by --continue, keeps its attribution.
+#[test]
+fn test_cherry_pick_range_with_conflict_and_continue() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("file.txt");
+    file.set_contents(crate::lines!["Line 1", "Line 2", "Line 3"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let main_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    let mut first = repo.filename("first.txt");
+    first.set_contents(crate::lines!["AI first".ai()]);
+    repo.stage_all_and_commit("AI first file").unwrap();
+    file.replace_at(1, "AI_FEATURE_VERSION".ai());
+    repo.stage_all_and_commit("AI conflicting change").unwrap();
+    let mut last = repo.filename("last.txt");
+    last.set_contents(crate::lines!["AI last".ai()]);
+    repo.stage_all_and_commit("AI last file").unwrap();
+
+    repo.git(&["checkout", &main_branch]).unwrap();
+    file.replace_at(1, "MAIN_BRANCH_VERSION".human());
+    repo.stage_all_and_commit("Human change").unwrap();
+
+    let range = format!("{}..feature", main_branch);
+    let result = repo.git(&["cherry-pick", "-x", &range]);
+    assert!(result.is_err(), "second pick should conflict");
+
+    fs::write(
+        repo.path().join("file.txt"),
+        "Line 1\nAI_FEATURE_VERSION\nLine 3",
+    )
+    .unwrap();
+    repo.git(&["add", "file.txt"]).unwrap();
+    repo.git(&["cherry-pick", "--continue"]).unwrap();
+
+    first.assert_lines_and_blame(crate::lines!["AI first".ai()]);
+    file.assert_lines_and_blame(crate::lines![
+        "Line 1".human(),
+        "AI_FEATURE_VERSION".ai(),
+        "Line 3".human(),
+    ]);
+    last.assert_lines_and_blame(crate::lines!["AI last".ai()]);
+
+    let git_ai_repo = git_ai::git::find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
+    for rev in ["HEAD~2", "HEAD~1", "HEAD"] {
+        let sha = repo.git(&["rev-parse", rev]).unwrap().trim().to_string();
+        assert!(
+            git_ai::git::notes_api::read_authorship_v3(&git_ai_repo, &sha).is_ok(),
+            "cherry-picked commit {} should carry a transferred note",
+            rev
+        );
+    }
+}
+
 /// Test cherry-pick --abort
 #[test]
 fn test_cherry_pick_abort() {
@@ -389,6 +445,63 @@ fn test_cherry_pick_abort() {
     ]);
 }
 
+/// A sequence aborted mid-way must not leak its pending sources into a later
+/// sequence that stops and is resumed with --continue.
+#[test]
+fn test_cherry_pick_continue_after_abort_uses_current_sequence() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("file.txt");
+    file.set_contents(crate::lines!["Line 1", "Line 2", "Line 3"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let main_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "stale"]).unwrap();
+    file.replace_at(1, "STALE_VERSION".ai());
+    repo.stage_all_and_commit("AI stale conflicting change")
+        .unwrap();
+    let mut stale = repo.filename("stale.txt");
+    stale.set_contents(crate::lines!["AI stale".ai()]);
+    repo.stage_all_and_commit("AI stale file").unwrap();
+
+    repo.git(&["checkout", &main_branch]).unwrap();
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    file.replace_at(1, "AI_FEATURE_VERSION".ai());
+    repo.stage_all_and_commit("AI conflicting change").unwrap();
+    let mut last = repo.filename("last.txt");
+    last.set_contents(crate::lines!["AI last".ai()]);
+    repo.stage_all_and_commit("AI last file").unwrap();
+
+    repo.git(&["checkout", &main_branch]).unwrap();
+    file.replace_at(1, "MAIN_BRANCH_VERSION".human());
+    repo.stage_all_and_commit("Human change").unwrap();
+
+    let stale_range = format!("{}..stale", main_branch);
+    let result = repo.git(&["cherry-pick", &stale_range]);
+    assert!(result.is_err(), "first stale pick should conflict");
+    repo.git(&["cherry-pick", "--abort"]).unwrap();
+
+    let feature_range = format!("{}..feature", main_branch);
+    let result = repo.git(&["cherry-pick", &feature_range]);
+    assert!(result.is_err(), "first feature pick should conflict");
+
+    fs::write(
+        repo.path().join("file.txt"),
+        "Line 1\nAI_FEATURE_VERSION\nLine 3",
+    )
+    .unwrap();
+    repo.git(&["add", "file.txt"]).unwrap();
+    repo.git(&["cherry-pick", "--continue"]).unwrap();
+
+    assert!(!repo.path().join("stale.txt").exists());
+    file.assert_lines_and_blame(crate::lines![
+        "Line 1".human(),
+        "AI_FEATURE_VERSION".ai(),
+        "Line 3".human(),
+    ]);
+    last.assert_lines_and_blame(crate::lines!["AI last".ai()]);
+}
+
 /// Test cherry-picking from branch without AI authorship
 #[test]
 fn test_cherry_pick_no_ai_authorship() {
@@ -1229,7 +1342,9 @@ crate::reuse_tests_in_worktree!(
     test_cherry_pick_preserves_prompt_only_commit_note_metadata,
     test_multiple_commits_cherry_pick,
     test_cherry_pick_with_conflict_and_continue,
+    test_cherry_pick_range_with_conflict_and_continue,
     test_cherry_pick_abort,
+    test_cherry_pick_continue_after_abort_uses_current_sequence,
     test_cherry_pick_no_ai_authorship,
     test_cherry_pick_multiple_ai_sessions,
     test_cherry_pick_identical_trees,
@@ -390,3 +390,203 @@ crate::reuse_tests_in_worktree!(
     test_claude_preset_does_not_ignore_when_transcript_path_is_claude,
     test_claude_e2e_prefers_latest_checkpoint_for_prompts,
 );
+
+#[test]
+fn test_claude_tool_use_ids_link_committed_lines_to_tool_calls() {
+    use crate::repos::test_repo::TestRepo;
+
+    let repo = TestRepo::new();
+    let repo_root = repo.canonical_path();
+    let file_path = repo_root.join("main.rs");
+    fs::write(&file_path, "fn main() {}\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let transcript_path = repo_root.join("claude-session.jsonl");
+    fs::write(&transcript_path, "").unwrap();
+    let hook_input = |tool_use_id: &str| {
+        json!({
+            "cwd": repo_root.to_string_lossy().to_string(),
+            "hook_event_name": "PostToolUse",
+            "tool_name": "Edit",
+            "tool_use_id": tool_use_id,
+            "transcript_path": transcript_path.to_string_lossy().to_string(),
+            "tool_input": {
+                "file_path": file_path.to_string_lossy().to_string()
+            }
+        })
+        .to_string()
+    };
+
+    fs::write(&file_path, "fn main() {}\n// first edit\n").unwrap();
+    repo.git_ai(&[
+        "checkpoint",
+        "claude",
+        "--hook-input",
+        &hook_input("toolu_first"),
+    ])
+    .unwrap();
+    fs::write(&file_path, "fn main() {}\n// first edit\n// second edit\n").unwrap();
+    repo.git_ai(&[
+        "checkpoint",
+        "claude",
+        "--hook-input",
+        &hook_input("toolu_second"),
+    ])
+    .unwrap();
+    let commit = repo.stage_all_and_commit("Add AI lines").unwrap();
+
+    let session = commit
+        .authorship_log
+        .metadata
+        .sessions
+        .values()
+        .next()
+        .expect("Session record should exist");
+    let mut tool_use_ids: Vec<&str> = session.tool_calls.values().map(String::as_str).collect();
+    tool_use_ids.sort();
+    assert_eq!(tool_use_ids, vec!["toolu_first", "toolu_second"]);
+
+    let attestation = commit
+        .authorship_log
+        .attestations
+        .iter()
+        .find(|file| file.file_path == "main.rs")
+        .expect("main.rs should be attested");
+    let tool_use_for_line = |line: u32| {
+        attestation
+            .entries
+            .iter()
+            .rev()
+            .find(|entry| entry.line_ranges.iter().any(|range| range.contains(line)))
+            .and_then(|entry| session.tool_use_id_for_attestation(&entry.hash))
+    };
+    assert_eq!(tool_use_for_line(2), Some("toolu_first"));
+    assert_eq!(tool_use_for_line(3), Some("toolu_second"));
+
+    let blame = repo.git_ai(&["blame", "--json", "main.rs"]).unwrap();
+    let blame_json: serde_json::Value = serde_json::from_str(&blame).unwrap();
+    let blamed_tool_use_ids: Vec<&str> = blame_json["ranges"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|range| range["tool_use_id"].as_str())
+        .collect();
+    assert_eq!(blamed_tool_use_ids, vec!["toolu_first", "toolu_second"]);
+}
+
+#[test]
+fn test_claude_subagent_edits_are_attributed_to_the_subagent() {
+    use crate::repos::test_repo::TestRepo;
+
+    let repo = TestRepo::new();
+    let repo_root = repo.canonical_path();
+    let file_path = repo_root.join("main.rs");
+    fs::write(&file_path, "fn main() {}\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let transcript_path = repo_root.join("claude-session.jsonl");
+    fs::write(&transcript_path, "").unwrap();
+    let hook_input = |tool_use_id: &str, subagent: Option<(&str, &str)>| {
+        let mut input = json!({
+            "cwd": repo_root.to_string_lossy().to_string(),
+            "hook_event_name": "PostToolUse",
+            "tool_name": "Edit",
+            "tool_use_id": tool_use_id,
+            "session_id": "sess-parent",
+            "transcript_path": transcript_path.to_string_lossy().to_string(),
+            "tool_input": {
+                "file_path": file_path.to_string_lossy().to_string()
+            }
+        });
+        if let Some((agent_id, agent_type)) = subagent {
+            input["agent_id"] = json!(agent_id);
+            input["agent_type"] = json!(agent_type);
+        }
+        input.to_string()
+    };
+
+    fs::write(&file_path, "fn main() {}\n// parent edit\n").unwrap();
+    repo.git_ai(&[
+        "checkpoint",
+        "claude",
+        "--hook-input",
+        &hook_input("toolu_parent", None),
+    ])
+    .unwrap();
+    fs::write(
+        &file_path,
+        "fn main() {}\n// parent edit\n// subagent edit\n",
+    )
+    .unwrap();
+    repo.git_ai(&[
+        "checkpoint",
+        "claude",
+        "--hook-input",
+        &hook_input("toolu_subagent", Some(("a1b2", "explorer"))),
+    ])
+    .unwrap();
+    repo.stage_all_and_commit("Add AI lines").unwrap();
+
+    let blame = repo.git_ai(&["blame", "--json", "main.rs"]).unwrap();
+    let blame_json: serde_json::Value = serde_json::from_str(&blame).unwrap();
+    let subagents: Vec<Option<&str>> = blame_json["ranges"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|range| range["tool_use_id"].is_string())
+        .map(|range| range["subagent"]["id"].as_str())
+        .collect();
+    assert_eq!(subagents, vec![None, Some("a1b2")]);
+
+    let stats = repo.git_ai(&["stats", "--detail", "--json"]).unwrap();
+    let stats_json: serde_json::Value = serde_json::from_str(&stats).unwrap();
+    assert_eq!(
+        stats_json["subagents"]["a1b2"],
+        json!({"agent_type": "explorer", "lines": 1})
+    );
+}
+
+#[test]
+fn test_claude_tool_version_from_transcript_is_reported_in_stats_detail() {
+    use crate::repos::test_repo::TestRepo;
+
+    let repo = TestRepo::new();
+    let repo_root = repo.canonical_path();
+    let file_path = repo_root.join("main.rs");
+    fs::write(&file_path, "fn main() {}\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let transcript_path = repo_root.join("claude-session.jsonl");
+    let transcript_entry = json!({
+        "type": "assistant",
+        "sessionId": "sess-versioned",
+        "version": "2.0.14",
+        "message": {"role": "assistant", "model": "claude-sonnet-4-5", "content": []}
+    });
+    fs::write(&transcript_path, format!("{}\n", transcript_entry)).unwrap();
+
+    fs::write(&file_path, "fn main() {}\n// ai edit\n").unwrap();
+    let hook_input = json!({
+        "cwd": repo_root.to_string_lossy().to_string(),
+        "hook_event_name": "PostToolUse",
+        "tool_name": "Edit",
+        "tool_use_id": "toolu_versioned",
+        "session_id": "sess-versioned",
+        "transcript_path": transcript_path.to_string_lossy().to_string(),
+        "tool_input": {
+            "file_path": file_path.to_string_lossy().to_string()
+        }
+    })
+    .to_string();
+    repo.git_ai(&["checkpoint", "claude", "--hook-input", &hook_input])
+        .unwrap();
+    repo.stage_all_and_commit("Add AI line").unwrap();
+
+    let stats = repo.git_ai(&["stats", "--detail", "--json"]).unwrap();
+    let stats_json: serde_json::Value = serde_json::from_str(&stats).unwrap();
+    assert_eq!(stats_json["tool_versions"]["claude 2.0.14"], json!(1));
+
+    let text = repo.git_ai(&["stats", "--detail"]).unwrap();
+    assert!(text.contains("Tool versions:"), "got: {}", text);
+    assert!(text.contains("claude 2.0.14  1 lines"), "got: {}", text);
+}
@@ -6,7 +6,7 @@
 //! tests cover `summarize_rebase_args`, which is still live in
 //! `src/git/cli_parser.rs`.
 
-use git_ai::git::cli_parser::summarize_rebase_args;
+use git_ai::git::cli_parser::{rebase_has_exec, summarize_rebase_args};
 
 /// Build a `command_args` slice as `summarize_rebase_args` expects (args after
 /// the "rebase" command word).
@@ -84,3 +84,12 @@ fn test_summarize_rebase_args_strategy_consumes_value() {
     assert!(!summary.is_control_mode);
     assert_eq!(summary.positionals, vec!["origin/main".to_string()]);
 }
+
+#[test]
+fn test_rebase_has_exec_detects_exec_forms() {
+    assert!(rebase_has_exec(&args(&["-x", "make test", "main"])));
+    assert!(rebase_has_exec(&args(&["--exec", "cargo fmt", "main"])));
+    assert!(rebase_has_exec(&args(&["--exec=cargo fmt", "main"])));
+    assert!(!rebase_has_exec(&args(&["-i", "main"])));
+    assert!(!rebase_has_exec(&args(&["main", "--", "-x"])));
+}
@@ -0,0 +1,149 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+use git_ai::authorship::authorship_log_serialization::AuthorshipLog;
+use std::fs;
+
+#[test]
+fn test_commit_all_with_uncheckpointed_delete_and_modify() {
+    let repo = TestRepo::new();
+    let mut kept = repo.filename("kept.txt");
+    kept.set_contents(crate::lines!["base".human()]);
+    let mut gone = repo.filename("gone.txt");
+    gone.set_contents(crate::lines!["one".human(), "two".human()]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // The agent edits both files, then the user deletes one outside any checkpoint.
+    kept.set_contents(crate::lines!["base".human(), "kept ai".ai()]);
+    gone.set_contents(crate::lines!["one".human(), "two".human(), "gone ai".ai()]);
+    fs::remove_file(repo.path().join("gone.txt")).unwrap();
+
+    let commit = repo
+        .commit_all("Modify one file, delete the other")
+        .expect("commit -a with a deleted file should write a note");
+
+    kept.assert_lines_and_blame(crate::lines!["base".human(), "kept ai".ai()]);
+
+    let deletions = &commit.authorship_log.metadata.deletions;
+    assert_eq!(deletions.len(), 1);
+    assert_eq!(deletions[0].file_path, "gone.txt");
+    assert_eq!(deletions[0].lines, 2);
+    assert!(
+        !deletions[0].author.starts_with("mock_ai"),
+        "a deletion no checkpoint saw belongs to the committer, got {}",
+        deletions[0].author
+    );
+    assert!(
+        commit
+            .authorship_log
+            .attestations
+            .iter()
+            .all(|attestation| attestation.file_path != "gone.txt")
+    );
+
+    // The deleted file's uncommitted AI line must not linger as carryover.
+    let initial = repo.current_working_logs().read_initial_attributions();
+    assert!(!initial.files.contains_key("gone.txt"));
+}
+
+#[test]
+fn test_commit_all_credits_deletion_to_the_checkpoint_that_recorded_it() {
+    let repo = TestRepo::new();
+    let mut kept = repo.filename("kept.txt");
+    kept.set_contents(crate::lines!["base".human()]);
+    fs::write(repo.path().join("gone.txt"), "one\ntwo\nthree\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    kept.set_contents(crate::lines!["base".human(), "kept ai".ai()]);
+    repo.git_ai(&["checkpoint", "human", "gone.txt"]).unwrap();
+    fs::remove_file(repo.path().join("gone.txt")).unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "gone.txt"])
+        .expect("checkpoint on a deleted file should succeed");
+
+    let commit = repo
+        .commit_all("Agent deletes a file")
+        .expect("commit -a with a deleted file should write a note");
+
+    let deletions = &commit.authorship_log.metadata.deletions;
+    assert_eq!(deletions.len(), 1);
+    assert_eq!(deletions[0].file_path, "gone.txt");
+    assert_eq!(deletions[0].lines, 3);
+    assert!(
+        deletions[0].author.starts_with("mock_ai/"),
+        "expected the AI checkpoint to own the deletion, got {}",
+        deletions[0].author
+    );
+    kept.assert_lines_and_blame(crate::lines!["base".human(), "kept ai".ai()]);
+}
+
+#[test]
+fn test_commit_all_records_a_deletion_only_commit() {
+    let repo = TestRepo::new();
+    let mut kept = repo.filename("kept.txt");
+    kept.set_contents(crate::lines!["base".human()]);
+    fs::write(repo.path().join("gone.txt"), "one\ntwo\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::remove_file(repo.path().join("gone.txt")).unwrap();
+    let commit = repo
+        .commit_all("Only delete a file")
+        .expect("a deletion-only commit -a should write a note");
+
+    let deletions = &commit.authorship_log.metadata.deletions;
+    assert_eq!(deletions.len(), 1);
+    assert_eq!(deletions[0].file_path, "gone.txt");
+    assert_eq!(deletions[0].lines, 2);
+    assert!(commit.authorship_log.attestations.is_empty());
+    kept.assert_lines_and_blame(crate::lines!["base".human()]);
+}
+
+#[test]
+fn test_amend_keeps_the_deletion_and_its_author() {
+    let repo = TestRepo::new();
+    let mut kept = repo.filename("kept.txt");
+    kept.set_contents(crate::lines!["base".human()]);
+    fs::write(repo.path().join("gone.txt"), "one\ntwo\nthree\n").unwrap();
+    fs::write(repo.path().join("moved.txt"), "a\nb\nc\nd\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    repo.git_ai(&["checkpoint", "human", "gone.txt"]).unwrap();
+    fs::remove_file(repo.path().join("gone.txt")).unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "gone.txt"])
+        .expect("checkpoint on a deleted file should succeed");
+    repo.commit_all("Agent deletes a file").unwrap();
+
+    // The amend also renames a file, which must not read as a deletion.
+    kept.set_contents(crate::lines!["base".human(), "kept ai".ai()]);
+    repo.git(&["mv", "moved.txt", "renamed.txt"]).unwrap();
+    repo.git(&[
+        "commit",
+        "-a",
+        "--amend",
+        "-m",
+        "Agent deletes a file (amended)",
+    ])
+    .unwrap();
+
+    let amended_sha = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+    let amended_note = repo
+        .read_authorship_note(&amended_sha)
+        .expect("amended commit should have authorship note");
+    let amended_log =
+        AuthorshipLog::deserialize_from_string(&amended_note).expect("parse amended note");
+    let deletions = &amended_log.metadata.deletions;
+    assert_eq!(deletions.len(), 1);
+    assert_eq!(deletions[0].file_path, "gone.txt");
+    assert_eq!(deletions[0].lines, 3);
+    assert!(
+        deletions[0].author.starts_with("mock_ai/"),
+        "the amend should keep the original deletion author, got {}",
+        deletions[0].author
+    );
+    kept.assert_lines_and_blame(crate::lines!["base".human(), "kept ai".ai()]);
+}
+
+crate::reuse_tests_in_worktree!(
+    test_commit_all_with_uncheckpointed_delete_and_modify,
+    test_commit_all_credits_deletion_to_the_checkpoint_that_recorded_it,
+    test_commit_all_records_a_deletion_only_commit,
+    test_amend_keeps_the_deletion_and_its_author,
+);
@@ -0,0 +1,190 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+
+#[test]
+fn test_commit_appends_attribution_footer_and_keeps_blame() {
+    let repo = TestRepo::new();
+    let mut base = repo.filename("base.txt");
+    base.set_contents(crate::lines!["base"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let mut file = repo.filename("feature.txt");
+    file.set_contents(crate::lines![
+        "human line".human(),
+        "ai line 1".ai(),
+        "ai line 2".ai(),
+    ]);
+    repo.git(&["add", "feature.txt"]).unwrap();
+
+    repo.git_ai(&["commit", "-m", "Add feature"])
+        .expect("git-ai commit should succeed");
+
+    let message = repo.git(&["log", "-1", "--format=%B"]).unwrap();
+    assert!(message.starts_with("Add feature\n\n"), "got: {}", message);
+    assert!(
+        message.contains("Git-AI-Lines: 2 of 3 added lines by AI"),
+        "got: {}",
+        message
+    );
+    assert!(
+        message.contains("Git-AI-Tool: mock_ai "),
+        "footer should name the tool, got: {}",
+        message
+    );
+
+    file.assert_lines_and_blame(crate::lines![
+        "human line".human(),
+        "ai line 1".ai(),
+        "ai line 2".ai(),
+    ]);
+}
+
+#[test]
+fn test_commit_dry_run_generates_subject_without_committing() {
+    let repo = TestRepo::new();
+    let mut base = repo.filename("notes.txt");
+    base.set_contents(crate::lines!["base"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let mut file = repo.filename("notes.txt");
+    file.set_contents(crate::lines!["base".human(), "ai line".ai()]);
+    repo.git(&["add", "notes.txt"]).unwrap();
+
+    let output = repo
+        .git_ai(&["commit", "--dry-run"])
+        .expect("dry run should succeed");
+    assert!(
+        output.starts_with("Update notes.txt\n\n"),
+        "got: {}",
+        output
+    );
+    assert!(
+        output.contains("Git-AI-Lines: 1 of 1 added lines by AI"),
+        "got: {}",
+        output
+    );
+
+    let head_message = repo.git(&["log", "-1", "--format=%s"]).unwrap();
+    assert_eq!(head_message.trim(), "Initial commit");
+}
+
+#[test]
+fn test_commit_without_staged_changes_fails() {
+    let repo = TestRepo::new();
+    let mut base = repo.filename("base.txt");
+    base.set_contents(crate::lines!["base"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let err = repo
+        .git_ai(&["commit", "-m", "nothing"])
+        .expect_err("commit without staged changes should fail");
+    assert!(err.contains("nothing staged to commit"), "got: {}", err);
+}
+
+#[test]
+fn test_commit_applies_workspace_config_from_repo() {
+    let repo = TestRepo::new();
+    std::fs::write(
+        repo.path().join(".git-ai.toml"),
+        "\
+required_trailers = [\"AI-Attribution\", \"Reviewed-by\"]
+
+[policy]
+max_ai_share_pct = 50
+
+[normalize.tools]
+mock_ai = \"team-bot\"
+",
+    )
+    .unwrap();
+    repo.stage_all_and_commit("Add workspace config").unwrap();
+
+    let mut file = repo.filename("feature.txt");
+    file.set_contents(crate::lines!["ai line 1".ai(), "ai line 2".ai()]);
+    repo.git(&["add", "feature.txt"]).unwrap();
+
+    let err = repo
+        .git_ai(&["commit", "--dry-run", "-m", "Add feature"])
+        .expect_err("missing required trailer should fail");
+    assert!(
+        err.contains("requires commit trailers: Reviewed-by"),
+        "got: {}",
+        err
+    );
+
+    let output = repo
+        .git_ai(&[
+            "commit",
+            "--dry-run",
+            "-m",
+            "Add feature\n\nReviewed-by: Sam",
+        ])
+        .expect("dry run with required trailers should succeed");
+    assert!(output.contains("policy maximum of 50%"), "got: {}", output);
+    assert!(output.contains("Git-AI-Tool: team-bot "), "got: {}", output);
+    assert!(
+        output.contains("AI-Attribution: ai=2 human=0 mixed=0 tools=team-bot"),
+        "got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_commit_blocks_secrets_in_ai_lines_when_configured() {
+    let repo = TestRepo::new();
+    std::fs::write(
+        repo.path().join(".git-ai.toml"),
+        "[policy]\nai_secret_scan = \"block\"\n",
+    )
+    .unwrap();
+    repo.stage_all_and_commit("Add workspace config").unwrap();
+
+    let mut file = repo.filename("config.rs");
+    file.set_contents(crate::lines![
+        "const HUMAN_KEY: &str = \"sk_test_4eC39HqLyjWDarjtT1zdp7dc\";".human(),
+        "const AI_KEY: &str = \"pk_test_TYooMQauvdEDq54NiTphI7jx\";".ai(),
+    ]);
+    repo.git(&["add", "config.rs"]).unwrap();
+
+    let err = repo
+        .git_ai(&["commit", "-m", "Add keys"])
+        .expect_err("AI-authored secret should block the commit");
+    assert!(
+        err.contains("config.rs:2  pk_t********I7jx"),
+        "got: {}",
+        err
+    );
+    assert!(!err.contains("sk_t********p7dc"), "got: {}", err);
+    assert!(err.contains("Commit blocked"), "got: {}", err);
+
+    repo.git_ai(&["commit", "-m", "Add keys", "--no-verify"])
+        .expect("--no-verify should skip the scan");
+    let subject = repo.git(&["log", "-1", "--format=%s"]).unwrap();
+    assert_eq!(subject.trim(), "Add keys");
+}
+
+#[test]
+fn test_commit_secret_scan_is_skipped_by_no_verify_in_a_flag_cluster() {
+    let repo = TestRepo::new();
+    std::fs::write(
+        repo.path().join(".git-ai.toml"),
+        "[policy]\nai_secret_scan = \"block\"\n",
+    )
+    .unwrap();
+    let mut file = repo.filename("config.rs");
+    file.set_contents(crate::lines![
+        "const AI_KEY: &str = \"placeholder\";".human()
+    ]);
+    repo.stage_all_and_commit("Add workspace config").unwrap();
+
+    file.set_contents(crate::lines![
+        "const AI_KEY: &str = \"pk_test_TYooMQauvdEDq54NiTphI7jx\";".ai(),
+    ]);
+    repo.git_ai(&["commit", "-am", "Add key"])
+        .expect_err("AI-authored secret should block the commit");
+
+    repo.git_ai(&["commit", "-anm", "Add key"])
+        .expect("-n inside a flag cluster should skip the scan");
+    let subject = repo.git(&["log", "-1", "--format=%s"]).unwrap();
+    assert_eq!(subject.trim(), "Add key");
+}
@@ -80,3 +80,35 @@ fn commit_msg_hook_rewriting_subject_still_writes_authorship_note() {
         "print('ai wrote this')".ai(),
     ]);
 }
+
+/// `git commit --no-verify` skips the pre-commit hook but not git-ai: the note
+/// is built from the working log either way, so attribution must match what a
+/// verified commit would have recorded.
+#[cfg(unix)]
+#[test]
+fn no_verify_commit_past_blocking_pre_commit_hook_keeps_attribution() {
+    let repo = TestRepo::new();
+    let mut app = repo.filename("app.py");
+    app.set_contents(crate::lines!["print('start')".human()]);
+    repo.stage_all_and_commit("initial commit").unwrap();
+
+    install_git_hook(&repo, "pre-commit", "#!/bin/sh\nexit 1\n");
+
+    app.set_contents(crate::lines![
+        "print('start')".human(),
+        "print('ai wrote this')".ai(),
+        "print('human wrote this')".human(),
+    ]);
+    repo.git(&["add", "app.py"]).unwrap();
+    repo.git(&["commit", "-m", "blocked"])
+        .expect_err("the pre-commit hook should block a verified commit");
+
+    repo.git(&["commit", "--no-verify", "-m", "add lines"])
+        .expect("--no-verify should bypass the pre-commit hook");
+
+    app.assert_lines_and_blame(crate::lines![
+        "print('start')".human(),
+        "print('ai wrote this')".ai(),
+        "print('human wrote this')".human(),
+    ]);
+}
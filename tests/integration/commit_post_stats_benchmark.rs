@@ -10,6 +10,7 @@ use git_ai::authorship::diff_ai_accepted::diff_ai_accepted_stats;
 use git_ai::authorship::post_commit::estimate_stats_cost_for_head;
 use git_ai::authorship::stats::{get_git_diff_stats, stats_for_commit_stats};
 use git_ai::git::find_repository_in_path;
+use git_ai::workspace_config::WhitespaceMode;
 use std::fs;
 use std::path::Path;
 use std::process::Command;
@@ -177,7 +178,8 @@ fn benchmark_stats(repo_path: &Path) -> StatsBreakdown {
     let diff_ai_accepted = diff_ai_start.elapsed();
 
     let total_stats_start = Instant::now();
-    let _stats = stats_for_commit_stats(&repo, &head_sha, &[]).expect("stats_for_commit_stats");
+    let _stats = stats_for_commit_stats(&repo, &head_sha, &[], WhitespaceMode::Exact)
+        .expect("stats_for_commit_stats");
     let total_stats = total_stats_start.elapsed();
 
     StatsBreakdown {
@@ -387,7 +389,8 @@ fn benchmark_stats_thousands_changed_files_fast_path() {
         .expect("failed to resolve HEAD target");
 
     // Warm-up to avoid one-time setup noise.
-    let warmup_stats = stats_for_commit_stats(&repo, &head_sha, &[]).expect("warmup stats failed");
+    let warmup_stats = stats_for_commit_stats(&repo, &head_sha, &[], WhitespaceMode::Exact)
+        .expect("warmup stats failed");
     assert_eq!(
         warmup_stats.git_diff_added_lines, file_count as u32,
         "expected one added line per changed file"
@@ -396,7 +399,8 @@ fn benchmark_stats_thousands_changed_files_fast_path() {
     let mut runs = Vec::with_capacity(runs_count);
     for _ in 0..runs_count {
         let start = Instant::now();
-        let stats = stats_for_commit_stats(&repo, &head_sha, &[]).expect("stats_for_commit_stats");
+        let stats = stats_for_commit_stats(&repo, &head_sha, &[], WhitespaceMode::Exact)
+            .expect("stats_for_commit_stats");
         let elapsed = start.elapsed();
         assert_eq!(stats.git_diff_added_lines, file_count as u32);
         runs.push(elapsed);
@@ -519,7 +523,7 @@ fn benchmark_stats_mass_deletion_fast_path() {
     let ignore_patterns: Vec<String> = vec![];
 
     let start = Instant::now();
-    let _stats = stats_for_commit_stats(&repo, &head_sha, &ignore_patterns)
+    let _stats = stats_for_commit_stats(&repo, &head_sha, &ignore_patterns, WhitespaceMode::Exact)
         .expect("stats_for_commit_stats should succeed");
     let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
 
@@ -7,6 +7,7 @@
 
 use crate::repos::test_repo::TestRepo;
 use git_ai::config::{AuthorConfig, FileConfig, NotesBackendConfig};
+use git_ai::workspace_config::WorkspaceConfig;
 use serde_json::Value;
 use std::collections::HashMap;
 
@@ -281,6 +282,9 @@ fn fully_populated_file_config() -> FileConfig {
         exclude_repositories: Some(vec!["*".to_string()]),
         telemetry_oss: Some("off".to_string()),
         telemetry_enterprise_dsn: Some("https://example.com".to_string()),
+        telemetry_metrics: Some("off".to_string()),
+        telemetry_prompts: Some("off".to_string()),
+        telemetry_crash_reports: Some("off".to_string()),
         disable_version_checks: Some(true),
         disable_auto_updates: Some(true),
         update_channel: Some("latest".to_string()),
@@ -300,9 +304,27 @@ fn fully_populated_file_config() -> FileConfig {
         codex_hooks_format: Some("config_toml".to_string()),
         notes_backend: Some(NotesBackendConfig::default()),
         transcript_streaming_lookback_days: Some(7),
+        diff_algorithm: Some("histogram".to_string()),
         max_checkpoint_file_size_bytes: Some(3 * 1024 * 1024),
         max_checkpoint_total_size_bytes: Some(32 * 1024 * 1024),
         max_checkpoint_total_lines: Some(500_000),
+        max_note_size_bytes: Some(512 * 1024),
+        trace_file: Some("/tmp/git-ai-trace.jsonl".to_string()),
+        slack_webhook_url: Some("https://hooks.slack.com/services/T0/B0/x".to_string()),
+        commit_webhook_url: Some("https://example.com/git-ai/commits".to_string()),
+        commit_webhook_secret: Some("webhook-secret".to_string()),
+        workspace: Some(WorkspaceConfig {
+            exclude_paths: vec!["generated/**".to_string()],
+            ..Default::default()
+        }),
+        hook_timeouts: Some(HashMap::from([("fetch_notes".to_string(), 10_000)])),
+        max_hook_latency_ms: Some(2_000),
+        ai_storage_warn_mb: Some(512),
+        working_logs_dir: Some("/tmp/git-ai-working-logs".to_string()),
+        anonymize_repositories: Some(vec!["*".to_string()]),
+        anonymization_key: Some("0123456789abcdef".to_string()),
+        workspace_roots: Some(vec!["/tmp/workspace".to_string()]),
+        repo_sync_min_interval_ms: Some(60_000),
     }
 }
 
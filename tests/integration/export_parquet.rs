@@ -0,0 +1,66 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::RowAccessor;
+use std::fs::File;
+
+fn read_rows(path: &std::path::Path) -> Vec<parquet::record::Row> {
+    let reader = SerializedFileReader::new(File::open(path).unwrap()).unwrap();
+    reader
+        .get_row_iter(None)
+        .unwrap()
+        .map(|row| row.unwrap())
+        .collect()
+}
+
+#[test]
+fn test_export_parquet_writes_commit_and_checkpoint_tables() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(crate::lines!["fn base() {}".human()]);
+    let base = repo.stage_all_and_commit("Initial").unwrap();
+
+    file.set_contents(crate::lines![
+        "fn base() {}".human(),
+        "fn ai() {}".ai(),
+        "fn ai2() {}".ai()
+    ]);
+    let ai_commit = repo.stage_all_and_commit("AI change").unwrap();
+
+    let out_dir = repo.path().join("export-out");
+    repo.git_ai(&[
+        "export",
+        "--format",
+        "parquet",
+        "--output-dir",
+        out_dir.to_str().unwrap(),
+    ])
+    .expect("export should succeed");
+
+    let commits = read_rows(&out_dir.join("commits.parquet"));
+    let shas: Vec<&String> = commits
+        .iter()
+        .map(|row| row.get_string(0).unwrap())
+        .collect();
+    assert_eq!(shas, vec![&base.commit_sha, &ai_commit.commit_sha]);
+    // ai_additions is the seventh column.
+    assert_eq!(commits[1].get_int(6).unwrap(), 2);
+
+    let checkpoints = read_rows(&out_dir.join("checkpoints.parquet"));
+    let ai_rows: Vec<_> = checkpoints
+        .iter()
+        .filter(|row| row.get_string(0).unwrap() == &ai_commit.commit_sha)
+        .filter(|row| row.get_string(3).unwrap() == "ai")
+        .collect();
+    assert!(!ai_rows.is_empty(), "{:?}", checkpoints);
+    assert_eq!(ai_rows[0].get_string(1).unwrap(), "lib.rs");
+    let ai_lines: i32 = ai_rows.iter().map(|row| row.get_int(10).unwrap()).sum();
+    assert_eq!(ai_lines, 2);
+
+    let err = repo
+        .git_ai(&["export", "--format", "csv"])
+        .expect_err("only parquet is supported");
+    assert!(err.contains("unsupported export format"), "{}", err);
+}
+
+crate::reuse_tests_in_worktree!(test_export_parquet_writes_commit_and_checkpoint_tables,);
@@ -0,0 +1,188 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+use std::fs;
+
+/// Push a `feature` branch holding one AI-authored commit on top of a human
+/// base commit, then drop the local branch. Returns `(repo, upstream, base, ai)`.
+fn setup_pushed_ai_feature() -> (TestRepo, TestRepo, String, String) {
+    let (repo, upstream) = TestRepo::new_with_remote();
+
+    let mut file = repo.filename("feature.txt");
+    file.set_contents(crate::lines!["base line"]);
+    let base = repo.stage_all_and_commit("base").unwrap().commit_sha;
+    repo.git(&["push", "origin", "main"]).unwrap();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    file.insert_at(
+        1,
+        crate::lines!["AI line 1".ai(), "AI line 2".ai(), "AI line 3".ai()],
+    );
+    let ai = repo.stage_all_and_commit("AI work").unwrap().commit_sha;
+    repo.git(&["push", "-u", "origin", "feature"]).unwrap();
+    assert!(repo.read_authorship_note(&ai).is_some());
+
+    (repo, upstream, base, ai)
+}
+
+/// Rewrite `feature` on the upstream without touching the local tracking ref,
+/// as if another clone had force-pushed it, then fetch.
+fn force_push_from_elsewhere_and_fetch(repo: &TestRepo, upstream: &TestRepo, rewritten: &str) {
+    let upstream_path = upstream.path().to_string_lossy().to_string();
+    repo.git_og(&[
+        "push",
+        "--force",
+        &upstream_path,
+        &format!("{}:refs/heads/feature", rewritten),
+    ])
+    .unwrap();
+
+    repo.git(&["checkout", "main"]).unwrap();
+    repo.git(&["branch", "-D", "feature"]).unwrap();
+    repo.git(&["fetch", "origin"]).unwrap();
+}
+
+#[test]
+fn test_reconcile_restores_note_for_reworded_force_push() {
+    let (repo, upstream, base, ai) = setup_pushed_ai_feature();
+
+    // Same patch on the same parent, new message: patch-id match.
+    let tree = repo
+        .git_og(&["rev-parse", &format!("{}^{{tree}}", ai)])
+        .unwrap();
+    let rewritten = repo
+        .git_og(&[
+            "commit-tree",
+            tree.trim(),
+            "-p",
+            &base,
+            "-m",
+            "AI work (reworded)",
+        ])
+        .unwrap()
+        .trim()
+        .to_string();
+    force_push_from_elsewhere_and_fetch(&repo, &upstream, &rewritten);
+
+    let listed = repo.git_ai(&["reconcile", "--list"]).unwrap();
+    assert!(
+        listed.contains("refs/remotes/origin/feature"),
+        "expected quarantined feature rewrite, got: {}",
+        listed
+    );
+    assert!(repo.read_authorship_note(&rewritten).is_none());
+
+    let output = repo.git_ai(&["reconcile"]).unwrap();
+    assert!(output.contains("(patch-id)"), "got: {}", output);
+
+    let note = repo
+        .read_authorship_note(&rewritten)
+        .expect("rewritten commit should carry the quarantined note");
+    assert!(note.contains("feature.txt"), "note: {}", note);
+
+    let again = repo.git_ai(&["reconcile"]).unwrap();
+    assert!(again.contains("Nothing to reconcile"), "got: {}", again);
+}
+
+#[test]
+fn test_reconcile_matches_edited_force_push_by_similarity() {
+    let (repo, upstream, base, ai) = setup_pushed_ai_feature();
+
+    // Same AI lines plus a reviewer tweak: the patch-id changes.
+    let mut content = fs::read_to_string(repo.path().join("feature.txt")).unwrap();
+    if !content.ends_with('\n') {
+        content.push('\n');
+    }
+    let scratch = tempfile::tempdir().unwrap();
+    let edited = scratch.path().join("feature-edited.txt");
+    fs::write(&edited, format!("{}reviewer tweak\n", content)).unwrap();
+    let blob = repo
+        .git_og(&["hash-object", "-w", edited.to_str().unwrap()])
+        .unwrap();
+
+    let index = scratch.path().join("index");
+    let index = index.to_str().unwrap();
+    let env = [("GIT_INDEX_FILE", index)];
+    repo.git_og_with_env(&["read-tree", &ai], &env).unwrap();
+    repo.git_og_with_env(
+        &[
+            "update-index",
+            "--cacheinfo",
+            &format!("100644,{},feature.txt", blob.trim()),
+        ],
+        &env,
+    )
+    .unwrap();
+    let tree = repo.git_og_with_env(&["write-tree"], &env).unwrap();
+    let rewritten = repo
+        .git_og(&[
+            "commit-tree",
+            tree.trim(),
+            "-p",
+            &base,
+            "-m",
+            "AI work (edited)",
+        ])
+        .unwrap()
+        .trim()
+        .to_string();
+    force_push_from_elsewhere_and_fetch(&repo, &upstream, &rewritten);
+
+    let dry_run = repo.git_ai(&["reconcile", "--dry-run"]).unwrap();
+    assert!(dry_run.contains("(similar patch)"), "got: {}", dry_run);
+    assert!(repo.read_authorship_note(&rewritten).is_none());
+
+    repo.git_ai(&["reconcile"]).unwrap();
+    let note = repo
+        .read_authorship_note(&rewritten)
+        .expect("edited commit should carry the shifted note");
+    assert!(note.contains("feature.txt"), "note: {}", note);
+}
+
+#[test]
+fn test_force_push_is_quarantined_only_after_local_branch_is_gone() {
+    let (repo, upstream, base, ai) = setup_pushed_ai_feature();
+
+    let tree = repo
+        .git_og(&["rev-parse", &format!("{}^{{tree}}", ai)])
+        .unwrap();
+    let rewritten = repo
+        .git_og(&[
+            "commit-tree",
+            tree.trim(),
+            "-p",
+            &base,
+            "-m",
+            "AI work (reworded)",
+        ])
+        .unwrap()
+        .trim()
+        .to_string();
+    let upstream_path = upstream.path().to_string_lossy().to_string();
+    repo.git_og(&[
+        "push",
+        "--force",
+        &upstream_path,
+        &format!("{}:refs/heads/feature", rewritten),
+    ])
+    .unwrap();
+
+    // The local feature branch still holds the AI commit: nothing is lost yet.
+    repo.git(&["checkout", "main"]).unwrap();
+    repo.git(&["fetch", "origin"]).unwrap();
+    let listed = repo.git_ai(&["reconcile", "--list"]).unwrap();
+    assert!(
+        !listed.contains("refs/remotes/origin/feature"),
+        "nothing should be quarantined while the branch exists, got: {}",
+        listed
+    );
+
+    // Once the branch is deleted, the next fetch quarantines the same update.
+    repo.git(&["branch", "-D", "feature"]).unwrap();
+    repo.git(&["fetch", "origin"]).unwrap();
+    let listed = repo.git_ai(&["reconcile", "--list"]).unwrap();
+    assert!(
+        listed.contains("refs/remotes/origin/feature"),
+        "expected quarantined feature rewrite after deleting the branch, got: {}",
+        listed
+    );
+}
@@ -0,0 +1,88 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+
+/// One commit with AI lines 2-3 of `lib.rs`. Returns `(repo, commit_sha)`.
+fn repo_with_ai_commit() -> (TestRepo, String) {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(crate::lines![
+        "human line",
+        "AI line 1".ai(),
+        "AI line 2".ai()
+    ]);
+    let sha = repo.stage_all_and_commit("AI work").unwrap().commit_sha;
+    assert!(repo.read_authorship_note(&sha).is_some());
+    (repo, sha)
+}
+
+fn overwrite_note(repo: &TestRepo, commit_sha: &str, note: &str) {
+    repo.git_og(&["notes", "--ref=ai", "add", "-f", "-m", note, commit_sha])
+        .unwrap();
+}
+
+#[test]
+fn test_fsck_passes_on_healthy_attribution() {
+    let (repo, _) = repo_with_ai_commit();
+
+    let output = repo.git_ai(&["fsck"]).unwrap();
+    assert!(output.contains("0 issue(s)"), "got: {}", output);
+}
+
+#[test]
+fn test_fsck_reports_and_prunes_out_of_range_lines() {
+    let (repo, sha) = repo_with_ai_commit();
+
+    // Stretch the AI entry past the end of the three-line file.
+    let note = repo.read_authorship_note(&sha).unwrap();
+    let (attestations, metadata) = note.split_once("---\n").unwrap();
+    let attestations: Vec<String> = attestations
+        .lines()
+        .map(|line| {
+            if line.starts_with("  ") {
+                format!("{},40-45", line)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    let corrupt = format!("{}\n---\n{}", attestations.join("\n"), metadata);
+    overwrite_note(&repo, &sha, &corrupt);
+
+    let output = repo
+        .git_ai(&["fsck"])
+        .expect_err("fsck should fail on a corrupt note");
+    assert!(output.contains("invalid-range"), "got: {}", output);
+    assert!(
+        output.contains("exceed the file's 3 lines"),
+        "got: {}",
+        output
+    );
+
+    repo.git_ai(&["fsck", "--prune"]).unwrap();
+    let repaired = repo.read_authorship_note(&sha).unwrap();
+    assert!(!repaired.contains("40-45"), "note: {}", repaired);
+    assert!(repaired.contains("lib.rs"), "note: {}", repaired);
+
+    let output = repo.git_ai(&["fsck"]).unwrap();
+    assert!(output.contains("0 issue(s)"), "got: {}", output);
+}
+
+#[test]
+fn test_fsck_prune_removes_unparseable_note() {
+    let (repo, sha) = repo_with_ai_commit();
+    overwrite_note(&repo, &sha, "not an authorship note");
+
+    let output = repo
+        .git_ai(&["fsck", "--json"])
+        .expect_err("fsck should fail");
+    assert!(output.contains("\"unparseable_note\""), "got: {}", output);
+
+    repo.git_ai(&["fsck", "--prune"]).unwrap();
+    assert!(repo.read_authorship_note(&sha).is_none());
+}
+
+crate::reuse_tests_in_worktree!(
+    test_fsck_passes_on_healthy_attribution,
+    test_fsck_reports_and_prunes_out_of_range_lines,
+    test_fsck_prune_removes_unparseable_note,
+);
@@ -0,0 +1,38 @@
+use crate::repos::test_repo::TestRepo;
+
+#[test]
+fn test_help_for_one_command_prints_its_block() {
+    let repo = TestRepo::new();
+
+    let output = repo
+        .git_ai(&["help", "stats"])
+        .expect("help for a known command should succeed");
+    assert!(output.contains("Usage: git ai stats"), "{}", output);
+    assert!(output.contains("--by-file"), "{}", output);
+    assert!(
+        !output.contains("Show local AI usage statistics"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_unknown_commands_exit_nonzero_with_hint() {
+    let repo = TestRepo::new();
+
+    let err = repo
+        .git_ai(&["help", "no-such-command"])
+        .expect_err("help for an unknown command should fail");
+    assert!(err.contains("git ai help"), "{}", err);
+
+    let err = repo
+        .git_ai(&["no-such-command"])
+        .expect_err("unknown command should fail");
+    assert!(err.contains("Unknown git-ai command"), "{}", err);
+    assert!(err.contains("git ai help"), "{}", err);
+}
+
+crate::reuse_tests_in_worktree!(
+    test_help_for_one_command_prints_its_block,
+    test_unknown_commands_exit_nonzero_with_hint,
+);
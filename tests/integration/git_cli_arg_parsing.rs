@@ -1,4 +1,6 @@
-use git_ai::git::cli_parser::parse_git_cli_args;
+use git_ai::git::cli_parser::{
+    commit_creates_commit, git_ai_subcommand_args, parse_git_cli_args, summarize_commit_args,
+};
 
 fn s(v: &[&str]) -> Vec<String> {
     v.iter().map(|x| x.to_string()).collect()
@@ -965,3 +967,84 @@ fn pull_verbose_flag_not_treated_as_version() {
     assert_eq!(got.command.as_deref(), Some("pull"));
     assert_eq!(got.command_args, s(&["-v"]));
 }
+
+#[test]
+fn git_ai_subcommand_keeps_repository_args() {
+    let args = s(&[
+        "-C",
+        "repo",
+        "-c",
+        "core.pager=less",
+        "--no-pager",
+        "ai",
+        "stats",
+        "--json",
+    ]);
+    let got = git_ai_subcommand_args(&parse_git_cli_args(&args));
+    assert_eq!(got, Some(s(&["-C", "repo", "stats", "--json"])));
+}
+
+#[test]
+fn git_help_ai_routes_to_git_ai_help() {
+    let got = git_ai_subcommand_args(&parse_git_cli_args(&s(&["help", "ai", "stats"])));
+    assert_eq!(got, Some(s(&["help", "stats"])));
+
+    let got = git_ai_subcommand_args(&parse_git_cli_args(&s(&["--help", "ai"])));
+    assert_eq!(got, Some(s(&["help"])));
+
+    assert_eq!(
+        git_ai_subcommand_args(&parse_git_cli_args(&s(&["help", "commit"]))),
+        None
+    );
+    assert_eq!(
+        git_ai_subcommand_args(&parse_git_cli_args(&s(&["status"]))),
+        None
+    );
+}
+
+#[test]
+fn commit_dry_run_and_status_formats_do_not_create_commits() {
+    assert!(commit_creates_commit(&s(&["-m", "msg"])));
+    assert!(commit_creates_commit(&s(&["-v", "--amend", "--no-edit"])));
+    assert!(!commit_creates_commit(&s(&["--dry-run", "-m", "msg"])));
+    assert!(!commit_creates_commit(&s(&["--short"])));
+    assert!(!commit_creates_commit(&s(&["--porcelain=v2", "-a"])));
+    assert!(!commit_creates_commit(&s(&["--long", "--no-dry-run"])));
+    assert!(!commit_creates_commit(&s(&["-qz"])));
+
+    // Values of message options are never mistaken for flags.
+    assert!(commit_creates_commit(&s(&["-m", "--dry-run"])));
+    assert!(commit_creates_commit(&s(&["--message", "--short"])));
+    assert!(commit_creates_commit(&s(&["-am", "--dry-run"])));
+    assert!(commit_creates_commit(&s(&["--dry-run", "--no-dry-run"])));
+}
+
+#[test]
+fn commit_only_include_and_pathspecs() {
+    let summary = summarize_commit_args(&s(&["-o", "src/a.rs", "-m", "msg", "src/b.rs"]));
+    assert!(summary.only);
+    assert!(!summary.include);
+    assert!(summary.creates_commit());
+    assert_eq!(summary.pathspecs, s(&["src/a.rs", "src/b.rs"]));
+
+    let summary = summarize_commit_args(&s(&["--include", "-e", "-n", "--", "-weird"]));
+    assert!(summary.include);
+    assert_eq!(summary.pathspecs, s(&["-weird"]));
+
+    let summary = summarize_commit_args(&s(&["-C", "HEAD", "--amend", "-S", "file"]));
+    assert!(summary.amend);
+    assert_eq!(summary.pathspecs, s(&["file"]));
+
+    let summary = summarize_commit_args(&s(&["-m", "--amend"]));
+    assert!(!summary.amend);
+}
+
+#[test]
+fn commit_no_verify_flags() {
+    assert!(summarize_commit_args(&s(&["--no-verify", "-m", "msg"])).no_verify);
+    assert!(summarize_commit_args(&s(&["-anm", "msg"])).no_verify);
+    assert!(!summarize_commit_args(&s(&["-n", "--verify"])).no_verify);
+    // A message that reads like the flag is still a message.
+    assert!(!summarize_commit_args(&s(&["-m", "-n"])).no_verify);
+    assert!(!summarize_commit_args(&s(&["--message", "--no-verify"])).no_verify);
+}
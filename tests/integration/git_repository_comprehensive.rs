@@ -867,6 +867,44 @@ fn test_remotes_with_urls() {
     assert!(has_origin, "Should have origin remote with URL");
 }
 
+#[test]
+fn test_remotes_with_urls_applies_instead_of_and_prefers_first_url() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .git_og(&["remote", "add", "origin", "gh:acme/app.git"])
+        .unwrap();
+    test_repo
+        .git_og(&[
+            "config",
+            "--add",
+            "remote.origin.url",
+            "https://mirror.internal/acme/app.git",
+        ])
+        .unwrap();
+    test_repo
+        .git_og(&["config", "url.git@github.com:.insteadOf", "gh:"])
+        .unwrap();
+
+    let repo = find_repository(&[
+        "-C".to_string(),
+        test_repo.path().to_str().unwrap().to_string(),
+    ])
+    .unwrap();
+
+    let remotes_with_urls = repo.remotes_with_urls().unwrap();
+    assert_eq!(
+        remotes_with_urls,
+        vec![(
+            "origin".to_string(),
+            "git@github.com:acme/app.git".to_string()
+        )]
+    );
+    assert_eq!(
+        git_ai::repo_url::resolve_repo_url_from_repo(&repo).as_deref(),
+        Some("https://github.com/acme/app")
+    );
+}
+
 #[test]
 fn test_get_default_remote() {
     let (mirror, _upstream) = TestRepo::new_with_remote();
@@ -1515,6 +1553,7 @@ crate::reuse_tests_in_worktree!(
     test_remotes_empty,
     test_remotes_with_origin,
     test_remotes_with_urls,
+    test_remotes_with_urls_applies_instead_of_and_prefers_first_url,
     test_get_default_remote,
     test_get_default_remote_no_remotes,
     test_commit_range_length,
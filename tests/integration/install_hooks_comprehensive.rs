@@ -273,6 +273,72 @@ fn plain_install_hooks_preserves_the_invoking_user_home() {
     );
 }
 
+#[test]
+fn install_hooks_plan_json_reports_changes_without_applying() {
+    let repo = TestRepo::new_with_daemon_scope(DaemonTestScope::NoDaemon);
+    let home = repo.test_home_path();
+    let gitconfig = home.join(".gitconfig");
+    let original_gitconfig =
+        "[core]\n\thooksPath = /opt/hooks\n[trace2]\n\tperfTarget = /tmp/perf\n";
+    fs::write(&gitconfig, original_gitconfig).unwrap();
+    let config_path = home.join(".git-ai").join("config.json");
+    let original_config = fs::read_to_string(&config_path).ok();
+
+    let test_db = repo.path().join("install-hooks.db");
+    let mut command = Command::new(get_binary_path());
+    command
+        .args(["install-hooks", "--plan-json"])
+        .current_dir(repo.path())
+        .env("HOME", home)
+        .env("API_KEY", "package-test-key")
+        .env("GIT_AI_TEST_DB_PATH", &test_db)
+        .env("GITAI_TEST_DB_PATH", &test_db)
+        .env("GIT_CONFIG_GLOBAL", &gitconfig)
+        .env("GIT_AI_ALLOW_SUPERUSER", "1")
+        .env("GIT_AI_DEBUG", "0");
+    #[cfg(windows)]
+    command
+        .env("USERPROFILE", home)
+        .env("APPDATA", home.join("AppData").join("Roaming"))
+        .env("LOCALAPPDATA", home.join("AppData").join("Local"));
+
+    let output = command
+        .output()
+        .expect("run git-ai install-hooks --plan-json");
+    assert!(
+        output.status.success(),
+        "install-hooks --plan-json failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap_or_else(|e| {
+        panic!(
+            "plan is not JSON ({e}): {}",
+            String::from_utf8_lossy(&output.stdout)
+        )
+    });
+
+    let git_config = plan["git_config"].as_array().unwrap();
+    let planned = |key: &str| {
+        git_config
+            .iter()
+            .find(|setting| setting["key"].as_str().unwrap().eq_ignore_ascii_case(key))
+            .map(|setting| setting["planned"].clone())
+    };
+    assert_eq!(planned("trace2.perfTarget"), Some(serde_json::Value::Null));
+    assert!(planned("trace2.eventTarget").is_some_and(|value| value.is_string()));
+    assert!(planned("merge.git-ai-notes.driver").is_none());
+    assert_eq!(
+        plan["preserved_git_config"][0],
+        serde_json::json!({ "key": "core.hooksPath", "value": "/opt/hooks" })
+    );
+    assert_eq!(plan["config_file"][0]["key"], "api_key");
+    assert_ne!(plan["config_file"][0]["planned"], "package-test-key");
+    assert!(plan["agents"].is_array());
+
+    assert_eq!(fs::read_to_string(&gitconfig).unwrap(), original_gitconfig);
+    assert_eq!(fs::read_to_string(&config_path).ok(), original_config);
+}
+
 #[test]
 fn test_run_install_hooks_no_args() {
     // This will try to run against the actual system, but should not crash
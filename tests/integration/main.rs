@@ -13,6 +13,7 @@ mod ai_reflow_attribution;
 mod ai_tab;
 mod amend;
 mod amp;
+mod attribution_comments;
 mod attribution_tracker_comprehensive;
 mod background_agent_attribution;
 mod bash_attribution;
@@ -23,9 +24,13 @@ mod bash_tool_timeouts;
 mod blame_comprehensive;
 mod blame_flags;
 mod blame_subdirectory;
+mod buffer_overlay_attribution;
+mod bundle_push;
 mod checkout_switch;
 mod checkpoint_debug_log;
 mod checkpoint_explicit_paths;
+mod checkpoint_hunks;
+mod checkpoint_labels;
 mod checkpoint_perf;
 mod checkpoint_size;
 mod checkpoint_telemetry;
@@ -43,6 +48,8 @@ mod claude_code;
 mod cli_parser_rebase_args;
 mod codex;
 mod cold_trace2_repo;
+mod commit_all_deletions;
+mod commit_command;
 mod commit_metric_metadata;
 mod commit_msg_hook_rewrite_note_loss;
 mod commit_post_stats_benchmark;
@@ -59,12 +66,17 @@ mod droid;
 mod e2big_post_filter;
 mod e2e_user_scenarios;
 mod event_timestamp_extraction;
+#[cfg(feature = "parquet-export")]
+mod export_parquet;
 mod fast_reader;
 mod fetch_notes;
 mod firebender;
+mod force_push_quarantine;
 mod formatting_non_substantial_ai_attribution;
+mod fsck;
 mod fuzzer;
 mod gemini;
+mod git_ai_help;
 mod git_alias_resolution;
 mod git_cli_arg_parsing;
 mod git_repository_comprehensive;
@@ -89,9 +101,14 @@ mod merge_rebase;
 mod metrics_retry_idle;
 mod multi_repo_workspace;
 mod non_utf8_files;
+mod note_overflow;
+mod notes_gc;
 mod notes_merge_mixed_fanout;
+mod notes_merge_union;
+mod notes_upgrade;
 mod opencode;
 mod pending_ai_edit_suppression;
+mod pending_notes;
 mod performance;
 mod performance_targets;
 mod pi;
@@ -100,6 +117,7 @@ mod pre_commit_unit;
 mod prompt_across_commit;
 mod prompt_hash_migration;
 mod prompt_utils_unit;
+mod prompts_command;
 mod pull_rebase_ff;
 mod push_upstream_authorship;
 mod range_authorship_unit;
@@ -107,19 +125,28 @@ mod realistic_complex_edits;
 mod rebase;
 mod rebase_attribution_remaining;
 mod rebase_benchmark;
+mod rebase_exec_attribution;
 mod rebase_merge_commit_note_leak;
 mod rebase_note_integrity;
 mod rebase_realworld;
+mod reconstruct;
 mod refs_unit;
+mod repair;
 mod repo_storage_unit;
+mod repository_discovery;
 mod repository_unit;
 mod reset;
+mod resolve;
+mod restore_attribution;
 mod rewrite_ops_attribution;
 mod secrets_benchmark;
+mod server_hooks;
 mod session_event_attribution;
 mod session_event_repo_url;
+mod session_override;
 mod sessions_backwards_compat;
 mod sessions_cutover;
+mod show_patch;
 mod show_prompt;
 mod simple_additions;
 mod simple_benchmark;
@@ -128,6 +155,7 @@ mod squash_merge;
 mod stale_prompt_carry;
 mod stash_attribution;
 mod stash_hooks_unit;
+mod state_bundle;
 mod stats;
 mod stats_unit;
 mod status_ignore;
@@ -135,8 +163,10 @@ mod status_unit;
 mod streams_claude_reader;
 mod streams_e2e;
 mod subdirs;
+mod subtree;
 mod superuser_guard;
 mod sweep_e2e;
+mod test_support_harness;
 mod test_utils_unit;
 mod tls_native_certs;
 mod utf8_filenames;
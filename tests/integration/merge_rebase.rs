@@ -1,5 +1,6 @@
 use crate::repos::test_file::ExpectedLineExt;
 use crate::repos::test_repo::TestRepo;
+use git_ai::authorship::authorship_log::LineRange;
 
 #[test]
 fn test_blame_after_merge_with_ai_contributions() {
@@ -336,7 +337,172 @@ fn test_merge_conflict_ai_resolution_outside_session() {
     ]);
 }
 
+/// A `git mergetool` wrapper reports the files an AI merge tool resolved; only
+/// the lines neither side had are credited to the tool in the merge note.
+#[test]
+fn test_mergetool_conflict_resolution_is_credited_in_merge_note() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.py");
+    file.set_contents(crate::lines!["class App:", "    pass"]);
+    repo.stage_all_and_commit("initial").unwrap();
+    let main_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    file.replace_at(1, "    def feature(): pass");
+    repo.stage_all_and_commit("feature change").unwrap();
+
+    repo.git(&["checkout", &main_branch]).unwrap();
+    let mut main_file = repo.filename("app.py");
+    main_file.replace_at(1, "    def main(): pass");
+    repo.stage_all_and_commit("main change").unwrap();
+
+    assert!(repo.git(&["merge", "feature"]).is_err());
+
+    // The merge tool keeps the feature method and writes a combined one.
+    std::fs::write(
+        repo.path().join("app.py"),
+        "class App:\n    def feature(): pass\n    def combined(): pass\n",
+    )
+    .unwrap();
+    repo.git(&["add", "app.py"]).unwrap();
+    repo.git_ai(&[
+        "checkpoint",
+        "--conflict-resolution",
+        "--tool",
+        "cursor",
+        "--model",
+        "gpt-5",
+        "app.py",
+    ])
+    .expect("recording a mergetool resolution should succeed");
+
+    let merge = repo.stage_all_and_commit("merge resolved by tool").unwrap();
+    let log = &merge.authorship_log;
+
+    let attestation = log
+        .attestations
+        .iter()
+        .find(|attestation| attestation.file_path == "app.py")
+        .expect("the resolved file should be attested");
+    assert_eq!(attestation.entries.len(), 1);
+    assert_eq!(
+        attestation.entries[0].line_ranges,
+        vec![LineRange::Single(3)]
+    );
+    let prompt = &log.metadata.prompts[&attestation.entries[0].hash];
+    assert_eq!(prompt.agent_id.tool, "cursor");
+    assert_eq!(prompt.agent_id.model, "gpt-5");
+
+    assert!(
+        log.metadata.labels.iter().any(|label| {
+            label.label == "conflict-resolution" && label.author == "cursor/gpt-5"
+        })
+    );
+}
+
+#[test]
+fn test_mergetool_conflict_resolution_requires_a_merge_in_progress() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.py");
+    file.set_contents(crate::lines!["class App:"]);
+    repo.stage_all_and_commit("initial").unwrap();
+
+    let output = repo
+        .git_ai(&[
+            "checkpoint",
+            "--conflict-resolution",
+            "--tool",
+            "cursor",
+            "app.py",
+        ])
+        .unwrap_or_else(|e| e);
+    assert!(
+        output.contains("no merge in progress"),
+        "unexpected output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_blame_after_octopus_merge_with_ai_contributions() {
+    let repo = TestRepo::new();
+    let mut base = repo.filename("base.txt");
+    base.set_contents(crate::lines!["base"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let default_branch = repo.current_branch();
+
+    for branch in ["feature-a", "feature-b", "feature-c"] {
+        repo.git(&["checkout", "-b", branch, &default_branch])
+            .unwrap();
+        let mut file = repo.filename(&format!("{}.txt", branch));
+        file.set_contents(crate::lines![
+            format!("{} human", branch).human(),
+            format!("{} ai", branch).ai(),
+        ]);
+        repo.stage_all_and_commit(&format!("{} changes", branch))
+            .unwrap();
+    }
+
+    repo.git(&["checkout", &default_branch]).unwrap();
+    repo.git(&[
+        "merge",
+        "feature-a",
+        "feature-b",
+        "feature-c",
+        "-m",
+        "octopus merge",
+    ])
+    .unwrap();
+
+    let parents = repo.git(&["show", "-s", "--format=%P", "HEAD"]).unwrap();
+    assert_eq!(parents.split_whitespace().count(), 4);
+
+    for branch in ["feature-a", "feature-b", "feature-c"] {
+        let mut file = repo.filename(&format!("{}.txt", branch));
+        file.assert_lines_and_blame(crate::lines![
+            format!("{} human", branch).human(),
+            format!("{} ai", branch).ai(),
+        ]);
+    }
+}
+
+#[test]
+fn test_octopus_merge_keeps_uncommitted_ai_attribution() {
+    let repo = TestRepo::new();
+    let mut base = repo.filename("base.txt");
+    base.set_contents(crate::lines!["base"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let default_branch = repo.current_branch();
+
+    for branch in ["feature-a", "feature-b"] {
+        repo.git(&["checkout", "-b", branch, &default_branch])
+            .unwrap();
+        let mut file = repo.filename(&format!("{}.txt", branch));
+        file.set_contents(crate::lines![format!("{} line", branch)]);
+        repo.stage_all_and_commit(&format!("{} changes", branch))
+            .unwrap();
+    }
+    repo.git(&["checkout", &default_branch]).unwrap();
+
+    // Uncommitted AI work on the target branch survives the merge untouched,
+    // so its working log must follow HEAD onto the octopus merge commit.
+    let mut wip = repo.filename("wip.txt");
+    wip.set_contents(crate::lines!["wip human".human(), "wip ai".ai()]);
+
+    repo.git(&["merge", "feature-a", "feature-b", "-m", "octopus merge"])
+        .unwrap();
+    let parents = repo.git(&["show", "-s", "--format=%P", "HEAD"]).unwrap();
+    assert_eq!(parents.split_whitespace().count(), 3);
+
+    repo.stage_all_and_commit("commit wip after merge").unwrap();
+    wip.assert_lines_and_blame(crate::lines!["wip human".human(), "wip ai".ai()]);
+}
+
 crate::reuse_tests_in_worktree!(
     test_blame_after_merge_conflict_resolution,
     test_merge_conflict_ai_resolution_outside_session,
+    test_mergetool_conflict_resolution_is_credited_in_merge_note,
+    test_mergetool_conflict_resolution_requires_a_merge_in_progress,
+    test_blame_after_octopus_merge_with_ai_contributions,
+    test_octopus_merge_keeps_uncommitted_ai_attribution,
 );
@@ -0,0 +1,100 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+use git_ai::authorship::authorship_log_serialization::AuthorshipLog;
+
+#[test]
+fn test_oversized_note_is_stored_as_overflow_and_read_transparently() {
+    let mut repo = TestRepo::new();
+    repo.patch_git_ai_config(|p| {
+        p.max_note_size_bytes = Some(64);
+    });
+
+    let mut file = repo.filename("a.txt");
+    file.set_contents(crate::lines!["one".ai(), "two".ai(), "three".human()]);
+    let commit = repo.stage_all_and_commit("large note").unwrap();
+
+    let stub = repo
+        .read_authorship_note(&commit.commit_sha)
+        .expect("commit should have a note");
+    let stub: serde_json::Value =
+        serde_json::from_str(stub.trim()).expect("oversized note should be a JSON stub");
+    assert_eq!(stub["git_ai_note_overflow"], 1);
+
+    let full = repo
+        .git_og(&["notes", "--ref=ai-overflow", "show", &commit.commit_sha])
+        .expect("full note should be in the overflow ref");
+    let overflow_blob = repo
+        .git_og(&["notes", "--ref=ai-overflow", "list", &commit.commit_sha])
+        .expect("overflow note blob");
+    assert_eq!(stub["blob"].as_str(), Some(overflow_blob.trim()));
+    let log = AuthorshipLog::deserialize_from_string(&full).expect("full note parses");
+    assert_eq!(log.attestations[0].file_path, "a.txt");
+
+    file.assert_lines_and_blame(crate::lines!["one".ai(), "two".ai(), "three".human()]);
+}
+
+#[test]
+fn test_note_under_the_limit_stays_inline() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("a.txt");
+    file.set_contents(crate::lines!["one".ai()]);
+    let commit = repo.stage_all_and_commit("small note").unwrap();
+
+    let note = repo
+        .read_authorship_note(&commit.commit_sha)
+        .expect("commit should have a note");
+    assert!(AuthorshipLog::deserialize_from_string(&note).is_ok());
+    assert!(
+        repo.git_og(&["notes", "--ref=ai-overflow", "show", &commit.commit_sha])
+            .is_err()
+    );
+}
+
+#[test]
+fn test_overflow_notes_are_pushed_and_fetched() {
+    let (mut mirror, upstream) = TestRepo::new_with_remote();
+    mirror.patch_git_ai_config(|p| {
+        p.max_note_size_bytes = Some(64);
+    });
+    let mut file = mirror.filename("a.txt");
+    file.set_contents(crate::lines!["one".ai(), "two".ai()]);
+    let commit = mirror.stage_all_and_commit("large note").unwrap();
+    mirror.git_og(&["push", "origin", "HEAD:main"]).unwrap();
+
+    // Push through the deferred-repair path, which runs the regular notes push.
+    let common_dir = mirror
+        .git_og(&["rev-parse", "--git-common-dir"])
+        .expect("git common dir");
+    let ai_dir = mirror.path().join(common_dir.trim()).join("ai");
+    std::fs::create_dir_all(&ai_dir).unwrap();
+    let record = serde_json::json!({
+        "kind": "push_notes",
+        "remote": "origin",
+        "deferred_at": 1_700_000_000,
+    });
+    std::fs::write(
+        ai_dir.join("pending_repairs.jsonl"),
+        format!("{}\n", record),
+    )
+    .unwrap();
+    mirror.git_ai(&["repair"]).unwrap();
+    let pushed = upstream
+        .git_og(&["notes", "--ref=ai-overflow", "show", &commit.commit_sha])
+        .expect("overflow note should be pushed with the stub");
+    assert!(AuthorshipLog::deserialize_from_string(&pushed).is_ok());
+
+    // A clone that only has the stub gets the full note with the notes fetch.
+    mirror
+        .git_og(&["update-ref", "-d", "refs/notes/ai-overflow"])
+        .unwrap();
+    mirror.git_ai(&["fetch-notes", "origin"]).unwrap();
+    let fetched = mirror
+        .git_og(&["notes", "--ref=ai-overflow", "show", &commit.commit_sha])
+        .expect("overflow note should be fetched with the stub");
+    assert_eq!(fetched, pushed);
+}
+
+crate::reuse_tests_in_worktree!(
+    test_oversized_note_is_stored_as_overflow_and_read_transparently,
+    test_note_under_the_limit_stays_inline,
+);
@@ -0,0 +1,77 @@
+use crate::repos::test_repo::{TestRepo, default_branchname};
+use std::fs;
+
+/// Commit on a branch that is deleted and whose reflog is expired, dated well
+/// outside the default retention window.
+fn repo_with_abandoned_commit() -> (TestRepo, String, String) {
+    let repo = TestRepo::new();
+
+    fs::write(repo.path().join("base.txt"), "base\n").unwrap();
+    let kept_sha = repo.stage_all_and_commit("base").unwrap().commit_sha;
+
+    repo.git(&["checkout", "-b", "abandoned"]).unwrap();
+    fs::write(repo.path().join("abandoned.txt"), "abandoned\n").unwrap();
+    let abandoned_sha = repo
+        .stage_all_and_commit_with_env(
+            "abandoned work",
+            &[("GIT_COMMITTER_DATE", "2020-01-01T00:00:00 +0000")],
+        )
+        .unwrap()
+        .commit_sha;
+
+    repo.git(&["checkout", default_branchname()]).unwrap();
+    repo.git(&["branch", "-D", "abandoned"]).unwrap();
+    repo.git_og(&["reflog", "expire", "--expire=now", "--all"])
+        .unwrap();
+
+    assert!(repo.read_authorship_note(&abandoned_sha).is_some());
+    (repo, kept_sha, abandoned_sha)
+}
+
+#[test]
+fn test_notes_gc_dry_run_reports_without_pruning() {
+    let (repo, _kept_sha, abandoned_sha) = repo_with_abandoned_commit();
+
+    let output = repo.git_ai(&["notes-gc", "--dry-run"]).unwrap();
+
+    assert!(
+        output.contains(&abandoned_sha),
+        "dry run should list the prunable commit, got: {}",
+        output
+    );
+    assert!(output.contains("would prune: 1"), "got: {}", output);
+    assert!(repo.read_authorship_note(&abandoned_sha).is_some());
+}
+
+#[test]
+fn test_notes_gc_prunes_unreachable_and_keeps_reachable_notes() {
+    let (repo, kept_sha, abandoned_sha) = repo_with_abandoned_commit();
+
+    repo.git_ai(&["notes-gc"]).unwrap();
+
+    assert!(repo.read_authorship_note(&abandoned_sha).is_none());
+    assert!(repo.read_authorship_note(&kept_sha).is_some());
+
+    // A second run has nothing left to do.
+    let output = repo.git_ai(&["notes-gc", "--dry-run"]).unwrap();
+    assert!(output.contains("would prune: 0"), "got: {}", output);
+}
+
+#[test]
+fn test_notes_gc_keeps_unreachable_notes_inside_retention_window() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join("base.txt"), "base\n").unwrap();
+    repo.stage_all_and_commit("base").unwrap();
+
+    repo.git(&["checkout", "-b", "recent"]).unwrap();
+    fs::write(repo.path().join("recent.txt"), "recent\n").unwrap();
+    let recent_sha = repo.stage_all_and_commit("recent work").unwrap().commit_sha;
+    repo.git(&["checkout", default_branchname()]).unwrap();
+    repo.git(&["branch", "-D", "recent"]).unwrap();
+    repo.git_og(&["reflog", "expire", "--expire=now", "--all"])
+        .unwrap();
+
+    repo.git_ai(&["notes-gc"]).unwrap();
+
+    assert!(repo.read_authorship_note(&recent_sha).is_some());
+}
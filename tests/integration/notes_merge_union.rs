@@ -0,0 +1,95 @@
+use crate::repos::test_repo::TestRepo;
+use git_ai::authorship::authorship_log_serialization::AuthorshipLog;
+
+fn note_for(attestations: &str, prompt_key: &str) -> String {
+    format!(
+        "{}---\n{{\"schema_version\": \"authorship/3.0.0\", \"base_commit_sha\": \"abc\", \"prompts\": {{\"{key}\": {{\"agent_id\": {{\"tool\": \"cursor\", \"id\": \"{key}\", \"model\": \"gpt-5\"}}, \"human_author\": null}}}}}}",
+        attestations,
+        key = prompt_key
+    )
+}
+
+fn attested_hashes(note: &str, file_path: &str) -> Vec<String> {
+    let log = AuthorshipLog::deserialize_from_string(note).expect("note should parse");
+    log.attestations
+        .iter()
+        .filter(|file| file.file_path == file_path)
+        .flat_map(|file| file.entries.iter().map(|entry| entry.hash.clone()))
+        .collect()
+}
+
+#[test]
+fn test_fetch_notes_unions_divergent_notes_for_same_commit() {
+    let (mirror, upstream) = TestRepo::new_with_remote();
+
+    std::fs::write(mirror.path().join("a.txt"), "1\n2\n3\n4\n").expect("write a.txt");
+    std::fs::write(mirror.path().join("b.txt"), "1\n2\n").expect("write b.txt");
+    let commit = mirror
+        .stage_all_and_commit("initial")
+        .expect("commit should succeed");
+    mirror
+        .git(&["push", "origin", "main"])
+        .expect("push should succeed");
+    let sha = commit.commit_sha;
+
+    let ours = note_for("a.txt\n  aaaa1111 1-2\n", "aaaa1111");
+    let theirs = note_for("a.txt\n  bbbb2222 1-4\nb.txt\n  bbbb2222 1-2\n", "bbbb2222");
+    mirror
+        .git_og(&["notes", "--ref=ai", "add", "-f", "-m", &ours, &sha])
+        .expect("write local note");
+    upstream
+        .git_og(&["notes", "--ref=ai", "add", "-f", "-m", &theirs, &sha])
+        .expect("write upstream note");
+
+    mirror
+        .git_ai(&["fetch-notes"])
+        .expect("fetch-notes should succeed");
+
+    let merged = mirror
+        .read_authorship_note(&sha)
+        .expect("commit should still have a note");
+    assert_eq!(
+        attested_hashes(&merged, "a.txt"),
+        vec!["aaaa1111", "bbbb2222"]
+    );
+    assert_eq!(attested_hashes(&merged, "b.txt"), vec!["bbbb2222"]);
+    let log = AuthorshipLog::deserialize_from_string(&merged).unwrap();
+    assert!(log.metadata.prompts.contains_key("aaaa1111"));
+    assert!(log.metadata.prompts.contains_key("bbbb2222"));
+}
+
+#[test]
+fn test_fetch_notes_does_not_resurrect_locally_removed_note() {
+    let (mirror, _upstream) = TestRepo::new_with_remote();
+
+    std::fs::write(mirror.path().join("a.txt"), "1\n").expect("write a.txt");
+    let sha = mirror
+        .stage_all_and_commit("initial")
+        .expect("commit should succeed")
+        .commit_sha;
+    let note = note_for("a.txt\n  aaaa1111 1\n", "aaaa1111");
+    mirror
+        .git_og(&["notes", "--ref=ai", "add", "-f", "-m", &note, &sha])
+        .expect("write local note");
+    mirror
+        .git(&["push", "origin", "main"])
+        .expect("push should succeed");
+    mirror
+        .git_ai(&["fetch-notes"])
+        .expect("fetch-notes should succeed");
+
+    // Pruned locally (as notes-gc does), unchanged upstream.
+    mirror
+        .git_og(&["notes", "--ref=ai", "remove", &sha])
+        .expect("remove local note");
+    mirror
+        .git_ai(&["fetch-notes"])
+        .expect("fetch-notes should succeed");
+
+    assert!(mirror.read_authorship_note(&sha).is_none());
+}
+
+crate::reuse_tests_in_worktree!(
+    test_fetch_notes_unions_divergent_notes_for_same_commit,
+    test_fetch_notes_does_not_resurrect_locally_removed_note,
+);
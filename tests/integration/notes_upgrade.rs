@@ -0,0 +1,106 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+
+#[test]
+fn test_notes_upgrade_copies_notes_into_v2_namespace() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(crate::lines!["fn human() {}".human(), "fn ai() {}".ai()]);
+    let commit = repo.stage_all_and_commit("AI change").unwrap();
+
+    let output = repo
+        .git_ai(&["notes", "upgrade"])
+        .expect("notes upgrade should succeed");
+    assert!(
+        output.contains("Upgraded 1 note(s) into refs/notes/ai-v2 (0 already present)"),
+        "{}",
+        output
+    );
+
+    let v2_note = repo
+        .git(&["notes", "--ref=ai-v2", "show", &commit.commit_sha])
+        .expect("upgraded note should exist");
+    assert!(v2_note.contains("\"authorship/3.0.0\""), "{}", v2_note);
+    assert!(v2_note.contains(&commit.commit_sha), "{}", v2_note);
+
+    let rerun = repo.git_ai(&["notes", "upgrade"]).unwrap();
+    assert!(
+        rerun.contains("Upgraded 0 note(s) into refs/notes/ai-v2 (1 already present)"),
+        "{}",
+        rerun
+    );
+}
+
+#[test]
+fn test_v2_notes_are_read_when_v1_note_is_missing() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(crate::lines!["fn human() {}".human(), "fn ai() {}".ai()]);
+    let commit = repo.stage_all_and_commit("AI change").unwrap();
+
+    repo.git_ai(&["notes", "upgrade"]).unwrap();
+    // A teammate on a newer client only published the v2 note.
+    repo.git(&["notes", "--ref=ai", "remove", &commit.commit_sha])
+        .expect("removing the v1 note should succeed");
+    assert!(repo.read_authorship_note(&commit.commit_sha).is_none());
+
+    file.assert_lines_and_blame(crate::lines!["fn human() {}".human(), "fn ai() {}".ai()]);
+}
+
+#[test]
+fn test_upgraded_notes_are_pushed_and_fetched() {
+    let (mirror, upstream) = TestRepo::new_with_remote();
+    let mut file = mirror.filename("lib.rs");
+    file.set_contents(crate::lines!["fn ai() {}".ai()]);
+    let commit = mirror.stage_all_and_commit("AI change").unwrap();
+    mirror.git_og(&["push", "origin", "HEAD:main"]).unwrap();
+    mirror.git_ai(&["notes", "upgrade"]).unwrap();
+
+    // Push through the deferred-repair path, which runs the regular notes push.
+    let common_dir = mirror
+        .git_og(&["rev-parse", "--git-common-dir"])
+        .expect("git common dir");
+    let ai_dir = mirror.path().join(common_dir.trim()).join("ai");
+    std::fs::create_dir_all(&ai_dir).unwrap();
+    let record = serde_json::json!({
+        "kind": "push_notes",
+        "remote": "origin",
+        "deferred_at": 1_700_000_000,
+    });
+    std::fs::write(
+        ai_dir.join("pending_repairs.jsonl"),
+        format!("{}\n", record),
+    )
+    .unwrap();
+    mirror.git_ai(&["repair"]).unwrap();
+    upstream
+        .git(&["notes", "--ref=ai-v2", "show", &commit.commit_sha])
+        .expect("upgraded note should be pushed");
+
+    // A teammate publishes an upgraded note for a commit this clone has not
+    // upgraded itself.
+    file.set_contents(crate::lines!["fn ai() {}".ai(), "fn more() {}".ai()]);
+    let second = mirror.stage_all_and_commit("more AI").unwrap();
+    mirror.git_og(&["push", "origin", "HEAD:main"]).unwrap();
+    upstream
+        .git(&[
+            "notes",
+            "--ref=ai-v2",
+            "add",
+            "-m",
+            "teammate note",
+            &second.commit_sha,
+        ])
+        .unwrap();
+
+    mirror.git_ai(&["fetch-notes", "origin"]).unwrap();
+    let fetched = mirror
+        .git(&["notes", "--ref=ai-v2", "show", &second.commit_sha])
+        .expect("upgraded note should be fetched");
+    assert!(fetched.contains("teammate note"), "{}", fetched);
+}
+
+crate::reuse_tests_in_worktree!(
+    test_notes_upgrade_copies_notes_into_v2_namespace,
+    test_v2_notes_are_read_when_v1_note_is_missing,
+);
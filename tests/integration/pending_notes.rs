@@ -0,0 +1,38 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+
+#[test]
+fn test_journaled_note_is_replayed_on_next_commit() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("a.txt");
+    file.set_contents(crate::lines!["one".ai()]);
+    let first = repo.stage_all_and_commit("first").unwrap();
+
+    // Simulate a note write that was journaled but lost before it landed.
+    let lost_note = repo
+        .read_authorship_note(&first.commit_sha)
+        .expect("first commit should have a note");
+    repo.git_og(&["notes", "--ref=ai", "remove", &first.commit_sha])
+        .expect("remove note");
+    let record = serde_json::json!({
+        "commit_sha": first.commit_sha,
+        "note": lost_note,
+    });
+    let common_dir = repo
+        .git_og(&["rev-parse", "--git-common-dir"])
+        .expect("git common dir");
+    let ai_dir = repo.path().join(common_dir.trim()).join("ai");
+    std::fs::create_dir_all(&ai_dir).unwrap();
+    std::fs::write(ai_dir.join("pending_notes.jsonl"), format!("{}\n", record)).unwrap();
+
+    file.set_contents(crate::lines!["one".ai(), "two".ai()]);
+    repo.stage_all_and_commit("second").unwrap();
+
+    assert_eq!(
+        repo.read_authorship_note(&first.commit_sha).as_deref(),
+        Some(lost_note.as_str())
+    );
+    assert!(!ai_dir.join("pending_notes.jsonl").exists());
+}
+
+crate::reuse_tests_in_worktree!(test_journaled_note_is_replayed_on_next_commit,);
@@ -0,0 +1,55 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+use serde_json::Value;
+
+fn prompts_json(repo: &TestRepo, args: &[&str]) -> Vec<Value> {
+    let output = repo.git_ai(args).expect("prompts should succeed");
+    let start = output.find('[').expect("JSON array in output");
+    let end = output.rfind(']').expect("JSON array in output");
+    serde_json::from_str::<Vec<Value>>(&output[start..=end]).unwrap()
+}
+
+#[test]
+fn test_prompts_lists_committed_and_uncommitted_records() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(crate::lines!["fn base() {}".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    file.set_contents(crate::lines!["fn base() {}".human(), "fn ai() {}".ai()]);
+    let ai_commit = repo.stage_all_and_commit("AI change").unwrap();
+
+    let entries = prompts_json(&repo, &["prompts", "list", "--json"]);
+    assert_eq!(entries.len(), 1, "{:?}", entries);
+    let committed = &entries[0];
+    assert_eq!(committed["source"], "note");
+    assert_eq!(committed["commit"], ai_commit.commit_sha.as_str());
+    assert_eq!(committed["tool"], "mock_ai");
+    assert_eq!(committed["files"], serde_json::json!(["lib.rs"]));
+
+    let mut other = repo.filename("other.rs");
+    other.set_contents(crate::lines!["fn pending() {}".ai()]);
+    let entries = prompts_json(&repo, &["prompts", "list", "--json"]);
+    assert_eq!(entries.len(), 2, "{:?}", entries);
+    assert_eq!(entries[0]["source"], "working_log");
+    assert_eq!(entries[0]["files"], serde_json::json!(["other.rs"]));
+
+    let filtered = prompts_json(&repo, &["prompts", "list", "--file", "lib.rs", "--json"]);
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0]["source"], "note");
+    assert!(prompts_json(&repo, &["prompts", "list", "--tool", "cursor", "--json"]).is_empty());
+    assert!(prompts_json(&repo, &["prompts", "search", "no-such-text", "--json"]).is_empty());
+    assert_eq!(
+        prompts_json(&repo, &["prompts", "search", "MOCK_AI", "--json"]).len(),
+        2
+    );
+
+    let id = committed["id"].as_str().unwrap();
+    let shown = repo.git_ai(&["prompts", "show", id]).unwrap();
+    assert!(shown.contains(&format!("prompt {}", id)), "{}", shown);
+    assert!(shown.contains(&ai_commit.commit_sha), "{}", shown);
+
+    assert!(repo.git_ai(&["prompts", "show", "s_missing"]).is_err());
+}
+
+crate::reuse_tests_in_worktree!(test_prompts_lists_committed_and_uncommitted_records,);
@@ -538,6 +538,89 @@ fn test_pull_rebase_preserves_committed_ai_authorship() {
     ]);
 }
 
+#[test]
+fn test_pull_rebase_merges_maps_notes_along_merge_topology() {
+    let (local, _upstream) = TestRepo::new_with_remote();
+
+    let mut readme = local.filename("README.md");
+    readme.set_contents(vec!["# Test Repo".to_string()]);
+    let initial = local
+        .stage_all_and_commit("initial commit")
+        .expect("initial commit should succeed");
+    local
+        .git(&["push", "-u", "origin", "HEAD"])
+        .expect("push initial commit should succeed");
+    let branch = local.current_branch();
+
+    // Local history: an AI commit on each side of a merge.
+    local
+        .git(&["checkout", "-b", "side"])
+        .expect("create side branch should succeed");
+    let mut side_file = local.filename("side.txt");
+    side_file.set_contents(vec!["side line 1".ai(), "side line 2".ai()]);
+    local
+        .stage_all_and_commit("side AI commit")
+        .expect("side commit should succeed");
+    local
+        .git(&["checkout", &branch])
+        .expect("switch back should succeed");
+    let mut main_file = local.filename("main.txt");
+    main_file.set_contents(vec!["main line 1".ai()]);
+    local
+        .stage_all_and_commit("main AI commit")
+        .expect("main commit should succeed");
+    local
+        .git(&["merge", "--no-ff", "side", "-m", "Merge side"])
+        .expect("merge should succeed");
+    let local_head = local
+        .git(&["rev-parse", "HEAD"])
+        .expect("rev-parse should succeed")
+        .trim()
+        .to_string();
+
+    // Diverge upstream.
+    local
+        .git(&["reset", "--hard", &initial.commit_sha])
+        .expect("reset should succeed");
+    let mut upstream_file = local.filename("upstream_change.txt");
+    upstream_file.set_contents(vec!["upstream content".to_string()]);
+    local
+        .stage_all_and_commit("upstream divergent commit")
+        .expect("upstream commit should succeed");
+    local
+        .git(&["push", "--force", "origin", &format!("HEAD:{}", branch)])
+        .expect("force push should succeed");
+    local
+        .git(&["reset", "--hard", &local_head])
+        .expect("reset to local head should succeed");
+
+    local
+        .git(&["pull", "--rebase=merges"])
+        .expect("pull --rebase=merges should succeed");
+
+    let parents = local
+        .git(&["rev-list", "--parents", "-n", "1", "HEAD"])
+        .expect("rev-list should succeed");
+    let parents: Vec<&str> = parents.split_whitespace().skip(1).collect();
+    assert_eq!(parents.len(), 2, "HEAD should still be a merge");
+    assert!(local.read_file("upstream_change.txt").is_some());
+
+    // Each replayed commit carries its own original's note, not its sibling's.
+    let main_note = local
+        .read_authorship_note(parents[0])
+        .expect("rebased main commit should have a note");
+    let side_note = local
+        .read_authorship_note(parents[1])
+        .expect("rebased side commit should have a note");
+    assert!(main_note.contains("main.txt"), "{}", main_note);
+    assert!(!main_note.contains("side.txt"), "{}", main_note);
+    assert!(side_note.contains("side.txt"), "{}", side_note);
+    assert!(!side_note.contains("main.txt"), "{}", side_note);
+
+    main_file.assert_lines_and_blame(vec!["main line 1".ai()]);
+    side_file.assert_lines_and_blame(vec!["side line 1".ai(), "side line 2".ai()]);
+}
+
 #[test]
 fn test_rejected_push_failed_pull_then_pull_rebase_preserves_committed_ai_authorship() {
     let (local, upstream) = TestRepo::new_with_remote();
@@ -1980,6 +2063,7 @@ crate::reuse_tests_in_worktree!(
     test_fast_forward_pull_preserves_ai_attribution,
     test_fast_forward_pull_without_local_changes,
     test_pull_rebase_preserves_committed_ai_authorship,
+    test_pull_rebase_merges_maps_notes_along_merge_topology,
     test_pull_rebase_via_git_config_preserves_committed_ai_authorship,
     test_pull_rebase_via_zero_arg_alias_and_git_config_preserves_committed_ai_authorship,
     test_pull_rebase_autostash_preserves_uncommitted_ai_attribution,
@@ -1,6 +1,7 @@
 use crate::repos::test_repo::TestRepo;
 use git_ai::authorship::range_authorship::{EMPTY_TREE_HASH, range_authorship, should_ignore_file};
 use git_ai::git::repository::{CommitRange, find_repository_in_path};
+use git_ai::workspace_config::WhitespaceMode;
 
 #[test]
 fn test_range_authorship_simple_range() {
@@ -48,7 +49,14 @@ fn test_range_authorship_simple_range() {
         "package-lock.json".to_string(),
         "yarn.lock".to_string(),
     ];
-    let stats = range_authorship(commit_range, false, &lockfile_patterns, None).unwrap();
+    let stats = range_authorship(
+        commit_range,
+        false,
+        &lockfile_patterns,
+        WhitespaceMode::Exact,
+        None,
+    )
+    .unwrap();
 
     // Verify stats
     assert_eq!(stats.authorship_stats.total_commits, 1);
@@ -97,7 +105,14 @@ fn test_range_authorship_from_empty_tree() {
         "package-lock.json".to_string(),
         "yarn.lock".to_string(),
     ];
-    let stats = range_authorship(commit_range, false, &lockfile_patterns, None).unwrap();
+    let stats = range_authorship(
+        commit_range,
+        false,
+        &lockfile_patterns,
+        WhitespaceMode::Exact,
+        None,
+    )
+    .unwrap();
 
     // Verify stats - should include all commits from beginning
     assert_eq!(stats.authorship_stats.total_commits, 2);
@@ -145,7 +160,14 @@ fn test_range_authorship_single_commit() {
         "package-lock.json".to_string(),
         "yarn.lock".to_string(),
     ];
-    let stats = range_authorship(commit_range, false, &lockfile_patterns, None).unwrap();
+    let stats = range_authorship(
+        commit_range,
+        false,
+        &lockfile_patterns,
+        WhitespaceMode::Exact,
+        None,
+    )
+    .unwrap();
 
     // For single commit, should use stats_for_commit_stats
     assert_eq!(stats.authorship_stats.total_commits, 1);
@@ -215,7 +237,14 @@ fn test_range_authorship_mixed_commits() {
         "package-lock.json".to_string(),
         "yarn.lock".to_string(),
     ];
-    let stats = range_authorship(commit_range, false, &lockfile_patterns, None).unwrap();
+    let stats = range_authorship(
+        commit_range,
+        false,
+        &lockfile_patterns,
+        WhitespaceMode::Exact,
+        None,
+    )
+    .unwrap();
 
     // Verify stats
     assert_eq!(stats.authorship_stats.total_commits, 3);
@@ -260,7 +289,14 @@ fn test_range_authorship_no_changes() {
         "package-lock.json".to_string(),
         "yarn.lock".to_string(),
     ];
-    let stats = range_authorship(commit_range, false, &lockfile_patterns, None).unwrap();
+    let stats = range_authorship(
+        commit_range,
+        false,
+        &lockfile_patterns,
+        WhitespaceMode::Exact,
+        None,
+    )
+    .unwrap();
 
     // Should have 1 commit but no diffs since start == end
     assert_eq!(stats.authorship_stats.total_commits, 1);
@@ -301,7 +337,14 @@ fn test_range_authorship_empty_tree_with_multiple_files() {
         "package-lock.json".to_string(),
         "yarn.lock".to_string(),
     ];
-    let stats = range_authorship(commit_range, false, &lockfile_patterns, None).unwrap();
+    let stats = range_authorship(
+        commit_range,
+        false,
+        &lockfile_patterns,
+        WhitespaceMode::Exact,
+        None,
+    )
+    .unwrap();
 
     // Verify all files are included
     assert_eq!(stats.authorship_stats.total_commits, 1);
@@ -364,7 +407,14 @@ fn test_range_authorship_ignores_single_lockfile() {
         "package-lock.json".to_string(),
         "yarn.lock".to_string(),
     ];
-    let stats = range_authorship(commit_range, false, &lockfile_patterns, None).unwrap();
+    let stats = range_authorship(
+        commit_range,
+        false,
+        &lockfile_patterns,
+        WhitespaceMode::Exact,
+        None,
+    )
+    .unwrap();
 
     // Verify lockfile is excluded: only 2 lines added (from main.rs), not 1000+ from lockfile
     assert_eq!(stats.authorship_stats.total_commits, 1);
@@ -441,7 +491,14 @@ fn test_range_authorship_mixed_lockfile_and_source() {
         "package-lock.json".to_string(),
         "yarn.lock".to_string(),
     ];
-    let stats = range_authorship(commit_range, false, &lockfile_patterns, None).unwrap();
+    let stats = range_authorship(
+        commit_range,
+        false,
+        &lockfile_patterns,
+        WhitespaceMode::Exact,
+        None,
+    )
+    .unwrap();
 
     // Key assertion: git_diff should only count lib.rs changes (3 lines), not package-lock.json (3000 lines)
     assert_eq!(stats.authorship_stats.total_commits, 2);
@@ -516,7 +573,14 @@ fn test_range_authorship_multiple_lockfile_types() {
         "poetry.lock".to_string(),
         "go.sum".to_string(),
     ];
-    let stats = range_authorship(commit_range, false, &lockfile_patterns, None).unwrap();
+    let stats = range_authorship(
+        commit_range,
+        false,
+        &lockfile_patterns,
+        WhitespaceMode::Exact,
+        None,
+    )
+    .unwrap();
 
     // Verify: only the 1 README line is counted, all lockfiles excluded (2000 lines ignored)
     assert_eq!(stats.authorship_stats.total_commits, 1);
@@ -575,7 +639,14 @@ fn test_range_authorship_lockfile_only_commit() {
         "package-lock.json".to_string(),
         "yarn.lock".to_string(),
     ];
-    let stats = range_authorship(commit_range, false, &lockfile_patterns, None).unwrap();
+    let stats = range_authorship(
+        commit_range,
+        false,
+        &lockfile_patterns,
+        WhitespaceMode::Exact,
+        None,
+    )
+    .unwrap();
 
     // Verify: no lines counted since only lockfiles changed
     assert_eq!(stats.authorship_stats.total_commits, 1);
@@ -1027,9 +1098,72 @@ fn test_range_authorship_with_glob_patterns() {
         "*lock.json".to_string(), // Matches package-lock.json
         "*.generated.*".to_string(),
     ];
-    let stats = range_authorship(commit_range, false, &glob_patterns, None).unwrap();
+    let stats = range_authorship(
+        commit_range,
+        false,
+        &glob_patterns,
+        WhitespaceMode::Exact,
+        None,
+    )
+    .unwrap();
 
     // Should only count the 1 line in main.rs, ignoring 1700 lines in lockfiles and generated files
     assert_eq!(stats.range_stats.git_diff_added_lines, 1);
     assert_eq!(stats.range_stats.ai_additions, 1);
 }
+
+#[test]
+fn test_range_authorship_ignore_reindent_leaves_out_reindented_lines() {
+    let repo = TestRepo::new();
+
+    std::fs::write(repo.path().join("test.rs"), "fn a() {\n  x();\n}\n").unwrap();
+    repo.git(&["add", "test.rs"]).unwrap();
+    repo.git_ai(&["checkpoint", "mock_known_human", "test.rs"])
+        .unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let first_sha = repo
+        .git_og(&["rev-parse", "HEAD"])
+        .unwrap()
+        .trim()
+        .to_string();
+
+    // A formatter reindents the existing line, then AI adds two lines.
+    std::fs::write(repo.path().join("test.rs"), "fn a() {\n    x();\n}\n").unwrap();
+    repo.git(&["add", "test.rs"]).unwrap();
+    repo.git_ai(&["checkpoint", "mock_known_human", "test.rs"])
+        .unwrap();
+    repo.stage_all_and_commit("Reformat").unwrap();
+
+    std::fs::write(
+        repo.path().join("test.rs"),
+        "fn a() {\n    x();\n    y();\n    z();\n}\n",
+    )
+    .unwrap();
+    repo.git(&["add", "test.rs"]).unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "test.rs"]).unwrap();
+    repo.stage_all_and_commit("AI adds lines").unwrap();
+    let third_sha = repo
+        .git_og(&["rev-parse", "HEAD"])
+        .unwrap()
+        .trim()
+        .to_string();
+
+    let gitai_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
+    let range = || {
+        CommitRange::new_infer_refname(
+            &gitai_repo,
+            first_sha.clone(),
+            third_sha.clone(),
+            Some("HEAD".to_string()),
+        )
+        .unwrap()
+    };
+
+    let exact = range_authorship(range(), false, &[], WhitespaceMode::Exact, None).unwrap();
+    assert_eq!(exact.range_stats.git_diff_added_lines, 3);
+
+    let reindent = range_authorship(range(), false, &[], WhitespaceMode::Reindent, None).unwrap();
+    assert_eq!(reindent.range_stats.git_diff_added_lines, 2);
+    assert_eq!(reindent.range_stats.git_diff_deleted_lines, 0);
+    assert_eq!(reindent.range_stats.ai_additions, 2);
+}
@@ -0,0 +1,63 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+
+/// Rewrites the second line of feature.txt and folds it into the picked commit.
+const EXEC_REWRITE: &str = "printf 'ai line 1\\nAI LINE 2 (formatted)\\n' > feature.txt && git commit -qa --amend --no-edit";
+
+fn rebase_with_exec_rewrite(workspace_config: Option<&str>) -> TestRepo {
+    let repo = TestRepo::new();
+    let mut base = repo.filename("base.txt");
+    base.set_contents(crate::lines!["base"]);
+    if let Some(config) = workspace_config {
+        std::fs::write(repo.path().join(".git-ai.toml"), config).unwrap();
+    }
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let default_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    let mut feature = repo.filename("feature.txt");
+    feature.set_contents(crate::lines!["ai line 1".ai(), "ai line 2".ai()]);
+    repo.stage_all_and_commit("AI feature").unwrap();
+
+    repo.git(&["checkout", &default_branch]).unwrap();
+    let mut other = repo.filename("other.txt");
+    other.set_contents(crate::lines!["other"]);
+    repo.stage_all_and_commit("Main advances").unwrap();
+
+    repo.git(&["checkout", "feature"]).unwrap();
+    repo.git(&["rebase", "--exec", EXEC_REWRITE, &default_branch])
+        .unwrap();
+    repo
+}
+
+#[test]
+fn test_rebase_exec_changes_default_to_human() {
+    let repo = rebase_with_exec_rewrite(None);
+
+    let mut feature = repo.filename("feature.txt");
+    feature.assert_lines_and_blame(crate::lines![
+        "ai line 1".ai(),
+        "AI LINE 2 (formatted)".unattributed_human(),
+    ]);
+}
+
+#[test]
+fn test_rebase_exec_changes_credit_configured_tool() {
+    let repo = rebase_with_exec_rewrite(Some("rebase_exec_author = \"formatter\"\n"));
+
+    let mut feature = repo.filename("feature.txt");
+    feature.assert_lines_and_blame(crate::lines![
+        "ai line 1".ai(),
+        "AI LINE 2 (formatted)".ai(),
+    ]);
+
+    let note = repo
+        .read_authorship_note(&repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string())
+        .expect("rebased commit should have a note");
+    assert!(note.contains("\"tool\": \"formatter\""), "note: {}", note);
+}
+
+crate::reuse_tests_in_worktree!(
+    test_rebase_exec_changes_default_to_human,
+    test_rebase_exec_changes_credit_configured_tool,
+);
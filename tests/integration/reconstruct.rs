@@ -0,0 +1,79 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+
+/// A `feature` branch with one AI-authored commit, cherry-picked onto `main`
+/// with plain git so the pick gets no note. Returns `(repo, ai, picked)`.
+fn setup_pick_without_git_ai() -> (TestRepo, String, String) {
+    let repo = TestRepo::new();
+    let mut base_file = repo.filename("base.txt");
+    base_file.set_contents(crate::lines!["base line"]);
+    repo.stage_all_and_commit("base").unwrap();
+    let main = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    let mut feature_file = repo.filename("feature.txt");
+    feature_file.set_contents(crate::lines!["AI line 1".ai(), "AI line 2".ai()]);
+    let ai = repo.stage_all_and_commit("AI work").unwrap().commit_sha;
+    assert!(repo.read_authorship_note(&ai).is_some());
+
+    repo.git(&["checkout", &main]).unwrap();
+    base_file.set_contents(crate::lines!["base line", "human line"]);
+    repo.stage_all_and_commit("human work").unwrap();
+
+    repo.git_og(&["cherry-pick", &ai]).unwrap();
+    let picked = repo
+        .git_og(&["rev-parse", "HEAD"])
+        .unwrap()
+        .trim()
+        .to_string();
+    assert_ne!(picked, ai);
+    assert!(repo.read_authorship_note(&picked).is_none());
+
+    (repo, ai, picked)
+}
+
+#[test]
+fn test_reconstruct_copies_note_from_commit_with_same_patch() {
+    let (repo, ai, picked) = setup_pick_without_git_ai();
+
+    let dry_run = repo
+        .git_ai(&["reconstruct", "HEAD~1..HEAD", "--dry-run"])
+        .unwrap();
+    assert!(
+        dry_run.contains(&format!("{} -> {}", &ai[..8], &picked[..8])),
+        "got: {}",
+        dry_run
+    );
+    assert!(repo.read_authorship_note(&picked).is_none());
+
+    let output = repo.git_ai(&["reconstruct", "HEAD~1..HEAD"]).unwrap();
+    assert!(output.contains("(patch-id)"), "got: {}", output);
+    let note = repo
+        .read_authorship_note(&picked)
+        .expect("picked commit should get the source's note");
+    assert!(note.contains("feature.txt"), "note: {}", note);
+
+    let again = repo.git_ai(&["reconstruct", "HEAD~1..HEAD"]).unwrap();
+    assert!(again.contains("Nothing to reconstruct"), "got: {}", again);
+}
+
+#[test]
+fn test_reconstruct_reports_commits_without_a_match() {
+    let (repo, _ai, picked) = setup_pick_without_git_ai();
+
+    // Restricting sources to main leaves nothing to take the note from.
+    let output = repo
+        .git_ai(&[
+            "reconstruct",
+            "HEAD~1..HEAD",
+            "--source",
+            "HEAD~1",
+            "--json",
+        ])
+        .unwrap();
+    let report: serde_json::Value = serde_json::from_str(&output).expect("valid JSON");
+    assert_eq!(report["commits"], 1);
+    assert_eq!(report["already_noted"], 0);
+    assert_eq!(report["reconstructed"].as_array().unwrap().len(), 0);
+    assert_eq!(report["unmatched"][0], picked.as_str());
+}
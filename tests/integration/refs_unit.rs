@@ -651,7 +651,7 @@ fn test_get_reference_as_authorship_log_v3_version_mismatch() {
     let result = read_authorship_v3(&gitai_repo, &commit_sha);
     assert!(result.is_err());
 
-    if let Err(GitAiError::Generic(msg)) = result {
+    if let Err(GitAiError::Notes(msg)) = result {
         assert!(msg.contains("Unsupported authorship log version"));
     } else {
         panic!("Expected version mismatch error");
@@ -0,0 +1,79 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::{DaemonTestScope, TestRepo};
+
+#[test]
+fn test_repair_pushes_notes_deferred_by_a_hook_timeout() {
+    let (mirror, upstream) = TestRepo::new_with_remote();
+    let mut file = mirror.filename("a.txt");
+    file.set_contents(crate::lines!["one".ai()]);
+    let commit = mirror.stage_all_and_commit("first").unwrap();
+    // Push the branch without git-ai so the notes stay behind.
+    mirror.git_og(&["push", "origin", "HEAD:main"]).unwrap();
+    assert!(upstream.read_authorship_note(&commit.commit_sha).is_none());
+
+    // Simulate a notes push the background service gave up on.
+    let record = serde_json::json!({
+        "kind": "push_notes",
+        "remote": "origin",
+        "deferred_at": 1_700_000_000,
+    });
+    let common_dir = mirror
+        .git_og(&["rev-parse", "--git-common-dir"])
+        .expect("git common dir");
+    let ai_dir = mirror.path().join(common_dir.trim()).join("ai");
+    std::fs::create_dir_all(&ai_dir).unwrap();
+    std::fs::write(
+        ai_dir.join("pending_repairs.jsonl"),
+        format!("{}\n", record),
+    )
+    .unwrap();
+
+    let listed = mirror.git_ai(&["repair", "--list"]).unwrap();
+    assert!(listed.contains("push_notes  origin"), "{}", listed);
+
+    mirror.git_ai(&["repair"]).unwrap();
+    assert!(upstream.read_authorship_note(&commit.commit_sha).is_some());
+    assert!(!ai_dir.join("pending_repairs.jsonl").exists());
+
+    let output = mirror.git_ai(&["repair"]).unwrap();
+    assert!(output.contains("Nothing to repair"), "{}", output);
+}
+
+#[test]
+fn test_degraded_post_commit_defers_recovery_to_repair() {
+    let mut repo = TestRepo::new_with_daemon_scope(DaemonTestScope::Dedicated);
+    repo.patch_git_ai_config(|p| {
+        p.max_hook_latency_ms = Some(1);
+    });
+
+    let mut file = repo.filename("a.txt");
+    let mut lines = Vec::new();
+    for n in 0..=git_ai::daemon::hook_budget::DEGRADE_AFTER_OVER_BUDGET {
+        lines.push(format!("line {}", n));
+        file.set_contents(lines.iter().map(|line| line.clone().ai()).collect());
+        repo.stage_all_and_commit(&format!("commit {}", n)).unwrap();
+    }
+    let status = repo.git_ai(&["status"]).unwrap();
+    assert!(status.contains("Hook budget exceeded"), "{}", status);
+
+    lines.push("degraded".to_string());
+    file.set_contents(lines.iter().map(|line| line.clone().ai()).collect());
+    let commit = repo.stage_all_and_commit("degraded commit").unwrap();
+    assert!(
+        repo.read_authorship_note(&commit.commit_sha).is_some(),
+        "a degraded commit still gets its note"
+    );
+
+    let listed = repo.git_ai(&["repair", "--list"]).unwrap();
+    assert!(
+        listed.contains(&format!("recover_attribution  {}", commit.commit_sha)),
+        "{}",
+        listed
+    );
+
+    repo.git_ai(&["repair"]).unwrap();
+    let output = repo.git_ai(&["repair"]).unwrap();
+    assert!(output.contains("Nothing to repair"), "{}", output);
+    let listed = repo.git_ai(&["repair", "--list"]).unwrap();
+    assert!(listed.contains("No deferred work"), "{}", listed);
+}
@@ -666,3 +666,51 @@ fn test_prune_does_not_touch_active_working_logs() {
         "Active working logs should not be pruned"
     );
 }
+
+// ---------------------------------------------------------------------------
+// 16. test_migrate_storage_command_upgrades_legacy_storage
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_migrate_storage_command_upgrades_legacy_storage() {
+    let repo = TestRepo::new();
+    let repo_storage = storage_for(&repo);
+
+    // Simulate a repo last touched by a git-ai that predates the version marker.
+    let version_file = repo_storage.ai_dir.join("STORAGE_VERSION");
+    let _ = fs::remove_file(&version_file);
+    let legacy_dir = repo_storage.working_logs.join("legacybase");
+    fs::create_dir_all(&legacy_dir).unwrap();
+    fs::write(legacy_dir.join("checkpoints.jsonl"), "").unwrap();
+
+    let output = repo.git_ai(&["migrate-storage", "--dry-run"]).unwrap();
+    assert!(output.contains("Would migrate"), "{}", output);
+    assert!(!version_file.exists());
+
+    let output = repo.git_ai(&["migrate-storage"]).unwrap();
+    assert!(output.contains("Migrated git-ai storage"), "{}", output);
+    assert_eq!(
+        fs::read_to_string(&version_file).unwrap().trim(),
+        git_ai::git::repo_storage::STORAGE_FORMAT_VERSION.to_string()
+    );
+    assert!(repo_storage.ai_dir.join("backups").exists());
+}
+
+// ---------------------------------------------------------------------------
+// 17. test_storage_from_newer_git_ai_is_refused
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_storage_from_newer_git_ai_is_refused() {
+    let repo = TestRepo::new();
+    let repo_storage = storage_for(&repo);
+
+    fs::write(
+        repo_storage.ai_dir.join("STORAGE_VERSION"),
+        (git_ai::git::repo_storage::STORAGE_FORMAT_VERSION + 1).to_string(),
+    )
+    .unwrap();
+
+    let err = repo.git_ai(&["migrate-storage"]).unwrap_err();
+    assert!(err.contains("git-ai upgrade"), "{}", err);
+}
@@ -1006,8 +1006,21 @@ pub(crate) fn git_command_requires_daemon_sync(args: &[&str]) -> bool {
     matches!(command, "notes")
 }
 
+/// Index of the git-ai subcommand, skipping leading options such as `-C <path>`.
+fn git_ai_primary_command_index(args: &[&str]) -> Option<usize> {
+    let mut i = 0usize;
+    while i < args.len() {
+        match args[i] {
+            "-C" | "--git-dir" | "--work-tree" => i += 2,
+            arg if arg.starts_with('-') => i += 1,
+            _ => return Some(i),
+        }
+    }
+    None
+}
+
 fn git_ai_primary_command<'a>(args: &'a [&'a str]) -> Option<&'a str> {
-    args.iter().copied().find(|arg| !arg.starts_with('-'))
+    git_ai_primary_command_index(args).map(|i| args[i])
 }
 
 fn is_known_checkpoint_preset(arg: &str) -> bool {
@@ -1022,6 +1035,8 @@ fn is_known_checkpoint_preset(arg: &str) -> bool {
             | "amp"
             | "windsurf"
             | "opencode"
+            | "openhands"
+            | "amazon-q"
             | "pi"
             | "ai_tab"
             | "firebender"
@@ -1038,7 +1053,10 @@ fn normalize_test_git_ai_checkpoint_args(args: &[&str]) -> Vec<String> {
         .iter()
         .map(|arg| (*arg).to_string())
         .collect::<Vec<_>>();
-    if git_ai_primary_command(args) != Some("checkpoint") || args.len() <= 1 {
+    let Some(command_index) = git_ai_primary_command_index(args) else {
+        return original;
+    };
+    if args[command_index] != "checkpoint" || args.len() <= command_index + 1 {
         return original;
     }
 
@@ -1046,8 +1064,8 @@ fn normalize_test_git_ai_checkpoint_args(args: &[&str]) -> Vec<String> {
         return original;
     }
 
-    let mut normalized = vec![args[0].to_string()];
-    let mut i = 1usize;
+    let mut normalized = original[..=command_index].to_vec();
+    let mut i = command_index + 1;
     while i < args.len() {
         match args[i] {
             "--hook-input" => {
@@ -1088,6 +1106,7 @@ fn git_ai_command_requires_daemon_sync(args: &[&str]) -> bool {
         Some(
             "blame"
                 | "blame-analysis"
+                | "commit"
                 | "diff"
                 | "log"
                 | "show"
@@ -1266,6 +1285,18 @@ impl TestRepo {
                 serde_json::Value::Number(serde_json::Number::from(max_lines as u64)),
             );
         }
+        if let Some(max_bytes) = patch.max_note_size_bytes {
+            config.insert(
+                "max_note_size_bytes".to_string(),
+                serde_json::Value::Number(serde_json::Number::from(max_bytes as u64)),
+            );
+        }
+        if let Some(max_ms) = patch.max_hook_latency_ms {
+            config.insert(
+                "max_hook_latency_ms".to_string(),
+                serde_json::Value::Number(serde_json::Number::from(max_ms)),
+            );
+        }
 
         let config_dir = home.join(".git-ai");
         fs::create_dir_all(&config_dir).expect("failed to create test HOME config directory");
@@ -3137,7 +3168,16 @@ impl TestRepo {
         working_dir: Option<&std::path::Path>,
     ) -> Result<NewCommit, String> {
         let output = self.git_with_env(&["commit", "-m", message], envs, working_dir);
+        self.new_commit_from_output(output)
+    }
+
+    /// `git commit -a`: stages tracked modifications and deletions, but not new files.
+    pub fn commit_all(&self, message: &str) -> Result<NewCommit, String> {
+        let output = self.git_with_env(&["commit", "-a", "-m", message], &[], None);
+        self.new_commit_from_output(output)
+    }
 
+    fn new_commit_from_output(&self, output: Result<String, String>) -> Result<NewCommit, String> {
         // println!("commit output: {:?}", output);
         match output {
             Ok(combined) => {
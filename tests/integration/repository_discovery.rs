@@ -0,0 +1,180 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+use git_ai::authorship::stats::CommitStats;
+use std::path::Path;
+
+/// Extract the first complete JSON object from mixed stdout/stderr output.
+fn extract_json_object(output: &str) -> String {
+    let start = output.find('{').unwrap_or(0);
+    let end = output.rfind('}').unwrap_or(output.len().saturating_sub(1));
+    output[start..=end].to_string()
+}
+
+/// Runs git-ai from `cwd` (outside the repository) with extra environment.
+fn git_ai_outside_repo(
+    repo: &TestRepo,
+    cwd: &Path,
+    args: &[&str],
+    envs: &[(&str, &str)],
+) -> Result<String, String> {
+    repo.sync_daemon_force();
+    let mut command = repo.git_ai_command_without_pre_sync_for_test(args, envs);
+    command.current_dir(cwd);
+    let output = command.output().expect("failed to run git-ai");
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    if output.status.success() {
+        Ok(format!("{}{}", stdout, stderr))
+    } else {
+        Err(format!("{}{}", stderr, stdout))
+    }
+}
+
+fn commit_ai_file(repo: &TestRepo) {
+    let mut file = repo.filename("tracked.txt");
+    file.set_contents(crate::lines![
+        "human line".human(),
+        "ai line".ai(),
+        "ai line 2".ai()
+    ]);
+    repo.stage_all_and_commit("Add tracked file").unwrap();
+}
+
+#[test]
+fn test_stats_honors_git_dir_and_work_tree_env() {
+    let repo = TestRepo::new();
+    commit_ai_file(&repo);
+    let outside = tempfile::tempdir().unwrap();
+    let repo_path = repo.canonical_path();
+    let git_dir = repo_path.join(".git");
+
+    let raw = git_ai_outside_repo(
+        &repo,
+        outside.path(),
+        &["stats", "--json"],
+        &[
+            ("GIT_DIR", git_dir.to_str().unwrap()),
+            ("GIT_WORK_TREE", repo_path.to_str().unwrap()),
+        ],
+    )
+    .expect("stats with GIT_DIR/GIT_WORK_TREE should succeed");
+    let stats: CommitStats = serde_json::from_str(&extract_json_object(&raw)).unwrap();
+    assert_eq!(stats.ai_additions, 2);
+}
+
+#[test]
+fn test_stats_honors_leading_git_dir_and_work_tree_args() {
+    let repo = TestRepo::new();
+    commit_ai_file(&repo);
+    let outside = tempfile::tempdir().unwrap();
+    let repo_path = repo.canonical_path();
+    let git_dir_arg = format!("--git-dir={}", repo_path.join(".git").display());
+    let work_tree_arg = format!("--work-tree={}", repo_path.display());
+
+    let raw = git_ai_outside_repo(
+        &repo,
+        outside.path(),
+        &[&git_dir_arg, &work_tree_arg, "stats", "--json"],
+        &[],
+    )
+    .expect("stats with --git-dir/--work-tree should succeed");
+    let stats: CommitStats = serde_json::from_str(&extract_json_object(&raw)).unwrap();
+    assert_eq!(stats.ai_additions, 2);
+}
+
+#[test]
+fn test_stats_resolves_relative_git_dir_env() {
+    let repo = TestRepo::new();
+    commit_ai_file(&repo);
+    let subdir = repo.path().join("nested");
+    std::fs::create_dir_all(&subdir).unwrap();
+
+    // Relative GIT_DIR/GIT_WORK_TREE are resolved against the caller's cwd,
+    // not the repository root that internal commands run from.
+    let raw = git_ai_outside_repo(
+        &repo,
+        &subdir,
+        &["stats", "--json"],
+        &[("GIT_DIR", "../.git"), ("GIT_WORK_TREE", "..")],
+    )
+    .expect("stats with relative GIT_DIR should succeed");
+    let stats: CommitStats = serde_json::from_str(&extract_json_object(&raw)).unwrap();
+    assert_eq!(stats.ai_additions, 2);
+}
+
+#[test]
+fn test_blame_honors_leading_change_dir_arg() {
+    let repo = TestRepo::new();
+    commit_ai_file(&repo);
+    let outside = tempfile::tempdir().unwrap();
+    let repo_path = repo.canonical_path();
+
+    let output = git_ai_outside_repo(
+        &repo,
+        outside.path(),
+        &["-C", repo_path.to_str().unwrap(), "blame", "tracked.txt"],
+        &[],
+    )
+    .expect("blame with -C should succeed");
+    assert!(
+        output.contains("ai line 2"),
+        "blame output should contain file content, got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_checkpoint_honors_leading_change_dir_arg() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("checkpointed.txt");
+    file.set_contents(crate::lines!["base".human()]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    std::fs::write(repo.path().join("checkpointed.txt"), "base\nfrom agent\n").unwrap();
+    let outside = tempfile::tempdir().unwrap();
+    let repo_path = repo.canonical_path();
+    repo.git_ai_from_working_dir(
+        outside.path(),
+        &[
+            "-C",
+            repo_path.to_str().unwrap(),
+            "checkpoint",
+            "mock_ai",
+            "checkpointed.txt",
+        ],
+    )
+    .expect("checkpoint with -C should succeed");
+    repo.stage_all_and_commit("Agent edit").unwrap();
+
+    file.assert_lines_and_blame(crate::lines!["base".human(), "from agent".ai()]);
+}
+
+#[test]
+fn test_debug_reports_repository_from_git_dir_env() {
+    let repo = TestRepo::new();
+    commit_ai_file(&repo);
+    let outside = tempfile::tempdir().unwrap();
+    let repo_path = repo.canonical_path();
+    let git_dir = repo_path.join(".git");
+
+    let output = git_ai_outside_repo(
+        &repo,
+        outside.path(),
+        &["debug"],
+        &[
+            ("GIT_DIR", git_dir.to_str().unwrap()),
+            ("GIT_WORK_TREE", repo_path.to_str().unwrap()),
+        ],
+    )
+    .expect("debug should succeed");
+    assert!(
+        output.contains("In repository: true"),
+        "debug should detect the repository from GIT_DIR, got: {}",
+        output
+    );
+    assert!(
+        output.contains(&format!("Workdir: {}", repo_path.display())),
+        "debug should report the GIT_WORK_TREE workdir, got: {}",
+        output
+    );
+}
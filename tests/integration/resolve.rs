@@ -0,0 +1,130 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+use git_ai::authorship::authorship_log::HumanRecord;
+use git_ai::authorship::authorship_log_serialization::AuthorshipLog;
+
+#[test]
+fn test_resolve_takes_theirs_from_reconstructed_note_and_records_audit() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(crate::lines!["fn one() {}".ai(), "fn two() {}".ai()]);
+    let commit = repo.stage_all_and_commit("AI lines").unwrap();
+
+    // A reconstructed version of the note that attributes the lines to a human.
+    let human_hash = "h_0123456789abcdef".to_string();
+    let mut theirs = commit.authorship_log.clone();
+    for entry in &mut theirs.attestations[0].entries {
+        entry.hash = human_hash.clone();
+    }
+    theirs.metadata.humans.insert(
+        human_hash.clone(),
+        HumanRecord {
+            author: "Alice <alice@example.com>".to_string(),
+        },
+    );
+    let theirs_path = repo.path().join("reconstructed-note.txt");
+    std::fs::write(&theirs_path, theirs.serialize_to_string().unwrap()).unwrap();
+    let theirs_arg = theirs_path.to_string_lossy().to_string();
+
+    let dry_run = repo
+        .git_ai(&["resolve", "--file", &theirs_arg, "--json"])
+        .expect("dry run should succeed");
+    let report: serde_json::Value = serde_json::from_str(dry_run.trim()).unwrap();
+    assert_eq!(report["written"], false);
+    assert_eq!(
+        report["conflicts"][0]["theirs_author"],
+        "Alice <alice@example.com>"
+    );
+
+    repo.git_ai(&["resolve", "--file", &theirs_arg, "--theirs"])
+        .expect("resolve should succeed");
+
+    let note = repo.read_authorship_note(&commit.commit_sha).unwrap();
+    let resolved = AuthorshipLog::deserialize_from_string(&note).unwrap();
+    assert!(
+        resolved.attestations[0]
+            .entries
+            .iter()
+            .all(|entry| entry.hash == human_hash)
+    );
+    let record = resolved.metadata.resolutions.last().expect("audit marker");
+    assert_eq!(record.source, format!("file:{}", theirs_arg));
+    assert_eq!((record.ours_lines, record.theirs_lines), (0, 2));
+}
+
+#[test]
+fn test_resolve_reports_unparsable_note_as_notes_error_with_exit_code() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(crate::lines!["fn one() {}".ai()]);
+    repo.stage_all_and_commit("AI line").unwrap();
+
+    let broken_path = repo.path().join("broken-note.txt");
+    std::fs::write(&broken_path, "not an authorship note").unwrap();
+    let broken_arg = broken_path.to_string_lossy().to_string();
+
+    let output = repo
+        .git_ai_command_without_pre_sync_for_test(
+            &["resolve", "--file", &broken_arg, "--json"],
+            &[],
+        )
+        .output()
+        .expect("run git-ai resolve");
+    assert_eq!(output.status.code(), Some(5));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let payload: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(payload["error"]["category"], "notes");
+    assert_eq!(payload["error"]["exit_code"], 5);
+    assert!(
+        payload["error"]["message"]
+            .as_str()
+            .is_some_and(|message| message.contains("does not parse")),
+        "{}",
+        stdout
+    );
+}
+
+crate::reuse_tests_in_worktree!(
+    test_resolve_takes_theirs_from_reconstructed_note_and_records_audit,
+    test_resolve_reports_unparsable_note_as_notes_error_with_exit_code,
+);
+
+#[test]
+fn test_resolve_aborts_when_stdin_ends_before_a_pick() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(crate::lines!["fn one() {}".ai()]);
+    let commit = repo.stage_all_and_commit("AI line").unwrap();
+    let before = repo.read_authorship_note(&commit.commit_sha).unwrap();
+
+    let human_hash = "h_0123456789abcdef".to_string();
+    let mut theirs = commit.authorship_log.clone();
+    for entry in &mut theirs.attestations[0].entries {
+        entry.hash = human_hash.clone();
+    }
+    theirs.metadata.humans.insert(
+        human_hash,
+        HumanRecord {
+            author: "Alice <alice@example.com>".to_string(),
+        },
+    );
+    let theirs_path = repo.path().join("reconstructed-note.txt");
+    std::fs::write(&theirs_path, theirs.serialize_to_string().unwrap()).unwrap();
+    let theirs_arg = theirs_path.to_string_lossy().to_string();
+
+    let output = repo
+        .git_ai_command_without_pre_sync_for_test(&["resolve", "--file", &theirs_arg], &[])
+        .stdin(std::process::Stdio::null())
+        .output()
+        .expect("run git-ai resolve");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("Aborted"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        repo.read_authorship_note(&commit.commit_sha).unwrap(),
+        before
+    );
+}
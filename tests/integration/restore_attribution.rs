@@ -0,0 +1,61 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+use git_ai::authorship::clean_archive::archive_before_clean;
+use git_ai::git::repository as GitAiRepository;
+
+/// The wrapper archives before forwarding `git clean`; call the same entry
+/// point directly since the test harness runs the real git binary.
+fn archive_clean(repo: &TestRepo, args: &[&str]) -> Option<Vec<String>> {
+    let gitai_repo = GitAiRepository::find_repository_in_path(repo.path().to_str().unwrap())
+        .expect("should open repo");
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    archive_before_clean(&gitai_repo, &args)
+        .expect("archive should succeed")
+        .map(|manifest| manifest.files)
+}
+
+#[test]
+fn test_restore_attribution_after_git_clean() {
+    let repo = TestRepo::new();
+    let mut base = repo.filename("base.txt");
+    base.set_contents(crate::lines!["base"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let mut file = repo.filename("gen/agent.rs");
+    file.set_contents(crate::lines!["ai line 1".ai(), "ai line 2".ai()]);
+    std::fs::write(repo.path().join("scratch.txt"), "scratch\n").unwrap();
+
+    assert_eq!(
+        archive_clean(&repo, &["-fd"]),
+        Some(vec!["gen/agent.rs".to_string()])
+    );
+    repo.git(&["clean", "-fd"]).unwrap();
+    assert!(!repo.path().join("gen/agent.rs").exists());
+
+    let output = repo.git_ai(&["restore-attribution"]).unwrap();
+    assert!(output.contains("restored gen/agent.rs"), "got: {}", output);
+
+    repo.stage_all_and_commit("Add agent file").unwrap();
+    file.assert_lines_and_blame(crate::lines!["ai line 1".ai(), "ai line 2".ai()]);
+}
+
+#[test]
+fn test_git_clean_dry_run_is_not_archived() {
+    let repo = TestRepo::new();
+    let mut base = repo.filename("base.txt");
+    base.set_contents(crate::lines!["base"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let mut file = repo.filename("agent.rs");
+    file.set_contents(crate::lines!["ai line".ai()]);
+
+    assert_eq!(archive_clean(&repo, &["-n"]), None);
+    assert_eq!(archive_clean(&repo, &["-fn"]), None);
+
+    let output = repo.git_ai(&["restore-attribution", "--list"]).unwrap();
+    assert!(
+        output.contains("No git clean archives found."),
+        "got: {}",
+        output
+    );
+}
@@ -264,6 +264,62 @@ fn test_revert_multiple_commits_restores_each_original_attribution() {
         .assert_committed_lines(crate::lines!["a base".human(), "AI a".ai()]);
 }
 
+/// `git revert A..B` records, on each revert commit, how many of the lines it
+/// removed were AI, known-human, or untracked in the reverted commit's note.
+#[test]
+fn test_revert_range_records_removed_lines_by_attribution() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join("base.txt"), "base\n").unwrap();
+    repo.stage_all_and_commit("base").unwrap();
+
+    fs::write(repo.path().join("ai.txt"), "ai 1\nai 2\nai 3\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "ai.txt"]).unwrap();
+    let ai_commit = repo
+        .stage_all_and_commit("add ai lines")
+        .unwrap()
+        .commit_sha;
+
+    fs::write(repo.path().join("human.txt"), "human 1\nhuman 2\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_known_human", "human.txt"])
+        .unwrap();
+    let human_commit = repo
+        .stage_all_and_commit("add human lines")
+        .unwrap()
+        .commit_sha;
+
+    repo.git(&["revert", "--no-edit", "HEAD~2..HEAD"]).unwrap();
+
+    let revert_record = |rev: &str| {
+        let sha = repo.git(&["rev-parse", rev]).unwrap().trim().to_string();
+        let note = repo
+            .read_authorship_note(&sha)
+            .unwrap_or_else(|| panic!("revert commit {} should have a note", rev));
+        let (_, metadata) = note.split_once("---\n").unwrap();
+        let metadata: serde_json::Value = serde_json::from_str(metadata).unwrap();
+        metadata["revert"].clone()
+    };
+
+    // Reverts run newest first: HEAD~1 reverts the human commit, HEAD the AI one.
+    assert_eq!(
+        revert_record("HEAD~1"),
+        json!({
+            "reverted_commits": [human_commit],
+            "ai_lines_removed": 0,
+            "human_lines_removed": 2,
+            "unknown_lines_removed": 0,
+        })
+    );
+    assert_eq!(
+        revert_record("HEAD"),
+        json!({
+            "reverted_commits": [ai_commit],
+            "ai_lines_removed": 3,
+            "human_lines_removed": 0,
+            "unknown_lines_removed": 0,
+        })
+    );
+}
+
 #[test]
 fn test_revert_restored_ai_attribution_survives_shifted_line_numbers() {
     let repo = TestRepo::new();
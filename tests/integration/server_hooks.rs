@@ -0,0 +1,63 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+
+fn remote_notes_tip(upstream: &TestRepo) -> Option<String> {
+    upstream
+        .git_og(&["rev-parse", "--verify", "refs/notes/ai"])
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+#[test]
+fn test_server_hooks_accept_valid_notes_and_reject_malformed_ones() {
+    let (repo, upstream) = TestRepo::new_with_remote();
+    upstream.git_ai(&["server-hooks", "install"]).unwrap();
+    let hook = std::fs::read_to_string(upstream.path().join("hooks").join("pre-receive")).unwrap();
+    assert!(hook.contains("server-hooks pre-receive"), "got: {}", hook);
+
+    let mut file = repo.filename("feature.txt");
+    file.set_contents(crate::lines!["human line", "ai line".ai()]);
+    let sha = repo.stage_all_and_commit("feature").unwrap().commit_sha;
+    assert!(repo.read_authorship_note(&sha).is_some());
+
+    repo.git_og(&["push", "origin", "HEAD:refs/heads/main", "refs/notes/ai"])
+        .expect("push with a valid note should be accepted");
+    let accepted_tip = remote_notes_tip(&upstream).expect("notes ref should be pushed");
+
+    repo.git_og(&[
+        "notes",
+        "--ref=ai",
+        "add",
+        "-f",
+        "-m",
+        "not an authorship log",
+        &sha,
+    ])
+    .unwrap();
+    let err = repo
+        .git_og(&["push", "origin", "refs/notes/ai"])
+        .expect_err("push with a malformed note should be rejected");
+    assert!(err.contains("malformed authorship note"), "got: {}", err);
+    assert_eq!(remote_notes_tip(&upstream), Some(accepted_tip));
+}
+
+#[test]
+fn test_server_hooks_enforce_note_size_limit() {
+    let (repo, upstream) = TestRepo::new_with_remote();
+    upstream
+        .git_ai(&["server-hooks", "install", "--max-note-bytes", "16"])
+        .unwrap();
+
+    let mut file = repo.filename("feature.txt");
+    file.set_contents(crate::lines!["ai line".ai()]);
+    repo.stage_all_and_commit("feature").unwrap();
+
+    let err = repo
+        .git_og(&["push", "origin", "HEAD:refs/heads/main", "refs/notes/ai"])
+        .expect_err("push with an oversized note should be rejected");
+    assert!(err.contains("(limit 16)"), "got: {}", err);
+    assert_eq!(remote_notes_tip(&upstream), None);
+
+    upstream.git_ai(&["server-hooks", "uninstall"]).unwrap();
+    assert!(!upstream.path().join("hooks").join("pre-receive").exists());
+}
@@ -0,0 +1,62 @@
+use crate::repos::test_repo::TestRepo;
+use std::fs;
+
+#[test]
+fn test_session_attributes_human_checkpoints_to_tool() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join("base.txt"), "base\n").unwrap();
+    repo.stage_all_and_commit("base").unwrap();
+
+    let started = repo
+        .git_ai(&["session", "start", "--tool", "aider", "--model", "gpt-4o"])
+        .unwrap();
+    assert!(started.contains("aider (gpt-4o)"), "got: {}", started);
+    let status = repo.git_ai(&["session", "status"]).unwrap();
+    assert!(status.contains("Active session: aider"), "got: {}", status);
+
+    fs::write(repo.path().join("agent.txt"), "one\ntwo\nthree\n").unwrap();
+    repo.git_ai(&["checkpoint"]).unwrap();
+    repo.stage_all_and_commit("terminal agent work").unwrap();
+
+    let stats = repo.stats().unwrap();
+    assert_eq!(stats.ai_additions, 3);
+    assert_eq!(
+        stats
+            .tool_model_breakdown
+            .get("aider::gpt-4o")
+            .map(|s| s.ai_additions),
+        Some(3)
+    );
+
+    let ended = repo.git_ai(&["session", "end"]).unwrap();
+    assert!(ended.contains("Ended aider"), "got: {}", ended);
+
+    fs::write(repo.path().join("human.txt"), "mine\n").unwrap();
+    repo.git_ai(&["checkpoint"]).unwrap();
+    repo.stage_all_and_commit("human work").unwrap();
+    let stats = repo.stats().unwrap();
+    assert_eq!(stats.ai_additions, 0);
+}
+
+#[test]
+fn test_expired_session_is_ignored() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join("base.txt"), "base\n").unwrap();
+    repo.stage_all_and_commit("base").unwrap();
+
+    repo.git_ai(&["session", "start", "--tool", "aider", "--ttl", "1s"])
+        .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+    let status = repo.git_ai(&["session", "status"]).unwrap();
+    assert!(status.contains("No active session"), "got: {}", status);
+
+    fs::write(repo.path().join("human.txt"), "mine\n").unwrap();
+    repo.git_ai(&["checkpoint"]).unwrap();
+    repo.stage_all_and_commit("human work").unwrap();
+    assert_eq!(repo.stats().unwrap().ai_additions, 0);
+}
+
+crate::reuse_tests_in_worktree!(
+    test_session_attributes_human_checkpoints_to_tool,
+    test_expired_session_is_ignored,
+);
@@ -0,0 +1,48 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+
+#[test]
+fn test_show_patch_marks_ai_lines_and_hunks() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(crate::lines!["fn old() {}".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    file.set_contents(crate::lines!["fn new() {}".ai(), "fn another() {}".ai()]);
+    let commit = repo.stage_all_and_commit("AI changes").unwrap();
+
+    let output = repo
+        .git_ai(&["show", "--patch", &commit.commit_sha])
+        .expect("git-ai show --patch should succeed");
+
+    assert!(
+        output.contains(&format!("commit {}", commit.commit_sha)),
+        "{}",
+        output
+    );
+    assert!(output.contains("    AI changes"), "{}", output);
+    assert!(
+        output.contains("diff --git a/lib.rs b/lib.rs"),
+        "{}",
+        output
+    );
+
+    let hunk_header = output
+        .lines()
+        .find(|line| line.trim_start().starts_with("@@ "))
+        .expect("hunk header");
+    assert!(hunk_header.contains("[AI: mock_ai 2]"), "{}", output);
+    for added in ["+fn new() {}", "+fn another() {}"] {
+        let line = output
+            .lines()
+            .find(|line| line.ends_with(added))
+            .unwrap_or_else(|| panic!("missing {} in {}", added, output));
+        assert!(line.trim_start().starts_with("mock_ai | "), "{}", line);
+    }
+
+    // Without --patch the raw authorship log is still printed.
+    let raw = repo.git_ai(&["show", &commit.commit_sha]).unwrap();
+    assert!(!raw.contains("diff --git"), "{}", raw);
+}
+
+crate::reuse_tests_in_worktree!(test_show_patch_marks_ai_lines_and_hunks,);
@@ -47,6 +47,46 @@ fn test_simple_additions_empty_repo() {
     file.assert_lines_and_blame(crate::lines!["Line1".human(), "Line 2".ai(), "Line 3".ai(),]);
 }
 
+#[test]
+fn test_simple_additions_orphan_branch_root_commit() {
+    let repo = TestRepo::new();
+    let mut base = repo.filename("base.txt");
+    base.set_contents(crate::lines!["Base line 1"]);
+    repo.stage_all_and_commit("Base commit").unwrap();
+
+    repo.git(&["checkout", "--orphan", "fresh"]).unwrap();
+    let mut file = repo.filename("orphan.txt");
+    file.set_contents(crate::lines!["Human line", "AI line".ai()]);
+    let commit = repo.stage_all_and_commit("Orphan root").unwrap();
+
+    assert_eq!(
+        repo.git(&["rev-list", "--count", "HEAD"]).unwrap().trim(),
+        "1",
+        "the orphan commit should be a root commit"
+    );
+    assert!(
+        repo.read_authorship_note(&commit.commit_sha).is_some(),
+        "the root commit of an orphan branch should get a note"
+    );
+    file.assert_lines_and_blame(crate::lines!["Human line".human(), "AI line".ai()]);
+}
+
+#[test]
+fn test_simple_additions_orphan_checkout_carries_uncommitted_attribution() {
+    let repo = TestRepo::new();
+    let mut base = repo.filename("base.txt");
+    base.set_contents(crate::lines!["Base line 1"]);
+    repo.stage_all_and_commit("Base commit").unwrap();
+
+    // AI edits made before `checkout --orphan` travel with the working tree.
+    let mut file = repo.filename("carried.txt");
+    file.set_contents(crate::lines!["Human line", "AI line".ai()]);
+    repo.git(&["checkout", "--orphan", "fresh"]).unwrap();
+    repo.stage_all_and_commit("Orphan root").unwrap();
+
+    file.assert_lines_and_blame(crate::lines!["Human line".human(), "AI line".ai()]);
+}
+
 #[test]
 fn test_simple_additions_with_base_commit() {
     let repo = TestRepo::new();
@@ -2303,6 +2343,8 @@ fn test_status_checkpoint_preserves_author_email_in_session() {
 
 crate::reuse_tests_in_worktree!(
     test_simple_additions_empty_repo,
+    test_simple_additions_orphan_branch_root_commit,
+    test_simple_additions_orphan_checkout_carries_uncommitted_attribution,
     test_simple_additions_with_base_commit,
     test_simple_additions_on_top_of_ai_contributions,
     test_simple_additions_new_file_not_git_added,
@@ -1140,6 +1140,44 @@ fn test_stash_branch_preserves_ai_attribution() {
     );
 }
 
+#[test]
+fn test_stash_branch_then_pop_remaining_stash_keeps_attribution() {
+    // `git stash branch` drops the stash it applied; the next `stash pop` must
+    // resolve to the stash that was underneath it, not the dropped one.
+    let repo = TestRepo::new();
+
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(vec!["# Test Repo".to_string()]);
+    repo.stage_all_and_commit("initial commit")
+        .expect("commit should succeed");
+
+    let mut first = repo.filename("first.txt");
+    first.set_contents(vec!["first ai line".ai()]);
+    repo.git_ai(&["checkpoint", "mock_ai"])
+        .expect("checkpoint should succeed");
+    repo.git(&["stash", "push", "-m", "first"])
+        .expect("stash should succeed");
+
+    let mut second = repo.filename("second.txt");
+    second.set_contents(vec!["second ai line".ai()]);
+    repo.git_ai(&["checkpoint", "mock_ai"])
+        .expect("checkpoint should succeed");
+    repo.git(&["stash", "push", "-m", "second"])
+        .expect("stash should succeed");
+
+    repo.git(&["stash", "branch", "from-second"])
+        .expect("stash branch should succeed");
+    repo.stage_all_and_commit("apply second stash via branch")
+        .expect("commit should succeed");
+    second.assert_lines_and_blame(vec!["second ai line".ai()]);
+
+    repo.git(&["stash", "pop"])
+        .expect("stash pop should succeed");
+    repo.stage_all_and_commit("apply first stash")
+        .expect("commit should succeed");
+    first.assert_lines_and_blame(vec!["first ai line".ai()]);
+}
+
 #[test]
 fn test_stash_pop_conflict_preserves_ai_attribution_without_new_checkpoint() {
     // ISSUE-010: git stash pop with conflict loses all AI attribution
@@ -1568,6 +1606,9 @@ fn test_partial_stash_trims_unstashed_initial_metadata() {
             agent_id: test_agent("session-b"),
             human_author: None,
             custom_attributes: None,
+            tool_calls: BTreeMap::new(),
+            subagents: BTreeMap::new(),
+            tool_versions: BTreeMap::new(),
         },
     );
 
@@ -1687,6 +1728,7 @@ crate::reuse_tests_in_worktree!(
     test_stash_pop_across_branches_with_conflict,
     test_stash_apply_reset_apply_again,
     test_stash_branch_preserves_ai_attribution,
+    test_stash_branch_then_pop_remaining_stash_keeps_attribution,
     test_stash_pop_conflict_preserves_ai_attribution_without_new_checkpoint,
     test_stash_apply_shift_uses_final_commit_tree_after_later_edit,
 );
@@ -0,0 +1,172 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::TestRepo;
+use std::fs;
+
+#[test]
+fn test_state_export_import_round_trips_working_logs_and_notes() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("planets.txt");
+    file.set_contents(crate::lines!["Mercury", "Venus".ai()]);
+    let commit = repo.stage_all_and_commit("Add planets").unwrap();
+    let note = repo
+        .read_authorship_note(&commit.commit_sha)
+        .expect("commit should have an authorship note");
+
+    fs::write(repo.path().join("planets.txt"), "Mercury\nVenus\nEarth\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "planets.txt"])
+        .unwrap();
+    let checkpoints_file = repo.current_working_logs().checkpoints_file();
+    let checkpoints = fs::read_to_string(&checkpoints_file).unwrap();
+    assert!(!checkpoints.trim().is_empty());
+
+    let bundle_dir = tempfile::tempdir().unwrap();
+    let bundle = bundle_dir.path().join("state-bundle.zip");
+    let bundle_arg = bundle.to_string_lossy().to_string();
+    let output = repo
+        .git_ai(&["state", "export", "--output", &bundle_arg])
+        .unwrap();
+    assert!(output.contains("1 note(s)"), "{}", output);
+    assert!(bundle.exists());
+
+    repo.git_og(&["notes", "--ref=ai", "remove", &commit.commit_sha])
+        .unwrap();
+    fs::remove_file(&checkpoints_file).unwrap();
+
+    let output = repo.git_ai(&["state", "import", &bundle_arg]).unwrap();
+    assert!(output.contains("1 note(s)"), "{}", output);
+    assert_eq!(fs::read_to_string(&checkpoints_file).unwrap(), checkpoints);
+    assert_eq!(
+        repo.read_authorship_note(&commit.commit_sha).as_deref(),
+        Some(note.as_str())
+    );
+}
+
+#[test]
+fn test_state_import_rejects_bundle_from_newer_storage_format() {
+    let repo = TestRepo::new();
+    let bundle_dir = tempfile::tempdir().unwrap();
+    let bundle = bundle_dir.path().join("future-state.zip");
+    let bundle_arg = bundle.to_string_lossy().to_string();
+    // A bundle written by a future git-ai: only the manifest matters for the check.
+    let mut writer = zip::ZipWriter::new(fs::File::create(&bundle).unwrap());
+    writer
+        .start_file("manifest.json", zip::write::SimpleFileOptions::default())
+        .unwrap();
+    std::io::Write::write_all(
+        &mut writer,
+        serde_json::json!({
+            "git_ai_version": "999.0.0",
+            "storage_format_version": git_ai::git::repo_storage::STORAGE_FORMAT_VERSION + 1,
+            "created_at": 0,
+            "ai_files": 0,
+            "notes": 0,
+        })
+        .to_string()
+        .as_bytes(),
+    )
+    .unwrap();
+    writer.finish().unwrap();
+
+    let err = repo.git_ai(&["state", "import", &bundle_arg]).unwrap_err();
+    assert!(err.contains("git-ai upgrade"), "{}", err);
+}
+
+#[test]
+fn test_state_import_rejects_bad_note_name_before_writing() {
+    let repo = TestRepo::new();
+    let bundle_dir = tempfile::tempdir().unwrap();
+    let bundle = bundle_dir.path().join("corrupt-state.zip");
+    let bundle_arg = bundle.to_string_lossy().to_string();
+    let mut writer = zip::ZipWriter::new(fs::File::create(&bundle).unwrap());
+    let options = zip::write::SimpleFileOptions::default();
+    for (name, contents) in [
+        (
+            "manifest.json",
+            serde_json::json!({
+                "git_ai_version": "1.0.0",
+                "storage_format_version": git_ai::git::repo_storage::STORAGE_FORMAT_VERSION,
+                "created_at": 0,
+                "ai_files": 1,
+                "notes": 1,
+            })
+            .to_string(),
+        ),
+        ("ai/imported-marker", "written too early".to_string()),
+        ("notes/not-a-commit", "{}".to_string()),
+    ] {
+        writer.start_file(name, options).unwrap();
+        std::io::Write::write_all(&mut writer, contents.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap();
+
+    let err = repo.git_ai(&["state", "import", &bundle_arg]).unwrap_err();
+    assert!(err.contains("notes/not-a-commit"), "{}", err);
+    assert!(
+        !repo
+            .path()
+            .join(".git")
+            .join("ai")
+            .join("imported-marker")
+            .exists(),
+        "no ai/ file may be written from a rejected bundle"
+    );
+}
+
+#[test]
+fn test_state_export_import_round_trips_relocated_working_logs() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("moons.txt");
+    file.set_contents(crate::lines!["Luna", "Phobos".ai()]);
+    repo.stage_all_and_commit("Add moons").unwrap();
+
+    let relocated_dir = tempfile::tempdir().unwrap();
+    let relocated = relocated_dir.path().join("working_logs");
+    repo.git_ai(&[
+        "migrate-storage",
+        "--working-logs-dir",
+        &relocated.to_string_lossy(),
+    ])
+    .unwrap();
+    let location_file = repo.path().join(".git").join("ai").join("location");
+    let location = fs::read_to_string(&location_file).unwrap();
+
+    fs::write(repo.path().join("moons.txt"), "Luna\nPhobos\nDeimos\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "moons.txt"])
+        .unwrap();
+    let checkpoints_file = repo.current_working_logs().checkpoints_file();
+    assert!(checkpoints_file.starts_with(&relocated));
+    let checkpoints = fs::read_to_string(&checkpoints_file).unwrap();
+
+    let bundle_dir = tempfile::tempdir().unwrap();
+    let bundle = bundle_dir.path().join("state-bundle.zip");
+    let bundle_arg = bundle.to_string_lossy().to_string();
+    repo.git_ai(&["state", "export", "--output", &bundle_arg])
+        .unwrap();
+
+    let archive = zip::ZipArchive::new(fs::File::open(&bundle).unwrap()).unwrap();
+    let names: Vec<&str> = archive.file_names().collect();
+    assert!(!names.contains(&"ai/location"), "{:?}", names);
+    assert!(
+        names
+            .iter()
+            .any(|name| name.starts_with("ai/working_logs/") && name.ends_with("checkpoints.jsonl")),
+        "{:?}",
+        names
+    );
+
+    fs::remove_file(&checkpoints_file).unwrap();
+    fs::write(&location_file, &location).unwrap();
+    repo.git_ai(&["state", "import", &bundle_arg]).unwrap();
+
+    assert_eq!(fs::read_to_string(&checkpoints_file).unwrap(), checkpoints);
+    assert_eq!(fs::read_to_string(&location_file).unwrap(), location);
+    assert!(
+        !repo
+            .path()
+            .join(".git")
+            .join("ai")
+            .join("working_logs")
+            .exists(),
+        "imported working logs belong in the relocated directory"
+    );
+}
@@ -1,6 +1,6 @@
 use crate::repos::test_file::ExpectedLineExt;
 use crate::repos::test_repo::TestRepo;
-use git_ai::authorship::stats::CommitStats;
+use git_ai::authorship::stats::{CommitStats, CommitStatsByFile};
 use insta::assert_debug_snapshot;
 use std::fs;
 use std::io::{BufRead, BufReader};
@@ -310,6 +310,46 @@ fn test_authorship_log_stats() {
     );
 }
 
+#[test]
+fn test_stats_trailer_for_rev() {
+    let repo = TestRepo::new();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(crate::lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let mut file = repo.filename("planets.txt");
+    file.set_contents(crate::lines!["Mercury".human(), "Venus".ai(), "Earth".ai()]);
+    repo.stage_all_and_commit("Add planets").unwrap();
+
+    let output = repo.git_ai(&["stats", "--trailer", "HEAD"]).unwrap();
+    assert_eq!(
+        output.trim(),
+        "AI-Attribution: ai=2 human=1 mixed=0 tools=mock_ai"
+    );
+}
+
+#[test]
+fn test_stats_trailer_defaults_to_staged_changes() {
+    let repo = TestRepo::new();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(crate::lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let mut file = repo.filename("feature.txt");
+    file.set_contents(crate::lines![
+        "human line".human(),
+        "ai line 1".ai(),
+        "ai line 2".ai(),
+    ]);
+    repo.git(&["add", "feature.txt"]).unwrap();
+
+    let output = repo.git_ai(&["stats", "--trailer"]).unwrap();
+    assert_eq!(
+        output.trim(),
+        "AI-Attribution: ai=2 human=1 mixed=0 tools=mock_ai"
+    );
+}
+
 #[test]
 fn test_stats_cli_range() {
     let repo = TestRepo::new();
@@ -348,6 +388,132 @@ fn test_stats_cli_range() {
     );
 }
 
+fn rollup_range_stats(repo: &TestRepo, range: &str) -> serde_json::Value {
+    let raw = repo
+        .git_ai(&["stats", "--range", range, "--json"])
+        .expect("git-ai stats --range should succeed");
+    serde_json::from_str(&extract_json_object(&raw)).expect("valid rollup json")
+}
+
+#[test]
+fn test_stats_rollup_range_sums_commits_and_recomputes_changed_notes() {
+    let repo = TestRepo::new();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(crate::lines!["# Project"]);
+    let base = repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let mut planets = repo.filename("planets.txt");
+    planets.set_contents(crate::lines!["Mercury".human(), "Venus".ai(), "Earth".ai()]);
+    let second = repo.stage_all_and_commit("Add planets").unwrap();
+
+    let mut moons = repo.filename("moons.txt");
+    moons.set_contents(crate::lines!["Luna".ai(), "Phobos".human()]);
+    let third = repo.stage_all_and_commit("Add moons").unwrap();
+
+    repo.git_ai(&["stats", "--backfill"])
+        .expect("git-ai stats --backfill should succeed");
+
+    let range = format!("{}..{}", base.commit_sha, third.commit_sha);
+    let stats = rollup_range_stats(&repo, &range);
+    assert_eq!(stats["commits"], 2);
+    assert_eq!(stats["cached"], 2);
+    assert_eq!(stats["computed"], 0);
+    assert_eq!(stats["ai_accepted"], 3);
+    assert_eq!(stats["git_diff_added_lines"], 5);
+    assert_eq!(
+        stats["tool_model_breakdown"]["mock_ai::unknown"]["ai_accepted"],
+        3
+    );
+
+    // Replacing a note changes its blob oid, so only that commit is recomputed.
+    repo.git(&[
+        "notes",
+        "--ref=ai",
+        "copy",
+        "-f",
+        &third.commit_sha,
+        &second.commit_sha,
+    ])
+    .unwrap();
+    let stats = rollup_range_stats(&repo, &range);
+    assert_eq!(stats["cached"], 1);
+    assert_eq!(stats["computed"], 1);
+}
+
+#[test]
+fn test_stats_baseline_reports_commits_since_saved_snapshot() {
+    let repo = TestRepo::new();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(crate::lines!["# Project"]);
+    let release = repo.stage_all_and_commit("Release 1.0").unwrap();
+
+    let saved = repo
+        .git_ai(&["stats", "--save-baseline", "release-1.0"])
+        .expect("git-ai stats --save-baseline should succeed");
+    assert!(saved.contains("release-1.0"), "{}", saved);
+
+    let mut planets = repo.filename("planets.txt");
+    planets.set_contents(crate::lines!["Mercury".human(), "Venus".ai(), "Earth".ai()]);
+    repo.stage_all_and_commit("Add planets").unwrap();
+
+    let raw = repo
+        .git_ai(&["stats", "--baseline", "release-1.0", "--json"])
+        .expect("git-ai stats --baseline should succeed");
+    let stats: serde_json::Value =
+        serde_json::from_str(&extract_json_object(&raw)).expect("valid rollup json");
+    assert_eq!(stats["commits"], 1);
+    assert_eq!(stats["ai_accepted"], 2);
+    assert_eq!(stats["git_diff_added_lines"], 3);
+
+    let listed = repo
+        .git_ai(&["stats", "--list-baselines"])
+        .expect("git-ai stats --list-baselines should succeed");
+    assert!(listed.contains("release-1.0"), "{}", listed);
+    assert!(listed.contains(&release.commit_sha[..8]), "{}", listed);
+
+    let err = repo
+        .git_ai(&["stats", "--baseline", "missing"])
+        .expect_err("unknown baseline should fail");
+    assert!(err.contains("No stats baseline named 'missing'"), "{}", err);
+}
+
+#[test]
+fn test_stats_coverage_reports_commits_without_notes() {
+    let repo = TestRepo::new();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(crate::lines!["# Project"]);
+    let base = repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let mut planets = repo.filename("planets.txt");
+    planets.set_contents(crate::lines!["Venus".ai(), "Earth".ai()]);
+    let tracked = repo.stage_all_and_commit("Add planets").unwrap();
+
+    fs::write(repo.path().join("moons.txt"), "Luna\nPhobos\n").unwrap();
+    repo.git_og(&["add", "moons.txt"]).unwrap();
+    repo.git_og(&["commit", "-m", "Add moons outside git-ai"])
+        .unwrap();
+    let untracked = repo.git_og(&["rev-parse", "HEAD"]).unwrap();
+
+    let range = format!("{}..{}", base.commit_sha, untracked.trim());
+    let raw = repo
+        .git_ai(&["stats", "--coverage", &range, "--json"])
+        .expect("git-ai stats --coverage should succeed");
+    let report: serde_json::Value =
+        serde_json::from_str(&extract_json_object(&raw)).expect("valid coverage json");
+    assert_eq!(report["added_lines"], 4);
+    assert_eq!(report["attributed_lines"], 2);
+    assert_eq!(report["coverage_pct"], 50.0);
+    assert_eq!(report["commits_with_notes"], 1);
+    assert_eq!(report["commits"][0]["commit_sha"], tracked.commit_sha);
+    assert_eq!(report["commits"][0]["coverage_pct"], 100.0);
+    assert_eq!(report["commits"][1]["has_note"], false);
+
+    let text = repo
+        .git_ai(&["stats", "--coverage", &range])
+        .expect("git-ai stats --coverage should succeed");
+    assert!(text.contains("Attribution coverage: 50.0%"), "{}", text);
+}
+
 #[test]
 fn test_stats_cli_range_ignores_repo_external_diff_helper() {
     let repo = TestRepo::new();
@@ -472,6 +638,7 @@ fn test_markdown_stats_deletion_only() {
         git_diff_deleted_lines: 5,
         git_diff_added_lines: 0,
         tool_model_breakdown: BTreeMap::new(),
+        test_code: Default::default(),
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -493,6 +660,7 @@ fn test_markdown_stats_all_human() {
         git_diff_deleted_lines: 0,
         git_diff_added_lines: 10,
         tool_model_breakdown: BTreeMap::new(),
+        test_code: Default::default(),
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -514,6 +682,7 @@ fn test_markdown_stats_all_ai() {
         git_diff_deleted_lines: 0,
         git_diff_added_lines: 15,
         tool_model_breakdown: BTreeMap::new(),
+        test_code: Default::default(),
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -535,6 +704,7 @@ fn test_markdown_stats_mixed() {
         git_diff_deleted_lines: 5,
         git_diff_added_lines: 30,
         tool_model_breakdown: BTreeMap::new(),
+        test_code: Default::default(),
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -556,6 +726,7 @@ fn test_markdown_stats_no_mixed() {
         git_diff_deleted_lines: 0,
         git_diff_added_lines: 20,
         tool_model_breakdown: BTreeMap::new(),
+        test_code: Default::default(),
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -578,6 +749,7 @@ fn test_markdown_stats_minimal_human() {
         git_diff_deleted_lines: 0,
         git_diff_added_lines: 100,
         tool_model_breakdown: BTreeMap::new(),
+        test_code: Default::default(),
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -607,6 +779,7 @@ fn test_markdown_stats_formatting() {
         git_diff_deleted_lines: 2,
         git_diff_added_lines: 13,
         tool_model_breakdown,
+        test_code: Default::default(),
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -614,6 +787,73 @@ fn test_markdown_stats_formatting() {
     assert_debug_snapshot!(markdown);
 }
 
+#[test]
+fn test_stats_format_markdown_renders_summary_and_collapsed_file_table() {
+    let repo = TestRepo::new();
+    repo.filename("README.md")
+        .set_contents(crate::lines!["# Repo"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    repo.filename("src/lib.rs")
+        .set_contents(crate::lines!["pub fn a() {}".ai(), "pub fn b() {}".human()]);
+    let commit = repo.stage_all_and_commit("Mixed commit").unwrap();
+
+    let markdown = repo
+        .git_ai(&["stats", &commit.commit_sha, "--format", "markdown"])
+        .expect("stats --format markdown should succeed");
+
+    assert!(markdown.contains(&format!(
+        "### AI attribution for `{}`",
+        &commit.commit_sha[..8]
+    )));
+    assert!(markdown.contains("| Author | Added lines | Share |"));
+    assert!(markdown.contains("```text\n🧠 you"));
+    assert!(markdown.contains("| AI | 1 | 50% |"));
+    assert!(markdown.contains("| Tool / model |"));
+    assert!(markdown.contains("<summary>Per-file breakdown (1 file)</summary>"));
+    assert!(markdown.contains("| `src/lib.rs` | 1 |"));
+
+    let err = repo
+        .git_ai(&["stats", "--json", "--format", "markdown"])
+        .expect_err("--json and --format markdown conflict");
+    assert!(err.contains("--json cannot be combined"));
+}
+
+#[test]
+fn test_stats_format_markdown_range_sums_the_per_file_table() {
+    let repo = TestRepo::new();
+    repo.filename("README.md")
+        .set_contents(crate::lines!["# Repo"]);
+    let base = repo.stage_all_and_commit("Initial commit").unwrap();
+
+    repo.filename("src/lib.rs")
+        .set_contents(crate::lines!["pub fn a() {}".ai()]);
+    repo.stage_all_and_commit("AI commit").unwrap();
+    repo.filename("src/lib.rs")
+        .set_contents(crate::lines!["pub fn a() {}".ai(), "pub fn b() {}".human()]);
+    let head = repo.stage_all_and_commit("Human commit").unwrap();
+
+    let range = format!("{}..{}", base.commit_sha, head.commit_sha);
+    let markdown = repo
+        .git_ai(&["stats", &range, "--format", "markdown", "--detail"])
+        .expect("range stats --format markdown should succeed");
+
+    assert!(markdown.contains(&format!(
+        "### AI attribution for `{}..{}`",
+        &base.commit_sha[..8],
+        &head.commit_sha[..8]
+    )));
+    assert!(markdown.contains("<summary>Per-file breakdown (1 file)</summary>"));
+    assert!(markdown.contains("| `src/lib.rs` | 1 |"));
+
+    let err = repo
+        .git_ai(&["stats", &range, "--detail"])
+        .expect_err("--detail needs markdown for a range");
+    assert!(
+        err.contains("--detail is only supported for a single commit or with --format markdown")
+    );
+}
+
 #[test]
 fn test_stats_default_ignores_snapshot_files() {
     let repo = TestRepo::new();
@@ -905,6 +1145,132 @@ fn test_stats_ignores_renamed_files() {
     assert_eq!(stats.human_additions, 0);
 }
 
+fn stats_by_file_from_args(repo: &TestRepo, args: &[&str]) -> CommitStatsByFile {
+    let raw = repo
+        .git_ai(args)
+        .expect("git-ai stats --by-file should succeed");
+    let json = extract_json_object(&raw);
+    serde_json::from_str(&json).expect("valid stats --by-file json")
+}
+
+#[test]
+fn test_stats_by_file_attributes_git_rm_of_ai_file_to_ai() {
+    let repo = TestRepo::new();
+    let mut ai_file = repo.filename("generated.rs");
+    ai_file.set_contents(crate::lines![
+        "fn a() {}".ai(),
+        "fn b() {}".ai(),
+        "fn c() {}".ai()
+    ]);
+    let mut human_file = repo.filename("notes.md");
+    human_file.set_contents(crate::lines!["# Notes".human(), "keep".human()]);
+    repo.stage_all_and_commit("add files").unwrap();
+
+    repo.git(&["rm", "generated.rs"]).unwrap();
+    repo.commit("remove generated code").unwrap();
+
+    let stats = stats_by_file_from_args(&repo, &["stats", "HEAD", "--json", "--by-file"]);
+    assert_eq!(stats.stats.git_diff_deleted_lines, 3);
+    let removed = stats
+        .files
+        .get("generated.rs")
+        .expect("deleted file should be reported");
+    assert_eq!(removed.ai_deletions, 3);
+    assert_eq!(removed.human_deletions, 0);
+    assert!(!stats.files.contains_key("notes.md"));
+}
+
+#[test]
+fn test_stats_by_file_splits_deletions_between_ai_and_human() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("mixed.txt");
+    file.set_contents(crate::lines![
+        "human one",
+        "AI one".ai(),
+        "AI two".ai(),
+        "human two"
+    ]);
+    repo.stage_all_and_commit("mixed authorship").unwrap();
+
+    file.set_contents(crate::lines!["human one", "replacement"]);
+    repo.stage_all_and_commit("rip out lines").unwrap();
+
+    let stats = stats_by_file_from_args(&repo, &["stats", "--json", "--by-file"]);
+    let mixed = stats
+        .files
+        .get("mixed.txt")
+        .expect("file should be reported");
+    assert_eq!(mixed.ai_deletions, 2);
+    assert_eq!(mixed.human_deletions, 1);
+    assert_eq!(
+        mixed.ai_additions + mixed.human_additions + mixed.unknown_additions,
+        1
+    );
+}
+
+#[test]
+fn test_stats_json_without_by_file_omits_files() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("plain.txt");
+    file.set_contents(crate::lines!["AI line".ai()]);
+    repo.stage_all_and_commit("ai commit").unwrap();
+
+    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&extract_json_object(&raw)).unwrap();
+    assert!(value.get("files").is_none());
+}
+
+#[test]
+fn test_stats_separates_test_code_from_production() {
+    let repo = TestRepo::new();
+    let mut feature = repo.filename("src/parser.rs");
+    feature.set_contents(crate::lines!["fn parse() {}".ai(), "// tuned".human()]);
+    let mut tests = repo.filename("tests/parser_test.rs");
+    tests.set_contents(crate::lines![
+        "#[test]".ai(),
+        "fn parses() {}".ai(),
+        "// edge case".human()
+    ]);
+    repo.stage_all_and_commit("parser with tests").unwrap();
+
+    let stats = stats_by_file_from_args(&repo, &["stats", "--json", "--by-file"]);
+    assert_eq!(stats.stats.ai_additions, 3);
+    assert_eq!(stats.stats.test_code.ai_additions, 2);
+    assert_eq!(stats.stats.test_code.total_additions(), 3);
+    assert!(stats.files["tests/parser_test.rs"].is_test);
+    assert!(!stats.files["src/parser.rs"].is_test);
+
+    let output = repo.git_ai(&["stats"]).unwrap();
+    assert!(
+        output.contains("tests       2 of 3 added lines by ai"),
+        "got: {}",
+        output
+    );
+    assert!(
+        output.contains("production  1 of 2 added lines by ai"),
+        "got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_stats_test_paths_from_workspace_config() {
+    let repo = TestRepo::new();
+    fs::write(
+        repo.path().join(".git-ai.toml"),
+        "test_paths = [\"qa/**\"]\n",
+    )
+    .unwrap();
+    let mut qa = repo.filename("qa/smoke.rs");
+    qa.set_contents(crate::lines!["smoke".ai()]);
+    let mut default_layout = repo.filename("tests/unit.rs");
+    default_layout.set_contents(crate::lines!["unit".ai()]);
+    repo.stage_all_and_commit("custom test layout").unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", "--json"]);
+    assert_eq!(stats.test_code.ai_additions, 1);
+}
+
 crate::reuse_tests_in_worktree!(
     test_authorship_log_stats,
     test_stats_cli_range,
@@ -916,6 +1282,8 @@ crate::reuse_tests_in_worktree!(
     test_markdown_stats_no_mixed,
     test_markdown_stats_minimal_human,
     test_markdown_stats_formatting,
+    test_stats_format_markdown_renders_summary_and_collapsed_file_table,
+    test_stats_format_markdown_range_sums_the_per_file_table,
     test_stats_default_ignores_snapshot_files,
     test_stats_default_ignores_lockfiles_and_generated_files,
     test_stats_ignores_linguist_generated_patterns,
@@ -925,4 +1293,10 @@ crate::reuse_tests_in_worktree!(
     test_stats_range_uses_default_ignores,
     test_post_commit_large_ignored_files_do_not_trigger_skip_warning,
     test_stats_ignores_renamed_files,
+    test_stats_by_file_attributes_git_rm_of_ai_file_to_ai,
+    test_stats_by_file_splits_deletions_between_ai_and_human,
+    test_stats_separates_test_code_from_production,
+    test_stats_test_paths_from_workspace_config,
+    test_stats_baseline_reports_commits_since_saved_snapshot,
+    test_stats_coverage_reports_commits_without_notes,
 );
@@ -8,6 +8,7 @@ use git_ai::authorship::authorship_log_serialization::generate_short_hash;
 use git_ai::authorship::stats::*;
 use git_ai::authorship::working_log::AgentId;
 use git_ai::git::repository::find_repository_in_path;
+use git_ai::workspace_config::WhitespaceMode;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 
@@ -41,7 +42,7 @@ fn test_stats_for_simple_ai_commit() {
     let gitai_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
 
     // Test our stats function
-    let stats = stats_for_commit_stats(&gitai_repo, &head_sha, &[]).unwrap();
+    let stats = stats_for_commit_stats(&gitai_repo, &head_sha, &[], WhitespaceMode::Exact).unwrap();
 
     // Verify the stats
     assert_eq!(
@@ -99,7 +100,7 @@ fn test_stats_for_mixed_commit() {
         .trim()
         .to_string();
     let gitai_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
-    let stats = stats_for_commit_stats(&gitai_repo, &head_sha, &[]).unwrap();
+    let stats = stats_for_commit_stats(&gitai_repo, &head_sha, &[], WhitespaceMode::Exact).unwrap();
 
     // Verify the stats
     // trigger_checkpoint_with_author produces KnownHuman checkpoints (post Task 9),
@@ -135,7 +136,7 @@ fn test_stats_for_initial_commit() {
         .trim()
         .to_string();
     let gitai_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
-    let stats = stats_for_commit_stats(&gitai_repo, &head_sha, &[]).unwrap();
+    let stats = stats_for_commit_stats(&gitai_repo, &head_sha, &[], WhitespaceMode::Exact).unwrap();
 
     // KnownHuman checkpoints record h_<hash> attributions for all human-edited lines,
     // so they appear as human_additions (not unknown) even on pure-human commits.
@@ -193,13 +194,19 @@ fn test_stats_ignores_single_lockfile() {
     let gitai_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
 
     // Test WITHOUT ignore - should count lockfile
-    let stats_with_lockfile = stats_for_commit_stats(&gitai_repo, &head_sha, &[]).unwrap();
+    let stats_with_lockfile =
+        stats_for_commit_stats(&gitai_repo, &head_sha, &[], WhitespaceMode::Exact).unwrap();
     assert_eq!(stats_with_lockfile.git_diff_added_lines, 1001); // 1 source + 1000 lockfile
 
     // Test WITH ignore - should exclude lockfile
     let ignore_patterns = vec!["Cargo.lock".to_string()];
-    let stats_without_lockfile =
-        stats_for_commit_stats(&gitai_repo, &head_sha, &ignore_patterns).unwrap();
+    let stats_without_lockfile = stats_for_commit_stats(
+        &gitai_repo,
+        &head_sha,
+        &ignore_patterns,
+        WhitespaceMode::Exact,
+    )
+    .unwrap();
     assert_eq!(stats_without_lockfile.git_diff_added_lines, 1); // Only 1 source line
     assert_eq!(stats_without_lockfile.ai_additions, 1);
 }
@@ -237,7 +244,8 @@ fn test_stats_ignores_multiple_lockfiles() {
     let gitai_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
 
     // Test WITHOUT ignore - counts all files (1501 lines)
-    let stats_all = stats_for_commit_stats(&gitai_repo, &head_sha, &[]).unwrap();
+    let stats_all =
+        stats_for_commit_stats(&gitai_repo, &head_sha, &[], WhitespaceMode::Exact).unwrap();
     assert_eq!(stats_all.git_diff_added_lines, 1501);
 
     // Test WITH ignore - only counts README (1 line)
@@ -246,7 +254,13 @@ fn test_stats_ignores_multiple_lockfiles() {
         "package-lock.json".to_string(),
         "yarn.lock".to_string(),
     ];
-    let stats_filtered = stats_for_commit_stats(&gitai_repo, &head_sha, &ignore_patterns).unwrap();
+    let stats_filtered = stats_for_commit_stats(
+        &gitai_repo,
+        &head_sha,
+        &ignore_patterns,
+        WhitespaceMode::Exact,
+    )
+    .unwrap();
     assert_eq!(stats_filtered.git_diff_added_lines, 1);
     // KnownHuman checkpoints record h_<hash> attributions, so the README line is human_additions.
     assert_eq!(stats_filtered.human_additions, 1);
@@ -281,12 +295,19 @@ fn test_stats_with_lockfile_only_commit() {
     let gitai_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
 
     // Test WITHOUT ignore - shows 2000 lines
-    let stats_with = stats_for_commit_stats(&gitai_repo, &head_sha, &[]).unwrap();
+    let stats_with =
+        stats_for_commit_stats(&gitai_repo, &head_sha, &[], WhitespaceMode::Exact).unwrap();
     assert_eq!(stats_with.git_diff_added_lines, 2000);
 
     // Test WITH ignore - shows 0 lines (lockfile-only commit)
     let ignore_patterns = vec!["Cargo.lock".to_string()];
-    let stats_without = stats_for_commit_stats(&gitai_repo, &head_sha, &ignore_patterns).unwrap();
+    let stats_without = stats_for_commit_stats(
+        &gitai_repo,
+        &head_sha,
+        &ignore_patterns,
+        WhitespaceMode::Exact,
+    )
+    .unwrap();
     assert_eq!(stats_without.git_diff_added_lines, 0);
     assert_eq!(stats_without.ai_additions, 0);
     assert_eq!(stats_without.human_additions, 0);
@@ -318,7 +339,7 @@ fn test_stats_empty_ignore_patterns() {
     let gitai_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
 
     // Test with empty patterns - should behave same as no filtering
-    let stats = stats_for_commit_stats(&gitai_repo, &head_sha, &[]).unwrap();
+    let stats = stats_for_commit_stats(&gitai_repo, &head_sha, &[], WhitespaceMode::Exact).unwrap();
     assert_eq!(stats.git_diff_added_lines, 2);
     assert_eq!(stats.ai_additions, 2);
 }
@@ -371,7 +392,8 @@ fn test_stats_with_glob_patterns() {
     let gitai_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
 
     // Test WITHOUT ignore - all files included (2001 lines)
-    let stats_all = stats_for_commit_stats(&gitai_repo, &head_sha, &[]).unwrap();
+    let stats_all =
+        stats_for_commit_stats(&gitai_repo, &head_sha, &[], WhitespaceMode::Exact).unwrap();
     assert_eq!(stats_all.git_diff_added_lines, 2001);
 
     // Test WITH glob patterns - only source code (1 line)
@@ -380,7 +402,13 @@ fn test_stats_with_glob_patterns() {
         "*lock.json".to_string(),    // Matches package-lock.json
         "*.generated.*".to_string(), // Matches *.generated.ts, *.generated.js
     ];
-    let stats_filtered = stats_for_commit_stats(&gitai_repo, &head_sha, &glob_patterns).unwrap();
+    let stats_filtered = stats_for_commit_stats(
+        &gitai_repo,
+        &head_sha,
+        &glob_patterns,
+        WhitespaceMode::Exact,
+    )
+    .unwrap();
     assert_eq!(stats_filtered.git_diff_added_lines, 1);
     assert_eq!(stats_filtered.ai_additions, 1);
 }
@@ -587,7 +615,8 @@ fn test_stats_for_merge_commit_skips_ai_acceptance() {
         .to_string();
 
     let gitai_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
-    let stats = stats_for_commit_stats(&gitai_repo, &merge_sha, &[]).unwrap();
+    let stats =
+        stats_for_commit_stats(&gitai_repo, &merge_sha, &[], WhitespaceMode::Exact).unwrap();
 
     assert_eq!(stats.ai_accepted, 0);
     assert_eq!(stats.ai_additions, 0);
@@ -607,8 +636,11 @@ fn test_stats_command_nonexistent_commit() {
     let result = stats_command(
         &gitai_repo,
         Some("0000000000000000000000000000000000000000"),
+        StatsFormat::Text,
+        false,
         false,
         &[],
+        WhitespaceMode::Exact,
     );
     assert!(result.is_err());
 }
@@ -632,7 +664,15 @@ fn test_stats_command_with_json_output() {
     let gitai_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
 
     // Should succeed with json output
-    let result = stats_command(&gitai_repo, Some(&head_sha), true, &[]);
+    let result = stats_command(
+        &gitai_repo,
+        Some(&head_sha),
+        StatsFormat::Json,
+        false,
+        false,
+        &[],
+        WhitespaceMode::Exact,
+    );
     assert!(result.is_ok());
 }
 
@@ -649,7 +689,15 @@ fn test_stats_command_default_to_head() {
     let gitai_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
 
     // No SHA provided should default to HEAD
-    let result = stats_command(&gitai_repo, None, false, &[]);
+    let result = stats_command(
+        &gitai_repo,
+        None,
+        StatsFormat::Text,
+        false,
+        false,
+        &[],
+        WhitespaceMode::Exact,
+    );
     assert!(result.is_ok());
 }
 
@@ -285,6 +285,30 @@ fn test_status_honors_git_ai_ignore_file() {
     assert_eq!(status.stats.ai_accepted, 1);
 }
 
+#[test]
+fn test_status_honors_workspace_config_exclude_paths() {
+    let repo = TestRepo::new();
+
+    write_file(&repo, "src/app.ts", "export const app = 1;\n");
+    repo.stage_all_and_commit("initial").unwrap();
+
+    write_file(&repo, ".git-ai.toml", "exclude_paths = [\"docs/**\"]\n");
+    write_file(
+        &repo,
+        "src/app.ts",
+        "export const app = 1;\nexport const next = 2;\n",
+    );
+    write_file(&repo, "docs/api.md", "# API\nendpoint 1\nendpoint 2\n");
+
+    repo.git_ai(&["checkpoint", "mock_ai", "src/app.ts", "docs/api.md"])
+        .unwrap();
+
+    let status = status_from_args(&repo, &["status", "--json"]);
+
+    assert_eq!(status.stats.git_diff_added_lines, 1);
+    assert_eq!(status.stats.ai_accepted, 1);
+}
+
 #[test]
 fn test_status_git_ai_ignore_union_with_gitattributes() {
     let repo = TestRepo::new();
@@ -379,6 +403,7 @@ crate::reuse_tests_in_worktree!(
     test_status_with_only_ignored_changes_reports_zero_diff,
     test_checkpoint_honors_git_ai_ignore_file,
     test_status_honors_git_ai_ignore_file,
+    test_status_honors_workspace_config_exclude_paths,
     test_status_git_ai_ignore_union_with_gitattributes,
     test_status_ignores_repo_external_diff_helper_for_internal_numstat,
     test_status_numstat_is_stable_under_hostile_diff_config,
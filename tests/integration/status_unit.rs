@@ -355,6 +355,77 @@ fn test_diff_only_no_changes_omits_checkpoints() {
     assert_eq!(diff_only.stats.git_diff_deleted_lines, 0);
 }
 
+/// Output flags given before the subcommand apply to it: a leading `--json`
+/// matches `status --json`, and `--quiet` drops the no-checkpoints hint.
+#[test]
+fn test_global_output_flags_apply_to_status() {
+    let repo = TestRepo::new();
+
+    write_file(&repo, "a.txt", "L1\nL2\n");
+    repo.stage_all_and_commit("initial").unwrap();
+
+    let quiet = repo
+        .git_ai(&["--quiet", "status"])
+        .expect("git-ai --quiet status should succeed");
+    assert!(
+        quiet.trim().is_empty(),
+        "--quiet should suppress the no-checkpoints hint, got: {quiet}"
+    );
+
+    write_file(&repo, "a.txt", "L1\nL2\nL3\n");
+    repo.git_ai(&["checkpoint", "mock_ai", "a.txt"]).unwrap();
+
+    let leading = status_json_with_args(&repo, &["--json", "status"]);
+    let trailing = status_json_with_args(&repo, &["status", "--json"]);
+    assert_eq!(
+        leading.stats.git_diff_added_lines,
+        trailing.stats.git_diff_added_lines
+    );
+    assert_eq!(
+        leading.checkpoints.map(|checkpoints| checkpoints.len()),
+        trailing.checkpoints.map(|checkpoints| checkpoints.len())
+    );
+}
+
+/// On an unborn branch `status` reads the `initial` working log and diffs the
+/// working tree against the empty tree instead of failing to resolve HEAD.
+#[test]
+fn test_status_on_unborn_branch() {
+    let repo = TestRepo::new();
+
+    write_file(&repo, "a.txt", "L1\nL2\nL3\n");
+    repo.git_ai(&["checkpoint", "mock_ai", "a.txt"]).unwrap();
+    repo.git(&["add", "a.txt"]).unwrap();
+
+    let status = status_json(&repo);
+    assert_eq!(status.checkpoints.len(), 1);
+    assert_eq!(status.stats.git_diff_added_lines, 3);
+}
+
+/// `status --json` reports the `.git/ai` footprint, counting checkpoint
+/// blobs separately; `--diff-only` omits it.
+#[test]
+fn test_status_reports_ai_storage_footprint() {
+    let repo = TestRepo::new();
+
+    write_file(&repo, "a.txt", "L1\n");
+    repo.stage_all_and_commit("initial").unwrap();
+
+    write_file(&repo, "a.txt", "L1\nL2\nL3\n");
+    repo.git_ai(&["checkpoint", "mock_ai", "a.txt"]).unwrap();
+
+    let raw = repo.git_ai(&["status", "--json"]).unwrap();
+    let status: serde_json::Value = serde_json::from_str(&extract_json_object(&raw)).unwrap();
+    let storage = &status["storage"];
+    assert!(storage["working_logs"].as_u64().unwrap() > 0);
+    assert!(storage["blobs"].as_u64().unwrap() > 0);
+    assert!(storage["total"].as_u64().unwrap() >= storage["working_logs"].as_u64().unwrap());
+
+    let raw = repo.git_ai(&["status", "--json", "--diff-only"]).unwrap();
+    let status: serde_json::Value = serde_json::from_str(&extract_json_object(&raw)).unwrap();
+    assert!(status.get("storage").is_none());
+}
+
 crate::reuse_tests_in_worktree!(
     test_working_dir_diff_stats_single_file_checkpoint,
     test_working_dir_diff_stats_exclusion_by_checkpoint,
@@ -366,4 +437,7 @@ crate::reuse_tests_in_worktree!(
     test_status_preserves_lowercase_agent_identifier,
     test_diff_only_omits_checkpoints_but_keeps_stats,
     test_diff_only_no_changes_omits_checkpoints,
+    test_global_output_flags_apply_to_status,
+    test_status_reports_ai_storage_footprint,
+    test_status_on_unborn_branch,
 );
@@ -0,0 +1,165 @@
+use crate::repos::test_file::ExpectedLineExt;
+use crate::repos::test_repo::{TestRepo, default_branchname};
+use git_ai::authorship::authorship_log_serialization::AuthorshipLog;
+
+fn head_note(repo: &TestRepo, rev: &str) -> AuthorshipLog {
+    let sha = repo.git(&["rev-parse", rev]).unwrap().trim().to_string();
+    let note = repo
+        .read_authorship_note(&sha)
+        .unwrap_or_else(|| panic!("{} should have an authorship note", rev));
+    AuthorshipLog::deserialize_from_string(&note).expect("parse note")
+}
+
+fn attested_paths(log: &AuthorshipLog) -> Vec<&str> {
+    log.attestations
+        .iter()
+        .map(|attestation| attestation.file_path.as_str())
+        .collect()
+}
+
+#[test]
+fn test_subtree_add_carries_notes_under_prefix() {
+    let repo = TestRepo::new();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(crate::lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    repo.git(&["checkout", "--orphan", "lib"]).unwrap();
+    repo.git(&["rm", "-rf", "--cached", "."]).unwrap();
+    std::fs::remove_file(repo.path().join("README.md")).unwrap();
+    let mut lib = repo.filename("lib.rs");
+    lib.set_contents(crate::lines!["fn helper() {}".ai(), "fn other() {}".ai()]);
+    repo.stage_all_and_commit("Library").unwrap();
+
+    repo.git(&["checkout", default_branchname()]).unwrap();
+    repo.git(&["subtree", "add", "--prefix=vendor/lib", "lib"])
+        .expect("subtree add should succeed");
+
+    let log = head_note(&repo, "HEAD");
+    assert_eq!(attested_paths(&log), vec!["vendor/lib/lib.rs"]);
+}
+
+#[test]
+fn test_subtree_split_strips_prefix_from_notes() {
+    let repo = TestRepo::new();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(crate::lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let mut tool = repo.filename("tools/gen.rs");
+    tool.set_contents(crate::lines!["fn generate() {}".ai()]);
+    let mut notes = repo.filename("NOTES.md");
+    notes.set_contents(crate::lines!["outside the subtree".ai()]);
+    repo.stage_all_and_commit("Add generator").unwrap();
+
+    repo.git(&["subtree", "split", "--prefix=tools", "-b", "tools-only"])
+        .expect("subtree split should succeed");
+
+    let log = head_note(&repo, "tools-only");
+    assert_eq!(attested_paths(&log), vec!["gen.rs"]);
+}
+
+/// `lib` is an orphan branch holding an AI-written `lib.rs`; the default
+/// branch only has a README.
+fn repo_with_lib_branch() -> TestRepo {
+    let repo = TestRepo::new();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(crate::lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    repo.git(&["checkout", "--orphan", "lib"]).unwrap();
+    repo.git(&["rm", "-rf", "--cached", "."]).unwrap();
+    std::fs::remove_file(repo.path().join("README.md")).unwrap();
+    let mut lib = repo.filename("lib.rs");
+    lib.set_contents(crate::lines!["fn helper() {}".ai()]);
+    repo.stage_all_and_commit("Library").unwrap();
+    repo.git(&["checkout", default_branchname()]).unwrap();
+    repo
+}
+
+/// Add an AI-written `extra.rs` on the `lib` branch and return to the default
+/// branch.
+fn extend_lib_branch(repo: &TestRepo) {
+    repo.git(&["checkout", "lib"]).unwrap();
+    let mut extra = repo.filename("extra.rs");
+    extra.set_contents(crate::lines!["fn extra() {}".ai()]);
+    repo.stage_all_and_commit("Extend library").unwrap();
+    repo.git(&["checkout", default_branchname()]).unwrap();
+}
+
+#[test]
+fn test_subtree_merge_carries_new_notes_under_prefix() {
+    let repo = repo_with_lib_branch();
+    repo.git(&["subtree", "add", "--prefix=vendor/lib", "lib"])
+        .expect("subtree add should succeed");
+    extend_lib_branch(&repo);
+
+    repo.git(&["subtree", "merge", "--prefix=vendor/lib", "lib"])
+        .expect("subtree merge should succeed");
+
+    let log = head_note(&repo, "HEAD");
+    assert_eq!(attested_paths(&log), vec!["vendor/lib/extra.rs"]);
+}
+
+#[test]
+fn test_subtree_pull_carries_new_notes_under_prefix() {
+    let repo = repo_with_lib_branch();
+    repo.git(&["subtree", "add", "--prefix=vendor/lib", "lib"])
+        .expect("subtree add should succeed");
+    extend_lib_branch(&repo);
+
+    repo.git(&["subtree", "pull", "--prefix=vendor/lib", ".", "lib"])
+        .expect("subtree pull should succeed");
+
+    let log = head_note(&repo, "HEAD");
+    assert_eq!(attested_paths(&log), vec!["vendor/lib/extra.rs"]);
+}
+
+#[test]
+fn test_subtree_squash_add_and_merge_carry_notes_under_prefix() {
+    let repo = repo_with_lib_branch();
+    repo.git(&["subtree", "add", "--prefix=vendor/lib", "lib", "--squash"])
+        .expect("subtree add --squash should succeed");
+    let log = head_note(&repo, "HEAD");
+    assert_eq!(attested_paths(&log), vec!["vendor/lib/lib.rs"]);
+
+    extend_lib_branch(&repo);
+    repo.git(&["subtree", "merge", "--prefix=vendor/lib", "lib", "--squash"])
+        .expect("subtree merge --squash should succeed");
+    let log = head_note(&repo, "HEAD");
+    assert_eq!(attested_paths(&log), vec!["vendor/lib/extra.rs"]);
+}
+
+#[test]
+fn test_subtree_split_without_branch_records_the_split() {
+    let repo = TestRepo::new();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(crate::lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let mut tool = repo.filename("tools/gen.rs");
+    tool.set_contents(crate::lines!["fn generate() {}".ai()]);
+    repo.stage_all_and_commit("Add generator").unwrap();
+
+    let split = repo
+        .git(&["subtree", "split", "--prefix=tools", "--quiet"])
+        .expect("subtree split should succeed")
+        .trim()
+        .to_string();
+
+    let log = head_note(&repo, &split);
+    assert_eq!(attested_paths(&log), vec!["gen.rs"]);
+    let common_dir = repo.git(&["rev-parse", "--git-common-dir"]).unwrap();
+    let recorded = std::fs::read_to_string(
+        repo.path()
+            .join(common_dir.trim())
+            .join("ai")
+            .join("subtree_splits.jsonl"),
+    )
+    .expect("the split should be recorded");
+    assert!(recorded.contains(&split), "{}", recorded);
+}
+
+crate::reuse_tests_in_worktree!(
+    test_subtree_add_carries_notes_under_prefix,
+    test_subtree_split_strips_prefix_from_notes,
+);
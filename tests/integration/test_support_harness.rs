@@ -0,0 +1,35 @@
+use crate::repos::test_repo::get_binary_path;
+use git_ai::test_support::TestRepo;
+
+#[test]
+fn test_published_harness_records_ai_attribution() {
+    let repo = TestRepo::with_binary(get_binary_path()).expect("harness repo should start");
+    repo.write_file("notes.txt", "one\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    repo.write_file("notes.txt", "one\ntwo\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "notes.txt"])
+        .expect("checkpoint should succeed");
+    let commit = repo.stage_all_and_commit("Agent edit").unwrap();
+
+    let log = repo
+        .authorship_log(&commit)
+        .unwrap()
+        .expect("agent commit should carry an authorship note");
+    assert!(
+        log.attestations
+            .iter()
+            .any(|attestation| attestation.file_path == "notes.txt"),
+        "note should attribute notes.txt, got {:?}",
+        log.attestations
+    );
+}
+
+#[test]
+fn test_published_harness_reports_failed_git_commands() {
+    let repo = TestRepo::with_binary(get_binary_path()).expect("harness repo should start");
+    let error = repo
+        .git(&["rev-parse", "--verify", "does-not-exist"])
+        .expect_err("unknown revision should fail");
+    assert!(error.to_string().contains("rev-parse"), "got: {}", error);
+}
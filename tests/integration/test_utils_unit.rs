@@ -33,6 +33,7 @@ fn build_scoped_human_checkpoint_request(
                 base_commit: BaseCommit::Sha(
                     "0000000000000000000000000000000000000000".to_string(),
                 ),
+                hunks: None,
             })
             .collect(),
         path_role: PreparedPathRole::WillEdit,
@@ -61,6 +62,7 @@ fn apply_default_checkpoint_scope(
                         base_commit: BaseCommit::Sha(
                             "0000000000000000000000000000000000000000".to_string(),
                         ),
+                        hunks: None,
                     })
                     .collect();
                 if checkpoint_kind == CheckpointKind::Human {
@@ -146,6 +148,7 @@ fn test_apply_default_checkpoint_scope_preserves_existing_explicit_scope() {
             content: None,
             repo_work_dir: PathBuf::new(),
             base_commit: BaseCommit::Sha("0000000000000000000000000000000000000000".to_string()),
+            hunks: None,
         }],
         path_role: PreparedPathRole::WillEdit,
         stream_source: None,